@@ -0,0 +1,327 @@
+// capstone/src/lib.rs
+//
+// Capstone project: a marketplace for collectibles
+//
+// The final project of the course brings the exercises together in one contract. It
+// is three things at once:
+//
+// - a fungible token, the market's currency (`Token`);
+// - a collection of non-fungible items (`Nft`);
+// - a fixed-price market where item owners list their items and anyone holding
+//   tokens buys them (`Marketplace`).
+//
+// Keeping all three in one contract means a sale is a single message, with no
+// cross-contract call that could fail half-way, and the whole project can be tested
+// off-chain. The three interfaces are still separate traits, so the token or the
+// collection could be split out into contracts of their own later.
+//
+// The rules
+// ---------
+// The account that deploys the contract is its admin. The admin holds the whole token
+// supply to start with, is the only account that mints items or sets the market fee,
+// and receives the fee on every sale.
+//
+// Items are identified by an `ItemId` chosen by the admin when minting. An item's
+// owner can transfer it or approve one other account to transfer it; the approval
+// ends when the item changes hands.
+//
+// An item's owner lists it at a price in tokens. Buying it moves the price from the
+// buyer to the seller, less the fee, which goes to the admin, and moves the item to
+// the buyer, all or nothing. A listing ends when the item is sold, when its seller
+// cancels it, or when the item is transferred some other way, so a listing never
+// sells an item its seller no longer owns.
+//
+// The fee is in basis points of the price, rounded down: 250 is 2.5%. It starts at
+// zero and can be at most 10 000.
+//
+// What to do
+// ----------
+// Every message below is a `todo!()`, and the contract has no storage yet. Design the
+// storage, then implement the messages until the tests in `tests/` pass:
+//
+//     cargo test
+//
+// Each test file covers one trait, so the token can be finished, and its tests made
+// to pass, before the items and the market. The tests only use the messages, so any
+// storage layout that behaves as described passes them. The traits and the error type
+// are the project's interface: change the implementation, not them.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::primitives::AccountId;
+
+/// Identifies an item of the collection.
+pub type ItemId = u32;
+
+/// Basis points in a whole: a fee of `MAX_FEE_BPS` is the full price.
+pub const MAX_FEE_BPS: u16 = 10_000;
+
+/// Why a message was refused. A message returning an error changes nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub enum Error {
+    /// The caller is not the admin.
+    NotAdmin,
+    /// The fee is above `MAX_FEE_BPS`.
+    InvalidFee,
+    /// The sender's token balance is below the amount.
+    InsufficientBalance,
+    /// The caller's allowance from the sender is below the amount.
+    InsufficientAllowance,
+    /// An item with the id already exists.
+    ItemExists,
+    /// There is no item with the id.
+    ItemNotFound,
+    /// The caller does not own the item.
+    NotItemOwner,
+    /// The caller neither owns the item nor is approved to transfer it.
+    NotApproved,
+    /// The price is zero.
+    ZeroPrice,
+    /// The item is already listed.
+    AlreadyListed,
+    /// The item is not listed.
+    NotListed,
+    /// The caller is not the seller of the listing.
+    NotSeller,
+    /// The caller is the seller of the listing they are buying.
+    OwnListing,
+}
+
+/// The market's currency: a fungible token with allowances.
+#[ink::trait_definition]
+pub trait Token {
+    /// The number of tokens in existence.
+    #[ink(message)]
+    fn total_supply(&self) -> u128;
+
+    /// The token balance of `owner`.
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u128;
+
+    /// How many tokens `spender` may still send on behalf of `owner`.
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, spender: AccountId) -> u128;
+
+    /// Sends `value` tokens from the caller to `to`.
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, value: u128) -> Result<(), Error>;
+
+    /// Lets `spender` send up to `value` of the caller's tokens, replacing any
+    /// allowance it had.
+    #[ink(message)]
+    fn approve(&mut self, spender: AccountId, value: u128) -> Result<(), Error>;
+
+    /// Sends `value` tokens from `from` to `to` on behalf of `from`, using up the
+    /// caller's allowance.
+    #[ink(message)]
+    fn transfer_from(&mut self, from: AccountId, to: AccountId, value: u128) -> Result<(), Error>;
+}
+
+/// The collection: non-fungible items with one owner each.
+#[ink::trait_definition]
+pub trait Nft {
+    /// The owner of item `id`, if it exists.
+    #[ink(message)]
+    fn owner_of(&self, id: ItemId) -> Option<AccountId>;
+
+    /// The number of items `owner` owns.
+    #[ink(message)]
+    fn items_of(&self, owner: AccountId) -> u32;
+
+    /// The account approved to transfer item `id`, if any.
+    #[ink(message)]
+    fn approved(&self, id: ItemId) -> Option<AccountId>;
+
+    /// Creates item `id`, owned by `to`. Only the admin can mint.
+    #[ink(message)]
+    fn mint(&mut self, to: AccountId, id: ItemId) -> Result<(), Error>;
+
+    /// Approves `to` to transfer item `id`, or removes the approval if `to` is
+    /// `None`. Only the item's owner can approve.
+    #[ink(message)]
+    fn approve_item(&mut self, to: Option<AccountId>, id: ItemId) -> Result<(), Error>;
+
+    /// Transfers item `id` to `to`. The caller must own the item or be approved to
+    /// transfer it. Ends the item's approval and its listing, if any.
+    #[ink(message)]
+    fn transfer_item(&mut self, to: AccountId, id: ItemId) -> Result<(), Error>;
+}
+
+/// The market: items sold at a fixed price in tokens.
+#[ink::trait_definition]
+pub trait Marketplace {
+    /// The price of item `id`, if it is listed.
+    #[ink(message)]
+    fn price_of(&self, id: ItemId) -> Option<u128>;
+
+    /// The listed items, in no particular order.
+    #[ink(message)]
+    fn listed_items(&self) -> ink::prelude::vec::Vec<ItemId>;
+
+    /// Lists item `id`, which the caller must own, at `price` tokens.
+    #[ink(message)]
+    fn list(&mut self, id: ItemId, price: u128) -> Result<(), Error>;
+
+    /// Takes the listing of item `id` down. Only its seller can cancel it.
+    #[ink(message)]
+    fn cancel(&mut self, id: ItemId) -> Result<(), Error>;
+
+    /// Buys listed item `id` at its price, paying the seller in tokens less the fee,
+    /// which goes to the admin.
+    #[ink(message)]
+    fn buy(&mut self, id: ItemId) -> Result<(), Error>;
+}
+
+#[ink::contract]
+pub mod capstone {
+    use super::{Error, ItemId, Marketplace, Nft, Token};
+    use ink::prelude::vec::Vec;
+
+    #[ink(storage)]
+    pub struct Capstone {
+        // TODO: the token balances and allowances, the items' owners and approvals,
+        // the listings, the admin and the fee.
+    }
+
+    impl Capstone {
+        /// Creates the contract with `total_supply` tokens, all the caller's, no items
+        /// and no fee. The caller becomes the admin.
+        #[ink(constructor)]
+        pub fn new(total_supply: Balance) -> Self {
+            // TODO: initialise the storage.
+            let _ = total_supply;
+            Self {}
+        }
+
+        /// The admin.
+        #[ink(message)]
+        pub fn admin(&self) -> AccountId {
+            todo!("implement `admin`")
+        }
+
+        /// The market fee, in basis points of the price.
+        #[ink(message)]
+        pub fn fee_bps(&self) -> u16 {
+            todo!("implement `fee_bps`")
+        }
+
+        /// Sets the market fee to `fee_bps` basis points. Only the admin can set it.
+        #[ink(message)]
+        pub fn set_fee(&mut self, fee_bps: u16) -> Result<(), Error> {
+            let _ = fee_bps;
+            todo!("implement `set_fee`")
+        }
+    }
+
+    impl Token for Capstone {
+        #[ink(message)]
+        fn total_supply(&self) -> Balance {
+            todo!("implement `Token::total_supply`")
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            let _ = owner;
+            todo!("implement `Token::balance_of`")
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            let _ = (owner, spender);
+            todo!("implement `Token::allowance`")
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, value: Balance) -> Result<(), Error> {
+            let _ = (to, value);
+            todo!("implement `Token::transfer`")
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), Error> {
+            let _ = (spender, value);
+            todo!("implement `Token::approve`")
+        }
+
+        #[ink(message)]
+        fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<(), Error> {
+            let _ = (from, to, value);
+            todo!("implement `Token::transfer_from`")
+        }
+    }
+
+    impl Nft for Capstone {
+        #[ink(message)]
+        fn owner_of(&self, id: ItemId) -> Option<AccountId> {
+            let _ = id;
+            todo!("implement `Nft::owner_of`")
+        }
+
+        #[ink(message)]
+        fn items_of(&self, owner: AccountId) -> u32 {
+            let _ = owner;
+            todo!("implement `Nft::items_of`")
+        }
+
+        #[ink(message)]
+        fn approved(&self, id: ItemId) -> Option<AccountId> {
+            let _ = id;
+            todo!("implement `Nft::approved`")
+        }
+
+        #[ink(message)]
+        fn mint(&mut self, to: AccountId, id: ItemId) -> Result<(), Error> {
+            let _ = (to, id);
+            todo!("implement `Nft::mint`")
+        }
+
+        #[ink(message)]
+        fn approve_item(&mut self, to: Option<AccountId>, id: ItemId) -> Result<(), Error> {
+            let _ = (to, id);
+            todo!("implement `Nft::approve_item`")
+        }
+
+        #[ink(message)]
+        fn transfer_item(&mut self, to: AccountId, id: ItemId) -> Result<(), Error> {
+            let _ = (to, id);
+            todo!("implement `Nft::transfer_item`")
+        }
+    }
+
+    impl Marketplace for Capstone {
+        #[ink(message)]
+        fn price_of(&self, id: ItemId) -> Option<Balance> {
+            let _ = id;
+            todo!("implement `Marketplace::price_of`")
+        }
+
+        #[ink(message)]
+        fn listed_items(&self) -> Vec<ItemId> {
+            todo!("implement `Marketplace::listed_items`")
+        }
+
+        #[ink(message)]
+        fn list(&mut self, id: ItemId, price: Balance) -> Result<(), Error> {
+            let _ = (id, price);
+            todo!("implement `Marketplace::list`")
+        }
+
+        #[ink(message)]
+        fn cancel(&mut self, id: ItemId) -> Result<(), Error> {
+            let _ = id;
+            todo!("implement `Marketplace::cancel`")
+        }
+
+        #[ink(message)]
+        fn buy(&mut self, id: ItemId) -> Result<(), Error> {
+            let _ = id;
+            todo!("implement `Marketplace::buy`")
+        }
+    }
+}
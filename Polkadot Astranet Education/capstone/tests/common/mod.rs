@@ -0,0 +1,67 @@
+// capstone/tests/common/mod.rs
+//
+// Accounts and starting states shared by the capstone's test files. Each file uses
+// some of them, so the ones a file leaves unused are not dead code.
+
+#![allow(dead_code)]
+
+use capstone::{capstone::Capstone, Nft, Token};
+use ink::{
+    env::{
+        test::{default_accounts, set_caller, DefaultAccounts},
+        DefaultEnvironment,
+    },
+    primitives::AccountId,
+};
+
+/// The total token supply of `market`.
+pub const SUPPLY: u128 = 1_000_000;
+
+/// The tokens Alice gives each of Bob, Charlie and Django in `market`.
+pub const POCKET_MONEY: u128 = 10_000;
+
+pub fn accounts() -> DefaultAccounts<DefaultEnvironment> {
+    default_accounts::<DefaultEnvironment>()
+}
+
+/// Makes `account` the caller of the following messages.
+pub fn as_caller(account: AccountId) {
+    set_caller::<DefaultEnvironment>(account);
+}
+
+/// A contract deployed by Alice, its admin, with `SUPPLY` tokens and nothing else.
+pub fn deployed() -> Capstone {
+    as_caller(accounts().alice);
+    Capstone::new(SUPPLY)
+}
+
+/// Alice's contract with `POCKET_MONEY` tokens each for Bob, Charlie and Django,
+/// Bob owning items 1 and 2 and Charlie item 3. Alice is left the caller.
+pub fn market() -> Capstone {
+    let accounts = accounts();
+    let mut market = deployed();
+    for account in [accounts.bob, accounts.charlie, accounts.django] {
+        market.transfer(account, POCKET_MONEY).unwrap();
+    }
+    market.mint(accounts.bob, 1).unwrap();
+    market.mint(accounts.bob, 2).unwrap();
+    market.mint(accounts.charlie, 3).unwrap();
+    market
+}
+
+/// The sum of the token balances of the default accounts, which hold every token in
+/// these tests.
+pub fn held(market: &Capstone) -> u128 {
+    let accounts = accounts();
+    [
+        accounts.alice,
+        accounts.bob,
+        accounts.charlie,
+        accounts.django,
+        accounts.eve,
+        accounts.frank,
+    ]
+    .iter()
+    .map(|&owner| market.balance_of(owner))
+    .sum()
+}
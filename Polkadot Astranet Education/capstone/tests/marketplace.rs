@@ -0,0 +1,422 @@
+// capstone/tests/marketplace.rs
+//
+// The `Marketplace` trait, and the admin's fee: listing items, cancelling listings
+// and buying, with the token and the items changing hands together.
+
+mod common;
+
+use capstone::{Error, Marketplace, Nft, Token, MAX_FEE_BPS};
+use common::{accounts, as_caller, held, market, POCKET_MONEY, SUPPLY};
+
+fn sorted(mut ids: Vec<u32>) -> Vec<u32> {
+    ids.sort_unstable();
+    ids
+}
+
+#[ink::test]
+fn the_deployer_is_the_admin_and_there_is_no_fee() {
+    let market = market();
+    assert_eq!(market.admin(), accounts().alice);
+    assert_eq!(market.fee_bps(), 0);
+    assert!(market.listed_items().is_empty());
+}
+
+#[ink::test]
+fn only_the_admin_sets_the_fee() {
+    let accounts = accounts();
+    let mut market = market();
+    market.set_fee(250).unwrap();
+    assert_eq!(market.fee_bps(), 250);
+    as_caller(accounts.bob);
+    assert_eq!(market.set_fee(0), Err(Error::NotAdmin));
+    assert_eq!(market.fee_bps(), 250);
+}
+
+#[ink::test]
+fn the_fee_is_at_most_the_price() {
+    let mut market = market();
+    assert_eq!(market.set_fee(MAX_FEE_BPS + 1), Err(Error::InvalidFee));
+    assert_eq!(market.fee_bps(), 0);
+    market.set_fee(MAX_FEE_BPS).unwrap();
+    assert_eq!(market.fee_bps(), MAX_FEE_BPS);
+}
+
+#[ink::test]
+fn the_owner_lists_an_item() {
+    let accounts = accounts();
+    let mut market = market();
+    as_caller(accounts.bob);
+    market.list(1, 500).unwrap();
+    market.list(2, 700).unwrap();
+    assert_eq!(market.price_of(1), Some(500));
+    assert_eq!(market.price_of(2), Some(700));
+    assert_eq!(market.price_of(3), None);
+    assert_eq!(sorted(market.listed_items()), vec![1, 2]);
+    // Listing keeps the item with its owner.
+    assert_eq!(market.owner_of(1), Some(accounts.bob));
+    assert_eq!(market.items_of(accounts.bob), 2);
+}
+
+#[ink::test]
+fn listing_needs_a_price() {
+    let mut market = market();
+    as_caller(accounts().bob);
+    assert_eq!(market.list(1, 0), Err(Error::ZeroPrice));
+    assert_eq!(market.price_of(1), None);
+    assert!(market.listed_items().is_empty());
+}
+
+#[ink::test]
+fn only_the_owner_lists_an_item() {
+    let accounts = accounts();
+    let mut market = market();
+    assert_eq!(market.list(1, 500), Err(Error::NotItemOwner));
+    assert_eq!(market.list(99, 500), Err(Error::ItemNotFound));
+
+    // Not even an account approved to transfer it.
+    as_caller(accounts.bob);
+    market.approve_item(Some(accounts.charlie), 1).unwrap();
+    as_caller(accounts.charlie);
+    assert_eq!(market.list(1, 500), Err(Error::NotItemOwner));
+    assert_eq!(market.price_of(1), None);
+}
+
+#[ink::test]
+fn an_item_is_listed_once() {
+    let mut market = market();
+    as_caller(accounts().bob);
+    market.list(1, 500).unwrap();
+    assert_eq!(market.list(1, 400), Err(Error::AlreadyListed));
+    assert_eq!(market.price_of(1), Some(500));
+    assert_eq!(market.listed_items(), vec![1]);
+}
+
+#[ink::test]
+fn the_seller_cancels_a_listing() {
+    let accounts = accounts();
+    let mut market = market();
+    as_caller(accounts.bob);
+    market.list(1, 500).unwrap();
+    market.list(2, 700).unwrap();
+    market.cancel(1).unwrap();
+    assert_eq!(market.price_of(1), None);
+    assert_eq!(market.listed_items(), vec![2]);
+    assert_eq!(market.cancel(1), Err(Error::NotListed));
+
+    // A cancelled item can be listed again, at a new price.
+    market.list(1, 450).unwrap();
+    assert_eq!(market.price_of(1), Some(450));
+    assert_eq!(sorted(market.listed_items()), vec![1, 2]);
+}
+
+#[ink::test]
+fn only_the_seller_cancels() {
+    let accounts = accounts();
+    let mut market = market();
+    as_caller(accounts.bob);
+    market.list(1, 500).unwrap();
+
+    as_caller(accounts.charlie);
+    assert_eq!(market.cancel(1), Err(Error::NotSeller));
+    as_caller(accounts.alice);
+    assert_eq!(market.cancel(1), Err(Error::NotSeller));
+    assert_eq!(market.price_of(1), Some(500));
+    assert_eq!(market.cancel(3), Err(Error::NotListed));
+}
+
+#[ink::test]
+fn buying_swaps_the_item_for_the_price() {
+    let accounts = accounts();
+    let mut market = market();
+    as_caller(accounts.bob);
+    market.list(1, 500).unwrap();
+
+    as_caller(accounts.charlie);
+    market.buy(1).unwrap();
+    assert_eq!(market.owner_of(1), Some(accounts.charlie));
+    assert_eq!(market.items_of(accounts.bob), 1);
+    assert_eq!(market.items_of(accounts.charlie), 2);
+    assert_eq!(market.balance_of(accounts.charlie), POCKET_MONEY - 500);
+    assert_eq!(market.balance_of(accounts.bob), POCKET_MONEY + 500);
+    assert_eq!(market.price_of(1), None);
+    assert!(market.listed_items().is_empty());
+    assert_eq!(held(&market), SUPPLY);
+}
+
+#[ink::test]
+fn buying_needs_no_allowance() {
+    let accounts = accounts();
+    let mut market = market();
+    as_caller(accounts.bob);
+    market.list(1, 500).unwrap();
+
+    // The market is the token, so it moves the buyer's tokens itself.
+    assert_eq!(market.allowance(accounts.charlie, accounts.bob), 0);
+    as_caller(accounts.charlie);
+    market.buy(1).unwrap();
+    assert_eq!(market.balance_of(accounts.bob), POCKET_MONEY + 500);
+}
+
+#[ink::test]
+fn the_fee_goes_to_the_admin() {
+    let accounts = accounts();
+    let mut market = market();
+    market.set_fee(250).unwrap();
+    as_caller(accounts.bob);
+    market.list(1, 1_000).unwrap();
+
+    as_caller(accounts.charlie);
+    market.buy(1).unwrap();
+    let admin_before = SUPPLY - 3 * POCKET_MONEY;
+    assert_eq!(market.balance_of(accounts.charlie), POCKET_MONEY - 1_000);
+    assert_eq!(market.balance_of(accounts.bob), POCKET_MONEY + 975);
+    assert_eq!(market.balance_of(accounts.alice), admin_before + 25);
+    assert_eq!(held(&market), SUPPLY);
+}
+
+#[ink::test]
+fn the_fee_is_rounded_down() {
+    let accounts = accounts();
+    let mut market = market();
+    market.set_fee(250).unwrap();
+    as_caller(accounts.bob);
+    // 2.5% of 399 is 9.975.
+    market.list(1, 399).unwrap();
+
+    as_caller(accounts.charlie);
+    market.buy(1).unwrap();
+    assert_eq!(market.balance_of(accounts.bob), POCKET_MONEY + 390);
+    assert_eq!(
+        market.balance_of(accounts.alice),
+        SUPPLY - 3 * POCKET_MONEY + 9
+    );
+    assert_eq!(held(&market), SUPPLY);
+}
+
+#[ink::test]
+fn the_fee_applies_to_sales_after_it_is_set() {
+    let accounts = accounts();
+    let mut market = market();
+    as_caller(accounts.bob);
+    market.list(1, 1_000).unwrap();
+
+    as_caller(accounts.alice);
+    market.set_fee(MAX_FEE_BPS).unwrap();
+    as_caller(accounts.charlie);
+    market.buy(1).unwrap();
+    // A listing does not lock in the fee: the whole price goes to the admin.
+    assert_eq!(market.balance_of(accounts.bob), POCKET_MONEY);
+    assert_eq!(
+        market.balance_of(accounts.alice),
+        SUPPLY - 3 * POCKET_MONEY + 1_000
+    );
+    assert_eq!(market.owner_of(1), Some(accounts.charlie));
+}
+
+#[ink::test]
+fn the_admin_sells_without_losing_the_fee() {
+    let accounts = accounts();
+    let mut market = market();
+    market.set_fee(1_000).unwrap();
+    market.mint(accounts.alice, 10).unwrap();
+    market.list(10, 2_000).unwrap();
+
+    as_caller(accounts.django);
+    market.buy(10).unwrap();
+    assert_eq!(
+        market.balance_of(accounts.alice),
+        SUPPLY - 3 * POCKET_MONEY + 2_000
+    );
+    assert_eq!(market.owner_of(10), Some(accounts.django));
+}
+
+#[ink::test]
+fn buying_more_than_you_can_afford_fails() {
+    let accounts = accounts();
+    let mut market = market();
+    as_caller(accounts.bob);
+    market.list(1, POCKET_MONEY + 1).unwrap();
+
+    as_caller(accounts.charlie);
+    assert_eq!(market.buy(1), Err(Error::InsufficientBalance));
+    // Nothing changed hands and the listing is still up.
+    assert_eq!(market.owner_of(1), Some(accounts.bob));
+    assert_eq!(market.balance_of(accounts.charlie), POCKET_MONEY);
+    assert_eq!(market.balance_of(accounts.bob), POCKET_MONEY);
+    assert_eq!(market.price_of(1), Some(POCKET_MONEY + 1));
+
+    // With a little help from Django, Charlie can afford it.
+    as_caller(accounts.django);
+    market.transfer(accounts.charlie, 1).unwrap();
+    as_caller(accounts.charlie);
+    market.buy(1).unwrap();
+    assert_eq!(market.balance_of(accounts.charlie), 0);
+    assert_eq!(market.owner_of(1), Some(accounts.charlie));
+}
+
+#[ink::test]
+fn the_buyer_pays_the_fee_in_full_or_not_at_all() {
+    let accounts = accounts();
+    let mut market = market();
+    market.set_fee(1_000).unwrap();
+    as_caller(accounts.bob);
+    // The seller's share is 9 000 of the price, which Charlie can afford.
+    market.list(1, POCKET_MONEY).unwrap();
+    as_caller(accounts.charlie);
+    market.transfer(accounts.django, 500).unwrap();
+
+    assert_eq!(market.buy(1), Err(Error::InsufficientBalance));
+    assert_eq!(market.balance_of(accounts.charlie), POCKET_MONEY - 500);
+    assert_eq!(market.balance_of(accounts.bob), POCKET_MONEY);
+    assert_eq!(market.balance_of(accounts.alice), SUPPLY - 3 * POCKET_MONEY);
+    assert_eq!(market.owner_of(1), Some(accounts.bob));
+    assert_eq!(market.price_of(1), Some(POCKET_MONEY));
+}
+
+#[ink::test]
+fn only_listed_items_are_bought() {
+    let accounts = accounts();
+    let mut market = market();
+    as_caller(accounts.charlie);
+    assert_eq!(market.buy(1), Err(Error::NotListed));
+    assert_eq!(market.buy(99), Err(Error::NotListed));
+
+    as_caller(accounts.bob);
+    market.list(1, 500).unwrap();
+    as_caller(accounts.charlie);
+    market.buy(1).unwrap();
+    // A sold item cannot be bought again.
+    as_caller(accounts.django);
+    assert_eq!(market.buy(1), Err(Error::NotListed));
+    assert_eq!(market.owner_of(1), Some(accounts.charlie));
+    assert_eq!(market.balance_of(accounts.django), POCKET_MONEY);
+}
+
+#[ink::test]
+fn sellers_cannot_buy_their_own_listing() {
+    let accounts = accounts();
+    let mut market = market();
+    as_caller(accounts.bob);
+    market.list(1, 500).unwrap();
+    assert_eq!(market.buy(1), Err(Error::OwnListing));
+    assert_eq!(market.price_of(1), Some(500));
+    assert_eq!(market.balance_of(accounts.bob), POCKET_MONEY);
+}
+
+#[ink::test]
+fn a_sale_ends_the_item_approval() {
+    let accounts = accounts();
+    let mut market = market();
+    as_caller(accounts.bob);
+    market.approve_item(Some(accounts.django), 1).unwrap();
+    market.list(1, 500).unwrap();
+
+    as_caller(accounts.charlie);
+    market.buy(1).unwrap();
+    assert_eq!(market.approved(1), None);
+    as_caller(accounts.django);
+    assert_eq!(
+        market.transfer_item(accounts.django, 1),
+        Err(Error::NotApproved)
+    );
+}
+
+#[ink::test]
+fn a_buyer_can_list_the_item_again() {
+    let accounts = accounts();
+    let mut market = market();
+    as_caller(accounts.bob);
+    market.list(1, 500).unwrap();
+    as_caller(accounts.charlie);
+    market.buy(1).unwrap();
+    market.list(1, 800).unwrap();
+
+    as_caller(accounts.django);
+    market.buy(1).unwrap();
+    assert_eq!(market.owner_of(1), Some(accounts.django));
+    assert_eq!(market.balance_of(accounts.charlie), POCKET_MONEY + 300);
+    assert_eq!(market.balance_of(accounts.django), POCKET_MONEY - 800);
+    assert_eq!(market.balance_of(accounts.bob), POCKET_MONEY + 500);
+}
+
+#[ink::test]
+fn transferring_a_listed_item_ends_its_listing() {
+    let accounts = accounts();
+    let mut market = market();
+    as_caller(accounts.bob);
+    market.list(1, 500).unwrap();
+    market.list(2, 700).unwrap();
+    market.transfer_item(accounts.django, 1).unwrap();
+    assert_eq!(market.price_of(1), None);
+    assert_eq!(market.listed_items(), vec![2]);
+
+    // The listing cannot sell Django's item for Bob.
+    as_caller(accounts.charlie);
+    assert_eq!(market.buy(1), Err(Error::NotListed));
+    assert_eq!(market.owner_of(1), Some(accounts.django));
+    as_caller(accounts.bob);
+    assert_eq!(market.cancel(1), Err(Error::NotListed));
+}
+
+#[ink::test]
+fn an_approved_transfer_ends_the_listing_too() {
+    let accounts = accounts();
+    let mut market = market();
+    as_caller(accounts.bob);
+    market.approve_item(Some(accounts.charlie), 1).unwrap();
+    market.list(1, 500).unwrap();
+
+    as_caller(accounts.charlie);
+    market.transfer_item(accounts.charlie, 1).unwrap();
+    assert_eq!(market.price_of(1), None);
+    assert!(market.listed_items().is_empty());
+}
+
+#[ink::test]
+fn listings_of_different_sellers_are_independent() {
+    let accounts = accounts();
+    let mut market = market();
+    as_caller(accounts.bob);
+    market.list(1, 500).unwrap();
+    as_caller(accounts.charlie);
+    market.list(3, 300).unwrap();
+    assert_eq!(sorted(market.listed_items()), vec![1, 3]);
+
+    as_caller(accounts.bob);
+    assert_eq!(market.cancel(3), Err(Error::NotSeller));
+    market.buy(3).unwrap();
+    assert_eq!(market.listed_items(), vec![1]);
+    assert_eq!(market.owner_of(3), Some(accounts.bob));
+    assert_eq!(market.balance_of(accounts.bob), POCKET_MONEY - 300);
+    assert_eq!(market.balance_of(accounts.charlie), POCKET_MONEY + 300);
+}
+
+#[ink::test]
+fn a_busy_market_keeps_every_token() {
+    let accounts = accounts();
+    let mut market = market();
+    market.set_fee(300).unwrap();
+    for id in 10..20 {
+        market.mint(accounts.django, id).unwrap();
+    }
+    as_caller(accounts.django);
+    for id in 10..20 {
+        market.list(id, 100 + u128::from(id)).unwrap();
+    }
+    assert_eq!(market.listed_items().len(), 10);
+
+    as_caller(accounts.bob);
+    for id in 10..15 {
+        market.buy(id).unwrap();
+    }
+    as_caller(accounts.charlie);
+    for id in 15..20 {
+        market.buy(id).unwrap();
+    }
+    assert!(market.listed_items().is_empty());
+    assert_eq!(market.items_of(accounts.django), 0);
+    assert_eq!(market.items_of(accounts.bob), 7);
+    assert_eq!(market.items_of(accounts.charlie), 6);
+    assert_eq!(market.total_supply(), SUPPLY);
+    assert_eq!(held(&market), SUPPLY);
+}
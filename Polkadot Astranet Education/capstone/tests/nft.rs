@@ -0,0 +1,196 @@
+// capstone/tests/nft.rs
+//
+// The `Nft` trait: minting items, approving and transferring them.
+
+mod common;
+
+use capstone::{Error, Nft};
+use common::{accounts, as_caller, deployed, market};
+
+#[ink::test]
+fn a_new_collection_is_empty() {
+    let items = deployed();
+    assert_eq!(items.owner_of(1), None);
+    assert_eq!(items.items_of(accounts().alice), 0);
+    assert_eq!(items.approved(1), None);
+}
+
+#[ink::test]
+fn the_admin_mints_items() {
+    let accounts = accounts();
+    let mut items = deployed();
+    items.mint(accounts.bob, 7).unwrap();
+    items.mint(accounts.bob, 8).unwrap();
+    items.mint(accounts.alice, 9).unwrap();
+    assert_eq!(items.owner_of(7), Some(accounts.bob));
+    assert_eq!(items.owner_of(8), Some(accounts.bob));
+    assert_eq!(items.owner_of(9), Some(accounts.alice));
+    assert_eq!(items.items_of(accounts.bob), 2);
+    assert_eq!(items.items_of(accounts.alice), 1);
+    assert_eq!(items.approved(7), None);
+}
+
+#[ink::test]
+fn only_the_admin_mints() {
+    let accounts = accounts();
+    let mut items = deployed();
+    as_caller(accounts.bob);
+    assert_eq!(items.mint(accounts.bob, 7), Err(Error::NotAdmin));
+    assert_eq!(items.owner_of(7), None);
+    assert_eq!(items.items_of(accounts.bob), 0);
+}
+
+#[ink::test]
+fn an_id_is_minted_once() {
+    let accounts = accounts();
+    let mut items = deployed();
+    items.mint(accounts.bob, 7).unwrap();
+    assert_eq!(items.mint(accounts.charlie, 7), Err(Error::ItemExists));
+    assert_eq!(items.owner_of(7), Some(accounts.bob));
+    assert_eq!(items.items_of(accounts.charlie), 0);
+}
+
+#[ink::test]
+fn the_owner_transfers_an_item() {
+    let accounts = accounts();
+    let mut items = market();
+    as_caller(accounts.bob);
+    items.transfer_item(accounts.django, 1).unwrap();
+    assert_eq!(items.owner_of(1), Some(accounts.django));
+    assert_eq!(items.items_of(accounts.bob), 1);
+    assert_eq!(items.items_of(accounts.django), 1);
+
+    // The new owner can pass it on.
+    as_caller(accounts.django);
+    items.transfer_item(accounts.eve, 1).unwrap();
+    assert_eq!(items.owner_of(1), Some(accounts.eve));
+    assert_eq!(items.items_of(accounts.django), 0);
+}
+
+#[ink::test]
+fn others_cannot_transfer_an_item() {
+    let accounts = accounts();
+    let mut items = market();
+    // Not even the admin.
+    assert_eq!(
+        items.transfer_item(accounts.alice, 1),
+        Err(Error::NotApproved)
+    );
+    as_caller(accounts.charlie);
+    assert_eq!(
+        items.transfer_item(accounts.charlie, 1),
+        Err(Error::NotApproved)
+    );
+    assert_eq!(items.owner_of(1), Some(accounts.bob));
+    assert_eq!(items.items_of(accounts.bob), 2);
+}
+
+#[ink::test]
+fn missing_items_cannot_be_transferred_or_approved() {
+    let accounts = accounts();
+    let mut items = market();
+    as_caller(accounts.bob);
+    assert_eq!(
+        items.transfer_item(accounts.charlie, 99),
+        Err(Error::ItemNotFound)
+    );
+    assert_eq!(
+        items.approve_item(Some(accounts.charlie), 99),
+        Err(Error::ItemNotFound)
+    );
+    assert_eq!(items.owner_of(99), None);
+}
+
+#[ink::test]
+fn transferring_an_item_to_its_owner_keeps_the_count() {
+    let accounts = accounts();
+    let mut items = market();
+    as_caller(accounts.bob);
+    items.transfer_item(accounts.bob, 1).unwrap();
+    assert_eq!(items.owner_of(1), Some(accounts.bob));
+    assert_eq!(items.items_of(accounts.bob), 2);
+}
+
+#[ink::test]
+fn an_approved_account_transfers_the_item() {
+    let accounts = accounts();
+    let mut items = market();
+    as_caller(accounts.bob);
+    items.approve_item(Some(accounts.charlie), 1).unwrap();
+    assert_eq!(items.approved(1), Some(accounts.charlie));
+    // The approval covers that item only.
+    assert_eq!(items.approved(2), None);
+
+    as_caller(accounts.charlie);
+    assert_eq!(
+        items.transfer_item(accounts.charlie, 2),
+        Err(Error::NotApproved)
+    );
+    items.transfer_item(accounts.django, 1).unwrap();
+    assert_eq!(items.owner_of(1), Some(accounts.django));
+    assert_eq!(items.items_of(accounts.bob), 1);
+    assert_eq!(items.items_of(accounts.django), 1);
+}
+
+#[ink::test]
+fn a_transfer_ends_the_approval() {
+    let accounts = accounts();
+    let mut items = market();
+    as_caller(accounts.bob);
+    items.approve_item(Some(accounts.charlie), 1).unwrap();
+    items.transfer_item(accounts.django, 1).unwrap();
+    assert_eq!(items.approved(1), None);
+
+    // Bob's old approval gives Charlie nothing over Django's item.
+    as_caller(accounts.charlie);
+    assert_eq!(
+        items.transfer_item(accounts.charlie, 1),
+        Err(Error::NotApproved)
+    );
+}
+
+#[ink::test]
+fn only_the_owner_approves() {
+    let accounts = accounts();
+    let mut items = market();
+    as_caller(accounts.charlie);
+    assert_eq!(
+        items.approve_item(Some(accounts.charlie), 1),
+        Err(Error::NotItemOwner)
+    );
+
+    // Being approved does not let an account approve others.
+    as_caller(accounts.bob);
+    items.approve_item(Some(accounts.charlie), 1).unwrap();
+    as_caller(accounts.charlie);
+    assert_eq!(
+        items.approve_item(Some(accounts.django), 1),
+        Err(Error::NotItemOwner)
+    );
+    assert_eq!(items.approved(1), Some(accounts.charlie));
+}
+
+#[ink::test]
+fn approvals_are_replaced_and_removed() {
+    let accounts = accounts();
+    let mut items = market();
+    as_caller(accounts.bob);
+    items.approve_item(Some(accounts.charlie), 1).unwrap();
+    items.approve_item(Some(accounts.django), 1).unwrap();
+    assert_eq!(items.approved(1), Some(accounts.django));
+
+    as_caller(accounts.charlie);
+    assert_eq!(
+        items.transfer_item(accounts.charlie, 1),
+        Err(Error::NotApproved)
+    );
+
+    as_caller(accounts.bob);
+    items.approve_item(None, 1).unwrap();
+    assert_eq!(items.approved(1), None);
+    as_caller(accounts.django);
+    assert_eq!(
+        items.transfer_item(accounts.django, 1),
+        Err(Error::NotApproved)
+    );
+}
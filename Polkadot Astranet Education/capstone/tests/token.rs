@@ -0,0 +1,180 @@
+// capstone/tests/token.rs
+//
+// The `Token` trait: balances, transfers and allowances of the market's currency.
+
+mod common;
+
+use capstone::{Error, Token};
+use common::{accounts, as_caller, deployed, held, SUPPLY};
+
+#[ink::test]
+fn the_deployer_holds_the_supply() {
+    let token = deployed();
+    assert_eq!(token.total_supply(), SUPPLY);
+    assert_eq!(token.balance_of(accounts().alice), SUPPLY);
+    assert_eq!(token.balance_of(accounts().bob), 0);
+}
+
+#[ink::test]
+fn transfer_moves_tokens() {
+    let accounts = accounts();
+    let mut token = deployed();
+    token.transfer(accounts.bob, 300).unwrap();
+    assert_eq!(token.balance_of(accounts.alice), SUPPLY - 300);
+    assert_eq!(token.balance_of(accounts.bob), 300);
+
+    as_caller(accounts.bob);
+    token.transfer(accounts.charlie, 100).unwrap();
+    assert_eq!(token.balance_of(accounts.bob), 200);
+    assert_eq!(token.balance_of(accounts.charlie), 100);
+    assert_eq!(token.total_supply(), SUPPLY);
+    assert_eq!(held(&token), SUPPLY);
+}
+
+#[ink::test]
+fn transfer_of_more_than_the_balance_fails() {
+    let accounts = accounts();
+    let mut token = deployed();
+    token.transfer(accounts.bob, 100).unwrap();
+    as_caller(accounts.bob);
+    assert_eq!(
+        token.transfer(accounts.charlie, 101),
+        Err(Error::InsufficientBalance)
+    );
+    assert_eq!(token.balance_of(accounts.bob), 100);
+    assert_eq!(token.balance_of(accounts.charlie), 0);
+
+    // The whole balance can be sent.
+    token.transfer(accounts.charlie, 100).unwrap();
+    assert_eq!(token.balance_of(accounts.bob), 0);
+}
+
+#[ink::test]
+fn accounts_without_tokens_can_send_nothing() {
+    let accounts = accounts();
+    let mut token = deployed();
+    as_caller(accounts.eve);
+    assert_eq!(
+        token.transfer(accounts.bob, 1),
+        Err(Error::InsufficientBalance)
+    );
+    token.transfer(accounts.bob, 0).unwrap();
+    assert_eq!(held(&token), SUPPLY);
+}
+
+#[ink::test]
+fn transfer_to_yourself_changes_nothing() {
+    let accounts = accounts();
+    let mut token = deployed();
+    token.transfer(accounts.alice, 500).unwrap();
+    assert_eq!(token.balance_of(accounts.alice), SUPPLY);
+    assert_eq!(
+        token.transfer(accounts.alice, SUPPLY + 1),
+        Err(Error::InsufficientBalance)
+    );
+}
+
+#[ink::test]
+fn approve_sets_the_allowance() {
+    let accounts = accounts();
+    let mut token = deployed();
+    assert_eq!(token.allowance(accounts.alice, accounts.bob), 0);
+    token.approve(accounts.bob, 500).unwrap();
+    assert_eq!(token.allowance(accounts.alice, accounts.bob), 500);
+    // Allowances are per owner and spender.
+    assert_eq!(token.allowance(accounts.bob, accounts.alice), 0);
+    assert_eq!(token.allowance(accounts.alice, accounts.charlie), 0);
+
+    // A new approval replaces the old one rather than adding to it.
+    token.approve(accounts.bob, 200).unwrap();
+    assert_eq!(token.allowance(accounts.alice, accounts.bob), 200);
+    token.approve(accounts.bob, 0).unwrap();
+    assert_eq!(token.allowance(accounts.alice, accounts.bob), 0);
+}
+
+#[ink::test]
+fn approving_more_than_the_balance_is_allowed() {
+    let accounts = accounts();
+    let mut token = deployed();
+    as_caller(accounts.bob);
+    token.approve(accounts.charlie, 1_000).unwrap();
+    assert_eq!(token.allowance(accounts.bob, accounts.charlie), 1_000);
+    // Approving moves no tokens.
+    assert_eq!(token.balance_of(accounts.bob), 0);
+}
+
+#[ink::test]
+fn transfer_from_uses_up_the_allowance() {
+    let accounts = accounts();
+    let mut token = deployed();
+    token.approve(accounts.bob, 500).unwrap();
+
+    as_caller(accounts.bob);
+    token
+        .transfer_from(accounts.alice, accounts.charlie, 200)
+        .unwrap();
+    assert_eq!(token.balance_of(accounts.alice), SUPPLY - 200);
+    assert_eq!(token.balance_of(accounts.charlie), 200);
+    assert_eq!(token.balance_of(accounts.bob), 0);
+    assert_eq!(token.allowance(accounts.alice, accounts.bob), 300);
+
+    token
+        .transfer_from(accounts.alice, accounts.bob, 300)
+        .unwrap();
+    assert_eq!(token.balance_of(accounts.bob), 300);
+    assert_eq!(token.allowance(accounts.alice, accounts.bob), 0);
+    assert_eq!(held(&token), SUPPLY);
+}
+
+#[ink::test]
+fn transfer_from_beyond_the_allowance_fails() {
+    let accounts = accounts();
+    let mut token = deployed();
+    token.approve(accounts.bob, 100).unwrap();
+
+    as_caller(accounts.bob);
+    assert_eq!(
+        token.transfer_from(accounts.alice, accounts.charlie, 101),
+        Err(Error::InsufficientAllowance)
+    );
+    assert_eq!(token.allowance(accounts.alice, accounts.bob), 100);
+    assert_eq!(token.balance_of(accounts.alice), SUPPLY);
+    assert_eq!(token.balance_of(accounts.charlie), 0);
+
+    // Without an approval there is nothing to spend.
+    as_caller(accounts.charlie);
+    assert_eq!(
+        token.transfer_from(accounts.alice, accounts.charlie, 1),
+        Err(Error::InsufficientAllowance)
+    );
+}
+
+#[ink::test]
+fn transfer_from_beyond_the_balance_fails() {
+    let accounts = accounts();
+    let mut token = deployed();
+    token.transfer(accounts.bob, 100).unwrap();
+    as_caller(accounts.bob);
+    token.approve(accounts.charlie, 1_000).unwrap();
+
+    as_caller(accounts.charlie);
+    assert_eq!(
+        token.transfer_from(accounts.bob, accounts.charlie, 101),
+        Err(Error::InsufficientBalance)
+    );
+    // A refused transfer leaves the allowance alone.
+    assert_eq!(token.allowance(accounts.bob, accounts.charlie), 1_000);
+    assert_eq!(token.balance_of(accounts.bob), 100);
+    assert_eq!(token.balance_of(accounts.charlie), 0);
+}
+
+#[ink::test]
+fn the_owner_needs_an_allowance_too() {
+    let accounts = accounts();
+    let mut token = deployed();
+    assert_eq!(
+        token.transfer_from(accounts.alice, accounts.bob, 1),
+        Err(Error::InsufficientAllowance)
+    );
+    assert_eq!(token.balance_of(accounts.bob), 0);
+}
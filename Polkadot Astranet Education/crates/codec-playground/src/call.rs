@@ -0,0 +1,107 @@
+// crates/codec-playground/src/call.rs
+//
+// Call data: what a contract receives as input.
+//
+// `pallet-contracts` passes the contract a single byte string. For ink! contracts it
+// is the 4-byte selector of the constructor or message, followed by the arguments
+// encoded as one tuple. There is no separator and no argument count; the contract
+// knows the argument types from the selector.
+
+use scale::{Decode, Encode};
+use std::fmt;
+
+/// A 4-byte message or constructor selector.
+pub type Selector = [u8; 4];
+
+/// Errors returned when splitting call data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallError {
+    /// The input is shorter than a selector.
+    MissingSelector,
+    /// The arguments did not decode as the requested type.
+    InvalidArguments,
+    /// The arguments decoded but bytes were left over.
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for CallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CallError::MissingSelector => write!(f, "call data is shorter than a selector"),
+            CallError::InvalidArguments => write!(f, "arguments do not decode as expected"),
+            CallError::TrailingBytes(n) => write!(f, "{} unexpected bytes after the arguments", n),
+        }
+    }
+}
+
+impl std::error::Error for CallError {}
+
+/// Builds call data for the message or constructor `selector` with `args`.
+///
+/// Pass several arguments as a tuple, `()` for none, or a single value for one.
+pub fn encode_call<A: Encode>(selector: Selector, args: &A) -> Vec<u8> {
+    let mut data = selector.to_vec();
+    args.encode_to(&mut data);
+    data
+}
+
+/// Splits call data into its selector and arguments decoded as `A`.
+pub fn decode_call<A: Decode>(data: &[u8]) -> Result<(Selector, A), CallError> {
+    if data.len() < 4 {
+        return Err(CallError::MissingSelector);
+    }
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&data[..4]);
+
+    let mut input = &data[4..];
+    let args = A::decode(&mut input).map_err(|_| CallError::InvalidArguments)?;
+    if !input.is_empty() {
+        return Err(CallError::TrailingBytes(input.len()));
+    }
+    Ok((selector, args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Selector of the ERC-20 example's `transfer` message.
+    const TRANSFER: Selector = [0x84, 0xa1, 0x5d, 0xa1];
+    /// Selector of the flipper example's `flip` message.
+    const FLIP: Selector = [0x63, 0x3a, 0xa5, 0x51];
+
+    #[test]
+    fn no_arguments_is_just_the_selector() {
+        assert_eq!(encode_call(FLIP, &()), FLIP.to_vec());
+    }
+
+    #[test]
+    fn arguments_follow_selector() {
+        let to = [0x01u8; 32];
+        let data = encode_call(TRANSFER, &(to, 10u128));
+        assert_eq!(&data[..4], &TRANSFER);
+        assert_eq!(&data[4..36], &to);
+        assert_eq!(&data[36..], &10u128.to_le_bytes());
+    }
+
+    #[test]
+    fn decode_round_trips() {
+        let data = encode_call(TRANSFER, &([0x01u8; 32], 10u128));
+        let (selector, (to, value)) = decode_call::<([u8; 32], u128)>(&data).unwrap();
+        assert_eq!(selector, TRANSFER);
+        assert_eq!(to, [0x01; 32]);
+        assert_eq!(value, 10);
+    }
+
+    #[test]
+    fn decode_rejects_short_input() {
+        assert_eq!(decode_call::<()>(&[0x01, 0x02]), Err(CallError::MissingSelector));
+    }
+
+    #[test]
+    fn decode_rejects_wrong_types() {
+        let data = encode_call(FLIP, &true);
+        assert_eq!(decode_call::<u32>(&data), Err(CallError::InvalidArguments));
+        assert_eq!(decode_call::<()>(&data), Err(CallError::TrailingBytes(1)));
+    }
+}
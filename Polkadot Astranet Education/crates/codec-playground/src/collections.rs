@@ -0,0 +1,80 @@
+// crates/codec-playground/src/collections.rs
+//
+// Values made of other values: tuples, structs, `Option`, `Vec`, and `String`.
+//
+// - Tuples and structs are just their fields concatenated in declaration order.
+//   Nothing marks where one field ends and the next begins, so both sides must
+//   agree on the types. This is also how several message arguments are passed:
+//   `transfer(to, value)` receives the encoding of the tuple `(to, value)`.
+// - `Option<T>` is one byte, `0x00` for `None` or `0x01` for `Some`, followed by
+//   the encoded `T` when present.
+// - `Vec<T>` is a compact length (the number of elements, not bytes) followed by
+//   the elements. `String` is encoded as its UTF-8 bytes in a `Vec<u8>`.
+// - Fixed-size arrays `[T; N]` carry no length, like `AccountId`.
+
+use scale::{Decode, Encode};
+
+/// A struct shaped like the ERC-20 `Transfer` event payload.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct TransferArgs {
+    /// Sender, absent for mints.
+    pub from: Option<[u8; 32]>,
+    /// Recipient, absent for burns.
+    pub to: Option<[u8; 32]>,
+    /// Amount transferred.
+    pub value: u128,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn tuples_concatenate_fields() {
+        let bytes = round_trip(&(1u8, true, 2u16));
+        assert_eq!(bytes, vec![0x01, 0x01, 0x02, 0x00]);
+    }
+
+    #[test]
+    fn structs_encode_like_tuples() {
+        let args = TransferArgs {
+            from: None,
+            to: Some([0x01; 32]),
+            value: 5,
+        };
+        assert_eq!(round_trip(&args), (args.from, args.to, args.value).encode());
+    }
+
+    #[test]
+    fn option_has_one_byte_tag() {
+        assert_eq!(round_trip(&None::<u32>), vec![0x00]);
+        assert_eq!(round_trip(&Some(7u32)), vec![0x01, 0x07, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn option_bool_is_not_packed() {
+        // Older codec versions squeezed `Option<bool>` into one byte; version 3
+        // encodes it like any other option.
+        assert_eq!(round_trip(&None::<bool>), vec![0x00]);
+        assert_eq!(round_trip(&Some(true)), vec![0x01, 0x01]);
+        assert_eq!(round_trip(&Some(false)), vec![0x01, 0x00]);
+    }
+
+    #[test]
+    fn vec_is_compact_length_then_elements() {
+        assert_eq!(round_trip(&Vec::<u16>::new()), vec![0x00]);
+        assert_eq!(round_trip(&vec![1u16, 2]), vec![0x08, 0x01, 0x00, 0x02, 0x00]);
+    }
+
+    #[test]
+    fn string_is_utf8_bytes_with_length() {
+        assert_eq!(round_trip(&String::from("TN")), vec![0x08, b'T', b'N']);
+        assert_eq!(round_trip(&String::from("é")), vec![0x08, 0xc3, 0xa9]);
+    }
+
+    #[test]
+    fn arrays_have_no_length() {
+        assert_eq!(round_trip(&[7u8; 3]), vec![7, 7, 7]);
+    }
+}
@@ -0,0 +1,65 @@
+// crates/codec-playground/src/enums.rs
+//
+// Enums: one byte holding the variant index, followed by that variant's fields.
+//
+// The index is the variant's position in the declaration unless overridden with
+// `#[codec(index = N)]`. Reordering variants therefore changes the encoding, which
+// is why a contract's public `Error` enum must only ever grow at the end. The same
+// rule covers `Result<T, E>`: `Ok` is `0x00` and `Err` is `0x01`.
+
+use scale::{Decode, Encode};
+
+/// An error enum shaped like the ERC-20 example's.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum TokenError {
+    /// Index 0.
+    InsufficientBalance,
+    /// Index 1.
+    InsufficientAllowance,
+}
+
+/// An enum whose variants carry data, with one explicit index.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum Action {
+    /// Index 0, no payload.
+    Pause,
+    /// Index 1, followed by the encoded amount.
+    Mint(u128),
+    /// Index 9, pinned so it keeps its encoding if variants are added above it.
+    #[codec(index = 9)]
+    SetOwner { owner: [u8; 32] },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn unit_variants_are_their_index() {
+        assert_eq!(round_trip(&TokenError::InsufficientBalance), vec![0x00]);
+        assert_eq!(round_trip(&TokenError::InsufficientAllowance), vec![0x01]);
+    }
+
+    #[test]
+    fn payload_follows_index() {
+        let mut expected = vec![0x01];
+        expected.extend_from_slice(&5u128.to_le_bytes());
+        assert_eq!(round_trip(&Action::Mint(5)), expected);
+    }
+
+    #[test]
+    fn explicit_index_is_used() {
+        let bytes = round_trip(&Action::SetOwner { owner: [0x02; 32] });
+        assert_eq!(bytes[0], 9);
+        assert_eq!(bytes.len(), 33);
+    }
+
+    #[test]
+    fn results_are_enums() {
+        let ok: Result<u8, TokenError> = Ok(3);
+        let err: Result<u8, TokenError> = Err(TokenError::InsufficientAllowance);
+        assert_eq!(round_trip(&ok), vec![0x00, 0x03]);
+        assert_eq!(round_trip(&err), vec![0x01, 0x01]);
+    }
+}
@@ -0,0 +1,35 @@
+// crates/codec-playground/src/lib.rs
+//
+// SCALE (Simple Concatenated Aggregate Little-Endian) is the encoding used for every
+// value that crosses the contract boundary: constructor and message arguments, return
+// values, events, and storage. This crate walks through how the common argument types
+// are laid out, exactly as `pallet-contracts` hands them to a contract, and provides
+// the helpers the CLI tools use to build call data.
+//
+// Each module pairs the explanation with `round_trip` tests, so the examples double
+// as executable documentation.
+
+pub mod call;
+pub mod collections;
+pub mod enums;
+pub mod primitives;
+
+pub use call::{decode_call, encode_call, CallError, Selector};
+
+use scale::{Decode, Encode};
+
+/// Encodes `value`, decodes it back, and returns the bytes in between.
+///
+/// Panics if decoding fails, does not consume every byte, or yields a different
+/// value, which makes it convenient for checking an encoding by hand in tests.
+pub fn round_trip<T>(value: &T) -> Vec<u8>
+where
+    T: Encode + Decode + PartialEq + core::fmt::Debug,
+{
+    let bytes = value.encode();
+    let mut input = &bytes[..];
+    let decoded = T::decode(&mut input).expect("value must decode from its own encoding");
+    assert!(input.is_empty(), "decoding left {} trailing bytes", input.len());
+    assert_eq!(&decoded, value, "round trip changed the value");
+    bytes
+}
@@ -0,0 +1,71 @@
+// crates/codec-playground/src/primitives.rs
+//
+// Fixed-width values: integers, booleans, and account ids.
+//
+// - Integers are little-endian and always use their full width: a `u32` is four
+//   bytes even when it holds `1`.
+// - `bool` is a single byte, `0x00` or `0x01`; any other byte fails to decode.
+// - An `AccountId` (and a `Hash`) is 32 raw bytes with no length prefix, because
+//   its length is part of the type.
+// - `Compact<T>` trades the fixed width for a variable one. The two lowest bits of
+//   the first byte select the mode: `0b00` one byte (values < 64), `0b01` two bytes
+//   (< 2^14), `0b10` four bytes (< 2^30), `0b11` "big integer" with the byte count
+//   in the upper six bits. Lengths of `Vec`s and strings use this encoding, and
+//   balances often do in runtime calls.
+
+use scale::Compact;
+
+/// A stand-in for `AccountId`, which encodes as its 32 raw bytes.
+pub type AccountId = [u8; 32];
+
+/// Returns the compact encoding of `value`, for inspection in lessons.
+pub fn compact_bytes(value: u128) -> Vec<u8> {
+    scale::Encode::encode(&Compact(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+    use scale::Decode;
+
+    #[test]
+    fn integers_are_little_endian_and_fixed_width() {
+        assert_eq!(round_trip(&1u8), vec![0x01]);
+        assert_eq!(round_trip(&1u32), vec![0x01, 0x00, 0x00, 0x00]);
+        assert_eq!(round_trip(&0x0102_0304u32), vec![0x04, 0x03, 0x02, 0x01]);
+        assert_eq!(round_trip(&-1i16), vec![0xff, 0xff]);
+        assert_eq!(round_trip(&1u128).len(), 16);
+    }
+
+    #[test]
+    fn bool_is_one_byte() {
+        assert_eq!(round_trip(&false), vec![0x00]);
+        assert_eq!(round_trip(&true), vec![0x01]);
+        assert!(bool::decode(&mut &[0x02][..]).is_err());
+    }
+
+    #[test]
+    fn account_ids_have_no_length_prefix() {
+        let account: AccountId = [0xaa; 32];
+        assert_eq!(round_trip(&account), vec![0xaa; 32]);
+    }
+
+    #[test]
+    fn compact_modes() {
+        assert_eq!(compact_bytes(0), vec![0x00]);
+        assert_eq!(compact_bytes(1), vec![0x04]);
+        assert_eq!(compact_bytes(63), vec![0xfc]);
+        assert_eq!(compact_bytes(64), vec![0x01, 0x01]);
+        assert_eq!(compact_bytes(16_383), vec![0xfd, 0xff]);
+        assert_eq!(compact_bytes(16_384), vec![0x02, 0x00, 0x01, 0x00]);
+        assert_eq!(compact_bytes(1 << 30), vec![0x03, 0x00, 0x00, 0x00, 0x40]);
+    }
+
+    #[test]
+    fn compact_round_trips() {
+        for value in [0u128, 1, 63, 64, 16_383, 16_384, 1 << 30, u128::MAX] {
+            round_trip(&Compact(value));
+        }
+    }
+}
@@ -0,0 +1,329 @@
+// crates/error-codes/src/lib.rs
+//
+// Pins the SCALE encoding of every public error enum.
+//
+// Callers match on the bytes a contract returns, not on names: an `Err(Error::NotOwner)`
+// reaches a front end or a calling contract as `0x01 0x02` (`Err`, then the variant
+// index). Reordering variants, inserting one in the middle or changing a variant's
+// fields silently changes those bytes for every deployed caller. The tests of this
+// crate render each error variant as a golden line
+//
+//     faucet::Error::RateLimited { retry_at: BlockNumber } = 0000000000
+//
+// and compare against `tests/golden/`. Appending a variant adds a line; anything that
+// changes or removes an existing line is a breaking change.
+//
+// The example contracts are single files that this workspace cannot compile, so their
+// enums are read from source with `syn` and encoded with the same index rules as
+// `parity-scale-codec`. Field values are fixed zero samples: the golden bytes pin the
+// variant index and the width of each field.
+
+use quote::ToTokens;
+use std::fmt;
+
+/// One variant of an error enum and its golden encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorCode {
+    /// Contract or module the enum belongs to, e.g. `faucet`.
+    pub owner: String,
+    /// Name of the enum, e.g. `Error`.
+    pub enum_name: String,
+    /// Name of the variant.
+    pub variant: String,
+    /// Field names (for struct variants) and types, in declaration order.
+    pub fields: Vec<(Option<String>, String)>,
+    /// The encoding of the variant with every field set to its zero sample.
+    pub bytes: Vec<u8>,
+}
+
+impl ErrorCode {
+    /// The part of the golden line before `=`, e.g. `faucet::Error::RateLimited { retry_at:
+    /// BlockNumber }`.
+    pub fn key(&self) -> String {
+        let mut key = format!("{}::{}::{}", self.owner, self.enum_name, self.variant);
+        if self.fields.is_empty() {
+            return key;
+        }
+        let named = self.fields.iter().all(|(name, _)| name.is_some());
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(name, ty)| match name {
+                Some(name) => format!("{}: {}", name, ty),
+                None => ty.clone(),
+            })
+            .collect();
+        if named {
+            key.push_str(&format!(" {{ {} }}", fields.join(", ")));
+        } else {
+            key.push_str(&format!("({})", fields.join(", ")));
+        }
+        key
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}", self.key(), to_hex(&self.bytes))
+    }
+}
+
+/// Errors reading error enums from a contract's source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanError {
+    /// The source is not valid Rust.
+    Parse(String),
+    /// A variant has a field whose zero sample is unknown.
+    UnknownFieldType { variant: String, ty: String },
+    /// A `#[codec(index = ..)]` or discriminant is not a `u8` literal.
+    BadIndex { variant: String },
+}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanError::Parse(err) => write!(f, "cannot parse source: {}", err),
+            ScanError::UnknownFieldType { variant, ty } => write!(
+                f,
+                "variant `{}` has a field of type `{}` with no zero sample; add it to `zero_sample`",
+                variant, ty
+            ),
+            ScanError::BadIndex { variant } => {
+                write!(f, "variant `{}` has an index that is not a u8 literal", variant)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScanError {}
+
+/// Returns the error codes of every SCALE-encoded enum named `*Error` in `source`,
+/// including those inside the `#[ink::contract]` module, labelled with `owner`.
+pub fn scan(owner: &str, source: &str) -> Result<Vec<ErrorCode>, ScanError> {
+    let file = syn::parse_file(source).map_err(|err| ScanError::Parse(err.to_string()))?;
+    let mut codes = Vec::new();
+    scan_items(owner, &file.items, &mut codes)?;
+    Ok(codes)
+}
+
+fn scan_items(
+    owner: &str,
+    items: &[syn::Item],
+    codes: &mut Vec<ErrorCode>,
+) -> Result<(), ScanError> {
+    for item in items {
+        match item {
+            syn::Item::Mod(module) => {
+                if let Some((_, items)) = &module.content {
+                    scan_items(owner, items, codes)?;
+                }
+            }
+            syn::Item::Enum(item) if is_error_enum(item) => {
+                for (position, variant) in item.variants.iter().enumerate() {
+                    codes.push(error_code(owner, item, position, variant)?);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// `pub enum *Error` deriving `Encode`, with `#[derive]` (ink! 3) or
+/// `#[ink::scale_derive]` (ink! 5).
+fn is_error_enum(item: &syn::ItemEnum) -> bool {
+    matches!(item.vis, syn::Visibility::Public(_))
+        && item.ident.to_string().ends_with("Error")
+        && item.attrs.iter().any(|attr| {
+            let path = attr.path();
+            (path.is_ident("derive")
+                || path
+                    .segments
+                    .last()
+                    .is_some_and(|last| last.ident == "scale_derive"))
+                && attr.meta.to_token_stream().to_string().contains("Encode")
+        })
+}
+
+fn error_code(
+    owner: &str,
+    item: &syn::ItemEnum,
+    position: usize,
+    variant: &syn::Variant,
+) -> Result<ErrorCode, ScanError> {
+    let name = variant.ident.to_string();
+    let mut bytes = vec![variant_index(position, variant)?];
+    let mut fields = Vec::new();
+    for field in &variant.fields {
+        let ty = field.ty.to_token_stream().to_string().replace(' ', "");
+        let sample = zero_sample(&ty).ok_or_else(|| ScanError::UnknownFieldType {
+            variant: name.clone(),
+            ty: ty.clone(),
+        })?;
+        bytes.extend(sample);
+        fields.push((field.ident.as_ref().map(ToString::to_string), ty));
+    }
+    Ok(ErrorCode {
+        owner: owner.to_owned(),
+        enum_name: item.ident.to_string(),
+        variant: name,
+        fields,
+        bytes,
+    })
+}
+
+/// The variant index `parity-scale-codec` uses: `#[codec(index = N)]`, else an explicit
+/// discriminant, else the position in declaration order.
+fn variant_index(position: usize, variant: &syn::Variant) -> Result<u8, ScanError> {
+    let bad_index = || ScanError::BadIndex {
+        variant: variant.ident.to_string(),
+    };
+    for attr in &variant.attrs {
+        if !attr.path().is_ident("codec") {
+            continue;
+        }
+        let mut index = None;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("index") {
+                let lit: syn::LitInt = meta.value()?.parse()?;
+                index = Some(lit.base10_parse::<u8>()?);
+            }
+            Ok(())
+        })
+        .map_err(|_| bad_index())?;
+        if let Some(index) = index {
+            return Ok(index);
+        }
+    }
+    if let Some((_, expr)) = &variant.discriminant {
+        return match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(lit),
+                ..
+            }) => lit.base10_parse().map_err(|_| bad_index()),
+            _ => Err(bad_index()),
+        };
+    }
+    u8::try_from(position).map_err(|_| bad_index())
+}
+
+/// The SCALE encoding of the zero value of a field type, for the types error variants
+/// in this repository carry.
+pub fn zero_sample(ty: &str) -> Option<Vec<u8>> {
+    let width = match ty {
+        "bool" | "u8" => 1,
+        "u16" => 2,
+        "u32" | "BlockNumber" => 4,
+        "u64" | "Timestamp" => 8,
+        "u128" | "Balance" => 16,
+        "AccountId" | "Hash" | "[u8;32]" => 32,
+        _ => return None,
+    };
+    Some(vec![0; width])
+}
+
+/// Formats `bytes` as lowercase hex without a prefix.
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Compares `current` golden lines against `golden`, ignoring blank lines and `#`
+/// comments. Returns the breaking changes (golden lines missing from `current`) and
+/// the additions (current lines not yet in `golden`).
+pub fn compare(golden: &str, current: &[String]) -> (Vec<String>, Vec<String>) {
+    let pinned: Vec<&str> = golden
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+    let breaking = pinned
+        .iter()
+        .filter(|line| !current.iter().any(|current| current == *line))
+        .map(|line| line.to_string())
+        .collect();
+    let added = current
+        .iter()
+        .filter(|line| !pinned.contains(&line.as_str()))
+        .cloned()
+        .collect();
+    (breaking, added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+        #[ink::contract]
+        mod demo {
+            #[derive(Debug, scale::Encode, scale::Decode)]
+            pub enum Error {
+                NotOwner,
+                RateLimited { retry_at: BlockNumber },
+                #[codec(index = 7)]
+                Pinned,
+                Wrapped(Balance),
+            }
+
+            #[derive(Debug)]
+            pub enum LocalError { Ignored }
+
+            enum PrivateError { Ignored }
+        }
+    "#;
+
+    #[test]
+    fn scans_variants_with_codec_indices() {
+        let codes = scan("demo", SOURCE).unwrap();
+        let lines: Vec<String> = codes.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            [
+                "demo::Error::NotOwner = 00",
+                "demo::Error::RateLimited { retry_at: BlockNumber } = 0100000000",
+                "demo::Error::Pinned = 07",
+                "demo::Error::Wrapped(Balance) = 0300000000000000000000000000000000",
+            ]
+        );
+    }
+
+    #[test]
+    fn ink_5_scale_derive_is_scanned() {
+        let source = "#[ink::scale_derive(Encode, Decode, TypeInfo)] pub enum Error { A, B }";
+        let lines: Vec<String> = scan("x", source)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(lines, ["x::Error::A = 00", "x::Error::B = 01"]);
+    }
+
+    #[test]
+    fn explicit_discriminants_set_the_index() {
+        let source = "#[derive(scale::Encode)] pub enum Error { A = 4, B = 9 }";
+        let codes = scan("x", source).unwrap();
+        assert_eq!(codes[0].bytes, [4]);
+        assert_eq!(codes[1].bytes, [9]);
+    }
+
+    #[test]
+    fn unknown_field_types_are_reported() {
+        let source = "#[derive(scale::Encode)] pub enum Error { Bad(Vec<u8>) }";
+        assert_eq!(
+            scan("x", source),
+            Err(ScanError::UnknownFieldType {
+                variant: "Bad".into(),
+                ty: "Vec<u8>".into()
+            })
+        );
+    }
+
+    #[test]
+    fn compare_separates_breaking_changes_from_additions() {
+        let golden = "# pinned\nx::Error::A = 00\nx::Error::B = 01\n";
+        let current = vec!["x::Error::B = 00".to_owned(), "x::Error::C = 01".to_owned()];
+        let (breaking, added) = compare(golden, &current);
+        assert_eq!(breaking, ["x::Error::A = 00", "x::Error::B = 01"]);
+        assert_eq!(added, ["x::Error::B = 00", "x::Error::C = 01"]);
+    }
+}
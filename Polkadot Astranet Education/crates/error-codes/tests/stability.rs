@@ -0,0 +1,144 @@
+// crates/error-codes/tests/stability.rs
+//
+// Fails when an error enum's encoding changes in a way deployed callers would notice.
+//
+// If a test fails with "breaking", the change must be reverted or shipped as a new
+// contract version. If it only lists additions, append them to the golden file, or
+// rerun with `UPDATE_GOLDEN=1` to rewrite it.
+
+use astranet_error_codes::{compare, scan, to_hex};
+use astranet_utils::{
+    bounded::BoundedError, circuit_breaker::CircuitBreakerError, nonces::NonceError,
+    ownable::OwnableError, safe_erc20::SafeErc20Error, storage_version::StorageVersionError,
+    validation::ValidationError,
+};
+use scale::Encode;
+use std::{env, fs, path::Path};
+
+const HEADER: &str =
+    "# Generated by `UPDATE_GOLDEN=1 cargo test`. Lines may be appended, never changed.\n";
+
+fn check(golden_name: &str, current: Vec<String>) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(golden_name);
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        let golden = fs::read_to_string(&path).unwrap_or_default();
+        let (breaking, _) = compare(&golden, &current);
+        assert!(
+            breaking.is_empty(),
+            "refusing to update {} over breaking changes:\n  {}",
+            golden_name,
+            breaking.join("\n  ")
+        );
+        fs::write(&path, format!("{}{}\n", HEADER, current.join("\n"))).unwrap();
+        return;
+    }
+
+    let golden =
+        fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+    let (breaking, added) = compare(&golden, &current);
+    assert!(
+        breaking.is_empty(),
+        "breaking changes to pinned error encodings in {}:\n  {}\nunpinned variants now:\n  {}",
+        golden_name,
+        breaking.join("\n  "),
+        added.join("\n  ")
+    );
+    assert!(
+        added.is_empty(),
+        "error variants not pinned in {} yet (rerun with UPDATE_GOLDEN=1):\n  {}",
+        golden_name,
+        added.join("\n  ")
+    );
+}
+
+#[test]
+fn example_contract_errors_are_stable() {
+    let examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples");
+    let mut current = Vec::new();
+    for dir in ["demo-contracts", "mocks"] {
+        let mut files: Vec<_> = fs::read_dir(examples.join(dir))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+            .collect();
+        files.sort();
+        for file in files {
+            let owner = file.file_stem().unwrap().to_string_lossy().into_owned();
+            let source = fs::read_to_string(&file).unwrap();
+            let codes =
+                scan(&owner, &source).unwrap_or_else(|err| panic!("{}: {}", file.display(), err));
+            current.extend(codes.iter().map(ToString::to_string));
+        }
+    }
+    check("contracts.txt", current);
+}
+
+#[test]
+fn utils_errors_are_stable() {
+    fn line(name: &str, error: impl Encode) -> String {
+        format!("astranet_utils::{} = {}", name, to_hex(&error.encode()))
+    }
+
+    let current = vec![
+        line("BoundedError::LimitExceeded", BoundedError::LimitExceeded),
+        line(
+            "CircuitBreakerError::NotGuardian",
+            CircuitBreakerError::NotGuardian,
+        ),
+        line("CircuitBreakerError::Tripped", CircuitBreakerError::Tripped),
+        line(
+            "CircuitBreakerError::NotTripped",
+            CircuitBreakerError::NotTripped,
+        ),
+        line(
+            "NonceError::InvalidNonce { expected: u64 }",
+            NonceError::InvalidNonce { expected: 0 },
+        ),
+        line("NonceError::Exhausted", NonceError::Exhausted),
+        line("OwnableError::NotOwner", OwnableError::NotOwner),
+        line(
+            "OwnableError::NotPendingOwner",
+            OwnableError::NotPendingOwner,
+        ),
+        line(
+            "OwnableError::NoPendingTransfer",
+            OwnableError::NoPendingTransfer,
+        ),
+        line("SafeErc20Error::CallFailed", SafeErc20Error::CallFailed),
+        line("SafeErc20Error::Rejected(u8)", SafeErc20Error::Rejected(0)),
+        line(
+            "StorageVersionError::UnexpectedVersion { expected: u16, found: u16 }",
+            StorageVersionError::UnexpectedVersion {
+                expected: 0,
+                found: 0,
+            },
+        ),
+        line(
+            "StorageVersionError::Exhausted",
+            StorageVersionError::Exhausted,
+        ),
+        line("ValidationError::Empty", ValidationError::Empty),
+        line("ValidationError::TooLong", ValidationError::TooLong),
+        line("ValidationError::TooLarge", ValidationError::TooLarge),
+        line("ValidationError::Zero", ValidationError::Zero),
+    ];
+
+    // The list above is written by hand so the bytes come from the real `Encode`
+    // impls. Reading the sources the same way as the contracts' must agree with it,
+    // which catches a variant added to utils without a line here.
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("../utils/src");
+    let mut scanned = Vec::new();
+    for entry in fs::read_dir(src).unwrap() {
+        let source = fs::read_to_string(entry.unwrap().path()).unwrap();
+        let codes = scan("astranet_utils", &source).unwrap();
+        scanned.extend(codes.iter().map(ToString::to_string));
+    }
+    let mut listed = current.clone();
+    listed.sort();
+    scanned.sort();
+    assert_eq!(listed, scanned, "utils error list is out of date");
+
+    check("utils.txt", current);
+}
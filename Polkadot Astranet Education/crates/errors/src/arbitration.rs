@@ -0,0 +1,71 @@
+// crates/errors/src/arbitration.rs
+//
+// Errors of `examples/demo-contracts/arbitration.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The arbitration error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the amount is zero.
+    ZeroAmount,
+    /// Returned if the juror has less free stake than asked for.
+    InsufficientStake,
+    /// Returned if the court already has `MAX_JURORS` jurors.
+    TooManyJurors,
+    /// Returned if there is no dispute with the id.
+    UnknownDispute,
+    /// Returned if the juror is not on the dispute's panel.
+    NotOnPanel,
+    /// Returned if the juror already voted on the dispute.
+    AlreadyVoted,
+    /// Returned if the voting period of the dispute is over.
+    VotingClosed,
+    /// Returned if the voting period is still running and votes are missing.
+    VotingOpen,
+    /// Returned if the dispute already has a ruling.
+    AlreadyResolved,
+    /// Returned if the dispute has no ruling yet.
+    NotResolved,
+    /// Returned if the token refused to move the tokens.
+    TransferFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ZeroAmount => f.write_str("the amount is zero"),
+            Error::InsufficientStake => f.write_str("the juror has less free stake than asked for"),
+            Error::TooManyJurors => f.write_str("the court already has MAX_JURORS jurors"),
+            Error::UnknownDispute => f.write_str("there is no dispute with the id"),
+            Error::NotOnPanel => f.write_str("the juror is not on the dispute's panel"),
+            Error::AlreadyVoted => f.write_str("the juror already voted on the dispute"),
+            Error::VotingClosed => f.write_str("the voting period of the dispute is over"),
+            Error::VotingOpen => {
+                f.write_str("the voting period is still running and votes are missing")
+            }
+            Error::AlreadyResolved => f.write_str("the dispute already has a ruling"),
+            Error::NotResolved => f.write_str("the dispute has no ruling yet"),
+            Error::TransferFailed => f.write_str("the token refused to move the tokens"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::InsufficientStake => ContractError::InsufficientBalance,
+            Error::TooManyJurors => ContractError::LimitExceeded,
+            Error::UnknownDispute => ContractError::NotFound,
+            Error::NotOnPanel => ContractError::Unauthorized,
+            Error::AlreadyVoted => ContractError::AlreadyExists,
+            Error::VotingClosed => ContractError::TooLate,
+            Error::VotingOpen => ContractError::TooEarly,
+            Error::AlreadyResolved => ContractError::AlreadyExists,
+            Error::NotResolved => ContractError::TooEarly,
+            Error::TransferFailed => ContractError::TransferFailed,
+        }
+    }
+}
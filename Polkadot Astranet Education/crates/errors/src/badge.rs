@@ -0,0 +1,61 @@
+// crates/errors/src/badge.rs
+//
+// Errors of `examples/demo-contracts/badge.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The badge error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the event code is already taken.
+    EventExists,
+    /// Returned if there is no event with the code.
+    UnknownEvent,
+    /// Returned if the caller is not the event's organizer.
+    NotOrganizer,
+    /// Returned if the caller already holds the event's badge.
+    AlreadyClaimed,
+    /// Returned if the voucher signature is malformed.
+    InvalidSignature,
+    /// Returned if the voucher was not signed by the event's signer.
+    InvalidVoucher,
+    /// Returned by every transfer and approval: badges are soulbound.
+    NonTransferable,
+    /// Returned if the batch query has differently sized lists.
+    LengthMismatch,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::EventExists => f.write_str("the event code is already taken"),
+            Error::UnknownEvent => f.write_str("there is no event with the code"),
+            Error::NotOrganizer => f.write_str("the caller is not the event's organizer"),
+            Error::AlreadyClaimed => f.write_str("the caller already holds the event's badge"),
+            Error::InvalidSignature => f.write_str("the voucher signature is malformed"),
+            Error::InvalidVoucher => {
+                f.write_str("the voucher was not signed by the event's signer")
+            }
+            Error::NonTransferable => {
+                f.write_str("badges are soulbound and cannot be transferred or approved")
+            }
+            Error::LengthMismatch => f.write_str("the batch query has differently sized lists"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::EventExists => ContractError::AlreadyExists,
+            Error::UnknownEvent => ContractError::NotFound,
+            Error::NotOrganizer => ContractError::Unauthorized,
+            Error::AlreadyClaimed => ContractError::AlreadyExists,
+            Error::InvalidSignature => ContractError::InvalidSignature,
+            Error::InvalidVoucher => ContractError::InvalidSignature,
+            Error::NonTransferable => ContractError::PreconditionFailed,
+            Error::LengthMismatch => ContractError::InvalidArgument,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// crates/errors/src/cdp.rs
+//
+// Errors of `examples/demo-contracts/cdp.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The CDP error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the synthetic balance is insufficient for the operation.
+    InsufficientBalance,
+    /// Returned if the synthetic allowance is insufficient for the operation.
+    InsufficientAllowance,
+    /// Returned if the amount is zero.
+    ZeroAmount,
+    /// Returned if the position holds less collateral than asked for.
+    InsufficientCollateral,
+    /// Returned if minting or withdrawing would leave the position below the minimum
+    /// collateral ratio.
+    BelowMinimumRatio,
+    /// Returned if a repayment is larger than the position's debt.
+    ExceedsDebt,
+    /// Returned if the position is safe and cannot be liquidated.
+    NotLiquidatable,
+    /// Returned if the oracle has no usable price for the collateral.
+    PriceUnavailable,
+    /// Returned if the collateral token refused to move the tokens.
+    TransferFailed,
+    /// Returned if the ratio math overflows.
+    Overflow,
+    /// Returned if the caller is not the guardian.
+    NotGuardian,
+    /// Returned if minting is stopped by the circuit breaker.
+    Tripped,
+    /// Returned if the circuit breaker is not tripped.
+    NotTripped,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InsufficientBalance => f.write_str("the synthetic balance is insufficient for the operation"),
+            Error::InsufficientAllowance => f.write_str("the synthetic allowance is insufficient for the operation"),
+            Error::ZeroAmount => f.write_str("the amount is zero"),
+            Error::InsufficientCollateral => f.write_str("the position holds less collateral than asked for"),
+            Error::BelowMinimumRatio => f.write_str("minting or withdrawing would leave the position below the minimum collateral ratio"),
+            Error::ExceedsDebt => f.write_str("a repayment is larger than the position's debt"),
+            Error::NotLiquidatable => f.write_str("the position is safe and cannot be liquidated"),
+            Error::PriceUnavailable => f.write_str("the oracle has no usable price for the collateral"),
+            Error::TransferFailed => f.write_str("the collateral token refused to move the tokens"),
+            Error::Overflow => f.write_str("the ratio math overflows"),
+            Error::NotGuardian => f.write_str("the caller is not the guardian"),
+            Error::Tripped => f.write_str("minting is stopped by the circuit breaker"),
+            Error::NotTripped => f.write_str("the circuit breaker is not tripped"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::InsufficientBalance => ContractError::InsufficientBalance,
+            Error::InsufficientAllowance => ContractError::InsufficientAllowance,
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::InsufficientCollateral => ContractError::InsufficientBalance,
+            Error::BelowMinimumRatio => ContractError::PreconditionFailed,
+            Error::ExceedsDebt => ContractError::InvalidArgument,
+            Error::NotLiquidatable => ContractError::PreconditionFailed,
+            Error::PriceUnavailable => ContractError::Unavailable,
+            Error::TransferFailed => ContractError::TransferFailed,
+            Error::Overflow => ContractError::Overflow,
+            Error::NotGuardian => ContractError::Unauthorized,
+            Error::Tripped => ContractError::Unavailable,
+            Error::NotTripped => ContractError::PreconditionFailed,
+        }
+    }
+}
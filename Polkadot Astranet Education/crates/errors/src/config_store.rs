@@ -0,0 +1,45 @@
+// crates/errors/src/config_store.rs
+//
+// Errors of `examples/demo-contracts/config_store.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The config store error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the admin.
+    NotAdmin,
+    /// Returned if the caller does not have the writer role.
+    NotWriter,
+    /// Returned if the key is empty or longer than `MAX_KEY_LEN`.
+    InvalidKey,
+    /// Returned if a bytes value is longer than `MAX_BYTES_LEN`.
+    ValueTooLong,
+    /// Returned if the value's type differs from the key's.
+    TypeMismatch,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotAdmin => f.write_str("the caller is not the admin"),
+            Error::NotWriter => f.write_str("the caller does not have the writer role"),
+            Error::InvalidKey => f.write_str("the key is empty or longer than MAX_KEY_LEN"),
+            Error::ValueTooLong => f.write_str("a bytes value is longer than MAX_BYTES_LEN"),
+            Error::TypeMismatch => f.write_str("the value's type differs from the key's"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotAdmin => ContractError::Unauthorized,
+            Error::NotWriter => ContractError::Unauthorized,
+            Error::InvalidKey => ContractError::InvalidArgument,
+            Error::ValueTooLong => ContractError::LimitExceeded,
+            Error::TypeMismatch => ContractError::InvalidArgument,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// crates/errors/src/crowdloan.rs
+//
+// Errors of `examples/demo-contracts/crowdloan.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The crowdloan error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the amount is zero.
+    ZeroAmount,
+    /// Returned if the contribution would take the total past the hard cap.
+    HardCapExceeded,
+    /// Returned if the campaign no longer takes contributions.
+    CampaignEnded,
+    /// Returned if the campaign has not ended yet.
+    CampaignRunning,
+    /// Returned if the lease has not ended yet.
+    LeaseRunning,
+    /// Returned if the caller has nothing to claim or take back.
+    NothingToClaim,
+    /// Returned if a transfer failed.
+    TransferFailed,
+    /// Returned if the reward math overflows.
+    Overflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ZeroAmount => f.write_str("the amount is zero"),
+            Error::HardCapExceeded => {
+                f.write_str("the contribution would take the total past the hard cap")
+            }
+            Error::CampaignEnded => f.write_str("the campaign no longer takes contributions"),
+            Error::CampaignRunning => f.write_str("the campaign has not ended yet"),
+            Error::LeaseRunning => f.write_str("the lease has not ended yet"),
+            Error::NothingToClaim => f.write_str("the caller has nothing to claim or take back"),
+            Error::TransferFailed => f.write_str("a transfer failed"),
+            Error::Overflow => f.write_str("the reward math overflows"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::HardCapExceeded => ContractError::LimitExceeded,
+            Error::CampaignEnded => ContractError::TooLate,
+            Error::CampaignRunning => ContractError::TooEarly,
+            Error::LeaseRunning => ContractError::TooEarly,
+            Error::NothingToClaim => ContractError::NothingToClaim,
+            Error::TransferFailed => ContractError::TransferFailed,
+            Error::Overflow => ContractError::Overflow,
+        }
+    }
+}
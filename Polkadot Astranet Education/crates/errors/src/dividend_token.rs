@@ -0,0 +1,57 @@
+// crates/errors/src/dividend_token.rs
+//
+// Errors of `examples/demo-contracts/dividend_token.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The dividend token error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the balance is insufficient for the operation.
+    InsufficientBalance,
+    /// Returned if the allowance is insufficient for the operation.
+    InsufficientAllowance,
+    /// Returned if dividends are deposited while the supply is zero.
+    NoHolders,
+    /// Returned if a deposit would take the total distributed past `MAX_DISTRIBUTION`,
+    /// or overflow the accumulator.
+    DistributionTooLarge,
+    /// Returned if the caller has no dividends to claim.
+    NothingToClaim,
+    /// Returned if the native transfer of a claim failed.
+    TransferFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InsufficientBalance => {
+                f.write_str("the balance is insufficient for the operation")
+            }
+            Error::InsufficientAllowance => {
+                f.write_str("the allowance is insufficient for the operation")
+            }
+            Error::NoHolders => f.write_str("dividends are deposited while the supply is zero"),
+            Error::DistributionTooLarge => f.write_str(
+                "a deposit would take the total distributed past MAX_DISTRIBUTION, \
+                     or overflow the accumulator",
+            ),
+            Error::NothingToClaim => f.write_str("the caller has no dividends to claim"),
+            Error::TransferFailed => f.write_str("the native transfer of a claim failed"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::InsufficientBalance => ContractError::InsufficientBalance,
+            Error::InsufficientAllowance => ContractError::InsufficientAllowance,
+            Error::NoHolders => ContractError::PreconditionFailed,
+            Error::DistributionTooLarge => ContractError::LimitExceeded,
+            Error::NothingToClaim => ContractError::NothingToClaim,
+            Error::TransferFailed => ContractError::TransferFailed,
+        }
+    }
+}
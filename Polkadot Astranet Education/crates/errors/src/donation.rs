@@ -0,0 +1,53 @@
+// crates/errors/src/donation.rs
+//
+// Errors of `examples/demo-contracts/donation.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The donation error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if no value was sent along.
+    ZeroAmount,
+    /// Returned if the campaign deadline has passed.
+    CampaignEnded,
+    /// Returned if the campaign deadline has not passed yet.
+    CampaignActive,
+    /// Returned if the caller is not the sponsor.
+    NotSponsor,
+    /// Returned if the caller is not the beneficiary.
+    NotBeneficiary,
+    /// Returned if there are no funds to send.
+    NothingToWithdraw,
+    /// Returned if the native transfer failed.
+    TransferFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ZeroAmount => f.write_str("no value was sent along"),
+            Error::CampaignEnded => f.write_str("the campaign deadline has passed"),
+            Error::CampaignActive => f.write_str("the campaign deadline has not passed yet"),
+            Error::NotSponsor => f.write_str("the caller is not the sponsor"),
+            Error::NotBeneficiary => f.write_str("the caller is not the beneficiary"),
+            Error::NothingToWithdraw => f.write_str("there are no funds to send"),
+            Error::TransferFailed => f.write_str("the native transfer failed"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::CampaignEnded => ContractError::TooLate,
+            Error::CampaignActive => ContractError::TooEarly,
+            Error::NotSponsor => ContractError::Unauthorized,
+            Error::NotBeneficiary => ContractError::Unauthorized,
+            Error::NothingToWithdraw => ContractError::NothingToClaim,
+            Error::TransferFailed => ContractError::TransferFailed,
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// crates/errors/src/enrollment.rs
+//
+// Errors of `examples/demo-contracts/enrollment.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The enrollment error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if no course has the given id.
+    CourseNotFound,
+    /// Returned if the value sent along is not the course fee.
+    WrongFee,
+    /// Returned if the learner is already enrolled.
+    AlreadyEnrolled,
+    /// Returned if the learner is not enrolled.
+    NotEnrolled,
+    /// Returned if the refund window of the course has closed.
+    RefundWindowClosed,
+    /// Returned if the refund window of the course is still open.
+    RefundWindowOpen,
+    /// Returned if the caller is not the course instructor.
+    NotInstructor,
+    /// Returned if there are no fees to withdraw.
+    NothingToWithdraw,
+    /// Returned if the native transfer failed.
+    TransferFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::CourseNotFound => f.write_str("no course has the given id"),
+            Error::WrongFee => f.write_str("the value sent along is not the course fee"),
+            Error::AlreadyEnrolled => f.write_str("the learner is already enrolled"),
+            Error::NotEnrolled => f.write_str("the learner is not enrolled"),
+            Error::RefundWindowClosed => f.write_str("the refund window of the course has closed"),
+            Error::RefundWindowOpen => f.write_str("the refund window of the course is still open"),
+            Error::NotInstructor => f.write_str("the caller is not the course instructor"),
+            Error::NothingToWithdraw => f.write_str("there are no fees to withdraw"),
+            Error::TransferFailed => f.write_str("the native transfer failed"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::CourseNotFound => ContractError::NotFound,
+            Error::WrongFee => ContractError::WrongPayment,
+            Error::AlreadyEnrolled => ContractError::AlreadyExists,
+            Error::NotEnrolled => ContractError::NotFound,
+            Error::RefundWindowClosed => ContractError::TooLate,
+            Error::RefundWindowOpen => ContractError::TooEarly,
+            Error::NotInstructor => ContractError::Unauthorized,
+            Error::NothingToWithdraw => ContractError::NothingToClaim,
+            Error::TransferFailed => ContractError::TransferFailed,
+        }
+    }
+}
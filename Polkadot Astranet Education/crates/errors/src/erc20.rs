@@ -0,0 +1,55 @@
+// crates/errors/src/erc20.rs
+//
+// Errors of `examples/demo-contracts/erc20.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The ERC-20 error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the balance is insufficient for the operation.
+    InsufficientBalance,
+    /// Returned if the allowance is insufficient for the operation.
+    InsufficientAllowance,
+    /// Returned if the caller is not the token owner.
+    NotOwner,
+    /// Returned if restricted mode is on and the recipient is not allowlisted.
+    RecipientNotAllowlisted,
+    /// Returned if the deadline of the allowance has passed.
+    AllowanceExpired,
+    /// Returned if the recipient is the zero address.
+    InvalidRecipient,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InsufficientBalance => {
+                f.write_str("the balance is insufficient for the operation")
+            }
+            Error::InsufficientAllowance => {
+                f.write_str("the allowance is insufficient for the operation")
+            }
+            Error::NotOwner => f.write_str("the caller is not the token owner"),
+            Error::RecipientNotAllowlisted => {
+                f.write_str("restricted mode is on and the recipient is not allowlisted")
+            }
+            Error::AllowanceExpired => f.write_str("the deadline of the allowance has passed"),
+            Error::InvalidRecipient => f.write_str("the recipient is the zero address"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::InsufficientBalance => ContractError::InsufficientBalance,
+            Error::InsufficientAllowance => ContractError::InsufficientAllowance,
+            Error::NotOwner => ContractError::Unauthorized,
+            Error::RecipientNotAllowlisted => ContractError::Unauthorized,
+            Error::AllowanceExpired => ContractError::TooLate,
+            Error::InvalidRecipient => ContractError::InvalidArgument,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// crates/errors/src/erc721.rs
+//
+// Errors of `examples/demo-contracts/erc721.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The ERC-721 error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the minter.
+    NotMinter,
+    /// Returned if the caller may not move or approve the token.
+    NotApproved,
+    /// Returned if a token with the id already exists.
+    TokenExists,
+    /// Returned if there is no token with the id.
+    TokenNotFound,
+    /// Returned if `from` does not own the token.
+    NotOwner,
+    /// Returned if an account tries to approve itself.
+    SelfApproval,
+    /// Returned if the expiry of an approval has already passed.
+    ExpiryInPast,
+    /// Returned if a token URI is longer than `MAX_URI_LEN`.
+    LimitExceeded,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotMinter => f.write_str("the caller is not the minter"),
+            Error::NotApproved => f.write_str("the caller may not move or approve the token"),
+            Error::TokenExists => f.write_str("a token with the id already exists"),
+            Error::TokenNotFound => f.write_str("there is no token with the id"),
+            Error::NotOwner => f.write_str("from does not own the token"),
+            Error::SelfApproval => f.write_str("an account tries to approve itself"),
+            Error::ExpiryInPast => f.write_str("the expiry of an approval has already passed"),
+            Error::LimitExceeded => f.write_str("a token URI is longer than MAX_URI_LEN"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotMinter => ContractError::Unauthorized,
+            Error::NotApproved => ContractError::Unauthorized,
+            Error::TokenExists => ContractError::AlreadyExists,
+            Error::TokenNotFound => ContractError::NotFound,
+            Error::NotOwner => ContractError::Unauthorized,
+            Error::SelfApproval => ContractError::InvalidArgument,
+            Error::ExpiryInPast => ContractError::InvalidArgument,
+            Error::LimitExceeded => ContractError::LimitExceeded,
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// crates/errors/src/events_showcase.rs
+//
+// Errors of `examples/demo-contracts/events_showcase.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The events showcase error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is already registered.
+    AlreadyRegistered,
+    /// Returned if the caller is not registered.
+    NotRegistered,
+    /// Returned if a note is longer than `MAX_NOTE_LEN`.
+    LimitExceeded,
+    /// Returned if a score is above the maximum score.
+    ScoreAboveMax,
+    /// Returned if the learner has used every attempt.
+    NoAttemptsLeft,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::AlreadyRegistered => f.write_str("the caller is already registered"),
+            Error::NotRegistered => f.write_str("the caller is not registered"),
+            Error::LimitExceeded => f.write_str("a note is longer than MAX_NOTE_LEN"),
+            Error::ScoreAboveMax => f.write_str("a score is above the maximum score"),
+            Error::NoAttemptsLeft => f.write_str("the learner has used every attempt"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::AlreadyRegistered => ContractError::AlreadyExists,
+            Error::NotRegistered => ContractError::NotFound,
+            Error::LimitExceeded => ContractError::LimitExceeded,
+            Error::ScoreAboveMax => ContractError::InvalidArgument,
+            Error::NoAttemptsLeft => ContractError::LimitExceeded,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// crates/errors/src/factory.rs
+//
+// Errors of `examples/demo-contracts/factory.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The factory error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if a child was already deployed with the given salt.
+    SaltAlreadyUsed,
+    /// Returned if the node rejected the instantiation.
+    InstantiationFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::SaltAlreadyUsed => {
+                f.write_str("a child was already deployed with the given salt")
+            }
+            Error::InstantiationFailed => f.write_str("the node rejected the instantiation"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::SaltAlreadyUsed => ContractError::AlreadyExists,
+            Error::InstantiationFailed => ContractError::CallFailed,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+// crates/errors/src/faucet.rs
+//
+// Errors of `examples/demo-contracts/faucet.rs`.
+
+use crate::{BlockNumber, ContractError};
+use std::fmt;
+
+/// The faucet error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller dripped too often; retry at the given block.
+    RateLimited { retry_at: BlockNumber },
+    /// Returned if the faucet cannot cover a drip.
+    FaucetEmpty,
+    /// Returned if the caller is not the faucet owner.
+    NotOwner,
+    /// Returned if the native transfer failed.
+    TransferFailed,
+    /// Returned if the caller is not the proposed new owner.
+    NotPendingOwner,
+    /// Returned if no ownership transfer is in progress.
+    NoPendingTransfer,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::RateLimited { retry_at } => write!(
+                f,
+                "the caller dripped too often; retry at block {}",
+                retry_at
+            ),
+            Error::FaucetEmpty => f.write_str("the faucet cannot cover a drip"),
+            Error::NotOwner => f.write_str("the caller is not the faucet owner"),
+            Error::TransferFailed => f.write_str("the native transfer failed"),
+            Error::NotPendingOwner => f.write_str("the caller is not the proposed new owner"),
+            Error::NoPendingTransfer => f.write_str("no ownership transfer is in progress"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::RateLimited { .. } => ContractError::RateLimited,
+            Error::FaucetEmpty => ContractError::InsufficientBalance,
+            Error::NotOwner => ContractError::Unauthorized,
+            Error::TransferFailed => ContractError::TransferFailed,
+            Error::NotPendingOwner => ContractError::Unauthorized,
+            Error::NoPendingTransfer => ContractError::NotFound,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// crates/errors/src/flash_borrower.rs
+//
+// Errors of `examples/demo-contracts/flash_borrower.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The flash borrower error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the borrower owner.
+    NotOwner,
+    /// Returned if `on_flash_loan` is called by anyone but the pool.
+    UntrustedLender,
+    /// Returned if the loan was not started by this contract.
+    UntrustedInitiator,
+    /// Returned if the pool or the token rejected a call.
+    CallFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotOwner => f.write_str("the caller is not the borrower owner"),
+            Error::UntrustedLender => f.write_str("on_flash_loan is called by anyone but the pool"),
+            Error::UntrustedInitiator => f.write_str("the loan was not started by this contract"),
+            Error::CallFailed => f.write_str("the pool or the token rejected a call"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotOwner => ContractError::Unauthorized,
+            Error::UntrustedLender => ContractError::Unauthorized,
+            Error::UntrustedInitiator => ContractError::Unauthorized,
+            Error::CallFailed => ContractError::CallFailed,
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// crates/errors/src/flashloan_pool.rs
+//
+// Errors of `examples/demo-contracts/flashloan_pool.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The flash loan pool error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the pool owner.
+    NotOwner,
+    /// Returned if the amount is zero.
+    ZeroAmount,
+    /// Returned if the pool holds less than the amount asked for.
+    InsufficientLiquidity,
+    /// Returned if the token refused to move the tokens.
+    TransferFailed,
+    /// Returned if the caller is not the guardian.
+    NotGuardian,
+    /// Returned if loans are stopped by the circuit breaker.
+    Tripped,
+    /// Returned if the circuit breaker is not tripped.
+    NotTripped,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotOwner => f.write_str("the caller is not the pool owner"),
+            Error::ZeroAmount => f.write_str("the amount is zero"),
+            Error::InsufficientLiquidity => {
+                f.write_str("the pool holds less than the amount asked for")
+            }
+            Error::TransferFailed => f.write_str("the token refused to move the tokens"),
+            Error::NotGuardian => f.write_str("the caller is not the guardian"),
+            Error::Tripped => f.write_str("loans are stopped by the circuit breaker"),
+            Error::NotTripped => f.write_str("the circuit breaker is not tripped"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotOwner => ContractError::Unauthorized,
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::InsufficientLiquidity => ContractError::InsufficientBalance,
+            Error::TransferFailed => ContractError::TransferFailed,
+            Error::NotGuardian => ContractError::Unauthorized,
+            Error::Tripped => ContractError::Unavailable,
+            Error::NotTripped => ContractError::PreconditionFailed,
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// crates/errors/src/forwarder.rs
+//
+// Errors of `examples/demo-contracts/forwarder.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The forwarder error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the signature does not recover to `request.from`.
+    InvalidSignature,
+    /// Returned if the request's nonce is not the signer's next nonce.
+    InvalidNonce,
+    /// Returned if `request.data` is too short to hold a selector.
+    MissingSelector,
+    /// Returned if the call to the target contract failed.
+    CallFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidSignature => {
+                f.write_str("the signature does not recover to request.from")
+            }
+            Error::InvalidNonce => {
+                f.write_str("the request's nonce is not the signer's next nonce")
+            }
+            Error::MissingSelector => f.write_str("request.data is too short to hold a selector"),
+            Error::CallFailed => f.write_str("the call to the target contract failed"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::InvalidSignature => ContractError::InvalidSignature,
+            Error::InvalidNonce => ContractError::InvalidSignature,
+            Error::MissingSelector => ContractError::InvalidArgument,
+            Error::CallFailed => ContractError::CallFailed,
+        }
+    }
+}
@@ -0,0 +1,89 @@
+// crates/errors/src/insurance.rs
+//
+// Errors of `examples/demo-contracts/insurance.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The insurance error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the admin.
+    NotAdmin,
+    /// Returned if the caller is not the assessor.
+    NotAssessor,
+    /// Returned if the amount is zero.
+    ZeroAmount,
+    /// Returned if the value sent along is not the premium.
+    WrongPremium,
+    /// Returned if the unlocked pool cannot back the coverage or withdrawal.
+    InsufficientCapacity,
+    /// Returned if there is no policy with the id.
+    PolicyNotFound,
+    /// Returned if the caller does not hold the policy.
+    NotPolicyholder,
+    /// Returned if the policy has expired.
+    PolicyExpired,
+    /// Returned if the policy has not expired yet.
+    PolicyActive,
+    /// Returned if the policy already has a claim awaiting assessment.
+    ClaimPending,
+    /// Returned if the policy has no claim awaiting assessment.
+    NoClaim,
+    /// Returned if the oracle value does not trigger a payout.
+    ConditionNotMet,
+    /// Returned if the oracle has no usable value for the index.
+    OracleUnavailable,
+    /// Returned if the native transfer failed.
+    TransferFailed,
+    /// Returned if the premium or payout math overflows.
+    Overflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotAdmin => f.write_str("the caller is not the admin"),
+            Error::NotAssessor => f.write_str("the caller is not the assessor"),
+            Error::ZeroAmount => f.write_str("the amount is zero"),
+            Error::WrongPremium => f.write_str("the value sent along is not the premium"),
+            Error::InsufficientCapacity => {
+                f.write_str("the unlocked pool cannot back the coverage or withdrawal")
+            }
+            Error::PolicyNotFound => f.write_str("there is no policy with the id"),
+            Error::NotPolicyholder => f.write_str("the caller does not hold the policy"),
+            Error::PolicyExpired => f.write_str("the policy has expired"),
+            Error::PolicyActive => f.write_str("the policy has not expired yet"),
+            Error::ClaimPending => {
+                f.write_str("the policy already has a claim awaiting assessment")
+            }
+            Error::NoClaim => f.write_str("the policy has no claim awaiting assessment"),
+            Error::ConditionNotMet => f.write_str("the oracle value does not trigger a payout"),
+            Error::OracleUnavailable => f.write_str("the oracle has no usable value for the index"),
+            Error::TransferFailed => f.write_str("the native transfer failed"),
+            Error::Overflow => f.write_str("the premium or payout math overflows"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotAdmin => ContractError::Unauthorized,
+            Error::NotAssessor => ContractError::Unauthorized,
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::WrongPremium => ContractError::WrongPayment,
+            Error::InsufficientCapacity => ContractError::InsufficientBalance,
+            Error::PolicyNotFound => ContractError::NotFound,
+            Error::NotPolicyholder => ContractError::Unauthorized,
+            Error::PolicyExpired => ContractError::TooLate,
+            Error::PolicyActive => ContractError::TooEarly,
+            Error::ClaimPending => ContractError::AlreadyExists,
+            Error::NoClaim => ContractError::NotFound,
+            Error::ConditionNotMet => ContractError::PreconditionFailed,
+            Error::OracleUnavailable => ContractError::Unavailable,
+            Error::TransferFailed => ContractError::TransferFailed,
+            Error::Overflow => ContractError::Overflow,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+// crates/errors/src/leaderboard.rs
+//
+// Errors of `examples/demo-contracts/leaderboard.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The leaderboard error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the admin.
+    NotAdmin,
+    /// Returned if the caller is not the grader.
+    NotGrader,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotAdmin => f.write_str("the caller is not the admin"),
+            Error::NotGrader => f.write_str("the caller is not the grader"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotAdmin => ContractError::Unauthorized,
+            Error::NotGrader => ContractError::Unauthorized,
+        }
+    }
+}
@@ -0,0 +1,381 @@
+// crates/errors/src/lib.rs
+//
+// The errors of every example contract, mapped to shared error codes.
+//
+// A message that fails returns the SCALE encoding of its `Err`: the variant index of
+// the contract's `Error`, followed by the variant's fields. Like events, the error
+// enums are declared inside the `#[ink::contract]` modules, so each module here
+// mirrors one contract's error enums variant for variant, with a `Display` message
+// taken from the variant's documentation and a `From` conversion into the shared
+// `ContractError`.
+//
+// `ContractError` groups the errors of all contracts into kinds a tool can handle
+// alike, each with a stable numeric code: `erc20::Error::NotOwner` and
+// `staking::Error::NotAdmin` are both `E100`, "unauthorized". Tools show both the
+// kind and the contract's own message:
+//
+//     let decoded = astranet_errors::decode("erc20", &error_bytes).unwrap();
+//     println!("{}", decoded); // E200 insufficient balance: the balance is ...
+//
+// Codes are pinned in `tests/golden/codes.txt` and never change or get reused; a new
+// kind gets a new code. `tests/mirrors.rs` fails when a mirror drifts from its
+// contract, so the module here must change in the same commit as the contract.
+
+use std::fmt;
+
+pub mod arbitration;
+pub mod badge;
+pub mod cdp;
+pub mod config_store;
+pub mod crowdloan;
+pub mod dividend_token;
+pub mod donation;
+pub mod enrollment;
+pub mod erc20;
+pub mod erc721;
+pub mod events_showcase;
+pub mod factory;
+pub mod faucet;
+pub mod flash_borrower;
+pub mod flashloan_pool;
+pub mod forwarder;
+pub mod insurance;
+pub mod leaderboard;
+pub mod limited_token;
+pub mod marketplace;
+pub mod migrate_v1;
+pub mod migrate_v2;
+pub mod mock_dao;
+pub mod mock_oracle;
+pub mod mock_token;
+pub mod multisig;
+pub mod nomination;
+pub mod orderbook;
+pub mod payroll;
+pub mod peer_review;
+pub mod prediction;
+pub mod progress;
+pub mod quadratic_funding;
+pub mod rebase_token;
+pub mod recovery_wallet;
+pub mod referral;
+pub mod revenue_share;
+pub mod runtime_call;
+pub mod scholarship;
+pub mod sig_verify;
+pub mod spending_manager;
+pub mod staking;
+pub mod storage_layouts;
+pub mod timed_quiz;
+pub mod token_locker;
+pub mod treasury;
+pub mod vesting;
+pub mod vesting_factory;
+pub mod votes_token;
+pub mod xcm_transfer;
+
+/// Block numbers as used by the default contracts environment.
+pub type BlockNumber = u32;
+
+/// The kind of an error, shared by every contract.
+///
+/// Codes are grouped by hundreds: access, funds, records, timing, arguments, state,
+/// other contracts and the runtime, and signatures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContractError {
+    /// The caller lacks the role or ownership the message needs.
+    Unauthorized = 100,
+    /// An account, position or pool holds too little.
+    InsufficientBalance = 200,
+    /// The caller may not spend that much on the owner's behalf.
+    InsufficientAllowance = 201,
+    /// An amount, price or value that must be positive is zero.
+    ZeroAmount = 202,
+    /// The value sent along differs from what is due.
+    WrongPayment = 203,
+    /// There is nothing to claim, withdraw or release.
+    NothingToClaim = 204,
+    /// No record exists under the given id or account.
+    NotFound = 300,
+    /// The record already exists, or the action was already taken.
+    AlreadyExists = 301,
+    /// The action is not allowed yet.
+    TooEarly = 400,
+    /// The action is no longer allowed.
+    TooLate = 401,
+    /// An argument is malformed or out of range.
+    InvalidArgument = 500,
+    /// A collection, budget or cap is full.
+    LimitExceeded = 501,
+    /// A calculation overflows.
+    Overflow = 502,
+    /// The contract's state does not allow the action.
+    PreconditionFailed = 600,
+    /// The caller must wait before trying again.
+    RateLimited = 601,
+    /// Sending native or ERC-20 tokens failed.
+    TransferFailed = 700,
+    /// A call to another contract or to the runtime failed.
+    CallFailed = 701,
+    /// A contract or oracle the message relies on cannot answer.
+    Unavailable = 702,
+    /// A signature, voucher or nonce does not verify.
+    InvalidSignature = 800,
+}
+
+impl ContractError {
+    /// Every kind, in code order.
+    pub const ALL: [ContractError; 19] = [
+        ContractError::Unauthorized,
+        ContractError::InsufficientBalance,
+        ContractError::InsufficientAllowance,
+        ContractError::ZeroAmount,
+        ContractError::WrongPayment,
+        ContractError::NothingToClaim,
+        ContractError::NotFound,
+        ContractError::AlreadyExists,
+        ContractError::TooEarly,
+        ContractError::TooLate,
+        ContractError::InvalidArgument,
+        ContractError::LimitExceeded,
+        ContractError::Overflow,
+        ContractError::PreconditionFailed,
+        ContractError::RateLimited,
+        ContractError::TransferFailed,
+        ContractError::CallFailed,
+        ContractError::Unavailable,
+        ContractError::InvalidSignature,
+    ];
+
+    /// Returns the stable numeric code.
+    pub fn code(self) -> u16 {
+        self as u16
+    }
+
+    /// Returns the kind with the given code.
+    pub fn from_code(code: u16) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.code() == code)
+    }
+
+    /// Returns a short description of the kind.
+    pub fn description(self) -> &'static str {
+        match self {
+            ContractError::Unauthorized => "unauthorized",
+            ContractError::InsufficientBalance => "insufficient balance",
+            ContractError::InsufficientAllowance => "insufficient allowance",
+            ContractError::ZeroAmount => "zero amount",
+            ContractError::WrongPayment => "wrong payment",
+            ContractError::NothingToClaim => "nothing to claim",
+            ContractError::NotFound => "not found",
+            ContractError::AlreadyExists => "already exists",
+            ContractError::TooEarly => "too early",
+            ContractError::TooLate => "too late",
+            ContractError::InvalidArgument => "invalid argument",
+            ContractError::LimitExceeded => "limit exceeded",
+            ContractError::Overflow => "overflow",
+            ContractError::PreconditionFailed => "precondition failed",
+            ContractError::RateLimited => "rate limited",
+            ContractError::TransferFailed => "transfer failed",
+            ContractError::CallFailed => "call failed",
+            ContractError::Unavailable => "unavailable",
+            ContractError::InvalidSignature => "invalid signature",
+        }
+    }
+}
+
+impl fmt::Display for ContractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "E{} {}", self.code(), self.description())
+    }
+}
+
+impl std::error::Error for ContractError {}
+
+/// A contract error decoded from its bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoded {
+    /// The shared kind of the error.
+    pub kind: ContractError,
+    /// The contract's own message for the error.
+    pub message: String,
+}
+
+impl fmt::Display for Decoded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.message)
+    }
+}
+
+/// The example contracts this crate knows the errors of, by file name.
+pub const CONTRACTS: [&str; 50] = [
+    "arbitration",
+    "badge",
+    "cdp",
+    "config_store",
+    "crowdloan",
+    "dividend_token",
+    "donation",
+    "enrollment",
+    "erc20",
+    "erc721",
+    "events_showcase",
+    "factory",
+    "faucet",
+    "flash_borrower",
+    "flashloan_pool",
+    "forwarder",
+    "insurance",
+    "leaderboard",
+    "limited_token",
+    "marketplace",
+    "migrate_v1",
+    "migrate_v2",
+    "mock_dao",
+    "mock_oracle",
+    "mock_token",
+    "multisig",
+    "nomination",
+    "orderbook",
+    "payroll",
+    "peer_review",
+    "prediction",
+    "progress",
+    "quadratic_funding",
+    "rebase_token",
+    "recovery_wallet",
+    "referral",
+    "revenue_share",
+    "runtime_call",
+    "scholarship",
+    "sig_verify",
+    "spending_manager",
+    "staking",
+    "storage_layouts",
+    "timed_quiz",
+    "token_locker",
+    "treasury",
+    "vesting",
+    "vesting_factory",
+    "votes_token",
+    "xcm_transfer",
+];
+
+/// Decodes the `Error` of `contract`, named like its file in `examples/`, from the
+/// bytes after the `Err` index of a message's result.
+///
+/// Returns `None` for an unknown contract, or if the bytes do not decode exactly.
+pub fn decode(contract: &str, data: &[u8]) -> Option<Decoded> {
+    match contract {
+        "arbitration" => decode_as::<arbitration::Error>(data),
+        "badge" => decode_as::<badge::Error>(data),
+        "cdp" => decode_as::<cdp::Error>(data),
+        "config_store" => decode_as::<config_store::Error>(data),
+        "crowdloan" => decode_as::<crowdloan::Error>(data),
+        "dividend_token" => decode_as::<dividend_token::Error>(data),
+        "donation" => decode_as::<donation::Error>(data),
+        "enrollment" => decode_as::<enrollment::Error>(data),
+        "erc20" => decode_as::<erc20::Error>(data),
+        "erc721" => decode_as::<erc721::Error>(data),
+        "events_showcase" => decode_as::<events_showcase::Error>(data),
+        "factory" => decode_as::<factory::Error>(data),
+        "faucet" => decode_as::<faucet::Error>(data),
+        "flash_borrower" => decode_as::<flash_borrower::Error>(data),
+        "flashloan_pool" => decode_as::<flashloan_pool::Error>(data),
+        "forwarder" => decode_as::<forwarder::Error>(data),
+        "insurance" => decode_as::<insurance::Error>(data),
+        "leaderboard" => decode_as::<leaderboard::Error>(data),
+        "limited_token" => decode_as::<limited_token::Error>(data),
+        "marketplace" => decode_as::<marketplace::Error>(data),
+        "migrate_v1" => decode_as::<migrate_v1::Error>(data),
+        "migrate_v2" => decode_as::<migrate_v2::Error>(data),
+        "mock_dao" => decode_as::<mock_dao::Error>(data),
+        "mock_oracle" => decode_as::<mock_oracle::Error>(data),
+        "mock_token" => decode_as::<mock_token::Error>(data),
+        "multisig" => decode_as::<multisig::Error>(data),
+        "nomination" => decode_as::<nomination::Error>(data),
+        "orderbook" => decode_as::<orderbook::Error>(data),
+        "payroll" => decode_as::<payroll::Error>(data),
+        "peer_review" => decode_as::<peer_review::Error>(data),
+        "prediction" => decode_as::<prediction::Error>(data),
+        "progress" => decode_as::<progress::Error>(data),
+        "quadratic_funding" => decode_as::<quadratic_funding::Error>(data),
+        "rebase_token" => decode_as::<rebase_token::Error>(data),
+        "recovery_wallet" => decode_as::<recovery_wallet::Error>(data),
+        "referral" => decode_as::<referral::Error>(data),
+        "revenue_share" => decode_as::<revenue_share::Error>(data),
+        "runtime_call" => decode_as::<runtime_call::Error>(data),
+        "scholarship" => decode_as::<scholarship::Error>(data),
+        "sig_verify" => decode_as::<sig_verify::Error>(data),
+        "spending_manager" => decode_as::<spending_manager::Error>(data),
+        "staking" => decode_as::<staking::Error>(data),
+        "storage_layouts" => decode_as::<storage_layouts::Error>(data),
+        "timed_quiz" => decode_as::<timed_quiz::Error>(data),
+        "token_locker" => decode_as::<token_locker::Error>(data),
+        "treasury" => decode_as::<treasury::Error>(data),
+        "vesting" => decode_as::<vesting::Error>(data),
+        "vesting_factory" => decode_as::<vesting_factory::Error>(data),
+        "votes_token" => decode_as::<votes_token::Error>(data),
+        "xcm_transfer" => decode_as::<xcm_transfer::Error>(data),
+        _ => None,
+    }
+}
+
+fn decode_as<E>(data: &[u8]) -> Option<Decoded>
+where
+    E: scale::Decode + fmt::Display + Into<ContractError>,
+{
+    let error: E = scale::DecodeAll::decode_all(&mut &data[..]).ok()?;
+    Some(Decoded {
+        message: error.to_string(),
+        kind: error.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip() {
+        for kind in ContractError::ALL {
+            assert_eq!(ContractError::from_code(kind.code()), Some(kind));
+        }
+        assert_eq!(ContractError::from_code(0), None);
+    }
+
+    #[test]
+    fn errors_decode_with_their_message() {
+        let decoded = decode("erc20", &[0]).unwrap();
+        assert_eq!(decoded.kind, ContractError::InsufficientBalance);
+        assert_eq!(
+            decoded.to_string(),
+            "E200 insufficient balance: the balance is insufficient for the operation"
+        );
+
+        let decoded = decode("faucet", &[0, 7, 0, 0, 0]).unwrap();
+        assert_eq!(decoded.kind, ContractError::RateLimited);
+        assert_eq!(
+            decoded.message,
+            "the caller dripped too often; retry at block 7"
+        );
+    }
+
+    #[test]
+    fn unknown_contracts_and_bad_bytes_are_rejected() {
+        assert_eq!(decode("flipper", &[0]), None);
+        assert_eq!(decode("erc20", &[]), None);
+        assert_eq!(decode("erc20", &[0, 0]), None);
+        assert_eq!(decode("erc20", &[200]), None);
+    }
+
+    #[test]
+    fn every_contract_decodes_its_first_variant() {
+        for contract in CONTRACTS {
+            let data: &[u8] = match contract {
+                "faucet" => &[0, 0, 0, 0, 0],
+                _ => &[0],
+            };
+            assert!(decode(contract, data).is_some(), "{}", contract);
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// crates/errors/src/limited_token.rs
+//
+// Errors of `examples/demo-contracts/limited_token.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The limited token error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the balance is insufficient for the operation.
+    InsufficientBalance,
+    /// Returned if the allowance is insufficient for the operation.
+    InsufficientAllowance,
+    /// Returned if the transfer is above the per-transfer cap, or would take the sender
+    /// above the rolling 24-hour cap.
+    LimitExceeded,
+    /// Returned if the caller is not the token owner.
+    NotOwner,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InsufficientBalance => f.write_str("the balance is insufficient for the operation"),
+            Error::InsufficientAllowance => f.write_str("the allowance is insufficient for the operation"),
+            Error::LimitExceeded => f.write_str("the transfer is above the per-transfer cap, or would take the sender above the rolling 24-hour cap"),
+            Error::NotOwner => f.write_str("the caller is not the token owner"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::InsufficientBalance => ContractError::InsufficientBalance,
+            Error::InsufficientAllowance => ContractError::InsufficientAllowance,
+            Error::LimitExceeded => ContractError::LimitExceeded,
+            Error::NotOwner => ContractError::Unauthorized,
+        }
+    }
+}
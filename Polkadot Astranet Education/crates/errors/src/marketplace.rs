@@ -0,0 +1,53 @@
+// crates/errors/src/marketplace.rs
+//
+// Errors of `examples/demo-contracts/marketplace.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The marketplace error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the price is zero.
+    ZeroPrice,
+    /// Returned if the token is already listed.
+    AlreadyListed,
+    /// Returned if no active listing has the given id.
+    ListingNotFound,
+    /// Returned if the caller is not the seller.
+    NotSeller,
+    /// Returned if the payment differs from the price.
+    WrongPayment,
+    /// Returned if the token could not be moved to the buyer.
+    TransferFailed,
+    /// Returned if the caller has no proceeds to withdraw.
+    NothingToWithdraw,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ZeroPrice => f.write_str("the price is zero"),
+            Error::AlreadyListed => f.write_str("the token is already listed"),
+            Error::ListingNotFound => f.write_str("no active listing has the given id"),
+            Error::NotSeller => f.write_str("the caller is not the seller"),
+            Error::WrongPayment => f.write_str("the payment differs from the price"),
+            Error::TransferFailed => f.write_str("the token could not be moved to the buyer"),
+            Error::NothingToWithdraw => f.write_str("the caller has no proceeds to withdraw"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::ZeroPrice => ContractError::ZeroAmount,
+            Error::AlreadyListed => ContractError::AlreadyExists,
+            Error::ListingNotFound => ContractError::NotFound,
+            Error::NotSeller => ContractError::Unauthorized,
+            Error::WrongPayment => ContractError::WrongPayment,
+            Error::TransferFailed => ContractError::TransferFailed,
+            Error::NothingToWithdraw => ContractError::NothingToClaim,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+// crates/errors/src/migrate_v1.rs
+//
+// Errors of `examples/demo-contracts/migrate_v1.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The score registry error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the contract owner.
+    NotOwner,
+    /// Returned if the node refused to switch to the new code.
+    UpgradeFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotOwner => f.write_str("the caller is not the contract owner"),
+            Error::UpgradeFailed => f.write_str("the node refused to switch to the new code"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotOwner => ContractError::Unauthorized,
+            Error::UpgradeFailed => ContractError::CallFailed,
+        }
+    }
+}
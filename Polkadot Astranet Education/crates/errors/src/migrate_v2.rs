@@ -0,0 +1,46 @@
+// crates/errors/src/migrate_v2.rs
+//
+// Errors of `examples/demo-contracts/migrate_v2.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The score registry error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the contract owner.
+    NotOwner,
+    /// Returned if the node refused to switch to the new code.
+    UpgradeFailed,
+    /// Returned if the storage is at `found` rather than the `expected` version, such as
+    /// when migrating again after the migration completed.
+    UnexpectedVersion { expected: u16, found: u16 },
+    /// Returned if the storage version cannot go any higher.
+    VersionExhausted,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotOwner => f.write_str("the caller is not the contract owner"),
+            Error::UpgradeFailed => f.write_str("the node refused to switch to the new code"),
+            Error::UnexpectedVersion { expected, found } => write!(
+                f,
+                "the storage is at version {} rather than version {}",
+                found, expected
+            ),
+            Error::VersionExhausted => f.write_str("the storage version cannot go any higher"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotOwner => ContractError::Unauthorized,
+            Error::UpgradeFailed => ContractError::CallFailed,
+            Error::UnexpectedVersion { .. } => ContractError::PreconditionFailed,
+            Error::VersionExhausted => ContractError::LimitExceeded,
+        }
+    }
+}
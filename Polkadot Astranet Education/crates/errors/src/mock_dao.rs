@@ -0,0 +1,33 @@
+// crates/errors/src/mock_dao.rs
+//
+// Errors of `examples/mocks/mock_dao.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The mock DAO error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the treasury could not be called.
+    CallFailed,
+    /// Returned if the treasury rejected the call.
+    Rejected,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::CallFailed => f.write_str("the treasury could not be called"),
+            Error::Rejected => f.write_str("the treasury rejected the call"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::CallFailed => ContractError::CallFailed,
+            Error::Rejected => ContractError::CallFailed,
+        }
+    }
+}
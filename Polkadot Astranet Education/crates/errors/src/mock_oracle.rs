@@ -0,0 +1,33 @@
+// crates/errors/src/mock_oracle.rs
+//
+// Errors of `examples/mocks/mock_oracle.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The mock oracle error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if no price has been set for the asset.
+    NoPrice,
+    /// Returned by every query while the oracle is set to fail.
+    Unavailable,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoPrice => f.write_str("no price has been set for the asset"),
+            Error::Unavailable => f.write_str("the oracle is set to fail"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NoPrice => ContractError::Unavailable,
+            Error::Unavailable => ContractError::Unavailable,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// crates/errors/src/mock_token.rs
+//
+// Errors of `examples/mocks/mock_token.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The mock token error types. Same encoding as the ERC-20 example's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the balance is insufficient for the operation.
+    InsufficientBalance,
+    /// Returned if the allowance is insufficient for the operation.
+    InsufficientAllowance,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InsufficientBalance => {
+                f.write_str("the balance is insufficient for the operation")
+            }
+            Error::InsufficientAllowance => {
+                f.write_str("the allowance is insufficient for the operation")
+            }
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::InsufficientBalance => ContractError::InsufficientBalance,
+            Error::InsufficientAllowance => ContractError::InsufficientAllowance,
+        }
+    }
+}
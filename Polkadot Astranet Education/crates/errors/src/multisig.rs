@@ -0,0 +1,98 @@
+// crates/errors/src/multisig.rs
+//
+// Errors of `examples/demo-contracts/multisig.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// Why the wallet refused or failed to run a module's transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum ModuleError {
+    /// The caller is not an enabled module.
+    NotModule,
+    /// The call was made but failed.
+    CallFailed,
+}
+
+impl fmt::Display for ModuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModuleError::NotModule => f.write_str("the caller is not an enabled module"),
+            ModuleError::CallFailed => f.write_str("the call was made but failed"),
+        }
+    }
+}
+
+impl From<ModuleError> for ContractError {
+    fn from(error: ModuleError) -> Self {
+        match error {
+            ModuleError::NotModule => ContractError::Unauthorized,
+            ModuleError::CallFailed => ContractError::CallFailed,
+        }
+    }
+}
+
+/// The multisig error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not an owner.
+    NotOwner,
+    /// Returned if the caller is not the wallet itself.
+    NotWallet,
+    /// Returned if there is no pending transaction with the id.
+    UnknownTransaction,
+    /// Returned if the owner already confirmed the transaction.
+    AlreadyConfirmed,
+    /// Returned if the owner has not confirmed the transaction.
+    NotConfirmed,
+    /// Returned if fewer than `threshold` owners confirmed the transaction.
+    BelowThreshold,
+    /// Returned if the call of the transaction failed.
+    CallFailed,
+    /// Returned if the module is already enabled.
+    ModuleAlreadyEnabled,
+    /// Returned if the module is not enabled.
+    ModuleNotEnabled,
+    /// Returned if `MAX_MODULES` modules are already enabled.
+    TooManyModules,
+    /// Returned if the wallet has used every transaction id.
+    TooManyTransactions,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotOwner => f.write_str("the caller is not an owner"),
+            Error::NotWallet => f.write_str("the caller is not the wallet itself"),
+            Error::UnknownTransaction => f.write_str("there is no pending transaction with the id"),
+            Error::AlreadyConfirmed => f.write_str("the owner already confirmed the transaction"),
+            Error::NotConfirmed => f.write_str("the owner has not confirmed the transaction"),
+            Error::BelowThreshold => {
+                f.write_str("fewer than threshold owners confirmed the transaction")
+            }
+            Error::CallFailed => f.write_str("the call of the transaction failed"),
+            Error::ModuleAlreadyEnabled => f.write_str("the module is already enabled"),
+            Error::ModuleNotEnabled => f.write_str("the module is not enabled"),
+            Error::TooManyModules => f.write_str("MAX_MODULES modules are already enabled"),
+            Error::TooManyTransactions => f.write_str("the wallet has used every transaction id"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotOwner => ContractError::Unauthorized,
+            Error::NotWallet => ContractError::Unauthorized,
+            Error::UnknownTransaction => ContractError::NotFound,
+            Error::AlreadyConfirmed => ContractError::AlreadyExists,
+            Error::NotConfirmed => ContractError::NotFound,
+            Error::BelowThreshold => ContractError::PreconditionFailed,
+            Error::CallFailed => ContractError::CallFailed,
+            Error::ModuleAlreadyEnabled => ContractError::AlreadyExists,
+            Error::ModuleNotEnabled => ContractError::NotFound,
+            Error::TooManyModules => ContractError::LimitExceeded,
+            Error::TooManyTransactions => ContractError::LimitExceeded,
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// crates/errors/src/nomination.rs
+//
+// Errors of `examples/demo-contracts/nomination.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The nomination error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the amount is zero.
+    ZeroAmount,
+    /// Returned if a commission is more than `BPS` basis points.
+    InvalidCommission,
+    /// Returned if the caller is already an operator.
+    AlreadyRegistered,
+    /// Returned if no operator is registered under the account.
+    UnknownOperator,
+    /// Returned if the caller has delegated less than the amount.
+    InsufficientDelegation,
+    /// Returned if rewards are sent to an operator nobody delegates to.
+    NoDelegations,
+    /// Returned if there is nothing to claim.
+    NothingToClaim,
+    /// Returned if a transfer failed.
+    TransferFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ZeroAmount => f.write_str("the amount is zero"),
+            Error::InvalidCommission => f.write_str("a commission is more than BPS basis points"),
+            Error::AlreadyRegistered => f.write_str("the caller is already an operator"),
+            Error::UnknownOperator => f.write_str("no operator is registered under the account"),
+            Error::InsufficientDelegation => {
+                f.write_str("the caller has delegated less than the amount")
+            }
+            Error::NoDelegations => {
+                f.write_str("rewards are sent to an operator nobody delegates to")
+            }
+            Error::NothingToClaim => f.write_str("there is nothing to claim"),
+            Error::TransferFailed => f.write_str("a transfer failed"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::InvalidCommission => ContractError::InvalidArgument,
+            Error::AlreadyRegistered => ContractError::AlreadyExists,
+            Error::UnknownOperator => ContractError::NotFound,
+            Error::InsufficientDelegation => ContractError::InsufficientBalance,
+            Error::NoDelegations => ContractError::PreconditionFailed,
+            Error::NothingToClaim => ContractError::NothingToClaim,
+            Error::TransferFailed => ContractError::TransferFailed,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// crates/errors/src/orderbook.rs
+//
+// Errors of `examples/demo-contracts/orderbook.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The order book error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the amount is zero.
+    ZeroAmount,
+    /// Returned if the price is zero.
+    ZeroPrice,
+    /// Returned if the token is neither the base nor the quote token.
+    UnknownToken,
+    /// Returned if the caller's exchange balance is insufficient.
+    InsufficientBalance,
+    /// Returned if no open order has the given id.
+    OrderNotFound,
+    /// Returned if the caller did not place the order.
+    NotOrderOwner,
+    /// Returned if the token refused to move the tokens.
+    TransferFailed,
+    /// Returned if the cost of an order overflows.
+    Overflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ZeroAmount => f.write_str("the amount is zero"),
+            Error::ZeroPrice => f.write_str("the price is zero"),
+            Error::UnknownToken => f.write_str("the token is neither the base nor the quote token"),
+            Error::InsufficientBalance => {
+                f.write_str("the caller's exchange balance is insufficient")
+            }
+            Error::OrderNotFound => f.write_str("no open order has the given id"),
+            Error::NotOrderOwner => f.write_str("the caller did not place the order"),
+            Error::TransferFailed => f.write_str("the token refused to move the tokens"),
+            Error::Overflow => f.write_str("the cost of an order overflows"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::ZeroPrice => ContractError::ZeroAmount,
+            Error::UnknownToken => ContractError::NotFound,
+            Error::InsufficientBalance => ContractError::InsufficientBalance,
+            Error::OrderNotFound => ContractError::NotFound,
+            Error::NotOrderOwner => ContractError::Unauthorized,
+            Error::TransferFailed => ContractError::TransferFailed,
+            Error::Overflow => ContractError::Overflow,
+        }
+    }
+}
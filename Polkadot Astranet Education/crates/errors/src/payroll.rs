@@ -0,0 +1,53 @@
+// crates/errors/src/payroll.rs
+//
+// Errors of `examples/demo-contracts/payroll.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The payroll error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the employer.
+    NotEmployer,
+    /// Returned if the account is already an employee.
+    AlreadyEmployed,
+    /// Returned if the account is not an employee.
+    NotEmployed,
+    /// Returned if the salary is zero.
+    ZeroSalary,
+    /// Returned if no whole period is due yet.
+    NothingDue,
+    /// Returned if the contract cannot cover the payment.
+    InsufficientFunds,
+    /// Returned if the native transfer failed.
+    TransferFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotEmployer => f.write_str("the caller is not the employer"),
+            Error::AlreadyEmployed => f.write_str("the account is already an employee"),
+            Error::NotEmployed => f.write_str("the account is not an employee"),
+            Error::ZeroSalary => f.write_str("the salary is zero"),
+            Error::NothingDue => f.write_str("no whole period is due yet"),
+            Error::InsufficientFunds => f.write_str("the contract cannot cover the payment"),
+            Error::TransferFailed => f.write_str("the native transfer failed"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotEmployer => ContractError::Unauthorized,
+            Error::AlreadyEmployed => ContractError::AlreadyExists,
+            Error::NotEmployed => ContractError::NotFound,
+            Error::ZeroSalary => ContractError::ZeroAmount,
+            Error::NothingDue => ContractError::NothingToClaim,
+            Error::InsufficientFunds => ContractError::InsufficientBalance,
+            Error::TransferFailed => ContractError::TransferFailed,
+        }
+    }
+}
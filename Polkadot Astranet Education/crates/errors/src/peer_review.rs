@@ -0,0 +1,83 @@
+// crates/errors/src/peer_review.rs
+//
+// Errors of `examples/demo-contracts/peer_review.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The peer review error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if no value was sent along, or the amount is zero.
+    ZeroAmount,
+    /// Returned if the caller's free stake is below what the action needs.
+    InsufficientStake,
+    /// Returned if no submission has the given id.
+    SubmissionNotFound,
+    /// Returned if the caller reviews their own submission.
+    OwnSubmission,
+    /// Returned if the caller already reviewed the submission.
+    AlreadyReviewed,
+    /// Returned if no review has the given id.
+    ReviewNotFound,
+    /// Returned if the caller votes on their own review.
+    OwnReview,
+    /// Returned if the caller already voted on the review.
+    AlreadyVoted,
+    /// Returned if the voting period of the review is over.
+    VotingClosed,
+    /// Returned if the voting period of the review is not over yet.
+    VotingOpen,
+    /// Returned if the review has already been resolved.
+    AlreadyResolved,
+    /// Returned if the caller is not the owner.
+    NotOwner,
+    /// Returned if there is nothing to withdraw.
+    NothingToWithdraw,
+    /// Returned if the native transfer failed.
+    TransferFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ZeroAmount => f.write_str("no value was sent along, or the amount is zero"),
+            Error::InsufficientStake => {
+                f.write_str("the caller's free stake is below what the action needs")
+            }
+            Error::SubmissionNotFound => f.write_str("no submission has the given id"),
+            Error::OwnSubmission => f.write_str("the caller reviews their own submission"),
+            Error::AlreadyReviewed => f.write_str("the caller already reviewed the submission"),
+            Error::ReviewNotFound => f.write_str("no review has the given id"),
+            Error::OwnReview => f.write_str("the caller votes on their own review"),
+            Error::AlreadyVoted => f.write_str("the caller already voted on the review"),
+            Error::VotingClosed => f.write_str("the voting period of the review is over"),
+            Error::VotingOpen => f.write_str("the voting period of the review is not over yet"),
+            Error::AlreadyResolved => f.write_str("the review has already been resolved"),
+            Error::NotOwner => f.write_str("the caller is not the owner"),
+            Error::NothingToWithdraw => f.write_str("there is nothing to withdraw"),
+            Error::TransferFailed => f.write_str("the native transfer failed"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::InsufficientStake => ContractError::InsufficientBalance,
+            Error::SubmissionNotFound => ContractError::NotFound,
+            Error::OwnSubmission => ContractError::InvalidArgument,
+            Error::AlreadyReviewed => ContractError::AlreadyExists,
+            Error::ReviewNotFound => ContractError::NotFound,
+            Error::OwnReview => ContractError::InvalidArgument,
+            Error::AlreadyVoted => ContractError::AlreadyExists,
+            Error::VotingClosed => ContractError::TooLate,
+            Error::VotingOpen => ContractError::TooEarly,
+            Error::AlreadyResolved => ContractError::AlreadyExists,
+            Error::NotOwner => ContractError::Unauthorized,
+            Error::NothingToWithdraw => ContractError::NothingToClaim,
+            Error::TransferFailed => ContractError::TransferFailed,
+        }
+    }
+}
@@ -0,0 +1,69 @@
+// crates/errors/src/prediction.rs
+//
+// Errors of `examples/demo-contracts/prediction.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The prediction market error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the admin.
+    NotAdmin,
+    /// Returned if the amount is zero.
+    ZeroAmount,
+    /// Returned if there is no market with the id.
+    MarketNotFound,
+    /// Returned if the market no longer sells shares.
+    MarketClosed,
+    /// Returned if the market has not closed yet.
+    MarketOpen,
+    /// Returned if the market is already resolved.
+    AlreadyResolved,
+    /// Returned if the market is not resolved yet.
+    NotResolved,
+    /// Returned if the caller holds no shares in the market.
+    NothingToRedeem,
+    /// Returned if the oracle has no usable price for the asset.
+    OracleUnavailable,
+    /// Returned if the token refused to move the tokens.
+    TransferFailed,
+    /// Returned if the share math overflows.
+    Overflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotAdmin => f.write_str("the caller is not the admin"),
+            Error::ZeroAmount => f.write_str("the amount is zero"),
+            Error::MarketNotFound => f.write_str("there is no market with the id"),
+            Error::MarketClosed => f.write_str("the market no longer sells shares"),
+            Error::MarketOpen => f.write_str("the market has not closed yet"),
+            Error::AlreadyResolved => f.write_str("the market is already resolved"),
+            Error::NotResolved => f.write_str("the market is not resolved yet"),
+            Error::NothingToRedeem => f.write_str("the caller holds no shares in the market"),
+            Error::OracleUnavailable => f.write_str("the oracle has no usable price for the asset"),
+            Error::TransferFailed => f.write_str("the token refused to move the tokens"),
+            Error::Overflow => f.write_str("the share math overflows"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotAdmin => ContractError::Unauthorized,
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::MarketNotFound => ContractError::NotFound,
+            Error::MarketClosed => ContractError::TooLate,
+            Error::MarketOpen => ContractError::TooEarly,
+            Error::AlreadyResolved => ContractError::AlreadyExists,
+            Error::NotResolved => ContractError::TooEarly,
+            Error::NothingToRedeem => ContractError::NothingToClaim,
+            Error::OracleUnavailable => ContractError::Unavailable,
+            Error::TransferFailed => ContractError::TransferFailed,
+            Error::Overflow => ContractError::Overflow,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+// crates/errors/src/progress.rs
+//
+// Errors of `examples/demo-contracts/progress.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The progress error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if there is no course with the id.
+    UnknownCourse,
+    /// Returned if the caller is not the course instructor.
+    NotInstructor,
+    /// Returned if the course already has `MAX_ITEMS` modules.
+    TooManyModules,
+    /// Returned if a module would have no lessons or more than `MAX_ITEMS`.
+    InvalidLessonCount,
+    /// Returned if a prerequisite is not an earlier module.
+    InvalidPrerequisites,
+    /// Returned if the course has no such module.
+    UnknownModule,
+    /// Returned if the module has no such lesson.
+    UnknownLesson,
+    /// Returned if a prerequisite of the module is not complete.
+    PrerequisitesIncomplete,
+    /// Returned if the learner already completed the lesson.
+    AlreadyCompleted,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownCourse => f.write_str("there is no course with the id"),
+            Error::NotInstructor => f.write_str("the caller is not the course instructor"),
+            Error::TooManyModules => f.write_str("the course already has MAX_ITEMS modules"),
+            Error::InvalidLessonCount => {
+                f.write_str("a module would have no lessons or more than MAX_ITEMS")
+            }
+            Error::InvalidPrerequisites => f.write_str("a prerequisite is not an earlier module"),
+            Error::UnknownModule => f.write_str("the course has no such module"),
+            Error::UnknownLesson => f.write_str("the module has no such lesson"),
+            Error::PrerequisitesIncomplete => {
+                f.write_str("a prerequisite of the module is not complete")
+            }
+            Error::AlreadyCompleted => f.write_str("the learner already completed the lesson"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::UnknownCourse => ContractError::NotFound,
+            Error::NotInstructor => ContractError::Unauthorized,
+            Error::TooManyModules => ContractError::LimitExceeded,
+            Error::InvalidLessonCount => ContractError::InvalidArgument,
+            Error::InvalidPrerequisites => ContractError::InvalidArgument,
+            Error::UnknownModule => ContractError::NotFound,
+            Error::UnknownLesson => ContractError::NotFound,
+            Error::PrerequisitesIncomplete => ContractError::PreconditionFailed,
+            Error::AlreadyCompleted => ContractError::AlreadyExists,
+        }
+    }
+}
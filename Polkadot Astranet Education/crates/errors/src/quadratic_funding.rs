@@ -0,0 +1,73 @@
+// crates/errors/src/quadratic_funding.rs
+//
+// Errors of `examples/demo-contracts/quadratic_funding.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The quadratic funding error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the admin.
+    NotAdmin,
+    /// Returned if the amount is zero.
+    ZeroAmount,
+    /// Returned if the round already has `MAX_PROJECTS` projects.
+    TooManyProjects,
+    /// Returned if there is no project with the id.
+    UnknownProject,
+    /// Returned if the round no longer takes contributions.
+    RoundClosed,
+    /// Returned if the round has not ended yet.
+    RoundOpen,
+    /// Returned if the matches are already computed.
+    AlreadyFinalized,
+    /// Returned if the matches are not computed yet.
+    NotFinalized,
+    /// Returned if the project was already paid.
+    AlreadyPaid,
+    /// Returned if there is nothing to withdraw.
+    NothingToWithdraw,
+    /// Returned if the transfer to the recipient failed.
+    TransferFailed,
+    /// Returned if the matching math overflows.
+    Overflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotAdmin => f.write_str("the caller is not the admin"),
+            Error::ZeroAmount => f.write_str("the amount is zero"),
+            Error::TooManyProjects => f.write_str("the round already has MAX_PROJECTS projects"),
+            Error::UnknownProject => f.write_str("there is no project with the id"),
+            Error::RoundClosed => f.write_str("the round no longer takes contributions"),
+            Error::RoundOpen => f.write_str("the round has not ended yet"),
+            Error::AlreadyFinalized => f.write_str("the matches are already computed"),
+            Error::NotFinalized => f.write_str("the matches are not computed yet"),
+            Error::AlreadyPaid => f.write_str("the project was already paid"),
+            Error::NothingToWithdraw => f.write_str("there is nothing to withdraw"),
+            Error::TransferFailed => f.write_str("the transfer to the recipient failed"),
+            Error::Overflow => f.write_str("the matching math overflows"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotAdmin => ContractError::Unauthorized,
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::TooManyProjects => ContractError::LimitExceeded,
+            Error::UnknownProject => ContractError::NotFound,
+            Error::RoundClosed => ContractError::TooLate,
+            Error::RoundOpen => ContractError::TooEarly,
+            Error::AlreadyFinalized => ContractError::AlreadyExists,
+            Error::NotFinalized => ContractError::TooEarly,
+            Error::AlreadyPaid => ContractError::AlreadyExists,
+            Error::NothingToWithdraw => ContractError::NothingToClaim,
+            Error::TransferFailed => ContractError::TransferFailed,
+            Error::Overflow => ContractError::Overflow,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// crates/errors/src/rebase_token.rs
+//
+// Errors of `examples/demo-contracts/rebase_token.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The rebase token error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the balance is insufficient for the operation.
+    InsufficientBalance,
+    /// Returned if the allowance is insufficient for the operation.
+    InsufficientAllowance,
+    /// Returned if the caller is not the token owner.
+    NotOwner,
+    /// Returned if a rebase targets zero, or a supply so large that a fragment would be
+    /// worth fewer than `MIN_SHARES_PER_FRAGMENT` shares.
+    InvalidSupply,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InsufficientBalance => f.write_str("the balance is insufficient for the operation"),
+            Error::InsufficientAllowance => f.write_str("the allowance is insufficient for the operation"),
+            Error::NotOwner => f.write_str("the caller is not the token owner"),
+            Error::InvalidSupply => f.write_str("a rebase targets zero, or a supply so large that a fragment would be worth fewer than MIN_SHARES_PER_FRAGMENT shares"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::InsufficientBalance => ContractError::InsufficientBalance,
+            Error::InsufficientAllowance => ContractError::InsufficientAllowance,
+            Error::NotOwner => ContractError::Unauthorized,
+            Error::InvalidSupply => ContractError::InvalidArgument,
+        }
+    }
+}
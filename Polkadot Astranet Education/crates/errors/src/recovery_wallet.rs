@@ -0,0 +1,83 @@
+// crates/errors/src/recovery_wallet.rs
+//
+// Errors of `examples/demo-contracts/recovery_wallet.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The recovery wallet error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the owner.
+    NotOwner,
+    /// Returned if the caller or account is not a guardian.
+    NotGuardian,
+    /// Returned if the account is already a guardian.
+    AlreadyGuardian,
+    /// Returned if the wallet already has `MAX_GUARDIANS` guardians.
+    TooManyGuardians,
+    /// Returned if the threshold would be zero or above the number of guardians.
+    InvalidThreshold,
+    /// Returned if the guardian set cannot change while a recovery is pending.
+    RecoveryPending,
+    /// Returned if a recovery to another new owner is pending.
+    DifferentRecovery,
+    /// Returned if the guardian already approved the pending recovery.
+    AlreadyApproved,
+    /// Returned if no recovery is pending.
+    NoRecovery,
+    /// Returned if the recovery lacks approvals or its delay has not passed.
+    NotReady,
+    /// Returned if the wallet holds less than the amount.
+    InsufficientBalance,
+    /// Returned if the transfer failed.
+    TransferFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotOwner => f.write_str("the caller is not the owner"),
+            Error::NotGuardian => f.write_str("the caller or account is not a guardian"),
+            Error::AlreadyGuardian => f.write_str("the account is already a guardian"),
+            Error::TooManyGuardians => {
+                f.write_str("the wallet already has MAX_GUARDIANS guardians")
+            }
+            Error::InvalidThreshold => {
+                f.write_str("the threshold would be zero or above the number of guardians")
+            }
+            Error::RecoveryPending => {
+                f.write_str("the guardian set cannot change while a recovery is pending")
+            }
+            Error::DifferentRecovery => f.write_str("a recovery to another new owner is pending"),
+            Error::AlreadyApproved => {
+                f.write_str("the guardian already approved the pending recovery")
+            }
+            Error::NoRecovery => f.write_str("no recovery is pending"),
+            Error::NotReady => {
+                f.write_str("the recovery lacks approvals or its delay has not passed")
+            }
+            Error::InsufficientBalance => f.write_str("the wallet holds less than the amount"),
+            Error::TransferFailed => f.write_str("the transfer failed"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotOwner => ContractError::Unauthorized,
+            Error::NotGuardian => ContractError::Unauthorized,
+            Error::AlreadyGuardian => ContractError::AlreadyExists,
+            Error::TooManyGuardians => ContractError::LimitExceeded,
+            Error::InvalidThreshold => ContractError::InvalidArgument,
+            Error::RecoveryPending => ContractError::PreconditionFailed,
+            Error::DifferentRecovery => ContractError::PreconditionFailed,
+            Error::AlreadyApproved => ContractError::AlreadyExists,
+            Error::NoRecovery => ContractError::NotFound,
+            Error::NotReady => ContractError::TooEarly,
+            Error::InsufficientBalance => ContractError::InsufficientBalance,
+            Error::TransferFailed => ContractError::TransferFailed,
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// crates/errors/src/referral.rs
+//
+// Errors of `examples/demo-contracts/referral.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The referral error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the admin.
+    NotAdmin,
+    /// Returned if the tiers are empty, too many, do not start at 1 or do not increase
+    /// strictly.
+    InvalidTiers,
+    /// Returned if the caller names themselves as referrer.
+    SelfReferral,
+    /// Returned if the referrer was referred by the caller.
+    CircularReferral,
+    /// Returned if the caller already registered a referrer.
+    AlreadyReferred,
+    /// Returned if the learner has no referrer.
+    NotReferred,
+    /// Returned if the referral was already rewarded.
+    AlreadyRewarded,
+    /// Returned if the learner is not enrolled in the course.
+    NotEnrolled,
+    /// Returned if the enrollment contract could not be asked.
+    EnrollmentUnavailable,
+    /// Returned if the token refused to pay the reward.
+    TransferFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotAdmin => f.write_str("the caller is not the admin"),
+            Error::InvalidTiers => f.write_str(
+                "the tiers are empty, too many, do not start at 1 or do not increase strictly",
+            ),
+            Error::SelfReferral => f.write_str("the caller names themselves as referrer"),
+            Error::CircularReferral => f.write_str("the referrer was referred by the caller"),
+            Error::AlreadyReferred => f.write_str("the caller already registered a referrer"),
+            Error::NotReferred => f.write_str("the learner has no referrer"),
+            Error::AlreadyRewarded => f.write_str("the referral was already rewarded"),
+            Error::NotEnrolled => f.write_str("the learner is not enrolled in the course"),
+            Error::EnrollmentUnavailable => {
+                f.write_str("the enrollment contract could not be asked")
+            }
+            Error::TransferFailed => f.write_str("the token refused to pay the reward"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotAdmin => ContractError::Unauthorized,
+            Error::InvalidTiers => ContractError::InvalidArgument,
+            Error::SelfReferral => ContractError::InvalidArgument,
+            Error::CircularReferral => ContractError::InvalidArgument,
+            Error::AlreadyReferred => ContractError::AlreadyExists,
+            Error::NotReferred => ContractError::NotFound,
+            Error::AlreadyRewarded => ContractError::AlreadyExists,
+            Error::NotEnrolled => ContractError::NotFound,
+            Error::EnrollmentUnavailable => ContractError::Unavailable,
+            Error::TransferFailed => ContractError::TransferFailed,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// crates/errors/src/revenue_share.rs
+//
+// Errors of `examples/demo-contracts/revenue_share.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The revenue share error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the admin.
+    NotAdmin,
+    /// Returned if adding the instructor would exceed `MAX_INSTRUCTORS`.
+    TooManyInstructors,
+    /// Returned if there is no instructor to split a payment among.
+    NoInstructors,
+    /// Returned if no value was sent along, or no fees were collected.
+    ZeroAmount,
+    /// Returned if the caller has no pending balance.
+    NothingToClaim,
+    /// Returned if the enrollment contract refused the call.
+    EnrollmentFailed,
+    /// Returned if the native transfer failed.
+    TransferFailed,
+    /// Returned if the split math overflows.
+    Overflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotAdmin => f.write_str("the caller is not the admin"),
+            Error::TooManyInstructors => {
+                f.write_str("adding the instructor would exceed MAX_INSTRUCTORS")
+            }
+            Error::NoInstructors => f.write_str("there is no instructor to split a payment among"),
+            Error::ZeroAmount => f.write_str("no value was sent along, or no fees were collected"),
+            Error::NothingToClaim => f.write_str("the caller has no pending balance"),
+            Error::EnrollmentFailed => f.write_str("the enrollment contract refused the call"),
+            Error::TransferFailed => f.write_str("the native transfer failed"),
+            Error::Overflow => f.write_str("the split math overflows"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotAdmin => ContractError::Unauthorized,
+            Error::TooManyInstructors => ContractError::LimitExceeded,
+            Error::NoInstructors => ContractError::PreconditionFailed,
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::NothingToClaim => ContractError::NothingToClaim,
+            Error::EnrollmentFailed => ContractError::CallFailed,
+            Error::TransferFailed => ContractError::TransferFailed,
+            Error::Overflow => ContractError::Overflow,
+        }
+    }
+}
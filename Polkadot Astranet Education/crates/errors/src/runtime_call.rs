@@ -0,0 +1,35 @@
+// crates/errors/src/runtime_call.rs
+//
+// Errors of `examples/demo-contracts/runtime_call.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The runtime-call error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the contract owner.
+    NotOwner,
+    /// Returned if the runtime rejected or failed to dispatch the call.
+    CallRuntimeFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotOwner => f.write_str("the caller is not the contract owner"),
+            Error::CallRuntimeFailed => {
+                f.write_str("the runtime rejected or failed to dispatch the call")
+            }
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotOwner => ContractError::Unauthorized,
+            Error::CallRuntimeFailed => ContractError::CallFailed,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+// crates/errors/src/scholarship.rs
+//
+// Errors of `examples/demo-contracts/scholarship.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The scholarship error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the admin.
+    NotAdmin,
+    /// Returned if no value was sent along, or a scholarship would pay nothing.
+    ZeroAmount,
+    /// Returned if the learner already has an application or a scholarship.
+    AlreadyApplied,
+    /// Returned if the learner has no pending application.
+    NotApplied,
+    /// Returned if the learner has no scholarship.
+    NotScholar,
+    /// Returned if the pool cannot cover the scholarship.
+    InsufficientFunds,
+    /// Returned if the milestone is not the learner's next one.
+    WrongMilestone,
+    /// Returned if the attestation signature is malformed.
+    InvalidSignature,
+    /// Returned if the attestation was not signed by an instructor.
+    NotInstructor,
+    /// Returned if the native transfer failed.
+    TransferFailed,
+    /// Returned if the scholarship total overflows.
+    Overflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotAdmin => f.write_str("the caller is not the admin"),
+            Error::ZeroAmount => {
+                f.write_str("no value was sent along, or a scholarship would pay nothing")
+            }
+            Error::AlreadyApplied => {
+                f.write_str("the learner already has an application or a scholarship")
+            }
+            Error::NotApplied => f.write_str("the learner has no pending application"),
+            Error::NotScholar => f.write_str("the learner has no scholarship"),
+            Error::InsufficientFunds => f.write_str("the pool cannot cover the scholarship"),
+            Error::WrongMilestone => f.write_str("the milestone is not the learner's next one"),
+            Error::InvalidSignature => f.write_str("the attestation signature is malformed"),
+            Error::NotInstructor => f.write_str("the attestation was not signed by an instructor"),
+            Error::TransferFailed => f.write_str("the native transfer failed"),
+            Error::Overflow => f.write_str("the scholarship total overflows"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotAdmin => ContractError::Unauthorized,
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::AlreadyApplied => ContractError::AlreadyExists,
+            Error::NotApplied => ContractError::NotFound,
+            Error::NotScholar => ContractError::NotFound,
+            Error::InsufficientFunds => ContractError::InsufficientBalance,
+            Error::WrongMilestone => ContractError::InvalidArgument,
+            Error::InvalidSignature => ContractError::InvalidSignature,
+            Error::NotInstructor => ContractError::InvalidSignature,
+            Error::TransferFailed => ContractError::TransferFailed,
+            Error::Overflow => ContractError::Overflow,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+// crates/errors/src/sig_verify.rs
+//
+// Errors of `examples/demo-contracts/sig_verify.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The signature verification error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the signature is malformed or does not match the message.
+    InvalidSignature,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidSignature => {
+                f.write_str("the signature is malformed or does not match the message")
+            }
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::InvalidSignature => ContractError::InvalidSignature,
+        }
+    }
+}
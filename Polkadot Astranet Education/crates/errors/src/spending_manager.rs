@@ -0,0 +1,47 @@
+// crates/errors/src/spending_manager.rs
+//
+// Errors of `examples/demo-contracts/spending_manager.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The spending manager error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the owner.
+    NotOwner,
+    /// Returned if the caller has no limit.
+    NotSpender,
+    /// Returned if the amount is zero.
+    ZeroAmount,
+    /// Returned if the spend would exceed the spender's limit for the window.
+    LimitExceeded,
+    /// Returned if the token refused to move the tokens.
+    TransferFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotOwner => f.write_str("the caller is not the owner"),
+            Error::NotSpender => f.write_str("the caller has no limit"),
+            Error::ZeroAmount => f.write_str("the amount is zero"),
+            Error::LimitExceeded => {
+                f.write_str("the spend would exceed the spender's limit for the window")
+            }
+            Error::TransferFailed => f.write_str("the token refused to move the tokens"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotOwner => ContractError::Unauthorized,
+            Error::NotSpender => ContractError::Unauthorized,
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::LimitExceeded => ContractError::LimitExceeded,
+            Error::TransferFailed => ContractError::TransferFailed,
+        }
+    }
+}
@@ -0,0 +1,69 @@
+// crates/errors/src/staking.rs
+//
+// Errors of `examples/demo-contracts/staking.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The staking error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the amount is zero.
+    ZeroAmount,
+    /// Returned if the caller has staked less than the amount.
+    InsufficientStake,
+    /// Returned if the caller has no rewards to claim.
+    NothingToClaim,
+    /// Returned if a token transfer failed.
+    TransferFailed,
+    /// Returned if the caller already has `MAX_UNBONDING_CHUNKS` chunks in flight.
+    TooManyUnbondingChunks,
+    /// Returned if none of the caller's unbonding chunks has matured.
+    NothingToWithdraw,
+    /// Returned if the caller is not the admin.
+    NotAdmin,
+    /// Returned if the caller is not the slasher.
+    NotSlasher,
+    /// Returned if a slash is zero or more than `BPS` basis points.
+    InvalidFraction,
+    /// Returned if the slash would take nothing from the staker.
+    NothingToSlash,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ZeroAmount => f.write_str("the amount is zero"),
+            Error::InsufficientStake => f.write_str("the caller has staked less than the amount"),
+            Error::NothingToClaim => f.write_str("the caller has no rewards to claim"),
+            Error::TransferFailed => f.write_str("a token transfer failed"),
+            Error::TooManyUnbondingChunks => {
+                f.write_str("the caller already has MAX_UNBONDING_CHUNKS chunks in flight")
+            }
+            Error::NothingToWithdraw => {
+                f.write_str("none of the caller's unbonding chunks has matured")
+            }
+            Error::NotAdmin => f.write_str("the caller is not the admin"),
+            Error::NotSlasher => f.write_str("the caller is not the slasher"),
+            Error::InvalidFraction => f.write_str("a slash is zero or more than BPS basis points"),
+            Error::NothingToSlash => f.write_str("the slash would take nothing from the staker"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::InsufficientStake => ContractError::InsufficientBalance,
+            Error::NothingToClaim => ContractError::NothingToClaim,
+            Error::TransferFailed => ContractError::TransferFailed,
+            Error::TooManyUnbondingChunks => ContractError::LimitExceeded,
+            Error::NothingToWithdraw => ContractError::NothingToClaim,
+            Error::NotAdmin => ContractError::Unauthorized,
+            Error::NotSlasher => ContractError::Unauthorized,
+            Error::InvalidFraction => ContractError::InvalidArgument,
+            Error::NothingToSlash => ContractError::PreconditionFailed,
+        }
+    }
+}
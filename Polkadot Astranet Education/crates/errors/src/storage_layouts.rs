@@ -0,0 +1,33 @@
+// crates/errors/src/storage_layouts.rs
+//
+// Errors of `examples/demo-contracts/storage_layouts.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The storage layouts error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the instructor.
+    NotInstructor,
+    /// Returned if a layout already holds `MAX_LEARNERS` learners.
+    TooManyLearners,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotInstructor => f.write_str("the caller is not the instructor"),
+            Error::TooManyLearners => f.write_str("a layout already holds MAX_LEARNERS learners"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotInstructor => ContractError::Unauthorized,
+            Error::TooManyLearners => ContractError::LimitExceeded,
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// crates/errors/src/timed_quiz.rs
+//
+// Errors of `examples/demo-contracts/timed_quiz.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The timed quiz error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the quiz has no questions.
+    NoQuestions,
+    /// Returned if the deadline has already passed.
+    DeadlineInPast,
+    /// Returned if there is no quiz with the id.
+    QuizNotFound,
+    /// Returned if the quiz deadline has passed.
+    QuizClosed,
+    /// Returned if the quiz deadline has not passed yet.
+    QuizOpen,
+    /// Returned if the number of answers differs from the number of questions.
+    WrongAnswerCount,
+    /// Returned if the caller has already submitted answers to the quiz.
+    AlreadySubmitted,
+    /// Returned if the caller is not the quiz's author.
+    NotAuthor,
+    /// Returned if the answer key has already been revealed.
+    AlreadyRevealed,
+    /// Returned if the answer key and salt do not match the commitment.
+    KeyMismatch,
+    /// Returned if the answer key has not been revealed yet.
+    NotRevealed,
+    /// Returned if the learner has not submitted answers to the quiz.
+    NoSubmission,
+    /// Returned if the submission has already been scored.
+    AlreadyScored,
+    /// Returned if the reveal period of the quiz has passed.
+    RevealPeriodOver,
+    /// Returned if the quiz was abandoned unrevealed.
+    Abandoned,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoQuestions => f.write_str("the quiz has no questions"),
+            Error::DeadlineInPast => f.write_str("the deadline has already passed"),
+            Error::QuizNotFound => f.write_str("there is no quiz with the id"),
+            Error::QuizClosed => f.write_str("the quiz deadline has passed"),
+            Error::QuizOpen => f.write_str("the quiz deadline has not passed yet"),
+            Error::WrongAnswerCount => {
+                f.write_str("the number of answers differs from the number of questions")
+            }
+            Error::AlreadySubmitted => {
+                f.write_str("the caller has already submitted answers to the quiz")
+            }
+            Error::NotAuthor => f.write_str("the caller is not the quiz's author"),
+            Error::AlreadyRevealed => f.write_str("the answer key has already been revealed"),
+            Error::KeyMismatch => {
+                f.write_str("the answer key and salt do not match the commitment")
+            }
+            Error::NotRevealed => f.write_str("the answer key has not been revealed yet"),
+            Error::NoSubmission => f.write_str("the learner has not submitted answers to the quiz"),
+            Error::AlreadyScored => f.write_str("the submission has already been scored"),
+            Error::RevealPeriodOver => f.write_str("the reveal period of the quiz has passed"),
+            Error::Abandoned => f.write_str("the quiz was abandoned unrevealed"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NoQuestions => ContractError::InvalidArgument,
+            Error::DeadlineInPast => ContractError::InvalidArgument,
+            Error::QuizNotFound => ContractError::NotFound,
+            Error::QuizClosed => ContractError::TooLate,
+            Error::QuizOpen => ContractError::TooEarly,
+            Error::WrongAnswerCount => ContractError::InvalidArgument,
+            Error::AlreadySubmitted => ContractError::AlreadyExists,
+            Error::NotAuthor => ContractError::Unauthorized,
+            Error::AlreadyRevealed => ContractError::AlreadyExists,
+            Error::KeyMismatch => ContractError::InvalidSignature,
+            Error::NotRevealed => ContractError::TooEarly,
+            Error::NoSubmission => ContractError::NotFound,
+            Error::AlreadyScored => ContractError::AlreadyExists,
+            Error::RevealPeriodOver => ContractError::TooLate,
+            Error::Abandoned => ContractError::PreconditionFailed,
+        }
+    }
+}
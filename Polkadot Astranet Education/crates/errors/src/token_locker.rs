@@ -0,0 +1,50 @@
+// crates/errors/src/token_locker.rs
+//
+// Errors of `examples/demo-contracts/token_locker.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The token locker error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the amount to lock is zero.
+    ZeroAmount,
+    /// Returned if a new unlock time is not in the future, or an extension does not move
+    /// the unlock time later.
+    InvalidUnlockTime,
+    /// Returned if no lock has the given id.
+    LockNotFound,
+    /// Returned if the caller does not own the lock.
+    NotLockOwner,
+    /// Returned if the lock's unlock time has not been reached yet.
+    StillLocked,
+    /// Returned if the token refused to move the tokens.
+    TransferFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ZeroAmount => f.write_str("the amount to lock is zero"),
+            Error::InvalidUnlockTime => f.write_str("a new unlock time is not in the future, or an extension does not move the unlock time later"),
+            Error::LockNotFound => f.write_str("no lock has the given id"),
+            Error::NotLockOwner => f.write_str("the caller does not own the lock"),
+            Error::StillLocked => f.write_str("the lock's unlock time has not been reached yet"),
+            Error::TransferFailed => f.write_str("the token refused to move the tokens"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::InvalidUnlockTime => ContractError::InvalidArgument,
+            Error::LockNotFound => ContractError::NotFound,
+            Error::NotLockOwner => ContractError::Unauthorized,
+            Error::StillLocked => ContractError::TooEarly,
+            Error::TransferFailed => ContractError::TransferFailed,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+// crates/errors/src/treasury.rs
+//
+// Errors of `examples/demo-contracts/treasury.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The treasury error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the amount is zero.
+    ZeroAmount,
+    /// Returned if the caller is not the DAO.
+    NotDao,
+    /// Returned if no proposal has the given id.
+    ProposalNotFound,
+    /// Returned if the proposal was already executed.
+    AlreadyExecuted,
+    /// Returned if the DAO has not approved the proposal.
+    NotApproved,
+    /// Returned if the spend exceeds what is left of the asset's budget for the current
+    /// period.
+    BudgetExceeded,
+    /// Returned if the current spend period was already closed.
+    PeriodNotOver,
+    /// Returned if sending funds failed.
+    TransferFailed,
+    /// Returned if a description is longer than `MAX_DESCRIPTION_LEN`.
+    LimitExceeded,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ZeroAmount => f.write_str("the amount is zero"),
+            Error::NotDao => f.write_str("the caller is not the DAO"),
+            Error::ProposalNotFound => f.write_str("no proposal has the given id"),
+            Error::AlreadyExecuted => f.write_str("the proposal was already executed"),
+            Error::NotApproved => f.write_str("the DAO has not approved the proposal"),
+            Error::BudgetExceeded => f.write_str(
+                "the spend exceeds what is left of the asset's budget for the current period",
+            ),
+            Error::PeriodNotOver => f.write_str("the current spend period was already closed"),
+            Error::TransferFailed => f.write_str("sending funds failed"),
+            Error::LimitExceeded => f.write_str("a description is longer than MAX_DESCRIPTION_LEN"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::NotDao => ContractError::Unauthorized,
+            Error::ProposalNotFound => ContractError::NotFound,
+            Error::AlreadyExecuted => ContractError::AlreadyExists,
+            Error::NotApproved => ContractError::PreconditionFailed,
+            Error::BudgetExceeded => ContractError::LimitExceeded,
+            Error::PeriodNotOver => ContractError::TooEarly,
+            Error::TransferFailed => ContractError::TransferFailed,
+            Error::LimitExceeded => ContractError::LimitExceeded,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+// crates/errors/src/vesting.rs
+//
+// Errors of `examples/demo-contracts/vesting.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The vesting error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if nothing has vested since the last release.
+    NothingToRelease,
+    /// Returned if sending the tokens failed.
+    TransferFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NothingToRelease => f.write_str("nothing has vested since the last release"),
+            Error::TransferFailed => f.write_str("sending the tokens failed"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NothingToRelease => ContractError::NothingToClaim,
+            Error::TransferFailed => ContractError::TransferFailed,
+        }
+    }
+}
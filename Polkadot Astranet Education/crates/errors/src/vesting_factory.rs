@@ -0,0 +1,39 @@
+// crates/errors/src/vesting_factory.rs
+//
+// Errors of `examples/demo-contracts/vesting_factory.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The vesting factory error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if no value was transferred to vest.
+    ZeroAmount,
+    /// Returned if the schedule would be rejected by the child's constructor.
+    InvalidSchedule,
+    /// Returned if the node rejected the instantiation.
+    InstantiationFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ZeroAmount => f.write_str("no value was transferred to vest"),
+            Error::InvalidSchedule => {
+                f.write_str("the schedule would be rejected by the child's constructor")
+            }
+            Error::InstantiationFailed => f.write_str("the node rejected the instantiation"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::ZeroAmount => ContractError::ZeroAmount,
+            Error::InvalidSchedule => ContractError::InvalidArgument,
+            Error::InstantiationFailed => ContractError::CallFailed,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+// crates/errors/src/votes_token.rs
+//
+// Errors of `examples/demo-contracts/votes_token.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The votes token error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the balance is insufficient for the operation.
+    InsufficientBalance,
+    /// Returned if the allowance is insufficient for the operation.
+    InsufficientAllowance,
+    /// Returned if historical votes are requested for the current or a future block.
+    BlockNotYetMined,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InsufficientBalance => {
+                f.write_str("the balance is insufficient for the operation")
+            }
+            Error::InsufficientAllowance => {
+                f.write_str("the allowance is insufficient for the operation")
+            }
+            Error::BlockNotYetMined => {
+                f.write_str("historical votes are requested for the current or a future block")
+            }
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::InsufficientBalance => ContractError::InsufficientBalance,
+            Error::InsufficientAllowance => ContractError::InsufficientAllowance,
+            Error::BlockNotYetMined => ContractError::TooEarly,
+        }
+    }
+}
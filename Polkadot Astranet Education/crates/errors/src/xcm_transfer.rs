@@ -0,0 +1,41 @@
+// crates/errors/src/xcm_transfer.rs
+//
+// Errors of `examples/demo-contracts/xcm_transfer.rs`.
+
+use crate::ContractError;
+use std::fmt;
+
+/// The XCM transfer error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum Error {
+    /// Returned if the caller is not the contract owner.
+    NotOwner,
+    /// Returned if the amount does not cover the destination fees.
+    AmountBelowFee,
+    /// Returned if the local XCM program failed to execute.
+    ExecuteFailed,
+    /// Returned if the XCM message could not be delivered.
+    SendFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotOwner => f.write_str("the caller is not the contract owner"),
+            Error::AmountBelowFee => f.write_str("the amount does not cover the destination fees"),
+            Error::ExecuteFailed => f.write_str("the local XCM program failed to execute"),
+            Error::SendFailed => f.write_str("the XCM message could not be delivered"),
+        }
+    }
+}
+
+impl From<Error> for ContractError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NotOwner => ContractError::Unauthorized,
+            Error::AmountBelowFee => ContractError::WrongPayment,
+            Error::ExecuteFailed => ContractError::CallFailed,
+            Error::SendFailed => ContractError::TransferFailed,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// crates/errors/tests/mirrors.rs
+//
+// Fails when a module of this crate no longer matches its contract's error enums.
+//
+// Both sides are read with the scanner of `astranet-error-codes`, so variant names,
+// indices and field types must agree exactly.
+
+use astranet_error_codes::scan;
+use astranet_errors::CONTRACTS;
+use std::{fs, path::Path};
+
+#[test]
+fn mirrors_match_the_contracts() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut mirrored = Vec::new();
+    for dir in ["demo-contracts", "mocks"] {
+        let mut files: Vec<_> = fs::read_dir(root.join("../../examples").join(dir))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+            .collect();
+        files.sort();
+        for file in files {
+            let name = file.file_stem().unwrap().to_string_lossy().into_owned();
+            let expected = scan(&name, &fs::read_to_string(&file).unwrap()).unwrap();
+            if expected.is_empty() {
+                continue;
+            }
+            let mirror = root.join("src").join(format!("{}.rs", name));
+            let source = fs::read_to_string(&mirror)
+                .unwrap_or_else(|_| panic!("{} has errors but no {}", name, mirror.display()));
+            assert_eq!(
+                scan(&name, &source).unwrap(),
+                expected,
+                "{} differs from {}",
+                mirror.display(),
+                file.display()
+            );
+            mirrored.push(name);
+        }
+    }
+    mirrored.sort();
+    assert_eq!(mirrored, CONTRACTS);
+}
@@ -0,0 +1,47 @@
+// crates/errors/tests/stability.rs
+//
+// Fails when a `ContractError` code changes.
+//
+// Tools and front ends match on the numeric codes, so a code may be added but never
+// changed, removed or reused. Rerun with `UPDATE_GOLDEN=1` to append new kinds to the
+// golden file.
+
+use astranet_error_codes::compare;
+use astranet_errors::ContractError;
+use std::{env, fs, path::Path};
+
+const HEADER: &str =
+    "# Generated by `UPDATE_GOLDEN=1 cargo test`. Lines may be appended, never changed.\n";
+
+#[test]
+fn codes_are_stable() {
+    let current: Vec<String> = ContractError::ALL
+        .iter()
+        .map(|kind| format!("ContractError::{:?} = {}", kind, kind.code()))
+        .collect();
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/codes.txt");
+    let golden = fs::read_to_string(&path).unwrap_or_default();
+    let (breaking, added) = compare(&golden, &current);
+    assert!(
+        breaking.is_empty(),
+        "breaking changes to pinned error codes:\n  {}",
+        breaking.join("\n  ")
+    );
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, format!("{}{}\n", HEADER, current.join("\n"))).unwrap();
+        return;
+    }
+    assert!(
+        added.is_empty(),
+        "error codes not pinned yet (rerun with UPDATE_GOLDEN=1):\n  {}",
+        added.join("\n  ")
+    );
+}
+
+#[test]
+fn codes_are_unique() {
+    let mut codes: Vec<u16> = ContractError::ALL.iter().map(|kind| kind.code()).collect();
+    codes.sort_unstable();
+    codes.dedup();
+    assert_eq!(codes.len(), ContractError::ALL.len());
+}
@@ -0,0 +1,109 @@
+// crates/events/src/arbitration.rs
+//
+// Events of `examples/demo-contracts/arbitration.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Identifies a dispute.
+pub type DisputeId = u32;
+
+/// The party a dispute is decided for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Ruling {
+    Claimant,
+    Respondent,
+}
+
+/// Emitted when a juror stakes tokens.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Staked {
+    pub juror: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when a juror withdraws free stake.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Unstaked {
+    pub juror: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when a contract opens a dispute.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DisputeCreated {
+    pub dispute_id: DisputeId,
+    pub arbitrable: AccountId,
+    pub claimant: AccountId,
+    pub respondent: AccountId,
+    pub voting_ends: u64,
+}
+
+/// Emitted when a juror is drawn onto a dispute's panel.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct JurorDrawn {
+    pub dispute_id: DisputeId,
+    pub juror: AccountId,
+}
+
+/// Emitted when a juror votes.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Voted {
+    pub dispute_id: DisputeId,
+    pub juror: AccountId,
+    pub ruling: Ruling,
+}
+
+/// Emitted when a dispute is resolved.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DisputeResolved {
+    pub dispute_id: DisputeId,
+    pub ruling: Ruling,
+}
+
+/// Emitted when a juror's locked stake is released with a reward or a slash.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct JurorSettled {
+    pub dispute_id: DisputeId,
+    pub juror: AccountId,
+    pub reward: Balance,
+    pub slashed: Balance,
+}
+
+/// Every event of the arbitration contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Staked(Staked),
+    Unstaked(Unstaked),
+    DisputeCreated(DisputeCreated),
+    JurorDrawn(JurorDrawn),
+    Voted(Voted),
+    DisputeResolved(DisputeResolved),
+    JurorSettled(JurorSettled),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn voted_round_trips() {
+        let bytes = round_trip(&Event::Voted(Voted {
+            dispute_id: 7,
+            juror: AccountId::from([0x01; 32]),
+            ruling: Ruling::Respondent,
+        }));
+        assert_eq!(bytes[0], 4);
+        assert_eq!(bytes[bytes.len() - 1], 1);
+        assert_eq!(bytes.len(), 1 + 4 + 32 + 1);
+    }
+}
@@ -0,0 +1,64 @@
+// crates/events/src/badge.rs
+//
+// Events of `examples/demo-contracts/badge.rs`.
+
+use crate::{AccountId, Balance};
+
+/// The code of an event, which is also the id of its badge.
+pub type EventCode = u32;
+
+/// Emitted when an organizer creates an event.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct EventCreated {
+    pub code: EventCode,
+    pub organizer: AccountId,
+    pub signer: AccountId,
+}
+
+/// Emitted when an organizer rotates the signing key of an event.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SignerChanged {
+    pub code: EventCode,
+    pub signer: AccountId,
+}
+
+/// Emitted when badges are minted; `from` is always `None`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct TransferSingle {
+    pub operator: AccountId,
+    pub from: Option<AccountId>,
+    pub to: Option<AccountId>,
+    pub id: EventCode,
+    pub value: Balance,
+}
+
+/// Every event of the badge contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    EventCreated(EventCreated),
+    SignerChanged(SignerChanged),
+    TransferSingle(TransferSingle),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn transfer_single_round_trips() {
+        let bytes = round_trip(&Event::TransferSingle(TransferSingle {
+            operator: AccountId::from([0x01; 32]),
+            from: None,
+            to: Some(AccountId::from([0x01; 32])),
+            id: 2024,
+            value: 1,
+        }));
+        assert_eq!(bytes[0], 2);
+        assert_eq!(bytes.len(), 1 + 32 + 1 + (1 + 32) + 4 + 16);
+    }
+}
@@ -0,0 +1,68 @@
+// crates/events/src/cdp.rs
+//
+// Events of `examples/demo-contracts/cdp.rs`.
+
+use crate::{AccountId, Balance};
+
+pub use crate::erc20::{Approval, Transfer};
+
+/// Emitted when collateral is added to or taken from `owner`'s position.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct CollateralChanged {
+    pub owner: AccountId,
+    pub deposited: Balance,
+    pub withdrawn: Balance,
+}
+
+/// Emitted when the debt of `owner`'s position grows by minting or shrinks by
+/// repaying.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DebtChanged {
+    pub owner: AccountId,
+    pub minted: Balance,
+    pub repaid: Balance,
+}
+
+/// Emitted when `liquidator` burns the `debt` of `owner`'s position and receives
+/// `collateral` from it.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Liquidated {
+    pub owner: AccountId,
+    pub liquidator: AccountId,
+    pub debt: Balance,
+    pub collateral: Balance,
+}
+
+/// Every event of the CDP contract, in declaration order.
+///
+/// The first two events share their layout with the ERC-20 ones.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Transfer(Transfer),
+    Approval(Approval),
+    CollateralChanged(CollateralChanged),
+    DebtChanged(DebtChanged),
+    Liquidated(Liquidated),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn liquidated_round_trips() {
+        let bytes = round_trip(&Event::Liquidated(Liquidated {
+            owner: AccountId::from([0x01; 32]),
+            liquidator: AccountId::from([0x02; 32]),
+            debt: 400,
+            collateral: 293,
+        }));
+        assert_eq!(bytes[0], 4);
+        assert_eq!(bytes.len(), 1 + 32 + 32 + 16 + 16);
+    }
+}
@@ -0,0 +1,60 @@
+// crates/events/src/config_store.rs
+//
+// Events of `examples/demo-contracts/config_store.rs`.
+
+use crate::AccountId;
+use ink_prelude::vec::Vec;
+
+/// A parameter value.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Value {
+    U128(u128),
+    Bool(bool),
+    Account(AccountId),
+    Bytes(Vec<u8>),
+}
+
+/// Emitted when the admin grants (`granted`) or revokes the writer role.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct WriterChanged {
+    pub account: AccountId,
+    pub granted: bool,
+}
+
+/// Emitted when `key` gets its version `version`, counting from 1.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ValueSet {
+    pub key: Vec<u8>,
+    pub version: u32,
+    pub value: Value,
+    pub set_by: AccountId,
+}
+
+/// Every event of the config store contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    WriterChanged(WriterChanged),
+    ValueSet(ValueSet),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn value_set_round_trips() {
+        let bytes = round_trip(&Event::ValueSet(ValueSet {
+            key: b"flashloan.fee_bps".to_vec(),
+            version: 2,
+            value: Value::U128(5),
+            set_by: AccountId::from([0x01; 32]),
+        }));
+        assert_eq!(bytes[0], 1);
+        assert_eq!(bytes.len(), 1 + (1 + 17) + 4 + (1 + 16) + 32);
+    }
+}
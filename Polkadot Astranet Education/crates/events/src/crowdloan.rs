@@ -0,0 +1,58 @@
+// crates/events/src/crowdloan.rs
+//
+// Events of `examples/demo-contracts/crowdloan.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when someone contributes, with the reward it earned and the new total.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Contributed {
+    pub contributor: AccountId,
+    pub amount: Balance,
+    pub reward: Balance,
+    pub raised: Balance,
+}
+
+/// Emitted when a contributor claims their reward tokens.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RewardClaimed {
+    pub contributor: AccountId,
+    pub reward: Balance,
+}
+
+/// Emitted when a contributor takes back their contribution after the lease.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Refunded {
+    pub contributor: AccountId,
+    pub amount: Balance,
+}
+
+/// Every event of the crowdloan contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Contributed(Contributed),
+    RewardClaimed(RewardClaimed),
+    Refunded(Refunded),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn contributed_round_trips() {
+        let bytes = round_trip(&Event::Contributed(Contributed {
+            contributor: AccountId::from([0x02; 32]),
+            amount: 500,
+            reward: 687,
+            raised: 1_000,
+        }));
+        assert_eq!(bytes[0], 0);
+        assert_eq!(bytes.len(), 1 + 32 + 16 * 3);
+    }
+}
@@ -0,0 +1,57 @@
+// crates/events/src/dividend_token.rs
+//
+// Events of `examples/demo-contracts/dividend_token.rs`.
+
+use crate::{AccountId, Balance};
+
+pub use crate::erc20::{Approval, Transfer};
+
+/// Emitted when `from` deposits `value` native tokens for the holders.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DividendsDistributed {
+    pub from: AccountId,
+    pub value: Balance,
+}
+
+/// Emitted when a holder claims `value` native tokens of dividends.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DividendClaimed {
+    pub to: AccountId,
+    pub value: Balance,
+}
+
+/// Every event of the dividend token contract, in declaration order.
+///
+/// The first two events share their layout with the ERC-20 ones.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Transfer(Transfer),
+    Approval(Approval),
+    DividendsDistributed(DividendsDistributed),
+    DividendClaimed(DividendClaimed),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn dividend_events_round_trip() {
+        let bytes = round_trip(&Event::DividendsDistributed(DividendsDistributed {
+            from: AccountId::from([1; 32]),
+            value: 1_000,
+        }));
+        assert_eq!(bytes[0], 2);
+
+        let bytes = round_trip(&Event::DividendClaimed(DividendClaimed {
+            to: AccountId::from([2; 32]),
+            value: 400,
+        }));
+        assert_eq!(bytes[0], 3);
+        assert_eq!(bytes.len(), 1 + 32 + 16);
+    }
+}
@@ -0,0 +1,72 @@
+// crates/events/src/donation.rs
+//
+// Events of `examples/demo-contracts/donation.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when the sponsor adds `amount` to the matching pool.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct MatchingFunded {
+    pub amount: Balance,
+    pub pool: Balance,
+}
+
+/// Emitted for every donation.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Donated {
+    pub donor: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when a donation is matched, with what is left of the pool afterwards.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Matched {
+    pub donor: AccountId,
+    pub amount: Balance,
+    pub pool: Balance,
+}
+
+/// Emitted when the beneficiary withdraws what has been raised.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Withdrawn {
+    pub amount: Balance,
+}
+
+/// Emitted when the sponsor reclaims the unused matching pool.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct MatchingReclaimed {
+    pub amount: Balance,
+}
+
+/// Every event of the donation contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    MatchingFunded(MatchingFunded),
+    Donated(Donated),
+    Matched(Matched),
+    Withdrawn(Withdrawn),
+    MatchingReclaimed(MatchingReclaimed),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn matched_round_trips() {
+        let bytes = round_trip(&Event::Matched(Matched {
+            donor: AccountId::from([0x01; 32]),
+            amount: 20,
+            pool: 0,
+        }));
+        assert_eq!(bytes[0], 2);
+        assert_eq!(bytes.len(), 1 + 32 + 16 + 16);
+    }
+}
@@ -0,0 +1,69 @@
+// crates/events/src/enrollment.rs
+//
+// Events of `examples/demo-contracts/enrollment.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when an instructor creates a course.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct CourseCreated {
+    pub course_id: u32,
+    pub instructor: AccountId,
+    pub fee: Balance,
+    pub refund_deadline: u64,
+}
+
+/// Emitted when a learner enrolls.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Enrolled {
+    pub course_id: u32,
+    pub learner: AccountId,
+    pub fee: Balance,
+}
+
+/// Emitted when a learner refunds their enrollment.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Refunded {
+    pub course_id: u32,
+    pub learner: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when the instructor withdraws the fees of a course.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct FeesWithdrawn {
+    pub course_id: u32,
+    pub amount: Balance,
+}
+
+/// Every event of the enrollment contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    CourseCreated(CourseCreated),
+    Enrolled(Enrolled),
+    Refunded(Refunded),
+    FeesWithdrawn(FeesWithdrawn),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn course_created_round_trips() {
+        let bytes = round_trip(&Event::CourseCreated(CourseCreated {
+            course_id: 0,
+            instructor: AccountId::from([0x01; 32]),
+            fee: 50,
+            refund_deadline: 604_800_000,
+        }));
+        assert_eq!(bytes[0], 0);
+        assert_eq!(bytes.len(), 1 + 4 + 32 + 16 + 8);
+    }
+}
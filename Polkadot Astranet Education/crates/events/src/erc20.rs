@@ -0,0 +1,112 @@
+// crates/events/src/erc20.rs
+//
+// Events of `examples/demo-contracts/erc20.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when tokens move; `from` is `None` on mint, `to` is `None` on burn.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Transfer {
+    pub from: Option<AccountId>,
+    pub to: Option<AccountId>,
+    pub value: Balance,
+}
+
+/// Emitted when `owner` lets `spender` withdraw up to `value`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Approval {
+    pub owner: AccountId,
+    pub spender: AccountId,
+    pub value: Balance,
+}
+
+/// Emitted when the owner adds `account` to the allowlist or removes it.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AllowlistUpdated {
+    pub account: AccountId,
+    pub allowed: bool,
+}
+
+/// Emitted when the owner turns restricted mode on or off.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RestrictedModeSet {
+    pub enabled: bool,
+}
+
+/// Emitted after `Approval` when the allowance can only be spent until `deadline`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AllowanceExpirySet {
+    pub owner: AccountId,
+    pub spender: AccountId,
+    pub deadline: u64,
+}
+
+/// Every event of the ERC-20 contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Transfer(Transfer),
+    Approval(Approval),
+    AllowlistUpdated(AllowlistUpdated),
+    RestrictedModeSet(RestrictedModeSet),
+    AllowanceExpirySet(AllowanceExpirySet),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn transfer_round_trips() {
+        let bytes = round_trip(&Event::Transfer(Transfer {
+            from: None,
+            to: Some(AccountId::from([1; 32])),
+            value: 100,
+        }));
+        // event index, `None`, `Some` + account, u128 value
+        assert_eq!(bytes.len(), 1 + 1 + 33 + 16);
+        assert_eq!(&bytes[..3], &[0, 0, 1]);
+    }
+
+    #[test]
+    fn approval_round_trips() {
+        let bytes = round_trip(&Event::Approval(Approval {
+            owner: AccountId::from([1; 32]),
+            spender: AccountId::from([2; 32]),
+            value: 7,
+        }));
+        assert_eq!(bytes[0], 1);
+    }
+
+    #[test]
+    fn restriction_events_follow_approval() {
+        let bytes = round_trip(&Event::AllowlistUpdated(AllowlistUpdated {
+            account: AccountId::from([1; 32]),
+            allowed: true,
+        }));
+        assert_eq!(bytes[0], 2);
+        assert_eq!(bytes[33], 1);
+
+        let bytes = round_trip(&Event::RestrictedModeSet(RestrictedModeSet {
+            enabled: false,
+        }));
+        assert_eq!(bytes, [3, 0]);
+    }
+
+    #[test]
+    fn allowance_expiry_set_round_trips() {
+        let bytes = round_trip(&Event::AllowanceExpirySet(AllowanceExpirySet {
+            owner: AccountId::from([1; 32]),
+            spender: AccountId::from([2; 32]),
+            deadline: 200,
+        }));
+        assert_eq!(bytes[0], 4);
+        assert_eq!(bytes.len(), 1 + 32 + 32 + 8);
+    }
+}
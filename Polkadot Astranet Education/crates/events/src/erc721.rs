@@ -0,0 +1,71 @@
+// crates/events/src/erc721.rs
+//
+// Events of `examples/demo-contracts/erc721.rs`.
+
+use crate::AccountId;
+
+/// Identifies a token.
+pub type TokenId = u32;
+
+/// Emitted when a token moves; `from` is `None` on mint, `to` is `None` on burn.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Transfer {
+    pub from: Option<AccountId>,
+    pub to: Option<AccountId>,
+    pub id: TokenId,
+}
+
+/// Emitted when `owner` lets `approved` move token `id`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Approval {
+    pub owner: AccountId,
+    pub approved: AccountId,
+    pub id: TokenId,
+}
+
+/// Emitted when an operator is approved or revoked without an expiry.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ApprovalForAll {
+    pub owner: AccountId,
+    pub operator: AccountId,
+    pub approved: bool,
+}
+
+/// Emitted when an operator is approved until `expiry`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct OperatorApprovedUntil {
+    pub owner: AccountId,
+    pub operator: AccountId,
+    pub expiry: u64,
+}
+
+/// Every event of the ERC-721 contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Transfer(Transfer),
+    Approval(Approval),
+    ApprovalForAll(ApprovalForAll),
+    OperatorApprovedUntil(OperatorApprovedUntil),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn operator_approved_until_round_trips() {
+        let bytes = round_trip(&Event::OperatorApprovedUntil(OperatorApprovedUntil {
+            owner: AccountId::from([0x01; 32]),
+            operator: AccountId::from([0x02; 32]),
+            expiry: 200,
+        }));
+        assert_eq!(bytes[0], 3);
+        assert_eq!(bytes.len(), 1 + 32 + 32 + 8);
+    }
+}
@@ -0,0 +1,143 @@
+// crates/events/src/events_showcase.rs
+//
+// Events of `examples/demo-contracts/events_showcase.rs`.
+//
+// `ScoreRecorded` carries a versioned payload: events emitted by version 1 of the
+// contract hold `ScorePayload::V1`, later ones `ScorePayload::V2`, and both decode
+// with the same `Event` type.
+
+use crate::AccountId;
+
+/// A recorded score, one variant per version of the event's payload.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ScorePayload {
+    /// Emitted by version 1: the score alone.
+    V1 { score: u32 },
+    /// Emitted since version 2: the score out of `max_score`, on the learner's
+    /// `attempt`-th try.
+    V2 {
+        score: u32,
+        max_score: u32,
+        attempt: u8,
+    },
+}
+
+impl ScorePayload {
+    /// Returns the score, whatever the version.
+    pub fn score(&self) -> u32 {
+        match self {
+            ScorePayload::V1 { score } | ScorePayload::V2 { score, .. } => *score,
+        }
+    }
+}
+
+/// Emitted when `learner` joins `cohort` at block `registered_at`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Registered {
+    pub learner: AccountId,
+    pub cohort: u32,
+    pub registered_at: u32,
+}
+
+/// Emitted when `author` posts a note.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct NotePosted {
+    pub author: AccountId,
+    pub note: ink_prelude::string::String,
+}
+
+/// Emitted, without a signature topic, on every ping; `count` is the number of
+/// pings so far.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Pinged {
+    pub from: AccountId,
+    pub count: u64,
+}
+
+/// Emitted when a score is recorded for `learner`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ScoreRecorded {
+    pub learner: AccountId,
+    pub payload: ScorePayload,
+}
+
+/// Every event of the events showcase contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Registered(Registered),
+    NotePosted(NotePosted),
+    Pinged(Pinged),
+    ScoreRecorded(ScoreRecorded),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, round_trip};
+
+    #[test]
+    fn registered_round_trips() {
+        let bytes = round_trip(&Event::Registered(Registered {
+            learner: AccountId::from([0x01; 32]),
+            cohort: 3,
+            registered_at: 12,
+        }));
+        assert_eq!(bytes[0], 0);
+        assert_eq!(bytes.len(), 1 + 32 + 4 + 4);
+    }
+
+    #[test]
+    fn version_1_scores_decode() {
+        // Event index 3, the learner, then variant 0 and the score.
+        let mut data = vec![3];
+        data.extend_from_slice(&[0x01; 32]);
+        data.push(0);
+        data.extend_from_slice(&7u32.to_le_bytes());
+
+        match decode::<Event>(&data).unwrap() {
+            Event::ScoreRecorded(recorded) => {
+                assert_eq!(recorded.payload, ScorePayload::V1 { score: 7 });
+                assert_eq!(recorded.payload.score(), 7);
+            }
+            event => panic!("expected ScoreRecorded, got {:?}", event),
+        }
+    }
+
+    #[test]
+    fn version_2_scores_decode() {
+        let mut data = vec![3];
+        data.extend_from_slice(&[0x01; 32]);
+        data.push(1);
+        data.extend_from_slice(&9u32.to_le_bytes());
+        data.extend_from_slice(&10u32.to_le_bytes());
+        data.push(2);
+
+        let event = decode::<Event>(&data).unwrap();
+        assert_eq!(
+            event,
+            Event::ScoreRecorded(ScoreRecorded {
+                learner: AccountId::from([0x01; 32]),
+                payload: ScorePayload::V2 {
+                    score: 9,
+                    max_score: 10,
+                    attempt: 2,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_payload_versions_are_rejected() {
+        let mut data = vec![3];
+        data.extend_from_slice(&[0x01; 32]);
+        data.push(2);
+        data.extend_from_slice(&9u32.to_le_bytes());
+        assert!(decode::<Event>(&data).is_err());
+    }
+}
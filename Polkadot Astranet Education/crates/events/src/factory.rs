@@ -0,0 +1,35 @@
+// crates/events/src/factory.rs
+//
+// Events of `examples/demo-contracts/factory.rs`.
+//
+// The contract is written for ink! 5, so `data` is the event struct itself.
+
+use crate::AccountId;
+use ink_prelude::vec::Vec;
+
+/// Emitted when the factory deploys a child contract.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ChildDeployed {
+    pub deployer: AccountId,
+    pub child: AccountId,
+    pub salt: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn child_deployed_round_trips() {
+        let bytes = round_trip(&ChildDeployed {
+            deployer: AccountId::from([1; 32]),
+            child: AccountId::from([2; 32]),
+            salt: b"lesson-7".to_vec(),
+        });
+        // two accounts, compact length 8, salt
+        assert_eq!(bytes.len(), 32 + 32 + 1 + 8);
+        assert_eq!(bytes[64], 8 << 2);
+    }
+}
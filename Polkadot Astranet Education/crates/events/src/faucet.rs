@@ -0,0 +1,67 @@
+// crates/events/src/faucet.rs
+//
+// Events of `examples/demo-contracts/faucet.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when the faucet sends `value` to `to`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Dripped {
+    pub to: AccountId,
+    pub value: Balance,
+}
+
+/// Emitted when the owner proposes a new owner.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct OwnershipTransferStarted {
+    pub previous_owner: AccountId,
+    pub new_owner: AccountId,
+}
+
+/// Emitted when the proposed owner accepts ownership.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct OwnershipTransferCompleted {
+    pub previous_owner: AccountId,
+    pub new_owner: AccountId,
+}
+
+/// Every event of the faucet contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Dripped(Dripped),
+    OwnershipTransferStarted(OwnershipTransferStarted),
+    OwnershipTransferCompleted(OwnershipTransferCompleted),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn every_event_round_trips() {
+        let alice = AccountId::from([1; 32]);
+        let bob = AccountId::from([2; 32]);
+        let events = [
+            Event::Dripped(Dripped {
+                to: alice,
+                value: 10,
+            }),
+            Event::OwnershipTransferStarted(OwnershipTransferStarted {
+                previous_owner: alice,
+                new_owner: bob,
+            }),
+            Event::OwnershipTransferCompleted(OwnershipTransferCompleted {
+                previous_owner: alice,
+                new_owner: bob,
+            }),
+        ];
+        for (index, event) in events.iter().enumerate() {
+            assert_eq!(round_trip(event)[0], index as u8);
+        }
+    }
+}
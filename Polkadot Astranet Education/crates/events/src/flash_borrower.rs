@@ -0,0 +1,38 @@
+// crates/events/src/flash_borrower.rs
+//
+// Events of `examples/demo-contracts/flash_borrower.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when the borrower receives a loan of `amount` of `token`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct LoanReceived {
+    pub token: AccountId,
+    pub amount: Balance,
+    pub fee: Balance,
+}
+
+/// Every event of the flash borrower contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    LoanReceived(LoanReceived),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn loan_received_round_trips() {
+        let bytes = round_trip(&Event::LoanReceived(LoanReceived {
+            token: AccountId::from([0x7a; 32]),
+            amount: 1_000_000,
+            fee: 900,
+        }));
+        assert_eq!(bytes[0], 0);
+        assert_eq!(bytes.len(), 1 + 32 + 16 + 16);
+    }
+}
@@ -0,0 +1,49 @@
+// crates/events/src/flashloan_pool.rs
+//
+// Events of `examples/demo-contracts/flashloan_pool.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when the owner adds or removes reserves.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct LiquidityChanged {
+    pub added: Balance,
+    pub removed: Balance,
+}
+
+/// Emitted when a flash loan to `receiver`, started by `initiator`, has been repaid.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct FlashLoan {
+    pub receiver: AccountId,
+    pub initiator: AccountId,
+    pub amount: Balance,
+    pub fee: Balance,
+}
+
+/// Every event of the flash loan pool contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    LiquidityChanged(LiquidityChanged),
+    FlashLoan(FlashLoan),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn flash_loan_round_trips() {
+        let bytes = round_trip(&Event::FlashLoan(FlashLoan {
+            receiver: AccountId::from([0x01; 32]),
+            initiator: AccountId::from([0x02; 32]),
+            amount: 1_000_000,
+            fee: 900,
+        }));
+        assert_eq!(bytes[0], 1);
+        assert_eq!(bytes.len(), 1 + 32 + 32 + 16 + 16);
+    }
+}
@@ -0,0 +1,40 @@
+// crates/events/src/forwarder.rs
+//
+// Events of `examples/demo-contracts/forwarder.rs`.
+
+use crate::AccountId;
+
+/// Emitted when a relayer executes a signed request.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Forwarded {
+    pub from: AccountId,
+    pub to: AccountId,
+    pub nonce: u64,
+    pub relayer: AccountId,
+}
+
+/// Every event of the forwarder contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Forwarded(Forwarded),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn forwarded_round_trips() {
+        let bytes = round_trip(&Event::Forwarded(Forwarded {
+            from: AccountId::from([1; 32]),
+            to: AccountId::from([2; 32]),
+            nonce: 3,
+            relayer: AccountId::from([4; 32]),
+        }));
+        // The nonce is a fixed-width u64, not compact.
+        assert_eq!(&bytes[65..73], &3u64.to_le_bytes());
+    }
+}
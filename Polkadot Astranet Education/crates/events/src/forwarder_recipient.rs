@@ -0,0 +1,36 @@
+// crates/events/src/forwarder_recipient.rs
+//
+// Events of `examples/demo-contracts/forwarder_recipient.rs`.
+
+use crate::AccountId;
+
+/// Emitted when an author sets their note, directly or through the forwarder.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct NoteSet {
+    pub author: AccountId,
+    pub relayed: bool,
+}
+
+/// Every event of the forwarder recipient contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    NoteSet(NoteSet),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn note_set_round_trips() {
+        let bytes = round_trip(&Event::NoteSet(NoteSet {
+            author: AccountId::from([1; 32]),
+            relayed: true,
+        }));
+        assert_eq!(bytes.len(), 1 + 32 + 1);
+        assert_eq!(bytes[33], 1);
+    }
+}
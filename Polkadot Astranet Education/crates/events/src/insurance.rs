@@ -0,0 +1,88 @@
+// crates/events/src/insurance.rs
+//
+// Events of `examples/demo-contracts/insurance.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Identifies a policy.
+pub type PolicyId = u32;
+
+/// Emitted when capital is added to the pool.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Funded {
+    pub funder: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when a policy is bought.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct PolicyBought {
+    pub policy_id: PolicyId,
+    pub holder: AccountId,
+    pub coverage: Balance,
+    pub premium: Balance,
+    pub expires_at: u64,
+}
+
+/// Emitted when a policyholder files a claim.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ClaimFiled {
+    pub policy_id: PolicyId,
+    pub observed: Balance,
+    pub amount: Balance,
+}
+
+/// Emitted when the assessor approves or rejects a claim.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ClaimAssessed {
+    pub policy_id: PolicyId,
+    pub approved: bool,
+    pub amount: Balance,
+}
+
+/// Emitted when an expired policy releases its coverage.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct PolicyExpired {
+    pub policy_id: PolicyId,
+}
+
+/// Emitted when the admin withdraws unlocked capital.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Withdrawn {
+    pub amount: Balance,
+}
+
+/// Every event of the insurance contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Funded(Funded),
+    PolicyBought(PolicyBought),
+    ClaimFiled(ClaimFiled),
+    ClaimAssessed(ClaimAssessed),
+    PolicyExpired(PolicyExpired),
+    Withdrawn(Withdrawn),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn claim_assessed_round_trips() {
+        let bytes = round_trip(&Event::ClaimAssessed(ClaimAssessed {
+            policy_id: 0,
+            approved: true,
+            amount: 300,
+        }));
+        assert_eq!(bytes[0], 3);
+        assert_eq!(bytes.len(), 1 + 4 + 1 + 16);
+    }
+}
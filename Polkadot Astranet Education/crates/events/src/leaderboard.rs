@@ -0,0 +1,52 @@
+// crates/events/src/leaderboard.rs
+//
+// Events of `examples/demo-contracts/leaderboard.rs`.
+
+use crate::AccountId;
+
+/// Emitted when a learner's best score improves; `rank` counts from 1 and is `None`
+/// off the board.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ScoreRecorded {
+    pub learner: AccountId,
+    pub score: u32,
+    pub rank: Option<u32>,
+}
+
+/// Emitted when the admin rotates the grader's key.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct GraderChanged {
+    pub grader: AccountId,
+}
+
+/// Every event of the leaderboard contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    ScoreRecorded(ScoreRecorded),
+    GraderChanged(GraderChanged),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn score_recorded_round_trips() {
+        let on_board = round_trip(&Event::ScoreRecorded(ScoreRecorded {
+            learner: AccountId::from([0x01; 32]),
+            score: 80,
+            rank: Some(3),
+        }));
+        assert_eq!(on_board.len(), 1 + 32 + 4 + 1 + 4);
+        let off_board = round_trip(&Event::ScoreRecorded(ScoreRecorded {
+            learner: AccountId::from([0x01; 32]),
+            score: 10,
+            rank: None,
+        }));
+        assert_eq!(off_board.len(), 1 + 32 + 4 + 1);
+    }
+}
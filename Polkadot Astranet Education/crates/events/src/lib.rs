@@ -0,0 +1,120 @@
+// crates/events/src/lib.rs
+//
+// The events of every example contract, as plain SCALE types.
+//
+// When an ink! 3 contract emits an event, `pallet-contracts` deposits a
+// `Contracts::ContractEmitted { contract, data }` event whose `data` is the SCALE
+// encoding of the contract's event enum: one byte with the index of the event in
+// declaration order, followed by the event's fields in declaration order. Topics are
+// stored separately and are not part of `data`.
+//
+// ink! 3 requires events to be declared inside the `#[ink::contract]` module, so the
+// contracts cannot import these types. Instead, each module here mirrors one
+// contract's events field for field, with an `Event` enum listing them in the same
+// order, and off-chain code decodes `data` with it:
+//
+//     let event: astranet_events::erc20::Event = astranet_events::decode(&data)?;
+//
+// Adding, removing or reordering events or fields in a contract changes the encoding,
+// so the matching module here must change in the same commit.
+//
+// The few ink! 5 contracts (`factory`, `runtime_call` and `xcm_transfer`; their
+// headers say why) encode events differently: `data` is the event struct alone, with
+// no index in front, and the event is told apart by its first topic, a hash of its
+// signature. Their modules have no `Event` enum; decode `data` into the struct the
+// topic names instead:
+//
+//     let event: astranet_events::runtime_call::RuntimeTransfer =
+//         astranet_events::decode(&data)?;
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod arbitration;
+pub mod badge;
+pub mod cdp;
+pub mod config_store;
+pub mod crowdloan;
+pub mod dividend_token;
+pub mod donation;
+pub mod enrollment;
+pub mod erc20;
+pub mod erc721;
+pub mod events_showcase;
+pub mod factory;
+pub mod faucet;
+pub mod flash_borrower;
+pub mod flashloan_pool;
+pub mod forwarder;
+pub mod forwarder_recipient;
+pub mod insurance;
+pub mod leaderboard;
+pub mod limited_token;
+pub mod marketplace;
+pub mod migrate_v2;
+pub mod multisig;
+pub mod nomination;
+pub mod orderbook;
+pub mod payroll;
+pub mod peer_review;
+pub mod prediction;
+pub mod progress;
+pub mod quadratic_funding;
+pub mod rebase_token;
+pub mod recovery_wallet;
+pub mod referral;
+pub mod revenue_share;
+pub mod runtime_call;
+pub mod scholarship;
+pub mod spending_manager;
+pub mod staking;
+pub mod timed_quiz;
+pub mod token_locker;
+pub mod treasury;
+pub mod vesting;
+pub mod vesting_factory;
+pub mod votes_token;
+pub mod xcm_transfer;
+
+pub use ink_env::{AccountId, Hash};
+
+/// Balances as used by the default contracts environment.
+pub type Balance = u128;
+
+/// Decodes a `ContractEmitted` payload into the contract's `Event` enum.
+///
+/// Fails if the payload does not decode or has bytes left over, which usually means
+/// the payload came from a different contract or the types are out of date.
+pub fn decode<E: scale::Decode>(data: &[u8]) -> Result<E, scale::Error> {
+    scale::DecodeAll::decode_all(&mut &data[..])
+}
+
+#[cfg(test)]
+fn round_trip<E>(event: &E) -> ink_prelude::vec::Vec<u8>
+where
+    E: scale::Encode + scale::Decode + PartialEq + core::fmt::Debug,
+{
+    let bytes = event.encode();
+    assert_eq!(decode::<E>(&bytes).as_ref(), Ok(event));
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trailing_bytes_are_rejected() {
+        let mut data = scale::Encode::encode(&erc20::Event::Approval(erc20::Approval {
+            owner: AccountId::from([1; 32]),
+            spender: AccountId::from([2; 32]),
+            value: 5,
+        }));
+        data.push(0);
+        assert!(decode::<erc20::Event>(&data).is_err());
+    }
+
+    #[test]
+    fn unknown_event_index_is_rejected() {
+        assert!(decode::<faucet::Event>(&[3]).is_err());
+    }
+}
@@ -0,0 +1,42 @@
+// crates/events/src/limited_token.rs
+//
+// Events of `examples/demo-contracts/limited_token.rs`.
+
+use crate::Balance;
+
+pub use crate::erc20::{Approval, Transfer};
+
+/// Emitted when the owner changes the per-transfer and 24-hour caps.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct LimitsChanged {
+    pub per_transfer_limit: Balance,
+    pub daily_limit: Balance,
+}
+
+/// Every event of the limited token contract, in declaration order.
+///
+/// The first two events share their layout with the ERC-20 ones.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Transfer(Transfer),
+    Approval(Approval),
+    LimitsChanged(LimitsChanged),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn limits_changed_round_trips() {
+        let bytes = round_trip(&Event::LimitsChanged(LimitsChanged {
+            per_transfer_limit: 10,
+            daily_limit: 25,
+        }));
+        assert_eq!(bytes[0], 2);
+        assert_eq!(bytes.len(), 1 + 16 + 16);
+    }
+}
@@ -0,0 +1,73 @@
+// crates/events/src/marketplace.rs
+//
+// Events of `examples/demo-contracts/marketplace.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Identifies a listing.
+pub type ListingId = u32;
+
+/// Identifies a token within an ERC-721 contract.
+pub type TokenId = u32;
+
+/// Emitted when `seller` lists token `token_id` of `nft` at `price`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Listed {
+    pub listing_id: ListingId,
+    pub seller: AccountId,
+    pub nft: AccountId,
+    pub token_id: TokenId,
+    pub price: Balance,
+}
+
+/// Emitted when a seller takes a listing down.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Cancelled {
+    pub listing_id: ListingId,
+}
+
+/// Emitted when `buyer` buys a listed token for `price`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Sold {
+    pub listing_id: ListingId,
+    pub buyer: AccountId,
+    pub price: Balance,
+}
+
+/// Emitted when a seller withdraws their proceeds.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ProceedsWithdrawn {
+    pub seller: AccountId,
+    pub amount: Balance,
+}
+
+/// Every event of the marketplace contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Listed(Listed),
+    Cancelled(Cancelled),
+    Sold(Sold),
+    ProceedsWithdrawn(ProceedsWithdrawn),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn sold_round_trips() {
+        let bytes = round_trip(&Event::Sold(Sold {
+            listing_id: 3,
+            buyer: AccountId::from([0x01; 32]),
+            price: 100,
+        }));
+        assert_eq!(bytes[0], 2);
+        assert_eq!(bytes.len(), 1 + 4 + 32 + 16);
+    }
+}
@@ -0,0 +1,35 @@
+// crates/events/src/migrate_v2.rs
+//
+// Events of `examples/demo-contracts/migrate_v2.rs`. Version 1 emits no events.
+
+/// Emitted after each migration batch.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Migrated {
+    /// Number of entries moved in this batch.
+    pub moved: u32,
+    /// Number of learners in v1's index still to visit.
+    pub remaining: u32,
+}
+
+/// Every event of the score registry v2 contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Migrated(Migrated),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn migrated_round_trips() {
+        let bytes = round_trip(&Event::Migrated(Migrated {
+            moved: 2,
+            remaining: 1,
+        }));
+        assert_eq!(bytes, vec![0, 2, 0, 0, 0, 1, 0, 0, 0]);
+    }
+}
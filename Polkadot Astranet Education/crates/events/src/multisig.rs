@@ -0,0 +1,93 @@
+// crates/events/src/multisig.rs
+//
+// Events of `examples/demo-contracts/multisig.rs`.
+
+use crate::AccountId;
+
+/// Identifies a submitted transaction.
+pub type TransactionId = u64;
+
+/// Emitted when an owner submits a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Submitted {
+    pub transaction_id: TransactionId,
+    pub owner: AccountId,
+}
+
+/// Emitted when an owner confirms a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Confirmed {
+    pub transaction_id: TransactionId,
+    pub owner: AccountId,
+}
+
+/// Emitted when an owner takes back their confirmation.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Revoked {
+    pub transaction_id: TransactionId,
+    pub owner: AccountId,
+}
+
+/// Emitted when a confirmed transaction is executed.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Executed {
+    pub transaction_id: TransactionId,
+    pub success: bool,
+}
+
+/// Emitted when the wallet enables a module.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ModuleEnabled {
+    pub module: AccountId,
+}
+
+/// Emitted when the wallet disables a module.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ModuleDisabled {
+    pub module: AccountId,
+}
+
+/// Emitted when a module executes a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ExecutedFromModule {
+    pub module: AccountId,
+    pub callee: AccountId,
+    pub success: bool,
+}
+
+/// Every event of the multisig contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Submitted(Submitted),
+    Confirmed(Confirmed),
+    Revoked(Revoked),
+    Executed(Executed),
+    ModuleEnabled(ModuleEnabled),
+    ModuleDisabled(ModuleDisabled),
+    ExecutedFromModule(ExecutedFromModule),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn executed_from_module_round_trips() {
+        let bytes = round_trip(&Event::ExecutedFromModule(ExecutedFromModule {
+            module: AccountId::from([0x80; 32]),
+            callee: AccountId::from([0x70; 32]),
+            success: true,
+        }));
+        assert_eq!(bytes[0], 6);
+        assert_eq!(bytes.len(), 1 + 32 + 32 + 1);
+    }
+}
@@ -0,0 +1,96 @@
+// crates/events/src/nomination.rs
+//
+// Events of `examples/demo-contracts/nomination.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when an operator registers with a commission in basis points.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct OperatorRegistered {
+    pub operator: AccountId,
+    pub commission_bps: u128,
+}
+
+/// Emitted when an operator changes their commission for later distributions.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct CommissionChanged {
+    pub operator: AccountId,
+    pub commission_bps: u128,
+}
+
+/// Emitted when stake is delegated to an operator.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Delegated {
+    pub delegator: AccountId,
+    pub operator: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when stake is taken back from an operator.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Undelegated {
+    pub delegator: AccountId,
+    pub operator: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when rewards are distributed to an operator's delegators; `commission`
+/// of `reward` went to the operator.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RewardsDistributed {
+    pub operator: AccountId,
+    pub reward: Balance,
+    pub commission: Balance,
+}
+
+/// Emitted when a delegator claims their rewards from an operator.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RewardsClaimed {
+    pub delegator: AccountId,
+    pub operator: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when an operator claims their commission.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct CommissionClaimed {
+    pub operator: AccountId,
+    pub amount: Balance,
+}
+
+/// Every event of the nomination contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    OperatorRegistered(OperatorRegistered),
+    CommissionChanged(CommissionChanged),
+    Delegated(Delegated),
+    Undelegated(Undelegated),
+    RewardsDistributed(RewardsDistributed),
+    RewardsClaimed(RewardsClaimed),
+    CommissionClaimed(CommissionClaimed),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn rewards_distributed_round_trips() {
+        let bytes = round_trip(&Event::RewardsDistributed(RewardsDistributed {
+            operator: AccountId::from([0x01; 32]),
+            reward: 1_000,
+            commission: 100,
+        }));
+        assert_eq!(bytes[0], 4);
+        assert_eq!(bytes.len(), 1 + 32 + 16 + 16);
+    }
+}
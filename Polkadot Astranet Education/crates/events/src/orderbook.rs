@@ -0,0 +1,84 @@
+// crates/events/src/orderbook.rs
+//
+// Events of `examples/demo-contracts/orderbook.rs`.
+
+use crate::{AccountId, Balance};
+
+/// The side of an order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+/// Emitted when an order is placed. `amount` is the full amount, filled or not.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct OrderPlaced {
+    pub order_id: u64,
+    pub owner: AccountId,
+    pub side: Side,
+    pub price: Balance,
+    pub amount: Balance,
+}
+
+/// Emitted when an order is cancelled with `remaining` unfilled.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct OrderCancelled {
+    pub order_id: u64,
+    pub remaining: Balance,
+}
+
+/// Emitted when a resting order and an incoming order trade `amount` base at the
+/// resting order's price.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Trade {
+    pub maker_order: u64,
+    pub taker_order: u64,
+    pub price: Balance,
+    pub amount: Balance,
+}
+
+/// Every event of the order book contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    OrderPlaced(OrderPlaced),
+    OrderCancelled(OrderCancelled),
+    Trade(Trade),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn order_placed_round_trips() {
+        let bytes = round_trip(&Event::OrderPlaced(OrderPlaced {
+            order_id: 3,
+            owner: AccountId::from([0x01; 32]),
+            side: Side::Sell,
+            price: 12,
+            amount: 5,
+        }));
+        assert_eq!(bytes[0], 0);
+        assert_eq!(bytes[1 + 8 + 32], 1);
+        assert_eq!(bytes.len(), 1 + 8 + 32 + 1 + 16 + 16);
+    }
+
+    #[test]
+    fn trade_round_trips() {
+        let bytes = round_trip(&Event::Trade(Trade {
+            maker_order: 1,
+            taker_order: 2,
+            price: 10,
+            amount: 4,
+        }));
+        assert_eq!(bytes[0], 2);
+        assert_eq!(bytes.len(), 1 + 8 + 8 + 16 + 16);
+    }
+}
@@ -0,0 +1,64 @@
+// crates/events/src/payroll.rs
+//
+// Events of `examples/demo-contracts/payroll.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when `from` funds the payroll.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Funded {
+    pub from: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when the employer adds an employee.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct EmployeeAdded {
+    pub employee: AccountId,
+    pub salary: Balance,
+}
+
+/// Emitted when the employer removes an employee.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct EmployeeRemoved {
+    pub employee: AccountId,
+}
+
+/// Emitted when an employee is paid `periods` periods of salary.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SalaryPaid {
+    pub employee: AccountId,
+    pub periods: u64,
+    pub amount: Balance,
+}
+
+/// Every event of the payroll contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Funded(Funded),
+    EmployeeAdded(EmployeeAdded),
+    EmployeeRemoved(EmployeeRemoved),
+    SalaryPaid(SalaryPaid),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn salary_paid_round_trips() {
+        let bytes = round_trip(&Event::SalaryPaid(SalaryPaid {
+            employee: AccountId::from([0x01; 32]),
+            periods: 2,
+            amount: 200,
+        }));
+        assert_eq!(bytes[0], 3);
+        assert_eq!(bytes.len(), 1 + 32 + 8 + 16);
+    }
+}
@@ -0,0 +1,97 @@
+// crates/events/src/peer_review.rs
+//
+// Events of `examples/demo-contracts/peer_review.rs`.
+
+use crate::{AccountId, Balance, Hash};
+
+/// How a review was resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Outcome {
+    NoQuorum,
+    Kept,
+    Slashed,
+}
+
+/// Emitted when a reviewer adds to their stake.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Staked {
+    pub reviewer: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when a reviewer takes back free stake.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Unstaked {
+    pub reviewer: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when work is submitted for review.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Submitted {
+    pub submission_id: u32,
+    pub author: AccountId,
+    pub content: Hash,
+}
+
+/// Emitted when a reviewer reviews a submission.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Reviewed {
+    pub review_id: u32,
+    pub submission_id: u32,
+    pub reviewer: AccountId,
+    pub report: Hash,
+}
+
+/// Emitted when a staked reviewer votes on flagging a review.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Voted {
+    pub review_id: u32,
+    pub voter: AccountId,
+    pub flag: bool,
+}
+
+/// Emitted when a review is resolved.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Resolved {
+    pub review_id: u32,
+    pub outcome: Outcome,
+    pub slashed: Balance,
+}
+
+/// Every event of the peer review contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Staked(Staked),
+    Unstaked(Unstaked),
+    Submitted(Submitted),
+    Reviewed(Reviewed),
+    Voted(Voted),
+    Resolved(Resolved),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn resolved_round_trips() {
+        let bytes = round_trip(&Event::Resolved(Resolved {
+            review_id: 4,
+            outcome: Outcome::Slashed,
+            slashed: 100,
+        }));
+        assert_eq!(bytes[0], 5);
+        assert_eq!(bytes[1 + 4], 2);
+        assert_eq!(bytes.len(), 1 + 4 + 1 + 16);
+    }
+}
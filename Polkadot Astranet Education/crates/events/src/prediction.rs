@@ -0,0 +1,93 @@
+// crates/events/src/prediction.rs
+//
+// Events of `examples/demo-contracts/prediction.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Identifies a market.
+pub type MarketId = u32;
+
+/// A side of a market.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Outcome {
+    Yes,
+    No,
+}
+
+/// Emitted when the admin opens a market.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct MarketCreated {
+    pub market_id: MarketId,
+    pub asset: AccountId,
+    pub strike: Balance,
+    pub closes_at: u64,
+}
+
+/// Emitted when shares are bought.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SharesBought {
+    pub market_id: MarketId,
+    pub buyer: AccountId,
+    pub outcome: Outcome,
+    pub shares: Balance,
+    pub cost: Balance,
+}
+
+/// Emitted when a market is resolved from the oracle price.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct MarketResolved {
+    pub market_id: MarketId,
+    pub price: Balance,
+    pub outcome: Outcome,
+    pub fee: Balance,
+}
+
+/// Emitted when a holder redeems their shares.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Redeemed {
+    pub market_id: MarketId,
+    pub holder: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when the admin withdraws the fees.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct FeesWithdrawn {
+    pub amount: Balance,
+}
+
+/// Every event of the prediction market contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    MarketCreated(MarketCreated),
+    SharesBought(SharesBought),
+    MarketResolved(MarketResolved),
+    Redeemed(Redeemed),
+    FeesWithdrawn(FeesWithdrawn),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn market_resolved_round_trips() {
+        let bytes = round_trip(&Event::MarketResolved(MarketResolved {
+            market_id: 0,
+            price: 2_000,
+            outcome: Outcome::No,
+            fee: 20,
+        }));
+        assert_eq!(bytes[0], 2);
+        assert_eq!(bytes[1 + 4 + 16], 1);
+        assert_eq!(bytes.len(), 1 + 4 + 16 + 1 + 16);
+    }
+}
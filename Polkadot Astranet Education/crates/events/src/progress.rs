@@ -0,0 +1,82 @@
+// crates/events/src/progress.rs
+//
+// Events of `examples/demo-contracts/progress.rs`.
+
+use crate::AccountId;
+
+/// The id of a course.
+pub type CourseId = u32;
+
+/// Emitted when an instructor creates a course.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct CourseCreated {
+    pub course_id: CourseId,
+    pub instructor: AccountId,
+}
+
+/// Emitted when an instructor adds a module to a course.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ModuleAdded {
+    pub course_id: CourseId,
+    pub module: u8,
+    pub lessons: u8,
+    pub prerequisites: u32,
+}
+
+/// Emitted when a learner completes a lesson.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct LessonCompleted {
+    pub course_id: CourseId,
+    pub learner: AccountId,
+    pub module: u8,
+    pub lesson: u8,
+}
+
+/// Emitted when a learner completes the last lesson of a module.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ModuleCompleted {
+    pub course_id: CourseId,
+    pub learner: AccountId,
+    pub module: u8,
+}
+
+/// Emitted when a learner completes the last module of a course.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct CourseCompleted {
+    pub course_id: CourseId,
+    pub learner: AccountId,
+}
+
+/// Every event of the progress contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    CourseCreated(CourseCreated),
+    ModuleAdded(ModuleAdded),
+    LessonCompleted(LessonCompleted),
+    ModuleCompleted(ModuleCompleted),
+    CourseCompleted(CourseCompleted),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn lesson_completed_round_trips() {
+        let bytes = round_trip(&Event::LessonCompleted(LessonCompleted {
+            course_id: 3,
+            learner: AccountId::from([0x01; 32]),
+            module: 1,
+            lesson: 2,
+        }));
+        assert_eq!(bytes[0], 2);
+        assert_eq!(bytes.len(), 1 + 4 + 32 + 1 + 1);
+    }
+}
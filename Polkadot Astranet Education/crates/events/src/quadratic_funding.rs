@@ -0,0 +1,88 @@
+// crates/events/src/quadratic_funding.rs
+//
+// Events of `examples/demo-contracts/quadratic_funding.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Identifies a project.
+pub type ProjectId = u32;
+
+/// Emitted when the admin lists a project.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ProjectAdded {
+    pub project_id: ProjectId,
+    pub recipient: AccountId,
+}
+
+/// Emitted when a sponsor adds to the matching pool.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct MatchingFunded {
+    pub sponsor: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when someone contributes to a project.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Contributed {
+    pub project_id: ProjectId,
+    pub contributor: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when the matches are computed.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoundFinalized {
+    pub matching_pool: Balance,
+    pub unmatched: Balance,
+}
+
+/// Emitted when a project is paid its contributions and match.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ProjectPaid {
+    pub project_id: ProjectId,
+    pub recipient: AccountId,
+    pub raised: Balance,
+    pub matched: Balance,
+}
+
+/// Emitted when the admin takes back the unused part of the pool.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct UnmatchedWithdrawn {
+    pub amount: Balance,
+}
+
+/// Every event of the quadratic funding contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    ProjectAdded(ProjectAdded),
+    MatchingFunded(MatchingFunded),
+    Contributed(Contributed),
+    RoundFinalized(RoundFinalized),
+    ProjectPaid(ProjectPaid),
+    UnmatchedWithdrawn(UnmatchedWithdrawn),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn project_paid_round_trips() {
+        let bytes = round_trip(&Event::ProjectPaid(ProjectPaid {
+            project_id: 2,
+            recipient: AccountId::from([0x82; 32]),
+            raised: 25,
+            matched: 12,
+        }));
+        assert_eq!(bytes[0], 4);
+        assert_eq!(bytes.len(), 1 + 4 + 32 + 16 + 16);
+    }
+}
@@ -0,0 +1,44 @@
+// crates/events/src/rebase_token.rs
+//
+// Events of `examples/demo-contracts/rebase_token.rs`.
+
+use crate::Balance;
+
+pub use crate::erc20::{Approval, Transfer};
+
+/// Emitted when the owner sets the total supply to `total_supply`; `epoch` counts
+/// rebases from 1.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Rebased {
+    pub epoch: u64,
+    pub total_supply: Balance,
+}
+
+/// Every event of the rebase token contract, in declaration order.
+///
+/// The first two events share their layout with the ERC-20 ones. `Transfer` values
+/// are in fragments at the supply of the block they were emitted in.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Transfer(Transfer),
+    Approval(Approval),
+    Rebased(Rebased),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn rebased_round_trips() {
+        let bytes = round_trip(&Event::Rebased(Rebased {
+            epoch: 1,
+            total_supply: 200,
+        }));
+        assert_eq!(bytes[0], 2);
+        assert_eq!(bytes.len(), 1 + 8 + 16);
+    }
+}
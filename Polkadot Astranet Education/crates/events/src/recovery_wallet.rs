@@ -0,0 +1,93 @@
+// crates/events/src/recovery_wallet.rs
+//
+// Events of `examples/demo-contracts/recovery_wallet.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Numbers recoveries, so approvals of an earlier recovery are ignored.
+pub type Round = u32;
+
+/// Emitted when the owner sends tokens from the wallet.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Sent {
+    pub to: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when the owner adds a guardian.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct GuardianAdded {
+    pub guardian: AccountId,
+}
+
+/// Emitted when the owner removes a guardian.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct GuardianRemoved {
+    pub guardian: AccountId,
+}
+
+/// Emitted when the owner changes the threshold.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ThresholdChanged {
+    pub threshold: u32,
+}
+
+/// Emitted when a guardian approves a recovery.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RecoveryApproved {
+    pub round: Round,
+    pub guardian: AccountId,
+    pub new_owner: AccountId,
+    pub approvals: u32,
+}
+
+/// Emitted when the owner cancels a recovery.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RecoveryCanceled {
+    pub round: Round,
+}
+
+/// Emitted when a recovery is finalized and the owner key rotated.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct OwnerRotated {
+    pub round: Round,
+    pub old_owner: AccountId,
+    pub new_owner: AccountId,
+}
+
+/// Every event of the recovery wallet contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Sent(Sent),
+    GuardianAdded(GuardianAdded),
+    GuardianRemoved(GuardianRemoved),
+    ThresholdChanged(ThresholdChanged),
+    RecoveryApproved(RecoveryApproved),
+    RecoveryCanceled(RecoveryCanceled),
+    OwnerRotated(OwnerRotated),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn owner_rotated_round_trips() {
+        let bytes = round_trip(&Event::OwnerRotated(OwnerRotated {
+            round: 3,
+            old_owner: AccountId::from([0x01; 32]),
+            new_owner: AccountId::from([0x05; 32]),
+        }));
+        assert_eq!(bytes[0], 6);
+        assert_eq!(bytes.len(), 1 + 4 + 32 + 32);
+    }
+}
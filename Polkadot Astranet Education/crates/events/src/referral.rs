@@ -0,0 +1,75 @@
+// crates/events/src/referral.rs
+//
+// Events of `examples/demo-contracts/referral.rs`.
+
+use crate::{AccountId, Balance};
+use ink_prelude::vec::Vec;
+
+/// Identifies a course on the enrollment contract.
+pub type CourseId = u32;
+
+/// A reward tier, as stored by the contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Tier {
+    pub min_referrals: u32,
+    pub reward: Balance,
+}
+
+/// Emitted when the admin replaces the reward tiers.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct TiersSet {
+    pub tiers: Vec<Tier>,
+}
+
+/// Emitted when a learner registers their referrer.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Referred {
+    pub referee: AccountId,
+    pub referrer: AccountId,
+}
+
+/// Emitted when a referrer is rewarded for a learner's enrollment.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RewardPaid {
+    pub referrer: AccountId,
+    pub referee: AccountId,
+    pub course_id: CourseId,
+    pub amount: Balance,
+}
+
+/// Every event of the referral contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    TiersSet(TiersSet),
+    Referred(Referred),
+    RewardPaid(RewardPaid),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn tiers_set_round_trips() {
+        let bytes = round_trip(&Event::TiersSet(TiersSet {
+            tiers: ink_prelude::vec![
+                Tier {
+                    min_referrals: 1,
+                    reward: 10,
+                },
+                Tier {
+                    min_referrals: 3,
+                    reward: 20,
+                },
+            ],
+        }));
+        assert_eq!(bytes[0], 0);
+        assert_eq!(bytes.len(), 1 + 1 + 2 * (4 + 16));
+    }
+}
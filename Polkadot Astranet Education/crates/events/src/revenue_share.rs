@@ -0,0 +1,66 @@
+// crates/events/src/revenue_share.rs
+//
+// Events of `examples/demo-contracts/revenue_share.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Identifies a course on the enrollment contract.
+pub type CourseId = u32;
+
+/// Emitted when the admin changes the weight of an instructor.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct WeightChanged {
+    pub instructor: AccountId,
+    pub weight: u32,
+}
+
+/// Emitted when the contract opens a course on the enrollment contract.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct CourseOpened {
+    pub course_id: CourseId,
+    pub fee: Balance,
+}
+
+/// Emitted when a payment is split among the instructors.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Distributed {
+    pub amount: Balance,
+    pub total_weight: u64,
+}
+
+/// Emitted when an instructor claims their pending balance.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Claimed {
+    pub instructor: AccountId,
+    pub amount: Balance,
+}
+
+/// Every event of the revenue share contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    WeightChanged(WeightChanged),
+    CourseOpened(CourseOpened),
+    Distributed(Distributed),
+    Claimed(Claimed),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn distributed_round_trips() {
+        let bytes = round_trip(&Event::Distributed(Distributed {
+            amount: 400,
+            total_weight: 4,
+        }));
+        assert_eq!(bytes[0], 2);
+        assert_eq!(bytes.len(), 1 + 16 + 8);
+    }
+}
@@ -0,0 +1,31 @@
+// crates/events/src/runtime_call.rs
+//
+// Events of `examples/demo-contracts/runtime_call.rs`.
+//
+// The contract is written for ink! 5, so `data` is the event struct itself.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when the contract sends funds through `Balances::transfer_keep_alive`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RuntimeTransfer {
+    pub to: AccountId,
+    pub value: Balance,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn runtime_transfer_round_trips() {
+        let bytes = round_trip(&RuntimeTransfer {
+            to: AccountId::from([1; 32]),
+            value: 1_000_000,
+        });
+        // no event index in front of the fields
+        assert_eq!(bytes.len(), 32 + 16);
+    }
+}
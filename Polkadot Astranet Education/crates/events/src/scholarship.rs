@@ -0,0 +1,86 @@
+// crates/events/src/scholarship.rs
+//
+// Events of `examples/demo-contracts/scholarship.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when a donor funds the pool.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Donated {
+    pub donor: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when the admin adds or removes an instructor.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct InstructorChanged {
+    pub instructor: AccountId,
+    pub active: bool,
+}
+
+/// Emitted when a learner applies.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Applied {
+    pub learner: AccountId,
+}
+
+/// Emitted when the admin approves a learner's application.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Approved {
+    pub learner: AccountId,
+    pub tranche: Balance,
+    pub milestones: u32,
+}
+
+/// Emitted when the admin revokes a scholarship, returning the unpaid tranches.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Revoked {
+    pub learner: AccountId,
+    pub returned: Balance,
+}
+
+/// Emitted when a learner is paid the tranche of a milestone attested by
+/// `instructor`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct TranchePaid {
+    pub learner: AccountId,
+    pub instructor: AccountId,
+    pub milestone: u32,
+    pub amount: Balance,
+}
+
+/// Every event of the scholarship contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Donated(Donated),
+    InstructorChanged(InstructorChanged),
+    Applied(Applied),
+    Approved(Approved),
+    Revoked(Revoked),
+    TranchePaid(TranchePaid),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn tranche_paid_round_trips() {
+        let bytes = round_trip(&Event::TranchePaid(TranchePaid {
+            learner: AccountId::from([0x01; 32]),
+            instructor: AccountId::from([0x02; 32]),
+            milestone: 2,
+            amount: 100,
+        }));
+        assert_eq!(bytes[0], 5);
+        assert_eq!(bytes.len(), 1 + 32 + 32 + 4 + 16);
+    }
+}
@@ -0,0 +1,64 @@
+// crates/events/src/spending_manager.rs
+//
+// Events of `examples/demo-contracts/spending_manager.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when tokens are deposited into the manager.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Deposited {
+    pub from: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when the owner withdraws tokens.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Withdrawn {
+    pub amount: Balance,
+}
+
+/// Emitted when the owner sets a spender's limit; zero removes the spender.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct LimitSet {
+    pub spender: AccountId,
+    pub daily_limit: Balance,
+}
+
+/// Emitted when a spender sends tokens.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Spent {
+    pub spender: AccountId,
+    pub to: AccountId,
+    pub amount: Balance,
+}
+
+/// Every event of the spending manager contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Deposited(Deposited),
+    Withdrawn(Withdrawn),
+    LimitSet(LimitSet),
+    Spent(Spent),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn spent_round_trips() {
+        let bytes = round_trip(&Event::Spent(Spent {
+            spender: AccountId::from([0x02; 32]),
+            to: AccountId::from([0x03; 32]),
+            amount: 40,
+        }));
+        assert_eq!(bytes[0], 3);
+        assert_eq!(bytes.len(), 1 + 32 + 32 + 16);
+    }
+}
@@ -0,0 +1,106 @@
+// crates/events/src/staking.rs
+//
+// Events of `examples/demo-contracts/staking.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when tokens are staked.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Staked {
+    pub staker: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when tokens start unbonding; they can be withdrawn from block
+/// `unlocks_at` on.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Unstaked {
+    pub staker: AccountId,
+    pub amount: Balance,
+    pub unlocks_at: u32,
+}
+
+/// Emitted when a staker claims their rewards.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RewardsClaimed {
+    pub staker: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when matured unbonding chunks are withdrawn.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Withdrawn {
+    pub staker: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when the admin appoints or removes the slasher.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SlasherSet {
+    pub slasher: Option<AccountId>,
+}
+
+/// Emitted when `fraction_bps` basis points of a staker's bond are slashed to the
+/// treasury.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Slashed {
+    pub staker: AccountId,
+    pub fraction_bps: u128,
+    pub amount: Balance,
+}
+
+/// Every event of the staking contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Staked(Staked),
+    Unstaked(Unstaked),
+    RewardsClaimed(RewardsClaimed),
+    Withdrawn(Withdrawn),
+    SlasherSet(SlasherSet),
+    Slashed(Slashed),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn rewards_claimed_round_trips() {
+        let bytes = round_trip(&Event::RewardsClaimed(RewardsClaimed {
+            staker: AccountId::from([0x02; 32]),
+            amount: 75,
+        }));
+        assert_eq!(bytes[0], 2);
+        assert_eq!(bytes.len(), 1 + 32 + 16);
+    }
+
+    #[test]
+    fn unstaked_round_trips() {
+        let bytes = round_trip(&Event::Unstaked(Unstaked {
+            staker: AccountId::from([0x02; 32]),
+            amount: 30,
+            unlocks_at: 12,
+        }));
+        assert_eq!(bytes[0], 1);
+        assert_eq!(bytes.len(), 1 + 32 + 16 + 4);
+    }
+
+    #[test]
+    fn slashed_round_trips() {
+        let bytes = round_trip(&Event::Slashed(Slashed {
+            staker: AccountId::from([0x02; 32]),
+            fraction_bps: 2_500,
+            amount: 24,
+        }));
+        assert_eq!(bytes[0], 5);
+        assert_eq!(bytes.len(), 1 + 32 + 16 + 16);
+    }
+}
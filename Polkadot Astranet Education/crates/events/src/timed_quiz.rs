@@ -0,0 +1,71 @@
+// crates/events/src/timed_quiz.rs
+//
+// Events of `examples/demo-contracts/timed_quiz.rs`.
+
+use crate::{AccountId, Hash};
+use ink_prelude::vec::Vec;
+
+/// Identifies a quiz.
+pub type QuizId = u32;
+
+/// Emitted when an author creates a quiz, committing to its answer key.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct QuizCreated {
+    pub quiz_id: QuizId,
+    pub author: AccountId,
+    pub key_hash: Hash,
+    pub questions: u8,
+    pub deadline: u64,
+}
+
+/// Emitted when a learner submits their answers.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AnswersSubmitted {
+    pub quiz_id: QuizId,
+    pub learner: AccountId,
+    pub answers: Vec<u8>,
+}
+
+/// Emitted when the author reveals the answer key.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct KeyRevealed {
+    pub quiz_id: QuizId,
+    pub key: Vec<u8>,
+}
+
+/// Emitted when a submission is scored.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SubmissionScored {
+    pub quiz_id: QuizId,
+    pub learner: AccountId,
+    pub score: u8,
+}
+
+/// Every event of the timed quiz contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    QuizCreated(QuizCreated),
+    AnswersSubmitted(AnswersSubmitted),
+    KeyRevealed(KeyRevealed),
+    SubmissionScored(SubmissionScored),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn key_revealed_round_trips() {
+        let bytes = round_trip(&Event::KeyRevealed(KeyRevealed {
+            quiz_id: 4,
+            key: vec![2, 0, 3, 1],
+        }));
+        assert_eq!(bytes, [2, 4, 0, 0, 0, 4 << 2, 2, 0, 3, 1]);
+    }
+}
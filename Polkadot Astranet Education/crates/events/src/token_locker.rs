@@ -0,0 +1,71 @@
+// crates/events/src/token_locker.rs
+//
+// Events of `examples/demo-contracts/token_locker.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when `owner` locks `amount` of `token` until `unlock_at`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Locked {
+    pub lock_id: u64,
+    pub owner: AccountId,
+    pub token: AccountId,
+    pub amount: Balance,
+    pub unlock_at: u64,
+}
+
+/// Emitted when the owner of a lock moves its unlock time later.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct LockExtended {
+    pub lock_id: u64,
+    pub unlock_at: u64,
+}
+
+/// Emitted when the tokens of a lock are withdrawn.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Withdrawn {
+    pub lock_id: u64,
+    pub owner: AccountId,
+    pub amount: Balance,
+}
+
+/// Every event of the token locker contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Locked(Locked),
+    LockExtended(LockExtended),
+    Withdrawn(Withdrawn),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn locked_round_trips() {
+        let bytes = round_trip(&Event::Locked(Locked {
+            lock_id: 7,
+            owner: AccountId::from([0x01; 32]),
+            token: AccountId::from([0x02; 32]),
+            amount: 100,
+            unlock_at: 1_700_000_000_000,
+        }));
+        assert_eq!(bytes[0], 0);
+        assert_eq!(bytes.len(), 1 + 8 + 32 + 32 + 16 + 8);
+    }
+
+    #[test]
+    fn lock_extended_round_trips() {
+        let bytes = round_trip(&Event::LockExtended(LockExtended {
+            lock_id: 7,
+            unlock_at: 1_700_000_000_000,
+        }));
+        assert_eq!(bytes[0], 1);
+        assert_eq!(bytes.len(), 1 + 8 + 8);
+    }
+}
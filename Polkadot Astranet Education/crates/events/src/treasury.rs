@@ -0,0 +1,90 @@
+// crates/events/src/treasury.rs
+//
+// Events of `examples/demo-contracts/treasury.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Identifies a spend proposal.
+pub type ProposalId = u32;
+
+/// Funds the treasury can hold and spend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Asset {
+    Native,
+    Erc20(AccountId),
+}
+
+/// Emitted when native tokens are deposited.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Deposited {
+    pub from: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when the DAO sets how much of an asset can be spent per period.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct BudgetSet {
+    pub asset: Asset,
+    pub per_period: Balance,
+}
+
+/// Emitted when a spend is proposed.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SpendProposed {
+    pub proposal_id: ProposalId,
+    pub proposer: AccountId,
+    pub asset: Asset,
+    pub beneficiary: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when an approved spend is paid out.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SpendExecuted {
+    pub proposal_id: ProposalId,
+    pub beneficiary: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when a spend period is closed and part of the native balance burned.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct PeriodClosed {
+    pub period: u32,
+    pub burned: Balance,
+}
+
+/// Every event of the treasury contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Deposited(Deposited),
+    BudgetSet(BudgetSet),
+    SpendProposed(SpendProposed),
+    SpendExecuted(SpendExecuted),
+    PeriodClosed(PeriodClosed),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn spend_proposed_round_trips() {
+        let bytes = round_trip(&Event::SpendProposed(SpendProposed {
+            proposal_id: 3,
+            proposer: AccountId::from([0x01; 32]),
+            asset: Asset::Erc20(AccountId::from([0x70; 32])),
+            beneficiary: AccountId::from([0x02; 32]),
+            amount: 500,
+        }));
+        assert_eq!(bytes[0], 2);
+        assert_eq!(bytes.len(), 1 + 4 + 32 + (1 + 32) + 32 + 16);
+    }
+}
@@ -0,0 +1,36 @@
+// crates/events/src/vesting.rs
+//
+// Events of `examples/demo-contracts/vesting.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when vested tokens are sent to the beneficiary.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Released {
+    pub beneficiary: AccountId,
+    pub amount: Balance,
+}
+
+/// Every event of the vesting contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Released(Released),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn released_round_trips() {
+        let bytes = round_trip(&Event::Released(Released {
+            beneficiary: AccountId::from([0x02; 32]),
+            amount: 400,
+        }));
+        assert_eq!(bytes[0], 0);
+        assert_eq!(bytes.len(), 1 + 32 + 16);
+    }
+}
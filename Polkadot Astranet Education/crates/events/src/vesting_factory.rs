@@ -0,0 +1,40 @@
+// crates/events/src/vesting_factory.rs
+//
+// Events of `examples/demo-contracts/vesting_factory.rs`.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when a vesting contract is deployed for a beneficiary.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct VestingCreated {
+    pub creator: AccountId,
+    pub beneficiary: AccountId,
+    pub vesting: AccountId,
+    pub amount: Balance,
+}
+
+/// Every event of the vesting factory contract, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    VestingCreated(VestingCreated),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn vesting_created_round_trips() {
+        let bytes = round_trip(&Event::VestingCreated(VestingCreated {
+            creator: AccountId::from([0x01; 32]),
+            beneficiary: AccountId::from([0x02; 32]),
+            vesting: AccountId::from([0x03; 32]),
+            amount: 1_000,
+        }));
+        assert_eq!(bytes[0], 0);
+        assert_eq!(bytes.len(), 1 + 32 * 3 + 16);
+    }
+}
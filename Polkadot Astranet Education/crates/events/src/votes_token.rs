@@ -0,0 +1,77 @@
+// crates/events/src/votes_token.rs
+//
+// Events of `examples/demo-contracts/votes_token.rs`.
+
+use crate::{AccountId, Balance};
+
+pub use crate::erc20::{Approval, Transfer};
+
+/// Emitted when `delegator` moves its votes from `from_delegate` to `to_delegate`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DelegateChanged {
+    pub delegator: AccountId,
+    pub from_delegate: Option<AccountId>,
+    pub to_delegate: AccountId,
+}
+
+/// Emitted when a delegate's voting power changes.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DelegateVotesChanged {
+    pub delegate: AccountId,
+    pub previous_votes: Balance,
+    pub new_votes: Balance,
+}
+
+/// Every event of the votes token contract, in declaration order.
+///
+/// The first two events share their layout with the ERC-20 ones.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Event {
+    Transfer(Transfer),
+    Approval(Approval),
+    DelegateChanged(DelegateChanged),
+    DelegateVotesChanged(DelegateVotesChanged),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn delegate_changed_round_trips() {
+        let bytes = round_trip(&Event::DelegateChanged(DelegateChanged {
+            delegator: AccountId::from([1; 32]),
+            from_delegate: None,
+            to_delegate: AccountId::from([1; 32]),
+        }));
+        assert_eq!(bytes[0], 2);
+        assert_eq!(bytes.len(), 1 + 32 + 1 + 32);
+    }
+
+    #[test]
+    fn delegate_votes_changed_round_trips() {
+        let bytes = round_trip(&Event::DelegateVotesChanged(DelegateVotesChanged {
+            delegate: AccountId::from([1; 32]),
+            previous_votes: 0,
+            new_votes: 50,
+        }));
+        assert_eq!(bytes[0], 3);
+    }
+
+    #[test]
+    fn token_events_match_erc20() {
+        let transfer = Transfer {
+            from: Some(AccountId::from([1; 32])),
+            to: None,
+            value: 9,
+        };
+        assert_eq!(
+            scale::Encode::encode(&Event::Transfer(transfer.clone())),
+            scale::Encode::encode(&crate::erc20::Event::Transfer(transfer))
+        );
+    }
+}
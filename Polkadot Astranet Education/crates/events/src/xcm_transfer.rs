@@ -0,0 +1,50 @@
+// crates/events/src/xcm_transfer.rs
+//
+// Events of `examples/demo-contracts/xcm_transfer.rs`.
+//
+// The contract is written for ink! 5, so `data` is the event struct itself.
+
+use crate::{AccountId, Balance};
+
+/// Emitted when the contract reserve-transfers tokens to a sibling parachain.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ReserveTransferred {
+    pub para_id: u32,
+    pub beneficiary: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when the contract asks a sibling to return tokens.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ReturnRequested {
+    pub para_id: u32,
+    pub amount: Balance,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_trip;
+
+    #[test]
+    fn reserve_transferred_round_trips() {
+        let bytes = round_trip(&ReserveTransferred {
+            para_id: 2000,
+            beneficiary: AccountId::from([1; 32]),
+            amount: 5,
+        });
+        // no event index in front of the fields
+        assert_eq!(&bytes[..4], &[0xd0, 0x07, 0, 0]);
+    }
+
+    #[test]
+    fn return_requested_round_trips() {
+        let bytes = round_trip(&ReturnRequested {
+            para_id: 2000,
+            amount: 5,
+        });
+        assert_eq!(bytes.len(), 4 + 16);
+    }
+}
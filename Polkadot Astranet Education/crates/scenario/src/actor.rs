@@ -0,0 +1,95 @@
+// crates/scenario/src/actor.rs
+//
+// The well-known development accounts a scenario can act as.
+
+use core::fmt;
+use ink::primitives::AccountId;
+use ink_e2e::Keypair;
+
+/// One of the development accounts every `substrate-contracts-node` endows at genesis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Actor {
+    Alice,
+    Bob,
+    Charlie,
+    Dave,
+    Eve,
+    Ferdie,
+}
+
+impl Actor {
+    /// All actors, in the order the node lists them.
+    pub const ALL: [Actor; 6] = [
+        Actor::Alice,
+        Actor::Bob,
+        Actor::Charlie,
+        Actor::Dave,
+        Actor::Eve,
+        Actor::Ferdie,
+    ];
+
+    /// The lowercase name used in step logs, e.g. `alice`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Actor::Alice => "alice",
+            Actor::Bob => "bob",
+            Actor::Charlie => "charlie",
+            Actor::Dave => "dave",
+            Actor::Eve => "eve",
+            Actor::Ferdie => "ferdie",
+        }
+    }
+
+    /// The actor's sr25519 keypair, derived from `//Alice`, `//Bob`, ...
+    pub fn keypair(self) -> Keypair {
+        match self {
+            Actor::Alice => ink_e2e::alice(),
+            Actor::Bob => ink_e2e::bob(),
+            Actor::Charlie => ink_e2e::charlie(),
+            Actor::Dave => ink_e2e::dave(),
+            Actor::Eve => ink_e2e::eve(),
+            Actor::Ferdie => ink_e2e::ferdie(),
+        }
+    }
+
+    /// The actor's account id, as passed to contract messages.
+    pub fn account_id(self) -> AccountId {
+        AccountId::from(self.keypair().public_key().0)
+    }
+}
+
+impl fmt::Display for Actor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keypairs_match_ink_e2e_dev_accounts() {
+        assert_eq!(
+            Actor::Alice.keypair().public_key().0,
+            ink_e2e::alice().public_key().0
+        );
+        assert_eq!(
+            Actor::Ferdie.account_id(),
+            AccountId::from(ink_e2e::ferdie().public_key().0)
+        );
+    }
+
+    #[test]
+    fn actors_have_distinct_accounts() {
+        let mut accounts: Vec<_> = Actor::ALL.iter().map(|actor| actor.account_id()).collect();
+        accounts.sort();
+        accounts.dedup();
+        assert_eq!(accounts.len(), Actor::ALL.len());
+    }
+
+    #[test]
+    fn display_uses_lowercase_name() {
+        assert_eq!(Actor::Charlie.to_string(), "charlie");
+    }
+}
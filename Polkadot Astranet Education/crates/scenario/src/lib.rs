@@ -0,0 +1,290 @@
+// crates/scenario/src/lib.rs
+//
+// A small step-by-step harness over `ink_e2e` for the course's end-to-end tests.
+//
+// Lessons describe flows as sentences: "alice deploys erc20; alice transfers 10 to
+// bob; bob's balance is 10". Written directly against `ink_e2e`, every step is a
+// builder chain with its own `.submit().await.expect(..)` and an `AccountId` built
+// from a keypair by hand. A `Scenario` wraps the client so each step is one line
+// naming the actor and what it does:
+//
+//     #[ink_e2e::test]
+//     async fn transfer_works<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+//         let mut scenario = Scenario::new("erc20 transfer", &mut client);
+//         let erc20 = scenario
+//             .deploy("alice deploys erc20", Actor::Alice, "erc20", &mut Erc20Ref::new(1_000))
+//             .await;
+//         let mut token = erc20.call_builder::<Erc20>();
+//
+//         scenario
+//             .call("transfer 10 to bob", Actor::Alice, &token.transfer(Actor::Bob.account_id(), 10))
+//             .await
+//             .expect("transfer failed");
+//         scenario
+//             .assert_query_eq("bob has 10", Actor::Bob, &token.balance_of(Actor::Bob.account_id()), 10)
+//             .await;
+//         Ok(())
+//     }
+//
+// Every step is logged as `[erc20 transfer] step 2: alice: transfer 10 to bob`, and
+// a failing step panics with the scenario name, step number and description, so the
+// test output says which sentence of the lesson broke.
+
+mod actor;
+
+pub use actor::Actor;
+
+use core::fmt::Debug;
+use ink::env::{
+    call::{
+        state::Salt,
+        utils::{ReturnType, Set, Unset},
+        CreateBuilder, ExecutionInput, LimitParamsV2,
+    },
+    DefaultEnvironment, Environment,
+};
+use ink::primitives::AccountId;
+use ink::scale::{Decode, Encode};
+use ink_e2e::{
+    CallBuilderFinal, ChainBackend, ContractsBackend, E2EBackend, InstantiationResult, Keypair,
+};
+
+/// The environment of the example contracts.
+pub type Env = DefaultEnvironment;
+
+/// The balance type of [`Env`].
+pub type Balance = <Env as Environment>::Balance;
+
+/// A constructor call as returned by `ContractRef::new(..)`, before `ink_e2e` fills in
+/// the code hash. Mirrors the type `ink_e2e` expects but does not export.
+pub type Constructor<Contract, Args, R> = CreateBuilder<
+    Env,
+    Contract,
+    Unset<<Env as Environment>::Hash>,
+    Set<LimitParamsV2<Env>>,
+    Unset<Balance>,
+    Set<ExecutionInput<Args>>,
+    Unset<Salt>,
+    Set<ReturnType<R>>,
+>;
+
+/// A message call as returned by `call_builder.message(..)`.
+pub type Message<Args, RetType> = CallBuilderFinal<Env, Args, RetType>;
+
+/// The result of a successful [`Scenario::deploy`].
+pub type Deployed<Client> = InstantiationResult<Env, <Client as ContractsBackend<Env>>::EventLog>;
+
+/// A named sequence of steps run against one `ink_e2e` client.
+pub struct Scenario<'c, Client> {
+    name: String,
+    client: &'c mut Client,
+    step: usize,
+}
+
+impl<'c, Client> Scenario<'c, Client>
+where
+    Client: E2EBackend<Env> + ChainBackend<AccountId = AccountId, Balance = Balance>,
+    <Client as ContractsBackend<Env>>::Error: Debug,
+    <Client as ChainBackend>::Error: Debug,
+{
+    /// Starts a scenario on `client`. `name` prefixes every log line and panic message.
+    pub fn new(name: impl Into<String>, client: &'c mut Client) -> Self {
+        Self {
+            name: name.into(),
+            client,
+            step: 0,
+        }
+    }
+
+    /// The number of steps run so far.
+    pub fn steps(&self) -> usize {
+        self.step
+    }
+
+    /// Gives the underlying client back, for anything the scenario does not cover.
+    pub fn client(&mut self) -> &mut Client {
+        self.client
+    }
+
+    /// `actor` instantiates the contract built from the crate `contract_name`.
+    pub async fn deploy<Contract, Args, R>(
+        &mut self,
+        description: &str,
+        actor: Actor,
+        contract_name: &str,
+        constructor: &mut Constructor<Contract, Args, R>,
+    ) -> Deployed<Client>
+    where
+        Contract: Clone,
+        Args: Encode + Clone + Send + Sync,
+    {
+        self.deploy_with_value(description, actor, contract_name, 0, constructor)
+            .await
+    }
+
+    /// Like [`Scenario::deploy`], transferring `value` to the new contract.
+    pub async fn deploy_with_value<Contract, Args, R>(
+        &mut self,
+        description: &str,
+        actor: Actor,
+        contract_name: &str,
+        value: Balance,
+        constructor: &mut Constructor<Contract, Args, R>,
+    ) -> Deployed<Client>
+    where
+        Contract: Clone,
+        Args: Encode + Clone + Send + Sync,
+    {
+        let label = self.begin(actor, description);
+        let caller = actor.keypair();
+        let result = self
+            .client
+            .instantiate(contract_name, &caller, constructor)
+            .value(value)
+            .submit()
+            .await;
+        self.check(&label, result)
+    }
+
+    /// `actor` submits `message` as a transaction and gets its return value.
+    ///
+    /// Panics if the transaction fails to execute. A message that returns
+    /// `Result::Err` has still executed, so its `Err` is returned, not a panic.
+    pub async fn call<Args, RetType>(
+        &mut self,
+        description: &str,
+        actor: Actor,
+        message: &Message<Args, RetType>,
+    ) -> RetType
+    where
+        Args: Encode + Clone + Send + Sync,
+        RetType: Decode + Send,
+    {
+        self.call_with_value(description, actor, 0, message).await
+    }
+
+    /// Like [`Scenario::call`], transferring `value` along with the message.
+    pub async fn call_with_value<Args, RetType>(
+        &mut self,
+        description: &str,
+        actor: Actor,
+        value: Balance,
+        message: &Message<Args, RetType>,
+    ) -> RetType
+    where
+        Args: Encode + Clone + Send + Sync,
+        RetType: Decode + Send,
+    {
+        let label = self.begin(actor, description);
+        let caller = actor.keypair();
+        let result = self
+            .client
+            .call(&caller, message)
+            .value(value)
+            .submit()
+            .await;
+        self.check(&label, result).return_value()
+    }
+
+    /// `actor` dry-runs `message` and gets its return value, without a transaction.
+    pub async fn query<Args, RetType>(
+        &mut self,
+        description: &str,
+        actor: Actor,
+        message: &Message<Args, RetType>,
+    ) -> RetType
+    where
+        Args: Encode + Clone + Send + Sync,
+        RetType: Decode + Send,
+    {
+        let label = self.begin(actor, description);
+        let caller = actor.keypair();
+        let result = self.client.call(&caller, message).dry_run().await;
+        let dry_run = self.check(&label, result);
+        if dry_run.is_err() {
+            self.fail(&label, &dry_run.exec_result.result);
+        }
+        dry_run.return_value()
+    }
+
+    /// Dry-runs `message` as `actor` and asserts it returns `expected`.
+    pub async fn assert_query_eq<Args, RetType>(
+        &mut self,
+        description: &str,
+        actor: Actor,
+        message: &Message<Args, RetType>,
+        expected: RetType,
+    ) where
+        Args: Encode + Clone + Send + Sync,
+        RetType: Decode + Send + PartialEq + Debug,
+    {
+        let actual = self.query(description, actor, message).await;
+        if actual != expected {
+            panic!(
+                "[{}] step {} ({}: {}): expected {:?}, got {:?}",
+                self.name, self.step, actor, description, expected, actual
+            );
+        }
+    }
+
+    /// Returns the free balance of `actor`'s account.
+    pub async fn balance(&mut self, actor: Actor) -> Balance {
+        let result = self.client.free_balance(actor.account_id()).await;
+        self.check(&format!("balance of {}", actor), result)
+    }
+
+    /// Asserts `actor`'s free balance is `expected`.
+    pub async fn assert_balance(&mut self, description: &str, actor: Actor, expected: Balance) {
+        let label = self.begin(actor, description);
+        let actual = self.balance(actor).await;
+        if actual != expected {
+            panic!(
+                "[{}] {}: expected a balance of {}, got {}",
+                self.name, label, expected, actual
+            );
+        }
+    }
+
+    /// Creates a fresh account funded with `amount` by `from`, for flows that need
+    /// more participants than the named actors.
+    pub async fn fund_new_account(&mut self, from: Actor, amount: Balance) -> Keypair {
+        self.begin(from, &format!("fund a new account with {}", amount));
+        self.client
+            .create_and_fund_account(&from.keypair(), amount)
+            .await
+    }
+
+    /// Numbers and logs the next step, returning its label.
+    fn begin(&mut self, actor: Actor, description: &str) -> String {
+        self.step += 1;
+        let label = step_label(self.step, actor, description);
+        println!("[{}] {}", self.name, label);
+        label
+    }
+
+    fn check<T, E: Debug>(&self, label: &str, result: Result<T, E>) -> T {
+        result.unwrap_or_else(|err| self.fail(label, &err))
+    }
+
+    fn fail(&self, label: &str, err: &dyn Debug) -> ! {
+        panic!("[{}] {} failed: {:?}", self.name, label, err)
+    }
+}
+
+/// Formats one step for logs and panic messages: `step 2: alice: transfer 10 to bob`.
+fn step_label(step: usize, actor: Actor, description: &str) -> String {
+    format!("step {}: {}: {}", step, actor, description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_label_names_step_actor_and_description() {
+        assert_eq!(
+            step_label(2, Actor::Alice, "transfer 10 to bob"),
+            "step 2: alice: transfer 10 to bob"
+        );
+    }
+}
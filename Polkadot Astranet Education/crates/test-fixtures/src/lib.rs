@@ -0,0 +1,280 @@
+// crates/test-fixtures/src/lib.rs
+//
+// Deterministic accounts and environment helpers for the examples' `#[ink::test]`
+// unit tests.
+//
+// Every test used to start with `ink_env::test::default_accounts::<Env>()` and move
+// between callers with `set_caller`, forgetting to switch back half of the time. The
+// helpers here wrap the off-chain environment of ink! 3:
+//
+//     use astranet_test_fixtures as fixtures;
+//
+//     let accounts = fixtures::accounts();
+//     let mut token = Erc20::new(100);
+//     {
+//         let _bob = fixtures::as_caller(accounts.bob);
+//         assert_eq!(token.transfer(accounts.charlie, 10), Err(Error::InsufficientBalance));
+//     }
+//     // alice is the caller again here
+//
+// All functions must run inside an `#[ink::test]`, which sets up a fresh off-chain
+// environment for each test. End-to-end tests use `astranet_scenario::Actor` for the
+// same named accounts on a real node.
+
+use ink_env::{test, DefaultEnvironment, Environment};
+
+/// The environment of the example contracts.
+pub type Env = DefaultEnvironment;
+
+/// The account id type of [`Env`].
+pub type AccountId = <Env as Environment>::AccountId;
+
+/// The balance type of [`Env`].
+pub type Balance = <Env as Environment>::Balance;
+
+/// The block number type of [`Env`].
+pub type BlockNumber = <Env as Environment>::BlockNumber;
+
+/// The timestamp type of [`Env`], in milliseconds.
+pub type Timestamp = <Env as Environment>::Timestamp;
+
+/// How far the off-chain environment's clock moves with each block, in milliseconds.
+/// The clock and the block number both start at zero.
+pub const BLOCK_TIME: Timestamp = 6;
+
+/// The six named accounts of the off-chain environment.
+///
+/// They are the accounts of `ink_env::test::default_accounts`, renamed after the
+/// development accounts of a node (`django` is `dave`, `frank` is `ferdie`) so unit and
+/// end-to-end tests tell the same story. `alice` is the default caller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Accounts {
+    pub alice: AccountId,
+    pub bob: AccountId,
+    pub charlie: AccountId,
+    pub dave: AccountId,
+    pub eve: AccountId,
+    pub ferdie: AccountId,
+}
+
+impl Accounts {
+    /// All six accounts, `alice` first.
+    pub fn all(&self) -> [AccountId; 6] {
+        [
+            self.alice,
+            self.bob,
+            self.charlie,
+            self.dave,
+            self.eve,
+            self.ferdie,
+        ]
+    }
+}
+
+/// Returns the named accounts.
+pub fn accounts() -> Accounts {
+    let defaults = test::default_accounts::<Env>();
+    Accounts {
+        alice: defaults.alice,
+        bob: defaults.bob,
+        charlie: defaults.charlie,
+        dave: defaults.django,
+        eve: defaults.eve,
+        ferdie: defaults.frank,
+    }
+}
+
+/// Returns the account of learner number `index`.
+///
+/// Learner accounts are derived from `index` alone, so a test that needs a classroom of
+/// participants gets the same accounts on every run, and none of them collide with the
+/// named accounts.
+pub fn learner(index: u32) -> AccountId {
+    let mut id = [0u8; 32];
+    id[..8].copy_from_slice(b"learner:");
+    id[8..12].copy_from_slice(&index.to_be_bytes());
+    AccountId::from(id)
+}
+
+/// Returns the accounts of learners `0..count`.
+pub fn learners(count: u32) -> Vec<AccountId> {
+    (0..count).map(learner).collect()
+}
+
+/// Sets the free balance of `account` to `amount`.
+pub fn fund(account: AccountId, amount: Balance) {
+    test::set_account_balance::<Env>(account, amount);
+}
+
+/// Sets the free balance of each of `accounts` to `amount`.
+pub fn fund_all(accounts: &[AccountId], amount: Balance) {
+    for account in accounts {
+        fund(*account, amount);
+    }
+}
+
+/// Sets the free balance of the contract under test to `amount`.
+pub fn fund_contract(amount: Balance) {
+    fund(test::callee::<Env>(), amount);
+}
+
+/// Returns the free balance of `account`, or zero if it has never been funded.
+pub fn balance_of(account: AccountId) -> Balance {
+    test::get_account_balance::<Env>(account).unwrap_or_default()
+}
+
+/// Makes `account` the caller of the following messages, until changed again.
+pub fn set_caller(account: AccountId) {
+    test::set_caller::<Env>(account);
+}
+
+/// Returns the current caller.
+pub fn caller() -> AccountId {
+    ink_env::caller::<Env>()
+}
+
+/// Makes `account` the caller until the returned guard is dropped, then restores the
+/// previous caller.
+#[must_use = "the previous caller is restored as soon as the guard is dropped"]
+pub fn as_caller(account: AccountId) -> CallerGuard {
+    let previous = caller();
+    set_caller(account);
+    CallerGuard { previous }
+}
+
+/// Runs `f` with `account` as the caller, then restores the previous caller.
+pub fn with_caller<R>(account: AccountId, f: impl FnOnce() -> R) -> R {
+    let _guard = as_caller(account);
+    f()
+}
+
+/// Restores the caller that was active before [`as_caller`] when dropped.
+#[derive(Debug)]
+pub struct CallerGuard {
+    previous: AccountId,
+}
+
+impl Drop for CallerGuard {
+    fn drop(&mut self) {
+        set_caller(self.previous);
+    }
+}
+
+/// Advances blocks until the block number is `block`.
+///
+/// ink! 3's off-chain environment cannot set the block number or the clock, only
+/// advance both by one block, so this panics if `block` has already passed.
+pub fn set_block_number(block: BlockNumber) {
+    let now = ink_env::block_number::<Env>();
+    assert!(
+        block >= now,
+        "block {} has passed; it is block {}",
+        block,
+        now
+    );
+    for _ in now..block {
+        test::advance_block::<Env>();
+    }
+}
+
+/// Advances blocks until the block timestamp is `timestamp`.
+///
+/// Panics if `timestamp` has already passed or is not a multiple of [`BLOCK_TIME`],
+/// as the clock cannot get there; tests of deadlines pick times such as
+/// `10 * BLOCK_TIME`.
+pub fn set_block_timestamp(timestamp: Timestamp) {
+    let now = ink_env::block_timestamp::<Env>();
+    assert!(
+        timestamp.is_multiple_of(BLOCK_TIME),
+        "the clock moves {} ms per block and never reads {}",
+        BLOCK_TIME,
+        timestamp
+    );
+    assert!(
+        timestamp >= now,
+        "time {} has passed; it is {}",
+        timestamp,
+        now
+    );
+    for _ in 0..(timestamp - now) / BLOCK_TIME {
+        test::advance_block::<Env>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_lang as ink;
+
+    #[ink::test]
+    fn accounts_match_default_accounts() {
+        let defaults = test::default_accounts::<Env>();
+        let accounts = accounts();
+        assert_eq!(accounts.alice, defaults.alice);
+        assert_eq!(accounts.dave, defaults.django);
+        assert_eq!(accounts.ferdie, defaults.frank);
+        assert_eq!(caller(), accounts.alice);
+    }
+
+    #[ink::test]
+    fn learners_are_deterministic_and_distinct() {
+        let named = accounts().all();
+        let mut generated = learners(50);
+        assert_eq!(generated, learners(50));
+        assert!(generated.iter().all(|learner| !named.contains(learner)));
+        generated.sort();
+        generated.dedup();
+        assert_eq!(generated.len(), 50);
+    }
+
+    #[ink::test]
+    fn funding_sets_balances() {
+        let accounts = accounts();
+        fund_all(&[accounts.bob, accounts.charlie], 500);
+        assert_eq!(balance_of(accounts.bob), 500);
+        assert_eq!(balance_of(accounts.charlie), 500);
+        assert_eq!(balance_of(learner(0)), 0);
+
+        fund_contract(42);
+        assert_eq!(balance_of(test::callee::<Env>()), 42);
+    }
+
+    #[ink::test]
+    fn guard_restores_previous_caller() {
+        let accounts = accounts();
+        {
+            let _bob = as_caller(accounts.bob);
+            assert_eq!(caller(), accounts.bob);
+            {
+                let _eve = as_caller(accounts.eve);
+                assert_eq!(caller(), accounts.eve);
+            }
+            assert_eq!(caller(), accounts.bob);
+        }
+        assert_eq!(caller(), accounts.alice);
+    }
+
+    #[ink::test]
+    fn with_caller_returns_closure_result() {
+        let accounts = accounts();
+        assert_eq!(with_caller(accounts.charlie, caller), accounts.charlie);
+        assert_eq!(caller(), accounts.alice);
+    }
+
+    #[ink::test]
+    fn blocks_and_time_advance_together() {
+        set_block_timestamp(5 * BLOCK_TIME);
+        assert_eq!(ink_env::block_timestamp::<Env>(), 30);
+        assert_eq!(ink_env::block_number::<Env>(), 5);
+        set_block_number(7);
+        assert_eq!(ink_env::block_timestamp::<Env>(), 7 * BLOCK_TIME);
+        set_block_timestamp(7 * BLOCK_TIME);
+        assert_eq!(ink_env::block_number::<Env>(), 7);
+    }
+
+    #[ink::test]
+    #[should_panic(expected = "never reads 100")]
+    fn unreachable_times_are_refused() {
+        set_block_timestamp(100);
+    }
+}
@@ -0,0 +1,68 @@
+// crates/test-fixtures/tests/examples.rs
+//
+// Fails when an example using these fixtures still uses ink! 3.0-rc4 APIs.
+//
+// The fixtures are built against ink! 3.4, so an rc4 contract that imports them
+// cannot compile. The examples have no manifests and are never built here, so this
+// reads their sources for the rc4 APIs that were removed or renamed in 3.4.
+
+use std::{fs, path::Path};
+
+/// rc4 APIs with no 3.4 counterpart under the same path.
+const RC4_APIS: &[&str] = &[
+    "ink_storage::collections",
+    "ink_storage::Lazy",
+    "lazy::Lazy",
+    "ReturnType",
+];
+
+/// Returns whether a `build_call` in `source` sets the callee directly, as in rc4,
+/// instead of through `call_type`.
+fn calls_callee_directly(source: &str) -> bool {
+    source.match_indices("build_call::<").any(|(start, _)| {
+        let rest = &source[start..];
+        rest.find("()")
+            .is_some_and(|end| rest[end + 2..].trim_start().starts_with(".callee("))
+    })
+}
+
+#[test]
+fn examples_using_the_fixtures_are_ink_3_4() {
+    let examples = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../../examples"));
+    let mut checked = 0;
+    for dir in ["demo-contracts", "mocks"] {
+        let files = fs::read_dir(examples.join(dir))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rs"));
+        for path in files {
+            let source = fs::read_to_string(&path).unwrap();
+            if !source.contains("astranet_test_fixtures") {
+                continue;
+            }
+            for api in RC4_APIS {
+                assert!(
+                    !source.contains(api),
+                    "{} uses the fixtures but still uses `{}`",
+                    path.display(),
+                    api
+                );
+            }
+            assert!(
+                !calls_callee_directly(&source),
+                "{} uses the fixtures but still sets a build_call's callee without `call_type`",
+                path.display()
+            );
+            checked += 1;
+        }
+    }
+    assert!(checked > 0, "no example uses the fixtures");
+}
+
+#[test]
+fn direct_callee_is_detected() {
+    let rc4 = "build_call::<Environment>()\n    .callee(token)\n    .gas_limit(0)";
+    let ported = "build_call::<Environment>()\n    .call_type(Call::new().callee(token))";
+    assert!(calls_callee_directly(rc4));
+    assert!(!calls_callee_directly(ported));
+}
@@ -0,0 +1,78 @@
+// crates/txbuilder/src/bridge.rs
+//
+// The JSON interface. A request names the operation in `op` and nests its input
+// under `request`:
+//
+//     { "op": "build_transfer_call", "request": { "transaction": {...}, "dest": "bob",
+//                                                 "value": "1000000000000" } }
+//     { "op": "build_instantiate", "request": { "transaction": {...}, "code": "0x...",
+//                                               "data": "0x9bae9d5e", "gas_limit": {...} } }
+//     { "op": "attach_signature", "request": { "unsigned": {...}, "signature": "0x..." } }
+//
+// The answer is what the operation returns (see `schema`), with `attach_signature`'s
+// `Signed` under `signed` as a built transaction's, or `{ "error": "..." }`.
+//
+// `txbuilder_handle` exposes `handle` to C, for a native Node module: it takes a
+// NUL-terminated UTF-8 string and returns one that the caller hands back to
+// `txbuilder_free`.
+
+use crate::{
+    attach_signature, build_instantiate, build_transfer_call, Attach, Built, Instantiate, Transfer,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::ffi::{c_char, CStr, CString};
+
+#[derive(Deserialize)]
+#[serde(tag = "op", content = "request", rename_all = "snake_case")]
+enum Request {
+    BuildTransferCall(Transfer),
+    BuildInstantiate(Instantiate),
+    AttachSignature(Attach),
+}
+
+/// Answers a JSON request.
+pub fn handle(request: &str) -> String {
+    let answer = serde_json::from_str(request)
+        .map_err(|err| format!("invalid request: {}", err))
+        .and_then(|request| match request {
+            Request::BuildTransferCall(transfer) => to_json(build_transfer_call(&transfer)?),
+            Request::BuildInstantiate(instantiate) => to_json(build_instantiate(&instantiate)?),
+            Request::AttachSignature(attach) => to_json(Built::Signed(attach_signature(&attach)?)),
+        });
+    answer
+        .unwrap_or_else(|err| json!({ "error": err }))
+        .to_string()
+}
+
+fn to_json(answer: impl serde::Serialize) -> Result<Value, String> {
+    Ok(serde_json::to_value(answer).expect("answers serialize"))
+}
+
+/// `handle` for C callers.
+///
+/// # Safety
+///
+/// `request` must point to a NUL-terminated string that stays valid for the call.
+/// The returned string must be released with `txbuilder_free`, once.
+#[no_mangle]
+pub unsafe extern "C" fn txbuilder_handle(request: *const c_char) -> *mut c_char {
+    let answer = match CStr::from_ptr(request).to_str() {
+        Ok(request) => handle(request),
+        Err(_) => json!({ "error": "the request is not UTF-8" }).to_string(),
+    };
+    // JSON escapes NUL inside strings, so the answer has none.
+    CString::new(answer).expect("no NUL in JSON").into_raw()
+}
+
+/// Releases a string returned by `txbuilder_handle`.
+///
+/// # Safety
+///
+/// `answer` must come from `txbuilder_handle` and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn txbuilder_free(answer: *mut c_char) {
+    if !answer.is_null() {
+        drop(CString::from_raw(answer));
+    }
+}
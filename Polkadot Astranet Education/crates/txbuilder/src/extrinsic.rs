@@ -0,0 +1,144 @@
+// crates/txbuilder/src/extrinsic.rs
+//
+// The layout of a signed extrinsic (format version 4), as the node decodes it:
+//
+//     Compact(length) ++ 0x84 ++ address ++ signature ++ extra ++ call
+//
+// `0x84` is the version with the "signed" bit set, the address is a
+// `MultiAddress::Id`, and the signature a `MultiSignature`. `extra` is what the
+// runtime's signed extensions carry in the extrinsic, in the order the runtime lists
+// them; for `substrate-contracts-node`:
+//
+//     CheckNonZeroSender, CheckSpecVersion, CheckTxVersion, CheckGenesis   nothing
+//     CheckMortality                                                         Era
+//     CheckNonce                                                             Compact<u32>
+//     CheckWeight                                                            nothing
+//     ChargeTransactionPayment                                               Compact<u128> tip
+//     CheckMetadataHash, on newer runtimes                                   u8 mode
+//
+// The signer signs the call and `extra` followed by what the extensions only sign
+// ("additional signed"): the spec and transaction versions, the genesis hash, the
+// hash of the block the mortality starts at (the genesis hash for an immortal
+// transaction) and, with `CheckMetadataHash`, the optional metadata hash. A payload
+// over 256 bytes is signed as its BLAKE2-256 hash.
+
+use crate::{Chain, Mortality, Transaction};
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use scale::{Compact, Encode};
+
+/// The signed bit and extrinsic format version.
+const SIGNED_V4: u8 = 0x80 | 4;
+
+/// Payloads longer than this are signed as their hash.
+const MAX_UNHASHED_PAYLOAD: usize = 256;
+
+pub fn blake2_256(bytes: &[u8]) -> [u8; 32] {
+    Blake2b::<U32>::digest(bytes).into()
+}
+
+/// Encodes the era of a transaction: `0x00` for an immortal one, or two bytes
+/// holding the period's power of two and the block's phase within it.
+pub fn era(mortality: Option<&Mortality>) -> Vec<u8> {
+    let Some(mortality) = mortality else {
+        return vec![0];
+    };
+    let period = mortality
+        .period
+        .checked_next_power_of_two()
+        .unwrap_or(1 << 16);
+    let period = period.clamp(4, 1 << 16);
+    let phase = mortality.block % period;
+    let quantize_factor = (period >> 12).max(1);
+    let quantized_phase = phase / quantize_factor * quantize_factor;
+    let low = (period.trailing_zeros() - 1).clamp(1, 15) as u16;
+    let high = (quantized_phase / quantize_factor) as u16;
+    (low | (high << 4)).to_le_bytes().to_vec()
+}
+
+/// The data the signed extensions carry in the extrinsic.
+pub fn extra(transaction: &Transaction) -> Vec<u8> {
+    let mut extra = era(transaction.mortality.as_ref());
+    Compact(transaction.nonce).encode_to(&mut extra);
+    Compact(transaction.tip).encode_to(&mut extra);
+    if transaction.chain.check_metadata_hash {
+        // `Mode::Disabled`
+        extra.push(0);
+    }
+    extra
+}
+
+/// The data the signed extensions only sign.
+pub fn additional(transaction: &Transaction) -> Vec<u8> {
+    let chain: &Chain = &transaction.chain;
+    let mut additional = Vec::new();
+    chain.spec_version.encode_to(&mut additional);
+    chain.transaction_version.encode_to(&mut additional);
+    chain.genesis_hash.encode_to(&mut additional);
+    let era_start = match &transaction.mortality {
+        Some(mortality) => mortality.block_hash,
+        None => chain.genesis_hash,
+    };
+    era_start.encode_to(&mut additional);
+    if chain.check_metadata_hash {
+        None::<[u8; 32]>.encode_to(&mut additional);
+    }
+    additional
+}
+
+/// What the signer signs for `call` with `extra` and `additional`.
+pub fn signing_payload(call: &[u8], extra: &[u8], additional: &[u8]) -> Vec<u8> {
+    let payload = [call, extra, additional].concat();
+    if payload.len() > MAX_UNHASHED_PAYLOAD {
+        blake2_256(&payload).to_vec()
+    } else {
+        payload
+    }
+}
+
+/// The signed extrinsic of `call` from `signer`, with its encoded `MultiSignature`
+/// and `extra`.
+pub fn signed(signer: [u8; 32], signature: &[u8], extra: &[u8], call: &[u8]) -> Vec<u8> {
+    let mut body = vec![SIGNED_V4, 0];
+    body.extend_from_slice(&signer);
+    body.extend_from_slice(signature);
+    body.extend_from_slice(extra);
+    body.extend_from_slice(call);
+    let mut extrinsic = Compact(body.len() as u32).encode();
+    extrinsic.extend(body);
+    extrinsic
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mortality(period: u64, block: u64) -> Mortality {
+        Mortality {
+            period,
+            block,
+            block_hash: [0; 32],
+        }
+    }
+
+    #[test]
+    fn eras_encode_as_substrate_does() {
+        assert_eq!(era(None), [0x00]);
+        // `Era::mortal(64, 42)`: period 2^6, phase 42.
+        assert_eq!(era(Some(&mortality(64, 42))), [0xa5, 0x02]);
+        // Periods round up to a power of two, at least 4.
+        assert_eq!(era(Some(&mortality(50, 42))), [0xa5, 0x02]);
+        assert_eq!(era(Some(&mortality(1, 5))), [0x11, 0x00]);
+        // Past 4096 blocks the phase is quantized.
+        assert_eq!(
+            era(Some(&mortality(1 << 16, 1_000_000))),
+            era(Some(&mortality(1 << 16, 1_000_000 + 3)))
+        );
+    }
+
+    #[test]
+    fn long_payloads_are_hashed() {
+        assert_eq!(signing_payload(&[1], &[2], &[3]), [1, 2, 3]);
+        let code = vec![0; 300];
+        assert_eq!(signing_payload(&code, &[], &[]), blake2_256(&code));
+    }
+}
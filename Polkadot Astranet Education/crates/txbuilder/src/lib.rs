@@ -0,0 +1,161 @@
+// crates/txbuilder/src/lib.rs
+//
+// Building transactions for the course website, so its JavaScript does not encode
+// SCALE itself. The website sends JSON describing a native token transfer or a
+// contract deployment (see `schema`) and gets back either:
+//
+// - a signed extrinsic, when the request names a development account's secret URI,
+//   ready for `author_submitExtrinsic`:
+//
+//       { "signed": { "extrinsic": "0x...", "hash": "0x..." } }
+//
+// - or, when it names an account, the payload for a wallet to sign, which
+//   `attach_signature` then turns into the signed extrinsic:
+//
+//       { "unsigned": { "signer": "5F...", "scheme": "sr25519", "call": "0x...",
+//                       "extra": "0x...", "signing_payload": "0x..." } }
+//
+// Nothing here talks to a node: the request carries the nonce, the chain's genesis
+// hash and versions, and the index of the pallet called, which the website already
+// reads from the node. `bridge` takes and returns the JSON as strings, in Rust or
+// through a C ABI for a native module.
+
+pub mod bridge;
+pub mod extrinsic;
+pub mod schema;
+
+pub use schema::{
+    Attach, Built, Chain, GasLimit, Instantiate, Mortality, Pallets, Signed, Signer, Transaction,
+    Transfer, Unsigned,
+};
+
+use deployer::Weight;
+use repl::{
+    keystore::{KeyPair, Scheme},
+    value::parse_account,
+};
+use scale::{Compact, Encode};
+use subxt::tx::Signer as _;
+use subxt_signer::sr25519;
+
+/// The index of `transfer_keep_alive` in `pallet_balances`.
+const TRANSFER_KEEP_ALIVE: u8 = 3;
+/// The index of `instantiate_with_code` in `pallet_contracts`.
+const INSTANTIATE_WITH_CODE: u8 = 7;
+
+/// Builds `Balances::transfer_keep_alive` of `transfer.value` to `transfer.dest`.
+pub fn build_transfer_call(transfer: &Transfer) -> Result<Built, String> {
+    let pallet = transfer
+        .transaction
+        .chain
+        .pallets
+        .balances
+        .ok_or("a transfer needs `chain.pallets.balances`")?;
+    let mut call = vec![pallet, TRANSFER_KEEP_ALIVE];
+    // `MultiAddress::Id`
+    call.push(0);
+    transfer.dest.encode_to(&mut call);
+    Compact(transfer.value).encode_to(&mut call);
+    build(&transfer.transaction, call)
+}
+
+/// Builds `Contracts::instantiate_with_code`, uploading `instantiate.code` and
+/// calling the constructor in `instantiate.data`.
+pub fn build_instantiate(instantiate: &Instantiate) -> Result<Built, String> {
+    let pallet = instantiate
+        .transaction
+        .chain
+        .pallets
+        .contracts
+        .ok_or("instantiating needs `chain.pallets.contracts`")?;
+    let mut call = vec![pallet, INSTANTIATE_WITH_CODE];
+    Compact(instantiate.value).encode_to(&mut call);
+    Weight {
+        ref_time: instantiate.gas_limit.ref_time,
+        proof_size: instantiate.gas_limit.proof_size,
+    }
+    .encode_to(&mut call);
+    instantiate
+        .storage_deposit_limit
+        .map(Compact)
+        .encode_to(&mut call);
+    instantiate.code.encode_to(&mut call);
+    instantiate.data.encode_to(&mut call);
+    instantiate.salt.encode_to(&mut call);
+    build(&instantiate.transaction, call)
+}
+
+/// Completes an unsigned extrinsic with a signature of its signing payload. An
+/// sr25519 signature is checked against the signer; an ECDSA one cannot be, as the
+/// account id is only a hash of the public key, so a bad one fails at the node.
+pub fn attach_signature(attach: &Attach) -> Result<Signed, String> {
+    let unsigned = &attach.unsigned;
+    let signer = parse_account(&unsigned.signer)?;
+    let mut signature = Vec::new();
+    match unsigned.scheme {
+        Scheme::Sr25519 => {
+            let bytes: [u8; 64] = attach.signature[..].try_into().map_err(|_| {
+                format!(
+                    "expected a 64-byte signature, got {} bytes",
+                    attach.signature.len()
+                )
+            })?;
+            let valid = sr25519::verify(
+                &sr25519::Signature(bytes),
+                &unsigned.signing_payload,
+                &sr25519::PublicKey(signer),
+            );
+            if !valid {
+                return Err("the signature is not the signer's signature of the payload".to_owned());
+            }
+            subxt::utils::MultiSignature::Sr25519(bytes).encode_to(&mut signature);
+        }
+        Scheme::Ecdsa => {
+            let bytes: [u8; 65] = attach.signature[..].try_into().map_err(|_| {
+                format!(
+                    "expected a 65-byte signature, got {} bytes",
+                    attach.signature.len()
+                )
+            })?;
+            subxt::utils::MultiSignature::Ecdsa(bytes).encode_to(&mut signature);
+        }
+    }
+    Ok(signed(extrinsic::signed(
+        signer,
+        &signature,
+        &unsigned.extra,
+        &unsigned.call,
+    )))
+}
+
+/// Signs `call` with the request's signer, or returns what to sign.
+fn build(transaction: &Transaction, call: Vec<u8>) -> Result<Built, String> {
+    let extra = extrinsic::extra(transaction);
+    let payload = extrinsic::signing_payload(&call, &extra, &extrinsic::additional(transaction));
+    match &transaction.signer {
+        Signer::Suri(suri) => {
+            let pair = KeyPair::from_suri(transaction.scheme, suri)?;
+            let signature = pair.sign(&payload).encode();
+            Ok(Built::Signed(signed(extrinsic::signed(
+                pair.account_id(),
+                &signature,
+                &extra,
+                &call,
+            ))))
+        }
+        Signer::Account(account) => Ok(Built::Unsigned(Unsigned {
+            signer: transaction.chain.address(*account),
+            scheme: transaction.scheme,
+            call,
+            extra,
+            signing_payload: payload,
+        })),
+    }
+}
+
+fn signed(extrinsic: Vec<u8>) -> Signed {
+    Signed {
+        hash: extrinsic::blake2_256(&extrinsic),
+        extrinsic,
+    }
+}
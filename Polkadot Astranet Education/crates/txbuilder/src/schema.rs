@@ -0,0 +1,268 @@
+// crates/txbuilder/src/schema.rs
+//
+// The JSON the website sends and gets back. This is the crate's stable interface:
+// fields may be added, with defaults, but are not renamed, removed or retyped, and
+// `tests/schema.rs` pins the output. Conventions follow the indexer's API:
+//
+// - accounts are read as an SS58 address, 32 bytes of hex or a development account
+//   name such as `alice`, and written as SS58 addresses of the chain;
+// - bytes (hashes, code, call data, signatures) are `0x`-prefixed hex;
+// - balances are decimal strings, as they do not fit in a JavaScript number; nonces,
+//   versions and weights are numbers.
+
+use deployer::network::{ss58_encode, GENERIC_PREFIX};
+use repl::{
+    keystore::Scheme,
+    value::{parse_account, parse_hex, to_hex},
+};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+/// What the signing payload commits to besides the call, read from the node by the
+/// caller: `genesisHash`, `runtimeVersion` and the pallets' indices in
+/// `metadata.pallets`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Chain {
+    #[serde(with = "hash")]
+    pub genesis_hash: [u8; 32],
+    pub spec_version: u32,
+    pub transaction_version: u32,
+    #[serde(default = "generic_prefix")]
+    pub ss58_prefix: u16,
+    pub pallets: Pallets,
+    /// Whether the runtime has the `CheckMetadataHash` extension. It is sent
+    /// disabled, with no hash.
+    #[serde(default)]
+    pub check_metadata_hash: bool,
+}
+
+fn generic_prefix() -> u16 {
+    GENERIC_PREFIX
+}
+
+/// The index of each pallet in the runtime, which varies between runtimes. Only the
+/// pallet of the call being built is needed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pallets {
+    #[serde(default)]
+    pub balances: Option<u8>,
+    #[serde(default)]
+    pub contracts: Option<u8>,
+}
+
+/// Who signs, and so whether the result is a signed extrinsic or a payload to sign
+/// elsewhere.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Signer {
+    /// A secret URI such as `//Alice`, for development accounts: the extrinsic is
+    /// signed here.
+    Suri(String),
+    /// The account that signs, with a wallet extension or hardware key: the signing
+    /// payload is returned.
+    #[serde(with = "account")]
+    Account([u8; 32]),
+}
+
+/// How long the transaction stays valid: `period` blocks from `block`, whose hash
+/// is `block_hash`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Mortality {
+    pub period: u64,
+    pub block: u64,
+    #[serde(with = "hash")]
+    pub block_hash: [u8; 32],
+}
+
+/// What every transaction needs besides its call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Transaction {
+    pub chain: Chain,
+    pub signer: Signer,
+    /// `sr25519` unless given.
+    #[serde(default = "sr25519")]
+    pub scheme: Scheme,
+    pub nonce: u32,
+    #[serde(default, with = "amount")]
+    pub tip: u128,
+    /// Valid forever unless given.
+    #[serde(default)]
+    pub mortality: Option<Mortality>,
+}
+
+fn sr25519() -> Scheme {
+    Scheme::Sr25519
+}
+
+/// A transfer of `value` of the chain's native token to `dest`, which leaves the
+/// sender's account alive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Transfer {
+    pub transaction: Transaction,
+    #[serde(with = "account")]
+    pub dest: [u8; 32],
+    #[serde(with = "amount")]
+    pub value: u128,
+}
+
+/// Computation time and proof size, as a dry run reports them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GasLimit {
+    pub ref_time: u64,
+    pub proof_size: u64,
+}
+
+/// Uploading `code` and instantiating it with the constructor call `data`. The gas
+/// limit and deposit come from a dry run, such as the deployer's.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Instantiate {
+    pub transaction: Transaction,
+    #[serde(with = "bytes")]
+    pub code: Vec<u8>,
+    #[serde(with = "bytes")]
+    pub data: Vec<u8>,
+    #[serde(default, with = "amount")]
+    pub value: u128,
+    pub gas_limit: GasLimit,
+    #[serde(default, with = "optional_amount")]
+    pub storage_deposit_limit: Option<u128>,
+    #[serde(default, with = "bytes")]
+    pub salt: Vec<u8>,
+}
+
+/// An extrinsic ready to submit with `author_submitExtrinsic`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Signed {
+    #[serde(with = "bytes")]
+    pub extrinsic: Vec<u8>,
+    /// The hash the node reports the extrinsic by.
+    #[serde(with = "hash")]
+    pub hash: [u8; 32],
+}
+
+/// An extrinsic waiting for its signature: `signer` signs `signing_payload` with
+/// `scheme`, and `attach_signature` completes it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Unsigned {
+    pub signer: String,
+    pub scheme: Scheme,
+    #[serde(with = "bytes")]
+    pub call: Vec<u8>,
+    /// The signed extensions' data, which goes into the extrinsic.
+    #[serde(with = "bytes")]
+    pub extra: Vec<u8>,
+    /// The bytes to sign: the call, `extra` and the data the extensions only sign,
+    /// hashed if that is over 256 bytes.
+    #[serde(with = "bytes")]
+    pub signing_payload: Vec<u8>,
+}
+
+/// What building a transaction gives.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Built {
+    Signed(Signed),
+    Unsigned(Unsigned),
+}
+
+/// A signature made elsewhere for `unsigned`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Attach {
+    pub unsigned: Unsigned,
+    #[serde(with = "bytes")]
+    pub signature: Vec<u8>,
+}
+
+impl Chain {
+    pub fn address(&self, account: [u8; 32]) -> String {
+        ss58_encode(self.ss58_prefix, account)
+    }
+}
+
+mod hash {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(hash: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(hash))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        let bytes = super::bytes::deserialize(deserializer)?;
+        bytes.try_into().map_err(|bytes: Vec<u8>| {
+            D::Error::custom(format!("expected 32 bytes, got {}", bytes.len()))
+        })
+    }
+}
+
+mod bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        parse_hex(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+mod account {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(account: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&ss58_encode(GENERIC_PREFIX, *account))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        parse_account(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+mod amount {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(amount: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&amount.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse()
+            .map_err(|_| D::Error::custom(format!("`{}` is not an amount", text)))
+    }
+}
+
+mod optional_amount {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        amount: &Option<u128>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match amount {
+            Some(amount) => super::amount::serialize(amount, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<u128>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(text) => text
+                .parse()
+                .map(Some)
+                .map_err(|_| D::Error::custom(format!("`{}` is not an amount", text))),
+            None => Ok(None),
+        }
+    }
+}
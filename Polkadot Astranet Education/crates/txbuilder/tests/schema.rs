@@ -0,0 +1,205 @@
+// crates/txbuilder/tests/schema.rs
+//
+// Pins the JSON the website relies on, through the bridge as the website calls it.
+// The encodings are spelled out byte by byte: a change here is a change of the
+// schema or of the extrinsic format, and the website must follow.
+
+use astranet_txbuilder::{bridge::handle, extrinsic::blake2_256};
+use repl::value::{parse_hex, to_hex};
+use serde_json::{json, Value};
+use subxt_signer::{sr25519, SecretUri};
+
+const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+const ALICE_HEX: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+const BOB_HEX: &str = "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48";
+
+fn transaction(signer: Value) -> Value {
+    json!({
+        "chain": {
+            "genesis_hash": format!("0x{}", "11".repeat(32)),
+            "spec_version": 100,
+            "transaction_version": 1,
+            "pallets": { "balances": 4, "contracts": 7 },
+        },
+        "signer": signer,
+        "nonce": 5,
+    })
+}
+
+fn transfer(signer: Value) -> Value {
+    json!({
+        "op": "build_transfer_call",
+        "request": {
+            "transaction": transaction(signer),
+            "dest": "bob",
+            "value": "1000000000000",
+        },
+    })
+}
+
+fn call(request: &Value) -> Value {
+    serde_json::from_str(&handle(&request.to_string())).unwrap()
+}
+
+fn hex(value: &Value) -> Vec<u8> {
+    parse_hex(value.as_str().unwrap()).unwrap()
+}
+
+/// The call, extra and additional signed data of `transfer`.
+fn transfer_parts() -> (String, String, String) {
+    let call = format!(
+        "0403{}{}{}",
+        "00",           // MultiAddress::Id
+        BOB_HEX,        // dest
+        "070010a5d4e8"  // Compact(10^12)
+    );
+    let extra = "00".to_owned() // immortal
+        + "14" // Compact(nonce 5)
+        + "00"; // Compact(tip 0)
+    let additional = "64000000".to_owned() // spec_version
+        + "01000000" // transaction_version
+        + &"11".repeat(32) // genesis hash
+        + &"11".repeat(32); // era start: the genesis for an immortal transaction
+    (call, extra, additional)
+}
+
+#[test]
+fn transfers_for_external_signing_give_the_payload() {
+    let (call_hex, extra, additional) = transfer_parts();
+    let unsigned = call(&transfer(json!({ "account": "alice" })));
+    assert_eq!(
+        unsigned,
+        json!({
+            "unsigned": {
+                "signer": ALICE,
+                "scheme": "sr25519",
+                "call": format!("0x{}", call_hex),
+                "extra": format!("0x{}", extra),
+                "signing_payload": format!("0x{}{}{}", call_hex, extra, additional),
+            }
+        })
+    );
+
+    // A wallet signs the payload, and the bridge attaches the signature.
+    let alice = sr25519::Keypair::from_uri(&"//Alice".parse::<SecretUri>().unwrap()).unwrap();
+    let payload = hex(&unsigned["unsigned"]["signing_payload"]);
+    let signature = alice.sign(&payload);
+    let signed = call(&json!({
+        "op": "attach_signature",
+        "request": { "unsigned": unsigned["unsigned"], "signature": to_hex(&signature.0) },
+    }));
+    let extrinsic = hex(&signed["signed"]["extrinsic"]);
+    let body = format!(
+        "84{}{}{}{}{}",
+        "00",      // MultiAddress::Id
+        ALICE_HEX, // signer
+        "01",      // MultiSignature::Sr25519
+        &to_hex(&signature.0)[2..],
+        extra + &call_hex
+    );
+    // Compact(length): 1 + 33 + 65 + 3 + 41 = 143 bytes, two bytes of compact.
+    assert_eq!(to_hex(&extrinsic), format!("0x3d02{}", body));
+    assert_eq!(hex(&signed["signed"]["hash"]), blake2_256(&extrinsic));
+
+    let tampered = call(&json!({
+        "op": "attach_signature",
+        "request": { "unsigned": unsigned["unsigned"], "signature": to_hex(&[0; 64]) },
+    }));
+    assert_eq!(
+        tampered,
+        json!({ "error": "the signature is not the signer's signature of the payload" })
+    );
+}
+
+#[test]
+fn transfers_from_a_secret_uri_come_signed() {
+    let (call_hex, extra, additional) = transfer_parts();
+    let signed = call(&transfer(json!({ "suri": "//Alice" })));
+    let extrinsic = hex(&signed["signed"]["extrinsic"]);
+    assert_eq!(hex(&signed["signed"]["hash"]), blake2_256(&extrinsic));
+
+    // Signatures are randomized, so check it rather than compare it.
+    let prefix = parse_hex(&format!("0x3d028400{}01", ALICE_HEX)).unwrap();
+    assert_eq!(extrinsic[..prefix.len()], prefix[..]);
+    let signature: [u8; 64] = extrinsic[prefix.len()..prefix.len() + 64]
+        .try_into()
+        .unwrap();
+    assert_eq!(
+        to_hex(&extrinsic[prefix.len() + 64..]),
+        format!("0x{}{}", extra, call_hex)
+    );
+    let payload = parse_hex(&format!("0x{}{}{}", call_hex, extra, additional)).unwrap();
+    assert!(sr25519::verify(
+        &sr25519::Signature(signature),
+        payload,
+        &sr25519::PublicKey(
+            parse_hex(&format!("0x{}", ALICE_HEX))
+                .unwrap()
+                .try_into()
+                .unwrap()
+        ),
+    ));
+}
+
+#[test]
+fn instantiations_encode_every_argument() {
+    let mut transaction = transaction(json!({ "account": ALICE }));
+    transaction["tip"] = json!("1");
+    transaction["mortality"] = json!({
+        "period": 64,
+        "block": 42,
+        "block_hash": format!("0x{}", "22".repeat(32)),
+    });
+    let unsigned = call(&json!({
+        "op": "build_instantiate",
+        "request": {
+            "transaction": transaction,
+            "code": "0x0061736d",
+            "data": "0x9bae9d5e",
+            "gas_limit": { "ref_time": 1000, "proof_size": 64 },
+            "storage_deposit_limit": "5",
+            "salt": "0x01",
+        },
+    }));
+    let call_hex = "0707".to_owned()
+        + "00" // Compact(value 0)
+        + "a10f" + "0101" // Weight { ref_time: Compact(1000), proof_size: Compact(64) }
+        + "01" + "14" // Some(Compact(5))
+        + "10" + "0061736d" // code
+        + "10" + "9bae9d5e" // constructor call data
+        + "04" + "01"; // salt
+    let extra = "a502".to_owned() // Era::Mortal(64, 42)
+        + "14" // Compact(nonce 5)
+        + "04"; // Compact(tip 1)
+    let additional = "6400000001000000".to_owned() + &"11".repeat(32) + &"22".repeat(32); // era start
+    assert_eq!(
+        unsigned["unsigned"]["call"],
+        json!(format!("0x{}", call_hex))
+    );
+    assert_eq!(unsigned["unsigned"]["extra"], json!(format!("0x{}", extra)));
+    assert_eq!(
+        unsigned["unsigned"]["signing_payload"],
+        json!(format!("0x{}{}{}", call_hex, extra, additional))
+    );
+}
+
+#[test]
+fn bad_requests_are_answered_with_errors() {
+    let mut request = transfer(json!({ "account": "alice" }));
+    request["request"]["transaction"]["chain"]["pallets"] = json!({ "contracts": 7 });
+    assert_eq!(
+        call(&request),
+        json!({ "error": "a transfer needs `chain.pallets.balances`" })
+    );
+
+    request["request"]["value"] = json!(1000);
+    assert!(call(&request)["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("invalid request: invalid type: integer `1000`, expected a string"));
+
+    assert!(call(&json!({ "op": "submit" }))["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("invalid request: unknown variant `submit`"));
+}
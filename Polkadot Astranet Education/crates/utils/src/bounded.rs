@@ -0,0 +1,358 @@
+// crates/utils/src/bounded.rs
+//
+// Vectors and strings with a maximum length, checked where they enter a contract.
+//
+// Every byte a contract stores costs its users storage deposit, and every byte it
+// returns must fit the runtime's return buffer. A `String` or `Vec` taken straight
+// from a message argument has neither limit, so one caller can make a proposal
+// description or token URI too large to read back. `BoundedVec<T, N>` and
+// `BoundedString<N>` can only be built with at most `N` elements or bytes:
+//
+//     let description = BoundedString::<MAX_DESCRIPTION_LEN>::try_from(description)?;
+//
+// fails with `BoundedError::LimitExceeded` before anything is written, and a
+// contract that stores the bounded type can rely on the limit from then on. Both
+// encode exactly like the unbounded type, so their metadata and wire format are
+// those of a `Vec<T>` or `String`; decoding a value over the limit fails.
+
+use core::ops::Deref;
+use ink_prelude::{string::String, vec::Vec};
+use ink_primitives::Key;
+use ink_storage::traits::{KeyPtr, PackedAllocate, PackedLayout, SpreadAllocate, SpreadLayout};
+
+/// Errors returned when building a bounded value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum BoundedError {
+    /// Returned if the value is longer than the bound.
+    LimitExceeded,
+}
+
+/// A `Vec<T>` of at most `N` elements.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoundedVec<T, const N: usize>(Vec<T>);
+
+/// A `String` of at most `N` bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoundedString<const N: usize>(String);
+
+impl<T, const N: usize> BoundedVec<T, N> {
+    /// Most elements the vector can hold.
+    pub const BOUND: usize = N;
+
+    /// Creates an empty vector.
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Appends `value`, or returns `LimitExceeded` if the vector is full.
+    pub fn try_push(&mut self, value: T) -> Result<(), BoundedError> {
+        if self.0.len() >= N {
+            return Err(BoundedError::LimitExceeded);
+        }
+        self.0.push(value);
+        Ok(())
+    }
+
+    /// Removes and returns the last element, if any.
+    pub fn pop(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+
+    /// Returns the inner vector.
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<const N: usize> BoundedString<N> {
+    /// Most bytes the string can hold.
+    pub const BOUND: usize = N;
+
+    /// Creates an empty string.
+    pub const fn new() -> Self {
+        Self(String::new())
+    }
+
+    /// Returns the inner string.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl<T, const N: usize> Default for BoundedVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Default for BoundedString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> TryFrom<Vec<T>> for BoundedVec<T, N> {
+    type Error = BoundedError;
+
+    fn try_from(value: Vec<T>) -> Result<Self, BoundedError> {
+        if value.len() > N {
+            return Err(BoundedError::LimitExceeded);
+        }
+        Ok(Self(value))
+    }
+}
+
+impl<const N: usize> TryFrom<String> for BoundedString<N> {
+    type Error = BoundedError;
+
+    fn try_from(value: String) -> Result<Self, BoundedError> {
+        if value.len() > N {
+            return Err(BoundedError::LimitExceeded);
+        }
+        Ok(Self(value))
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for BoundedString<N> {
+    type Error = BoundedError;
+
+    fn try_from(value: &str) -> Result<Self, BoundedError> {
+        Self::try_from(String::from(value))
+    }
+}
+
+impl<T, const N: usize> Deref for BoundedVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<const N: usize> Deref for BoundedString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> From<BoundedVec<T, N>> for Vec<T> {
+    fn from(value: BoundedVec<T, N>) -> Self {
+        value.0
+    }
+}
+
+impl<const N: usize> From<BoundedString<N>> for String {
+    fn from(value: BoundedString<N>) -> Self {
+        value.0
+    }
+}
+
+impl<T: scale::Encode, const N: usize> scale::Encode for BoundedVec<T, N> {
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+
+    fn encode_to<O: scale::Output + ?Sized>(&self, dest: &mut O) {
+        self.0.encode_to(dest)
+    }
+}
+
+impl<const N: usize> scale::Encode for BoundedString<N> {
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+
+    fn encode_to<O: scale::Output + ?Sized>(&self, dest: &mut O) {
+        self.0.encode_to(dest)
+    }
+}
+
+impl<T: scale::Encode, const N: usize> scale::EncodeLike for BoundedVec<T, N> {}
+impl<T: scale::Encode, const N: usize> scale::EncodeLike<Vec<T>> for BoundedVec<T, N> {}
+impl<const N: usize> scale::EncodeLike for BoundedString<N> {}
+impl<const N: usize> scale::EncodeLike<String> for BoundedString<N> {}
+
+impl<T: scale::Decode, const N: usize> scale::Decode for BoundedVec<T, N> {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        Self::try_from(Vec::<T>::decode(input)?)
+            .map_err(|_| "BoundedVec longer than its bound".into())
+    }
+}
+
+impl<const N: usize> scale::Decode for BoundedString<N> {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        Self::try_from(String::decode(input)?)
+            .map_err(|_| "BoundedString longer than its bound".into())
+    }
+}
+
+/// Implements the storage traits of a bounded type by forwarding to the
+/// unbounded type it wraps; values are only ever stored within their bound.
+macro_rules! forward_storage_traits {
+    ($inner:ty, [$($generics:tt)*], $bounded:ty, [$($bounds:tt)*]) => {
+        impl<$($generics)*> SpreadLayout for $bounded
+        where
+            $inner: SpreadLayout,
+            $($bounds)*
+        {
+            const FOOTPRINT: u64 = <$inner as SpreadLayout>::FOOTPRINT;
+            const REQUIRES_DEEP_CLEAN_UP: bool = <$inner as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP;
+
+            fn pull_spread(ptr: &mut KeyPtr) -> Self {
+                Self(<$inner as SpreadLayout>::pull_spread(ptr))
+            }
+
+            fn push_spread(&self, ptr: &mut KeyPtr) {
+                SpreadLayout::push_spread(&self.0, ptr)
+            }
+
+            fn clear_spread(&self, ptr: &mut KeyPtr) {
+                SpreadLayout::clear_spread(&self.0, ptr)
+            }
+        }
+
+        impl<$($generics)*> PackedLayout for $bounded
+        where
+            $inner: PackedLayout,
+            $($bounds)*
+        {
+            fn pull_packed(&mut self, at: &Key) {
+                PackedLayout::pull_packed(&mut self.0, at)
+            }
+
+            fn push_packed(&self, at: &Key) {
+                PackedLayout::push_packed(&self.0, at)
+            }
+
+            fn clear_packed(&self, at: &Key) {
+                PackedLayout::clear_packed(&self.0, at)
+            }
+        }
+
+        impl<$($generics)*> SpreadAllocate for $bounded
+        where
+            $inner: SpreadAllocate,
+            $($bounds)*
+        {
+            fn allocate_spread(ptr: &mut KeyPtr) -> Self {
+                Self(<$inner as SpreadAllocate>::allocate_spread(ptr))
+            }
+        }
+
+        impl<$($generics)*> PackedAllocate for $bounded
+        where
+            $inner: PackedAllocate,
+            $($bounds)*
+        {
+            fn allocate_packed(&mut self, at: &Key) {
+                PackedAllocate::allocate_packed(&mut self.0, at)
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<$($generics)*> ink_storage::traits::StorageLayout for $bounded
+        where
+            $inner: ink_storage::traits::StorageLayout,
+            $($bounds)*
+        {
+            fn layout(key_ptr: &mut KeyPtr) -> ink_metadata::layout::Layout {
+                <$inner as ink_storage::traits::StorageLayout>::layout(key_ptr)
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<$($generics)*> scale_info::TypeInfo for $bounded
+        where
+            $inner: scale_info::TypeInfo + 'static,
+            $($bounds)*
+        {
+            type Identity = $inner;
+
+            fn type_info() -> scale_info::Type {
+                <$inner as scale_info::TypeInfo>::type_info()
+            }
+        }
+    };
+}
+
+forward_storage_traits!(Vec<T>, [T, const N: usize], BoundedVec<T, N>, [T: scale::Codec,]);
+forward_storage_traits!(String, [const N: usize], BoundedString<N>, []);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::{Decode, Encode};
+
+    #[test]
+    fn vec_at_the_bound_is_accepted() {
+        let vec = BoundedVec::<u8, 3>::try_from(vec![1, 2, 3]).unwrap();
+        assert_eq!(&*vec, &[1, 2, 3]);
+        assert_eq!(
+            BoundedVec::<u8, 3>::try_from(vec![1, 2, 3, 4]),
+            Err(BoundedError::LimitExceeded)
+        );
+    }
+
+    #[test]
+    fn push_stops_at_the_bound() {
+        let mut vec = BoundedVec::<u8, 2>::new();
+        assert_eq!(vec.try_push(1), Ok(()));
+        assert_eq!(vec.try_push(2), Ok(()));
+        assert_eq!(vec.try_push(3), Err(BoundedError::LimitExceeded));
+        assert_eq!(vec.len(), 2);
+        assert_eq!(vec.pop(), Some(2));
+        assert_eq!(vec.try_push(3), Ok(()));
+        assert_eq!(vec.into_inner(), vec![1, 3]);
+    }
+
+    #[test]
+    fn zero_bound_only_holds_empty_values() {
+        assert!(BoundedVec::<u8, 0>::try_from(Vec::new()).is_ok());
+        assert!(BoundedString::<0>::try_from("").is_ok());
+        assert_eq!(
+            BoundedString::<0>::try_from("a"),
+            Err(BoundedError::LimitExceeded)
+        );
+    }
+
+    #[test]
+    fn string_bound_counts_bytes() {
+        // "é" is two bytes in UTF-8.
+        assert!(BoundedString::<4>::try_from("éé").is_ok());
+        assert_eq!(
+            BoundedString::<4>::try_from("ééa"),
+            Err(BoundedError::LimitExceeded)
+        );
+        assert_eq!(&*BoundedString::<5>::try_from("abcde").unwrap(), "abcde");
+    }
+
+    #[test]
+    fn encoding_matches_the_unbounded_type() {
+        let string = BoundedString::<8>::try_from("token").unwrap();
+        assert_eq!(string.encode(), String::from("token").encode());
+        let vec = BoundedVec::<u32, 2>::try_from(vec![7, 9]).unwrap();
+        assert_eq!(vec.encode(), vec![7u32, 9].encode());
+    }
+
+    #[test]
+    fn decoding_enforces_the_bound() {
+        let bytes = String::from("abcd").encode();
+        assert!(BoundedString::<4>::decode(&mut &bytes[..]).is_ok());
+        assert!(BoundedString::<3>::decode(&mut &bytes[..]).is_err());
+        let bytes = vec![1u8, 2, 3].encode();
+        assert!(BoundedVec::<u8, 3>::decode(&mut &bytes[..]).is_ok());
+        assert!(BoundedVec::<u8, 2>::decode(&mut &bytes[..]).is_err());
+    }
+
+    #[ink_lang::test]
+    fn bounded_values_survive_storage() {
+        let string = BoundedString::<16>::try_from("ipfs://badge").unwrap();
+        let key = Key::from([0x01; 32]);
+        ink_storage::traits::push_spread_root(&string, &key);
+        let pulled: BoundedString<16> = ink_storage::traits::pull_spread_root(&key);
+        assert_eq!(pulled, string);
+    }
+}
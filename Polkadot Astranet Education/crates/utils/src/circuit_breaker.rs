@@ -0,0 +1,207 @@
+// crates/utils/src/circuit_breaker.rs
+//
+// An emergency stop that a designated guardian can pull.
+//
+// Contracts holding user funds embed a `CircuitBreaker` and call `ensure_running()`
+// at the top of every message that moves funds in normal operation. When something
+// goes wrong the guardian trips the breaker and those messages fail until it is
+// reset. Messages that must keep working during an emergency, such as letting users
+// withdraw their own deposits, simply skip the check or call `ensure_tripped()`.
+//
+// The guardian is usually a multisig or the DAO, not a single hot key, and can be
+// a different account from the contract owner.
+
+use ink_env::AccountId;
+use ink_storage::traits::{PackedLayout, SpreadAllocate, SpreadLayout};
+
+/// Errors returned by the circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum CircuitBreakerError {
+    /// Returned if the caller is not the guardian.
+    NotGuardian,
+    /// Returned if an operation requires the breaker to be running but it is tripped.
+    Tripped,
+    /// Returned if an operation requires the breaker to be tripped but it is running.
+    NotTripped,
+}
+
+/// A guardian-controlled emergency stop.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    SpreadLayout,
+    PackedLayout,
+    SpreadAllocate,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct CircuitBreaker {
+    guardian: AccountId,
+    tripped: bool,
+}
+
+impl CircuitBreaker {
+    /// Creates a running breaker controlled by `guardian`.
+    pub fn new(guardian: AccountId) -> Self {
+        Self {
+            guardian,
+            tripped: false,
+        }
+    }
+
+    /// Returns the account allowed to trip and reset the breaker.
+    pub fn guardian(&self) -> AccountId {
+        self.guardian
+    }
+
+    /// Returns whether the breaker is tripped.
+    pub fn is_tripped(&self) -> bool {
+        self.tripped
+    }
+
+    /// Fails if the breaker is tripped. Guard normal operations with this.
+    pub fn ensure_running(&self) -> Result<(), CircuitBreakerError> {
+        if self.tripped {
+            return Err(CircuitBreakerError::Tripped);
+        }
+        Ok(())
+    }
+
+    /// Fails unless the breaker is tripped. Guard emergency-only operations with this.
+    pub fn ensure_tripped(&self) -> Result<(), CircuitBreakerError> {
+        if !self.tripped {
+            return Err(CircuitBreakerError::NotTripped);
+        }
+        Ok(())
+    }
+
+    /// Trips the breaker. Only the guardian may do this.
+    pub fn trip(&mut self, caller: AccountId) -> Result<(), CircuitBreakerError> {
+        self.ensure_guardian(caller)?;
+        self.ensure_running()?;
+        self.tripped = true;
+        Ok(())
+    }
+
+    /// Resets a tripped breaker. Only the guardian may do this.
+    pub fn reset(&mut self, caller: AccountId) -> Result<(), CircuitBreakerError> {
+        self.ensure_guardian(caller)?;
+        self.ensure_tripped()?;
+        self.tripped = false;
+        Ok(())
+    }
+
+    /// Hands the guardian role to `new_guardian`. Only the guardian may do this.
+    pub fn set_guardian(
+        &mut self,
+        caller: AccountId,
+        new_guardian: AccountId,
+    ) -> Result<(), CircuitBreakerError> {
+        self.ensure_guardian(caller)?;
+        self.guardian = new_guardian;
+        Ok(())
+    }
+
+    fn ensure_guardian(&self, caller: AccountId) -> Result<(), CircuitBreakerError> {
+        if caller != self.guardian {
+            return Err(CircuitBreakerError::NotGuardian);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUARDIAN: [u8; 32] = [0x01; 32];
+    const OTHER: [u8; 32] = [0x02; 32];
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(AccountId::from(GUARDIAN))
+    }
+
+    #[test]
+    fn starts_running() {
+        let breaker = breaker();
+        assert!(!breaker.is_tripped());
+        assert_eq!(breaker.ensure_running(), Ok(()));
+        assert_eq!(
+            breaker.ensure_tripped(),
+            Err(CircuitBreakerError::NotTripped)
+        );
+    }
+
+    #[test]
+    fn guardian_can_trip_and_reset() {
+        let mut breaker = breaker();
+        assert_eq!(breaker.trip(AccountId::from(GUARDIAN)), Ok(()));
+        assert!(breaker.is_tripped());
+        assert_eq!(breaker.ensure_running(), Err(CircuitBreakerError::Tripped));
+        assert_eq!(breaker.ensure_tripped(), Ok(()));
+
+        assert_eq!(breaker.reset(AccountId::from(GUARDIAN)), Ok(()));
+        assert_eq!(breaker.ensure_running(), Ok(()));
+    }
+
+    #[test]
+    fn only_guardian_can_trip() {
+        let mut breaker = breaker();
+        assert_eq!(
+            breaker.trip(AccountId::from(OTHER)),
+            Err(CircuitBreakerError::NotGuardian)
+        );
+        assert!(!breaker.is_tripped());
+    }
+
+    #[test]
+    fn only_guardian_can_reset() {
+        let mut breaker = breaker();
+        breaker.trip(AccountId::from(GUARDIAN)).unwrap();
+        assert_eq!(
+            breaker.reset(AccountId::from(OTHER)),
+            Err(CircuitBreakerError::NotGuardian)
+        );
+        assert!(breaker.is_tripped());
+    }
+
+    #[test]
+    fn cannot_trip_twice_or_reset_running() {
+        let mut breaker = breaker();
+        assert_eq!(
+            breaker.reset(AccountId::from(GUARDIAN)),
+            Err(CircuitBreakerError::NotTripped)
+        );
+        breaker.trip(AccountId::from(GUARDIAN)).unwrap();
+        assert_eq!(
+            breaker.trip(AccountId::from(GUARDIAN)),
+            Err(CircuitBreakerError::Tripped)
+        );
+    }
+
+    #[test]
+    fn guardian_role_can_be_handed_over() {
+        let mut breaker = breaker();
+        assert_eq!(
+            breaker.set_guardian(AccountId::from(OTHER), AccountId::from(OTHER)),
+            Err(CircuitBreakerError::NotGuardian)
+        );
+        assert_eq!(
+            breaker.set_guardian(AccountId::from(GUARDIAN), AccountId::from(OTHER)),
+            Ok(())
+        );
+        assert_eq!(
+            breaker.trip(AccountId::from(GUARDIAN)),
+            Err(CircuitBreakerError::NotGuardian)
+        );
+        assert_eq!(breaker.trip(AccountId::from(OTHER)), Ok(()));
+    }
+}
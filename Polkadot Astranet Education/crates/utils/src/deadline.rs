@@ -0,0 +1,126 @@
+// crates/utils/src/deadline.rs
+//
+// A point in time after which something is no longer allowed.
+//
+// Auctions close, crowdfunds end and vesting cliffs pass at a block timestamp, and
+// every one of those checks has the same off-by-one question: is the deadline itself
+// still open? `Deadline` answers it once. The deadline is inclusive: an action at
+// exactly `at` is still on time, and the deadline has expired from `at + 1` on.
+//
+// Timestamps are milliseconds since the Unix epoch, as returned by
+// `self.env().block_timestamp()`. Like the other components, `Deadline` takes the
+// current time as an argument instead of reading it from the environment:
+//
+//     if self.ends.is_expired(self.env().block_timestamp()) {
+//         return Err(Error::AuctionClosed);
+//     }
+
+use ink_storage::traits::{PackedLayout, SpreadAllocate, SpreadLayout};
+
+/// Timestamps as used by the default contracts environment.
+pub type Timestamp = u64;
+
+/// An inclusive deadline, in milliseconds since the Unix epoch.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    scale::Encode,
+    scale::Decode,
+    SpreadLayout,
+    PackedLayout,
+    SpreadAllocate,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct Deadline(Timestamp);
+
+impl Deadline {
+    /// Creates a deadline at the absolute timestamp `at`.
+    pub fn at(at: Timestamp) -> Self {
+        Self(at)
+    }
+
+    /// Creates a deadline `duration` milliseconds after `now`, or `None` if that
+    /// lies beyond the largest representable timestamp.
+    pub fn after(now: Timestamp, duration: Timestamp) -> Option<Self> {
+        now.checked_add(duration).map(Self)
+    }
+
+    /// Returns the timestamp of the deadline.
+    pub fn timestamp(&self) -> Timestamp {
+        self.0
+    }
+
+    /// Returns `true` if the deadline passed before `now`.
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        now > self.0
+    }
+
+    /// Returns the milliseconds left until the deadline, or zero once it has expired.
+    pub fn remaining(&self, now: Timestamp) -> Timestamp {
+        self.0.saturating_sub(now)
+    }
+
+    /// Returns a deadline `duration` milliseconds later, saturating at the largest
+    /// representable timestamp.
+    pub fn extended_by(&self, duration: Timestamp) -> Self {
+        Self(self.0.saturating_add(duration))
+    }
+}
+
+impl From<Timestamp> for Deadline {
+    fn from(at: Timestamp) -> Self {
+        Self(at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::{Decode, Encode};
+
+    #[test]
+    fn deadline_is_inclusive() {
+        let deadline = Deadline::at(1_000);
+        assert!(!deadline.is_expired(999));
+        assert!(!deadline.is_expired(1_000));
+        assert!(deadline.is_expired(1_001));
+    }
+
+    #[test]
+    fn remaining_counts_down_to_zero() {
+        let deadline = Deadline::at(1_000);
+        assert_eq!(deadline.remaining(400), 600);
+        assert_eq!(deadline.remaining(1_000), 0);
+        assert_eq!(deadline.remaining(5_000), 0);
+    }
+
+    #[test]
+    fn after_rejects_overflow() {
+        assert_eq!(Deadline::after(100, 50), Some(Deadline::at(150)));
+        assert_eq!(Deadline::after(Timestamp::MAX, 1), None);
+    }
+
+    #[test]
+    fn extended_by_saturates() {
+        assert_eq!(Deadline::at(100).extended_by(50), Deadline::at(150));
+        assert_eq!(
+            Deadline::at(Timestamp::MAX - 1).extended_by(10),
+            Deadline::at(Timestamp::MAX)
+        );
+    }
+
+    #[test]
+    fn encodes_as_plain_timestamp() {
+        let deadline = Deadline::at(1_700_000_000_000);
+        assert_eq!(deadline.encode(), 1_700_000_000_000u64.encode());
+        assert_eq!(Deadline::decode(&mut &deadline.encode()[..]), Ok(deadline));
+    }
+}
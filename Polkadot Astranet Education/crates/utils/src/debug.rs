@@ -0,0 +1,210 @@
+// crates/utils/src/debug.rs
+//
+// Structured debug lines for tracing contract calls.
+//
+// `trace!` formats an event name and `key=value` pairs into one line and hands it to
+// `ink_env::debug_println!`:
+//
+//     astranet_utils::trace!("transfer", from, to, value = amount);
+//     // transfer from=0xd43593c7…a56da27d to=0x8eaf0415…a6e26a48 value=250
+//
+// A bare key traces the variable of that name. Accounts and hashes print as full
+// hex (shortened above), strings are quoted, and numbers and booleans print as-is,
+// so lines can be grepped and split on spaces.
+//
+// Lines are only built when tracing is enabled, which is in `std` builds (unit
+// tests, where `ink_env::test::recorded_debug_messages()` returns them) and in
+// Wasm builds with this crate's `debug` feature. Contracts forward it like `std`:
+//
+//     [features]
+//     debug = ["astranet-utils/debug"]
+//
+// and build with `cargo contract build --features debug`. Everywhere else the
+// calls compile to nothing, so they can stay in release code. On a node, debug
+// lines are only produced by dry-runs (RPC calls, not extrinsics) and are logged
+// when the node runs with `-lruntime::contracts=debug`, as `devnet --debug` does;
+// `cargo contract call` without `--execute` also prints them.
+
+use core::fmt::Write;
+use ink_env::{AccountId, Hash};
+use ink_prelude::string::String;
+
+/// Whether `trace!` emits lines in this build.
+pub const ENABLED: bool = cfg!(any(feature = "debug", feature = "std"));
+
+/// Traces an event with `key=value` fields, if tracing is enabled.
+///
+/// Each field is `key = expression`, or a bare `key` to trace the variable of that
+/// name. Values must implement [`TraceValue`](crate::debug::TraceValue).
+#[macro_export]
+macro_rules! trace {
+    (@value $key:ident = $value:expr) => {
+        $value
+    };
+    (@value $key:ident) => {
+        $key
+    };
+    ($event:expr $(, $key:ident $(= $value:expr)?)* $(,)?) => {
+        if $crate::debug::ENABLED {
+            $crate::debug::emit(
+                &$crate::debug::Line::new($event)
+                    $(.field(stringify!($key), &$crate::trace!(@value $key $(= $value)?)))*,
+            );
+        }
+    };
+}
+
+/// A value that can appear in a trace line.
+pub trait TraceValue {
+    /// Appends the value to `out`, without spaces.
+    fn write_to(&self, out: &mut String);
+}
+
+macro_rules! impl_display {
+    ($($ty:ty),*) => {
+        $(
+            impl TraceValue for $ty {
+                fn write_to(&self, out: &mut String) {
+                    let _ = write!(out, "{}", self);
+                }
+            }
+        )*
+    };
+}
+
+impl_display!(bool, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl TraceValue for str {
+    fn write_to(&self, out: &mut String) {
+        let _ = write!(out, "{:?}", self);
+    }
+}
+
+impl TraceValue for String {
+    fn write_to(&self, out: &mut String) {
+        self.as_str().write_to(out)
+    }
+}
+
+impl TraceValue for [u8] {
+    fn write_to(&self, out: &mut String) {
+        out.push_str("0x");
+        for byte in self {
+            let _ = write!(out, "{:02x}", byte);
+        }
+    }
+}
+
+impl TraceValue for AccountId {
+    fn write_to(&self, out: &mut String) {
+        AsRef::<[u8]>::as_ref(self).write_to(out)
+    }
+}
+
+impl TraceValue for Hash {
+    fn write_to(&self, out: &mut String) {
+        AsRef::<[u8]>::as_ref(self).write_to(out)
+    }
+}
+
+impl<T: TraceValue> TraceValue for Option<T> {
+    fn write_to(&self, out: &mut String) {
+        match self {
+            Some(value) => value.write_to(out),
+            None => out.push_str("none"),
+        }
+    }
+}
+
+impl<T: TraceValue + ?Sized> TraceValue for &T {
+    fn write_to(&self, out: &mut String) {
+        (**self).write_to(out)
+    }
+}
+
+/// One trace line: an event name followed by ` key=value` fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    text: String,
+}
+
+impl Line {
+    /// Starts a line for `event`.
+    pub fn new(event: &str) -> Self {
+        Self {
+            text: String::from(event),
+        }
+    }
+
+    /// Appends ` key=value`.
+    pub fn field<T: TraceValue + ?Sized>(mut self, key: &str, value: &T) -> Self {
+        self.text.push(' ');
+        self.text.push_str(key);
+        self.text.push('=');
+        value.write_to(&mut self.text);
+        self
+    }
+
+    /// Returns the line.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Writes `line` to the contract's debug buffer.
+#[cfg_attr(not(any(feature = "debug", feature = "std")), allow(unused_variables))]
+pub fn emit(line: &Line) {
+    ink_env::debug_println!("{}", line.as_str());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_prelude::vec::Vec;
+
+    #[test]
+    fn fields_are_formatted_as_key_value_pairs() {
+        let line = Line::new("deposit")
+            .field("amount", &250u128)
+            .field("paused", &false)
+            .field("memo", "two words")
+            .field("limit", &None::<u32>)
+            .field("delta", &Some(-3i64));
+        assert_eq!(
+            line.as_str(),
+            r#"deposit amount=250 paused=false memo="two words" limit=none delta=-3"#
+        );
+    }
+
+    #[test]
+    fn accounts_and_hashes_are_hex() {
+        let line = Line::new("approve")
+            .field("owner", &AccountId::from([0xab; 32]))
+            .field("hash", &Hash::from([0x01; 32]));
+        let ab = "ab".repeat(32);
+        let one = "01".repeat(32);
+        assert_eq!(
+            line.as_str(),
+            format!("approve owner=0x{} hash=0x{}", ab, one)
+        );
+    }
+
+    #[test]
+    fn trace_records_a_debug_message() {
+        let to = AccountId::from([0x02; 32]);
+        let amount = 5u128;
+        crate::trace!("transfer", to, value = amount + 1);
+        crate::trace!("paused");
+
+        let messages: Vec<_> = ink_env::test::recorded_debug_messages()
+            .into_iter()
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                format!("transfer to=0x{} value=6\n", "02".repeat(32)),
+                String::from("paused\n"),
+            ]
+        );
+    }
+}
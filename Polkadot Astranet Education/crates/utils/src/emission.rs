@@ -0,0 +1,233 @@
+// crates/utils/src/emission.rs
+//
+// Emission schedules: how many reward tokens a contract releases per block.
+//
+// Three curves cover most reward programs:
+//
+// - `Constant` releases the same amount every block, forever.
+// - `Halving` starts at some rate and halves it every `period` blocks, the way
+//   Bitcoin's block subsidy does, so the total ever released is bounded by twice
+//   what the first period releases.
+// - `Piecewise` lists the rate from each of a few blocks on, for programs with a
+//   launch boost or a planned wind-down.
+//
+// Blocks are counted from the start of the schedule, so a contract stores the block
+// it started at and passes `now - start`. `emitted_between` sums the curve over a
+// range of blocks in time proportional to the number of steps or halvings crossed,
+// never the number of blocks, so contracts can settle rewards lazily however long
+// ago they last did.
+
+use crate::{rate_limiter::BlockNumber, safe_erc20::Balance};
+use ink_prelude::vec::Vec;
+use ink_primitives::KeyPtr;
+use ink_storage::traits::{PackedLayout, SpreadAllocate, SpreadLayout};
+
+/// Most steps a piecewise curve may have, keeping settlement cheap.
+pub const MAX_STEPS: usize = 16;
+
+/// From block `from` of the schedule on, `per_block` tokens are emitted each block.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct EmissionStep {
+    pub from: BlockNumber,
+    pub per_block: Balance,
+}
+
+/// How many tokens are emitted at each block of a schedule.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub enum EmissionCurve {
+    /// `per_block` tokens at every block.
+    Constant { per_block: Balance },
+    /// `initial_per_block` tokens at every block of the first `period` blocks,
+    /// half as many in the next `period` blocks, and so on.
+    Halving {
+        initial_per_block: Balance,
+        period: BlockNumber,
+    },
+    /// The rate of the last step starting at or before the block, or nothing
+    /// before the first step. Steps are ordered by strictly increasing `from`.
+    Piecewise(Vec<EmissionStep>),
+}
+
+impl Default for EmissionCurve {
+    fn default() -> Self {
+        EmissionCurve::Constant { per_block: 0 }
+    }
+}
+
+// Enums cannot derive `SpreadAllocate`; like `Option`, the curve is allocated as
+// its default value in the cells it would occupy.
+impl SpreadAllocate for EmissionCurve {
+    fn allocate_spread(ptr: &mut KeyPtr) -> Self {
+        ptr.advance_by(<Self as SpreadLayout>::FOOTPRINT);
+        Self::default()
+    }
+}
+
+impl EmissionCurve {
+    /// Returns whether the curve can be used: a halving period is at least one
+    /// block, and piecewise steps are few and strictly ordered.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            EmissionCurve::Constant { .. } => true,
+            EmissionCurve::Halving { period, .. } => *period > 0,
+            EmissionCurve::Piecewise(steps) => {
+                steps.len() <= MAX_STEPS && steps.windows(2).all(|w| w[0].from < w[1].from)
+            }
+        }
+    }
+
+    /// Returns the tokens emitted at `block`.
+    pub fn rate_at(&self, block: BlockNumber) -> Balance {
+        match self {
+            EmissionCurve::Constant { per_block } => *per_block,
+            EmissionCurve::Halving {
+                initial_per_block,
+                period,
+            } => initial_per_block.checked_shr(block / period).unwrap_or(0),
+            EmissionCurve::Piecewise(steps) => steps
+                .iter()
+                .rev()
+                .find(|step| step.from <= block)
+                .map_or(0, |step| step.per_block),
+        }
+    }
+
+    /// Returns the tokens emitted at blocks `from..to`, saturating at
+    /// `Balance::MAX`. The range is empty if `to <= from`.
+    pub fn emitted_between(&self, from: BlockNumber, to: BlockNumber) -> Balance {
+        if to <= from {
+            return 0;
+        }
+        match self {
+            EmissionCurve::Constant { per_block } => {
+                per_block.saturating_mul(Balance::from(to - from))
+            }
+            EmissionCurve::Halving { period, .. } => {
+                let mut total: Balance = 0;
+                let mut block = from;
+                while block < to {
+                    let rate = self.rate_at(block);
+                    if rate == 0 {
+                        break;
+                    }
+                    let era_end = (block / period + 1).saturating_mul(*period).min(to);
+                    total =
+                        total.saturating_add(rate.saturating_mul(Balance::from(era_end - block)));
+                    block = era_end;
+                }
+                total
+            }
+            EmissionCurve::Piecewise(steps) => {
+                let mut total: Balance = 0;
+                for (i, step) in steps.iter().enumerate() {
+                    let end = steps.get(i + 1).map_or(BlockNumber::MAX, |next| next.from);
+                    let start = step.from.max(from);
+                    let end = end.min(to);
+                    if start < end {
+                        total = total.saturating_add(
+                            step.per_block.saturating_mul(Balance::from(end - start)),
+                        );
+                    }
+                }
+                total
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(from: BlockNumber, per_block: Balance) -> EmissionStep {
+        EmissionStep { from, per_block }
+    }
+
+    fn sum_block_by_block(curve: &EmissionCurve, from: BlockNumber, to: BlockNumber) -> Balance {
+        (from..to).map(|block| curve.rate_at(block)).sum()
+    }
+
+    #[test]
+    fn constant_emits_the_same_every_block() {
+        let curve = EmissionCurve::Constant { per_block: 5 };
+        assert_eq!(curve.rate_at(0), 5);
+        assert_eq!(curve.rate_at(BlockNumber::MAX), 5);
+        assert_eq!(curve.emitted_between(10, 30), 100);
+        assert_eq!(curve.emitted_between(30, 10), 0);
+    }
+
+    #[test]
+    fn halving_halves_every_period() {
+        let curve = EmissionCurve::Halving {
+            initial_per_block: 100,
+            period: 10,
+        };
+        assert_eq!(curve.rate_at(9), 100);
+        assert_eq!(curve.rate_at(10), 50);
+        assert_eq!(curve.rate_at(25), 25);
+        // 10 * 100 + 10 * 50 + 5 * 25
+        assert_eq!(curve.emitted_between(0, 25), 1_625);
+        assert_eq!(
+            curve.emitted_between(7, 23),
+            sum_block_by_block(&curve, 7, 23)
+        );
+        // 100 halves to zero after seven periods, bounding the total below 2_000.
+        assert_eq!(curve.rate_at(70), 0);
+        assert_eq!(curve.emitted_between(0, BlockNumber::MAX), 1_970);
+    }
+
+    #[test]
+    fn halving_survives_huge_shifts() {
+        let curve = EmissionCurve::Halving {
+            initial_per_block: Balance::MAX,
+            period: 1,
+        };
+        assert_eq!(curve.rate_at(127), 1);
+        assert_eq!(curve.rate_at(128), 0);
+        assert_eq!(curve.rate_at(BlockNumber::MAX), 0);
+        assert_eq!(curve.emitted_between(0, BlockNumber::MAX), Balance::MAX);
+    }
+
+    #[test]
+    fn piecewise_follows_its_steps() {
+        let curve = EmissionCurve::Piecewise(vec![step(10, 30), step(20, 10), step(40, 0)]);
+        assert_eq!(curve.rate_at(9), 0);
+        assert_eq!(curve.rate_at(10), 30);
+        assert_eq!(curve.rate_at(39), 10);
+        assert_eq!(curve.rate_at(40), 0);
+        // 10 * 30 + 20 * 10
+        assert_eq!(curve.emitted_between(0, 100), 500);
+        assert_eq!(
+            curve.emitted_between(15, 25),
+            sum_block_by_block(&curve, 15, 25)
+        );
+        assert_eq!(curve.emitted_between(45, 100), 0);
+    }
+
+    #[test]
+    fn validity() {
+        assert!(EmissionCurve::default().is_valid());
+        assert!(!EmissionCurve::Halving {
+            initial_per_block: 1,
+            period: 0
+        }
+        .is_valid());
+        assert!(EmissionCurve::Piecewise(vec![]).is_valid());
+        assert!(!EmissionCurve::Piecewise(vec![step(5, 1), step(5, 2)]).is_valid());
+        assert!(!EmissionCurve::Piecewise(vec![step(5, 1), step(4, 2)]).is_valid());
+        let too_many = (0..=MAX_STEPS as BlockNumber)
+            .map(|from| step(from, 1))
+            .collect();
+        assert!(!EmissionCurve::Piecewise(too_many).is_valid());
+    }
+}
@@ -0,0 +1,206 @@
+// crates/utils/src/fixed_point.rs
+//
+// Unsigned fixed-point numbers for rates, prices and curves.
+//
+// Contracts cannot use floats (see `math.rs`), and plain integers cannot say "1.5
+// reward tokens per DOT" or "a 20% bonus". `FixedU128` stores such a number as an
+// integer count of 10^-18 units, like Substrate's type of the same name, so 1.5 is
+// `1_500_000_000_000_000_000`. Multiplying it with an amount goes through
+// `mul_div`, which keeps the full 256-bit product before dividing, so the result
+// only fails if it does not fit in a `u128` itself.
+//
+// Every operation rounds down. When the rounding matters, decide which party it
+// should favour and order the operations accordingly: a contract paying out should
+// round its payouts down, as these functions do.
+
+use ink_storage::traits::{PackedLayout, SpreadAllocate, SpreadLayout};
+
+/// The number of `FixedU128` units in one.
+pub const DIV: u128 = 1_000_000_000_000_000_000;
+
+/// Returns `a * b / c`, rounded down, or `None` if `c` is zero or the result does
+/// not fit in a `u128`.
+///
+/// The product is computed in 256 bits, so it may exceed `u128::MAX` as long as
+/// the quotient does not.
+pub fn mul_div(a: u128, b: u128, c: u128) -> Option<u128> {
+    if c == 0 {
+        return None;
+    }
+    if let Some(product) = a.checked_mul(b) {
+        return Some(product / c);
+    }
+    let (high, low) = widening_mul(a, b);
+    if high >= c {
+        return None;
+    }
+    // Long division of the 256-bit product, one bit of `low` at a time. The
+    // remainder starts as `high` and stays below `c`; `carry` is the bit shifted
+    // out of it, in which case it certainly exceeds `c`.
+    let mut remainder = high;
+    let mut quotient = 0u128;
+    for bit in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> bit) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= c {
+            remainder = remainder.wrapping_sub(c);
+            quotient |= 1;
+        }
+    }
+    Some(quotient)
+}
+
+/// Returns the 256-bit product of `a` and `b` as its high and low halves.
+fn widening_mul(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_high, a_low) = (a >> 64, a & MASK);
+    let (b_high, b_low) = (b >> 64, b & MASK);
+    let low_low = a_low * b_low;
+    let low_high = a_low * b_high;
+    let high_low = a_high * b_low;
+    let high_high = a_high * b_high;
+    let middle = (low_low >> 64) + (low_high & MASK) + (high_low & MASK);
+    let low = (low_low & MASK) | (middle << 64);
+    let high = high_high + (low_high >> 64) + (high_low >> 64) + (middle >> 64);
+    (high, low)
+}
+
+/// A non-negative number with 18 decimal places.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    scale::Encode,
+    scale::Decode,
+    SpreadLayout,
+    PackedLayout,
+    SpreadAllocate,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct FixedU128(u128);
+
+impl FixedU128 {
+    /// Returns the number `inner / DIV`.
+    pub const fn from_inner(inner: u128) -> Self {
+        Self(inner)
+    }
+
+    /// Returns the number of `DIV` units.
+    pub const fn into_inner(self) -> u128 {
+        self.0
+    }
+
+    /// Returns one.
+    pub const fn one() -> Self {
+        Self(DIV)
+    }
+
+    /// Returns the whole number `n`, or `None` if it is too large.
+    pub fn from_integer(n: u128) -> Option<Self> {
+        n.checked_mul(DIV).map(Self)
+    }
+
+    /// Returns `numerator / denominator`, rounded down, or `None` if the
+    /// denominator is zero or the result is too large.
+    pub fn from_rational(numerator: u128, denominator: u128) -> Option<Self> {
+        mul_div(numerator, DIV, denominator).map(Self)
+    }
+
+    /// Returns `self + other`, or `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// Returns `self - other`, or `None` if `other` is larger.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    /// Returns `self * other`, rounded down, or `None` on overflow.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        mul_div(self.0, other.0, DIV).map(Self)
+    }
+
+    /// Returns `self * n` as a whole number, rounded down, or `None` on overflow.
+    pub fn checked_mul_int(self, n: u128) -> Option<u128> {
+        mul_div(self.0, n, DIV)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mul_div_small_values() {
+        assert_eq!(mul_div(6, 7, 3), Some(14));
+        assert_eq!(mul_div(10, 10, 3), Some(33));
+        assert_eq!(mul_div(0, u128::MAX, 1), Some(0));
+        assert_eq!(mul_div(1, 1, 0), None);
+    }
+
+    #[test]
+    fn mul_div_keeps_wide_products() {
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX), Some(u128::MAX));
+        assert_eq!(mul_div(1 << 127, 4, 8), Some(1 << 126));
+        assert_eq!(mul_div(u128::MAX, 3, 4), Some(u128::MAX / 4 * 3 + 2));
+        assert_eq!(mul_div(u128::MAX, 2, 1), None);
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX - 1), None);
+    }
+
+    #[test]
+    fn mul_div_agrees_with_narrow_math() {
+        let values = [1, 3, 1_000, DIV, u64::MAX as u128, 1 << 100];
+        for a in values {
+            for b in values {
+                for c in values {
+                    if let Some(product) = a.checked_mul(b) {
+                        assert_eq!(mul_div(a, b, c), Some(product / c), "{} {} {}", a, b, c);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn widening_mul_splits_the_product() {
+        assert_eq!(widening_mul(u128::MAX, u128::MAX), (u128::MAX - 1, 1));
+        assert_eq!(widening_mul(1 << 64, 1 << 64), (1, 0));
+        assert_eq!(widening_mul(12, 34), (0, 408));
+    }
+
+    #[test]
+    fn fixed_arithmetic() {
+        let one_and_a_half = FixedU128::from_rational(3, 2).unwrap();
+        assert_eq!(one_and_a_half.into_inner(), 1_500_000_000_000_000_000);
+        assert_eq!(one_and_a_half.checked_mul_int(1_000), Some(1_500));
+        assert_eq!(
+            one_and_a_half.checked_mul(one_and_a_half),
+            FixedU128::from_rational(9, 4)
+        );
+        assert_eq!(
+            one_and_a_half.checked_sub(FixedU128::one()),
+            FixedU128::from_rational(1, 2)
+        );
+        assert_eq!(FixedU128::one().checked_sub(one_and_a_half), None);
+        assert_eq!(
+            FixedU128::from_integer(2),
+            FixedU128::one().checked_add(FixedU128::one())
+        );
+        // A third cannot be represented exactly and rounds down.
+        assert_eq!(
+            FixedU128::from_rational(1, 3).unwrap().checked_mul_int(3),
+            Some(0)
+        );
+        assert_eq!(FixedU128::from_integer(u128::MAX), None);
+    }
+}
@@ -0,0 +1,115 @@
+// crates/utils/src/hashing.rs
+//
+// Thin wrappers around the hash functions `pallet-contracts` exposes to contracts.
+//
+// Which one to use:
+// - BLAKE2 is what Substrate itself uses (storage keys, account ids, selectors), so
+//   pick `blake2_256` when matching anything the runtime computes.
+// - Keccak-256 is Ethereum's hash; use it to check data signed or hashed by
+//   Ethereum tooling, such as ECDSA signatures from MetaMask.
+// - SHA2-256 interoperates with Bitcoin and most general-purpose libraries.
+//
+// All of them run natively in the node, which is far cheaper than hashing in Wasm.
+
+use ink_env::hash::{Blake2x128, Blake2x256, HashOutput, Keccak256, Sha2x256};
+
+/// Hashes `input` with BLAKE2b-256.
+pub fn blake2_256(input: &[u8]) -> [u8; 32] {
+    hash::<Blake2x256>(input)
+}
+
+/// Hashes `input` with BLAKE2b-128.
+pub fn blake2_128(input: &[u8]) -> [u8; 16] {
+    hash::<Blake2x128>(input)
+}
+
+/// Hashes `input` with Keccak-256.
+pub fn keccak_256(input: &[u8]) -> [u8; 32] {
+    hash::<Keccak256>(input)
+}
+
+/// Hashes `input` with SHA2-256.
+pub fn sha2_256(input: &[u8]) -> [u8; 32] {
+    hash::<Sha2x256>(input)
+}
+
+/// Hashes the SCALE encoding of `value` with BLAKE2b-256.
+///
+/// Hashing a tuple this way avoids ambiguity between e.g. `("ab", "c")` and
+/// `("a", "bc")`, because strings and vectors carry a length prefix.
+pub fn blake2_256_encoded<T: scale::Encode>(value: &T) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    ink_env::hash_encoded::<Blake2x256, T>(value, &mut output);
+    output
+}
+
+/// Hashes `input` with the algorithm `H`.
+pub fn hash<H>(input: &[u8]) -> <H as HashOutput>::Type
+where
+    H: ink_env::hash::CryptoHash,
+{
+    let mut output = <H as HashOutput>::Type::default();
+    ink_env::hash_bytes::<H>(input, &mut output);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn empty_input_vectors() {
+        assert_eq!(
+            hex(&blake2_256(b"")),
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+        );
+        assert_eq!(hex(&blake2_128(b"")), "cae66941d9efbd404e4d88758ea67670");
+        assert_eq!(
+            hex(&keccak_256(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            hex(&sha2_256(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn abc_vectors() {
+        assert_eq!(
+            hex(&keccak_256(b"abc")),
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+        assert_eq!(
+            hex(&sha2_256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn selector_is_blake2_prefix() {
+        assert_eq!(blake2_256(b"flip")[..4], [0x63, 0x3a, 0xa5, 0x51]);
+    }
+
+    #[test]
+    fn encoded_hash_separates_fields() {
+        assert_ne!(
+            blake2_256_encoded(&("ab", "c")),
+            blake2_256_encoded(&("a", "bc"))
+        );
+        assert_eq!(blake2_256(b"abc"), blake2_256(b"abc"));
+    }
+
+    #[test]
+    fn encoded_hash_matches_manual_encoding() {
+        let value = (1u32, true);
+        assert_eq!(
+            blake2_256_encoded(&value),
+            blake2_256(&scale::Encode::encode(&value))
+        );
+    }
+}
@@ -0,0 +1,30 @@
+// crates/utils/src/lib.rs
+//
+// Reusable building blocks for the example contracts.
+//
+// Contracts depend on this crate like any other library:
+//
+//     astranet-utils = { path = "../../crates/utils", default-features = false }
+//
+// and forward their `std` feature to `astranet-utils/std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod bounded;
+pub mod circuit_breaker;
+pub mod deadline;
+pub mod debug;
+pub mod emission;
+pub mod fixed_point;
+pub mod hashing;
+pub mod math;
+pub mod nonces;
+pub mod ownable;
+pub mod pagination;
+pub mod randomness;
+pub mod rate_limiter;
+pub mod safe_erc20;
+pub mod storage_heap;
+pub mod storage_linked_list;
+pub mod storage_version;
+pub mod validation;
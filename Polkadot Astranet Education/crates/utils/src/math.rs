@@ -0,0 +1,65 @@
+// crates/utils/src/math.rs
+//
+// Integer math that contracts cannot get from `core` on stable Rust.
+//
+// Contracts avoid floating point entirely: wasm floats are not guaranteed to be
+// deterministic across nodes, and pallet-contracts rejects code that uses them.
+// Everything here works on `u128`, the width of `Balance`, and rounds down.
+
+/// Returns the integer square root of `n`, the largest `r` with `r * r <= n`.
+///
+/// Uses Newton's method, starting from a power of two no smaller than the root, so
+/// the estimate decreases monotonically and the loop stops as soon as it does not.
+/// It takes at most a handful of iterations for any `u128`.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let bits = 128 - n.leading_zeros();
+    let mut root = 1u128 << bits.div_ceil(2);
+    loop {
+        let next = (root + n / root) / 2;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_values() {
+        let expected = [0, 1, 1, 1, 2, 2, 2, 2, 2, 3, 3];
+        for (n, root) in expected.iter().enumerate() {
+            assert_eq!(isqrt(n as u128), *root, "isqrt({})", n);
+        }
+    }
+
+    #[test]
+    fn rounds_down_between_squares() {
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(17), 4);
+        assert_eq!(isqrt(1_000_000_000_000_000_000), 1_000_000_000);
+        assert_eq!(isqrt(999_999_999_999_999_999), 999_999_999);
+    }
+
+    #[test]
+    fn extremes() {
+        let max_root = u128::from(u64::MAX);
+        assert_eq!(isqrt(u128::MAX), max_root);
+        assert_eq!(isqrt(max_root * max_root), max_root);
+        assert_eq!(isqrt(max_root * max_root - 1), max_root - 1);
+    }
+
+    #[test]
+    fn brackets_the_root() {
+        for n in 0..10_000u128 {
+            let root = isqrt(n);
+            assert!(root * root <= n && n < (root + 1) * (root + 1), "isqrt({})", n);
+        }
+    }
+}
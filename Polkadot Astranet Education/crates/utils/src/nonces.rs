@@ -0,0 +1,126 @@
+// crates/utils/src/nonces.rs
+//
+// Per-account nonces for replay protection.
+//
+// Anything an account signs off-chain and someone else submits (a permit, a relayed
+// meta-transaction, a multisig confirmation) can be submitted twice unless the signed
+// payload includes a nonce the contract consumes. Each account's nonce starts at zero
+// and only ever goes up by one, so every signature is valid exactly once and requests
+// are processed in the order they were signed.
+
+use ink_env::AccountId;
+use ink_storage::{
+    traits::{SpreadAllocate, SpreadLayout},
+    Mapping,
+};
+
+/// Errors returned by `Nonces`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum NonceError {
+    /// Returned if a request carries a nonce other than the account's next one.
+    /// Lower values are replays; higher values skip requests still to come.
+    InvalidNonce { expected: u64 },
+    /// Returned if the account has used every nonce.
+    Exhausted,
+}
+
+/// The next unused nonce of every account.
+#[derive(Debug, Default, SpreadLayout, SpreadAllocate)]
+#[cfg_attr(feature = "std", derive(ink_storage::traits::StorageLayout))]
+pub struct Nonces {
+    next: Mapping<AccountId, u64>,
+}
+
+impl Nonces {
+    /// Creates an empty nonce store; every account starts at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the nonce `account`'s next request must carry.
+    pub fn nonce_of(&self, account: &AccountId) -> u64 {
+        self.next.get(account).unwrap_or(0)
+    }
+
+    /// Consumes and returns `account`'s next nonce.
+    pub fn use_nonce(&mut self, account: &AccountId) -> Result<u64, NonceError> {
+        let current = self.nonce_of(account);
+        let next = current.checked_add(1).ok_or(NonceError::Exhausted)?;
+        self.next.insert(account, &next);
+        Ok(current)
+    }
+
+    /// Consumes `account`'s next nonce if it equals `nonce`, and fails otherwise.
+    ///
+    /// Call this before acting on a signed request, and before any cross-contract
+    /// call, so a re-entrant call cannot replay the same request.
+    pub fn use_checked_nonce(&mut self, account: &AccountId, nonce: u64) -> Result<(), NonceError> {
+        let expected = self.nonce_of(account);
+        if nonce != expected {
+            return Err(NonceError::InvalidNonce { expected });
+        }
+        self.use_nonce(account).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(byte: u8) -> AccountId {
+        AccountId::from([byte; 32])
+    }
+
+    #[ink_lang::test]
+    fn nonces_start_at_zero() {
+        let nonces = Nonces::new();
+        assert_eq!(nonces.nonce_of(&account(1)), 0);
+    }
+
+    #[ink_lang::test]
+    fn use_nonce_is_monotonic() {
+        let mut nonces = Nonces::new();
+        assert_eq!(nonces.use_nonce(&account(1)), Ok(0));
+        assert_eq!(nonces.use_nonce(&account(1)), Ok(1));
+        assert_eq!(nonces.use_nonce(&account(1)), Ok(2));
+        assert_eq!(nonces.nonce_of(&account(1)), 3);
+    }
+
+    #[ink_lang::test]
+    fn nonces_are_per_account() {
+        let mut nonces = Nonces::new();
+        nonces.use_nonce(&account(1)).unwrap();
+        assert_eq!(nonces.nonce_of(&account(2)), 0);
+        assert_eq!(nonces.use_nonce(&account(2)), Ok(0));
+    }
+
+    #[ink_lang::test]
+    fn replayed_nonce_is_rejected() {
+        let mut nonces = Nonces::new();
+        assert_eq!(nonces.use_checked_nonce(&account(1), 0), Ok(()));
+        assert_eq!(
+            nonces.use_checked_nonce(&account(1), 0),
+            Err(NonceError::InvalidNonce { expected: 1 })
+        );
+        assert_eq!(nonces.nonce_of(&account(1)), 1);
+    }
+
+    #[ink_lang::test]
+    fn skipped_nonce_is_rejected() {
+        let mut nonces = Nonces::new();
+        assert_eq!(
+            nonces.use_checked_nonce(&account(1), 1),
+            Err(NonceError::InvalidNonce { expected: 0 })
+        );
+        assert_eq!(nonces.nonce_of(&account(1)), 0);
+    }
+
+    #[ink_lang::test]
+    fn exhausted_nonces_fail() {
+        let mut nonces = Nonces::new();
+        nonces.next.insert(account(1), &u64::MAX);
+        assert_eq!(nonces.use_nonce(&account(1)), Err(NonceError::Exhausted));
+        assert_eq!(nonces.nonce_of(&account(1)), u64::MAX);
+    }
+}
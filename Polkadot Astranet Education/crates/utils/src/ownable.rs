@@ -0,0 +1,279 @@
+// crates/utils/src/ownable.rs
+//
+// Single-owner access control with a two-step handover.
+//
+// Handing ownership straight to a new account is risky: a typo in the address, or
+// an account nobody holds the key for, locks the contract's admin functions forever.
+// Here the current owner only *proposes* a new owner, and ownership moves once that
+// account calls `accept_ownership`, proving it can sign. Until then the current owner
+// stays in charge and can cancel or redirect the handover.
+//
+// ink! events must be declared in the contract, so the state-changing methods return
+// the event payloads below and the contract emits its own event types from them.
+
+use ink_env::AccountId;
+use ink_storage::traits::{PackedLayout, SpreadAllocate, SpreadLayout};
+
+/// Errors returned by `Ownable`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum OwnableError {
+    /// Returned if the caller is not the owner.
+    NotOwner,
+    /// Returned if the caller is not the proposed new owner.
+    NotPendingOwner,
+    /// Returned if no ownership transfer is in progress.
+    NoPendingTransfer,
+}
+
+/// Payload for an `OwnershipTransferStarted` event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnershipTransferStarted {
+    pub previous_owner: AccountId,
+    pub new_owner: AccountId,
+}
+
+/// Payload for an `OwnershipTransferCompleted` event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnershipTransferCompleted {
+    pub previous_owner: AccountId,
+    pub new_owner: AccountId,
+}
+
+/// The owner of a contract and, during a handover, the proposed next owner.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    SpreadLayout,
+    PackedLayout,
+    SpreadAllocate,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct Ownable {
+    owner: AccountId,
+    pending_owner: Option<AccountId>,
+}
+
+impl Ownable {
+    /// Creates an `Ownable` owned by `owner`, usually the constructor's caller.
+    pub fn new(owner: AccountId) -> Self {
+        Self {
+            owner,
+            pending_owner: None,
+        }
+    }
+
+    /// Returns the current owner.
+    pub fn owner(&self) -> AccountId {
+        self.owner
+    }
+
+    /// Returns the proposed new owner, if a handover is in progress.
+    pub fn pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner
+    }
+
+    /// Fails unless `caller` is the owner.
+    pub fn ensure_owner(&self, caller: AccountId) -> Result<(), OwnableError> {
+        if caller != self.owner {
+            return Err(OwnableError::NotOwner);
+        }
+        Ok(())
+    }
+
+    /// Proposes `new_owner` as the next owner. Replaces any earlier proposal.
+    pub fn transfer_ownership(
+        &mut self,
+        caller: AccountId,
+        new_owner: AccountId,
+    ) -> Result<OwnershipTransferStarted, OwnableError> {
+        self.ensure_owner(caller)?;
+        self.pending_owner = Some(new_owner);
+        Ok(OwnershipTransferStarted {
+            previous_owner: self.owner,
+            new_owner,
+        })
+    }
+
+    /// Completes the handover. Must be called by the proposed new owner.
+    pub fn accept_ownership(
+        &mut self,
+        caller: AccountId,
+    ) -> Result<OwnershipTransferCompleted, OwnableError> {
+        match self.pending_owner {
+            None => Err(OwnableError::NoPendingTransfer),
+            Some(pending) if pending != caller => Err(OwnableError::NotPendingOwner),
+            Some(new_owner) => {
+                let previous_owner = self.owner;
+                self.owner = new_owner;
+                self.pending_owner = None;
+                Ok(OwnershipTransferCompleted {
+                    previous_owner,
+                    new_owner,
+                })
+            }
+        }
+    }
+
+    /// Withdraws the pending proposal and returns the account it named.
+    pub fn cancel_ownership_transfer(
+        &mut self,
+        caller: AccountId,
+    ) -> Result<AccountId, OwnableError> {
+        self.ensure_owner(caller)?;
+        self.pending_owner
+            .take()
+            .ok_or(OwnableError::NoPendingTransfer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(byte: u8) -> AccountId {
+        AccountId::from([byte; 32])
+    }
+
+    #[test]
+    fn owner_is_set_at_creation() {
+        let ownable = Ownable::new(account(1));
+        assert_eq!(ownable.owner(), account(1));
+        assert_eq!(ownable.pending_owner(), None);
+        assert_eq!(ownable.ensure_owner(account(1)), Ok(()));
+        assert_eq!(
+            ownable.ensure_owner(account(2)),
+            Err(OwnableError::NotOwner)
+        );
+    }
+
+    #[test]
+    fn two_step_transfer_works() {
+        let mut ownable = Ownable::new(account(1));
+        assert_eq!(
+            ownable.transfer_ownership(account(1), account(2)),
+            Ok(OwnershipTransferStarted {
+                previous_owner: account(1),
+                new_owner: account(2),
+            })
+        );
+        // Nothing changes until the new owner accepts.
+        assert_eq!(ownable.owner(), account(1));
+        assert_eq!(ownable.pending_owner(), Some(account(2)));
+
+        assert_eq!(
+            ownable.accept_ownership(account(2)),
+            Ok(OwnershipTransferCompleted {
+                previous_owner: account(1),
+                new_owner: account(2),
+            })
+        );
+        assert_eq!(ownable.owner(), account(2));
+        assert_eq!(ownable.pending_owner(), None);
+    }
+
+    #[test]
+    fn only_owner_can_start_transfer() {
+        let mut ownable = Ownable::new(account(1));
+        assert_eq!(
+            ownable.transfer_ownership(account(2), account(2)),
+            Err(OwnableError::NotOwner)
+        );
+        assert_eq!(ownable.pending_owner(), None);
+    }
+
+    #[test]
+    fn only_pending_owner_can_accept() {
+        let mut ownable = Ownable::new(account(1));
+        ownable.transfer_ownership(account(1), account(2)).unwrap();
+        assert_eq!(
+            ownable.accept_ownership(account(3)),
+            Err(OwnableError::NotPendingOwner)
+        );
+        assert_eq!(
+            ownable.accept_ownership(account(1)),
+            Err(OwnableError::NotPendingOwner)
+        );
+        assert_eq!(ownable.owner(), account(1));
+    }
+
+    #[test]
+    fn accept_without_transfer_fails() {
+        let mut ownable = Ownable::new(account(1));
+        assert_eq!(
+            ownable.accept_ownership(account(2)),
+            Err(OwnableError::NoPendingTransfer)
+        );
+    }
+
+    #[test]
+    fn cancelled_transfer_cannot_be_accepted() {
+        let mut ownable = Ownable::new(account(1));
+        ownable.transfer_ownership(account(1), account(2)).unwrap();
+        assert_eq!(
+            ownable.cancel_ownership_transfer(account(1)),
+            Ok(account(2))
+        );
+        assert_eq!(ownable.pending_owner(), None);
+        assert_eq!(
+            ownable.accept_ownership(account(2)),
+            Err(OwnableError::NoPendingTransfer)
+        );
+        assert_eq!(ownable.owner(), account(1));
+    }
+
+    #[test]
+    fn only_owner_can_cancel() {
+        let mut ownable = Ownable::new(account(1));
+        ownable.transfer_ownership(account(1), account(2)).unwrap();
+        assert_eq!(
+            ownable.cancel_ownership_transfer(account(2)),
+            Err(OwnableError::NotOwner)
+        );
+        assert_eq!(ownable.pending_owner(), Some(account(2)));
+    }
+
+    #[test]
+    fn cancel_without_transfer_fails() {
+        let mut ownable = Ownable::new(account(1));
+        assert_eq!(
+            ownable.cancel_ownership_transfer(account(1)),
+            Err(OwnableError::NoPendingTransfer)
+        );
+    }
+
+    #[test]
+    fn new_proposal_replaces_old_one() {
+        let mut ownable = Ownable::new(account(1));
+        ownable.transfer_ownership(account(1), account(2)).unwrap();
+        ownable.transfer_ownership(account(1), account(3)).unwrap();
+        assert_eq!(
+            ownable.accept_ownership(account(2)),
+            Err(OwnableError::NotPendingOwner)
+        );
+        assert!(ownable.accept_ownership(account(3)).is_ok());
+    }
+
+    #[test]
+    fn old_owner_loses_rights_after_handover() {
+        let mut ownable = Ownable::new(account(1));
+        ownable.transfer_ownership(account(1), account(2)).unwrap();
+        ownable.accept_ownership(account(2)).unwrap();
+        assert_eq!(
+            ownable.ensure_owner(account(1)),
+            Err(OwnableError::NotOwner)
+        );
+        assert_eq!(
+            ownable.transfer_ownership(account(1), account(1)),
+            Err(OwnableError::NotOwner)
+        );
+    }
+}
@@ -0,0 +1,161 @@
+// crates/utils/src/pagination.rs
+//
+// Bounded pages for query messages that list entries.
+//
+// A message that returns every listing, proposal or holder works in tests and
+// then fails on chain once the list outgrows the buffer the runtime reserves for
+// return values. Such messages should instead take a `(cursor, limit)` pair and
+// return a `Page`: at most `limit` entries, never more than `MAX_PAGE_LIMIT`,
+// starting at `cursor`, and the cursor of the entry after the last one returned:
+//
+//     #[ink(message)]
+//     pub fn proposals(&self, cursor: Option<ProposalId>, limit: u32) -> Page<(ProposalId, Proposal)> {
+//         Page::from_range(cursor.unwrap_or(0)..self.next_proposal_id, limit, |id| {
+//             self.proposals.get(id).map(|proposal| (id, proposal))
+//         })
+//     }
+//
+// A caller starts with `None`, passes each page's `next_cursor` back in, and is
+// done when it comes back `None`.
+
+use core::ops::Range;
+use ink_prelude::vec::Vec;
+
+/// Most entries a single page holds, whatever limit is asked for.
+pub const MAX_PAGE_LIMIT: u32 = 50;
+
+/// Up to `MAX_PAGE_LIMIT` entries of a list, and where the next page starts.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Page<T, C = u32> {
+    /// Entries of this page, in list order.
+    pub items: Vec<T>,
+    /// Cursor of the first entry after this page, `None` on the last page.
+    pub next_cursor: Option<C>,
+}
+
+impl<T, C> Page<T, C> {
+    /// Returns an empty last page.
+    pub fn empty() -> Self {
+        Self {
+            items: Vec::new(),
+            next_cursor: None,
+        }
+    }
+
+    /// Takes up to `limit` entries, at most `MAX_PAGE_LIMIT`, from `entries`,
+    /// which yields each entry with its cursor starting at the requested one.
+    ///
+    /// One entry past the page is read to find `next_cursor`.
+    pub fn collect<I>(entries: I, limit: u32) -> Self
+    where
+        I: IntoIterator<Item = (C, T)>,
+    {
+        let limit = limit.min(MAX_PAGE_LIMIT) as usize;
+        let mut entries = entries.into_iter();
+        let items = entries.by_ref().take(limit).map(|(_, item)| item).collect();
+        Self {
+            items,
+            next_cursor: entries.next().map(|(cursor, _)| cursor),
+        }
+    }
+
+    /// Returns the number of entries in this page.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if this page holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T> Page<T, u32> {
+    /// Takes up to `limit` entries, at most `MAX_PAGE_LIMIT`, from the indices in
+    /// `range`, skipping those for which `get` returns `None`.
+    pub fn from_range<F>(range: Range<u32>, limit: u32, mut get: F) -> Self
+    where
+        F: FnMut(u32) -> Option<T>,
+    {
+        Self::collect(
+            range.filter_map(|index| get(index).map(|item| (index, item))),
+            limit,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pages(len: u32, limit: u32) -> Vec<Page<u32>> {
+        let mut pages = Vec::new();
+        let mut cursor = Some(0);
+        while let Some(start) = cursor {
+            let page = Page::from_range(start..len, limit, Some);
+            cursor = page.next_cursor;
+            pages.push(page);
+        }
+        pages
+    }
+
+    #[test]
+    fn empty_list_gives_one_empty_last_page() {
+        assert_eq!(pages(0, 10), vec![Page::empty()]);
+    }
+
+    #[test]
+    fn exact_multiple_of_the_limit_has_no_trailing_empty_page() {
+        let pages = pages(6, 3);
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].items, vec![0, 1, 2]);
+        assert_eq!(pages[0].next_cursor, Some(3));
+        assert_eq!(pages[1].items, vec![3, 4, 5]);
+        assert_eq!(pages[1].next_cursor, None);
+    }
+
+    #[test]
+    fn last_page_holds_the_remainder() {
+        let pages = pages(7, 3);
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages[2].items, vec![6]);
+        assert_eq!(pages[2].next_cursor, None);
+    }
+
+    #[test]
+    fn limit_is_capped() {
+        let page = Page::from_range(0..1_000, u32::MAX, Some);
+        assert_eq!(page.len(), MAX_PAGE_LIMIT as usize);
+        assert_eq!(page.next_cursor, Some(MAX_PAGE_LIMIT));
+    }
+
+    #[test]
+    fn zero_limit_returns_nothing_and_keeps_the_cursor() {
+        let page = Page::from_range(4..10, 0, Some);
+        assert!(page.is_empty());
+        assert_eq!(page.next_cursor, Some(4));
+    }
+
+    #[test]
+    fn cursor_past_the_end_gives_an_empty_last_page() {
+        let (cursor, len) = (12, 10);
+        assert_eq!(Page::from_range(cursor..len, 5, Some), Page::empty());
+    }
+
+    #[test]
+    fn missing_entries_are_skipped_and_do_not_count() {
+        let even = |index: u32| Some(index).filter(|index| index % 2 == 0);
+        let page = Page::from_range(0..10, 3, even);
+        assert_eq!(page.items, vec![0, 2, 4]);
+        assert_eq!(page.next_cursor, Some(6));
+    }
+
+    #[test]
+    fn collect_reports_the_cursor_of_the_next_entry() {
+        let entries = [(10, 'a'), (20, 'b'), (30, 'c')];
+        let page: Page<char> = Page::collect(entries, 2);
+        assert_eq!(page.items, vec!['a', 'b']);
+        assert_eq!(page.next_cursor, Some(30));
+    }
+}
@@ -0,0 +1,142 @@
+// crates/utils/src/randomness.rs
+//
+// Pseudo-randomness derived from on-chain data. NOT SECURE.
+//
+// A contract has no private source of randomness: everything it can read, every
+// other contract and every block author can read too. The values produced here mix
+// chain entropy (a recent block hash or the output of `self.env().random(..)`), the
+// caller and a per-call nonce, which is enough to make results differ between calls
+// but not to make them unpredictable:
+//
+// - A caller can compute the result before submitting a transaction, and simply not
+//   submit (or revert) when the outcome is unfavourable.
+// - A block author chooses which transactions go into a block and can withhold a
+//   block whose hash is unfavourable.
+//
+// Use these functions for games and demonstrations where nothing of value depends
+// on the outcome. Anything holding real funds needs a commit-reveal scheme or a
+// verifiable randomness source instead. Every function is prefixed with `insecure_`
+// so that reviewers see the caveat at the call site.
+//
+// `examples/demo-contracts/arbitration.rs` draws its juror panels this way: the
+// entropy is `self.env().random(..)`, and the dispute id and seat number make up
+// the nonce.
+
+use crate::hashing::blake2_256_encoded;
+use ink_env::AccountId;
+
+/// Returns 32 pseudo-random bytes derived from `entropy`, `caller` and `nonce`.
+///
+/// The same inputs always produce the same output, so `nonce` must change between
+/// calls, for instance with a counter kept in the contract's storage.
+pub fn insecure_random_seed(entropy: &[u8; 32], caller: &AccountId, nonce: u64) -> [u8; 32] {
+    blake2_256_encoded(&(entropy, caller, nonce))
+}
+
+/// Returns a pseudo-random number in `low..high` derived from `entropy`, `caller`
+/// and `nonce`.
+///
+/// The seed is scaled into the range by multiplication rather than by taking a
+/// remainder, which keeps the bias below one in 2^32 for any range narrower than
+/// 2^32 values.
+///
+/// # Panics
+///
+/// If `low >= high`.
+pub fn insecure_random_in_range(
+    entropy: &[u8; 32],
+    caller: &AccountId,
+    nonce: u64,
+    low: u64,
+    high: u64,
+) -> u64 {
+    assert!(low < high, "random range must not be empty");
+    let seed = insecure_random_seed(entropy, caller, nonce);
+    let mut word = [0u8; 8];
+    word.copy_from_slice(&seed[..8]);
+    let span = u128::from(high - low);
+    let scaled = (u128::from(u64::from_le_bytes(word)) * span) >> 64;
+    low + scaled as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTROPY: [u8; 32] = [0x5a; 32];
+
+    fn account(byte: u8) -> AccountId {
+        AccountId::from([byte; 32])
+    }
+
+    #[test]
+    fn seed_is_deterministic() {
+        assert_eq!(
+            insecure_random_seed(&ENTROPY, &account(1), 7),
+            insecure_random_seed(&ENTROPY, &account(1), 7)
+        );
+    }
+
+    #[test]
+    fn seed_depends_on_every_input() {
+        let base = insecure_random_seed(&ENTROPY, &account(1), 7);
+        assert_ne!(base, insecure_random_seed(&[0x5b; 32], &account(1), 7));
+        assert_ne!(base, insecure_random_seed(&ENTROPY, &account(2), 7));
+        assert_ne!(base, insecure_random_seed(&ENTROPY, &account(1), 8));
+    }
+
+    #[test]
+    fn values_stay_in_range() {
+        for nonce in 0..1_000 {
+            let value = insecure_random_in_range(&ENTROPY, &account(1), nonce, 10, 13);
+            assert!((10..13).contains(&value));
+        }
+    }
+
+    #[test]
+    fn single_value_range_is_constant() {
+        assert_eq!(insecure_random_in_range(&ENTROPY, &account(1), 0, 4, 5), 4);
+    }
+
+    #[test]
+    fn full_range_is_accepted() {
+        insecure_random_in_range(&ENTROPY, &account(1), 0, 0, u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "random range must not be empty")]
+    fn empty_range_panics() {
+        insecure_random_in_range(&ENTROPY, &account(1), 0, 5, 5);
+    }
+
+    #[test]
+    fn distribution_is_roughly_uniform() {
+        const BUCKETS: usize = 10;
+        const SAMPLES: u64 = 20_000;
+        let mut counts = [0u64; BUCKETS];
+        for nonce in 0..SAMPLES {
+            let value = insecure_random_in_range(&ENTROPY, &account(1), nonce, 0, BUCKETS as u64);
+            counts[value as usize] += 1;
+        }
+
+        // Each bucket expects 2_000 hits with a standard deviation of about 42, so
+        // a 10% tolerance only fails on a real skew.
+        let expected = SAMPLES / BUCKETS as u64;
+        for count in counts {
+            assert!(
+                count.abs_diff(expected) < expected / 10,
+                "skewed distribution: {:?}",
+                counts
+            );
+        }
+    }
+
+    #[test]
+    fn coin_flips_are_balanced() {
+        // A coin flip over consecutive nonces should come up heads about half the time.
+        let heads = (0..1_000)
+            .filter(|nonce| insecure_random_in_range(&ENTROPY, &account(1), *nonce, 0, 2) == 1)
+            .count();
+        assert!((400..600).contains(&heads), "{} heads in 1000 flips", heads);
+    }
+}
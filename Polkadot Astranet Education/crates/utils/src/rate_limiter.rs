@@ -0,0 +1,226 @@
+// crates/utils/src/rate_limiter.rs
+//
+// A token-bucket rate limiter measured in blocks.
+//
+// Each account owns a `Bucket` holding up to `capacity` tokens. Every action spends
+// tokens, and every `window` blocks `refill` tokens are added back, never exceeding
+// the capacity. A full bucket therefore allows a burst of `capacity` actions, after
+// which the account is held to `refill` actions per window.
+//
+// The limiter itself is just configuration; buckets live in the contract's own
+// storage, usually a mapping keyed by account:
+//
+//     let mut bucket = self.buckets.get(caller)
+//         .unwrap_or_else(|| self.limiter.full_bucket(now));
+//     self.limiter.try_consume(&mut bucket, now, 1)?;
+//     self.buckets.insert(caller, &bucket);
+
+use ink_storage::traits::{PackedLayout, SpreadAllocate, SpreadLayout};
+
+/// Block numbers as used by the default contracts environment.
+pub type BlockNumber = u32;
+
+/// Returned when a bucket holds too few tokens for an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RateLimited {
+    /// First block at which the action would succeed, assuming no other spending.
+    pub retry_at: BlockNumber,
+}
+
+/// An account's token balance within a rate limiter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct Bucket {
+    /// Tokens available as of `last_refill`.
+    pub tokens: u32,
+    /// Start of the window in which tokens were last topped up.
+    pub last_refill: BlockNumber,
+}
+
+/// Token-bucket configuration shared by all accounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout, SpreadAllocate)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct RateLimiter {
+    capacity: u32,
+    refill: u32,
+    window: BlockNumber,
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing bursts of `capacity` and adding `refill` tokens
+    /// every `window` blocks.
+    ///
+    /// # Panics
+    ///
+    /// If `window` is zero.
+    pub fn new(capacity: u32, refill: u32, window: BlockNumber) -> Self {
+        assert!(window > 0, "rate limiter window must be at least one block");
+        Self {
+            capacity,
+            refill,
+            window,
+        }
+    }
+
+    /// Returns the maximum number of tokens a bucket holds.
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Returns the number of tokens added per window.
+    pub fn refill(&self) -> u32 {
+        self.refill
+    }
+
+    /// Returns the window length in blocks.
+    pub fn window(&self) -> BlockNumber {
+        self.window
+    }
+
+    /// Returns a full bucket for an account seen for the first time at `now`.
+    pub fn full_bucket(&self, now: BlockNumber) -> Bucket {
+        Bucket {
+            tokens: self.capacity,
+            last_refill: now,
+        }
+    }
+
+    /// Returns the bucket as it stands at `now`, with all due refills applied.
+    pub fn refilled(&self, bucket: Bucket, now: BlockNumber) -> Bucket {
+        let windows = now.saturating_sub(bucket.last_refill) / self.window;
+        if windows == 0 {
+            return bucket;
+        }
+        let added = u64::from(windows) * u64::from(self.refill);
+        let tokens = (u64::from(bucket.tokens) + added).min(u64::from(self.capacity)) as u32;
+        Bucket {
+            tokens,
+            last_refill: bucket.last_refill + windows * self.window,
+        }
+    }
+
+    /// Returns the number of tokens available in `bucket` at `now`.
+    pub fn available(&self, bucket: Bucket, now: BlockNumber) -> u32 {
+        self.refilled(bucket, now).tokens
+    }
+
+    /// Spends `cost` tokens from `bucket` at `now`.
+    ///
+    /// On failure the bucket is left untouched and the error tells the caller when
+    /// enough tokens will have accumulated.
+    pub fn try_consume(&self, bucket: &mut Bucket, now: BlockNumber, cost: u32) -> Result<(), RateLimited> {
+        let current = self.refilled(*bucket, now);
+        if current.tokens >= cost {
+            *bucket = Bucket {
+                tokens: current.tokens - cost,
+                ..current
+            };
+            return Ok(());
+        }
+
+        let retry_at = if cost > self.capacity || self.refill == 0 {
+            BlockNumber::MAX
+        } else {
+            let missing = cost - current.tokens;
+            let windows = missing.div_ceil(self.refill);
+            current
+                .last_refill
+                .saturating_add(windows.saturating_mul(self.window))
+        };
+        Err(RateLimited { retry_at })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_bucket_allows_burst() {
+        let limiter = RateLimiter::new(3, 1, 10);
+        let mut bucket = limiter.full_bucket(0);
+        for _ in 0..3 {
+            assert_eq!(limiter.try_consume(&mut bucket, 0, 1), Ok(()));
+        }
+        assert_eq!(
+            limiter.try_consume(&mut bucket, 0, 1),
+            Err(RateLimited { retry_at: 10 })
+        );
+    }
+
+    #[test]
+    fn refills_once_per_window() {
+        let limiter = RateLimiter::new(3, 1, 10);
+        let mut bucket = limiter.full_bucket(0);
+        assert_eq!(limiter.try_consume(&mut bucket, 0, 3), Ok(()));
+        assert_eq!(limiter.available(bucket, 9), 0);
+        assert_eq!(limiter.available(bucket, 10), 1);
+        assert_eq!(limiter.available(bucket, 19), 1);
+        assert_eq!(limiter.available(bucket, 20), 2);
+    }
+
+    #[test]
+    fn refill_is_capped_at_capacity() {
+        let limiter = RateLimiter::new(3, 2, 10);
+        let mut bucket = limiter.full_bucket(0);
+        assert_eq!(limiter.try_consume(&mut bucket, 0, 1), Ok(()));
+        assert_eq!(limiter.available(bucket, 1_000), 3);
+    }
+
+    #[test]
+    fn partial_window_progress_is_kept() {
+        // Spending mid-window must not reset the window start, or frequent callers
+        // would never see a refill.
+        let limiter = RateLimiter::new(2, 1, 10);
+        let mut bucket = limiter.full_bucket(0);
+        assert_eq!(limiter.try_consume(&mut bucket, 0, 2), Ok(()));
+        assert_eq!(limiter.try_consume(&mut bucket, 15, 1), Ok(()));
+        assert_eq!(bucket.last_refill, 10);
+        assert_eq!(limiter.available(bucket, 20), 1);
+    }
+
+    #[test]
+    fn failed_consume_leaves_bucket_untouched() {
+        let limiter = RateLimiter::new(2, 1, 10);
+        let mut bucket = limiter.full_bucket(0);
+        assert_eq!(limiter.try_consume(&mut bucket, 0, 2), Ok(()));
+        let before = bucket;
+        assert!(limiter.try_consume(&mut bucket, 25, 3).is_err());
+        assert_eq!(bucket, before);
+    }
+
+    #[test]
+    fn retry_at_accounts_for_multiple_windows() {
+        let limiter = RateLimiter::new(4, 1, 5);
+        let mut bucket = limiter.full_bucket(100);
+        assert_eq!(limiter.try_consume(&mut bucket, 100, 4), Ok(()));
+        assert_eq!(
+            limiter.try_consume(&mut bucket, 101, 3),
+            Err(RateLimited { retry_at: 115 })
+        );
+        assert_eq!(limiter.try_consume(&mut bucket, 115, 3), Ok(()));
+    }
+
+    #[test]
+    fn impossible_costs_never_succeed() {
+        let limiter = RateLimiter::new(2, 1, 10);
+        let mut bucket = limiter.full_bucket(0);
+        assert_eq!(
+            limiter.try_consume(&mut bucket, 0, 3),
+            Err(RateLimited { retry_at: BlockNumber::MAX })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one block")]
+    fn zero_window_is_rejected() {
+        RateLimiter::new(1, 1, 0);
+    }
+}
@@ -0,0 +1,156 @@
+// crates/utils/src/safe_erc20.rs
+//
+// Calls to an ERC-20 token contract that cannot fail silently.
+//
+// A contract moving tokens can get the result of the call wrong in two ways. The
+// call itself can fail (wrong address, out of gas, the token trapped), and the token
+// can refuse the transfer by returning `Err`. In ink! 3 a message returning `Err`
+// does not revert, so a refused transfer looks like any other successful call
+// unless the returned value is decoded and checked. `SafeErc20` wraps the token's
+// account id and turns both kinds of failure into a `SafeErc20Error`, so a contract
+// only needs `?` or `map_err` at the call site:
+//
+//     self.token.transfer_from(caller, self.env().account_id(), amount)
+//         .map_err(|_| Error::TransferFailed)?;
+//
+// The wrapper talks to the tokens of this repository: the messages have the
+// standard ERC-20 selectors below, and the token's error type encodes as a single
+// variant index, which is passed on in `Rejected`. The off-chain test environment
+// cannot call other contracts, so contracts keep their bookkeeping in helpers
+// their unit tests can call without going through the token.
+
+use ink_env::{
+    call::{build_call, Call, ExecutionInput, Selector},
+    AccountId, DefaultEnvironment,
+};
+use ink_storage::traits::{PackedLayout, SpreadAllocate, SpreadLayout};
+
+/// Balances as used by the default contracts environment.
+pub type Balance = u128;
+
+/// Selector of the ERC-20 `transfer(to, value)` message.
+pub const TRANSFER_SELECTOR: [u8; 4] = [0x84, 0xa1, 0x5d, 0xa1];
+
+/// Selector of the ERC-20 `transfer_from(from, to, value)` message.
+pub const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x0b, 0x39, 0x6f, 0x18];
+
+/// Selector of the ERC-20 `balance_of(owner)` message.
+pub const BALANCE_OF_SELECTOR: [u8; 4] = [0x0f, 0x75, 0x5a, 0x56];
+
+/// Errors returned by `SafeErc20`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum SafeErc20Error {
+    /// The call did not complete, or its output could not be decoded.
+    CallFailed,
+    /// The token returned `Err`, with the index of its error variant.
+    Rejected(u8),
+}
+
+/// Decodes any token error that encodes as a bare variant index.
+#[derive(scale::Decode)]
+struct TokenError(u8);
+
+/// An ERC-20 token contract, called through checked messages.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    SpreadLayout,
+    PackedLayout,
+    SpreadAllocate,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct SafeErc20 {
+    token: AccountId,
+}
+
+impl SafeErc20 {
+    /// Wraps the token contract at `token`.
+    pub fn new(token: AccountId) -> Self {
+        Self { token }
+    }
+
+    /// Returns the account id of the token contract.
+    pub fn token(&self) -> AccountId {
+        self.token
+    }
+
+    /// Transfers `value` tokens from the calling contract to `to`.
+    pub fn transfer(&self, to: AccountId, value: Balance) -> Result<(), SafeErc20Error> {
+        self.call(
+            ExecutionInput::new(Selector::new(TRANSFER_SELECTOR))
+                .push_arg(to)
+                .push_arg(value),
+        )
+    }
+
+    /// Transfers `value` tokens from `from` to `to`, spending the allowance
+    /// `from` gave the calling contract.
+    pub fn transfer_from(
+        &self,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+    ) -> Result<(), SafeErc20Error> {
+        self.call(
+            ExecutionInput::new(Selector::new(TRANSFER_FROM_SELECTOR))
+                .push_arg(from)
+                .push_arg(to)
+                .push_arg(value),
+        )
+    }
+
+    /// Returns the token balance of `owner`.
+    pub fn balance_of(&self, owner: AccountId) -> Result<Balance, SafeErc20Error> {
+        build_call::<DefaultEnvironment>()
+            .call_type(Call::new().callee(self.token).gas_limit(0))
+            .exec_input(ExecutionInput::new(Selector::new(BALANCE_OF_SELECTOR)).push_arg(owner))
+            .returns::<Balance>()
+            .fire()
+            .map_err(|_| SafeErc20Error::CallFailed)
+    }
+
+    /// Calls a message returning `Result<(), E>` and checks both layers.
+    fn call<Args: scale::Encode>(&self, input: ExecutionInput<Args>) -> Result<(), SafeErc20Error> {
+        build_call::<DefaultEnvironment>()
+            .call_type(Call::new().callee(self.token).gas_limit(0))
+            .exec_input(input)
+            .returns::<Result<(), TokenError>>()
+            .fire()
+            .map_err(|_| SafeErc20Error::CallFailed)?
+            .map_err(|TokenError(index)| SafeErc20Error::Rejected(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::blake2_256;
+
+    /// Computes the selector ink! gives a message named `label`.
+    fn selector(label: &str) -> [u8; 4] {
+        let hash = blake2_256(label.as_bytes());
+        [hash[0], hash[1], hash[2], hash[3]]
+    }
+
+    #[test]
+    fn selectors_match_message_names() {
+        assert_eq!(TRANSFER_SELECTOR, selector("transfer"));
+        assert_eq!(TRANSFER_FROM_SELECTOR, selector("transfer_from"));
+        assert_eq!(BALANCE_OF_SELECTOR, selector("balance_of"));
+    }
+
+    #[test]
+    fn wraps_token() {
+        let token = AccountId::from([0x70; 32]);
+        assert_eq!(SafeErc20::new(token).token(), token);
+    }
+}
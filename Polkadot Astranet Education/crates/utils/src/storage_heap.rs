@@ -0,0 +1,281 @@
+// crates/utils/src/storage_heap.rs
+//
+// A binary max-heap kept in contract storage, for priority queues.
+//
+// Contract storage is a key-value store with no ordered collection, so "the best
+// bid", "the earliest deadline" or "the highest bidder" would otherwise mean
+// loading every candidate or keeping a sorted list in which each insert walks
+// past the entries ahead of it. `StorageHeap` keeps the items in a binary heap
+// laid out over a `Mapping<u32, T>`: the item at index `i` has its children at
+// `2i + 1` and `2i + 2`, and no child is greater than its parent. Peeking at the
+// greatest item reads one cell, and pushing or popping reads and writes about
+// `log2(len)` cells, however many items there are.
+//
+// The heap always yields its greatest item first. For the smallest, push keys
+// whose order is reversed, such as `Balance::MAX - price`.
+//
+// A heap cannot remove an item from the middle. Contracts that need to usually
+// mark the item dead in their own storage and drop it once `peek` reaches it.
+
+use ink_prelude::vec::Vec;
+use ink_storage::{
+    traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+    Mapping,
+};
+
+/// A binary max-heap of `T` in contract storage.
+#[derive(Debug, SpreadLayout, SpreadAllocate)]
+pub struct StorageHeap<T: PackedLayout> {
+    /// Mapping from heap index to item.
+    items: Mapping<u32, T>,
+    /// Number of items.
+    len: u32,
+}
+
+impl<T: PackedLayout> Default for StorageHeap<T> {
+    fn default() -> Self {
+        Self {
+            items: Mapping::default(),
+            len: 0,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> ink_storage::traits::StorageLayout for StorageHeap<T>
+where
+    T: PackedLayout + scale_info::TypeInfo + 'static,
+{
+    fn layout(key_ptr: &mut ink_storage::traits::KeyPtr) -> ink_metadata::layout::Layout {
+        use ink_metadata::layout::{FieldLayout, Layout, StructLayout};
+        use ink_storage::traits::StorageLayout;
+        Layout::Struct(StructLayout::new([
+            FieldLayout::new("items", <Mapping<u32, T> as StorageLayout>::layout(key_ptr)),
+            FieldLayout::new("len", <u32 as StorageLayout>::layout(key_ptr)),
+        ]))
+    }
+}
+
+impl<T: PackedLayout + scale::EncodeLike + Ord> StorageHeap<T> {
+    /// Creates an empty heap.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if the heap holds no items.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the greatest item without removing it.
+    pub fn peek(&self) -> Option<T> {
+        self.items.get(0)
+    }
+
+    /// Adds `item` to the heap.
+    ///
+    /// # Panics
+    ///
+    /// If the heap already holds `u32::MAX` items.
+    pub fn push(&mut self, item: T) {
+        let mut index = self.len;
+        self.len = self.len.checked_add(1).expect("heap is full");
+        // Move smaller parents down until `item`'s place is found, then write it
+        // once, instead of swapping at every level.
+        while index > 0 {
+            let parent_index = (index - 1) / 2;
+            let parent = self.item(parent_index);
+            if parent >= item {
+                break;
+            }
+            self.items.insert(index, &parent);
+            index = parent_index;
+        }
+        self.items.insert(index, &item);
+    }
+
+    /// Removes and returns the greatest item.
+    pub fn pop(&mut self) -> Option<T> {
+        let top = self.peek()?;
+        self.len -= 1;
+        let last = self.item(self.len);
+        self.items.remove(self.len);
+        if self.len > 0 {
+            self.sift_down(last);
+        }
+        Some(top)
+    }
+
+    /// Returns an iterator over the items from greatest to smallest.
+    ///
+    /// It reads each item it yields plus at most two more, so taking the first
+    /// `n` items costs about `2n` reads rather than loading the whole heap.
+    pub fn iter_sorted(&self) -> SortedIter<'_, T> {
+        let mut frontier = Vec::new();
+        if let Some(top) = self.peek() {
+            frontier.push((top, 0));
+        }
+        SortedIter {
+            heap: self,
+            frontier,
+        }
+    }
+
+    /// Places `item` at the root and moves greater children up until its place
+    /// is found.
+    fn sift_down(&mut self, item: T) {
+        let mut index = 0u32;
+        loop {
+            let left = index.saturating_mul(2).saturating_add(1);
+            if left >= self.len {
+                break;
+            }
+            let right = left + 1;
+            let mut child_index = left;
+            let mut child = self.item(left);
+            if right < self.len {
+                let right_child = self.item(right);
+                if right_child > child {
+                    child_index = right;
+                    child = right_child;
+                }
+            }
+            if child <= item {
+                break;
+            }
+            self.items.insert(index, &child);
+            index = child_index;
+        }
+        self.items.insert(index, &item);
+    }
+
+    /// Returns the item at `index`, which must be below `len`.
+    fn item(&self, index: u32) -> T {
+        self.items.get(index).expect("heap index in bounds")
+    }
+}
+
+/// Iterator over a `StorageHeap`'s items from greatest to smallest, created by
+/// `StorageHeap::iter_sorted`.
+pub struct SortedIter<'a, T: PackedLayout> {
+    heap: &'a StorageHeap<T>,
+    /// Items not yielded yet whose parents have been, with their heap indices.
+    /// The greatest remaining item is always among them.
+    frontier: Vec<(T, u32)>,
+}
+
+impl<'a, T: PackedLayout + scale::EncodeLike + Ord> Iterator for SortedIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let greatest = self
+            .frontier
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.0.cmp(&b.0))
+            .map(|(position, _)| position)?;
+        let (item, index) = self.frontier.swap_remove(greatest);
+        for child in [2 * index + 1, 2 * index + 2] {
+            if child < self.heap.len {
+                self.frontier.push((self.heap.item(child), child));
+            }
+        }
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Returns whether no item of `heap` is greater than its parent.
+    fn is_heap(heap: &StorageHeap<u64>) -> bool {
+        (1..heap.len).all(|index| heap.item((index - 1) / 2) >= heap.item(index))
+    }
+
+    #[ink_lang::test]
+    fn pops_greatest_first() {
+        let mut heap = StorageHeap::new();
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+        for item in [5u64, 1, 8, 3, 8, 2] {
+            heap.push(item);
+        }
+        assert_eq!(heap.len(), 6);
+        assert_eq!(heap.peek(), Some(8));
+        let popped: Vec<u64> = core::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, [8, 8, 5, 3, 2, 1]);
+        assert!(heap.is_empty());
+        assert_eq!(heap.peek(), None);
+    }
+
+    #[ink_lang::test]
+    fn iter_sorted_does_not_consume() {
+        let mut heap = StorageHeap::new();
+        for item in [4u64, 9, 1, 7] {
+            heap.push(item);
+        }
+        assert_eq!(heap.iter_sorted().take(2).collect::<Vec<_>>(), [9, 7]);
+        assert_eq!(heap.iter_sorted().collect::<Vec<_>>(), [9, 7, 4, 1]);
+        assert_eq!(heap.len(), 4);
+    }
+
+    #[ink_lang::test]
+    fn popped_slots_are_cleared() {
+        let mut heap = StorageHeap::new();
+        heap.push(1u64);
+        heap.push(2);
+        heap.pop();
+        assert_eq!(heap.items.get(1), None);
+        assert_eq!(heap.peek(), Some(1));
+    }
+
+    /// An operation on the heap: push an item, or pop.
+    #[derive(Debug, Clone)]
+    enum Op {
+        Push(u64),
+        Pop,
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            3 => (0u64..50).prop_map(Op::Push),
+            1 => Just(Op::Pop),
+        ]
+    }
+
+    proptest! {
+        /// After any sequence of pushes and pops the heap property holds and the
+        /// heap agrees with a sorted model of its contents.
+        #[test]
+        fn behaves_like_a_sorted_list(ops in proptest::collection::vec(op(), 0..64)) {
+            ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+                let mut heap = StorageHeap::new();
+                let mut model: Vec<u64> = Vec::new();
+                for op in ops {
+                    match op {
+                        Op::Push(item) => {
+                            heap.push(item);
+                            model.push(item);
+                            model.sort_unstable();
+                        }
+                        Op::Pop => assert_eq!(heap.pop(), model.pop()),
+                    }
+                    assert!(is_heap(&heap));
+                    assert_eq!(heap.len() as usize, model.len());
+                    assert_eq!(heap.peek(), model.last().copied());
+                }
+                let sorted: Vec<u64> = model.iter().rev().copied().collect();
+                assert_eq!(heap.iter_sorted().collect::<Vec<_>>(), sorted);
+                Ok(())
+            })
+            .unwrap();
+        }
+    }
+}
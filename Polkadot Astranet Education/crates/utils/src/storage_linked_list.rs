@@ -0,0 +1,350 @@
+// crates/utils/src/storage_linked_list.rs
+//
+// A doubly-linked list of keys kept in contract storage, for indexes that must
+// be listed.
+//
+// A `Mapping` cannot be iterated, so a contract that wants to list its open
+// orders, active listings or live proposals has to keep an index of their keys.
+// A `Vec` of keys works until an entry in the middle must be removed, which means
+// loading, shifting and storing the whole vector. `StorageLinkedList` links each
+// key to its neighbours in a `Mapping<K, Links<K>>`, so adding a key at either end
+// or removing any key reads and writes a handful of cells, however long the list.
+//
+// Reading the list goes through iterators that load one key per step, so a query
+// message can take a bounded page instead of loading everything:
+//
+//     let page: Vec<ListingId> = self.active.iter_from(cursor).take(limit).collect();
+//
+// Every key can be in the list at most once; `push_back` and `push_front` return
+// `false` and change nothing for a key that is already there.
+
+use ink_storage::{
+    traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+    Mapping,
+};
+
+/// The neighbours of a key in a `StorageLinkedList`.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct Links<K> {
+    /// Key before this one, `None` at the head.
+    pub prev: Option<K>,
+    /// Key after this one, `None` at the tail.
+    pub next: Option<K>,
+}
+
+/// A doubly-linked list of distinct `K` in contract storage.
+#[derive(Debug, SpreadLayout, SpreadAllocate)]
+pub struct StorageLinkedList<K: PackedLayout> {
+    /// Mapping from key to its neighbours.
+    links: Mapping<K, Links<K>>,
+    /// First key.
+    head: Option<K>,
+    /// Last key.
+    tail: Option<K>,
+    /// Number of keys.
+    len: u32,
+}
+
+impl<K: PackedLayout> Default for StorageLinkedList<K> {
+    fn default() -> Self {
+        Self {
+            links: Mapping::default(),
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K> ink_storage::traits::StorageLayout for StorageLinkedList<K>
+where
+    K: PackedLayout + ink_storage::traits::StorageLayout + scale_info::TypeInfo + 'static,
+{
+    fn layout(key_ptr: &mut ink_storage::traits::KeyPtr) -> ink_metadata::layout::Layout {
+        use ink_metadata::layout::{FieldLayout, Layout, StructLayout};
+        use ink_storage::traits::StorageLayout;
+        Layout::Struct(StructLayout::new([
+            FieldLayout::new(
+                "links",
+                <Mapping<K, Links<K>> as StorageLayout>::layout(key_ptr),
+            ),
+            FieldLayout::new("head", <Option<K> as StorageLayout>::layout(key_ptr)),
+            FieldLayout::new("tail", <Option<K> as StorageLayout>::layout(key_ptr)),
+            FieldLayout::new("len", <u32 as StorageLayout>::layout(key_ptr)),
+        ]))
+    }
+}
+
+impl<K> StorageLinkedList<K>
+where
+    K: PackedLayout + scale::EncodeLike + Copy + PartialEq,
+{
+    /// Creates an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of keys.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if the list holds no keys.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the first key.
+    pub fn head(&self) -> Option<K> {
+        self.head
+    }
+
+    /// Returns the last key.
+    pub fn tail(&self) -> Option<K> {
+        self.tail
+    }
+
+    /// Returns `true` if `key` is in the list.
+    pub fn contains(&self, key: K) -> bool {
+        self.links.contains(key)
+    }
+
+    /// Returns the key after `key`, or `None` if `key` is the last or not in the
+    /// list.
+    pub fn next(&self, key: K) -> Option<K> {
+        self.links.get(key).and_then(|links| links.next)
+    }
+
+    /// Returns the key before `key`, or `None` if `key` is the first or not in the
+    /// list.
+    pub fn prev(&self, key: K) -> Option<K> {
+        self.links.get(key).and_then(|links| links.prev)
+    }
+
+    /// Adds `key` at the end. Returns `false`, leaving the list unchanged, if it
+    /// is already in the list.
+    pub fn push_back(&mut self, key: K) -> bool {
+        if self.contains(key) {
+            return false;
+        }
+        let links = Links {
+            prev: self.tail,
+            next: None,
+        };
+        match self.tail {
+            Some(tail) => self.set_next(tail, Some(key)),
+            None => self.head = Some(key),
+        }
+        self.tail = Some(key);
+        self.links.insert(key, &links);
+        self.len += 1;
+        true
+    }
+
+    /// Adds `key` at the start. Returns `false`, leaving the list unchanged, if
+    /// it is already in the list.
+    pub fn push_front(&mut self, key: K) -> bool {
+        if self.contains(key) {
+            return false;
+        }
+        let links = Links {
+            prev: None,
+            next: self.head,
+        };
+        match self.head {
+            Some(head) => self.set_prev(head, Some(key)),
+            None => self.tail = Some(key),
+        }
+        self.head = Some(key);
+        self.links.insert(key, &links);
+        self.len += 1;
+        true
+    }
+
+    /// Removes `key`, linking its neighbours to each other. Returns `false` if it
+    /// was not in the list.
+    pub fn remove(&mut self, key: K) -> bool {
+        let links = match self.links.get(key) {
+            Some(links) => links,
+            None => return false,
+        };
+        match links.prev {
+            Some(prev) => self.set_next(prev, links.next),
+            None => self.head = links.next,
+        }
+        match links.next {
+            Some(next) => self.set_prev(next, links.prev),
+            None => self.tail = links.prev,
+        }
+        self.links.remove(key);
+        self.len -= 1;
+        true
+    }
+
+    /// Returns an iterator over the keys from first to last.
+    pub fn iter(&self) -> Iter<'_, K> {
+        Iter {
+            list: self,
+            next: self.head,
+        }
+    }
+
+    /// Returns an iterator over the keys from `key` to the last, or an empty one
+    /// if `key` is not in the list.
+    ///
+    /// Paginated queries pass the key after the last one of the previous page,
+    /// or start with `iter` for the first page.
+    pub fn iter_from(&self, key: K) -> Iter<'_, K> {
+        Iter {
+            list: self,
+            next: Some(key).filter(|key| self.contains(*key)),
+        }
+    }
+
+    /// Sets the key after `key`, which must be in the list.
+    fn set_next(&mut self, key: K, next: Option<K>) {
+        let mut links = self.links.get(key).expect("linked key exists");
+        links.next = next;
+        self.links.insert(key, &links);
+    }
+
+    /// Sets the key before `key`, which must be in the list.
+    fn set_prev(&mut self, key: K, prev: Option<K>) {
+        let mut links = self.links.get(key).expect("linked key exists");
+        links.prev = prev;
+        self.links.insert(key, &links);
+    }
+}
+
+/// Iterator over the keys of a `StorageLinkedList`, created by
+/// `StorageLinkedList::iter` and `StorageLinkedList::iter_from`.
+pub struct Iter<'a, K: PackedLayout> {
+    list: &'a StorageLinkedList<K>,
+    next: Option<K>,
+}
+
+impl<'a, K> Iterator for Iter<'a, K>
+where
+    K: PackedLayout + scale::EncodeLike + Copy + PartialEq,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<K> {
+        let key = self.next?;
+        self.next = self.list.next(key);
+        Some(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_prelude::vec::Vec;
+
+    fn list_of(keys: &[u32]) -> StorageLinkedList<u32> {
+        let mut list = StorageLinkedList::new();
+        for key in keys {
+            assert!(list.push_back(*key));
+        }
+        list
+    }
+
+    fn keys(list: &StorageLinkedList<u32>) -> Vec<u32> {
+        list.iter().collect()
+    }
+
+    /// Checks that walking backwards from the tail gives the reverse of `iter`.
+    fn assert_consistent(list: &StorageLinkedList<u32>) {
+        let forward = keys(list);
+        let mut backward = Vec::new();
+        let mut key = list.tail();
+        while let Some(current) = key {
+            backward.push(current);
+            key = list.prev(current);
+        }
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(forward.len() as u32, list.len());
+        assert_eq!(list.head(), forward.first().copied());
+        assert_eq!(list.tail(), forward.last().copied());
+    }
+
+    #[ink_lang::test]
+    fn pushes_at_both_ends() {
+        let mut list = StorageLinkedList::new();
+        assert!(list.is_empty());
+        assert!(list.push_back(2));
+        assert!(list.push_back(3));
+        assert!(list.push_front(1));
+        assert_eq!(keys(&list), [1, 2, 3]);
+        assert_consistent(&list);
+    }
+
+    #[ink_lang::test]
+    fn keys_are_distinct() {
+        let mut list = list_of(&[1, 2]);
+        assert!(!list.push_back(1));
+        assert!(!list.push_front(2));
+        assert_eq!(keys(&list), [1, 2]);
+        assert!(list.contains(1));
+        assert!(!list.contains(3));
+    }
+
+    #[ink_lang::test]
+    fn removes_from_anywhere() {
+        let mut list = list_of(&[1, 2, 3, 4, 5]);
+        assert!(list.remove(3));
+        assert_eq!(keys(&list), [1, 2, 4, 5]);
+        assert_consistent(&list);
+        assert!(list.remove(1));
+        assert_eq!(keys(&list), [2, 4, 5]);
+        assert_consistent(&list);
+        assert!(list.remove(5));
+        assert_eq!(keys(&list), [2, 4]);
+        assert_consistent(&list);
+        assert!(!list.remove(5));
+        assert!(!list.contains(5));
+    }
+
+    #[ink_lang::test]
+    fn removing_the_only_key_empties_the_list() {
+        let mut list = list_of(&[7]);
+        assert!(list.remove(7));
+        assert!(list.is_empty());
+        assert_eq!(list.head(), None);
+        assert_eq!(list.tail(), None);
+        assert!(list.push_back(8));
+        assert_eq!(keys(&list), [8]);
+        assert_consistent(&list);
+    }
+
+    #[ink_lang::test]
+    fn removed_keys_can_be_added_again() {
+        let mut list = list_of(&[1, 2, 3]);
+        assert!(list.remove(2));
+        assert!(list.push_back(2));
+        assert_eq!(keys(&list), [1, 3, 2]);
+        assert_consistent(&list);
+    }
+
+    #[ink_lang::test]
+    fn iter_from_pages_through_the_list() {
+        let list = list_of(&[10, 20, 30, 40, 50]);
+        let first: Vec<u32> = list.iter().take(2).collect();
+        assert_eq!(first, [10, 20]);
+        let cursor = list.next(20).unwrap();
+        let second: Vec<u32> = list.iter_from(cursor).take(2).collect();
+        assert_eq!(second, [30, 40]);
+        let last: Vec<u32> = list.iter_from(50).take(2).collect();
+        assert_eq!(last, [50]);
+        assert_eq!(list.next(50), None);
+        assert_eq!(list.iter_from(99).count(), 0);
+    }
+}
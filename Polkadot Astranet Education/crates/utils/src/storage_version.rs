@@ -0,0 +1,135 @@
+// crates/utils/src/storage_version.rs
+//
+// A version number for a contract's storage layout, for gating migrations.
+//
+// After `set_code_hash` the new code runs on storage written by the old code, and
+// a migration message is what converts it. Nothing stops that message from being
+// called twice, or a migration for a later layout from running before the one it
+// builds on, and either can corrupt the storage for good. Keeping a
+// `StorageVersion` next to the other fields makes the layout explicit: a migration
+// calls `bump_version` with the version it converts from, which fails unless the
+// storage is at exactly that version, and messages that need the new layout call
+// `ensure_version` first.
+//
+// A fresh deployment starts at the version of the code that deployed it, so the
+// migrations to that version never run on it.
+
+use ink_storage::traits::{PackedLayout, SpreadAllocate, SpreadLayout};
+
+/// Errors returned by `StorageVersion`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum StorageVersionError {
+    /// Returned if the storage is at `found` rather than the `expected` version.
+    /// A migration finding a higher version has already run; one finding a lower
+    /// version is running before a migration it depends on.
+    UnexpectedVersion { expected: u16, found: u16 },
+    /// Returned if the version cannot go any higher.
+    Exhausted,
+}
+
+/// The version of a contract's storage layout.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    scale::Encode,
+    scale::Decode,
+    SpreadLayout,
+    PackedLayout,
+    SpreadAllocate,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct StorageVersion(u16);
+
+impl StorageVersion {
+    /// Creates a version, usually the current layout's in a constructor.
+    pub const fn new(version: u16) -> Self {
+        Self(version)
+    }
+
+    /// Returns the version number.
+    pub const fn get(self) -> u16 {
+        self.0
+    }
+
+    /// Fails unless the storage is at version `expected`.
+    pub fn ensure_version(&self, expected: u16) -> Result<(), StorageVersionError> {
+        if self.0 != expected {
+            return Err(StorageVersionError::UnexpectedVersion {
+                expected,
+                found: self.0,
+            });
+        }
+        Ok(())
+    }
+
+    /// Moves the storage from version `from` to the next one and returns it.
+    /// Fails, leaving the version unchanged, unless the storage is at `from`.
+    ///
+    /// Call this once a migration from `from` is complete.
+    pub fn bump_version(&mut self, from: u16) -> Result<u16, StorageVersionError> {
+        self.ensure_version(from)?;
+        let next = from.checked_add(1).ok_or(StorageVersionError::Exhausted)?;
+        self.0 = next;
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ensure_version_checks_exact_match() {
+        let version = StorageVersion::new(2);
+        assert_eq!(version.get(), 2);
+        assert_eq!(version.ensure_version(2), Ok(()));
+        assert_eq!(
+            version.ensure_version(1),
+            Err(StorageVersionError::UnexpectedVersion {
+                expected: 1,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn migrations_run_once_and_in_order() {
+        let mut version = StorageVersion::new(1);
+        assert_eq!(
+            version.bump_version(2),
+            Err(StorageVersionError::UnexpectedVersion {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(version.bump_version(1), Ok(2));
+        assert_eq!(
+            version.bump_version(1),
+            Err(StorageVersionError::UnexpectedVersion {
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(version.bump_version(2), Ok(3));
+        assert_eq!(version.get(), 3);
+    }
+
+    #[test]
+    fn bump_fails_at_the_last_version() {
+        let mut version = StorageVersion::new(u16::MAX);
+        assert_eq!(
+            version.bump_version(u16::MAX),
+            Err(StorageVersionError::Exhausted)
+        );
+        assert_eq!(version.get(), u16::MAX);
+    }
+}
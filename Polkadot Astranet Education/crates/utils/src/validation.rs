@@ -0,0 +1,131 @@
+// crates/utils/src/validation.rs
+//
+// Checks for constructor arguments.
+//
+// An ink! 3 constructor cannot return an error, so the only way to refuse bad
+// arguments is to panic, which reverts the instantiation. A token deployed with an
+// empty symbol or 200 decimals cannot be fixed afterwards, so it is worth failing
+// loudly. The checks here return a `ValidationError`, which keeps them usable from
+// messages too, and `require` turns one into a panic naming the argument:
+//
+//     validation::require("token symbol", validation::non_empty(&symbol));
+//     validation::require("decimals", validation::at_most(decimals, MAX_DECIMALS));
+//
+// panics with "token symbol must not be empty" or "decimals is too large", which
+// is also what a `#[should_panic(expected = ...)]` test matches.
+
+use crate::bounded::BoundedError;
+use core::fmt;
+
+/// Most decimals a token may declare. Beyond this a single whole token would not
+/// fit in a `u128`.
+pub const MAX_DECIMALS: u8 = 36;
+
+/// Why an argument was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ValidationError {
+    /// Returned if a string that must have content is empty.
+    Empty,
+    /// Returned if a string or list is longer than allowed.
+    TooLong,
+    /// Returned if a number is above its maximum.
+    TooLarge,
+    /// Returned if a number that must be positive is zero.
+    Zero,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ValidationError::Empty => "must not be empty",
+            ValidationError::TooLong => "is too long",
+            ValidationError::TooLarge => "is too large",
+            ValidationError::Zero => "must not be zero",
+        })
+    }
+}
+
+impl From<BoundedError> for ValidationError {
+    fn from(error: BoundedError) -> Self {
+        match error {
+            BoundedError::LimitExceeded => ValidationError::TooLong,
+        }
+    }
+}
+
+/// Returns the value in `result`, or panics with `argument` followed by why it
+/// was rejected. For constructors, which cannot return errors.
+pub fn require<T, E: Into<ValidationError>>(argument: &str, result: Result<T, E>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(error) => panic!("{} {}", argument, error.into()),
+    }
+}
+
+/// Checks that `value` is not empty.
+pub fn non_empty(value: &str) -> Result<(), ValidationError> {
+    if value.is_empty() {
+        return Err(ValidationError::Empty);
+    }
+    Ok(())
+}
+
+/// Checks that `value` is at most `max`.
+pub fn at_most<T: PartialOrd>(value: T, max: T) -> Result<(), ValidationError> {
+    if value > max {
+        return Err(ValidationError::TooLarge);
+    }
+    Ok(())
+}
+
+/// Checks that `value` is not zero.
+pub fn non_zero<T: Default + PartialEq>(value: T) -> Result<(), ValidationError> {
+    if value == T::default() {
+        return Err(ValidationError::Zero);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded::BoundedString;
+
+    #[test]
+    fn checks_accept_their_boundaries() {
+        assert_eq!(non_empty("a"), Ok(()));
+        assert_eq!(non_empty(""), Err(ValidationError::Empty));
+        assert_eq!(at_most(MAX_DECIMALS, MAX_DECIMALS), Ok(()));
+        assert_eq!(
+            at_most(MAX_DECIMALS + 1, MAX_DECIMALS),
+            Err(ValidationError::TooLarge)
+        );
+        assert_eq!(non_zero(1u128), Ok(()));
+        assert_eq!(non_zero(0u128), Err(ValidationError::Zero));
+    }
+
+    #[test]
+    fn max_decimals_fit_one_token_in_a_u128() {
+        assert!(10u128.checked_pow(MAX_DECIMALS.into()).is_some());
+        assert!(10u128.checked_pow(u32::from(MAX_DECIMALS) + 3).is_none());
+    }
+
+    #[test]
+    fn require_returns_the_value() {
+        let name: BoundedString<4> = require("name", BoundedString::try_from("abcd"));
+        assert_eq!(&*name, "abcd");
+    }
+
+    #[test]
+    #[should_panic(expected = "token symbol must not be empty")]
+    fn require_names_the_argument() {
+        require("token symbol", non_empty(""));
+    }
+
+    #[test]
+    #[should_panic(expected = "token name is too long")]
+    fn bounded_errors_read_as_too_long() {
+        let _: BoundedString<1> = require("token name", BoundedString::try_from("ab"));
+    }
+}
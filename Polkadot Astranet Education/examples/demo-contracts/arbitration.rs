@@ -0,0 +1,889 @@
+// examples/demo-contracts/arbitration.rs
+//
+// An arbitration court contract example for Polkadot using ink!
+// Jurors stake ERC-20 tokens to join the court. When a contract that holds funds
+// for two parties, such as an escrow, cannot tell who is right, it opens a dispute
+// here; a panel of jurors is drawn, votes for the claimant or the respondent, and
+// the contract reads back the ruling and pays out accordingly.
+//
+// The `Arbitrator` trait
+// ----------------------
+// Contracts that need a ruling only depend on the `Arbitrator` trait below:
+// `create_dispute` and `ruling`. Its messages are selected by
+// `Arbitrator::create_dispute` and `Arbitrator::ruling` (pinned in
+// `tools/selector/tests/pinned.rs`), so any court implementing the trait can stand
+// in for this one. The repository has no escrow example yet; its dispute path would
+// call `create_dispute(buyer, seller)` and later release the funds to whichever
+// party `ruling` names.
+//
+// Juror selection
+// ---------------
+// Each juror of a panel is drawn with the utils crate's `insecure_random_in_range`:
+// a random registry slot is picked, and the first eligible juror from there on is
+// seated. Jurors are eligible if they are not a party to the dispute, are not
+// seated already, and have `juror_stake` free; that much of their stake is locked
+// until the dispute is settled. This is NOT secure randomness (see
+// `crates/utils/src/randomness.rs`), and opening disputes is free, so this court is
+// for teaching only.
+//
+// Rewards and slashing
+// --------------------
+// The ruling goes to the side with more votes; ties, including disputes nobody
+// voted on, go to the respondent. Jurors who voted with the ruling get their locked
+// stake back plus an equal share of the locked stake of everyone else on the panel,
+// who lose it. Jurors are settled one at a time with `settle`, which anyone can
+// call. Rounding leftovers, and the whole pot when nobody voted, stay in the
+// contract.
+//
+// Voting closes at the utils crate's inclusive `Deadline`: jurors can still vote at
+// exactly `voting_ends`, and the dispute can be resolved the millisecond after
+// unless every juror has already voted.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+/// Identifies a dispute.
+pub type DisputeId = u32;
+
+/// The party a dispute is decided for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+#[derive(ink_storage::traits::SpreadLayout, ink_storage::traits::PackedLayout)]
+pub enum Ruling {
+    Claimant,
+    Respondent,
+}
+
+/// The interface a contract uses to have its disputes decided.
+#[ink::trait_definition]
+pub trait Arbitrator {
+    /// Opens a dispute between `claimant` and `respondent` on behalf of the caller,
+    /// and returns its id, or `None` if not enough jurors are available.
+    #[ink(message)]
+    fn create_dispute(
+        &mut self,
+        claimant: ink_env::AccountId,
+        respondent: ink_env::AccountId,
+    ) -> Option<DisputeId>;
+
+    /// Returns the ruling of `dispute_id`, or `None` until it is resolved.
+    #[ink(message)]
+    fn ruling(&self, dispute_id: DisputeId) -> Option<Ruling>;
+}
+
+#[ink::contract]
+mod arbitration {
+    use super::{Arbitrator, DisputeId, Ruling};
+    use astranet_utils::{deadline::Deadline, randomness::insecure_random_in_range};
+    use ink_env::call::{build_call, Call, ExecutionInput, Selector};
+    use ink_prelude::vec::Vec;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// The largest number of jurors with stake in the court, which bounds the cost
+    /// of drawing a panel.
+    pub const MAX_JURORS: u32 = 64;
+
+    /// Selector of the ERC-20 `transfer(to, value)` message.
+    const TRANSFER_SELECTOR: [u8; 4] = [0x84, 0xa1, 0x5d, 0xa1];
+
+    /// Selector of the ERC-20 `transfer_from(from, to, value)` message.
+    const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x0b, 0x39, 0x6f, 0x18];
+
+    /// The arbitration error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the amount is zero.
+        ZeroAmount,
+        /// Returned if the juror has less free stake than asked for.
+        InsufficientStake,
+        /// Returned if the court already has `MAX_JURORS` jurors.
+        TooManyJurors,
+        /// Returned if there is no dispute with the id.
+        UnknownDispute,
+        /// Returned if the juror is not on the dispute's panel.
+        NotOnPanel,
+        /// Returned if the juror already voted on the dispute.
+        AlreadyVoted,
+        /// Returned if the voting period of the dispute is over.
+        VotingClosed,
+        /// Returned if the voting period is still running and votes are missing.
+        VotingOpen,
+        /// Returned if the dispute already has a ruling.
+        AlreadyResolved,
+        /// Returned if the dispute has no ruling yet.
+        NotResolved,
+        /// Returned if the token refused to move the tokens.
+        TransferFailed,
+    }
+
+    /// The arbitration result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Stands in for the token's error type, which encodes as a bare variant index;
+    /// all this contract needs is to tell success from failure.
+    #[derive(scale::Decode)]
+    struct CalleeError(#[allow(dead_code)] u8);
+
+    /// The tokens a juror has staked.
+    #[derive(
+        Debug,
+        Default,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        scale::Encode,
+        scale::Decode,
+        SpreadLayout,
+        PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Stake {
+        /// Tokens staked in total.
+        pub total: Balance,
+        /// Tokens locked on panels.
+        pub locked: Balance,
+    }
+
+    impl Stake {
+        /// Returns the tokens not locked on a panel.
+        pub fn free(&self) -> Balance {
+            self.total - self.locked
+        }
+    }
+
+    /// A dispute and its votes.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Dispute {
+        /// Contract that opened the dispute and acts on the ruling.
+        pub arbitrable: AccountId,
+        /// Party asking for the funds.
+        pub claimant: AccountId,
+        /// Party holding on to the funds.
+        pub respondent: AccountId,
+        /// Last time jurors can vote; the dispute can be resolved after it.
+        pub voting_ends: Deadline,
+        /// Number of jurors on the panel.
+        pub panel_size: u32,
+        /// Votes for the claimant.
+        pub for_claimant: u32,
+        /// Votes for the respondent.
+        pub for_respondent: u32,
+        /// The ruling, once resolved.
+        pub ruling: Option<Ruling>,
+    }
+
+    impl Dispute {
+        /// Returns the number of jurors who voted for `ruling`.
+        pub fn votes_for(&self, ruling: Ruling) -> u32 {
+            match ruling {
+                Ruling::Claimant => self.for_claimant,
+                Ruling::Respondent => self.for_respondent,
+            }
+        }
+    }
+
+    // Events are mirrored in `crates/events/src/arbitration.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when a juror stakes tokens.
+    #[ink(event)]
+    pub struct Staked {
+        #[ink(topic)]
+        juror: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when a juror withdraws free stake.
+    #[ink(event)]
+    pub struct Unstaked {
+        #[ink(topic)]
+        juror: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when a contract opens a dispute.
+    #[ink(event)]
+    pub struct DisputeCreated {
+        #[ink(topic)]
+        dispute_id: DisputeId,
+        #[ink(topic)]
+        arbitrable: AccountId,
+        claimant: AccountId,
+        respondent: AccountId,
+        voting_ends: Timestamp,
+    }
+
+    /// Event emitted when a juror is drawn onto a dispute's panel.
+    #[ink(event)]
+    pub struct JurorDrawn {
+        #[ink(topic)]
+        dispute_id: DisputeId,
+        #[ink(topic)]
+        juror: AccountId,
+    }
+
+    /// Event emitted when a juror votes.
+    #[ink(event)]
+    pub struct Voted {
+        #[ink(topic)]
+        dispute_id: DisputeId,
+        #[ink(topic)]
+        juror: AccountId,
+        ruling: Ruling,
+    }
+
+    /// Event emitted when a dispute is resolved.
+    #[ink(event)]
+    pub struct DisputeResolved {
+        #[ink(topic)]
+        dispute_id: DisputeId,
+        ruling: Ruling,
+    }
+
+    /// Event emitted when a juror's locked stake is released after a ruling, with
+    /// either a reward or a slash.
+    #[ink(event)]
+    pub struct JurorSettled {
+        #[ink(topic)]
+        dispute_id: DisputeId,
+        #[ink(topic)]
+        juror: AccountId,
+        reward: Balance,
+        slashed: Balance,
+    }
+
+    /// The arbitration storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Arbitration {
+        /// The ERC-20 token jurors stake.
+        token: AccountId,
+        /// Stake locked per panel seat, and the free stake needed to be drawn.
+        juror_stake: Balance,
+        /// Number of jurors drawn per dispute.
+        panel_size: u32,
+        /// How long jurors can vote on a dispute, in milliseconds.
+        voting_period: Timestamp,
+        /// Mapping from juror to their stake.
+        stakes: Mapping<AccountId, Stake>,
+        /// Mapping from registry slot to juror, for slots below `juror_count`.
+        jurors: Mapping<u32, AccountId>,
+        /// Mapping from juror to their registry slot.
+        juror_slots: Mapping<AccountId, u32>,
+        /// Number of jurors with stake.
+        juror_count: u32,
+        /// Mapping from dispute id to the dispute.
+        disputes: Mapping<DisputeId, Dispute>,
+        /// Mapping from dispute id and panel seat to the juror.
+        panels: Mapping<(DisputeId, u32), AccountId>,
+        /// Mapping from dispute id and unsettled juror to their vote, if any.
+        votes: Mapping<(DisputeId, AccountId), Option<Ruling>>,
+        /// Id the next dispute will get.
+        next_dispute_id: DisputeId,
+    }
+
+    impl Arbitration {
+        /// Creates a court staking `token`, which locks `juror_stake` per seat on
+        /// panels of `panel_size` jurors who vote for `voting_period` milliseconds.
+        /// An odd `panel_size` avoids ties.
+        #[ink(constructor)]
+        pub fn new(
+            token: AccountId,
+            juror_stake: Balance,
+            panel_size: u32,
+            voting_period: Timestamp,
+        ) -> Self {
+            ink_lang::utils::initialize_contract(|court: &mut Self| {
+                court.token = token;
+                court.juror_stake = juror_stake;
+                court.panel_size = panel_size;
+                court.voting_period = voting_period;
+            })
+        }
+
+        /// Returns the stake of `juror`.
+        #[ink(message)]
+        pub fn stake_of(&self, juror: AccountId) -> Stake {
+            self.stakes.get(juror).unwrap_or_default()
+        }
+
+        /// Returns the number of jurors with stake.
+        #[ink(message)]
+        pub fn juror_count(&self) -> u32 {
+            self.juror_count
+        }
+
+        /// Returns the dispute with `dispute_id`, if any.
+        #[ink(message)]
+        pub fn dispute(&self, dispute_id: DisputeId) -> Option<Dispute> {
+            self.disputes.get(dispute_id)
+        }
+
+        /// Returns the jurors drawn for `dispute_id`.
+        #[ink(message)]
+        pub fn panel(&self, dispute_id: DisputeId) -> Vec<AccountId> {
+            let size = self.dispute(dispute_id).map_or(0, |d| d.panel_size);
+            (0..size)
+                .filter_map(|seat| self.panels.get((dispute_id, seat)))
+                .collect()
+        }
+
+        /// Moves `amount` tokens from the caller into their stake. The caller must
+        /// have approved this contract to spend at least `amount`.
+        #[ink(message)]
+        pub fn stake(&mut self, amount: Balance) -> Result<()> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let juror = self.env().caller();
+            if !self.juror_slots.contains(juror) && self.juror_count >= MAX_JURORS {
+                return Err(Error::TooManyJurors);
+            }
+            let contract = self.env().account_id();
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.token).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(TRANSFER_FROM_SELECTOR))
+                        .push_arg(juror)
+                        .push_arg(contract)
+                        .push_arg(amount),
+                )
+                .returns::<core::result::Result<(), CalleeError>>()
+                .fire()
+                .map_err(|_| Error::TransferFailed)?
+                .map_err(|_| Error::TransferFailed)?;
+
+            self.credit_stake(juror, amount);
+            Ok(())
+        }
+
+        /// Sends `amount` of the caller's free stake back to them.
+        #[ink(message)]
+        pub fn unstake(&mut self, amount: Balance) -> Result<()> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let juror = self.env().caller();
+            let stake = self.stake_of(juror);
+            if stake.free() < amount {
+                return Err(Error::InsufficientStake);
+            }
+            self.set_stake(
+                juror,
+                Stake {
+                    total: stake.total - amount,
+                    ..stake
+                },
+            );
+
+            let sent = build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.token).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(TRANSFER_SELECTOR))
+                        .push_arg(juror)
+                        .push_arg(amount),
+                )
+                .returns::<core::result::Result<(), CalleeError>>()
+                .fire();
+            if !matches!(sent, Ok(Ok(()))) {
+                self.set_stake(juror, stake);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(Unstaked { juror, amount });
+            Ok(())
+        }
+
+        /// Records the caller's vote on `dispute_id`. Only jurors on the panel can
+        /// vote, once, before voting closes.
+        #[ink(message)]
+        pub fn vote(&mut self, dispute_id: DisputeId, ruling: Ruling) -> Result<()> {
+            let mut dispute = self.dispute(dispute_id).ok_or(Error::UnknownDispute)?;
+            let juror = self.env().caller();
+            match self.votes.get((dispute_id, juror)) {
+                None => return Err(Error::NotOnPanel),
+                Some(Some(_)) => return Err(Error::AlreadyVoted),
+                Some(None) => {}
+            }
+            if dispute.voting_ends.is_expired(self.env().block_timestamp()) {
+                return Err(Error::VotingClosed);
+            }
+            match ruling {
+                Ruling::Claimant => dispute.for_claimant += 1,
+                Ruling::Respondent => dispute.for_respondent += 1,
+            }
+            self.disputes.insert(dispute_id, &dispute);
+            self.votes.insert((dispute_id, juror), &Some(ruling));
+            self.env().emit_event(Voted {
+                dispute_id,
+                juror,
+                ruling,
+            });
+            Ok(())
+        }
+
+        /// Decides `dispute_id` for the side with more votes, or the respondent on
+        /// a tie. Anyone can call this once voting closes or every juror voted.
+        #[ink(message)]
+        pub fn resolve(&mut self, dispute_id: DisputeId) -> Result<Ruling> {
+            let dispute = self.dispute(dispute_id).ok_or(Error::UnknownDispute)?;
+            if dispute.ruling.is_some() {
+                return Err(Error::AlreadyResolved);
+            }
+            let all_voted = dispute.for_claimant + dispute.for_respondent == dispute.panel_size;
+            if !all_voted && !dispute.voting_ends.is_expired(self.env().block_timestamp()) {
+                return Err(Error::VotingOpen);
+            }
+            let ruling = if dispute.for_claimant > dispute.for_respondent {
+                Ruling::Claimant
+            } else {
+                Ruling::Respondent
+            };
+            self.disputes.insert(
+                dispute_id,
+                &Dispute {
+                    ruling: Some(ruling),
+                    ..dispute
+                },
+            );
+            self.env()
+                .emit_event(DisputeResolved { dispute_id, ruling });
+            Ok(ruling)
+        }
+
+        /// Releases the locked stake of `juror` on the resolved `dispute_id`,
+        /// rewarding them if they voted with the ruling and slashing them
+        /// otherwise. Returns the reward. Anyone can call this.
+        #[ink(message)]
+        pub fn settle(&mut self, dispute_id: DisputeId, juror: AccountId) -> Result<Balance> {
+            let dispute = self.dispute(dispute_id).ok_or(Error::UnknownDispute)?;
+            let ruling = dispute.ruling.ok_or(Error::NotResolved)?;
+            let vote = self
+                .votes
+                .get((dispute_id, juror))
+                .ok_or(Error::NotOnPanel)?;
+            let (reward, slashed) = if vote == Some(ruling) {
+                let winners = dispute.votes_for(ruling);
+                let losers = dispute.panel_size - winners;
+                (
+                    self.juror_stake * Balance::from(losers) / Balance::from(winners),
+                    0,
+                )
+            } else {
+                (0, self.juror_stake)
+            };
+
+            self.votes.remove((dispute_id, juror));
+            let stake = self.stake_of(juror);
+            self.set_stake(
+                juror,
+                Stake {
+                    total: stake.total + reward - slashed,
+                    locked: stake.locked - self.juror_stake,
+                },
+            );
+            self.env().emit_event(JurorSettled {
+                dispute_id,
+                juror,
+                reward,
+                slashed,
+            });
+            Ok(reward)
+        }
+
+        /// Adds `amount` to the stake of `juror`, registering them if new.
+        fn credit_stake(&mut self, juror: AccountId, amount: Balance) {
+            let stake = self.stake_of(juror);
+            self.set_stake(
+                juror,
+                Stake {
+                    total: stake.total + amount,
+                    ..stake
+                },
+            );
+            self.env().emit_event(Staked { juror, amount });
+        }
+
+        /// Stores the stake of `juror`, keeping the registry to the jurors with a
+        /// nonzero stake.
+        fn set_stake(&mut self, juror: AccountId, stake: Stake) {
+            let slot = self.juror_slots.get(juror);
+            if stake.total == 0 {
+                self.stakes.remove(juror);
+                if let Some(slot) = slot {
+                    // Move the last juror into the freed slot.
+                    let last = self.juror_count - 1;
+                    if slot != last {
+                        let moved = self.jurors.get(last).expect("registry slot is filled");
+                        self.jurors.insert(slot, &moved);
+                        self.juror_slots.insert(moved, &slot);
+                    }
+                    self.jurors.remove(last);
+                    self.juror_slots.remove(juror);
+                    self.juror_count = last;
+                }
+                return;
+            }
+            self.stakes.insert(juror, &stake);
+            if slot.is_none() {
+                self.jurors.insert(self.juror_count, &juror);
+                self.juror_slots.insert(juror, &self.juror_count);
+                self.juror_count += 1;
+            }
+        }
+
+        /// Returns whether `juror` can be seated on a panel for `claimant` and
+        /// `respondent` next to the jurors in `drawn`.
+        fn is_eligible(
+            &self,
+            juror: AccountId,
+            claimant: AccountId,
+            respondent: AccountId,
+            drawn: &[AccountId],
+        ) -> bool {
+            juror != claimant
+                && juror != respondent
+                && !drawn.contains(&juror)
+                && self.stake_of(juror).free() >= self.juror_stake
+        }
+
+        /// Draws `panel_size` eligible jurors, or returns `None` if there are not
+        /// enough of them.
+        fn draw_panel(
+            &self,
+            dispute_id: DisputeId,
+            claimant: AccountId,
+            respondent: AccountId,
+        ) -> Option<Vec<AccountId>> {
+            let count = self.juror_count;
+            if count == 0 {
+                return None;
+            }
+            let (seed, _) = self.env().random(&scale::Encode::encode(&dispute_id));
+            let mut entropy = [0u8; 32];
+            entropy.copy_from_slice(seed.as_ref());
+            let caller = self.env().caller();
+
+            let mut drawn = Vec::new();
+            for seat in 0..self.panel_size {
+                let nonce = (u64::from(dispute_id) << 32) | u64::from(seat);
+                let start = insecure_random_in_range(&entropy, &caller, nonce, 0, count.into());
+                let juror = (0..count)
+                    .map(|step| {
+                        let slot = (start as u32 + step) % count;
+                        self.jurors.get(slot).expect("registry slot is filled")
+                    })
+                    .find(|juror| self.is_eligible(*juror, claimant, respondent, &drawn))?;
+                drawn.push(juror);
+            }
+            Some(drawn)
+        }
+    }
+
+    impl Arbitrator for Arbitration {
+        /// Opens a dispute between `claimant` and `respondent` on behalf of the
+        /// caller, drawing its panel and locking the jurors' stake. Returns `None`
+        /// if not enough jurors are eligible.
+        #[ink(message)]
+        fn create_dispute(
+            &mut self,
+            claimant: AccountId,
+            respondent: AccountId,
+        ) -> Option<DisputeId> {
+            let dispute_id = self.next_dispute_id;
+            let panel = self.draw_panel(dispute_id, claimant, respondent)?;
+            self.next_dispute_id += 1;
+
+            let arbitrable = self.env().caller();
+            let voting_ends = self
+                .env()
+                .block_timestamp()
+                .saturating_add(self.voting_period);
+            self.disputes.insert(
+                dispute_id,
+                &Dispute {
+                    arbitrable,
+                    claimant,
+                    respondent,
+                    voting_ends: Deadline::at(voting_ends),
+                    panel_size: self.panel_size,
+                    for_claimant: 0,
+                    for_respondent: 0,
+                    ruling: None,
+                },
+            );
+            self.env().emit_event(DisputeCreated {
+                dispute_id,
+                arbitrable,
+                claimant,
+                respondent,
+                voting_ends,
+            });
+            for (seat, juror) in (0..).zip(panel) {
+                let stake = self.stake_of(juror);
+                self.set_stake(
+                    juror,
+                    Stake {
+                        locked: stake.locked + self.juror_stake,
+                        ..stake
+                    },
+                );
+                self.panels.insert((dispute_id, seat), &juror);
+                self.votes.insert((dispute_id, juror), &None::<Ruling>);
+                self.env().emit_event(JurorDrawn { dispute_id, juror });
+            }
+            Some(dispute_id)
+        }
+
+        /// Returns the ruling of `dispute_id`, or `None` until it is resolved.
+        #[ink(message)]
+        fn ruling(&self, dispute_id: DisputeId) -> Option<Ruling> {
+            self.dispute(dispute_id).and_then(|dispute| dispute.ruling)
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// stake with `credit_stake` instead of moving tokens.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use fixtures::BLOCK_TIME;
+        use ink_lang as ink;
+
+        const STAKE: Balance = 100;
+        const PERIOD: Timestamp = 200 * BLOCK_TIME;
+
+        /// Returns the `n`-th test juror, clear of the default accounts.
+        fn juror(n: u8) -> AccountId {
+            AccountId::from([0x80 + n; 32])
+        }
+
+        /// A court drawing panels of 3, with `jurors` jurors staking `STAKE`.
+        fn court_with_jurors(jurors: u8) -> Arbitration {
+            let mut court = Arbitration::new(AccountId::from([0x70; 32]), STAKE, 3, PERIOD);
+            for n in 0..jurors {
+                court.credit_stake(juror(n), STAKE);
+            }
+            court
+        }
+
+        /// Opens a dispute between bob and charlie and returns it with its panel.
+        fn open(court: &mut Arbitration) -> (DisputeId, Vec<AccountId>) {
+            let accounts = fixtures::accounts();
+            let dispute_id = court
+                .create_dispute(accounts.bob, accounts.charlie)
+                .expect("enough jurors");
+            (dispute_id, court.panel(dispute_id))
+        }
+
+        fn vote_as(
+            court: &mut Arbitration,
+            juror: AccountId,
+            dispute_id: DisputeId,
+            ruling: Ruling,
+        ) -> Result<()> {
+            fixtures::with_caller(juror, || court.vote(dispute_id, ruling))
+        }
+
+        #[ink::test]
+        fn panel_is_drawn_from_eligible_jurors() {
+            let accounts = fixtures::accounts();
+            let mut court = court_with_jurors(5);
+            court.credit_stake(accounts.bob, STAKE);
+            let (dispute_id, panel) = open(&mut court);
+
+            assert_eq!(panel.len(), 3);
+            assert!(!panel.contains(&accounts.bob));
+            for (i, juror) in panel.iter().enumerate() {
+                assert!(!panel[i + 1..].contains(juror));
+                assert_eq!(court.stake_of(*juror).locked, STAKE);
+            }
+            assert_eq!(court.ruling(dispute_id), None);
+        }
+
+        #[ink::test]
+        fn panels_need_free_stake() {
+            let accounts = fixtures::accounts();
+            let mut court = court_with_jurors(4);
+            let (_, first) = open(&mut court);
+
+            // One juror is left with free stake, so no second panel can be drawn
+            // until the first panel stakes more.
+            assert_eq!(court.create_dispute(accounts.bob, accounts.charlie), None);
+            for juror in &first {
+                court.credit_stake(*juror, STAKE);
+            }
+            assert!(court
+                .create_dispute(accounts.bob, accounts.charlie)
+                .is_some());
+        }
+
+        #[ink::test]
+        fn majority_decides_and_is_rewarded() {
+            let mut court = court_with_jurors(3);
+            let (dispute_id, panel) = open(&mut court);
+            assert_eq!(
+                vote_as(&mut court, panel[0], dispute_id, Ruling::Claimant),
+                Ok(())
+            );
+            assert_eq!(
+                vote_as(&mut court, panel[1], dispute_id, Ruling::Claimant),
+                Ok(())
+            );
+            assert_eq!(court.resolve(dispute_id), Err(Error::VotingOpen));
+            assert_eq!(
+                vote_as(&mut court, panel[2], dispute_id, Ruling::Respondent),
+                Ok(())
+            );
+
+            // Every juror voted, so the dispute can be resolved early.
+            assert_eq!(court.resolve(dispute_id), Ok(Ruling::Claimant));
+            assert_eq!(court.ruling(dispute_id), Some(Ruling::Claimant));
+
+            assert_eq!(court.settle(dispute_id, panel[0]), Ok(STAKE / 2));
+            assert_eq!(court.settle(dispute_id, panel[1]), Ok(STAKE / 2));
+            assert_eq!(court.settle(dispute_id, panel[2]), Ok(0));
+            assert_eq!(
+                court.stake_of(panel[0]),
+                Stake {
+                    total: 150,
+                    locked: 0
+                }
+            );
+            assert_eq!(court.stake_of(panel[2]), Stake::default());
+            assert_eq!(court.settle(dispute_id, panel[0]), Err(Error::NotOnPanel));
+            assert_eq!(court.juror_count(), 2);
+        }
+
+        #[ink::test]
+        fn absent_jurors_are_slashed() {
+            let mut court = court_with_jurors(3);
+            let (dispute_id, panel) = open(&mut court);
+            assert_eq!(
+                vote_as(&mut court, panel[0], dispute_id, Ruling::Respondent),
+                Ok(())
+            );
+            // The end itself still belongs to the vote.
+            fixtures::set_block_timestamp(PERIOD);
+            assert_eq!(court.resolve(dispute_id), Err(Error::VotingOpen));
+            assert_eq!(
+                vote_as(&mut court, panel[1], dispute_id, Ruling::Respondent),
+                Ok(())
+            );
+
+            fixtures::set_block_timestamp(PERIOD + BLOCK_TIME);
+            assert_eq!(
+                vote_as(&mut court, panel[2], dispute_id, Ruling::Claimant),
+                Err(Error::VotingClosed)
+            );
+            assert_eq!(court.resolve(dispute_id), Ok(Ruling::Respondent));
+            assert_eq!(court.resolve(dispute_id), Err(Error::AlreadyResolved));
+
+            assert_eq!(court.settle(dispute_id, panel[0]), Ok(STAKE / 2));
+            assert_eq!(court.settle(dispute_id, panel[1]), Ok(STAKE / 2));
+            assert_eq!(court.settle(dispute_id, panel[2]), Ok(0));
+            assert_eq!(court.stake_of(panel[1]).total, STAKE + STAKE / 2);
+            assert_eq!(court.stake_of(panel[2]).total, 0);
+        }
+
+        #[ink::test]
+        fn ties_go_to_the_respondent() {
+            let mut court = court_with_jurors(3);
+            let (dispute_id, panel) = open(&mut court);
+            assert_eq!(
+                vote_as(&mut court, panel[0], dispute_id, Ruling::Claimant),
+                Ok(())
+            );
+            assert_eq!(
+                vote_as(&mut court, panel[1], dispute_id, Ruling::Respondent),
+                Ok(())
+            );
+            fixtures::set_block_timestamp(PERIOD + BLOCK_TIME);
+            assert_eq!(court.resolve(dispute_id), Ok(Ruling::Respondent));
+            assert_eq!(court.settle(dispute_id, panel[0]), Ok(0));
+        }
+
+        #[ink::test]
+        fn only_panel_votes_once() {
+            let accounts = fixtures::accounts();
+            let mut court = court_with_jurors(3);
+            let (dispute_id, panel) = open(&mut court);
+            assert_eq!(
+                vote_as(&mut court, accounts.eve, dispute_id, Ruling::Claimant),
+                Err(Error::NotOnPanel)
+            );
+            assert_eq!(
+                vote_as(&mut court, panel[0], dispute_id, Ruling::Claimant),
+                Ok(())
+            );
+            assert_eq!(
+                vote_as(&mut court, panel[0], dispute_id, Ruling::Respondent),
+                Err(Error::AlreadyVoted)
+            );
+            assert_eq!(court.settle(dispute_id, panel[0]), Err(Error::NotResolved));
+            assert_eq!(
+                vote_as(&mut court, panel[0], dispute_id + 1, Ruling::Claimant),
+                Err(Error::UnknownDispute)
+            );
+        }
+
+        #[ink::test]
+        fn locked_stake_cannot_be_unstaked() {
+            let mut court = court_with_jurors(3);
+            let (_, panel) = open(&mut court);
+            court.credit_stake(panel[0], 50);
+            fixtures::set_caller(panel[0]);
+            assert_eq!(court.unstake(51), Err(Error::InsufficientStake));
+            assert_eq!(court.unstake(0), Err(Error::ZeroAmount));
+        }
+
+        #[ink::test]
+        fn registry_is_bounded() {
+            let mut court = court_with_jurors(MAX_JURORS as u8);
+            assert_eq!(court.juror_count(), MAX_JURORS);
+            fixtures::set_caller(juror(MAX_JURORS as u8));
+            assert_eq!(court.stake(1), Err(Error::TooManyJurors));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Deploy an ERC-20 token first
+//    - Upload the generated .contract file and deploy it with the token address,
+//      a juror stake, an odd panel size and a voting period
+//
+// 4. Interact with the contract:
+//    - As a juror, approve the court on the token and use "stake"
+//    - From the arbitrable contract, use "Arbitrator::createDispute"
+//    - Jurors on the panel use "vote"; anyone uses "resolve", then "settle" for
+//      each juror
+//    - The arbitrable contract reads "Arbitrator::ruling"
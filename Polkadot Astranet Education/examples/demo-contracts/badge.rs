@@ -0,0 +1,611 @@
+// examples/demo-contracts/badge.rs
+//
+// An attendance badge contract example for Polkadot using ink!
+// Organizers create an event code, and everyone who attended can mint one badge for
+// it. Badges follow the ERC-1155 multi-token layout, where the event code is the
+// token id, but they are soulbound: they cannot be transferred or approved, only
+// claimed.
+//
+// Vouchers
+// --------
+// Claims are authorised off-chain. The event's signer hands each attendee a voucher:
+// an ECDSA (secp256k1) signature over the BLAKE2-256 hash of the SCALE-encoded
+// `(badge contract, event code, attendee)` tuple; see `voucher_hash`. Binding the
+// contract and the attendee stops a voucher from being used on another deployment or
+// by someone it was not handed to, and each account can hold at most one badge per
+// event, so a voucher mints once. Signer accounts are the BLAKE2-256 hash of their
+// compressed public key, as in `forwarder.rs`.
+//
+// The organizer's on-chain account and the signing key are separate, so the key can
+// live on the check-in laptop and be rotated with `set_signer` if it leaks.
+//
+// Awards
+// ------
+// An organizer can also `award` a badge straight to an account, with no voucher. This
+// is for badges earned somewhere other than at the door: `services/badge-minter`
+// awards them, signing as the organizer, when learners complete milestones in
+// `progress.rs`. An award and a claim mint the same badge, once per account.
+//
+// Testing
+// -------
+// The tests sign vouchers with a real secp256k1 key, so add
+// `secp256k1 = { version = "0.28", features = ["recovery", "global-context"] }`
+// to the contract's dev-dependencies.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod badge {
+    use ink_env::hash::{Blake2x256, CryptoHash};
+    use ink_prelude::vec::Vec;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// The code of an event, which is also the id of its badge.
+    pub type EventCode = u32;
+
+    /// The badge error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the event code is already taken.
+        EventExists,
+        /// Returned if there is no event with the code.
+        UnknownEvent,
+        /// Returned if the caller is not the event's organizer.
+        NotOrganizer,
+        /// Returned if the caller already holds the event's badge.
+        AlreadyClaimed,
+        /// Returned if the voucher signature is malformed.
+        InvalidSignature,
+        /// Returned if the voucher was not signed by the event's signer.
+        InvalidVoucher,
+        /// Returned by every transfer and approval: badges are soulbound.
+        NonTransferable,
+        /// Returned if the batch query has differently sized lists.
+        LengthMismatch,
+    }
+
+    /// The badge result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// An event whose attendees can claim a badge.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct BadgeEvent {
+        /// Account that created the event and may change its signer.
+        pub organizer: AccountId,
+        /// ECDSA account whose vouchers are accepted.
+        pub signer: AccountId,
+        /// Number of badges minted.
+        pub supply: Balance,
+    }
+
+    /// Returns the hash an event's signer signs to let `attendee` claim the badge
+    /// of `code` at `badge`.
+    pub fn voucher_hash(badge: &AccountId, code: EventCode, attendee: &AccountId) -> [u8; 32] {
+        let mut output = [0u8; 32];
+        Blake2x256::hash(
+            &scale::Encode::encode(&(badge, code, attendee)),
+            &mut output,
+        );
+        output
+    }
+
+    /// Returns the `AccountId` of the ECDSA key with compressed public key `public_key`.
+    pub fn ecdsa_account(public_key: &[u8; 33]) -> AccountId {
+        let mut output = [0u8; 32];
+        Blake2x256::hash(public_key, &mut output);
+        AccountId::from(output)
+    }
+
+    // Events are mirrored in `crates/events/src/badge.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when an organizer creates an event.
+    #[ink(event)]
+    pub struct EventCreated {
+        #[ink(topic)]
+        code: EventCode,
+        #[ink(topic)]
+        organizer: AccountId,
+        signer: AccountId,
+    }
+
+    /// Event emitted when an organizer rotates the signing key of an event.
+    #[ink(event)]
+    pub struct SignerChanged {
+        #[ink(topic)]
+        code: EventCode,
+        signer: AccountId,
+    }
+
+    /// Event emitted when badges are minted, in the shape of ERC-1155's
+    /// `TransferSingle`. `from` is always `None`, since badges never move.
+    #[ink(event)]
+    pub struct TransferSingle {
+        #[ink(topic)]
+        operator: AccountId,
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        id: EventCode,
+        value: Balance,
+    }
+
+    /// The badge storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Badge {
+        /// Mapping from event code to the event.
+        events: Mapping<EventCode, BadgeEvent>,
+        /// Mapping from event code and attendee to whether they hold its badge.
+        claimed: Mapping<(EventCode, AccountId), bool>,
+    }
+
+    impl Badge {
+        /// Creates a contract without events.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|_: &mut Self| {})
+        }
+
+        /// Returns the event with `code`, if any.
+        #[ink(message)]
+        pub fn event(&self, code: EventCode) -> Option<BadgeEvent> {
+            self.events.get(code)
+        }
+
+        /// Returns the number of badges `owner` holds for `id`, which is 0 or 1.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId, id: EventCode) -> Balance {
+            if self.claimed.get((id, owner)).unwrap_or(false) {
+                1
+            } else {
+                0
+            }
+        }
+
+        /// Returns `balance_of(owners[i], ids[i])` for every `i`.
+        #[ink(message)]
+        pub fn balance_of_batch(
+            &self,
+            owners: Vec<AccountId>,
+            ids: Vec<EventCode>,
+        ) -> Result<Vec<Balance>> {
+            if owners.len() != ids.len() {
+                return Err(Error::LengthMismatch);
+            }
+            Ok(owners
+                .into_iter()
+                .zip(ids)
+                .map(|(owner, id)| self.balance_of(owner, id))
+                .collect())
+        }
+
+        /// Returns the number of badges minted for `id`.
+        #[ink(message)]
+        pub fn total_supply(&self, id: EventCode) -> Balance {
+            self.event(id).map(|event| event.supply).unwrap_or(0)
+        }
+
+        /// Creates the event `code` with the caller as organizer, accepting
+        /// vouchers signed by `signer`.
+        #[ink(message)]
+        pub fn create_event(&mut self, code: EventCode, signer: AccountId) -> Result<()> {
+            if self.events.contains(code) {
+                return Err(Error::EventExists);
+            }
+            let organizer = self.env().caller();
+            self.events.insert(
+                code,
+                &BadgeEvent {
+                    organizer,
+                    signer,
+                    supply: 0,
+                },
+            );
+            self.env().emit_event(EventCreated {
+                code,
+                organizer,
+                signer,
+            });
+            Ok(())
+        }
+
+        /// Accepts vouchers for `code` signed by `signer` only, so vouchers signed
+        /// by the previous key stop working. Only the event's organizer can call
+        /// this.
+        #[ink(message)]
+        pub fn set_signer(&mut self, code: EventCode, signer: AccountId) -> Result<()> {
+            let event = self.event(code).ok_or(Error::UnknownEvent)?;
+            if self.env().caller() != event.organizer {
+                return Err(Error::NotOrganizer);
+            }
+            self.events.insert(code, &BadgeEvent { signer, ..event });
+            self.env().emit_event(SignerChanged { code, signer });
+            Ok(())
+        }
+
+        /// Mints the caller the badge of `code`, given the event signer's
+        /// `signature` over `voucher_hash(this contract, code, caller)`.
+        #[ink(message)]
+        pub fn claim(&mut self, code: EventCode, signature: [u8; 65]) -> Result<()> {
+            let attendee = self.env().caller();
+            let event = self.event(code).ok_or(Error::UnknownEvent)?;
+            if self.balance_of(attendee, code) > 0 {
+                return Err(Error::AlreadyClaimed);
+            }
+
+            let hash = voucher_hash(&self.env().account_id(), code, &attendee);
+            let public_key = self
+                .env()
+                .ecdsa_recover(&signature, &hash)
+                .map_err(|_| Error::InvalidSignature)?;
+            if ecdsa_account(&public_key) != event.signer {
+                return Err(Error::InvalidVoucher);
+            }
+
+            self.mint(code, event, attendee, attendee);
+            Ok(())
+        }
+
+        /// Mints `attendee` the badge of `code` without a voucher. Only the event's
+        /// organizer can call this.
+        #[ink(message)]
+        pub fn award(&mut self, code: EventCode, attendee: AccountId) -> Result<()> {
+            let event = self.event(code).ok_or(Error::UnknownEvent)?;
+            let organizer = self.env().caller();
+            if organizer != event.organizer {
+                return Err(Error::NotOrganizer);
+            }
+            if self.balance_of(attendee, code) > 0 {
+                return Err(Error::AlreadyClaimed);
+            }
+            self.mint(code, event, organizer, attendee);
+            Ok(())
+        }
+
+        /// Always fails with `NonTransferable`: badges stay with their attendee.
+        #[ink(message)]
+        pub fn safe_transfer_from(
+            &mut self,
+            _from: AccountId,
+            _to: AccountId,
+            _id: EventCode,
+            _value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<()> {
+            Err(Error::NonTransferable)
+        }
+
+        /// Always fails with `NonTransferable`: badges stay with their attendee.
+        #[ink(message)]
+        pub fn safe_batch_transfer_from(
+            &mut self,
+            _from: AccountId,
+            _to: AccountId,
+            _ids: Vec<EventCode>,
+            _values: Vec<Balance>,
+            _data: Vec<u8>,
+        ) -> Result<()> {
+            Err(Error::NonTransferable)
+        }
+
+        /// Always fails with `NonTransferable`, since there is nothing an operator
+        /// could do.
+        #[ink(message)]
+        pub fn set_approval_for_all(
+            &mut self,
+            _operator: AccountId,
+            _approved: bool,
+        ) -> Result<()> {
+            Err(Error::NonTransferable)
+        }
+
+        /// Always returns `false`.
+        #[ink(message)]
+        pub fn is_approved_for_all(&self, _owner: AccountId, _operator: AccountId) -> bool {
+            false
+        }
+
+        /// Mints `attendee` the badge of `code` on behalf of `operator`.
+        fn mint(
+            &mut self,
+            code: EventCode,
+            event: BadgeEvent,
+            operator: AccountId,
+            attendee: AccountId,
+        ) {
+            self.claimed.insert((code, attendee), &true);
+            self.events.insert(
+                code,
+                &BadgeEvent {
+                    supply: event.supply + 1,
+                    ..event
+                },
+            );
+            self.env().emit_event(TransferSingle {
+                operator,
+                from: None,
+                to: Some(attendee),
+                id: code,
+                value: 1,
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+        use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+
+        type Env = ink_env::DefaultEnvironment;
+
+        const CODE: EventCode = 2024;
+
+        /// A test signer with a fixed ECDSA key.
+        struct Signer {
+            secret: SecretKey,
+            public: [u8; 33],
+        }
+
+        impl Signer {
+            fn new(seed: u8) -> Self {
+                let secret = SecretKey::from_slice(&[seed; 32]).expect("valid secret key");
+                let public = PublicKey::from_secret_key(&Secp256k1::new(), &secret).serialize();
+                Self { secret, public }
+            }
+
+            fn account(&self) -> AccountId {
+                ecdsa_account(&self.public)
+            }
+
+            fn voucher(&self, code: EventCode, attendee: AccountId) -> [u8; 65] {
+                let hash = voucher_hash(&contract_address(), code, &attendee);
+                let message = Message::from_digest_slice(&hash).expect("hash is 32 bytes");
+                let (recovery_id, compact) = Secp256k1::new()
+                    .sign_ecdsa_recoverable(&message, &self.secret)
+                    .serialize_compact();
+                let mut signature = [0u8; 65];
+                signature[..64].copy_from_slice(&compact);
+                signature[64] = recovery_id.to_i32() as u8;
+                signature
+            }
+        }
+
+        fn contract_address() -> AccountId {
+            ink_env::test::callee::<Env>()
+        }
+
+        /// A contract with event `CODE` organized by alice and signed by `signer`.
+        fn with_event(signer: &Signer) -> Badge {
+            let mut badge = Badge::new();
+            assert_eq!(badge.create_event(CODE, signer.account()), Ok(()));
+            badge
+        }
+
+        #[ink::test]
+        fn voucher_mints_one_badge() {
+            let accounts = fixtures::accounts();
+            let signer = Signer::new(0x11);
+            let mut badge = with_event(&signer);
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                badge.claim(CODE, signer.voucher(CODE, accounts.bob)),
+                Ok(())
+            );
+            assert_eq!(badge.balance_of(accounts.bob, CODE), 1);
+            assert_eq!(badge.balance_of(accounts.charlie, CODE), 0);
+            assert_eq!(badge.total_supply(CODE), 1);
+            assert_eq!(ink_env::test::recorded_events().count(), 2);
+        }
+
+        #[ink::test]
+        fn badge_is_claimed_once() {
+            let accounts = fixtures::accounts();
+            let signer = Signer::new(0x11);
+            let mut badge = with_event(&signer);
+            let voucher = signer.voucher(CODE, accounts.bob);
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(badge.claim(CODE, voucher), Ok(()));
+            assert_eq!(badge.claim(CODE, voucher), Err(Error::AlreadyClaimed));
+            assert_eq!(badge.balance_of(accounts.bob, CODE), 1);
+            assert_eq!(badge.total_supply(CODE), 1);
+        }
+
+        #[ink::test]
+        fn voucher_is_bound_to_attendee_and_event() {
+            let accounts = fixtures::accounts();
+            let signer = Signer::new(0x11);
+            let mut badge = with_event(&signer);
+            assert_eq!(badge.create_event(CODE + 1, signer.account()), Ok(()));
+
+            // Charlie cannot use bob's voucher, nor bob use it for another event.
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(
+                badge.claim(CODE, signer.voucher(CODE, accounts.bob)),
+                Err(Error::InvalidVoucher)
+            );
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                badge.claim(CODE + 1, signer.voucher(CODE, accounts.bob)),
+                Err(Error::InvalidVoucher)
+            );
+            assert_eq!(badge.total_supply(CODE), 0);
+        }
+
+        #[ink::test]
+        fn voucher_must_come_from_signer() {
+            let accounts = fixtures::accounts();
+            let signer = Signer::new(0x11);
+            let stranger = Signer::new(0x22);
+            let mut badge = with_event(&signer);
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                badge.claim(CODE, stranger.voucher(CODE, accounts.bob)),
+                Err(Error::InvalidVoucher)
+            );
+            assert_eq!(badge.claim(CODE, [0u8; 65]), Err(Error::InvalidSignature));
+            assert_eq!(
+                badge.claim(CODE + 1, signer.voucher(CODE + 1, accounts.bob)),
+                Err(Error::UnknownEvent)
+            );
+        }
+
+        #[ink::test]
+        fn rotated_signer_replaces_old_key() {
+            let accounts = fixtures::accounts();
+            let old = Signer::new(0x11);
+            let new = Signer::new(0x22);
+            let mut badge = with_event(&old);
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                badge.set_signer(CODE, new.account()),
+                Err(Error::NotOrganizer)
+            );
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(badge.set_signer(CODE, new.account()), Ok(()));
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                badge.claim(CODE, old.voucher(CODE, accounts.bob)),
+                Err(Error::InvalidVoucher)
+            );
+            assert_eq!(badge.claim(CODE, new.voucher(CODE, accounts.bob)), Ok(()));
+        }
+
+        #[ink::test]
+        fn organizer_awards_badges_without_vouchers() {
+            let accounts = fixtures::accounts();
+            let signer = Signer::new(0x11);
+            let mut badge = with_event(&signer);
+
+            assert_eq!(badge.award(CODE, accounts.bob), Ok(()));
+            assert_eq!(badge.balance_of(accounts.bob, CODE), 1);
+            assert_eq!(badge.total_supply(CODE), 1);
+            assert_eq!(ink_env::test::recorded_events().count(), 2);
+
+            // An awarded badge cannot be claimed or awarded again.
+            assert_eq!(badge.award(CODE, accounts.bob), Err(Error::AlreadyClaimed));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                badge.claim(CODE, signer.voucher(CODE, accounts.bob)),
+                Err(Error::AlreadyClaimed)
+            );
+            assert_eq!(badge.total_supply(CODE), 1);
+        }
+
+        #[ink::test]
+        fn only_the_organizer_awards() {
+            let accounts = fixtures::accounts();
+            let signer = Signer::new(0x11);
+            let mut badge = with_event(&signer);
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(badge.award(CODE, accounts.bob), Err(Error::NotOrganizer));
+            assert_eq!(
+                badge.award(CODE + 1, accounts.bob),
+                Err(Error::UnknownEvent)
+            );
+            assert_eq!(badge.balance_of(accounts.bob, CODE), 0);
+        }
+
+        #[ink::test]
+        fn badges_are_soulbound() {
+            let accounts = fixtures::accounts();
+            let signer = Signer::new(0x11);
+            let mut badge = with_event(&signer);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                badge.claim(CODE, signer.voucher(CODE, accounts.bob)),
+                Ok(())
+            );
+
+            assert_eq!(
+                badge.safe_transfer_from(accounts.bob, accounts.charlie, CODE, 1, Vec::new()),
+                Err(Error::NonTransferable)
+            );
+            assert_eq!(
+                badge.safe_batch_transfer_from(
+                    accounts.bob,
+                    accounts.charlie,
+                    vec![CODE],
+                    vec![1],
+                    Vec::new()
+                ),
+                Err(Error::NonTransferable)
+            );
+            assert_eq!(
+                badge.set_approval_for_all(accounts.charlie, true),
+                Err(Error::NonTransferable)
+            );
+            assert!(!badge.is_approved_for_all(accounts.bob, accounts.charlie));
+            assert_eq!(badge.balance_of(accounts.bob, CODE), 1);
+        }
+
+        #[ink::test]
+        fn batch_balances_follow_claims() {
+            let accounts = fixtures::accounts();
+            let signer = Signer::new(0x11);
+            let mut badge = with_event(&signer);
+            assert_eq!(
+                badge.create_event(CODE, signer.account()),
+                Err(Error::EventExists)
+            );
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                badge.claim(CODE, signer.voucher(CODE, accounts.bob)),
+                Ok(())
+            );
+
+            assert_eq!(
+                badge.balance_of_batch(vec![accounts.bob, accounts.charlie], vec![CODE, CODE]),
+                Ok(vec![1, 0])
+            );
+            assert_eq!(
+                badge.balance_of_batch(vec![accounts.bob], Vec::new()),
+                Err(Error::LengthMismatch)
+            );
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy with the "new" constructor
+//
+// 4. Interact with the contract:
+//    - As an organizer, use "createEvent" with an event code and the ECDSA account
+//      of the key that signs vouchers
+//    - Sign `voucher_hash(contract, code, attendee)` off-chain for each attendee
+//    - As an attendee, use "claim" with the event code and the voucher
+//    - Use "balanceOf" to check for a badge
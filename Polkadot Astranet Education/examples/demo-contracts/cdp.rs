@@ -0,0 +1,961 @@
+// examples/demo-contracts/cdp.rs
+//
+// A collateralized debt position (CDP) contract example for Polkadot using ink!
+// Users deposit a collateral token (typically a wrapped native token, or any token
+// with the selectors of `erc20.rs`) and mint this contract's own synthetic ERC-20
+// against it, as long as their collateral stays worth at least the minimum
+// collateral ratio of their debt. The collateral price is read from an oracle with
+// the `price(asset)` message of `mocks/mock_oracle.rs`.
+//
+// Ratio math
+// ----------
+// The oracle quotes the price of `PRICE_UNIT` smallest units of collateral (one whole
+// token at 12 decimals) in the synthetic's smallest units. Ratios are in basis points:
+//
+//     value = collateral * price / PRICE_UNIT
+//     ratio = value * 10_000 / debt
+//
+// A position is safe while its ratio is at least `min_collateral_ratio`, e.g. 15_000
+// for 150%. Minting and withdrawing must leave a position safe; once a price drop
+// makes it unsafe, anyone may liquidate it by burning its whole debt from their own
+// synthetic balance. The liquidator receives collateral worth the debt plus
+// `liquidation_bonus`, credited to their own position, and the owner keeps the rest.
+//
+// ink! 3 does not revert storage when a message returns an error, so collateral is
+// pulled before it is credited, and a withdrawal is credited back if sending fails.
+//
+// Emergency stop
+// --------------
+// Minting goes through the utils crate's `CircuitBreaker`. Its guardian, the
+// deployer at first, can `trip` it if the oracle misbehaves, which stops `mint`
+// until `reset`. Deposits, repayments, withdrawals and liquidations stay open, so
+// users can still shrink or close their positions and unsafe ones still get cleared.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod cdp {
+    use astranet_utils::circuit_breaker::{CircuitBreaker, CircuitBreakerError};
+    use ink_env::call::{build_call, Call, ExecutionInput, Selector};
+    use ink_prelude::string::String;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Ratios and the liquidation bonus are in basis points of this.
+    pub const BPS: u128 = 10_000;
+
+    /// Smallest units of collateral the oracle price is quoted for.
+    pub const PRICE_UNIT: Balance = 1_000_000_000_000;
+
+    /// Selector of the ERC-20 `transfer(to, value)` message.
+    const TRANSFER_SELECTOR: [u8; 4] = [0x84, 0xa1, 0x5d, 0xa1];
+
+    /// Selector of the ERC-20 `transfer_from(from, to, value)` message.
+    const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x0b, 0x39, 0x6f, 0x18];
+
+    /// Selector of the oracle's `price(asset)` message.
+    const PRICE_SELECTOR: [u8; 4] = [0xd4, 0xbd, 0x7b, 0xc1];
+
+    /// The CDP error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the synthetic balance is insufficient for the operation.
+        InsufficientBalance,
+        /// Returned if the synthetic allowance is insufficient for the operation.
+        InsufficientAllowance,
+        /// Returned if the amount is zero.
+        ZeroAmount,
+        /// Returned if the position holds less collateral than asked for.
+        InsufficientCollateral,
+        /// Returned if minting or withdrawing would leave the position below the
+        /// minimum collateral ratio.
+        BelowMinimumRatio,
+        /// Returned if a repayment is larger than the position's debt.
+        ExceedsDebt,
+        /// Returned if the position is safe and cannot be liquidated.
+        NotLiquidatable,
+        /// Returned if the oracle has no usable price for the collateral.
+        PriceUnavailable,
+        /// Returned if the collateral token refused to move the tokens.
+        TransferFailed,
+        /// Returned if the ratio math overflows.
+        Overflow,
+        /// Returned if the caller is not the guardian.
+        NotGuardian,
+        /// Returned if minting is stopped by the circuit breaker.
+        Tripped,
+        /// Returned if the circuit breaker is not tripped.
+        NotTripped,
+    }
+
+    impl From<CircuitBreakerError> for Error {
+        fn from(error: CircuitBreakerError) -> Self {
+            match error {
+                CircuitBreakerError::NotGuardian => Error::NotGuardian,
+                CircuitBreakerError::Tripped => Error::Tripped,
+                CircuitBreakerError::NotTripped => Error::NotTripped,
+            }
+        }
+    }
+
+    /// The CDP result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Stands in for the error types of the token and the oracle. Both encode their
+    /// errors as a bare variant index, and all this contract needs is to tell
+    /// success from failure.
+    #[derive(scale::Decode)]
+    struct CalleeError(#[allow(dead_code)] u8);
+
+    /// The collateral deposited by an account and the synthetic it has minted.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Position {
+        /// Collateral held for the account.
+        pub collateral: Balance,
+        /// Synthetic minted against it and not yet repaid.
+        pub debt: Balance,
+    }
+
+    /// Returns what `collateral` is worth in the synthetic at `price`.
+    pub fn collateral_value(collateral: Balance, price: Balance) -> Result<Balance> {
+        collateral
+            .checked_mul(price)
+            .map(|value| value / PRICE_UNIT)
+            .ok_or(Error::Overflow)
+    }
+
+    /// Returns the collateral ratio of a position in basis points, or `None` if it
+    /// has no debt.
+    pub fn collateral_ratio(position: Position, price: Balance) -> Result<Option<u128>> {
+        if position.debt == 0 {
+            return Ok(None);
+        }
+        let value = collateral_value(position.collateral, price)?;
+        value
+            .checked_mul(BPS)
+            .map(|scaled| Some(scaled / position.debt))
+            .ok_or(Error::Overflow)
+    }
+
+    /// Returns `true` if the position's ratio is at least `min_ratio`. A position
+    /// without debt is always safe.
+    pub fn is_safe(position: Position, price: Balance, min_ratio: u128) -> Result<bool> {
+        Ok(match collateral_ratio(position, price)? {
+            Some(ratio) => ratio >= min_ratio,
+            None => true,
+        })
+    }
+
+    /// Returns the most synthetic `collateral` can back at `price` and `min_ratio`.
+    pub fn max_debt(collateral: Balance, price: Balance, min_ratio: u128) -> Result<Balance> {
+        let value = collateral_value(collateral, price)?;
+        value
+            .checked_mul(BPS)
+            .map(|scaled| scaled / min_ratio)
+            .ok_or(Error::Overflow)
+    }
+
+    /// Returns the collateral worth `debt` plus `bonus` basis points at `price`.
+    pub fn collateral_for(debt: Balance, price: Balance, bonus: u128) -> Result<Balance> {
+        debt.checked_mul(BPS + bonus)
+            .map(|scaled| scaled / BPS)
+            .and_then(|with_bonus| with_bonus.checked_mul(PRICE_UNIT))
+            .map(|units| units / price)
+            .ok_or(Error::Overflow)
+    }
+
+    // Events are mirrored in `crates/events/src/cdp.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when a synthetic transfer occurs, or synthetic is minted (`from`
+    /// is `None`) or burned (`to` is `None`).
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    /// Event emitted when an approval occurs.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when collateral is added to or taken from a position.
+    #[ink(event)]
+    pub struct CollateralChanged {
+        #[ink(topic)]
+        owner: AccountId,
+        deposited: Balance,
+        withdrawn: Balance,
+    }
+
+    /// Event emitted when the debt of a position grows by minting or shrinks by
+    /// repaying.
+    #[ink(event)]
+    pub struct DebtChanged {
+        #[ink(topic)]
+        owner: AccountId,
+        minted: Balance,
+        repaid: Balance,
+    }
+
+    /// Event emitted when an unsafe position is liquidated.
+    #[ink(event)]
+    pub struct Liquidated {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        liquidator: AccountId,
+        debt: Balance,
+        collateral: Balance,
+    }
+
+    /// The CDP storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Cdp {
+        /// Total synthetic supply.
+        total_supply: Balance,
+        /// Mapping from owner to synthetic balance.
+        balances: Mapping<AccountId, Balance>,
+        /// Mapping from owner to spender to allowance.
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+        /// Synthetic name.
+        name: String,
+        /// Synthetic symbol.
+        symbol: String,
+        /// Synthetic decimals.
+        decimals: u8,
+        /// Token accepted as collateral.
+        collateral_token: AccountId,
+        /// Oracle quoting the collateral price.
+        oracle: AccountId,
+        /// Lowest ratio a position may be left at, in basis points.
+        min_collateral_ratio: u128,
+        /// Extra collateral a liquidator receives, in basis points of the debt.
+        liquidation_bonus: u128,
+        /// Mapping from account to its position.
+        positions: Mapping<AccountId, Position>,
+        /// Emergency stop for `mint`.
+        breaker: CircuitBreaker,
+    }
+
+    impl Cdp {
+        /// Creates a CDP minting a synthetic called `name` against `collateral_token`,
+        /// priced by `oracle`. `min_collateral_ratio` must exceed 100% plus
+        /// `liquidation_bonus`, so a position that has only just become unsafe still
+        /// holds enough collateral to pay the liquidator in full.
+        #[ink(constructor)]
+        pub fn new(
+            collateral_token: AccountId,
+            oracle: AccountId,
+            min_collateral_ratio: u128,
+            liquidation_bonus: u128,
+            name: String,
+            symbol: String,
+            decimals: u8,
+        ) -> Self {
+            assert!(
+                min_collateral_ratio > BPS + liquidation_bonus,
+                "minimum ratio must exceed 100% plus the liquidation bonus"
+            );
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.name = name;
+                contract.symbol = symbol;
+                contract.decimals = decimals;
+                contract.collateral_token = collateral_token;
+                contract.oracle = oracle;
+                contract.min_collateral_ratio = min_collateral_ratio;
+                contract.liquidation_bonus = liquidation_bonus;
+                contract.breaker = CircuitBreaker::new(Self::env().caller());
+            })
+        }
+
+        /// Returns the synthetic name.
+        #[ink(message)]
+        pub fn name(&self) -> String {
+            self.name.clone()
+        }
+
+        /// Returns the synthetic symbol.
+        #[ink(message)]
+        pub fn symbol(&self) -> String {
+            self.symbol.clone()
+        }
+
+        /// Returns the synthetic decimals.
+        #[ink(message)]
+        pub fn decimals(&self) -> u8 {
+            self.decimals
+        }
+
+        /// Returns the total synthetic supply.
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// Returns the synthetic balance of `owner`.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or(0)
+        }
+
+        /// Returns the amount which `spender` is allowed to withdraw from `owner`.
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or(0)
+        }
+
+        /// Returns the token accepted as collateral.
+        #[ink(message)]
+        pub fn collateral_token(&self) -> AccountId {
+            self.collateral_token
+        }
+
+        /// Returns the oracle quoting the collateral price.
+        #[ink(message)]
+        pub fn oracle(&self) -> AccountId {
+            self.oracle
+        }
+
+        /// Returns the lowest ratio a position may be left at, in basis points.
+        #[ink(message)]
+        pub fn min_collateral_ratio(&self) -> u128 {
+            self.min_collateral_ratio
+        }
+
+        /// Returns the liquidation bonus, in basis points of the debt.
+        #[ink(message)]
+        pub fn liquidation_bonus(&self) -> u128 {
+            self.liquidation_bonus
+        }
+
+        /// Returns the account that can stop and restart minting.
+        #[ink(message)]
+        pub fn guardian(&self) -> AccountId {
+            self.breaker.guardian()
+        }
+
+        /// Returns whether minting is stopped.
+        #[ink(message)]
+        pub fn is_tripped(&self) -> bool {
+            self.breaker.is_tripped()
+        }
+
+        /// Stops minting until `reset`. Only the guardian can call this.
+        #[ink(message)]
+        pub fn trip(&mut self) -> Result<()> {
+            self.breaker.trip(self.env().caller())?;
+            Ok(())
+        }
+
+        /// Restarts minting after `trip`. Only the guardian can call this.
+        #[ink(message)]
+        pub fn reset(&mut self) -> Result<()> {
+            self.breaker.reset(self.env().caller())?;
+            Ok(())
+        }
+
+        /// Hands the guardian role to `new_guardian`. Only the guardian can call
+        /// this.
+        #[ink(message)]
+        pub fn set_guardian(&mut self, new_guardian: AccountId) -> Result<()> {
+            self.breaker
+                .set_guardian(self.env().caller(), new_guardian)?;
+            Ok(())
+        }
+
+        /// Returns the position of `owner`.
+        #[ink(message)]
+        pub fn position_of(&self, owner: AccountId) -> Position {
+            self.positions.get(owner).unwrap_or_default()
+        }
+
+        /// Returns the collateral ratio of `owner`'s position at the oracle price, in
+        /// basis points, or `None` if it has no debt.
+        #[ink(message)]
+        pub fn collateral_ratio_of(&self, owner: AccountId) -> Result<Option<u128>> {
+            collateral_ratio(self.position_of(owner), self.price()?)
+        }
+
+        /// Returns how much more synthetic `owner` can mint against their collateral
+        /// at the oracle price.
+        #[ink(message)]
+        pub fn available_to_mint(&self, owner: AccountId) -> Result<Balance> {
+            let position = self.position_of(owner);
+            let max = max_debt(position.collateral, self.price()?, self.min_collateral_ratio())?;
+            Ok(max.saturating_sub(position.debt))
+        }
+
+        /// Moves `amount` of collateral from the caller into their position. The
+        /// caller must have approved this contract to spend at least `amount` on
+        /// the collateral token.
+        #[ink(message)]
+        pub fn deposit(&mut self, amount: Balance) -> Result<()> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let owner = self.env().caller();
+            let contract = self.env().account_id();
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.collateral_token).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(TRANSFER_FROM_SELECTOR))
+                        .push_arg(owner)
+                        .push_arg(contract)
+                        .push_arg(amount),
+                )
+                .returns::<core::result::Result<(), CalleeError>>()
+                .fire()
+                .map_err(|_| Error::TransferFailed)?
+                .map_err(|_| Error::TransferFailed)?;
+
+            self.credit_collateral(owner, amount);
+            Ok(())
+        }
+
+        /// Sends `amount` of collateral from the caller's position back to them, as
+        /// long as the position stays safe at the oracle price.
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: Balance) -> Result<()> {
+            let price = self.price()?;
+            let owner = self.env().caller();
+            self.debit_collateral(owner, amount, price)?;
+
+            let sent = build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.collateral_token).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(TRANSFER_SELECTOR))
+                        .push_arg(owner)
+                        .push_arg(amount),
+                )
+                .returns::<core::result::Result<(), CalleeError>>()
+                .fire();
+            if !matches!(sent, Ok(Ok(()))) {
+                let mut position = self.position_of(owner);
+                position.collateral += amount;
+                self.positions.insert(owner, &position);
+                return Err(Error::TransferFailed);
+            }
+
+            self.env().emit_event(CollateralChanged {
+                owner,
+                deposited: 0,
+                withdrawn: amount,
+            });
+            Ok(())
+        }
+
+        /// Mints `amount` of synthetic to the caller against their collateral, as
+        /// long as the position stays safe at the oracle price. Fails with `Tripped`
+        /// while the circuit breaker is tripped.
+        #[ink(message)]
+        pub fn mint(&mut self, amount: Balance) -> Result<()> {
+            self.breaker.ensure_running()?;
+            let price = self.price()?;
+            self.mint_at(self.env().caller(), amount, price)
+        }
+
+        /// Burns `amount` of the caller's synthetic to pay back the debt of their
+        /// position.
+        #[ink(message)]
+        pub fn repay(&mut self, amount: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let mut position = self.position_of(owner);
+            if amount > position.debt {
+                return Err(Error::ExceedsDebt);
+            }
+            self.burn(owner, amount)?;
+            position.debt -= amount;
+            self.positions.insert(owner, &position);
+            self.env().emit_event(DebtChanged {
+                owner,
+                minted: 0,
+                repaid: amount,
+            });
+            Ok(())
+        }
+
+        /// Liquidates `owner`'s position if it is below the minimum ratio at the
+        /// oracle price. The caller burns the whole debt of the position from their
+        /// own synthetic balance and receives its collateral worth the debt plus the
+        /// liquidation bonus in their own position.
+        #[ink(message)]
+        pub fn liquidate(&mut self, owner: AccountId) -> Result<()> {
+            let price = self.price()?;
+            self.liquidate_at(self.env().caller(), owner, price)
+        }
+
+        /// Transfers `value` amount of synthetic from the caller's account to `to`.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            self.transfer_from_to(from, to, value)
+        }
+
+        /// Allows `spender` to withdraw from the caller's account multiple times, up to
+        /// the `value` amount.
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            self.allowances.insert((owner, spender), &value);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` synthetic on behalf of `from` to the account `to`.
+        #[ink(message)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let caller = self.env().caller();
+            let allowance = self.allowance(from, caller);
+            if allowance < value {
+                return Err(Error::InsufficientAllowance);
+            }
+            self.transfer_from_to(from, to, value)?;
+            self.allowances.insert((from, caller), &(allowance - value));
+            Ok(())
+        }
+
+        /// Reads the collateral price from the oracle. A zero price counts as
+        /// unavailable, as no position could be valued with it.
+        fn price(&self) -> Result<Balance> {
+            let price = build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.oracle).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(PRICE_SELECTOR))
+                        .push_arg(self.collateral_token),
+                )
+                .returns::<core::result::Result<Balance, CalleeError>>()
+                .fire()
+                .map_err(|_| Error::PriceUnavailable)?
+                .map_err(|_| Error::PriceUnavailable)?;
+            if price == 0 {
+                return Err(Error::PriceUnavailable);
+            }
+            Ok(price)
+        }
+
+        /// Adds `amount` to `owner`'s collateral.
+        fn credit_collateral(&mut self, owner: AccountId, amount: Balance) {
+            let mut position = self.position_of(owner);
+            position.collateral += amount;
+            self.positions.insert(owner, &position);
+            self.env().emit_event(CollateralChanged {
+                owner,
+                deposited: amount,
+                withdrawn: 0,
+            });
+        }
+
+        /// Takes `amount` from `owner`'s collateral if the position stays safe at
+        /// `price`.
+        fn debit_collateral(
+            &mut self,
+            owner: AccountId,
+            amount: Balance,
+            price: Balance,
+        ) -> Result<()> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let mut position = self.position_of(owner);
+            if amount > position.collateral {
+                return Err(Error::InsufficientCollateral);
+            }
+            position.collateral -= amount;
+            if !is_safe(position, price, self.min_collateral_ratio())? {
+                return Err(Error::BelowMinimumRatio);
+            }
+            self.positions.insert(owner, &position);
+            Ok(())
+        }
+
+        /// Mints `amount` to `owner` against their collateral at `price`.
+        fn mint_at(&mut self, owner: AccountId, amount: Balance, price: Balance) -> Result<()> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let mut position = self.position_of(owner);
+            position.debt = position.debt.checked_add(amount).ok_or(Error::Overflow)?;
+            if !is_safe(position, price, self.min_collateral_ratio())? {
+                return Err(Error::BelowMinimumRatio);
+            }
+            self.positions.insert(owner, &position);
+
+            self.total_supply += amount;
+            let balance = self.balance_of(owner);
+            self.balances.insert(owner, &(balance + amount));
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(owner),
+                value: amount,
+            });
+            self.env().emit_event(DebtChanged {
+                owner,
+                minted: amount,
+                repaid: 0,
+            });
+            Ok(())
+        }
+
+        /// Liquidates `owner`'s position on behalf of `liquidator` at `price`.
+        fn liquidate_at(
+            &mut self,
+            liquidator: AccountId,
+            owner: AccountId,
+            price: Balance,
+        ) -> Result<()> {
+            let mut position = self.position_of(owner);
+            if is_safe(position, price, self.min_collateral_ratio())? {
+                return Err(Error::NotLiquidatable);
+            }
+
+            let debt = position.debt;
+            let seized = collateral_for(debt, price, self.liquidation_bonus())?
+                .min(position.collateral);
+            self.burn(liquidator, debt)?;
+            position.debt = 0;
+            position.collateral -= seized;
+            self.positions.insert(owner, &position);
+
+            let mut reward = self.position_of(liquidator);
+            reward.collateral += seized;
+            self.positions.insert(liquidator, &reward);
+
+            self.env().emit_event(Liquidated {
+                owner,
+                liquidator,
+                debt,
+                collateral: seized,
+            });
+            Ok(())
+        }
+
+        /// Burns `amount` of `owner`'s synthetic.
+        fn burn(&mut self, owner: AccountId, amount: Balance) -> Result<()> {
+            let balance = self.balance_of(owner);
+            if balance < amount {
+                return Err(Error::InsufficientBalance);
+            }
+            self.balances.insert(owner, &(balance - amount));
+            self.total_supply -= amount;
+            self.env().emit_event(Transfer {
+                from: Some(owner),
+                to: None,
+                value: amount,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` amount of synthetic from the `from` account to the `to`
+        /// account.
+        fn transfer_from_to(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let from_balance = self.balance_of(from);
+            if from_balance < value {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.balances.insert(from, &(from_balance - value));
+            let to_balance = self.balance_of(to);
+            self.balances.insert(to, &(to_balance + value));
+
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                value,
+            });
+
+            Ok(())
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// credit collateral with `credit_collateral` and pass prices to the `_at`
+    /// helpers instead of reading the oracle.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        /// A price of 2 synthetic per collateral unit.
+        const PRICE: Balance = 2 * PRICE_UNIT;
+
+        /// A CDP requiring 150% collateral with a 10% liquidation bonus.
+        fn new_cdp() -> Cdp {
+            Cdp::new(
+                AccountId::from([0xc0; 32]),
+                AccountId::from([0x0e; 32]),
+                15_000,
+                1_000,
+                String::from("Synthetic Dollar"),
+                String::from("sUSD"),
+                12,
+            )
+        }
+
+        fn position(collateral: Balance, debt: Balance) -> Position {
+            Position { collateral, debt }
+        }
+
+        #[ink::test]
+        fn ratio_math_works() {
+            assert_eq!(collateral_value(300, PRICE), Ok(600));
+            assert_eq!(collateral_value(3, PRICE_UNIT / 2), Ok(1));
+            assert_eq!(collateral_ratio(position(300, 400), PRICE), Ok(Some(15_000)));
+            assert_eq!(collateral_ratio(position(300, 0), PRICE), Ok(None));
+            assert_eq!(max_debt(300, PRICE, 15_000), Ok(400));
+            assert_eq!(collateral_value(Balance::MAX, PRICE), Err(Error::Overflow));
+        }
+
+        #[ink::test]
+        fn safety_is_inclusive_of_the_minimum() {
+            assert_eq!(is_safe(position(300, 400), PRICE, 15_000), Ok(true));
+            assert_eq!(is_safe(position(300, 401), PRICE, 15_000), Ok(false));
+            assert_eq!(is_safe(position(0, 0), PRICE, 15_000), Ok(true));
+        }
+
+        #[ink::test]
+        fn collateral_for_includes_bonus() {
+            // 100 debt plus 10% is 110 synthetic, i.e. 55 collateral at a price of 2.
+            assert_eq!(collateral_for(100, PRICE, 1_000), Ok(55));
+            assert_eq!(collateral_for(100, PRICE, 0), Ok(50));
+        }
+
+        #[ink::test]
+        fn mint_is_capped_by_the_ratio() {
+            let accounts = fixtures::accounts();
+            let mut cdp = new_cdp();
+            cdp.credit_collateral(accounts.alice, 300);
+
+            assert_eq!(cdp.mint_at(accounts.alice, 401, PRICE), Err(Error::BelowMinimumRatio));
+            assert_eq!(cdp.mint_at(accounts.alice, 400, PRICE), Ok(()));
+            assert_eq!(cdp.balance_of(accounts.alice), 400);
+            assert_eq!(cdp.total_supply(), 400);
+            assert_eq!(cdp.position_of(accounts.alice), position(300, 400));
+            assert_eq!(cdp.mint_at(accounts.alice, 1, PRICE), Err(Error::BelowMinimumRatio));
+        }
+
+        #[ink::test]
+        fn withdraw_keeps_the_position_safe() {
+            let accounts = fixtures::accounts();
+            let mut cdp = new_cdp();
+            cdp.credit_collateral(accounts.alice, 300);
+            assert_eq!(cdp.mint_at(accounts.alice, 200, PRICE), Ok(()));
+
+            // 200 debt needs 150 collateral at a price of 2.
+            assert_eq!(
+                cdp.debit_collateral(accounts.alice, 151, PRICE),
+                Err(Error::BelowMinimumRatio)
+            );
+            assert_eq!(cdp.debit_collateral(accounts.alice, 150, PRICE), Ok(()));
+            assert_eq!(cdp.position_of(accounts.alice), position(150, 200));
+            assert_eq!(
+                cdp.debit_collateral(accounts.bob, 1, PRICE),
+                Err(Error::InsufficientCollateral)
+            );
+        }
+
+        #[ink::test]
+        fn repay_burns_and_reduces_debt() {
+            let accounts = fixtures::accounts();
+            let mut cdp = new_cdp();
+            cdp.credit_collateral(accounts.alice, 300);
+            assert_eq!(cdp.mint_at(accounts.alice, 400, PRICE), Ok(()));
+
+            assert_eq!(cdp.repay(401), Err(Error::ExceedsDebt));
+            assert_eq!(cdp.repay(150), Ok(()));
+            assert_eq!(cdp.position_of(accounts.alice), position(300, 250));
+            assert_eq!(cdp.balance_of(accounts.alice), 250);
+            assert_eq!(cdp.total_supply(), 250);
+        }
+
+        #[ink::test]
+        fn repay_needs_the_synthetic() {
+            let accounts = fixtures::accounts();
+            let mut cdp = new_cdp();
+            cdp.credit_collateral(accounts.alice, 300);
+            assert_eq!(cdp.mint_at(accounts.alice, 400, PRICE), Ok(()));
+            assert_eq!(cdp.transfer(accounts.bob, 300), Ok(()));
+
+            assert_eq!(cdp.repay(101), Err(Error::InsufficientBalance));
+            assert_eq!(cdp.position_of(accounts.alice).debt, 400);
+        }
+
+        #[ink::test]
+        fn safe_position_cannot_be_liquidated() {
+            let accounts = fixtures::accounts();
+            let mut cdp = new_cdp();
+            cdp.credit_collateral(accounts.alice, 300);
+            assert_eq!(cdp.mint_at(accounts.alice, 400, PRICE), Ok(()));
+            assert_eq!(
+                cdp.liquidate_at(accounts.bob, accounts.alice, PRICE),
+                Err(Error::NotLiquidatable)
+            );
+        }
+
+        #[ink::test]
+        fn liquidation_seizes_debt_plus_bonus() {
+            let accounts = fixtures::accounts();
+            let mut cdp = new_cdp();
+            cdp.credit_collateral(accounts.alice, 300);
+            assert_eq!(cdp.mint_at(accounts.alice, 400, PRICE), Ok(()));
+            // Bob gets hold of the synthetic needed to cover alice's debt.
+            assert_eq!(cdp.transfer(accounts.bob, 400), Ok(()));
+
+            // The price falls 25%: 300 collateral is now worth 450, a ratio of 112.5%.
+            let price = PRICE * 3 / 4;
+            assert_eq!(
+                collateral_ratio(cdp.position_of(accounts.alice), price),
+                Ok(Some(11_250))
+            );
+            assert_eq!(cdp.liquidate_at(accounts.bob, accounts.alice, price), Ok(()));
+
+            // 400 debt plus 10% is 440 synthetic, i.e. 293 collateral at 1.5.
+            assert_eq!(cdp.position_of(accounts.alice), position(7, 0));
+            assert_eq!(cdp.position_of(accounts.bob), position(293, 0));
+            assert_eq!(cdp.balance_of(accounts.bob), 0);
+            assert_eq!(cdp.total_supply(), 0);
+        }
+
+        #[ink::test]
+        fn liquidation_needs_the_whole_debt() {
+            let accounts = fixtures::accounts();
+            let mut cdp = new_cdp();
+            cdp.credit_collateral(accounts.alice, 300);
+            assert_eq!(cdp.mint_at(accounts.alice, 400, PRICE), Ok(()));
+            assert_eq!(cdp.transfer(accounts.bob, 399), Ok(()));
+
+            assert_eq!(
+                cdp.liquidate_at(accounts.bob, accounts.alice, PRICE / 2),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(cdp.position_of(accounts.alice), position(300, 400));
+        }
+
+        #[ink::test]
+        fn deep_underwater_liquidation_takes_all_collateral() {
+            let accounts = fixtures::accounts();
+            let mut cdp = new_cdp();
+            cdp.credit_collateral(accounts.alice, 300);
+            assert_eq!(cdp.mint_at(accounts.alice, 400, PRICE), Ok(()));
+            assert_eq!(cdp.transfer(accounts.bob, 400), Ok(()));
+
+            // At a price of 1 the collateral is worth less than the debt.
+            assert_eq!(cdp.liquidate_at(accounts.bob, accounts.alice, PRICE_UNIT), Ok(()));
+            assert_eq!(cdp.position_of(accounts.alice), position(0, 0));
+            assert_eq!(cdp.position_of(accounts.bob), position(300, 0));
+        }
+
+        #[ink::test]
+        fn only_guardian_can_trip() {
+            let accounts = fixtures::accounts();
+            let mut cdp = new_cdp();
+            assert_eq!(cdp.guardian(), accounts.alice);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(cdp.trip(), Err(Error::NotGuardian));
+            assert!(!cdp.is_tripped());
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(cdp.trip(), Ok(()));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(cdp.reset(), Err(Error::NotGuardian));
+            assert!(cdp.is_tripped());
+        }
+
+        #[ink::test]
+        fn tripped_breaker_stops_minting_only() {
+            let accounts = fixtures::accounts();
+            let mut cdp = new_cdp();
+            cdp.credit_collateral(accounts.alice, 300);
+            assert_eq!(cdp.mint_at(accounts.alice, 400, PRICE), Ok(()));
+            assert_eq!(cdp.trip(), Ok(()));
+            assert_eq!(cdp.mint(1), Err(Error::Tripped));
+
+            // Positions can still be paid down while minting is stopped.
+            assert_eq!(cdp.repay(100), Ok(()));
+            assert_eq!(cdp.position_of(accounts.alice), position(300, 300));
+
+            assert_eq!(cdp.reset(), Ok(()));
+            assert!(!cdp.is_tripped());
+            assert_eq!(cdp.reset(), Err(Error::NotTripped));
+        }
+
+        /// ink! derives a selector from the message name, so the CDP reaches the
+        /// `price`, `transfer` and `transfer_from` messages of the mocks it is tried
+        /// out with.
+        #[ink::test]
+        fn selectors_match_the_mocks() {
+            assert_eq!(PRICE_SELECTOR, ink::selector_bytes!("price"));
+            assert_eq!(TRANSFER_SELECTOR, ink::selector_bytes!("transfer"));
+            assert_eq!(
+                TRANSFER_FROM_SELECTOR,
+                ink::selector_bytes!("transfer_from")
+            );
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy with the collateral token, an oracle quoting its price, and the
+//      ratios in basis points, e.g. 15000 (150%) and 1000 (10% bonus)
+//    - Without a real token or price feed, deploy `mocks/mock_token.rs` as the
+//      collateral and `mocks/mock_oracle.rs` as the oracle first; "mint" yourself
+//      collateral and "setPrice" for the token's address, then move the price to
+//      make positions liquidatable
+//
+// 4. Interact with the contract:
+//    - On the collateral token, "approve" the CDP for the amount to deposit
+//    - Use the "deposit" method to add collateral to your position
+//    - Use the "mint" method to mint synthetic against it
+//    - Use the "repay" and "withdraw" methods to close the position
+//    - Use the "liquidate" method on positions that fall below the minimum ratio
+//    - Use "setGuardian" to give the emergency stop to a multisig; it can "trip"
+//      the CDP to stop minting and "reset" it once the price feed is sound again
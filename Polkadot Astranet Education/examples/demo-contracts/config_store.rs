@@ -0,0 +1,412 @@
+// examples/demo-contracts/config_store.rs
+//
+// An on-chain configuration store contract example for Polkadot using ink!
+// Protocol parameters (fees, ratios, switches, addresses of other contracts) are
+// often compiled in as constants or fixed at deployment, so changing one means a
+// redeployment. This contract keeps them in one place instead: other contracts
+// read a parameter with a cross-contract call when they need it, and the
+// accounts given the writer role can change it without touching those contracts.
+// flashloan_pool.rs reads its fee this way.
+//
+// Typed values and history
+// ------------------------
+// Keys are short byte strings such as `b"flashloan.fee_bps"`, by convention
+// prefixed with the name of the contract reading them. A value is a `u128`, a
+// `bool`, an `AccountId` or raw bytes, and a key keeps the type of its first
+// value, so a reader expecting a number never gets an address. Every write adds a
+// new version of the key rather than overwriting it; the current value is the
+// latest version, and earlier ones, with who set them and when, stay readable for
+// audits.
+//
+// Readers call the typed getters, such as `get_u128`, whose selectors are pinned
+// in `tools/selector/tests/pinned.rs`; they return `None` for missing keys and
+// values of another type.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod config_store {
+    use ink_prelude::vec::Vec;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Longest key, in bytes.
+    pub const MAX_KEY_LEN: usize = 64;
+
+    /// Longest bytes value, in bytes.
+    pub const MAX_BYTES_LEN: usize = 256;
+
+    /// A parameter value.
+    #[derive(
+        Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub enum Value {
+        U128(u128),
+        Bool(bool),
+        Account(AccountId),
+        Bytes(Vec<u8>),
+    }
+
+    impl Value {
+        /// Returns whether `self` and `other` are of the same type.
+        pub fn same_type(&self, other: &Value) -> bool {
+            core::mem::discriminant(self) == core::mem::discriminant(other)
+        }
+    }
+
+    /// One version of a parameter.
+    #[derive(
+        Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Entry {
+        /// The value.
+        pub value: Value,
+        /// Writer that set it.
+        pub set_by: AccountId,
+        /// Block it was set in.
+        pub set_at: BlockNumber,
+    }
+
+    /// The config store error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the admin.
+        NotAdmin,
+        /// Returned if the caller does not have the writer role.
+        NotWriter,
+        /// Returned if the key is empty or longer than `MAX_KEY_LEN`.
+        InvalidKey,
+        /// Returned if a bytes value is longer than `MAX_BYTES_LEN`.
+        ValueTooLong,
+        /// Returned if the value's type differs from the key's.
+        TypeMismatch,
+    }
+
+    /// The config store result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    // Events are mirrored in `crates/events/src/config_store.rs` for off-chain
+    // decoding; keep the order of events and fields in sync with it.
+
+    /// Event emitted when the admin grants or revokes the writer role.
+    #[ink(event)]
+    pub struct WriterChanged {
+        #[ink(topic)]
+        account: AccountId,
+        granted: bool,
+    }
+
+    /// Event emitted when a parameter gets a new version.
+    #[ink(event)]
+    pub struct ValueSet {
+        key: Vec<u8>,
+        version: u32,
+        value: Value,
+        #[ink(topic)]
+        set_by: AccountId,
+    }
+
+    /// The config store storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct ConfigStore {
+        /// Account granting and revoking the writer role.
+        admin: AccountId,
+        /// Accounts with the writer role.
+        writers: Mapping<AccountId, ()>,
+        /// Mapping from key to its number of versions.
+        versions: Mapping<Vec<u8>, u32>,
+        /// Mapping from (key, version) to that version, versions counting from 1.
+        history: Mapping<(Vec<u8>, u32), Entry>,
+    }
+
+    impl ConfigStore {
+        /// Creates an empty store administered by the caller, who also gets the
+        /// writer role.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|store: &mut Self| {
+                let admin = Self::env().caller();
+                store.admin = admin;
+                store.writers.insert(admin, &());
+            })
+        }
+
+        /// Returns the admin.
+        #[ink(message)]
+        pub fn admin(&self) -> AccountId {
+            self.admin
+        }
+
+        /// Returns whether `account` has the writer role.
+        #[ink(message)]
+        pub fn is_writer(&self, account: AccountId) -> bool {
+            self.writers.get(account).is_some()
+        }
+
+        /// Grants `account` the writer role. Only the admin can call this.
+        #[ink(message)]
+        pub fn grant_writer(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_admin()?;
+            self.writers.insert(account, &());
+            self.env().emit_event(WriterChanged {
+                account,
+                granted: true,
+            });
+            Ok(())
+        }
+
+        /// Revokes the writer role from `account`. Only the admin can call this.
+        #[ink(message)]
+        pub fn revoke_writer(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_admin()?;
+            self.writers.remove(account);
+            self.env().emit_event(WriterChanged {
+                account,
+                granted: false,
+            });
+            Ok(())
+        }
+
+        /// Sets `key` to `value` as a new version, and returns the version.
+        /// Only writers can call this.
+        #[ink(message)]
+        pub fn set(&mut self, key: Vec<u8>, value: Value) -> Result<u32> {
+            let set_by = self.env().caller();
+            if !self.is_writer(set_by) {
+                return Err(Error::NotWriter);
+            }
+            if key.is_empty() || key.len() > MAX_KEY_LEN {
+                return Err(Error::InvalidKey);
+            }
+            if matches!(&value, Value::Bytes(bytes) if bytes.len() > MAX_BYTES_LEN) {
+                return Err(Error::ValueTooLong);
+            }
+            if let Some(current) = self.get(key.clone()) {
+                if !current.same_type(&value) {
+                    return Err(Error::TypeMismatch);
+                }
+            }
+            let version = self.version_of(key.clone()) + 1;
+            self.history.insert(
+                (key.clone(), version),
+                &Entry {
+                    value: value.clone(),
+                    set_by,
+                    set_at: self.env().block_number(),
+                },
+            );
+            self.versions.insert(key.clone(), &version);
+            self.env().emit_event(ValueSet {
+                key,
+                version,
+                value,
+                set_by,
+            });
+            Ok(version)
+        }
+
+        /// Returns the number of versions of `key`, zero if it was never set.
+        #[ink(message)]
+        pub fn version_of(&self, key: Vec<u8>) -> u32 {
+            self.versions.get(key).unwrap_or(0)
+        }
+
+        /// Returns version `version` of `key`, versions counting from 1.
+        #[ink(message)]
+        pub fn entry_at(&self, key: Vec<u8>, version: u32) -> Option<Entry> {
+            self.history.get((key, version))
+        }
+
+        /// Returns the current value of `key`.
+        #[ink(message)]
+        pub fn get(&self, key: Vec<u8>) -> Option<Value> {
+            let version = self.version_of(key.clone());
+            self.entry_at(key, version).map(|entry| entry.value)
+        }
+
+        /// Returns the current value of `key` if it is a `u128`.
+        #[ink(message)]
+        pub fn get_u128(&self, key: Vec<u8>) -> Option<u128> {
+            match self.get(key) {
+                Some(Value::U128(value)) => Some(value),
+                _ => None,
+            }
+        }
+
+        /// Returns the current value of `key` if it is a `bool`.
+        #[ink(message)]
+        pub fn get_bool(&self, key: Vec<u8>) -> Option<bool> {
+            match self.get(key) {
+                Some(Value::Bool(value)) => Some(value),
+                _ => None,
+            }
+        }
+
+        /// Returns the current value of `key` if it is an `AccountId`.
+        #[ink(message)]
+        pub fn get_account(&self, key: Vec<u8>) -> Option<AccountId> {
+            match self.get(key) {
+                Some(Value::Account(value)) => Some(value),
+                _ => None,
+            }
+        }
+
+        /// Returns the current value of `key` if it is bytes.
+        #[ink(message)]
+        pub fn get_bytes(&self, key: Vec<u8>) -> Option<Vec<u8>> {
+            match self.get(key) {
+                Some(Value::Bytes(value)) => Some(value),
+                _ => None,
+            }
+        }
+
+        /// Returns `NotAdmin` unless the caller is the admin.
+        fn ensure_admin(&self) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAdmin);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        fn key(name: &str) -> Vec<u8> {
+            name.as_bytes().to_vec()
+        }
+
+        #[ink::test]
+        fn only_writers_write() {
+            let accounts = fixtures::accounts();
+            let mut store = ConfigStore::new();
+            assert!(store.is_writer(accounts.alice));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                store.set(key("a"), Value::Bool(true)),
+                Err(Error::NotWriter)
+            );
+            assert_eq!(store.grant_writer(accounts.bob), Err(Error::NotAdmin));
+
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(store.grant_writer(accounts.bob), Ok(()));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(store.set(key("a"), Value::Bool(true)), Ok(1));
+
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(store.revoke_writer(accounts.bob), Ok(()));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                store.set(key("a"), Value::Bool(false)),
+                Err(Error::NotWriter)
+            );
+            assert_eq!(store.get_bool(key("a")), Some(true));
+        }
+
+        #[ink::test]
+        fn keys_and_values_are_bounded() {
+            let mut store = ConfigStore::new();
+            assert_eq!(
+                store.set(Vec::new(), Value::U128(1)),
+                Err(Error::InvalidKey)
+            );
+            assert_eq!(
+                store.set(vec![b'k'; MAX_KEY_LEN + 1], Value::U128(1)),
+                Err(Error::InvalidKey)
+            );
+            assert_eq!(store.set(vec![b'k'; MAX_KEY_LEN], Value::U128(1)), Ok(1));
+            assert_eq!(
+                store.set(key("b"), Value::Bytes(vec![0; MAX_BYTES_LEN + 1])),
+                Err(Error::ValueTooLong)
+            );
+            assert_eq!(
+                store.set(key("b"), Value::Bytes(vec![0; MAX_BYTES_LEN])),
+                Ok(1)
+            );
+        }
+
+        #[ink::test]
+        fn keys_keep_their_type() {
+            let accounts = fixtures::accounts();
+            let mut store = ConfigStore::new();
+            assert_eq!(store.set(key("fee"), Value::U128(9)), Ok(1));
+            assert_eq!(
+                store.set(key("fee"), Value::Account(accounts.bob)),
+                Err(Error::TypeMismatch)
+            );
+            assert_eq!(store.get_u128(key("fee")), Some(9));
+            assert_eq!(store.get_account(key("fee")), None);
+            assert_eq!(store.get_bool(key("missing")), None);
+            assert_eq!(store.get_bytes(key("missing")), None);
+        }
+
+        #[ink::test]
+        fn every_write_is_a_new_version() {
+            let accounts = fixtures::accounts();
+            let mut store = ConfigStore::new();
+            assert_eq!(store.version_of(key("fee")), 0);
+            assert_eq!(store.set(key("fee"), Value::U128(9)), Ok(1));
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            assert_eq!(store.grant_writer(accounts.bob), Ok(()));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(store.set(key("fee"), Value::U128(5)), Ok(2));
+
+            assert_eq!(store.version_of(key("fee")), 2);
+            assert_eq!(store.get_u128(key("fee")), Some(5));
+            assert_eq!(
+                store.entry_at(key("fee"), 1),
+                Some(Entry {
+                    value: Value::U128(9),
+                    set_by: accounts.alice,
+                    set_at: 0,
+                })
+            );
+            assert_eq!(
+                store.entry_at(key("fee"), 2),
+                Some(Entry {
+                    value: Value::U128(5),
+                    set_by: accounts.bob,
+                    set_at: 1,
+                })
+            );
+            assert_eq!(store.entry_at(key("fee"), 3), None);
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Upload the generated .contract file and deploy it; the deployer is admin
+//
+// 4. Interact with the contract:
+//    - As admin, use "grantWriter" for the accounts allowed to change parameters
+//    - Writers use "set", e.g. key "flashloan.fee_bps" with a U128 value
+//    - Point readers at the store, e.g. "setConfigStore" on flashloan_pool.rs
+//    - Use "versionOf" and "entryAt" to audit past values
@@ -0,0 +1,525 @@
+// examples/demo-contracts/crowdloan.rs
+//
+// A crowdloan contract example for Polkadot using ink!
+// On Polkadot, a parachain team wins a slot with DOT its supporters lend it
+// through a crowdloan: contributions are locked for the lease and returned
+// afterwards, and the team thanks contributors with its own tokens, more generously
+// for those who commit early. This contract plays the same game with native tokens:
+// contributions are accepted until the campaign ends or the hard cap is reached,
+// each contribution earns ERC-20 reward tokens, rewards can be claimed once the
+// campaign ends, and contributions can be taken back once the lease ends. The
+// contract never lets anyone, not even its creator, spend what was contributed.
+//
+// Both ends are the utils crate's inclusive `Deadline`s: contributions are still
+// accepted at exactly `ends_at`, and rewards and refunds open the millisecond after
+// `ends_at` and `lease_ends_at`.
+//
+// The reward curve
+// ----------------
+// Every unit contributed earns `base_rate` reward tokens, plus a bonus that starts
+// at `max_bonus` for the first unit and falls linearly to zero at the hard cap. A
+// contribution of `c` when `r` has been raised so far is paid the integral of the
+// curve over `r..r + c`:
+//
+//     base_rate * (c + max_bonus * c * (2 * cap - 2 * r - c) / (2 * cap))
+//
+// Integrating rather than pricing a contribution at the rate of its first unit
+// means that splitting a contribution in two earns the same reward, up to rounding,
+// so nobody gains from contributing in many small pieces. The math uses
+// `FixedU128` and `mul_div` from the utils crate's `fixed_point` module; every step
+// rounds down.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod crowdloan {
+    use astranet_utils::{
+        deadline::Deadline,
+        fixed_point::{mul_div, FixedU128},
+        safe_erc20::SafeErc20,
+        trace,
+    };
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// The crowdloan error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the amount is zero.
+        ZeroAmount,
+        /// Returned if the contribution would take the total past the hard cap.
+        HardCapExceeded,
+        /// Returned if the campaign no longer takes contributions.
+        CampaignEnded,
+        /// Returned if the campaign has not ended yet.
+        CampaignRunning,
+        /// Returned if the lease has not ended yet.
+        LeaseRunning,
+        /// Returned if the caller has nothing to claim or take back.
+        NothingToClaim,
+        /// Returned if a transfer failed.
+        TransferFailed,
+        /// Returned if the reward math overflows.
+        Overflow,
+    }
+
+    /// The crowdloan result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Returns the reward for contributing `amount` when `raised` has been raised
+    /// out of `cap`, at `base_rate` reward tokens per unit and a bonus falling
+    /// from `max_bonus` to zero.
+    pub fn reward_for(
+        raised: Balance,
+        amount: Balance,
+        cap: Balance,
+        base_rate: FixedU128,
+        max_bonus: FixedU128,
+    ) -> Result<Balance> {
+        if amount > cap.saturating_sub(raised) {
+            return Err(Error::HardCapExceeded);
+        }
+        let double_cap = cap.checked_mul(2).ok_or(Error::Overflow)?;
+        // Units of the contribution weighted by how far below the cap they are,
+        // which is the part the bonus applies to.
+        let weighted =
+            mul_div(amount, double_cap - 2 * raised - amount, double_cap).ok_or(Error::Overflow)?;
+        let bonus = max_bonus.checked_mul_int(weighted).ok_or(Error::Overflow)?;
+        amount
+            .checked_add(bonus)
+            .and_then(|units| base_rate.checked_mul_int(units))
+            .ok_or(Error::Overflow)
+    }
+
+    // Events are mirrored in `crates/events/src/crowdloan.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when someone contributes.
+    #[ink(event)]
+    pub struct Contributed {
+        #[ink(topic)]
+        contributor: AccountId,
+        amount: Balance,
+        reward: Balance,
+        raised: Balance,
+    }
+
+    /// Event emitted when a contributor claims their reward tokens.
+    #[ink(event)]
+    pub struct RewardClaimed {
+        #[ink(topic)]
+        contributor: AccountId,
+        reward: Balance,
+    }
+
+    /// Event emitted when a contributor takes back their contribution.
+    #[ink(event)]
+    pub struct Refunded {
+        #[ink(topic)]
+        contributor: AccountId,
+        amount: Balance,
+    }
+
+    /// The crowdloan storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Crowdloan {
+        /// The ERC-20 token rewards are paid in.
+        reward_token: SafeErc20,
+        /// Most the campaign raises.
+        hard_cap: Balance,
+        /// Reward tokens per unit contributed, before the bonus.
+        base_rate: FixedU128,
+        /// Bonus on the first unit contributed, falling to zero at the cap.
+        max_bonus: FixedU128,
+        /// Last time contributions are accepted; rewards can be claimed after it.
+        ends_at: Deadline,
+        /// Last time contributions are locked; they can be taken back after it.
+        lease_ends_at: Deadline,
+        /// Sum of all contributions.
+        raised: Balance,
+        /// Mapping from contributor to what they contributed and not taken back.
+        contributions: Mapping<AccountId, Balance>,
+        /// Mapping from contributor to their unclaimed reward.
+        rewards: Mapping<AccountId, Balance>,
+    }
+
+    impl Crowdloan {
+        /// Creates a campaign raising up to `hard_cap` until `ends_at`, locking
+        /// contributions until `lease_ends_at`, and rewarding them in
+        /// `reward_token` along the curve set by `base_rate` and `max_bonus`. The
+        /// contract must hold enough reward tokens to pay every reward.
+        #[ink(constructor)]
+        pub fn new(
+            reward_token: AccountId,
+            hard_cap: Balance,
+            base_rate: FixedU128,
+            max_bonus: FixedU128,
+            ends_at: Timestamp,
+            lease_ends_at: Timestamp,
+        ) -> Self {
+            assert!(hard_cap > 0, "hard cap must not be zero");
+            assert!(
+                lease_ends_at >= ends_at,
+                "the lease cannot end before the campaign"
+            );
+            ink_lang::utils::initialize_contract(|crowdloan: &mut Self| {
+                crowdloan.reward_token = SafeErc20::new(reward_token);
+                crowdloan.hard_cap = hard_cap;
+                crowdloan.base_rate = base_rate;
+                crowdloan.max_bonus = max_bonus;
+                crowdloan.ends_at = Deadline::at(ends_at);
+                crowdloan.lease_ends_at = Deadline::at(lease_ends_at);
+            })
+        }
+
+        /// Returns the sum of all contributions.
+        #[ink(message)]
+        pub fn raised(&self) -> Balance {
+            self.raised
+        }
+
+        /// Returns the hard cap.
+        #[ink(message)]
+        pub fn hard_cap(&self) -> Balance {
+            self.hard_cap
+        }
+
+        /// Returns what `contributor` has contributed and not taken back.
+        #[ink(message)]
+        pub fn contribution_of(&self, contributor: AccountId) -> Balance {
+            self.contributions.get(contributor).unwrap_or(0)
+        }
+
+        /// Returns the unclaimed reward of `contributor`.
+        #[ink(message)]
+        pub fn reward_of(&self, contributor: AccountId) -> Balance {
+            self.rewards.get(contributor).unwrap_or(0)
+        }
+
+        /// Returns the reward contributing `amount` would earn right now.
+        #[ink(message)]
+        pub fn quote(&self, amount: Balance) -> Result<Balance> {
+            reward_for(
+                self.raised,
+                amount,
+                self.hard_cap,
+                self.base_rate,
+                self.max_bonus,
+            )
+        }
+
+        /// Contributes the transferred value, and returns the reward it earned.
+        #[ink(message, payable)]
+        pub fn contribute(&mut self) -> Result<Balance> {
+            let amount = self.env().transferred_value();
+            trace!(
+                "contribute",
+                amount,
+                now = self.env().block_timestamp(),
+                ends_at = self.ends_at.timestamp(),
+            );
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            if self.ends_at.is_expired(self.env().block_timestamp()) {
+                return Err(Error::CampaignEnded);
+            }
+            let reward = self.quote(amount)?;
+            let contributor = self.env().caller();
+            self.raised += amount;
+            self.contributions
+                .insert(contributor, &(self.contribution_of(contributor) + amount));
+            self.rewards
+                .insert(contributor, &(self.reward_of(contributor) + reward));
+            self.env().emit_event(Contributed {
+                contributor,
+                amount,
+                reward,
+                raised: self.raised,
+            });
+            Ok(reward)
+        }
+
+        /// Sends the caller their reward tokens, once the campaign has ended.
+        #[ink(message)]
+        pub fn claim_reward(&mut self) -> Result<Balance> {
+            if !self.ends_at.is_expired(self.env().block_timestamp()) {
+                return Err(Error::CampaignRunning);
+            }
+            let contributor = self.env().caller();
+            let reward = self.reward_of(contributor);
+            if reward == 0 {
+                return Err(Error::NothingToClaim);
+            }
+            self.rewards.remove(contributor);
+            if self.reward_token.transfer(contributor, reward).is_err() {
+                self.rewards.insert(contributor, &reward);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(RewardClaimed {
+                contributor,
+                reward,
+            });
+            Ok(reward)
+        }
+
+        /// Sends the caller back their contribution, once the lease has ended.
+        #[ink(message)]
+        pub fn refund(&mut self) -> Result<Balance> {
+            trace!(
+                "refund",
+                now = self.env().block_timestamp(),
+                lease_ends_at = self.lease_ends_at.timestamp(),
+            );
+            if !self.lease_ends_at.is_expired(self.env().block_timestamp()) {
+                return Err(Error::LeaseRunning);
+            }
+            let contributor = self.env().caller();
+            let amount = self.contribution_of(contributor);
+            if amount == 0 {
+                return Err(Error::NothingToClaim);
+            }
+            self.contributions.remove(contributor);
+            if self.env().transfer(contributor, amount).is_err() {
+                self.contributions.insert(contributor, &amount);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(Refunded {
+                contributor,
+                amount,
+            });
+            Ok(amount)
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// stop short of paying out reward tokens.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use fixtures::BLOCK_TIME;
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        const CAP: Balance = 1_000;
+        const END: Timestamp = 200 * BLOCK_TIME;
+        const LEASE_END: Timestamp = 800 * BLOCK_TIME;
+
+        fn rate(numerator: u128, denominator: u128) -> FixedU128 {
+            FixedU128::from_rational(numerator, denominator).unwrap()
+        }
+
+        /// A campaign paying 1 reward token per unit plus a bonus starting at 50%.
+        fn campaign() -> Crowdloan {
+            Crowdloan::new(
+                AccountId::from([0x70; 32]),
+                CAP,
+                FixedU128::one(),
+                rate(1, 2),
+                END,
+                LEASE_END,
+            )
+        }
+
+        fn contribute(
+            crowdloan: &mut Crowdloan,
+            contributor: AccountId,
+            amount: Balance,
+        ) -> Result<Balance> {
+            ink_env::test::set_value_transferred::<Env>(amount);
+            let result = fixtures::with_caller(contributor, || crowdloan.contribute());
+            ink_env::test::set_value_transferred::<Env>(0);
+            result
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "hard cap must not be zero")]
+        fn new_rejects_a_zero_cap() {
+            Crowdloan::new(
+                AccountId::from([0x70; 32]),
+                0,
+                FixedU128::one(),
+                FixedU128::default(),
+                END,
+                LEASE_END,
+            );
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "the lease cannot end before the campaign")]
+        fn new_rejects_a_lease_ending_first() {
+            Crowdloan::new(
+                AccountId::from([0x70; 32]),
+                CAP,
+                FixedU128::one(),
+                FixedU128::default(),
+                END,
+                END - 1,
+            );
+        }
+
+        #[test]
+        fn curve_vectors() {
+            let half = rate(1, 2);
+            let one = FixedU128::one();
+            // The whole cap at once earns the average bonus of 25%.
+            assert_eq!(reward_for(0, 1_000, CAP, one, half), Ok(1_250));
+            // The first half earns 500 + 0.5 * 500 * 1_500 / 2_000.
+            assert_eq!(reward_for(0, 500, CAP, one, half), Ok(687));
+            // The second half earns 500 + 0.5 * 500 * 500 / 2_000.
+            assert_eq!(reward_for(500, 500, CAP, one, half), Ok(562));
+            // The base rate scales everything.
+            assert_eq!(reward_for(0, 1_000, CAP, rate(3, 2), half), Ok(1_875));
+            // Without a bonus the curve is flat.
+            assert_eq!(
+                reward_for(300, 200, CAP, one, FixedU128::default()),
+                Ok(200)
+            );
+        }
+
+        #[test]
+        fn curve_at_the_cap() {
+            let half = rate(1, 2);
+            let one = FixedU128::one();
+            // The last unit earns no bonus, and nothing fits beyond it.
+            assert_eq!(reward_for(999, 1, CAP, one, half), Ok(1));
+            assert_eq!(
+                reward_for(999, 2, CAP, one, half),
+                Err(Error::HardCapExceeded)
+            );
+            assert_eq!(
+                reward_for(1_000, 1, CAP, one, half),
+                Err(Error::HardCapExceeded)
+            );
+            assert_eq!(
+                reward_for(0, 1, Balance::MAX, one, half),
+                Err(Error::Overflow)
+            );
+        }
+
+        #[ink::test]
+        fn early_contributors_earn_more() {
+            let accounts = fixtures::accounts();
+            let mut crowdloan = campaign();
+            assert_eq!(contribute(&mut crowdloan, accounts.bob, 500), Ok(687));
+            assert_eq!(contribute(&mut crowdloan, accounts.charlie, 500), Ok(562));
+            assert_eq!(crowdloan.raised(), CAP);
+            assert_eq!(crowdloan.reward_of(accounts.bob), 687);
+            assert_eq!(crowdloan.contribution_of(accounts.charlie), 500);
+        }
+
+        #[ink::test]
+        fn splitting_a_contribution_does_not_pay() {
+            let accounts = fixtures::accounts();
+            let mut crowdloan = campaign();
+            for _ in 0..4 {
+                assert!(contribute(&mut crowdloan, accounts.bob, 100).is_ok());
+            }
+            // 400 at once would have earned 400 + 0.5 * 400 * 1_600 / 2_000 = 560.
+            assert_eq!(crowdloan.reward_of(accounts.bob), 558);
+        }
+
+        #[ink::test]
+        fn hard_cap_is_enforced() {
+            let accounts = fixtures::accounts();
+            let mut crowdloan = campaign();
+            assert_eq!(contribute(&mut crowdloan, accounts.bob, 999), Ok(1_248));
+            assert_eq!(
+                contribute(&mut crowdloan, accounts.charlie, 2),
+                Err(Error::HardCapExceeded)
+            );
+            assert_eq!(contribute(&mut crowdloan, accounts.charlie, 1), Ok(1));
+            assert_eq!(
+                contribute(&mut crowdloan, accounts.charlie, 1),
+                Err(Error::HardCapExceeded)
+            );
+            assert_eq!(crowdloan.raised(), CAP);
+        }
+
+        #[ink::test]
+        fn contributions_are_locked_for_the_lease() {
+            let accounts = fixtures::accounts();
+            let mut crowdloan = campaign();
+            assert_eq!(contribute(&mut crowdloan, accounts.bob, 300), Ok(427));
+            assert_eq!(
+                contribute(&mut crowdloan, accounts.bob, 0),
+                Err(Error::ZeroAmount)
+            );
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(crowdloan.claim_reward(), Err(Error::CampaignRunning));
+
+            // The end itself still belongs to the campaign.
+            fixtures::set_block_timestamp(END);
+            assert_eq!(crowdloan.claim_reward(), Err(Error::CampaignRunning));
+
+            fixtures::set_block_timestamp(END + BLOCK_TIME);
+            assert_eq!(
+                contribute(&mut crowdloan, accounts.bob, 1),
+                Err(Error::CampaignEnded)
+            );
+            assert_eq!(crowdloan.refund(), Err(Error::LeaseRunning));
+
+            fixtures::set_block_timestamp(LEASE_END);
+            assert_eq!(crowdloan.refund(), Err(Error::LeaseRunning));
+
+            fixtures::set_block_timestamp(LEASE_END + BLOCK_TIME);
+            fixtures::fund_contract(300);
+            let before = fixtures::balance_of(accounts.bob);
+            assert_eq!(crowdloan.refund(), Ok(300));
+            assert_eq!(fixtures::balance_of(accounts.bob) - before, 300);
+            assert_eq!(crowdloan.refund(), Err(Error::NothingToClaim));
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(crowdloan.claim_reward(), Err(Error::NothingToClaim));
+        }
+
+        #[ink::test]
+        fn late_contributions_are_traced() {
+            let accounts = fixtures::accounts();
+            let mut crowdloan = campaign();
+            fixtures::set_block_timestamp(END + BLOCK_TIME);
+            assert_eq!(
+                contribute(&mut crowdloan, accounts.bob, 1),
+                Err(Error::CampaignEnded)
+            );
+            let trace = ink_env::test::recorded_debug_messages()
+                .into_iter()
+                .last()
+                .unwrap();
+            assert_eq!(
+                trace,
+                format!(
+                    "contribute amount=1 now={} ends_at={}\n",
+                    END + BLOCK_TIME,
+                    END
+                )
+            );
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract (it depends on `crates/utils` for `FixedU128` and
+//    `SafeErc20`):
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Deploy the reward token first (examples/demo-contracts/erc20.rs)
+//    - Upload the generated .contract file and deploy it with the token, the cap,
+//      the rates (in units of 10^-18) and the end of the campaign and the lease
+//    - Transfer enough reward tokens to the crowdloan to pay a full campaign
+//
+// 4. Interact with the contract:
+//    - Use "quote" to preview a reward, and "contribute" with a value attached
+//    - After the campaign, use "claimReward"; after the lease, use "refund"
@@ -0,0 +1,572 @@
+// examples/demo-contracts/dividend_token.rs
+//
+// A dividend-paying token contract example for Polkadot using ink!
+// This contract extends the ERC-20 example so that native tokens deposited into it
+// are shared among holders in proportion to their balances, each holder claiming
+// their share whenever they like.
+//
+// Paying every holder on each deposit would cost gas proportional to the number of
+// holders. Instead the contract keeps one scaled accumulator, the "magnified
+// dividends per share": every deposit adds `value * MAGNITUDE / total_supply` to it,
+// and a holder's earnings are `balance * accumulator / MAGNITUDE`. Because balances
+// change over time, each account also has a signed correction that cancels the
+// dividends its tokens earned before it held them (or adds back what tokens it sent
+// away had earned while it held them). Every operation is O(1).
+//
+// `MAGNITUDE` keeps the per-share value precise when a deposit is small compared to
+// the supply. The division remainder of each deposit is carried into the next one, so
+// rounding never creates funds, and at most one unit per holder stays unclaimed.
+//
+// The balances mapping cannot be listed, so each account is also appended to a
+// holders index the first time it receives tokens. `holders` returns it a `Page`
+// at a time, with balances, so listing a large distribution never outgrows the
+// return buffer. Accounts stay in the index after their balance drops to zero.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod dividend_token {
+    use astranet_utils::{
+        pagination::Page,
+        validation::{self, MAX_DECIMALS},
+    };
+    use ink_prelude::string::String;
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// Scale of the dividends-per-share accumulator.
+    pub const MAGNITUDE: u128 = 1 << 64;
+
+    /// Largest total that can ever be distributed. `balance * accumulator` is at most
+    /// the total distributed times `MAGNITUDE`, and must fit in the `i128` used with
+    /// the corrections.
+    pub const MAX_DISTRIBUTION: Balance = i128::MAX as u128 / MAGNITUDE;
+
+    /// The dividend token error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the balance is insufficient for the operation.
+        InsufficientBalance,
+        /// Returned if the allowance is insufficient for the operation.
+        InsufficientAllowance,
+        /// Returned if dividends are deposited while the supply is zero.
+        NoHolders,
+        /// Returned if a deposit would take the total distributed past
+        /// `MAX_DISTRIBUTION`, or overflow the accumulator.
+        DistributionTooLarge,
+        /// Returned if the caller has no dividends to claim.
+        NothingToClaim,
+        /// Returned if the native transfer of a claim failed.
+        TransferFailed,
+    }
+
+    /// The dividend token result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    // Events are mirrored in `crates/events/src/dividend_token.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when a token transfer occurs.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    /// Event emitted when an approval occurs.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when native tokens are deposited for holders.
+    #[ink(event)]
+    pub struct DividendsDistributed {
+        #[ink(topic)]
+        from: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when a holder claims their dividends.
+    #[ink(event)]
+    pub struct DividendClaimed {
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+    }
+
+    /// The dividend token storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct DividendToken {
+        /// Total token supply.
+        total_supply: Balance,
+        /// Mapping from owner to balance.
+        balances: Mapping<AccountId, Balance>,
+        /// Mapping from index to every account that ever held tokens, in order of
+        /// first receipt.
+        holders: Mapping<u32, AccountId>,
+        /// Number of entries in `holders`.
+        holder_count: u32,
+        /// Mapping from owner to spender to allowance.
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+        /// Token name.
+        name: String,
+        /// Token symbol.
+        symbol: String,
+        /// Token decimals.
+        decimals: u8,
+        /// Dividends per token held, times `MAGNITUDE`, accumulated over all deposits.
+        magnified_dividend_per_share: u128,
+        /// Remainder of the last deposit's division, carried into the next one.
+        magnified_remainder: u128,
+        /// Total native tokens deposited for holders.
+        total_distributed: Balance,
+        /// Mapping from holder to the correction for balance changes, times
+        /// `MAGNITUDE`.
+        magnified_corrections: Mapping<AccountId, i128>,
+        /// Mapping from holder to the dividends already claimed.
+        withdrawn: Mapping<AccountId, Balance>,
+    }
+
+    impl DividendToken {
+        /// Creates a new dividend token with the specified initial supply. Panics if
+        /// `symbol` is empty or `decimals` is above `MAX_DECIMALS`.
+        #[ink(constructor)]
+        pub fn new(
+            initial_supply: Balance,
+            name: String,
+            symbol: String,
+            decimals: u8,
+        ) -> Self {
+            validation::require("token symbol", validation::non_empty(&symbol));
+            validation::require("decimals", validation::at_most(decimals, MAX_DECIMALS));
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                let caller = Self::env().caller();
+                contract.balances.insert(caller, &initial_supply);
+                contract.holders.insert(0, &caller);
+                contract.holder_count = 1;
+                contract.total_supply = initial_supply;
+                contract.name = name;
+                contract.symbol = symbol;
+                contract.decimals = decimals;
+
+                Self::env().emit_event(Transfer {
+                    from: None,
+                    to: Some(caller),
+                    value: initial_supply,
+                });
+            })
+        }
+
+        /// Returns the token name.
+        #[ink(message)]
+        pub fn name(&self) -> String {
+            self.name.clone()
+        }
+
+        /// Returns the token symbol.
+        #[ink(message)]
+        pub fn symbol(&self) -> String {
+            self.symbol.clone()
+        }
+
+        /// Returns the token decimals.
+        #[ink(message)]
+        pub fn decimals(&self) -> u8 {
+            self.decimals
+        }
+
+        /// Returns the total token supply.
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// Returns the account balance for the specified `owner`.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or(0)
+        }
+
+        /// Returns the number of accounts that ever held tokens.
+        #[ink(message)]
+        pub fn holder_count(&self) -> u32 {
+            self.holder_count
+        }
+
+        /// Returns a page of up to `limit` holders with their balances, in order
+        /// of first receipt, starting at index `cursor` or at the first holder if
+        /// `cursor` is `None`.
+        #[ink(message)]
+        pub fn holders(&self, cursor: Option<u32>, limit: u32) -> Page<(AccountId, Balance)> {
+            Page::from_range(cursor.unwrap_or(0)..self.holder_count, limit, |index| {
+                self.holders
+                    .get(index)
+                    .map(|holder| (holder, self.balance_of(holder)))
+            })
+        }
+
+        /// Returns the amount which `spender` is allowed to withdraw from `owner`.
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or(0)
+        }
+
+        /// Returns the total native tokens deposited for holders so far.
+        #[ink(message)]
+        pub fn total_distributed(&self) -> Balance {
+            self.total_distributed
+        }
+
+        /// Returns all dividends `holder` has earned, claimed or not.
+        #[ink(message)]
+        pub fn accumulative_dividend_of(&self, holder: AccountId) -> Balance {
+            let magnified = (self.magnified_dividend_per_share * self.balance_of(holder)) as i128
+                + self.correction_of(holder);
+            (magnified as u128) / MAGNITUDE
+        }
+
+        /// Returns the dividends `holder` can claim now.
+        #[ink(message)]
+        pub fn withdrawable_dividend_of(&self, holder: AccountId) -> Balance {
+            self.accumulative_dividend_of(holder) - self.withdrawn_dividend_of(holder)
+        }
+
+        /// Returns the dividends `holder` has already claimed.
+        #[ink(message)]
+        pub fn withdrawn_dividend_of(&self, holder: AccountId) -> Balance {
+            self.withdrawn.get(holder).unwrap_or(0)
+        }
+
+        /// Shares the native tokens sent with this call among the current holders, in
+        /// proportion to their balances.
+        #[ink(message, payable)]
+        pub fn distribute(&mut self) -> Result<()> {
+            let value = self.env().transferred_value();
+            let supply = self.total_supply;
+            if supply == 0 {
+                return Err(Error::NoHolders);
+            }
+            let total = self
+                .total_distributed()
+                .checked_add(value)
+                .filter(|total| *total <= MAX_DISTRIBUTION)
+                .ok_or(Error::DistributionTooLarge)?;
+
+            let magnified = value
+                .checked_mul(MAGNITUDE)
+                .and_then(|magnified| magnified.checked_add(self.magnified_remainder))
+                .ok_or(Error::DistributionTooLarge)?;
+            self.magnified_dividend_per_share += magnified / supply;
+            self.magnified_remainder = magnified % supply;
+            self.total_distributed = total;
+
+            self.env().emit_event(DividendsDistributed {
+                from: self.env().caller(),
+                value,
+            });
+            Ok(())
+        }
+
+        /// Pays the caller their withdrawable dividends.
+        #[ink(message)]
+        pub fn claim(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            let value = self.withdrawable_dividend_of(caller);
+            if value == 0 {
+                return Err(Error::NothingToClaim);
+            }
+            self.withdrawn
+                .insert(caller, &(self.withdrawn_dividend_of(caller) + value));
+            if self.env().transfer(caller, value).is_err() {
+                self.withdrawn
+                    .insert(caller, &(self.withdrawn_dividend_of(caller) - value));
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(DividendClaimed { to: caller, value });
+            Ok(())
+        }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            self.transfer_from_to(from, to, value)
+        }
+
+        /// Allows `spender` to withdraw from the caller's account multiple times, up to
+        /// the `value` amount.
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            self.allowances.insert((owner, spender), &value);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` tokens on behalf of `from` to the account `to`.
+        #[ink(message)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let caller = self.env().caller();
+            let allowance = self.allowance(from, caller);
+            if allowance < value {
+                return Err(Error::InsufficientAllowance);
+            }
+            self.transfer_from_to(from, to, value)?;
+            self.allowances.insert((from, caller), &(allowance - value));
+            Ok(())
+        }
+
+        fn correction_of(&self, holder: AccountId) -> i128 {
+            self.magnified_corrections.get(holder).unwrap_or(0)
+        }
+
+        /// Transfers `value` amount of tokens from the `from` account to the `to`
+        /// account. The dividends those tokens earned so far stay with `from`.
+        fn transfer_from_to(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let from_balance = self.balance_of(from);
+            if from_balance < value {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.balances.insert(from, &(from_balance - value));
+            if !self.balances.contains(to) {
+                self.holders.insert(self.holder_count, &to);
+                self.holder_count += 1;
+            }
+            let to_balance = self.balance_of(to);
+            self.balances.insert(to, &(to_balance + value));
+
+            let shift = (self.magnified_dividend_per_share * value) as i128;
+            self.magnified_corrections
+                .insert(from, &(self.correction_of(from) + shift));
+            self.magnified_corrections
+                .insert(to, &(self.correction_of(to) - shift));
+
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                value,
+            });
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        fn new_token(supply: Balance) -> DividendToken {
+            DividendToken::new(supply, String::from("Dividend"), String::from("DIV"), 12)
+        }
+
+        /// Calls `distribute` as the current caller with `value` attached.
+        fn deposit(token: &mut DividendToken, value: Balance) -> Result<()> {
+            ink_env::test::set_value_transferred::<Env>(value);
+            let result = token.distribute();
+            ink_env::test::set_value_transferred::<Env>(0);
+            result
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "token symbol must not be empty")]
+        fn new_rejects_an_empty_symbol() {
+            DividendToken::new(100, String::from("Dividend"), String::new(), 12);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "decimals is too large")]
+        fn new_rejects_too_many_decimals() {
+            DividendToken::new(
+                100,
+                String::from("Dividend"),
+                String::from("DIV"),
+                MAX_DECIMALS + 1,
+            );
+        }
+
+        #[ink::test]
+        fn holders_are_indexed_once_and_paged() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token(100);
+            assert_eq!(token.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(token.transfer(accounts.charlie, 20), Ok(()));
+            assert_eq!(token.transfer(accounts.bob, 5), Ok(()));
+            assert_eq!(token.holder_count(), 3);
+
+            let first = token.holders(None, 2);
+            assert_eq!(first.items, [(accounts.alice, 65), (accounts.bob, 15)]);
+            assert_eq!(first.next_cursor, Some(2));
+            let last = token.holders(first.next_cursor, 2);
+            assert_eq!(last.items, [(accounts.charlie, 20)]);
+            assert_eq!(last.next_cursor, None);
+            assert_eq!(token.holders(Some(3), 2), Page::empty());
+
+            // Emptied accounts stay listed, with a zero balance.
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(token.transfer(accounts.bob, 20), Ok(()));
+            assert_eq!(token.holder_count(), 3);
+            assert_eq!(token.holders(Some(2), 1).items, [(accounts.charlie, 0)]);
+        }
+
+        #[ink::test]
+        fn dividends_are_pro_rata() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token(100);
+            assert_eq!(token.transfer(accounts.bob, 25), Ok(()));
+
+            assert_eq!(deposit(&mut token, 1_000), Ok(()));
+            assert_eq!(token.withdrawable_dividend_of(accounts.alice), 750);
+            assert_eq!(token.withdrawable_dividend_of(accounts.bob), 250);
+            assert_eq!(token.withdrawable_dividend_of(accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn earned_dividends_stay_with_the_sender() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token(100);
+            assert_eq!(deposit(&mut token, 100), Ok(()));
+
+            assert_eq!(token.transfer(accounts.bob, 100), Ok(()));
+            assert_eq!(token.withdrawable_dividend_of(accounts.alice), 100);
+            assert_eq!(token.withdrawable_dividend_of(accounts.bob), 0);
+
+            assert_eq!(deposit(&mut token, 50), Ok(()));
+            assert_eq!(token.withdrawable_dividend_of(accounts.alice), 100);
+            assert_eq!(token.withdrawable_dividend_of(accounts.bob), 50);
+        }
+
+        #[ink::test]
+        fn rounding_remainders_carry_over() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token(3);
+            assert_eq!(token.transfer(accounts.bob, 1), Ok(()));
+            assert_eq!(token.transfer(accounts.charlie, 1), Ok(()));
+
+            // 10 over three holders: each gets 3, one unit is not yet claimable.
+            assert_eq!(deposit(&mut token, 10), Ok(()));
+            for holder in [accounts.alice, accounts.bob, accounts.charlie] {
+                assert_eq!(token.withdrawable_dividend_of(holder), 3);
+            }
+
+            // The remainder joins the next deposit: 12 in total, exactly 4 each.
+            assert_eq!(deposit(&mut token, 2), Ok(()));
+            for holder in [accounts.alice, accounts.bob, accounts.charlie] {
+                assert_eq!(token.withdrawable_dividend_of(holder), 4);
+            }
+        }
+
+        #[ink::test]
+        fn small_deposits_over_a_large_supply_are_not_lost() {
+            let accounts = fixtures::accounts();
+            let supply = 1_000_000_000_000_000;
+            let mut token = new_token(supply);
+            assert_eq!(token.transfer(accounts.bob, supply / 2), Ok(()));
+
+            // Each deposit is far below one unit per token, yet adds up: of the 7_000
+            // deposited, each holder loses at most one unit to rounding.
+            for _ in 0..1_000 {
+                assert_eq!(deposit(&mut token, 7), Ok(()));
+            }
+            let alice = token.withdrawable_dividend_of(accounts.alice);
+            let bob = token.withdrawable_dividend_of(accounts.bob);
+            assert_eq!(alice, 3_499);
+            assert_eq!(bob, 3_499);
+            assert!(token.total_distributed() - (alice + bob) <= 2);
+        }
+
+        #[ink::test]
+        fn claim_pays_native_tokens_once() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token(100);
+            assert_eq!(token.transfer(accounts.bob, 40), Ok(()));
+            assert_eq!(deposit(&mut token, 1_000), Ok(()));
+            fixtures::fund_contract(1_000);
+
+            fixtures::set_caller(accounts.bob);
+            let before = fixtures::balance_of(accounts.bob);
+            assert_eq!(token.claim(), Ok(()));
+            assert_eq!(fixtures::balance_of(accounts.bob), before + 400);
+            assert_eq!(token.withdrawn_dividend_of(accounts.bob), 400);
+            assert_eq!(token.claim(), Err(Error::NothingToClaim));
+
+            // Tokens bought after a claim do not bring back old dividends.
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(token.transfer(accounts.bob, 60), Ok(()));
+            assert_eq!(token.withdrawable_dividend_of(accounts.bob), 0);
+            assert_eq!(token.withdrawable_dividend_of(accounts.alice), 600);
+        }
+
+        #[ink::test]
+        fn deposits_need_holders_and_fit_the_accumulator() {
+            let mut empty = new_token(0);
+            assert_eq!(deposit(&mut empty, 10), Err(Error::NoHolders));
+
+            let mut token = new_token(100);
+            assert_eq!(deposit(&mut token, MAX_DISTRIBUTION), Ok(()));
+            assert_eq!(deposit(&mut token, 1), Err(Error::DistributionTooLarge));
+        }
+
+        #[ink::test]
+        fn deposits_that_overflow_the_magnified_value_are_refused() {
+            let mut token = new_token(100);
+            token.magnified_remainder = u128::MAX - MAGNITUDE + 1;
+            assert_eq!(deposit(&mut token, 1), Err(Error::DistributionTooLarge));
+            assert_eq!(token.total_distributed(), 0);
+            assert_eq!(token.magnified_dividend_per_share, 0);
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy using the Polkadot JS Apps UI:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your desired network
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Set the initial supply, name, symbol, and decimals
+//    - Deploy the contract
+//
+// 4. Interact with the contract:
+//    - Use the "transfer" method to hand tokens to other holders
+//    - Use "holders" to page through the holders and their balances
+//    - Call "distribute" with some value attached to share it among holders
+//    - Use "withdrawableDividendOf" to see an account's share
+//    - Use "claim" to receive your share
@@ -0,0 +1,439 @@
+// examples/demo-contracts/donation.rs
+//
+// A matched charity donation contract example for Polkadot using ink!
+// A sponsor deploys this contract for a charity and deposits a matching pool. Until
+// the campaign deadline, every donation is matched 1:1 from the pool: donate 10 and
+// the charity receives 20. Once the pool runs out, donations are still accepted but
+// no longer matched; a donation larger than what is left of the pool is matched
+// only up to the remainder.
+//
+// Donations and matches accumulate in the contract until the charity withdraws
+// them. After the deadline the sponsor can reclaim whatever is left of the pool,
+// so unused matching funds are never stuck.
+//
+// The deadline is the utils crate's inclusive `Deadline`: donations are still
+// accepted at exactly `deadline`, and the pool can be reclaimed the millisecond after.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod donation {
+    use astranet_utils::deadline::Deadline;
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// The donation error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if no value was sent along.
+        ZeroAmount,
+        /// Returned if the campaign deadline has passed.
+        CampaignEnded,
+        /// Returned if the campaign deadline has not passed yet.
+        CampaignActive,
+        /// Returned if the caller is not the sponsor.
+        NotSponsor,
+        /// Returned if the caller is not the beneficiary.
+        NotBeneficiary,
+        /// Returned if there are no funds to send.
+        NothingToWithdraw,
+        /// Returned if the native transfer failed.
+        TransferFailed,
+    }
+
+    /// The donation result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    // Events are mirrored in `crates/events/src/donation.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when the sponsor adds to the matching pool.
+    #[ink(event)]
+    pub struct MatchingFunded {
+        amount: Balance,
+        pool: Balance,
+    }
+
+    /// Event emitted for every donation.
+    #[ink(event)]
+    pub struct Donated {
+        #[ink(topic)]
+        donor: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when a donation is matched from the pool, with what is left
+    /// of the pool afterwards.
+    #[ink(event)]
+    pub struct Matched {
+        #[ink(topic)]
+        donor: AccountId,
+        amount: Balance,
+        pool: Balance,
+    }
+
+    /// Event emitted when the beneficiary withdraws what has been raised.
+    #[ink(event)]
+    pub struct Withdrawn {
+        amount: Balance,
+    }
+
+    /// Event emitted when the sponsor reclaims the unused matching pool.
+    #[ink(event)]
+    pub struct MatchingReclaimed {
+        amount: Balance,
+    }
+
+    /// The donation storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Donation {
+        /// Account funding the matching pool.
+        sponsor: AccountId,
+        /// Charity receiving donations and matches.
+        beneficiary: AccountId,
+        /// Last time at which donations are accepted.
+        deadline: Deadline,
+        /// Matching funds not used yet.
+        pool: Balance,
+        /// Donations and matches not withdrawn yet.
+        raised: Balance,
+        /// Donations and matches raised over the whole campaign.
+        total_raised: Balance,
+        /// Mapping from donor to the total they donated.
+        donations: Mapping<AccountId, Balance>,
+    }
+
+    impl Donation {
+        /// Creates a campaign for `beneficiary` running until `deadline`, with the
+        /// caller as sponsor and an empty matching pool.
+        #[ink(constructor)]
+        pub fn new(beneficiary: AccountId, deadline: Timestamp) -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.sponsor = Self::env().caller();
+                contract.beneficiary = beneficiary;
+                contract.deadline = Deadline::at(deadline);
+            })
+        }
+
+        /// Returns the sponsor.
+        #[ink(message)]
+        pub fn sponsor(&self) -> AccountId {
+            self.sponsor
+        }
+
+        /// Returns the beneficiary.
+        #[ink(message)]
+        pub fn beneficiary(&self) -> AccountId {
+            self.beneficiary
+        }
+
+        /// Returns the campaign deadline.
+        #[ink(message)]
+        pub fn deadline(&self) -> Timestamp {
+            self.deadline.timestamp()
+        }
+
+        /// Returns the matching funds not used yet.
+        #[ink(message)]
+        pub fn pool(&self) -> Balance {
+            self.pool
+        }
+
+        /// Returns the donations and matches the beneficiary can withdraw.
+        #[ink(message)]
+        pub fn raised(&self) -> Balance {
+            self.raised
+        }
+
+        /// Returns the donations and matches raised over the whole campaign.
+        #[ink(message)]
+        pub fn total_raised(&self) -> Balance {
+            self.total_raised
+        }
+
+        /// Returns the total `donor` has donated.
+        #[ink(message)]
+        pub fn donated_by(&self, donor: AccountId) -> Balance {
+            self.donations.get(donor).unwrap_or(0)
+        }
+
+        /// Adds the value sent along to the matching pool. Only the sponsor can call
+        /// this, and only before the deadline.
+        #[ink(message, payable)]
+        pub fn fund_matching(&mut self) -> Result<()> {
+            if self.env().caller() != self.sponsor {
+                return Err(Error::NotSponsor);
+            }
+            self.ensure_active()?;
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.pool += amount;
+            self.env().emit_event(MatchingFunded {
+                amount,
+                pool: self.pool,
+            });
+            Ok(())
+        }
+
+        /// Donates the value sent along, matched 1:1 from the pool as far as it
+        /// reaches. Returns the matched amount.
+        #[ink(message, payable)]
+        pub fn donate(&mut self) -> Result<Balance> {
+            self.ensure_active()?;
+            let donor = self.env().caller();
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let matched = amount.min(self.pool);
+            self.pool -= matched;
+            self.raised += amount + matched;
+            self.total_raised += amount + matched;
+            let donated = self.donated_by(donor);
+            self.donations.insert(donor, &(donated + amount));
+
+            self.env().emit_event(Donated { donor, amount });
+            if matched > 0 {
+                self.env().emit_event(Matched {
+                    donor,
+                    amount: matched,
+                    pool: self.pool,
+                });
+            }
+            Ok(matched)
+        }
+
+        /// Sends everything raised so far to the beneficiary. Only the beneficiary
+        /// can call this, at any time.
+        #[ink(message)]
+        pub fn withdraw(&mut self) -> Result<()> {
+            let beneficiary = self.beneficiary;
+            if self.env().caller() != beneficiary {
+                return Err(Error::NotBeneficiary);
+            }
+            let amount = self.raised;
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+            self.raised = 0;
+            if self.env().transfer(beneficiary, amount).is_err() {
+                self.raised = amount;
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(Withdrawn { amount });
+            Ok(())
+        }
+
+        /// Sends what is left of the matching pool back to the sponsor. Only the
+        /// sponsor can call this, once the deadline has passed.
+        #[ink(message)]
+        pub fn reclaim_matching(&mut self) -> Result<()> {
+            let sponsor = self.sponsor;
+            if self.env().caller() != sponsor {
+                return Err(Error::NotSponsor);
+            }
+            if !self.deadline.is_expired(self.env().block_timestamp()) {
+                return Err(Error::CampaignActive);
+            }
+            let amount = self.pool;
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+            self.pool = 0;
+            if self.env().transfer(sponsor, amount).is_err() {
+                self.pool = amount;
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(MatchingReclaimed { amount });
+            Ok(())
+        }
+
+        /// Returns `CampaignEnded` once the deadline has passed.
+        fn ensure_active(&self) -> Result<()> {
+            if self.deadline.is_expired(self.env().block_timestamp()) {
+                return Err(Error::CampaignEnded);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use fixtures::{set_block_timestamp, BLOCK_TIME};
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        const DEADLINE: Timestamp = 200 * BLOCK_TIME;
+
+        fn charity() -> AccountId {
+            AccountId::from([0xc4; 32])
+        }
+
+        /// Runs `f` with `value` attached, as the current caller.
+        fn with_value<R>(value: Balance, f: impl FnOnce() -> R) -> R {
+            ink_env::test::set_value_transferred::<Env>(value);
+            let result = f();
+            ink_env::test::set_value_transferred::<Env>(0);
+            result
+        }
+
+        /// A campaign with a matching pool of `pool`, sponsored by alice.
+        fn campaign(pool: Balance) -> Donation {
+            let mut donation = Donation::new(charity(), DEADLINE);
+            assert_eq!(with_value(pool, || donation.fund_matching()), Ok(()));
+            donation
+        }
+
+        fn donate_as(donation: &mut Donation, donor: AccountId, value: Balance) -> Result<Balance> {
+            fixtures::with_caller(donor, || with_value(value, || donation.donate()))
+        }
+
+        #[ink::test]
+        fn donations_are_matched_one_to_one() {
+            let accounts = fixtures::accounts();
+            let mut donation = campaign(100);
+            assert_eq!(donate_as(&mut donation, accounts.bob, 30), Ok(30));
+            assert_eq!(donation.pool(), 70);
+            assert_eq!(donation.raised(), 60);
+            assert_eq!(donation.donated_by(accounts.bob), 30);
+            assert_eq!(ink_env::test::recorded_events().count(), 3);
+        }
+
+        #[ink::test]
+        fn donation_exhausting_pool_is_matched_partially() {
+            let accounts = fixtures::accounts();
+            let mut donation = campaign(100);
+            assert_eq!(donate_as(&mut donation, accounts.bob, 80), Ok(80));
+            assert_eq!(donate_as(&mut donation, accounts.charlie, 50), Ok(20));
+            assert_eq!(donation.pool(), 0);
+            assert_eq!(donation.raised(), 80 + 80 + 50 + 20);
+        }
+
+        #[ink::test]
+        fn donation_draining_pool_exactly() {
+            let accounts = fixtures::accounts();
+            let mut donation = campaign(100);
+            assert_eq!(donate_as(&mut donation, accounts.bob, 100), Ok(100));
+            assert_eq!(donation.pool(), 0);
+            assert_eq!(donation.raised(), 200);
+        }
+
+        #[ink::test]
+        fn donations_after_exhaustion_are_unmatched() {
+            let accounts = fixtures::accounts();
+            let mut donation = campaign(10);
+            assert_eq!(donate_as(&mut donation, accounts.bob, 10), Ok(10));
+            let events = ink_env::test::recorded_events().count();
+            assert_eq!(donate_as(&mut donation, accounts.bob, 5), Ok(0));
+            // Only `Donated`, no `Matched`.
+            assert_eq!(ink_env::test::recorded_events().count(), events + 1);
+            assert_eq!(donation.donated_by(accounts.bob), 15);
+            assert_eq!(donation.raised(), 25);
+        }
+
+        #[ink::test]
+        fn pool_can_be_topped_up() {
+            let accounts = fixtures::accounts();
+            let mut donation = campaign(10);
+            assert_eq!(donate_as(&mut donation, accounts.bob, 20), Ok(10));
+            assert_eq!(with_value(15, || donation.fund_matching()), Ok(()));
+            assert_eq!(donate_as(&mut donation, accounts.bob, 20), Ok(15));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                with_value(15, || donation.fund_matching()),
+                Err(Error::NotSponsor)
+            );
+        }
+
+        #[ink::test]
+        fn deadline_closes_campaign() {
+            let accounts = fixtures::accounts();
+            let mut donation = campaign(100);
+            set_block_timestamp(DEADLINE);
+            assert_eq!(donate_as(&mut donation, accounts.bob, 1), Ok(1));
+            set_block_timestamp(DEADLINE + BLOCK_TIME);
+            assert_eq!(
+                donate_as(&mut donation, accounts.bob, 1),
+                Err(Error::CampaignEnded)
+            );
+            assert_eq!(
+                donate_as(&mut donation, accounts.bob, 0),
+                Err(Error::CampaignEnded)
+            );
+        }
+
+        #[ink::test]
+        fn beneficiary_withdraws_raised() {
+            let accounts = fixtures::accounts();
+            let mut donation = campaign(100);
+            assert_eq!(donate_as(&mut donation, accounts.bob, 40), Ok(40));
+            fixtures::fund_contract(140);
+            fixtures::fund(charity(), 0);
+
+            assert_eq!(donation.withdraw(), Err(Error::NotBeneficiary));
+            fixtures::set_caller(charity());
+            assert_eq!(donation.withdraw(), Ok(()));
+            assert_eq!(fixtures::balance_of(charity()), 80);
+            assert_eq!(donation.raised(), 0);
+            assert_eq!(donation.total_raised(), 80);
+            assert_eq!(donation.withdraw(), Err(Error::NothingToWithdraw));
+        }
+
+        #[ink::test]
+        fn sponsor_reclaims_unused_pool_after_deadline() {
+            let accounts = fixtures::accounts();
+            let mut donation = campaign(100);
+            assert_eq!(donate_as(&mut donation, accounts.bob, 40), Ok(40));
+            fixtures::fund_contract(140);
+            let before = fixtures::balance_of(accounts.alice);
+
+            assert_eq!(donation.reclaim_matching(), Err(Error::CampaignActive));
+            set_block_timestamp(DEADLINE + BLOCK_TIME);
+            assert_eq!(donation.reclaim_matching(), Ok(()));
+            assert_eq!(fixtures::balance_of(accounts.alice), before + 60);
+            assert_eq!(donation.pool(), 0);
+            assert_eq!(donation.reclaim_matching(), Err(Error::NothingToWithdraw));
+        }
+
+        #[ink::test]
+        fn zero_donation_is_rejected() {
+            let accounts = fixtures::accounts();
+            let mut donation = campaign(100);
+            assert_eq!(
+                donate_as(&mut donation, accounts.bob, 0),
+                Err(Error::ZeroAmount)
+            );
+            assert_eq!(donation.pool(), 100);
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy from the sponsor account with the charity address and a deadline
+//
+// 4. Interact with the contract:
+//    - As the sponsor, use "fundMatching" with some value to fill the pool
+//    - Use the "donate" method with some value to donate and get matched
+//    - As the charity, use "withdraw" to collect what has been raised
+//    - After the deadline, the sponsor can use "reclaimMatching"
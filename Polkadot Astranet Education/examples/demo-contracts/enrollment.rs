@@ -0,0 +1,465 @@
+// examples/demo-contracts/enrollment.rs
+//
+// A course enrollment contract example for Polkadot using ink!
+// Instructors create courses with a fee and a refund window. Learners enroll by
+// paying the fee, and can take their money back themselves until the window
+// closes. After that, the instructor withdraws the fees; learners who enroll once
+// the window has closed pay a fee the instructor can withdraw right away. The end
+// of the window is the utils crate's inclusive `Deadline`: refunds still work at
+// exactly the deadline, and withdrawals from the millisecond after it.
+//
+// Storage
+// -------
+// Unlike the older examples, which keep entries in `collections::HashMap`, this
+// contract stores its courses and enrollments in ink!'s `Mapping`, like the utils
+// crate does. A `Mapping` stores every value under its own storage key and has no
+// way to iterate, so the contract keeps counts and totals per course instead of
+// walking the entries. Contracts holding a `Mapping` derive `SpreadAllocate` and
+// build their storage with `initialize_contract`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod enrollment {
+    use astranet_utils::deadline::Deadline;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Identifies a course.
+    pub type CourseId = u32;
+
+    /// The enrollment error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if no course has the given id.
+        CourseNotFound,
+        /// Returned if the value sent along is not the course fee.
+        WrongFee,
+        /// Returned if the learner is already enrolled.
+        AlreadyEnrolled,
+        /// Returned if the learner is not enrolled.
+        NotEnrolled,
+        /// Returned if the refund window of the course has closed.
+        RefundWindowClosed,
+        /// Returned if the refund window of the course is still open.
+        RefundWindowOpen,
+        /// Returned if the caller is not the course instructor.
+        NotInstructor,
+        /// Returned if there are no fees to withdraw.
+        NothingToWithdraw,
+        /// Returned if the native transfer failed.
+        TransferFailed,
+    }
+
+    /// The enrollment result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A course and the fees it holds.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Course {
+        /// Account that created the course and receives its fees.
+        pub instructor: AccountId,
+        /// Fee paid to enroll.
+        pub fee: Balance,
+        /// Last time at which enrolled learners can refund themselves.
+        pub refund_deadline: Deadline,
+        /// Number of learners enrolled.
+        pub enrolled: u32,
+        /// Fees paid and not yet refunded or withdrawn.
+        pub held: Balance,
+    }
+
+    // Events are mirrored in `crates/events/src/enrollment.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when an instructor creates a course.
+    #[ink(event)]
+    pub struct CourseCreated {
+        #[ink(topic)]
+        course_id: CourseId,
+        #[ink(topic)]
+        instructor: AccountId,
+        fee: Balance,
+        refund_deadline: Timestamp,
+    }
+
+    /// Event emitted when a learner enrolls.
+    #[ink(event)]
+    pub struct Enrolled {
+        #[ink(topic)]
+        course_id: CourseId,
+        #[ink(topic)]
+        learner: AccountId,
+        fee: Balance,
+    }
+
+    /// Event emitted when a learner refunds their enrollment.
+    #[ink(event)]
+    pub struct Refunded {
+        #[ink(topic)]
+        course_id: CourseId,
+        #[ink(topic)]
+        learner: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when the instructor withdraws the fees of a course.
+    #[ink(event)]
+    pub struct FeesWithdrawn {
+        #[ink(topic)]
+        course_id: CourseId,
+        amount: Balance,
+    }
+
+    /// The enrollment storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Enrollment {
+        /// Mapping from course id to course.
+        courses: Mapping<CourseId, Course>,
+        /// Mapping from course id and learner to the fee they paid.
+        enrollments: Mapping<(CourseId, AccountId), Balance>,
+        /// Id the next course will get.
+        next_course_id: CourseId,
+    }
+
+    impl Enrollment {
+        /// Creates a contract with no courses.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|_: &mut Self| {})
+        }
+
+        /// Returns the course with id `course_id`.
+        #[ink(message)]
+        pub fn course(&self, course_id: CourseId) -> Option<Course> {
+            self.courses.get(course_id)
+        }
+
+        /// Returns whether `learner` is enrolled in course `course_id`.
+        #[ink(message)]
+        pub fn is_enrolled(&self, course_id: CourseId, learner: AccountId) -> bool {
+            self.enrollments.contains((course_id, learner))
+        }
+
+        /// Creates a course taught by the caller, costing `fee`, whose learners can
+        /// refund themselves for `refund_window` milliseconds from now. Returns the
+        /// course id.
+        #[ink(message)]
+        pub fn create_course(&mut self, fee: Balance, refund_window: Timestamp) -> CourseId {
+            let course_id = self.next_course_id;
+            self.next_course_id += 1;
+            let instructor = self.env().caller();
+            let refund_deadline =
+                Deadline::at(self.env().block_timestamp().saturating_add(refund_window));
+            self.courses.insert(
+                course_id,
+                &Course {
+                    instructor,
+                    fee,
+                    refund_deadline,
+                    enrolled: 0,
+                    held: 0,
+                },
+            );
+            self.env().emit_event(CourseCreated {
+                course_id,
+                instructor,
+                fee,
+                refund_deadline: refund_deadline.timestamp(),
+            });
+            course_id
+        }
+
+        /// Enrolls the caller in course `course_id`. The value sent along must be
+        /// exactly the course fee.
+        #[ink(message, payable)]
+        pub fn enroll(&mut self, course_id: CourseId) -> Result<()> {
+            let mut course = self.course(course_id).ok_or(Error::CourseNotFound)?;
+            let learner = self.env().caller();
+            let fee = self.env().transferred_value();
+            if fee != course.fee {
+                return Err(Error::WrongFee);
+            }
+            if self.is_enrolled(course_id, learner) {
+                return Err(Error::AlreadyEnrolled);
+            }
+            course.enrolled += 1;
+            course.held += fee;
+            self.courses.insert(course_id, &course);
+            self.enrollments.insert((course_id, learner), &fee);
+            self.env().emit_event(Enrolled {
+                course_id,
+                learner,
+                fee,
+            });
+            Ok(())
+        }
+
+        /// Unenrolls the caller from course `course_id` and returns their fee. Only
+        /// possible until the refund deadline.
+        #[ink(message)]
+        pub fn refund(&mut self, course_id: CourseId) -> Result<()> {
+            let course = self.course(course_id).ok_or(Error::CourseNotFound)?;
+            let learner = self.env().caller();
+            let amount = self
+                .enrollments
+                .get((course_id, learner))
+                .ok_or(Error::NotEnrolled)?;
+            if course.refund_deadline.is_expired(self.env().block_timestamp()) {
+                return Err(Error::RefundWindowClosed);
+            }
+
+            self.enrollments.remove((course_id, learner));
+            self.courses.insert(
+                course_id,
+                &Course {
+                    enrolled: course.enrolled - 1,
+                    held: course.held - amount,
+                    ..course
+                },
+            );
+            if self.env().transfer(learner, amount).is_err() {
+                self.enrollments.insert((course_id, learner), &amount);
+                self.courses.insert(course_id, &course);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(Refunded {
+                course_id,
+                learner,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Sends the fees held by course `course_id` to its instructor. Only the
+        /// instructor can call this, once the refund deadline has passed.
+        #[ink(message)]
+        pub fn withdraw(&mut self, course_id: CourseId) -> Result<()> {
+            let course = self.course(course_id).ok_or(Error::CourseNotFound)?;
+            if self.env().caller() != course.instructor {
+                return Err(Error::NotInstructor);
+            }
+            if !course.refund_deadline.is_expired(self.env().block_timestamp()) {
+                return Err(Error::RefundWindowOpen);
+            }
+            let amount = course.held;
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+
+            self.courses
+                .insert(course_id, &Course { held: 0, ..course });
+            if self.env().transfer(course.instructor, amount).is_err() {
+                self.courses.insert(course_id, &course);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(FeesWithdrawn { course_id, amount });
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use fixtures::BLOCK_TIME;
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        const FEE: Balance = 50;
+        const WINDOW: Timestamp = 100 * BLOCK_TIME;
+        /// The first block after the refund window.
+        const AFTER_WINDOW: Timestamp = WINDOW + BLOCK_TIME;
+
+        /// Enrolls `learner` in `course_id`, sending `value` along.
+        fn enroll_as(
+            contract: &mut Enrollment,
+            learner: AccountId,
+            course_id: CourseId,
+            value: Balance,
+        ) -> Result<()> {
+            fixtures::with_caller(learner, || {
+                ink_env::test::set_value_transferred::<Env>(value);
+                let result = contract.enroll(course_id);
+                ink_env::test::set_value_transferred::<Env>(0);
+                result
+            })
+        }
+
+        /// A contract with one course by alice, with bob and charlie enrolled.
+        fn course_with_learners() -> (Enrollment, CourseId) {
+            let accounts = fixtures::accounts();
+            let mut contract = Enrollment::new();
+            let course_id = contract.create_course(FEE, WINDOW);
+            assert_eq!(
+                enroll_as(&mut contract, accounts.bob, course_id, FEE),
+                Ok(())
+            );
+            assert_eq!(
+                enroll_as(&mut contract, accounts.charlie, course_id, FEE),
+                Ok(())
+            );
+            fixtures::fund_contract(2 * FEE);
+            (contract, course_id)
+        }
+
+        #[ink::test]
+        fn enrolling_requires_exact_fee() {
+            let accounts = fixtures::accounts();
+            let (mut contract, course_id) = course_with_learners();
+            assert_eq!(
+                enroll_as(&mut contract, accounts.dave, course_id, FEE - 1),
+                Err(Error::WrongFee)
+            );
+            assert_eq!(
+                enroll_as(&mut contract, accounts.dave, course_id, FEE + 1),
+                Err(Error::WrongFee)
+            );
+            assert_eq!(
+                enroll_as(&mut contract, accounts.bob, course_id, FEE),
+                Err(Error::AlreadyEnrolled)
+            );
+            assert_eq!(
+                enroll_as(&mut contract, accounts.bob, 7, FEE),
+                Err(Error::CourseNotFound)
+            );
+
+            let course = contract.course(course_id).expect("course exists");
+            assert_eq!(course.enrolled, 2);
+            assert_eq!(course.held, 2 * FEE);
+            assert!(contract.is_enrolled(course_id, accounts.bob));
+            assert!(!contract.is_enrolled(course_id, accounts.dave));
+        }
+
+        #[ink::test]
+        fn refund_within_window() {
+            let accounts = fixtures::accounts();
+            let (mut contract, course_id) = course_with_learners();
+            let before = fixtures::balance_of(accounts.bob);
+            // The deadline itself is still within the window.
+            fixtures::set_block_timestamp(WINDOW);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(contract.refund(course_id), Ok(()));
+            assert_eq!(fixtures::balance_of(accounts.bob), before + FEE);
+            assert!(!contract.is_enrolled(course_id, accounts.bob));
+            assert_eq!(
+                contract.course(course_id).map(|course| course.held),
+                Some(FEE)
+            );
+            assert_eq!(contract.refund(course_id), Err(Error::NotEnrolled));
+        }
+
+        #[ink::test]
+        fn refund_closes_after_deadline() {
+            let accounts = fixtures::accounts();
+            let (mut contract, course_id) = course_with_learners();
+            fixtures::set_block_timestamp(AFTER_WINDOW);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(contract.refund(course_id), Err(Error::RefundWindowClosed));
+            assert!(contract.is_enrolled(course_id, accounts.bob));
+        }
+
+        #[ink::test]
+        fn instructor_withdraws_after_window() {
+            let accounts = fixtures::accounts();
+            let (mut contract, course_id) = course_with_learners();
+            let before = fixtures::balance_of(accounts.alice);
+            fixtures::set_block_timestamp(WINDOW);
+            assert_eq!(contract.withdraw(course_id), Err(Error::RefundWindowOpen));
+
+            fixtures::set_block_timestamp(AFTER_WINDOW);
+            assert_eq!(contract.withdraw(course_id), Ok(()));
+            assert_eq!(fixtures::balance_of(accounts.alice), before + 2 * FEE);
+            assert_eq!(contract.withdraw(course_id), Err(Error::NothingToWithdraw));
+        }
+
+        #[ink::test]
+        fn late_enrollment_is_withdrawable_at_once() {
+            let accounts = fixtures::accounts();
+            let (mut contract, course_id) = course_with_learners();
+            fixtures::set_block_timestamp(AFTER_WINDOW);
+            assert_eq!(contract.withdraw(course_id), Ok(()));
+
+            assert_eq!(
+                enroll_as(&mut contract, accounts.dave, course_id, FEE),
+                Ok(())
+            );
+            fixtures::fund_contract(FEE);
+            fixtures::set_caller(accounts.dave);
+            assert_eq!(contract.refund(course_id), Err(Error::RefundWindowClosed));
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(contract.withdraw(course_id), Ok(()));
+        }
+
+        #[ink::test]
+        fn refunded_fees_are_not_withdrawn() {
+            let accounts = fixtures::accounts();
+            let (mut contract, course_id) = course_with_learners();
+            fixtures::with_caller(accounts.bob, || contract.refund(course_id)).expect("refunded");
+            fixtures::set_block_timestamp(AFTER_WINDOW);
+            let before = fixtures::balance_of(accounts.alice);
+            assert_eq!(contract.withdraw(course_id), Ok(()));
+            assert_eq!(fixtures::balance_of(accounts.alice), before + FEE);
+        }
+
+        #[ink::test]
+        fn only_instructor_withdraws() {
+            let accounts = fixtures::accounts();
+            let (mut contract, course_id) = course_with_learners();
+            fixtures::set_block_timestamp(AFTER_WINDOW);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(contract.withdraw(course_id), Err(Error::NotInstructor));
+        }
+
+        #[ink::test]
+        fn courses_are_independent() {
+            let accounts = fixtures::accounts();
+            let (mut contract, first) = course_with_learners();
+            let second = fixtures::with_caller(accounts.eve, || contract.create_course(10, 0));
+            assert_eq!(second, first + 1);
+            assert!(!contract.is_enrolled(second, accounts.bob));
+            assert_eq!(enroll_as(&mut contract, accounts.bob, second, 10), Ok(()));
+            // A zero-length window closes after the block that created the course.
+            fixtures::set_block_timestamp(BLOCK_TIME);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(contract.refund(second), Err(Error::RefundWindowClosed));
+            assert_eq!(contract.refund(first), Ok(()));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy the contract
+//
+// 4. Interact with the contract:
+//    - As an instructor, use "createCourse" with a fee and a refund window in
+//      milliseconds
+//    - As a learner, use "enroll" with the course id, sending the fee along
+//    - Use "refund" to get the fee back before the refund deadline
+//    - After the deadline, the instructor uses "withdraw" to collect the fees
@@ -2,6 +2,29 @@
 //
 // An ERC-20 token contract example for Polkadot using ink!
 // This contract demonstrates a standard token implementation with transfer functionality.
+//
+// For the compliance lesson the token also has an optional restricted mode: while it
+// is on, only addresses on an allowlist kept by the owner can receive tokens. Sending
+// is never restricted, so holders can always move funds back to an allowed address.
+//
+// Allowances can also expire: `approve_with_expiry` stores a deadline next to the
+// value, and `transfer_from` refuses to spend the allowance after it. The deadline is
+// the utils crate's `Deadline`, which is inclusive, so the allowance can still be
+// spent at exactly `deadline`. A plain `approve` never expires.
+//
+// The name and symbol are bounded with the utils crate's `BoundedString`: a name
+// over `MAX_NAME_LEN` bytes or a symbol over `MAX_SYMBOL_LEN` bytes makes the
+// constructor panic, so wallets never have to display or store an unbounded one.
+// The constructor also rejects an empty symbol and more than `MAX_DECIMALS`
+// decimals, and a zero initial supply if `REQUIRE_INITIAL_SUPPLY` is set, using
+// the checks in the utils crate's `validation` module.
+//
+// Transfers to the zero address (`AccountId::from([0; 32])`) are rejected with
+// `Error::InvalidRecipient`: nobody holds its key, so tokens sent there by mistake,
+// usually from a default-initialised address in a front-end, are lost for good.
+// Burning, where it exists, is its own message. A transfer from an account to
+// itself is a no-op: it still fails if the balance or allowance is too small, but
+// moves nothing, spends no allowance and emits no `Transfer` event.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -9,10 +32,29 @@ use ink_lang as ink;
 
 #[ink::contract]
 mod erc20 {
-    use ink_storage::{
-        collections::HashMap,
-        lazy::Lazy,
+    use astranet_utils::{
+        bounded::BoundedString,
+        deadline::Deadline,
+        validation::{self, MAX_DECIMALS},
     };
+    use ink_prelude::string::String;
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// Deadline of an allowance that never expires.
+    pub const NO_EXPIRY: Timestamp = Timestamp::MAX;
+
+    /// The zero address, which tokens cannot be sent to.
+    pub const ZERO_ADDRESS: [u8; 32] = [0; 32];
+
+    /// Longest token name, in bytes.
+    pub const MAX_NAME_LEN: usize = 32;
+
+    /// Longest token symbol, in bytes.
+    pub const MAX_SYMBOL_LEN: usize = 8;
+
+    /// Whether the constructor rejects a zero initial supply. Off so that a token
+    /// can start empty and be minted later by an extension.
+    pub const REQUIRE_INITIAL_SUPPLY: bool = false;
 
     /// The ERC-20 error types.
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
@@ -22,11 +64,22 @@ mod erc20 {
         InsufficientBalance,
         /// Returned if the allowance is insufficient for the operation.
         InsufficientAllowance,
+        /// Returned if the caller is not the token owner.
+        NotOwner,
+        /// Returned if restricted mode is on and the recipient is not allowlisted.
+        RecipientNotAllowlisted,
+        /// Returned if the deadline of the allowance has passed.
+        AllowanceExpired,
+        /// Returned if the recipient is the zero address.
+        InvalidRecipient,
     }
 
     /// The ERC-20 result type.
     pub type Result<T> = core::result::Result<T, Error>;
 
+    // Events are mirrored in `crates/events/src/erc20.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
     /// Event emitted when a token transfer occurs.
     #[ink(event)]
     pub struct Transfer {
@@ -47,25 +100,62 @@ mod erc20 {
         value: Balance,
     }
 
+    /// Event emitted when the owner adds or removes an account from the allowlist.
+    #[ink(event)]
+    pub struct AllowlistUpdated {
+        #[ink(topic)]
+        account: AccountId,
+        allowed: bool,
+    }
+
+    /// Event emitted when the owner turns restricted mode on or off.
+    #[ink(event)]
+    pub struct RestrictedModeSet {
+        enabled: bool,
+    }
+
+    /// Event emitted after `Approval` when the allowance is given a deadline.
+    #[ink(event)]
+    pub struct AllowanceExpirySet {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        deadline: Timestamp,
+    }
+
     /// The ERC-20 storage items.
     #[ink(storage)]
+    #[derive(SpreadAllocate)]
     pub struct Erc20 {
         /// Total token supply.
-        total_supply: Lazy<Balance>,
+        total_supply: Balance,
         /// Mapping from owner to balance.
-        balances: HashMap<AccountId, Balance>,
-        /// Mapping from owner to spender to allowance.
-        allowances: HashMap<(AccountId, AccountId), Balance>,
+        balances: Mapping<AccountId, Balance>,
+        /// Mapping from owner to spender to allowance and its deadline.
+        allowances: Mapping<(AccountId, AccountId), (Balance, Deadline)>,
         /// Token name.
-        name: Lazy<String>,
+        name: String,
         /// Token symbol.
-        symbol: Lazy<String>,
+        symbol: String,
         /// Token decimals.
-        decimals: Lazy<u8>,
+        decimals: u8,
+        /// Account allowed to manage restricted mode and the allowlist.
+        owner: AccountId,
+        /// Whether only allowlisted accounts can receive tokens.
+        restricted: bool,
+        /// Accounts that can receive tokens in restricted mode.
+        allowlist: Mapping<AccountId, bool>,
     }
 
     impl Erc20 {
-        /// Creates a new ERC-20 contract with the specified initial supply.
+        /// Creates a new ERC-20 contract with the specified initial supply, owned by the
+        /// caller and with restricted mode off.
+        ///
+        /// Panics if `name` or `symbol` is longer than `MAX_NAME_LEN` or
+        /// `MAX_SYMBOL_LEN` bytes, if `symbol` is empty, if `decimals` is above
+        /// `MAX_DECIMALS`, or if `initial_supply` is zero while
+        /// `REQUIRE_INITIAL_SUPPLY` is set.
         #[ink(constructor)]
         pub fn new(
             initial_supply: Balance,
@@ -73,60 +163,119 @@ mod erc20 {
             symbol: String,
             decimals: u8,
         ) -> Self {
-            let caller = Self::env().caller();
-            let mut balances = HashMap::new();
-            balances.insert(caller, initial_supply);
-
-            Self::env().emit_event(Transfer {
-                from: None,
-                to: Some(caller),
-                value: initial_supply,
-            });
+            let (name, symbol) =
+                Self::validate_new(initial_supply, name, symbol, decimals, REQUIRE_INITIAL_SUPPLY);
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                let caller = Self::env().caller();
+                contract.balances.insert(caller, &initial_supply);
+                contract.total_supply = initial_supply;
+                contract.name = name;
+                contract.symbol = symbol;
+                contract.decimals = decimals;
+                contract.owner = caller;
 
-            Self {
-                total_supply: Lazy::new(initial_supply),
-                balances,
-                allowances: HashMap::new(),
-                name: Lazy::new(name),
-                symbol: Lazy::new(symbol),
-                decimals: Lazy::new(decimals),
-            }
+                Self::env().emit_event(Transfer {
+                    from: None,
+                    to: Some(caller),
+                    value: initial_supply,
+                });
+            })
         }
 
         /// Returns the token name.
         #[ink(message)]
         pub fn name(&self) -> String {
-            (*self.name).clone()
+            self.name.clone()
         }
 
         /// Returns the token symbol.
         #[ink(message)]
         pub fn symbol(&self) -> String {
-            (*self.symbol).clone()
+            self.symbol.clone()
         }
 
         /// Returns the token decimals.
         #[ink(message)]
         pub fn decimals(&self) -> u8 {
-            *self.decimals
+            self.decimals
         }
 
         /// Returns the total token supply.
         #[ink(message)]
         pub fn total_supply(&self) -> Balance {
-            *self.total_supply
+            self.total_supply
         }
 
         /// Returns the account balance for the specified `owner`.
         #[ink(message)]
         pub fn balance_of(&self, owner: AccountId) -> Balance {
-            self.balances.get(&owner).copied().unwrap_or(0)
+            self.balances.get(owner).unwrap_or(0)
         }
 
-        /// Returns the amount which `spender` is allowed to withdraw from `owner`.
+        /// Returns the amount which `spender` is allowed to withdraw from `owner`,
+        /// or zero once the allowance has expired.
         #[ink(message)]
         pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
-            self.allowances.get(&(owner, spender)).copied().unwrap_or(0)
+            match self.allowances.get((owner, spender)) {
+                Some((value, deadline)) if !deadline.is_expired(self.env().block_timestamp()) => {
+                    value
+                }
+                _ => 0,
+            }
+        }
+
+        /// Returns the deadline of the allowance of `spender` over `owner`'s tokens,
+        /// `NO_EXPIRY` if it never expires, or `None` if there is no allowance.
+        #[ink(message)]
+        pub fn allowance_deadline(
+            &self,
+            owner: AccountId,
+            spender: AccountId,
+        ) -> Option<Timestamp> {
+            self.allowances
+                .get((owner, spender))
+                .map(|(_, deadline)| deadline.timestamp())
+        }
+
+        /// Returns the token owner.
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Returns whether restricted mode is on.
+        #[ink(message)]
+        pub fn is_restricted(&self) -> bool {
+            self.restricted
+        }
+
+        /// Returns whether `account` is on the allowlist.
+        #[ink(message)]
+        pub fn is_allowlisted(&self, account: AccountId) -> bool {
+            self.allowlist.get(account).unwrap_or(false)
+        }
+
+        /// Turns restricted mode on or off. Only the owner can call this.
+        #[ink(message)]
+        pub fn set_restricted(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.restricted = enabled;
+            self.env().emit_event(RestrictedModeSet { enabled });
+            Ok(())
+        }
+
+        /// Adds `account` to the allowlist, or removes it if `allowed` is false. Only
+        /// the owner can call this.
+        #[ink(message)]
+        pub fn set_allowlisted(&mut self, account: AccountId, allowed: bool) -> Result<()> {
+            self.ensure_owner()?;
+            if allowed {
+                self.allowlist.insert(account, &true);
+            } else {
+                self.allowlist.remove(account);
+            }
+            self.env().emit_event(AllowlistUpdated { account, allowed });
+            Ok(())
         }
 
         /// Transfers `value` amount of tokens from the caller's account to account `to`.
@@ -141,12 +290,38 @@ mod erc20 {
         #[ink(message)]
         pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
             let owner = self.env().caller();
-            self.allowances.insert((owner, spender), value);
+            self.allowances
+                .insert((owner, spender), &(value, Deadline::at(NO_EXPIRY)));
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Like `approve`, but the allowance can only be spent up to and including
+        /// `deadline`.
+        #[ink(message)]
+        pub fn approve_with_expiry(
+            &mut self,
+            spender: AccountId,
+            value: Balance,
+            deadline: Timestamp,
+        ) -> Result<()> {
+            let owner = self.env().caller();
+            self.allowances
+                .insert((owner, spender), &(value, Deadline::at(deadline)));
             self.env().emit_event(Approval {
                 owner,
                 spender,
                 value,
             });
+            self.env().emit_event(AllowanceExpirySet {
+                owner,
+                spender,
+                deadline,
+            });
             Ok(())
         }
 
@@ -159,30 +334,79 @@ mod erc20 {
             value: Balance,
         ) -> Result<()> {
             let caller = self.env().caller();
-            let allowance = self.allowance(from, caller);
+            let (allowance, deadline) = self
+                .allowances
+                .get((from, caller))
+                .unwrap_or((0, Deadline::at(NO_EXPIRY)));
+            if deadline.is_expired(self.env().block_timestamp()) {
+                return Err(Error::AllowanceExpired);
+            }
             if allowance < value {
                 return Err(Error::InsufficientAllowance);
             }
             self.transfer_from_to(from, to, value)?;
-            self.allowances.insert((from, caller), allowance - value);
+            if from != to {
+                self.allowances
+                    .insert((from, caller), &(allowance - value, deadline));
+            }
+            Ok(())
+        }
+
+        /// Checks the constructor arguments and returns the name and symbol,
+        /// panicking on the first invalid argument.
+        fn validate_new(
+            initial_supply: Balance,
+            name: String,
+            symbol: String,
+            decimals: u8,
+            require_initial_supply: bool,
+        ) -> (String, String) {
+            let name: BoundedString<MAX_NAME_LEN> =
+                validation::require("token name", name.try_into());
+            let symbol: BoundedString<MAX_SYMBOL_LEN> =
+                validation::require("token symbol", symbol.try_into());
+            validation::require("token symbol", validation::non_empty(&symbol));
+            validation::require("decimals", validation::at_most(decimals, MAX_DECIMALS));
+            if require_initial_supply {
+                validation::require("initial supply", validation::non_zero(initial_supply));
+            }
+            (name.into_inner(), symbol.into_inner())
+        }
+
+        /// Returns `NotOwner` unless the caller is the owner.
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
             Ok(())
         }
 
         /// Transfers `value` amount of tokens from the `from` account to the `to` account.
+        /// Does nothing if they are the same account.
         fn transfer_from_to(
             &mut self,
             from: AccountId,
             to: AccountId,
             value: Balance,
         ) -> Result<()> {
+            if to == AccountId::from(ZERO_ADDRESS) {
+                return Err(Error::InvalidRecipient);
+            }
+            if from != to && self.restricted && !self.is_allowlisted(to) {
+                return Err(Error::RecipientNotAllowlisted);
+            }
+
             let from_balance = self.balance_of(from);
             if from_balance < value {
                 return Err(Error::InsufficientBalance);
             }
+            if from == to {
+                return Ok(());
+            }
 
-            self.balances.insert(from, from_balance - value);
+            self.balances.insert(from, &(from_balance - value));
             let to_balance = self.balance_of(to);
-            self.balances.insert(to, to_balance + value);
+            self.balances.insert(to, &(to_balance + value));
 
             self.env().emit_event(Transfer {
                 from: Some(from),
@@ -197,6 +421,8 @@ mod erc20 {
     #[cfg(test)]
     mod tests {
         use super::*;
+        use astranet_test_fixtures as fixtures;
+        use fixtures::BLOCK_TIME;
         use ink_lang as ink;
 
         #[ink::test]
@@ -213,6 +439,67 @@ mod erc20 {
             assert_eq!(contract.decimals(), 18);
         }
 
+        #[ink::test]
+        fn new_accepts_the_longest_name_and_symbol() {
+            let name = "n".repeat(MAX_NAME_LEN);
+            let symbol = "S".repeat(MAX_SYMBOL_LEN);
+            let contract = Erc20::new(100, name.clone(), symbol.clone(), 18);
+            assert_eq!(contract.name(), name);
+            assert_eq!(contract.symbol(), symbol);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "token name is too long")]
+        fn new_rejects_a_long_name() {
+            Erc20::new(100, "n".repeat(MAX_NAME_LEN + 1), String::from("TN"), 18);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "token symbol must not be empty")]
+        fn new_rejects_an_empty_symbol() {
+            Erc20::new(100, String::from("Token Name"), String::new(), 18);
+        }
+
+        #[ink::test]
+        fn new_accepts_max_decimals_and_zero_supply() {
+            let contract = Erc20::new(
+                0,
+                String::from("Token Name"),
+                String::from("TN"),
+                MAX_DECIMALS,
+            );
+            assert_eq!(contract.decimals(), MAX_DECIMALS);
+            assert_eq!(contract.total_supply(), 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "decimals is too large")]
+        fn new_rejects_too_many_decimals() {
+            Erc20::new(
+                100,
+                String::from("Token Name"),
+                String::from("TN"),
+                MAX_DECIMALS + 1,
+            );
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "initial supply must not be zero")]
+        fn zero_supply_is_rejected_when_required() {
+            Erc20::validate_new(0, String::from("Token Name"), String::from("TN"), 18, true);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "token symbol is too long")]
+        fn new_rejects_a_long_symbol() {
+            Erc20::new(
+                100,
+                String::from("Token Name"),
+                "S".repeat(MAX_SYMBOL_LEN + 1),
+                18,
+            );
+        }
+
         #[ink::test]
         fn transfer_works() {
             let mut contract = Erc20::new(
@@ -221,7 +508,7 @@ mod erc20 {
                 String::from("TN"),
                 18,
             );
-            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let accounts = fixtures::accounts();
             
             // Transfer to accounts.bob
             assert_eq!(contract.balance_of(accounts.bob), 0);
@@ -237,13 +524,13 @@ mod erc20 {
                 String::from("TN"),
                 18,
             );
-            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let accounts = fixtures::accounts();
             
             // Approve accounts.bob to spend 20 tokens
             assert_eq!(contract.approve(accounts.bob, 20), Ok(()));
             
             // Transfer from alice to charlie using bob's allowance
-            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            fixtures::set_caller(accounts.bob);
             assert_eq!(contract.transfer_from(accounts.alice, accounts.charlie, 10), Ok(()));
             assert_eq!(contract.balance_of(accounts.charlie), 10);
         }
@@ -256,12 +543,223 @@ mod erc20 {
                 String::from("TN"),
                 18,
             );
-            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let accounts = fixtures::accounts();
             
             // Try to transfer more than the total supply
             assert_eq!(contract.transfer(accounts.bob, 101), Err(Error::InsufficientBalance));
             assert_eq!(contract.balance_of(accounts.bob), 0);
         }
+
+        #[ink::test]
+        fn transfers_to_the_zero_address_are_rejected() {
+            let accounts = fixtures::accounts();
+            let mut contract = Erc20::new(
+                100,
+                String::from("Token Name"),
+                String::from("TN"),
+                18,
+            );
+            let zero = AccountId::from(ZERO_ADDRESS);
+            assert_eq!(contract.transfer(zero, 10), Err(Error::InvalidRecipient));
+            assert_eq!(contract.transfer(zero, 0), Err(Error::InvalidRecipient));
+
+            assert_eq!(contract.approve(accounts.bob, 10), Ok(()));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                contract.transfer_from(accounts.alice, zero, 10),
+                Err(Error::InvalidRecipient)
+            );
+            assert_eq!(contract.allowance(accounts.alice, accounts.bob), 10);
+            assert_eq!(contract.balance_of(accounts.alice), 100);
+            assert_eq!(contract.balance_of(zero), 0);
+        }
+
+        #[ink::test]
+        fn self_transfers_are_no_ops() {
+            let accounts = fixtures::accounts();
+            let mut contract = Erc20::new(
+                100,
+                String::from("Token Name"),
+                String::from("TN"),
+                18,
+            );
+            let events = ink_env::test::recorded_events().count();
+            assert_eq!(contract.transfer(accounts.alice, 40), Ok(()));
+            assert_eq!(contract.balance_of(accounts.alice), 100);
+            assert_eq!(ink_env::test::recorded_events().count(), events);
+            assert_eq!(
+                contract.transfer(accounts.alice, 101),
+                Err(Error::InsufficientBalance)
+            );
+
+            assert_eq!(contract.approve(accounts.bob, 50), Ok(()));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                contract.transfer_from(accounts.alice, accounts.alice, 40),
+                Ok(())
+            );
+            assert_eq!(contract.allowance(accounts.alice, accounts.bob), 50);
+            assert_eq!(
+                contract.transfer_from(accounts.alice, accounts.alice, 60),
+                Err(Error::InsufficientAllowance)
+            );
+            assert_eq!(contract.balance_of(accounts.alice), 100);
+        }
+
+        #[ink::test]
+        fn unrestricted_by_default() {
+            let mut contract = Erc20::new(
+                100,
+                String::from("Token Name"),
+                String::from("TN"),
+                18,
+            );
+            let accounts = fixtures::accounts();
+
+            assert!(!contract.is_restricted());
+            assert!(!contract.is_allowlisted(accounts.bob));
+            assert_eq!(contract.transfer(accounts.bob, 10), Ok(()));
+        }
+
+        #[ink::test]
+        fn restricted_mode_only_pays_allowlisted_accounts() {
+            let mut contract = Erc20::new(
+                100,
+                String::from("Token Name"),
+                String::from("TN"),
+                18,
+            );
+            let accounts = fixtures::accounts();
+            assert_eq!(contract.set_restricted(true), Ok(()));
+            assert_eq!(contract.set_allowlisted(accounts.bob, true), Ok(()));
+
+            assert_eq!(contract.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(
+                contract.transfer(accounts.charlie, 10),
+                Err(Error::RecipientNotAllowlisted)
+            );
+            assert_eq!(contract.balance_of(accounts.charlie), 0);
+
+            // Allowances do not get around the allowlist.
+            assert_eq!(contract.approve(accounts.bob, 20), Ok(()));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                contract.transfer_from(accounts.alice, accounts.charlie, 10),
+                Err(Error::RecipientNotAllowlisted)
+            );
+            assert_eq!(contract.allowance(accounts.alice, accounts.bob), 20);
+
+            // Only receiving is restricted: bob, who is not the owner, can still pay
+            // another allowlisted account.
+            fixtures::with_caller(accounts.alice, || {
+                contract.set_allowlisted(accounts.dave, true)
+            })
+            .unwrap();
+            assert_eq!(contract.transfer(accounts.dave, 5), Ok(()));
+            assert_eq!(contract.balance_of(accounts.dave), 5);
+        }
+
+        #[ink::test]
+        fn removed_accounts_stop_receiving() {
+            let mut contract = Erc20::new(
+                100,
+                String::from("Token Name"),
+                String::from("TN"),
+                18,
+            );
+            let accounts = fixtures::accounts();
+            assert_eq!(contract.set_restricted(true), Ok(()));
+            assert_eq!(contract.set_allowlisted(accounts.bob, true), Ok(()));
+            assert_eq!(contract.set_allowlisted(accounts.bob, false), Ok(()));
+            assert!(!contract.is_allowlisted(accounts.bob));
+            assert_eq!(
+                contract.transfer(accounts.bob, 10),
+                Err(Error::RecipientNotAllowlisted)
+            );
+
+            assert_eq!(contract.set_restricted(false), Ok(()));
+            assert_eq!(contract.transfer(accounts.bob, 10), Ok(()));
+        }
+
+        #[ink::test]
+        fn expiring_allowance_is_spendable_until_deadline() {
+            let mut contract = Erc20::new(
+                100,
+                String::from("Token Name"),
+                String::from("TN"),
+                18,
+            );
+            let accounts = fixtures::accounts();
+            let deadline = 20 * BLOCK_TIME;
+            fixtures::set_block_timestamp(10 * BLOCK_TIME);
+            assert_eq!(contract.approve_with_expiry(accounts.bob, 20, deadline), Ok(()));
+            assert_eq!(
+                contract.allowance_deadline(accounts.alice, accounts.bob),
+                Some(deadline)
+            );
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(contract.transfer_from(accounts.alice, accounts.charlie, 5), Ok(()));
+            // The deadline itself is still in time, and keeps applying to the rest.
+            fixtures::set_block_timestamp(deadline);
+            assert_eq!(contract.transfer_from(accounts.alice, accounts.charlie, 5), Ok(()));
+            assert_eq!(contract.allowance(accounts.alice, accounts.bob), 10);
+            assert_eq!(
+                contract.allowance_deadline(accounts.alice, accounts.bob),
+                Some(deadline)
+            );
+
+            fixtures::set_block_timestamp(deadline + BLOCK_TIME);
+            assert_eq!(
+                contract.transfer_from(accounts.alice, accounts.charlie, 5),
+                Err(Error::AllowanceExpired)
+            );
+            assert_eq!(contract.allowance(accounts.alice, accounts.bob), 0);
+            assert_eq!(contract.balance_of(accounts.charlie), 10);
+        }
+
+        #[ink::test]
+        fn plain_approval_replaces_expiry() {
+            let mut contract = Erc20::new(
+                100,
+                String::from("Token Name"),
+                String::from("TN"),
+                18,
+            );
+            let accounts = fixtures::accounts();
+            assert_eq!(contract.approve_with_expiry(accounts.bob, 20, 0), Ok(()));
+            fixtures::set_block_timestamp(BLOCK_TIME);
+            assert_eq!(contract.allowance(accounts.alice, accounts.bob), 0);
+
+            assert_eq!(contract.approve(accounts.bob, 20), Ok(()));
+            assert_eq!(
+                contract.allowance_deadline(accounts.alice, accounts.bob),
+                Some(NO_EXPIRY)
+            );
+            fixtures::set_block_timestamp(1_000 * BLOCK_TIME);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(contract.transfer_from(accounts.alice, accounts.charlie, 20), Ok(()));
+        }
+
+        #[ink::test]
+        fn only_owner_manages_restrictions() {
+            let mut contract = Erc20::new(
+                100,
+                String::from("Token Name"),
+                String::from("TN"),
+                18,
+            );
+            let accounts = fixtures::accounts();
+            assert_eq!(contract.owner(), accounts.alice);
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(contract.set_restricted(true), Err(Error::NotOwner));
+            assert_eq!(
+                contract.set_allowlisted(accounts.bob, true),
+                Err(Error::NotOwner)
+            );
+            assert!(!contract.is_restricted());
+        }
     }
 }
 
@@ -284,6 +782,9 @@ mod erc20 {
 //
 // 4. Interact with the contract:
 //    - Use the "transfer" method to send tokens
-//    - Use the "approve" method to allow others to spend your tokens
+//    - Use the "approve" method to allow others to spend your tokens, or
+//      "approveWithExpiry" for an allowance that ends at a deadline
 //    - Use the "transferFrom" method to spend approved tokens
-//    - Use the "balanceOf" method to check account balances
\ No newline at end of file
+//    - Use the "balanceOf" method to check account balances
+//    - As the owner, use "setAllowlisted" and "setRestricted" to limit who can
+//      receive tokens
\ No newline at end of file
@@ -0,0 +1,554 @@
+// examples/demo-contracts/erc721.rs
+//
+// An ERC-721 non-fungible token contract example for Polkadot using ink!
+// Every token has an id and exactly one owner. The minter creates tokens, owners
+// transfer them, and owners can let another account move a single token
+// (`approve`) or all of their tokens (`set_approval_for_all`).
+//
+// Time-bounded operators
+// ----------------------
+// A game that moves a player's items during a match needs operator rights, but a
+// player should not have to remember to take them back afterwards. With
+// `approve_until(operator, expiry)` the operator can act on all of the caller's
+// tokens up to and including `expiry` (the approval is stored as the utils crate's
+// inclusive `Deadline`), after which it simply stops counting; no transaction is
+// needed to end it. `set_approval_for_all(operator,
+// true)` is the same approval without an end, and `set_approval_for_all(operator,
+// false)` revokes either kind early.
+//
+// Token URIs
+// ----------
+// The minter can point each token at its metadata with `set_token_uri`. URIs are
+// checked with the utils crate's `BoundedString`, so one longer than
+// `MAX_URI_LEN` bytes is rejected with `Error::LimitExceeded` instead of making
+// the token expensive to store and read. Burning a token removes its URI.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod erc721 {
+    use astranet_utils::{
+        bounded::{BoundedError, BoundedString},
+        deadline::Deadline,
+    };
+    use ink_prelude::string::String;
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// Identifies a token.
+    pub type TokenId = u32;
+
+    /// Expiry of an operator approval that never ends.
+    pub const NO_EXPIRY: Timestamp = Timestamp::MAX;
+
+    /// Longest token URI, in bytes.
+    pub const MAX_URI_LEN: usize = 128;
+
+    /// The ERC-721 error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the minter.
+        NotMinter,
+        /// Returned if the caller may not move or approve the token.
+        NotApproved,
+        /// Returned if a token with the id already exists.
+        TokenExists,
+        /// Returned if there is no token with the id.
+        TokenNotFound,
+        /// Returned if `from` does not own the token.
+        NotOwner,
+        /// Returned if an account tries to approve itself.
+        SelfApproval,
+        /// Returned if the expiry of an approval has already passed.
+        ExpiryInPast,
+        /// Returned if a token URI is longer than `MAX_URI_LEN`.
+        LimitExceeded,
+    }
+
+    impl From<BoundedError> for Error {
+        fn from(error: BoundedError) -> Self {
+            match error {
+                BoundedError::LimitExceeded => Error::LimitExceeded,
+            }
+        }
+    }
+
+    /// The ERC-721 result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    // Events are mirrored in `crates/events/src/erc721.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when a token is minted, transferred or burned.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        #[ink(topic)]
+        id: TokenId,
+    }
+
+    /// Event emitted when an account is approved to move a single token.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        approved: AccountId,
+        #[ink(topic)]
+        id: TokenId,
+    }
+
+    /// Event emitted when an operator is approved or revoked without an expiry.
+    #[ink(event)]
+    pub struct ApprovalForAll {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        approved: bool,
+    }
+
+    /// Event emitted when an operator is approved until a point in time.
+    #[ink(event)]
+    pub struct OperatorApprovedUntil {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        expiry: Timestamp,
+    }
+
+    /// The ERC-721 storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Erc721 {
+        /// Account that mints tokens.
+        minter: AccountId,
+        /// Mapping from token to owner.
+        token_owner: Mapping<TokenId, AccountId>,
+        /// Mapping from token to the account approved to move it.
+        token_approvals: Mapping<TokenId, AccountId>,
+        /// Mapping from owner to number of tokens owned.
+        owned_tokens_count: Mapping<AccountId, u32>,
+        /// Mapping from owner and operator to the last time the approval counts.
+        operator_approvals: Mapping<(AccountId, AccountId), Deadline>,
+        /// Mapping from token to its metadata URI.
+        token_uris: Mapping<TokenId, String>,
+    }
+
+    impl Erc721 {
+        /// Creates an empty collection with the caller as minter.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.minter = Self::env().caller();
+            })
+        }
+
+        /// Returns the number of tokens `owner` holds.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> u32 {
+            self.owned_tokens_count.get(owner).unwrap_or(0)
+        }
+
+        /// Returns the owner of token `id`, if it exists.
+        #[ink(message)]
+        pub fn owner_of(&self, id: TokenId) -> Option<AccountId> {
+            self.token_owner.get(id)
+        }
+
+        /// Returns the account approved to move token `id`, if any.
+        #[ink(message)]
+        pub fn get_approved(&self, id: TokenId) -> Option<AccountId> {
+            self.token_approvals.get(id)
+        }
+
+        /// Returns whether `operator` may currently act on all of `owner`'s tokens.
+        #[ink(message)]
+        pub fn is_approved_for_all(&self, owner: AccountId, operator: AccountId) -> bool {
+            self.operator_approvals
+                .get((owner, operator))
+                .is_some_and(|expiry| !expiry.is_expired(self.env().block_timestamp()))
+        }
+
+        /// Returns the last time at which `operator`'s approval for `owner` counts,
+        /// `NO_EXPIRY` if it never ends, or `None` if there is none. An expired
+        /// approval is still returned until it is replaced or revoked.
+        #[ink(message)]
+        pub fn operator_expiry(&self, owner: AccountId, operator: AccountId) -> Option<Timestamp> {
+            self.operator_approvals
+                .get((owner, operator))
+                .map(|expiry| expiry.timestamp())
+        }
+
+        /// Returns the metadata URI of token `id`, if one was set.
+        #[ink(message)]
+        pub fn token_uri(&self, id: TokenId) -> Option<String> {
+            self.token_uris.get(id)
+        }
+
+        /// Sets the metadata URI of token `id`. Only the minter can call this.
+        #[ink(message)]
+        pub fn set_token_uri(&mut self, id: TokenId, uri: String) -> Result<()> {
+            if self.env().caller() != self.minter {
+                return Err(Error::NotMinter);
+            }
+            if !self.token_owner.contains(id) {
+                return Err(Error::TokenNotFound);
+            }
+            let uri = BoundedString::<MAX_URI_LEN>::try_from(uri)?;
+            self.token_uris.insert(id, &uri.into_inner());
+            Ok(())
+        }
+
+        /// Mints token `id` to `to`. Only the minter can call this.
+        #[ink(message)]
+        pub fn mint(&mut self, to: AccountId, id: TokenId) -> Result<()> {
+            if self.env().caller() != self.minter {
+                return Err(Error::NotMinter);
+            }
+            if self.token_owner.contains(id) {
+                return Err(Error::TokenExists);
+            }
+            self.add_token_to(to, id);
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(to),
+                id,
+            });
+            Ok(())
+        }
+
+        /// Burns token `id`. Only its owner can call this.
+        #[ink(message)]
+        pub fn burn(&mut self, id: TokenId) -> Result<()> {
+            let caller = self.env().caller();
+            let owner = self.owner_of(id).ok_or(Error::TokenNotFound)?;
+            if owner != caller {
+                return Err(Error::NotOwner);
+            }
+            self.remove_token_from(owner, id);
+            self.token_uris.remove(id);
+            self.env().emit_event(Transfer {
+                from: Some(owner),
+                to: None,
+                id,
+            });
+            Ok(())
+        }
+
+        /// Approves `to` to move token `id`. The owner of the token or one of
+        /// their operators can call this.
+        #[ink(message)]
+        pub fn approve(&mut self, to: AccountId, id: TokenId) -> Result<()> {
+            let caller = self.env().caller();
+            let owner = self.owner_of(id).ok_or(Error::TokenNotFound)?;
+            if caller != owner && !self.is_approved_for_all(owner, caller) {
+                return Err(Error::NotApproved);
+            }
+            if to == owner {
+                return Err(Error::SelfApproval);
+            }
+            self.token_approvals.insert(id, &to);
+            self.env().emit_event(Approval {
+                owner,
+                approved: to,
+                id,
+            });
+            Ok(())
+        }
+
+        /// Approves `operator` to act on all of the caller's tokens without an
+        /// expiry, or revokes any approval of `operator` if `approved` is false.
+        #[ink(message)]
+        pub fn set_approval_for_all(&mut self, operator: AccountId, approved: bool) -> Result<()> {
+            let owner = self.env().caller();
+            if operator == owner {
+                return Err(Error::SelfApproval);
+            }
+            if approved {
+                self.operator_approvals
+                    .insert((owner, operator), &Deadline::at(NO_EXPIRY));
+            } else {
+                self.operator_approvals.remove((owner, operator));
+            }
+            self.env().emit_event(ApprovalForAll {
+                owner,
+                operator,
+                approved,
+            });
+            Ok(())
+        }
+
+        /// Approves `operator` to act on all of the caller's tokens up to and
+        /// including `expiry`, replacing any earlier approval of `operator`.
+        #[ink(message)]
+        pub fn approve_until(&mut self, operator: AccountId, expiry: Timestamp) -> Result<()> {
+            let owner = self.env().caller();
+            if operator == owner {
+                return Err(Error::SelfApproval);
+            }
+            let deadline = Deadline::at(expiry);
+            if deadline.is_expired(self.env().block_timestamp()) {
+                return Err(Error::ExpiryInPast);
+            }
+            self.operator_approvals.insert((owner, operator), &deadline);
+            self.env().emit_event(OperatorApprovedUntil {
+                owner,
+                operator,
+                expiry,
+            });
+            Ok(())
+        }
+
+        /// Transfers token `id` from the caller to `to`.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, id: TokenId) -> Result<()> {
+            let caller = self.env().caller();
+            self.transfer_token_from(caller, to, id)
+        }
+
+        /// Transfers token `id` from `from` to `to`. The owner, the account
+        /// approved for the token, or a current operator of the owner can call
+        /// this.
+        #[ink(message)]
+        pub fn transfer_from(&mut self, from: AccountId, to: AccountId, id: TokenId) -> Result<()> {
+            self.transfer_token_from(from, to, id)
+        }
+
+        /// Moves token `id` from `from` to `to` if the caller may move it.
+        fn transfer_token_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            id: TokenId,
+        ) -> Result<()> {
+            let caller = self.env().caller();
+            let owner = self.owner_of(id).ok_or(Error::TokenNotFound)?;
+            if owner != from {
+                return Err(Error::NotOwner);
+            }
+            if !self.approved_or_owner(caller, owner, id) {
+                return Err(Error::NotApproved);
+            }
+            self.remove_token_from(from, id);
+            self.add_token_to(to, id);
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                id,
+            });
+            Ok(())
+        }
+
+        /// Returns whether `caller` may move `owner`'s token `id`.
+        fn approved_or_owner(&self, caller: AccountId, owner: AccountId, id: TokenId) -> bool {
+            caller == owner
+                || self.get_approved(id) == Some(caller)
+                || self.is_approved_for_all(owner, caller)
+        }
+
+        /// Removes token `id` from `owner`, along with its approval.
+        fn remove_token_from(&mut self, owner: AccountId, id: TokenId) {
+            self.token_owner.remove(id);
+            self.token_approvals.remove(id);
+            let count = self.balance_of(owner);
+            self.owned_tokens_count.insert(owner, &(count - 1));
+        }
+
+        /// Gives token `id` to `to`.
+        fn add_token_to(&mut self, to: AccountId, id: TokenId) {
+            self.token_owner.insert(id, &to);
+            let count = self.balance_of(to);
+            self.owned_tokens_count.insert(to, &(count + 1));
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use fixtures::BLOCK_TIME;
+        use ink_lang as ink;
+
+        /// A collection where alice holds tokens 1 and 2.
+        fn collection() -> Erc721 {
+            let accounts = fixtures::accounts();
+            let mut contract = Erc721::new();
+            assert_eq!(contract.mint(accounts.alice, 1), Ok(()));
+            assert_eq!(contract.mint(accounts.alice, 2), Ok(()));
+            contract
+        }
+
+        #[ink::test]
+        fn token_uris_are_bounded() {
+            let accounts = fixtures::accounts();
+            let mut contract = collection();
+            let longest = "u".repeat(MAX_URI_LEN);
+            assert_eq!(contract.set_token_uri(1, longest.clone()), Ok(()));
+            assert_eq!(contract.token_uri(1), Some(longest.clone()));
+            assert_eq!(
+                contract.set_token_uri(2, longest + "u"),
+                Err(Error::LimitExceeded)
+            );
+            assert_eq!(contract.token_uri(2), None);
+            assert_eq!(
+                contract.set_token_uri(3, String::from("ipfs://3")),
+                Err(Error::TokenNotFound)
+            );
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                contract.set_token_uri(1, String::from("ipfs://1")),
+                Err(Error::NotMinter)
+            );
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(contract.burn(1), Ok(()));
+            assert_eq!(contract.token_uri(1), None);
+        }
+
+        #[ink::test]
+        fn mint_and_transfer_work() {
+            let accounts = fixtures::accounts();
+            let mut contract = collection();
+            assert_eq!(contract.balance_of(accounts.alice), 2);
+            assert_eq!(contract.mint(accounts.bob, 1), Err(Error::TokenExists));
+
+            assert_eq!(contract.transfer(accounts.bob, 1), Ok(()));
+            assert_eq!(contract.owner_of(1), Some(accounts.bob));
+            assert_eq!(contract.balance_of(accounts.alice), 1);
+            assert_eq!(contract.balance_of(accounts.bob), 1);
+            assert_eq!(contract.transfer(accounts.bob, 1), Err(Error::NotOwner));
+            assert_eq!(
+                contract.transfer(accounts.bob, 9),
+                Err(Error::TokenNotFound)
+            );
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(contract.mint(accounts.bob, 3), Err(Error::NotMinter));
+            assert_eq!(contract.burn(1), Ok(()));
+            assert_eq!(contract.owner_of(1), None);
+            assert_eq!(contract.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn single_token_approval_is_cleared_on_transfer() {
+            let accounts = fixtures::accounts();
+            let mut contract = collection();
+            assert_eq!(contract.approve(accounts.bob, 1), Ok(()));
+            assert_eq!(
+                contract.approve(accounts.alice, 1),
+                Err(Error::SelfApproval)
+            );
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                contract.transfer_from(accounts.alice, accounts.charlie, 2),
+                Err(Error::NotApproved)
+            );
+            assert_eq!(
+                contract.transfer_from(accounts.alice, accounts.charlie, 1),
+                Ok(())
+            );
+            assert_eq!(contract.get_approved(1), None);
+        }
+
+        #[ink::test]
+        fn timed_operator_expires() {
+            let accounts = fixtures::accounts();
+            let mut contract = collection();
+            let expiry = 20 * BLOCK_TIME;
+            fixtures::set_block_timestamp(10 * BLOCK_TIME);
+            assert_eq!(contract.approve_until(accounts.bob, expiry), Ok(()));
+            assert!(contract.is_approved_for_all(accounts.alice, accounts.bob));
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                contract.transfer_from(accounts.alice, accounts.bob, 1),
+                Ok(())
+            );
+            // The expiry itself is still within the approval.
+            fixtures::set_block_timestamp(expiry);
+            assert_eq!(contract.approve(accounts.charlie, 2), Ok(()));
+
+            fixtures::set_block_timestamp(expiry + BLOCK_TIME);
+            assert!(!contract.is_approved_for_all(accounts.alice, accounts.bob));
+            assert_eq!(
+                contract.operator_expiry(accounts.alice, accounts.bob),
+                Some(expiry)
+            );
+            assert_eq!(
+                contract.transfer_from(accounts.alice, accounts.bob, 2),
+                Err(Error::NotApproved)
+            );
+            assert_eq!(contract.approve(accounts.dave, 2), Err(Error::NotApproved));
+        }
+
+        #[ink::test]
+        fn timed_operator_can_be_extended_or_revoked() {
+            let accounts = fixtures::accounts();
+            let mut contract = collection();
+            fixtures::set_block_timestamp(10 * BLOCK_TIME);
+            assert_eq!(
+                contract.approve_until(accounts.bob, 10 * BLOCK_TIME - 1),
+                Err(Error::ExpiryInPast)
+            );
+            assert_eq!(
+                contract.approve_until(accounts.alice, 20 * BLOCK_TIME),
+                Err(Error::SelfApproval)
+            );
+
+            assert_eq!(contract.approve_until(accounts.bob, 20 * BLOCK_TIME), Ok(()));
+            assert_eq!(contract.approve_until(accounts.bob, 50 * BLOCK_TIME), Ok(()));
+            fixtures::set_block_timestamp(30 * BLOCK_TIME);
+            assert!(contract.is_approved_for_all(accounts.alice, accounts.bob));
+
+            assert_eq!(contract.set_approval_for_all(accounts.bob, false), Ok(()));
+            assert!(!contract.is_approved_for_all(accounts.alice, accounts.bob));
+            assert_eq!(contract.operator_expiry(accounts.alice, accounts.bob), None);
+        }
+
+        #[ink::test]
+        fn unbounded_operator_never_expires() {
+            let accounts = fixtures::accounts();
+            let mut contract = collection();
+            assert_eq!(contract.set_approval_for_all(accounts.bob, true), Ok(()));
+            assert_eq!(
+                contract.operator_expiry(accounts.alice, accounts.bob),
+                Some(NO_EXPIRY)
+            );
+            fixtures::set_block_timestamp(1_000 * BLOCK_TIME);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                contract.transfer_from(accounts.alice, accounts.charlie, 2),
+                Ok(())
+            );
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Upload the generated .contract file and deploy it
+//
+// 4. Interact with the contract:
+//    - As minter, use "mint"; owners use "transfer" and "burn"
+//    - As minter, use "setTokenUri" to attach metadata to a token
+//    - Use "approve" for a single token and "setApprovalForAll" for all of them
+//    - Use "approveUntil" to give a game or marketplace operator rights that end
+//      on their own
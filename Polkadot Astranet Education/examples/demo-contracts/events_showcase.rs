@@ -0,0 +1,400 @@
+// examples/demo-contracts/events_showcase.rs
+//
+// An event design contract example for Polkadot using ink!
+// The messages of this contract do very little; each one exists to emit an event
+// that shows one decision a contract author makes when designing events: which
+// fields become topics, when an event should be anonymous, and how to change an
+// event's payload without breaking the indexers that already decode it.
+//
+// Topics
+// ------
+// An event has two parts. Its data is the SCALE encoding of the whole event and
+// can only be read by decoding it. Its topics are up to four 32-byte values that
+// the chain indexes, so a front-end can ask a node for "every `Registered` event
+// of cohort 3" without downloading and decoding the rest. Fields marked
+// `#[ink(topic)]` become topics; every field, topic or not, is also in the data.
+//
+// ink! 3 does not store a topic field's value as-is. It SCALE-encodes the field's
+// path, such as "EventsShowcase::Registered::learner", followed by the value, and
+// uses that if it fits in 32 bytes or its Blake2x256 hash otherwise. Since paths
+// are long, topics are in practice hashes: they can be matched against a known
+// value but not read back. So make a field a topic when callers will filter by it
+// (accounts, ids, categories such as `cohort`), and leave free text, amounts and
+// timestamps in the data, as `NotePosted` does with its note. Each topic costs
+// extra gas and storage in every event, which is why they are capped.
+//
+// Anonymous events
+// ----------------
+// A normal event also gets one topic of its own, the event's signature: here a
+// zero byte followed by "EventsShowcase::Registered", which is short enough to be
+// stored padded rather than hashed. It lets indexers filter by event type. An event
+// declared with `#[ink(event, anonymous)]`, like `Pinged`, leaves that topic out,
+// which saves gas and frees a topic slot, but can then only be found through its
+// field topics. Use it for frequent, low-value events whose emitter is enough to
+// tell them apart.
+//
+// Versioned payloads
+// ------------------
+// Off-chain code decodes event data with types mirrored from the contract (see
+// `crates/events/src/events_showcase.rs`), so adding a field to an event breaks
+// every decoder of the old layout. `ScoreRecorded` avoids this by carrying a
+// `ScorePayload` enum with one variant per version. Version 1 of this contract
+// emitted `ScorePayload::V1 { score }`; this version emits `V2`, which adds the
+// maximum score and the attempt number. Decoders handle both variants, so
+// historical events still decode, and a later change becomes a new `V3` variant
+// appended at the end; variants are never edited or reordered.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod events_showcase {
+    use astranet_utils::bounded::{BoundedError, BoundedString};
+    use ink_prelude::string::String;
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// Longest note, in bytes.
+    pub const MAX_NOTE_LEN: usize = 140;
+
+    /// The events showcase error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is already registered.
+        AlreadyRegistered,
+        /// Returned if the caller is not registered.
+        NotRegistered,
+        /// Returned if a note is longer than `MAX_NOTE_LEN`.
+        LimitExceeded,
+        /// Returned if a score is above the maximum score.
+        ScoreAboveMax,
+        /// Returned if the learner has used every attempt.
+        NoAttemptsLeft,
+    }
+
+    impl From<BoundedError> for Error {
+        fn from(error: BoundedError) -> Self {
+            match error {
+                BoundedError::LimitExceeded => Error::LimitExceeded,
+            }
+        }
+    }
+
+    /// The events showcase result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A recorded score, one variant per version of the event's payload.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum ScorePayload {
+        /// Emitted by version 1: the score alone.
+        V1 { score: u32 },
+        /// Emitted since version 2: the score out of `max_score`, on the
+        /// learner's `attempt`-th try.
+        V2 {
+            score: u32,
+            max_score: u32,
+            attempt: u8,
+        },
+    }
+
+    // Events are mirrored in `crates/events/src/events_showcase.rs` for off-chain
+    // decoding; keep the order of events and fields in sync with it.
+
+    /// Event emitted when a learner joins a cohort. Both fields callers filter by
+    /// are topics; the block number is only data.
+    #[ink(event)]
+    pub struct Registered {
+        #[ink(topic)]
+        learner: AccountId,
+        #[ink(topic)]
+        cohort: u32,
+        registered_at: BlockNumber,
+    }
+
+    /// Event emitted when a learner posts a note. The note is free text, so it is
+    /// data only.
+    #[ink(event)]
+    pub struct NotePosted {
+        #[ink(topic)]
+        author: AccountId,
+        note: String,
+    }
+
+    /// Event emitted on every ping. Anonymous, so it has no signature topic.
+    #[ink(event, anonymous)]
+    pub struct Pinged {
+        #[ink(topic)]
+        from: AccountId,
+        count: u64,
+    }
+
+    /// Event emitted when a score is recorded, with a versioned payload.
+    #[ink(event)]
+    pub struct ScoreRecorded {
+        #[ink(topic)]
+        learner: AccountId,
+        payload: ScorePayload,
+    }
+
+    /// The events showcase storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct EventsShowcase {
+        /// Mapping from learner to their cohort.
+        cohorts: Mapping<AccountId, u32>,
+        /// Mapping from learner to the number of scores recorded for them.
+        attempts: Mapping<AccountId, u8>,
+        /// Number of pings so far.
+        pings: u64,
+    }
+
+    impl EventsShowcase {
+        /// Creates an empty showcase.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|_: &mut Self| {})
+        }
+
+        /// Returns the cohort of `learner`, if registered.
+        #[ink(message)]
+        pub fn cohort_of(&self, learner: AccountId) -> Option<u32> {
+            self.cohorts.get(learner)
+        }
+
+        /// Returns the number of scores recorded for `learner`.
+        #[ink(message)]
+        pub fn attempts_of(&self, learner: AccountId) -> u8 {
+            self.attempts.get(learner).unwrap_or(0)
+        }
+
+        /// Returns the number of pings so far.
+        #[ink(message)]
+        pub fn pings(&self) -> u64 {
+            self.pings
+        }
+
+        /// Registers the caller in `cohort`.
+        #[ink(message)]
+        pub fn register(&mut self, cohort: u32) -> Result<()> {
+            let learner = self.env().caller();
+            if self.cohorts.contains(learner) {
+                return Err(Error::AlreadyRegistered);
+            }
+            self.cohorts.insert(learner, &cohort);
+            self.env().emit_event(Registered {
+                learner,
+                cohort,
+                registered_at: self.env().block_number(),
+            });
+            Ok(())
+        }
+
+        /// Posts `note` as the caller, who must be registered.
+        #[ink(message)]
+        pub fn post_note(&mut self, note: String) -> Result<()> {
+            let author = self.env().caller();
+            if !self.cohorts.contains(author) {
+                return Err(Error::NotRegistered);
+            }
+            let note = BoundedString::<MAX_NOTE_LEN>::try_from(note)?;
+            self.env().emit_event(NotePosted {
+                author,
+                note: note.into_inner(),
+            });
+            Ok(())
+        }
+
+        /// Counts a ping from the caller.
+        #[ink(message)]
+        pub fn ping(&mut self) {
+            self.pings += 1;
+            self.env().emit_event(Pinged {
+                from: self.env().caller(),
+                count: self.pings,
+            });
+        }
+
+        /// Records the caller's `score` out of `max_score` as their next attempt.
+        /// The caller must be registered.
+        #[ink(message)]
+        pub fn record_score(&mut self, score: u32, max_score: u32) -> Result<()> {
+            let learner = self.env().caller();
+            if !self.cohorts.contains(learner) {
+                return Err(Error::NotRegistered);
+            }
+            if score > max_score {
+                return Err(Error::ScoreAboveMax);
+            }
+            let attempt = self
+                .attempts_of(learner)
+                .checked_add(1)
+                .ok_or(Error::NoAttemptsLeft)?;
+            self.attempts.insert(learner, &attempt);
+            self.env().emit_event(ScoreRecorded {
+                learner,
+                payload: ScorePayload::V2 {
+                    score,
+                    max_score,
+                    attempt,
+                },
+            });
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_env::hash::{Blake2x256, CryptoHash, HashOutput};
+        use ink_lang as ink;
+        use scale::{Decode, Encode};
+
+        /// Computes a topic the way ink! 3 does: `prefix` and `value` encoded, then
+        /// padded if they fit in 32 bytes and hashed otherwise.
+        fn topic(prefix: &[u8], value: &impl Encode) -> Vec<u8> {
+            let mut encoded = prefix.encode();
+            value.encode_to(&mut encoded);
+            if encoded.len() <= 32 {
+                encoded.resize(32, 0);
+                return encoded;
+            }
+            let mut hash = <Blake2x256 as HashOutput>::Type::default();
+            <Blake2x256 as CryptoHash>::hash(&encoded, &mut hash);
+            hash.to_vec()
+        }
+
+        /// The signature topic of event `name`: a zero byte, then its path.
+        fn signature(name: &str) -> Vec<u8> {
+            let mut encoded = vec![0];
+            encoded.extend_from_slice(format!("EventsShowcase::{}", name).as_bytes());
+            encoded.resize(32, 0);
+            encoded
+        }
+
+        fn last_event() -> ink_env::test::EmittedEvent {
+            ink_env::test::recorded_events().last().unwrap()
+        }
+
+        fn registered(cohort: u32) -> EventsShowcase {
+            let mut showcase = EventsShowcase::new();
+            assert_eq!(showcase.register(cohort), Ok(()));
+            showcase
+        }
+
+        #[ink::test]
+        fn registered_has_a_signature_and_two_field_topics() {
+            let accounts = fixtures::accounts();
+            let mut showcase = registered(3);
+            assert_eq!(showcase.register(4), Err(Error::AlreadyRegistered));
+            assert_eq!(showcase.cohort_of(accounts.alice), Some(3));
+
+            let event = last_event();
+            assert_eq!(
+                event.topics,
+                [
+                    signature("Registered"),
+                    topic(b"EventsShowcase::Registered::learner", &accounts.alice),
+                    topic(b"EventsShowcase::Registered::cohort", &3u32),
+                ]
+            );
+            // Data is the event index, then every field, topics included.
+            assert_eq!(event.data[0], 0);
+            assert_eq!(event.data.len(), 1 + 32 + 4 + 4);
+        }
+
+        #[ink::test]
+        fn notes_stay_out_of_the_topics() {
+            let accounts = fixtures::accounts();
+            let mut showcase = EventsShowcase::new();
+            let note = String::from("events are cheaper than storage");
+            assert_eq!(showcase.post_note(note.clone()), Err(Error::NotRegistered));
+            assert_eq!(showcase.register(1), Ok(()));
+            assert_eq!(showcase.post_note(note.clone()), Ok(()));
+            assert_eq!(
+                showcase.post_note("n".repeat(MAX_NOTE_LEN + 1)),
+                Err(Error::LimitExceeded)
+            );
+
+            let event = last_event();
+            assert_eq!(
+                event.topics,
+                [
+                    signature("NotePosted"),
+                    topic(b"EventsShowcase::NotePosted::author", &accounts.alice),
+                ]
+            );
+            assert_eq!(event.data[0], 1);
+            assert!(event.data.ends_with(&note.encode()));
+        }
+
+        #[ink::test]
+        fn anonymous_events_have_only_field_topics() {
+            let accounts = fixtures::accounts();
+            let mut showcase = EventsShowcase::new();
+            showcase.ping();
+            showcase.ping();
+            assert_eq!(showcase.pings(), 2);
+
+            let event = last_event();
+            assert_eq!(
+                event.topics,
+                [topic(b"EventsShowcase::Pinged::from", &accounts.alice)]
+            );
+            assert_eq!(event.data[0], 2);
+            assert_eq!(&event.data[33..], &2u64.encode()[..]);
+        }
+
+        #[ink::test]
+        fn scores_are_emitted_as_the_current_payload_version() {
+            let accounts = fixtures::accounts();
+            let mut showcase = registered(1);
+            assert_eq!(showcase.record_score(11, 10), Err(Error::ScoreAboveMax));
+            assert_eq!(showcase.record_score(7, 10), Ok(()));
+            assert_eq!(showcase.record_score(9, 10), Ok(()));
+            assert_eq!(showcase.attempts_of(accounts.alice), 2);
+
+            let event = last_event();
+            assert_eq!(event.data[0], 3);
+            let payload = ScorePayload::decode(&mut &event.data[33..]).unwrap();
+            assert_eq!(
+                payload,
+                ScorePayload::V2 {
+                    score: 9,
+                    max_score: 10,
+                    attempt: 2,
+                }
+            );
+        }
+
+        #[ink::test]
+        fn attempts_run_out() {
+            let mut showcase = registered(1);
+            for _ in 0..u8::MAX {
+                assert_eq!(showcase.record_score(1, 1), Ok(()));
+            }
+            assert_eq!(showcase.record_score(1, 1), Err(Error::NoAttemptsLeft));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Upload the generated .contract file and deploy it
+//
+// 4. Interact with the contract:
+//    - Use "register", "postNote", "ping" and "recordScore"
+//    - Open "Network" -> "Explorer" and compare the topics of each event
+//    - Decode the event data with `crates/events/src/events_showcase.rs`
@@ -0,0 +1,343 @@
+// examples/demo-contracts/factory.rs
+//
+// A contract factory example for Polkadot using ink!
+// This contract demonstrates deploying child contracts from an uploaded code hash
+// with a caller-chosen salt, and predicting the resulting address before deployment.
+//
+// ink! version
+// ------------
+// Unlike the ink! 3 examples, this contract is written for ink! 5, like the
+// exercises: the address derivation it predicts is the one of the `pallet-contracts`
+// versions ink! 5 targets, and only `ink_e2e` can check the prediction against a
+// node. Its e2e test needs an ink! 5 build of Flipper, the child: `flipper.rs` is
+// an ink! 3 contract, so the factory deploys `exercises/support/flipper` instead,
+// added to the contract's manifest as a dev-dependency with the
+// `ink-as-dependency` feature so that `ink_e2e` builds and uploads it:
+//
+//     flipper = { path = "../../exercises/support/flipper", default-features = false, features = ["ink-as-dependency"] }
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod factory {
+    use ink::{
+        env::{
+            call::{build_create, ExecutionInput, FromAccountId, Selector},
+            hash::{Blake2x256, CryptoHash},
+            ContractEnv,
+        },
+        prelude::vec::Vec,
+        storage::{Mapping, StorageVec},
+    };
+
+    /// Selector of the child's `new(init_value: bool)` constructor.
+    ///
+    /// The factory deploys Flipper children, so this is the selector
+    /// `cargo contract` reports for `Flipper::new`.
+    pub const CHILD_NEW_SELECTOR: [u8; 4] = [0x9b, 0xae, 0x9d, 0x5e];
+
+    /// Prefix mixed into the address derivation by `pallet-contracts`.
+    const ADDRESS_PREFIX: &[u8; 16] = b"contract_addr_v1";
+
+    /// The factory error types.
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// Returned if a child was already deployed with the given salt.
+        SaltAlreadyUsed,
+        /// Returned if the node rejected the instantiation.
+        InstantiationFailed,
+    }
+
+    /// The factory result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    // Events are mirrored in `crates/events/src/factory.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when a child contract is deployed.
+    #[ink(event)]
+    pub struct ChildDeployed {
+        #[ink(topic)]
+        deployer: AccountId,
+        #[ink(topic)]
+        child: AccountId,
+        salt: Vec<u8>,
+    }
+
+    /// The factory storage items.
+    #[ink(storage)]
+    pub struct Factory {
+        /// Code hash of the uploaded child contract.
+        child_code_hash: Hash,
+        /// Addresses of every child deployed through this factory.
+        children: StorageVec<AccountId>,
+        /// Salts that have already been used, so duplicate deployments fail early.
+        used_salts: Mapping<Vec<u8>, ()>,
+    }
+
+    /// A deployed child, known only by its address.
+    ///
+    /// `build_create` needs a type to turn the new address into; the factory
+    /// deploys by code hash and selector, so it has no generated `FlipperRef`.
+    struct ChildRef(AccountId);
+
+    impl ContractEnv for ChildRef {
+        type Env = Environment;
+    }
+
+    impl FromAccountId<Environment> for ChildRef {
+        fn from_account_id(account_id: AccountId) -> Self {
+            Self(account_id)
+        }
+    }
+
+    /// Predicts the address `pallet-contracts` assigns to a new contract.
+    ///
+    /// This mirrors the node's default address generator: the BLAKE2-256 hash of
+    /// the SCALE encoding of `(b"contract_addr_v1", deployer, code_hash, input, salt)`.
+    /// Because the fixed prefix and the two 32-byte values encode without a length
+    /// prefix while `input` and `salt` are byte slices, the slices carry a compact
+    /// length prefix in front of their contents.
+    pub fn predict_address(
+        deployer: &AccountId,
+        code_hash: &Hash,
+        input: &[u8],
+        salt: &[u8],
+    ) -> AccountId {
+        let encoded = ink::scale::Encode::encode(&(
+            ADDRESS_PREFIX,
+            deployer,
+            code_hash,
+            input,
+            salt,
+        ));
+        let mut output = [0u8; 32];
+        Blake2x256::hash(&encoded, &mut output);
+        AccountId::from(output)
+    }
+
+    /// Returns the SCALE-encoded constructor input the factory passes to children.
+    pub fn child_input(init_value: bool) -> Vec<u8> {
+        let mut input = CHILD_NEW_SELECTOR.to_vec();
+        ink::scale::Encode::encode_to(&init_value, &mut input);
+        input
+    }
+
+    impl Factory {
+        /// Creates a new factory deploying children from `child_code_hash`.
+        #[ink(constructor)]
+        pub fn new(child_code_hash: Hash) -> Self {
+            Self {
+                child_code_hash,
+                children: StorageVec::new(),
+                used_salts: Mapping::new(),
+            }
+        }
+
+        /// Returns the code hash children are deployed from.
+        #[ink(message)]
+        pub fn child_code_hash(&self) -> Hash {
+            self.child_code_hash
+        }
+
+        /// Returns every child deployed so far, in deployment order.
+        #[ink(message)]
+        pub fn children(&self) -> Vec<AccountId> {
+            (0..self.children.len())
+                .filter_map(|index| self.children.get(index))
+                .collect()
+        }
+
+        /// Returns the address a child deployed with `salt` and `init_value` will get.
+        ///
+        /// The deployer seen by the node is the factory itself, not the caller.
+        #[ink(message)]
+        pub fn predict_address(&self, salt: Vec<u8>, init_value: bool) -> AccountId {
+            predict_address(
+                &self.env().account_id(),
+                &self.child_code_hash,
+                &child_input(init_value),
+                &salt,
+            )
+        }
+
+        /// Deploys a new child with the given `salt`, forwarding the transferred
+        /// value as the child's endowment.
+        #[ink(message, payable)]
+        pub fn deploy(&mut self, salt: Vec<u8>, init_value: bool) -> Result<AccountId> {
+            if self.used_salts.contains(&salt) {
+                return Err(Error::SaltAlreadyUsed);
+            }
+
+            let params = build_create::<ChildRef>()
+                .code_hash(self.child_code_hash)
+                .endowment(self.env().transferred_value())
+                .exec_input(
+                    ExecutionInput::new(Selector::new(CHILD_NEW_SELECTOR)).push_arg(init_value),
+                )
+                .salt_bytes(&salt)
+                .returns::<ChildRef>()
+                .params();
+            let ChildRef(child) = self
+                .env()
+                .instantiate_contract(&params)
+                .map_err(|_| Error::InstantiationFailed)?
+                .map_err(|_| Error::InstantiationFailed)?;
+
+            self.children.push(&child);
+            self.used_salts.insert(&salt, &());
+            self.env().emit_event(ChildDeployed {
+                deployer: self.env().caller(),
+                child,
+                salt,
+            });
+            Ok(child)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn code_hash() -> Hash {
+            Hash::from([0x42; 32])
+        }
+
+        #[ink::test]
+        fn new_works() {
+            let factory = Factory::new(code_hash());
+            assert_eq!(factory.child_code_hash(), code_hash());
+            assert!(factory.children().is_empty());
+        }
+
+        #[ink::test]
+        fn prediction_is_deterministic() {
+            let factory = Factory::new(code_hash());
+            assert_eq!(
+                factory.predict_address(vec![1, 2, 3], true),
+                factory.predict_address(vec![1, 2, 3], true),
+            );
+        }
+
+        #[ink::test]
+        fn prediction_depends_on_every_input() {
+            let deployer = AccountId::from([0x01; 32]);
+            let base = predict_address(&deployer, &code_hash(), &child_input(true), b"salt");
+
+            let other_deployer = AccountId::from([0x02; 32]);
+            assert_ne!(
+                base,
+                predict_address(&other_deployer, &code_hash(), &child_input(true), b"salt")
+            );
+            assert_ne!(
+                base,
+                predict_address(&deployer, &Hash::from([0x43; 32]), &child_input(true), b"salt")
+            );
+            assert_ne!(
+                base,
+                predict_address(&deployer, &code_hash(), &child_input(false), b"salt")
+            );
+            assert_ne!(
+                base,
+                predict_address(&deployer, &code_hash(), &child_input(true), b"pepper")
+            );
+        }
+
+        #[ink::test]
+        fn salt_is_length_prefixed() {
+            // Moving a byte between input and salt must change the address,
+            // otherwise two different deployments could collide.
+            let deployer = AccountId::from([0x01; 32]);
+            assert_ne!(
+                predict_address(&deployer, &code_hash(), &[1, 2], &[3]),
+                predict_address(&deployer, &code_hash(), &[1], &[2, 3]),
+            );
+        }
+
+        #[ink::test]
+        fn child_input_starts_with_selector() {
+            assert_eq!(child_input(true), vec![0x9b, 0xae, 0x9d, 0x5e, 0x01]);
+            assert_eq!(child_input(false), vec![0x9b, 0xae, 0x9d, 0x5e, 0x00]);
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        // `E2EBackend`, used in the test signatures, is imported by `#[ink_e2e::test]`.
+        use ink_e2e::ContractsBackend;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        /// Deploys a Flipper through the factory and checks that the address the
+        /// node assigned is the one `predict_address` reported beforehand.
+        #[ink_e2e::test]
+        async fn predicted_address_matches_deployed<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let flipper_hash = client
+                .upload("flipper", &ink_e2e::alice())
+                .submit()
+                .await
+                .expect("flipper upload failed")
+                .code_hash;
+
+            let factory = client
+                .instantiate("factory", &ink_e2e::alice(), &mut FactoryRef::new(flipper_hash))
+                .submit()
+                .await
+                .expect("factory instantiate failed");
+            let mut call_builder = factory.call_builder::<Factory>();
+
+            let salt = b"lesson-7".to_vec();
+            let predicted = client
+                .call(&ink_e2e::alice(), &call_builder.predict_address(salt.clone(), true))
+                .dry_run()
+                .await?
+                .return_value();
+
+            let deployed = client
+                .call(&ink_e2e::alice(), &call_builder.deploy(salt.clone(), true))
+                .submit()
+                .await
+                .expect("deploy failed")
+                .return_value()
+                .expect("factory returned an error");
+
+            assert_eq!(predicted, deployed);
+
+            let result = client
+                .call(&ink_e2e::alice(), &call_builder.deploy(salt, true))
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(result, Err(Error::SaltAlreadyUsed));
+
+            Ok(())
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Build and upload the child contract (`exercises/support/flipper`) first:
+//    cargo +nightly contract build
+//    Upload the generated .contract file and note its code hash.
+//
+// 2. Compile the factory:
+//    cargo +nightly contract build
+//
+// 3. Deploy using the Polkadot JS Apps UI:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your desired network
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Set the child code hash noted in step 1
+//    - Deploy the contract
+//
+// 4. Interact with the contract:
+//    - Use the "predictAddress" method with a salt to see where a child will land
+//    - Use the "deploy" method with the same salt and check the emitted ChildDeployed event
+//    - Use the "children" method to list every deployed child
@@ -0,0 +1,345 @@
+// examples/demo-contracts/faucet.rs
+//
+// A rate-limited faucet contract example for Polkadot using ink!
+// This contract demonstrates throttling a message per account with the token-bucket
+// `RateLimiter` from the utils crate: each learner can drip a few times in a row,
+// then has to wait for their bucket to refill. Administration uses the utils crate's
+// two-step `Ownable`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod faucet {
+    use astranet_utils::{
+        ownable::{Ownable, OwnableError},
+        rate_limiter::{Bucket, RateLimiter},
+    };
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// The faucet error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller dripped too often; retry at the given block.
+        RateLimited { retry_at: BlockNumber },
+        /// Returned if the faucet cannot cover a drip.
+        FaucetEmpty,
+        /// Returned if the caller is not the faucet owner.
+        NotOwner,
+        /// Returned if the native transfer failed.
+        TransferFailed,
+        /// Returned if the caller is not the proposed new owner.
+        NotPendingOwner,
+        /// Returned if no ownership transfer is in progress.
+        NoPendingTransfer,
+    }
+
+    impl From<OwnableError> for Error {
+        fn from(error: OwnableError) -> Self {
+            match error {
+                OwnableError::NotOwner => Error::NotOwner,
+                OwnableError::NotPendingOwner => Error::NotPendingOwner,
+                OwnableError::NoPendingTransfer => Error::NoPendingTransfer,
+            }
+        }
+    }
+
+    /// The faucet result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    // Events are mirrored in `crates/events/src/faucet.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when an account receives a drip.
+    #[ink(event)]
+    pub struct Dripped {
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when the owner proposes a new owner.
+    #[ink(event)]
+    pub struct OwnershipTransferStarted {
+        #[ink(topic)]
+        previous_owner: AccountId,
+        #[ink(topic)]
+        new_owner: AccountId,
+    }
+
+    /// Event emitted when the proposed owner accepts ownership.
+    #[ink(event)]
+    pub struct OwnershipTransferCompleted {
+        #[ink(topic)]
+        previous_owner: AccountId,
+        #[ink(topic)]
+        new_owner: AccountId,
+    }
+
+    /// The faucet storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Faucet {
+        /// Account allowed to change the drip amount.
+        ownable: Ownable,
+        /// Amount paid out per drip.
+        drip_amount: Balance,
+        /// Shared rate limit configuration.
+        limiter: RateLimiter,
+        /// Mapping from account to its rate limit bucket.
+        buckets: Mapping<AccountId, Bucket>,
+    }
+
+    impl Faucet {
+        /// Creates a new faucet paying `drip_amount` per drip, allowing bursts of
+        /// `burst` drips and one more drip every `window` blocks. Any value sent
+        /// along funds the faucet.
+        #[ink(constructor, payable)]
+        pub fn new(drip_amount: Balance, burst: u32, window: BlockNumber) -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.ownable = Ownable::new(Self::env().caller());
+                contract.drip_amount = drip_amount;
+                contract.limiter = RateLimiter::new(burst, 1, window);
+            })
+        }
+
+        /// Returns the amount paid out per drip.
+        #[ink(message)]
+        pub fn drip_amount(&self) -> Balance {
+            self.drip_amount
+        }
+
+        /// Returns how many drips `account` may take right now.
+        #[ink(message)]
+        pub fn drips_available(&self, account: AccountId) -> u32 {
+            let now = self.env().block_number();
+            self.limiter.available(self.bucket_of(account, now), now)
+        }
+
+        /// Sends `drip_amount` to the caller, if their rate limit allows it.
+        #[ink(message)]
+        pub fn drip(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            let now = self.env().block_number();
+            let amount = self.drip_amount;
+            if self.env().balance() < amount {
+                return Err(Error::FaucetEmpty);
+            }
+
+            let mut bucket = self.bucket_of(caller, now);
+            self.limiter
+                .try_consume(&mut bucket, now, 1)
+                .map_err(|limited| Error::RateLimited {
+                    retry_at: limited.retry_at,
+                })?;
+            self.buckets.insert(caller, &bucket);
+
+            self.env()
+                .transfer(caller, amount)
+                .map_err(|_| Error::TransferFailed)?;
+            self.env().emit_event(Dripped { to: caller, value: amount });
+            Ok(())
+        }
+
+        /// Accepts funds to top up the faucet.
+        #[ink(message, payable)]
+        pub fn fund(&mut self) {}
+
+        /// Changes the amount paid out per drip.
+        #[ink(message)]
+        pub fn set_drip_amount(&mut self, drip_amount: Balance) -> Result<()> {
+            self.ownable.ensure_owner(self.env().caller())?;
+            self.drip_amount = drip_amount;
+            Ok(())
+        }
+
+        /// Returns the faucet owner.
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.ownable.owner()
+        }
+
+        /// Returns the proposed new owner, if a handover is in progress.
+        #[ink(message)]
+        pub fn pending_owner(&self) -> Option<AccountId> {
+            self.ownable.pending_owner()
+        }
+
+        /// Proposes `new_owner` as the next owner. They must call `accept_ownership`.
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<()> {
+            let started = self
+                .ownable
+                .transfer_ownership(self.env().caller(), new_owner)?;
+            self.env().emit_event(OwnershipTransferStarted {
+                previous_owner: started.previous_owner,
+                new_owner: started.new_owner,
+            });
+            Ok(())
+        }
+
+        /// Accepts a pending ownership proposal made to the caller.
+        #[ink(message)]
+        pub fn accept_ownership(&mut self) -> Result<()> {
+            let completed = self.ownable.accept_ownership(self.env().caller())?;
+            self.env().emit_event(OwnershipTransferCompleted {
+                previous_owner: completed.previous_owner,
+                new_owner: completed.new_owner,
+            });
+            Ok(())
+        }
+
+        /// Withdraws a pending ownership proposal.
+        #[ink(message)]
+        pub fn cancel_ownership_transfer(&mut self) -> Result<()> {
+            self.ownable
+                .cancel_ownership_transfer(self.env().caller())?;
+            Ok(())
+        }
+
+        fn bucket_of(&self, account: AccountId, now: BlockNumber) -> Bucket {
+            self.buckets
+                .get(account)
+                .unwrap_or_else(|| self.limiter.full_bucket(now))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        fn funded_faucet() -> Faucet {
+            let faucet = Faucet::new(10, 2, 100);
+            fixtures::fund_contract(1_000);
+            faucet
+        }
+
+        #[ink::test]
+        fn drip_pays_out() {
+            let accounts = fixtures::accounts();
+            let mut faucet = funded_faucet();
+            fixtures::set_caller(accounts.bob);
+            let before = fixtures::balance_of(accounts.bob);
+            assert_eq!(faucet.drip(), Ok(()));
+            assert_eq!(fixtures::balance_of(accounts.bob), before + 10);
+        }
+
+        #[ink::test]
+        fn burst_then_rate_limited() {
+            let accounts = fixtures::accounts();
+            let mut faucet = funded_faucet();
+            fixtures::set_caller(accounts.bob);
+            fixtures::set_block_number(5);
+            assert_eq!(faucet.drip(), Ok(()));
+            assert_eq!(faucet.drip(), Ok(()));
+            assert_eq!(faucet.drip(), Err(Error::RateLimited { retry_at: 105 }));
+            assert_eq!(faucet.drips_available(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn bucket_refills_after_window() {
+            let accounts = fixtures::accounts();
+            let mut faucet = funded_faucet();
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(faucet.drip(), Ok(()));
+            assert_eq!(faucet.drip(), Ok(()));
+
+            fixtures::set_block_number(99);
+            assert!(faucet.drip().is_err());
+            fixtures::set_block_number(100);
+            assert_eq!(faucet.drips_available(accounts.bob), 1);
+            assert_eq!(faucet.drip(), Ok(()));
+            assert!(faucet.drip().is_err());
+        }
+
+        #[ink::test]
+        fn limits_are_per_account() {
+            let accounts = fixtures::accounts();
+            let mut faucet = funded_faucet();
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(faucet.drip(), Ok(()));
+            assert_eq!(faucet.drip(), Ok(()));
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(faucet.drips_available(accounts.charlie), 2);
+            assert_eq!(faucet.drip(), Ok(()));
+        }
+
+        #[ink::test]
+        fn empty_faucet_does_not_spend_tokens() {
+            let accounts = fixtures::accounts();
+            let mut faucet = Faucet::new(10, 2, 100);
+            fixtures::fund_contract(5);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(faucet.drip(), Err(Error::FaucetEmpty));
+            assert_eq!(faucet.drips_available(accounts.bob), 2);
+        }
+
+        #[ink::test]
+        fn only_owner_sets_drip_amount() {
+            let accounts = fixtures::accounts();
+            let mut faucet = funded_faucet();
+            assert_eq!(faucet.set_drip_amount(20), Ok(()));
+            assert_eq!(faucet.drip_amount(), 20);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(faucet.set_drip_amount(30), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn ownership_moves_after_acceptance() {
+            let accounts = fixtures::accounts();
+            let mut faucet = funded_faucet();
+            assert_eq!(faucet.transfer_ownership(accounts.bob), Ok(()));
+            assert_eq!(faucet.owner(), accounts.alice);
+            assert_eq!(faucet.pending_owner(), Some(accounts.bob));
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(faucet.accept_ownership(), Ok(()));
+            assert_eq!(faucet.owner(), accounts.bob);
+            assert_eq!(faucet.set_drip_amount(30), Ok(()));
+
+            let events = ink_env::test::recorded_events().count();
+            assert_eq!(events, 2);
+        }
+
+        #[ink::test]
+        fn cancelled_handover_cannot_be_accepted() {
+            let accounts = fixtures::accounts();
+            let mut faucet = funded_faucet();
+            assert_eq!(faucet.transfer_ownership(accounts.bob), Ok(()));
+            assert_eq!(faucet.cancel_ownership_transfer(), Ok(()));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(faucet.accept_ownership(), Err(Error::NoPendingTransfer));
+            assert_eq!(faucet.owner(), accounts.alice);
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy using the Polkadot JS Apps UI:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your desired network
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Set the drip amount, burst size, and refill window (in blocks)
+//    - Send some value along to fund the faucet
+//    - Deploy the contract
+//
+// 4. Interact with the contract:
+//    - Use the "drip" method to receive funds, or run
+//      `deployer faucet --contract <faucet address> --key <your key>` from tools/deployer,
+//      which waits out the rate limit and retries
+//    - Use the "dripsAvailable" method to see how many drips you have left
+//    - Use the "fund" method to top the faucet up
+//    - Use "transferOwnership" and then "acceptOwnership" from the new account to hand over admin rights
@@ -0,0 +1,281 @@
+// examples/demo-contracts/flash_borrower.rs
+//
+// A flash loan borrower contract example for Polkadot using ink!
+// This contract is the receiving side of `flashloan_pool.rs`. Its owner calls
+// `borrow`, the pool sends the tokens and calls back `on_flash_loan`, and the
+// borrower approves the pool to take the loan plus the fee back before returning.
+// A real borrower would use the tokens inside the callback, e.g. for an arbitrage
+// that earns more than the fee; this one only counts its loans.
+//
+// The callback checks who is calling it: only the configured pool may, and only
+// for loans this contract started itself. Without these checks anyone could call
+// `on_flash_loan` directly, or start a loan on the borrower's behalf, and make it
+// pay fees it never agreed to.
+//
+// The borrower must hold enough of the token to pay the fee before borrowing.
+// `set_repays(false)` makes the callback skip the approval, to show the pool
+// reverting a loan that is not paid back.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod flash_borrower {
+    use ink_env::call::{build_call, Call, ExecutionInput, Selector};
+    use ink_prelude::vec::Vec;
+    use ink_storage::traits::SpreadAllocate;
+
+    /// Selector of the ERC-20 `approve(spender, value)` message.
+    const APPROVE_SELECTOR: [u8; 4] = [0x68, 0x12, 0x66, 0xa0];
+
+    /// Selector of the pool's `flash_loan(receiver, amount, data)` message.
+    const FLASH_LOAN_SELECTOR: [u8; 4] = [0x90, 0x25, 0x7a, 0x58];
+
+    /// The flash borrower error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the borrower owner.
+        NotOwner,
+        /// Returned if `on_flash_loan` is called by anyone but the pool.
+        UntrustedLender,
+        /// Returned if the loan was not started by this contract.
+        UntrustedInitiator,
+        /// Returned if the pool or the token rejected a call.
+        CallFailed,
+    }
+
+    /// The flash borrower result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Stands in for the error types of the pool and the token. All the borrower
+    /// needs is to tell success from failure.
+    #[derive(scale::Decode)]
+    struct CalleeError(#[allow(dead_code)] u8);
+
+    // Events are mirrored in `crates/events/src/flash_borrower.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when the borrower receives a loan in `on_flash_loan`.
+    #[ink(event)]
+    pub struct LoanReceived {
+        #[ink(topic)]
+        token: AccountId,
+        amount: Balance,
+        fee: Balance,
+    }
+
+    /// The flash borrower storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct FlashBorrower {
+        /// Account allowed to start loans.
+        owner: AccountId,
+        /// Pool the borrower takes loans from.
+        pool: AccountId,
+        /// Whether the callback approves the repayment.
+        repays: bool,
+        /// Number of loans received.
+        loans: u32,
+    }
+
+    impl FlashBorrower {
+        /// Creates a borrower taking loans from `pool`, owned by the caller.
+        #[ink(constructor)]
+        pub fn new(pool: AccountId) -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.owner = Self::env().caller();
+                contract.pool = pool;
+                contract.repays = true;
+            })
+        }
+
+        /// Returns the borrower owner.
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Returns the pool the borrower takes loans from.
+        #[ink(message)]
+        pub fn pool(&self) -> AccountId {
+            self.pool
+        }
+
+        /// Returns whether the callback approves the repayment.
+        #[ink(message)]
+        pub fn repays(&self) -> bool {
+            self.repays
+        }
+
+        /// Returns the number of loans received.
+        #[ink(message)]
+        pub fn loans(&self) -> u32 {
+            self.loans
+        }
+
+        /// Sets whether the callback approves the repayment. Only the owner can call
+        /// this.
+        #[ink(message)]
+        pub fn set_repays(&mut self, repays: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.repays = repays;
+            Ok(())
+        }
+
+        /// Borrows `amount` from the pool, passing `data` through to the callback.
+        /// Only the owner can call this.
+        #[ink(message)]
+        pub fn borrow(&mut self, amount: Balance, data: Vec<u8>) -> Result<()> {
+            self.ensure_owner()?;
+            let receiver = self.env().account_id();
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.pool).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(FLASH_LOAN_SELECTOR))
+                        .push_arg(receiver)
+                        .push_arg(amount)
+                        .push_arg(data),
+                )
+                .returns::<core::result::Result<(), CalleeError>>()
+                .fire()
+                .map_err(|_| Error::CallFailed)?
+                .map_err(|_| Error::CallFailed)
+        }
+
+        /// The flash loan callback. Called by the pool after sending `amount` of
+        /// `token`; approves the pool to take `amount + fee` back.
+        #[ink(message)]
+        pub fn on_flash_loan(
+            &mut self,
+            initiator: AccountId,
+            token: AccountId,
+            amount: Balance,
+            fee: Balance,
+            _data: Vec<u8>,
+        ) -> Result<()> {
+            if self.env().caller() != self.pool {
+                return Err(Error::UntrustedLender);
+            }
+            if initiator != self.env().account_id() {
+                return Err(Error::UntrustedInitiator);
+            }
+
+            // This is where a real borrower would put the tokens to use.
+            self.loans += 1;
+            self.env().emit_event(LoanReceived { token, amount, fee });
+
+            if !self.repays {
+                return Ok(());
+            }
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(token).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(APPROVE_SELECTOR))
+                        .push_arg(self.pool)
+                        .push_arg(amount + fee),
+                )
+                .returns::<core::result::Result<(), CalleeError>>()
+                .fire()
+                .map_err(|_| Error::CallFailed)?
+                .map_err(|_| Error::CallFailed)
+        }
+
+        /// Returns `NotOwner` unless the caller is the owner.
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// cover the callback's checks and leave the approval out with `set_repays`.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        fn pool() -> AccountId {
+            AccountId::from([0x9a; 32])
+        }
+
+        fn token() -> AccountId {
+            AccountId::from([0x7a; 32])
+        }
+
+        #[ink::test]
+        fn only_pool_may_call_back() {
+            let accounts = fixtures::accounts();
+            let mut borrower = FlashBorrower::new(pool());
+            let this = ink_env::test::callee::<Env>();
+            fixtures::set_caller(accounts.eve);
+            assert_eq!(
+                borrower.on_flash_loan(this, token(), 1_000, 1, Vec::new()),
+                Err(Error::UntrustedLender)
+            );
+            assert_eq!(borrower.loans(), 0);
+        }
+
+        #[ink::test]
+        fn only_own_loans_are_accepted() {
+            let accounts = fixtures::accounts();
+            let mut borrower = FlashBorrower::new(pool());
+            fixtures::set_caller(pool());
+            assert_eq!(
+                borrower.on_flash_loan(accounts.eve, token(), 1_000, 1, Vec::new()),
+                Err(Error::UntrustedInitiator)
+            );
+            assert_eq!(borrower.loans(), 0);
+        }
+
+        #[ink::test]
+        fn trusted_callback_counts_loan() {
+            let mut borrower = FlashBorrower::new(pool());
+            assert_eq!(borrower.set_repays(false), Ok(()));
+            let this = ink_env::test::callee::<Env>();
+            fixtures::set_caller(pool());
+            assert_eq!(
+                borrower.on_flash_loan(this, token(), 1_000, 1, Vec::new()),
+                Ok(())
+            );
+            assert_eq!(borrower.loans(), 1);
+        }
+
+        #[ink::test]
+        fn only_owner_can_borrow() {
+            let accounts = fixtures::accounts();
+            let mut borrower = FlashBorrower::new(pool());
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(borrower.borrow(1_000, Vec::new()), Err(Error::NotOwner));
+            assert_eq!(borrower.set_repays(false), Err(Error::NotOwner));
+            assert!(borrower.repays());
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy with the address of a deployed `flashloan_pool.rs`
+//
+// 4. Interact with the contract:
+//    - Transfer enough of the pool's token to the borrower to pay the fee
+//    - Use the "borrow" method with an amount up to the pool's reserves
+//    - Use "setRepays" with false, then "borrow" again, to see the loan reverted
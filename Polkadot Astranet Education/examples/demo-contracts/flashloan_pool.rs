@@ -0,0 +1,567 @@
+// examples/demo-contracts/flashloan_pool.rs
+//
+// A flash loan pool contract example for Polkadot using ink!
+// The pool lends out its reserves of one ERC-20 token for the duration of a single
+// call. `flash_loan` sends the tokens to a receiver contract, calls back into it with
+// `on_flash_loan`, and then takes the amount plus a fee back with `transfer_from`.
+// `flash_borrower.rs` is a receiver implementing the callback.
+//
+// Callback interface
+// ------------------
+// A receiver must have the message
+//
+//     on_flash_loan(initiator: AccountId, token: AccountId, amount: Balance,
+//                   fee: Balance, data: Vec<u8>) -> Result<(), _>
+//
+// and, before returning `Ok`, approve the pool to spend `amount + fee` of `token`.
+// `data` is passed through from `flash_loan` untouched.
+//
+// Repayment is checked within the same call. By the time the callback runs the
+// tokens have already left the pool, and ink! 3 does not undo a transfer when a
+// message returns an error, so a failed callback or repayment panics instead: the
+// contract traps and the whole transaction, loan included, is reverted.
+//
+// Configurable fee
+// ----------------
+// The fee given to the constructor is a fallback. Once the owner points the pool
+// at a `config_store.rs` contract with `set_config_store`, the fee is read from
+// its `flashloan.fee_bps` key on every loan, so it can be changed without
+// redeploying the pool. The fallback applies while the key is unset, holds
+// something other than a `u128` of at most `BPS`, or the store cannot be called.
+//
+// Emergency stop
+// --------------
+// The pool embeds the utils crate's `CircuitBreaker`, with the deployer as its
+// guardian until `set_guardian` hands the role on, e.g. to a multisig. While the
+// breaker is tripped `flash_loan` fails with `Tripped`; `deposit` and `withdraw`
+// keep working, so the owner can still take the reserves out of a pool in trouble.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod flashloan_pool {
+    use astranet_utils::circuit_breaker::{CircuitBreaker, CircuitBreakerError};
+    use ink_env::call::{build_call, Call, ExecutionInput, Selector};
+    use ink_prelude::vec::Vec;
+    use ink_storage::traits::SpreadAllocate;
+
+    /// The fee is in basis points of this.
+    pub const BPS: u128 = 10_000;
+
+    /// Selector of the ERC-20 `transfer(to, value)` message.
+    const TRANSFER_SELECTOR: [u8; 4] = [0x84, 0xa1, 0x5d, 0xa1];
+
+    /// Selector of the ERC-20 `transfer_from(from, to, value)` message.
+    const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x0b, 0x39, 0x6f, 0x18];
+
+    /// Selector of the receiver's `on_flash_loan(initiator, token, amount, fee, data)`
+    /// message.
+    const ON_FLASH_LOAN_SELECTOR: [u8; 4] = [0x08, 0x48, 0x1e, 0x7f];
+
+    /// Selector of the config store's `get_u128(key)` message.
+    const GET_U128_SELECTOR: [u8; 4] = [0xe5, 0x3f, 0xd7, 0x8c];
+
+    /// Config store key holding the fee, in basis points.
+    pub const FEE_KEY: &[u8] = b"flashloan.fee_bps";
+
+    /// The flash loan pool error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the pool owner.
+        NotOwner,
+        /// Returned if the amount is zero.
+        ZeroAmount,
+        /// Returned if the pool holds less than the amount asked for.
+        InsufficientLiquidity,
+        /// Returned if the token refused to move the tokens.
+        TransferFailed,
+        /// Returned if the caller is not the guardian.
+        NotGuardian,
+        /// Returned if loans are stopped by the circuit breaker.
+        Tripped,
+        /// Returned if the circuit breaker is not tripped.
+        NotTripped,
+    }
+
+    impl From<CircuitBreakerError> for Error {
+        fn from(error: CircuitBreakerError) -> Self {
+            match error {
+                CircuitBreakerError::NotGuardian => Error::NotGuardian,
+                CircuitBreakerError::Tripped => Error::Tripped,
+                CircuitBreakerError::NotTripped => Error::NotTripped,
+            }
+        }
+    }
+
+    /// The flash loan pool result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Stands in for the error types of the token and the receiver. All the pool
+    /// needs is to tell success from failure.
+    #[derive(scale::Decode)]
+    struct CalleeError(#[allow(dead_code)] u8);
+
+    /// Returns the fee for borrowing `amount` at `fee_bps`, rounded up so that no
+    /// loan is free.
+    pub fn flash_fee(amount: Balance, fee_bps: u128) -> Balance {
+        let fee = amount.saturating_mul(fee_bps);
+        fee / BPS + u128::from(!fee.is_multiple_of(BPS))
+    }
+
+    // Events are mirrored in `crates/events/src/flashloan_pool.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when the owner adds or removes reserves.
+    #[ink(event)]
+    pub struct LiquidityChanged {
+        added: Balance,
+        removed: Balance,
+    }
+
+    /// Event emitted when a flash loan has been repaid.
+    #[ink(event)]
+    pub struct FlashLoan {
+        #[ink(topic)]
+        receiver: AccountId,
+        #[ink(topic)]
+        initiator: AccountId,
+        amount: Balance,
+        fee: Balance,
+    }
+
+    /// The flash loan pool storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct FlashloanPool {
+        /// Account allowed to add and remove reserves.
+        owner: AccountId,
+        /// Token the pool lends.
+        token: AccountId,
+        /// Fee per loan, in basis points of the amount, unless the config store
+        /// has one.
+        fee_bps: u128,
+        /// Config store to read the fee from, if any.
+        config_store: Option<AccountId>,
+        /// Tokens the pool holds, fees included.
+        reserves: Balance,
+        /// Emergency stop for `flash_loan`.
+        breaker: CircuitBreaker,
+    }
+
+    impl FlashloanPool {
+        /// Creates an empty pool lending `token` at `fee_bps`, owned by the caller.
+        #[ink(constructor)]
+        pub fn new(token: AccountId, fee_bps: u128) -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.owner = Self::env().caller();
+                contract.token = token;
+                contract.fee_bps = fee_bps;
+                contract.breaker = CircuitBreaker::new(Self::env().caller());
+            })
+        }
+
+        /// Returns the pool owner.
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Returns the token the pool lends.
+        #[ink(message)]
+        pub fn token(&self) -> AccountId {
+            self.token
+        }
+
+        /// Returns the fee per loan, in basis points of the amount.
+        #[ink(message)]
+        pub fn fee_bps(&self) -> u128 {
+            self.configured_fee_bps().unwrap_or(self.fee_bps)
+        }
+
+        /// Returns the config store the fee is read from, if any.
+        #[ink(message)]
+        pub fn config_store(&self) -> Option<AccountId> {
+            self.config_store
+        }
+
+        /// Reads the fee from `config_store` from now on, or goes back to the
+        /// constructor's fee if `None`. Only the owner can call this.
+        #[ink(message)]
+        pub fn set_config_store(&mut self, config_store: Option<AccountId>) -> Result<()> {
+            self.ensure_owner()?;
+            self.config_store = config_store;
+            Ok(())
+        }
+
+        /// Returns the account that can stop and restart loans.
+        #[ink(message)]
+        pub fn guardian(&self) -> AccountId {
+            self.breaker.guardian()
+        }
+
+        /// Returns whether loans are stopped.
+        #[ink(message)]
+        pub fn is_tripped(&self) -> bool {
+            self.breaker.is_tripped()
+        }
+
+        /// Stops loans until `reset`. Only the guardian can call this.
+        #[ink(message)]
+        pub fn trip(&mut self) -> Result<()> {
+            self.breaker.trip(self.env().caller())?;
+            Ok(())
+        }
+
+        /// Restarts loans after `trip`. Only the guardian can call this.
+        #[ink(message)]
+        pub fn reset(&mut self) -> Result<()> {
+            self.breaker.reset(self.env().caller())?;
+            Ok(())
+        }
+
+        /// Hands the guardian role to `new_guardian`. Only the guardian can call
+        /// this.
+        #[ink(message)]
+        pub fn set_guardian(&mut self, new_guardian: AccountId) -> Result<()> {
+            self.breaker
+                .set_guardian(self.env().caller(), new_guardian)?;
+            Ok(())
+        }
+
+        /// Returns the most that can be borrowed in one loan.
+        #[ink(message)]
+        pub fn max_flash_loan(&self) -> Balance {
+            self.reserves
+        }
+
+        /// Returns the fee for borrowing `amount`.
+        #[ink(message)]
+        pub fn fee_for(&self, amount: Balance) -> Balance {
+            flash_fee(amount, self.fee_bps())
+        }
+
+        /// Moves `amount` of the token from the owner into the reserves. The owner
+        /// must have approved the pool to spend at least `amount`.
+        #[ink(message)]
+        pub fn deposit(&mut self, amount: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let pool = self.env().account_id();
+            self.pull(self.env().caller(), pool, amount)?;
+            self.reserves += amount;
+            self.env().emit_event(LiquidityChanged {
+                added: amount,
+                removed: 0,
+            });
+            Ok(())
+        }
+
+        /// Sends `amount` of the reserves, fees included, to the owner.
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            self.ensure_liquidity(amount)?;
+            self.reserves -= amount;
+            if self.push(self.owner, amount).is_err() {
+                self.reserves += amount;
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(LiquidityChanged {
+                added: 0,
+                removed: amount,
+            });
+            Ok(())
+        }
+
+        /// Lends `amount` to `receiver` for the duration of this call.
+        ///
+        /// Sends the tokens, calls `receiver.on_flash_loan`, and takes `amount` plus
+        /// the fee back. Panics, reverting the loan, if the callback fails or the
+        /// repayment cannot be taken.
+        #[ink(message)]
+        pub fn flash_loan(
+            &mut self,
+            receiver: AccountId,
+            amount: Balance,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            self.breaker.ensure_running()?;
+            self.ensure_liquidity(amount)?;
+            let fee = self.fee_for(amount);
+            let initiator = self.env().caller();
+            let token = self.token;
+            self.push(receiver, amount)?;
+
+            let callback = build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(receiver).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ON_FLASH_LOAN_SELECTOR))
+                        .push_arg(initiator)
+                        .push_arg(token)
+                        .push_arg(amount)
+                        .push_arg(fee)
+                        .push_arg(data),
+                )
+                .returns::<core::result::Result<(), CalleeError>>()
+                .fire();
+            if !matches!(callback, Ok(Ok(()))) {
+                panic!("flash loan callback failed");
+            }
+
+            let pool = self.env().account_id();
+            if self.pull(receiver, pool, amount + fee).is_err() {
+                panic!("flash loan was not repaid");
+            }
+
+            self.reserves += fee;
+            self.env().emit_event(FlashLoan {
+                receiver,
+                initiator,
+                amount,
+                fee,
+            });
+            Ok(())
+        }
+
+        /// Returns `NotOwner` unless the caller is the owner.
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+
+        /// Returns the fee set in the config store, or `None` if there is no store,
+        /// the call fails, or the key holds no valid fee.
+        fn configured_fee_bps(&self) -> Option<u128> {
+            let store = self.config_store?;
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(store).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(GET_U128_SELECTOR))
+                        .push_arg(FEE_KEY.to_vec()),
+                )
+                .returns::<Option<u128>>()
+                .fire()
+                .ok()
+                .flatten()
+                .filter(|fee_bps| *fee_bps <= BPS)
+        }
+
+        /// Returns an error unless `amount` is non-zero and covered by the reserves.
+        fn ensure_liquidity(&self, amount: Balance) -> Result<()> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            if amount > self.reserves {
+                return Err(Error::InsufficientLiquidity);
+            }
+            Ok(())
+        }
+
+        /// Sends `amount` of the token from the pool to `to`.
+        fn push(&self, to: AccountId, amount: Balance) -> Result<()> {
+            match build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.token).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(TRANSFER_SELECTOR))
+                        .push_arg(to)
+                        .push_arg(amount),
+                )
+                .returns::<core::result::Result<(), CalleeError>>()
+                .fire()
+            {
+                Ok(Ok(())) => Ok(()),
+                _ => Err(Error::TransferFailed),
+            }
+        }
+
+        /// Takes `amount` of the token from `from` to `to` on the pool's allowance.
+        fn pull(
+            &self,
+            from: AccountId,
+            to: AccountId,
+            amount: Balance,
+        ) -> Result<()> {
+            match build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.token).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(TRANSFER_FROM_SELECTOR))
+                        .push_arg(from)
+                        .push_arg(to)
+                        .push_arg(amount),
+                )
+                .returns::<core::result::Result<(), CalleeError>>()
+                .fire()
+            {
+                Ok(Ok(())) => Ok(()),
+                _ => Err(Error::TransferFailed),
+            }
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// cover the fee math and everything checked before the first token call.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        fn token() -> AccountId {
+            AccountId::from([0x7a; 32])
+        }
+
+        /// The pool is tried out with `mocks/mock_token.rs` as its token, whose
+        /// messages get these selectors from their names.
+        #[ink::test]
+        fn token_selectors_match_the_mock() {
+            assert_eq!(TRANSFER_SELECTOR, ink::selector_bytes!("transfer"));
+            assert_eq!(
+                TRANSFER_FROM_SELECTOR,
+                ink::selector_bytes!("transfer_from")
+            );
+        }
+
+        /// A pool charging 0.09% with `reserves` already deposited.
+        fn new_pool(reserves: Balance) -> FlashloanPool {
+            let mut pool = FlashloanPool::new(token(), 9);
+            pool.reserves = reserves;
+            pool
+        }
+
+        #[ink::test]
+        fn fee_rounds_up() {
+            assert_eq!(flash_fee(10_000, 9), 9);
+            assert_eq!(flash_fee(10_001, 9), 10);
+            assert_eq!(flash_fee(1, 9), 1);
+            assert_eq!(flash_fee(0, 9), 0);
+            assert_eq!(flash_fee(1_000, 0), 0);
+        }
+
+        #[ink::test]
+        fn fee_for_uses_pool_rate() {
+            let pool = new_pool(0);
+            assert_eq!(pool.fee_bps(), 9);
+            assert_eq!(pool.fee_for(1_000_000), 900);
+        }
+
+        #[ink::test]
+        fn only_owner_sets_config_store() {
+            let accounts = fixtures::accounts();
+            let mut pool = new_pool(0);
+            assert_eq!(pool.config_store(), None);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                pool.set_config_store(Some(accounts.charlie)),
+                Err(Error::NotOwner)
+            );
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(pool.set_config_store(Some(accounts.charlie)), Ok(()));
+            assert_eq!(pool.config_store(), Some(accounts.charlie));
+            assert_eq!(pool.set_config_store(None), Ok(()));
+            assert_eq!(pool.fee_bps(), 9);
+        }
+
+        #[ink::test]
+        fn loan_is_capped_by_reserves() {
+            let accounts = fixtures::accounts();
+            let mut pool = new_pool(1_000);
+            assert_eq!(pool.max_flash_loan(), 1_000);
+            assert_eq!(
+                pool.flash_loan(accounts.bob, 1_001, Vec::new()),
+                Err(Error::InsufficientLiquidity)
+            );
+            assert_eq!(
+                pool.flash_loan(accounts.bob, 0, Vec::new()),
+                Err(Error::ZeroAmount)
+            );
+        }
+
+        #[ink::test]
+        fn only_owner_manages_reserves() {
+            let accounts = fixtures::accounts();
+            let mut pool = new_pool(1_000);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(pool.deposit(100), Err(Error::NotOwner));
+            assert_eq!(pool.withdraw(100), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn withdraw_is_capped_by_reserves() {
+            let mut pool = new_pool(1_000);
+            assert_eq!(pool.withdraw(1_001), Err(Error::InsufficientLiquidity));
+            assert_eq!(pool.deposit(0), Err(Error::ZeroAmount));
+        }
+
+        #[ink::test]
+        fn only_guardian_can_trip() {
+            let accounts = fixtures::accounts();
+            let mut pool = new_pool(1_000);
+            assert_eq!(pool.guardian(), accounts.alice);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(pool.trip(), Err(Error::NotGuardian));
+            assert_eq!(pool.set_guardian(accounts.bob), Err(Error::NotGuardian));
+            assert!(!pool.is_tripped());
+
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(pool.set_guardian(accounts.bob), Ok(()));
+            assert_eq!(pool.trip(), Err(Error::NotGuardian));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(pool.trip(), Ok(()));
+            assert!(pool.is_tripped());
+        }
+
+        #[ink::test]
+        fn tripped_pool_refuses_loans() {
+            let accounts = fixtures::accounts();
+            let mut pool = new_pool(1_000);
+            assert_eq!(pool.reset(), Err(Error::NotTripped));
+            assert_eq!(pool.trip(), Ok(()));
+            assert_eq!(
+                pool.flash_loan(accounts.bob, 100, Vec::new()),
+                Err(Error::Tripped)
+            );
+            // The owner can still get the reserves out.
+            assert_eq!(pool.withdraw(1_001), Err(Error::InsufficientLiquidity));
+
+            assert_eq!(pool.reset(), Ok(()));
+            assert_eq!(
+                pool.flash_loan(accounts.bob, 0, Vec::new()),
+                Err(Error::ZeroAmount)
+            );
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy with the token to lend and the fallback fee in basis points; to try
+//      it out, deploy `mocks/mock_token.rs` first and lend that
+//
+// 4. Interact with the contract:
+//    - On the token contract, "approve" the pool, then use "deposit" to add reserves
+//      (with the mock token, "mint" yourself the reserves and the borrower its fees)
+//    - Deploy `flash_borrower.rs` pointing at this pool and use its "borrow" method
+//    - Use the "withdraw" method to take out reserves and collected fees
+//    - Optionally use "setConfigStore" with a `config_store.rs` contract and set
+//      its "flashloan.fee_bps" key to a U128 fee
+//    - Use "setGuardian" to hand the emergency stop to a multisig, which can then
+//      "trip" the pool to stop loans and "reset" it to restart them
@@ -0,0 +1,330 @@
+// examples/demo-contracts/forwarder.rs
+//
+// A meta-transaction forwarder contract example for Polkadot using ink!
+// This contract demonstrates "gasless" transactions: a user signs a request off-chain,
+// and a relayer submits it (and pays the fees). The forwarder checks the signature
+// and nonce, then calls the target contract, passing the signer along as the
+// effective caller. See forwarder_recipient.rs for the receiving side.
+//
+// Request and signature format
+// ----------------------------
+// The user signs, with an ECDSA (secp256k1) key, the BLAKE2-256 hash of the SCALE
+// encoding of `(forwarder_address, request)`. Including the forwarder's address
+// stops a signature from being replayed on another forwarder deployment; the nonce
+// stops it from being replayed on this one.
+//
+// The signer's account is the BLAKE2-256 hash of their compressed public key,
+// which is how Substrate derives `AccountId`s for ECDSA keys.
+//
+// Calling convention
+// ------------------
+// `request.data` holds the target message's selector followed by its arguments,
+// *without* the sender. The forwarder inserts the verified signer as the first
+// argument, so messages meant to be relayed take `sender: AccountId` first and
+// only trust it when called by the forwarder.
+//
+// Testing
+// -------
+// The tests sign requests with real keys, so the contract's manifest needs
+// `secp256k1 = { version = "0.28", features = ["recovery", "global-context"] }`
+// as a dev-dependency.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod forwarder {
+    use ink_env::{
+        call::{build_call, Call, ExecutionInput, Selector},
+        hash::{Blake2x256, CryptoHash},
+    };
+    use astranet_utils::nonces::{NonceError, Nonces};
+    use ink_prelude::vec::Vec;
+    use ink_storage::traits::SpreadAllocate;
+
+    /// The forwarder error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the signature does not recover to `request.from`.
+        InvalidSignature,
+        /// Returned if the request's nonce is not the signer's next nonce.
+        InvalidNonce,
+        /// Returned if `request.data` is too short to hold a selector.
+        MissingSelector,
+        /// Returned if the call to the target contract failed.
+        CallFailed,
+    }
+
+    impl From<NonceError> for Error {
+        fn from(_: NonceError) -> Self {
+            Error::InvalidNonce
+        }
+    }
+
+    /// The forwarder result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A call the signer wants executed on their behalf.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ForwardRequest {
+        /// The account that signed the request.
+        pub from: AccountId,
+        /// The contract to call.
+        pub to: AccountId,
+        /// The signer's nonce at this forwarder.
+        pub nonce: u64,
+        /// Selector and SCALE-encoded arguments of the target message, minus the sender.
+        pub data: Vec<u8>,
+    }
+
+    // Events are mirrored in `crates/events/src/forwarder.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when a request is executed.
+    #[ink(event)]
+    pub struct Forwarded {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        nonce: u64,
+        relayer: AccountId,
+    }
+
+    /// Writes pre-encoded bytes verbatim when building the call input.
+    struct RawBytes<'a>(&'a [u8]);
+
+    impl scale::Encode for RawBytes<'_> {
+        fn size_hint(&self) -> usize {
+            self.0.len()
+        }
+
+        fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+            dest.write(self.0);
+        }
+    }
+
+    /// Returns the hash a user signs to authorize `request` at `forwarder`.
+    pub fn request_hash(forwarder: &AccountId, request: &ForwardRequest) -> [u8; 32] {
+        let mut output = [0u8; 32];
+        Blake2x256::hash(&scale::Encode::encode(&(forwarder, request)), &mut output);
+        output
+    }
+
+    /// Returns the `AccountId` of the ECDSA key with compressed public key `public_key`.
+    pub fn ecdsa_account(public_key: &[u8; 33]) -> AccountId {
+        let mut output = [0u8; 32];
+        Blake2x256::hash(public_key, &mut output);
+        AccountId::from(output)
+    }
+
+    /// The forwarder storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Forwarder {
+        /// The nonce each signer's next request must carry.
+        nonces: Nonces,
+    }
+
+    impl Forwarder {
+        /// Creates a new forwarder.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|_: &mut Self| {})
+        }
+
+        /// Returns the nonce `account`'s next request must carry.
+        #[ink(message)]
+        pub fn nonce_of(&self, account: AccountId) -> u64 {
+            self.nonces.nonce_of(&account)
+        }
+
+        /// Checks that `signature` authorizes `request` and that the request is the
+        /// signer's next one, without executing it. Relayers call this first to avoid
+        /// paying for requests that would fail.
+        #[ink(message)]
+        pub fn verify(&self, request: ForwardRequest, signature: [u8; 65]) -> Result<()> {
+            if request.nonce != self.nonce_of(request.from) {
+                return Err(Error::InvalidNonce);
+            }
+            if request.data.len() < 4 {
+                return Err(Error::MissingSelector);
+            }
+
+            let hash = request_hash(&self.env().account_id(), &request);
+            let public_key = self
+                .env()
+                .ecdsa_recover(&signature, &hash)
+                .map_err(|_| Error::InvalidSignature)?;
+            if ecdsa_account(&public_key) != request.from {
+                return Err(Error::InvalidSignature);
+            }
+            Ok(())
+        }
+
+        /// Verifies and executes `request`, calling `request.to` with the signer
+        /// inserted as the first argument. The caller of this message is the relayer
+        /// and pays the fees; it gains no rights over the signer's account.
+        #[ink(message)]
+        pub fn execute(&mut self, request: ForwardRequest, signature: [u8; 65]) -> Result<()> {
+            self.verify(request.clone(), signature)?;
+
+            // Consume the nonce before calling out, so a re-entrant call cannot
+            // replay the same request.
+            self.nonces.use_checked_nonce(&request.from, request.nonce)?;
+
+            let mut selector = [0u8; 4];
+            selector.copy_from_slice(&request.data[..4]);
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(request.to).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(selector))
+                        .push_arg(request.from)
+                        .push_arg(RawBytes(&request.data[4..])),
+                )
+                .returns::<()>()
+                .fire()
+                .map_err(|_| Error::CallFailed)?;
+
+            self.env().emit_event(Forwarded {
+                from: request.from,
+                to: request.to,
+                nonce: request.nonce,
+                relayer: self.env().caller(),
+            });
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_lang as ink;
+        use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+
+        /// A test signer with a fixed ECDSA key.
+        struct Signer {
+            secret: SecretKey,
+            public: [u8; 33],
+        }
+
+        impl Signer {
+            fn new(seed: u8) -> Self {
+                let secret = SecretKey::from_slice(&[seed; 32]).expect("valid secret key");
+                let public = PublicKey::from_secret_key(&Secp256k1::new(), &secret).serialize();
+                Self { secret, public }
+            }
+
+            fn account(&self) -> AccountId {
+                ecdsa_account(&self.public)
+            }
+
+            fn sign(&self, forwarder: &AccountId, request: &ForwardRequest) -> [u8; 65] {
+                let message = Message::from_digest_slice(&request_hash(forwarder, request))
+                    .expect("hash is 32 bytes");
+                let (recovery_id, compact) = Secp256k1::new()
+                    .sign_ecdsa_recoverable(&message, &self.secret)
+                    .serialize_compact();
+                let mut signature = [0u8; 65];
+                signature[..64].copy_from_slice(&compact);
+                signature[64] = recovery_id.to_i32() as u8;
+                signature
+            }
+        }
+
+        fn forwarder_address() -> AccountId {
+            ink_env::test::callee::<ink_env::DefaultEnvironment>()
+        }
+
+        fn request(from: AccountId, nonce: u64) -> ForwardRequest {
+            ForwardRequest {
+                from,
+                to: AccountId::from([0x07; 32]),
+                nonce,
+                data: vec![0xde, 0xad, 0xbe, 0xef, 0x01],
+            }
+        }
+
+        #[ink::test]
+        fn valid_request_verifies() {
+            let forwarder = Forwarder::new();
+            let signer = Signer::new(0x11);
+            let req = request(signer.account(), 0);
+            let signature = signer.sign(&forwarder_address(), &req);
+            assert_eq!(forwarder.verify(req, signature), Ok(()));
+        }
+
+        #[ink::test]
+        fn signature_by_someone_else_is_rejected() {
+            let forwarder = Forwarder::new();
+            let signer = Signer::new(0x11);
+            let impostor = Signer::new(0x22);
+            let req = request(signer.account(), 0);
+            let signature = impostor.sign(&forwarder_address(), &req);
+            assert_eq!(forwarder.verify(req, signature), Err(Error::InvalidSignature));
+        }
+
+        #[ink::test]
+        fn tampered_request_is_rejected() {
+            let forwarder = Forwarder::new();
+            let signer = Signer::new(0x11);
+            let req = request(signer.account(), 0);
+            let signature = signer.sign(&forwarder_address(), &req);
+            let mut tampered = req;
+            tampered.data.push(0x02);
+            assert_eq!(forwarder.verify(tampered, signature), Err(Error::InvalidSignature));
+        }
+
+        #[ink::test]
+        fn signature_for_other_forwarder_is_rejected() {
+            let forwarder = Forwarder::new();
+            let signer = Signer::new(0x11);
+            let req = request(signer.account(), 0);
+            let signature = signer.sign(&AccountId::from([0xff; 32]), &req);
+            assert_eq!(forwarder.verify(req, signature), Err(Error::InvalidSignature));
+        }
+
+        #[ink::test]
+        fn wrong_nonce_is_rejected() {
+            let forwarder = Forwarder::new();
+            let signer = Signer::new(0x11);
+            let req = request(signer.account(), 1);
+            let signature = signer.sign(&forwarder_address(), &req);
+            assert_eq!(forwarder.verify(req, signature), Err(Error::InvalidNonce));
+        }
+
+        #[ink::test]
+        fn request_without_selector_is_rejected() {
+            let forwarder = Forwarder::new();
+            let signer = Signer::new(0x11);
+            let mut req = request(signer.account(), 0);
+            req.data = vec![0x01];
+            let signature = signer.sign(&forwarder_address(), &req);
+            assert_eq!(forwarder.verify(req, signature), Err(Error::MissingSelector));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy using the Polkadot JS Apps UI:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your desired network
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy the contract, then deploy forwarder_recipient.rs with its address
+//
+// 4. Interact with the contract:
+//    - Sign a request off-chain with an ECDSA key (see the tests for the format)
+//    - Use the "verify" method to check it, then "execute" from any funded account
+//    - Use the "nonceOf" method to find the signer's next nonce
@@ -0,0 +1,144 @@
+// examples/demo-contracts/forwarder_recipient.rs
+//
+// A meta-transaction recipient contract example for Polkadot using ink!
+// This contract stores a note per account and accepts updates either directly or
+// relayed through a trusted forwarder (see forwarder.rs), in which case the forwarder
+// tells it who the real sender is.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod forwarder_recipient {
+    use ink_prelude::string::String;
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    // Events are mirrored in `crates/events/src/forwarder_recipient.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when an account's note changes.
+    #[ink(event)]
+    pub struct NoteSet {
+        #[ink(topic)]
+        author: AccountId,
+        relayed: bool,
+    }
+
+    /// The recipient storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct ForwarderRecipient {
+        /// The only contract whose sender claims are believed.
+        trusted_forwarder: AccountId,
+        /// Mapping from author to note.
+        notes: Mapping<AccountId, String>,
+    }
+
+    impl ForwarderRecipient {
+        /// Creates a new recipient trusting `trusted_forwarder`.
+        #[ink(constructor)]
+        pub fn new(trusted_forwarder: AccountId) -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.trusted_forwarder = trusted_forwarder;
+            })
+        }
+
+        /// Returns the forwarder this contract trusts.
+        #[ink(message)]
+        pub fn trusted_forwarder(&self) -> AccountId {
+            self.trusted_forwarder
+        }
+
+        /// Returns the note of `author`, if any.
+        #[ink(message)]
+        pub fn note_of(&self, author: AccountId) -> Option<String> {
+            self.notes.get(author)
+        }
+
+        /// Sets the note of `sender`.
+        ///
+        /// `sender` is only believed when the call comes from the trusted forwarder,
+        /// which has checked the sender's signature. Anyone else calling directly
+        /// always writes their own note, whatever `sender` they pass.
+        #[ink(message)]
+        pub fn set_note(&mut self, sender: AccountId, note: String) {
+            let relayed = self.is_trusted_forwarder(self.env().caller());
+            let author = self.effective_caller(sender);
+            self.notes.insert(author, &note);
+            self.env().emit_event(NoteSet { author, relayed });
+        }
+
+        /// Returns whether `account` is the trusted forwarder.
+        fn is_trusted_forwarder(&self, account: AccountId) -> bool {
+            account == self.trusted_forwarder
+        }
+
+        /// Returns the account a call acts for: the claimed sender if the forwarder
+        /// relayed it, otherwise the direct caller.
+        fn effective_caller(&self, claimed: AccountId) -> AccountId {
+            let caller = self.env().caller();
+            if self.is_trusted_forwarder(caller) {
+                claimed
+            } else {
+                caller
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        #[ink::test]
+        fn direct_calls_write_callers_note() {
+            let accounts = fixtures::accounts();
+            let mut recipient = ForwarderRecipient::new(accounts.dave);
+            recipient.set_note(accounts.alice, String::from("hello"));
+            assert_eq!(recipient.note_of(accounts.alice), Some(String::from("hello")));
+        }
+
+        #[ink::test]
+        fn direct_calls_cannot_spoof_sender() {
+            let accounts = fixtures::accounts();
+            let mut recipient = ForwarderRecipient::new(accounts.dave);
+            fixtures::set_caller(accounts.eve);
+            recipient.set_note(accounts.bob, String::from("spoofed"));
+            assert_eq!(recipient.note_of(accounts.bob), None);
+            assert_eq!(recipient.note_of(accounts.eve), Some(String::from("spoofed")));
+        }
+
+        #[ink::test]
+        fn forwarder_calls_write_senders_note() {
+            let accounts = fixtures::accounts();
+            let mut recipient = ForwarderRecipient::new(accounts.dave);
+            fixtures::set_caller(accounts.dave);
+            recipient.set_note(accounts.bob, String::from("relayed"));
+            assert_eq!(recipient.note_of(accounts.bob), Some(String::from("relayed")));
+            assert_eq!(recipient.note_of(accounts.dave), None);
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Deploy forwarder.rs first and note its address.
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy using the Polkadot JS Apps UI:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your desired network
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Set the forwarder address as the trusted forwarder
+//    - Deploy the contract
+//
+// 4. Interact with the contract:
+//    - Call "setNote" directly to write your own note
+//    - Relay a signed "setNote" request through the forwarder to write a note
+//      for an account that holds no funds
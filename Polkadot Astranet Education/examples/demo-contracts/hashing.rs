@@ -0,0 +1,142 @@
+// examples/demo-contracts/hashing.rs
+//
+// A hashing contract example for Polkadot using ink!
+// This contract exposes the hash functions available to contracts (BLAKE2, Keccak,
+// SHA2) so learners can compare their outputs from the Polkadot JS Apps UI. The
+// Merkle proof, commit-reveal, and signature lessons build on these primitives.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod hashing {
+    use astranet_utils::hashing;
+    use ink_prelude::vec::Vec;
+
+    /// The output of every supported hash function for the same input.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Digests {
+        pub blake2_256: [u8; 32],
+        pub blake2_128: [u8; 16],
+        pub keccak_256: [u8; 32],
+        pub sha2_256: [u8; 32],
+    }
+
+    /// The hashing contract holds no state; every message is a pure function.
+    #[ink(storage)]
+    pub struct Hashing {}
+
+    impl Hashing {
+        /// Creates a new hashing contract.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {}
+        }
+
+        /// Hashes `input` with BLAKE2b-256, the hash Substrate uses internally.
+        #[ink(message)]
+        pub fn blake2_256(&self, input: Vec<u8>) -> [u8; 32] {
+            hashing::blake2_256(&input)
+        }
+
+        /// Hashes `input` with BLAKE2b-128.
+        #[ink(message)]
+        pub fn blake2_128(&self, input: Vec<u8>) -> [u8; 16] {
+            hashing::blake2_128(&input)
+        }
+
+        /// Hashes `input` with Keccak-256, the hash Ethereum uses.
+        #[ink(message)]
+        pub fn keccak_256(&self, input: Vec<u8>) -> [u8; 32] {
+            hashing::keccak_256(&input)
+        }
+
+        /// Hashes `input` with SHA2-256.
+        #[ink(message)]
+        pub fn sha2_256(&self, input: Vec<u8>) -> [u8; 32] {
+            hashing::sha2_256(&input)
+        }
+
+        /// Hashes `input` with every supported algorithm at once.
+        #[ink(message)]
+        pub fn digests(&self, input: Vec<u8>) -> Digests {
+            Digests {
+                blake2_256: hashing::blake2_256(&input),
+                blake2_128: hashing::blake2_128(&input),
+                keccak_256: hashing::keccak_256(&input),
+                sha2_256: hashing::sha2_256(&input),
+            }
+        }
+
+        /// Returns whether `input` hashes to `expected` under BLAKE2b-256, the check
+        /// at the heart of commit-reveal schemes.
+        #[ink(message)]
+        pub fn matches_commitment(&self, input: Vec<u8>, expected: [u8; 32]) -> bool {
+            hashing::blake2_256(&input) == expected
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_lang as ink;
+
+        #[ink::test]
+        fn messages_match_utils() {
+            let contract = Hashing::new();
+            let input = b"polkadot".to_vec();
+            assert_eq!(contract.blake2_256(input.clone()), hashing::blake2_256(&input));
+            assert_eq!(contract.blake2_128(input.clone()), hashing::blake2_128(&input));
+            assert_eq!(contract.keccak_256(input.clone()), hashing::keccak_256(&input));
+            assert_eq!(contract.sha2_256(input.clone()), hashing::sha2_256(&input));
+        }
+
+        #[ink::test]
+        fn digests_differ_per_algorithm() {
+            let contract = Hashing::new();
+            let digests = contract.digests(b"polkadot".to_vec());
+            assert_ne!(digests.blake2_256, digests.keccak_256);
+            assert_ne!(digests.keccak_256, digests.sha2_256);
+            assert_eq!(digests.blake2_128.len(), 16);
+        }
+
+        #[ink::test]
+        fn sha2_known_vector() {
+            let contract = Hashing::new();
+            assert_eq!(
+                contract.sha2_256(b"abc".to_vec())[..4],
+                [0xba, 0x78, 0x16, 0xbf]
+            );
+        }
+
+        #[ink::test]
+        fn commitment_check_works() {
+            let contract = Hashing::new();
+            let commitment = contract.blake2_256(b"secret".to_vec());
+            assert!(contract.matches_commitment(b"secret".to_vec(), commitment));
+            assert!(!contract.matches_commitment(b"guess".to_vec(), commitment));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy using the Polkadot JS Apps UI:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your desired network
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy the contract
+//
+// 4. Interact with the contract:
+//    - Use the "digests" method to hash the same input with every algorithm
+//    - Use the "matchesCommitment" method to check a revealed value against its hash
@@ -0,0 +1,676 @@
+// examples/demo-contracts/insurance.rs
+//
+// A parametric insurance pool contract example for Polkadot using ink!
+// Policyholders pay a premium for coverage against an index reported by an oracle,
+// for instance rainfall in a growing region: when the index falls below a trigger
+// level, policies pay out. Premiums and the admin's capital form one pool that
+// backs every policy.
+//
+// Payout ratio
+// ------------
+// Payouts grow linearly as the index falls from the `trigger` level to the
+// `exhaust` level: nothing at or above the trigger, the full coverage at or below
+// the exhaust level, and the matching share of it in between; see `payout_ratio`.
+// A claim snapshots the oracle value when it is filed, so a later recovery of the
+// index does not shrink it.
+//
+// Assessment
+// ----------
+// The oracle only says that the condition happened, not that the policyholder
+// suffered it, so claims do not pay automatically. An assessor, a role the admin
+// appoints, approves or rejects each filed claim. A rejected claim leaves the policy
+// active.
+//
+// Pooled risk
+// -----------
+// Every policy is fully backed: buying coverage locks that much of the pool until
+// the policy pays out or expires, and a policy is only sold while the unlocked pool
+// covers it. A real insurer would sell more coverage than it holds, betting that
+// not every policy claims at once; this example keeps the pool solvent in the worst
+// case instead.
+//
+// A policy's expiry is the utils crate's inclusive `Deadline`: claims can still be
+// filed at exactly `expires_at`, and the policy can be expired the millisecond
+// after.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod insurance {
+    use astranet_utils::deadline::Deadline;
+    use ink_env::call::{build_call, Call, ExecutionInput, Selector};
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Identifies a policy.
+    pub type PolicyId = u32;
+
+    /// Premiums and payout ratios are in basis points of this.
+    pub const BPS: u128 = 10_000;
+
+    /// Selector of the oracle's `price(asset)` message.
+    const PRICE_SELECTOR: [u8; 4] = [0xd4, 0xbd, 0x7b, 0xc1];
+
+    /// The insurance error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the admin.
+        NotAdmin,
+        /// Returned if the caller is not the assessor.
+        NotAssessor,
+        /// Returned if the amount is zero.
+        ZeroAmount,
+        /// Returned if the value sent along is not the premium.
+        WrongPremium,
+        /// Returned if the unlocked pool cannot back the coverage or withdrawal.
+        InsufficientCapacity,
+        /// Returned if there is no policy with the id.
+        PolicyNotFound,
+        /// Returned if the caller does not hold the policy.
+        NotPolicyholder,
+        /// Returned if the policy has expired.
+        PolicyExpired,
+        /// Returned if the policy has not expired yet.
+        PolicyActive,
+        /// Returned if the policy already has a claim awaiting assessment.
+        ClaimPending,
+        /// Returned if the policy has no claim awaiting assessment.
+        NoClaim,
+        /// Returned if the oracle value does not trigger a payout.
+        ConditionNotMet,
+        /// Returned if the oracle has no usable value for the index.
+        OracleUnavailable,
+        /// Returned if the native transfer failed.
+        TransferFailed,
+        /// Returned if the premium or payout math overflows.
+        Overflow,
+    }
+
+    /// The insurance result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Stands in for the oracle's error type, which encodes as a bare variant
+    /// index; all this contract needs is to tell success from failure.
+    #[derive(scale::Decode)]
+    struct CalleeError(#[allow(dead_code)] u8);
+
+    /// A claim awaiting assessment.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Claim {
+        /// Oracle value when the claim was filed.
+        pub observed: Balance,
+        /// Payout if approved.
+        pub amount: Balance,
+    }
+
+    /// A policy.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Policy {
+        /// Account paid on an approved claim.
+        pub holder: AccountId,
+        /// Largest possible payout, locked in the pool.
+        pub coverage: Balance,
+        /// Last time the policy can claim; it can be expired after it.
+        pub expires_at: Deadline,
+        /// The claim awaiting assessment, if any.
+        pub claim: Option<Claim>,
+    }
+
+    /// Returns the share of coverage paid out, in basis points, when the index is
+    /// at `observed`: 0 at or above `trigger`, `BPS` at or below `exhaust`, and
+    /// linear in between.
+    pub fn payout_ratio(observed: Balance, trigger: Balance, exhaust: Balance) -> u128 {
+        if observed >= trigger {
+            0
+        } else if observed <= exhaust {
+            BPS
+        } else {
+            (trigger - observed) * BPS / (trigger - exhaust)
+        }
+    }
+
+    /// Returns `ratio` basis points of `amount`, rounded down.
+    pub fn apply_bps(amount: Balance, ratio: u128) -> Result<Balance> {
+        amount
+            .checked_mul(ratio)
+            .map(|scaled| scaled / BPS)
+            .ok_or(Error::Overflow)
+    }
+
+    // Events are mirrored in `crates/events/src/insurance.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when capital is added to the pool.
+    #[ink(event)]
+    pub struct Funded {
+        #[ink(topic)]
+        funder: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when a policy is bought.
+    #[ink(event)]
+    pub struct PolicyBought {
+        #[ink(topic)]
+        policy_id: PolicyId,
+        #[ink(topic)]
+        holder: AccountId,
+        coverage: Balance,
+        premium: Balance,
+        expires_at: Timestamp,
+    }
+
+    /// Event emitted when a policyholder files a claim.
+    #[ink(event)]
+    pub struct ClaimFiled {
+        #[ink(topic)]
+        policy_id: PolicyId,
+        observed: Balance,
+        amount: Balance,
+    }
+
+    /// Event emitted when the assessor approves or rejects a claim.
+    #[ink(event)]
+    pub struct ClaimAssessed {
+        #[ink(topic)]
+        policy_id: PolicyId,
+        approved: bool,
+        amount: Balance,
+    }
+
+    /// Event emitted when an expired policy releases its coverage.
+    #[ink(event)]
+    pub struct PolicyExpired {
+        #[ink(topic)]
+        policy_id: PolicyId,
+    }
+
+    /// Event emitted when the admin withdraws unlocked capital.
+    #[ink(event)]
+    pub struct Withdrawn {
+        amount: Balance,
+    }
+
+    /// The insurance storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Insurance {
+        /// Account that appoints the assessor and manages capital.
+        admin: AccountId,
+        /// Account that approves or rejects claims.
+        assessor: AccountId,
+        /// Oracle reporting the index.
+        oracle: AccountId,
+        /// Asset id under which the oracle reports the index.
+        index: AccountId,
+        /// Index level below which policies start paying out.
+        trigger: Balance,
+        /// Index level at or below which policies pay their full coverage.
+        exhaust: Balance,
+        /// Premium, in basis points of the coverage.
+        premium_bps: u128,
+        /// How long a policy lasts, in milliseconds.
+        term: Timestamp,
+        /// Funds held for policies and capital.
+        pool: Balance,
+        /// Part of the pool backing active policies.
+        locked: Balance,
+        /// Mapping from policy id to the policy.
+        policies: Mapping<PolicyId, Policy>,
+        /// Id the next policy will get.
+        next_policy_id: PolicyId,
+    }
+
+    impl Insurance {
+        /// Creates an empty pool insuring against the `index` reported by `oracle`
+        /// falling below `trigger`, paying in full at `exhaust`, with the caller as
+        /// admin and assessor. Policies cost `premium_bps` of their coverage and
+        /// last `term` milliseconds.
+        #[ink(constructor)]
+        pub fn new(
+            oracle: AccountId,
+            index: AccountId,
+            trigger: Balance,
+            exhaust: Balance,
+            premium_bps: u128,
+            term: Timestamp,
+        ) -> Self {
+            ink_lang::utils::initialize_contract(|pool: &mut Self| {
+                let caller = Self::env().caller();
+                pool.admin = caller;
+                pool.assessor = caller;
+                pool.oracle = oracle;
+                pool.index = index;
+                pool.trigger = trigger;
+                pool.exhaust = exhaust;
+                pool.premium_bps = premium_bps;
+                pool.term = term;
+            })
+        }
+
+        /// Returns the assessor.
+        #[ink(message)]
+        pub fn assessor(&self) -> AccountId {
+            self.assessor
+        }
+
+        /// Returns the funds held for policies and capital.
+        #[ink(message)]
+        pub fn pool(&self) -> Balance {
+            self.pool
+        }
+
+        /// Returns the part of the pool backing active policies.
+        #[ink(message)]
+        pub fn locked(&self) -> Balance {
+            self.locked
+        }
+
+        /// Returns the policy with `policy_id`, if any.
+        #[ink(message)]
+        pub fn policy(&self, policy_id: PolicyId) -> Option<Policy> {
+            self.policies.get(policy_id)
+        }
+
+        /// Returns the premium for `coverage`.
+        #[ink(message)]
+        pub fn premium_for(&self, coverage: Balance) -> Result<Balance> {
+            apply_bps(coverage, self.premium_bps)
+        }
+
+        /// Returns what a policy with `coverage` would be paid at index `observed`.
+        #[ink(message)]
+        pub fn payout_for(&self, coverage: Balance, observed: Balance) -> Result<Balance> {
+            apply_bps(coverage, payout_ratio(observed, self.trigger, self.exhaust))
+        }
+
+        /// Adds the value sent along to the pool as capital.
+        #[ink(message, payable)]
+        pub fn fund(&mut self) -> Result<()> {
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.pool += amount;
+            self.env().emit_event(Funded {
+                funder: self.env().caller(),
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Buys a policy with `coverage` for the caller, who must send exactly
+        /// `premium_for(coverage)` along. Returns the policy id.
+        #[ink(message, payable)]
+        pub fn buy_policy(&mut self, coverage: Balance) -> Result<PolicyId> {
+            if coverage == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let premium = self.premium_for(coverage)?;
+            if self.env().transferred_value() != premium {
+                return Err(Error::WrongPremium);
+            }
+            // The premium joins the pool and helps back the policy.
+            let pool = self.pool + premium;
+            if pool - self.locked < coverage {
+                return Err(Error::InsufficientCapacity);
+            }
+            self.pool = pool;
+            self.locked += coverage;
+
+            let policy_id = self.next_policy_id;
+            self.next_policy_id += 1;
+            let holder = self.env().caller();
+            let expires_at = self.env().block_timestamp().saturating_add(self.term);
+            self.policies.insert(
+                policy_id,
+                &Policy {
+                    holder,
+                    coverage,
+                    expires_at: Deadline::at(expires_at),
+                    claim: None,
+                },
+            );
+            self.env().emit_event(PolicyBought {
+                policy_id,
+                holder,
+                coverage,
+                premium,
+                expires_at,
+            });
+            Ok(policy_id)
+        }
+
+        /// Files a claim on the caller's policy at the current oracle value.
+        /// Returns the payout the claim asks for.
+        #[ink(message)]
+        pub fn file_claim(&mut self, policy_id: PolicyId) -> Result<Balance> {
+            let observed = self.index_value()?;
+            self.file_claim_at(policy_id, observed)
+        }
+
+        /// Approves or rejects the claim on `policy_id`, paying it out if approved.
+        /// Only the assessor can call this.
+        #[ink(message)]
+        pub fn assess(&mut self, policy_id: PolicyId, approve: bool) -> Result<()> {
+            if self.env().caller() != self.assessor {
+                return Err(Error::NotAssessor);
+            }
+            let policy = self.policy(policy_id).ok_or(Error::PolicyNotFound)?;
+            let claim = policy.claim.ok_or(Error::NoClaim)?;
+            if !approve {
+                self.policies.insert(
+                    policy_id,
+                    &Policy {
+                        claim: None,
+                        ..policy
+                    },
+                );
+                self.env().emit_event(ClaimAssessed {
+                    policy_id,
+                    approved: false,
+                    amount: 0,
+                });
+                return Ok(());
+            }
+
+            self.policies.remove(policy_id);
+            self.locked -= policy.coverage;
+            self.pool -= claim.amount;
+            if self.env().transfer(policy.holder, claim.amount).is_err() {
+                self.policies.insert(policy_id, &policy);
+                self.locked += policy.coverage;
+                self.pool += claim.amount;
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(ClaimAssessed {
+                policy_id,
+                approved: true,
+                amount: claim.amount,
+            });
+            Ok(())
+        }
+
+        /// Ends `policy_id` after it expired without a pending claim, releasing its
+        /// coverage. Anyone can call this.
+        #[ink(message)]
+        pub fn expire(&mut self, policy_id: PolicyId) -> Result<()> {
+            let policy = self.policy(policy_id).ok_or(Error::PolicyNotFound)?;
+            if !policy.expires_at.is_expired(self.env().block_timestamp()) {
+                return Err(Error::PolicyActive);
+            }
+            if policy.claim.is_some() {
+                return Err(Error::ClaimPending);
+            }
+            self.policies.remove(policy_id);
+            self.locked -= policy.coverage;
+            self.env().emit_event(PolicyExpired { policy_id });
+            Ok(())
+        }
+
+        /// Appoints `assessor`. Only the admin can call this.
+        #[ink(message)]
+        pub fn set_assessor(&mut self, assessor: AccountId) -> Result<()> {
+            self.ensure_admin()?;
+            self.assessor = assessor;
+            Ok(())
+        }
+
+        /// Sends `amount` of unlocked capital to the admin. Only the admin can call
+        /// this.
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: Balance) -> Result<()> {
+            self.ensure_admin()?;
+            if amount > self.pool - self.locked {
+                return Err(Error::InsufficientCapacity);
+            }
+            self.pool -= amount;
+            if self.env().transfer(self.admin, amount).is_err() {
+                self.pool += amount;
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(Withdrawn { amount });
+            Ok(())
+        }
+
+        /// Files a claim on the caller's policy as if the oracle reported
+        /// `observed`.
+        fn file_claim_at(&mut self, policy_id: PolicyId, observed: Balance) -> Result<Balance> {
+            let policy = self.policy(policy_id).ok_or(Error::PolicyNotFound)?;
+            if self.env().caller() != policy.holder {
+                return Err(Error::NotPolicyholder);
+            }
+            if policy.expires_at.is_expired(self.env().block_timestamp()) {
+                return Err(Error::PolicyExpired);
+            }
+            if policy.claim.is_some() {
+                return Err(Error::ClaimPending);
+            }
+            let amount = self.payout_for(policy.coverage, observed)?;
+            if amount == 0 {
+                return Err(Error::ConditionNotMet);
+            }
+            self.policies.insert(
+                policy_id,
+                &Policy {
+                    claim: Some(Claim { observed, amount }),
+                    ..policy
+                },
+            );
+            self.env().emit_event(ClaimFiled {
+                policy_id,
+                observed,
+                amount,
+            });
+            Ok(amount)
+        }
+
+        /// Reads the index from the oracle.
+        fn index_value(&self) -> Result<Balance> {
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.oracle).gas_limit(0))
+                .exec_input(ExecutionInput::new(Selector::new(PRICE_SELECTOR)).push_arg(self.index))
+                .returns::<core::result::Result<Balance, CalleeError>>()
+                .fire()
+                .map_err(|_| Error::OracleUnavailable)?
+                .map_err(|_| Error::OracleUnavailable)
+        }
+
+        /// Returns `NotAdmin` unless the caller is the admin.
+        fn ensure_admin(&self) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAdmin);
+            }
+            Ok(())
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// file claims with `file_claim_at` instead of reading the oracle.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use fixtures::BLOCK_TIME;
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        const TRIGGER: Balance = 100;
+        const EXHAUST: Balance = 60;
+        const TERM: Timestamp = 200 * BLOCK_TIME;
+
+        fn pay<T>(value: Balance, call: impl FnOnce() -> T) -> T {
+            ink_env::test::set_value_transferred::<Env>(value);
+            let result = call();
+            ink_env::test::set_value_transferred::<Env>(0);
+            result
+        }
+
+        /// A pool with 1_000 of capital charging 5% premiums, where bob holds
+        /// policy 0 covering 400.
+        fn insured() -> Insurance {
+            let accounts = fixtures::accounts();
+            let mut pool = Insurance::new(
+                AccountId::from([0x0e; 32]),
+                AccountId::from([0x1d; 32]),
+                TRIGGER,
+                EXHAUST,
+                500,
+                TERM,
+            );
+            assert_eq!(pay(1_000, || pool.fund()), Ok(()));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(pay(20, || pool.buy_policy(400)), Ok(0));
+            fixtures::set_caller(accounts.alice);
+            fixtures::fund_contract(1_020);
+            fixtures::fund(accounts.bob, 0);
+            pool
+        }
+
+        #[ink::test]
+        fn payout_ratio_is_linear_between_levels() {
+            assert_eq!(payout_ratio(120, TRIGGER, EXHAUST), 0);
+            assert_eq!(payout_ratio(TRIGGER, TRIGGER, EXHAUST), 0);
+            assert_eq!(payout_ratio(99, TRIGGER, EXHAUST), 250);
+            assert_eq!(payout_ratio(80, TRIGGER, EXHAUST), 5_000);
+            assert_eq!(payout_ratio(61, TRIGGER, EXHAUST), 9_750);
+            assert_eq!(payout_ratio(EXHAUST, TRIGGER, EXHAUST), BPS);
+            assert_eq!(payout_ratio(0, TRIGGER, EXHAUST), BPS);
+            assert_eq!(apply_bps(400, 250), Ok(10));
+            assert_eq!(apply_bps(Balance::MAX, 2), Err(Error::Overflow));
+        }
+
+        #[ink::test]
+        fn premiums_back_fully_locked_coverage() {
+            let accounts = fixtures::accounts();
+            let mut pool = insured();
+            assert_eq!(pool.pool(), 1_020);
+            assert_eq!(pool.locked(), 400);
+
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(pay(10, || pool.buy_policy(400)), Err(Error::WrongPremium));
+            // 620 is unlocked, plus the 32 premium.
+            assert_eq!(
+                pay(33, || pool.buy_policy(660)),
+                Err(Error::InsufficientCapacity)
+            );
+            assert_eq!(pay(32, || pool.buy_policy(640)), Ok(1));
+            assert_eq!(pool.locked(), 1_040);
+            assert_eq!(pool.pool(), 1_052);
+        }
+
+        #[ink::test]
+        fn approved_claim_pays_the_ratio() {
+            let accounts = fixtures::accounts();
+            let mut pool = insured();
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(pool.file_claim_at(0, TRIGGER), Err(Error::ConditionNotMet));
+            assert_eq!(pool.file_claim_at(0, 70), Ok(300));
+            assert_eq!(pool.file_claim_at(0, 50), Err(Error::ClaimPending));
+
+            assert_eq!(pool.assess(0, true), Err(Error::NotAssessor));
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(pool.assess(0, true), Ok(()));
+            assert_eq!(fixtures::balance_of(accounts.bob), 300);
+            assert_eq!(pool.pool(), 720);
+            assert_eq!(pool.locked(), 0);
+            assert_eq!(pool.policy(0), None);
+        }
+
+        #[ink::test]
+        fn rejected_claim_keeps_policy() {
+            let accounts = fixtures::accounts();
+            let mut pool = insured();
+            assert_eq!(pool.set_assessor(accounts.eve), Ok(()));
+            fixtures::with_caller(accounts.bob, || pool.file_claim_at(0, 0)).expect("filed");
+
+            fixtures::set_caller(accounts.eve);
+            assert_eq!(pool.assess(0, false), Ok(()));
+            assert_eq!(pool.assess(0, false), Err(Error::NoClaim));
+            assert_eq!(pool.policy(0).map(|policy| policy.claim), Some(None));
+            assert_eq!(pool.locked(), 400);
+        }
+
+        #[ink::test]
+        fn claims_stop_at_expiry() {
+            let accounts = fixtures::accounts();
+            let mut pool = insured();
+            assert_eq!(pool.expire(0), Err(Error::PolicyActive));
+
+            // The expiry itself still belongs to the term.
+            fixtures::set_block_timestamp(TERM);
+            assert_eq!(pool.expire(0), Err(Error::PolicyActive));
+            fixtures::with_caller(accounts.bob, || pool.file_claim_at(0, 0)).expect("filed");
+            assert_eq!(pool.assess(0, false), Ok(()));
+
+            fixtures::set_block_timestamp(TERM + BLOCK_TIME);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(pool.file_claim_at(0, 0), Err(Error::PolicyExpired));
+            assert_eq!(pool.expire(0), Ok(()));
+            assert_eq!(pool.locked(), 0);
+            assert_eq!(pool.expire(0), Err(Error::PolicyNotFound));
+        }
+
+        #[ink::test]
+        fn only_holder_claims() {
+            let accounts = fixtures::accounts();
+            let mut pool = insured();
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(pool.file_claim_at(0, 0), Err(Error::NotPolicyholder));
+            assert_eq!(pool.file_claim_at(1, 0), Err(Error::PolicyNotFound));
+        }
+
+        #[ink::test]
+        fn locked_capital_cannot_be_withdrawn() {
+            let accounts = fixtures::accounts();
+            let mut pool = insured();
+            assert_eq!(pool.withdraw(621), Err(Error::InsufficientCapacity));
+            assert_eq!(pool.withdraw(620), Ok(()));
+            assert_eq!(pool.pool(), 400);
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(pool.withdraw(1), Err(Error::NotAdmin));
+            assert_eq!(pool.set_assessor(accounts.bob), Err(Error::NotAdmin));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Deploy an oracle first (examples/mocks/mock_oracle.rs for testing)
+//    - Upload the generated .contract file and deploy it with the oracle, the
+//      index id, the trigger and exhaust levels, a premium and a term
+//
+// 4. Interact with the contract:
+//    - As admin, use "fund" with some value
+//    - Use "premiumFor", then "buyPolicy" sending that premium along
+//    - When the oracle reports the index below the trigger, the holder uses
+//      "fileClaim" and the assessor "assess"
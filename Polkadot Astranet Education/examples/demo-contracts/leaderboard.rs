@@ -0,0 +1,488 @@
+// examples/demo-contracts/leaderboard.rs
+//
+// A leaderboard contract example for Polkadot using ink!
+// Learners' scores are recorded by a grader, an account whose key belongs to the
+// course's grading service rather than to any learner, and the contract keeps the
+// best `capacity` of them in rank order. The admin who deploys the contract can
+// rotate the grader's key with `set_grader` if it leaks.
+//
+// Ranking
+// -------
+// Each learner has one best score; a new score replaces it only if it is higher. The
+// board ranks best scores from highest to lowest, and equal scores by who reached
+// them first, so a learner cannot overtake a tie by resubmitting the same score. Every
+// score improvement takes a sequence number from a counter, and among equal scores the
+// lower number ranks first. Sequence numbers rather than timestamps break the ties
+// because several scores can be recorded in the same block.
+//
+// The board is full once it holds `capacity` learners. A score that would rank below
+// the last of a full board, or tie with it, does not enter; one that ranks higher
+// enters and the last learner drops off. Since scores only improve, the board always
+// holds the best `capacity` learners.
+//
+// Storage
+// -------
+// The board is a `Vec` sorted by rank, at most `MAX_CAPACITY` entries stored together
+// in one cell, so showing it costs a single storage read. A score finds its place by
+// binary search and is inserted there, a shift of at most `MAX_CAPACITY` entries in
+// memory, instead of re-sorting the board. Every learner's best score, on the board
+// or not, is also kept in a `Mapping`, from which `rank_of` finds the learner's entry
+// by binary search too.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod leaderboard {
+    use astranet_utils::validation;
+    use ink_prelude::vec::Vec;
+    use ink_storage::{
+        traits::{PackedAllocate, PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Most learners a board can hold.
+    pub const MAX_CAPACITY: u32 = 100;
+
+    /// The leaderboard error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the admin.
+        NotAdmin,
+        /// Returned if the caller is not the grader.
+        NotGrader,
+    }
+
+    /// The leaderboard result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A learner's best score.
+    #[derive(
+        Debug,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        scale::Encode,
+        scale::Decode,
+        SpreadLayout,
+        PackedLayout,
+        SpreadAllocate,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Entry {
+        /// Learner who scored.
+        pub learner: AccountId,
+        /// The score.
+        pub score: u32,
+        /// Position of the score in the order scores were recorded; the lower of
+        /// two equal scores ranks first.
+        pub seq: u64,
+    }
+
+    // Lets the board `Vec<Entry>` be default-allocated by `initialize_contract`.
+    // There is no derive for it; an entry holds no storage of its own, so there is
+    // nothing to allocate.
+    impl PackedAllocate for Entry {
+        fn allocate_packed(&mut self, _at: &ink_primitives::Key) {}
+    }
+
+    impl Entry {
+        /// Whether `self` ranks above `other`.
+        fn ranks_above(&self, other: &Entry) -> bool {
+            self.score > other.score || (self.score == other.score && self.seq < other.seq)
+        }
+    }
+
+    /// Returns the index of `entry` in `board`, which is sorted by rank, if it is
+    /// there.
+    fn position(board: &[Entry], entry: &Entry) -> Option<usize> {
+        let index = board.partition_point(|other| other.ranks_above(entry));
+        (board.get(index) == Some(entry)).then_some(index)
+    }
+
+    /// Inserts `entry` into `board` at its rank, keeping at most `capacity` entries,
+    /// and returns its index, or `None` if it ranks below every entry of a full
+    /// board.
+    fn insert(board: &mut Vec<Entry>, entry: Entry, capacity: usize) -> Option<usize> {
+        let index = board.partition_point(|other| other.ranks_above(&entry));
+        if index >= capacity {
+            return None;
+        }
+        board.insert(index, entry);
+        board.truncate(capacity);
+        Some(index)
+    }
+
+    // Events are mirrored in `crates/events/src/leaderboard.rs` for off-chain
+    // decoding; keep the order of events and fields in sync with it.
+
+    /// Event emitted when a learner's best score improves. `rank` is their rank
+    /// afterwards, from 1, or `None` if they are not on the board.
+    #[ink(event)]
+    pub struct ScoreRecorded {
+        #[ink(topic)]
+        learner: AccountId,
+        score: u32,
+        rank: Option<u32>,
+    }
+
+    /// Event emitted when the admin rotates the grader's key.
+    #[ink(event)]
+    pub struct GraderChanged {
+        #[ink(topic)]
+        grader: AccountId,
+    }
+
+    /// The leaderboard storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Leaderboard {
+        /// Account that may rotate the grader.
+        admin: AccountId,
+        /// Account that records scores.
+        grader: AccountId,
+        /// Most learners on the board.
+        capacity: u32,
+        /// The best learners, highest rank first.
+        board: Vec<Entry>,
+        /// Mapping from learner to their best score and its sequence number.
+        best: Mapping<AccountId, (u32, u64)>,
+        /// Sequence number of the next score improvement.
+        next_seq: u64,
+    }
+
+    impl Leaderboard {
+        /// Creates an empty board of `capacity` learners, whose scores `grader`
+        /// records, with the caller as admin. Panics unless `capacity` is between 1
+        /// and `MAX_CAPACITY`.
+        #[ink(constructor)]
+        pub fn new(grader: AccountId, capacity: u32) -> Self {
+            validation::require("capacity", validation::non_zero(capacity));
+            validation::require("capacity", validation::at_most(capacity, MAX_CAPACITY));
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.admin = Self::env().caller();
+                contract.grader = grader;
+                contract.capacity = capacity;
+            })
+        }
+
+        /// Returns the admin.
+        #[ink(message)]
+        pub fn admin(&self) -> AccountId {
+            self.admin
+        }
+
+        /// Returns the grader.
+        #[ink(message)]
+        pub fn grader(&self) -> AccountId {
+            self.grader
+        }
+
+        /// Returns the most learners the board holds.
+        #[ink(message)]
+        pub fn capacity(&self) -> u32 {
+            self.capacity
+        }
+
+        /// Returns the board, highest rank first.
+        #[ink(message)]
+        pub fn board(&self) -> Vec<Entry> {
+            self.board.clone()
+        }
+
+        /// Returns the best score of `learner`, if they have one.
+        #[ink(message)]
+        pub fn best_score(&self, learner: AccountId) -> Option<u32> {
+            self.best.get(learner).map(|(score, _)| score)
+        }
+
+        /// Returns the rank of `learner` on the board, from 1, or `None` if they are
+        /// not on it.
+        #[ink(message)]
+        pub fn rank_of(&self, learner: AccountId) -> Option<u32> {
+            let (score, seq) = self.best.get(learner)?;
+            let entry = Entry {
+                learner,
+                score,
+                seq,
+            };
+            position(&self.board, &entry).map(|index| index as u32 + 1)
+        }
+
+        /// Records `score` for `learner` and returns their rank afterwards, from 1,
+        /// or `None` if they are not on the board. A score no higher than the
+        /// learner's best changes nothing. Only the grader can call this.
+        #[ink(message)]
+        pub fn submit_score(&mut self, learner: AccountId, score: u32) -> Result<Option<u32>> {
+            if self.env().caller() != self.grader {
+                return Err(Error::NotGrader);
+            }
+            let previous = self.best.get(learner);
+            if let Some((best, _)) = previous {
+                if score <= best {
+                    return Ok(self.rank_of(learner));
+                }
+            }
+
+            // Take the learner's old entry off the board before placing the new one,
+            // so they appear at most once.
+            if let Some((best, seq)) = previous {
+                let old = Entry {
+                    learner,
+                    score: best,
+                    seq,
+                };
+                if let Some(index) = position(&self.board, &old) {
+                    self.board.remove(index);
+                }
+            }
+            let seq = self.next_seq;
+            self.next_seq += 1;
+            self.best.insert(learner, &(score, seq));
+            let entry = Entry {
+                learner,
+                score,
+                seq,
+            };
+            let rank = insert(&mut self.board, entry, self.capacity as usize)
+                .map(|index| index as u32 + 1);
+            self.env().emit_event(ScoreRecorded {
+                learner,
+                score,
+                rank,
+            });
+            Ok(rank)
+        }
+
+        /// Lets `grader` record scores instead of the current grader. Only the admin
+        /// can call this.
+        #[ink(message)]
+        pub fn set_grader(&mut self, grader: AccountId) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAdmin);
+            }
+            self.grader = grader;
+            self.env().emit_event(GraderChanged { grader });
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        /// A board of `capacity` learners deployed by alice, with bob as grader.
+        /// Bob is left the caller.
+        fn board_of(capacity: u32) -> Leaderboard {
+            let accounts = fixtures::accounts();
+            let board = Leaderboard::new(accounts.bob, capacity);
+            fixtures::set_caller(accounts.bob);
+            board
+        }
+
+        fn learners(board: &Leaderboard) -> Vec<AccountId> {
+            board.board().iter().map(|entry| entry.learner).collect()
+        }
+
+        fn scores(board: &Leaderboard) -> Vec<u32> {
+            board.board().iter().map(|entry| entry.score).collect()
+        }
+
+        #[ink::test]
+        fn scores_are_kept_in_rank_order() {
+            let learner = fixtures::learner;
+            let mut board = board_of(5);
+            assert_eq!(board.submit_score(learner(0), 50), Ok(Some(1)));
+            assert_eq!(board.submit_score(learner(1), 80), Ok(Some(1)));
+            assert_eq!(board.submit_score(learner(2), 65), Ok(Some(2)));
+            assert_eq!(board.submit_score(learner(3), 10), Ok(Some(4)));
+            assert_eq!(scores(&board), vec![80, 65, 50, 10]);
+            assert_eq!(
+                learners(&board),
+                vec![learner(1), learner(2), learner(0), learner(3)]
+            );
+            assert_eq!(board.rank_of(learner(0)), Some(3));
+            assert_eq!(board.rank_of(learner(4)), None);
+            assert_eq!(board.best_score(learner(2)), Some(65));
+            assert_eq!(ink_env::test::recorded_events().count(), 4);
+        }
+
+        #[ink::test]
+        fn equal_scores_rank_by_who_reached_them_first() {
+            let learner = fixtures::learner;
+            let mut board = board_of(5);
+            assert_eq!(board.submit_score(learner(0), 70), Ok(Some(1)));
+            assert_eq!(board.submit_score(learner(1), 70), Ok(Some(2)));
+            assert_eq!(board.submit_score(learner(2), 90), Ok(Some(1)));
+            assert_eq!(board.submit_score(learner(3), 70), Ok(Some(4)));
+            assert_eq!(
+                learners(&board),
+                vec![learner(2), learner(0), learner(1), learner(3)]
+            );
+
+            // Improving to a tie ranks the learner after those already there.
+            assert_eq!(board.submit_score(learner(4), 40), Ok(Some(5)));
+            assert_eq!(board.submit_score(learner(4), 70), Ok(Some(5)));
+            assert_eq!(board.rank_of(learner(3)), Some(4));
+        }
+
+        #[ink::test]
+        fn resubmitting_a_tie_keeps_the_earlier_rank() {
+            let learner = fixtures::learner;
+            let mut board = board_of(5);
+            assert_eq!(board.submit_score(learner(0), 70), Ok(Some(1)));
+            assert_eq!(board.submit_score(learner(1), 70), Ok(Some(2)));
+            // Neither an equal nor a lower score moves learner 1 ahead of learner 0,
+            // or learner 0 behind.
+            assert_eq!(board.submit_score(learner(1), 70), Ok(Some(2)));
+            assert_eq!(board.submit_score(learner(0), 70), Ok(Some(1)));
+            assert_eq!(board.submit_score(learner(0), 20), Ok(Some(1)));
+            assert_eq!(board.best_score(learner(0)), Some(70));
+            assert_eq!(learners(&board), vec![learner(0), learner(1)]);
+            // Only the two improvements were recorded.
+            assert_eq!(ink_env::test::recorded_events().count(), 2);
+        }
+
+        #[ink::test]
+        fn an_improved_score_moves_the_learner_up() {
+            let learner = fixtures::learner;
+            let mut board = board_of(3);
+            for (index, score) in [90, 80, 70].into_iter().enumerate() {
+                assert!(board.submit_score(learner(index as u32), score).is_ok());
+            }
+            assert_eq!(board.submit_score(learner(2), 85), Ok(Some(2)));
+            assert_eq!(learners(&board), vec![learner(0), learner(2), learner(1)]);
+            assert_eq!(board.submit_score(learner(1), 95), Ok(Some(1)));
+            assert_eq!(scores(&board), vec![95, 90, 85]);
+            // The learner is on the board once.
+            assert_eq!(board.board().len(), 3);
+        }
+
+        #[ink::test]
+        fn a_full_board_drops_its_last_learner() {
+            let learner = fixtures::learner;
+            let mut board = board_of(3);
+            for (index, score) in [90, 80, 70].into_iter().enumerate() {
+                assert!(board.submit_score(learner(index as u32), score).is_ok());
+            }
+            assert_eq!(board.submit_score(learner(3), 75), Ok(Some(3)));
+            assert_eq!(scores(&board), vec![90, 80, 75]);
+            assert_eq!(board.rank_of(learner(2)), None);
+            // Their best score is still known, off the board.
+            assert_eq!(board.best_score(learner(2)), Some(70));
+
+            // Back on the board once they beat the last learner.
+            assert_eq!(board.submit_score(learner(2), 76), Ok(Some(3)));
+            assert_eq!(board.rank_of(learner(3)), None);
+        }
+
+        #[ink::test]
+        fn a_tie_with_the_last_of_a_full_board_does_not_enter() {
+            let learner = fixtures::learner;
+            let mut board = board_of(3);
+            for (index, score) in [90, 80, 70].into_iter().enumerate() {
+                assert!(board.submit_score(learner(index as u32), score).is_ok());
+            }
+            assert_eq!(board.submit_score(learner(3), 70), Ok(None));
+            assert_eq!(board.submit_score(learner(4), 10), Ok(None));
+            assert_eq!(learners(&board), vec![learner(0), learner(1), learner(2)]);
+            assert_eq!(board.best_score(learner(3)), Some(70));
+            assert_eq!(board.rank_of(learner(3)), None);
+        }
+
+        #[ink::test]
+        fn a_full_board_stays_sorted() {
+            let mut board = board_of(MAX_CAPACITY);
+            // Scores in a scrambled order, with many ties.
+            for index in 0..2 * MAX_CAPACITY {
+                let score = (index * 37) % 50;
+                assert!(board.submit_score(fixtures::learner(index), score).is_ok());
+            }
+            let entries = board.board();
+            assert_eq!(entries.len(), MAX_CAPACITY as usize);
+            assert!(entries.windows(2).all(|pair| pair[0].ranks_above(&pair[1])));
+            for (index, entry) in entries.iter().enumerate() {
+                assert_eq!(board.rank_of(entry.learner), Some(index as u32 + 1));
+            }
+        }
+
+        #[ink::test]
+        fn only_the_grader_records_scores() {
+            let accounts = fixtures::accounts();
+            let mut board = board_of(5);
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(
+                board.submit_score(accounts.charlie, 50),
+                Err(Error::NotGrader)
+            );
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(
+                board.submit_score(accounts.charlie, 50),
+                Err(Error::NotGrader)
+            );
+            assert_eq!(board.best_score(accounts.charlie), None);
+            assert!(board.board().is_empty());
+        }
+
+        #[ink::test]
+        fn the_admin_rotates_the_grader() {
+            let accounts = fixtures::accounts();
+            let mut board = board_of(5);
+            assert_eq!(board.set_grader(accounts.dave), Err(Error::NotAdmin));
+
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(board.set_grader(accounts.dave), Ok(()));
+            assert_eq!(board.grader(), accounts.dave);
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                board.submit_score(accounts.charlie, 50),
+                Err(Error::NotGrader)
+            );
+            fixtures::set_caller(accounts.dave);
+            assert_eq!(board.submit_score(accounts.charlie, 50), Ok(Some(1)));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "capacity must not be zero")]
+        fn an_empty_board_is_rejected() {
+            Leaderboard::new(fixtures::accounts().bob, 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "capacity is too large")]
+        fn an_oversized_board_is_rejected() {
+            Leaderboard::new(fixtures::accounts().bob, MAX_CAPACITY + 1);
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy with the "new" constructor, the grading service's account and the
+//      size of the board
+//
+// 4. Interact with the contract:
+//    - As the grader, use "submitScore" with a learner and their score
+//    - Use "board" to show the leaderboard and "rankOf" for one learner
+//    - As admin, use "setGrader" to rotate the grader's key
@@ -0,0 +1,506 @@
+// examples/demo-contracts/limited_token.rs
+//
+// A transfer-limited token contract example for Polkadot using ink!
+// This contract extends the ERC-20 example with two caps set by the owner: a maximum
+// per transfer, and a maximum each account may send within any rolling 24 hours.
+//
+// The rolling window is kept with timestamped accumulators: each account has 24
+// hourly buckets, each remembering which hour it belongs to and how much was sent in
+// it. The amount sent "in the last 24 hours" is the sum of the buckets whose hour is
+// one of the last 24, so old spending drops out hour by hour instead of all at once
+// at midnight. A bucket is reused when its slot comes round again a day later.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod limited_token {
+    use astranet_utils::validation::{self, MAX_DECIMALS};
+    use ink_prelude::string::String;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Milliseconds in one accumulator bucket.
+    pub const HOUR: Timestamp = 60 * 60 * 1000;
+
+    /// Number of hourly buckets in the rolling window.
+    pub const WINDOW_HOURS: u64 = 24;
+
+    /// The limited token error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the balance is insufficient for the operation.
+        InsufficientBalance,
+        /// Returned if the allowance is insufficient for the operation.
+        InsufficientAllowance,
+        /// Returned if the transfer is above the per-transfer cap, or would take the
+        /// sender above the rolling 24-hour cap.
+        LimitExceeded,
+        /// Returned if the caller is not the token owner.
+        NotOwner,
+    }
+
+    /// The limited token result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// The amount an account sent during one hour.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Bucket {
+        /// Hours since the Unix epoch this bucket counts for.
+        pub hour: u64,
+        /// Amount sent during that hour.
+        pub amount: Balance,
+    }
+
+    // Events are mirrored in `crates/events/src/limited_token.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when a token transfer occurs.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    /// Event emitted when an approval occurs.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when the owner changes the caps.
+    #[ink(event)]
+    pub struct LimitsChanged {
+        per_transfer_limit: Balance,
+        daily_limit: Balance,
+    }
+
+    /// The limited token storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct LimitedToken {
+        /// Total token supply.
+        total_supply: Balance,
+        /// Mapping from owner to balance.
+        balances: Mapping<AccountId, Balance>,
+        /// Mapping from owner to spender to allowance.
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+        /// Token name.
+        name: String,
+        /// Token symbol.
+        symbol: String,
+        /// Token decimals.
+        decimals: u8,
+        /// Account allowed to change the caps.
+        owner: AccountId,
+        /// Maximum value of a single transfer.
+        per_transfer_limit: Balance,
+        /// Maximum an account may send within any 24 hours.
+        daily_limit: Balance,
+        /// Mapping from account and slot (`hour % WINDOW_HOURS`) to the bucket in it.
+        buckets: Mapping<(AccountId, u8), Bucket>,
+    }
+
+    impl LimitedToken {
+        /// Creates a new limited token with the specified initial supply and caps,
+        /// owned by the caller. Panics if `symbol` is empty or `decimals` is above
+        /// `MAX_DECIMALS`.
+        #[ink(constructor)]
+        pub fn new(
+            initial_supply: Balance,
+            name: String,
+            symbol: String,
+            decimals: u8,
+            per_transfer_limit: Balance,
+            daily_limit: Balance,
+        ) -> Self {
+            validation::require("token symbol", validation::non_empty(&symbol));
+            validation::require("decimals", validation::at_most(decimals, MAX_DECIMALS));
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                let caller = Self::env().caller();
+                contract.balances.insert(caller, &initial_supply);
+                contract.total_supply = initial_supply;
+                contract.name = name;
+                contract.symbol = symbol;
+                contract.decimals = decimals;
+                contract.owner = caller;
+                contract.per_transfer_limit = per_transfer_limit;
+                contract.daily_limit = daily_limit;
+
+                Self::env().emit_event(Transfer {
+                    from: None,
+                    to: Some(caller),
+                    value: initial_supply,
+                });
+            })
+        }
+
+        /// Returns the token name.
+        #[ink(message)]
+        pub fn name(&self) -> String {
+            self.name.clone()
+        }
+
+        /// Returns the token symbol.
+        #[ink(message)]
+        pub fn symbol(&self) -> String {
+            self.symbol.clone()
+        }
+
+        /// Returns the token decimals.
+        #[ink(message)]
+        pub fn decimals(&self) -> u8 {
+            self.decimals
+        }
+
+        /// Returns the total token supply.
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// Returns the account balance for the specified `owner`.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or(0)
+        }
+
+        /// Returns the amount which `spender` is allowed to withdraw from `owner`.
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or(0)
+        }
+
+        /// Returns the token owner.
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Returns the maximum value of a single transfer.
+        #[ink(message)]
+        pub fn per_transfer_limit(&self) -> Balance {
+            self.per_transfer_limit
+        }
+
+        /// Returns the maximum an account may send within any 24 hours.
+        #[ink(message)]
+        pub fn daily_limit(&self) -> Balance {
+            self.daily_limit
+        }
+
+        /// Returns how much `account` sent during the last 24 hours.
+        #[ink(message)]
+        pub fn spent_in_window(&self, account: AccountId) -> Balance {
+            let hour = self.current_hour();
+            (0..WINDOW_HOURS)
+                .filter_map(|slot| self.buckets.get((account, slot as u8)))
+                .filter(|bucket| bucket.hour + WINDOW_HOURS > hour)
+                .map(|bucket| bucket.amount)
+                .sum()
+        }
+
+        /// Returns how much more `account` can send before hitting the 24-hour cap.
+        #[ink(message)]
+        pub fn remaining_in_window(&self, account: AccountId) -> Balance {
+            self.daily_limit()
+                .saturating_sub(self.spent_in_window(account))
+        }
+
+        /// Changes both caps. Only the owner can call this. Spending already recorded
+        /// keeps counting against the new daily cap.
+        #[ink(message)]
+        pub fn set_limits(
+            &mut self,
+            per_transfer_limit: Balance,
+            daily_limit: Balance,
+        ) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.per_transfer_limit = per_transfer_limit;
+            self.daily_limit = daily_limit;
+            self.env().emit_event(LimitsChanged {
+                per_transfer_limit,
+                daily_limit,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            self.transfer_from_to(from, to, value)
+        }
+
+        /// Allows `spender` to withdraw from the caller's account multiple times, up to
+        /// the `value` amount.
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            self.allowances.insert((owner, spender), &value);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` tokens on behalf of `from` to the account `to`. The
+        /// transfer counts against `from`'s caps, not the spender's.
+        #[ink(message)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let caller = self.env().caller();
+            let allowance = self.allowance(from, caller);
+            if allowance < value {
+                return Err(Error::InsufficientAllowance);
+            }
+            self.transfer_from_to(from, to, value)?;
+            self.allowances.insert((from, caller), &(allowance - value));
+            Ok(())
+        }
+
+        /// Hours since the Unix epoch at the current block.
+        fn current_hour(&self) -> u64 {
+            self.env().block_timestamp() / HOUR
+        }
+
+        /// Adds `value` to `account`'s bucket for the current hour, starting the bucket
+        /// afresh if its slot still holds an hour from a previous day.
+        fn record_spending(&mut self, account: AccountId, value: Balance) {
+            let hour = self.current_hour();
+            let slot = (hour % WINDOW_HOURS) as u8;
+            let amount = match self.buckets.get((account, slot)) {
+                Some(bucket) if bucket.hour == hour => bucket.amount + value,
+                _ => value,
+            };
+            self.buckets.insert((account, slot), &Bucket { hour, amount });
+        }
+
+        /// Transfers `value` amount of tokens from the `from` account to the `to`
+        /// account, enforcing both caps on `from`.
+        fn transfer_from_to(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            if value > self.per_transfer_limit() || value > self.remaining_in_window(from) {
+                return Err(Error::LimitExceeded);
+            }
+
+            let from_balance = self.balance_of(from);
+            if from_balance < value {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.balances.insert(from, &(from_balance - value));
+            let to_balance = self.balance_of(to);
+            self.balances.insert(to, &(to_balance + value));
+            self.record_spending(from, value);
+
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                value,
+            });
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use fixtures::BLOCK_TIME;
+        use ink_lang as ink;
+
+        /// A token of 1000 with a cap of 10 per transfer and 25 per 24 hours.
+        fn new_token() -> LimitedToken {
+            LimitedToken::new(1_000, String::from("Limited"), String::from("LIM"), 12, 10, 25)
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "token symbol must not be empty")]
+        fn new_rejects_an_empty_symbol() {
+            LimitedToken::new(1_000, String::from("Limited"), String::new(), 12, 10, 25);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "decimals is too large")]
+        fn new_rejects_too_many_decimals() {
+            LimitedToken::new(
+                1_000,
+                String::from("Limited"),
+                String::from("LIM"),
+                MAX_DECIMALS + 1,
+                10,
+                25,
+            );
+        }
+
+        #[ink::test]
+        fn per_transfer_cap_applies() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            assert_eq!(token.transfer(accounts.bob, 11), Err(Error::LimitExceeded));
+            assert_eq!(token.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(token.balance_of(accounts.bob), 10);
+        }
+
+        #[ink::test]
+        fn daily_cap_sums_transfers() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            assert_eq!(token.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(token.transfer(accounts.charlie, 10), Ok(()));
+            assert_eq!(token.remaining_in_window(accounts.alice), 5);
+            assert_eq!(token.transfer(accounts.bob, 6), Err(Error::LimitExceeded));
+            assert_eq!(token.transfer(accounts.bob, 5), Ok(()));
+            assert_eq!(token.spent_in_window(accounts.alice), 25);
+        }
+
+        #[ink::test]
+        fn window_rolls_hour_by_hour() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            assert_eq!(token.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(token.transfer(accounts.bob, 10), Ok(()));
+
+            // Twelve hours later only 5 is left of the cap.
+            fixtures::set_block_timestamp(12 * HOUR);
+            assert_eq!(token.transfer(accounts.bob, 5), Ok(()));
+            assert_eq!(token.transfer(accounts.bob, 1), Err(Error::LimitExceeded));
+
+            // In the last block before the first hour leaves the window, nothing has
+            // been freed.
+            fixtures::set_block_timestamp(24 * HOUR - BLOCK_TIME);
+            assert_eq!(token.remaining_in_window(accounts.alice), 0);
+
+            // Then the 20 sent in hour 0 drops out, while the 5 from hour 12 stays.
+            fixtures::set_block_timestamp(24 * HOUR);
+            assert_eq!(token.spent_in_window(accounts.alice), 5);
+            assert_eq!(token.transfer(accounts.bob, 10), Ok(()));
+
+            // A day after hour 12 only the 10 from hour 24 is left, and a day after
+            // that nothing is.
+            fixtures::set_block_timestamp(36 * HOUR);
+            assert_eq!(token.spent_in_window(accounts.alice), 10);
+            fixtures::set_block_timestamp(48 * HOUR);
+            assert_eq!(token.spent_in_window(accounts.alice), 0);
+        }
+
+        #[ink::test]
+        fn reused_slot_starts_afresh() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            fixtures::set_block_timestamp(3 * HOUR);
+            assert_eq!(token.transfer(accounts.bob, 10), Ok(()));
+            // Same slot (3), one day later: the old amount must not be added to.
+            fixtures::set_block_timestamp(27 * HOUR);
+            assert_eq!(token.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(token.spent_in_window(accounts.alice), 10);
+        }
+
+        #[ink::test]
+        fn limits_are_per_sender() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            assert_eq!(token.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(token.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(token.transfer(accounts.bob, 5), Ok(()));
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(token.transfer(accounts.charlie, 10), Ok(()));
+            assert_eq!(token.spent_in_window(accounts.bob), 10);
+        }
+
+        #[ink::test]
+        fn transfer_from_counts_against_the_holder() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            assert_eq!(token.approve(accounts.bob, 100), Ok(()));
+            assert_eq!(token.transfer(accounts.charlie, 10), Ok(()));
+            assert_eq!(token.transfer(accounts.charlie, 10), Ok(()));
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                token.transfer_from(accounts.alice, accounts.bob, 10),
+                Err(Error::LimitExceeded)
+            );
+            assert_eq!(token.allowance(accounts.alice, accounts.bob), 100);
+            assert_eq!(token.transfer_from(accounts.alice, accounts.bob, 5), Ok(()));
+            assert_eq!(token.spent_in_window(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn failed_transfers_do_not_use_the_cap() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(token.transfer(accounts.charlie, 5), Err(Error::InsufficientBalance));
+            assert_eq!(token.spent_in_window(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn only_owner_sets_limits() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(token.set_limits(100, 1_000), Err(Error::NotOwner));
+
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(token.set_limits(100, 1_000), Ok(()));
+            assert_eq!(token.per_transfer_limit(), 100);
+            assert_eq!(token.daily_limit(), 1_000);
+            assert_eq!(token.transfer(accounts.bob, 100), Ok(()));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy using the Polkadot JS Apps UI:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your desired network
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Set the initial supply, name, symbol, decimals, per-transfer cap and
+//      24-hour cap
+//    - Deploy the contract
+//
+// 4. Interact with the contract:
+//    - Use the "transfer" method to send tokens; transfers above either cap fail
+//      with LimitExceeded
+//    - Use "remainingInWindow" to see how much an account can still send
+//    - As the owner, use "setLimits" to change the caps
@@ -0,0 +1,493 @@
+// examples/demo-contracts/marketplace.rs
+//
+// A fixed-price NFT marketplace contract example for Polkadot using ink!
+// Owners of ERC-721 tokens (such as `erc721.rs`) list a token at a price in the
+// native currency, and anyone can buy it by paying exactly that price. The
+// marketplace never holds the tokens: a seller approves it as an operator, and on
+// a sale it moves the token straight from the seller to the buyer with
+// `transfer_from`. The payment is kept as the seller's proceeds until they
+// withdraw it.
+//
+// Active listings index
+// ---------------------
+// Listings live in a `Mapping`, which cannot be iterated, so the ids of the
+// active ones are also kept in a `StorageLinkedList` from the utils crate. Listing
+// appends an id and cancelling or selling unlinks it, each in a few storage
+// operations however many listings there are, and `active_listings` returns a
+// `Page` of them, oldest first, for front-ends to browse with a cursor.
+//
+// A listing does not check that the seller owns the token or has approved the
+// marketplace. If either is no longer true at sale time, `transfer_from` fails,
+// the buyer is refunded and the listing stays up until its seller cancels it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod marketplace {
+    use astranet_utils::{pagination::Page, storage_linked_list::StorageLinkedList, trace};
+    use ink_env::call::{build_call, Call, ExecutionInput, Selector};
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Identifies a listing.
+    pub type ListingId = u32;
+
+    /// Identifies a token within an ERC-721 contract.
+    pub type TokenId = u32;
+
+    /// Selector of the ERC-721 `transfer_from(from, to, id)` message.
+    const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x0b, 0x39, 0x6f, 0x18];
+
+    /// A token offered for sale.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Listing {
+        /// Account selling the token and receiving the payment.
+        pub seller: AccountId,
+        /// ERC-721 contract of the token.
+        pub nft: AccountId,
+        /// Id of the token.
+        pub token_id: TokenId,
+        /// Price in the native currency.
+        pub price: Balance,
+    }
+
+    /// The marketplace error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the price is zero.
+        ZeroPrice,
+        /// Returned if the token is already listed.
+        AlreadyListed,
+        /// Returned if no active listing has the given id.
+        ListingNotFound,
+        /// Returned if the caller is not the seller.
+        NotSeller,
+        /// Returned if the payment differs from the price.
+        WrongPayment,
+        /// Returned if the token could not be moved to the buyer.
+        TransferFailed,
+        /// Returned if the caller has no proceeds to withdraw.
+        NothingToWithdraw,
+    }
+
+    /// The marketplace result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Stands in for the token's own error type. All the marketplace needs is to
+    /// tell success from failure.
+    #[derive(scale::Decode)]
+    struct TokenError(#[allow(dead_code)] u8);
+
+    // Events are mirrored in `crates/events/src/marketplace.rs` for off-chain
+    // decoding; keep the order of events and fields in sync with it.
+
+    /// Event emitted when a token is listed.
+    #[ink(event)]
+    pub struct Listed {
+        #[ink(topic)]
+        listing_id: ListingId,
+        #[ink(topic)]
+        seller: AccountId,
+        nft: AccountId,
+        token_id: TokenId,
+        price: Balance,
+    }
+
+    /// Event emitted when a seller takes a listing down.
+    #[ink(event)]
+    pub struct Cancelled {
+        #[ink(topic)]
+        listing_id: ListingId,
+    }
+
+    /// Event emitted when a listed token is bought.
+    #[ink(event)]
+    pub struct Sold {
+        #[ink(topic)]
+        listing_id: ListingId,
+        #[ink(topic)]
+        buyer: AccountId,
+        price: Balance,
+    }
+
+    /// Event emitted when a seller withdraws their proceeds.
+    #[ink(event)]
+    pub struct ProceedsWithdrawn {
+        #[ink(topic)]
+        seller: AccountId,
+        amount: Balance,
+    }
+
+    /// The marketplace storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Marketplace {
+        /// Mapping from listing id to active listing.
+        listings: Mapping<ListingId, Listing>,
+        /// Ids of the active listings, oldest first.
+        active: StorageLinkedList<ListingId>,
+        /// Mapping from ERC-721 contract and token id to its active listing.
+        listing_of_token: Mapping<(AccountId, TokenId), ListingId>,
+        /// Mapping from seller to payments not withdrawn yet.
+        proceeds: Mapping<AccountId, Balance>,
+        /// Id the next listing will get.
+        next_listing_id: ListingId,
+    }
+
+    impl Marketplace {
+        /// Creates an empty marketplace.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|_: &mut Self| {})
+        }
+
+        /// Returns the active listing with id `listing_id`.
+        #[ink(message)]
+        pub fn listing(&self, listing_id: ListingId) -> Option<Listing> {
+            self.listings.get(listing_id)
+        }
+
+        /// Returns the number of active listings.
+        #[ink(message)]
+        pub fn active_count(&self) -> u32 {
+            self.active.len()
+        }
+
+        /// Returns a page of up to `limit` active listings, oldest first, starting
+        /// at `cursor` or at the oldest if `cursor` is `None`.
+        ///
+        /// A cursor whose listing was sold or cancelled since gives an empty page;
+        /// start again from `None`.
+        #[ink(message)]
+        pub fn active_listings(
+            &self,
+            cursor: Option<ListingId>,
+            limit: u32,
+        ) -> Page<(ListingId, Listing), ListingId> {
+            let ids = match cursor {
+                Some(cursor) => self.active.iter_from(cursor),
+                None => self.active.iter(),
+            };
+            Page::collect(
+                ids.filter_map(|id| self.listing(id).map(|listing| (id, (id, listing)))),
+                limit,
+            )
+        }
+
+        /// Returns the proceeds `seller` can withdraw.
+        #[ink(message)]
+        pub fn proceeds_of(&self, seller: AccountId) -> Balance {
+            self.proceeds.get(seller).unwrap_or(0)
+        }
+
+        /// Lists token `token_id` of the ERC-721 contract `nft` at `price` and
+        /// returns the listing id. The caller must own the token and approve the
+        /// marketplace to move it before it can be bought.
+        #[ink(message)]
+        pub fn list(
+            &mut self,
+            nft: AccountId,
+            token_id: TokenId,
+            price: Balance,
+        ) -> Result<ListingId> {
+            if price == 0 {
+                return Err(Error::ZeroPrice);
+            }
+            if self.listing_of_token.contains((nft, token_id)) {
+                return Err(Error::AlreadyListed);
+            }
+            let seller = self.env().caller();
+            let listing_id = self.next_listing_id;
+            self.next_listing_id += 1;
+            self.insert_listing(
+                listing_id,
+                Listing {
+                    seller,
+                    nft,
+                    token_id,
+                    price,
+                },
+            );
+            self.env().emit_event(Listed {
+                listing_id,
+                seller,
+                nft,
+                token_id,
+                price,
+            });
+            Ok(listing_id)
+        }
+
+        /// Takes listing `listing_id` down. Only its seller can call this.
+        #[ink(message)]
+        pub fn cancel(&mut self, listing_id: ListingId) -> Result<()> {
+            let listing = self.listing(listing_id).ok_or(Error::ListingNotFound)?;
+            if listing.seller != self.env().caller() {
+                return Err(Error::NotSeller);
+            }
+            self.remove_listing(listing_id, listing);
+            self.env().emit_event(Cancelled { listing_id });
+            Ok(())
+        }
+
+        /// Buys the token of listing `listing_id`. The transferred value must be
+        /// exactly the price. If the token cannot be moved, the payment is
+        /// refunded and the listing stays up.
+        #[ink(message, payable)]
+        pub fn buy(&mut self, listing_id: ListingId) -> Result<()> {
+            let listing = self.listing(listing_id).ok_or(Error::ListingNotFound)?;
+            trace!(
+                "buy",
+                listing_id,
+                price = listing.price,
+                paid = self.env().transferred_value(),
+            );
+            if self.env().transferred_value() != listing.price {
+                return Err(Error::WrongPayment);
+            }
+            let buyer = self.env().caller();
+            // Take the listing down before calling the token, so a re-entrant call
+            // cannot buy it twice.
+            self.remove_listing(listing_id, listing);
+            let moved = build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(listing.nft).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(TRANSFER_FROM_SELECTOR))
+                        .push_arg(listing.seller)
+                        .push_arg(buyer)
+                        .push_arg(listing.token_id),
+                )
+                .returns::<core::result::Result<(), TokenError>>()
+                .fire();
+            if !matches!(moved, Ok(Ok(()))) {
+                trace!("nft_transfer_failed", listing_id, nft = listing.nft);
+                // The listing rejoins the active listings at the end.
+                self.insert_listing(listing_id, listing);
+                self.env()
+                    .transfer(buyer, listing.price)
+                    .expect("the payment is still in the contract");
+                return Err(Error::TransferFailed);
+            }
+            self.complete_sale(listing_id, listing, buyer);
+            Ok(())
+        }
+
+        /// Sends the caller all of their proceeds.
+        #[ink(message)]
+        pub fn withdraw_proceeds(&mut self) -> Result<Balance> {
+            let seller = self.env().caller();
+            let amount = self.proceeds_of(seller);
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+            self.proceeds.remove(seller);
+            if self.env().transfer(seller, amount).is_err() {
+                self.proceeds.insert(seller, &amount);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(ProceedsWithdrawn { seller, amount });
+            Ok(amount)
+        }
+
+        /// Stores `listing` and adds it to the end of the active listings.
+        fn insert_listing(&mut self, listing_id: ListingId, listing: Listing) {
+            self.listings.insert(listing_id, &listing);
+            self.listing_of_token
+                .insert((listing.nft, listing.token_id), &listing_id);
+            self.active.push_back(listing_id);
+        }
+
+        /// Deletes `listing` and removes it from the active listings.
+        fn remove_listing(&mut self, listing_id: ListingId, listing: Listing) {
+            self.listings.remove(listing_id);
+            self.listing_of_token
+                .remove((listing.nft, listing.token_id));
+            self.active.remove(listing_id);
+        }
+
+        /// Credits the seller of a listing that has been taken down and whose
+        /// token has reached `buyer`.
+        fn complete_sale(&mut self, listing_id: ListingId, listing: Listing, buyer: AccountId) {
+            self.proceeds.insert(
+                listing.seller,
+                &(self.proceeds_of(listing.seller) + listing.price),
+            );
+            self.env().emit_event(Sold {
+                listing_id,
+                buyer,
+                price: listing.price,
+            });
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// cover listing, paging and withdrawing, and settle sales with `remove_listing`
+    /// and `complete_sale` instead of `buy`.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use astranet_utils::pagination::MAX_PAGE_LIMIT;
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        fn nft() -> AccountId {
+            AccountId::from([0x72; 32])
+        }
+
+        /// A marketplace where alice has listed tokens 0 to `count - 1` at 100
+        /// each.
+        fn with_listings(count: u32) -> Marketplace {
+            let mut market = Marketplace::new();
+            for token_id in 0..count {
+                assert_eq!(market.list(nft(), token_id, 100), Ok(token_id));
+            }
+            market
+        }
+
+        fn ids(page: &Page<(ListingId, Listing), ListingId>) -> Vec<ListingId> {
+            page.items.iter().map(|(id, _)| *id).collect()
+        }
+
+        #[ink::test]
+        fn list_records_the_listing() {
+            let accounts = fixtures::accounts();
+            let mut market = Marketplace::new();
+            assert_eq!(market.list(nft(), 7, 0), Err(Error::ZeroPrice));
+            assert_eq!(market.list(nft(), 7, 100), Ok(0));
+            assert_eq!(
+                market.listing(0),
+                Some(Listing {
+                    seller: accounts.alice,
+                    nft: nft(),
+                    token_id: 7,
+                    price: 100,
+                })
+            );
+            assert_eq!(market.active_count(), 1);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(market.list(nft(), 7, 50), Err(Error::AlreadyListed));
+        }
+
+        #[ink::test]
+        fn only_seller_cancels() {
+            let accounts = fixtures::accounts();
+            let mut market = with_listings(2);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(market.cancel(0), Err(Error::NotSeller));
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(market.cancel(0), Ok(()));
+            assert_eq!(market.cancel(0), Err(Error::ListingNotFound));
+            assert_eq!(market.active_count(), 1);
+            // The token can be listed again once the old listing is gone.
+            assert_eq!(market.list(nft(), 0, 80), Ok(2));
+        }
+
+        #[ink::test]
+        fn active_listings_pages_oldest_first() {
+            let market = with_listings(5);
+            let first = market.active_listings(None, 2);
+            assert_eq!(ids(&first), [0, 1]);
+            assert_eq!(first.next_cursor, Some(2));
+            let second = market.active_listings(first.next_cursor, 2);
+            assert_eq!(ids(&second), [2, 3]);
+            let last = market.active_listings(second.next_cursor, 2);
+            assert_eq!(ids(&last), [4]);
+            assert_eq!(last.next_cursor, None);
+            assert_eq!(market.active_listings(Some(99), 2), Page::empty());
+        }
+
+        #[ink::test]
+        fn pages_end_exactly_at_the_last_listing() {
+            let market = with_listings(4);
+            let first = market.active_listings(None, 2);
+            let last = market.active_listings(first.next_cursor, 2);
+            assert_eq!(ids(&last), [2, 3]);
+            assert_eq!(last.next_cursor, None);
+            assert_eq!(Marketplace::new().active_listings(None, 2), Page::empty());
+        }
+
+        #[ink::test]
+        fn pages_are_capped() {
+            let market = with_listings(MAX_PAGE_LIMIT + 5);
+            let page = market.active_listings(None, u32::MAX);
+            assert_eq!(page.len() as u32, MAX_PAGE_LIMIT);
+            assert_eq!(page.next_cursor, Some(MAX_PAGE_LIMIT));
+        }
+
+        #[ink::test]
+        fn removed_listings_leave_the_index() {
+            let accounts = fixtures::accounts();
+            let mut market = with_listings(4);
+            assert_eq!(market.cancel(1), Ok(()));
+            let sold = market.listing(2).unwrap();
+            market.remove_listing(2, sold);
+            market.complete_sale(2, sold, accounts.bob);
+            assert_eq!(ids(&market.active_listings(None, 10)), [0, 3]);
+            assert_eq!(ids(&market.active_listings(Some(3), 10)), [3]);
+        }
+
+        #[ink::test]
+        fn buy_needs_exact_payment() {
+            let accounts = fixtures::accounts();
+            let mut market = with_listings(1);
+            fixtures::set_caller(accounts.bob);
+            ink_env::test::set_value_transferred::<Env>(99);
+            assert_eq!(market.buy(0), Err(Error::WrongPayment));
+            assert_eq!(market.buy(1), Err(Error::ListingNotFound));
+            assert_eq!(market.active_count(), 1);
+        }
+
+        #[ink::test]
+        fn sellers_withdraw_proceeds() {
+            let accounts = fixtures::accounts();
+            let mut market = with_listings(2);
+            for listing_id in [0, 1] {
+                let listing = market.listing(listing_id).unwrap();
+                market.remove_listing(listing_id, listing);
+                market.complete_sale(listing_id, listing, accounts.bob);
+            }
+            assert_eq!(market.proceeds_of(accounts.alice), 200);
+
+            fixtures::fund_contract(200);
+            let before = fixtures::balance_of(accounts.alice);
+            assert_eq!(market.withdraw_proceeds(), Ok(200));
+            assert_eq!(fixtures::balance_of(accounts.alice), before + 200);
+            assert_eq!(market.withdraw_proceeds(), Err(Error::NothingToWithdraw));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Upload the generated .contract file and deploy it
+//
+// 4. Interact with the contract:
+//    - On `erc721.rs`, use "setApprovalForAll" to make the marketplace an operator
+//    - Use "list" with the ERC-721 address, the token id and a price
+//    - Buyers call "buy" with the listing id, sending exactly the price
+//    - Use "activeListings" to page through the listings, passing each page's
+//      "nextCursor" back as the cursor
+//    - Sellers use "withdrawProceeds" to collect their payments
@@ -0,0 +1,170 @@
+// examples/demo-contracts/migrate_v1.rs
+//
+// The first version of an upgradeable score registry for Polkadot using ink!
+// This contract is upgraded in place to migrate_v2.rs with `set_code_hash`;
+// see that file for how the stored scores are carried over to the new layout.
+// The storage records its layout version, 1 here, so that the migration in v2 can
+// check it runs exactly once and on the layout it expects.
+//
+// A `Mapping` cannot list its keys, so the registry also keeps an index of every
+// learner it has a score for. v2 walks that index to find the entries to migrate.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod migrate_v1 {
+    use astranet_utils::storage_version::StorageVersion;
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// Version of the storage layout written by this code.
+    pub const STORAGE_VERSION: u16 = 1;
+
+    /// The score registry error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the contract owner.
+        NotOwner,
+        /// Returned if the node refused to switch to the new code.
+        UpgradeFailed,
+    }
+
+    /// The score registry result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// The score registry storage items.
+    ///
+    /// The order of these fields is part of the contract's storage layout.
+    /// Later versions must keep them, in this order, in front of any new fields.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct ScoreRegistry {
+        /// Account allowed to record scores and upgrade the contract.
+        owner: AccountId,
+        /// Mapping from learner to score.
+        scores: Mapping<AccountId, u32>,
+        /// Mapping from index to every learner with a score, in order of their
+        /// first score.
+        learners: Mapping<u32, AccountId>,
+        /// Number of entries in `learners`.
+        learner_count: u32,
+        /// Version of this layout.
+        version: StorageVersion,
+    }
+
+    impl ScoreRegistry {
+        /// Creates a new registry owned by the caller.
+        #[ink(constructor)]
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.owner = Self::env().caller();
+                contract.version = StorageVersion::new(STORAGE_VERSION);
+            })
+        }
+
+        /// Returns the version of the storage layout.
+        #[ink(message)]
+        pub fn storage_version(&self) -> u16 {
+            self.version.get()
+        }
+
+        /// Returns the score of `learner`, or zero if none was recorded.
+        #[ink(message)]
+        pub fn score_of(&self, learner: AccountId) -> u32 {
+            self.scores.get(learner).unwrap_or(0)
+        }
+
+        /// Records `score` for `learner`.
+        #[ink(message)]
+        pub fn set_score(&mut self, learner: AccountId, score: u32) -> Result<()> {
+            self.ensure_owner()?;
+            if !self.scores.contains(learner) {
+                self.learners.insert(self.learner_count, &learner);
+                self.learner_count += 1;
+            }
+            self.scores.insert(learner, &score);
+            Ok(())
+        }
+
+        /// Replaces this contract's code with the code at `code_hash`, keeping its
+        /// address, balance, and storage.
+        #[ink(message)]
+        pub fn upgrade(&mut self, code_hash: [u8; 32]) -> Result<()> {
+            self.ensure_owner()?;
+            ink_env::set_code_hash(&code_hash).map_err(|_| Error::UpgradeFailed)
+        }
+
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        #[ink::test]
+        fn set_score_works() {
+            let accounts = fixtures::accounts();
+            let mut registry = ScoreRegistry::new();
+            assert_eq!(registry.storage_version(), STORAGE_VERSION);
+            assert_eq!(registry.score_of(accounts.bob), 0);
+            assert_eq!(registry.set_score(accounts.bob, 7), Ok(()));
+            assert_eq!(registry.score_of(accounts.bob), 7);
+        }
+
+        #[ink::test]
+        fn learners_are_indexed_once() {
+            let accounts = fixtures::accounts();
+            let mut registry = ScoreRegistry::new();
+            assert_eq!(registry.set_score(accounts.bob, 7), Ok(()));
+            assert_eq!(registry.set_score(accounts.charlie, 8), Ok(()));
+            assert_eq!(registry.set_score(accounts.bob, 9), Ok(()));
+            assert_eq!(registry.learner_count, 2);
+            assert_eq!(registry.learners.get(0), Some(accounts.bob));
+            assert_eq!(registry.learners.get(1), Some(accounts.charlie));
+        }
+
+        #[ink::test]
+        fn only_owner_can_set_score() {
+            let accounts = fixtures::accounts();
+            let mut registry = ScoreRegistry::new();
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(registry.set_score(accounts.bob, 7), Err(Error::NotOwner));
+            assert_eq!(registry.upgrade([0x01; 32]), Err(Error::NotOwner));
+        }
+    }
+}
+
+// Lets migrate_v2.rs deploy this contract in its e2e test when built with the
+// `ink-as-dependency` feature.
+pub use self::migrate_v1::{ScoreRegistry, ScoreRegistryRef};
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy using the Polkadot JS Apps UI:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your desired network
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy the contract
+//
+// 4. Interact with the contract:
+//    - Use the "setScore" method to record some scores
+//    - Upload migrate_v2.rs (without deploying) and note its code hash
+//    - Use the "upgrade" method with that code hash, then continue with migrate_v2.rs
@@ -0,0 +1,440 @@
+// examples/demo-contracts/migrate_v2.rs
+//
+// The second version of the upgradeable score registry for Polkadot using ink!
+// This contract demonstrates changing a contract's storage layout after an in-place
+// upgrade and migrating the existing entries lazily, a batch at a time.
+//
+// What changed since migrate_v1.rs: scores are widened from `u32` to `u64` and
+// every record now remembers the block it was last updated in. The old `scores`
+// mapping stays in the layout (renamed `legacy_scores`) so the data written by v1
+// can still be found; `migrate` moves entries from it into `records`, and reads fall
+// back to the legacy mapping until an entry has been moved.
+//
+// A `Mapping` cannot list its keys, so `migrate` walks v1's index of learners
+// instead, from the last entry back, shortening the index as it goes. The index
+// length is a v1 field, so the migration needs no new plain field: ink! fails to
+// load a plain field that the old code never wrote, whereas a new `Mapping` such
+// as `records` simply starts out empty.
+//
+// Storage version
+// ---------------
+// v1 stores its layout version, 1, and this code's layout is version 2. `migrate`
+// only runs while the storage is at version 1 and moves it to version 2 with the
+// batch that finishes v1's index, so once the migration is complete, or on
+// a fresh v2 deployment that never had a v1 layout, it refuses to run. A v3 would
+// check for version 2 the same way and could not run before this migration.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod migrate_v2 {
+    use astranet_utils::storage_version::{StorageVersion, StorageVersionError};
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Version of the storage layout written by this code.
+    pub const STORAGE_VERSION: u16 = 2;
+
+    /// The score registry error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the contract owner.
+        NotOwner,
+        /// Returned if the node refused to switch to the new code.
+        UpgradeFailed,
+        /// Returned if the storage is at `found` rather than the `expected`
+        /// version, such as when migrating again after the migration completed.
+        UnexpectedVersion { expected: u16, found: u16 },
+        /// Returned if the storage version cannot go any higher.
+        VersionExhausted,
+    }
+
+    impl From<StorageVersionError> for Error {
+        fn from(error: StorageVersionError) -> Self {
+            match error {
+                StorageVersionError::UnexpectedVersion { expected, found } => {
+                    Error::UnexpectedVersion { expected, found }
+                }
+                StorageVersionError::Exhausted => Error::VersionExhausted,
+            }
+        }
+    }
+
+    /// The score registry result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A learner's score in the v2 layout.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Record {
+        /// The learner's score.
+        pub score: u64,
+        /// Block in which the score was last written. Migrated entries carry the
+        /// block they were migrated in, since v1 never recorded one.
+        pub updated_at: BlockNumber,
+    }
+
+    // Events are mirrored in `crates/events/src/migrate_v2.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted after each migration batch.
+    #[ink(event)]
+    pub struct Migrated {
+        /// Number of entries moved in this batch.
+        moved: u32,
+        /// Number of learners in v1's index still to visit.
+        remaining: u32,
+    }
+
+    /// The score registry storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct ScoreRegistry {
+        /// Account allowed to record scores and upgrade the contract. Same slot as v1.
+        owner: AccountId,
+        /// v1's `scores` mapping. Same slot as v1; only ever drained from now on.
+        legacy_scores: Mapping<AccountId, u32>,
+        /// v1's index of learners. Same slot as v1; entries at or past
+        /// `legacy_learner_count` have been migrated.
+        legacy_learners: Mapping<u32, AccountId>,
+        /// Number of learners in the index still to migrate. Same slot as v1.
+        legacy_learner_count: u32,
+        /// Version of the layout. Same slot as v1; 1 until `migrate` completes.
+        version: StorageVersion,
+        /// Mapping from learner to record, introduced in v2.
+        records: Mapping<AccountId, Record>,
+    }
+
+    impl ScoreRegistry {
+        /// Creates a new registry owned by the caller. Only used for fresh
+        /// deployments; upgraded contracts keep the storage v1 created.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.owner = Self::env().caller();
+                contract.version = StorageVersion::new(STORAGE_VERSION);
+            })
+        }
+
+        /// Returns the version of the storage layout.
+        #[ink(message)]
+        pub fn storage_version(&self) -> u16 {
+            self.version.get()
+        }
+
+        /// Returns the record of `learner`, migrating nothing but reading through
+        /// to the legacy mapping for entries that have not been moved yet.
+        #[ink(message)]
+        pub fn record_of(&self, learner: AccountId) -> Option<Record> {
+            self.records.get(learner).or_else(|| {
+                self.legacy_scores.get(learner).map(|score| Record {
+                    score: u64::from(score),
+                    updated_at: 0,
+                })
+            })
+        }
+
+        /// Returns the score of `learner`, or zero if none was recorded.
+        #[ink(message)]
+        pub fn score_of(&self, learner: AccountId) -> u64 {
+            self.record_of(learner).map(|record| record.score).unwrap_or(0)
+        }
+
+        /// Records `score` for `learner`, dropping any legacy entry for them. Their
+        /// index entry stays, and `migrate` skips it once it gets there.
+        #[ink(message)]
+        pub fn set_score(&mut self, learner: AccountId, score: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.legacy_scores.remove(learner);
+            self.records.insert(
+                learner,
+                &Record {
+                    score,
+                    updated_at: self.env().block_number(),
+                },
+            );
+            Ok(())
+        }
+
+        /// Returns the number of learners in v1's index that `migrate` has not
+        /// visited yet.
+        #[ink(message)]
+        pub fn pending_migrations(&self) -> u32 {
+            self.legacy_learner_count
+        }
+
+        /// Visits up to `batch` learners from v1's index, moves those still in the
+        /// legacy mapping into `records`, and returns how many were moved. The batch
+        /// that finishes the index moves the storage to version 2, after which this
+        /// fails. Anyone may call this: migrating an entry never changes its
+        /// observable score.
+        ///
+        /// Migrating in batches keeps every call under the block weight limit no
+        /// matter how many learners v1 accumulated.
+        #[ink(message)]
+        pub fn migrate(&mut self, batch: u32) -> Result<u32> {
+            self.version.ensure_version(STORAGE_VERSION - 1)?;
+            let now = self.env().block_number();
+            let mut moved = 0;
+            for _ in 0..batch.min(self.legacy_learner_count) {
+                self.legacy_learner_count -= 1;
+                let index = self.legacy_learner_count;
+                let learner = self
+                    .legacy_learners
+                    .get(index)
+                    .expect("learners below the count are always indexed");
+                self.legacy_learners.remove(index);
+                if let Some(score) = self.legacy_scores.get(learner) {
+                    self.legacy_scores.remove(learner);
+                    self.records.insert(
+                        learner,
+                        &Record {
+                            score: u64::from(score),
+                            updated_at: now,
+                        },
+                    );
+                    moved += 1;
+                }
+            }
+
+            let remaining = self.legacy_learner_count;
+            if remaining == 0 {
+                self.version.bump_version(STORAGE_VERSION - 1)?;
+            }
+            self.env().emit_event(Migrated { moved, remaining });
+            Ok(moved)
+        }
+
+        /// Replaces this contract's code with the code at `code_hash`.
+        #[ink(message)]
+        pub fn upgrade(&mut self, code_hash: [u8; 32]) -> Result<()> {
+            self.ensure_owner()?;
+            ink_env::set_code_hash(&code_hash).map_err(|_| Error::UpgradeFailed)
+        }
+
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        /// Builds a registry as it looks right after the upgrade from v1.
+        fn upgraded(entries: &[(AccountId, u32)]) -> ScoreRegistry {
+            let mut registry = ScoreRegistry::new();
+            registry.version = StorageVersion::new(STORAGE_VERSION - 1);
+            for (learner, score) in entries {
+                let index = registry.legacy_learner_count;
+                registry.legacy_learners.insert(index, learner);
+                registry.legacy_learner_count += 1;
+                registry.legacy_scores.insert(learner, score);
+            }
+            registry
+        }
+
+        #[ink::test]
+        fn legacy_entries_are_readable_before_migration() {
+            let accounts = fixtures::accounts();
+            let registry = upgraded(&[(accounts.bob, 7)]);
+            assert_eq!(registry.score_of(accounts.bob), 7);
+            assert_eq!(registry.pending_migrations(), 1);
+        }
+
+        #[ink::test]
+        fn migrate_moves_entries_in_batches() {
+            let accounts = fixtures::accounts();
+            let mut registry = upgraded(&[
+                (accounts.bob, 1),
+                (accounts.charlie, 2),
+                (accounts.dave, 3),
+            ]);
+
+            assert_eq!(registry.migrate(2), Ok(2));
+            assert_eq!(registry.pending_migrations(), 1);
+            assert_eq!(registry.storage_version(), 1);
+            assert_eq!(registry.migrate(2), Ok(1));
+            assert_eq!(registry.pending_migrations(), 0);
+            assert_eq!(registry.storage_version(), 2);
+
+            assert_eq!(registry.score_of(accounts.bob), 1);
+            assert_eq!(registry.score_of(accounts.charlie), 2);
+            assert_eq!(registry.score_of(accounts.dave), 3);
+        }
+
+        #[ink::test]
+        fn migrated_records_carry_migration_block() {
+            let accounts = fixtures::accounts();
+            let mut registry = upgraded(&[(accounts.bob, 7)]);
+            fixtures::set_block_number(5);
+            assert_eq!(registry.migrate(1), Ok(1));
+            assert_eq!(
+                registry.record_of(accounts.bob),
+                Some(Record { score: 7, updated_at: 5 })
+            );
+        }
+
+        #[ink::test]
+        fn set_score_replaces_legacy_entry() {
+            let accounts = fixtures::accounts();
+            let mut registry = upgraded(&[(accounts.bob, 7)]);
+            assert_eq!(registry.set_score(accounts.bob, u64::from(u32::MAX) + 1), Ok(()));
+            assert_eq!(registry.score_of(accounts.bob), u64::from(u32::MAX) + 1);
+
+            // The migration passes over bob without bringing back the old score.
+            assert_eq!(registry.pending_migrations(), 1);
+            assert_eq!(registry.migrate(10), Ok(0));
+            assert_eq!(registry.storage_version(), STORAGE_VERSION);
+            assert_eq!(registry.score_of(accounts.bob), u64::from(u32::MAX) + 1);
+        }
+
+        #[ink::test]
+        fn migration_refuses_to_run_twice() {
+            let accounts = fixtures::accounts();
+            let mut registry = upgraded(&[(accounts.bob, 7)]);
+            assert_eq!(registry.migrate(10), Ok(1));
+            assert_eq!(
+                registry.migrate(10),
+                Err(Error::UnexpectedVersion {
+                    expected: 1,
+                    found: 2
+                })
+            );
+            assert_eq!(registry.score_of(accounts.bob), 7);
+        }
+
+        #[ink::test]
+        fn empty_v1_storage_migrates_in_one_call() {
+            let mut registry = upgraded(&[]);
+            assert_eq!(registry.migrate(10), Ok(0));
+            assert_eq!(registry.storage_version(), STORAGE_VERSION);
+        }
+
+        #[ink::test]
+        fn fresh_deployment_has_nothing_to_migrate() {
+            let mut registry = ScoreRegistry::new();
+            assert_eq!(registry.storage_version(), STORAGE_VERSION);
+            assert_eq!(
+                registry.migrate(10),
+                Err(Error::UnexpectedVersion {
+                    expected: 1,
+                    found: 2
+                })
+            );
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink_e2e::ContractsBackend;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        /// Deploys v1, records scores, upgrades the same address to v2, migrates,
+        /// and checks every score survived the layout change.
+        ///
+        /// `ink_e2e` builds v1 because it is a dev-dependency of this contract with
+        /// the `ink-as-dependency` feature:
+        ///
+        /// ```toml
+        /// migrate_v1 = { path = "../migrate_v1", default-features = false, features = ["ink-as-dependency"] }
+        /// ```
+        #[ink_e2e::test]
+        async fn data_survives_upgrade<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let v1 = client
+                .instantiate("migrate_v1", &ink_e2e::alice(), &mut migrate_v1::ScoreRegistryRef::new())
+                .submit()
+                .await
+                .expect("v1 instantiate failed");
+            let mut v1_calls = v1.call_builder::<migrate_v1::ScoreRegistry>();
+
+            let bob = AccountId::from(ink_e2e::bob().public_key().0);
+            let charlie = AccountId::from(ink_e2e::charlie().public_key().0);
+            for (learner, score) in [(bob, 10), (charlie, 20)] {
+                client
+                    .call(&ink_e2e::alice(), &v1_calls.set_score(learner, score))
+                    .submit()
+                    .await
+                    .expect("set_score failed");
+            }
+
+            let v2_hash = client
+                .upload("migrate_v2", &ink_e2e::alice())
+                .submit()
+                .await
+                .expect("v2 upload failed")
+                .code_hash;
+            client
+                .call(&ink_e2e::alice(), &v1_calls.upgrade(<[u8; 32]>::from(v2_hash)))
+                .submit()
+                .await
+                .expect("upgrade failed");
+
+            // Same address, new code.
+            let mut v2_calls = ink_e2e::create_call_builder::<ScoreRegistry>(v1.account_id);
+            let pending = client
+                .call(&ink_e2e::alice(), &v2_calls.pending_migrations())
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(pending, 2);
+            let version = client
+                .call(&ink_e2e::alice(), &v2_calls.storage_version())
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(version, 1);
+
+            client
+                .call(&ink_e2e::bob(), &v2_calls.migrate(10))
+                .submit()
+                .await
+                .expect("migrate failed");
+
+            for (learner, score) in [(bob, 10u64), (charlie, 20u64)] {
+                let stored = client
+                    .call(&ink_e2e::alice(), &v2_calls.score_of(learner))
+                    .dry_run()
+                    .await?
+                    .return_value();
+                assert_eq!(stored, score);
+            }
+            Ok(())
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Deploy migrate_v1.rs and record some scores (see its instructions).
+//
+// 2. Compile this contract:
+//    cargo +nightly contract build
+//
+// 3. Upload the code without instantiating it:
+//    - Go to https://polkadot.js.org/apps/
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code", upload the .contract file, and stop after the upload
+//    - Note the code hash
+//
+// 4. Upgrade and migrate:
+//    - Call "upgrade" on the v1 contract with the v2 code hash
+//    - Add the v1 address again using this contract's metadata
+//    - Call "migrate" with a batch size until "pendingMigrations" returns 0 and
+//      "storageVersion" returns 2
+//    - Use "scoreOf" to check the scores survived
@@ -0,0 +1,655 @@
+// examples/demo-contracts/multisig.rs
+//
+// A multisig wallet contract example for Polkadot using ink!
+// A fixed set of owners shares the wallet. Any owner submits a transaction (a call
+// to another contract, optionally with value), the other owners confirm it, and
+// once `threshold` owners have confirmed, anyone can execute it.
+//
+// Modules
+// -------
+// Like a Gnosis Safe, the wallet can delegate part of its power to "modules":
+// separate contracts that may execute transactions on its behalf without any
+// confirmations. A module could, for instance, let one owner spend a small daily
+// allowance alone, or let a recovery contract take over after a timeout, without
+// the wallet having to know anything about those rules.
+//
+// Modules plug in through the `ModuleExecutor` trait below. A module calls
+// `ModuleExecutor::exec_from_module` on the wallet (the selector is pinned in
+// `tools/selector/tests/pinned.rs`), and the wallet runs the transaction if the
+// caller is an enabled module. Enabling and disabling modules is itself something
+// only the wallet can do, so the owners submit and confirm a transaction calling
+// the wallet's own `enable_module` or `disable_module`.
+//
+// Enabling a module hands it the full power of the owners, including enabling
+// further modules. Only enable modules whose code the owners have reviewed.
+//
+// Execution
+// ---------
+// A transaction is removed before its call is made, so it cannot be executed
+// twice, even by a callee that calls back into the wallet. A failed call still
+// uses up the transaction; the owners submit it again if they want to retry.
+//
+// Transaction ids are the wallet's own nonce, kept in the utils crate's `Nonces`
+// like the forwarder's per-signer nonces, so no id is ever handed out twice.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+/// A call the wallet makes to another contract.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+#[derive(ink_storage::traits::SpreadLayout, ink_storage::traits::PackedLayout)]
+pub struct Transaction {
+    /// Contract to call.
+    pub callee: ink_env::AccountId,
+    /// Selector of the message to call.
+    pub selector: [u8; 4],
+    /// SCALE-encoded arguments of the message.
+    pub input: ink_prelude::vec::Vec<u8>,
+    /// Value sent along with the call.
+    pub transferred_value: u128,
+    /// Gas limit of the call, or zero for all remaining gas.
+    pub gas_limit: u64,
+}
+
+/// Why the wallet refused or failed to run a module's transaction.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ModuleError {
+    /// The caller is not an enabled module.
+    NotModule,
+    /// The call was made but failed.
+    CallFailed,
+}
+
+/// The interface modules use to act on behalf of the wallet.
+#[ink::trait_definition]
+pub trait ModuleExecutor {
+    /// Runs `transaction` without confirmations, if the caller is an enabled
+    /// module.
+    #[ink(message)]
+    fn exec_from_module(&mut self, transaction: Transaction) -> Result<(), ModuleError>;
+
+    /// Returns whether `module` is enabled.
+    #[ink(message)]
+    fn is_module(&self, module: ink_env::AccountId) -> bool;
+}
+
+#[ink::contract]
+mod multisig {
+    use super::{ModuleError, ModuleExecutor, Transaction};
+    use astranet_utils::nonces::{NonceError, Nonces};
+    use ink_env::{
+        call::{build_call, Call, ExecutionInput, Selector},
+        CallFlags,
+    };
+    use ink_prelude::vec::Vec;
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// Identifies a submitted transaction.
+    pub type TransactionId = u64;
+
+    /// The largest number of owners, which bounds the cost of the owner checks.
+    pub const MAX_OWNERS: u32 = 16;
+
+    /// The largest number of enabled modules.
+    pub const MAX_MODULES: u32 = 8;
+
+    /// The multisig error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not an owner.
+        NotOwner,
+        /// Returned if the caller is not the wallet itself.
+        NotWallet,
+        /// Returned if there is no pending transaction with the id.
+        UnknownTransaction,
+        /// Returned if the owner already confirmed the transaction.
+        AlreadyConfirmed,
+        /// Returned if the owner has not confirmed the transaction.
+        NotConfirmed,
+        /// Returned if fewer than `threshold` owners confirmed the transaction.
+        BelowThreshold,
+        /// Returned if the call of the transaction failed.
+        CallFailed,
+        /// Returned if the module is already enabled.
+        ModuleAlreadyEnabled,
+        /// Returned if the module is not enabled.
+        ModuleNotEnabled,
+        /// Returned if `MAX_MODULES` modules are already enabled.
+        TooManyModules,
+        /// Returned if the wallet has used every transaction id.
+        TooManyTransactions,
+    }
+
+    impl From<NonceError> for Error {
+        fn from(_: NonceError) -> Self {
+            Error::TooManyTransactions
+        }
+    }
+
+    /// The multisig result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Encodes the arguments of a transaction as the raw bytes they already are,
+    /// without the length prefix a `Vec<u8>` would get.
+    struct CallInput<'a>(&'a [u8]);
+
+    impl scale::Encode for CallInput<'_> {
+        fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+            dest.write(self.0);
+        }
+    }
+
+    // Events are mirrored in `crates/events/src/multisig.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when an owner submits a transaction.
+    #[ink(event)]
+    pub struct Submitted {
+        #[ink(topic)]
+        transaction_id: TransactionId,
+        #[ink(topic)]
+        owner: AccountId,
+    }
+
+    /// Event emitted when an owner confirms a transaction.
+    #[ink(event)]
+    pub struct Confirmed {
+        #[ink(topic)]
+        transaction_id: TransactionId,
+        #[ink(topic)]
+        owner: AccountId,
+    }
+
+    /// Event emitted when an owner takes back their confirmation.
+    #[ink(event)]
+    pub struct Revoked {
+        #[ink(topic)]
+        transaction_id: TransactionId,
+        #[ink(topic)]
+        owner: AccountId,
+    }
+
+    /// Event emitted when a confirmed transaction is executed.
+    #[ink(event)]
+    pub struct Executed {
+        #[ink(topic)]
+        transaction_id: TransactionId,
+        success: bool,
+    }
+
+    /// Event emitted when the wallet enables a module.
+    #[ink(event)]
+    pub struct ModuleEnabled {
+        #[ink(topic)]
+        module: AccountId,
+    }
+
+    /// Event emitted when the wallet disables a module.
+    #[ink(event)]
+    pub struct ModuleDisabled {
+        #[ink(topic)]
+        module: AccountId,
+    }
+
+    /// Event emitted when a module executes a transaction.
+    #[ink(event)]
+    pub struct ExecutedFromModule {
+        #[ink(topic)]
+        module: AccountId,
+        #[ink(topic)]
+        callee: AccountId,
+        success: bool,
+    }
+
+    /// The multisig storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Multisig {
+        /// Accounts that submit and confirm transactions.
+        owners: Vec<AccountId>,
+        /// Confirmations a transaction needs before it can be executed.
+        threshold: u32,
+        /// Mapping from transaction id to the pending transaction.
+        transactions: Mapping<TransactionId, Transaction>,
+        /// Mapping from transaction id to its number of confirmations.
+        confirmation_counts: Mapping<TransactionId, u32>,
+        /// Set of (transaction id, owner) pairs where the owner confirmed.
+        confirmations: Mapping<(TransactionId, AccountId), ()>,
+        /// The wallet's nonce, which is the id the next transaction will get.
+        nonces: Nonces,
+        /// Contracts allowed to execute transactions without confirmations.
+        modules: Vec<AccountId>,
+    }
+
+    impl Multisig {
+        /// Creates a wallet shared by `owners`, executing transactions once
+        /// `threshold` of them have confirmed.
+        #[ink(constructor)]
+        pub fn new(owners: Vec<AccountId>, threshold: u32) -> Self {
+            assert!(
+                owners.len() as u32 <= MAX_OWNERS,
+                "too many owners for the wallet"
+            );
+            assert!(
+                threshold > 0 && threshold as usize <= owners.len(),
+                "threshold must be between one and the number of owners"
+            );
+            assert!(
+                owners
+                    .iter()
+                    .enumerate()
+                    .all(|(i, owner)| !owners[i + 1..].contains(owner)),
+                "owners must be distinct"
+            );
+            ink_lang::utils::initialize_contract(|wallet: &mut Self| {
+                wallet.owners = owners;
+                wallet.threshold = threshold;
+            })
+        }
+
+        /// Returns the owners.
+        #[ink(message)]
+        pub fn owners(&self) -> Vec<AccountId> {
+            self.owners.clone()
+        }
+
+        /// Returns the number of confirmations a transaction needs.
+        #[ink(message)]
+        pub fn threshold(&self) -> u32 {
+            self.threshold
+        }
+
+        /// Returns the enabled modules.
+        #[ink(message)]
+        pub fn modules(&self) -> Vec<AccountId> {
+            self.modules.clone()
+        }
+
+        /// Returns the pending transaction with `transaction_id`, if any.
+        #[ink(message)]
+        pub fn transaction(&self, transaction_id: TransactionId) -> Option<Transaction> {
+            self.transactions.get(transaction_id)
+        }
+
+        /// Returns the number of confirmations of `transaction_id`.
+        #[ink(message)]
+        pub fn confirmation_count(&self, transaction_id: TransactionId) -> u32 {
+            self.confirmation_counts.get(transaction_id).unwrap_or(0)
+        }
+
+        /// Returns whether `owner` confirmed `transaction_id`.
+        #[ink(message)]
+        pub fn is_confirmed_by(&self, transaction_id: TransactionId, owner: AccountId) -> bool {
+            self.confirmations.contains((transaction_id, owner))
+        }
+
+        /// Submits `transaction`, confirms it for the caller, and returns its id.
+        /// Only owners can call this.
+        #[ink(message)]
+        pub fn submit(&mut self, transaction: Transaction) -> Result<TransactionId> {
+            let owner = self.ensure_owner()?;
+            let transaction_id = self.nonces.use_nonce(&self.env().account_id())?;
+            self.transactions.insert(transaction_id, &transaction);
+            self.env().emit_event(Submitted {
+                transaction_id,
+                owner,
+            });
+            self.confirm(transaction_id)?;
+            Ok(transaction_id)
+        }
+
+        /// Confirms `transaction_id` for the caller. Only owners can call this.
+        #[ink(message)]
+        pub fn confirm(&mut self, transaction_id: TransactionId) -> Result<()> {
+            let owner = self.ensure_owner()?;
+            self.ensure_pending(transaction_id)?;
+            if self.is_confirmed_by(transaction_id, owner) {
+                return Err(Error::AlreadyConfirmed);
+            }
+            self.confirmations.insert((transaction_id, owner), &());
+            let count = self.confirmation_count(transaction_id) + 1;
+            self.confirmation_counts.insert(transaction_id, &count);
+            self.env().emit_event(Confirmed {
+                transaction_id,
+                owner,
+            });
+            Ok(())
+        }
+
+        /// Takes back the caller's confirmation of `transaction_id`. Only owners
+        /// can call this.
+        #[ink(message)]
+        pub fn revoke(&mut self, transaction_id: TransactionId) -> Result<()> {
+            let owner = self.ensure_owner()?;
+            self.ensure_pending(transaction_id)?;
+            if !self.is_confirmed_by(transaction_id, owner) {
+                return Err(Error::NotConfirmed);
+            }
+            self.confirmations.remove((transaction_id, owner));
+            let count = self.confirmation_count(transaction_id) - 1;
+            self.confirmation_counts.insert(transaction_id, &count);
+            self.env().emit_event(Revoked {
+                transaction_id,
+                owner,
+            });
+            Ok(())
+        }
+
+        /// Executes `transaction_id` once enough owners have confirmed it. Anyone
+        /// can call this.
+        #[ink(message)]
+        pub fn execute(&mut self, transaction_id: TransactionId) -> Result<()> {
+            let transaction = self
+                .transaction(transaction_id)
+                .ok_or(Error::UnknownTransaction)?;
+            if self.confirmation_count(transaction_id) < self.threshold {
+                return Err(Error::BelowThreshold);
+            }
+            self.take_transaction(transaction_id);
+            let success = self.dispatch(&transaction);
+            self.env().emit_event(Executed {
+                transaction_id,
+                success,
+            });
+            if !success {
+                return Err(Error::CallFailed);
+            }
+            Ok(())
+        }
+
+        /// Lets `module` execute transactions without confirmations. Only the
+        /// wallet itself can call this, through a confirmed transaction.
+        #[ink(message)]
+        pub fn enable_module(&mut self, module: AccountId) -> Result<()> {
+            self.ensure_from_wallet()?;
+            if self.modules.contains(&module) {
+                return Err(Error::ModuleAlreadyEnabled);
+            }
+            if self.modules.len() as u32 >= MAX_MODULES {
+                return Err(Error::TooManyModules);
+            }
+            self.modules.push(module);
+            self.env().emit_event(ModuleEnabled { module });
+            Ok(())
+        }
+
+        /// Takes away the power of `module`. Only the wallet itself can call this,
+        /// through a confirmed transaction.
+        #[ink(message)]
+        pub fn disable_module(&mut self, module: AccountId) -> Result<()> {
+            self.ensure_from_wallet()?;
+            let index = self
+                .modules
+                .iter()
+                .position(|enabled| *enabled == module)
+                .ok_or(Error::ModuleNotEnabled)?;
+            self.modules.swap_remove(index);
+            self.env().emit_event(ModuleDisabled { module });
+            Ok(())
+        }
+
+        /// Removes `transaction_id` and its confirmations.
+        fn take_transaction(&mut self, transaction_id: TransactionId) {
+            self.transactions.remove(transaction_id);
+            self.confirmation_counts.remove(transaction_id);
+            for owner in &self.owners {
+                self.confirmations.remove((transaction_id, *owner));
+            }
+        }
+
+        /// Makes the call described by `transaction`, returning whether it
+        /// succeeded. Re-entry is allowed so that transactions can call the
+        /// wallet's own messages, such as `enable_module`.
+        fn dispatch(&self, transaction: &Transaction) -> bool {
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(
+                    Call::new()
+                        .callee(transaction.callee)
+                        .gas_limit(transaction.gas_limit)
+                        .transferred_value(transaction.transferred_value),
+                )
+                .call_flags(CallFlags::default().set_allow_reentry(true))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(transaction.selector))
+                        .push_arg(CallInput(&transaction.input)),
+                )
+                .returns::<()>()
+                .fire()
+                .is_ok()
+        }
+
+        /// Returns `UnknownTransaction` unless `transaction_id` is pending.
+        fn ensure_pending(&self, transaction_id: TransactionId) -> Result<()> {
+            if !self.transactions.contains(transaction_id) {
+                return Err(Error::UnknownTransaction);
+            }
+            Ok(())
+        }
+
+        /// Returns the caller if they are an owner, and `NotOwner` otherwise.
+        fn ensure_owner(&self) -> Result<AccountId> {
+            let caller = self.env().caller();
+            if !self.owners.contains(&caller) {
+                return Err(Error::NotOwner);
+            }
+            Ok(caller)
+        }
+
+        /// Returns `NotWallet` unless the wallet is calling itself.
+        fn ensure_from_wallet(&self) -> Result<()> {
+            if self.env().caller() != self.env().account_id() {
+                return Err(Error::NotWallet);
+            }
+            Ok(())
+        }
+    }
+
+    impl ModuleExecutor for Multisig {
+        #[ink(message)]
+        fn exec_from_module(
+            &mut self,
+            transaction: Transaction,
+        ) -> core::result::Result<(), ModuleError> {
+            let module = self.env().caller();
+            if !self.is_module(module) {
+                return Err(ModuleError::NotModule);
+            }
+            let success = self.dispatch(&transaction);
+            self.env().emit_event(ExecutedFromModule {
+                module,
+                callee: transaction.callee,
+                success,
+            });
+            if !success {
+                return Err(ModuleError::CallFailed);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn is_module(&self, module: AccountId) -> bool {
+            self.modules.contains(&module)
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// stop short of executing transactions, and act as the wallet itself by
+    /// setting the caller to the contract's own account.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        /// Returns the `n`-th test module, clear of the default accounts.
+        fn module(n: u8) -> AccountId {
+            AccountId::from([0x80 + n; 32])
+        }
+
+        /// A wallet shared by alice, bob and charlie with a threshold of 2. The
+        /// off-chain environment deploys contracts at alice's account by default,
+        /// so the wallet is moved to an account of its own.
+        fn wallet() -> Multisig {
+            let accounts = fixtures::accounts();
+            ink_env::test::set_callee::<Env>(AccountId::from([0x60; 32]));
+            Multisig::new(vec![accounts.alice, accounts.bob, accounts.charlie], 2)
+        }
+
+        fn transaction() -> Transaction {
+            Transaction {
+                callee: AccountId::from([0x70; 32]),
+                selector: [0x84, 0xa1, 0x5d, 0xa1],
+                input: vec![0; 48],
+                transferred_value: 0,
+                gas_limit: 0,
+            }
+        }
+
+        /// Calls `message` as the wallet itself, as a confirmed transaction would.
+        fn as_wallet<R>(message: impl FnOnce() -> R) -> R {
+            fixtures::with_caller(ink_env::test::callee::<Env>(), message)
+        }
+
+        #[ink::test]
+        fn submit_confirms_for_the_submitter() {
+            let accounts = fixtures::accounts();
+            let mut wallet = wallet();
+            assert_eq!(wallet.submit(transaction()), Ok(0));
+            assert_eq!(wallet.confirmation_count(0), 1);
+            assert!(wallet.is_confirmed_by(0, accounts.alice));
+            assert_eq!(wallet.confirm(0), Err(Error::AlreadyConfirmed));
+            assert_eq!(wallet.execute(0), Err(Error::BelowThreshold));
+
+            fixtures::set_caller(accounts.dave);
+            assert_eq!(wallet.submit(transaction()), Err(Error::NotOwner));
+            assert_eq!(wallet.confirm(0), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn transaction_ids_count_up_across_owners() {
+            let accounts = fixtures::accounts();
+            let mut wallet = wallet();
+            assert_eq!(wallet.submit(transaction()), Ok(0));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(wallet.submit(transaction()), Ok(1));
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(wallet.submit(transaction()), Ok(2));
+            assert_eq!(wallet.confirmation_count(1), 1);
+            assert!(wallet.is_confirmed_by(1, accounts.bob));
+        }
+
+        #[ink::test]
+        fn confirmations_can_be_revoked() {
+            let accounts = fixtures::accounts();
+            let mut wallet = wallet();
+            assert_eq!(wallet.submit(transaction()), Ok(0));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(wallet.revoke(0), Err(Error::NotConfirmed));
+            assert_eq!(wallet.confirm(0), Ok(()));
+            assert_eq!(wallet.confirmation_count(0), 2);
+            assert_eq!(wallet.revoke(0), Ok(()));
+            assert_eq!(wallet.confirmation_count(0), 1);
+            assert_eq!(wallet.execute(0), Err(Error::BelowThreshold));
+            assert_eq!(wallet.confirm(1), Err(Error::UnknownTransaction));
+        }
+
+        #[ink::test]
+        fn only_the_wallet_manages_modules() {
+            let mut wallet = wallet();
+            // Owners have to go through a confirmed transaction.
+            assert_eq!(wallet.enable_module(module(0)), Err(Error::NotWallet));
+            assert_eq!(wallet.disable_module(module(0)), Err(Error::NotWallet));
+
+            assert_eq!(as_wallet(|| wallet.enable_module(module(0))), Ok(()));
+            assert_eq!(as_wallet(|| wallet.enable_module(module(1))), Ok(()));
+            assert!(wallet.is_module(module(0)));
+            assert_eq!(
+                as_wallet(|| wallet.enable_module(module(0))),
+                Err(Error::ModuleAlreadyEnabled)
+            );
+
+            assert_eq!(as_wallet(|| wallet.disable_module(module(0))), Ok(()));
+            assert!(!wallet.is_module(module(0)));
+            assert_eq!(wallet.modules(), vec![module(1)]);
+            assert_eq!(
+                as_wallet(|| wallet.disable_module(module(0))),
+                Err(Error::ModuleNotEnabled)
+            );
+        }
+
+        #[ink::test]
+        fn modules_are_bounded() {
+            let mut wallet = wallet();
+            for n in 0..MAX_MODULES as u8 {
+                assert_eq!(as_wallet(|| wallet.enable_module(module(n))), Ok(()));
+            }
+            assert_eq!(
+                as_wallet(|| wallet.enable_module(module(MAX_MODULES as u8))),
+                Err(Error::TooManyModules)
+            );
+        }
+
+        #[ink::test]
+        fn only_modules_skip_confirmations() {
+            let accounts = fixtures::accounts();
+            let mut wallet = wallet();
+            // Not even an owner can execute alone.
+            assert_eq!(
+                wallet.exec_from_module(transaction()),
+                Err(ModuleError::NotModule)
+            );
+            assert_eq!(as_wallet(|| wallet.enable_module(module(0))), Ok(()));
+            assert_eq!(as_wallet(|| wallet.disable_module(module(0))), Ok(()));
+            // A disabled module has lost its power.
+            assert_eq!(
+                fixtures::with_caller(module(0), || wallet.exec_from_module(transaction())),
+                Err(ModuleError::NotModule)
+            );
+            assert!(!wallet.is_module(accounts.alice));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "threshold must be between one and the number of owners")]
+        fn threshold_above_owners_panics() {
+            let accounts = fixtures::accounts();
+            Multisig::new(vec![accounts.alice], 2);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "owners must be distinct")]
+        fn duplicate_owners_panic() {
+            let accounts = fixtures::accounts();
+            Multisig::new(vec![accounts.alice, accounts.alice], 1);
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Upload the generated .contract file and deploy it with the owners and the
+//      threshold
+//
+// 4. Interact with the contract:
+//    - Owners use "submit", "confirm" and "revoke"; anyone uses "execute"
+//    - To enable a module, submit a transaction calling the wallet's own
+//      "enableModule" and have enough owners confirm it
+//    - The module then calls "ModuleExecutor::execFromModule" on the wallet
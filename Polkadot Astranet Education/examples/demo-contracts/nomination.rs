@@ -0,0 +1,671 @@
+// examples/demo-contracts/nomination.rs
+//
+// A delegated staking contract example for Polkadot using ink!
+// On Polkadot, nominators back validators with their stake and share in the
+// validators' rewards, after the validator takes its commission. This contract
+// plays the same game with an ERC-20 stake token: operators register with a
+// commission rate, token holders delegate stake to the operators they trust, and
+// whoever pays an operator's rewards (a protocol, or the operator itself) sends them
+// to `distribute` as native tokens.
+//
+// Layered accounting
+// ------------------
+// Every distribution is split twice. First the operator's commission, in basis
+// points, is set aside for the operator. Then the rest is added to the operator's
+// own reward-per-token accumulator, the same technique staking.rs uses for the
+// whole pool, so each delegator's rewards are their delegation times the growth of
+// their operator's accumulator since they last settled. A commission change only
+// affects later distributions. Every division rounds down, and the leftovers stay
+// in the contract.
+//
+// Stake moves with `SafeErc20`; it is pulled before a delegation is recorded, and
+// the record is restored if sending it back fails. Rewards and commission are paid
+// in native tokens.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod nomination {
+    use astranet_utils::{
+        fixed_point::{mul_div, FixedU128},
+        safe_erc20::SafeErc20,
+    };
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Basis points in a whole reward.
+    pub const BPS: u128 = 10_000;
+
+    /// Returns `fraction_bps` basis points of `amount`, rounded down.
+    pub fn share_of(amount: Balance, fraction_bps: u128) -> Balance {
+        // `fraction_bps <= BPS`, so the share never exceeds `amount`.
+        mul_div(amount, fraction_bps, BPS).unwrap_or(amount)
+    }
+
+    /// The nomination error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the amount is zero.
+        ZeroAmount,
+        /// Returned if a commission is more than `BPS` basis points.
+        InvalidCommission,
+        /// Returned if the caller is already an operator.
+        AlreadyRegistered,
+        /// Returned if no operator is registered under the account.
+        UnknownOperator,
+        /// Returned if the caller has delegated less than the amount.
+        InsufficientDelegation,
+        /// Returned if rewards are sent to an operator nobody delegates to.
+        NoDelegations,
+        /// Returned if there is nothing to claim.
+        NothingToClaim,
+        /// Returned if a transfer failed.
+        TransferFailed,
+    }
+
+    /// The nomination result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// An operator delegators can back.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Operator {
+        /// Share of each distribution the operator keeps, in basis points.
+        pub commission_bps: u128,
+        /// Sum of the delegations to the operator.
+        pub total_delegated: Balance,
+        /// Rewards per delegated token distributed to the operator's delegators.
+        pub reward_per_token: FixedU128,
+        /// Commission earned and not claimed yet.
+        pub commission_owed: Balance,
+    }
+
+    /// A delegator's stake behind one operator.
+    #[derive(
+        Debug,
+        Default,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        scale::Encode,
+        scale::Decode,
+        SpreadLayout,
+        PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Delegation {
+        /// Tokens delegated.
+        pub amount: Balance,
+        /// Value of the operator's accumulator when the delegation last settled.
+        pub reward_per_token_paid: FixedU128,
+        /// Rewards settled and not claimed yet.
+        pub owed: Balance,
+    }
+
+    // Events are mirrored in `crates/events/src/nomination.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when an operator registers.
+    #[ink(event)]
+    pub struct OperatorRegistered {
+        #[ink(topic)]
+        operator: AccountId,
+        commission_bps: u128,
+    }
+
+    /// Event emitted when an operator changes their commission.
+    #[ink(event)]
+    pub struct CommissionChanged {
+        #[ink(topic)]
+        operator: AccountId,
+        commission_bps: u128,
+    }
+
+    /// Event emitted when stake is delegated to an operator.
+    #[ink(event)]
+    pub struct Delegated {
+        #[ink(topic)]
+        delegator: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when stake is taken back from an operator.
+    #[ink(event)]
+    pub struct Undelegated {
+        #[ink(topic)]
+        delegator: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when rewards are distributed to an operator's delegators.
+    #[ink(event)]
+    pub struct RewardsDistributed {
+        #[ink(topic)]
+        operator: AccountId,
+        reward: Balance,
+        commission: Balance,
+    }
+
+    /// Event emitted when a delegator claims their rewards from an operator.
+    #[ink(event)]
+    pub struct RewardsClaimed {
+        #[ink(topic)]
+        delegator: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when an operator claims their commission.
+    #[ink(event)]
+    pub struct CommissionClaimed {
+        #[ink(topic)]
+        operator: AccountId,
+        amount: Balance,
+    }
+
+    /// The nomination storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Nomination {
+        /// The ERC-20 token delegators stake.
+        stake_token: SafeErc20,
+        /// Mapping from account to their operator record.
+        operators: Mapping<AccountId, Operator>,
+        /// Mapping from (delegator, operator) to the delegation.
+        delegations: Mapping<(AccountId, AccountId), Delegation>,
+    }
+
+    impl Nomination {
+        /// Creates a nomination pool staking `stake_token`.
+        #[ink(constructor)]
+        pub fn new(stake_token: AccountId) -> Self {
+            ink_lang::utils::initialize_contract(|nomination: &mut Self| {
+                nomination.stake_token = SafeErc20::new(stake_token);
+            })
+        }
+
+        /// Returns the record of `operator`, if registered.
+        #[ink(message)]
+        pub fn operator(&self, operator: AccountId) -> Option<Operator> {
+            self.operators.get(operator)
+        }
+
+        /// Returns what `delegator` has delegated to `operator`.
+        #[ink(message)]
+        pub fn delegation_of(&self, delegator: AccountId, operator: AccountId) -> Balance {
+            self.delegation(delegator, operator).amount
+        }
+
+        /// Returns the rewards `delegator` could claim from `operator`.
+        #[ink(message)]
+        pub fn pending_rewards(&self, delegator: AccountId, operator: AccountId) -> Balance {
+            match self.operators.get(operator) {
+                Some(record) => Self::earned(&self.delegation(delegator, operator), &record),
+                None => 0,
+            }
+        }
+
+        /// Registers the caller as an operator keeping `commission_bps` of their
+        /// delegators' rewards.
+        #[ink(message)]
+        pub fn register_operator(&mut self, commission_bps: u128) -> Result<()> {
+            if commission_bps > BPS {
+                return Err(Error::InvalidCommission);
+            }
+            let operator = self.env().caller();
+            if self.operators.get(operator).is_some() {
+                return Err(Error::AlreadyRegistered);
+            }
+            self.operators.insert(
+                operator,
+                &Operator {
+                    commission_bps,
+                    total_delegated: 0,
+                    reward_per_token: FixedU128::default(),
+                    commission_owed: 0,
+                },
+            );
+            self.env().emit_event(OperatorRegistered {
+                operator,
+                commission_bps,
+            });
+            Ok(())
+        }
+
+        /// Sets the caller's commission for later distributions.
+        #[ink(message)]
+        pub fn set_commission(&mut self, commission_bps: u128) -> Result<()> {
+            if commission_bps > BPS {
+                return Err(Error::InvalidCommission);
+            }
+            let operator = self.env().caller();
+            let mut record = self.operator_record(operator)?;
+            record.commission_bps = commission_bps;
+            self.operators.insert(operator, &record);
+            self.env().emit_event(CommissionChanged {
+                operator,
+                commission_bps,
+            });
+            Ok(())
+        }
+
+        /// Delegates `amount` of the stake token to `operator`. The caller must
+        /// have approved the contract to spend it.
+        #[ink(message)]
+        pub fn delegate(&mut self, operator: AccountId, amount: Balance) -> Result<()> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.operator_record(operator)?;
+            let delegator = self.env().caller();
+            if self
+                .stake_token
+                .transfer_from(delegator, self.env().account_id(), amount)
+                .is_err()
+            {
+                return Err(Error::TransferFailed);
+            }
+            self.add_delegation(delegator, operator, amount)?;
+            self.env().emit_event(Delegated {
+                delegator,
+                operator,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Takes `amount` of the caller's delegation back from `operator`.
+        #[ink(message)]
+        pub fn undelegate(&mut self, operator: AccountId, amount: Balance) -> Result<()> {
+            let delegator = self.env().caller();
+            self.remove_delegation(delegator, operator, amount)?;
+            if self.stake_token.transfer(delegator, amount).is_err() {
+                self.add_delegation(delegator, operator, amount)?;
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(Undelegated {
+                delegator,
+                operator,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Distributes the transferred value to `operator`'s delegators, less
+        /// the operator's commission.
+        #[ink(message, payable)]
+        pub fn distribute(&mut self, operator: AccountId) -> Result<()> {
+            let reward = self.env().transferred_value();
+            if reward == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let mut record = self.operator_record(operator)?;
+            if record.total_delegated == 0 {
+                return Err(Error::NoDelegations);
+            }
+            let commission = share_of(reward, record.commission_bps);
+            // Both factors are bounded by the total supply, so this cannot
+            // overflow in practice; saturate rather than trap if it somehow does.
+            let increase = FixedU128::from_rational(reward - commission, record.total_delegated)
+                .unwrap_or_else(|| FixedU128::from_inner(u128::MAX));
+            record.reward_per_token = record
+                .reward_per_token
+                .checked_add(increase)
+                .unwrap_or_else(|| FixedU128::from_inner(u128::MAX));
+            record.commission_owed += commission;
+            self.operators.insert(operator, &record);
+            self.env().emit_event(RewardsDistributed {
+                operator,
+                reward,
+                commission,
+            });
+            Ok(())
+        }
+
+        /// Sends the caller their rewards from `operator`.
+        #[ink(message)]
+        pub fn claim_rewards(&mut self, operator: AccountId) -> Result<Balance> {
+            let delegator = self.env().caller();
+            let (_, mut delegation) = self.settle(delegator, operator)?;
+            let amount = delegation.owed;
+            if amount == 0 {
+                return Err(Error::NothingToClaim);
+            }
+            delegation.owed = 0;
+            self.delegations.insert((delegator, operator), &delegation);
+            if self.env().transfer(delegator, amount).is_err() {
+                delegation.owed = amount;
+                self.delegations.insert((delegator, operator), &delegation);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(RewardsClaimed {
+                delegator,
+                operator,
+                amount,
+            });
+            Ok(amount)
+        }
+
+        /// Sends the caller, an operator, their commission.
+        #[ink(message)]
+        pub fn claim_commission(&mut self) -> Result<Balance> {
+            let operator = self.env().caller();
+            let mut record = self.operator_record(operator)?;
+            let amount = record.commission_owed;
+            if amount == 0 {
+                return Err(Error::NothingToClaim);
+            }
+            record.commission_owed = 0;
+            self.operators.insert(operator, &record);
+            if self.env().transfer(operator, amount).is_err() {
+                record.commission_owed = amount;
+                self.operators.insert(operator, &record);
+                return Err(Error::TransferFailed);
+            }
+            self.env()
+                .emit_event(CommissionClaimed { operator, amount });
+            Ok(amount)
+        }
+
+        /// Returns the record of `operator`, or `UnknownOperator`.
+        fn operator_record(&self, operator: AccountId) -> Result<Operator> {
+            self.operators.get(operator).ok_or(Error::UnknownOperator)
+        }
+
+        /// Returns the delegation of `delegator` to `operator`, empty if none.
+        fn delegation(&self, delegator: AccountId, operator: AccountId) -> Delegation {
+            self.delegations
+                .get((delegator, operator))
+                .unwrap_or_default()
+        }
+
+        /// Returns the rewards of `delegation` once settled against `record`.
+        fn earned(delegation: &Delegation, record: &Operator) -> Balance {
+            let growth = record
+                .reward_per_token
+                .checked_sub(delegation.reward_per_token_paid)
+                .unwrap_or_default();
+            let fresh = growth
+                .checked_mul_int(delegation.amount)
+                .unwrap_or(Balance::MAX);
+            delegation.owed.saturating_add(fresh)
+        }
+
+        /// Brings the delegation of `delegator` to `operator` up to the operator's
+        /// accumulator, and returns both records.
+        fn settle(
+            &mut self,
+            delegator: AccountId,
+            operator: AccountId,
+        ) -> Result<(Operator, Delegation)> {
+            let record = self.operator_record(operator)?;
+            let mut delegation = self.delegation(delegator, operator);
+            delegation.owed = Self::earned(&delegation, &record);
+            delegation.reward_per_token_paid = record.reward_per_token;
+            Ok((record, delegation))
+        }
+
+        /// Records `amount` more delegated by `delegator` to `operator`.
+        fn add_delegation(
+            &mut self,
+            delegator: AccountId,
+            operator: AccountId,
+            amount: Balance,
+        ) -> Result<()> {
+            let (mut record, mut delegation) = self.settle(delegator, operator)?;
+            delegation.amount += amount;
+            record.total_delegated += amount;
+            self.delegations.insert((delegator, operator), &delegation);
+            self.operators.insert(operator, &record);
+            Ok(())
+        }
+
+        /// Records `amount` less delegated by `delegator` to `operator`.
+        fn remove_delegation(
+            &mut self,
+            delegator: AccountId,
+            operator: AccountId,
+            amount: Balance,
+        ) -> Result<()> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let (mut record, mut delegation) = self.settle(delegator, operator)?;
+            if delegation.amount < amount {
+                return Err(Error::InsufficientDelegation);
+            }
+            delegation.amount -= amount;
+            record.total_delegated -= amount;
+            self.delegations.insert((delegator, operator), &delegation);
+            self.operators.insert(operator, &record);
+            Ok(())
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// record delegations directly instead of pulling the stake token.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        fn operator() -> AccountId {
+            AccountId::from([0x81; 32])
+        }
+
+        /// A pool with one operator keeping `commission_bps`.
+        fn pool(commission_bps: u128) -> Nomination {
+            let mut nomination = Nomination::new(AccountId::from([0x70; 32]));
+            fixtures::set_caller(operator());
+            assert_eq!(nomination.register_operator(commission_bps), Ok(()));
+            fixtures::set_caller(fixtures::accounts().alice);
+            nomination
+        }
+
+        fn distribute(nomination: &mut Nomination, reward: Balance) -> Result<()> {
+            ink_env::test::set_value_transferred::<Env>(reward);
+            let result = nomination.distribute(operator());
+            ink_env::test::set_value_transferred::<Env>(0);
+            result
+        }
+
+        #[test]
+        fn commission_shares_round_down() {
+            assert_eq!(share_of(1_000, 1_000), 100);
+            assert_eq!(share_of(10, 3_333), 3);
+            assert_eq!(share_of(10, 0), 0);
+            assert_eq!(share_of(10, BPS), 10);
+        }
+
+        #[ink::test]
+        fn registration_is_checked() {
+            let mut nomination = pool(1_000);
+            fixtures::set_caller(operator());
+            assert_eq!(
+                nomination.register_operator(500),
+                Err(Error::AlreadyRegistered)
+            );
+            assert_eq!(
+                nomination.set_commission(BPS + 1),
+                Err(Error::InvalidCommission)
+            );
+            fixtures::set_caller(fixtures::accounts().bob);
+            assert_eq!(
+                nomination.register_operator(BPS + 1),
+                Err(Error::InvalidCommission)
+            );
+            assert_eq!(nomination.set_commission(0), Err(Error::UnknownOperator));
+            assert_eq!(
+                nomination.add_delegation(fixtures::accounts().bob, fixtures::accounts().bob, 1),
+                Err(Error::UnknownOperator)
+            );
+        }
+
+        #[ink::test]
+        fn rewards_flow_pro_rata_after_commission() {
+            let accounts = fixtures::accounts();
+            let mut nomination = pool(1_000);
+            assert_eq!(distribute(&mut nomination, 100), Err(Error::NoDelegations));
+            assert_eq!(
+                nomination.add_delegation(accounts.bob, operator(), 100),
+                Ok(())
+            );
+            assert_eq!(
+                nomination.add_delegation(accounts.charlie, operator(), 300),
+                Ok(())
+            );
+            assert_eq!(distribute(&mut nomination, 1_000), Ok(()));
+            assert_eq!(
+                nomination.operator(operator()).unwrap().commission_owed,
+                100
+            );
+            assert_eq!(nomination.pending_rewards(accounts.bob, operator()), 225);
+            assert_eq!(
+                nomination.pending_rewards(accounts.charlie, operator()),
+                675
+            );
+        }
+
+        #[ink::test]
+        fn commission_changes_apply_to_later_rewards() {
+            let accounts = fixtures::accounts();
+            let mut nomination = pool(1_000);
+            assert_eq!(
+                nomination.add_delegation(accounts.bob, operator(), 100),
+                Ok(())
+            );
+            assert_eq!(distribute(&mut nomination, 1_000), Ok(()));
+            fixtures::set_caller(operator());
+            assert_eq!(nomination.set_commission(BPS), Ok(()));
+            assert_eq!(distribute(&mut nomination, 1_000), Ok(()));
+            // The second reward went entirely to the operator.
+            assert_eq!(nomination.pending_rewards(accounts.bob, operator()), 900);
+            assert_eq!(
+                nomination.operator(operator()).unwrap().commission_owed,
+                1_100
+            );
+        }
+
+        #[ink::test]
+        fn rounding_leftovers_stay_in_the_contract() {
+            let accounts = fixtures::accounts();
+            let mut nomination = pool(3_333);
+            for delegator in [accounts.bob, accounts.charlie, accounts.dave] {
+                assert_eq!(nomination.add_delegation(delegator, operator(), 1), Ok(()));
+            }
+            // 3 commission, and 7 split three ways.
+            assert_eq!(distribute(&mut nomination, 10), Ok(()));
+            assert_eq!(nomination.operator(operator()).unwrap().commission_owed, 3);
+            for delegator in [accounts.bob, accounts.charlie, accounts.dave] {
+                assert_eq!(nomination.pending_rewards(delegator, operator()), 2);
+            }
+        }
+
+        #[ink::test]
+        fn late_delegators_do_not_share_earlier_rewards() {
+            let accounts = fixtures::accounts();
+            let mut nomination = pool(0);
+            assert_eq!(
+                nomination.add_delegation(accounts.bob, operator(), 100),
+                Ok(())
+            );
+            assert_eq!(distribute(&mut nomination, 500), Ok(()));
+            assert_eq!(
+                nomination.add_delegation(accounts.charlie, operator(), 100),
+                Ok(())
+            );
+            assert_eq!(distribute(&mut nomination, 500), Ok(()));
+            assert_eq!(nomination.pending_rewards(accounts.bob, operator()), 750);
+            assert_eq!(
+                nomination.pending_rewards(accounts.charlie, operator()),
+                250
+            );
+
+            // Undelegating keeps what was earned.
+            assert_eq!(
+                nomination.remove_delegation(accounts.bob, operator(), 101),
+                Err(Error::InsufficientDelegation)
+            );
+            assert_eq!(
+                nomination.remove_delegation(accounts.bob, operator(), 100),
+                Ok(())
+            );
+            assert_eq!(distribute(&mut nomination, 100), Ok(()));
+            assert_eq!(nomination.pending_rewards(accounts.bob, operator()), 750);
+            assert_eq!(
+                nomination.pending_rewards(accounts.charlie, operator()),
+                350
+            );
+        }
+
+        #[ink::test]
+        fn claims_pay_native_tokens() {
+            let accounts = fixtures::accounts();
+            let mut nomination = pool(1_000);
+            assert_eq!(
+                nomination.add_delegation(accounts.bob, operator(), 100),
+                Ok(())
+            );
+            assert_eq!(distribute(&mut nomination, 1_000), Ok(()));
+            fixtures::fund_contract(1_000);
+
+            fixtures::set_caller(accounts.bob);
+            let before = fixtures::balance_of(accounts.bob);
+            assert_eq!(nomination.claim_rewards(operator()), Ok(900));
+            assert_eq!(fixtures::balance_of(accounts.bob) - before, 900);
+            assert_eq!(
+                nomination.claim_rewards(operator()),
+                Err(Error::NothingToClaim)
+            );
+            assert_eq!(nomination.claim_commission(), Err(Error::UnknownOperator));
+
+            fixtures::set_caller(operator());
+            assert_eq!(nomination.claim_commission(), Ok(100));
+            assert_eq!(nomination.claim_commission(), Err(Error::NothingToClaim));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract (it depends on `crates/utils` for `FixedU128` and
+//    `SafeErc20`):
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Deploy the stake token first (examples/demo-contracts/erc20.rs)
+//    - Upload the generated .contract file and deploy it with the stake token
+//
+// 4. Interact with the contract:
+//    - Operators use "registerOperator" with their commission in basis points
+//    - Delegators approve the contract on the stake token, then use "delegate"
+//    - Send rewards to "distribute" with a value attached
+//    - Delegators use "claimRewards"; operators use "claimCommission"
@@ -0,0 +1,882 @@
+// examples/demo-contracts/orderbook.rs
+//
+// A limit order book contract example for Polkadot using ink!
+// This contract exchanges two ERC-20 tokens, a base token and a quote token. Users
+// deposit tokens into the exchange, place limit orders to buy or sell base tokens at
+// a price in quote tokens, and withdraw what they have left or received. Orders that
+// cross the book are filled right away, possibly only partly; the rest of an order
+// rests on the book until it is filled or cancelled.
+//
+// Data structures
+// ---------------
+// Storage has no sorted map, so the prices of each side of the book are kept in a
+// `StorageHeap` from the utils crate, a priority queue yielding the best price
+// first, next to a `Mapping<Price, Level>` holding the orders at each price:
+//
+//     bid_prices: 12, 11, 10, ...         (bids, best = highest)
+//     ask_prices: 13, 15, 20, ...         (asks, best = lowest)
+//
+// The heap always yields its greatest item, so bids are pushed as their price and
+// asks as `Price::MAX - price`; `Side::priority` converts in both directions. Each
+// level holds a FIFO queue of the orders at that price, a linked list through the
+// orders' `prev` and `next` ids. Matching takes from the head of the best level, so
+// orders at the same price fill in the order they were placed; a new price costs
+// a heap push, and cancelling an order unlinks it in constant time.
+//
+// A heap can only remove its top, so a level emptied by a cancellation further
+// down the book stays in storage with a total of zero, and its price stays in the
+// heap. It is reused if an order arrives at that price again, and dropped once it
+// reaches the top of the heap; the best price on either side is therefore always
+// a level with orders.
+//
+// Prices are in quote units per base unit: a buy of 5 at 12 locks 60 quote, a sell
+// of 5 locks 5 base. Trades execute at the price of the resting (maker) order, so a
+// buyer who bid above it is refunded the difference.
+//
+// Every fill costs gas, so a large order sweeping many small ones can run out of it;
+// a production exchange would cap the fills per call.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod orderbook {
+    use astranet_utils::storage_heap::StorageHeap;
+    use ink_env::call::{build_call, Call, ExecutionInput, Selector};
+    use ink_prelude::vec::Vec;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Selector of the ERC-20 `transfer(to, value)` message.
+    const TRANSFER_SELECTOR: [u8; 4] = [0x84, 0xa1, 0x5d, 0xa1];
+
+    /// Selector of the ERC-20 `transfer_from(from, to, value)` message.
+    const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x0b, 0x39, 0x6f, 0x18];
+
+    /// Identifies an order.
+    pub type OrderId = u64;
+
+    /// A price in quote units per base unit.
+    pub type Price = Balance;
+
+    /// The order book error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the amount is zero.
+        ZeroAmount,
+        /// Returned if the price is zero.
+        ZeroPrice,
+        /// Returned if the token is neither the base nor the quote token.
+        UnknownToken,
+        /// Returned if the caller's exchange balance is insufficient.
+        InsufficientBalance,
+        /// Returned if no open order has the given id.
+        OrderNotFound,
+        /// Returned if the caller did not place the order.
+        NotOrderOwner,
+        /// Returned if the token refused to move the tokens.
+        TransferFailed,
+        /// Returned if the cost of an order overflows.
+        Overflow,
+    }
+
+    /// The order book result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Stands in for the token's own error type. All the exchange needs is to tell
+    /// success from failure.
+    #[derive(scale::Decode)]
+    struct TokenError(#[allow(dead_code)] u8);
+
+    /// The side of an order.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub enum Side {
+        /// Buys base tokens with quote tokens.
+        Buy,
+        /// Sells base tokens for quote tokens.
+        Sell,
+    }
+
+    impl Side {
+        /// Returns the side orders of this side trade against.
+        pub fn opposite(self) -> Self {
+            match self {
+                Side::Buy => Side::Sell,
+                Side::Sell => Side::Buy,
+            }
+        }
+
+        /// Returns `true` if `a` is a better price than `b` for orders on this side.
+        pub fn is_better(self, a: Price, b: Price) -> bool {
+            match self {
+                Side::Buy => a > b,
+                Side::Sell => a < b,
+            }
+        }
+
+        /// Converts a price on this side to its key in the side's price heap, where
+        /// better prices are greater, and a key back to its price.
+        pub fn priority(self, price_or_key: Price) -> Price {
+            match self {
+                Side::Buy => price_or_key,
+                Side::Sell => Price::MAX - price_or_key,
+            }
+        }
+    }
+
+    /// An open order.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Order {
+        /// Account that placed the order.
+        pub owner: AccountId,
+        /// Whether the order buys or sells.
+        pub side: Side,
+        /// Limit price of the order.
+        pub price: Price,
+        /// Base amount not filled yet.
+        pub remaining: Balance,
+        /// Order placed just before this one at the same price.
+        pub prev: Option<OrderId>,
+        /// Order placed just after this one at the same price.
+        pub next: Option<OrderId>,
+    }
+
+    /// The orders resting at one price. A level with a `total` of zero has no
+    /// orders and is waiting to be dropped from the heap; `head` and `tail` are
+    /// then stale.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Level {
+        /// Oldest order at this price, filled first.
+        pub head: OrderId,
+        /// Newest order at this price.
+        pub tail: OrderId,
+        /// Base amount of all orders at this price.
+        pub total: Balance,
+    }
+
+    // Events are mirrored in `crates/events/src/orderbook.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when an order is placed. `amount` is the full amount, filled
+    /// or not.
+    #[ink(event)]
+    pub struct OrderPlaced {
+        #[ink(topic)]
+        order_id: OrderId,
+        #[ink(topic)]
+        owner: AccountId,
+        side: Side,
+        price: Price,
+        amount: Balance,
+    }
+
+    /// Event emitted when an order is cancelled with `remaining` unfilled.
+    #[ink(event)]
+    pub struct OrderCancelled {
+        #[ink(topic)]
+        order_id: OrderId,
+        remaining: Balance,
+    }
+
+    /// Event emitted when a resting order and an incoming order trade `amount` base
+    /// at the resting order's price.
+    #[ink(event)]
+    pub struct Trade {
+        #[ink(topic)]
+        maker_order: OrderId,
+        #[ink(topic)]
+        taker_order: OrderId,
+        price: Price,
+        amount: Balance,
+    }
+
+    /// The order book storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Orderbook {
+        /// Token bought and sold.
+        base: AccountId,
+        /// Token prices are paid in.
+        quote: AccountId,
+        /// Mapping from token and owner to exchange balance, not counting funds
+        /// locked in open orders.
+        balances: Mapping<(AccountId, AccountId), Balance>,
+        /// Mapping from order id to open order.
+        orders: Mapping<OrderId, Order>,
+        /// Mapping from price to the buy orders at it.
+        bids: Mapping<Price, Level>,
+        /// Mapping from price to the sell orders at it.
+        asks: Mapping<Price, Level>,
+        /// Prices of the levels in `bids`, as heap keys, best first.
+        bid_prices: StorageHeap<Price>,
+        /// Prices of the levels in `asks`, as heap keys, best first.
+        ask_prices: StorageHeap<Price>,
+        /// Id the next order will get.
+        next_order_id: OrderId,
+    }
+
+    impl Orderbook {
+        /// Creates an empty book exchanging `base` for `quote`.
+        #[ink(constructor)]
+        pub fn new(base: AccountId, quote: AccountId) -> Self {
+            ink_lang::utils::initialize_contract(|book: &mut Self| {
+                book.base = base;
+                book.quote = quote;
+            })
+        }
+
+        /// Returns the base token.
+        #[ink(message)]
+        pub fn base(&self) -> AccountId {
+            self.base
+        }
+
+        /// Returns the quote token.
+        #[ink(message)]
+        pub fn quote(&self) -> AccountId {
+            self.quote
+        }
+
+        /// Returns the exchange balance of `owner` in `token`, not counting funds
+        /// locked in open orders.
+        #[ink(message)]
+        pub fn balance_of(&self, token: AccountId, owner: AccountId) -> Balance {
+            self.balances.get((token, owner)).unwrap_or(0)
+        }
+
+        /// Returns the open order with id `order_id`.
+        #[ink(message)]
+        pub fn order(&self, order_id: OrderId) -> Option<Order> {
+            self.orders.get(order_id)
+        }
+
+        /// Returns the best price on `side`, if any order rests there.
+        #[ink(message)]
+        pub fn best_price(&self, side: Side) -> Option<Price> {
+            self.prices(side).peek().map(|key| side.priority(key))
+        }
+
+        /// Returns up to `max_levels` price levels of `side` with the base amount at
+        /// each, best price first.
+        #[ink(message)]
+        pub fn depth(&self, side: Side, max_levels: u32) -> Vec<(Price, Balance)> {
+            self.prices(side)
+                .iter_sorted()
+                .map(|key| {
+                    let price = side.priority(key);
+                    (price, self.level(side, price).total)
+                })
+                .filter(|(_, total)| *total > 0)
+                .take(max_levels as usize)
+                .collect()
+        }
+
+        /// Moves `amount` of `token` from the caller into their exchange balance. The
+        /// caller must have approved the exchange to spend at least `amount`.
+        #[ink(message)]
+        pub fn deposit(&mut self, token: AccountId, amount: Balance) -> Result<()> {
+            self.ensure_known(token)?;
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let owner = self.env().caller();
+            let exchange = self.env().account_id();
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(token).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(TRANSFER_FROM_SELECTOR))
+                        .push_arg(owner)
+                        .push_arg(exchange)
+                        .push_arg(amount),
+                )
+                .returns::<core::result::Result<(), TokenError>>()
+                .fire()
+                .map_err(|_| Error::TransferFailed)?
+                .map_err(|_| Error::TransferFailed)?;
+            self.credit(token, owner, amount);
+            Ok(())
+        }
+
+        /// Sends `amount` of `token` from the caller's exchange balance back to them.
+        #[ink(message)]
+        pub fn withdraw(&mut self, token: AccountId, amount: Balance) -> Result<()> {
+            self.ensure_known(token)?;
+            let owner = self.env().caller();
+            self.debit(token, owner, amount)?;
+            let sent = build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(token).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(TRANSFER_SELECTOR))
+                        .push_arg(owner)
+                        .push_arg(amount),
+                )
+                .returns::<core::result::Result<(), TokenError>>()
+                .fire();
+            if !matches!(sent, Ok(Ok(()))) {
+                self.credit(token, owner, amount);
+                return Err(Error::TransferFailed);
+            }
+            Ok(())
+        }
+
+        /// Places a limit order to buy or sell `amount` base at `price` and returns
+        /// its id. The order fills against the book as far as it crosses; whatever
+        /// is left rests on the book.
+        #[ink(message)]
+        pub fn place_order(
+            &mut self,
+            side: Side,
+            price: Price,
+            amount: Balance,
+        ) -> Result<OrderId> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            if price == 0 {
+                return Err(Error::ZeroPrice);
+            }
+            let owner = self.env().caller();
+            match side {
+                Side::Buy => {
+                    let cost = amount.checked_mul(price).ok_or(Error::Overflow)?;
+                    self.debit(self.quote, owner, cost)?;
+                }
+                Side::Sell => self.debit(self.base, owner, amount)?,
+            }
+
+            let order_id = self.next_order_id;
+            self.next_order_id = order_id + 1;
+            self.env().emit_event(OrderPlaced {
+                order_id,
+                owner,
+                side,
+                price,
+                amount,
+            });
+
+            let remaining = self.match_order(order_id, owner, side, price, amount);
+            if remaining > 0 {
+                self.push_order(
+                    order_id,
+                    Order {
+                        owner,
+                        side,
+                        price,
+                        remaining,
+                        prev: None,
+                        next: None,
+                    },
+                );
+            }
+            Ok(order_id)
+        }
+
+        /// Cancels order `order_id` and returns its locked funds to the caller's
+        /// exchange balance. Only the account that placed the order can call this.
+        #[ink(message)]
+        pub fn cancel_order(&mut self, order_id: OrderId) -> Result<()> {
+            let order = self.order(order_id).ok_or(Error::OrderNotFound)?;
+            if order.owner != self.env().caller() {
+                return Err(Error::NotOrderOwner);
+            }
+            self.unlink_order(order_id);
+            match order.side {
+                Side::Buy => self.credit(self.quote, order.owner, order.remaining * order.price),
+                Side::Sell => self.credit(self.base, order.owner, order.remaining),
+            }
+            self.env().emit_event(OrderCancelled {
+                order_id,
+                remaining: order.remaining,
+            });
+            Ok(())
+        }
+
+        /// Fills the incoming order `taker_order` against the opposite side, best
+        /// price first, and returns the base amount left unfilled.
+        fn match_order(
+            &mut self,
+            taker_order: OrderId,
+            taker: AccountId,
+            side: Side,
+            price: Price,
+            amount: Balance,
+        ) -> Balance {
+            let maker_side = side.opposite();
+            let mut remaining = amount;
+            while remaining > 0 {
+                let best = match self.best_price(maker_side) {
+                    // The orders cross unless the maker asks for more than the limit.
+                    Some(best) if !side.is_better(best, price) => best,
+                    _ => break,
+                };
+                let maker_order = self.level(maker_side, best).head;
+                let maker = self.orders.get(maker_order).expect("level head exists");
+                let filled = remaining.min(maker.remaining);
+
+                let (buyer, seller) = match side {
+                    Side::Buy => (taker, maker.owner),
+                    Side::Sell => (maker.owner, taker),
+                };
+                self.credit(self.base, buyer, filled);
+                self.credit(self.quote, seller, filled * best);
+                if side == Side::Buy && price > best {
+                    // The taker locked quote at its own limit; refund the improvement.
+                    self.credit(self.quote, taker, filled * (price - best));
+                }
+
+                self.reduce_order(maker_order, filled);
+                remaining -= filled;
+                self.env().emit_event(Trade {
+                    maker_order,
+                    taker_order,
+                    price: best,
+                    amount: filled,
+                });
+            }
+            remaining
+        }
+
+        /// Returns the price heap of `side`.
+        fn prices(&self, side: Side) -> &StorageHeap<Price> {
+            match side {
+                Side::Buy => &self.bid_prices,
+                Side::Sell => &self.ask_prices,
+            }
+        }
+
+        /// Returns the level at `price` on `side`, if it is in the heap.
+        fn find_level(&self, side: Side, price: Price) -> Option<Level> {
+            match side {
+                Side::Buy => self.bids.get(price),
+                Side::Sell => self.asks.get(price),
+            }
+        }
+
+        /// Returns the level at `price` on `side`, which must exist.
+        fn level(&self, side: Side, price: Price) -> Level {
+            self.find_level(side, price).expect("level exists")
+        }
+
+        /// Stores `level` at `price` on `side`.
+        fn set_level(&mut self, side: Side, price: Price, level: Level) {
+            match side {
+                Side::Buy => self.bids.insert(price, &level),
+                Side::Sell => self.asks.insert(price, &level),
+            }
+        }
+
+        /// Appends `order` to the queue at its price, creating the level and
+        /// adding its price to the heap if it is not there yet.
+        fn push_order(&mut self, order_id: OrderId, mut order: Order) {
+            let side = order.side;
+            let level = match self.find_level(side, order.price) {
+                Some(mut level) if level.total > 0 => {
+                    let mut tail = self.orders.get(level.tail).expect("level tail exists");
+                    tail.next = Some(order_id);
+                    self.orders.insert(level.tail, &tail);
+                    order.prev = Some(level.tail);
+                    level.tail = order_id;
+                    level.total += order.remaining;
+                    level
+                }
+                existing => {
+                    if existing.is_none() {
+                        let key = side.priority(order.price);
+                        match side {
+                            Side::Buy => self.bid_prices.push(key),
+                            Side::Sell => self.ask_prices.push(key),
+                        }
+                    }
+                    Level {
+                        head: order_id,
+                        tail: order_id,
+                        total: order.remaining,
+                    }
+                }
+            };
+            self.set_level(side, order.price, level);
+            self.orders.insert(order_id, &order);
+        }
+
+        /// Reduces order `order_id` by `filled`, removing it once nothing is left.
+        fn reduce_order(&mut self, order_id: OrderId, filled: Balance) {
+            let mut order = self.orders.get(order_id).expect("order exists");
+            if order.remaining == filled {
+                self.unlink_order(order_id);
+                return;
+            }
+            order.remaining -= filled;
+            self.orders.insert(order_id, &order);
+            let mut level = self.level(order.side, order.price);
+            level.total -= filled;
+            self.set_level(order.side, order.price, level);
+        }
+
+        /// Removes order `order_id` from its queue, leaving its level empty if it
+        /// was the last order there.
+        fn unlink_order(&mut self, order_id: OrderId) {
+            let order = self.orders.get(order_id).expect("order exists");
+            self.orders.remove(order_id);
+            let side = order.side;
+            let mut level = self.level(side, order.price);
+            level.total -= order.remaining;
+
+            match order.prev {
+                Some(prev) => {
+                    let mut before = self.orders.get(prev).expect("prev exists");
+                    before.next = order.next;
+                    self.orders.insert(prev, &before);
+                }
+                None => {
+                    if let Some(next) = order.next {
+                        level.head = next;
+                    }
+                }
+            }
+            match order.next {
+                Some(next) => {
+                    let mut after = self.orders.get(next).expect("next exists");
+                    after.prev = order.prev;
+                    self.orders.insert(next, &after);
+                }
+                None => {
+                    if let Some(prev) = order.prev {
+                        level.tail = prev;
+                    }
+                }
+            }
+            self.set_level(side, order.price, level);
+            if level.total == 0 {
+                self.drop_empty_levels(side);
+            }
+        }
+
+        /// Pops empty levels off the top of `side`'s heap until its best price has
+        /// orders again.
+        fn drop_empty_levels(&mut self, side: Side) {
+            while let Some(price) = self.best_price(side) {
+                if self.level(side, price).total > 0 {
+                    break;
+                }
+                match side {
+                    Side::Buy => {
+                        self.bid_prices.pop();
+                        self.bids.remove(price);
+                    }
+                    Side::Sell => {
+                        self.ask_prices.pop();
+                        self.asks.remove(price);
+                    }
+                }
+            }
+        }
+
+        /// Returns `UnknownToken` unless `token` is the base or the quote token.
+        fn ensure_known(&self, token: AccountId) -> Result<()> {
+            if token != self.base && token != self.quote {
+                return Err(Error::UnknownToken);
+            }
+            Ok(())
+        }
+
+        /// Adds `amount` to `owner`'s exchange balance in `token`.
+        fn credit(&mut self, token: AccountId, owner: AccountId, amount: Balance) {
+            let balance = self.balance_of(token, owner);
+            self.balances.insert((token, owner), &(balance + amount));
+        }
+
+        /// Takes `amount` from `owner`'s exchange balance in `token`.
+        fn debit(&mut self, token: AccountId, owner: AccountId, amount: Balance) -> Result<()> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let balance = self.balance_of(token, owner);
+            if balance < amount {
+                return Err(Error::InsufficientBalance);
+            }
+            self.balances.insert((token, owner), &(balance - amount));
+            Ok(())
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// fund exchange balances with `credit` instead of `deposit`.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        fn base() -> AccountId {
+            AccountId::from([0xba; 32])
+        }
+
+        fn quote() -> AccountId {
+            AccountId::from([0x0e; 32])
+        }
+
+        /// A book where alice, bob and charlie each hold 1_000 base and 100_000 quote.
+        fn new_book() -> Orderbook {
+            let accounts = fixtures::accounts();
+            let mut book = Orderbook::new(base(), quote());
+            for account in [accounts.alice, accounts.bob, accounts.charlie] {
+                book.credit(base(), account, 1_000);
+                book.credit(quote(), account, 100_000);
+            }
+            book
+        }
+
+        fn place_as(
+            book: &mut Orderbook,
+            who: AccountId,
+            side: Side,
+            price: Price,
+            amount: Balance,
+        ) -> OrderId {
+            fixtures::with_caller(who, || book.place_order(side, price, amount))
+                .expect("order placed")
+        }
+
+        #[ink::test]
+        fn levels_are_kept_sorted() {
+            let accounts = fixtures::accounts();
+            let mut book = new_book();
+            for (price, amount) in [(10, 1), (12, 2), (11, 3), (12, 4), (9, 5)] {
+                place_as(&mut book, accounts.alice, Side::Buy, price, amount);
+            }
+            for (price, amount) in [(20, 1), (15, 2), (13, 3), (15, 4)] {
+                place_as(&mut book, accounts.bob, Side::Sell, price, amount);
+            }
+
+            assert_eq!(
+                book.depth(Side::Buy, 10),
+                vec![(12, 6), (11, 3), (10, 1), (9, 5)]
+            );
+            assert_eq!(book.depth(Side::Sell, 10), vec![(13, 3), (15, 6), (20, 1)]);
+            assert_eq!(book.depth(Side::Buy, 2), vec![(12, 6), (11, 3)]);
+            assert_eq!(book.best_price(Side::Buy), Some(12));
+            assert_eq!(book.best_price(Side::Sell), Some(13));
+        }
+
+        #[ink::test]
+        fn orders_lock_funds() {
+            let accounts = fixtures::accounts();
+            let mut book = new_book();
+            place_as(&mut book, accounts.alice, Side::Buy, 12, 5);
+            place_as(&mut book, accounts.bob, Side::Sell, 15, 7);
+            assert_eq!(book.balance_of(quote(), accounts.alice), 100_000 - 60);
+            assert_eq!(book.balance_of(base(), accounts.bob), 1_000 - 7);
+        }
+
+        #[ink::test]
+        fn crossing_buy_fills_at_maker_price() {
+            let accounts = fixtures::accounts();
+            let mut book = new_book();
+            let ask = place_as(&mut book, accounts.bob, Side::Sell, 10, 5);
+            let bid = place_as(&mut book, accounts.alice, Side::Buy, 12, 5);
+
+            // Both orders are gone, and alice paid 10 per unit, not her limit of 12.
+            assert_eq!(book.order(ask), None);
+            assert_eq!(book.order(bid), None);
+            assert_eq!(book.best_price(Side::Sell), None);
+            assert_eq!(book.balance_of(base(), accounts.alice), 1_005);
+            assert_eq!(book.balance_of(quote(), accounts.alice), 100_000 - 50);
+            assert_eq!(book.balance_of(base(), accounts.bob), 995);
+            assert_eq!(book.balance_of(quote(), accounts.bob), 100_050);
+        }
+
+        #[ink::test]
+        fn crossing_sell_fills_at_maker_price() {
+            let accounts = fixtures::accounts();
+            let mut book = new_book();
+            place_as(&mut book, accounts.alice, Side::Buy, 12, 5);
+            place_as(&mut book, accounts.bob, Side::Sell, 10, 5);
+            assert_eq!(book.balance_of(quote(), accounts.bob), 100_060);
+            assert_eq!(book.balance_of(base(), accounts.alice), 1_005);
+            assert_eq!(book.balance_of(quote(), accounts.alice), 100_000 - 60);
+        }
+
+        #[ink::test]
+        fn partial_fill_rests_remainder() {
+            let accounts = fixtures::accounts();
+            let mut book = new_book();
+            let ask = place_as(&mut book, accounts.bob, Side::Sell, 10, 3);
+            let bid = place_as(&mut book, accounts.alice, Side::Buy, 10, 8);
+
+            assert_eq!(book.order(ask), None);
+            assert_eq!(book.order(bid).map(|order| order.remaining), Some(5));
+            assert_eq!(book.depth(Side::Buy, 10), vec![(10, 5)]);
+            assert_eq!(book.best_price(Side::Sell), None);
+
+            // A later sell takes part of the resting bid.
+            place_as(&mut book, accounts.charlie, Side::Sell, 9, 2);
+            assert_eq!(book.order(bid).map(|order| order.remaining), Some(3));
+            assert_eq!(book.balance_of(quote(), accounts.charlie), 100_020);
+        }
+
+        #[ink::test]
+        fn same_price_fills_oldest_first() {
+            let accounts = fixtures::accounts();
+            let mut book = new_book();
+            let first = place_as(&mut book, accounts.bob, Side::Sell, 10, 4);
+            let second = place_as(&mut book, accounts.charlie, Side::Sell, 10, 4);
+            place_as(&mut book, accounts.alice, Side::Buy, 10, 6);
+
+            assert_eq!(book.order(first), None);
+            assert_eq!(book.order(second).map(|order| order.remaining), Some(2));
+            assert_eq!(book.balance_of(quote(), accounts.bob), 100_040);
+            assert_eq!(book.balance_of(quote(), accounts.charlie), 100_020);
+        }
+
+        #[ink::test]
+        fn taker_sweeps_levels_up_to_limit() {
+            let accounts = fixtures::accounts();
+            let mut book = new_book();
+            place_as(&mut book, accounts.bob, Side::Sell, 10, 2);
+            place_as(&mut book, accounts.bob, Side::Sell, 11, 2);
+            place_as(&mut book, accounts.bob, Side::Sell, 13, 2);
+            let bid = place_as(&mut book, accounts.alice, Side::Buy, 12, 10);
+
+            // Fills 2 at 10 and 2 at 11, stops before 13, rests 6 at 12.
+            assert_eq!(book.depth(Side::Sell, 10), vec![(13, 2)]);
+            assert_eq!(book.depth(Side::Buy, 10), vec![(12, 6)]);
+            assert_eq!(book.order(bid).map(|order| order.remaining), Some(6));
+            assert_eq!(book.balance_of(base(), accounts.alice), 1_004);
+            // 120 locked, 42 paid, 4 refunded as improvement, 72 still locked.
+            assert_eq!(book.balance_of(quote(), accounts.alice), 100_000 - 42 - 72);
+        }
+
+        #[ink::test]
+        fn cancel_refunds_and_relinks() {
+            let accounts = fixtures::accounts();
+            let mut book = new_book();
+            let a = place_as(&mut book, accounts.alice, Side::Buy, 10, 1);
+            let b = place_as(&mut book, accounts.alice, Side::Buy, 10, 2);
+            let c = place_as(&mut book, accounts.alice, Side::Buy, 10, 3);
+            let d = place_as(&mut book, accounts.alice, Side::Buy, 11, 4);
+
+            // Middle of a queue.
+            assert_eq!(book.cancel_order(b), Ok(()));
+            assert_eq!(book.order(a).and_then(|order| order.next), Some(c));
+            assert_eq!(book.order(c).and_then(|order| order.prev), Some(a));
+            assert_eq!(book.depth(Side::Buy, 10), vec![(11, 4), (10, 4)]);
+
+            // Only order of the best level.
+            assert_eq!(book.cancel_order(d), Ok(()));
+            assert_eq!(book.best_price(Side::Buy), Some(10));
+            assert_eq!(book.cancel_order(a), Ok(()));
+            assert_eq!(book.cancel_order(c), Ok(()));
+            assert_eq!(book.best_price(Side::Buy), None);
+            assert_eq!(book.balance_of(quote(), accounts.alice), 100_000);
+            assert_eq!(book.cancel_order(c), Err(Error::OrderNotFound));
+        }
+
+        #[ink::test]
+        fn emptied_levels_below_the_top_are_skipped_then_dropped() {
+            let accounts = fixtures::accounts();
+            let mut book = new_book();
+            let best = place_as(&mut book, accounts.bob, Side::Sell, 10, 1);
+            let middle = place_as(&mut book, accounts.bob, Side::Sell, 11, 2);
+            place_as(&mut book, accounts.bob, Side::Sell, 12, 3);
+
+            // The emptied level at 11 stays in the heap but not in the depth.
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(book.cancel_order(middle), Ok(()));
+            assert_eq!(book.depth(Side::Sell, 2), vec![(10, 1), (12, 3)]);
+            assert_eq!(book.ask_prices.len(), 3);
+
+            // A new order at 11 reuses the level without pushing its price again.
+            let again = place_as(&mut book, accounts.charlie, Side::Sell, 11, 4);
+            assert_eq!(book.ask_prices.len(), 3);
+            assert_eq!(book.depth(Side::Sell, 10), vec![(10, 1), (11, 4), (12, 3)]);
+
+            // Emptying 11 and then the best level drops both from the heap.
+            assert_eq!(
+                fixtures::with_caller(accounts.charlie, || book.cancel_order(again)),
+                Ok(())
+            );
+            assert_eq!(book.cancel_order(best), Ok(()));
+            assert_eq!(book.best_price(Side::Sell), Some(12));
+            assert_eq!(book.ask_prices.len(), 1);
+            assert_eq!(book.asks.get(11), None);
+        }
+
+        #[ink::test]
+        fn only_owner_can_cancel() {
+            let accounts = fixtures::accounts();
+            let mut book = new_book();
+            let order = place_as(&mut book, accounts.alice, Side::Sell, 10, 1);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(book.cancel_order(order), Err(Error::NotOrderOwner));
+        }
+
+        #[ink::test]
+        fn orders_need_funds() {
+            let accounts = fixtures::accounts();
+            let mut book = new_book();
+            fixtures::set_caller(accounts.dave);
+            assert_eq!(
+                book.place_order(Side::Buy, 10, 1),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(
+                book.place_order(Side::Sell, 10, 1),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(book.place_order(Side::Sell, 0, 1), Err(Error::ZeroPrice));
+            assert_eq!(
+                book.place_order(Side::Buy, Balance::MAX, 2),
+                Err(Error::Overflow)
+            );
+            assert_eq!(
+                book.withdraw(AccountId::from([0x01; 32]), 1),
+                Err(Error::UnknownToken)
+            );
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy with the addresses of the base and quote tokens
+//
+// 4. Interact with the contract:
+//    - On each token contract, "approve" the exchange, then use "deposit"
+//    - Use the "placeOrder" method to buy or sell at a limit price
+//    - Use the "depth" and "bestPrice" methods to look at the book
+//    - Use the "cancelOrder" method to take a resting order off the book
+//    - Use the "withdraw" method to take tokens out of the exchange
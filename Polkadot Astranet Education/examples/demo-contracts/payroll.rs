@@ -0,0 +1,485 @@
+// examples/demo-contracts/payroll.rs
+//
+// A payroll contract example for Polkadot using ink!
+// An employer funds this contract and adds employees, each with a salary paid per
+// period (for example, 30 days). Salaries accrue from the moment an employee is
+// added: once a full period has passed, its salary can be claimed by the employee
+// or paid out by the employer to everyone at once with `pay_all`. Only whole
+// periods are paid; the running period keeps accruing.
+//
+// Each employee has a `paid_until` timestamp that advances by whole periods as
+// salary is paid, so a late claim pays every period missed and nothing twice.
+// Removing an employee first pays the whole periods they are owed; the partial
+// period they are in is not paid.
+//
+// The employer can withdraw funds the contract holds beyond what is owed right
+// now, but never salary that has already accrued.
+//
+// Salary records live in a `Mapping`, which cannot be iterated, so the employees'
+// accounts are also kept in a `Vec` for `employees`, `total_due` and `pay_all`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod payroll {
+    use ink_prelude::vec::Vec;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// The payroll error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the employer.
+        NotEmployer,
+        /// Returned if the account is already an employee.
+        AlreadyEmployed,
+        /// Returned if the account is not an employee.
+        NotEmployed,
+        /// Returned if the salary is zero.
+        ZeroSalary,
+        /// Returned if no whole period is due yet.
+        NothingDue,
+        /// Returned if the contract cannot cover the payment.
+        InsufficientFunds,
+        /// Returned if the native transfer failed.
+        TransferFailed,
+    }
+
+    /// The payroll result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// The salary of an employee and how far it has been paid.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Employee {
+        /// Amount paid per period.
+        pub salary: Balance,
+        /// End of the last period paid.
+        pub paid_until: Timestamp,
+    }
+
+    // Events are mirrored in `crates/events/src/payroll.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when the payroll is funded.
+    #[ink(event)]
+    pub struct Funded {
+        #[ink(topic)]
+        from: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when the employer adds an employee.
+    #[ink(event)]
+    pub struct EmployeeAdded {
+        #[ink(topic)]
+        employee: AccountId,
+        salary: Balance,
+    }
+
+    /// Event emitted when the employer removes an employee.
+    #[ink(event)]
+    pub struct EmployeeRemoved {
+        #[ink(topic)]
+        employee: AccountId,
+    }
+
+    /// Event emitted when an employee is paid `periods` periods of salary.
+    #[ink(event)]
+    pub struct SalaryPaid {
+        #[ink(topic)]
+        employee: AccountId,
+        periods: u64,
+        amount: Balance,
+    }
+
+    /// The payroll storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Payroll {
+        /// Account that manages employees and funds.
+        employer: AccountId,
+        /// Length of a pay period in milliseconds.
+        period: Timestamp,
+        /// Mapping from employee to their salary record.
+        employees: Mapping<AccountId, Employee>,
+        /// Every employee, in the order they were added.
+        staff: Vec<AccountId>,
+    }
+
+    impl Payroll {
+        /// Creates an empty payroll paying every `period` milliseconds, with the
+        /// caller as employer.
+        #[ink(constructor)]
+        pub fn new(period: Timestamp) -> Self {
+            assert!(period > 0, "period must not be zero");
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.employer = Self::env().caller();
+                contract.period = period;
+            })
+        }
+
+        /// Returns the employer.
+        #[ink(message)]
+        pub fn employer(&self) -> AccountId {
+            self.employer
+        }
+
+        /// Returns the length of a pay period in milliseconds.
+        #[ink(message)]
+        pub fn period(&self) -> Timestamp {
+            self.period
+        }
+
+        /// Returns the salary record of `account`, if they are an employee.
+        #[ink(message)]
+        pub fn employee(&self, account: AccountId) -> Option<Employee> {
+            self.employees.get(account)
+        }
+
+        /// Returns all employees.
+        #[ink(message)]
+        pub fn employees(&self) -> Vec<AccountId> {
+            self.staff.clone()
+        }
+
+        /// Returns the salary `account` can be paid right now.
+        #[ink(message)]
+        pub fn due(&self, account: AccountId) -> Balance {
+            self.employee(account)
+                .map(|employee| self.accrued(employee).1)
+                .unwrap_or(0)
+        }
+
+        /// Returns the salary owed to all employees right now.
+        #[ink(message)]
+        pub fn total_due(&self) -> Balance {
+            self.staff
+                .iter()
+                .map(|account| self.due(*account))
+                .fold(0, Balance::saturating_add)
+        }
+
+        /// Accepts funds to pay salaries with.
+        #[ink(message, payable)]
+        pub fn fund(&mut self) {
+            self.env().emit_event(Funded {
+                from: self.env().caller(),
+                amount: self.env().transferred_value(),
+            });
+        }
+
+        /// Adds `account` as an employee paid `salary` per period, starting now.
+        /// Only the employer can call this.
+        #[ink(message)]
+        pub fn add_employee(&mut self, account: AccountId, salary: Balance) -> Result<()> {
+            self.ensure_employer()?;
+            if salary == 0 {
+                return Err(Error::ZeroSalary);
+            }
+            if self.employees.contains(account) {
+                return Err(Error::AlreadyEmployed);
+            }
+            let paid_until = self.env().block_timestamp();
+            self.employees
+                .insert(account, &Employee { salary, paid_until });
+            self.staff.push(account);
+            self.env().emit_event(EmployeeAdded {
+                employee: account,
+                salary,
+            });
+            Ok(())
+        }
+
+        /// Pays `account` the whole periods they are owed and removes them. Only the
+        /// employer can call this.
+        #[ink(message)]
+        pub fn remove_employee(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_employer()?;
+            if !self.employees.contains(account) {
+                return Err(Error::NotEmployed);
+            }
+            self.pay(account)?;
+            self.employees.remove(account);
+            self.staff.retain(|employee| *employee != account);
+            self.env().emit_event(EmployeeRemoved { employee: account });
+            Ok(())
+        }
+
+        /// Pays the caller the salary they are owed.
+        #[ink(message)]
+        pub fn claim(&mut self) -> Result<Balance> {
+            let caller = self.env().caller();
+            if !self.employees.contains(caller) {
+                return Err(Error::NotEmployed);
+            }
+            match self.pay(caller)? {
+                0 => Err(Error::NothingDue),
+                paid => Ok(paid),
+            }
+        }
+
+        /// Pays every employee the salary they are owed and returns the total. Fails
+        /// without paying anyone if the contract cannot cover it all. Only the
+        /// employer can call this.
+        #[ink(message)]
+        pub fn pay_all(&mut self) -> Result<Balance> {
+            self.ensure_employer()?;
+            if self.env().balance() < self.total_due() {
+                return Err(Error::InsufficientFunds);
+            }
+            let mut total = 0;
+            for account in self.employees() {
+                total += self.pay(account)?;
+            }
+            Ok(total)
+        }
+
+        /// Sends `amount` of the funds not owed to employees back to the employer.
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: Balance) -> Result<()> {
+            self.ensure_employer()?;
+            let free = self.env().balance().saturating_sub(self.total_due());
+            if amount > free {
+                return Err(Error::InsufficientFunds);
+            }
+            self.env()
+                .transfer(self.employer, amount)
+                .map_err(|_| Error::TransferFailed)
+        }
+
+        /// Returns the whole periods `employee` is owed and their salary.
+        fn accrued(&self, employee: Employee) -> (u64, Balance) {
+            let elapsed = self
+                .env()
+                .block_timestamp()
+                .saturating_sub(employee.paid_until);
+            let periods = elapsed / self.period;
+            (periods, employee.salary.saturating_mul(periods as Balance))
+        }
+
+        /// Pays `account` the whole periods they are owed, if any, and returns the
+        /// amount paid.
+        fn pay(&mut self, account: AccountId) -> Result<Balance> {
+            let employee = self.employee(account).ok_or(Error::NotEmployed)?;
+            let (periods, amount) = self.accrued(employee);
+            if periods == 0 {
+                return Ok(0);
+            }
+            if self.env().balance() < amount {
+                return Err(Error::InsufficientFunds);
+            }
+
+            let paid_until = employee.paid_until + periods * self.period;
+            self.employees.insert(
+                account,
+                &Employee {
+                    paid_until,
+                    ..employee
+                },
+            );
+            if self.env().transfer(account, amount).is_err() {
+                self.employees.insert(account, &employee);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(SalaryPaid {
+                employee: account,
+                periods,
+                amount,
+            });
+            Ok(amount)
+        }
+
+        /// Returns `NotEmployer` unless the caller is the employer.
+        fn ensure_employer(&self) -> Result<()> {
+            if self.env().caller() != self.employer {
+                return Err(Error::NotEmployer);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use fixtures::{set_block_timestamp as set_time, BLOCK_TIME};
+        use ink_lang as ink;
+
+        /// A day of ten blocks: the fixtures clock moves a block at a time, so
+        /// real days would take the tests millions of blocks to reach.
+        const DAY: Timestamp = 10 * BLOCK_TIME;
+        const MONTH: Timestamp = 30 * DAY;
+
+        /// A monthly payroll holding `funds`, paying bob 100 and charlie 250.
+        fn staffed_payroll(funds: Balance) -> Payroll {
+            let accounts = fixtures::accounts();
+            let mut payroll = Payroll::new(MONTH);
+            fixtures::fund_contract(funds);
+            fixtures::fund_all(&[accounts.bob, accounts.charlie], 0);
+            assert_eq!(payroll.add_employee(accounts.bob, 100), Ok(()));
+            assert_eq!(payroll.add_employee(accounts.charlie, 250), Ok(()));
+            payroll
+        }
+
+        #[ink::test]
+        fn salary_accrues_per_whole_period() {
+            let accounts = fixtures::accounts();
+            let payroll = staffed_payroll(10_000);
+            set_time(MONTH - BLOCK_TIME);
+            assert_eq!(payroll.due(accounts.bob), 0);
+            set_time(MONTH);
+            assert_eq!(payroll.due(accounts.bob), 100);
+            set_time(3 * MONTH + 10 * DAY);
+            assert_eq!(payroll.due(accounts.bob), 300);
+            assert_eq!(payroll.total_due(), 300 + 750);
+        }
+
+        #[ink::test]
+        fn claim_pays_missed_periods_once() {
+            let accounts = fixtures::accounts();
+            let mut payroll = staffed_payroll(10_000);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(payroll.claim(), Err(Error::NothingDue));
+
+            set_time(2 * MONTH + DAY);
+            assert_eq!(payroll.claim(), Ok(200));
+            assert_eq!(fixtures::balance_of(accounts.bob), 200);
+            assert_eq!(payroll.claim(), Err(Error::NothingDue));
+            // The partial period keeps counting from the end of the last one paid.
+            assert_eq!(
+                payroll.employee(accounts.bob).map(|e| e.paid_until),
+                Some(2 * MONTH)
+            );
+            set_time(3 * MONTH);
+            assert_eq!(payroll.claim(), Ok(100));
+        }
+
+        #[ink::test]
+        fn late_joiner_accrues_from_hire_date() {
+            let accounts = fixtures::accounts();
+            let mut payroll = staffed_payroll(10_000);
+            set_time(MONTH / 2);
+            assert_eq!(payroll.add_employee(accounts.dave, 50), Ok(()));
+            set_time(MONTH);
+            assert_eq!(payroll.due(accounts.dave), 0);
+            set_time(MONTH + MONTH / 2);
+            assert_eq!(payroll.due(accounts.dave), 50);
+        }
+
+        #[ink::test]
+        fn pay_all_pays_everyone_due() {
+            let accounts = fixtures::accounts();
+            let mut payroll = staffed_payroll(10_000);
+            set_time(2 * MONTH);
+            assert_eq!(payroll.pay_all(), Ok(700));
+            assert_eq!(fixtures::balance_of(accounts.bob), 200);
+            assert_eq!(fixtures::balance_of(accounts.charlie), 500);
+            assert_eq!(payroll.total_due(), 0);
+            assert_eq!(payroll.pay_all(), Ok(0));
+        }
+
+        #[ink::test]
+        fn underfunded_pay_all_pays_no_one() {
+            let accounts = fixtures::accounts();
+            let mut payroll = staffed_payroll(300);
+            set_time(MONTH);
+            assert_eq!(payroll.pay_all(), Err(Error::InsufficientFunds));
+            assert_eq!(fixtures::balance_of(accounts.bob), 0);
+            assert_eq!(payroll.due(accounts.bob), 100);
+
+            // Claims still work one by one while funds last.
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(payroll.claim(), Ok(250));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(payroll.claim(), Err(Error::InsufficientFunds));
+        }
+
+        #[ink::test]
+        fn removal_settles_whole_periods() {
+            let accounts = fixtures::accounts();
+            let mut payroll = staffed_payroll(10_000);
+            set_time(MONTH + 20 * DAY);
+            assert_eq!(payroll.remove_employee(accounts.bob), Ok(()));
+            assert_eq!(fixtures::balance_of(accounts.bob), 100);
+            assert_eq!(payroll.employee(accounts.bob), None);
+            assert_eq!(payroll.employees(), vec![accounts.charlie]);
+            assert_eq!(
+                payroll.remove_employee(accounts.bob),
+                Err(Error::NotEmployed)
+            );
+
+            fixtures::set_caller(accounts.bob);
+            set_time(5 * MONTH);
+            assert_eq!(payroll.claim(), Err(Error::NotEmployed));
+        }
+
+        #[ink::test]
+        fn employer_cannot_withdraw_owed_salary() {
+            let mut payroll = staffed_payroll(1_000);
+            set_time(2 * MONTH);
+            // 700 is owed, so only 300 is free.
+            assert_eq!(payroll.withdraw(301), Err(Error::InsufficientFunds));
+            assert_eq!(payroll.withdraw(300), Ok(()));
+            assert_eq!(payroll.pay_all(), Ok(700));
+        }
+
+        #[ink::test]
+        fn only_employer_manages_staff() {
+            let accounts = fixtures::accounts();
+            let mut payroll = staffed_payroll(1_000);
+            assert_eq!(
+                payroll.add_employee(accounts.bob, 100),
+                Err(Error::AlreadyEmployed)
+            );
+            assert_eq!(
+                payroll.add_employee(accounts.dave, 0),
+                Err(Error::ZeroSalary)
+            );
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                payroll.add_employee(accounts.eve, 100),
+                Err(Error::NotEmployer)
+            );
+            assert_eq!(
+                payroll.remove_employee(accounts.charlie),
+                Err(Error::NotEmployer)
+            );
+            assert_eq!(payroll.pay_all(), Err(Error::NotEmployer));
+            assert_eq!(payroll.withdraw(1), Err(Error::NotEmployer));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy with the pay period in milliseconds (2592000000 for 30 days)
+//
+// 4. Interact with the contract:
+//    - Use the "fund" method with some value to fund salaries
+//    - Use the "addEmployee" method with an account and a salary per period
+//    - Use the "due" and "totalDue" methods to see what is owed
+//    - Employees use the "claim" method; the employer can use "payAll" instead
+//    - Use the "removeEmployee" method to settle and remove an employee
@@ -0,0 +1,772 @@
+// examples/demo-contracts/peer_review.rs
+//
+// A staked peer review contract example for Polkadot using ink!
+// Authors submit work by its content hash. Reviewers stake native tokens, and each
+// review they post locks `review_stake` of their stake. Other staked reviewers then
+// vote during a voting period on whether the review should be flagged, e.g. as
+// careless or copied. Once the period is over, anyone can resolve the review:
+//
+// - fewer than `quorum` votes: the vote failed, and the stake is simply unlocked;
+// - a strict majority to flag: the locked stake is slashed;
+// - otherwise: the review stands, and the stake is unlocked.
+//
+// Slashed stake is set aside for the owner to withdraw, e.g. to reward good
+// reviewers. Staking is what makes flagging mean something: a reviewer who posts
+// low-effort reviews loses money, while an honest one only has their stake locked
+// for a while.
+//
+// To stay short, voting weight is one vote per staked reviewer, and a voter only
+// needs `review_stake` staked when voting. A production system would weigh votes
+// by stake and lock the voters' stake too, so nobody can stake, vote and leave.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod peer_review {
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Identifies a submission.
+    pub type SubmissionId = u32;
+
+    /// Identifies a review.
+    pub type ReviewId = u32;
+
+    /// The peer review error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if no value was sent along, or the amount is zero.
+        ZeroAmount,
+        /// Returned if the caller's free stake is below what the action needs.
+        InsufficientStake,
+        /// Returned if no submission has the given id.
+        SubmissionNotFound,
+        /// Returned if the caller reviews their own submission.
+        OwnSubmission,
+        /// Returned if the caller already reviewed the submission.
+        AlreadyReviewed,
+        /// Returned if no review has the given id.
+        ReviewNotFound,
+        /// Returned if the caller votes on their own review.
+        OwnReview,
+        /// Returned if the caller already voted on the review.
+        AlreadyVoted,
+        /// Returned if the voting period of the review is over.
+        VotingClosed,
+        /// Returned if the voting period of the review is not over yet.
+        VotingOpen,
+        /// Returned if the review has already been resolved.
+        AlreadyResolved,
+        /// Returned if the caller is not the owner.
+        NotOwner,
+        /// Returned if there is nothing to withdraw.
+        NothingToWithdraw,
+        /// Returned if the native transfer failed.
+        TransferFailed,
+    }
+
+    /// The peer review result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// The stake of a reviewer.
+    #[derive(
+        Debug,
+        Default,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        scale::Encode,
+        scale::Decode,
+        SpreadLayout,
+        PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Stake {
+        /// Everything staked.
+        pub total: Balance,
+        /// Part of `total` locked by unresolved reviews.
+        pub locked: Balance,
+    }
+
+    impl Stake {
+        /// Returns the stake that is not locked.
+        pub fn free(&self) -> Balance {
+            self.total - self.locked
+        }
+    }
+
+    /// A piece of work up for review.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Submission {
+        /// Account that submitted the work.
+        pub author: AccountId,
+        /// Hash of the work, stored off-chain.
+        pub content: Hash,
+    }
+
+    /// A review and the vote on flagging it.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Review {
+        /// Submission reviewed.
+        pub submission_id: SubmissionId,
+        /// Account that wrote the review.
+        pub reviewer: AccountId,
+        /// Hash of the review text, stored off-chain.
+        pub report: Hash,
+        /// Stake locked by the review.
+        pub stake: Balance,
+        /// End of the voting period.
+        pub voting_ends: Timestamp,
+        /// Votes to flag the review.
+        pub flags: u32,
+        /// Votes to keep the review.
+        pub keeps: u32,
+        /// Whether the review has been resolved.
+        pub resolved: bool,
+    }
+
+    /// How a review was resolved.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Outcome {
+        /// Fewer votes than the quorum were cast; the stake was unlocked.
+        NoQuorum,
+        /// The majority kept the review; the stake was unlocked.
+        Kept,
+        /// The majority flagged the review; the stake was slashed.
+        Slashed,
+    }
+
+    // Events are mirrored in `crates/events/src/peer_review.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when a reviewer adds to their stake.
+    #[ink(event)]
+    pub struct Staked {
+        #[ink(topic)]
+        reviewer: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when a reviewer takes back free stake.
+    #[ink(event)]
+    pub struct Unstaked {
+        #[ink(topic)]
+        reviewer: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when work is submitted for review.
+    #[ink(event)]
+    pub struct Submitted {
+        #[ink(topic)]
+        submission_id: SubmissionId,
+        #[ink(topic)]
+        author: AccountId,
+        content: Hash,
+    }
+
+    /// Event emitted when a reviewer reviews a submission.
+    #[ink(event)]
+    pub struct Reviewed {
+        #[ink(topic)]
+        review_id: ReviewId,
+        #[ink(topic)]
+        submission_id: SubmissionId,
+        #[ink(topic)]
+        reviewer: AccountId,
+        report: Hash,
+    }
+
+    /// Event emitted when a staked reviewer votes on flagging a review.
+    #[ink(event)]
+    pub struct Voted {
+        #[ink(topic)]
+        review_id: ReviewId,
+        #[ink(topic)]
+        voter: AccountId,
+        flag: bool,
+    }
+
+    /// Event emitted when a review is resolved.
+    #[ink(event)]
+    pub struct Resolved {
+        #[ink(topic)]
+        review_id: ReviewId,
+        outcome: Outcome,
+        slashed: Balance,
+    }
+
+    /// The peer review storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct PeerReview {
+        /// Account that receives slashed stake.
+        owner: AccountId,
+        /// Stake locked by each review, and needed to vote.
+        review_stake: Balance,
+        /// Length of the voting period of each review, in milliseconds.
+        voting_period: Timestamp,
+        /// Votes a review needs for the vote to count.
+        quorum: u32,
+        /// Slashed stake not withdrawn yet.
+        slashed: Balance,
+        /// Mapping from reviewer to their stake.
+        stakes: Mapping<AccountId, Stake>,
+        /// Mapping from submission id to submission.
+        submissions: Mapping<SubmissionId, Submission>,
+        /// Mapping from review id to review.
+        reviews: Mapping<ReviewId, Review>,
+        /// Mapping from submission and reviewer to their review.
+        reviewed: Mapping<(SubmissionId, AccountId), ReviewId>,
+        /// Mapping from review and voter to their vote.
+        votes: Mapping<(ReviewId, AccountId), bool>,
+        /// Id the next submission will get.
+        next_submission_id: SubmissionId,
+        /// Id the next review will get.
+        next_review_id: ReviewId,
+    }
+
+    impl PeerReview {
+        /// Creates a contract where each review locks `review_stake`, is open to
+        /// votes for `voting_period` milliseconds, and needs `quorum` votes. The
+        /// caller becomes the owner.
+        #[ink(constructor)]
+        pub fn new(review_stake: Balance, voting_period: Timestamp, quorum: u32) -> Self {
+            assert!(review_stake > 0, "review stake must not be zero");
+            assert!(quorum > 0, "quorum must not be zero");
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.owner = Self::env().caller();
+                contract.review_stake = review_stake;
+                contract.voting_period = voting_period;
+                contract.quorum = quorum;
+            })
+        }
+
+        /// Returns the stake locked by each review.
+        #[ink(message)]
+        pub fn review_stake(&self) -> Balance {
+            self.review_stake
+        }
+
+        /// Returns the votes a review needs for the vote to count.
+        #[ink(message)]
+        pub fn quorum(&self) -> u32 {
+            self.quorum
+        }
+
+        /// Returns the slashed stake not withdrawn yet.
+        #[ink(message)]
+        pub fn slashed(&self) -> Balance {
+            self.slashed
+        }
+
+        /// Returns the stake of `reviewer`.
+        #[ink(message)]
+        pub fn stake_of(&self, reviewer: AccountId) -> Stake {
+            self.stakes.get(reviewer).unwrap_or_default()
+        }
+
+        /// Returns the submission with id `submission_id`.
+        #[ink(message)]
+        pub fn submission(&self, submission_id: SubmissionId) -> Option<Submission> {
+            self.submissions.get(submission_id)
+        }
+
+        /// Returns the review with id `review_id`.
+        #[ink(message)]
+        pub fn review(&self, review_id: ReviewId) -> Option<Review> {
+            self.reviews.get(review_id)
+        }
+
+        /// Adds the value sent along to the caller's stake.
+        #[ink(message, payable)]
+        pub fn stake(&mut self) -> Result<()> {
+            let reviewer = self.env().caller();
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let mut stake = self.stake_of(reviewer);
+            stake.total += amount;
+            self.stakes.insert(reviewer, &stake);
+            self.env().emit_event(Staked { reviewer, amount });
+            Ok(())
+        }
+
+        /// Sends `amount` of the caller's free stake back to them.
+        #[ink(message)]
+        pub fn unstake(&mut self, amount: Balance) -> Result<()> {
+            let reviewer = self.env().caller();
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let stake = self.stake_of(reviewer);
+            if stake.free() < amount {
+                return Err(Error::InsufficientStake);
+            }
+            self.stakes.insert(
+                reviewer,
+                &Stake {
+                    total: stake.total - amount,
+                    ..stake
+                },
+            );
+            if self.env().transfer(reviewer, amount).is_err() {
+                self.stakes.insert(reviewer, &stake);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(Unstaked { reviewer, amount });
+            Ok(())
+        }
+
+        /// Submits work with hash `content` for review and returns its id.
+        #[ink(message)]
+        pub fn submit(&mut self, content: Hash) -> SubmissionId {
+            let submission_id = self.next_submission_id;
+            self.next_submission_id += 1;
+            let author = self.env().caller();
+            self.submissions
+                .insert(submission_id, &Submission { author, content });
+            self.env().emit_event(Submitted {
+                submission_id,
+                author,
+                content,
+            });
+            submission_id
+        }
+
+        /// Reviews submission `submission_id` with a review whose text hashes to
+        /// `report`, locking `review_stake` of the caller's stake. Returns the
+        /// review id.
+        #[ink(message)]
+        pub fn post_review(
+            &mut self,
+            submission_id: SubmissionId,
+            report: Hash,
+        ) -> Result<ReviewId> {
+            let reviewer = self.env().caller();
+            let submission = self
+                .submission(submission_id)
+                .ok_or(Error::SubmissionNotFound)?;
+            if submission.author == reviewer {
+                return Err(Error::OwnSubmission);
+            }
+            if self.reviewed.contains((submission_id, reviewer)) {
+                return Err(Error::AlreadyReviewed);
+            }
+            let mut stake = self.stake_of(reviewer);
+            let locked = self.review_stake;
+            if stake.free() < locked {
+                return Err(Error::InsufficientStake);
+            }
+            stake.locked += locked;
+            self.stakes.insert(reviewer, &stake);
+
+            let review_id = self.next_review_id;
+            self.next_review_id += 1;
+            self.reviews.insert(
+                review_id,
+                &Review {
+                    submission_id,
+                    reviewer,
+                    report,
+                    stake: locked,
+                    voting_ends: self.env().block_timestamp() + self.voting_period,
+                    flags: 0,
+                    keeps: 0,
+                    resolved: false,
+                },
+            );
+            self.reviewed.insert((submission_id, reviewer), &review_id);
+            self.env().emit_event(Reviewed {
+                review_id,
+                submission_id,
+                reviewer,
+                report,
+            });
+            Ok(review_id)
+        }
+
+        /// Votes to flag review `review_id` if `flag` is true, or to keep it. The
+        /// caller needs at least `review_stake` staked.
+        #[ink(message)]
+        pub fn vote(&mut self, review_id: ReviewId, flag: bool) -> Result<()> {
+            let voter = self.env().caller();
+            let mut review = self.review(review_id).ok_or(Error::ReviewNotFound)?;
+            if self.env().block_timestamp() > review.voting_ends {
+                return Err(Error::VotingClosed);
+            }
+            if review.reviewer == voter {
+                return Err(Error::OwnReview);
+            }
+            if self.stake_of(voter).total < self.review_stake {
+                return Err(Error::InsufficientStake);
+            }
+            if self.votes.contains((review_id, voter)) {
+                return Err(Error::AlreadyVoted);
+            }
+            if flag {
+                review.flags += 1;
+            } else {
+                review.keeps += 1;
+            }
+            self.reviews.insert(review_id, &review);
+            self.votes.insert((review_id, voter), &flag);
+            self.env().emit_event(Voted {
+                review_id,
+                voter,
+                flag,
+            });
+            Ok(())
+        }
+
+        /// Resolves review `review_id` once its voting period is over: slashes the
+        /// reviewer's locked stake if a quorum was reached and a strict majority
+        /// flagged the review, and unlocks it otherwise.
+        #[ink(message)]
+        pub fn resolve(&mut self, review_id: ReviewId) -> Result<Outcome> {
+            let mut review = self.review(review_id).ok_or(Error::ReviewNotFound)?;
+            if review.resolved {
+                return Err(Error::AlreadyResolved);
+            }
+            if self.env().block_timestamp() <= review.voting_ends {
+                return Err(Error::VotingOpen);
+            }
+
+            let votes = review.flags + review.keeps;
+            let outcome = if votes < self.quorum {
+                Outcome::NoQuorum
+            } else if review.flags > review.keeps {
+                Outcome::Slashed
+            } else {
+                Outcome::Kept
+            };
+
+            let mut stake = self.stake_of(review.reviewer);
+            stake.locked -= review.stake;
+            let slashed = if outcome == Outcome::Slashed {
+                stake.total -= review.stake;
+                self.slashed += review.stake;
+                review.stake
+            } else {
+                0
+            };
+            self.stakes.insert(review.reviewer, &stake);
+            review.resolved = true;
+            self.reviews.insert(review_id, &review);
+            self.env().emit_event(Resolved {
+                review_id,
+                outcome,
+                slashed,
+            });
+            Ok(outcome)
+        }
+
+        /// Sends the slashed stake to the owner. Only the owner can call this.
+        #[ink(message)]
+        pub fn withdraw_slashed(&mut self) -> Result<()> {
+            let owner = self.owner;
+            if self.env().caller() != owner {
+                return Err(Error::NotOwner);
+            }
+            let amount = self.slashed;
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+            self.slashed = 0;
+            if self.env().transfer(owner, amount).is_err() {
+                self.slashed = amount;
+                return Err(Error::TransferFailed);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use fixtures::{set_block_timestamp as set_time, BLOCK_TIME};
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        const STAKE: Balance = 100;
+        const PERIOD: Timestamp = 200 * BLOCK_TIME;
+
+        fn stake_as(contract: &mut PeerReview, reviewer: AccountId, value: Balance) -> Result<()> {
+            fixtures::with_caller(reviewer, || {
+                ink_env::test::set_value_transferred::<Env>(value);
+                let result = contract.stake();
+                ink_env::test::set_value_transferred::<Env>(0);
+                result
+            })
+        }
+
+        fn vote_as(
+            contract: &mut PeerReview,
+            voter: AccountId,
+            review_id: ReviewId,
+            flag: bool,
+        ) -> Result<()> {
+            fixtures::with_caller(voter, || contract.vote(review_id, flag))
+        }
+
+        /// A contract with a quorum of 3 where bob, charlie, dave and eve each
+        /// staked 2 * STAKE, and bob reviewed a submission by alice.
+        fn reviewed() -> (PeerReview, ReviewId) {
+            let accounts = fixtures::accounts();
+            let mut contract = PeerReview::new(STAKE, PERIOD, 3);
+            for reviewer in [accounts.bob, accounts.charlie, accounts.dave, accounts.eve] {
+                assert_eq!(stake_as(&mut contract, reviewer, 2 * STAKE), Ok(()));
+            }
+            fixtures::fund_contract(8 * STAKE);
+            let submission_id = contract.submit(Hash::from([0x01; 32]));
+            let review_id = fixtures::with_caller(accounts.bob, || {
+                contract.post_review(submission_id, Hash::from([0x02; 32]))
+            })
+            .expect("reviewed");
+            (contract, review_id)
+        }
+
+        #[ink::test]
+        fn review_locks_stake() {
+            let accounts = fixtures::accounts();
+            let (mut contract, _) = reviewed();
+            assert_eq!(
+                contract.stake_of(accounts.bob),
+                Stake {
+                    total: 2 * STAKE,
+                    locked: STAKE
+                }
+            );
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(contract.unstake(STAKE + 1), Err(Error::InsufficientStake));
+            assert_eq!(contract.unstake(STAKE), Ok(()));
+            assert_eq!(
+                contract.post_review(0, Hash::from([0x03; 32])),
+                Err(Error::AlreadyReviewed)
+            );
+
+            // Nothing free is left for another review.
+            let other =
+                fixtures::with_caller(accounts.alice, || contract.submit(Hash::from([0x04; 32])));
+            assert_eq!(
+                contract.post_review(other, Hash::from([0x05; 32])),
+                Err(Error::InsufficientStake)
+            );
+        }
+
+        #[ink::test]
+        fn majority_flag_slashes() {
+            let accounts = fixtures::accounts();
+            let (mut contract, review_id) = reviewed();
+            assert_eq!(
+                vote_as(&mut contract, accounts.charlie, review_id, true),
+                Ok(())
+            );
+            assert_eq!(
+                vote_as(&mut contract, accounts.dave, review_id, true),
+                Ok(())
+            );
+            assert_eq!(
+                vote_as(&mut contract, accounts.eve, review_id, false),
+                Ok(())
+            );
+
+            assert_eq!(contract.resolve(review_id), Err(Error::VotingOpen));
+            set_time(PERIOD + BLOCK_TIME);
+            assert_eq!(contract.resolve(review_id), Ok(Outcome::Slashed));
+            assert_eq!(
+                contract.stake_of(accounts.bob),
+                Stake {
+                    total: STAKE,
+                    locked: 0
+                }
+            );
+            assert_eq!(contract.slashed(), STAKE);
+            assert_eq!(contract.resolve(review_id), Err(Error::AlreadyResolved));
+        }
+
+        #[ink::test]
+        fn majority_keep_unlocks() {
+            let accounts = fixtures::accounts();
+            let (mut contract, review_id) = reviewed();
+            assert_eq!(
+                vote_as(&mut contract, accounts.charlie, review_id, false),
+                Ok(())
+            );
+            assert_eq!(
+                vote_as(&mut contract, accounts.dave, review_id, false),
+                Ok(())
+            );
+            assert_eq!(
+                vote_as(&mut contract, accounts.eve, review_id, true),
+                Ok(())
+            );
+            set_time(PERIOD + BLOCK_TIME);
+            assert_eq!(contract.resolve(review_id), Ok(Outcome::Kept));
+            assert_eq!(contract.stake_of(accounts.bob).free(), 2 * STAKE);
+            assert_eq!(contract.slashed(), 0);
+        }
+
+        #[ink::test]
+        fn tie_keeps_review() {
+            let accounts = fixtures::accounts();
+            let mut contract = PeerReview::new(STAKE, PERIOD, 2);
+            for reviewer in [accounts.bob, accounts.charlie, accounts.dave] {
+                assert_eq!(stake_as(&mut contract, reviewer, STAKE), Ok(()));
+            }
+            let submission_id = contract.submit(Hash::from([0x01; 32]));
+            let review_id = fixtures::with_caller(accounts.bob, || {
+                contract.post_review(submission_id, Hash::from([0x02; 32]))
+            })
+            .expect("reviewed");
+            assert_eq!(
+                vote_as(&mut contract, accounts.charlie, review_id, true),
+                Ok(())
+            );
+            assert_eq!(
+                vote_as(&mut contract, accounts.dave, review_id, false),
+                Ok(())
+            );
+            set_time(PERIOD + BLOCK_TIME);
+            assert_eq!(contract.resolve(review_id), Ok(Outcome::Kept));
+        }
+
+        #[ink::test]
+        fn below_quorum_does_not_slash() {
+            let accounts = fixtures::accounts();
+            let (mut contract, review_id) = reviewed();
+            assert_eq!(
+                vote_as(&mut contract, accounts.charlie, review_id, true),
+                Ok(())
+            );
+            assert_eq!(
+                vote_as(&mut contract, accounts.dave, review_id, true),
+                Ok(())
+            );
+            set_time(PERIOD + BLOCK_TIME);
+            assert_eq!(contract.resolve(review_id), Ok(Outcome::NoQuorum));
+            assert_eq!(contract.stake_of(accounts.bob).free(), 2 * STAKE);
+        }
+
+        #[ink::test]
+        fn voting_rules() {
+            let accounts = fixtures::accounts();
+            let (mut contract, review_id) = reviewed();
+            assert_eq!(
+                vote_as(&mut contract, accounts.bob, review_id, false),
+                Err(Error::OwnReview)
+            );
+            assert_eq!(
+                vote_as(&mut contract, accounts.alice, review_id, true),
+                Err(Error::InsufficientStake)
+            );
+            assert_eq!(
+                vote_as(&mut contract, accounts.charlie, review_id, true),
+                Ok(())
+            );
+            assert_eq!(
+                vote_as(&mut contract, accounts.charlie, review_id, false),
+                Err(Error::AlreadyVoted)
+            );
+            assert_eq!(
+                vote_as(&mut contract, accounts.dave, 9, true),
+                Err(Error::ReviewNotFound)
+            );
+            set_time(PERIOD + BLOCK_TIME);
+            assert_eq!(
+                vote_as(&mut contract, accounts.dave, review_id, true),
+                Err(Error::VotingClosed)
+            );
+        }
+
+        #[ink::test]
+        fn reviewing_needs_free_stake_and_other_author() {
+            let accounts = fixtures::accounts();
+            let mut contract = PeerReview::new(STAKE, PERIOD, 1);
+            assert_eq!(stake_as(&mut contract, accounts.bob, STAKE - 1), Ok(()));
+            let submission_id = contract.submit(Hash::from([0x01; 32]));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                contract.post_review(submission_id, Hash::from([0x02; 32])),
+                Err(Error::InsufficientStake)
+            );
+            let own = contract.submit(Hash::from([0x03; 32]));
+            assert_eq!(
+                contract.post_review(own, Hash::from([0x04; 32])),
+                Err(Error::OwnSubmission)
+            );
+            assert_eq!(
+                contract.post_review(7, Hash::from([0x04; 32])),
+                Err(Error::SubmissionNotFound)
+            );
+        }
+
+        #[ink::test]
+        fn owner_withdraws_slashed_stake() {
+            let accounts = fixtures::accounts();
+            let (mut contract, review_id) = reviewed();
+            for voter in [accounts.charlie, accounts.dave, accounts.eve] {
+                assert_eq!(vote_as(&mut contract, voter, review_id, true), Ok(()));
+            }
+            set_time(PERIOD + BLOCK_TIME);
+            assert_eq!(contract.resolve(review_id), Ok(Outcome::Slashed));
+
+            let before = fixtures::balance_of(accounts.alice);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(contract.withdraw_slashed(), Err(Error::NotOwner));
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(contract.withdraw_slashed(), Ok(()));
+            assert_eq!(fixtures::balance_of(accounts.alice), before + STAKE);
+            assert_eq!(contract.withdraw_slashed(), Err(Error::NothingToWithdraw));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy with the stake per review, the voting period in milliseconds and
+//      the quorum
+//
+// 4. Interact with the contract:
+//    - Use the "stake" method with some value to become a reviewer
+//    - Use the "submit" method with the hash of a piece of work
+//    - Use the "postReview" method with a submission id and the hash of a review
+//    - Use the "vote" method to flag or keep a review
+//    - After the voting period, use "resolve" to slash or unlock the stake
@@ -0,0 +1,744 @@
+// examples/demo-contracts/prediction.rs
+//
+// A prediction market contract example for Polkadot using ink!
+// Each market asks a yes/no question about a price: "will the oracle price of this
+// asset be at least `strike` once the market closes?". Anyone buys YES or NO shares
+// with an ERC-20 token until the close; afterwards anyone resolves the market from
+// the oracle, and the holders of the winning side redeem their shares. This brings
+// together the oracle reads of `cdp.rs`, the token calls of `token_locker.rs` and
+// the pull-based payouts of the other examples.
+//
+// Pricing
+// -------
+// Shares are minted at a fixed `share_price` on either side, and all the money goes
+// into one pot per market. At resolution the contract keeps a `fee_bps` cut of the
+// pot, and the winning shares split the rest equally, so a YES share pays more the
+// fewer YES shares there are (a parimutuel market). If nobody bought the winning
+// side, there is nobody to pay; every share is then refunded at its price and no
+// fee is taken. Rounding leftovers stay in the contract.
+//
+// A market's close is the utils crate's inclusive `Deadline`: shares are still sold
+// at exactly `closes_at`, and the market can be resolved the millisecond after.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod prediction {
+    use astranet_utils::deadline::Deadline;
+    use ink_env::call::{build_call, Call, ExecutionInput, Selector};
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Identifies a market.
+    pub type MarketId = u32;
+
+    /// Fees are in basis points of this.
+    pub const BPS: u128 = 10_000;
+
+    /// Selector of the ERC-20 `transfer(to, value)` message.
+    const TRANSFER_SELECTOR: [u8; 4] = [0x84, 0xa1, 0x5d, 0xa1];
+
+    /// Selector of the ERC-20 `transfer_from(from, to, value)` message.
+    const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x0b, 0x39, 0x6f, 0x18];
+
+    /// Selector of the oracle's `price(asset)` message.
+    const PRICE_SELECTOR: [u8; 4] = [0xd4, 0xbd, 0x7b, 0xc1];
+
+    /// The prediction market error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the admin.
+        NotAdmin,
+        /// Returned if the amount is zero.
+        ZeroAmount,
+        /// Returned if there is no market with the id.
+        MarketNotFound,
+        /// Returned if the market no longer sells shares.
+        MarketClosed,
+        /// Returned if the market has not closed yet.
+        MarketOpen,
+        /// Returned if the market is already resolved.
+        AlreadyResolved,
+        /// Returned if the market is not resolved yet.
+        NotResolved,
+        /// Returned if the caller holds no shares in the market.
+        NothingToRedeem,
+        /// Returned if the oracle has no usable price for the asset.
+        OracleUnavailable,
+        /// Returned if the token refused to move the tokens.
+        TransferFailed,
+        /// Returned if the share math overflows.
+        Overflow,
+    }
+
+    /// The prediction market result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Stands in for the error types of the token and the oracle. Both encode their
+    /// errors as a bare variant index, and all this contract needs is to tell
+    /// success from failure.
+    #[derive(scale::Decode)]
+    struct CalleeError(#[allow(dead_code)] u8);
+
+    /// A side of a market.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub enum Outcome {
+        /// The price ended at or above the strike.
+        Yes,
+        /// The price ended below the strike.
+        No,
+    }
+
+    /// A market.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Market {
+        /// Asset whose oracle price decides the market.
+        pub asset: AccountId,
+        /// Price at or above which YES wins.
+        pub strike: Balance,
+        /// Last time shares are sold; the market can be resolved after it.
+        pub closes_at: Deadline,
+        /// YES shares sold.
+        pub yes_shares: Balance,
+        /// NO shares sold.
+        pub no_shares: Balance,
+        /// Tokens paid for shares.
+        pub pot: Balance,
+        /// The winning side, once resolved.
+        pub outcome: Option<Outcome>,
+        /// Part of the pot the winning shares split, once resolved.
+        pub payout_pot: Balance,
+    }
+
+    impl Market {
+        /// Returns the number of shares sold on `outcome`.
+        pub fn shares(&self, outcome: Outcome) -> Balance {
+            match outcome {
+                Outcome::Yes => self.yes_shares,
+                Outcome::No => self.no_shares,
+            }
+        }
+    }
+
+    /// The shares an account holds in a market.
+    #[derive(
+        Debug,
+        Default,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        scale::Encode,
+        scale::Decode,
+        SpreadLayout,
+        PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Position {
+        /// YES shares held.
+        pub yes: Balance,
+        /// NO shares held.
+        pub no: Balance,
+    }
+
+    impl Position {
+        /// Returns the number of shares held on `outcome`.
+        pub fn shares(&self, outcome: Outcome) -> Balance {
+            match outcome {
+                Outcome::Yes => self.yes,
+                Outcome::No => self.no,
+            }
+        }
+    }
+
+    /// Returns what `position` redeems for in the resolved `market`, whose shares
+    /// were sold at `share_price`.
+    pub fn redemption(
+        market: &Market,
+        position: &Position,
+        share_price: Balance,
+    ) -> Result<Balance> {
+        let outcome = market.outcome.ok_or(Error::NotResolved)?;
+        let winning = market.shares(outcome);
+        if winning == 0 {
+            return (position.yes + position.no)
+                .checked_mul(share_price)
+                .ok_or(Error::Overflow);
+        }
+        position
+            .shares(outcome)
+            .checked_mul(market.payout_pot)
+            .map(|scaled| scaled / winning)
+            .ok_or(Error::Overflow)
+    }
+
+    // Events are mirrored in `crates/events/src/prediction.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when the admin opens a market.
+    #[ink(event)]
+    pub struct MarketCreated {
+        #[ink(topic)]
+        market_id: MarketId,
+        asset: AccountId,
+        strike: Balance,
+        closes_at: Timestamp,
+    }
+
+    /// Event emitted when shares are bought.
+    #[ink(event)]
+    pub struct SharesBought {
+        #[ink(topic)]
+        market_id: MarketId,
+        #[ink(topic)]
+        buyer: AccountId,
+        outcome: Outcome,
+        shares: Balance,
+        cost: Balance,
+    }
+
+    /// Event emitted when a market is resolved from the oracle price.
+    #[ink(event)]
+    pub struct MarketResolved {
+        #[ink(topic)]
+        market_id: MarketId,
+        price: Balance,
+        outcome: Outcome,
+        fee: Balance,
+    }
+
+    /// Event emitted when a holder redeems their shares.
+    #[ink(event)]
+    pub struct Redeemed {
+        #[ink(topic)]
+        market_id: MarketId,
+        #[ink(topic)]
+        holder: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when the admin withdraws the fees.
+    #[ink(event)]
+    pub struct FeesWithdrawn {
+        amount: Balance,
+    }
+
+    /// The prediction market storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Prediction {
+        /// Account that opens markets and receives the fees.
+        admin: AccountId,
+        /// The ERC-20 token shares are paid in.
+        token: AccountId,
+        /// Oracle reporting the prices markets are decided on.
+        oracle: AccountId,
+        /// Price of one share, on either side.
+        share_price: Balance,
+        /// Cut of each pot kept at resolution, in basis points.
+        fee_bps: u128,
+        /// Fees not withdrawn yet.
+        fees: Balance,
+        /// Mapping from market id to the market.
+        markets: Mapping<MarketId, Market>,
+        /// Mapping from market id and holder to their shares.
+        positions: Mapping<(MarketId, AccountId), Position>,
+        /// Id the next market will get.
+        next_market_id: MarketId,
+    }
+
+    impl Prediction {
+        /// Creates a contract selling shares for `share_price` of `token`, decided
+        /// by `oracle`, keeping `fee_bps` of each pot, with the caller as admin.
+        #[ink(constructor)]
+        pub fn new(
+            token: AccountId,
+            oracle: AccountId,
+            share_price: Balance,
+            fee_bps: u128,
+        ) -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.admin = Self::env().caller();
+                contract.token = token;
+                contract.oracle = oracle;
+                contract.share_price = share_price;
+                contract.fee_bps = fee_bps;
+            })
+        }
+
+        /// Returns the price of one share.
+        #[ink(message)]
+        pub fn share_price(&self) -> Balance {
+            self.share_price
+        }
+
+        /// Returns the fees not withdrawn yet.
+        #[ink(message)]
+        pub fn fees(&self) -> Balance {
+            self.fees
+        }
+
+        /// Returns the market with `market_id`, if any.
+        #[ink(message)]
+        pub fn market(&self, market_id: MarketId) -> Option<Market> {
+            self.markets.get(market_id)
+        }
+
+        /// Returns the shares `holder` holds in `market_id`.
+        #[ink(message)]
+        pub fn position(&self, market_id: MarketId, holder: AccountId) -> Position {
+            self.positions.get((market_id, holder)).unwrap_or_default()
+        }
+
+        /// Opens a market on whether the oracle price of `asset` is at least
+        /// `strike` at `closes_at`, and returns its id. Only the admin can call
+        /// this.
+        #[ink(message)]
+        pub fn create_market(
+            &mut self,
+            asset: AccountId,
+            strike: Balance,
+            closes_at: Timestamp,
+        ) -> Result<MarketId> {
+            self.ensure_admin()?;
+            if Deadline::at(closes_at).is_expired(self.env().block_timestamp()) {
+                return Err(Error::MarketClosed);
+            }
+            let market_id = self.next_market_id;
+            self.next_market_id += 1;
+            self.markets.insert(
+                market_id,
+                &Market {
+                    asset,
+                    strike,
+                    closes_at: Deadline::at(closes_at),
+                    yes_shares: 0,
+                    no_shares: 0,
+                    pot: 0,
+                    outcome: None,
+                    payout_pot: 0,
+                },
+            );
+            self.env().emit_event(MarketCreated {
+                market_id,
+                asset,
+                strike,
+                closes_at,
+            });
+            Ok(market_id)
+        }
+
+        /// Buys `shares` shares on `outcome` of `market_id` for the caller, paying
+        /// `shares * share_price` tokens. The caller must have approved this
+        /// contract to spend at least that much.
+        #[ink(message)]
+        pub fn buy(
+            &mut self,
+            market_id: MarketId,
+            outcome: Outcome,
+            shares: Balance,
+        ) -> Result<()> {
+            let cost = self.cost_of(market_id, shares)?;
+            let buyer = self.env().caller();
+            let contract = self.env().account_id();
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.token).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(TRANSFER_FROM_SELECTOR))
+                        .push_arg(buyer)
+                        .push_arg(contract)
+                        .push_arg(cost),
+                )
+                .returns::<core::result::Result<(), CalleeError>>()
+                .fire()
+                .map_err(|_| Error::TransferFailed)?
+                .map_err(|_| Error::TransferFailed)?;
+
+            self.mint_shares(market_id, buyer, outcome, shares, cost);
+            Ok(())
+        }
+
+        /// Decides `market_id` from the current oracle price and sets the fee
+        /// aside. Anyone can call this once the market has closed.
+        #[ink(message)]
+        pub fn resolve(&mut self, market_id: MarketId) -> Result<Outcome> {
+            let market = self.market(market_id).ok_or(Error::MarketNotFound)?;
+            if market.outcome.is_some() {
+                return Err(Error::AlreadyResolved);
+            }
+            if !market.closes_at.is_expired(self.env().block_timestamp()) {
+                return Err(Error::MarketOpen);
+            }
+            let price = self.oracle_price(market.asset)?;
+            self.resolve_at(market_id, price)
+        }
+
+        /// Sends the caller what their shares in the resolved `market_id` are
+        /// worth, and returns it.
+        #[ink(message)]
+        pub fn redeem(&mut self, market_id: MarketId) -> Result<Balance> {
+            let market = self.market(market_id).ok_or(Error::MarketNotFound)?;
+            let holder = self.env().caller();
+            let position = self
+                .positions
+                .get((market_id, holder))
+                .ok_or(Error::NothingToRedeem)?;
+            let amount = redemption(&market, &position, self.share_price)?;
+            self.positions.remove((market_id, holder));
+            if amount > 0 && !self.send(holder, amount) {
+                self.positions.insert((market_id, holder), &position);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(Redeemed {
+                market_id,
+                holder,
+                amount,
+            });
+            Ok(amount)
+        }
+
+        /// Sends the collected fees to the admin. Only the admin can call this.
+        #[ink(message)]
+        pub fn withdraw_fees(&mut self) -> Result<()> {
+            self.ensure_admin()?;
+            let amount = self.fees;
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.fees = 0;
+            if !self.send(self.admin, amount) {
+                self.fees = amount;
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(FeesWithdrawn { amount });
+            Ok(())
+        }
+
+        /// Returns the cost of `shares` shares in the open `market_id`.
+        fn cost_of(&self, market_id: MarketId, shares: Balance) -> Result<Balance> {
+            if shares == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let market = self.market(market_id).ok_or(Error::MarketNotFound)?;
+            if market.closes_at.is_expired(self.env().block_timestamp()) {
+                return Err(Error::MarketClosed);
+            }
+            shares
+                .checked_mul(self.share_price)
+                .filter(|cost| market.pot.checked_add(*cost).is_some())
+                .ok_or(Error::Overflow)
+        }
+
+        /// Credits `buyer` with `shares` shares on `outcome`, paid with `cost`.
+        fn mint_shares(
+            &mut self,
+            market_id: MarketId,
+            buyer: AccountId,
+            outcome: Outcome,
+            shares: Balance,
+            cost: Balance,
+        ) {
+            let mut market = self.market(market_id).expect("market was checked");
+            let mut position = self.position(market_id, buyer);
+            match outcome {
+                Outcome::Yes => {
+                    market.yes_shares += shares;
+                    position.yes += shares;
+                }
+                Outcome::No => {
+                    market.no_shares += shares;
+                    position.no += shares;
+                }
+            }
+            market.pot += cost;
+            self.markets.insert(market_id, &market);
+            self.positions.insert((market_id, buyer), &position);
+            self.env().emit_event(SharesBought {
+                market_id,
+                buyer,
+                outcome,
+                shares,
+                cost,
+            });
+        }
+
+        /// Decides `market_id` as if the oracle reported `price`.
+        fn resolve_at(&mut self, market_id: MarketId, price: Balance) -> Result<Outcome> {
+            let market = self.market(market_id).ok_or(Error::MarketNotFound)?;
+            let outcome = if price >= market.strike {
+                Outcome::Yes
+            } else {
+                Outcome::No
+            };
+            let fee = if market.shares(outcome) == 0 {
+                0
+            } else {
+                market
+                    .pot
+                    .checked_mul(self.fee_bps)
+                    .ok_or(Error::Overflow)?
+                    / BPS
+            };
+            self.fees += fee;
+            self.markets.insert(
+                market_id,
+                &Market {
+                    outcome: Some(outcome),
+                    payout_pot: market.pot - fee,
+                    ..market
+                },
+            );
+            self.env().emit_event(MarketResolved {
+                market_id,
+                price,
+                outcome,
+                fee,
+            });
+            Ok(outcome)
+        }
+
+        /// Reads the price of `asset` from the oracle.
+        fn oracle_price(&self, asset: AccountId) -> Result<Balance> {
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.oracle).gas_limit(0))
+                .exec_input(ExecutionInput::new(Selector::new(PRICE_SELECTOR)).push_arg(asset))
+                .returns::<core::result::Result<Balance, CalleeError>>()
+                .fire()
+                .map_err(|_| Error::OracleUnavailable)?
+                .map_err(|_| Error::OracleUnavailable)
+        }
+
+        /// Sends `amount` tokens to `to`, returning whether the token accepted.
+        fn send(&self, to: AccountId, amount: Balance) -> bool {
+            let sent = build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.token).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(TRANSFER_SELECTOR))
+                        .push_arg(to)
+                        .push_arg(amount),
+                )
+                .returns::<core::result::Result<(), CalleeError>>()
+                .fire();
+            matches!(sent, Ok(Ok(())))
+        }
+
+        /// Returns `NotAdmin` unless the caller is the admin.
+        fn ensure_admin(&self) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAdmin);
+            }
+            Ok(())
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// buy shares with `mint_shares`, resolve with `resolve_at`, and compute payouts
+    /// with `redemption` instead of moving tokens.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use fixtures::BLOCK_TIME;
+        use ink_lang as ink;
+
+        const PRICE: Balance = 10;
+        const STRIKE: Balance = 2_000;
+        const CLOSE: Timestamp = 200 * BLOCK_TIME;
+
+        fn buy(
+            contract: &mut Prediction,
+            buyer: AccountId,
+            outcome: Outcome,
+            shares: Balance,
+        ) -> Result<()> {
+            let cost = contract.cost_of(0, shares)?;
+            contract.mint_shares(0, buyer, outcome, shares, cost);
+            Ok(())
+        }
+
+        fn payout(contract: &Prediction, holder: AccountId) -> Result<Balance> {
+            let market = contract.market(0).expect("market exists");
+            redemption(&market, &contract.position(0, holder), PRICE)
+        }
+
+        /// Market 0 with a 2% fee, where bob buys 30 YES, charlie 10 YES and dave
+        /// 60 NO.
+        fn traded() -> Prediction {
+            let accounts = fixtures::accounts();
+            let mut contract = Prediction::new(
+                AccountId::from([0x70; 32]),
+                AccountId::from([0x0e; 32]),
+                PRICE,
+                200,
+            );
+            assert_eq!(
+                contract.create_market(AccountId::from([0xd0; 32]), STRIKE, CLOSE),
+                Ok(0)
+            );
+            assert_eq!(buy(&mut contract, accounts.bob, Outcome::Yes, 30), Ok(()));
+            assert_eq!(
+                buy(&mut contract, accounts.charlie, Outcome::Yes, 10),
+                Ok(())
+            );
+            assert_eq!(buy(&mut contract, accounts.dave, Outcome::No, 60), Ok(()));
+            contract
+        }
+
+        #[ink::test]
+        fn shares_are_minted_at_fixed_price() {
+            let accounts = fixtures::accounts();
+            let contract = traded();
+            let market = contract.market(0).expect("market exists");
+            assert_eq!(market.yes_shares, 40);
+            assert_eq!(market.no_shares, 60);
+            assert_eq!(market.pot, 1_000);
+            assert_eq!(
+                contract.position(0, accounts.bob),
+                Position { yes: 30, no: 0 }
+            );
+        }
+
+        #[ink::test]
+        fn winners_split_the_pot_after_fee() {
+            let accounts = fixtures::accounts();
+            let mut contract = traded();
+            fixtures::set_block_timestamp(CLOSE + BLOCK_TIME);
+            assert_eq!(contract.resolve_at(0, STRIKE), Ok(Outcome::Yes));
+            assert_eq!(contract.fees(), 20);
+
+            assert_eq!(payout(&contract, accounts.bob), Ok(735));
+            assert_eq!(payout(&contract, accounts.charlie), Ok(245));
+            assert_eq!(payout(&contract, accounts.dave), Ok(0));
+        }
+
+        #[ink::test]
+        fn no_wins_below_strike() {
+            let accounts = fixtures::accounts();
+            let mut contract = traded();
+            assert_eq!(contract.resolve_at(0, STRIKE - 1), Ok(Outcome::No));
+            // 980 split over 60 shares, rounded down.
+            assert_eq!(payout(&contract, accounts.dave), Ok(980));
+            assert_eq!(payout(&contract, accounts.bob), Ok(0));
+        }
+
+        #[ink::test]
+        fn unbacked_winning_side_refunds_everyone() {
+            let accounts = fixtures::accounts();
+            let mut contract = traded();
+            assert_eq!(
+                buy(&mut contract, accounts.dave, Outcome::Yes, 0),
+                Err(Error::ZeroAmount)
+            );
+            let other = contract
+                .create_market(AccountId::from([0xd0; 32]), STRIKE, CLOSE)
+                .expect("created");
+            let cost = contract.cost_of(other, 5).expect("open");
+            contract.mint_shares(other, accounts.bob, Outcome::No, 5, cost);
+
+            assert_eq!(contract.resolve_at(other, STRIKE), Ok(Outcome::Yes));
+            assert_eq!(contract.fees(), 0);
+            let market = contract.market(other).expect("market exists");
+            assert_eq!(
+                redemption(&market, &contract.position(other, accounts.bob), PRICE),
+                Ok(50)
+            );
+        }
+
+        #[ink::test]
+        fn market_closes_and_resolves_once() {
+            let accounts = fixtures::accounts();
+            let mut contract = traded();
+            assert_eq!(contract.resolve(0), Err(Error::MarketOpen));
+
+            // The close itself still belongs to the market.
+            fixtures::set_block_timestamp(CLOSE);
+            assert_eq!(buy(&mut contract, accounts.bob, Outcome::Yes, 1), Ok(()));
+            assert_eq!(contract.resolve(0), Err(Error::MarketOpen));
+
+            fixtures::set_block_timestamp(CLOSE + BLOCK_TIME);
+            assert_eq!(
+                buy(&mut contract, accounts.bob, Outcome::Yes, 1),
+                Err(Error::MarketClosed)
+            );
+            assert_eq!(contract.resolve_at(0, STRIKE), Ok(Outcome::Yes));
+            assert_eq!(contract.resolve(0), Err(Error::AlreadyResolved));
+            assert_eq!(contract.resolve(1), Err(Error::MarketNotFound));
+        }
+
+        #[ink::test]
+        fn redemption_waits_for_resolution() {
+            let accounts = fixtures::accounts();
+            let mut contract = traded();
+            assert_eq!(payout(&contract, accounts.bob), Err(Error::NotResolved));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(contract.redeem(0), Err(Error::NotResolved));
+            // A failed redemption leaves the shares in place.
+            assert_eq!(contract.position(0, accounts.bob).yes, 30);
+            fixtures::set_caller(accounts.eve);
+            assert_eq!(contract.redeem(0), Err(Error::NothingToRedeem));
+        }
+
+        #[ink::test]
+        fn only_admin_creates_markets() {
+            let accounts = fixtures::accounts();
+            let mut contract = traded();
+            fixtures::set_block_timestamp(BLOCK_TIME);
+            assert_eq!(
+                contract.create_market(AccountId::from([0xd0; 32]), STRIKE, 0),
+                Err(Error::MarketClosed)
+            );
+            assert_eq!(
+                contract.create_market(AccountId::from([0xd0; 32]), STRIKE, BLOCK_TIME),
+                Ok(1)
+            );
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                contract.create_market(AccountId::from([0xd0; 32]), STRIKE, CLOSE),
+                Err(Error::NotAdmin)
+            );
+            assert_eq!(contract.withdraw_fees(), Err(Error::NotAdmin));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Deploy an ERC-20 token and an oracle (examples/mocks/mock_oracle.rs for
+//      testing) first
+//    - Upload the generated .contract file and deploy it with both addresses, a
+//      share price and a fee
+//
+// 4. Interact with the contract:
+//    - As admin, use "createMarket" with an asset, a strike and a closing time
+//    - Approve the contract on the token, then use "buy" with Yes or No
+//    - After the close, anyone uses "resolve"; holders use "redeem"
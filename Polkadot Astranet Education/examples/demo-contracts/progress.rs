@@ -0,0 +1,527 @@
+// examples/demo-contracts/progress.rs
+//
+// A course progress contract example for Polkadot using ink!
+// Courses are split into modules, and modules into lessons. The course instructor
+// records the lessons each learner completes; a module is complete once all of its
+// lessons are, and the course once all of its modules are.
+//
+// Bitmaps
+// -------
+// Progress is stored as bitmaps rather than lists: bit `l` of a learner's lesson
+// bitmap for a module is set once lesson `l` is complete, and bit `m` of their module
+// bitmap once module `m` is. A whole module's progress is a single `u32`, so a
+// completion check is a mask comparison and a learner costs one storage cell per
+// module started. This caps courses at 32 modules of 32 lessons each.
+//
+// Prerequisites
+// -------------
+// Each module lists the modules that must be complete before any of its lessons can
+// be recorded, again as a bitmap. Prerequisites may only name earlier modules, which
+// keeps the dependency graph acyclic without any graph walking.
+//
+// Other contracts, such as a certificate minter, call `course_completed` (selector
+// `0xb7543bee`, pinned in `tools/selector/tests/pinned.rs`) before issuing anything.
+// Adding a module to a course raises the bar for every learner, including those who
+// had already completed it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod progress {
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// The id of a course.
+    pub type CourseId = u32;
+
+    /// The largest number of modules in a course, and of lessons in a module.
+    pub const MAX_ITEMS: u8 = 32;
+
+    /// The progress error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if there is no course with the id.
+        UnknownCourse,
+        /// Returned if the caller is not the course instructor.
+        NotInstructor,
+        /// Returned if the course already has `MAX_ITEMS` modules.
+        TooManyModules,
+        /// Returned if a module would have no lessons or more than `MAX_ITEMS`.
+        InvalidLessonCount,
+        /// Returned if a prerequisite is not an earlier module.
+        InvalidPrerequisites,
+        /// Returned if the course has no such module.
+        UnknownModule,
+        /// Returned if the module has no such lesson.
+        UnknownLesson,
+        /// Returned if a prerequisite of the module is not complete.
+        PrerequisitesIncomplete,
+        /// Returned if the learner already completed the lesson.
+        AlreadyCompleted,
+    }
+
+    /// The progress result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A course.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Course {
+        /// Account that adds modules and records progress.
+        pub instructor: AccountId,
+        /// Number of modules, numbered from 0.
+        pub modules: u8,
+    }
+
+    /// A module of a course.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Module {
+        /// Number of lessons, numbered from 0.
+        pub lessons: u8,
+        /// Bitmap of the modules to complete first.
+        pub prerequisites: u32,
+    }
+
+    /// Returns the bitmap with the lowest `count` bits set.
+    pub fn full_mask(count: u8) -> u32 {
+        if count >= MAX_ITEMS {
+            u32::MAX
+        } else {
+            (1 << count) - 1
+        }
+    }
+
+    // Events are mirrored in `crates/events/src/progress.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when an instructor creates a course.
+    #[ink(event)]
+    pub struct CourseCreated {
+        #[ink(topic)]
+        course_id: CourseId,
+        #[ink(topic)]
+        instructor: AccountId,
+    }
+
+    /// Event emitted when an instructor adds a module to a course.
+    #[ink(event)]
+    pub struct ModuleAdded {
+        #[ink(topic)]
+        course_id: CourseId,
+        module: u8,
+        lessons: u8,
+        prerequisites: u32,
+    }
+
+    /// Event emitted when a learner completes a lesson.
+    #[ink(event)]
+    pub struct LessonCompleted {
+        #[ink(topic)]
+        course_id: CourseId,
+        #[ink(topic)]
+        learner: AccountId,
+        module: u8,
+        lesson: u8,
+    }
+
+    /// Event emitted when a learner completes the last lesson of a module.
+    #[ink(event)]
+    pub struct ModuleCompleted {
+        #[ink(topic)]
+        course_id: CourseId,
+        #[ink(topic)]
+        learner: AccountId,
+        module: u8,
+    }
+
+    /// Event emitted when a learner completes the last module of a course.
+    #[ink(event)]
+    pub struct CourseCompleted {
+        #[ink(topic)]
+        course_id: CourseId,
+        #[ink(topic)]
+        learner: AccountId,
+    }
+
+    /// The progress storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Progress {
+        /// Mapping from course id to the course.
+        courses: Mapping<CourseId, Course>,
+        /// Mapping from course id and module to the module.
+        modules: Mapping<(CourseId, u8), Module>,
+        /// Mapping from course id, learner and module to the learner's lesson bitmap.
+        lessons_done: Mapping<(CourseId, AccountId, u8), u32>,
+        /// Mapping from course id and learner to the learner's module bitmap.
+        modules_done: Mapping<(CourseId, AccountId), u32>,
+        /// The id of the next course.
+        next_course_id: CourseId,
+    }
+
+    impl Progress {
+        /// Creates a contract without courses.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|_: &mut Self| {})
+        }
+
+        /// Returns the course with `course_id`, if any.
+        #[ink(message)]
+        pub fn course(&self, course_id: CourseId) -> Option<Course> {
+            self.courses.get(course_id)
+        }
+
+        /// Returns module `module` of the course, if any.
+        #[ink(message)]
+        pub fn module(&self, course_id: CourseId, module: u8) -> Option<Module> {
+            self.modules.get((course_id, module))
+        }
+
+        /// Returns the bitmap of the lessons of `module` that `learner` completed.
+        #[ink(message)]
+        pub fn lessons_completed(
+            &self,
+            course_id: CourseId,
+            learner: AccountId,
+            module: u8,
+        ) -> u32 {
+            self.lessons_done
+                .get((course_id, learner, module))
+                .unwrap_or(0)
+        }
+
+        /// Returns the bitmap of the modules of the course that `learner` completed.
+        #[ink(message)]
+        pub fn modules_completed(&self, course_id: CourseId, learner: AccountId) -> u32 {
+            self.modules_done.get((course_id, learner)).unwrap_or(0)
+        }
+
+        /// Returns whether `learner` completed `module` of the course.
+        #[ink(message)]
+        pub fn module_completed(
+            &self,
+            course_id: CourseId,
+            learner: AccountId,
+            module: u8,
+        ) -> bool {
+            module < MAX_ITEMS && self.modules_completed(course_id, learner) & (1 << module) != 0
+        }
+
+        /// Returns whether `learner` completed every module of the course. A course
+        /// without modules is never completed.
+        #[ink(message)]
+        pub fn course_completed(&self, course_id: CourseId, learner: AccountId) -> bool {
+            match self.course(course_id) {
+                Some(course) if course.modules > 0 => {
+                    self.modules_completed(course_id, learner) == full_mask(course.modules)
+                }
+                _ => false,
+            }
+        }
+
+        /// Creates a course without modules, with the caller as instructor, and
+        /// returns its id.
+        #[ink(message)]
+        pub fn create_course(&mut self) -> CourseId {
+            let course_id = self.next_course_id;
+            self.next_course_id += 1;
+            let instructor = self.env().caller();
+            self.courses.insert(
+                course_id,
+                &Course {
+                    instructor,
+                    modules: 0,
+                },
+            );
+            self.env().emit_event(CourseCreated {
+                course_id,
+                instructor,
+            });
+            course_id
+        }
+
+        /// Appends a module of `lessons` lessons to the course, which requires the
+        /// modules in the `prerequisites` bitmap to be completed first, and returns
+        /// its number. Only the course instructor can call this.
+        #[ink(message)]
+        pub fn add_module(
+            &mut self,
+            course_id: CourseId,
+            lessons: u8,
+            prerequisites: u32,
+        ) -> Result<u8> {
+            let course = self.instructed_course(course_id)?;
+            if course.modules >= MAX_ITEMS {
+                return Err(Error::TooManyModules);
+            }
+            if lessons == 0 || lessons > MAX_ITEMS {
+                return Err(Error::InvalidLessonCount);
+            }
+            if prerequisites & !full_mask(course.modules) != 0 {
+                return Err(Error::InvalidPrerequisites);
+            }
+            let module = course.modules;
+            self.modules.insert(
+                (course_id, module),
+                &Module {
+                    lessons,
+                    prerequisites,
+                },
+            );
+            self.courses.insert(
+                course_id,
+                &Course {
+                    modules: module + 1,
+                    ..course
+                },
+            );
+            self.env().emit_event(ModuleAdded {
+                course_id,
+                module,
+                lessons,
+                prerequisites,
+            });
+            Ok(module)
+        }
+
+        /// Records that `learner` completed `lesson` of `module`. Only the course
+        /// instructor can call this, and only once the module's prerequisites are
+        /// complete.
+        #[ink(message)]
+        pub fn complete_lesson(
+            &mut self,
+            course_id: CourseId,
+            learner: AccountId,
+            module: u8,
+            lesson: u8,
+        ) -> Result<()> {
+            let course = self.instructed_course(course_id)?;
+            let spec = self.module(course_id, module).ok_or(Error::UnknownModule)?;
+            if lesson >= spec.lessons {
+                return Err(Error::UnknownLesson);
+            }
+            let modules_done = self.modules_completed(course_id, learner);
+            if modules_done & spec.prerequisites != spec.prerequisites {
+                return Err(Error::PrerequisitesIncomplete);
+            }
+            let lessons_done = self.lessons_completed(course_id, learner, module);
+            if lessons_done & (1 << lesson) != 0 {
+                return Err(Error::AlreadyCompleted);
+            }
+
+            let lessons_done = lessons_done | (1 << lesson);
+            self.lessons_done
+                .insert((course_id, learner, module), &lessons_done);
+            self.env().emit_event(LessonCompleted {
+                course_id,
+                learner,
+                module,
+                lesson,
+            });
+            if lessons_done != full_mask(spec.lessons) {
+                return Ok(());
+            }
+
+            let modules_done = modules_done | (1 << module);
+            self.modules_done
+                .insert((course_id, learner), &modules_done);
+            self.env().emit_event(ModuleCompleted {
+                course_id,
+                learner,
+                module,
+            });
+            if modules_done == full_mask(course.modules) {
+                self.env()
+                    .emit_event(CourseCompleted { course_id, learner });
+            }
+            Ok(())
+        }
+
+        /// Returns the course, or `NotInstructor` unless the caller instructs it.
+        fn instructed_course(&self, course_id: CourseId) -> Result<Course> {
+            let course = self.course(course_id).ok_or(Error::UnknownCourse)?;
+            if self.env().caller() != course.instructor {
+                return Err(Error::NotInstructor);
+            }
+            Ok(course)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        /// A course by alice with module 0 of 2 lessons, module 1 of 3 lessons
+        /// requiring module 0, and module 2 of 1 lesson without prerequisites.
+        fn with_course() -> (Progress, CourseId) {
+            let mut progress = Progress::new();
+            let course = progress.create_course();
+            assert_eq!(progress.add_module(course, 2, 0), Ok(0));
+            assert_eq!(progress.add_module(course, 3, 0b1), Ok(1));
+            assert_eq!(progress.add_module(course, 1, 0), Ok(2));
+            (progress, course)
+        }
+
+        #[ink::test]
+        fn lessons_fill_bitmaps() {
+            let accounts = fixtures::accounts();
+            let (mut progress, course) = with_course();
+            assert_eq!(progress.complete_lesson(course, accounts.bob, 0, 1), Ok(()));
+            assert_eq!(progress.lessons_completed(course, accounts.bob, 0), 0b10);
+            assert!(!progress.module_completed(course, accounts.bob, 0));
+
+            assert_eq!(progress.complete_lesson(course, accounts.bob, 0, 0), Ok(()));
+            assert_eq!(progress.lessons_completed(course, accounts.bob, 0), 0b11);
+            assert!(progress.module_completed(course, accounts.bob, 0));
+            assert_eq!(progress.modules_completed(course, accounts.bob), 0b1);
+            assert_eq!(progress.modules_completed(course, accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn prerequisites_are_enforced() {
+            let accounts = fixtures::accounts();
+            let (mut progress, course) = with_course();
+            assert_eq!(
+                progress.complete_lesson(course, accounts.bob, 1, 0),
+                Err(Error::PrerequisitesIncomplete)
+            );
+            // Module 2 has no prerequisites, so it can be taken first.
+            assert_eq!(progress.complete_lesson(course, accounts.bob, 2, 0), Ok(()));
+
+            assert_eq!(progress.complete_lesson(course, accounts.bob, 0, 0), Ok(()));
+            assert_eq!(
+                progress.complete_lesson(course, accounts.bob, 1, 0),
+                Err(Error::PrerequisitesIncomplete)
+            );
+            assert_eq!(progress.complete_lesson(course, accounts.bob, 0, 1), Ok(()));
+            assert_eq!(progress.complete_lesson(course, accounts.bob, 1, 0), Ok(()));
+        }
+
+        #[ink::test]
+        fn course_completes_with_last_module() {
+            let accounts = fixtures::accounts();
+            let (mut progress, course) = with_course();
+            for (module, lesson) in [(0, 0), (0, 1), (2, 0), (1, 0), (1, 2)] {
+                assert_eq!(
+                    progress.complete_lesson(course, accounts.bob, module, lesson),
+                    Ok(())
+                );
+                assert!(!progress.course_completed(course, accounts.bob));
+            }
+            assert_eq!(progress.complete_lesson(course, accounts.bob, 1, 1), Ok(()));
+            assert!(progress.course_completed(course, accounts.bob));
+            assert!(!progress.course_completed(course, accounts.charlie));
+
+            // 3 created modules, 6 lessons, 3 completed modules and the course.
+            assert_eq!(ink_env::test::recorded_events().count(), 1 + 3 + 6 + 3 + 1);
+
+            // A new module raises the bar again.
+            assert_eq!(progress.add_module(course, 1, 0b111), Ok(3));
+            assert!(!progress.course_completed(course, accounts.bob));
+        }
+
+        #[ink::test]
+        fn lessons_complete_once() {
+            let accounts = fixtures::accounts();
+            let (mut progress, course) = with_course();
+            assert_eq!(progress.complete_lesson(course, accounts.bob, 0, 0), Ok(()));
+            assert_eq!(
+                progress.complete_lesson(course, accounts.bob, 0, 0),
+                Err(Error::AlreadyCompleted)
+            );
+            assert_eq!(
+                progress.complete_lesson(course, accounts.bob, 0, 2),
+                Err(Error::UnknownLesson)
+            );
+            assert_eq!(
+                progress.complete_lesson(course, accounts.bob, 3, 0),
+                Err(Error::UnknownModule)
+            );
+            assert_eq!(
+                progress.complete_lesson(course + 1, accounts.bob, 0, 0),
+                Err(Error::UnknownCourse)
+            );
+        }
+
+        #[ink::test]
+        fn modules_are_validated() {
+            let (mut progress, course) = with_course();
+            assert_eq!(
+                progress.add_module(course, 1, 0b1000),
+                Err(Error::InvalidPrerequisites)
+            );
+            assert_eq!(
+                progress.add_module(course, 0, 0),
+                Err(Error::InvalidLessonCount)
+            );
+            assert_eq!(
+                progress.add_module(course, MAX_ITEMS + 1, 0),
+                Err(Error::InvalidLessonCount)
+            );
+
+            let other = progress.create_course();
+            for module in 0..MAX_ITEMS {
+                assert_eq!(progress.add_module(other, MAX_ITEMS, 0), Ok(module));
+            }
+            assert_eq!(progress.add_module(other, 1, 0), Err(Error::TooManyModules));
+            assert_eq!(full_mask(MAX_ITEMS), u32::MAX);
+        }
+
+        #[ink::test]
+        fn only_instructor_records_progress() {
+            let accounts = fixtures::accounts();
+            let (mut progress, course) = with_course();
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                progress.complete_lesson(course, accounts.bob, 0, 0),
+                Err(Error::NotInstructor)
+            );
+            assert_eq!(progress.add_module(course, 1, 0), Err(Error::NotInstructor));
+
+            // A course without modules is never completed.
+            let empty = progress.create_course();
+            assert!(!progress.course_completed(empty, accounts.bob));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy with the "new" constructor
+//
+// 4. Interact with the contract:
+//    - As an instructor, use "createCourse", then "addModule" with the number of
+//      lessons and a bitmap of prerequisite modules (e.g. 3 for modules 0 and 1)
+//    - Use "completeLesson" as each learner finishes a lesson
+//    - Use "courseCompleted" to check whether a learner finished the course
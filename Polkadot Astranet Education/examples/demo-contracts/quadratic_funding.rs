@@ -0,0 +1,634 @@
+// examples/demo-contracts/quadratic_funding.rs
+//
+// A quadratic funding round contract example for Polkadot using ink!
+// Sponsors put native tokens into a matching pool, and the admin lists the projects
+// of the round. Until the round ends, anyone contributes to the projects they like;
+// afterwards the pool is split between the projects so that broad support counts
+// for more than large cheques, and each project is paid its contributions plus its
+// match.
+//
+// The matching formula
+// --------------------
+// A project whose contributors gave c_1, c_2, ... would ideally be matched
+//
+//     (sqrt(c_1) + sqrt(c_2) + ...)^2 - (c_1 + c_2 + ...)
+//
+// so a hundred contributors giving 1 each earn a match of 9_900, while one
+// contributor giving 100 earns none. Square roots are taken with `isqrt` from the
+// utils crate's `math` module, on each contributor's running total per project.
+// When the ideal matches add up to more than the pool, every match is scaled down
+// by the same factor (capital-constrained matching); when they add up to less, the
+// rest of the pool goes back to the admin. Rounding leftovers go back too.
+//
+// The formula assumes one account per person. Nothing here stops a contributor
+// from splitting a donation over many accounts to inflate a project's match; real
+// rounds pair quadratic funding with identity checks or sybil scoring.
+//
+// The end of the round is the utils crate's inclusive `Deadline`: contributions are
+// still taken at exactly `ends_at`, and the round can be finalized the millisecond
+// after.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod quadratic_funding {
+    use astranet_utils::{deadline::Deadline, math::isqrt};
+    use ink_prelude::vec::Vec;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Identifies a project.
+    pub type ProjectId = u32;
+
+    /// The largest number of projects in a round, which bounds the cost of
+    /// `finalize`.
+    pub const MAX_PROJECTS: u32 = 32;
+
+    /// The quadratic funding error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the admin.
+        NotAdmin,
+        /// Returned if the amount is zero.
+        ZeroAmount,
+        /// Returned if the round already has `MAX_PROJECTS` projects.
+        TooManyProjects,
+        /// Returned if there is no project with the id.
+        UnknownProject,
+        /// Returned if the round no longer takes contributions.
+        RoundClosed,
+        /// Returned if the round has not ended yet.
+        RoundOpen,
+        /// Returned if the matches are already computed.
+        AlreadyFinalized,
+        /// Returned if the matches are not computed yet.
+        NotFinalized,
+        /// Returned if the project was already paid.
+        AlreadyPaid,
+        /// Returned if there is nothing to withdraw.
+        NothingToWithdraw,
+        /// Returned if the transfer to the recipient failed.
+        TransferFailed,
+        /// Returned if the matching math overflows.
+        Overflow,
+    }
+
+    /// The quadratic funding result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A project and what it has raised.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Project {
+        /// Account the project is paid to.
+        pub recipient: AccountId,
+        /// Sum of all contributions.
+        pub raised: Balance,
+        /// Sum of the square roots of each contributor's total.
+        pub sum_sqrt: Balance,
+        /// Share of the matching pool, once finalized.
+        pub matched: Balance,
+        /// Whether the project has been paid.
+        pub paid: bool,
+    }
+
+    /// Returns the ideal match of a project whose contributors' square roots sum to
+    /// `sum_sqrt` and whose contributions sum to `raised`.
+    ///
+    /// Each square root is rounded down, so the square of the sum can fall slightly
+    /// short of `raised`; the match is then zero.
+    pub fn ideal_match(sum_sqrt: Balance, raised: Balance) -> Result<Balance> {
+        sum_sqrt
+            .checked_mul(sum_sqrt)
+            .map(|square| square.saturating_sub(raised))
+            .ok_or(Error::Overflow)
+    }
+
+    /// Splits `pool` between projects with the given ideal matches. Each project
+    /// gets its ideal match if the pool covers them all, and otherwise the same
+    /// fraction of it, rounded down.
+    pub fn constrained_matches(ideals: &[Balance], pool: Balance) -> Result<Vec<Balance>> {
+        let total = ideals
+            .iter()
+            .try_fold(0 as Balance, |total, ideal| total.checked_add(*ideal))
+            .ok_or(Error::Overflow)?;
+        if total <= pool {
+            return Ok(ideals.to_vec());
+        }
+        ideals
+            .iter()
+            .map(|ideal| {
+                ideal
+                    .checked_mul(pool)
+                    .map(|scaled| scaled / total)
+                    .ok_or(Error::Overflow)
+            })
+            .collect()
+    }
+
+    // Events are mirrored in `crates/events/src/quadratic_funding.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when the admin lists a project.
+    #[ink(event)]
+    pub struct ProjectAdded {
+        #[ink(topic)]
+        project_id: ProjectId,
+        #[ink(topic)]
+        recipient: AccountId,
+    }
+
+    /// Event emitted when a sponsor adds to the matching pool.
+    #[ink(event)]
+    pub struct MatchingFunded {
+        #[ink(topic)]
+        sponsor: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when someone contributes to a project.
+    #[ink(event)]
+    pub struct Contributed {
+        #[ink(topic)]
+        project_id: ProjectId,
+        #[ink(topic)]
+        contributor: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when the matches are computed.
+    #[ink(event)]
+    pub struct RoundFinalized {
+        matching_pool: Balance,
+        unmatched: Balance,
+    }
+
+    /// Event emitted when a project is paid its contributions and match.
+    #[ink(event)]
+    pub struct ProjectPaid {
+        #[ink(topic)]
+        project_id: ProjectId,
+        #[ink(topic)]
+        recipient: AccountId,
+        raised: Balance,
+        matched: Balance,
+    }
+
+    /// Event emitted when the admin takes back the unused part of the pool.
+    #[ink(event)]
+    pub struct UnmatchedWithdrawn {
+        amount: Balance,
+    }
+
+    /// The quadratic funding storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct QuadraticFunding {
+        /// Account that lists projects and gets back the unused pool.
+        admin: AccountId,
+        /// Last time contributions are taken; the round can be finalized after it.
+        ends_at: Deadline,
+        /// Tokens sponsors have put into the matching pool.
+        matching_pool: Balance,
+        /// Mapping from project id to the project.
+        projects: Mapping<ProjectId, Project>,
+        /// Number of projects listed.
+        project_count: u32,
+        /// Mapping from project id and contributor to their total contribution.
+        contributions: Mapping<(ProjectId, AccountId), Balance>,
+        /// Whether the matches have been computed.
+        finalized: bool,
+        /// Part of the pool no project was matched, until withdrawn.
+        unmatched: Balance,
+    }
+
+    impl QuadraticFunding {
+        /// Creates a round taking contributions until `ends_at`, with the caller
+        /// as admin.
+        #[ink(constructor)]
+        pub fn new(ends_at: Timestamp) -> Self {
+            ink_lang::utils::initialize_contract(|round: &mut Self| {
+                round.admin = Self::env().caller();
+                round.ends_at = Deadline::at(ends_at);
+            })
+        }
+
+        /// Returns the tokens in the matching pool.
+        #[ink(message)]
+        pub fn matching_pool(&self) -> Balance {
+            self.matching_pool
+        }
+
+        /// Returns the project with `project_id`, if any.
+        #[ink(message)]
+        pub fn project(&self, project_id: ProjectId) -> Option<Project> {
+            self.projects.get(project_id)
+        }
+
+        /// Returns the number of projects listed.
+        #[ink(message)]
+        pub fn project_count(&self) -> u32 {
+            self.project_count
+        }
+
+        /// Returns what `contributor` has given to `project_id`.
+        #[ink(message)]
+        pub fn contribution_of(&self, project_id: ProjectId, contributor: AccountId) -> Balance {
+            self.contributions
+                .get((project_id, contributor))
+                .unwrap_or(0)
+        }
+
+        /// Lists a project paid to `recipient` and returns its id. Only the admin
+        /// can call this, before the round ends.
+        #[ink(message)]
+        pub fn add_project(&mut self, recipient: AccountId) -> Result<ProjectId> {
+            self.ensure_admin()?;
+            self.ensure_open()?;
+            if self.project_count >= MAX_PROJECTS {
+                return Err(Error::TooManyProjects);
+            }
+            let project_id = self.project_count;
+            self.project_count += 1;
+            self.projects.insert(
+                project_id,
+                &Project {
+                    recipient,
+                    raised: 0,
+                    sum_sqrt: 0,
+                    matched: 0,
+                    paid: false,
+                },
+            );
+            self.env().emit_event(ProjectAdded {
+                project_id,
+                recipient,
+            });
+            Ok(project_id)
+        }
+
+        /// Adds the transferred value to the matching pool. Anyone can sponsor the
+        /// round until the matches are computed.
+        #[ink(message, payable)]
+        pub fn fund_matching(&mut self) -> Result<()> {
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            if self.finalized {
+                return Err(Error::AlreadyFinalized);
+            }
+            self.matching_pool = self
+                .matching_pool
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+            self.env().emit_event(MatchingFunded {
+                sponsor: self.env().caller(),
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Contributes the transferred value to `project_id`.
+        #[ink(message, payable)]
+        pub fn contribute(&mut self, project_id: ProjectId) -> Result<()> {
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.ensure_open()?;
+            let mut project = self.project(project_id).ok_or(Error::UnknownProject)?;
+            let contributor = self.env().caller();
+            let before = self.contribution_of(project_id, contributor);
+            let after = before.checked_add(amount).ok_or(Error::Overflow)?;
+            project.raised = project.raised.checked_add(amount).ok_or(Error::Overflow)?;
+            // Replace the contributor's old square root with the one of their new
+            // total; the sum of the roots is what the match depends on.
+            project.sum_sqrt = project.sum_sqrt - isqrt(before) + isqrt(after);
+            self.projects.insert(project_id, &project);
+            self.contributions.insert((project_id, contributor), &after);
+            self.env().emit_event(Contributed {
+                project_id,
+                contributor,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Computes every project's share of the matching pool. Anyone can call
+        /// this once the round has ended.
+        #[ink(message)]
+        pub fn finalize(&mut self) -> Result<()> {
+            if !self.ends_at.is_expired(self.env().block_timestamp()) {
+                return Err(Error::RoundOpen);
+            }
+            if self.finalized {
+                return Err(Error::AlreadyFinalized);
+            }
+            let projects: Vec<Project> = (0..self.project_count)
+                .map(|project_id| self.project(project_id).expect("listed project exists"))
+                .collect();
+            let ideals = projects
+                .iter()
+                .map(|project| ideal_match(project.sum_sqrt, project.raised))
+                .collect::<Result<Vec<Balance>>>()?;
+            let matches = constrained_matches(&ideals, self.matching_pool)?;
+
+            let mut unmatched = self.matching_pool;
+            for (project_id, (project, matched)) in projects.into_iter().zip(matches).enumerate() {
+                unmatched -= matched;
+                self.projects
+                    .insert(project_id as ProjectId, &Project { matched, ..project });
+            }
+            self.finalized = true;
+            self.unmatched = unmatched;
+            self.env().emit_event(RoundFinalized {
+                matching_pool: self.matching_pool,
+                unmatched,
+            });
+            Ok(())
+        }
+
+        /// Sends `project_id` its contributions plus its match. Anyone can call
+        /// this once the round is finalized.
+        #[ink(message)]
+        pub fn pay_project(&mut self, project_id: ProjectId) -> Result<()> {
+            if !self.finalized {
+                return Err(Error::NotFinalized);
+            }
+            let project = self.project(project_id).ok_or(Error::UnknownProject)?;
+            if project.paid {
+                return Err(Error::AlreadyPaid);
+            }
+            let amount = project.raised + project.matched;
+            self.projects.insert(
+                project_id,
+                &Project {
+                    paid: true,
+                    ..project
+                },
+            );
+            if amount > 0 && self.env().transfer(project.recipient, amount).is_err() {
+                self.projects.insert(project_id, &project);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(ProjectPaid {
+                project_id,
+                recipient: project.recipient,
+                raised: project.raised,
+                matched: project.matched,
+            });
+            Ok(())
+        }
+
+        /// Sends the part of the pool no project was matched to the admin. Only the
+        /// admin can call this, once the round is finalized.
+        #[ink(message)]
+        pub fn withdraw_unmatched(&mut self) -> Result<()> {
+            self.ensure_admin()?;
+            if !self.finalized {
+                return Err(Error::NotFinalized);
+            }
+            let amount = self.unmatched;
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+            self.unmatched = 0;
+            if self.env().transfer(self.admin, amount).is_err() {
+                self.unmatched = amount;
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(UnmatchedWithdrawn { amount });
+            Ok(())
+        }
+
+        /// Returns `RoundClosed` once the round has ended.
+        fn ensure_open(&self) -> Result<()> {
+            if self.ends_at.is_expired(self.env().block_timestamp()) {
+                return Err(Error::RoundClosed);
+            }
+            Ok(())
+        }
+
+        /// Returns `NotAdmin` unless the caller is the admin.
+        fn ensure_admin(&self) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAdmin);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use fixtures::BLOCK_TIME;
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        const END: Timestamp = 200 * BLOCK_TIME;
+
+        /// Returns the recipient of the `n`-th test project, clear of the default
+        /// accounts.
+        fn recipient(n: u8) -> AccountId {
+            AccountId::from([0x80 + n; 32])
+        }
+
+        /// Calls `message` as `caller` with `value` attached.
+        fn paying<R>(
+            caller: AccountId,
+            value: Balance,
+            message: impl FnOnce() -> Result<R>,
+        ) -> Result<R> {
+            ink_env::test::set_value_transferred::<Env>(value);
+            let result = fixtures::with_caller(caller, message);
+            ink_env::test::set_value_transferred::<Env>(0);
+            result
+        }
+
+        /// A round with `projects` projects and `pool` in the matching pool.
+        fn round(projects: u8, pool: Balance) -> QuadraticFunding {
+            let mut round = QuadraticFunding::new(END);
+            for n in 0..projects {
+                assert_eq!(round.add_project(recipient(n)), Ok(n as ProjectId));
+            }
+            if pool > 0 {
+                let sponsor = fixtures::accounts().eve;
+                assert_eq!(paying(sponsor, pool, || round.fund_matching()), Ok(()));
+            }
+            round
+        }
+
+        fn contribute(
+            round: &mut QuadraticFunding,
+            contributor: AccountId,
+            project_id: ProjectId,
+            amount: Balance,
+        ) -> Result<()> {
+            paying(contributor, amount, || round.contribute(project_id))
+        }
+
+        #[test]
+        fn ideal_match_vectors() {
+            // Four contributors giving 1 each: (1 + 1 + 1 + 1)^2 - 4.
+            assert_eq!(ideal_match(4, 4), Ok(12));
+            // One contributor giving 4: 2^2 - 4.
+            assert_eq!(ideal_match(2, 4), Ok(0));
+            // Contributors giving 9 and 16: (3 + 4)^2 - 25.
+            assert_eq!(ideal_match(7, 25), Ok(24));
+            // A hundred contributors giving 1 each, against one giving 100.
+            assert_eq!(ideal_match(100, 100), Ok(9_900));
+            assert_eq!(ideal_match(10, 100), Ok(0));
+            // Rounded-down roots never make the match negative: 2 and 3 give
+            // isqrt(2) + isqrt(3) = 2, and 2^2 < 5.
+            assert_eq!(ideal_match(2, 5), Ok(0));
+            assert_eq!(ideal_match(Balance::MAX, 0), Err(Error::Overflow));
+        }
+
+        #[test]
+        fn matches_are_scaled_to_the_pool() {
+            assert_eq!(constrained_matches(&[12, 0, 24], 18), Ok(vec![6, 0, 12]));
+            assert_eq!(constrained_matches(&[12, 0, 24], 36), Ok(vec![12, 0, 24]));
+            assert_eq!(constrained_matches(&[12, 0, 24], 100), Ok(vec![12, 0, 24]));
+            // 10 * 7 / 30 and 20 * 7 / 30, rounded down, leave 1 of 7 unmatched.
+            assert_eq!(constrained_matches(&[10, 20], 7), Ok(vec![2, 4]));
+            assert_eq!(constrained_matches(&[], 7), Ok(vec![]));
+        }
+
+        #[ink::test]
+        fn contributions_track_square_roots() {
+            let accounts = fixtures::accounts();
+            let mut round = round(1, 0);
+            assert_eq!(contribute(&mut round, accounts.bob, 0, 4), Ok(()));
+            assert_eq!(contribute(&mut round, accounts.charlie, 0, 9), Ok(()));
+            assert_eq!(round.project(0).map(|p| p.sum_sqrt), Some(5));
+            // Bob's second contribution replaces sqrt(4) with sqrt(4 + 12).
+            assert_eq!(contribute(&mut round, accounts.bob, 0, 12), Ok(()));
+            let project = round.project(0).expect("project exists");
+            assert_eq!(project.sum_sqrt, 7);
+            assert_eq!(project.raised, 25);
+            assert_eq!(round.contribution_of(0, accounts.bob), 16);
+        }
+
+        #[ink::test]
+        fn broad_support_wins_the_pool() {
+            let accounts = fixtures::accounts();
+            let mut round = round(3, 18);
+            for contributor in [accounts.bob, accounts.charlie, accounts.dave, accounts.eve] {
+                assert_eq!(contribute(&mut round, contributor, 0, 1), Ok(()));
+            }
+            assert_eq!(contribute(&mut round, accounts.bob, 1, 4), Ok(()));
+            assert_eq!(contribute(&mut round, accounts.charlie, 2, 9), Ok(()));
+            assert_eq!(contribute(&mut round, accounts.dave, 2, 16), Ok(()));
+
+            fixtures::set_block_timestamp(END + BLOCK_TIME);
+            assert_eq!(round.finalize(), Ok(()));
+            let matched: Vec<Balance> = (0..3)
+                .map(|id| round.project(id).expect("project exists").matched)
+                .collect();
+            assert_eq!(matched, vec![6, 0, 12]);
+            assert_eq!(round.withdraw_unmatched(), Err(Error::NothingToWithdraw));
+        }
+
+        #[ink::test]
+        fn projects_are_paid_once() {
+            let accounts = fixtures::accounts();
+            let mut round = round(1, 100);
+            assert_eq!(contribute(&mut round, accounts.bob, 0, 1), Ok(()));
+            assert_eq!(contribute(&mut round, accounts.charlie, 0, 1), Ok(()));
+            assert_eq!(round.pay_project(0), Err(Error::NotFinalized));
+
+            fixtures::set_block_timestamp(END + BLOCK_TIME);
+            assert_eq!(round.finalize(), Ok(()));
+            fixtures::fund_contract(102);
+            let before = fixtures::balance_of(recipient(0));
+            assert_eq!(round.pay_project(0), Ok(()));
+            // 2 raised, and a match of (1 + 1)^2 - 2.
+            assert_eq!(fixtures::balance_of(recipient(0)) - before, 4);
+            assert_eq!(round.pay_project(0), Err(Error::AlreadyPaid));
+
+            let before = fixtures::balance_of(accounts.alice);
+            assert_eq!(round.withdraw_unmatched(), Ok(()));
+            assert_eq!(fixtures::balance_of(accounts.alice) - before, 98);
+        }
+
+        #[ink::test]
+        fn round_closes_at_end() {
+            let accounts = fixtures::accounts();
+            let mut round = round(1, 0);
+            assert_eq!(round.finalize(), Err(Error::RoundOpen));
+            assert_eq!(
+                contribute(&mut round, accounts.bob, 1, 5),
+                Err(Error::UnknownProject)
+            );
+            assert_eq!(
+                contribute(&mut round, accounts.bob, 0, 0),
+                Err(Error::ZeroAmount)
+            );
+
+            // The end itself still belongs to the round.
+            fixtures::set_block_timestamp(END);
+            assert_eq!(contribute(&mut round, accounts.bob, 0, 5), Ok(()));
+            assert_eq!(round.finalize(), Err(Error::RoundOpen));
+
+            fixtures::set_block_timestamp(END + BLOCK_TIME);
+            assert_eq!(
+                contribute(&mut round, accounts.bob, 0, 5),
+                Err(Error::RoundClosed)
+            );
+            assert_eq!(round.add_project(recipient(1)), Err(Error::RoundClosed));
+            assert_eq!(round.finalize(), Ok(()));
+            assert_eq!(round.finalize(), Err(Error::AlreadyFinalized));
+            assert_eq!(
+                paying(accounts.eve, 5, || round.fund_matching()),
+                Err(Error::AlreadyFinalized)
+            );
+        }
+
+        #[ink::test]
+        fn only_admin_lists_projects() {
+            let accounts = fixtures::accounts();
+            let mut round = round(0, 0);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(round.add_project(recipient(0)), Err(Error::NotAdmin));
+            fixtures::set_caller(accounts.alice);
+            for n in 0..MAX_PROJECTS {
+                assert_eq!(round.add_project(recipient(0)), Ok(n));
+            }
+            assert_eq!(round.add_project(recipient(0)), Err(Error::TooManyProjects));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract (it depends on `crates/utils` for `isqrt`):
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Upload the generated .contract file and deploy it with the end of the round
+//
+// 4. Interact with the contract:
+//    - As admin, use "addProject" for each project
+//    - Sponsors use "fundMatching" with a value attached
+//    - Contributors use "contribute" with a value attached until the round ends
+//    - Anyone then uses "finalize" and "payProject"; the admin uses
+//      "withdrawUnmatched"
@@ -0,0 +1,466 @@
+// examples/demo-contracts/rebase_token.rs
+//
+// A rebasing token contract example for Polkadot using ink!
+// This contract extends the ERC-20 example so that the owner can change the total
+// supply at any time ("rebase"), with every holder's balance scaling by the same
+// factor: after a rebase from 100 to 200, a holder of 30 holds 60.
+//
+// Rewriting every balance on each rebase would cost gas proportional to the number
+// of holders. Instead balances are kept in *shares*, a fixed internal unit, and the
+// visible amounts ("fragments") are derived from them:
+//
+//     balance_of(account) = shares_of(account) / shares_per_fragment
+//     shares_per_fragment = TOTAL_SHARES / total_supply
+//
+// A rebase only changes `total_supply`, and with it `shares_per_fragment`. Transfers
+// convert the fragment amount into shares and move those, so they stay correct at
+// any supply. `TOTAL_SHARES` is the largest multiple of the initial supply that
+// fits in a `u128`, which makes the initial conversion exact and leaves plenty of
+// shares per fragment for precision later.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod rebase_token {
+    use astranet_utils::validation::{self, MAX_DECIMALS};
+    use ink_prelude::string::String;
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// Fewest shares a fragment may be worth. Bounds how far the supply can be
+    /// rebased up while balances stay precise.
+    pub const MIN_SHARES_PER_FRAGMENT: u128 = 1_000_000;
+
+    /// The rebase token error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the balance is insufficient for the operation.
+        InsufficientBalance,
+        /// Returned if the allowance is insufficient for the operation.
+        InsufficientAllowance,
+        /// Returned if the caller is not the token owner.
+        NotOwner,
+        /// Returned if a rebase targets zero, or a supply so large that a fragment
+        /// would be worth fewer than `MIN_SHARES_PER_FRAGMENT` shares.
+        InvalidSupply,
+    }
+
+    /// The rebase token result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    // Events are mirrored in `crates/events/src/rebase_token.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when a token transfer occurs.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    /// Event emitted when an approval occurs.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when the owner rebases the supply.
+    #[ink(event)]
+    pub struct Rebased {
+        #[ink(topic)]
+        epoch: u64,
+        total_supply: Balance,
+    }
+
+    /// The rebase token storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct RebaseToken {
+        /// Total token supply, in fragments.
+        total_supply: Balance,
+        /// Total shares; never changes after construction.
+        total_shares: u128,
+        /// Shares one fragment is worth at the current supply.
+        shares_per_fragment: u128,
+        /// Mapping from owner to balance, in shares.
+        share_balances: Mapping<AccountId, u128>,
+        /// Mapping from owner to spender to allowance, in fragments.
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+        /// Token name.
+        name: String,
+        /// Token symbol.
+        symbol: String,
+        /// Token decimals.
+        decimals: u8,
+        /// Account allowed to rebase.
+        owner: AccountId,
+        /// Number of rebases so far.
+        epoch: u64,
+    }
+
+    impl RebaseToken {
+        /// Creates a new rebase token with the specified initial supply, owned by the
+        /// caller. Panics if `initial_supply` is zero or so large that a fragment
+        /// would be worth fewer than `MIN_SHARES_PER_FRAGMENT` shares, `symbol` is
+        /// empty or `decimals` is above `MAX_DECIMALS`.
+        #[ink(constructor)]
+        pub fn new(
+            initial_supply: Balance,
+            name: String,
+            symbol: String,
+            decimals: u8,
+        ) -> Self {
+            validation::require("initial supply", validation::non_zero(initial_supply));
+            // The same bound `rebase` enforces: `total_shares / initial_supply` is
+            // `u128::MAX / initial_supply`, rounded down.
+            validation::require(
+                "initial supply",
+                validation::at_most(initial_supply, u128::MAX / MIN_SHARES_PER_FRAGMENT),
+            );
+            validation::require("token symbol", validation::non_empty(&symbol));
+            validation::require("decimals", validation::at_most(decimals, MAX_DECIMALS));
+            let total_shares = u128::MAX - u128::MAX % initial_supply;
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                let caller = Self::env().caller();
+                contract.share_balances.insert(caller, &total_shares);
+                contract.total_supply = initial_supply;
+                contract.total_shares = total_shares;
+                contract.shares_per_fragment = total_shares / initial_supply;
+                contract.name = name;
+                contract.symbol = symbol;
+                contract.decimals = decimals;
+                contract.owner = caller;
+
+                Self::env().emit_event(Transfer {
+                    from: None,
+                    to: Some(caller),
+                    value: initial_supply,
+                });
+            })
+        }
+
+        /// Returns the token name.
+        #[ink(message)]
+        pub fn name(&self) -> String {
+            self.name.clone()
+        }
+
+        /// Returns the token symbol.
+        #[ink(message)]
+        pub fn symbol(&self) -> String {
+            self.symbol.clone()
+        }
+
+        /// Returns the token decimals.
+        #[ink(message)]
+        pub fn decimals(&self) -> u8 {
+            self.decimals
+        }
+
+        /// Returns the total token supply.
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// Returns the account balance for the specified `owner`, at the current
+        /// supply.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.shares_of(owner) / self.shares_per_fragment
+        }
+
+        /// Returns the shares held by `owner`. Unlike the balance, they only change
+        /// on transfers.
+        #[ink(message)]
+        pub fn shares_of(&self, owner: AccountId) -> u128 {
+            self.share_balances.get(owner).unwrap_or(0)
+        }
+
+        /// Returns the amount which `spender` is allowed to withdraw from `owner`.
+        /// Allowances are in fragments and do not scale with rebases.
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or(0)
+        }
+
+        /// Returns the token owner.
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Returns the number of rebases so far.
+        #[ink(message)]
+        pub fn epoch(&self) -> u64 {
+            self.epoch
+        }
+
+        /// Sets the total supply to `new_supply`, scaling every balance by
+        /// `new_supply / total_supply`. Only the owner can call this.
+        #[ink(message)]
+        pub fn rebase(&mut self, new_supply: Balance) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            if new_supply == 0 || self.total_shares / new_supply < MIN_SHARES_PER_FRAGMENT {
+                return Err(Error::InvalidSupply);
+            }
+            self.total_supply = new_supply;
+            self.shares_per_fragment = self.total_shares / new_supply;
+            self.epoch += 1;
+            self.env().emit_event(Rebased {
+                epoch: self.epoch,
+                total_supply: new_supply,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            self.transfer_from_to(from, to, value)
+        }
+
+        /// Allows `spender` to withdraw from the caller's account multiple times, up to
+        /// the `value` amount.
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            self.allowances.insert((owner, spender), &value);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` tokens on behalf of `from` to the account `to`.
+        #[ink(message)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let caller = self.env().caller();
+            let allowance = self.allowance(from, caller);
+            if allowance < value {
+                return Err(Error::InsufficientAllowance);
+            }
+            self.transfer_from_to(from, to, value)?;
+            self.allowances.insert((from, caller), &(allowance - value));
+            Ok(())
+        }
+
+        /// Transfers `value` fragments from the `from` account to the `to` account by
+        /// moving the shares they are worth at the current supply.
+        fn transfer_from_to(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            if self.balance_of(from) < value {
+                return Err(Error::InsufficientBalance);
+            }
+            // Cannot overflow: `value <= total_supply`, and `total_supply` fragments
+            // are worth at most `total_shares`.
+            let shares = value * self.shares_per_fragment;
+
+            self.share_balances
+                .insert(from, &(self.shares_of(from) - shares));
+            self.share_balances.insert(to, &(self.shares_of(to) + shares));
+
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                value,
+            });
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        fn new_token(supply: Balance) -> RebaseToken {
+            RebaseToken::new(supply, String::from("Elastic"), String::from("ELA"), 12)
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "token symbol must not be empty")]
+        fn new_rejects_an_empty_symbol() {
+            RebaseToken::new(100, String::from("Elastic"), String::new(), 12);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "decimals is too large")]
+        fn new_rejects_too_many_decimals() {
+            RebaseToken::new(
+                100,
+                String::from("Elastic"),
+                String::from("ELA"),
+                MAX_DECIMALS + 1,
+            );
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "initial supply must not be zero")]
+        fn new_rejects_a_zero_supply() {
+            new_token(0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "initial supply is too large")]
+        fn new_rejects_a_supply_with_too_few_shares_per_fragment() {
+            new_token(u128::MAX / MIN_SHARES_PER_FRAGMENT + 1);
+        }
+
+        #[ink::test]
+        fn new_accepts_the_largest_supply() {
+            let token = new_token(u128::MAX / MIN_SHARES_PER_FRAGMENT);
+            assert!(token.total_shares / token.total_supply() >= MIN_SHARES_PER_FRAGMENT);
+        }
+
+        #[ink::test]
+        fn initial_shares_convert_exactly() {
+            let accounts = fixtures::accounts();
+            let token = new_token(1_000);
+            assert_eq!(token.balance_of(accounts.alice), 1_000);
+            assert_eq!(token.shares_of(accounts.alice) % 1_000, 0);
+            assert_eq!(token.epoch(), 0);
+        }
+
+        #[ink::test]
+        fn rebase_scales_every_balance() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token(100);
+            assert_eq!(token.transfer(accounts.bob, 40), Ok(()));
+
+            assert_eq!(token.rebase(200), Ok(()));
+            assert_eq!(token.total_supply(), 200);
+            assert_eq!(token.balance_of(accounts.alice), 120);
+            assert_eq!(token.balance_of(accounts.bob), 80);
+
+            assert_eq!(token.rebase(50), Ok(()));
+            assert_eq!(token.balance_of(accounts.alice), 30);
+            assert_eq!(token.balance_of(accounts.bob), 20);
+            assert_eq!(token.epoch(), 2);
+        }
+
+        #[ink::test]
+        fn transfers_stay_correct_across_rebases() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token(100);
+            assert_eq!(token.transfer(accounts.bob, 40), Ok(()));
+            assert_eq!(token.rebase(200), Ok(()));
+
+            // Bob now holds 80 and sends 30 at the new supply.
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(token.transfer(accounts.charlie, 30), Ok(()));
+            assert_eq!(token.balance_of(accounts.bob), 50);
+            assert_eq!(token.balance_of(accounts.charlie), 30);
+            assert_eq!(
+                token.transfer(accounts.charlie, 51),
+                Err(Error::InsufficientBalance)
+            );
+
+            // Shrinking back halves everyone, including the new holder.
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(token.rebase(100), Ok(()));
+            assert_eq!(token.balance_of(accounts.alice), 60);
+            assert_eq!(token.balance_of(accounts.bob), 25);
+            assert_eq!(token.balance_of(accounts.charlie), 15);
+        }
+
+        #[ink::test]
+        fn uneven_rebase_never_exceeds_supply() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token(3);
+            assert_eq!(token.transfer(accounts.bob, 1), Ok(()));
+            assert_eq!(token.transfer(accounts.charlie, 1), Ok(()));
+
+            assert_eq!(token.rebase(10), Ok(()));
+            let holders = [accounts.alice, accounts.bob, accounts.charlie];
+            let total: Balance = holders.iter().map(|h| token.balance_of(*h)).sum();
+            assert!(total <= token.total_supply());
+            for holder in holders {
+                assert_eq!(token.balance_of(holder), 3);
+            }
+
+            // Sending a whole visible balance leaves no visible dust behind.
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(token.transfer(accounts.charlie, 3), Ok(()));
+            assert_eq!(token.balance_of(accounts.bob), 0);
+            assert_eq!(token.balance_of(accounts.charlie), 6);
+        }
+
+        #[ink::test]
+        fn allowances_do_not_scale() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token(100);
+            assert_eq!(token.approve(accounts.bob, 10), Ok(()));
+            assert_eq!(token.rebase(1_000), Ok(()));
+            assert_eq!(token.allowance(accounts.alice, accounts.bob), 10);
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                token.transfer_from(accounts.alice, accounts.bob, 10),
+                Ok(())
+            );
+            assert_eq!(token.balance_of(accounts.bob), 10);
+            assert_eq!(token.balance_of(accounts.alice), 990);
+        }
+
+        #[ink::test]
+        fn only_owner_rebases_to_a_valid_supply() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token(100);
+            assert_eq!(token.rebase(0), Err(Error::InvalidSupply));
+            assert_eq!(token.rebase(u128::MAX), Err(Error::InvalidSupply));
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(token.rebase(200), Err(Error::NotOwner));
+            assert_eq!(token.total_supply(), 100);
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy using the Polkadot JS Apps UI:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your desired network
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Set the initial supply, name, symbol, and decimals
+//    - Deploy the contract
+//
+// 4. Interact with the contract:
+//    - Use the "transfer" method to send tokens
+//    - As the owner, use "rebase" with a new total supply
+//    - Use "balanceOf" before and after to watch balances scale, and "sharesOf" to
+//      see that the underlying shares did not move
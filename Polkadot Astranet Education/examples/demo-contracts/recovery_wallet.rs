@@ -0,0 +1,574 @@
+// examples/demo-contracts/recovery_wallet.rs
+//
+// A social recovery wallet contract example for Polkadot using ink!
+// The wallet holds native tokens for a single owner key. If the key is lost, a set
+// of guardians (friends, other devices, a custodian) chosen by the owner can vote
+// to rotate ownership to a new key, without ever holding the funds themselves.
+//
+// Recovery
+// --------
+// Any guardian proposes a new owner by approving it; the other guardians approve
+// the same new owner. Once `threshold` guardians agree, a delay starts, and when it
+// has passed anyone can finalize the rotation. During the delay the current owner
+// can still cancel the recovery, which protects them from guardians colluding
+// against an owner who has not lost their key. Every recovery gets a new round
+// number, so approvals given to a canceled or finished recovery never count again.
+//
+// The delay cuts both ways: someone who stole the owner key can also cancel every
+// recovery. Production wallets such as Argent therefore also let guardians lock
+// the wallet; that is left out here to keep the flow short.
+//
+// Guardian sets
+// -------------
+// The owner adds and removes guardians and sets the threshold, but not while a
+// recovery is pending, so a stolen key cannot swap the guardians under a running
+// vote. The threshold is always between one and the number of guardians.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod recovery_wallet {
+    use ink_prelude::vec::Vec;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Numbers recoveries, so approvals of an earlier recovery are ignored.
+    pub type Round = u32;
+
+    /// The largest number of guardians.
+    pub const MAX_GUARDIANS: u32 = 8;
+
+    /// The recovery wallet error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the owner.
+        NotOwner,
+        /// Returned if the caller or account is not a guardian.
+        NotGuardian,
+        /// Returned if the account is already a guardian.
+        AlreadyGuardian,
+        /// Returned if the wallet already has `MAX_GUARDIANS` guardians.
+        TooManyGuardians,
+        /// Returned if the threshold would be zero or above the number of
+        /// guardians.
+        InvalidThreshold,
+        /// Returned if the guardian set cannot change while a recovery is pending.
+        RecoveryPending,
+        /// Returned if a recovery to another new owner is pending.
+        DifferentRecovery,
+        /// Returned if the guardian already approved the pending recovery.
+        AlreadyApproved,
+        /// Returned if no recovery is pending.
+        NoRecovery,
+        /// Returned if the recovery lacks approvals or its delay has not passed.
+        NotReady,
+        /// Returned if the wallet holds less than the amount.
+        InsufficientBalance,
+        /// Returned if the transfer failed.
+        TransferFailed,
+    }
+
+    /// The recovery wallet result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A pending recovery.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Recovery {
+        /// Account that becomes the owner.
+        pub new_owner: AccountId,
+        /// Guardians who approved it.
+        pub approvals: u32,
+        /// Time from which it can be finalized, once enough guardians approved.
+        pub ready_at: Option<Timestamp>,
+    }
+
+    // Events are mirrored in `crates/events/src/recovery_wallet.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when the owner sends tokens from the wallet.
+    #[ink(event)]
+    pub struct Sent {
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when the owner adds a guardian.
+    #[ink(event)]
+    pub struct GuardianAdded {
+        #[ink(topic)]
+        guardian: AccountId,
+    }
+
+    /// Event emitted when the owner removes a guardian.
+    #[ink(event)]
+    pub struct GuardianRemoved {
+        #[ink(topic)]
+        guardian: AccountId,
+    }
+
+    /// Event emitted when the owner changes the threshold.
+    #[ink(event)]
+    pub struct ThresholdChanged {
+        threshold: u32,
+    }
+
+    /// Event emitted when a guardian approves a recovery.
+    #[ink(event)]
+    pub struct RecoveryApproved {
+        #[ink(topic)]
+        round: Round,
+        #[ink(topic)]
+        guardian: AccountId,
+        new_owner: AccountId,
+        approvals: u32,
+    }
+
+    /// Event emitted when the owner cancels a recovery.
+    #[ink(event)]
+    pub struct RecoveryCanceled {
+        #[ink(topic)]
+        round: Round,
+    }
+
+    /// Event emitted when a recovery is finalized and the owner key rotated.
+    #[ink(event)]
+    pub struct OwnerRotated {
+        #[ink(topic)]
+        round: Round,
+        old_owner: AccountId,
+        new_owner: AccountId,
+    }
+
+    /// The recovery wallet storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct RecoveryWallet {
+        /// Account that controls the wallet.
+        owner: AccountId,
+        /// Accounts that can vote to rotate the owner.
+        guardians: Vec<AccountId>,
+        /// Guardian approvals a recovery needs.
+        threshold: u32,
+        /// Time between enough approvals and the rotation, in milliseconds.
+        delay: Timestamp,
+        /// The pending recovery, if any.
+        recovery: Option<Recovery>,
+        /// Round of the pending or next recovery.
+        round: Round,
+        /// Set of (round, guardian) pairs where the guardian approved.
+        approvals: Mapping<(Round, AccountId), ()>,
+    }
+
+    impl RecoveryWallet {
+        /// Creates a wallet owned by the caller, recoverable by `threshold` of
+        /// `guardians` after `delay` milliseconds.
+        #[ink(constructor)]
+        pub fn new(guardians: Vec<AccountId>, threshold: u32, delay: Timestamp) -> Self {
+            assert!(
+                guardians.len() as u32 <= MAX_GUARDIANS,
+                "too many guardians for the wallet"
+            );
+            assert!(
+                threshold > 0 && threshold as usize <= guardians.len(),
+                "threshold must be between one and the number of guardians"
+            );
+            assert!(
+                guardians
+                    .iter()
+                    .enumerate()
+                    .all(|(i, guardian)| !guardians[i + 1..].contains(guardian)),
+                "guardians must be distinct"
+            );
+            ink_lang::utils::initialize_contract(|wallet: &mut Self| {
+                wallet.owner = Self::env().caller();
+                wallet.guardians = guardians;
+                wallet.threshold = threshold;
+                wallet.delay = delay;
+            })
+        }
+
+        /// Returns the owner.
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Returns the guardians.
+        #[ink(message)]
+        pub fn guardians(&self) -> Vec<AccountId> {
+            self.guardians.clone()
+        }
+
+        /// Returns the number of approvals a recovery needs.
+        #[ink(message)]
+        pub fn threshold(&self) -> u32 {
+            self.threshold
+        }
+
+        /// Returns the pending recovery, if any.
+        #[ink(message)]
+        pub fn recovery(&self) -> Option<Recovery> {
+            self.recovery
+        }
+
+        /// Returns whether `guardian` approved the pending recovery.
+        #[ink(message)]
+        pub fn has_approved(&self, guardian: AccountId) -> bool {
+            self.approvals.contains((self.round, guardian))
+        }
+
+        /// Accepts the transferred value into the wallet.
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) {}
+
+        /// Sends `amount` from the wallet to `to`. Only the owner can call this.
+        #[ink(message)]
+        pub fn send(&mut self, to: AccountId, amount: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            if self.env().balance() < amount {
+                return Err(Error::InsufficientBalance);
+            }
+            self.env()
+                .transfer(to, amount)
+                .map_err(|_| Error::TransferFailed)?;
+            self.env().emit_event(Sent { to, amount });
+            Ok(())
+        }
+
+        /// Adds `guardian`. Only the owner can call this, while no recovery is
+        /// pending.
+        #[ink(message)]
+        pub fn add_guardian(&mut self, guardian: AccountId) -> Result<()> {
+            self.ensure_owner_between_recoveries()?;
+            if self.guardians.contains(&guardian) {
+                return Err(Error::AlreadyGuardian);
+            }
+            if self.guardians.len() as u32 >= MAX_GUARDIANS {
+                return Err(Error::TooManyGuardians);
+            }
+            self.guardians.push(guardian);
+            self.env().emit_event(GuardianAdded { guardian });
+            Ok(())
+        }
+
+        /// Removes `guardian`, as long as enough guardians remain to reach the
+        /// threshold. Only the owner can call this, while no recovery is pending.
+        #[ink(message)]
+        pub fn remove_guardian(&mut self, guardian: AccountId) -> Result<()> {
+            self.ensure_owner_between_recoveries()?;
+            let index = self
+                .guardians
+                .iter()
+                .position(|known| *known == guardian)
+                .ok_or(Error::NotGuardian)?;
+            if (self.guardians.len() as u32) <= self.threshold {
+                return Err(Error::InvalidThreshold);
+            }
+            self.guardians.swap_remove(index);
+            self.env().emit_event(GuardianRemoved { guardian });
+            Ok(())
+        }
+
+        /// Sets the number of approvals a recovery needs. Only the owner can call
+        /// this, while no recovery is pending.
+        #[ink(message)]
+        pub fn set_threshold(&mut self, threshold: u32) -> Result<()> {
+            self.ensure_owner_between_recoveries()?;
+            if threshold == 0 || threshold as usize > self.guardians.len() {
+                return Err(Error::InvalidThreshold);
+            }
+            self.threshold = threshold;
+            self.env().emit_event(ThresholdChanged { threshold });
+            Ok(())
+        }
+
+        /// Approves rotating the owner to `new_owner`, starting a recovery if none
+        /// is pending. The approval that reaches the threshold starts the delay.
+        /// Only guardians can call this.
+        #[ink(message)]
+        pub fn approve_recovery(&mut self, new_owner: AccountId) -> Result<()> {
+            let guardian = self.env().caller();
+            if !self.guardians.contains(&guardian) {
+                return Err(Error::NotGuardian);
+            }
+            let mut recovery = match self.recovery {
+                Some(recovery) if recovery.new_owner != new_owner => {
+                    return Err(Error::DifferentRecovery)
+                }
+                Some(recovery) => recovery,
+                None => Recovery {
+                    new_owner,
+                    approvals: 0,
+                    ready_at: None,
+                },
+            };
+            if self.has_approved(guardian) {
+                return Err(Error::AlreadyApproved);
+            }
+            self.approvals.insert((self.round, guardian), &());
+            recovery.approvals += 1;
+            if recovery.approvals == self.threshold {
+                recovery.ready_at = Some(self.env().block_timestamp().saturating_add(self.delay));
+            }
+            self.recovery = Some(recovery);
+            self.env().emit_event(RecoveryApproved {
+                round: self.round,
+                guardian,
+                new_owner,
+                approvals: recovery.approvals,
+            });
+            Ok(())
+        }
+
+        /// Cancels the pending recovery. Only the owner can call this.
+        #[ink(message)]
+        pub fn cancel_recovery(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            if self.recovery.is_none() {
+                return Err(Error::NoRecovery);
+            }
+            let round = self.end_recovery();
+            self.env().emit_event(RecoveryCanceled { round });
+            Ok(())
+        }
+
+        /// Rotates the owner to the new owner of the pending recovery, once enough
+        /// guardians approved it and the delay has passed. Anyone can call this.
+        #[ink(message)]
+        pub fn finalize_recovery(&mut self) -> Result<()> {
+            let recovery = self.recovery.ok_or(Error::NoRecovery)?;
+            match recovery.ready_at {
+                Some(ready_at) if self.env().block_timestamp() >= ready_at => {}
+                _ => return Err(Error::NotReady),
+            }
+            let old_owner = self.owner;
+            self.owner = recovery.new_owner;
+            let round = self.end_recovery();
+            self.env().emit_event(OwnerRotated {
+                round,
+                old_owner,
+                new_owner: recovery.new_owner,
+            });
+            Ok(())
+        }
+
+        /// Clears the pending recovery and moves on to the next round, which
+        /// makes its approvals stale. Returns the round that ended.
+        fn end_recovery(&mut self) -> Round {
+            let round = self.round;
+            self.recovery = None;
+            self.round += 1;
+            round
+        }
+
+        /// Returns `NotOwner` unless the caller is the owner.
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+
+        /// Returns `NotOwner` unless the caller is the owner, and
+        /// `RecoveryPending` while a recovery is pending.
+        fn ensure_owner_between_recoveries(&self) -> Result<()> {
+            self.ensure_owner()?;
+            if self.recovery.is_some() {
+                return Err(Error::RecoveryPending);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use fixtures::{set_block_timestamp as set_time, BLOCK_TIME};
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        const START: Timestamp = 20 * BLOCK_TIME;
+        const DELAY: Timestamp = 200 * BLOCK_TIME;
+
+        /// A wallet owned by alice, recoverable by two of bob, charlie and dave.
+        fn wallet() -> RecoveryWallet {
+            let accounts = fixtures::accounts();
+            ink_env::test::set_callee::<Env>(AccountId::from([0x60; 32]));
+            RecoveryWallet::new(
+                vec![accounts.bob, accounts.charlie, accounts.dave],
+                2,
+                DELAY,
+            )
+        }
+
+        fn approve(
+            wallet: &mut RecoveryWallet,
+            guardian: AccountId,
+            new_owner: AccountId,
+        ) -> Result<()> {
+            fixtures::with_caller(guardian, || wallet.approve_recovery(new_owner))
+        }
+
+        #[ink::test]
+        fn guardians_rotate_the_owner_after_the_delay() {
+            let accounts = fixtures::accounts();
+            let mut wallet = wallet();
+            set_time(START);
+            assert_eq!(approve(&mut wallet, accounts.bob, accounts.eve), Ok(()));
+            assert_eq!(wallet.recovery().and_then(|r| r.ready_at), None);
+            assert_eq!(wallet.finalize_recovery(), Err(Error::NotReady));
+
+            assert_eq!(approve(&mut wallet, accounts.charlie, accounts.eve), Ok(()));
+            assert_eq!(
+                wallet.recovery().and_then(|r| r.ready_at),
+                Some(START + DELAY)
+            );
+            set_time(START + DELAY - BLOCK_TIME);
+            assert_eq!(wallet.finalize_recovery(), Err(Error::NotReady));
+
+            set_time(START + DELAY);
+            assert_eq!(wallet.finalize_recovery(), Ok(()));
+            assert_eq!(wallet.owner(), accounts.eve);
+            assert_eq!(wallet.recovery(), None);
+
+            // The old key has lost control, the new one has it.
+            fixtures::fund_contract(500);
+            assert_eq!(wallet.send(accounts.bob, 100), Err(Error::NotOwner));
+            fixtures::set_caller(accounts.eve);
+            assert_eq!(wallet.send(accounts.bob, 100), Ok(()));
+        }
+
+        #[ink::test]
+        fn owner_cancels_and_approvals_go_stale() {
+            let accounts = fixtures::accounts();
+            let mut wallet = wallet();
+            assert_eq!(approve(&mut wallet, accounts.bob, accounts.eve), Ok(()));
+            assert_eq!(approve(&mut wallet, accounts.charlie, accounts.eve), Ok(()));
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(wallet.cancel_recovery(), Err(Error::NotOwner));
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(wallet.cancel_recovery(), Ok(()));
+            assert_eq!(wallet.cancel_recovery(), Err(Error::NoRecovery));
+            assert_eq!(wallet.finalize_recovery(), Err(Error::NoRecovery));
+
+            // A new recovery starts from zero approvals.
+            assert!(!wallet.has_approved(accounts.bob));
+            assert_eq!(approve(&mut wallet, accounts.bob, accounts.eve), Ok(()));
+            assert_eq!(wallet.recovery().map(|r| r.approvals), Some(1));
+            set_time(DELAY);
+            assert_eq!(wallet.finalize_recovery(), Err(Error::NotReady));
+            assert_eq!(wallet.owner(), accounts.alice);
+        }
+
+        #[ink::test]
+        fn guardians_must_agree_on_the_new_owner() {
+            let accounts = fixtures::accounts();
+            let mut wallet = wallet();
+            assert_eq!(approve(&mut wallet, accounts.bob, accounts.eve), Ok(()));
+            assert_eq!(
+                approve(&mut wallet, accounts.bob, accounts.eve),
+                Err(Error::AlreadyApproved)
+            );
+            assert_eq!(
+                approve(&mut wallet, accounts.charlie, accounts.ferdie),
+                Err(Error::DifferentRecovery)
+            );
+            assert_eq!(
+                approve(&mut wallet, accounts.eve, accounts.eve),
+                Err(Error::NotGuardian)
+            );
+            assert_eq!(wallet.recovery().map(|r| r.approvals), Some(1));
+        }
+
+        #[ink::test]
+        fn guardian_set_is_managed_between_recoveries() {
+            let accounts = fixtures::accounts();
+            let mut wallet = wallet();
+            assert_eq!(wallet.add_guardian(accounts.eve), Ok(()));
+            assert_eq!(
+                wallet.add_guardian(accounts.eve),
+                Err(Error::AlreadyGuardian)
+            );
+            assert_eq!(wallet.set_threshold(4), Ok(()));
+            assert_eq!(wallet.set_threshold(5), Err(Error::InvalidThreshold));
+            assert_eq!(wallet.set_threshold(0), Err(Error::InvalidThreshold));
+            // Removing a guardian would make 4 approvals unreachable.
+            assert_eq!(
+                wallet.remove_guardian(accounts.bob),
+                Err(Error::InvalidThreshold)
+            );
+            assert_eq!(wallet.set_threshold(3), Ok(()));
+            assert_eq!(wallet.remove_guardian(accounts.bob), Ok(()));
+            assert_eq!(
+                wallet.remove_guardian(accounts.bob),
+                Err(Error::NotGuardian)
+            );
+
+            assert_eq!(approve(&mut wallet, accounts.eve, accounts.ferdie), Ok(()));
+            assert_eq!(
+                wallet.remove_guardian(accounts.eve),
+                Err(Error::RecoveryPending)
+            );
+            assert_eq!(wallet.set_threshold(1), Err(Error::RecoveryPending));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(wallet.add_guardian(accounts.bob), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn owner_sends_from_the_wallet() {
+            let accounts = fixtures::accounts();
+            let mut wallet = wallet();
+            fixtures::fund_contract(500);
+            let before = fixtures::balance_of(accounts.bob);
+            assert_eq!(wallet.send(accounts.bob, 200), Ok(()));
+            assert_eq!(fixtures::balance_of(accounts.bob) - before, 200);
+            assert_eq!(
+                wallet.send(accounts.bob, 301),
+                Err(Error::InsufficientBalance)
+            );
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "threshold must be between one and the number of guardians")]
+        fn zero_threshold_panics() {
+            let accounts = fixtures::accounts();
+            RecoveryWallet::new(vec![accounts.bob], 0, DELAY);
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Upload the generated .contract file and deploy it with the guardians, the
+//      threshold and the delay
+//
+// 4. Interact with the contract:
+//    - As owner, use "deposit" and "send", and manage guardians
+//    - If the key is lost, guardians use "approveRecovery" with the new key
+//    - After the delay, anyone uses "finalizeRecovery"; until then the owner can
+//      use "cancelRecovery"
@@ -0,0 +1,479 @@
+// examples/demo-contracts/referral.rs
+//
+// A referral rewards contract example for Polkadot using ink!
+// Learners register who referred them. Once a referred learner enrolls in a course
+// on `enrollment.rs`, their referrer earns an ERC-20 reward, which grows with the
+// number of learners the referrer has brought in.
+//
+// Tiers
+// -----
+// The admin sets a list of tiers, each a minimum referral count and a reward. The
+// reward for a referrer's n-th rewarded referral is that of the last tier whose
+// minimum is at most n; see `reward_for`. Tiers must start at 1 and increase
+// strictly, so every referral falls into exactly one tier.
+//
+// Checks
+// ------
+// - Nobody can refer themselves, or the account that referred them, so two
+//   accounts cannot farm rewards by referring each other.
+// - A learner registers one referrer, once, and earns it one reward.
+// - The enrollment is checked on the enrollment contract with `is_enrolled`, not
+//   taken from the caller, so anyone can trigger the payout without being trusted.
+//
+// The contract pays rewards from its own token balance, which the admin tops up by
+// transferring tokens to it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod referral {
+    use ink_env::call::{build_call, Call, ExecutionInput, Selector};
+    use ink_prelude::vec::Vec;
+    use ink_storage::{
+        traits::{PackedAllocate, PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Identifies a course on the enrollment contract.
+    pub type CourseId = u32;
+
+    /// The largest number of reward tiers.
+    pub const MAX_TIERS: usize = 8;
+
+    /// Selector of the ERC-20 `transfer(to, value)` message.
+    const TRANSFER_SELECTOR: [u8; 4] = [0x84, 0xa1, 0x5d, 0xa1];
+
+    /// Selector of the enrollment `is_enrolled(course_id, learner)` message.
+    const IS_ENROLLED_SELECTOR: [u8; 4] = [0x5f, 0x4a, 0xd3, 0x45];
+
+    /// The referral error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the admin.
+        NotAdmin,
+        /// Returned if the tiers are empty, too many, do not start at 1 or do not
+        /// increase strictly.
+        InvalidTiers,
+        /// Returned if the caller names themselves as referrer.
+        SelfReferral,
+        /// Returned if the referrer was referred by the caller.
+        CircularReferral,
+        /// Returned if the caller already registered a referrer.
+        AlreadyReferred,
+        /// Returned if the learner has no referrer.
+        NotReferred,
+        /// Returned if the referral was already rewarded.
+        AlreadyRewarded,
+        /// Returned if the learner is not enrolled in the course.
+        NotEnrolled,
+        /// Returned if the enrollment contract could not be asked.
+        EnrollmentUnavailable,
+        /// Returned if the token refused to pay the reward.
+        TransferFailed,
+    }
+
+    /// The referral result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Stands in for the token's error type, which encodes as a bare variant index;
+    /// all this contract needs is to tell success from failure.
+    #[derive(scale::Decode)]
+    struct CalleeError(#[allow(dead_code)] u8);
+
+    /// A reward tier.
+    #[derive(
+        Debug,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        scale::Encode,
+        scale::Decode,
+        SpreadLayout,
+        PackedLayout,
+        SpreadAllocate,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Tier {
+        /// Number of rewarded referrals, counting the one being paid, from which the
+        /// tier applies.
+        pub min_referrals: u32,
+        /// Tokens paid per referral in the tier.
+        pub reward: Balance,
+    }
+
+    // `initialize_contract` needs this to set up the empty `tiers` list. A tier is
+    // plain data with no storage cells of its own, and ink! has no derive for it.
+    impl PackedAllocate for Tier {
+        fn allocate_packed(&mut self, _at: &ink_primitives::Key) {}
+    }
+
+    /// Returns the reward of the `n`-th referral under `tiers`, or 0 if no tier
+    /// applies.
+    pub fn tier_reward(tiers: &[Tier], n: u32) -> Balance {
+        tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_referrals <= n)
+            .map(|tier| tier.reward)
+            .unwrap_or(0)
+    }
+
+    // Events are mirrored in `crates/events/src/referral.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when the admin replaces the reward tiers.
+    #[ink(event)]
+    pub struct TiersSet {
+        tiers: Vec<Tier>,
+    }
+
+    /// Event emitted when a learner registers their referrer.
+    #[ink(event)]
+    pub struct Referred {
+        #[ink(topic)]
+        referee: AccountId,
+        #[ink(topic)]
+        referrer: AccountId,
+    }
+
+    /// Event emitted when a referrer is rewarded for a learner's enrollment.
+    #[ink(event)]
+    pub struct RewardPaid {
+        #[ink(topic)]
+        referrer: AccountId,
+        #[ink(topic)]
+        referee: AccountId,
+        course_id: CourseId,
+        amount: Balance,
+    }
+
+    /// The referral storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Referral {
+        /// Account that sets the tiers.
+        admin: AccountId,
+        /// The enrollment contract checked for enrollments.
+        enrollment: AccountId,
+        /// The ERC-20 token rewards are paid in.
+        token: AccountId,
+        /// The reward tiers, by increasing minimum.
+        tiers: Vec<Tier>,
+        /// Mapping from learner to the account that referred them.
+        referrers: Mapping<AccountId, AccountId>,
+        /// Mapping from learner to whether their referral was rewarded.
+        rewarded: Mapping<AccountId, bool>,
+        /// Mapping from referrer to their number of rewarded referrals.
+        referral_counts: Mapping<AccountId, u32>,
+    }
+
+    impl Referral {
+        /// Creates a contract without tiers checking `enrollment` and paying in
+        /// `token`, with the caller as admin.
+        #[ink(constructor)]
+        pub fn new(enrollment: AccountId, token: AccountId) -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.admin = Self::env().caller();
+                contract.enrollment = enrollment;
+                contract.token = token;
+            })
+        }
+
+        /// Returns the reward tiers.
+        #[ink(message)]
+        pub fn tiers(&self) -> Vec<Tier> {
+            self.tiers.clone()
+        }
+
+        /// Returns the account that referred `referee`, if any.
+        #[ink(message)]
+        pub fn referrer_of(&self, referee: AccountId) -> Option<AccountId> {
+            self.referrers.get(referee)
+        }
+
+        /// Returns whether the referral of `referee` was rewarded.
+        #[ink(message)]
+        pub fn is_rewarded(&self, referee: AccountId) -> bool {
+            self.rewarded.get(referee).unwrap_or(false)
+        }
+
+        /// Returns the number of rewarded referrals of `referrer`.
+        #[ink(message)]
+        pub fn referral_count(&self, referrer: AccountId) -> u32 {
+            self.referral_counts.get(referrer).unwrap_or(0)
+        }
+
+        /// Returns the reward of a referrer's `n`-th rewarded referral.
+        #[ink(message)]
+        pub fn reward_for(&self, n: u32) -> Balance {
+            tier_reward(&self.tiers, n)
+        }
+
+        /// Replaces the reward tiers. Only the admin can call this.
+        #[ink(message)]
+        pub fn set_tiers(&mut self, tiers: Vec<Tier>) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAdmin);
+            }
+            let starts_at_one = tiers.first().map(|tier| tier.min_referrals) == Some(1);
+            let increasing = tiers
+                .windows(2)
+                .all(|pair| pair[0].min_referrals < pair[1].min_referrals);
+            if !starts_at_one || !increasing || tiers.len() > MAX_TIERS {
+                return Err(Error::InvalidTiers);
+            }
+            self.tiers = tiers.clone();
+            self.env().emit_event(TiersSet { tiers });
+            Ok(())
+        }
+
+        /// Registers `referrer` as the account that referred the caller.
+        #[ink(message)]
+        pub fn register(&mut self, referrer: AccountId) -> Result<()> {
+            let referee = self.env().caller();
+            if referrer == referee {
+                return Err(Error::SelfReferral);
+            }
+            if self.referrer_of(referrer) == Some(referee) {
+                return Err(Error::CircularReferral);
+            }
+            if self.referrers.contains(referee) {
+                return Err(Error::AlreadyReferred);
+            }
+            self.referrers.insert(referee, &referrer);
+            self.env().emit_event(Referred { referee, referrer });
+            Ok(())
+        }
+
+        /// Pays the referrer of `referee` their reward, once `referee` is enrolled
+        /// in `course_id`. Returns the reward. Anyone can call this.
+        #[ink(message)]
+        pub fn reward(&mut self, referee: AccountId, course_id: CourseId) -> Result<Balance> {
+            self.ensure_rewardable(referee)?;
+            let enrolled = build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.enrollment).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(IS_ENROLLED_SELECTOR))
+                        .push_arg(course_id)
+                        .push_arg(referee),
+                )
+                .returns::<bool>()
+                .fire()
+                .map_err(|_| Error::EnrollmentUnavailable)?;
+            if !enrolled {
+                return Err(Error::NotEnrolled);
+            }
+
+            let (referrer, amount) = self.record_reward(referee)?;
+            if amount > 0 {
+                let sent = build_call::<ink_env::DefaultEnvironment>()
+                    .call_type(Call::new().callee(self.token).gas_limit(0))
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(TRANSFER_SELECTOR))
+                            .push_arg(referrer)
+                            .push_arg(amount),
+                    )
+                    .returns::<core::result::Result<(), CalleeError>>()
+                    .fire();
+                if !matches!(sent, Ok(Ok(()))) {
+                    self.unrecord_reward(referee, referrer);
+                    return Err(Error::TransferFailed);
+                }
+            }
+            self.env().emit_event(RewardPaid {
+                referrer,
+                referee,
+                course_id,
+                amount,
+            });
+            Ok(amount)
+        }
+
+        /// Returns the referrer of `referee`, unless there is nothing to reward.
+        fn ensure_rewardable(&self, referee: AccountId) -> Result<AccountId> {
+            let referrer = self.referrer_of(referee).ok_or(Error::NotReferred)?;
+            if self.is_rewarded(referee) {
+                return Err(Error::AlreadyRewarded);
+            }
+            Ok(referrer)
+        }
+
+        /// Marks the referral of `referee` rewarded and counts it for the referrer.
+        /// Returns the referrer and their reward.
+        fn record_reward(&mut self, referee: AccountId) -> Result<(AccountId, Balance)> {
+            let referrer = self.ensure_rewardable(referee)?;
+            let count = self.referral_count(referrer) + 1;
+            self.rewarded.insert(referee, &true);
+            self.referral_counts.insert(referrer, &count);
+            Ok((referrer, self.reward_for(count)))
+        }
+
+        /// Reverts `record_reward` after the payout failed.
+        fn unrecord_reward(&mut self, referee: AccountId, referrer: AccountId) {
+            self.rewarded.remove(referee);
+            let count = self.referral_count(referrer);
+            self.referral_counts.insert(referrer, &(count - 1));
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// cover everything up to the enrollment check and record rewards with
+    /// `record_reward`.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        fn tier(min_referrals: u32, reward: Balance) -> Tier {
+            Tier {
+                min_referrals,
+                reward,
+            }
+        }
+
+        /// A contract paying 10 from the 1st referral, 20 from the 3rd and 50 from
+        /// the 5th.
+        fn with_tiers() -> Referral {
+            let mut referral =
+                Referral::new(AccountId::from([0xe7; 32]), AccountId::from([0x70; 32]));
+            assert_eq!(
+                referral.set_tiers(vec![tier(1, 10), tier(3, 20), tier(5, 50)]),
+                Ok(())
+            );
+            referral
+        }
+
+        #[ink::test]
+        fn rewards_change_at_tier_boundaries() {
+            let referral = with_tiers();
+            let rewards: Vec<Balance> = (0..=6).map(|n| referral.reward_for(n)).collect();
+            assert_eq!(rewards, vec![0, 10, 10, 20, 20, 50, 50]);
+            assert_eq!(referral.reward_for(u32::MAX), 50);
+        }
+
+        #[ink::test]
+        fn referrals_climb_the_tiers() {
+            let mut referral = with_tiers();
+            let referrer = AccountId::from([0xaa; 32]);
+            let mut paid = Vec::new();
+            for seed in 1..=5 {
+                let referee = AccountId::from([seed; 32]);
+                fixtures::with_caller(referee, || referral.register(referrer)).expect("registered");
+                paid.push(referral.record_reward(referee).expect("rewarded").1);
+            }
+            assert_eq!(paid, vec![10, 10, 20, 20, 50]);
+            assert_eq!(referral.referral_count(referrer), 5);
+        }
+
+        #[ink::test]
+        fn self_and_circular_referrals_are_rejected() {
+            let accounts = fixtures::accounts();
+            let mut referral = with_tiers();
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(referral.register(accounts.bob), Err(Error::SelfReferral));
+            assert_eq!(referral.register(accounts.charlie), Ok(()));
+
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(
+                referral.register(accounts.bob),
+                Err(Error::CircularReferral)
+            );
+            assert_eq!(referral.register(accounts.dave), Ok(()));
+        }
+
+        #[ink::test]
+        fn referral_is_registered_and_rewarded_once() {
+            let accounts = fixtures::accounts();
+            let mut referral = with_tiers();
+            assert_eq!(
+                referral.record_reward(accounts.bob),
+                Err(Error::NotReferred)
+            );
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(referral.register(accounts.charlie), Ok(()));
+            assert_eq!(
+                referral.register(accounts.dave),
+                Err(Error::AlreadyReferred)
+            );
+            assert_eq!(referral.referrer_of(accounts.bob), Some(accounts.charlie));
+
+            assert_eq!(
+                referral.record_reward(accounts.bob),
+                Ok((accounts.charlie, 10))
+            );
+            assert!(referral.is_rewarded(accounts.bob));
+            assert_eq!(
+                referral.record_reward(accounts.bob),
+                Err(Error::AlreadyRewarded)
+            );
+            assert_eq!(
+                referral.reward(accounts.bob, 0),
+                Err(Error::AlreadyRewarded)
+            );
+        }
+
+        #[ink::test]
+        fn failed_payout_is_unrecorded() {
+            let accounts = fixtures::accounts();
+            let mut referral = with_tiers();
+            fixtures::with_caller(accounts.bob, || referral.register(accounts.charlie))
+                .expect("registered");
+            referral.record_reward(accounts.bob).expect("rewarded");
+            referral.unrecord_reward(accounts.bob, accounts.charlie);
+            assert!(!referral.is_rewarded(accounts.bob));
+            assert_eq!(referral.referral_count(accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn tiers_are_validated() {
+            let accounts = fixtures::accounts();
+            let mut referral = with_tiers();
+            assert_eq!(referral.set_tiers(Vec::new()), Err(Error::InvalidTiers));
+            assert_eq!(
+                referral.set_tiers(vec![tier(2, 10)]),
+                Err(Error::InvalidTiers)
+            );
+            assert_eq!(
+                referral.set_tiers(vec![tier(1, 10), tier(3, 20), tier(3, 30)]),
+                Err(Error::InvalidTiers)
+            );
+            assert_eq!(
+                referral.set_tiers((1..=9).map(|n| tier(n, 1)).collect()),
+                Err(Error::InvalidTiers)
+            );
+            assert_eq!(referral.tiers().len(), 3);
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(referral.set_tiers(vec![tier(1, 1)]), Err(Error::NotAdmin));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Deploy enrollment.rs and an ERC-20 token first
+//    - Upload the generated .contract file and deploy it with both addresses
+//
+// 4. Interact with the contract:
+//    - As admin, use "setTiers" (e.g. [{1, 10}, {3, 20}]) and transfer reward
+//      tokens to the contract
+//    - As a learner, use "register" with your referrer, then enroll in a course
+//    - Anyone can then use "reward" with the learner and the course id
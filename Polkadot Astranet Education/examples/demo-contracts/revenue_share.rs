@@ -0,0 +1,467 @@
+// examples/demo-contracts/revenue_share.rs
+//
+// An instructor revenue-share contract example for Polkadot using ink!
+// A team of instructors teaches a course together and splits its fees by weight. The
+// contract opens the course on `enrollment.rs` as its instructor, so the enrollment
+// contract pays the fees here, and every fee collected is split among the current
+// instructors in proportion to their weights.
+//
+// Pull payments
+// -------------
+// Splitting a payment does not send anything: it credits each instructor's pending
+// balance, and instructors claim their balance whenever they like. Pushing a
+// transfer to every instructor would let one failing recipient block everyone else's
+// pay, and would make each distribution cost a transfer per instructor.
+//
+// Rebalancing
+// -----------
+// The admin can change weights at any time. A split uses the weights at the moment
+// the fees arrive, so a change only affects future income; what was already
+// credited stays credited, even to an instructor whose weight drops to zero. Integer
+// division leaves up to one unit per instructor behind; it is kept and added to the
+// next split rather than lost.
+//
+// A `Mapping` cannot be walked, so the instructors with a nonzero weight are also
+// listed in a `Vec`; a split goes down that list. `MAX_INSTRUCTORS` keeps it short.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod revenue_share {
+    use ink_env::call::{build_call, Call, ExecutionInput, Selector};
+    use ink_prelude::vec::Vec;
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// Identifies a course on the enrollment contract.
+    pub type CourseId = u32;
+
+    /// The largest number of instructors sharing the revenue, which bounds the cost
+    /// of a split.
+    pub const MAX_INSTRUCTORS: u32 = 16;
+
+    /// Selector of the enrollment `create_course(fee, refund_window)` message.
+    const CREATE_COURSE_SELECTOR: [u8; 4] = [0xc7, 0x59, 0x02, 0xbc];
+
+    /// Selector of the enrollment `withdraw(course_id)` message.
+    const WITHDRAW_SELECTOR: [u8; 4] = [0x41, 0x0f, 0xcc, 0x9d];
+
+    /// The revenue share error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the admin.
+        NotAdmin,
+        /// Returned if adding the instructor would exceed `MAX_INSTRUCTORS`.
+        TooManyInstructors,
+        /// Returned if there is no instructor to split a payment among.
+        NoInstructors,
+        /// Returned if no value was sent along, or no fees were collected.
+        ZeroAmount,
+        /// Returned if the caller has no pending balance.
+        NothingToClaim,
+        /// Returned if the enrollment contract refused the call.
+        EnrollmentFailed,
+        /// Returned if the native transfer failed.
+        TransferFailed,
+        /// Returned if the split math overflows.
+        Overflow,
+    }
+
+    /// The revenue share result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Stands in for the enrollment error type, which encodes as a bare variant
+    /// index; all this contract needs is to tell success from failure.
+    #[derive(scale::Decode)]
+    struct CalleeError(#[allow(dead_code)] u8);
+
+    // Events are mirrored in `crates/events/src/revenue_share.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when the admin changes the weight of an instructor.
+    #[ink(event)]
+    pub struct WeightChanged {
+        #[ink(topic)]
+        instructor: AccountId,
+        weight: u32,
+    }
+
+    /// Event emitted when the contract opens a course on the enrollment contract.
+    #[ink(event)]
+    pub struct CourseOpened {
+        #[ink(topic)]
+        course_id: CourseId,
+        fee: Balance,
+    }
+
+    /// Event emitted when a payment is split among the instructors.
+    #[ink(event)]
+    pub struct Distributed {
+        amount: Balance,
+        total_weight: u64,
+    }
+
+    /// Event emitted when an instructor claims their pending balance.
+    #[ink(event)]
+    pub struct Claimed {
+        #[ink(topic)]
+        instructor: AccountId,
+        amount: Balance,
+    }
+
+    /// The revenue share storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct RevenueShare {
+        /// Account that sets weights and opens courses.
+        admin: AccountId,
+        /// The enrollment contract the courses are opened on.
+        enrollment: AccountId,
+        /// Mapping from instructor to their nonzero weight.
+        weights: Mapping<AccountId, u32>,
+        /// Every instructor with a nonzero weight.
+        instructors: Vec<AccountId>,
+        /// Sum of all weights.
+        total_weight: u64,
+        /// Mapping from instructor to the balance they can claim.
+        pending: Mapping<AccountId, Balance>,
+        /// Remainder of past splits, added to the next one.
+        undistributed: Balance,
+    }
+
+    impl RevenueShare {
+        /// Creates a contract without instructors, opening courses on
+        /// `enrollment`, with the caller as admin.
+        #[ink(constructor)]
+        pub fn new(enrollment: AccountId) -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.admin = Self::env().caller();
+                contract.enrollment = enrollment;
+            })
+        }
+
+        /// Returns the admin.
+        #[ink(message)]
+        pub fn admin(&self) -> AccountId {
+            self.admin
+        }
+
+        /// Returns the enrollment contract.
+        #[ink(message)]
+        pub fn enrollment(&self) -> AccountId {
+            self.enrollment
+        }
+
+        /// Returns the weight of `instructor`, which is 0 for non-instructors.
+        #[ink(message)]
+        pub fn weight_of(&self, instructor: AccountId) -> u32 {
+            self.weights.get(instructor).unwrap_or(0)
+        }
+
+        /// Returns the sum of all weights.
+        #[ink(message)]
+        pub fn total_weight(&self) -> u64 {
+            self.total_weight
+        }
+
+        /// Returns the balance `instructor` can claim.
+        #[ink(message)]
+        pub fn pending_of(&self, instructor: AccountId) -> Balance {
+            self.pending.get(instructor).unwrap_or(0)
+        }
+
+        /// Returns the remainder of past splits, added to the next one.
+        #[ink(message)]
+        pub fn undistributed(&self) -> Balance {
+            self.undistributed
+        }
+
+        /// Sets the weight of `instructor` for future splits; a weight of 0 removes
+        /// them. Only the admin can call this.
+        #[ink(message)]
+        pub fn set_weight(&mut self, instructor: AccountId, weight: u32) -> Result<()> {
+            self.ensure_admin()?;
+            let previous = self.weight_of(instructor);
+            if previous == 0 && weight > 0 && self.instructors.len() >= MAX_INSTRUCTORS as usize {
+                return Err(Error::TooManyInstructors);
+            }
+            if weight == 0 {
+                self.weights.remove(instructor);
+                self.instructors.retain(|account| *account != instructor);
+            } else {
+                self.weights.insert(instructor, &weight);
+                if previous == 0 {
+                    self.instructors.push(instructor);
+                }
+            }
+            self.total_weight = self.total_weight - u64::from(previous) + u64::from(weight);
+            self.env().emit_event(WeightChanged { instructor, weight });
+            Ok(())
+        }
+
+        /// Creates a course costing `fee` on the enrollment contract, with this
+        /// contract as its instructor, and returns its id. Only the admin can call
+        /// this.
+        #[ink(message)]
+        pub fn open_course(&mut self, fee: Balance, refund_window: Timestamp) -> Result<CourseId> {
+            self.ensure_admin()?;
+            let course_id = build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.enrollment).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(CREATE_COURSE_SELECTOR))
+                        .push_arg(fee)
+                        .push_arg(refund_window),
+                )
+                .returns::<CourseId>()
+                .fire()
+                .map_err(|_| Error::EnrollmentFailed)?;
+            self.env().emit_event(CourseOpened { course_id, fee });
+            Ok(course_id)
+        }
+
+        /// Withdraws the fees of `course_id` from the enrollment contract and splits
+        /// them among the instructors. Returns the amount collected. Anyone can call
+        /// this once the course's refund window has closed.
+        #[ink(message)]
+        pub fn collect(&mut self, course_id: CourseId) -> Result<Balance> {
+            if self.total_weight == 0 {
+                return Err(Error::NoInstructors);
+            }
+            let before = self.env().balance();
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.enrollment).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(WITHDRAW_SELECTOR)).push_arg(course_id),
+                )
+                .returns::<core::result::Result<(), CalleeError>>()
+                .fire()
+                .map_err(|_| Error::EnrollmentFailed)?
+                .map_err(|_| Error::EnrollmentFailed)?;
+            let amount = self.env().balance().saturating_sub(before);
+            self.distribute(amount)?;
+            Ok(amount)
+        }
+
+        /// Splits the value sent along among the instructors, for fees or tips paid
+        /// outside the enrollment contract.
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) -> Result<()> {
+            self.distribute(self.env().transferred_value())
+        }
+
+        /// Sends the caller their pending balance and returns it.
+        #[ink(message)]
+        pub fn claim(&mut self) -> Result<Balance> {
+            let instructor = self.env().caller();
+            let amount = self.pending.get(instructor).ok_or(Error::NothingToClaim)?;
+            self.pending.remove(instructor);
+            if self.env().transfer(instructor, amount).is_err() {
+                self.pending.insert(instructor, &amount);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(Claimed { instructor, amount });
+            Ok(amount)
+        }
+
+        /// Credits `amount`, plus the remainder of past splits, to the instructors in
+        /// proportion to their weights.
+        fn distribute(&mut self, amount: Balance) -> Result<()> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let total_weight = self.total_weight;
+            if total_weight == 0 {
+                return Err(Error::NoInstructors);
+            }
+            let available = amount
+                .checked_add(self.undistributed)
+                .ok_or(Error::Overflow)?;
+            let shares = self
+                .instructors
+                .iter()
+                .map(|instructor| {
+                    available
+                        .checked_mul(Balance::from(self.weight_of(*instructor)))
+                        .map(|scaled| (*instructor, scaled / Balance::from(total_weight)))
+                        .ok_or(Error::Overflow)
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let mut credited = 0;
+            for (instructor, share) in shares {
+                if share > 0 {
+                    let pending = self.pending_of(instructor);
+                    self.pending.insert(instructor, &(pending + share));
+                    credited += share;
+                }
+            }
+            self.undistributed = available - credited;
+            self.env().emit_event(Distributed {
+                amount,
+                total_weight,
+            });
+            Ok(())
+        }
+
+        /// Returns `NotAdmin` unless the caller is the admin.
+        fn ensure_admin(&self) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAdmin);
+            }
+            Ok(())
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// pay fees in with `deposit`, which splits them like `collect` does.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        fn enrollment() -> AccountId {
+            AccountId::from([0xe7; 32])
+        }
+
+        fn deposit(share: &mut RevenueShare, amount: Balance) -> Result<()> {
+            ink_env::test::set_value_transferred::<Env>(amount);
+            let result = share.deposit();
+            ink_env::test::set_value_transferred::<Env>(0);
+            result
+        }
+
+        /// A contract with bob and charlie at weight 1 each.
+        fn with_instructors() -> RevenueShare {
+            let accounts = fixtures::accounts();
+            let mut share = RevenueShare::new(enrollment());
+            assert_eq!(share.set_weight(accounts.bob, 1), Ok(()));
+            assert_eq!(share.set_weight(accounts.charlie, 1), Ok(()));
+            share
+        }
+
+        #[ink::test]
+        fn fees_split_by_weight() {
+            let accounts = fixtures::accounts();
+            let mut share = with_instructors();
+            assert_eq!(share.set_weight(accounts.charlie, 3), Ok(()));
+            assert_eq!(share.total_weight(), 4);
+
+            assert_eq!(deposit(&mut share, 400), Ok(()));
+            assert_eq!(share.pending_of(accounts.bob), 100);
+            assert_eq!(share.pending_of(accounts.charlie), 300);
+            assert_eq!(share.undistributed(), 0);
+        }
+
+        #[ink::test]
+        fn rebalancing_only_affects_future_fees() {
+            let accounts = fixtures::accounts();
+            let mut share = with_instructors();
+            assert_eq!(deposit(&mut share, 100), Ok(()));
+
+            // Charlie leaves and dave joins at twice bob's weight.
+            assert_eq!(share.set_weight(accounts.charlie, 0), Ok(()));
+            assert_eq!(share.set_weight(accounts.dave, 2), Ok(()));
+            assert_eq!(share.total_weight(), 3);
+            assert_eq!(deposit(&mut share, 300), Ok(()));
+
+            assert_eq!(share.pending_of(accounts.bob), 50 + 100);
+            assert_eq!(share.pending_of(accounts.charlie), 50);
+            assert_eq!(share.pending_of(accounts.dave), 200);
+            assert_eq!(share.weight_of(accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn remainder_carries_to_next_split() {
+            let accounts = fixtures::accounts();
+            let mut share = with_instructors();
+            assert_eq!(share.set_weight(accounts.dave, 1), Ok(()));
+
+            assert_eq!(deposit(&mut share, 100), Ok(()));
+            assert_eq!(share.pending_of(accounts.bob), 33);
+            assert_eq!(share.undistributed(), 1);
+
+            assert_eq!(deposit(&mut share, 200), Ok(()));
+            assert_eq!(share.pending_of(accounts.bob), 33 + 67);
+            assert_eq!(share.pending_of(accounts.dave), 33 + 67);
+            assert_eq!(share.undistributed(), 0);
+        }
+
+        #[ink::test]
+        fn instructors_pull_their_balance() {
+            let accounts = fixtures::accounts();
+            let mut share = with_instructors();
+            assert_eq!(deposit(&mut share, 100), Ok(()));
+            fixtures::fund_contract(100);
+            fixtures::fund(accounts.bob, 0);
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(share.claim(), Ok(50));
+            assert_eq!(fixtures::balance_of(accounts.bob), 50);
+            assert_eq!(share.claim(), Err(Error::NothingToClaim));
+            assert_eq!(share.pending_of(accounts.charlie), 50);
+        }
+
+        #[ink::test]
+        fn split_needs_instructors_and_value() {
+            let accounts = fixtures::accounts();
+            let mut share = RevenueShare::new(enrollment());
+            assert_eq!(deposit(&mut share, 100), Err(Error::NoInstructors));
+            assert_eq!(share.set_weight(accounts.bob, 1), Ok(()));
+            assert_eq!(deposit(&mut share, 0), Err(Error::ZeroAmount));
+            assert_eq!(share.set_weight(accounts.bob, 0), Ok(()));
+            assert_eq!(share.collect(0), Err(Error::NoInstructors));
+        }
+
+        #[ink::test]
+        fn instructors_are_bounded() {
+            let mut share = RevenueShare::new(enrollment());
+            for seed in 0..MAX_INSTRUCTORS as u8 {
+                assert_eq!(share.set_weight(AccountId::from([seed; 32]), 1), Ok(()));
+            }
+            let extra = AccountId::from([0xff; 32]);
+            assert_eq!(share.set_weight(extra, 1), Err(Error::TooManyInstructors));
+            // Existing instructors can still be rebalanced.
+            assert_eq!(share.set_weight(AccountId::from([0; 32]), 5), Ok(()));
+            assert_eq!(share.total_weight(), u64::from(MAX_INSTRUCTORS) + 4);
+        }
+
+        #[ink::test]
+        fn only_admin_manages_weights() {
+            let accounts = fixtures::accounts();
+            let mut share = with_instructors();
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(share.set_weight(accounts.bob, 10), Err(Error::NotAdmin));
+            assert_eq!(share.open_course(50, 0), Err(Error::NotAdmin));
+            assert_eq!(share.admin(), accounts.alice);
+            assert_eq!(share.enrollment(), enrollment());
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Deploy enrollment.rs first
+//    - Upload the generated .contract file and deploy it with the enrollment
+//      contract's address
+//
+// 4. Interact with the contract:
+//    - As admin, use "setWeight" for each instructor
+//    - Use "openCourse" with a fee and a refund window; learners enroll in the
+//      returned course on the enrollment contract
+//    - Once the refund window closes, anyone can use "collect" with the course id
+//    - Instructors use "claim" to receive their share
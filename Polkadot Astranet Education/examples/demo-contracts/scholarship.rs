@@ -0,0 +1,616 @@
+// examples/demo-contracts/scholarship.rs
+//
+// A scholarship fund contract example for Polkadot using ink!
+// Donors fund a common pool. Learners apply, and the admin approves an applicant
+// with a scholarship of a number of milestones, each paid out as a tranche. When an
+// instructor attests that the learner reached a milestone, the learner submits the
+// attestation and claims that tranche.
+//
+// Roles
+// -----
+// - Admin: approves and revokes scholarships and manages the instructor list.
+// - Instructors: sign milestone attestations off-chain; they never send a
+//   transaction, so grading costs them nothing.
+// - Donors: anyone sending value to `donate`.
+// - Learners: apply, then claim tranches with their instructors' attestations.
+//
+// Attestations
+// ------------
+// An instructor signs, with an ECDSA (secp256k1) key, the BLAKE2-256 hash of the
+// SCALE-encoded `(scholarship contract, learner, milestone)` tuple; see
+// `attestation_hash`. Binding the contract and the learner stops an attestation
+// from being used on another deployment or by another learner, and milestones are
+// claimed strictly in order, so each attestation pays once. Instructor accounts are
+// the BLAKE2-256 hash of their compressed public key, as in `forwarder.rs`.
+//
+// Approving a scholarship reserves all of its tranches from the pool, so approved
+// learners are always paid; revoking one returns the unpaid tranches to the pool.
+//
+// Testing
+// -------
+// The tests sign attestations with real instructor keys and need
+// `secp256k1 = { version = "0.28", features = ["recovery", "global-context"] }`
+// in the contract's dev-dependencies.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod scholarship {
+    use ink_env::hash::{Blake2x256, CryptoHash};
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// The scholarship error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the admin.
+        NotAdmin,
+        /// Returned if no value was sent along, or a scholarship would pay nothing.
+        ZeroAmount,
+        /// Returned if the learner already has an application or a scholarship.
+        AlreadyApplied,
+        /// Returned if the learner has no pending application.
+        NotApplied,
+        /// Returned if the learner has no scholarship.
+        NotScholar,
+        /// Returned if the pool cannot cover the scholarship.
+        InsufficientFunds,
+        /// Returned if the milestone is not the learner's next one.
+        WrongMilestone,
+        /// Returned if the attestation signature is malformed.
+        InvalidSignature,
+        /// Returned if the attestation was not signed by an instructor.
+        NotInstructor,
+        /// Returned if the native transfer failed.
+        TransferFailed,
+        /// Returned if the scholarship total overflows.
+        Overflow,
+    }
+
+    /// The scholarship result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// An approved scholarship.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Award {
+        /// Amount paid per milestone.
+        pub tranche: Balance,
+        /// Number of milestones, numbered from 0.
+        pub milestones: u32,
+        /// Number of milestones paid so far, which is also the next milestone.
+        pub claimed: u32,
+    }
+
+    impl Award {
+        /// Returns the tranches not paid yet.
+        pub fn unpaid(&self) -> Balance {
+            self.tranche * Balance::from(self.milestones - self.claimed)
+        }
+    }
+
+    /// Returns the hash an instructor signs to attest that `learner` reached
+    /// `milestone` of their scholarship at `scholarship`.
+    pub fn attestation_hash(
+        scholarship: &AccountId,
+        learner: &AccountId,
+        milestone: u32,
+    ) -> [u8; 32] {
+        let mut output = [0u8; 32];
+        Blake2x256::hash(
+            &scale::Encode::encode(&(scholarship, learner, milestone)),
+            &mut output,
+        );
+        output
+    }
+
+    /// Returns the `AccountId` of the ECDSA key with compressed public key `public_key`.
+    pub fn ecdsa_account(public_key: &[u8; 33]) -> AccountId {
+        let mut output = [0u8; 32];
+        Blake2x256::hash(public_key, &mut output);
+        AccountId::from(output)
+    }
+
+    // Events are mirrored in `crates/events/src/scholarship.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when a donor funds the pool.
+    #[ink(event)]
+    pub struct Donated {
+        #[ink(topic)]
+        donor: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when the admin adds or removes an instructor.
+    #[ink(event)]
+    pub struct InstructorChanged {
+        #[ink(topic)]
+        instructor: AccountId,
+        active: bool,
+    }
+
+    /// Event emitted when a learner applies.
+    #[ink(event)]
+    pub struct Applied {
+        #[ink(topic)]
+        learner: AccountId,
+    }
+
+    /// Event emitted when the admin approves a learner's application.
+    #[ink(event)]
+    pub struct Approved {
+        #[ink(topic)]
+        learner: AccountId,
+        tranche: Balance,
+        milestones: u32,
+    }
+
+    /// Event emitted when the admin revokes a scholarship, returning the unpaid
+    /// tranches to the pool.
+    #[ink(event)]
+    pub struct Revoked {
+        #[ink(topic)]
+        learner: AccountId,
+        returned: Balance,
+    }
+
+    /// Event emitted when a learner is paid the tranche of a milestone attested by
+    /// `instructor`.
+    #[ink(event)]
+    pub struct TranchePaid {
+        #[ink(topic)]
+        learner: AccountId,
+        #[ink(topic)]
+        instructor: AccountId,
+        milestone: u32,
+        amount: Balance,
+    }
+
+    /// The scholarship storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Scholarship {
+        /// Account that approves scholarships and manages instructors.
+        admin: AccountId,
+        /// Mapping from instructor account to whether it may attest.
+        instructors: Mapping<AccountId, bool>,
+        /// Donations not reserved for a scholarship.
+        pool: Balance,
+        /// Mapping from learner to whether they have a pending application.
+        applications: Mapping<AccountId, bool>,
+        /// Mapping from learner to their scholarship.
+        awards: Mapping<AccountId, Award>,
+    }
+
+    impl Scholarship {
+        /// Creates an empty fund with the caller as admin.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.admin = Self::env().caller();
+            })
+        }
+
+        /// Returns the admin.
+        #[ink(message)]
+        pub fn admin(&self) -> AccountId {
+            self.admin
+        }
+
+        /// Returns the donations not reserved for a scholarship.
+        #[ink(message)]
+        pub fn pool(&self) -> Balance {
+            self.pool
+        }
+
+        /// Returns whether `account` may attest milestones.
+        #[ink(message)]
+        pub fn is_instructor(&self, account: AccountId) -> bool {
+            self.instructors.get(account).unwrap_or(false)
+        }
+
+        /// Returns whether `learner` has a pending application.
+        #[ink(message)]
+        pub fn has_applied(&self, learner: AccountId) -> bool {
+            self.applications.get(learner).unwrap_or(false)
+        }
+
+        /// Returns the scholarship of `learner`, if approved.
+        #[ink(message)]
+        pub fn award_of(&self, learner: AccountId) -> Option<Award> {
+            self.awards.get(learner)
+        }
+
+        /// Adds the value sent along to the pool.
+        #[ink(message, payable)]
+        pub fn donate(&mut self) -> Result<()> {
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.pool += amount;
+            self.env().emit_event(Donated {
+                donor: self.env().caller(),
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Allows or stops `instructor` attesting milestones. Only the admin can
+        /// call this.
+        #[ink(message)]
+        pub fn set_instructor(&mut self, instructor: AccountId, active: bool) -> Result<()> {
+            self.ensure_admin()?;
+            if active {
+                self.instructors.insert(instructor, &true);
+            } else {
+                self.instructors.remove(instructor);
+            }
+            self.env()
+                .emit_event(InstructorChanged { instructor, active });
+            Ok(())
+        }
+
+        /// Applies for a scholarship as the caller.
+        #[ink(message)]
+        pub fn apply(&mut self) -> Result<()> {
+            let learner = self.env().caller();
+            if self.has_applied(learner) || self.awards.contains(learner) {
+                return Err(Error::AlreadyApplied);
+            }
+            self.applications.insert(learner, &true);
+            self.env().emit_event(Applied { learner });
+            Ok(())
+        }
+
+        /// Approves the application of `learner` with `milestones` tranches of
+        /// `tranche` each, reserving them from the pool. Only the admin can call
+        /// this.
+        #[ink(message)]
+        pub fn approve(
+            &mut self,
+            learner: AccountId,
+            tranche: Balance,
+            milestones: u32,
+        ) -> Result<()> {
+            self.ensure_admin()?;
+            if !self.has_applied(learner) {
+                return Err(Error::NotApplied);
+            }
+            if tranche == 0 || milestones == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let total = tranche
+                .checked_mul(Balance::from(milestones))
+                .ok_or(Error::Overflow)?;
+            if total > self.pool {
+                return Err(Error::InsufficientFunds);
+            }
+            self.pool -= total;
+            self.applications.remove(learner);
+            self.awards.insert(
+                learner,
+                &Award {
+                    tranche,
+                    milestones,
+                    claimed: 0,
+                },
+            );
+            self.env().emit_event(Approved {
+                learner,
+                tranche,
+                milestones,
+            });
+            Ok(())
+        }
+
+        /// Declines the pending application of `learner`. Only the admin can call
+        /// this.
+        #[ink(message)]
+        pub fn reject(&mut self, learner: AccountId) -> Result<()> {
+            self.ensure_admin()?;
+            if !self.has_applied(learner) {
+                return Err(Error::NotApplied);
+            }
+            self.applications.remove(learner);
+            Ok(())
+        }
+
+        /// Ends the scholarship of `learner` and returns its unpaid tranches to the
+        /// pool. Only the admin can call this.
+        #[ink(message)]
+        pub fn revoke(&mut self, learner: AccountId) -> Result<()> {
+            self.ensure_admin()?;
+            let award = self.award_of(learner).ok_or(Error::NotScholar)?;
+            self.awards.remove(learner);
+            let returned = award.unpaid();
+            self.pool += returned;
+            self.env().emit_event(Revoked { learner, returned });
+            Ok(())
+        }
+
+        /// Pays the caller the tranche of `milestone`, given an instructor's
+        /// `signature` over `attestation_hash(this contract, caller, milestone)`.
+        /// Milestones are claimed in order.
+        #[ink(message)]
+        pub fn claim(&mut self, milestone: u32, signature: [u8; 65]) -> Result<()> {
+            let learner = self.env().caller();
+            let award = self.award_of(learner).ok_or(Error::NotScholar)?;
+            if milestone != award.claimed || milestone >= award.milestones {
+                return Err(Error::WrongMilestone);
+            }
+
+            let hash = attestation_hash(&self.env().account_id(), &learner, milestone);
+            let public_key = self
+                .env()
+                .ecdsa_recover(&signature, &hash)
+                .map_err(|_| Error::InvalidSignature)?;
+            let instructor = ecdsa_account(&public_key);
+            if !self.is_instructor(instructor) {
+                return Err(Error::NotInstructor);
+            }
+
+            self.awards.insert(
+                learner,
+                &Award {
+                    claimed: award.claimed + 1,
+                    ..award
+                },
+            );
+            if self.env().transfer(learner, award.tranche).is_err() {
+                self.awards.insert(learner, &award);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(TranchePaid {
+                learner,
+                instructor,
+                milestone,
+                amount: award.tranche,
+            });
+            Ok(())
+        }
+
+        /// Returns `NotAdmin` unless the caller is the admin.
+        fn ensure_admin(&self) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAdmin);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+        use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+
+        type Env = ink_env::DefaultEnvironment;
+
+        /// A test instructor with a fixed ECDSA key.
+        struct Instructor {
+            secret: SecretKey,
+            public: [u8; 33],
+        }
+
+        impl Instructor {
+            fn new(seed: u8) -> Self {
+                let secret = SecretKey::from_slice(&[seed; 32]).expect("valid secret key");
+                let public = PublicKey::from_secret_key(&Secp256k1::new(), &secret).serialize();
+                Self { secret, public }
+            }
+
+            fn account(&self) -> AccountId {
+                ecdsa_account(&self.public)
+            }
+
+            fn attest(&self, learner: AccountId, milestone: u32) -> [u8; 65] {
+                let hash = attestation_hash(&contract_address(), &learner, milestone);
+                let message = Message::from_digest_slice(&hash).expect("hash is 32 bytes");
+                let (recovery_id, compact) = Secp256k1::new()
+                    .sign_ecdsa_recoverable(&message, &self.secret)
+                    .serialize_compact();
+                let mut signature = [0u8; 65];
+                signature[..64].copy_from_slice(&compact);
+                signature[64] = recovery_id.to_i32() as u8;
+                signature
+            }
+        }
+
+        fn contract_address() -> AccountId {
+            ink_env::test::callee::<Env>()
+        }
+
+        /// A fund holding 1_000, with an instructor, and bob approved for 3
+        /// milestones of 100.
+        fn funded(instructor: &Instructor) -> Scholarship {
+            let accounts = fixtures::accounts();
+            let mut fund = Scholarship::new();
+            ink_env::test::set_value_transferred::<Env>(1_000);
+            assert_eq!(fund.donate(), Ok(()));
+            ink_env::test::set_value_transferred::<Env>(0);
+            fixtures::fund_contract(1_000);
+            fixtures::fund(accounts.bob, 0);
+
+            assert_eq!(fund.set_instructor(instructor.account(), true), Ok(()));
+            fixtures::with_caller(accounts.bob, || fund.apply()).expect("applied");
+            assert_eq!(fund.approve(accounts.bob, 100, 3), Ok(()));
+            fund
+        }
+
+        #[ink::test]
+        fn approval_reserves_tranches() {
+            let accounts = fixtures::accounts();
+            let fund = funded(&Instructor::new(0x11));
+            assert_eq!(fund.pool(), 700);
+            assert!(!fund.has_applied(accounts.bob));
+            assert_eq!(
+                fund.award_of(accounts.bob),
+                Some(Award {
+                    tranche: 100,
+                    milestones: 3,
+                    claimed: 0
+                })
+            );
+        }
+
+        #[ink::test]
+        fn attested_milestones_pay_in_order() {
+            let accounts = fixtures::accounts();
+            let instructor = Instructor::new(0x11);
+            let mut fund = funded(&instructor);
+            fixtures::set_caller(accounts.bob);
+
+            assert_eq!(
+                fund.claim(1, instructor.attest(accounts.bob, 1)),
+                Err(Error::WrongMilestone)
+            );
+            assert_eq!(fund.claim(0, instructor.attest(accounts.bob, 0)), Ok(()));
+            assert_eq!(fund.claim(1, instructor.attest(accounts.bob, 1)), Ok(()));
+            assert_eq!(fixtures::balance_of(accounts.bob), 200);
+
+            // An attestation pays once.
+            assert_eq!(
+                fund.claim(1, instructor.attest(accounts.bob, 1)),
+                Err(Error::WrongMilestone)
+            );
+            assert_eq!(fund.claim(2, instructor.attest(accounts.bob, 2)), Ok(()));
+            assert_eq!(
+                fund.claim(3, instructor.attest(accounts.bob, 3)),
+                Err(Error::WrongMilestone)
+            );
+            assert_eq!(fixtures::balance_of(accounts.bob), 300);
+        }
+
+        #[ink::test]
+        fn attestation_must_come_from_instructor() {
+            let accounts = fixtures::accounts();
+            let instructor = Instructor::new(0x11);
+            let stranger = Instructor::new(0x22);
+            let mut fund = funded(&instructor);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                fund.claim(0, stranger.attest(accounts.bob, 0)),
+                Err(Error::NotInstructor)
+            );
+
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(fund.set_instructor(instructor.account(), false), Ok(()));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                fund.claim(0, instructor.attest(accounts.bob, 0)),
+                Err(Error::NotInstructor)
+            );
+        }
+
+        #[ink::test]
+        fn attestation_is_bound_to_learner() {
+            let accounts = fixtures::accounts();
+            let instructor = Instructor::new(0x11);
+            let mut fund = funded(&instructor);
+            fixtures::with_caller(accounts.charlie, || fund.apply()).expect("applied");
+            assert_eq!(fund.approve(accounts.charlie, 100, 1), Ok(()));
+
+            // Charlie cannot use an attestation made out to bob.
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(
+                fund.claim(0, instructor.attest(accounts.bob, 0)),
+                Err(Error::NotInstructor)
+            );
+            assert_eq!(fund.claim(0, [0u8; 65]), Err(Error::InvalidSignature));
+        }
+
+        #[ink::test]
+        fn approval_needs_application_and_funds() {
+            let accounts = fixtures::accounts();
+            let mut fund = funded(&Instructor::new(0x11));
+            assert_eq!(
+                fund.approve(accounts.charlie, 100, 1),
+                Err(Error::NotApplied)
+            );
+            fixtures::with_caller(accounts.charlie, || fund.apply()).expect("applied");
+            assert_eq!(
+                fund.approve(accounts.charlie, 100, 8),
+                Err(Error::InsufficientFunds)
+            );
+            assert_eq!(fund.approve(accounts.charlie, 0, 8), Err(Error::ZeroAmount));
+            assert_eq!(fund.reject(accounts.charlie), Ok(()));
+            assert!(!fund.has_applied(accounts.charlie));
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(fund.apply(), Err(Error::AlreadyApplied));
+        }
+
+        #[ink::test]
+        fn revoke_returns_unpaid_tranches() {
+            let accounts = fixtures::accounts();
+            let instructor = Instructor::new(0x11);
+            let mut fund = funded(&instructor);
+            fixtures::with_caller(accounts.bob, || {
+                fund.claim(0, instructor.attest(accounts.bob, 0))
+            })
+            .expect("claimed");
+
+            assert_eq!(fund.revoke(accounts.bob), Ok(()));
+            assert_eq!(fund.pool(), 900);
+            assert_eq!(fund.award_of(accounts.bob), None);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                fund.claim(1, instructor.attest(accounts.bob, 1)),
+                Err(Error::NotScholar)
+            );
+        }
+
+        #[ink::test]
+        fn only_admin_manages_roles() {
+            let accounts = fixtures::accounts();
+            let instructor = Instructor::new(0x11);
+            let mut fund = funded(&instructor);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                fund.set_instructor(accounts.bob, true),
+                Err(Error::NotAdmin)
+            );
+            assert_eq!(fund.approve(accounts.bob, 1, 1), Err(Error::NotAdmin));
+            assert_eq!(fund.revoke(accounts.bob), Err(Error::NotAdmin));
+            assert!(fund.is_instructor(instructor.account()));
+            assert!(!fund.is_instructor(accounts.bob));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy from the admin account
+//
+// 4. Interact with the contract:
+//    - Use the "donate" method with some value to fund the pool
+//    - As admin, use "setInstructor" with each instructor's ECDSA account
+//    - As a learner, use "apply"; as admin, use "approve" with a tranche and a
+//      number of milestones
+//    - Instructors sign `attestation_hash(contract, learner, milestone)` off-chain
+//    - As the learner, use "claim" with the milestone and the signature
@@ -0,0 +1,429 @@
+// examples/demo-contracts/spending_manager.rs
+//
+// A spending manager contract example for Polkadot using ink!
+// The owner deposits ERC-20 tokens into the manager and gives other accounts (a
+// hot wallet, a bot, a family member) a daily limit. Each spender can send tokens
+// from the manager to anyone, but never more than their limit within any 24 hours,
+// while the owner can top up, withdraw, and change limits at any time. Approving
+// those accounts on the token directly would give each of them a lump sum instead
+// of a budget.
+//
+// The rolling window
+// ------------------
+// A limit that resets at midnight lets a spender use it twice within minutes, at
+// 23:59 and at 00:00. Here every spender's budget keeps what they spent in each of
+// the last 24 hours, in 24 slots indexed by hour of the day; the spending that
+// counts against the limit is the sum of the slots written within the last 24
+// hours. Spending therefore leaves the window exactly one day after it happened, to
+// the hour, and a slot is reused once its hour is a day old.
+//
+// Token calls go through `SafeErc20` from the utils crate, which turns both failed
+// calls and tokens refusing a transfer into errors. A spend is recorded before the
+// tokens are sent and taken back out of the budget if the token refuses.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod spending_manager {
+    use astranet_utils::safe_erc20::SafeErc20;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Number of hourly slots in the window.
+    pub const WINDOW_HOURS: usize = 24;
+
+    /// One hour in milliseconds.
+    pub const HOUR: Timestamp = 60 * 60 * 1000;
+
+    /// The spending manager error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the owner.
+        NotOwner,
+        /// Returned if the caller has no limit.
+        NotSpender,
+        /// Returned if the amount is zero.
+        ZeroAmount,
+        /// Returned if the spend would exceed the spender's limit for the window.
+        LimitExceeded,
+        /// Returned if the token refused to move the tokens.
+        TransferFailed,
+    }
+
+    /// The spending manager result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A spender's daily limit and what they spent in the last 24 hours.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Budget {
+        /// Most the spender may spend within any 24 hours.
+        pub daily_limit: Balance,
+        /// Amount spent per slot; slot `hour % 24` holds the spending of `hour`.
+        pub spent: [Balance; WINDOW_HOURS],
+        /// Hour, counted from the epoch, each slot was last written for.
+        pub hours: [u64; WINDOW_HOURS],
+    }
+
+    impl Budget {
+        /// Creates a budget with nothing spent.
+        pub fn new(daily_limit: Balance) -> Self {
+            Self {
+                daily_limit,
+                spent: [0; WINDOW_HOURS],
+                hours: [0; WINDOW_HOURS],
+            }
+        }
+
+        /// Returns what was spent in the 24 hours up to and including `hour`.
+        pub fn spent_in_window(&self, hour: u64) -> Balance {
+            self.spent
+                .iter()
+                .zip(self.hours.iter())
+                .filter(|(_, written)| hour.saturating_sub(**written) < WINDOW_HOURS as u64)
+                .map(|(spent, _)| *spent)
+                .sum()
+        }
+
+        /// Returns what can still be spent in `hour`.
+        pub fn available(&self, hour: u64) -> Balance {
+            self.daily_limit.saturating_sub(self.spent_in_window(hour))
+        }
+
+        /// Records `amount` as spent in `hour`, or returns `LimitExceeded`.
+        pub fn record(&mut self, hour: u64, amount: Balance) -> Result<()> {
+            if amount > self.available(hour) {
+                return Err(Error::LimitExceeded);
+            }
+            let slot = (hour % WINDOW_HOURS as u64) as usize;
+            if self.hours[slot] != hour {
+                self.hours[slot] = hour;
+                self.spent[slot] = 0;
+            }
+            self.spent[slot] += amount;
+            Ok(())
+        }
+    }
+
+    // Events are mirrored in `crates/events/src/spending_manager.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when tokens are deposited into the manager.
+    #[ink(event)]
+    pub struct Deposited {
+        #[ink(topic)]
+        from: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when the owner withdraws tokens.
+    #[ink(event)]
+    pub struct Withdrawn {
+        amount: Balance,
+    }
+
+    /// Event emitted when the owner sets or removes a spender's limit.
+    #[ink(event)]
+    pub struct LimitSet {
+        #[ink(topic)]
+        spender: AccountId,
+        daily_limit: Balance,
+    }
+
+    /// Event emitted when a spender sends tokens.
+    #[ink(event)]
+    pub struct Spent {
+        #[ink(topic)]
+        spender: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+    }
+
+    /// The spending manager storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct SpendingManager {
+        /// Account that funds the manager and sets limits.
+        owner: AccountId,
+        /// The ERC-20 token being managed.
+        token: SafeErc20,
+        /// Mapping from spender to their budget.
+        budgets: Mapping<AccountId, Budget>,
+    }
+
+    impl SpendingManager {
+        /// Creates a manager for `token`, owned by the caller.
+        #[ink(constructor)]
+        pub fn new(token: AccountId) -> Self {
+            ink_lang::utils::initialize_contract(|manager: &mut Self| {
+                manager.owner = Self::env().caller();
+                manager.token = SafeErc20::new(token);
+            })
+        }
+
+        /// Returns the owner.
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Returns the daily limit of `spender`, if they have one.
+        #[ink(message)]
+        pub fn limit_of(&self, spender: AccountId) -> Option<Balance> {
+            self.budgets.get(spender).map(|budget| budget.daily_limit)
+        }
+
+        /// Returns what `spender` can spend right now.
+        #[ink(message)]
+        pub fn available(&self, spender: AccountId) -> Balance {
+            self.budgets
+                .get(spender)
+                .map_or(0, |budget| budget.available(self.hour()))
+        }
+
+        /// Moves `amount` tokens from the caller into the manager. The caller must
+        /// have approved the manager on the token first.
+        #[ink(message)]
+        pub fn deposit(&mut self, amount: Balance) -> Result<()> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let from = self.env().caller();
+            self.token
+                .transfer_from(from, self.env().account_id(), amount)
+                .map_err(|_| Error::TransferFailed)?;
+            self.env().emit_event(Deposited { from, amount });
+            Ok(())
+        }
+
+        /// Sends `amount` tokens back to the owner. Only the owner can call this.
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.token
+                .transfer(self.owner, amount)
+                .map_err(|_| Error::TransferFailed)?;
+            self.env().emit_event(Withdrawn { amount });
+            Ok(())
+        }
+
+        /// Sets the daily limit of `spender`, keeping what they already spent in
+        /// the window; a limit of zero removes the spender. Only the owner can
+        /// call this.
+        #[ink(message)]
+        pub fn set_limit(&mut self, spender: AccountId, daily_limit: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            if daily_limit == 0 {
+                self.budgets.remove(spender);
+            } else {
+                let mut budget = self
+                    .budgets
+                    .get(spender)
+                    .unwrap_or_else(|| Budget::new(daily_limit));
+                budget.daily_limit = daily_limit;
+                self.budgets.insert(spender, &budget);
+            }
+            self.env().emit_event(LimitSet {
+                spender,
+                daily_limit,
+            });
+            Ok(())
+        }
+
+        /// Sends `amount` of the managed tokens to `to`, within the caller's limit.
+        #[ink(message)]
+        pub fn spend(&mut self, to: AccountId, amount: Balance) -> Result<()> {
+            let spender = self.env().caller();
+            let before = self.charge(spender, amount)?;
+            if self.token.transfer(to, amount).is_err() {
+                self.budgets.insert(spender, &before);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(Spent {
+                spender,
+                to,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Records `amount` against the budget of `spender`, and returns the budget
+        /// as it was before.
+        fn charge(&mut self, spender: AccountId, amount: Balance) -> Result<Budget> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let before = self.budgets.get(spender).ok_or(Error::NotSpender)?;
+            let mut budget = before;
+            budget.record(self.hour(), amount)?;
+            self.budgets.insert(spender, &budget);
+            Ok(before)
+        }
+
+        /// Returns the current hour, counted from the epoch.
+        fn hour(&self) -> u64 {
+            self.env().block_timestamp() / HOUR
+        }
+
+        /// Returns `NotOwner` unless the caller is the owner.
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// check the limits through `charge`, which `spend` runs before sending.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        fn set_hour(hour: u64) {
+            fixtures::set_block_timestamp(hour * HOUR);
+        }
+
+        /// A manager owned by alice, where bob may spend 100 a day.
+        fn manager() -> SpendingManager {
+            let accounts = fixtures::accounts();
+            let mut manager = SpendingManager::new(AccountId::from([0x70; 32]));
+            assert_eq!(manager.set_limit(accounts.bob, 100), Ok(()));
+            manager
+        }
+
+        fn charge(manager: &mut SpendingManager, amount: Balance) -> Result<()> {
+            let bob = fixtures::accounts().bob;
+            manager.charge(bob, amount).map(|_| ())
+        }
+
+        #[ink::test]
+        fn spending_leaves_the_window_after_a_day() {
+            let bob = fixtures::accounts().bob;
+            let mut manager = manager();
+            set_hour(1);
+            assert_eq!(charge(&mut manager, 60), Ok(()));
+            set_hour(11);
+            assert_eq!(charge(&mut manager, 40), Ok(()));
+
+            // Just before the first spend is a day old, nothing is left.
+            set_hour(24);
+            assert_eq!(manager.available(bob), 0);
+            assert_eq!(charge(&mut manager, 1), Err(Error::LimitExceeded));
+
+            set_hour(25);
+            assert_eq!(manager.available(bob), 60);
+            assert_eq!(charge(&mut manager, 60), Ok(()));
+            set_hour(35);
+            assert_eq!(manager.available(bob), 40);
+        }
+
+        #[ink::test]
+        fn no_double_spend_across_midnight() {
+            let mut manager = manager();
+            set_hour(23);
+            assert_eq!(charge(&mut manager, 100), Ok(()));
+            set_hour(24);
+            assert_eq!(charge(&mut manager, 1), Err(Error::LimitExceeded));
+        }
+
+        #[ink::test]
+        fn spends_within_an_hour_add_up() {
+            let bob = fixtures::accounts().bob;
+            let mut manager = manager();
+            set_hour(5);
+            assert_eq!(charge(&mut manager, 30), Ok(()));
+            assert_eq!(charge(&mut manager, 30), Ok(()));
+            assert_eq!(manager.available(bob), 40);
+            assert_eq!(charge(&mut manager, 41), Err(Error::LimitExceeded));
+            // A slot is cleared when it is reused for a later hour.
+            set_hour(5 + 24 * 3);
+            assert_eq!(charge(&mut manager, 100), Ok(()));
+            assert_eq!(manager.available(bob), 0);
+        }
+
+        #[ink::test]
+        fn budget_sums_only_recent_slots() {
+            let mut budget = Budget::new(100);
+            assert_eq!(budget.record(0, 10), Ok(()));
+            assert_eq!(budget.record(23, 20), Ok(()));
+            assert_eq!(budget.spent_in_window(23), 30);
+            assert_eq!(budget.spent_in_window(24), 20);
+            assert_eq!(budget.spent_in_window(47), 0);
+            assert_eq!(budget.record(48, 100), Ok(()));
+            assert_eq!(budget.spent, {
+                let mut spent = [0; WINDOW_HOURS];
+                spent[0] = 100;
+                spent[23] = 20;
+                spent
+            });
+        }
+
+        #[ink::test]
+        fn lowering_the_limit_counts_past_spending() {
+            let accounts = fixtures::accounts();
+            let mut manager = manager();
+            assert_eq!(charge(&mut manager, 70), Ok(()));
+            assert_eq!(manager.set_limit(accounts.bob, 50), Ok(()));
+            assert_eq!(manager.available(accounts.bob), 0);
+            assert_eq!(manager.set_limit(accounts.bob, 120), Ok(()));
+            assert_eq!(manager.available(accounts.bob), 50);
+
+            assert_eq!(manager.set_limit(accounts.bob, 0), Ok(()));
+            assert_eq!(manager.limit_of(accounts.bob), None);
+            assert_eq!(charge(&mut manager, 1), Err(Error::NotSpender));
+        }
+
+        #[ink::test]
+        fn only_owner_manages_limits() {
+            let accounts = fixtures::accounts();
+            let mut manager = manager();
+            assert_eq!(charge(&mut manager, 0), Err(Error::ZeroAmount));
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(manager.set_limit(accounts.bob, 1_000), Err(Error::NotOwner));
+            assert_eq!(manager.withdraw(10), Err(Error::NotOwner));
+            assert_eq!(
+                manager.spend(accounts.charlie, 101),
+                Err(Error::LimitExceeded)
+            );
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(manager.spend(accounts.charlie, 1), Err(Error::NotSpender));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract (it depends on `crates/utils` for `SafeErc20`):
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Deploy an ERC-20 token first (examples/demo-contracts/erc20.rs)
+//    - Upload the generated .contract file and deploy it with the token address
+//
+// 4. Interact with the contract:
+//    - Approve the manager on the token, then use "deposit"
+//    - As owner, use "setLimit" for each spender, and "withdraw" to take tokens back
+//    - Spenders use "spend" and check "available"
@@ -0,0 +1,945 @@
+// examples/demo-contracts/staking.rs
+//
+// A staking contract example for Polkadot using ink!
+// Stakers lock an ERC-20 stake token in this contract and earn a reward token,
+// emitted block by block along a schedule fixed at deployment and shared among
+// stakers in proportion to their stake.
+//
+// Emission schedules
+// ------------------
+// The schedule is an `EmissionCurve` from the utils crate's `emission` module: a
+// constant rate, a rate halving every N blocks, or a piecewise rate. Blocks are
+// counted from the one the contract was deployed in.
+//
+// Reward accounting
+// -----------------
+// Paying every staker at every block would cost gas proportional to the number of
+// stakers. Instead the contract keeps one accumulator, the reward per staked token
+// since deployment: whenever someone stakes, unstakes or claims, the tokens emitted
+// since the last update are divided by the total stake and added to it. A staker's
+// rewards are then their stake times the growth of the accumulator since they last
+// settled, which takes O(1) work per operation. Tokens emitted while nothing is
+// staked are not distributed. The accumulator is a `FixedU128`, and every division
+// rounds down, so the contract never owes more than it emitted.
+//
+// Unbonding
+// ---------
+// As on Polkadot's relay chain, unstaking does not return tokens at once. `unstake`
+// stops the amount earning rewards and queues it as an unbonding chunk that
+// matures `unbonding_period` blocks later; `withdraw_unbonded` then sends back every
+// matured chunk. Any part of a stake can be unbonded, and a staker can have up to
+// `MAX_UNBONDING_CHUNKS` chunks in flight; chunks maturing in the same block are
+// merged.
+//
+// Slashing
+// --------
+// The admin can appoint a slasher, typically a governance or fraud-proof contract,
+// which may slash a share of a staker's bond, given in basis points, to the
+// treasury. The share is taken from the stake and from every unbonding chunk, so
+// unstaking does not escape a slash that comes in during the unbonding period.
+// The staker's rewards are settled first, so they keep what the slashed tokens
+// earned until then, and the smaller total stake shares later emissions among
+// fewer tokens. Each part rounds down, in the staker's favour.
+//
+// Token calls go through `SafeErc20`. Stakes are pulled before they are recorded,
+// and records are restored if sending tokens out fails. The contract must hold
+// enough reward tokens to pay what the schedule emits.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod staking {
+    use astranet_utils::{
+        emission::EmissionCurve,
+        fixed_point::{mul_div, FixedU128},
+        safe_erc20::SafeErc20,
+        trace,
+    };
+    use ink_prelude::vec::Vec;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Most unbonding chunks a staker can have in flight.
+    pub const MAX_UNBONDING_CHUNKS: usize = 16;
+
+    /// Basis points in a whole bond.
+    pub const BPS: u128 = 10_000;
+
+    /// Returns `fraction_bps` basis points of `amount`, rounded down.
+    pub fn share_of(amount: Balance, fraction_bps: u128) -> Balance {
+        // `fraction_bps <= BPS`, so the share never exceeds `amount`.
+        mul_div(amount, fraction_bps, BPS).unwrap_or(amount)
+    }
+
+    /// The staking error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the amount is zero.
+        ZeroAmount,
+        /// Returned if the caller has staked less than the amount.
+        InsufficientStake,
+        /// Returned if the caller has no rewards to claim.
+        NothingToClaim,
+        /// Returned if a token transfer failed.
+        TransferFailed,
+        /// Returned if the caller already has `MAX_UNBONDING_CHUNKS` chunks in
+        /// flight.
+        TooManyUnbondingChunks,
+        /// Returned if none of the caller's unbonding chunks has matured.
+        NothingToWithdraw,
+        /// Returned if the caller is not the admin.
+        NotAdmin,
+        /// Returned if the caller is not the slasher.
+        NotSlasher,
+        /// Returned if a slash is zero or more than `BPS` basis points.
+        InvalidFraction,
+        /// Returned if the slash would take nothing from the staker.
+        NothingToSlash,
+    }
+
+    /// The staking result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A staker's position.
+    #[derive(
+        Debug,
+        Default,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        scale::Encode,
+        scale::Decode,
+        SpreadLayout,
+        PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Staker {
+        /// Tokens staked.
+        pub staked: Balance,
+        /// Value of the accumulator when the staker last settled.
+        pub reward_per_token_paid: FixedU128,
+        /// Rewards settled and not claimed yet.
+        pub owed: Balance,
+    }
+
+    /// Tokens on their way out of the pool.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct UnbondingChunk {
+        /// Tokens unbonding.
+        pub amount: Balance,
+        /// Block from which the tokens can be withdrawn.
+        pub unlocks_at: BlockNumber,
+    }
+
+    // Events are mirrored in `crates/events/src/staking.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when tokens are staked.
+    #[ink(event)]
+    pub struct Staked {
+        #[ink(topic)]
+        staker: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when tokens start unbonding.
+    #[ink(event)]
+    pub struct Unstaked {
+        #[ink(topic)]
+        staker: AccountId,
+        amount: Balance,
+        unlocks_at: BlockNumber,
+    }
+
+    /// Event emitted when a staker claims their rewards.
+    #[ink(event)]
+    pub struct RewardsClaimed {
+        #[ink(topic)]
+        staker: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when matured unbonding chunks are withdrawn.
+    #[ink(event)]
+    pub struct Withdrawn {
+        #[ink(topic)]
+        staker: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when the admin appoints or removes the slasher.
+    #[ink(event)]
+    pub struct SlasherSet {
+        slasher: Option<AccountId>,
+    }
+
+    /// Event emitted when part of a staker's bond is slashed to the treasury.
+    #[ink(event)]
+    pub struct Slashed {
+        #[ink(topic)]
+        staker: AccountId,
+        fraction_bps: u128,
+        amount: Balance,
+    }
+
+    /// The staking storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Staking {
+        /// The ERC-20 token stakers lock.
+        stake_token: SafeErc20,
+        /// The ERC-20 token rewards are paid in.
+        reward_token: SafeErc20,
+        /// Rewards emitted per block, counted from `start_block`.
+        curve: EmissionCurve,
+        /// Block the schedule starts at.
+        start_block: BlockNumber,
+        /// Block of the schedule up to which emissions are in the accumulator.
+        last_update: BlockNumber,
+        /// Rewards per staked token emitted since the start.
+        reward_per_token: FixedU128,
+        /// Sum of all stakes.
+        total_staked: Balance,
+        /// Mapping from account to their position.
+        stakers: Mapping<AccountId, Staker>,
+        /// Blocks between unstaking and being able to withdraw.
+        unbonding_period: BlockNumber,
+        /// Mapping from account to their unbonding chunks, by maturity.
+        unbonding: Mapping<AccountId, Vec<UnbondingChunk>>,
+        /// Account allowed to appoint the slasher.
+        admin: AccountId,
+        /// Account allowed to slash, if any.
+        slasher: Option<AccountId>,
+        /// Account slashed tokens are sent to.
+        treasury: AccountId,
+    }
+
+    impl Staking {
+        /// Creates a staking pool for `stake_token`, paying `reward_token` along
+        /// `curve` from the current block on, keeping unstaked tokens for
+        /// `unbonding_period` blocks, and sending slashed tokens to `treasury`.
+        /// The caller becomes the admin.
+        #[ink(constructor)]
+        pub fn new(
+            stake_token: AccountId,
+            reward_token: AccountId,
+            curve: EmissionCurve,
+            unbonding_period: BlockNumber,
+            treasury: AccountId,
+        ) -> Self {
+            assert!(curve.is_valid(), "invalid emission curve");
+            ink_lang::utils::initialize_contract(|staking: &mut Self| {
+                staking.stake_token = SafeErc20::new(stake_token);
+                staking.reward_token = SafeErc20::new(reward_token);
+                staking.curve = curve;
+                staking.start_block = Self::env().block_number();
+                staking.unbonding_period = unbonding_period;
+                staking.admin = Self::env().caller();
+                staking.treasury = treasury;
+            })
+        }
+
+        /// Returns the emission schedule.
+        #[ink(message)]
+        pub fn emission_curve(&self) -> EmissionCurve {
+            self.curve.clone()
+        }
+
+        /// Returns the rewards emitted at the current block.
+        #[ink(message)]
+        pub fn current_emission_rate(&self) -> Balance {
+            self.curve
+                .rate_at(self.schedule_block(self.env().block_number()))
+        }
+
+        /// Returns the sum of all stakes.
+        #[ink(message)]
+        pub fn total_staked(&self) -> Balance {
+            self.total_staked
+        }
+
+        /// Returns the stake of `staker`.
+        #[ink(message)]
+        pub fn staked_of(&self, staker: AccountId) -> Balance {
+            self.staker(staker).staked
+        }
+
+        /// Returns the unbonding period in blocks.
+        #[ink(message)]
+        pub fn unbonding_period(&self) -> BlockNumber {
+            self.unbonding_period
+        }
+
+        /// Returns the unbonding chunks of `staker`, by maturity.
+        #[ink(message)]
+        pub fn unbonding_of(&self, staker: AccountId) -> Vec<UnbondingChunk> {
+            self.unbonding.get(staker).unwrap_or_default()
+        }
+
+        /// Returns the tokens `staker` could withdraw right now.
+        #[ink(message)]
+        pub fn withdrawable(&self, staker: AccountId) -> Balance {
+            let now = self.env().block_number();
+            self.unbonding_of(staker)
+                .iter()
+                .filter(|chunk| chunk.unlocks_at <= now)
+                .map(|chunk| chunk.amount)
+                .sum()
+        }
+
+        /// Returns the slasher, if one is appointed.
+        #[ink(message)]
+        pub fn slasher(&self) -> Option<AccountId> {
+            self.slasher
+        }
+
+        /// Returns the account slashed tokens are sent to.
+        #[ink(message)]
+        pub fn treasury(&self) -> AccountId {
+            self.treasury
+        }
+
+        /// Returns the rewards `staker` could claim right now.
+        #[ink(message)]
+        pub fn pending_rewards(&self, staker: AccountId) -> Balance {
+            self.projected_rewards(staker, self.env().block_number())
+        }
+
+        /// Returns the rewards `staker` will be able to claim at `block`, assuming
+        /// no stake changes until then.
+        #[ink(message)]
+        pub fn projected_rewards(&self, staker: AccountId, block: BlockNumber) -> Balance {
+            let reward_per_token = self.reward_per_token_at(self.schedule_block(block));
+            Self::earned(&self.staker(staker), reward_per_token)
+        }
+
+        /// Stakes `amount` of the stake token, which the caller must have approved
+        /// the contract to spend.
+        #[ink(message)]
+        pub fn stake(&mut self, amount: Balance) -> Result<()> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let staker = self.env().caller();
+            if self
+                .stake_token
+                .transfer_from(staker, self.env().account_id(), amount)
+                .is_err()
+            {
+                return Err(Error::TransferFailed);
+            }
+            self.add_stake(staker, amount);
+            self.env().emit_event(Staked { staker, amount });
+            Ok(())
+        }
+
+        /// Starts unbonding `amount` of the caller's stake, and returns the block
+        /// from which it can be withdrawn. The amount stops earning rewards now.
+        #[ink(message)]
+        pub fn unstake(&mut self, amount: Balance) -> Result<BlockNumber> {
+            let staker = self.env().caller();
+            let unlocks_at = self.unbond(staker, amount)?;
+            self.env().emit_event(Unstaked {
+                staker,
+                amount,
+                unlocks_at,
+            });
+            Ok(unlocks_at)
+        }
+
+        /// Sends the caller every unbonding chunk that has matured, and returns
+        /// how many tokens were sent.
+        #[ink(message)]
+        pub fn withdraw_unbonded(&mut self) -> Result<Balance> {
+            let staker = self.env().caller();
+            let queue = self.unbonding_of(staker);
+            let amount = self.take_unbonded(staker)?;
+            if self.stake_token.transfer(staker, amount).is_err() {
+                self.unbonding.insert(staker, &queue);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(Withdrawn { staker, amount });
+            Ok(amount)
+        }
+
+        /// Sends the caller their rewards, and returns how many were sent.
+        #[ink(message)]
+        pub fn claim_rewards(&mut self) -> Result<Balance> {
+            let staker = self.env().caller();
+            let amount = self.take_rewards(staker)?;
+            if self.reward_token.transfer(staker, amount).is_err() {
+                let mut position = self.staker(staker);
+                position.owed += amount;
+                self.stakers.insert(staker, &position);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(RewardsClaimed { staker, amount });
+            Ok(amount)
+        }
+
+        /// Appoints `slasher`, or removes the slasher if `None`. Only the admin
+        /// can call this.
+        #[ink(message)]
+        pub fn set_slasher(&mut self, slasher: Option<AccountId>) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAdmin);
+            }
+            self.slasher = slasher;
+            self.env().emit_event(SlasherSet { slasher });
+            Ok(())
+        }
+
+        /// Slashes `fraction_bps` basis points of `staker`'s stake and unbonding
+        /// chunks to the treasury, and returns how many tokens were slashed. Only
+        /// the slasher can call this.
+        #[ink(message)]
+        pub fn slash(&mut self, staker: AccountId, fraction_bps: u128) -> Result<Balance> {
+            if self.slasher != Some(self.env().caller()) {
+                return Err(Error::NotSlasher);
+            }
+            let position = self.staker(staker);
+            let queue = self.unbonding_of(staker);
+            let (from_stake, from_unbonding) = self.apply_slash(staker, fraction_bps)?;
+            let amount = from_stake + from_unbonding;
+            if self.stake_token.transfer(self.treasury, amount).is_err() {
+                self.stakers.insert(staker, &position);
+                if !queue.is_empty() {
+                    self.unbonding.insert(staker, &queue);
+                }
+                self.total_staked += from_stake;
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(Slashed {
+                staker,
+                fraction_bps,
+                amount,
+            });
+            Ok(amount)
+        }
+
+        /// Returns the position of `staker`, empty if they never staked.
+        fn staker(&self, staker: AccountId) -> Staker {
+            self.stakers.get(staker).unwrap_or_default()
+        }
+
+        /// Converts a block number to a block of the schedule.
+        fn schedule_block(&self, block: BlockNumber) -> BlockNumber {
+            block.saturating_sub(self.start_block)
+        }
+
+        /// Returns the accumulator as it will be at `schedule_block` if the total
+        /// stake does not change until then.
+        fn reward_per_token_at(&self, schedule_block: BlockNumber) -> FixedU128 {
+            if self.total_staked == 0 || schedule_block <= self.last_update {
+                return self.reward_per_token;
+            }
+            let emitted = self.curve.emitted_between(self.last_update, schedule_block);
+            // Only reachable with absurd emissions; saturating keeps rewards payable
+            // up to that point instead of locking every stake.
+            FixedU128::from_rational(emitted, self.total_staked)
+                .and_then(|increase| self.reward_per_token.checked_add(increase))
+                .unwrap_or_else(|| FixedU128::from_inner(u128::MAX))
+        }
+
+        /// Returns the rewards of `position` once the accumulator is at
+        /// `reward_per_token`.
+        fn earned(position: &Staker, reward_per_token: FixedU128) -> Balance {
+            let growth = reward_per_token
+                .checked_sub(position.reward_per_token_paid)
+                .unwrap_or_default();
+            let fresh = growth
+                .checked_mul_int(position.staked)
+                .unwrap_or(Balance::MAX);
+            position.owed.saturating_add(fresh)
+        }
+
+        /// Brings the accumulator up to the current block, and `staker`'s rewards
+        /// up to the accumulator. Returns the settled position.
+        fn settle(&mut self, staker: AccountId) -> Staker {
+            let now = self.schedule_block(self.env().block_number());
+            self.reward_per_token = self.reward_per_token_at(now);
+            self.last_update = self.last_update.max(now);
+            let mut position = self.staker(staker);
+            position.owed = Self::earned(&position, self.reward_per_token);
+            position.reward_per_token_paid = self.reward_per_token;
+            position
+        }
+
+        /// Records `amount` more stake for `staker`.
+        fn add_stake(&mut self, staker: AccountId, amount: Balance) {
+            let mut position = self.settle(staker);
+            position.staked += amount;
+            self.total_staked += amount;
+            self.stakers.insert(staker, &position);
+        }
+
+        /// Records `amount` less stake for `staker`.
+        fn remove_stake(&mut self, staker: AccountId, amount: Balance) -> Result<()> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let mut position = self.settle(staker);
+            if position.staked < amount {
+                return Err(Error::InsufficientStake);
+            }
+            position.staked -= amount;
+            self.total_staked -= amount;
+            self.stakers.insert(staker, &position);
+            Ok(())
+        }
+
+        /// Moves `amount` of `staker`'s stake into their unbonding queue, and
+        /// returns the block it matures at.
+        fn unbond(&mut self, staker: AccountId, amount: Balance) -> Result<BlockNumber> {
+            let unlocks_at = self
+                .env()
+                .block_number()
+                .saturating_add(self.unbonding_period);
+            let mut queue = self.unbonding_of(staker);
+            match queue.last_mut() {
+                Some(last) if last.unlocks_at == unlocks_at => last.amount += amount,
+                _ => {
+                    if queue.len() >= MAX_UNBONDING_CHUNKS {
+                        return Err(Error::TooManyUnbondingChunks);
+                    }
+                    queue.push(UnbondingChunk { amount, unlocks_at });
+                }
+            }
+            trace!("unbond", staker, amount, unlocks_at, chunks = queue.len());
+            self.remove_stake(staker, amount)?;
+            self.unbonding.insert(staker, &queue);
+            Ok(unlocks_at)
+        }
+
+        /// Removes the matured chunks from `staker`'s unbonding queue, and returns
+        /// their sum.
+        fn take_unbonded(&mut self, staker: AccountId) -> Result<Balance> {
+            let now = self.env().block_number();
+            let (matured, pending): (Vec<_>, Vec<_>) = self
+                .unbonding_of(staker)
+                .into_iter()
+                .partition(|chunk| chunk.unlocks_at <= now);
+            trace!(
+                "take_unbonded",
+                staker,
+                now,
+                matured = matured.len(),
+                pending = pending.len(),
+            );
+            if matured.is_empty() {
+                return Err(Error::NothingToWithdraw);
+            }
+            if pending.is_empty() {
+                self.unbonding.remove(staker);
+            } else {
+                self.unbonding.insert(staker, &pending);
+            }
+            Ok(matured.iter().map(|chunk| chunk.amount).sum())
+        }
+
+        /// Takes `fraction_bps` basis points of `staker`'s stake and of each of
+        /// their unbonding chunks, and returns the amounts taken from each.
+        fn apply_slash(
+            &mut self,
+            staker: AccountId,
+            fraction_bps: u128,
+        ) -> Result<(Balance, Balance)> {
+            if fraction_bps == 0 || fraction_bps > BPS {
+                return Err(Error::InvalidFraction);
+            }
+            let mut position = self.settle(staker);
+            let from_stake = share_of(position.staked, fraction_bps);
+            let mut queue = self.unbonding_of(staker);
+            let mut from_unbonding = 0;
+            for chunk in queue.iter_mut() {
+                let share = share_of(chunk.amount, fraction_bps);
+                chunk.amount -= share;
+                from_unbonding += share;
+            }
+            trace!("slash", staker, fraction_bps, from_stake, from_unbonding);
+            if from_stake + from_unbonding == 0 {
+                return Err(Error::NothingToSlash);
+            }
+            position.staked -= from_stake;
+            self.total_staked -= from_stake;
+            self.stakers.insert(staker, &position);
+            queue.retain(|chunk| chunk.amount > 0);
+            if queue.is_empty() {
+                self.unbonding.remove(staker);
+            } else {
+                self.unbonding.insert(staker, &queue);
+            }
+            Ok((from_stake, from_unbonding))
+        }
+
+        /// Settles `staker` and zeroes their owed rewards, returning them.
+        fn take_rewards(&mut self, staker: AccountId) -> Result<Balance> {
+            let mut position = self.settle(staker);
+            let amount = position.owed;
+            if amount == 0 {
+                return Err(Error::NothingToClaim);
+            }
+            position.owed = 0;
+            self.stakers.insert(staker, &position);
+            Ok(amount)
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// drive the bookkeeping behind `stake`, `unstake` and `claim_rewards` directly.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use astranet_utils::emission::EmissionStep;
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        const UNBONDING: BlockNumber = 10;
+
+        fn pool(curve: EmissionCurve) -> Staking {
+            Staking::new(
+                AccountId::from([0x70; 32]),
+                AccountId::from([0x71; 32]),
+                curve,
+                UNBONDING,
+                AccountId::from([0x72; 32]),
+            )
+        }
+
+        fn advance(blocks: u32) {
+            for _ in 0..blocks {
+                ink_env::test::advance_block::<Env>();
+            }
+        }
+
+        fn now() -> BlockNumber {
+            ink_env::block_number::<Env>()
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "invalid emission curve")]
+        fn new_rejects_invalid_curves() {
+            pool(EmissionCurve::Halving {
+                initial_per_block: 1,
+                period: 0,
+            });
+        }
+
+        #[ink::test]
+        fn constant_rewards_are_shared_by_stake() {
+            let accounts = fixtures::accounts();
+            let mut staking = pool(EmissionCurve::Constant { per_block: 10 });
+            staking.add_stake(accounts.bob, 100);
+            advance(5);
+            assert_eq!(staking.pending_rewards(accounts.bob), 50);
+            assert_eq!(staking.projected_rewards(accounts.bob, now() + 5), 100);
+
+            staking.add_stake(accounts.charlie, 300);
+            // From now on bob earns a quarter of each block's 10.
+            assert_eq!(staking.projected_rewards(accounts.bob, now() + 10), 75);
+            assert_eq!(staking.projected_rewards(accounts.charlie, now() + 10), 75);
+            advance(10);
+            assert_eq!(staking.pending_rewards(accounts.bob), 75);
+            assert_eq!(staking.take_rewards(accounts.charlie), Ok(75));
+            assert_eq!(staking.pending_rewards(accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn halving_projection_matches_accrual() {
+            let accounts = fixtures::accounts();
+            let mut staking = pool(EmissionCurve::Halving {
+                initial_per_block: 100,
+                period: 4,
+            });
+            staking.add_stake(accounts.bob, 7);
+            // 4 * 100 + 4 * 50 + 2 * 25, less rounding of the per-token share.
+            let projected = staking.projected_rewards(accounts.bob, now() + 10);
+            assert_eq!(projected, 649);
+            advance(10);
+            assert_eq!(staking.current_emission_rate(), 25);
+            assert_eq!(staking.pending_rewards(accounts.bob), projected);
+            assert_eq!(staking.take_rewards(accounts.bob), Ok(projected));
+        }
+
+        #[ink::test]
+        fn piecewise_projection_matches_accrual() {
+            let accounts = fixtures::accounts();
+            let mut staking = pool(EmissionCurve::Piecewise(vec![
+                EmissionStep {
+                    from: 3,
+                    per_block: 20,
+                },
+                EmissionStep {
+                    from: 6,
+                    per_block: 5,
+                },
+            ]));
+            staking.add_stake(accounts.bob, 100);
+            assert_eq!(staking.current_emission_rate(), 0);
+            // 3 * 20 + 2 * 5
+            assert_eq!(staking.projected_rewards(accounts.bob, now() + 8), 70);
+            advance(8);
+            assert_eq!(staking.pending_rewards(accounts.bob), 70);
+        }
+
+        #[ink::test]
+        fn emissions_with_nothing_staked_are_not_distributed() {
+            let accounts = fixtures::accounts();
+            let mut staking = pool(EmissionCurve::Constant { per_block: 10 });
+            advance(5);
+            staking.add_stake(accounts.bob, 100);
+            advance(1);
+            assert_eq!(staking.pending_rewards(accounts.bob), 10);
+        }
+
+        #[ink::test]
+        fn unstaking_keeps_earned_rewards() {
+            let accounts = fixtures::accounts();
+            let mut staking = pool(EmissionCurve::Constant { per_block: 10 });
+            staking.add_stake(accounts.bob, 100);
+            advance(3);
+            assert_eq!(
+                staking.remove_stake(accounts.bob, 0),
+                Err(Error::ZeroAmount)
+            );
+            assert_eq!(
+                staking.remove_stake(accounts.bob, 101),
+                Err(Error::InsufficientStake)
+            );
+            assert_eq!(staking.remove_stake(accounts.bob, 100), Ok(()));
+            assert_eq!(staking.total_staked(), 0);
+            advance(3);
+            assert_eq!(staking.pending_rewards(accounts.bob), 30);
+            assert_eq!(staking.take_rewards(accounts.bob), Ok(30));
+            assert_eq!(
+                staking.take_rewards(accounts.bob),
+                Err(Error::NothingToClaim)
+            );
+        }
+
+        #[ink::test]
+        fn unbonded_tokens_mature_after_the_period() {
+            let accounts = fixtures::accounts();
+            let mut staking = pool(EmissionCurve::Constant { per_block: 10 });
+            staking.add_stake(accounts.bob, 100);
+            advance(2);
+            assert_eq!(staking.unbond(accounts.bob, 30), Ok(2 + UNBONDING));
+            assert_eq!(staking.staked_of(accounts.bob), 70);
+            assert_eq!(staking.total_staked(), 70);
+
+            advance(UNBONDING - 1);
+            assert_eq!(staking.withdrawable(accounts.bob), 0);
+            assert_eq!(
+                staking.take_unbonded(accounts.bob),
+                Err(Error::NothingToWithdraw)
+            );
+            advance(1);
+            assert_eq!(staking.withdrawable(accounts.bob), 30);
+            assert_eq!(staking.take_unbonded(accounts.bob), Ok(30));
+            assert!(staking.unbonding_of(accounts.bob).is_empty());
+            assert_eq!(
+                staking.take_unbonded(accounts.bob),
+                Err(Error::NothingToWithdraw)
+            );
+        }
+
+        #[ink::test]
+        fn unbonding_tokens_stop_earning() {
+            let accounts = fixtures::accounts();
+            let mut staking = pool(EmissionCurve::Constant { per_block: 10 });
+            staking.add_stake(accounts.bob, 100);
+            staking.add_stake(accounts.charlie, 100);
+            advance(2);
+            assert!(staking.unbond(accounts.bob, 100).is_ok());
+            advance(2);
+            // Two blocks shared, then two blocks to charlie alone.
+            assert_eq!(staking.pending_rewards(accounts.bob), 10);
+            assert_eq!(staking.pending_rewards(accounts.charlie), 30);
+            assert_eq!(
+                staking.unbond(accounts.bob, 1),
+                Err(Error::InsufficientStake)
+            );
+            assert_eq!(staking.unbond(accounts.charlie, 0), Err(Error::ZeroAmount));
+            assert_eq!(staking.unbonding_of(accounts.charlie), vec![]);
+        }
+
+        #[ink::test]
+        fn queue_keeps_chunks_in_maturity_order() {
+            let accounts = fixtures::accounts();
+            let mut staking = pool(EmissionCurve::Constant { per_block: 10 });
+            staking.add_stake(accounts.bob, 100);
+            assert!(staking.unbond(accounts.bob, 10).is_ok());
+            assert!(staking.unbond(accounts.bob, 5).is_ok());
+            advance(3);
+            assert!(staking.unbond(accounts.bob, 20).is_ok());
+            assert_eq!(
+                staking.unbonding_of(accounts.bob),
+                vec![
+                    UnbondingChunk {
+                        amount: 15,
+                        unlocks_at: UNBONDING,
+                    },
+                    UnbondingChunk {
+                        amount: 20,
+                        unlocks_at: 3 + UNBONDING,
+                    },
+                ]
+            );
+
+            // Only the first chunk has matured; the second stays queued.
+            advance(UNBONDING - 3);
+            assert_eq!(staking.take_unbonded(accounts.bob), Ok(15));
+            assert_eq!(staking.unbonding_of(accounts.bob).len(), 1);
+            advance(3);
+            assert_eq!(staking.take_unbonded(accounts.bob), Ok(20));
+            assert_eq!(staking.staked_of(accounts.bob), 65);
+        }
+
+        #[ink::test]
+        fn queue_is_bounded() {
+            let accounts = fixtures::accounts();
+            let mut staking = pool(EmissionCurve::Constant { per_block: 10 });
+            staking.add_stake(accounts.bob, 100);
+            for _ in 0..MAX_UNBONDING_CHUNKS {
+                assert!(staking.unbond(accounts.bob, 1).is_ok());
+                advance(1);
+            }
+            assert_eq!(
+                staking.unbond(accounts.bob, 1),
+                Err(Error::TooManyUnbondingChunks)
+            );
+            assert_eq!(staking.staked_of(accounts.bob), 84);
+            // Withdrawing matured chunks makes room again.
+            advance(UNBONDING);
+            assert!(staking.take_unbonded(accounts.bob).is_ok());
+            assert!(staking.unbond(accounts.bob, 1).is_ok());
+        }
+
+        #[test]
+        fn shares_round_down() {
+            assert_eq!(share_of(100, 5_000), 50);
+            assert_eq!(share_of(100, 3_333), 33);
+            assert_eq!(share_of(1, 9_999), 0);
+            assert_eq!(share_of(7, BPS), 7);
+            assert_eq!(share_of(Balance::MAX, BPS), Balance::MAX);
+            assert_eq!(share_of(Balance::MAX, 5_000), Balance::MAX / 2);
+        }
+
+        #[ink::test]
+        fn only_the_admin_appoints_the_slasher() {
+            let accounts = fixtures::accounts();
+            let mut staking = pool(EmissionCurve::Constant { per_block: 10 });
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                staking.set_slasher(Some(accounts.bob)),
+                Err(Error::NotAdmin)
+            );
+            assert_eq!(staking.slash(accounts.charlie, 100), Err(Error::NotSlasher));
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(staking.set_slasher(Some(accounts.bob)), Ok(()));
+            assert_eq!(staking.slasher(), Some(accounts.bob));
+            assert_eq!(staking.slash(accounts.charlie, 100), Err(Error::NotSlasher));
+            assert_eq!(staking.set_slasher(None), Ok(()));
+            assert_eq!(staking.slasher(), None);
+        }
+
+        #[ink::test]
+        fn slashing_takes_a_share_of_stake_and_unbonding() {
+            let accounts = fixtures::accounts();
+            let mut staking = pool(EmissionCurve::Constant { per_block: 10 });
+            staking.add_stake(accounts.bob, 100);
+            assert!(staking.unbond(accounts.bob, 30).is_ok());
+            advance(1);
+            assert!(staking.unbond(accounts.bob, 1).is_ok());
+
+            assert_eq!(
+                staking.apply_slash(accounts.bob, 0),
+                Err(Error::InvalidFraction)
+            );
+            assert_eq!(
+                staking.apply_slash(accounts.bob, BPS + 1),
+                Err(Error::InvalidFraction)
+            );
+            // A quarter of 69 staked, 30 and 1 unbonding, each rounded down.
+            assert_eq!(staking.apply_slash(accounts.bob, 2_500), Ok((17, 7)));
+            assert_eq!(staking.staked_of(accounts.bob), 52);
+            assert_eq!(staking.total_staked(), 52);
+            assert_eq!(
+                staking.unbonding_of(accounts.bob),
+                vec![
+                    UnbondingChunk {
+                        amount: 23,
+                        unlocks_at: UNBONDING,
+                    },
+                    UnbondingChunk {
+                        amount: 1,
+                        unlocks_at: 1 + UNBONDING,
+                    },
+                ]
+            );
+            // A full slash empties the bond and drops the emptied chunks.
+            assert_eq!(staking.apply_slash(accounts.bob, BPS), Ok((52, 24)));
+            assert!(staking.unbonding_of(accounts.bob).is_empty());
+            assert_eq!(
+                staking.apply_slash(accounts.bob, BPS),
+                Err(Error::NothingToSlash)
+            );
+        }
+
+        #[ink::test]
+        fn slashing_keeps_rewards_earned_before_it() {
+            let accounts = fixtures::accounts();
+            let mut staking = pool(EmissionCurve::Constant { per_block: 10 });
+            staking.add_stake(accounts.bob, 100);
+            staking.add_stake(accounts.charlie, 100);
+            advance(2);
+            assert_eq!(staking.apply_slash(accounts.bob, 5_000), Ok((50, 0)));
+            advance(3);
+            // Two blocks shared evenly, then three shared 50 to 100.
+            assert_eq!(staking.pending_rewards(accounts.bob), 20);
+            assert_eq!(staking.pending_rewards(accounts.charlie), 30);
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract (it depends on `crates/utils` for `EmissionCurve`,
+//    `FixedU128` and `SafeErc20`):
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Deploy the stake and reward tokens first (examples/demo-contracts/erc20.rs)
+//    - Upload the generated .contract file and deploy it with both tokens, an
+//      emission curve, the unbonding period in blocks and the treasury account
+//    - Transfer enough reward tokens to the staking contract to cover the schedule
+//
+// 4. Interact with the contract:
+//    - Approve the staking contract on the stake token, then use "stake"
+//    - Use "pendingRewards" and "projectedRewards" to follow rewards
+//    - Use "claimRewards" to collect rewards
+//    - Use "unstake" to start unbonding, and "withdrawUnbonded" once it has matured
+//    - As admin, use "setSlasher" to let a governance contract or account "slash"
@@ -0,0 +1,393 @@
+// examples/demo-contracts/storage_layouts.rs
+//
+// A storage layout teaching contract for Polkadot using ink!
+// The same gradebook (an instructor, a passing score, and a score per learner) is
+// stored three times, each in a different layout, with the same messages for each
+// so that their weights can be compared call for call.
+//
+// The three layouts
+// -----------------
+// ink! 3.4 loads every plain field of the storage struct at the start of each
+// message, whichever fields the message touches, and stores them back at the end
+// of a mutating one. Only a `Mapping` entry is read and written on demand.
+//
+// `spread_*`: each field of the gradebook is a plain storage field in its own cell,
+// and the scores are one `Vec` in one cell. Every message of the contract pays to
+// load all of them, including the `packed_*` and `mapped_*` ones; a `spread_*`
+// read looks free only because that cost was already paid.
+//
+// `packed_*`: the whole gradebook is one value in one cell, kept as the only entry
+// of a `Mapping` with the key `()` so that only the `packed_*` messages load it.
+// Reading just the passing score still decodes every learner's score, and a write
+// encodes and stores all of them again. The cost grows with the number of learners.
+//
+// `mapped_*`: the scalar fields are plain fields, and the scores are a `Mapping`
+// with one cell per learner at a key hashed from the learner's account. Reading or
+// writing a score costs the same however many learners there are; the price is
+// that the entries cannot be iterated, so the number of learners is kept separately.
+//
+// Comparing weights
+// -----------------
+// Deploy, call `seed` with a few hundred learners, and dry-run the same message
+// for each layout, e.g. `packedScoreOf` and `mappedScoreOf` for the same learner:
+// the UI shows the weight each dry run consumed. Then deploy a second copy, seed it
+// with a handful of learners, and dry-run `mappedScoreOf` there to see what the
+// `spread_*` scores add to every call. This repository has no benchmark harness to
+// automate the comparison, and ink! 3 has no end-to-end test framework, so it is
+// done by hand; the tests below check the layouts through the storage metadata.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod storage_layouts {
+    use ink_prelude::vec::Vec;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Most learners the spread and packed layouts hold. Their scores share one
+    /// cell, and a cell's value has a size limit.
+    pub const MAX_LEARNERS: u32 = 256;
+
+    /// The gradebook as a single value, as the packed layout stores it.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Gradebook {
+        /// Account allowed to record scores.
+        pub instructor: AccountId,
+        /// Lowest passing score.
+        pub passing_score: u32,
+        /// Every learner's score, in the order they were first recorded.
+        pub scores: Vec<(AccountId, u32)>,
+    }
+
+    /// The storage layouts error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the instructor.
+        NotInstructor,
+        /// Returned if a layout already holds `MAX_LEARNERS` learners.
+        TooManyLearners,
+    }
+
+    /// The storage layouts result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// The storage layouts storage items. Each prefix is one complete copy of the
+    /// gradebook.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct StorageLayouts {
+        /// Account allowed to record scores, in its own cell.
+        spread_instructor: AccountId,
+        /// Lowest passing score, in its own cell.
+        spread_passing_score: u32,
+        /// Every learner's score, together in one cell.
+        spread_scores: Vec<(AccountId, u32)>,
+        /// The whole gradebook in one cell, under the key `()`.
+        packed: Mapping<(), Gradebook>,
+        /// Account allowed to record scores, in its own cell.
+        mapped_instructor: AccountId,
+        /// Lowest passing score, in its own cell.
+        mapped_passing_score: u32,
+        /// Mapping from learner to score, one cell per learner.
+        mapped_scores: Mapping<AccountId, u32>,
+        /// Number of learners in `mapped_scores`.
+        mapped_learners: u32,
+    }
+
+    impl StorageLayouts {
+        /// Creates three empty copies of a gradebook with `passing_score`, with
+        /// the caller as instructor.
+        #[ink(constructor)]
+        pub fn new(passing_score: u32) -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                let instructor = Self::env().caller();
+                contract.spread_instructor = instructor;
+                contract.spread_passing_score = passing_score;
+                contract.packed.insert(
+                    (),
+                    &Gradebook {
+                        instructor,
+                        passing_score,
+                        scores: Vec::new(),
+                    },
+                );
+                contract.mapped_instructor = instructor;
+                contract.mapped_passing_score = passing_score;
+            })
+        }
+
+        /// Records a score of zero for `count` made-up learners in every layout,
+        /// so that the weights can be compared at a realistic size. Learner `i`
+        /// is the account whose first four bytes are `i` in little endian.
+        #[ink(message)]
+        pub fn seed(&mut self, count: u32) -> Result<()> {
+            for i in 0..count {
+                let learner = seeded_learner(i);
+                self.spread_set_score(learner, 0)?;
+                self.packed_set_score(learner, 0)?;
+                self.mapped_set_score(learner, 0)?;
+            }
+            Ok(())
+        }
+
+        /// Returns the passing score from the spread layout.
+        #[ink(message)]
+        pub fn spread_passing_score(&self) -> u32 {
+            self.spread_passing_score
+        }
+
+        /// Returns the score of `learner` from the spread layout.
+        #[ink(message)]
+        pub fn spread_score_of(&self, learner: AccountId) -> Option<u32> {
+            score_in(&self.spread_scores, learner)
+        }
+
+        /// Records `score` for `learner` in the spread layout.
+        #[ink(message)]
+        pub fn spread_set_score(&mut self, learner: AccountId, score: u32) -> Result<()> {
+            if self.env().caller() != self.spread_instructor {
+                return Err(Error::NotInstructor);
+            }
+            set_score_in(&mut self.spread_scores, learner, score)
+        }
+
+        /// Returns the passing score from the packed layout.
+        #[ink(message)]
+        pub fn packed_passing_score(&self) -> u32 {
+            self.gradebook().passing_score
+        }
+
+        /// Returns the score of `learner` from the packed layout.
+        #[ink(message)]
+        pub fn packed_score_of(&self, learner: AccountId) -> Option<u32> {
+            score_in(&self.gradebook().scores, learner)
+        }
+
+        /// Records `score` for `learner` in the packed layout.
+        #[ink(message)]
+        pub fn packed_set_score(&mut self, learner: AccountId, score: u32) -> Result<()> {
+            let mut gradebook = self.gradebook();
+            if self.env().caller() != gradebook.instructor {
+                return Err(Error::NotInstructor);
+            }
+            set_score_in(&mut gradebook.scores, learner, score)?;
+            self.packed.insert((), &gradebook);
+            Ok(())
+        }
+
+        /// Returns the passing score from the mapped layout.
+        #[ink(message)]
+        pub fn mapped_passing_score(&self) -> u32 {
+            self.mapped_passing_score
+        }
+
+        /// Returns the score of `learner` from the mapped layout.
+        #[ink(message)]
+        pub fn mapped_score_of(&self, learner: AccountId) -> Option<u32> {
+            self.mapped_scores.get(learner)
+        }
+
+        /// Records `score` for `learner` in the mapped layout.
+        #[ink(message)]
+        pub fn mapped_set_score(&mut self, learner: AccountId, score: u32) -> Result<()> {
+            if self.env().caller() != self.mapped_instructor {
+                return Err(Error::NotInstructor);
+            }
+            if !self.mapped_scores.contains(learner) {
+                self.mapped_learners += 1;
+            }
+            self.mapped_scores.insert(learner, &score);
+            Ok(())
+        }
+
+        /// Returns the number of learners in the mapped layout.
+        #[ink(message)]
+        pub fn mapped_learners(&self) -> u32 {
+            self.mapped_learners
+        }
+
+        /// Loads the packed gradebook, which the constructor always stores.
+        fn gradebook(&self) -> Gradebook {
+            self.packed.get(()).expect("stored by the constructor")
+        }
+    }
+
+    /// Returns the made-up learner number `i` used by `seed`.
+    pub fn seeded_learner(i: u32) -> AccountId {
+        let mut bytes = [0u8; 32];
+        bytes[..4].copy_from_slice(&i.to_le_bytes());
+        AccountId::from(bytes)
+    }
+
+    /// Returns the score of `learner` in a list of scores.
+    fn score_in(scores: &[(AccountId, u32)], learner: AccountId) -> Option<u32> {
+        scores
+            .iter()
+            .find(|(account, _)| *account == learner)
+            .map(|(_, score)| *score)
+    }
+
+    /// Records `score` for `learner` in a list of scores holding at most
+    /// `MAX_LEARNERS`.
+    fn set_score_in(
+        scores: &mut Vec<(AccountId, u32)>,
+        learner: AccountId,
+        score: u32,
+    ) -> Result<()> {
+        if let Some(entry) = scores.iter_mut().find(|(account, _)| *account == learner) {
+            entry.1 = score;
+        } else if scores.len() as u32 >= MAX_LEARNERS {
+            return Err(Error::TooManyLearners);
+        } else {
+            scores.push((learner, score));
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+        use ink_metadata::layout::Layout;
+        use ink_primitives::Key;
+        use ink_storage::traits::{KeyPtr, StorageLayout};
+
+        /// Returns the kind of layout of each storage field, by name: "cell" for
+        /// a value in a single cell, "mapping" for a `Mapping`, whose entries live
+        /// at hashed keys and are only read on demand.
+        fn field_layouts() -> Vec<(String, &'static str)> {
+            let mut key_ptr = KeyPtr::from(Key::from([0x00; 32]));
+            match <StorageLayouts as StorageLayout>::layout(&mut key_ptr) {
+                Layout::Struct(layout) => layout
+                    .fields()
+                    .iter()
+                    .map(|field| {
+                        let kind = match field.layout() {
+                            Layout::Cell(cell) => {
+                                let info = cell.ty().type_info();
+                                match info.path.segments.last() {
+                                    Some(&"Mapping") => "mapping",
+                                    _ => "cell",
+                                }
+                            }
+                            other => panic!("unexpected layout: {:?}", other),
+                        };
+                        (field.name().unwrap().to_string(), kind)
+                    })
+                    .collect(),
+                other => panic!("storage is not a struct: {:?}", other),
+            }
+        }
+
+        /// Returns the kinds of layout of the fields whose names start with
+        /// `prefix`, in declaration order.
+        fn layouts_of(prefix: &str) -> Vec<&'static str> {
+            field_layouts()
+                .into_iter()
+                .filter(|(name, _)| name.starts_with(prefix))
+                .map(|(_, kind)| kind)
+                .collect()
+        }
+
+        #[ink::test]
+        fn packed_layout_is_one_entry_read_on_demand() {
+            assert_eq!(layouts_of("packed"), ["mapping"]);
+        }
+
+        #[ink::test]
+        fn spread_layout_is_one_cell_per_field() {
+            assert_eq!(layouts_of("spread_"), ["cell", "cell", "cell"]);
+        }
+
+        #[ink::test]
+        fn mapped_layout_hashes_the_scores() {
+            assert_eq!(layouts_of("mapped_"), ["cell", "cell", "mapping", "cell"]);
+            assert!(field_layouts().contains(&("mapped_scores".to_string(), "mapping")));
+        }
+
+        #[ink::test]
+        fn layouts_hold_the_same_data() {
+            let accounts = fixtures::accounts();
+            let mut contract = StorageLayouts::new(50);
+            assert_eq!(contract.seed(3), Ok(()));
+            assert_eq!(contract.packed_set_score(accounts.bob, 70), Ok(()));
+            assert_eq!(contract.spread_set_score(accounts.bob, 70), Ok(()));
+            assert_eq!(contract.mapped_set_score(accounts.bob, 70), Ok(()));
+
+            assert_eq!(contract.packed_passing_score(), 50);
+            assert_eq!(contract.spread_passing_score(), 50);
+            assert_eq!(contract.mapped_passing_score(), 50);
+            for learner in [seeded_learner(2), accounts.bob] {
+                let score = contract.packed_score_of(learner);
+                assert!(score.is_some());
+                assert_eq!(contract.spread_score_of(learner), score);
+                assert_eq!(contract.mapped_score_of(learner), score);
+            }
+            assert_eq!(contract.packed_score_of(accounts.charlie), None);
+            assert_eq!(contract.mapped_score_of(accounts.charlie), None);
+            assert_eq!(contract.mapped_learners(), 4);
+        }
+
+        #[ink::test]
+        fn only_instructor_sets_scores() {
+            let accounts = fixtures::accounts();
+            let mut contract = StorageLayouts::new(50);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(contract.packed_set_score(accounts.bob, 1), Err(Error::NotInstructor));
+            assert_eq!(contract.spread_set_score(accounts.bob, 1), Err(Error::NotInstructor));
+            assert_eq!(contract.mapped_set_score(accounts.bob, 1), Err(Error::NotInstructor));
+            assert_eq!(contract.seed(1), Err(Error::NotInstructor));
+        }
+
+        #[ink::test]
+        fn vec_layouts_are_bounded() {
+            let accounts = fixtures::accounts();
+            let mut contract = StorageLayouts::new(50);
+            assert_eq!(contract.seed(MAX_LEARNERS), Ok(()));
+            assert_eq!(
+                contract.packed_set_score(accounts.bob, 1),
+                Err(Error::TooManyLearners)
+            );
+            assert_eq!(
+                contract.spread_set_score(accounts.bob, 1),
+                Err(Error::TooManyLearners)
+            );
+            // Existing learners can still be updated, and the mapping has no bound.
+            assert_eq!(contract.packed_set_score(seeded_learner(0), 1), Ok(()));
+            assert_eq!(contract.mapped_set_score(accounts.bob, 1), Ok(()));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Upload the generated .contract file and deploy it with a passing score
+//
+// 4. Compare the layouts:
+//    - Use "seed" with, say, 200 learners
+//    - Dry-run "packedScoreOf" and "mappedScoreOf" for the same learner and
+//      compare the weight each consumed
+//    - Do the same with the "...PassingScore" and "...SetScore" messages
+//    - Deploy a second copy, "seed" it with a few learners, and compare its
+//      "mappedScoreOf" weight with the first copy's to see the spread scores'
+//      share of every call
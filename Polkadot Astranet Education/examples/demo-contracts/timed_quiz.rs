@@ -0,0 +1,651 @@
+// examples/demo-contracts/timed_quiz.rs
+//
+// A timed quiz contract example for Polkadot using ink!
+// An author creates a multiple-choice quiz with a deadline, learners submit their
+// answers until then, and once the deadline has passed the author reveals the answer
+// key and every submission is scored on-chain against it.
+//
+// Committing to the answer key
+// ----------------------------
+// Storing the key when the quiz is created would let every learner read it, since
+// contract storage is public. Instead the author commits to it: `create_quiz` takes
+// only `answer_key_hash(contract, author, key, salt)`, the BLAKE2-256 hash of the
+// SCALE-encoded tuple, and the key itself is revealed after the deadline. `reveal`
+// accepts the key only if it hashes to the commitment, so the author cannot change
+// the answers once they have seen the submissions. The salt is 32 random bytes kept
+// with the key: without it, a short quiz has few enough possible keys that learners
+// could hash each of them and find the one committed to. Binding the contract and
+// the author keeps a commitment from being replayed on another deployment or by
+// another author.
+//
+// Answers and scores
+// ------------------
+// An answer is the index of the chosen option, one byte per question. Each learner
+// submits once, before or at the deadline. After the reveal anyone can call `score`
+// for a submission, which counts the answers matching the key and records the score;
+// scoring one submission per call keeps the cost of each call independent of the
+// number of learners.
+//
+// Submissions are public as soon as they are made, so a learner could copy another's
+// answers before the deadline. Quizzes where that matters would have learners commit
+// to their answers too, in the same way as the key.
+//
+// Deadlines
+// ---------
+// A quiz's deadline is the utils crate's inclusive `Deadline`: answers are still
+// accepted at exactly `deadline`, and the key can be revealed the millisecond after.
+// The author then has the contract's `reveal_period` to reveal it, until the quiz's
+// `reveal_by` deadline. An author who never reveals cannot leave a quiz pending
+// forever, nor reveal at a moment of their choosing long after: once `reveal_by` has
+// passed the key is refused and the quiz is abandoned, a final state in which no
+// submission is ever scored, so whoever awards credit for the quiz can set it aside.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod timed_quiz {
+    use astranet_utils::deadline::Deadline;
+    use ink_env::hash::{Blake2x256, CryptoHash};
+    use ink_prelude::vec::Vec;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Identifies a quiz.
+    pub type QuizId = u32;
+
+    /// The timed quiz error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the quiz has no questions.
+        NoQuestions,
+        /// Returned if the deadline has already passed.
+        DeadlineInPast,
+        /// Returned if there is no quiz with the id.
+        QuizNotFound,
+        /// Returned if the quiz deadline has passed.
+        QuizClosed,
+        /// Returned if the quiz deadline has not passed yet.
+        QuizOpen,
+        /// Returned if the number of answers differs from the number of questions.
+        WrongAnswerCount,
+        /// Returned if the caller has already submitted answers to the quiz.
+        AlreadySubmitted,
+        /// Returned if the caller is not the quiz's author.
+        NotAuthor,
+        /// Returned if the answer key has already been revealed.
+        AlreadyRevealed,
+        /// Returned if the answer key and salt do not match the commitment.
+        KeyMismatch,
+        /// Returned if the answer key has not been revealed yet.
+        NotRevealed,
+        /// Returned if the learner has not submitted answers to the quiz.
+        NoSubmission,
+        /// Returned if the submission has already been scored.
+        AlreadyScored,
+        /// Returned if the reveal period of the quiz has passed.
+        RevealPeriodOver,
+        /// Returned if the quiz was abandoned unrevealed.
+        Abandoned,
+    }
+
+    /// The timed quiz result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A quiz and the commitment to its answer key.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Quiz {
+        /// Account that created the quiz and reveals its key.
+        pub author: AccountId,
+        /// `answer_key_hash` of the answer key.
+        pub key_hash: Hash,
+        /// Number of questions, and so of answers in a submission.
+        pub questions: u8,
+        /// Last time submissions are accepted.
+        pub deadline: Deadline,
+        /// Last time the author can reveal the key.
+        pub reveal_by: Deadline,
+        /// Number of submissions received.
+        pub submissions: u32,
+        /// Whether the answer key has been revealed.
+        pub revealed: bool,
+    }
+
+    /// Returns the commitment `author` makes to answer key `key`, with `salt`, when
+    /// creating a quiz at `quiz_contract`.
+    pub fn answer_key_hash(
+        quiz_contract: &AccountId,
+        author: &AccountId,
+        key: &[u8],
+        salt: &[u8; 32],
+    ) -> Hash {
+        let mut output = [0u8; 32];
+        Blake2x256::hash(
+            &scale::Encode::encode(&(quiz_contract, author, key, salt)),
+            &mut output,
+        );
+        Hash::from(output)
+    }
+
+    // Events are mirrored in `crates/events/src/timed_quiz.rs` for off-chain
+    // decoding; keep the order of events and fields in sync with it.
+
+    /// Event emitted when an author creates a quiz.
+    #[ink(event)]
+    pub struct QuizCreated {
+        #[ink(topic)]
+        quiz_id: QuizId,
+        #[ink(topic)]
+        author: AccountId,
+        key_hash: Hash,
+        questions: u8,
+        deadline: Timestamp,
+    }
+
+    /// Event emitted when a learner submits their answers.
+    #[ink(event)]
+    pub struct AnswersSubmitted {
+        #[ink(topic)]
+        quiz_id: QuizId,
+        #[ink(topic)]
+        learner: AccountId,
+        answers: Vec<u8>,
+    }
+
+    /// Event emitted when the author reveals the answer key.
+    #[ink(event)]
+    pub struct KeyRevealed {
+        #[ink(topic)]
+        quiz_id: QuizId,
+        key: Vec<u8>,
+    }
+
+    /// Event emitted when a submission is scored.
+    #[ink(event)]
+    pub struct SubmissionScored {
+        #[ink(topic)]
+        quiz_id: QuizId,
+        #[ink(topic)]
+        learner: AccountId,
+        score: u8,
+    }
+
+    /// The timed quiz storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct TimedQuiz {
+        /// Time the author of a quiz has after its deadline to reveal the key.
+        reveal_period: Timestamp,
+        /// Mapping from quiz id to quiz.
+        quizzes: Mapping<QuizId, Quiz>,
+        /// Mapping from quiz id to its revealed answer key.
+        keys: Mapping<QuizId, Vec<u8>>,
+        /// Mapping from quiz id and learner to their answers.
+        submissions: Mapping<(QuizId, AccountId), Vec<u8>>,
+        /// Mapping from quiz id and learner to their score.
+        scores: Mapping<(QuizId, AccountId), u8>,
+        /// Id the next quiz will get.
+        next_quiz_id: QuizId,
+    }
+
+    impl TimedQuiz {
+        /// Creates a contract without quizzes, whose authors have `reveal_period`
+        /// milliseconds after a quiz's deadline to reveal its key.
+        #[ink(constructor)]
+        pub fn new(reveal_period: Timestamp) -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.reveal_period = reveal_period;
+            })
+        }
+
+        /// Returns the time authors have after a quiz's deadline to reveal its key.
+        #[ink(message)]
+        pub fn reveal_period(&self) -> Timestamp {
+            self.reveal_period
+        }
+
+        /// Returns the quiz with id `quiz_id`, if any.
+        #[ink(message)]
+        pub fn quiz(&self, quiz_id: QuizId) -> Option<Quiz> {
+            self.quizzes.get(quiz_id)
+        }
+
+        /// Returns whether `quiz_id` was abandoned: its reveal period has passed
+        /// without the key being revealed.
+        #[ink(message)]
+        pub fn is_abandoned(&self, quiz_id: QuizId) -> bool {
+            self.quiz(quiz_id).is_some_and(|quiz| {
+                !quiz.revealed && quiz.reveal_by.is_expired(self.env().block_timestamp())
+            })
+        }
+
+        /// Returns the answer key of `quiz_id`, once revealed.
+        #[ink(message)]
+        pub fn answer_key(&self, quiz_id: QuizId) -> Option<Vec<u8>> {
+            self.keys.get(quiz_id)
+        }
+
+        /// Returns the answers `learner` submitted to `quiz_id`, if any.
+        #[ink(message)]
+        pub fn submission(&self, quiz_id: QuizId, learner: AccountId) -> Option<Vec<u8>> {
+            self.submissions.get((quiz_id, learner))
+        }
+
+        /// Returns the score of `learner` on `quiz_id`, once scored.
+        #[ink(message)]
+        pub fn score_of(&self, quiz_id: QuizId, learner: AccountId) -> Option<u8> {
+            self.scores.get((quiz_id, learner))
+        }
+
+        /// Creates a quiz of `questions` questions, taking submissions up to and
+        /// including `deadline`, with the caller as author, and returns its id.
+        /// `key_hash` is `answer_key_hash(this contract, caller, key, salt)`.
+        #[ink(message)]
+        pub fn create_quiz(
+            &mut self,
+            key_hash: Hash,
+            questions: u8,
+            deadline: Timestamp,
+        ) -> Result<QuizId> {
+            if questions == 0 {
+                return Err(Error::NoQuestions);
+            }
+            let deadline = Deadline::at(deadline);
+            if deadline.is_expired(self.env().block_timestamp()) {
+                return Err(Error::DeadlineInPast);
+            }
+            let reveal_by = deadline.extended_by(self.reveal_period);
+            let author = self.env().caller();
+            let quiz_id = self.next_quiz_id;
+            self.next_quiz_id += 1;
+            self.quizzes.insert(
+                quiz_id,
+                &Quiz {
+                    author,
+                    key_hash,
+                    questions,
+                    deadline,
+                    reveal_by,
+                    submissions: 0,
+                    revealed: false,
+                },
+            );
+            self.env().emit_event(QuizCreated {
+                quiz_id,
+                author,
+                key_hash,
+                questions,
+                deadline: deadline.timestamp(),
+            });
+            Ok(quiz_id)
+        }
+
+        /// Submits the caller's `answers` to `quiz_id`, one per question. Accepted
+        /// once per learner, up to and including the deadline.
+        #[ink(message)]
+        pub fn submit(&mut self, quiz_id: QuizId, answers: Vec<u8>) -> Result<()> {
+            let mut quiz = self.quiz(quiz_id).ok_or(Error::QuizNotFound)?;
+            if quiz.deadline.is_expired(self.env().block_timestamp()) {
+                return Err(Error::QuizClosed);
+            }
+            if answers.len() != usize::from(quiz.questions) {
+                return Err(Error::WrongAnswerCount);
+            }
+            let learner = self.env().caller();
+            if self.submissions.contains((quiz_id, learner)) {
+                return Err(Error::AlreadySubmitted);
+            }
+            self.submissions.insert((quiz_id, learner), &answers);
+            quiz.submissions += 1;
+            self.quizzes.insert(quiz_id, &quiz);
+            self.env().emit_event(AnswersSubmitted {
+                quiz_id,
+                learner,
+                answers,
+            });
+            Ok(())
+        }
+
+        /// Reveals the answer key of `quiz_id`, which must match the commitment with
+        /// `salt`. Only the author can reveal, and only after the deadline, up to
+        /// and including the quiz's `reveal_by`.
+        #[ink(message)]
+        pub fn reveal(&mut self, quiz_id: QuizId, key: Vec<u8>, salt: [u8; 32]) -> Result<()> {
+            let mut quiz = self.quiz(quiz_id).ok_or(Error::QuizNotFound)?;
+            if self.env().caller() != quiz.author {
+                return Err(Error::NotAuthor);
+            }
+            let now = self.env().block_timestamp();
+            if !quiz.deadline.is_expired(now) {
+                return Err(Error::QuizOpen);
+            }
+            if quiz.revealed {
+                return Err(Error::AlreadyRevealed);
+            }
+            if quiz.reveal_by.is_expired(now) {
+                return Err(Error::RevealPeriodOver);
+            }
+            let committed = answer_key_hash(&self.env().account_id(), &quiz.author, &key, &salt);
+            if committed != quiz.key_hash {
+                return Err(Error::KeyMismatch);
+            }
+            // A key of the wrong length could not have been committed to in good
+            // faith, but it is checked so that scoring can rely on it.
+            if key.len() != usize::from(quiz.questions) {
+                return Err(Error::WrongAnswerCount);
+            }
+            self.keys.insert(quiz_id, &key);
+            quiz.revealed = true;
+            self.quizzes.insert(quiz_id, &quiz);
+            self.env().emit_event(KeyRevealed { quiz_id, key });
+            Ok(())
+        }
+
+        /// Scores the submission of `learner` to `quiz_id` against the revealed key,
+        /// records the score and returns it. Anyone can call this.
+        #[ink(message)]
+        pub fn score(&mut self, quiz_id: QuizId, learner: AccountId) -> Result<u8> {
+            self.quiz(quiz_id).ok_or(Error::QuizNotFound)?;
+            if self.is_abandoned(quiz_id) {
+                return Err(Error::Abandoned);
+            }
+            let key = self.answer_key(quiz_id).ok_or(Error::NotRevealed)?;
+            let answers = self
+                .submission(quiz_id, learner)
+                .ok_or(Error::NoSubmission)?;
+            if self.scores.contains((quiz_id, learner)) {
+                return Err(Error::AlreadyScored);
+            }
+            let score = answers
+                .iter()
+                .zip(&key)
+                .filter(|(answer, correct)| answer == correct)
+                .count() as u8;
+            self.scores.insert((quiz_id, learner), &score);
+            self.env().emit_event(SubmissionScored {
+                quiz_id,
+                learner,
+                score,
+            });
+            Ok(score)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        use fixtures::{set_block_timestamp as set_time, BLOCK_TIME};
+
+        type Env = ink_env::DefaultEnvironment;
+
+        const DEADLINE: Timestamp = 100 * BLOCK_TIME;
+        const REVEAL_PERIOD: Timestamp = 50 * BLOCK_TIME;
+        const SALT: [u8; 32] = [0x5a; 32];
+
+        fn key() -> Vec<u8> {
+            vec![2, 0, 3, 1]
+        }
+
+        /// The commitment to `key` with `SALT` by `author` on the contract under
+        /// test.
+        fn commitment(author: AccountId, key: &[u8]) -> Hash {
+            answer_key_hash(&ink_env::test::callee::<Env>(), &author, key, &SALT)
+        }
+
+        /// A contract with quiz 0 of four questions by alice, closing at
+        /// `DEADLINE`, and answers from bob (3 right) and charlie (1 right).
+        fn with_submissions() -> TimedQuiz {
+            let accounts = fixtures::accounts();
+            let mut contract = TimedQuiz::new(REVEAL_PERIOD);
+            assert_eq!(
+                contract.create_quiz(commitment(accounts.alice, &key()), 4, DEADLINE),
+                Ok(0)
+            );
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(contract.submit(0, vec![2, 0, 3, 0]), Ok(()));
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(contract.submit(0, vec![1, 1, 3, 0]), Ok(()));
+            fixtures::set_caller(accounts.alice);
+            contract
+        }
+
+        #[ink::test]
+        fn create_quiz_stores_only_the_commitment() {
+            let accounts = fixtures::accounts();
+            let mut contract = TimedQuiz::new(REVEAL_PERIOD);
+            let key_hash = commitment(accounts.alice, &key());
+            assert_eq!(
+                contract.create_quiz(key_hash, 0, DEADLINE),
+                Err(Error::NoQuestions)
+            );
+            assert_eq!(contract.create_quiz(key_hash, 4, DEADLINE), Ok(0));
+            assert_eq!(
+                contract.quiz(0),
+                Some(Quiz {
+                    author: accounts.alice,
+                    key_hash,
+                    questions: 4,
+                    deadline: Deadline::at(DEADLINE),
+                    reveal_by: Deadline::at(DEADLINE + REVEAL_PERIOD),
+                    submissions: 0,
+                    revealed: false,
+                })
+            );
+            assert_eq!(contract.answer_key(0), None);
+            assert_eq!(contract.create_quiz(key_hash, 4, DEADLINE), Ok(1));
+            assert_eq!(ink_env::test::recorded_events().count(), 2);
+
+            set_time(DEADLINE + BLOCK_TIME);
+            assert_eq!(
+                contract.create_quiz(key_hash, 4, DEADLINE),
+                Err(Error::DeadlineInPast)
+            );
+        }
+
+        #[ink::test]
+        fn the_commitment_binds_key_salt_and_author() {
+            let accounts = fixtures::accounts();
+            let contract = ink_env::test::callee::<Env>();
+            let hash = answer_key_hash(&contract, &accounts.alice, &key(), &SALT);
+            assert_ne!(
+                answer_key_hash(&contract, &accounts.alice, &[2, 0, 3, 2], &SALT),
+                hash
+            );
+            assert_ne!(
+                answer_key_hash(&contract, &accounts.alice, &key(), &[0x5b; 32]),
+                hash
+            );
+            assert_ne!(
+                answer_key_hash(&contract, &accounts.bob, &key(), &SALT),
+                hash
+            );
+            assert_ne!(
+                answer_key_hash(&AccountId::from([0x99; 32]), &accounts.alice, &key(), &SALT),
+                hash
+            );
+        }
+
+        #[ink::test]
+        fn submissions_are_accepted_until_the_deadline() {
+            let accounts = fixtures::accounts();
+            let mut contract = with_submissions();
+            assert_eq!(contract.quiz(0).unwrap().submissions, 2);
+            assert_eq!(contract.submission(0, accounts.bob), Some(vec![2, 0, 3, 0]));
+
+            // The deadline itself still counts.
+            set_time(DEADLINE);
+            fixtures::set_caller(accounts.dave);
+            assert_eq!(contract.submit(0, vec![2, 0, 3, 1]), Ok(()));
+            set_time(DEADLINE + BLOCK_TIME);
+            fixtures::set_caller(accounts.eve);
+            assert_eq!(contract.submit(0, vec![2, 0, 3, 1]), Err(Error::QuizClosed));
+            assert_eq!(contract.submission(0, accounts.eve), None);
+            assert_eq!(contract.quiz(0).unwrap().submissions, 3);
+        }
+
+        #[ink::test]
+        fn each_learner_submits_once_with_every_answer() {
+            let accounts = fixtures::accounts();
+            let mut contract = with_submissions();
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                contract.submit(0, vec![2, 0, 3, 1]),
+                Err(Error::AlreadySubmitted)
+            );
+            assert_eq!(contract.submission(0, accounts.bob), Some(vec![2, 0, 3, 0]));
+
+            fixtures::set_caller(accounts.dave);
+            assert_eq!(
+                contract.submit(0, vec![2, 0, 3]),
+                Err(Error::WrongAnswerCount)
+            );
+            assert_eq!(
+                contract.submit(0, vec![2, 0, 3, 1, 0]),
+                Err(Error::WrongAnswerCount)
+            );
+            assert_eq!(
+                contract.submit(1, vec![2, 0, 3, 1]),
+                Err(Error::QuizNotFound)
+            );
+        }
+
+        #[ink::test]
+        fn the_key_is_revealed_after_the_deadline() {
+            let mut contract = with_submissions();
+            assert_eq!(contract.reveal(0, key(), SALT), Err(Error::QuizOpen));
+            set_time(DEADLINE);
+            assert_eq!(contract.reveal(0, key(), SALT), Err(Error::QuizOpen));
+
+            set_time(DEADLINE + BLOCK_TIME);
+            assert_eq!(contract.reveal(0, key(), SALT), Ok(()));
+            assert_eq!(contract.answer_key(0), Some(key()));
+            assert!(contract.quiz(0).unwrap().revealed);
+            assert_eq!(contract.reveal(0, key(), SALT), Err(Error::AlreadyRevealed));
+        }
+
+        #[ink::test]
+        fn the_key_is_revealed_until_the_reveal_period_ends() {
+            let mut contract = with_submissions();
+            // `reveal_by` itself still counts.
+            set_time(DEADLINE + REVEAL_PERIOD);
+            assert!(!contract.is_abandoned(0));
+            assert_eq!(contract.reveal(0, key(), SALT), Ok(()));
+            set_time(DEADLINE + REVEAL_PERIOD + BLOCK_TIME);
+            assert!(!contract.is_abandoned(0));
+        }
+
+        #[ink::test]
+        fn a_quiz_left_unrevealed_is_abandoned() {
+            let accounts = fixtures::accounts();
+            let mut contract = with_submissions();
+            set_time(DEADLINE + REVEAL_PERIOD + BLOCK_TIME);
+            assert!(contract.is_abandoned(0));
+            assert_eq!(
+                contract.reveal(0, key(), SALT),
+                Err(Error::RevealPeriodOver)
+            );
+            assert_eq!(contract.answer_key(0), None);
+            assert_eq!(contract.score(0, accounts.bob), Err(Error::Abandoned));
+            assert!(!contract.is_abandoned(1));
+        }
+
+        #[ink::test]
+        fn only_the_committed_key_is_accepted() {
+            let accounts = fixtures::accounts();
+            let mut contract = with_submissions();
+            set_time(DEADLINE + BLOCK_TIME);
+
+            // Bob's answers, or the right answers with the wrong salt, are refused.
+            assert_eq!(
+                contract.reveal(0, vec![2, 0, 3, 0], SALT),
+                Err(Error::KeyMismatch)
+            );
+            assert_eq!(
+                contract.reveal(0, key(), [0u8; 32]),
+                Err(Error::KeyMismatch)
+            );
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(contract.reveal(0, key(), SALT), Err(Error::NotAuthor));
+            assert_eq!(contract.answer_key(0), None);
+
+            fixtures::set_caller(accounts.alice);
+            assert_eq!(contract.reveal(0, key(), SALT), Ok(()));
+        }
+
+        #[ink::test]
+        fn a_committed_key_of_the_wrong_length_is_refused() {
+            let accounts = fixtures::accounts();
+            let mut contract = TimedQuiz::new(REVEAL_PERIOD);
+            let short = vec![2, 0, 3];
+            assert_eq!(
+                contract.create_quiz(commitment(accounts.alice, &short), 4, DEADLINE),
+                Ok(0)
+            );
+            set_time(DEADLINE + BLOCK_TIME);
+            assert_eq!(
+                contract.reveal(0, short, SALT),
+                Err(Error::WrongAnswerCount)
+            );
+            assert_eq!(contract.answer_key(0), None);
+        }
+
+        #[ink::test]
+        fn submissions_are_scored_against_the_key() {
+            let accounts = fixtures::accounts();
+            let mut contract = with_submissions();
+            assert_eq!(contract.score(0, accounts.bob), Err(Error::NotRevealed));
+            set_time(DEADLINE + BLOCK_TIME);
+            assert_eq!(contract.reveal(0, key(), SALT), Ok(()));
+
+            // Anyone can score anyone's submission.
+            fixtures::set_caller(accounts.dave);
+            assert_eq!(contract.score(0, accounts.bob), Ok(3));
+            assert_eq!(contract.score(0, accounts.charlie), Ok(1));
+            assert_eq!(contract.score_of(0, accounts.bob), Some(3));
+            assert_eq!(contract.score_of(0, accounts.charlie), Some(1));
+            assert_eq!(contract.score(0, accounts.bob), Err(Error::AlreadyScored));
+            assert_eq!(contract.score(0, accounts.dave), Err(Error::NoSubmission));
+            assert_eq!(contract.score_of(0, accounts.dave), None);
+            assert_eq!(contract.score(1, accounts.bob), Err(Error::QuizNotFound));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy with the "new" constructor and the time authors have to reveal a
+//      key after the deadline, in milliseconds
+//
+// 4. Interact with the contract:
+//    - As an author, pick an answer key and 32 random salt bytes, compute
+//      `answer_key_hash(contract, author, key, salt)` off-chain and use "createQuiz"
+//      with it, the number of questions and a deadline; keep the key and salt
+//    - As a learner, use "submit" with one answer per question before the deadline
+//    - After the deadline, and before the quiz's "revealBy", the author uses
+//      "reveal" with the key and salt
+//    - Anyone uses "score" for each submission; "scoreOf" reads the result
@@ -0,0 +1,395 @@
+// examples/demo-contracts/token_locker.rs
+//
+// A token locker contract example for Polkadot using ink!
+// Anyone can lock ERC-20 tokens in this contract until a timestamp of their choosing.
+// Each lock gets its own id; the owner of a lock may push its unlock time further
+// out, and once the unlock time has been reached, withdraw the tokens with
+// `withdraw(lock_id)`.
+//
+// Tokens are moved with cross-contract calls to the token's `transfer_from` and
+// `transfer` messages, so any token with the selectors of `erc20.rs` works. Before
+// calling `lock`, approve this contract to spend the amount on the token.
+//
+// ink! 3 does not revert storage when a message returns an error, so `lock` pulls
+// the tokens before recording the lock, and `withdraw` puts the lock back if sending
+// the tokens fails.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod token_locker {
+    use ink_env::call::{build_call, Call, ExecutionInput, Selector};
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Selector of the ERC-20 `transfer(to, value)` message.
+    const TRANSFER_SELECTOR: [u8; 4] = [0x84, 0xa1, 0x5d, 0xa1];
+
+    /// Selector of the ERC-20 `transfer_from(from, to, value)` message.
+    const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x0b, 0x39, 0x6f, 0x18];
+
+    /// Identifies a lock.
+    pub type LockId = u64;
+
+    /// The token locker error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the amount to lock is zero.
+        ZeroAmount,
+        /// Returned if a new unlock time is not in the future, or an extension does
+        /// not move the unlock time later.
+        InvalidUnlockTime,
+        /// Returned if no lock has the given id.
+        LockNotFound,
+        /// Returned if the caller does not own the lock.
+        NotLockOwner,
+        /// Returned if the lock's unlock time has not been reached yet.
+        StillLocked,
+        /// Returned if the token refused to move the tokens.
+        TransferFailed,
+    }
+
+    /// The token locker result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Stands in for the token's own error type. The ERC-20 examples encode their
+    /// errors as a bare variant index, and all the locker needs is to tell success
+    /// from failure.
+    #[derive(scale::Decode)]
+    struct TokenError(#[allow(dead_code)] u8);
+
+    /// Tokens held until a point in time.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Lock {
+        /// Account that locked the tokens and may withdraw them.
+        pub owner: AccountId,
+        /// Token contract the tokens belong to.
+        pub token: AccountId,
+        /// Amount of tokens locked.
+        pub amount: Balance,
+        /// Timestamp from which the tokens can be withdrawn.
+        pub unlock_at: Timestamp,
+    }
+
+    // Events are mirrored in `crates/events/src/token_locker.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when tokens are locked.
+    #[ink(event)]
+    pub struct Locked {
+        #[ink(topic)]
+        lock_id: LockId,
+        #[ink(topic)]
+        owner: AccountId,
+        token: AccountId,
+        amount: Balance,
+        unlock_at: Timestamp,
+    }
+
+    /// Event emitted when the owner of a lock moves its unlock time later.
+    #[ink(event)]
+    pub struct LockExtended {
+        #[ink(topic)]
+        lock_id: LockId,
+        unlock_at: Timestamp,
+    }
+
+    /// Event emitted when the tokens of a lock are withdrawn.
+    #[ink(event)]
+    pub struct Withdrawn {
+        #[ink(topic)]
+        lock_id: LockId,
+        #[ink(topic)]
+        owner: AccountId,
+        amount: Balance,
+    }
+
+    /// The token locker storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct TokenLocker {
+        /// Mapping from lock id to lock. Withdrawn locks are removed.
+        locks: Mapping<LockId, Lock>,
+        /// Id the next lock will get.
+        next_lock_id: LockId,
+    }
+
+    impl TokenLocker {
+        /// Creates a locker holding no locks.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|_: &mut Self| {})
+        }
+
+        /// Returns the lock with id `lock_id`, or `None` if it does not exist or has
+        /// been withdrawn.
+        #[ink(message)]
+        pub fn get_lock(&self, lock_id: LockId) -> Option<Lock> {
+            self.locks.get(lock_id)
+        }
+
+        /// Returns the id the next lock will get.
+        #[ink(message)]
+        pub fn next_lock_id(&self) -> LockId {
+            self.next_lock_id
+        }
+
+        /// Locks `amount` of `token` from the caller until `unlock_at` and returns the
+        /// id of the new lock. The caller must have approved this contract to spend
+        /// at least `amount` on `token`.
+        #[ink(message)]
+        pub fn lock(
+            &mut self,
+            token: AccountId,
+            amount: Balance,
+            unlock_at: Timestamp,
+        ) -> Result<LockId> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            if unlock_at <= self.env().block_timestamp() {
+                return Err(Error::InvalidUnlockTime);
+            }
+
+            let owner = self.env().caller();
+            let locker = self.env().account_id();
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(token).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(TRANSFER_FROM_SELECTOR))
+                        .push_arg(owner)
+                        .push_arg(locker)
+                        .push_arg(amount),
+                )
+                .returns::<core::result::Result<(), TokenError>>()
+                .fire()
+                .map_err(|_| Error::TransferFailed)?
+                .map_err(|_| Error::TransferFailed)?;
+
+            Ok(self.insert_lock(Lock {
+                owner,
+                token,
+                amount,
+                unlock_at,
+            }))
+        }
+
+        /// Moves the unlock time of lock `lock_id` to `unlock_at`, which must be later
+        /// than the current one. Only the owner of the lock can call this.
+        #[ink(message)]
+        pub fn extend(&mut self, lock_id: LockId, unlock_at: Timestamp) -> Result<()> {
+            let mut lock = self.owned_lock(lock_id)?;
+            if unlock_at <= lock.unlock_at {
+                return Err(Error::InvalidUnlockTime);
+            }
+            lock.unlock_at = unlock_at;
+            self.locks.insert(lock_id, &lock);
+            self.env().emit_event(LockExtended { lock_id, unlock_at });
+            Ok(())
+        }
+
+        /// Sends the tokens of lock `lock_id` back to its owner and removes the lock.
+        /// Only the owner of the lock can call this, and only once its unlock time
+        /// has been reached.
+        #[ink(message)]
+        pub fn withdraw(&mut self, lock_id: LockId) -> Result<()> {
+            let lock = self.owned_lock(lock_id)?;
+            if self.env().block_timestamp() < lock.unlock_at {
+                return Err(Error::StillLocked);
+            }
+
+            // Remove the lock before calling out, so a re-entrant call cannot
+            // withdraw it twice.
+            self.locks.remove(lock_id);
+            let sent = build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(lock.token).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(TRANSFER_SELECTOR))
+                        .push_arg(lock.owner)
+                        .push_arg(lock.amount),
+                )
+                .returns::<core::result::Result<(), TokenError>>()
+                .fire();
+            if !matches!(sent, Ok(Ok(()))) {
+                self.locks.insert(lock_id, &lock);
+                return Err(Error::TransferFailed);
+            }
+
+            self.env().emit_event(Withdrawn {
+                lock_id,
+                owner: lock.owner,
+                amount: lock.amount,
+            });
+            Ok(())
+        }
+
+        /// Returns lock `lock_id` if the caller owns it.
+        fn owned_lock(&self, lock_id: LockId) -> Result<Lock> {
+            let lock = self.get_lock(lock_id).ok_or(Error::LockNotFound)?;
+            if lock.owner != self.env().caller() {
+                return Err(Error::NotLockOwner);
+            }
+            Ok(lock)
+        }
+
+        /// Records `lock` under the next id and returns that id.
+        fn insert_lock(&mut self, lock: Lock) -> LockId {
+            let lock_id = self.next_lock_id;
+            self.next_lock_id = lock_id + 1;
+            self.locks.insert(lock_id, &lock);
+            self.env().emit_event(Locked {
+                lock_id,
+                owner: lock.owner,
+                token: lock.token,
+                amount: lock.amount,
+                unlock_at: lock.unlock_at,
+            });
+            lock_id
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// cover everything up to the token calls and create locks with `insert_lock`.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use fixtures::{set_block_timestamp as set_time, BLOCK_TIME};
+        use ink_lang as ink;
+
+        /// When locks made with `lock_for` unlock.
+        const UNLOCK: Timestamp = 100 * BLOCK_TIME;
+
+        fn token() -> AccountId {
+            AccountId::from([0x7a; 32])
+        }
+
+        /// Creates a lock of 100 tokens owned by `owner` that unlocks at `UNLOCK`.
+        fn lock_for(locker: &mut TokenLocker, owner: AccountId) -> LockId {
+            locker.insert_lock(Lock {
+                owner,
+                token: token(),
+                amount: 100,
+                unlock_at: UNLOCK,
+            })
+        }
+
+        #[ink::test]
+        fn lock_ids_increase() {
+            let accounts = fixtures::accounts();
+            let mut locker = TokenLocker::new();
+            assert_eq!(lock_for(&mut locker, accounts.alice), 0);
+            assert_eq!(lock_for(&mut locker, accounts.bob), 1);
+            assert_eq!(locker.next_lock_id(), 2);
+            assert_eq!(locker.get_lock(1).map(|lock| lock.owner), Some(accounts.bob));
+            assert_eq!(locker.get_lock(2), None);
+        }
+
+        #[ink::test]
+        fn lock_rejects_zero_amount() {
+            let mut locker = TokenLocker::new();
+            set_time(0);
+            assert_eq!(locker.lock(token(), 0, UNLOCK), Err(Error::ZeroAmount));
+        }
+
+        #[ink::test]
+        fn lock_rejects_unlock_time_not_in_future() {
+            let mut locker = TokenLocker::new();
+            set_time(UNLOCK);
+            assert_eq!(locker.lock(token(), 100, UNLOCK - 1), Err(Error::InvalidUnlockTime));
+            assert_eq!(locker.lock(token(), 100, UNLOCK), Err(Error::InvalidUnlockTime));
+            assert_eq!(locker.next_lock_id(), 0);
+        }
+
+        #[ink::test]
+        fn early_withdrawal_is_rejected() {
+            let accounts = fixtures::accounts();
+            let mut locker = TokenLocker::new();
+            let lock_id = lock_for(&mut locker, accounts.alice);
+
+            set_time(0);
+            assert_eq!(locker.withdraw(lock_id), Err(Error::StillLocked));
+            set_time(UNLOCK - BLOCK_TIME);
+            assert_eq!(locker.withdraw(lock_id), Err(Error::StillLocked));
+            assert!(locker.get_lock(lock_id).is_some());
+        }
+
+        #[ink::test]
+        fn only_owner_can_withdraw() {
+            let accounts = fixtures::accounts();
+            let mut locker = TokenLocker::new();
+            let lock_id = lock_for(&mut locker, accounts.alice);
+
+            set_time(2 * UNLOCK);
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(locker.withdraw(lock_id), Err(Error::NotLockOwner));
+            assert_eq!(locker.withdraw(lock_id + 1), Err(Error::LockNotFound));
+        }
+
+        #[ink::test]
+        fn extend_moves_unlock_time_later() {
+            let accounts = fixtures::accounts();
+            let mut locker = TokenLocker::new();
+            let lock_id = lock_for(&mut locker, accounts.alice);
+
+            assert_eq!(locker.extend(lock_id, 2 * UNLOCK), Ok(()));
+            assert_eq!(locker.get_lock(lock_id).map(|lock| lock.unlock_at), Some(2 * UNLOCK));
+
+            // The old unlock time no longer frees the tokens.
+            set_time(UNLOCK);
+            assert_eq!(locker.withdraw(lock_id), Err(Error::StillLocked));
+        }
+
+        #[ink::test]
+        fn extend_cannot_shorten_lock() {
+            let accounts = fixtures::accounts();
+            let mut locker = TokenLocker::new();
+            let lock_id = lock_for(&mut locker, accounts.alice);
+
+            assert_eq!(locker.extend(lock_id, UNLOCK), Err(Error::InvalidUnlockTime));
+            assert_eq!(locker.extend(lock_id, UNLOCK - 1), Err(Error::InvalidUnlockTime));
+            assert_eq!(locker.get_lock(lock_id).map(|lock| lock.unlock_at), Some(UNLOCK));
+        }
+
+        #[ink::test]
+        fn only_owner_can_extend() {
+            let accounts = fixtures::accounts();
+            let mut locker = TokenLocker::new();
+            let lock_id = lock_for(&mut locker, accounts.alice);
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(locker.extend(lock_id, 2 * UNLOCK), Err(Error::NotLockOwner));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Deploy the contract
+//
+// 4. Interact with the contract:
+//    - On the token contract, "approve" the locker for the amount to lock
+//    - Use the "lock" method with the token address, amount and unlock timestamp
+//    - Use the "extend" method to push an unlock time further out
+//    - Use the "withdraw" method once the unlock time has been reached
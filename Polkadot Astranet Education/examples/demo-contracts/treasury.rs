@@ -0,0 +1,719 @@
+// examples/demo-contracts/treasury.rs
+//
+// A treasury contract example for Polkadot using ink!
+// The treasury holds native tokens and ERC-20 tokens on behalf of a DAO. Anyone
+// can propose a spend (an asset, a beneficiary and an amount), but a spend is
+// only executed once the DAO approves it, and only within the budget the DAO set
+// for that asset for the current spend period.
+//
+// Authorization
+// -------------
+// The treasury does not keep a list of approved proposals. When a spend is
+// executed, it asks the DAO contract, through a cross-contract call to
+// `SpendApproval::is_spend_approved`, whether this proposal was approved, so the
+// DAO's own voting rules decide and the treasury never has to trust a relayer. Any
+// contract implementing `SpendApproval` can act as the DAO; its selector is pinned
+// in `tools/selector/tests/pinned.rs`. Budgets can only be set by the DAO itself.
+// The repo has no DAO example yet; on a local node, `examples/mocks/mock_dao.rs`
+// plays the DAO.
+//
+// Spend periods and burning
+// -------------------------
+// Time is divided into spend periods of `period_length` blocks. Each asset's
+// budget is how much can be spent from it per period, and what is left unspent
+// does not carry over. Like Polkadot's treasury, which burns part of its funds at
+// the end of every spend period so they do not pile up unused, this treasury
+// burns `burn_bps` basis points of its native balance when a period is closed. Any
+// account may close a period once it is over; periods nobody closed are skipped,
+// so each call burns at most once. ERC-20 funds are never burned.
+//
+// ink! 3 does not revert storage when a message returns an error, so a spend is
+// charged to the budget before the funds are sent and put back if sending fails.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+/// Identifies a spend proposal.
+pub type ProposalId = u32;
+
+/// The interface the treasury uses to ask its DAO whether a spend was approved.
+#[ink::trait_definition]
+pub trait SpendApproval {
+    /// Returns whether the DAO approved proposal `proposal_id` of `treasury`.
+    #[ink(message)]
+    fn is_spend_approved(&self, treasury: ink_env::AccountId, proposal_id: ProposalId) -> bool;
+}
+
+#[ink::contract]
+mod treasury {
+    use super::ProposalId;
+    use astranet_utils::{
+        bounded::{BoundedError, BoundedString},
+        fixed_point::mul_div,
+        pagination::Page,
+        safe_erc20::SafeErc20,
+        trace,
+    };
+    use ink_env::call::{build_call, Call, ExecutionInput, Selector};
+    use ink_prelude::string::String;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// Selector of `SpendApproval::is_spend_approved`.
+    pub const IS_SPEND_APPROVED_SELECTOR: [u8; 4] = [0x98, 0x1a, 0x73, 0xb5];
+
+    /// Basis points in the whole native balance.
+    pub const BPS: u128 = 10_000;
+
+    /// Longest proposal description, in bytes.
+    pub const MAX_DESCRIPTION_LEN: usize = 256;
+
+    /// Account burned tokens are sent to; nobody holds its key.
+    pub const BURN_ACCOUNT: [u8; 32] = [0x00; 32];
+
+    /// The treasury error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the amount is zero.
+        ZeroAmount,
+        /// Returned if the caller is not the DAO.
+        NotDao,
+        /// Returned if no proposal has the given id.
+        ProposalNotFound,
+        /// Returned if the proposal was already executed.
+        AlreadyExecuted,
+        /// Returned if the DAO has not approved the proposal.
+        NotApproved,
+        /// Returned if the spend exceeds what is left of the asset's budget for
+        /// the current period.
+        BudgetExceeded,
+        /// Returned if the current spend period was already closed.
+        PeriodNotOver,
+        /// Returned if sending funds failed.
+        TransferFailed,
+        /// Returned if a description is longer than `MAX_DESCRIPTION_LEN`.
+        LimitExceeded,
+    }
+
+    impl From<BoundedError> for Error {
+        fn from(error: BoundedError) -> Self {
+            match error {
+                BoundedError::LimitExceeded => Error::LimitExceeded,
+            }
+        }
+    }
+
+    /// The treasury result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Funds the treasury can hold and spend.
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub enum Asset {
+        /// The chain's native token.
+        Native,
+        /// The ERC-20 token at the given address.
+        Erc20(AccountId),
+    }
+
+    /// A proposed spend.
+    #[derive(
+        Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Proposal {
+        /// Account that proposed the spend.
+        pub proposer: AccountId,
+        /// Asset to spend.
+        pub asset: Asset,
+        /// Account to pay.
+        pub beneficiary: AccountId,
+        /// Amount to pay.
+        pub amount: Balance,
+        /// What the spend is for, as given by the proposer.
+        pub description: BoundedString<MAX_DESCRIPTION_LEN>,
+        /// Whether the spend was executed.
+        pub executed: bool,
+    }
+
+    /// An asset's budget and what was spent of it.
+    #[derive(
+        Debug,
+        Default,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        scale::Encode,
+        scale::Decode,
+        SpreadLayout,
+        PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Budget {
+        /// Most that can be spent per period.
+        pub per_period: Balance,
+        /// Spent in period `period`.
+        pub spent: Balance,
+        /// Period `spent` was counted in.
+        pub period: u32,
+    }
+
+    // Events are mirrored in `crates/events/src/treasury.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when native tokens are deposited.
+    #[ink(event)]
+    pub struct Deposited {
+        #[ink(topic)]
+        from: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when the DAO sets an asset's budget.
+    #[ink(event)]
+    pub struct BudgetSet {
+        asset: Asset,
+        per_period: Balance,
+    }
+
+    /// Event emitted when a spend is proposed.
+    #[ink(event)]
+    pub struct SpendProposed {
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        #[ink(topic)]
+        proposer: AccountId,
+        asset: Asset,
+        beneficiary: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when an approved spend is paid out.
+    #[ink(event)]
+    pub struct SpendExecuted {
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        beneficiary: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when a spend period is closed.
+    #[ink(event)]
+    pub struct PeriodClosed {
+        period: u32,
+        burned: Balance,
+    }
+
+    /// The treasury storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Treasury {
+        /// The DAO contract approving spends and setting budgets.
+        dao: AccountId,
+        /// Block the first spend period starts at.
+        start_block: BlockNumber,
+        /// Length of a spend period in blocks.
+        period_length: BlockNumber,
+        /// Share of the native balance burned when a period is closed.
+        burn_bps: u128,
+        /// First period that has not been closed yet.
+        next_period_to_close: u32,
+        /// Mapping from asset to its budget.
+        budgets: Mapping<Asset, Budget>,
+        /// Mapping from proposal id to the proposal.
+        proposals: Mapping<ProposalId, Proposal>,
+        /// Id the next proposal gets.
+        next_proposal_id: ProposalId,
+    }
+
+    impl Treasury {
+        /// Creates a treasury controlled by `dao`, with spend periods of
+        /// `period_length` blocks starting now, burning `burn_bps` of the native
+        /// balance at the close of each period.
+        #[ink(constructor)]
+        pub fn new(dao: AccountId, period_length: BlockNumber, burn_bps: u128) -> Self {
+            assert!(period_length > 0, "spend period must be at least one block");
+            assert!(burn_bps <= BPS, "burn cannot exceed the whole balance");
+            ink_lang::utils::initialize_contract(|treasury: &mut Self| {
+                treasury.dao = dao;
+                treasury.start_block = Self::env().block_number();
+                treasury.period_length = period_length;
+                treasury.burn_bps = burn_bps;
+            })
+        }
+
+        /// Returns the DAO contract.
+        #[ink(message)]
+        pub fn dao(&self) -> AccountId {
+            self.dao
+        }
+
+        /// Returns the index of the current spend period.
+        #[ink(message)]
+        pub fn current_period(&self) -> u32 {
+            (self.env().block_number() - self.start_block) / self.period_length
+        }
+
+        /// Returns the budget of `asset`.
+        #[ink(message)]
+        pub fn budget_of(&self, asset: Asset) -> Budget {
+            self.budgets.get(asset).unwrap_or_default()
+        }
+
+        /// Returns how much of `asset` can still be spent this period.
+        #[ink(message)]
+        pub fn remaining_budget(&self, asset: Asset) -> Balance {
+            let budget = self.current_budget(asset);
+            budget.per_period.saturating_sub(budget.spent)
+        }
+
+        /// Returns proposal `proposal_id`, if it exists.
+        #[ink(message)]
+        pub fn proposal(&self, proposal_id: ProposalId) -> Option<Proposal> {
+            self.proposals.get(proposal_id)
+        }
+
+        /// Returns a page of up to `limit` proposals, oldest first, starting at
+        /// `cursor` or at the first proposal if `cursor` is `None`.
+        #[ink(message)]
+        pub fn proposals(
+            &self,
+            cursor: Option<ProposalId>,
+            limit: u32,
+        ) -> Page<(ProposalId, Proposal)> {
+            Page::from_range(cursor.unwrap_or(0)..self.next_proposal_id, limit, |id| {
+                self.proposal(id).map(|proposal| (id, proposal))
+            })
+        }
+
+        /// Deposits the transferred native tokens.
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) -> Result<()> {
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.env().emit_event(Deposited {
+                from: self.env().caller(),
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Sets how much of `asset` can be spent per period. Only the DAO can
+        /// call this; the spending already counted this period is kept.
+        #[ink(message)]
+        pub fn set_budget(&mut self, asset: Asset, per_period: Balance) -> Result<()> {
+            if self.env().caller() != self.dao {
+                return Err(Error::NotDao);
+            }
+            let mut budget = self.current_budget(asset);
+            budget.per_period = per_period;
+            self.budgets.insert(asset, &budget);
+            self.env().emit_event(BudgetSet { asset, per_period });
+            Ok(())
+        }
+
+        /// Proposes paying `amount` of `asset` to `beneficiary` for what
+        /// `description` explains, and returns the proposal's id.
+        #[ink(message)]
+        pub fn propose_spend(
+            &mut self,
+            asset: Asset,
+            beneficiary: AccountId,
+            amount: Balance,
+            description: String,
+        ) -> Result<ProposalId> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let description = BoundedString::try_from(description)?;
+            let proposal_id = self.next_proposal_id;
+            self.next_proposal_id += 1;
+            let proposer = self.env().caller();
+            self.proposals.insert(
+                proposal_id,
+                &Proposal {
+                    proposer,
+                    asset,
+                    beneficiary,
+                    amount,
+                    description,
+                    executed: false,
+                },
+            );
+            self.env().emit_event(SpendProposed {
+                proposal_id,
+                proposer,
+                asset,
+                beneficiary,
+                amount,
+            });
+            Ok(proposal_id)
+        }
+
+        /// Pays out proposal `proposal_id`, if the DAO approved it and it fits in
+        /// this period's budget. Anyone can call this.
+        #[ink(message)]
+        pub fn execute_spend(&mut self, proposal_id: ProposalId) -> Result<()> {
+            let proposal = self.pending_proposal(proposal_id)?;
+            let approved = self.is_approved(proposal_id);
+            trace!("execute_spend", proposal_id, approved);
+            if !approved {
+                return Err(Error::NotApproved);
+            }
+            let budget = self.charge(proposal.asset, proposal.amount)?;
+            self.proposals.insert(
+                proposal_id,
+                &Proposal {
+                    executed: true,
+                    ..proposal.clone()
+                },
+            );
+            if !self.pay(proposal.asset, proposal.beneficiary, proposal.amount) {
+                self.budgets.insert(proposal.asset, &budget);
+                self.proposals.insert(proposal_id, &proposal);
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(SpendExecuted {
+                proposal_id,
+                beneficiary: proposal.beneficiary,
+                amount: proposal.amount,
+            });
+            Ok(())
+        }
+
+        /// Closes the spend periods that are over, burning `burn_bps` of the
+        /// native balance once, and returns how much was burned.
+        #[ink(message)]
+        pub fn close_period(&mut self) -> Result<Balance> {
+            let current = self.current_period();
+            if current <= self.next_period_to_close {
+                return Err(Error::PeriodNotOver);
+            }
+            let previous = self.next_period_to_close;
+            self.next_period_to_close = current;
+            let burned = mul_div(self.env().balance(), self.burn_bps, BPS).unwrap_or(0);
+            if burned > 0
+                && self
+                    .env()
+                    .transfer(AccountId::from(BURN_ACCOUNT), burned)
+                    .is_err()
+            {
+                self.next_period_to_close = previous;
+                return Err(Error::TransferFailed);
+            }
+            self.env().emit_event(PeriodClosed {
+                period: current - 1,
+                burned,
+            });
+            Ok(burned)
+        }
+
+        /// Returns proposal `proposal_id` if it exists and was not executed.
+        fn pending_proposal(&self, proposal_id: ProposalId) -> Result<Proposal> {
+            let proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            if proposal.executed {
+                return Err(Error::AlreadyExecuted);
+            }
+            Ok(proposal)
+        }
+
+        /// Asks the DAO whether it approved `proposal_id`. A failed call counts as
+        /// not approved.
+        fn is_approved(&self, proposal_id: ProposalId) -> bool {
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(self.dao).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(IS_SPEND_APPROVED_SELECTOR))
+                        .push_arg(self.env().account_id())
+                        .push_arg(proposal_id),
+                )
+                .returns::<bool>()
+                .fire()
+                .unwrap_or(false)
+        }
+
+        /// Returns the budget of `asset` with the spending of past periods
+        /// forgotten.
+        fn current_budget(&self, asset: Asset) -> Budget {
+            let period = self.current_period();
+            let mut budget = self.budget_of(asset);
+            if budget.period != period {
+                budget.spent = 0;
+                budget.period = period;
+            }
+            budget
+        }
+
+        /// Counts `amount` against this period's budget of `asset`, and returns
+        /// the budget as it was before.
+        fn charge(&mut self, asset: Asset, amount: Balance) -> Result<Budget> {
+            let before = self.budget_of(asset);
+            let mut budget = self.current_budget(asset);
+            trace!(
+                "charge",
+                amount,
+                period = budget.period,
+                spent = budget.spent,
+                per_period = budget.per_period,
+            );
+            budget.spent = budget
+                .spent
+                .checked_add(amount)
+                .filter(|spent| *spent <= budget.per_period)
+                .ok_or(Error::BudgetExceeded)?;
+            self.budgets.insert(asset, &budget);
+            Ok(before)
+        }
+
+        /// Sends `amount` of `asset` to `to`, and returns whether it arrived.
+        fn pay(&mut self, asset: Asset, to: AccountId, amount: Balance) -> bool {
+            match asset {
+                Asset::Native => self.env().transfer(to, amount).is_ok(),
+                Asset::Erc20(token) => SafeErc20::new(token).transfer(to, amount).is_ok(),
+            }
+        }
+    }
+
+    /// The off-chain test environment cannot call other contracts, so these tests
+    /// pay native spends through the steps `execute_spend` takes after asking the
+    /// DAO.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        type Env = ink_env::DefaultEnvironment;
+
+        const PERIOD: BlockNumber = 10;
+
+        fn dao() -> AccountId {
+            AccountId::from([0x81; 32])
+        }
+
+        fn contract() -> AccountId {
+            AccountId::from([0x60; 32])
+        }
+
+        /// A treasury holding 10_000 native tokens, burning 10% per period, with
+        /// a native budget of 1_000.
+        fn treasury() -> Treasury {
+            ink_env::test::set_callee::<Env>(contract());
+            let mut treasury = Treasury::new(dao(), PERIOD, 1_000);
+            fixtures::fund_contract(10_000);
+            fixtures::set_caller(dao());
+            assert_eq!(treasury.set_budget(Asset::Native, 1_000), Ok(()));
+            fixtures::set_caller(fixtures::accounts().alice);
+            treasury
+        }
+
+        fn advance(blocks: BlockNumber) {
+            for _ in 0..blocks {
+                ink_env::test::advance_block::<Env>();
+            }
+        }
+
+        /// Proposes and pays a native spend to bob, as `execute_spend` does once
+        /// the DAO approved it.
+        fn spend(treasury: &mut Treasury, amount: Balance) -> Result<()> {
+            let id = treasury.propose_spend(
+                Asset::Native,
+                fixtures::accounts().bob,
+                amount,
+                String::from("spend"),
+            )?;
+            let proposal = treasury.pending_proposal(id)?;
+            treasury.charge(proposal.asset, proposal.amount)?;
+            assert!(treasury.pay(proposal.asset, proposal.beneficiary, proposal.amount));
+            Ok(())
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "spend period must be at least one block")]
+        fn new_rejects_empty_periods() {
+            Treasury::new(dao(), 0, 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "burn cannot exceed the whole balance")]
+        fn new_rejects_burning_more_than_everything() {
+            Treasury::new(dao(), PERIOD, BPS + 1);
+        }
+
+        #[ink::test]
+        fn only_the_dao_sets_budgets() {
+            let mut treasury = treasury();
+            let token = Asset::Erc20(AccountId::from([0x70; 32]));
+            assert_eq!(treasury.set_budget(token, 5), Err(Error::NotDao));
+            fixtures::set_caller(dao());
+            assert_eq!(treasury.set_budget(token, 5), Ok(()));
+            assert_eq!(treasury.remaining_budget(token), 5);
+            assert_eq!(treasury.remaining_budget(Asset::Native), 1_000);
+        }
+
+        #[ink::test]
+        fn proposals_are_recorded() {
+            let accounts = fixtures::accounts();
+            let mut treasury = treasury();
+            assert_eq!(
+                treasury.propose_spend(Asset::Native, accounts.bob, 0, String::new()),
+                Err(Error::ZeroAmount)
+            );
+            assert_eq!(
+                treasury.propose_spend(Asset::Native, accounts.bob, 5, String::from("tooling")),
+                Ok(0)
+            );
+            assert_eq!(
+                treasury.proposal(0),
+                Some(Proposal {
+                    proposer: accounts.alice,
+                    asset: Asset::Native,
+                    beneficiary: accounts.bob,
+                    amount: 5,
+                    description: BoundedString::try_from("tooling").unwrap(),
+                    executed: false,
+                })
+            );
+            assert_eq!(treasury.execute_spend(1), Err(Error::ProposalNotFound));
+        }
+
+        #[ink::test]
+        fn descriptions_are_bounded() {
+            let accounts = fixtures::accounts();
+            let mut treasury = treasury();
+            let longest = "x".repeat(MAX_DESCRIPTION_LEN);
+            assert_eq!(
+                treasury.propose_spend(Asset::Native, accounts.bob, 5, longest.clone()),
+                Ok(0)
+            );
+            assert_eq!(&*treasury.proposal(0).unwrap().description, longest);
+            assert_eq!(
+                treasury.propose_spend(Asset::Native, accounts.bob, 5, longest + "x"),
+                Err(Error::LimitExceeded)
+            );
+            assert_eq!(treasury.proposal(1), None);
+        }
+
+        #[ink::test]
+        fn proposals_are_paged_oldest_first() {
+            let accounts = fixtures::accounts();
+            let mut treasury = treasury();
+            assert_eq!(treasury.proposals(None, 10), Page::empty());
+            for amount in 1..=5 {
+                treasury
+                    .propose_spend(Asset::Native, accounts.bob, amount, String::new())
+                    .unwrap();
+            }
+            let amounts = |page: &Page<(ProposalId, Proposal)>| -> Vec<Balance> {
+                page.items
+                    .iter()
+                    .map(|(_, proposal)| proposal.amount)
+                    .collect()
+            };
+
+            let first = treasury.proposals(None, 2);
+            assert_eq!(amounts(&first), [1, 2]);
+            assert_eq!(first.next_cursor, Some(2));
+            let second = treasury.proposals(first.next_cursor, 3);
+            assert_eq!(amounts(&second), [3, 4, 5]);
+            assert_eq!(second.next_cursor, None);
+            assert_eq!(treasury.proposals(Some(5), 2), Page::empty());
+            assert_eq!(treasury.proposals(Some(1), 0).next_cursor, Some(1));
+        }
+
+        #[ink::test]
+        fn spends_are_limited_per_period() {
+            let bob = fixtures::accounts().bob;
+            let mut treasury = treasury();
+            let before = fixtures::balance_of(bob);
+            assert_eq!(spend(&mut treasury, 600), Ok(()));
+            assert_eq!(spend(&mut treasury, 401), Err(Error::BudgetExceeded));
+            assert_eq!(spend(&mut treasury, 400), Ok(()));
+            assert_eq!(treasury.remaining_budget(Asset::Native), 0);
+            assert_eq!(fixtures::balance_of(bob) - before, 1_000);
+
+            // Unspent budget does not carry over, but a new period starts afresh.
+            advance(PERIOD - 1);
+            assert_eq!(spend(&mut treasury, 1), Err(Error::BudgetExceeded));
+            advance(1);
+            assert_eq!(treasury.current_period(), 1);
+            assert_eq!(treasury.remaining_budget(Asset::Native), 1_000);
+            assert_eq!(spend(&mut treasury, 1_000), Ok(()));
+        }
+
+        #[ink::test]
+        fn budget_changes_keep_this_periods_spending() {
+            let mut treasury = treasury();
+            assert_eq!(spend(&mut treasury, 600), Ok(()));
+            fixtures::set_caller(dao());
+            assert_eq!(treasury.set_budget(Asset::Native, 500), Ok(()));
+            assert_eq!(treasury.remaining_budget(Asset::Native), 0);
+            assert_eq!(treasury.set_budget(Asset::Native, 800), Ok(()));
+            assert_eq!(treasury.remaining_budget(Asset::Native), 200);
+        }
+
+        #[ink::test]
+        fn closing_a_period_burns_part_of_the_balance() {
+            let mut treasury = treasury();
+            assert_eq!(treasury.close_period(), Err(Error::PeriodNotOver));
+            advance(PERIOD);
+            assert_eq!(treasury.close_period(), Ok(1_000));
+            assert_eq!(fixtures::balance_of(contract()), 9_000);
+            assert_eq!(fixtures::balance_of(AccountId::from(BURN_ACCOUNT)), 1_000);
+            assert_eq!(treasury.close_period(), Err(Error::PeriodNotOver));
+
+            // Periods nobody closed are skipped rather than burned one by one.
+            advance(3 * PERIOD);
+            assert_eq!(treasury.close_period(), Ok(900));
+            assert_eq!(treasury.close_period(), Err(Error::PeriodNotOver));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract (it depends on `crates/utils` for `mul_div` and
+//    `SafeErc20`):
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Deploy a DAO contract implementing `SpendApproval` first; on a local node,
+//      `examples/mocks/mock_dao.rs` will do
+//    - Upload the generated .contract file and deploy it with the DAO's address,
+//      the spend period in blocks, and the share to burn in basis points
+//
+// 4. Interact with the contract:
+//    - Fund it with "deposit", or by transferring ERC-20 tokens to its address
+//    - Have the DAO call "setBudget" for each asset (with the mock DAO, call its
+//      "setBudget" with the treasury's address)
+//    - Use "proposeSpend", get the proposal approved by the DAO (with the mock DAO,
+//      "approveSpend"), then "executeSpend"
+//    - Browse proposals with "proposals", passing each page's "nextCursor" back
+//    - Call "closePeriod" at the end of each spend period
@@ -0,0 +1,249 @@
+// examples/demo-contracts/vesting.rs
+//
+// A vesting wallet contract example for Polkadot using ink!
+// The native tokens this contract is endowed with are released to a single
+// beneficiary over time: nothing until the cliff, then linearly from the start of
+// the schedule until it ends. Anyone may call `release` to send the beneficiary
+// what has vested so far; tokens sent to the contract later simply join the
+// schedule.
+//
+// Deploy it directly, or one per beneficiary through vesting_factory.rs.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod vesting {
+    use astranet_utils::deadline::Deadline;
+    /// The vesting error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if nothing has vested since the last release.
+        NothingToRelease,
+        /// Returned if sending the tokens failed.
+        TransferFailed,
+    }
+
+    /// The vesting result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Returns how much of `total` has vested at `now` for a schedule running
+    /// `duration` milliseconds from `start`, with nothing vesting until `cliff` has
+    /// passed. Like every `Deadline`, the cliff includes its own timestamp.
+    pub fn vested_at(
+        total: Balance,
+        start: Timestamp,
+        cliff: Deadline,
+        duration: Timestamp,
+        now: Timestamp,
+    ) -> Balance {
+        if now < start || !cliff.is_expired(now) {
+            return 0;
+        }
+        let elapsed = now - start;
+        if elapsed >= duration {
+            return total;
+        }
+        // Saturate rather than overflow for endowments near `Balance::MAX`.
+        total.saturating_mul(elapsed as Balance) / duration as Balance
+    }
+
+    // Events are mirrored in `crates/events/src/vesting.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when vested tokens are sent to the beneficiary.
+    #[ink(event)]
+    pub struct Released {
+        #[ink(topic)]
+        beneficiary: AccountId,
+        amount: Balance,
+    }
+
+    /// The vesting storage items.
+    #[ink(storage)]
+    pub struct Vesting {
+        /// Account the tokens vest to.
+        beneficiary: AccountId,
+        /// Time from which tokens vest.
+        start: Timestamp,
+        /// Nothing can be released until this has passed.
+        cliff: Deadline,
+        /// Length of the schedule in milliseconds.
+        duration: Timestamp,
+        /// Tokens released so far.
+        released: Balance,
+    }
+
+    impl Vesting {
+        /// Creates a schedule vesting the endowment to `beneficiary` linearly over
+        /// `duration` milliseconds from `start`, releasing nothing for the first
+        /// `cliff_duration` milliseconds.
+        #[ink(constructor)]
+        pub fn new(
+            beneficiary: AccountId,
+            start: Timestamp,
+            cliff_duration: Timestamp,
+            duration: Timestamp,
+        ) -> Self {
+            assert!(duration > 0, "duration must not be zero");
+            assert!(
+                cliff_duration <= duration,
+                "the cliff cannot be longer than the schedule"
+            );
+            Self {
+                beneficiary,
+                start,
+                cliff: Deadline::at(start.saturating_add(cliff_duration)),
+                duration,
+                released: 0,
+            }
+        }
+
+        /// Returns the beneficiary.
+        #[ink(message)]
+        pub fn beneficiary(&self) -> AccountId {
+            self.beneficiary
+        }
+
+        /// Returns the tokens released so far.
+        #[ink(message)]
+        pub fn released(&self) -> Balance {
+            self.released
+        }
+
+        /// Returns the tokens vested so far, released or not.
+        #[ink(message)]
+        pub fn vested(&self) -> Balance {
+            vested_at(
+                self.env().balance() + self.released,
+                self.start,
+                self.cliff,
+                self.duration,
+                self.env().block_timestamp(),
+            )
+        }
+
+        /// Returns the tokens that could be released right now.
+        #[ink(message)]
+        pub fn releasable(&self) -> Balance {
+            self.vested() - self.released
+        }
+
+        /// Sends the beneficiary everything vested and not yet released.
+        #[ink(message)]
+        pub fn release(&mut self) -> Result<Balance> {
+            let amount = self.releasable();
+            if amount == 0 {
+                return Err(Error::NothingToRelease);
+            }
+            let beneficiary = self.beneficiary;
+            if self.env().transfer(beneficiary, amount).is_err() {
+                return Err(Error::TransferFailed);
+            }
+            self.released += amount;
+            self.env().emit_event(Released {
+                beneficiary,
+                amount,
+            });
+            Ok(amount)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        // Multiples of the fixtures' block time, so blocks can land on them.
+        const START: Timestamp = 1_200;
+        const CLIFF: Timestamp = 120;
+        const DURATION: Timestamp = 480;
+
+        #[test]
+        fn schedule_vectors() {
+            let cliff = Deadline::at(START + CLIFF);
+            assert_eq!(vested_at(800, START, cliff, DURATION, 0), 0);
+            // Nothing vests at the cliff itself, only from the next millisecond.
+            assert_eq!(vested_at(800, START, cliff, DURATION, START + CLIFF), 0);
+            assert_eq!(
+                vested_at(800, START, cliff, DURATION, START + CLIFF + 1),
+                201
+            );
+            assert_eq!(
+                vested_at(800, START, cliff, DURATION, START + DURATION - 1),
+                798
+            );
+            assert_eq!(
+                vested_at(800, START, cliff, DURATION, START + DURATION),
+                800
+            );
+            assert_eq!(
+                vested_at(800, START, cliff, DURATION, Timestamp::MAX),
+                800
+            );
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "duration must not be zero")]
+        fn new_rejects_zero_duration() {
+            Vesting::new(AccountId::from([0x01; 32]), START, 0, 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "the cliff cannot be longer than the schedule")]
+        fn new_rejects_a_long_cliff() {
+            Vesting::new(AccountId::from([0x01; 32]), START, DURATION + 1, DURATION);
+        }
+
+        #[ink::test]
+        fn release_follows_the_schedule() {
+            let accounts = fixtures::accounts();
+            let mut vesting = Vesting::new(accounts.bob, START, CLIFF, DURATION);
+            fixtures::fund_contract(800);
+            let before = fixtures::balance_of(accounts.bob);
+
+            fixtures::set_block_timestamp(START + CLIFF / 2);
+            assert_eq!(vesting.releasable(), 0);
+            assert_eq!(vesting.release(), Err(Error::NothingToRelease));
+
+            fixtures::set_block_timestamp(START + DURATION / 2);
+            assert_eq!(vesting.release(), Ok(400));
+            assert_eq!(vesting.release(), Err(Error::NothingToRelease));
+
+            fixtures::set_block_timestamp(START + DURATION);
+            assert_eq!(vesting.vested(), 800);
+            // Anyone may trigger a release, but only the beneficiary is paid.
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(vesting.release(), Ok(400));
+            assert_eq!(vesting.released(), 800);
+            assert_eq!(fixtures::balance_of(accounts.bob) - before, 800);
+        }
+    }
+}
+
+// Lets vesting_factory.rs deploy this contract in its e2e test when built with the
+// `ink-as-dependency` feature.
+pub use self::vesting::{Vesting, VestingRef};
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy to a Polkadot/Substrate chain:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your local node
+//    - Navigate to "Developer" -> "Contracts"
+//    - Upload the generated .contract file
+//    - Set the beneficiary and schedule (timestamps in milliseconds), and endow the
+//      contract with the tokens to vest
+//
+// 4. Interact with the contract:
+//    - Use "vested" and "releasable" to follow the schedule
+//    - Use "release" to send the vested tokens to the beneficiary
@@ -0,0 +1,325 @@
+// examples/demo-contracts/vesting_factory.rs
+//
+// A vesting factory contract example for Polkadot using ink!
+// This contract combines two earlier lessons: it deploys a fresh vesting.rs
+// instance per grant from an uploaded code hash, the way factory.rs deploys
+// Flippers, and keeps a registry of the grants of each beneficiary so wallets and
+// front ends can find them without scanning events.
+//
+// The value transferred with `create_vesting` becomes the child's endowment, which
+// is what it vests. The factory never holds the tokens and has no say over a
+// schedule once it is deployed.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod vesting_factory {
+    use ink_env::call::{build_create, ExecutionInput, FromAccountId, Selector};
+    use ink_prelude::vec::Vec;
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// Selector of the child's `new(beneficiary, start, cliff_duration, duration)`
+    /// constructor in vesting.rs.
+    pub const VESTING_NEW_SELECTOR: [u8; 4] = [0x9b, 0xae, 0x9d, 0x5e];
+
+    /// The vesting factory error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if no value was transferred to vest.
+        ZeroAmount,
+        /// Returned if the schedule would be rejected by the child's constructor.
+        InvalidSchedule,
+        /// Returned if the node rejected the instantiation.
+        InstantiationFailed,
+    }
+
+    /// The vesting factory result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    // Events are mirrored in `crates/events/src/vesting_factory.rs` for off-chain
+    // decoding; keep the order of events and fields in sync with it.
+
+    /// Event emitted when a vesting contract is deployed.
+    #[ink(event)]
+    pub struct VestingCreated {
+        #[ink(topic)]
+        creator: AccountId,
+        #[ink(topic)]
+        beneficiary: AccountId,
+        #[ink(topic)]
+        vesting: AccountId,
+        amount: Balance,
+    }
+
+    /// The vesting factory storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct VestingFactory {
+        /// Code hash of the uploaded vesting contract.
+        vesting_code_hash: Hash,
+        /// Number of vesting contracts deployed, used as the salt of the next one.
+        created: u32,
+        /// Mapping from beneficiary to their vesting contracts, in creation order.
+        by_beneficiary: Mapping<AccountId, Vec<AccountId>>,
+    }
+
+    /// Stands in for the child's contract reference in `build_create`.
+    ///
+    /// The factory deploys by code hash and selector, so it has no generated
+    /// `VestingRef`; `instantiate_contract` returns the new address either way.
+    struct ChildRef;
+
+    impl FromAccountId<Environment> for ChildRef {
+        fn from_account_id(_: AccountId) -> Self {
+            Self
+        }
+    }
+
+    impl VestingFactory {
+        /// Creates a new factory deploying vesting contracts from `vesting_code_hash`.
+        #[ink(constructor)]
+        pub fn new(vesting_code_hash: Hash) -> Self {
+            ink_lang::utils::initialize_contract(|factory: &mut Self| {
+                factory.vesting_code_hash = vesting_code_hash;
+            })
+        }
+
+        /// Returns the code hash vesting contracts are deployed from.
+        #[ink(message)]
+        pub fn vesting_code_hash(&self) -> Hash {
+            self.vesting_code_hash
+        }
+
+        /// Returns the number of vesting contracts deployed so far.
+        #[ink(message)]
+        pub fn created(&self) -> u32 {
+            self.created
+        }
+
+        /// Returns the vesting contracts of `beneficiary`, in creation order.
+        #[ink(message)]
+        pub fn vestings_of(&self, beneficiary: AccountId) -> Vec<AccountId> {
+            self.by_beneficiary.get(beneficiary).unwrap_or_default()
+        }
+
+        /// Deploys a vesting contract releasing the transferred value to
+        /// `beneficiary` linearly over `duration` milliseconds from `start`, with
+        /// nothing released for the first `cliff_duration` milliseconds.
+        #[ink(message, payable)]
+        pub fn create_vesting(
+            &mut self,
+            beneficiary: AccountId,
+            start: Timestamp,
+            cliff_duration: Timestamp,
+            duration: Timestamp,
+        ) -> Result<AccountId> {
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            // Checked here so a bad schedule is a clean error rather than a trapped
+            // instantiation.
+            if duration == 0 || cliff_duration > duration {
+                return Err(Error::InvalidSchedule);
+            }
+
+            let salt = self.created.to_le_bytes();
+            let params = build_create::<Environment, ChildRef>()
+                .code_hash(self.vesting_code_hash)
+                .gas_limit(0)
+                .endowment(amount)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(VESTING_NEW_SELECTOR))
+                        .push_arg(beneficiary)
+                        .push_arg(start)
+                        .push_arg(cliff_duration)
+                        .push_arg(duration),
+                )
+                .salt_bytes(&salt)
+                .params();
+            let vesting = self
+                .env()
+                .instantiate_contract(&params)
+                .map_err(|_| Error::InstantiationFailed)?;
+
+            self.record(beneficiary, vesting);
+            self.env().emit_event(VestingCreated {
+                creator: self.env().caller(),
+                beneficiary,
+                vesting,
+                amount,
+            });
+            Ok(vesting)
+        }
+
+        /// Adds `vesting` to the registry of `beneficiary`.
+        fn record(&mut self, beneficiary: AccountId, vesting: AccountId) {
+            self.created += 1;
+            let mut vestings = self.vestings_of(beneficiary);
+            vestings.push(vesting);
+            self.by_beneficiary.insert(beneficiary, &vestings);
+        }
+    }
+
+    /// The off-chain test environment cannot instantiate contracts, so these tests
+    /// cover the checks and the registry; the e2e test below deploys real children.
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        fn code_hash() -> Hash {
+            Hash::from([0x42; 32])
+        }
+
+        fn create(
+            factory: &mut VestingFactory,
+            amount: Balance,
+            cliff_duration: Timestamp,
+            duration: Timestamp,
+        ) -> Result<AccountId> {
+            ink_env::test::set_value_transferred::<ink_env::DefaultEnvironment>(amount);
+            let beneficiary = fixtures::accounts().bob;
+            factory.create_vesting(beneficiary, 0, cliff_duration, duration)
+        }
+
+        #[ink::test]
+        fn new_works() {
+            let factory = VestingFactory::new(code_hash());
+            assert_eq!(factory.vesting_code_hash(), code_hash());
+            assert_eq!(factory.created(), 0);
+            assert!(factory.vestings_of(fixtures::accounts().bob).is_empty());
+        }
+
+        #[ink::test]
+        fn registry_is_per_beneficiary() {
+            let accounts = fixtures::accounts();
+            let mut factory = VestingFactory::new(code_hash());
+            let (first, second, third) = (
+                AccountId::from([0x81; 32]),
+                AccountId::from([0x82; 32]),
+                AccountId::from([0x83; 32]),
+            );
+            factory.record(accounts.bob, first);
+            factory.record(accounts.charlie, second);
+            factory.record(accounts.bob, third);
+            assert_eq!(factory.vestings_of(accounts.bob), vec![first, third]);
+            assert_eq!(factory.vestings_of(accounts.charlie), vec![second]);
+            assert_eq!(factory.created(), 3);
+        }
+
+        #[ink::test]
+        fn bad_requests_are_rejected_before_deploying() {
+            let mut factory = VestingFactory::new(code_hash());
+            assert_eq!(create(&mut factory, 0, 0, 100), Err(Error::ZeroAmount));
+            assert_eq!(create(&mut factory, 10, 0, 0), Err(Error::InvalidSchedule));
+            assert_eq!(
+                create(&mut factory, 10, 101, 100),
+                Err(Error::InvalidSchedule)
+            );
+            assert_eq!(factory.created(), 0);
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink_e2e::ContractsBackend;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        /// Creates a vesting contract for bob through the factory, finds it in the
+        /// registry, and releases the whole grant from the child.
+        ///
+        /// `ink_e2e` builds vesting.rs because it is a dev-dependency of this
+        /// contract with the `ink-as-dependency` feature:
+        ///
+        /// ```toml
+        /// vesting = { path = "../vesting", default-features = false, features = ["ink-as-dependency"] }
+        /// ```
+        #[ink_e2e::test]
+        async fn create_and_release<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let vesting_hash = client
+                .upload("vesting", &ink_e2e::alice())
+                .submit()
+                .await
+                .expect("vesting upload failed")
+                .code_hash;
+            let factory = client
+                .instantiate(
+                    "vesting_factory",
+                    &ink_e2e::alice(),
+                    &mut VestingFactoryRef::new(vesting_hash),
+                )
+                .submit()
+                .await
+                .expect("factory instantiate failed");
+            let mut factory_calls = factory.call_builder::<VestingFactory>();
+
+            // A one-millisecond schedule starting at the epoch has fully vested by
+            // the time the next block is built.
+            let bob = AccountId::from(ink_e2e::bob().public_key().0);
+            let vesting = client
+                .call(
+                    &ink_e2e::alice(),
+                    &factory_calls.create_vesting(bob, 0, 0, 1),
+                )
+                .value(1_000_000)
+                .submit()
+                .await
+                .expect("create_vesting failed")
+                .return_value()
+                .expect("factory returned an error");
+
+            let registered = client
+                .call(&ink_e2e::alice(), &factory_calls.vestings_of(bob))
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(registered, vec![vesting]);
+
+            let mut vesting_calls = ink_e2e::create_call_builder::<vesting::Vesting>(vesting);
+            let released = client
+                .call(&ink_e2e::charlie(), &vesting_calls.release())
+                .submit()
+                .await
+                .expect("release failed")
+                .return_value();
+            assert_eq!(released, Ok(1_000_000));
+            let total = client
+                .call(&ink_e2e::alice(), &vesting_calls.released())
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(total, 1_000_000);
+            Ok(())
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Build and upload the child contract (vesting.rs) first:
+//    cargo +nightly contract build
+//    Upload the generated .contract file and note its code hash.
+//
+// 2. Compile the factory:
+//    cargo +nightly contract build
+//
+// 3. Deploy using the Polkadot JS Apps UI:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your desired network
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Set the vesting code hash noted in step 1
+//    - Deploy the contract
+//
+// 4. Interact with the contract:
+//    - Use "createVesting" with a value attached to deploy a grant
+//    - Use "vestingsOf" to list the grants of a beneficiary
+//    - Call "release" on a grant to pay out what has vested
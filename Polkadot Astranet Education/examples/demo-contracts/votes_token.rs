@@ -0,0 +1,562 @@
+// examples/demo-contracts/votes_token.rs
+//
+// A governance token contract example for Polkadot using ink!
+// This contract extends the ERC-20 example with vote delegation and historical
+// voting-power checkpoints, so a DAO can weigh votes by the power an account held
+// when a proposal was created rather than by its current balance.
+//
+// Balances do not count as votes by themselves: a holder must delegate, either to
+// another account or to themselves, before their tokens add to anyone's voting power.
+//
+// Weighing votes
+// --------------
+// A DAO using this token records the block a proposal was created in, and counts
+// each vote with `get_past_votes(voter, created_at - 1)`, the power the voter had
+// before the proposal existed. Tokens bought or delegated once a proposal is up
+// then cannot swing it. The DAO calls `get_past_votes` through its selector, which
+// is pinned in `tools/selector/tests/pinned.rs`. The repo has no DAO example yet:
+// the treasury's `examples/mocks/mock_dao.rs` approves spends without a vote.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod votes_token {
+    use astranet_utils::validation::{self, MAX_DECIMALS};
+    use ink_prelude::string::String;
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// The votes token error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the balance is insufficient for the operation.
+        InsufficientBalance,
+        /// Returned if the allowance is insufficient for the operation.
+        InsufficientAllowance,
+        /// Returned if historical votes are requested for the current or a future block.
+        BlockNotYetMined,
+    }
+
+    /// The votes token result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// The voting power of an account from `from_block` onwards.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct Checkpoint {
+        /// First block this voting power applies to.
+        pub from_block: BlockNumber,
+        /// Voting power held from `from_block` until the next checkpoint.
+        pub votes: Balance,
+    }
+
+    // Events are mirrored in `crates/events/src/votes_token.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when a token transfer occurs.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    /// Event emitted when an approval occurs.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when an account changes its delegate.
+    #[ink(event)]
+    pub struct DelegateChanged {
+        #[ink(topic)]
+        delegator: AccountId,
+        #[ink(topic)]
+        from_delegate: Option<AccountId>,
+        #[ink(topic)]
+        to_delegate: AccountId,
+    }
+
+    /// Event emitted when a delegate's voting power changes.
+    #[ink(event)]
+    pub struct DelegateVotesChanged {
+        #[ink(topic)]
+        delegate: AccountId,
+        previous_votes: Balance,
+        new_votes: Balance,
+    }
+
+    /// The votes token storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct VotesToken {
+        /// Total token supply.
+        total_supply: Balance,
+        /// Mapping from owner to balance.
+        balances: Mapping<AccountId, Balance>,
+        /// Mapping from owner to spender to allowance.
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+        /// Token name.
+        name: String,
+        /// Token symbol.
+        symbol: String,
+        /// Token decimals.
+        decimals: u8,
+        /// Mapping from holder to the account their tokens vote for.
+        delegates: Mapping<AccountId, AccountId>,
+        /// Mapping from delegate and checkpoint index to checkpoint.
+        checkpoints: Mapping<(AccountId, u32), Checkpoint>,
+        /// Mapping from delegate to the number of checkpoints written for them.
+        num_checkpoints: Mapping<AccountId, u32>,
+    }
+
+    impl VotesToken {
+        /// Creates a new votes token with the specified initial supply.
+        ///
+        /// The initial supply carries no voting power until its holder delegates.
+        /// Panics if `symbol` is empty or `decimals` is above `MAX_DECIMALS`.
+        #[ink(constructor)]
+        pub fn new(
+            initial_supply: Balance,
+            name: String,
+            symbol: String,
+            decimals: u8,
+        ) -> Self {
+            validation::require("token symbol", validation::non_empty(&symbol));
+            validation::require("decimals", validation::at_most(decimals, MAX_DECIMALS));
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                let caller = Self::env().caller();
+                contract.balances.insert(caller, &initial_supply);
+                contract.total_supply = initial_supply;
+                contract.name = name;
+                contract.symbol = symbol;
+                contract.decimals = decimals;
+
+                Self::env().emit_event(Transfer {
+                    from: None,
+                    to: Some(caller),
+                    value: initial_supply,
+                });
+            })
+        }
+
+        /// Returns the token name.
+        #[ink(message)]
+        pub fn name(&self) -> String {
+            self.name.clone()
+        }
+
+        /// Returns the token symbol.
+        #[ink(message)]
+        pub fn symbol(&self) -> String {
+            self.symbol.clone()
+        }
+
+        /// Returns the token decimals.
+        #[ink(message)]
+        pub fn decimals(&self) -> u8 {
+            self.decimals
+        }
+
+        /// Returns the total token supply.
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// Returns the account balance for the specified `owner`.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or(0)
+        }
+
+        /// Returns the amount which `spender` is allowed to withdraw from `owner`.
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or(0)
+        }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            self.transfer_from_to(from, to, value)
+        }
+
+        /// Allows `spender` to withdraw from the caller's account multiple times, up to
+        /// the `value` amount.
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            self.allowances.insert((owner, spender), &value);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` tokens on behalf of `from` to the account `to`.
+        #[ink(message)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let caller = self.env().caller();
+            let allowance = self.allowance(from, caller);
+            if allowance < value {
+                return Err(Error::InsufficientAllowance);
+            }
+            self.transfer_from_to(from, to, value)?;
+            self.allowances.insert((from, caller), &(allowance - value));
+            Ok(())
+        }
+
+        /// Returns the account `account` delegates its votes to, if any.
+        #[ink(message)]
+        pub fn delegates(&self, account: AccountId) -> Option<AccountId> {
+            self.delegates.get(account)
+        }
+
+        /// Delegates the caller's votes to `delegatee`. Delegate to yourself to vote
+        /// with your own balance.
+        #[ink(message)]
+        pub fn delegate(&mut self, delegatee: AccountId) {
+            let delegator = self.env().caller();
+            let previous = self.delegates(delegator);
+            self.delegates.insert(delegator, &delegatee);
+
+            self.env().emit_event(DelegateChanged {
+                delegator,
+                from_delegate: previous,
+                to_delegate: delegatee,
+            });
+
+            let amount = self.balance_of(delegator);
+            self.move_voting_power(previous, Some(delegatee), amount);
+        }
+
+        /// Returns the current voting power of `account`.
+        #[ink(message)]
+        pub fn get_votes(&self, account: AccountId) -> Balance {
+            match self.num_checkpoints(account) {
+                0 => 0,
+                n => self.checkpoint(account, n - 1).votes,
+            }
+        }
+
+        /// Returns the voting power `account` had at the end of `block`.
+        ///
+        /// Only finished blocks can be queried: the power at the current block may
+        /// still change within it.
+        #[ink(message)]
+        pub fn get_past_votes(&self, account: AccountId, block: BlockNumber) -> Result<Balance> {
+            if block >= self.env().block_number() {
+                return Err(Error::BlockNotYetMined);
+            }
+            Ok(self.votes_at(account, block))
+        }
+
+        /// Returns the number of checkpoints written for `account`.
+        #[ink(message)]
+        pub fn num_checkpoints(&self, account: AccountId) -> u32 {
+            self.num_checkpoints.get(account).unwrap_or(0)
+        }
+
+        /// Returns checkpoint number `index` of `account`.
+        #[ink(message)]
+        pub fn checkpoints(&self, account: AccountId, index: u32) -> Option<Checkpoint> {
+            self.checkpoints.get((account, index))
+        }
+
+        /// Transfers `value` amount of tokens from the `from` account to the `to` account.
+        fn transfer_from_to(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let from_balance = self.balance_of(from);
+            if from_balance < value {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.balances.insert(from, &(from_balance - value));
+            let to_balance = self.balance_of(to);
+            self.balances.insert(to, &(to_balance + value));
+
+            self.move_voting_power(self.delegates(from), self.delegates(to), value);
+
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                value,
+            });
+
+            Ok(())
+        }
+
+        fn checkpoint(&self, account: AccountId, index: u32) -> Checkpoint {
+            self.checkpoints
+                .get((account, index))
+                .expect("checkpoints below num_checkpoints always exist")
+        }
+
+        /// Binary-searches the last checkpoint at or before `block`.
+        fn votes_at(&self, account: AccountId, block: BlockNumber) -> Balance {
+            let count = self.num_checkpoints(account);
+            if count == 0 || self.checkpoint(account, 0).from_block > block {
+                return 0;
+            }
+
+            // Invariant: checkpoint `low` starts at or before `block`, and every
+            // checkpoint after `high` starts after it.
+            let mut low = 0;
+            let mut high = count - 1;
+            while low < high {
+                let mid = high - (high - low) / 2;
+                if self.checkpoint(account, mid).from_block <= block {
+                    low = mid;
+                } else {
+                    high = mid - 1;
+                }
+            }
+            self.checkpoint(account, low).votes
+        }
+
+        fn move_voting_power(
+            &mut self,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            amount: Balance,
+        ) {
+            if from == to || amount == 0 {
+                return;
+            }
+            if let Some(from) = from {
+                let previous = self.get_votes(from);
+                self.write_checkpoint(from, previous, previous - amount);
+            }
+            if let Some(to) = to {
+                let previous = self.get_votes(to);
+                self.write_checkpoint(to, previous, previous + amount);
+            }
+        }
+
+        /// Records `new_votes` for `delegate` at the current block. Several changes
+        /// in one block share a single checkpoint holding the last value.
+        fn write_checkpoint(&mut self, delegate: AccountId, previous: Balance, new_votes: Balance) {
+            let block = self.env().block_number();
+            let count = self.num_checkpoints(delegate);
+            let checkpoint = Checkpoint {
+                from_block: block,
+                votes: new_votes,
+            };
+
+            if count > 0 && self.checkpoint(delegate, count - 1).from_block == block {
+                self.checkpoints.insert((delegate, count - 1), &checkpoint);
+            } else {
+                self.checkpoints.insert((delegate, count), &checkpoint);
+                self.num_checkpoints.insert(delegate, &(count + 1));
+            }
+
+            self.env().emit_event(DelegateVotesChanged {
+                delegate,
+                previous_votes: previous,
+                new_votes,
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        fn new_token() -> VotesToken {
+            VotesToken::new(100, String::from("Vote"), String::from("VOTE"), 18)
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "token symbol must not be empty")]
+        fn new_rejects_an_empty_symbol() {
+            VotesToken::new(100, String::from("Vote"), String::new(), 12);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "decimals is too large")]
+        fn new_rejects_too_many_decimals() {
+            VotesToken::new(
+                100,
+                String::from("Vote"),
+                String::from("VOTE"),
+                MAX_DECIMALS + 1,
+            );
+        }
+
+        #[ink::test]
+        fn balances_do_not_vote_until_delegated() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            assert_eq!(token.get_votes(accounts.alice), 0);
+            token.delegate(accounts.alice);
+            assert_eq!(token.get_votes(accounts.alice), 100);
+            assert_eq!(token.delegates(accounts.alice), Some(accounts.alice));
+        }
+
+        #[ink::test]
+        fn redelegation_moves_votes() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            token.delegate(accounts.alice);
+            fixtures::set_block_number(1);
+            token.delegate(accounts.bob);
+            assert_eq!(token.get_votes(accounts.alice), 0);
+            assert_eq!(token.get_votes(accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn transfers_move_votes_between_delegates() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            token.delegate(accounts.alice);
+
+            fixtures::with_caller(accounts.bob, || token.delegate(accounts.charlie));
+
+            fixtures::set_block_number(1);
+            assert_eq!(token.transfer(accounts.bob, 30), Ok(()));
+            assert_eq!(token.get_votes(accounts.alice), 70);
+            assert_eq!(token.get_votes(accounts.charlie), 30);
+        }
+
+        #[ink::test]
+        fn transfer_to_undelegated_account_burns_votes() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            token.delegate(accounts.alice);
+            assert_eq!(token.transfer(accounts.bob, 40), Ok(()));
+            assert_eq!(token.get_votes(accounts.alice), 60);
+            assert_eq!(token.get_votes(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn changes_in_one_block_share_a_checkpoint() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            fixtures::set_block_number(3);
+            token.delegate(accounts.alice);
+            assert_eq!(token.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(token.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(token.num_checkpoints(accounts.alice), 1);
+            assert_eq!(
+                token.checkpoints(accounts.alice, 0),
+                Some(Checkpoint { from_block: 3, votes: 80 })
+            );
+        }
+
+        #[ink::test]
+        fn past_votes_follow_checkpoints() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            fixtures::set_block_number(2);
+            token.delegate(accounts.alice);
+            fixtures::set_block_number(5);
+            assert_eq!(token.transfer(accounts.bob, 10), Ok(()));
+            fixtures::set_block_number(9);
+            assert_eq!(token.transfer(accounts.bob, 20), Ok(()));
+            fixtures::set_block_number(12);
+
+            // Before the first checkpoint.
+            assert_eq!(token.get_past_votes(accounts.alice, 1), Ok(0));
+            // Exactly at each checkpoint, and between them.
+            assert_eq!(token.get_past_votes(accounts.alice, 2), Ok(100));
+            assert_eq!(token.get_past_votes(accounts.alice, 4), Ok(100));
+            assert_eq!(token.get_past_votes(accounts.alice, 5), Ok(90));
+            assert_eq!(token.get_past_votes(accounts.alice, 8), Ok(90));
+            assert_eq!(token.get_past_votes(accounts.alice, 9), Ok(70));
+            // After the last checkpoint.
+            assert_eq!(token.get_past_votes(accounts.alice, 11), Ok(70));
+        }
+
+        /// A DAO counts votes with the power held the block before a proposal was
+        /// created, so tokens moved once it is up cannot swing it.
+        #[ink::test]
+        fn power_moved_after_a_proposal_does_not_count() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            fixtures::set_block_number(2);
+            token.delegate(accounts.alice);
+            let created_at = 5;
+            fixtures::set_block_number(created_at);
+            assert_eq!(token.transfer(accounts.bob, 60), Ok(()));
+            fixtures::with_caller(accounts.bob, || token.delegate(accounts.bob));
+            fixtures::set_block_number(created_at + 1);
+
+            let snapshot = created_at - 1;
+            assert_eq!(token.get_past_votes(accounts.alice, snapshot), Ok(100));
+            assert_eq!(token.get_past_votes(accounts.bob, snapshot), Ok(0));
+            assert_eq!(token.get_votes(accounts.bob), 60);
+        }
+
+        #[ink::test]
+        fn past_votes_reject_unfinished_blocks() {
+            let accounts = fixtures::accounts();
+            let token = new_token();
+            fixtures::set_block_number(4);
+            assert_eq!(token.get_past_votes(accounts.alice, 4), Err(Error::BlockNotYetMined));
+            assert_eq!(token.get_past_votes(accounts.alice, 5), Err(Error::BlockNotYetMined));
+            assert_eq!(token.get_past_votes(accounts.alice, 3), Ok(0));
+        }
+
+        #[ink::test]
+        fn self_transfer_keeps_votes() {
+            let accounts = fixtures::accounts();
+            let mut token = new_token();
+            token.delegate(accounts.alice);
+            assert_eq!(token.transfer(accounts.alice, 50), Ok(()));
+            assert_eq!(token.get_votes(accounts.alice), 100);
+            assert_eq!(token.num_checkpoints(accounts.alice), 1);
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy using the Polkadot JS Apps UI:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to your desired network
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Set the initial supply, name, symbol, and decimals
+//    - Deploy the contract
+//
+// 4. Interact with the contract:
+//    - Use the "delegate" method with your own address to activate your votes
+//    - Use the "getVotes" method to check current voting power
+//    - Use the "getPastVotes" method with an earlier block to see historical power
@@ -0,0 +1,463 @@
+// examples/demo-contracts/xcm_transfer.rs
+//
+// A cross-chain transfer contract example for Polkadot using ink!
+// This contract demonstrates reserve-transferring the contracts chain's native
+// token to an account on a sibling parachain using XCM (Cross-Consensus Messaging).
+//
+// How a reserve transfer works
+// ----------------------------
+// Every asset has a *reserve*: the chain where the "real" tokens live. When the
+// native token of this chain is sent to a sibling parachain, this chain stays the
+// reserve. The tokens are moved into the sibling's *sovereign account* here, and
+// the sibling mints an equal amount of a derivative token to the beneficiary.
+// Sending the tokens back later burns the derivative on the sibling and releases
+// the reserved tokens from the sovereign account on this chain.
+//
+// The contract builds two XCM programs:
+//
+// 1. A *local* program, executed with `xcm_execute`, which withdraws the tokens
+//    from the contract's own account and deposits them into the sibling's
+//    sovereign account (`DepositReserveAsset`). That instruction also sends the
+//    sibling a `ReserveAssetDeposited` notice followed by the nested program.
+//
+// 2. A *remote* program (the nested one), executed by the sibling, which buys
+//    execution time with part of the transferred tokens and deposits the rest
+//    into the beneficiary's account.
+//
+// `xcm_send` is shown separately: it only *sends* a program to another chain and
+// executes nothing locally, which is how the contract asks the sibling to return
+// tokens that the contract's sovereign account holds there.
+//
+// Runtime requirements
+// --------------------
+// The node must configure `pallet-contracts` with `Xcm = pallet_xcm::Pallet<Runtime>`
+// and allow the contract's origin to execute and send XCM. The runtime weighs the
+// local program itself and charges the contract for it. The local development
+// node used elsewhere in the course has no sibling chains, so the e2e tests below
+// can only check that a transfer with nowhere to go is rolled back; the full
+// round trip needs two parachains, as in the deployment instructions.
+//
+// ink! version
+// ------------
+// Unlike the ink! 3 examples, this contract is written for ink! 5, like the
+// exercises: ink! 3 has no `xcm_execute` or `xcm_send`. Its unit tests use ink! 5's
+// own test API rather than the ink! 3 test fixtures, and its e2e tests use
+// `ink_e2e` 5.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod xcm_transfer {
+    use ink::{
+        prelude::vec,
+        xcm::{
+            v4::{prelude::*, Asset, Location, WeightLimit},
+            VersionedLocation, VersionedXcm,
+        },
+    };
+
+    /// The XCM transfer error types.
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// Returned if the caller is not the contract owner.
+        NotOwner,
+        /// Returned if the amount does not cover the destination fees.
+        AmountBelowFee,
+        /// Returned if the local XCM program failed to execute.
+        ExecuteFailed,
+        /// Returned if the XCM message could not be delivered.
+        SendFailed,
+    }
+
+    /// The XCM transfer result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    // Events are mirrored in `crates/events/src/xcm_transfer.rs` for off-chain decoding;
+    // keep the order of events and fields in sync with it.
+
+    /// Event emitted when a reserve transfer to a sibling is executed.
+    #[ink(event)]
+    pub struct ReserveTransferred {
+        #[ink(topic)]
+        para_id: u32,
+        #[ink(topic)]
+        beneficiary: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when the contract asks a sibling to return tokens.
+    #[ink(event)]
+    pub struct ReturnRequested {
+        #[ink(topic)]
+        para_id: u32,
+        amount: Balance,
+    }
+
+    /// The XCM transfer storage items.
+    #[ink(storage)]
+    pub struct XcmTransfer {
+        /// Account allowed to move the contract's funds.
+        owner: AccountId,
+        /// Parachain id of the chain this contract is deployed on.
+        self_para_id: u32,
+        /// Amount reserved from each transfer to pay for execution on the sibling.
+        remote_fee: Balance,
+    }
+
+    /// Returns the location of the sibling parachain `para_id`, as seen from here.
+    pub fn sibling(para_id: u32) -> Location {
+        Location::new(1, [Parachain(para_id)])
+    }
+
+    /// Returns the `AccountId32` junction for `account` on any network.
+    pub fn account_junction(account: &AccountId) -> Junction {
+        let id: [u8; 32] = *account.as_ref();
+        AccountId32 { network: None, id }
+    }
+
+    /// Builds the program this chain executes to reserve-transfer `amount` of its
+    /// native token to `beneficiary` on `para_id`.
+    ///
+    /// `self_para_id` is needed because the nested program runs on the sibling,
+    /// where this chain's native token is located at `../Parachain(self_para_id)`
+    /// rather than at `Here`.
+    pub fn reserve_transfer_message(
+        self_para_id: u32,
+        para_id: u32,
+        beneficiary: &AccountId,
+        amount: Balance,
+        remote_fee: Balance,
+    ) -> Xcm<()> {
+        // The asset as this chain sees it: its own native token.
+        let local_asset: Asset = (Here, amount).into();
+        // The fee asset as the sibling sees it: the derivative of our token.
+        let remote_fee_asset: Asset = (sibling(self_para_id), remote_fee).into();
+
+        let remote_program = Xcm(vec![
+            BuyExecution {
+                fees: remote_fee_asset,
+                weight_limit: WeightLimit::Unlimited,
+            },
+            DepositAsset {
+                assets: Wild(AllCounted(1)),
+                beneficiary: Location::new(0, [account_junction(beneficiary)]),
+            },
+        ]);
+
+        Xcm(vec![
+            WithdrawAsset(local_asset.into()),
+            DepositReserveAsset {
+                assets: Wild(AllCounted(1)),
+                dest: sibling(para_id),
+                xcm: remote_program,
+            },
+        ])
+    }
+
+    /// Builds the program sent to `para_id` asking it to return `amount` of our
+    /// native token, held by the contract's sovereign account there, to `recipient`
+    /// on this chain.
+    pub fn return_request_message(
+        self_para_id: u32,
+        recipient: &AccountId,
+        amount: Balance,
+        remote_fee: Balance,
+    ) -> Xcm<()> {
+        let derivative: Asset = (sibling(self_para_id), amount).into();
+        let fee: Asset = (sibling(self_para_id), remote_fee).into();
+
+        Xcm(vec![
+            WithdrawAsset(derivative.into()),
+            BuyExecution {
+                fees: fee,
+                weight_limit: WeightLimit::Unlimited,
+            },
+            // Burns the derivative on the sibling and tells us to release the
+            // reserved tokens to `recipient`.
+            InitiateReserveWithdraw {
+                assets: Wild(AllCounted(1)),
+                reserve: sibling(self_para_id),
+                xcm: Xcm(vec![
+                    BuyExecution {
+                        fees: (Here, remote_fee).into(),
+                        weight_limit: WeightLimit::Unlimited,
+                    },
+                    DepositAsset {
+                        assets: Wild(AllCounted(1)),
+                        beneficiary: Location::new(0, [account_junction(recipient)]),
+                    },
+                ]),
+            },
+        ])
+    }
+
+    impl XcmTransfer {
+        /// Creates a new transfer contract for the chain `self_para_id`, reserving
+        /// `remote_fee` from every transfer for execution on the sibling.
+        #[ink(constructor)]
+        pub fn new(self_para_id: u32, remote_fee: Balance) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                self_para_id,
+                remote_fee,
+            }
+        }
+
+        /// Returns the parachain id this contract was configured with.
+        #[ink(message)]
+        pub fn self_para_id(&self) -> u32 {
+            self.self_para_id
+        }
+
+        /// Returns the fee reserved for execution on the sibling.
+        #[ink(message)]
+        pub fn remote_fee(&self) -> Balance {
+            self.remote_fee
+        }
+
+        /// Reserve-transfers `amount` of the contract's balance to `beneficiary` on
+        /// the sibling parachain `para_id`. `beneficiary` receives `amount` minus
+        /// whatever part of `remote_fee` the sibling did not consume.
+        #[ink(message)]
+        pub fn reserve_transfer(
+            &mut self,
+            para_id: u32,
+            beneficiary: AccountId,
+            amount: Balance,
+        ) -> Result<()> {
+            self.ensure_owner()?;
+            if amount <= self.remote_fee {
+                return Err(Error::AmountBelowFee);
+            }
+
+            let message = VersionedXcm::V4(reserve_transfer_message(
+                self.self_para_id,
+                para_id,
+                &beneficiary,
+                amount,
+                self.remote_fee,
+            ));
+            self.env()
+                .xcm_execute(&message)
+                .map_err(|_| Error::ExecuteFailed)?;
+
+            self.env().emit_event(ReserveTransferred {
+                para_id,
+                beneficiary,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Asks the sibling `para_id` to return `amount` of our native token held by
+        /// this contract's sovereign account there. Nothing executes locally; the
+        /// tokens arrive once the sibling processes the message.
+        #[ink(message)]
+        pub fn request_return(&mut self, para_id: u32, amount: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            if amount <= self.remote_fee {
+                return Err(Error::AmountBelowFee);
+            }
+
+            let dest = VersionedLocation::V4(sibling(para_id));
+            let message = VersionedXcm::V4(return_request_message(
+                self.self_para_id,
+                &self.env().account_id(),
+                amount,
+                self.remote_fee,
+            ));
+            self.env()
+                .xcm_send(&dest, &message)
+                .map_err(|_| Error::SendFailed)?;
+
+            self.env().emit_event(ReturnRequested { para_id, amount });
+            Ok(())
+        }
+
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{
+            test::{default_accounts, set_caller},
+            DefaultEnvironment,
+        };
+
+        const SELF_PARA: u32 = 1000;
+        const SIBLING_PARA: u32 = 2000;
+
+        fn bob() -> AccountId {
+            default_accounts::<DefaultEnvironment>().bob
+        }
+
+        #[ink::test]
+        fn new_works() {
+            let contract = XcmTransfer::new(SELF_PARA, 10);
+            assert_eq!(contract.self_para_id(), SELF_PARA);
+            assert_eq!(contract.remote_fee(), 10);
+        }
+
+        #[ink::test]
+        fn reserve_transfer_withdraws_local_asset_first() {
+            let message = reserve_transfer_message(SELF_PARA, SIBLING_PARA, &bob(), 100, 10);
+            assert_eq!(message.0[0], WithdrawAsset((Here, 100u128).into()));
+        }
+
+        #[ink::test]
+        fn reserve_transfer_targets_sibling() {
+            let message = reserve_transfer_message(SELF_PARA, SIBLING_PARA, &bob(), 100, 10);
+            match &message.0[1] {
+                DepositReserveAsset { dest, .. } => {
+                    assert_eq!(*dest, Location::new(1, [Parachain(SIBLING_PARA)]));
+                }
+                other => panic!("unexpected instruction: {:?}", other),
+            }
+        }
+
+        #[ink::test]
+        fn remote_program_pays_in_derivative_and_deposits_to_beneficiary() {
+            let message = reserve_transfer_message(SELF_PARA, SIBLING_PARA, &bob(), 100, 10);
+            let remote = match &message.0[1] {
+                DepositReserveAsset { xcm, .. } => xcm.clone(),
+                other => panic!("unexpected instruction: {:?}", other),
+            };
+            assert_eq!(
+                remote.0[0],
+                BuyExecution {
+                    fees: (Location::new(1, [Parachain(SELF_PARA)]), 10u128).into(),
+                    weight_limit: WeightLimit::Unlimited,
+                }
+            );
+            assert_eq!(
+                remote.0[1],
+                DepositAsset {
+                    assets: Wild(AllCounted(1)),
+                    beneficiary: Location::new(0, [account_junction(&bob())]),
+                }
+            );
+        }
+
+        #[ink::test]
+        fn return_request_releases_reserve_to_recipient() {
+            let message = return_request_message(SELF_PARA, &bob(), 100, 10);
+            match &message.0[2] {
+                InitiateReserveWithdraw { reserve, xcm, .. } => {
+                    assert_eq!(*reserve, Location::new(1, [Parachain(SELF_PARA)]));
+                    assert_eq!(
+                        xcm.0[1],
+                        DepositAsset {
+                            assets: Wild(AllCounted(1)),
+                            beneficiary: Location::new(0, [account_junction(&bob())]),
+                        }
+                    );
+                }
+                other => panic!("unexpected instruction: {:?}", other),
+            }
+        }
+
+        #[ink::test]
+        fn only_owner_can_transfer() {
+            let mut contract = XcmTransfer::new(SELF_PARA, 10);
+            let accounts = default_accounts::<DefaultEnvironment>();
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.reserve_transfer(SIBLING_PARA, accounts.bob, 100),
+                Err(Error::NotOwner)
+            );
+            assert_eq!(contract.request_return(SIBLING_PARA, 100), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn amount_must_cover_fee() {
+            let mut contract = XcmTransfer::new(SELF_PARA, 10);
+            assert_eq!(
+                contract.reserve_transfer(SIBLING_PARA, bob(), 10),
+                Err(Error::AmountBelowFee)
+            );
+        }
+    }
+
+    /// Runs against the course's development node, which has no sibling chains:
+    /// XCM to a sibling cannot be routed, and the contract must not lose funds
+    /// over it.
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        // `E2EBackend`, used in the test signatures, is imported by `#[ink_e2e::test]`.
+        use ink_e2e::{ChainBackend, ContractsBackend};
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        const ENDOWMENT: Balance = 1_000_000_000_000;
+
+        #[ink_e2e::test]
+        async fn unroutable_transfer_is_rolled_back<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let contract = client
+                .instantiate("xcm_transfer", &ink_e2e::alice(), &mut XcmTransferRef::new(1000, 10))
+                .value(ENDOWMENT)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<XcmTransfer>();
+            let before = client.free_balance(contract.account_id).await?;
+
+            let bob = AccountId::from(ink_e2e::bob().public_key().0);
+            let transfer = call_builder.reserve_transfer(2000, bob, 500);
+            let result = client.call(&ink_e2e::alice(), &transfer).dry_run().await?;
+            assert_eq!(result.return_value(), Err(Error::ExecuteFailed));
+
+            assert_eq!(client.free_balance(contract.account_id).await?, before);
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn unroutable_return_request_fails<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let contract = client
+                .instantiate("xcm_transfer", &ink_e2e::alice(), &mut XcmTransferRef::new(1000, 10))
+                .value(ENDOWMENT)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<XcmTransfer>();
+
+            let request = call_builder.request_return(2000, 500);
+            let result = client.call(&ink_e2e::alice(), &request).dry_run().await?;
+            assert_eq!(result.return_value(), Err(Error::SendFailed));
+            Ok(())
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// 1. Install the ink! CLI:
+//    cargo install cargo-contract --force
+//
+// 2. Compile the contract:
+//    cargo +nightly contract build
+//
+// 3. Deploy on a parachain whose runtime wires pallet-xcm into pallet-contracts:
+//    - Go to https://polkadot.js.org/apps/
+//    - Connect to the contracts parachain (e.g. a local zombienet with two parachains)
+//    - Navigate to "Developer" -> "Contracts"
+//    - Click "Upload & Deploy Code"
+//    - Upload the generated .contract file
+//    - Set this chain's parachain id and the remote fee
+//    - Endow the contract with enough balance to transfer
+//
+// 4. Interact with the contract:
+//    - Use the "reserveTransfer" method with the sibling's parachain id and a beneficiary
+//    - Check the beneficiary's derivative balance on the sibling chain
+//    - Use the "requestReturn" method to bring the tokens back
@@ -0,0 +1,186 @@
+// examples/mocks/mock_dao.rs
+//
+// A mock DAO for testing contracts that take orders from a DAO.
+// It implements `SpendApproval` with the same selector as `demo-contracts/treasury.rs`,
+// but any account may approve or revoke a spend, and any account may have it set a
+// treasury's budget, so a test can play every part of a vote without running one.
+// Never deploy it anywhere real.
+//
+// `treasury.rs` asks this DAO whether a spend was approved, and only accepts
+// `setBudget` from it, on a local node; its deployment notes walk through both.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+/// Identifies a spend proposal, as in the treasury example.
+pub type ProposalId = u32;
+
+/// The interface the treasury uses to ask its DAO whether a spend was approved.
+#[ink::trait_definition]
+pub trait SpendApproval {
+    /// Returns whether the DAO approved proposal `proposal_id` of `treasury`.
+    #[ink(message)]
+    fn is_spend_approved(&self, treasury: ink_env::AccountId, proposal_id: ProposalId) -> bool;
+}
+
+#[ink::contract]
+mod mock_dao {
+    use super::{ProposalId, SpendApproval};
+    use ink_env::call::{build_call, Call, ExecutionInput, Selector};
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// Selector of the treasury's `set_budget`.
+    pub const SET_BUDGET_SELECTOR: [u8; 4] = [0x71, 0x0f, 0x2f, 0x52];
+
+    /// The mock DAO error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the treasury could not be called.
+        CallFailed,
+        /// Returned if the treasury rejected the call.
+        Rejected,
+    }
+
+    /// The mock DAO result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Funds a treasury can hold and spend. Same encoding as the treasury's `Asset`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Asset {
+        /// The chain's native token.
+        Native,
+        /// The ERC-20 token at the given address.
+        Erc20(AccountId),
+    }
+
+    /// Event emitted when a spend is approved or revoked.
+    #[ink(event)]
+    pub struct SpendApprovalSet {
+        #[ink(topic)]
+        treasury: AccountId,
+        proposal_id: ProposalId,
+        approved: bool,
+    }
+
+    /// The mock DAO storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct MockDao {
+        /// Approved proposals of each treasury.
+        approved: Mapping<(AccountId, ProposalId), ()>,
+    }
+
+    impl MockDao {
+        /// Creates a DAO that has approved nothing.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|_: &mut Self| {})
+        }
+
+        /// Approves proposal `proposal_id` of `treasury`. Anyone may call this.
+        #[ink(message)]
+        pub fn approve_spend(&mut self, treasury: AccountId, proposal_id: ProposalId) {
+            self.approved.insert((treasury, proposal_id), &());
+            self.env().emit_event(SpendApprovalSet {
+                treasury,
+                proposal_id,
+                approved: true,
+            });
+        }
+
+        /// Withdraws the approval of proposal `proposal_id` of `treasury`. Anyone may
+        /// call this.
+        #[ink(message)]
+        pub fn revoke_spend(&mut self, treasury: AccountId, proposal_id: ProposalId) {
+            self.approved.remove((treasury, proposal_id));
+            self.env().emit_event(SpendApprovalSet {
+                treasury,
+                proposal_id,
+                approved: false,
+            });
+        }
+
+        /// Sets the budget of `asset` on `treasury`, as the DAO. Anyone may call this.
+        #[ink(message)]
+        pub fn set_budget(
+            &mut self,
+            treasury: AccountId,
+            asset: Asset,
+            per_period: Balance,
+        ) -> Result<()> {
+            // The treasury's `Error` has no fields, so any of its errors decodes as
+            // one byte.
+            build_call::<Environment>()
+                .call_type(Call::new().callee(treasury).gas_limit(0))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(SET_BUDGET_SELECTOR))
+                        .push_arg(asset)
+                        .push_arg(per_period),
+                )
+                .returns::<core::result::Result<(), u8>>()
+                .fire()
+                .map_err(|_| Error::CallFailed)?
+                .map_err(|_| Error::Rejected)
+        }
+    }
+
+    impl SpendApproval for MockDao {
+        #[ink(message)]
+        fn is_spend_approved(&self, treasury: AccountId, proposal_id: ProposalId) -> bool {
+            self.approved.contains((treasury, proposal_id))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        #[ink::test]
+        fn nothing_is_approved_at_first() {
+            let dao = MockDao::new();
+            assert!(!dao.is_spend_approved(fixtures::learner(0), 0));
+        }
+
+        #[ink::test]
+        fn anyone_can_approve_and_revoke() {
+            let accounts = fixtures::accounts();
+            let treasury = fixtures::learner(0);
+            let mut dao = MockDao::new();
+            fixtures::set_caller(accounts.eve);
+            dao.approve_spend(treasury, 3);
+            assert!(dao.is_spend_approved(treasury, 3));
+            assert!(!dao.is_spend_approved(treasury, 4));
+            assert!(!dao.is_spend_approved(fixtures::learner(1), 3));
+            dao.revoke_spend(treasury, 3);
+            assert!(!dao.is_spend_approved(treasury, 3));
+        }
+
+        /// The treasury calls `is_spend_approved` through its pinned selector, and this
+        /// DAO calls the treasury's `set_budget` through its own.
+        #[ink::test]
+        fn selectors_match_the_treasury() {
+            assert_eq!(
+                ink::selector_bytes!("SpendApproval::is_spend_approved"),
+                [0x98, 0x1a, 0x73, 0xb5]
+            );
+            assert_eq!(SET_BUDGET_SELECTOR, ink::selector_bytes!("set_budget"));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// This contract is for tests only. Deploy it on a local development node, never
+// on a live chain:
+//
+// 1. Compile the contract:
+//    cargo +nightly contract build
+//
+// 2. Deploy it with "new" and pass its address to the contract under test (e.g. as
+//    `treasury.rs`'s DAO). Call "setBudget" with the treasury's address to set its
+//    budgets, and "approveSpend" to approve a proposal before executing it.
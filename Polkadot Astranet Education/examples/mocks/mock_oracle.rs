@@ -0,0 +1,165 @@
+// examples/mocks/mock_oracle.rs
+//
+// A mock price oracle for testing contracts that read prices.
+// Anyone may set the price of any asset, and the oracle can be switched into a
+// failing state, so a test can drive price moves and outages directly. Never deploy
+// it anywhere real.
+//
+// ink! 3 cannot call one contract from another in off-chain tests, so the mocks are
+// deployed next to the contract under test on a local node; `cdp.rs` reads its
+// collateral price from this oracle that way.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod mock_oracle {
+    use ink_storage::{
+        traits::{PackedLayout, SpreadAllocate, SpreadLayout},
+        Mapping,
+    };
+
+    /// The mock oracle error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if no price has been set for the asset.
+        NoPrice,
+        /// Returned by every query while the oracle is set to fail.
+        Unavailable,
+    }
+
+    /// The mock oracle result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A price and the block timestamp it was set at.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub struct PricePoint {
+        /// Price of one unit of the asset, in the quote asset's smallest unit.
+        pub price: Balance,
+        /// When the price was set.
+        pub updated_at: Timestamp,
+    }
+
+    /// Event emitted when a price is set.
+    #[ink(event)]
+    pub struct PriceSet {
+        #[ink(topic)]
+        asset: AccountId,
+        price: Balance,
+    }
+
+    /// The mock oracle storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct MockOracle {
+        /// Latest price of each asset.
+        prices: Mapping<AccountId, PricePoint>,
+        /// Whether queries currently fail.
+        failing: bool,
+    }
+
+    impl MockOracle {
+        /// Creates an oracle with no prices.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|_: &mut Self| {})
+        }
+
+        /// Sets the price of `asset`, stamped with the current block time. Anyone may
+        /// call this.
+        #[ink(message)]
+        pub fn set_price(&mut self, asset: AccountId, price: Balance) {
+            let updated_at = self.env().block_timestamp();
+            self.prices.insert(asset, &PricePoint { price, updated_at });
+            self.env().emit_event(PriceSet { asset, price });
+        }
+
+        /// Sets the price of `asset` with an explicit timestamp, to test staleness
+        /// checks without moving the chain's clock.
+        #[ink(message)]
+        pub fn set_price_at(&mut self, asset: AccountId, price: Balance, updated_at: Timestamp) {
+            self.prices.insert(asset, &PricePoint { price, updated_at });
+            self.env().emit_event(PriceSet { asset, price });
+        }
+
+        /// Makes every query fail with `Unavailable` until switched back.
+        #[ink(message)]
+        pub fn set_failing(&mut self, failing: bool) {
+            self.failing = failing;
+        }
+
+        /// Returns the latest price of `asset`.
+        #[ink(message)]
+        pub fn price(&self, asset: AccountId) -> Result<Balance> {
+            self.latest(asset).map(|point| point.price)
+        }
+
+        /// Returns the latest price of `asset` and when it was set.
+        #[ink(message)]
+        pub fn latest(&self, asset: AccountId) -> Result<PricePoint> {
+            if self.failing {
+                return Err(Error::Unavailable);
+            }
+            self.prices.get(asset).ok_or(Error::NoPrice)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        #[ink::test]
+        fn unset_asset_has_no_price() {
+            let oracle = MockOracle::new();
+            assert_eq!(oracle.price(fixtures::learner(0)), Err(Error::NoPrice));
+        }
+
+        #[ink::test]
+        fn anyone_can_set_prices() {
+            let accounts = fixtures::accounts();
+            let asset = fixtures::learner(0);
+            let mut oracle = MockOracle::new();
+            fixtures::set_caller(accounts.eve);
+            oracle.set_price(asset, 1_500);
+            assert_eq!(oracle.price(asset), Ok(1_500));
+            oracle.set_price_at(asset, 1_200, 42);
+            assert_eq!(
+                oracle.latest(asset),
+                Ok(PricePoint {
+                    price: 1_200,
+                    updated_at: 42
+                })
+            );
+        }
+
+        #[ink::test]
+        fn failing_oracle_rejects_queries() {
+            let asset = fixtures::learner(0);
+            let mut oracle = MockOracle::new();
+            oracle.set_price(asset, 1_500);
+            oracle.set_failing(true);
+            assert_eq!(oracle.price(asset), Err(Error::Unavailable));
+            oracle.set_failing(false);
+            assert_eq!(oracle.price(asset), Ok(1_500));
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// This contract is for tests only. Deploy it on a local development node, never
+// on a live chain:
+//
+// 1. Compile the contract:
+//    cargo +nightly contract build
+//
+// 2. Deploy it with "new", then call "setPrice" for each asset the contract under
+//    test reads, and pass its address to that contract (e.g. as `cdp.rs`'s oracle).
@@ -0,0 +1,258 @@
+// examples/mocks/mock_token.rs
+//
+// A mock ERC-20 token for testing contracts that hold or move tokens.
+// It has the same messages and selectors as `demo-contracts/erc20.rs`, plus `mint`
+// and `burn` that anyone may call, so a test can hand out balances without a
+// faucet or an owner. Never deploy it anywhere real.
+//
+// `cdp.rs` takes it as collateral and `flashloan_pool.rs` lends it on a local node;
+// their deployment notes walk through minting the balances they need.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod mock_token {
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// The mock token error types. Same encoding as the ERC-20 example's.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the balance is insufficient for the operation.
+        InsufficientBalance,
+        /// Returned if the allowance is insufficient for the operation.
+        InsufficientAllowance,
+    }
+
+    /// The mock token result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Event emitted when tokens move, are minted (`from` is `None`) or burned (`to`
+    /// is `None`).
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    /// Event emitted when an approval occurs.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    /// The mock token storage items.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct MockToken {
+        /// Total token supply.
+        total_supply: Balance,
+        /// Mapping from owner to balance.
+        balances: Mapping<AccountId, Balance>,
+        /// Mapping from owner to spender to allowance.
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+        /// Token decimals, so tests can mimic tokens with different precision.
+        decimals: u8,
+    }
+
+    impl MockToken {
+        /// Creates a token with no supply.
+        #[ink(constructor)]
+        pub fn new(decimals: u8) -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.decimals = decimals;
+            })
+        }
+
+        /// Returns the token decimals.
+        #[ink(message)]
+        pub fn decimals(&self) -> u8 {
+            self.decimals
+        }
+
+        /// Returns the total token supply.
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// Returns the account balance for the specified `owner`.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or(0)
+        }
+
+        /// Returns the amount which `spender` is allowed to withdraw from `owner`.
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or(0)
+        }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            self.transfer_from_to(from, to, value)
+        }
+
+        /// Allows `spender` to withdraw from the caller's account multiple times, up to
+        /// the `value` amount.
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            self.allowances.insert((owner, spender), &value);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` tokens on behalf of `from` to the account `to`.
+        #[ink(message)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let caller = self.env().caller();
+            let allowance = self.allowance(from, caller);
+            if allowance < value {
+                return Err(Error::InsufficientAllowance);
+            }
+            self.transfer_from_to(from, to, value)?;
+            self.allowances.insert((from, caller), &(allowance - value));
+            Ok(())
+        }
+
+        /// Creates `value` new tokens for `to`. Anyone may call this.
+        #[ink(message)]
+        pub fn mint(&mut self, to: AccountId, value: Balance) {
+            let balance = self.balance_of(to);
+            self.balances.insert(to, &(balance + value));
+            self.total_supply += value;
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(to),
+                value,
+            });
+        }
+
+        /// Destroys `value` tokens of `from`. Anyone may call this.
+        #[ink(message)]
+        pub fn burn(&mut self, from: AccountId, value: Balance) -> Result<()> {
+            let balance = self.balance_of(from);
+            if balance < value {
+                return Err(Error::InsufficientBalance);
+            }
+            self.balances.insert(from, &(balance - value));
+            self.total_supply -= value;
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: None,
+                value,
+            });
+            Ok(())
+        }
+
+        fn transfer_from_to(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let from_balance = self.balance_of(from);
+            if from_balance < value {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.balances.insert(from, &(from_balance - value));
+            let to_balance = self.balance_of(to);
+            self.balances.insert(to, &(to_balance + value));
+
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                value,
+            });
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use astranet_test_fixtures as fixtures;
+        use ink_lang as ink;
+
+        #[ink::test]
+        fn anyone_can_mint() {
+            let accounts = fixtures::accounts();
+            let mut token = MockToken::new(12);
+            fixtures::set_caller(accounts.eve);
+            token.mint(accounts.bob, 100);
+            assert_eq!(token.balance_of(accounts.bob), 100);
+            assert_eq!(token.total_supply(), 100);
+            assert_eq!(token.decimals(), 12);
+        }
+
+        #[ink::test]
+        fn anyone_can_burn() {
+            let accounts = fixtures::accounts();
+            let mut token = MockToken::new(12);
+            token.mint(accounts.bob, 100);
+            fixtures::set_caller(accounts.charlie);
+            assert_eq!(token.burn(accounts.bob, 40), Ok(()));
+            assert_eq!(token.balance_of(accounts.bob), 60);
+            assert_eq!(token.total_supply(), 60);
+            assert_eq!(
+                token.burn(accounts.bob, 61),
+                Err(Error::InsufficientBalance)
+            );
+        }
+
+        #[ink::test]
+        fn transfer_from_works_like_erc20() {
+            let accounts = fixtures::accounts();
+            let mut token = MockToken::new(12);
+            token.mint(accounts.alice, 50);
+            assert_eq!(token.approve(accounts.bob, 20), Ok(()));
+
+            fixtures::set_caller(accounts.bob);
+            assert_eq!(
+                token.transfer_from(accounts.alice, accounts.charlie, 30),
+                Err(Error::InsufficientAllowance)
+            );
+            assert_eq!(
+                token.transfer_from(accounts.alice, accounts.charlie, 20),
+                Ok(())
+            );
+            assert_eq!(token.balance_of(accounts.charlie), 20);
+            assert_eq!(token.allowance(accounts.alice, accounts.bob), 0);
+        }
+    }
+}
+
+// Deployment Instructions:
+//
+// This contract is for tests only. Deploy it on a local development node, never
+// on a live chain:
+//
+// 1. Compile the contract:
+//    cargo +nightly contract build
+//
+// 2. Deploy it with "new" and the decimals to mimic, then call "mint" to give each
+//    test account its starting balance, and pass its address to the contract under
+//    test (e.g. as `flashloan_pool.rs`'s token or `cdp.rs`'s collateral).
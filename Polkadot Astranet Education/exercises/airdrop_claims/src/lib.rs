@@ -0,0 +1,85 @@
+// exercises/airdrop_claims/src/lib.rs
+//
+// Ignored cross-contract results
+//
+// This airdrop hands out a token (`exercises/support/token`): the owner allocates
+// amounts to accounts, and each account claims its allocation once, paid from the
+// airdrop's own token balance. The token's `transfer` does not trap when it cannot
+// pay; it returns `Err(TokenError::InsufficientBalance)`. A caller that ignores that
+// result carries on as if it had paid. Here, an account claiming from an airdrop that
+// has run short loses its allocation and gets nothing.
+//
+// When a message returns `Err`, everything it wrote is rolled back, so turning the
+// token's error into the message's error is enough to leave the claim for later.
+//
+// TODO: find and fix the bug. A claim the airdrop cannot pay must fail with
+// `Error::TransferFailed` and keep the allocation, so it can be claimed once the
+// airdrop is topped up. Claims it can pay must keep working as they do.
+//
+// The grading tests deploy the token and the airdrop to a local node, so they need
+// `substrate-contracts-node` on the `PATH` or in `CONTRACTS_NODE`; `devnet` downloads
+// one. Then run `grader run airdrop_claims` to check your work.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod airdrop_claims {
+    use ink::storage::Mapping;
+    use token::Token;
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// Only the owner can allocate.
+        NotOwner,
+        /// The caller has no allocation.
+        NothingToClaim,
+        /// The token did not pay out the claim.
+        TransferFailed,
+    }
+
+    #[ink(storage)]
+    pub struct AirdropClaims {
+        owner: AccountId,
+        token: ink::contract_ref!(Token),
+        allocations: Mapping<AccountId, Balance>,
+    }
+
+    impl AirdropClaims {
+        /// Creates an airdrop of the token at `token`, owned by the caller.
+        #[ink(constructor)]
+        pub fn new(token: AccountId) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                token: token.into(),
+                allocations: Mapping::default(),
+            }
+        }
+
+        /// Allocates `amount` more to `account`. Owner only.
+        #[ink(message)]
+        pub fn allocate(&mut self, account: AccountId, amount: Balance) -> Result<(), Error> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            let allocation = self.allocation(account).saturating_add(amount);
+            self.allocations.insert(account, &allocation);
+            Ok(())
+        }
+
+        /// What `account` can claim.
+        #[ink(message)]
+        pub fn allocation(&self, account: AccountId) -> Balance {
+            self.allocations.get(account).unwrap_or_default()
+        }
+
+        /// Pays the caller their allocation, returning the amount.
+        #[ink(message)]
+        pub fn claim(&mut self) -> Result<Balance, Error> {
+            let caller = self.env().caller();
+            let amount = self.allocations.take(caller).ok_or(Error::NothingToClaim)?;
+            let _ = self.token.transfer(caller, amount);
+            Ok(amount)
+        }
+    }
+}
@@ -0,0 +1,191 @@
+// exercises/airdrop_claims/tests/grading.rs
+//
+// The grading tests of the exercise. Learners are asked not to read them: they check
+// the behaviour `src/lib.rs` describes, whichever way it is implemented.
+//
+// They are end-to-end tests: each deploys the token and the airdrop to a fresh local
+// node and calls them as accounts would.
+
+use airdrop_claims::airdrop_claims::{AirdropClaims, AirdropClaimsRef, Error};
+use astranet_scenario::{Actor, Scenario};
+use token::{
+    token::{SimpleToken, SimpleTokenRef},
+    Token,
+};
+
+type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+#[ink_e2e::test]
+async fn allocations_are_claimed_once<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+    let mut scenario = Scenario::new("claims", &mut client);
+    let token = scenario
+        .deploy(
+            "deploy a token",
+            Actor::Alice,
+            "token",
+            &mut SimpleTokenRef::new(1_000),
+        )
+        .await;
+    let airdrop = scenario
+        .deploy(
+            "deploy the airdrop",
+            Actor::Alice,
+            "airdrop_claims",
+            &mut AirdropClaimsRef::new(token.account_id),
+        )
+        .await;
+    let airdrop_account = airdrop.account_id;
+    let mut token = token.call_builder::<SimpleToken>();
+    let mut airdrop = airdrop.call_builder::<AirdropClaims>();
+
+    scenario
+        .call(
+            "fund the airdrop",
+            Actor::Alice,
+            &token.transfer(airdrop_account, 500),
+        )
+        .await
+        .expect("funding the airdrop failed");
+    for (account, amount) in [(Actor::Bob, 100), (Actor::Charlie, 50)] {
+        scenario
+            .call(
+                "allocate",
+                Actor::Alice,
+                &airdrop.allocate(account.account_id(), amount),
+            )
+            .await
+            .expect("allocating failed");
+    }
+    let result = scenario
+        .call(
+            "bob allocates to bob",
+            Actor::Bob,
+            &airdrop.allocate(Actor::Bob.account_id(), 1),
+        )
+        .await;
+    assert_eq!(result, Err(Error::NotOwner));
+
+    let claimed = scenario
+        .call("bob claims", Actor::Bob, &airdrop.claim())
+        .await;
+    assert_eq!(claimed, Ok(100));
+    scenario
+        .assert_query_eq(
+            "bob has 100 tokens",
+            Actor::Bob,
+            &token.balance_of(Actor::Bob.account_id()),
+            100,
+        )
+        .await;
+    scenario
+        .assert_query_eq(
+            "bob's allocation is used",
+            Actor::Bob,
+            &airdrop.allocation(Actor::Bob.account_id()),
+            0,
+        )
+        .await;
+    let claimed = scenario
+        .call("bob claims again", Actor::Bob, &airdrop.claim())
+        .await;
+    assert_eq!(claimed, Err(Error::NothingToClaim));
+    let claimed = scenario
+        .call("dave claims", Actor::Dave, &airdrop.claim())
+        .await;
+    assert_eq!(claimed, Err(Error::NothingToClaim));
+    scenario
+        .assert_query_eq(
+            "the airdrop paid 100",
+            Actor::Bob,
+            &token.balance_of(airdrop_account),
+            400,
+        )
+        .await;
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn claims_the_airdrop_cannot_pay_are_kept<Client: E2EBackend>(
+    mut client: Client,
+) -> E2EResult<()> {
+    let mut scenario = Scenario::new("short airdrop", &mut client);
+    let token = scenario
+        .deploy(
+            "deploy a token",
+            Actor::Alice,
+            "token",
+            &mut SimpleTokenRef::new(1_000),
+        )
+        .await;
+    let airdrop = scenario
+        .deploy(
+            "deploy the airdrop",
+            Actor::Alice,
+            "airdrop_claims",
+            &mut AirdropClaimsRef::new(token.account_id),
+        )
+        .await;
+    let airdrop_account = airdrop.account_id;
+    let mut token = token.call_builder::<SimpleToken>();
+    let mut airdrop = airdrop.call_builder::<AirdropClaims>();
+
+    scenario
+        .call(
+            "fund the airdrop with 30",
+            Actor::Alice,
+            &token.transfer(airdrop_account, 30),
+        )
+        .await
+        .expect("funding the airdrop failed");
+    scenario
+        .call(
+            "allocate 100 to bob",
+            Actor::Alice,
+            &airdrop.allocate(Actor::Bob.account_id(), 100),
+        )
+        .await
+        .expect("allocating failed");
+
+    let claimed = scenario
+        .call("bob claims", Actor::Bob, &airdrop.claim())
+        .await;
+    assert_eq!(claimed, Err(Error::TransferFailed));
+    scenario
+        .assert_query_eq(
+            "bob has no tokens",
+            Actor::Bob,
+            &token.balance_of(Actor::Bob.account_id()),
+            0,
+        )
+        .await;
+    scenario
+        .assert_query_eq(
+            "bob keeps the allocation",
+            Actor::Bob,
+            &airdrop.allocation(Actor::Bob.account_id()),
+            100,
+        )
+        .await;
+
+    scenario
+        .call(
+            "top the airdrop up",
+            Actor::Alice,
+            &token.transfer(airdrop_account, 70),
+        )
+        .await
+        .expect("funding the airdrop failed");
+    let claimed = scenario
+        .call("bob claims again", Actor::Bob, &airdrop.claim())
+        .await;
+    assert_eq!(claimed, Ok(100));
+    scenario
+        .assert_query_eq(
+            "bob has 100 tokens",
+            Actor::Bob,
+            &token.balance_of(Actor::Bob.account_id()),
+            100,
+        )
+        .await;
+    Ok(())
+}
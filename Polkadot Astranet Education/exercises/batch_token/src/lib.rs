@@ -0,0 +1,104 @@
+// exercises/batch_token/src/lib.rs
+//
+// Overflow in a batch transfer
+//
+// This token works, and its tests pass, but it has a bug that lets anyone with a
+// balance mint tokens out of nothing. It is the bug that ended the BEC token in 2018:
+// `batch_transfer` sends the same value to many recipients, checks the sender can
+// afford `value * recipients`, and that product wraps around. With two recipients
+// and a value of 2^127 it wraps to 0, which every sender can afford, and both
+// recipients are credited 2^127 tokens.
+//
+// Whether plain arithmetic panics or wraps on overflow depends on how the contract is
+// built, so a contract cannot count on the panic. Here the wrapping is spelled out,
+// and behaves the same however it is built.
+//
+// TODO: find and fix the bug. A batch whose total does not fit in a `Balance` must
+// fail with `Error::Overflow` and change nothing. Everything else, `batch_transfer`
+// included, must keep working as it does.
+//
+// Then run `grader run batch_token` to check your work.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod batch_token {
+    use ink::{prelude::vec::Vec, storage::Mapping};
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// The sender's balance is below the amount.
+        InsufficientBalance,
+        /// An amount does not fit in a `Balance`.
+        Overflow,
+    }
+
+    #[ink(storage)]
+    pub struct BatchToken {
+        total_supply: Balance,
+        balances: Mapping<AccountId, Balance>,
+    }
+
+    impl BatchToken {
+        /// Creates a token with `total_supply` tokens, all the caller's.
+        #[ink(constructor)]
+        pub fn new(total_supply: Balance) -> Self {
+            let mut balances = Mapping::default();
+            balances.insert(Self::env().caller(), &total_supply);
+            Self {
+                total_supply,
+                balances,
+            }
+        }
+
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or_default()
+        }
+
+        /// Sends `value` from the caller to `to`.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<(), Error> {
+            let from = self.env().caller();
+            let balance = self.balance_of(from);
+            if balance < value {
+                return Err(Error::InsufficientBalance);
+            }
+            self.balances.insert(from, &(balance - value));
+            self.credit(to, value)
+        }
+
+        /// Sends `value` from the caller to each of `recipients`.
+        #[ink(message)]
+        pub fn batch_transfer(
+            &mut self,
+            recipients: Vec<AccountId>,
+            value: Balance,
+        ) -> Result<(), Error> {
+            let from = self.env().caller();
+            let total = value.wrapping_mul(recipients.len() as Balance);
+            let balance = self.balance_of(from);
+            if balance < total {
+                return Err(Error::InsufficientBalance);
+            }
+            self.balances.insert(from, &(balance - total));
+            for to in recipients {
+                self.credit(to, value)?;
+            }
+            Ok(())
+        }
+
+        fn credit(&mut self, to: AccountId, value: Balance) -> Result<(), Error> {
+            let balance = self.balance_of(to);
+            // Cannot overflow while no balance exceeds the total supply.
+            self.balances.insert(to, &balance.wrapping_add(value));
+            Ok(())
+        }
+    }
+}
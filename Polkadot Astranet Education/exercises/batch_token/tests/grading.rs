@@ -0,0 +1,112 @@
+// exercises/batch_token/tests/grading.rs
+//
+// The grading tests of the exercise. Learners are asked not to read them: they check
+// the behaviour `src/lib.rs` describes, whichever way it is implemented.
+
+use batch_token::batch_token::{BatchToken, Error};
+use ink::{
+    env::{
+        test::{default_accounts, set_caller, DefaultAccounts},
+        DefaultEnvironment,
+    },
+    primitives::AccountId,
+};
+
+fn accounts() -> DefaultAccounts<DefaultEnvironment> {
+    default_accounts::<DefaultEnvironment>()
+}
+
+fn holders() -> [AccountId; 4] {
+    let accounts = accounts();
+    [
+        accounts.alice,
+        accounts.bob,
+        accounts.charlie,
+        accounts.django,
+    ]
+}
+
+/// Alice's token with a supply of 1000, Bob holding 100 of it.
+fn token() -> BatchToken {
+    set_caller::<DefaultEnvironment>(accounts().alice);
+    let mut token = BatchToken::new(1_000);
+    token.transfer(accounts().bob, 100).unwrap();
+    token
+}
+
+fn assert_supply_adds_up(token: &BatchToken) {
+    let held: u128 = holders().iter().map(|&owner| token.balance_of(owner)).sum();
+    assert_eq!(held, token.total_supply());
+}
+
+#[ink::test]
+fn transfers_work() {
+    let mut token = token();
+    assert_eq!(token.balance_of(accounts().alice), 900);
+    assert_eq!(token.balance_of(accounts().bob), 100);
+    assert_eq!(
+        token.transfer(accounts().charlie, 901),
+        Err(Error::InsufficientBalance)
+    );
+    token.transfer(accounts().charlie, 900).unwrap();
+    assert_eq!(token.balance_of(accounts().alice), 0);
+    assert_eq!(token.balance_of(accounts().charlie), 900);
+}
+
+#[ink::test]
+fn batch_transfers_credit_every_recipient() {
+    let mut token = token();
+    let recipients = vec![accounts().bob, accounts().charlie, accounts().django];
+    token.batch_transfer(recipients, 100).unwrap();
+    assert_eq!(token.balance_of(accounts().alice), 600);
+    assert_eq!(token.balance_of(accounts().bob), 200);
+    assert_eq!(token.balance_of(accounts().charlie), 100);
+    assert_eq!(token.balance_of(accounts().django), 100);
+    token.batch_transfer(Vec::new(), 100).unwrap();
+    assert_eq!(token.balance_of(accounts().alice), 600);
+    assert_supply_adds_up(&token);
+}
+
+#[ink::test]
+fn batch_transfers_check_the_total() {
+    let mut token = token();
+    let recipients = vec![accounts().charlie, accounts().django];
+    assert_eq!(
+        token.batch_transfer(recipients, 451),
+        Err(Error::InsufficientBalance)
+    );
+    assert_eq!(token.balance_of(accounts().alice), 900);
+    assert_eq!(token.balance_of(accounts().charlie), 0);
+    assert_supply_adds_up(&token);
+}
+
+#[ink::test]
+fn a_wrapping_total_is_refused() {
+    let mut token = token();
+    set_caller::<DefaultEnvironment>(accounts().bob);
+    let recipients = vec![accounts().charlie, accounts().django];
+    assert_eq!(
+        token.batch_transfer(recipients, 1 << 127),
+        Err(Error::Overflow)
+    );
+    assert_eq!(token.balance_of(accounts().bob), 100);
+    assert_eq!(token.balance_of(accounts().charlie), 0);
+    assert_eq!(token.balance_of(accounts().django), 0);
+    assert_supply_adds_up(&token);
+}
+
+#[ink::test]
+fn a_total_wrapping_to_an_affordable_amount_is_refused() {
+    let mut token = token();
+    set_caller::<DefaultEnvironment>(accounts().bob);
+    // Three times this wraps around to 2, which Bob could afford.
+    let value = u128::MAX / 3 + 1;
+    let recipients = vec![accounts().charlie, accounts().django, accounts().alice];
+    assert_eq!(
+        token.batch_transfer(recipients, value),
+        Err(Error::Overflow)
+    );
+    assert_eq!(token.balance_of(accounts().bob), 100);
+    assert_eq!(token.balance_of(accounts().alice), 900);
+    assert_supply_adds_up(&token);
+}
@@ -0,0 +1,71 @@
+// exercises/child_factory/src/lib.rs
+//
+// Deploying contracts from a contract
+//
+// A contract can instantiate other contracts, as long as their code is already on
+// chain: it names the code by its hash, and the new contract gets its own address and
+// storage. The code deployed here is `exercises/support/child`, whose `ChildRef` has
+// the same constructors as `Child`; `ChildRef::new(owner, label)` starts a builder
+// which needs the code hash, an endowment and a salt before `instantiate` or
+// `try_instantiate` deploys the child.
+//
+// The salt is what makes addresses unique: a child's address is derived from the
+// deployer, the code hash, the constructor input and the salt, so the same input and
+// salt twice would mean the same address twice, and the second deployment fails.
+//
+// Implement:
+//
+// - `new`: remembers the code hash children are deployed from.
+// - `create`: deploys a child labelled `label` and owned by the caller, returning its
+//   address. Every call deploys a new child, even with a label used before. If the
+//   child cannot be deployed (say the code hash is not uploaded), returns
+//   `Error::InstantiationFailed` instead of trapping.
+// - `children`: the addresses of the children deployed so far, oldest first.
+//
+// The grading tests upload the child's code and deploy the factory to a local node, so
+// they need `substrate-contracts-node` on the `PATH` or in `CONTRACTS_NODE`; `devnet`
+// downloads one.
+//
+// Fill in the `TODO`s below, then run `grader run child_factory` to check your work.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod child_factory {
+    use ink::prelude::{string::String, vec::Vec};
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// The child could not be deployed.
+        InstantiationFailed,
+    }
+
+    #[ink(storage)]
+    pub struct ChildFactory {
+        // TODO: the child code hash and the children deployed so far.
+    }
+
+    impl ChildFactory {
+        /// Creates a factory deploying children from `child_code_hash`.
+        #[ink(constructor)]
+        pub fn new(child_code_hash: Hash) -> Self {
+            // TODO: remember the code hash.
+            let _ = child_code_hash;
+            Self {}
+        }
+
+        /// Deploys a child labelled `label`, owned by the caller.
+        #[ink(message)]
+        pub fn create(&mut self, label: String) -> Result<AccountId, Error> {
+            let _ = label;
+            todo!("implement `create`")
+        }
+
+        /// The children deployed so far, oldest first.
+        #[ink(message)]
+        pub fn children(&self) -> Vec<AccountId> {
+            todo!("implement `children`")
+        }
+    }
+}
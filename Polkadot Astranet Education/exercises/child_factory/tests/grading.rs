@@ -0,0 +1,157 @@
+// exercises/child_factory/tests/grading.rs
+//
+// The grading tests of the exercise. Learners are asked not to read them: they check
+// the behaviour `src/lib.rs` describes, whichever way it is implemented.
+//
+// They are end-to-end tests: each uploads the child's code to a fresh local node,
+// deploys the factory and calls it as accounts would.
+
+use astranet_scenario::{Actor, Scenario};
+use child::child::Child;
+use child_factory::child_factory::{ChildFactory, ChildFactoryRef, Error};
+use ink::primitives::Hash;
+use ink_e2e::ContractsBackend;
+
+type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+#[ink_e2e::test]
+async fn children_belong_to_their_creator<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+    let child_code_hash = client
+        .upload("child", &Actor::Alice.keypair())
+        .submit()
+        .await
+        .expect("uploading the child failed")
+        .code_hash;
+    let mut scenario = Scenario::new("create a child", &mut client);
+    let factory = scenario
+        .deploy(
+            "deploy the factory",
+            Actor::Alice,
+            "child_factory",
+            &mut ChildFactoryRef::new(child_code_hash),
+        )
+        .await;
+    let mut factory = factory.call_builder::<ChildFactory>();
+
+    let address = scenario
+        .call(
+            "bob creates a child",
+            Actor::Bob,
+            &factory.create("first".into()),
+        )
+        .await
+        .expect("creating a child failed");
+    let child = ink_e2e::create_call_builder::<Child>(address);
+    scenario
+        .assert_query_eq(
+            "the child is bob's",
+            Actor::Charlie,
+            &child.owner(),
+            Actor::Bob.account_id(),
+        )
+        .await;
+    scenario
+        .assert_query_eq(
+            "the child is labelled",
+            Actor::Charlie,
+            &child.label(),
+            "first".into(),
+        )
+        .await;
+    scenario
+        .assert_query_eq(
+            "the factory lists it",
+            Actor::Charlie,
+            &factory.children(),
+            vec![address],
+        )
+        .await;
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn every_child_gets_its_own_address<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+    let child_code_hash = client
+        .upload("child", &Actor::Alice.keypair())
+        .submit()
+        .await
+        .expect("uploading the child failed")
+        .code_hash;
+    let mut scenario = Scenario::new("create several children", &mut client);
+    let factory = scenario
+        .deploy(
+            "deploy the factory",
+            Actor::Alice,
+            "child_factory",
+            &mut ChildFactoryRef::new(child_code_hash),
+        )
+        .await;
+    let mut factory = factory.call_builder::<ChildFactory>();
+
+    let mut addresses = Vec::new();
+    for (actor, label) in [
+        (Actor::Bob, "same"),
+        (Actor::Bob, "same"),
+        (Actor::Charlie, "other"),
+    ] {
+        let address = scenario
+            .call("create a child", actor, &factory.create(label.into()))
+            .await
+            .expect("creating a child failed");
+        assert!(
+            !addresses.contains(&address),
+            "two children share an address"
+        );
+        addresses.push(address);
+    }
+    scenario
+        .assert_query_eq(
+            "the factory lists them in order",
+            Actor::Bob,
+            &factory.children(),
+            addresses.clone(),
+        )
+        .await;
+    let last = ink_e2e::create_call_builder::<Child>(addresses[2]);
+    scenario
+        .assert_query_eq(
+            "the last child is charlie's",
+            Actor::Bob,
+            &last.owner(),
+            Actor::Charlie.account_id(),
+        )
+        .await;
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn failed_deployments_are_reported<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+    let mut scenario = Scenario::new("missing code", &mut client);
+    let factory = scenario
+        .deploy(
+            "deploy a factory with a code hash nobody uploaded",
+            Actor::Alice,
+            "child_factory",
+            &mut ChildFactoryRef::new(Hash::from([7; 32])),
+        )
+        .await;
+    let mut factory = factory.call_builder::<ChildFactory>();
+
+    let result = scenario
+        .call(
+            "create a child",
+            Actor::Bob,
+            &factory.create("orphan".into()),
+        )
+        .await;
+    assert_eq!(result, Err(Error::InstantiationFailed));
+    scenario
+        .assert_query_eq(
+            "no child is listed",
+            Actor::Bob,
+            &factory.children(),
+            Vec::new(),
+        )
+        .await;
+    Ok(())
+}
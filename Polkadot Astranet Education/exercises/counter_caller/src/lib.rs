@@ -0,0 +1,68 @@
+// exercises/counter_caller/src/lib.rs
+//
+// Calling a contract through its interface
+//
+// A contract can call another through a typed reference, as long as it knows the
+// callee's interface. The counter this contract drives is `exercises/support/counter`,
+// but all this contract may rely on is its `Counter` trait, so it must work with any
+// contract implementing it. `ink::contract_ref!(Counter)` is a reference to such a
+// contract: it is built from an `AccountId`, stored like any other value, and has the
+// trait's messages as methods, each of which makes a cross-contract call.
+//
+// Implement:
+//
+// - `new`: remembers the counter at `counter`.
+// - `current`: the counter's count, read through the counter.
+// - `increment_by`: increments the counter by `by`, returning what the counter
+//   returned, its error included.
+// - `increment_twice`: increments the counter by `by` twice, returning the count after
+//   the second increment, or the first error.
+//
+// The grading tests deploy the counter and this contract to a local node, so they
+// need `substrate-contracts-node` on the `PATH` or in `CONTRACTS_NODE`; `devnet`
+// downloads one.
+//
+// Fill in the `TODO`s below, then run `grader run counter_caller` to check your work.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod counter_caller {
+    use counter::CounterError;
+
+    #[ink(storage)]
+    pub struct CounterCaller {
+        // TODO: a reference to the counter, through the `counter::Counter` trait.
+    }
+
+    impl CounterCaller {
+        /// Creates the contract, calling the counter at `counter`.
+        #[ink(constructor)]
+        pub fn new(counter: AccountId) -> Self {
+            // TODO: remember the counter.
+            let _ = counter;
+            Self {}
+        }
+
+        /// The counter's count.
+        #[ink(message)]
+        pub fn current(&self) -> u32 {
+            todo!("implement `current`")
+        }
+
+        /// Increments the counter by `by`, returning the new count.
+        #[ink(message)]
+        pub fn increment_by(&mut self, by: u32) -> Result<u32, CounterError> {
+            let _ = by;
+            todo!("implement `increment_by`")
+        }
+
+        /// Increments the counter by `by` twice, returning the count after the second
+        /// increment.
+        #[ink(message)]
+        pub fn increment_twice(&mut self, by: u32) -> Result<u32, CounterError> {
+            let _ = by;
+            todo!("implement `increment_twice`")
+        }
+    }
+}
@@ -0,0 +1,149 @@
+// exercises/counter_caller/tests/grading.rs
+//
+// The grading tests of the exercise. Learners are asked not to read them: they check
+// the behaviour `src/lib.rs` describes, whichever way it is implemented.
+//
+// They are end-to-end tests: each deploys the counter and the exercise's contract to a
+// fresh local node and calls them as accounts would.
+
+use astranet_scenario::{Actor, Scenario};
+use counter::{
+    counter::{BasicCounter, BasicCounterRef},
+    Counter, CounterError,
+};
+use counter_caller::counter_caller::{CounterCaller, CounterCallerRef};
+
+type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+#[ink_e2e::test]
+async fn the_count_is_read_through_the_counter<Client: E2EBackend>(
+    mut client: Client,
+) -> E2EResult<()> {
+    let mut scenario = Scenario::new("read the count", &mut client);
+    let counter = scenario
+        .deploy(
+            "deploy a counter at 5",
+            Actor::Alice,
+            "counter",
+            &mut BasicCounterRef::new(5),
+        )
+        .await;
+    let caller = scenario
+        .deploy(
+            "deploy the caller",
+            Actor::Alice,
+            "counter_caller",
+            &mut CounterCallerRef::new(counter.account_id),
+        )
+        .await;
+    let caller = caller.call_builder::<CounterCaller>();
+    scenario
+        .assert_query_eq("the caller reads 5", Actor::Bob, &caller.current(), 5)
+        .await;
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn increments_reach_the_counter<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+    let mut scenario = Scenario::new("increment", &mut client);
+    let counter = scenario
+        .deploy(
+            "deploy a counter at 5",
+            Actor::Alice,
+            "counter",
+            &mut BasicCounterRef::new(5),
+        )
+        .await;
+    let caller = scenario
+        .deploy(
+            "deploy the caller",
+            Actor::Alice,
+            "counter_caller",
+            &mut CounterCallerRef::new(counter.account_id),
+        )
+        .await;
+    let mut caller = caller.call_builder::<CounterCaller>();
+    let mut direct = counter.call_builder::<BasicCounter>();
+
+    let count = scenario
+        .call("increment by 3", Actor::Bob, &caller.increment_by(3))
+        .await;
+    assert_eq!(count, Ok(8));
+    scenario
+        .assert_query_eq("the counter itself reads 8", Actor::Bob, &direct.get(), 8)
+        .await;
+
+    let count = scenario
+        .call(
+            "increment by 2 twice",
+            Actor::Charlie,
+            &caller.increment_twice(2),
+        )
+        .await;
+    assert_eq!(count, Ok(12));
+    scenario
+        .assert_query_eq("the counter itself reads 12", Actor::Bob, &direct.get(), 12)
+        .await;
+    scenario
+        .call(
+            "the counter is still callable directly",
+            Actor::Alice,
+            &direct.increment(1),
+        )
+        .await
+        .expect("a direct increment failed");
+    scenario
+        .assert_query_eq("the caller sees 13", Actor::Bob, &caller.current(), 13)
+        .await;
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn the_counters_errors_are_passed_on<Client: E2EBackend>(
+    mut client: Client,
+) -> E2EResult<()> {
+    let mut scenario = Scenario::new("overflow", &mut client);
+    let counter = scenario
+        .deploy(
+            "deploy a counter near the top",
+            Actor::Alice,
+            "counter",
+            &mut BasicCounterRef::new(u32::MAX - 1),
+        )
+        .await;
+    let caller = scenario
+        .deploy(
+            "deploy the caller",
+            Actor::Alice,
+            "counter_caller",
+            &mut CounterCallerRef::new(counter.account_id),
+        )
+        .await;
+    let mut caller = caller.call_builder::<CounterCaller>();
+
+    let count = scenario
+        .call(
+            "increment past the top",
+            Actor::Bob,
+            &caller.increment_by(5),
+        )
+        .await;
+    assert_eq!(count, Err(CounterError::Overflow));
+    let count = scenario
+        .call(
+            "increment by 1 twice",
+            Actor::Bob,
+            &caller.increment_twice(1),
+        )
+        .await;
+    assert_eq!(count, Err(CounterError::Overflow));
+    scenario
+        .assert_query_eq(
+            "the count is unchanged",
+            Actor::Bob,
+            &caller.current(),
+            u32::MAX - 1,
+        )
+        .await;
+    Ok(())
+}
@@ -0,0 +1,83 @@
+// exercises/lazy_config/src/lib.rs
+//
+// Rarely read settings behind Lazy
+//
+// All the fields of a contract's storage struct are stored together, in one cell
+// that every call loads and every mutating call writes back, however little of it
+// the call uses. This contract counts visits on every call but only reads its
+// `Config`, with a long description, when asked for it. Wrapping a field in
+// `Lazy` moves it to a cell of its own, loaded with `get` and stored with `set` only
+// when a call needs it.
+//
+// - Change the type of `config` to `Lazy<Config>`, keeping its name.
+// - `config`: returns the configuration.
+// - `set_fee`: the owner changes the fee only, keeping the rest of the configuration;
+//   anyone else gets `Error::NotOwner`.
+//
+// Fill in the `TODO`s below, then run `grader run lazy_config` to check your work.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod lazy_config {
+    use ink::prelude::string::String;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Config {
+        pub name: String,
+        pub description: String,
+        pub fee: Balance,
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// Only the owner can change the configuration.
+        NotOwner,
+    }
+
+    #[ink(storage)]
+    pub struct LazyConfig {
+        owner: AccountId,
+        visits: u64,
+        // TODO: keep the configuration in a cell of its own.
+        config: Config,
+    }
+
+    impl LazyConfig {
+        /// Creates the contract with `config`, owned by the caller.
+        #[ink(constructor)]
+        pub fn new(config: Config) -> Self {
+            // TODO: store `config` the way its new type needs.
+            Self {
+                owner: Self::env().caller(),
+                visits: 0,
+                config,
+            }
+        }
+
+        /// Counts a visit, returning the count so far.
+        #[ink(message)]
+        pub fn visit(&mut self) -> u64 {
+            self.visits = self.visits.saturating_add(1);
+            self.visits
+        }
+
+        /// The configuration.
+        #[ink(message)]
+        pub fn config(&self) -> Config {
+            // TODO: load the configuration.
+            todo!("implement `config`")
+        }
+
+        /// Changes the fee, keeping the rest of the configuration. Only the owner can.
+        #[ink(message)]
+        pub fn set_fee(&mut self, fee: Balance) -> Result<(), Error> {
+            // TODO: check the caller, then change the fee.
+            let _ = fee;
+            todo!("implement `set_fee`")
+        }
+    }
+}
@@ -0,0 +1,81 @@
+// exercises/lazy_config/tests/grading.rs
+//
+// The grading tests of the exercise. Learners are asked not to read them: they check
+// the behaviour `src/lib.rs` describes, whichever way it is implemented.
+
+use ink::{
+    env::{
+        test::{default_accounts, set_caller},
+        DefaultEnvironment,
+    },
+    metadata::layout::Layout,
+    storage::traits::StorageLayout,
+};
+use lazy_config::lazy_config::{Config, Error, LazyConfig};
+
+fn config() -> Config {
+    Config {
+        name: "Astranet course".into(),
+        description: "A long description nobody reads on every call. ".repeat(20),
+        fee: 10,
+    }
+}
+
+/// Whether the contract's storage field `name` is stored in a cell of its own.
+fn has_own_cell(name: &str) -> bool {
+    let Layout::Struct(layout) = <LazyConfig as StorageLayout>::layout(&0) else {
+        panic!("the storage of the contract is not a struct");
+    };
+    let field = layout
+        .fields()
+        .iter()
+        .find(|field| *field.name() == name)
+        .unwrap_or_else(|| panic!("the contract has no storage field `{}`", name));
+    matches!(field.layout(), Layout::Root(_))
+}
+
+#[test]
+fn the_configuration_has_a_cell_of_its_own() {
+    assert!(
+        has_own_cell("config"),
+        "`config` is still stored with the rest of the contract"
+    );
+    assert!(!has_own_cell("visits"));
+}
+
+#[ink::test]
+fn the_configuration_is_kept() {
+    let contract = LazyConfig::new(config());
+    assert_eq!(contract.config(), config());
+}
+
+#[ink::test]
+fn visits_are_counted() {
+    let mut contract = LazyConfig::new(config());
+    assert_eq!(contract.visit(), 1);
+    assert_eq!(contract.visit(), 2);
+    assert_eq!(contract.config(), config());
+}
+
+#[ink::test]
+fn the_owner_changes_the_fee_only() {
+    let mut contract = LazyConfig::new(config());
+    contract.set_fee(25).unwrap();
+    assert_eq!(
+        contract.config(),
+        Config {
+            fee: 25,
+            ..config()
+        }
+    );
+    contract.set_fee(0).unwrap();
+    assert_eq!(contract.config().fee, 0);
+}
+
+#[ink::test]
+fn only_the_owner_changes_the_fee() {
+    let mut contract = LazyConfig::new(config());
+    set_caller::<DefaultEnvironment>(default_accounts::<DefaultEnvironment>().bob);
+    assert_eq!(contract.set_fee(25), Err(Error::NotOwner));
+    assert_eq!(contract.config(), config());
+}
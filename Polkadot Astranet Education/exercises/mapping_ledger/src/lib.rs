@@ -0,0 +1,88 @@
+// exercises/mapping_ledger/src/lib.rs
+//
+// Balances in a Mapping
+//
+// A token ledger keeps a balance per account in a `Mapping`. Unlike a `HashMap`, a
+// `Mapping` stores each entry in its own storage cell: it cannot be iterated or
+// counted, `get` returns a copy of the value (or `None` for an account never
+// written), and a changed copy is only stored once it is inserted back.
+//
+// Implement:
+//
+// - `balance_of`: the balance of an account, zero if it has none.
+// - `mint`: the owner credits an account, raising the total supply. Anyone else gets
+//   `Error::NotOwner`; a total supply that would overflow gets `Error::Overflow`,
+//   with nothing changed.
+// - `transfer`: the caller moves part of their balance to another account, or gets
+//   `Error::InsufficientBalance` with nothing changed. Sending to yourself must leave
+//   your balance as it was.
+//
+// Fill in the `TODO`s below, then run `grader run mapping_ledger` to check your work.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod mapping_ledger {
+    use ink::storage::Mapping;
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// Only the owner can mint.
+        NotOwner,
+        /// The caller's balance is below the amount.
+        InsufficientBalance,
+        /// The total supply would overflow.
+        Overflow,
+    }
+
+    #[ink(storage)]
+    pub struct MappingLedger {
+        owner: AccountId,
+        balances: Mapping<AccountId, Balance>,
+        total_supply: Balance,
+    }
+
+    impl MappingLedger {
+        /// Creates an empty ledger owned by the caller.
+        #[ink(constructor)]
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Self {
+                owner: Self::env().caller(),
+                balances: Mapping::default(),
+                total_supply: 0,
+            }
+        }
+
+        /// The balance of `owner`, zero if it has none.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            // TODO: read `owner`'s entry of `balances`.
+            let _ = owner;
+            todo!("implement `balance_of`")
+        }
+
+        /// The sum of every balance.
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// Credits `amount` to `to`. Only the owner can mint.
+        #[ink(message)]
+        pub fn mint(&mut self, to: AccountId, amount: Balance) -> Result<(), Error> {
+            // TODO: check the caller, then raise the total supply and `to`'s balance.
+            let _ = (to, amount);
+            todo!("implement `mint`")
+        }
+
+        /// Moves `amount` from the caller's balance to `to`'s.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, amount: Balance) -> Result<(), Error> {
+            // TODO: check the caller's balance, then move `amount`.
+            let _ = (to, amount);
+            todo!("implement `transfer`")
+        }
+    }
+}
@@ -0,0 +1,101 @@
+// exercises/mapping_ledger/tests/grading.rs
+//
+// The grading tests of the exercise. Learners are asked not to read them: they check
+// the behaviour `src/lib.rs` describes, whichever way it is implemented.
+
+use ink::env::{
+    test::{default_accounts, set_caller, DefaultAccounts},
+    DefaultEnvironment,
+};
+use mapping_ledger::mapping_ledger::{Error, MappingLedger};
+
+fn accounts() -> DefaultAccounts<DefaultEnvironment> {
+    default_accounts::<DefaultEnvironment>()
+}
+
+#[ink::test]
+fn a_new_ledger_is_empty() {
+    let ledger = MappingLedger::new();
+    assert_eq!(ledger.balance_of(accounts().alice), 0);
+    assert_eq!(ledger.balance_of(accounts().bob), 0);
+    assert_eq!(ledger.total_supply(), 0);
+}
+
+#[ink::test]
+fn minting_credits_the_account_and_the_supply() {
+    let mut ledger = MappingLedger::new();
+    ledger.mint(accounts().bob, 100).unwrap();
+    ledger.mint(accounts().bob, 50).unwrap();
+    ledger.mint(accounts().charlie, 7).unwrap();
+    assert_eq!(ledger.balance_of(accounts().bob), 150);
+    assert_eq!(ledger.balance_of(accounts().charlie), 7);
+    assert_eq!(ledger.total_supply(), 157);
+}
+
+#[ink::test]
+fn only_the_owner_mints() {
+    let mut ledger = MappingLedger::new();
+    set_caller::<DefaultEnvironment>(accounts().bob);
+    assert_eq!(ledger.mint(accounts().bob, 100), Err(Error::NotOwner));
+    assert_eq!(ledger.balance_of(accounts().bob), 0);
+    assert_eq!(ledger.total_supply(), 0);
+}
+
+#[ink::test]
+fn minting_past_the_maximum_supply_fails() {
+    let mut ledger = MappingLedger::new();
+    ledger.mint(accounts().bob, u128::MAX - 1).unwrap();
+    assert_eq!(ledger.mint(accounts().charlie, 2), Err(Error::Overflow));
+    assert_eq!(ledger.balance_of(accounts().charlie), 0);
+    assert_eq!(ledger.total_supply(), u128::MAX - 1);
+}
+
+#[ink::test]
+fn transfers_move_balance() {
+    let mut ledger = MappingLedger::new();
+    ledger.mint(accounts().bob, 100).unwrap();
+    set_caller::<DefaultEnvironment>(accounts().bob);
+    ledger.transfer(accounts().charlie, 30).unwrap();
+    assert_eq!(ledger.balance_of(accounts().bob), 70);
+    assert_eq!(ledger.balance_of(accounts().charlie), 30);
+    assert_eq!(ledger.total_supply(), 100);
+}
+
+#[ink::test]
+fn transfers_beyond_the_balance_fail() {
+    let mut ledger = MappingLedger::new();
+    ledger.mint(accounts().bob, 100).unwrap();
+    set_caller::<DefaultEnvironment>(accounts().bob);
+    assert_eq!(
+        ledger.transfer(accounts().charlie, 101),
+        Err(Error::InsufficientBalance)
+    );
+    set_caller::<DefaultEnvironment>(accounts().django);
+    assert_eq!(
+        ledger.transfer(accounts().charlie, 1),
+        Err(Error::InsufficientBalance)
+    );
+    assert_eq!(ledger.balance_of(accounts().bob), 100);
+    assert_eq!(ledger.balance_of(accounts().charlie), 0);
+}
+
+#[ink::test]
+fn the_whole_balance_and_nothing_can_be_sent() {
+    let mut ledger = MappingLedger::new();
+    ledger.mint(accounts().bob, 100).unwrap();
+    set_caller::<DefaultEnvironment>(accounts().bob);
+    ledger.transfer(accounts().charlie, 0).unwrap();
+    ledger.transfer(accounts().charlie, 100).unwrap();
+    assert_eq!(ledger.balance_of(accounts().bob), 0);
+    assert_eq!(ledger.balance_of(accounts().charlie), 100);
+}
+
+#[ink::test]
+fn sending_to_yourself_changes_nothing() {
+    let mut ledger = MappingLedger::new();
+    ledger.mint(accounts().bob, 100).unwrap();
+    set_caller::<DefaultEnvironment>(accounts().bob);
+    ledger.transfer(accounts().bob, 40).unwrap();
+    assert_eq!(ledger.balance_of(accounts().bob), 100);
+    assert_eq!(ledger.total_supply(), 100);
+}
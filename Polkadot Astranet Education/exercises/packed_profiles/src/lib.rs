@@ -0,0 +1,116 @@
+// exercises/packed_profiles/src/lib.rs
+//
+// Packed structs as storage values
+//
+// A value in a `Mapping` is "packed": it is encoded whole into one storage cell, so
+// its type must be encodable (`Encode`, `Decode`), describable in metadata
+// (`TypeInfo`, `StorageLayout`), and made only of other packed types, so no
+// `Mapping` or `Lazy` inside. Reading one field loads the whole value, and changing
+// one field means writing the whole value back.
+//
+// Learners register a `Profile` under their account; the owner awards points and
+// badges:
+//
+// - `register`: the caller's profile starts with `name`, no points and no badges.
+//   A second registration gets `Error::AlreadyRegistered`, and a name longer than
+//   `MAX_NAME_LEN` bytes `Error::NameTooLong`.
+// - `rename`: the caller changes their name, under the same rules.
+// - `add_points`: the owner adds points to a profile, saturating at `u32::MAX`.
+// - `award_badge`: the owner awards a badge; awarding one twice keeps one, and a
+//   profile holds at most `MAX_BADGES` of them (`Error::TooManyBadges`).
+//
+// Calls about an account without a profile get `Error::NotRegistered`, calls by
+// anyone but the owner to `add_points` and `award_badge` `Error::NotOwner`, and no
+// failing call changes anything.
+//
+// Fill in the `TODO`s below, then run `grader run packed_profiles` to check your work.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod packed_profiles {
+    use ink::{
+        prelude::{string::String, vec::Vec},
+        storage::Mapping,
+    };
+
+    /// The longest name, in bytes.
+    pub const MAX_NAME_LEN: usize = 32;
+    /// The most badges a profile holds.
+    pub const MAX_BADGES: usize = 8;
+
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Profile {
+        pub name: String,
+        pub points: u32,
+        pub badges: Vec<u32>,
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        NotOwner,
+        AlreadyRegistered,
+        NotRegistered,
+        NameTooLong,
+        TooManyBadges,
+    }
+
+    #[ink(storage)]
+    pub struct PackedProfiles {
+        owner: AccountId,
+        profiles: Mapping<AccountId, Profile>,
+    }
+
+    impl PackedProfiles {
+        /// Creates the contract, owned by the caller.
+        #[ink(constructor)]
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Self {
+                owner: Self::env().caller(),
+                profiles: Mapping::default(),
+            }
+        }
+
+        /// The profile of `account`, if it registered.
+        #[ink(message)]
+        pub fn profile(&self, account: AccountId) -> Option<Profile> {
+            self.profiles.get(account)
+        }
+
+        /// Registers the caller under `name`.
+        #[ink(message)]
+        pub fn register(&mut self, name: String) -> Result<(), Error> {
+            // TODO
+            let _ = name;
+            todo!("implement `register`")
+        }
+
+        /// Changes the caller's name.
+        #[ink(message)]
+        pub fn rename(&mut self, name: String) -> Result<(), Error> {
+            // TODO
+            let _ = name;
+            todo!("implement `rename`")
+        }
+
+        /// Adds `points` to `account`'s profile. Only the owner can.
+        #[ink(message)]
+        pub fn add_points(&mut self, account: AccountId, points: u32) -> Result<(), Error> {
+            // TODO
+            let _ = (account, points);
+            todo!("implement `add_points`")
+        }
+
+        /// Awards `badge` to `account`. Only the owner can.
+        #[ink(message)]
+        pub fn award_badge(&mut self, account: AccountId, badge: u32) -> Result<(), Error> {
+            // TODO
+            let _ = (account, badge);
+            todo!("implement `award_badge`")
+        }
+    }
+}
@@ -0,0 +1,164 @@
+// exercises/packed_profiles/tests/grading.rs
+//
+// The grading tests of the exercise. Learners are asked not to read them: they check
+// the behaviour `src/lib.rs` describes, whichever way it is implemented.
+
+use ink::{
+    env::{
+        test::{default_accounts, set_caller, DefaultAccounts},
+        DefaultEnvironment,
+    },
+    primitives::AccountId,
+};
+use packed_profiles::packed_profiles::{
+    Error, PackedProfiles, Profile, MAX_BADGES, MAX_NAME_LEN,
+};
+
+fn accounts() -> DefaultAccounts<DefaultEnvironment> {
+    default_accounts::<DefaultEnvironment>()
+}
+
+fn call_as(account: AccountId) {
+    set_caller::<DefaultEnvironment>(account);
+}
+
+/// A contract owned by alice where bob registered as "bob".
+fn with_bob() -> PackedProfiles {
+    let mut contract = PackedProfiles::new();
+    call_as(accounts().bob);
+    contract.register("bob".into()).unwrap();
+    call_as(accounts().alice);
+    contract
+}
+
+#[ink::test]
+fn registering_creates_an_empty_profile() {
+    let contract = with_bob();
+    assert_eq!(
+        contract.profile(accounts().bob),
+        Some(Profile {
+            name: "bob".into(),
+            points: 0,
+            badges: Vec::new(),
+        })
+    );
+    assert_eq!(contract.profile(accounts().charlie), None);
+}
+
+#[ink::test]
+fn registering_twice_fails() {
+    let mut contract = with_bob();
+    call_as(accounts().bob);
+    assert_eq!(
+        contract.register("robert".into()),
+        Err(Error::AlreadyRegistered)
+    );
+    assert_eq!(contract.profile(accounts().bob).unwrap().name, "bob");
+}
+
+#[ink::test]
+fn names_are_bounded() {
+    let mut contract = PackedProfiles::new();
+    call_as(accounts().bob);
+    assert_eq!(
+        contract.register("b".repeat(MAX_NAME_LEN + 1)),
+        Err(Error::NameTooLong)
+    );
+    assert_eq!(contract.profile(accounts().bob), None);
+    contract.register("b".repeat(MAX_NAME_LEN)).unwrap();
+    assert_eq!(
+        contract.rename("b".repeat(MAX_NAME_LEN + 1)),
+        Err(Error::NameTooLong)
+    );
+    assert_eq!(
+        contract.profile(accounts().bob).unwrap().name,
+        "b".repeat(MAX_NAME_LEN)
+    );
+}
+
+#[ink::test]
+fn renaming_keeps_the_rest_of_the_profile() {
+    let mut contract = with_bob();
+    contract.add_points(accounts().bob, 10).unwrap();
+    contract.award_badge(accounts().bob, 3).unwrap();
+    call_as(accounts().bob);
+    contract.rename("robert".into()).unwrap();
+    assert_eq!(
+        contract.profile(accounts().bob),
+        Some(Profile {
+            name: "robert".into(),
+            points: 10,
+            badges: vec![3],
+        })
+    );
+    call_as(accounts().charlie);
+    assert_eq!(contract.rename("c".into()), Err(Error::NotRegistered));
+}
+
+#[ink::test]
+fn points_add_up_and_saturate() {
+    let mut contract = with_bob();
+    contract.add_points(accounts().bob, 10).unwrap();
+    contract.add_points(accounts().bob, 5).unwrap();
+    assert_eq!(contract.profile(accounts().bob).unwrap().points, 15);
+    contract.add_points(accounts().bob, u32::MAX).unwrap();
+    assert_eq!(contract.profile(accounts().bob).unwrap().points, u32::MAX);
+}
+
+#[ink::test]
+fn badges_are_kept_once_each() {
+    let mut contract = with_bob();
+    contract.award_badge(accounts().bob, 7).unwrap();
+    contract.award_badge(accounts().bob, 2).unwrap();
+    contract.award_badge(accounts().bob, 7).unwrap();
+    let mut badges = contract.profile(accounts().bob).unwrap().badges;
+    badges.sort();
+    assert_eq!(badges, [2, 7]);
+}
+
+#[ink::test]
+fn badges_are_bounded() {
+    let mut contract = with_bob();
+    for badge in 0..MAX_BADGES as u32 {
+        contract.award_badge(accounts().bob, badge).unwrap();
+    }
+    assert_eq!(
+        contract.award_badge(accounts().bob, 100),
+        Err(Error::TooManyBadges)
+    );
+    // Awarding one it already has is not one more.
+    contract.award_badge(accounts().bob, 0).unwrap();
+    assert_eq!(
+        contract.profile(accounts().bob).unwrap().badges.len(),
+        MAX_BADGES
+    );
+}
+
+#[ink::test]
+fn only_the_owner_awards() {
+    let mut contract = with_bob();
+    call_as(accounts().bob);
+    assert_eq!(contract.add_points(accounts().bob, 1), Err(Error::NotOwner));
+    assert_eq!(contract.award_badge(accounts().bob, 1), Err(Error::NotOwner));
+    assert_eq!(
+        contract.profile(accounts().bob),
+        Some(Profile {
+            name: "bob".into(),
+            ..Profile::default()
+        })
+    );
+}
+
+#[ink::test]
+fn awards_need_a_profile() {
+    let mut contract = with_bob();
+    assert_eq!(
+        contract.add_points(accounts().charlie, 1),
+        Err(Error::NotRegistered)
+    );
+    assert_eq!(
+        contract.award_badge(accounts().charlie, 1),
+        Err(Error::NotRegistered)
+    );
+    assert_eq!(contract.profile(accounts().charlie), None);
+}
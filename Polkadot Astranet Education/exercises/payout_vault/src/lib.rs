@@ -0,0 +1,94 @@
+// exercises/payout_vault/src/lib.rs
+//
+// Reentrancy through a payment callback
+//
+// This vault holds deposits and pays them out on request. Contracts it pays are told
+// so through their `PaymentReceiver::on_payment`, and may call back into the vault
+// while it is paying them: the call allows reentry, so a contract can, say, deposit
+// the payment straight back. That callback is also a way in for an attacker. A contract
+// that withdraws again from inside `on_payment` finds the vault midway through the
+// first withdrawal, its balance not yet reduced, and is paid twice, out of everyone
+// else's deposits.
+//
+// The fix is the checks-effects-interactions order: check, then update the vault's
+// own state, and only then call out to another contract.
+//
+// TODO: find and fix the bug. A re-entering withdrawal must not be paid out of other
+// deposits, and a contract calling back to deposit the payment must still work, so
+// the call must keep allowing reentry.
+//
+// The grading tests deploy the vault and receiving contracts
+// (`exercises/support/receiver`) to a local node, so they need
+// `substrate-contracts-node` on the `PATH` or in `CONTRACTS_NODE`; `devnet` downloads
+// one. Then run `grader run payout_vault` to check your work.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod payout_vault {
+    use ink::{codegen::TraitCallBuilder, env::CallFlags, storage::Mapping};
+    use receiver::{PaymentReceiver, Vault, VaultError};
+
+    #[ink(storage)]
+    pub struct PayoutVault {
+        balances: Mapping<AccountId, Balance>,
+    }
+
+    impl PayoutVault {
+        /// Creates an empty vault.
+        #[ink(constructor)]
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Self {
+                balances: Mapping::default(),
+            }
+        }
+
+        /// Pays `amount` to `to`, calling it back if it is a contract.
+        fn pay(&self, to: AccountId, amount: Balance) -> Result<(), VaultError> {
+            if !self.env().is_contract(&to) {
+                return self
+                    .env()
+                    .transfer(to, amount)
+                    .map_err(|_| VaultError::PaymentFailed);
+            }
+            let mut receiver: ink::contract_ref!(PaymentReceiver) = to.into();
+            match receiver
+                .call_mut()
+                .on_payment()
+                .transferred_value(amount)
+                .call_flags(CallFlags::ALLOW_REENTRY)
+                .try_invoke()
+            {
+                Ok(Ok(())) => Ok(()),
+                _ => Err(VaultError::PaymentFailed),
+            }
+        }
+    }
+
+    impl Vault for PayoutVault {
+        #[ink(message, payable)]
+        fn deposit(&mut self) {
+            let caller = self.env().caller();
+            let balance = self.balance_of(caller) + self.env().transferred_value();
+            self.balances.insert(caller, &balance);
+        }
+
+        #[ink(message)]
+        fn withdraw(&mut self, amount: Balance) -> Result<(), VaultError> {
+            let caller = self.env().caller();
+            let balance = self.balance_of(caller);
+            if balance < amount {
+                return Err(VaultError::InsufficientBalance);
+            }
+            self.pay(caller, amount)?;
+            self.balances.insert(caller, &(balance - amount));
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or_default()
+        }
+    }
+}
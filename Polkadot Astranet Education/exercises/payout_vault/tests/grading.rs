@@ -0,0 +1,234 @@
+// exercises/payout_vault/tests/grading.rs
+//
+// The grading tests of the exercise. Learners are asked not to read them: they check
+// the behaviour `src/lib.rs` describes, whichever way it is implemented.
+//
+// They are end-to-end tests: each deploys the vault and, for contracts it pays,
+// receivers from `exercises/support/receiver` to a fresh local node.
+
+use astranet_scenario::{Actor, Scenario};
+use payout_vault::payout_vault::{PayoutVault, PayoutVaultRef};
+use receiver::{
+    receiver::{Behaviour, Receiver, ReceiverRef},
+    Vault, VaultError,
+};
+
+type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/// One token of the node, at 12 decimals.
+const UNIT: u128 = 1_000_000_000_000;
+
+#[ink_e2e::test]
+async fn accounts_deposit_and_withdraw<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+    let mut scenario = Scenario::new("accounts", &mut client);
+    let vault = scenario
+        .deploy(
+            "deploy the vault",
+            Actor::Alice,
+            "payout_vault",
+            &mut PayoutVaultRef::new(),
+        )
+        .await;
+    let mut vault = vault.call_builder::<PayoutVault>();
+
+    scenario
+        .call_with_value("deposit 10", Actor::Alice, 10 * UNIT, &vault.deposit())
+        .await;
+    let result = scenario
+        .call("withdraw 4", Actor::Alice, &vault.withdraw(4 * UNIT))
+        .await;
+    assert_eq!(result, Ok(()));
+    let result = scenario
+        .call("withdraw 7", Actor::Alice, &vault.withdraw(7 * UNIT))
+        .await;
+    assert_eq!(result, Err(VaultError::InsufficientBalance));
+    scenario
+        .assert_query_eq(
+            "6 are left",
+            Actor::Alice,
+            &vault.balance_of(Actor::Alice.account_id()),
+            6 * UNIT,
+        )
+        .await;
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn contracts_are_paid_through_their_callback<Client: E2EBackend>(
+    mut client: Client,
+) -> E2EResult<()> {
+    let mut scenario = Scenario::new("receivers", &mut client);
+    let vault = scenario
+        .deploy(
+            "deploy the vault",
+            Actor::Alice,
+            "payout_vault",
+            &mut PayoutVaultRef::new(),
+        )
+        .await;
+    let keeper = scenario
+        .deploy(
+            "deploy a receiver that keeps payments",
+            Actor::Bob,
+            "receiver",
+            &mut ReceiverRef::new(vault.account_id, Behaviour::Keep),
+        )
+        .await;
+    let vault = vault.call_builder::<PayoutVault>();
+    let keeper_account = keeper.account_id;
+    let mut keeper = keeper.call_builder::<Receiver>();
+
+    scenario
+        .call_with_value(
+            "the keeper deposits 5",
+            Actor::Bob,
+            5 * UNIT,
+            &keeper.deposit(),
+        )
+        .await;
+    let result = scenario
+        .call(
+            "the keeper withdraws 5",
+            Actor::Bob,
+            &keeper.withdraw(5 * UNIT),
+        )
+        .await;
+    assert_eq!(result, Ok(()));
+    scenario
+        .assert_query_eq(
+            "the keeper has nothing left",
+            Actor::Bob,
+            &vault.balance_of(keeper_account),
+            0,
+        )
+        .await;
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn receivers_can_deposit_while_being_paid<Client: E2EBackend>(
+    mut client: Client,
+) -> E2EResult<()> {
+    let mut scenario = Scenario::new("redeposit", &mut client);
+    let vault = scenario
+        .deploy(
+            "deploy the vault",
+            Actor::Alice,
+            "payout_vault",
+            &mut PayoutVaultRef::new(),
+        )
+        .await;
+    let redepositor = scenario
+        .deploy(
+            "deploy a receiver that deposits payments back",
+            Actor::Bob,
+            "receiver",
+            &mut ReceiverRef::new(vault.account_id, Behaviour::Redeposit),
+        )
+        .await;
+    let vault = vault.call_builder::<PayoutVault>();
+    let redepositor_account = redepositor.account_id;
+    let mut redepositor = redepositor.call_builder::<Receiver>();
+
+    scenario
+        .call_with_value(
+            "the receiver deposits 5",
+            Actor::Bob,
+            5 * UNIT,
+            &redepositor.deposit(),
+        )
+        .await;
+    let result = scenario
+        .call(
+            "the receiver withdraws 2",
+            Actor::Bob,
+            &redepositor.withdraw(2 * UNIT),
+        )
+        .await;
+    assert_eq!(result, Ok(()));
+    scenario
+        .assert_query_eq(
+            "the 2 are deposited back",
+            Actor::Bob,
+            &vault.balance_of(redepositor_account),
+            5 * UNIT,
+        )
+        .await;
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn reentering_withdrawals_are_paid_once<Client: E2EBackend>(
+    mut client: Client,
+) -> E2EResult<()> {
+    let mut scenario = Scenario::new("reentrancy", &mut client);
+    let vault = scenario
+        .deploy(
+            "deploy the vault",
+            Actor::Alice,
+            "payout_vault",
+            &mut PayoutVaultRef::new(),
+        )
+        .await;
+    let attacker = scenario
+        .deploy(
+            "deploy a receiver that withdraws again while paid",
+            Actor::Eve,
+            "receiver",
+            &mut ReceiverRef::new(vault.account_id, Behaviour::Reenter),
+        )
+        .await;
+    let mut vault = vault.call_builder::<PayoutVault>();
+    let attacker_account = attacker.account_id;
+    let mut attacker = attacker.call_builder::<Receiver>();
+
+    scenario
+        .call_with_value(
+            "alice deposits 10",
+            Actor::Alice,
+            10 * UNIT,
+            &vault.deposit(),
+        )
+        .await;
+    scenario
+        .call_with_value(
+            "the attacker deposits 5",
+            Actor::Eve,
+            5 * UNIT,
+            &attacker.deposit(),
+        )
+        .await;
+    let result = scenario
+        .call(
+            "the attacker withdraws 5",
+            Actor::Eve,
+            &attacker.withdraw(5 * UNIT),
+        )
+        .await;
+    assert_eq!(result, Ok(()));
+    scenario
+        .assert_query_eq(
+            "the attacker has nothing left",
+            Actor::Eve,
+            &vault.balance_of(attacker_account),
+            0,
+        )
+        .await;
+    scenario
+        .assert_query_eq(
+            "alice still has 10",
+            Actor::Alice,
+            &vault.balance_of(Actor::Alice.account_id()),
+            10 * UNIT,
+        )
+        .await;
+    let result = scenario
+        .call(
+            "alice withdraws all 10",
+            Actor::Alice,
+            &vault.withdraw(10 * UNIT),
+        )
+        .await;
+    assert_eq!(result, Ok(()), "the vault no longer holds alice's deposit");
+    Ok(())
+}
@@ -0,0 +1,80 @@
+// exercises/safe_relay/src/lib.rs
+//
+// Surviving a failing callee
+//
+// A plain cross-contract call, `counter.increment(by)`, panics if the call itself
+// fails: the callee traps, there is no contract at the address, or it does not
+// understand the message. The panic traps the caller too, and its whole transaction
+// is rolled back. A contract that must keep working when a callee misbehaves has to
+// make the call so that such failures come back as values instead: ink!'s call
+// builders do that with `try_invoke`, which returns
+//
+//     Result<MessageResult<R>, ink::env::Error>
+//
+// where the outer `Err` is a failed call, the inner `Err` (an `ink::LangError`) a
+// message the callee did not understand, and `R` what the message returned, here the
+// counter's own `Result<u32, CounterError>`.
+//
+// This relay forwards increments to a counter behind the `counter::Counter` trait
+// (`exercises/support/counter`, which can be told to trap) and reports how each went,
+// without ever trapping itself. Implement:
+//
+// - `new`: remembers the counter at `counter`.
+// - `try_increment`: increments the counter by `by`. Returns `Outcome::Incremented`
+//   with the new count, `Outcome::Rejected` with the counter's error, or
+//   `Outcome::CallFailed` when the call could not be made or did not return, and counts
+//   each outcome other than `Incremented` as a failure.
+// - `failures`: the number of failures so far.
+//
+// The grading tests deploy the counter and the relay to a local node, so they need
+// `substrate-contracts-node` on the `PATH` or in `CONTRACTS_NODE`; `devnet` downloads
+// one.
+//
+// Fill in the `TODO`s below, then run `grader run safe_relay` to check your work.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod safe_relay {
+    use counter::CounterError;
+
+    /// How an increment went.
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Outcome {
+        /// The counter was incremented to this count.
+        Incremented(u32),
+        /// The counter refused the increment.
+        Rejected(CounterError),
+        /// The call did not return: the counter trapped, or is not there.
+        CallFailed,
+    }
+
+    #[ink(storage)]
+    pub struct SafeRelay {
+        // TODO: the counter and the number of failures.
+    }
+
+    impl SafeRelay {
+        /// Creates a relay to the counter at `counter`.
+        #[ink(constructor)]
+        pub fn new(counter: AccountId) -> Self {
+            // TODO: remember the counter.
+            let _ = counter;
+            Self {}
+        }
+
+        /// Increments the counter by `by`, reporting how it went.
+        #[ink(message)]
+        pub fn try_increment(&mut self, by: u32) -> Outcome {
+            let _ = by;
+            todo!("implement `try_increment`")
+        }
+
+        /// The number of increments that did not go through.
+        #[ink(message)]
+        pub fn failures(&self) -> u32 {
+            todo!("implement `failures`")
+        }
+    }
+}
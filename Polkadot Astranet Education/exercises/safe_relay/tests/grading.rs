@@ -0,0 +1,180 @@
+// exercises/safe_relay/tests/grading.rs
+//
+// The grading tests of the exercise. Learners are asked not to read them: they check
+// the behaviour `src/lib.rs` describes, whichever way it is implemented.
+//
+// They are end-to-end tests: each deploys the counter and the relay to a fresh local
+// node. A relay that traps fails its step, so every `call` below also checks that the
+// relay survived.
+
+use astranet_scenario::{Actor, Scenario};
+use counter::{
+    counter::{BasicCounter, BasicCounterRef},
+    CounterError,
+};
+use safe_relay::safe_relay::{Outcome, SafeRelay, SafeRelayRef};
+
+type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+#[ink_e2e::test]
+async fn increments_go_through<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+    let mut scenario = Scenario::new("working counter", &mut client);
+    let counter = scenario
+        .deploy(
+            "deploy a counter at 5",
+            Actor::Alice,
+            "counter",
+            &mut BasicCounterRef::new(5),
+        )
+        .await;
+    let relay = scenario
+        .deploy(
+            "deploy the relay",
+            Actor::Alice,
+            "safe_relay",
+            &mut SafeRelayRef::new(counter.account_id),
+        )
+        .await;
+    let mut relay = relay.call_builder::<SafeRelay>();
+
+    let outcome = scenario
+        .call("increment by 1", Actor::Bob, &relay.try_increment(1))
+        .await;
+    assert_eq!(outcome, Outcome::Incremented(6));
+    let outcome = scenario
+        .call("increment by 4", Actor::Bob, &relay.try_increment(4))
+        .await;
+    assert_eq!(outcome, Outcome::Incremented(10));
+    scenario
+        .assert_query_eq("nothing failed", Actor::Bob, &relay.failures(), 0)
+        .await;
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn refusals_are_reported<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+    let mut scenario = Scenario::new("refusing counter", &mut client);
+    let counter = scenario
+        .deploy(
+            "deploy a counter at the top",
+            Actor::Alice,
+            "counter",
+            &mut BasicCounterRef::new(u32::MAX),
+        )
+        .await;
+    let relay = scenario
+        .deploy(
+            "deploy the relay",
+            Actor::Alice,
+            "safe_relay",
+            &mut SafeRelayRef::new(counter.account_id),
+        )
+        .await;
+    let mut relay = relay.call_builder::<SafeRelay>();
+
+    let outcome = scenario
+        .call(
+            "increment past the top",
+            Actor::Bob,
+            &relay.try_increment(1),
+        )
+        .await;
+    assert_eq!(outcome, Outcome::Rejected(CounterError::Overflow));
+    let outcome = scenario
+        .call("increment by nothing", Actor::Bob, &relay.try_increment(0))
+        .await;
+    assert_eq!(outcome, Outcome::Incremented(u32::MAX));
+    scenario
+        .assert_query_eq("one failure counted", Actor::Bob, &relay.failures(), 1)
+        .await;
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn a_trapping_counter_is_survived<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+    let mut scenario = Scenario::new("trapping counter", &mut client);
+    let counter = scenario
+        .deploy(
+            "deploy a counter at 5",
+            Actor::Alice,
+            "counter",
+            &mut BasicCounterRef::new(5),
+        )
+        .await;
+    let relay = scenario
+        .deploy(
+            "deploy the relay",
+            Actor::Alice,
+            "safe_relay",
+            &mut SafeRelayRef::new(counter.account_id),
+        )
+        .await;
+    let mut relay = relay.call_builder::<SafeRelay>();
+    let mut direct = counter.call_builder::<BasicCounter>();
+
+    scenario
+        .call("break the counter", Actor::Alice, &direct.set_broken(true))
+        .await;
+    let outcome = scenario
+        .call(
+            "increment the broken counter",
+            Actor::Bob,
+            &relay.try_increment(1),
+        )
+        .await;
+    assert_eq!(outcome, Outcome::CallFailed);
+    scenario
+        .assert_query_eq("one failure counted", Actor::Bob, &relay.failures(), 1)
+        .await;
+
+    scenario
+        .call(
+            "repair the counter",
+            Actor::Alice,
+            &direct.set_broken(false),
+        )
+        .await;
+    let outcome = scenario
+        .call(
+            "increment the repaired counter",
+            Actor::Bob,
+            &relay.try_increment(1),
+        )
+        .await;
+    assert_eq!(outcome, Outcome::Incremented(6));
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn a_missing_counter_is_survived<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+    let mut scenario = Scenario::new("missing counter", &mut client);
+    let relay = scenario
+        .deploy(
+            "deploy a relay to an account that is not a contract",
+            Actor::Alice,
+            "safe_relay",
+            &mut SafeRelayRef::new(Actor::Dave.account_id()),
+        )
+        .await;
+    let mut relay = relay.call_builder::<SafeRelay>();
+
+    for attempt in 1..=2 {
+        let outcome = scenario
+            .call(
+                "increment a counter that is not there",
+                Actor::Bob,
+                &relay.try_increment(1),
+            )
+            .await;
+        assert_eq!(outcome, Outcome::CallFailed);
+        scenario
+            .assert_query_eq(
+                "the failure is counted",
+                Actor::Bob,
+                &relay.failures(),
+                attempt,
+            )
+            .await;
+    }
+    Ok(())
+}
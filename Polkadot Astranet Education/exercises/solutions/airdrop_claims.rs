@@ -0,0 +1,70 @@
+// exercises/solutions/airdrop_claims.rs
+//
+// The reference solution of `airdrop_claims`. The fix: the token's error becomes
+// `Error::TransferFailed`.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod airdrop_claims {
+    use ink::storage::Mapping;
+    use token::Token;
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// Only the owner can allocate.
+        NotOwner,
+        /// The caller has no allocation.
+        NothingToClaim,
+        /// The token did not pay out the claim.
+        TransferFailed,
+    }
+
+    #[ink(storage)]
+    pub struct AirdropClaims {
+        owner: AccountId,
+        token: ink::contract_ref!(Token),
+        allocations: Mapping<AccountId, Balance>,
+    }
+
+    impl AirdropClaims {
+        /// Creates an airdrop of the token at `token`, owned by the caller.
+        #[ink(constructor)]
+        pub fn new(token: AccountId) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                token: token.into(),
+                allocations: Mapping::default(),
+            }
+        }
+
+        /// Allocates `amount` more to `account`. Owner only.
+        #[ink(message)]
+        pub fn allocate(&mut self, account: AccountId, amount: Balance) -> Result<(), Error> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            let allocation = self.allocation(account).saturating_add(amount);
+            self.allocations.insert(account, &allocation);
+            Ok(())
+        }
+
+        /// What `account` can claim.
+        #[ink(message)]
+        pub fn allocation(&self, account: AccountId) -> Balance {
+            self.allocations.get(account).unwrap_or_default()
+        }
+
+        /// Pays the caller their allocation, returning the amount.
+        #[ink(message)]
+        pub fn claim(&mut self) -> Result<Balance, Error> {
+            let caller = self.env().caller();
+            let amount = self.allocations.take(caller).ok_or(Error::NothingToClaim)?;
+            self.token
+                .transfer(caller, amount)
+                .map_err(|_| Error::TransferFailed)?;
+            Ok(amount)
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// exercises/solutions/child_factory.rs
+//
+// The reference solution of `child_factory`. The number of children so far is each
+// child's salt, so no two collide.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod child_factory {
+    use child::child::ChildRef;
+    use ink::prelude::{string::String, vec::Vec};
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// The child could not be deployed.
+        InstantiationFailed,
+    }
+
+    #[ink(storage)]
+    pub struct ChildFactory {
+        child_code_hash: Hash,
+        children: Vec<AccountId>,
+    }
+
+    impl ChildFactory {
+        /// Creates a factory deploying children from `child_code_hash`.
+        #[ink(constructor)]
+        pub fn new(child_code_hash: Hash) -> Self {
+            Self {
+                child_code_hash,
+                children: Vec::new(),
+            }
+        }
+
+        /// Deploys a child labelled `label`, owned by the caller.
+        #[ink(message)]
+        pub fn create(&mut self, label: String) -> Result<AccountId, Error> {
+            let salt = (self.children.len() as u32).to_le_bytes();
+            let child = ChildRef::new(self.env().caller(), label)
+                .code_hash(self.child_code_hash)
+                .endowment(0)
+                .salt_bytes(salt)
+                .try_instantiate()
+                .map_err(|_| Error::InstantiationFailed)?
+                .map_err(|_| Error::InstantiationFailed)?;
+            let address = ink::ToAccountId::to_account_id(&child);
+            self.children.push(address);
+            Ok(address)
+        }
+
+        /// The children deployed so far, oldest first.
+        #[ink(message)]
+        pub fn children(&self) -> Vec<AccountId> {
+            self.children.clone()
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// exercises/solutions/counter_caller.rs
+//
+// The reference solution of `counter_caller`. The counter is a
+// `contract_ref!(Counter)`, whose methods make the calls.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod counter_caller {
+    use counter::{Counter, CounterError};
+
+    #[ink(storage)]
+    pub struct CounterCaller {
+        counter: ink::contract_ref!(Counter),
+    }
+
+    impl CounterCaller {
+        /// Creates the contract, calling the counter at `counter`.
+        #[ink(constructor)]
+        pub fn new(counter: AccountId) -> Self {
+            Self {
+                counter: counter.into(),
+            }
+        }
+
+        /// The counter's count.
+        #[ink(message)]
+        pub fn current(&self) -> u32 {
+            self.counter.get()
+        }
+
+        /// Increments the counter by `by`, returning the new count.
+        #[ink(message)]
+        pub fn increment_by(&mut self, by: u32) -> Result<u32, CounterError> {
+            self.counter.increment(by)
+        }
+
+        /// Increments the counter by `by` twice, returning the count after the second
+        /// increment.
+        #[ink(message)]
+        pub fn increment_twice(&mut self, by: u32) -> Result<u32, CounterError> {
+            self.counter.increment(by)?;
+            self.counter.increment(by)
+        }
+    }
+}
@@ -0,0 +1,73 @@
+// exercises/solutions/lazy_config.rs
+//
+// The reference solution of `lazy_config`. The motto sits behind `Lazy`, so only the
+// messages that use it load it.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod lazy_config {
+    use ink::{prelude::string::String, storage::Lazy};
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Config {
+        pub name: String,
+        pub description: String,
+        pub fee: Balance,
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// Only the owner can change the configuration.
+        NotOwner,
+    }
+
+    #[ink(storage)]
+    pub struct LazyConfig {
+        owner: AccountId,
+        visits: u64,
+        config: Lazy<Config>,
+    }
+
+    impl LazyConfig {
+        /// Creates the contract with `config`, owned by the caller.
+        #[ink(constructor)]
+        pub fn new(config: Config) -> Self {
+            let mut lazy = Lazy::new();
+            lazy.set(&config);
+            Self {
+                owner: Self::env().caller(),
+                visits: 0,
+                config: lazy,
+            }
+        }
+
+        /// Counts a visit, returning the count so far.
+        #[ink(message)]
+        pub fn visit(&mut self) -> u64 {
+            self.visits = self.visits.saturating_add(1);
+            self.visits
+        }
+
+        /// The configuration.
+        #[ink(message)]
+        pub fn config(&self) -> Config {
+            self.config.get().expect("set by the constructor")
+        }
+
+        /// Changes the fee, keeping the rest of the configuration. Only the owner can.
+        #[ink(message)]
+        pub fn set_fee(&mut self, fee: Balance) -> Result<(), Error> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            let mut config = self.config();
+            config.fee = fee;
+            self.config.set(&config);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,88 @@
+// exercises/solutions/mapping_ledger.rs
+//
+// The reference solution of `mapping_ledger`. Every check comes before the first
+// `insert`, and `transfer` writes the sender's balance before reading the recipient's,
+// so sending to yourself changes nothing.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod mapping_ledger {
+    use ink::storage::Mapping;
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// Only the owner can mint.
+        NotOwner,
+        /// The caller's balance is below the amount.
+        InsufficientBalance,
+        /// The total supply would overflow.
+        Overflow,
+    }
+
+    #[ink(storage)]
+    pub struct MappingLedger {
+        owner: AccountId,
+        balances: Mapping<AccountId, Balance>,
+        total_supply: Balance,
+    }
+
+    impl MappingLedger {
+        /// Creates an empty ledger owned by the caller.
+        #[ink(constructor)]
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Self {
+                owner: Self::env().caller(),
+                balances: Mapping::default(),
+                total_supply: 0,
+            }
+        }
+
+        /// The balance of `owner`, zero if it has none.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or(0)
+        }
+
+        /// The sum of every balance.
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// Credits `amount` to `to`. Only the owner can mint.
+        #[ink(message)]
+        pub fn mint(&mut self, to: AccountId, amount: Balance) -> Result<(), Error> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            let total_supply = self
+                .total_supply
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+            // Every balance is at most the total supply, so this cannot overflow.
+            let balance = self.balance_of(to) + amount;
+            self.total_supply = total_supply;
+            self.balances.insert(to, &balance);
+            Ok(())
+        }
+
+        /// Moves `amount` from the caller's balance to `to`'s.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, amount: Balance) -> Result<(), Error> {
+            let from = self.env().caller();
+            let from_balance = self.balance_of(from);
+            if from_balance < amount {
+                return Err(Error::InsufficientBalance);
+            }
+            self.balances.insert(from, &(from_balance - amount));
+            // Read after the write above, so a transfer to oneself adds back what it
+            // took.
+            let to_balance = self.balance_of(to);
+            self.balances.insert(to, &(to_balance + amount));
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,132 @@
+// exercises/solutions/packed_profiles.rs
+//
+// The reference solution of `packed_profiles`. Each update loads the whole profile,
+// changes it and inserts it back.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod packed_profiles {
+    use ink::{
+        prelude::{string::String, vec::Vec},
+        storage::Mapping,
+    };
+
+    /// The longest name, in bytes.
+    pub const MAX_NAME_LEN: usize = 32;
+    /// The most badges a profile holds.
+    pub const MAX_BADGES: usize = 8;
+
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Profile {
+        pub name: String,
+        pub points: u32,
+        pub badges: Vec<u32>,
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        NotOwner,
+        AlreadyRegistered,
+        NotRegistered,
+        NameTooLong,
+        TooManyBadges,
+    }
+
+    #[ink(storage)]
+    pub struct PackedProfiles {
+        owner: AccountId,
+        profiles: Mapping<AccountId, Profile>,
+    }
+
+    impl PackedProfiles {
+        /// Creates the contract, owned by the caller.
+        #[ink(constructor)]
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Self {
+                owner: Self::env().caller(),
+                profiles: Mapping::default(),
+            }
+        }
+
+        /// The profile of `account`, if it registered.
+        #[ink(message)]
+        pub fn profile(&self, account: AccountId) -> Option<Profile> {
+            self.profiles.get(account)
+        }
+
+        /// Registers the caller under `name`.
+        #[ink(message)]
+        pub fn register(&mut self, name: String) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if self.profiles.contains(caller) {
+                return Err(Error::AlreadyRegistered);
+            }
+            check_name(&name)?;
+            self.profiles.insert(
+                caller,
+                &Profile {
+                    name,
+                    ..Profile::default()
+                },
+            );
+            Ok(())
+        }
+
+        /// Changes the caller's name.
+        #[ink(message)]
+        pub fn rename(&mut self, name: String) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let mut profile = self.profiles.get(caller).ok_or(Error::NotRegistered)?;
+            check_name(&name)?;
+            profile.name = name;
+            self.profiles.insert(caller, &profile);
+            Ok(())
+        }
+
+        /// Adds `points` to `account`'s profile. Only the owner can.
+        #[ink(message)]
+        pub fn add_points(&mut self, account: AccountId, points: u32) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let mut profile = self.profiles.get(account).ok_or(Error::NotRegistered)?;
+            profile.points = profile.points.saturating_add(points);
+            self.profiles.insert(account, &profile);
+            Ok(())
+        }
+
+        /// Awards `badge` to `account`. Only the owner can.
+        #[ink(message)]
+        pub fn award_badge(&mut self, account: AccountId, badge: u32) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let mut profile = self.profiles.get(account).ok_or(Error::NotRegistered)?;
+            if profile.badges.contains(&badge) {
+                return Ok(());
+            }
+            if profile.badges.len() >= MAX_BADGES {
+                return Err(Error::TooManyBadges);
+            }
+            profile.badges.push(badge);
+            self.profiles.insert(account, &profile);
+            Ok(())
+        }
+
+        fn ensure_owner(&self) -> Result<(), Error> {
+            if self.env().caller() == self.owner {
+                Ok(())
+            } else {
+                Err(Error::NotOwner)
+            }
+        }
+    }
+
+    fn check_name(name: &str) -> Result<(), Error> {
+        if name.len() > MAX_NAME_LEN {
+            return Err(Error::NameTooLong);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+// exercises/solutions/payout_vault.rs
+//
+// The reference solution of `payout_vault`. The fix: the balance is written before the
+// payment, not after it.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod payout_vault {
+    use ink::{codegen::TraitCallBuilder, env::CallFlags, storage::Mapping};
+    use receiver::{PaymentReceiver, Vault, VaultError};
+
+    #[ink(storage)]
+    pub struct PayoutVault {
+        balances: Mapping<AccountId, Balance>,
+    }
+
+    impl PayoutVault {
+        /// Creates an empty vault.
+        #[ink(constructor)]
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Self {
+                balances: Mapping::default(),
+            }
+        }
+
+        /// Pays `amount` to `to`, calling it back if it is a contract.
+        fn pay(&self, to: AccountId, amount: Balance) -> Result<(), VaultError> {
+            if !self.env().is_contract(&to) {
+                return self
+                    .env()
+                    .transfer(to, amount)
+                    .map_err(|_| VaultError::PaymentFailed);
+            }
+            let mut receiver: ink::contract_ref!(PaymentReceiver) = to.into();
+            match receiver
+                .call_mut()
+                .on_payment()
+                .transferred_value(amount)
+                .call_flags(CallFlags::ALLOW_REENTRY)
+                .try_invoke()
+            {
+                Ok(Ok(())) => Ok(()),
+                _ => Err(VaultError::PaymentFailed),
+            }
+        }
+    }
+
+    impl Vault for PayoutVault {
+        #[ink(message, payable)]
+        fn deposit(&mut self) {
+            let caller = self.env().caller();
+            let balance = self.balance_of(caller) + self.env().transferred_value();
+            self.balances.insert(caller, &balance);
+        }
+
+        #[ink(message)]
+        fn withdraw(&mut self, amount: Balance) -> Result<(), VaultError> {
+            let caller = self.env().caller();
+            let balance = self.balance_of(caller);
+            if balance < amount {
+                return Err(VaultError::InsufficientBalance);
+            }
+            // The balance is reduced before paying, so a call back into the vault sees
+            // it reduced; if the payment fails, the `Err` reverts it.
+            self.balances.insert(caller, &(balance - amount));
+            self.pay(caller, amount)
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or_default()
+        }
+    }
+}
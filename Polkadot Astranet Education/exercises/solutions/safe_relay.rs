@@ -0,0 +1,61 @@
+// exercises/solutions/safe_relay.rs
+//
+// The reference solution of `safe_relay`. `try_invoke` turns every way the call can go
+// into a value, matched into an `Outcome`.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod safe_relay {
+    use counter::{Counter, CounterError};
+    use ink::codegen::TraitCallBuilder;
+
+    /// How an increment went.
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Outcome {
+        /// The counter was incremented to this count.
+        Incremented(u32),
+        /// The counter refused the increment.
+        Rejected(CounterError),
+        /// The call did not return: the counter trapped, or is not there.
+        CallFailed,
+    }
+
+    #[ink(storage)]
+    pub struct SafeRelay {
+        counter: ink::contract_ref!(Counter),
+        failures: u32,
+    }
+
+    impl SafeRelay {
+        /// Creates a relay to the counter at `counter`.
+        #[ink(constructor)]
+        pub fn new(counter: AccountId) -> Self {
+            Self {
+                counter: counter.into(),
+                failures: 0,
+            }
+        }
+
+        /// Increments the counter by `by`, reporting how it went.
+        #[ink(message)]
+        pub fn try_increment(&mut self, by: u32) -> Outcome {
+            let outcome = match self.counter.call_mut().increment(by).try_invoke() {
+                Ok(Ok(Ok(count))) => Outcome::Incremented(count),
+                Ok(Ok(Err(err))) => Outcome::Rejected(err),
+                Ok(Err(_)) | Err(_) => Outcome::CallFailed,
+            };
+            if !matches!(outcome, Outcome::Incremented(_)) {
+                self.failures = self.failures.saturating_add(1);
+            }
+            outcome
+        }
+
+        /// The number of increments that did not go through.
+        #[ink(message)]
+        pub fn failures(&self) -> u32 {
+            self.failures
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// exercises/solutions/storage_limits.rs
+//
+// The reference solution of `storage_limits`. Sizes are checked before anything is
+// stored, and a document is stored a chunk per cell.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod storage_limits {
+    use ink::{
+        prelude::{string::String, vec::Vec},
+        storage::Mapping,
+    };
+
+    /// The size of every chunk but a document's last, in bytes.
+    pub const CHUNK_SIZE: usize = 8 * 1024;
+    /// The most chunks in a document.
+    pub const MAX_CHUNKS: u32 = 8;
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// The document or its title is too large to store.
+        TooLarge,
+    }
+
+    #[ink(storage)]
+    pub struct StorageLimits {
+        next_id: u32,
+        titles: Mapping<u32, String>,
+        lengths: Mapping<u32, u32>,
+        /// Chunk `index` of document `id`, by `(id, index)`.
+        chunks: Mapping<(u32, u32), Vec<u8>>,
+    }
+
+    impl StorageLimits {
+        /// Creates an empty document store.
+        #[ink(constructor)]
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Self {
+                next_id: 0,
+                titles: Mapping::default(),
+                lengths: Mapping::default(),
+                chunks: Mapping::default(),
+            }
+        }
+
+        /// Stores `data` under `title`, returning its id.
+        #[ink(message)]
+        pub fn store(&mut self, title: String, data: Vec<u8>) -> Result<u32, Error> {
+            if data.len() > MAX_CHUNKS as usize * CHUNK_SIZE {
+                return Err(Error::TooLarge);
+            }
+            let id = self.next_id;
+            // Before anything else, so a title too large leaves nothing behind.
+            self.titles
+                .try_insert(id, &title)
+                .map_err(|_| Error::TooLarge)?;
+            self.lengths.insert(id, &(data.len() as u32));
+            for (index, chunk) in data.chunks(CHUNK_SIZE).enumerate() {
+                self.chunks.insert((id, index as u32), &chunk.to_vec());
+            }
+            self.next_id += 1;
+            Ok(id)
+        }
+
+        /// The title of document `id`.
+        #[ink(message)]
+        pub fn title(&self, id: u32) -> Option<String> {
+            self.titles.get(id)
+        }
+
+        /// The length of document `id`, in bytes.
+        #[ink(message)]
+        pub fn len(&self, id: u32) -> Option<u32> {
+            self.lengths.get(id)
+        }
+
+        /// The number of chunks of document `id`.
+        #[ink(message)]
+        pub fn chunk_count(&self, id: u32) -> Option<u32> {
+            let len = self.lengths.get(id)?;
+            Some(len.div_ceil(CHUNK_SIZE as u32))
+        }
+
+        /// Chunk `index` of document `id`.
+        #[ink(message)]
+        pub fn chunk(&self, id: u32, index: u32) -> Option<Vec<u8>> {
+            self.chunks.get((id, index))
+        }
+    }
+}
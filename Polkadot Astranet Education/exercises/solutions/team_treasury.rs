@@ -0,0 +1,116 @@
+// exercises/solutions/team_treasury.rs
+//
+// The reference solution of `team_treasury`. The fix: the owner check in `add_spender`
+// and `set_limit`.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod team_treasury {
+    use ink::storage::Mapping;
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// Only the owner can do this.
+        NotOwner,
+        /// Only the owner and spenders can pay.
+        NotSpender,
+        /// The payment is above the limit.
+        AboveLimit,
+        /// The treasury cannot make the payment.
+        TransferFailed,
+    }
+
+    #[ink(storage)]
+    pub struct TeamTreasury {
+        owner: AccountId,
+        spenders: Mapping<AccountId, ()>,
+        limit: Balance,
+    }
+
+    impl TeamTreasury {
+        /// Creates a treasury owned by the caller, paying at most `limit` at a time.
+        #[ink(constructor, payable)]
+        pub fn new(limit: Balance) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                spenders: Mapping::default(),
+                limit,
+            }
+        }
+
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        #[ink(message)]
+        pub fn limit(&self) -> Balance {
+            self.limit
+        }
+
+        #[ink(message)]
+        pub fn is_spender(&self, account: AccountId) -> bool {
+            self.spenders.contains(account)
+        }
+
+        /// Adds funds to the treasury.
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) {}
+
+        /// Lets `account` make payments. Owner only.
+        #[ink(message)]
+        pub fn add_spender(&mut self, account: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.spenders.insert(account, &());
+            Ok(())
+        }
+
+        /// Stops `account` making payments. Owner only.
+        #[ink(message)]
+        pub fn remove_spender(&mut self, account: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.spenders.remove(account);
+            Ok(())
+        }
+
+        /// Sets the largest payment. Owner only.
+        #[ink(message)]
+        pub fn set_limit(&mut self, limit: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.limit = limit;
+            Ok(())
+        }
+
+        /// Hands the treasury to `new_owner`. Owner only.
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.owner = new_owner;
+            Ok(())
+        }
+
+        /// Pays `amount` to `to`. Owner and spenders only.
+        #[ink(message)]
+        pub fn pay(&mut self, to: AccountId, amount: Balance) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if caller != self.owner && !self.spenders.contains(caller) {
+                return Err(Error::NotSpender);
+            }
+            if amount > self.limit {
+                return Err(Error::AboveLimit);
+            }
+            self.env()
+                .transfer(to, amount)
+                .map_err(|_| Error::TransferFailed)
+        }
+
+        fn ensure_owner(&self) -> Result<(), Error> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,85 @@
+// exercises/solutions/vec_pitfalls.rs
+//
+// The reference solution of `vec_pitfalls`. The entries live in a `StorageVec`, one
+// cell each, and pages are bounded.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod vec_pitfalls {
+    use ink::{
+        prelude::{string::String, vec::Vec},
+        storage::StorageVec,
+    };
+
+    /// The longest message, in bytes.
+    pub const MAX_MESSAGE_LEN: usize = 140;
+    /// The most entries `page` returns.
+    pub const MAX_PAGE: u32 = 20;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Entry {
+        pub author: AccountId,
+        pub message: String,
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        MessageTooLong,
+    }
+
+    #[ink(storage)]
+    pub struct VecPitfalls {
+        entries: StorageVec<Entry>,
+    }
+
+    impl VecPitfalls {
+        /// Creates an empty guest book.
+        #[ink(constructor)]
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Self {
+                entries: Default::default(),
+            }
+        }
+
+        /// Signs the guest book with `message`.
+        #[ink(message)]
+        pub fn sign(&mut self, message: String) -> Result<(), Error> {
+            if message.len() > MAX_MESSAGE_LEN {
+                return Err(Error::MessageTooLong);
+            }
+            self.entries.push(&Entry {
+                author: self.env().caller(),
+                message,
+            });
+            Ok(())
+        }
+
+        /// The number of entries.
+        #[ink(message)]
+        pub fn len(&self) -> u32 {
+            self.entries.len()
+        }
+
+        /// The entry at `index`.
+        #[ink(message)]
+        pub fn entry(&self, index: u32) -> Option<Entry> {
+            self.entries.get(index)
+        }
+
+        /// Up to `count` entries from index `start`, at most `MAX_PAGE`.
+        #[ink(message)]
+        pub fn page(&self, start: u32, count: u32) -> Vec<Entry> {
+            let end = start
+                .saturating_add(count.min(MAX_PAGE))
+                .min(self.entries.len());
+            (start..end)
+                .filter_map(|index| self.entries.get(index))
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,101 @@
+// exercises/storage_limits/src/lib.rs
+//
+// Values too large for one storage cell
+//
+// A contract reads and writes storage values, call arguments and return values
+// through a static buffer of `ink::env::BUFFER_SIZE` bytes, 16 KiB. A value that
+// encodes to more than that cannot be stored or loaded in one piece: `insert` traps,
+// leaving the caller with nothing but a failed transaction. This contract stores
+// documents of up to `MAX_CHUNKS` chunks of `CHUNK_SIZE` bytes, one chunk per cell,
+// and hands them back a chunk at a time.
+//
+// - `store`: stores a document under the next id, starting from 0, and returns the
+//   id. A document of more than `MAX_CHUNKS * CHUNK_SIZE` bytes, or a title too long
+//   to store in one cell, gets `Error::TooLarge` instead of a trap, with nothing
+//   stored and the id not used up.
+// - `title`, `len` and `chunk_count`: about a stored document, or `None`.
+// - `chunk`: the bytes of chunk `index` of a document, every chunk but the last
+//   `CHUNK_SIZE` bytes long.
+//
+// Fill in the `TODO`s below, then run `grader run storage_limits` to check your work.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod storage_limits {
+    use ink::{
+        prelude::{string::String, vec::Vec},
+        storage::Mapping,
+    };
+
+    /// The size of every chunk but a document's last, in bytes.
+    pub const CHUNK_SIZE: usize = 8 * 1024;
+    /// The most chunks in a document.
+    pub const MAX_CHUNKS: u32 = 8;
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// The document or its title is too large to store.
+        TooLarge,
+    }
+
+    #[ink(storage)]
+    pub struct StorageLimits {
+        next_id: u32,
+        titles: Mapping<u32, String>,
+        lengths: Mapping<u32, u32>,
+        /// Chunk `index` of document `id`, by `(id, index)`.
+        chunks: Mapping<(u32, u32), Vec<u8>>,
+    }
+
+    impl StorageLimits {
+        /// Creates an empty document store.
+        #[ink(constructor)]
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Self {
+                next_id: 0,
+                titles: Mapping::default(),
+                lengths: Mapping::default(),
+                chunks: Mapping::default(),
+            }
+        }
+
+        /// Stores `data` under `title`, returning its id.
+        #[ink(message)]
+        pub fn store(&mut self, title: String, data: Vec<u8>) -> Result<u32, Error> {
+            // TODO: check the sizes, then store the title, the length and each chunk.
+            let _ = (title, data);
+            todo!("implement `store`")
+        }
+
+        /// The title of document `id`.
+        #[ink(message)]
+        pub fn title(&self, id: u32) -> Option<String> {
+            self.titles.get(id)
+        }
+
+        /// The length of document `id`, in bytes.
+        #[ink(message)]
+        pub fn len(&self, id: u32) -> Option<u32> {
+            self.lengths.get(id)
+        }
+
+        /// The number of chunks of document `id`.
+        #[ink(message)]
+        pub fn chunk_count(&self, id: u32) -> Option<u32> {
+            // TODO
+            let _ = id;
+            todo!("implement `chunk_count`")
+        }
+
+        /// Chunk `index` of document `id`.
+        #[ink(message)]
+        pub fn chunk(&self, id: u32, index: u32) -> Option<Vec<u8>> {
+            // TODO
+            let _ = (id, index);
+            todo!("implement `chunk`")
+        }
+    }
+}
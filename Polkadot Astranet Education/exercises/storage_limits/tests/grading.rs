@@ -0,0 +1,101 @@
+// exercises/storage_limits/tests/grading.rs
+//
+// The grading tests of the exercise. Learners are asked not to read them: they check
+// the behaviour `src/lib.rs` describes, whichever way it is implemented.
+
+use storage_limits::storage_limits::{Error, StorageLimits, CHUNK_SIZE, MAX_CHUNKS};
+
+/// `len` bytes counting up from 0, wrapping.
+fn document(len: usize) -> Vec<u8> {
+    (0..len).map(|byte| byte as u8).collect()
+}
+
+/// Document `id` put back together from its chunks.
+fn reassemble(store: &StorageLimits, id: u32) -> Vec<u8> {
+    let count = store.chunk_count(id).expect("no such document");
+    let mut data = Vec::new();
+    for index in 0..count {
+        let chunk = store.chunk(id, index).expect("missing chunk");
+        assert!(chunk.len() <= CHUNK_SIZE, "chunk {} is too large", index);
+        data.extend(chunk);
+    }
+    data
+}
+
+#[ink::test]
+fn small_documents_take_one_chunk() {
+    let mut store = StorageLimits::new();
+    let id = store.store("notes".into(), document(100)).unwrap();
+    assert_eq!(id, 0);
+    assert_eq!(store.title(id).as_deref(), Some("notes"));
+    assert_eq!(store.len(id), Some(100));
+    assert_eq!(store.chunk_count(id), Some(1));
+    assert_eq!(store.chunk(id, 0), Some(document(100)));
+    assert_eq!(store.chunk(id, 1), None);
+}
+
+#[ink::test]
+fn large_documents_are_split_into_chunks() {
+    let mut store = StorageLimits::new();
+    let exact = store.store("exact".into(), document(CHUNK_SIZE)).unwrap();
+    let over = store.store("over".into(), document(CHUNK_SIZE + 1)).unwrap();
+    let large = store
+        .store("large".into(), document(3 * CHUNK_SIZE + 500))
+        .unwrap();
+    assert_eq!((exact, over, large), (0, 1, 2));
+    assert_eq!(store.chunk_count(exact), Some(1));
+    assert_eq!(store.chunk_count(over), Some(2));
+    assert_eq!(store.chunk(over, 1), Some(vec![0]));
+    assert_eq!(store.chunk_count(large), Some(4));
+    assert_eq!(reassemble(&store, large), document(3 * CHUNK_SIZE + 500));
+}
+
+#[ink::test]
+fn the_largest_document_fits() {
+    let mut store = StorageLimits::new();
+    let size = MAX_CHUNKS as usize * CHUNK_SIZE;
+    let id = store.store("largest".into(), document(size)).unwrap();
+    assert_eq!(store.chunk_count(id), Some(MAX_CHUNKS));
+    assert_eq!(reassemble(&store, id), document(size));
+}
+
+#[ink::test]
+fn empty_documents_have_no_chunks() {
+    let mut store = StorageLimits::new();
+    let id = store.store("empty".into(), Vec::new()).unwrap();
+    assert_eq!(store.len(id), Some(0));
+    assert_eq!(store.chunk_count(id), Some(0));
+    assert_eq!(store.chunk(id, 0), None);
+}
+
+#[ink::test]
+fn oversized_documents_are_refused() {
+    let mut store = StorageLimits::new();
+    let size = MAX_CHUNKS as usize * CHUNK_SIZE + 1;
+    assert_eq!(
+        store.store("too large".into(), document(size)),
+        Err(Error::TooLarge)
+    );
+    assert_eq!(store.title(0), None);
+    assert_eq!(store.chunk(0, 0), None);
+    assert_eq!(store.store("next".into(), document(1)), Ok(0));
+}
+
+#[ink::test]
+fn titles_larger_than_a_cell_are_refused() {
+    let mut store = StorageLimits::new();
+    let title = "t".repeat(ink::env::BUFFER_SIZE);
+    assert_eq!(store.store(title, document(10)), Err(Error::TooLarge));
+    assert_eq!(store.len(0), None);
+    assert_eq!(store.chunk(0, 0), None);
+    assert_eq!(store.store("next".into(), document(1)), Ok(0));
+}
+
+#[ink::test]
+fn unknown_documents_are_none() {
+    let store = StorageLimits::new();
+    assert_eq!(store.title(7), None);
+    assert_eq!(store.len(7), None);
+    assert_eq!(store.chunk_count(7), None);
+    assert_eq!(store.chunk(7, 0), None);
+}
@@ -0,0 +1,36 @@
+// exercises/support/child/src/lib.rs
+//
+// The contract `child_factory` deploys: a labelled record of who created it.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod child {
+    use ink::prelude::string::String;
+
+    #[ink(storage)]
+    pub struct Child {
+        owner: AccountId,
+        label: String,
+    }
+
+    impl Child {
+        /// Creates a child belonging to `owner`.
+        #[ink(constructor)]
+        pub fn new(owner: AccountId, label: String) -> Self {
+            Self { owner, label }
+        }
+
+        /// The account the child belongs to.
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// The label it was created with.
+        #[ink(message)]
+        pub fn label(&self) -> String {
+            self.label.clone()
+        }
+    }
+}
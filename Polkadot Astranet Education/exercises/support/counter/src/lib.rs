@@ -0,0 +1,74 @@
+// exercises/support/counter/src/lib.rs
+//
+// The callee of the cross-contract exercises: a counter behind the `Counter` trait,
+// which is all the exercises' contracts may rely on. It can also stand in for a
+// callee with a bug: after `set_broken(true)`, its `Counter` messages trap.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Why a counter refused an increment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub enum CounterError {
+    /// The count would overflow.
+    Overflow,
+}
+
+/// The interface of a counter.
+#[ink::trait_definition]
+pub trait Counter {
+    /// The count.
+    #[ink(message)]
+    fn get(&self) -> u32;
+
+    /// Adds `by` to the count, returning the new count.
+    #[ink(message)]
+    fn increment(&mut self, by: u32) -> Result<u32, CounterError>;
+}
+
+#[ink::contract]
+pub mod counter {
+    use super::{Counter, CounterError};
+
+    #[ink(storage)]
+    pub struct BasicCounter {
+        count: u32,
+        broken: bool,
+    }
+
+    impl BasicCounter {
+        /// Creates a counter at `start`.
+        #[ink(constructor)]
+        pub fn new(start: u32) -> Self {
+            Self {
+                count: start,
+                broken: false,
+            }
+        }
+
+        /// Makes the `Counter` messages trap from now on, or work again.
+        #[ink(message)]
+        pub fn set_broken(&mut self, broken: bool) {
+            self.broken = broken;
+        }
+
+        fn ensure_working(&self) {
+            assert!(!self.broken, "the counter is broken");
+        }
+    }
+
+    impl Counter for BasicCounter {
+        #[ink(message)]
+        fn get(&self) -> u32 {
+            self.ensure_working();
+            self.count
+        }
+
+        #[ink(message)]
+        fn increment(&mut self, by: u32) -> Result<u32, CounterError> {
+            self.ensure_working();
+            self.count = self.count.checked_add(by).ok_or(CounterError::Overflow)?;
+            Ok(self.count)
+        }
+    }
+}
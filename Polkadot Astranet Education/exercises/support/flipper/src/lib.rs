@@ -0,0 +1,35 @@
+// exercises/support/flipper/src/lib.rs
+//
+// The ink! 5 build of `examples/demo-contracts/flipper.rs`, which is written for
+// ink! 3. It is the child `examples/demo-contracts/factory.rs` deploys, so `new`
+// must keep the selector the factory hard-codes.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod flipper {
+    #[ink(storage)]
+    pub struct Flipper {
+        value: bool,
+    }
+
+    impl Flipper {
+        /// Creates a flipper holding `init_value`.
+        #[ink(constructor)]
+        pub fn new(init_value: bool) -> Self {
+            Self { value: init_value }
+        }
+
+        /// Flips the value.
+        #[ink(message)]
+        pub fn flip(&mut self) {
+            self.value = !self.value;
+        }
+
+        /// The current value.
+        #[ink(message)]
+        pub fn get(&self) -> bool {
+            self.value
+        }
+    }
+}
@@ -0,0 +1,135 @@
+// exercises/support/receiver/src/lib.rs
+//
+// The other side of `payout_vault`: the interfaces of a vault and of the contracts it
+// pays, and a receiving contract that deposits into a vault, withdraws from it, and
+// does one of three things when paid:
+//
+// - `Keep`: keeps the payment.
+// - `Redeposit`: deposits it straight back, an honest use of calling back into the
+//   vault while it is paying.
+// - `Reenter`: withdraws the same amount again, once, before the vault has finished
+//   the first withdrawal. A vault that is not safe against reentrancy pays twice.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Why a vault refused a withdrawal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub enum VaultError {
+    /// The caller's balance is below the amount.
+    InsufficientBalance,
+    /// The vault could not pay the caller.
+    PaymentFailed,
+}
+
+/// The interface of a vault.
+#[ink::trait_definition]
+pub trait Vault {
+    /// Credits the transferred value to the caller.
+    #[ink(message, payable)]
+    fn deposit(&mut self);
+
+    /// Pays `amount` of the caller's balance out to the caller.
+    #[ink(message)]
+    fn withdraw(&mut self, amount: u128) -> Result<(), VaultError>;
+
+    /// The balance of `owner`.
+    #[ink(message)]
+    fn balance_of(&self, owner: ink::primitives::AccountId) -> u128;
+}
+
+/// The interface of a contract a vault pays.
+#[ink::trait_definition]
+pub trait PaymentReceiver {
+    /// Receives a payment, the transferred value.
+    #[ink(message, payable)]
+    fn on_payment(&mut self);
+}
+
+#[ink::contract]
+pub mod receiver {
+    use super::{PaymentReceiver, Vault, VaultError};
+    use ink::{codegen::TraitCallBuilder, env::CallFlags, storage::Lazy};
+
+    /// What a receiver does when paid.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum Behaviour {
+        Keep,
+        Redeposit,
+        Reenter,
+    }
+
+    #[ink(storage)]
+    pub struct Receiver {
+        vault: ink::contract_ref!(Vault),
+        behaviour: Behaviour,
+        /// Whether `Reenter` has re-entered. Kept in its own cell, which is written at
+        /// once, unlike the rest of the storage, written when the message returns: the
+        /// re-entered call must see it.
+        reentered: Lazy<bool>,
+    }
+
+    impl Receiver {
+        /// Creates a receiver using the vault at `vault`.
+        #[ink(constructor)]
+        pub fn new(vault: AccountId, behaviour: Behaviour) -> Self {
+            Self {
+                vault: vault.into(),
+                behaviour,
+                reentered: Lazy::new(),
+            }
+        }
+
+        /// Deposits the transferred value into the vault.
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) {
+            let value = self.env().transferred_value();
+            self.vault
+                .call_mut()
+                .deposit()
+                .transferred_value(value)
+                .invoke();
+        }
+
+        /// Withdraws `amount` from the vault.
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: Balance) -> Result<(), VaultError> {
+            // The vault calls back while paying, which re-enters this contract.
+            self.vault
+                .call_mut()
+                .withdraw(amount)
+                .call_flags(CallFlags::ALLOW_REENTRY)
+                .invoke()
+        }
+    }
+
+    impl PaymentReceiver for Receiver {
+        #[ink(message, payable)]
+        fn on_payment(&mut self) {
+            let value = self.env().transferred_value();
+            match self.behaviour {
+                Behaviour::Keep => {}
+                Behaviour::Redeposit => self
+                    .vault
+                    .call_mut()
+                    .deposit()
+                    .transferred_value(value)
+                    .invoke(),
+                Behaviour::Reenter => {
+                    if !self.reentered.get().unwrap_or_default() {
+                        self.reentered.set(&true);
+                        // A vault that refuses is fine; the first withdrawal stands.
+                        let _ = self
+                            .vault
+                            .call_mut()
+                            .withdraw(value)
+                            .call_flags(CallFlags::ALLOW_REENTRY)
+                            .invoke();
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+// exercises/support/token/src/lib.rs
+//
+// The token `airdrop_claims` pays out: balances behind the `Token` trait, with a
+// transfer that returns an error, rather than trapping, when the sender is short.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Why a token transfer was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub enum TokenError {
+    /// The sender's balance is below the amount.
+    InsufficientBalance,
+}
+
+/// The interface of a token.
+#[ink::trait_definition]
+pub trait Token {
+    /// The balance of `owner`.
+    #[ink(message)]
+    fn balance_of(&self, owner: ink::primitives::AccountId) -> u128;
+
+    /// Sends `value` from the caller to `to`.
+    #[ink(message)]
+    fn transfer(&mut self, to: ink::primitives::AccountId, value: u128) -> Result<(), TokenError>;
+}
+
+#[ink::contract]
+pub mod token {
+    use super::{Token, TokenError};
+    use ink::storage::Mapping;
+
+    #[ink(storage)]
+    pub struct SimpleToken {
+        balances: Mapping<AccountId, Balance>,
+    }
+
+    impl SimpleToken {
+        /// Creates a token with `total_supply` tokens, all the caller's.
+        #[ink(constructor)]
+        pub fn new(total_supply: Balance) -> Self {
+            let mut balances = Mapping::default();
+            balances.insert(Self::env().caller(), &total_supply);
+            Self { balances }
+        }
+    }
+
+    impl Token for SimpleToken {
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, value: Balance) -> Result<(), TokenError> {
+            let from = self.env().caller();
+            let from_balance = self.balance_of(from);
+            if from_balance < value {
+                return Err(TokenError::InsufficientBalance);
+            }
+            self.balances.insert(from, &(from_balance - value));
+            let to_balance = self.balance_of(to);
+            self.balances.insert(to, &(to_balance + value));
+            Ok(())
+        }
+    }
+}
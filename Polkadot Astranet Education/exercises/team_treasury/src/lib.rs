@@ -0,0 +1,124 @@
+// exercises/team_treasury/src/lib.rs
+//
+// Missing access control
+//
+// A team keeps its funds in this treasury. The owner decides who may spend and how
+// much one payment may be; spenders pay out, each payment up to the limit. Every
+// message that changes who may do what has to check who is calling: a message is
+// callable by anyone, and one that forgets the check hands its power to everyone.
+//
+// TODO: find and fix the bugs. Some of the owner's messages do not check the caller.
+// Anyone but the owner calling an owner's message must get `Error::NotOwner`, with
+// nothing changed; the owner, spenders and payments must keep working as they do.
+//
+// Then run `grader run team_treasury` to check your work.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod team_treasury {
+    use ink::storage::Mapping;
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// Only the owner can do this.
+        NotOwner,
+        /// Only the owner and spenders can pay.
+        NotSpender,
+        /// The payment is above the limit.
+        AboveLimit,
+        /// The treasury cannot make the payment.
+        TransferFailed,
+    }
+
+    #[ink(storage)]
+    pub struct TeamTreasury {
+        owner: AccountId,
+        spenders: Mapping<AccountId, ()>,
+        limit: Balance,
+    }
+
+    impl TeamTreasury {
+        /// Creates a treasury owned by the caller, paying at most `limit` at a time.
+        #[ink(constructor, payable)]
+        pub fn new(limit: Balance) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                spenders: Mapping::default(),
+                limit,
+            }
+        }
+
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        #[ink(message)]
+        pub fn limit(&self) -> Balance {
+            self.limit
+        }
+
+        #[ink(message)]
+        pub fn is_spender(&self, account: AccountId) -> bool {
+            self.spenders.contains(account)
+        }
+
+        /// Adds funds to the treasury.
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) {}
+
+        /// Lets `account` make payments. Owner only.
+        #[ink(message)]
+        pub fn add_spender(&mut self, account: AccountId) -> Result<(), Error> {
+            self.spenders.insert(account, &());
+            Ok(())
+        }
+
+        /// Stops `account` making payments. Owner only.
+        #[ink(message)]
+        pub fn remove_spender(&mut self, account: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.spenders.remove(account);
+            Ok(())
+        }
+
+        /// Sets the largest payment. Owner only.
+        #[ink(message)]
+        pub fn set_limit(&mut self, limit: Balance) -> Result<(), Error> {
+            self.limit = limit;
+            Ok(())
+        }
+
+        /// Hands the treasury to `new_owner`. Owner only.
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.owner = new_owner;
+            Ok(())
+        }
+
+        /// Pays `amount` to `to`. Owner and spenders only.
+        #[ink(message)]
+        pub fn pay(&mut self, to: AccountId, amount: Balance) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if caller != self.owner && !self.spenders.contains(caller) {
+                return Err(Error::NotSpender);
+            }
+            if amount > self.limit {
+                return Err(Error::AboveLimit);
+            }
+            self.env()
+                .transfer(to, amount)
+                .map_err(|_| Error::TransferFailed)
+        }
+
+        fn ensure_owner(&self) -> Result<(), Error> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+    }
+}
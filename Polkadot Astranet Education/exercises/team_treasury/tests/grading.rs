@@ -0,0 +1,123 @@
+// exercises/team_treasury/tests/grading.rs
+//
+// The grading tests of the exercise. Learners are asked not to read them: they check
+// the behaviour `src/lib.rs` describes, whichever way it is implemented.
+
+use ink::{
+    env::{
+        test::{
+            callee, default_accounts, get_account_balance, set_account_balance, set_caller,
+            DefaultAccounts,
+        },
+        DefaultEnvironment,
+    },
+    primitives::AccountId,
+};
+use team_treasury::team_treasury::{Error, TeamTreasury};
+
+fn accounts() -> DefaultAccounts<DefaultEnvironment> {
+    default_accounts::<DefaultEnvironment>()
+}
+
+fn balance(account: AccountId) -> u128 {
+    get_account_balance::<DefaultEnvironment>(account).unwrap()
+}
+
+fn call_as(account: AccountId) {
+    set_caller::<DefaultEnvironment>(account);
+}
+
+/// Alice's treasury, well funded and paying at most 100 at a time.
+fn treasury() -> TeamTreasury {
+    call_as(accounts().alice);
+    let treasury = TeamTreasury::new(100);
+    set_account_balance::<DefaultEnvironment>(callee::<DefaultEnvironment>(), 1_000_000_000);
+    treasury
+}
+
+#[ink::test]
+fn spenders_pay_up_to_the_limit() {
+    let mut treasury = treasury();
+    treasury.add_spender(accounts().bob).unwrap();
+    assert!(treasury.is_spender(accounts().bob));
+
+    let before = balance(accounts().django);
+    call_as(accounts().bob);
+    treasury.pay(accounts().django, 60).unwrap();
+    assert_eq!(balance(accounts().django), before + 60);
+    assert_eq!(treasury.pay(accounts().django, 101), Err(Error::AboveLimit));
+    call_as(accounts().charlie);
+    assert_eq!(treasury.pay(accounts().django, 1), Err(Error::NotSpender));
+    call_as(accounts().alice);
+    treasury.pay(accounts().django, 100).unwrap();
+    assert_eq!(balance(accounts().django), before + 160);
+}
+
+#[ink::test]
+fn the_owner_manages_the_treasury() {
+    let mut treasury = treasury();
+    treasury.add_spender(accounts().bob).unwrap();
+    treasury.set_limit(500).unwrap();
+    assert_eq!(treasury.limit(), 500);
+    call_as(accounts().bob);
+    treasury.pay(accounts().django, 500).unwrap();
+
+    call_as(accounts().alice);
+    treasury.remove_spender(accounts().bob).unwrap();
+    call_as(accounts().bob);
+    assert_eq!(treasury.pay(accounts().django, 1), Err(Error::NotSpender));
+
+    call_as(accounts().alice);
+    treasury.transfer_ownership(accounts().charlie).unwrap();
+    assert_eq!(treasury.owner(), accounts().charlie);
+    assert_eq!(treasury.set_limit(1), Err(Error::NotOwner));
+    call_as(accounts().charlie);
+    treasury.set_limit(1).unwrap();
+    assert_eq!(treasury.limit(), 1);
+}
+
+#[ink::test]
+fn only_the_owner_adds_spenders() {
+    let mut treasury = treasury();
+    call_as(accounts().bob);
+    assert_eq!(treasury.add_spender(accounts().bob), Err(Error::NotOwner));
+    assert!(!treasury.is_spender(accounts().bob));
+    assert_eq!(treasury.pay(accounts().bob, 100), Err(Error::NotSpender));
+
+    call_as(accounts().alice);
+    treasury.add_spender(accounts().bob).unwrap();
+    call_as(accounts().bob);
+    assert_eq!(
+        treasury.add_spender(accounts().charlie),
+        Err(Error::NotOwner)
+    );
+    assert!(!treasury.is_spender(accounts().charlie));
+}
+
+#[ink::test]
+fn only_the_owner_sets_the_limit() {
+    let mut treasury = treasury();
+    treasury.add_spender(accounts().bob).unwrap();
+    call_as(accounts().bob);
+    assert_eq!(treasury.set_limit(1_000), Err(Error::NotOwner));
+    assert_eq!(treasury.limit(), 100);
+    assert_eq!(treasury.pay(accounts().bob, 1_000), Err(Error::AboveLimit));
+}
+
+#[ink::test]
+fn only_the_owner_removes_spenders_and_hands_over() {
+    let mut treasury = treasury();
+    treasury.add_spender(accounts().bob).unwrap();
+    treasury.add_spender(accounts().charlie).unwrap();
+    call_as(accounts().bob);
+    assert_eq!(
+        treasury.remove_spender(accounts().charlie),
+        Err(Error::NotOwner)
+    );
+    assert!(treasury.is_spender(accounts().charlie));
+    assert_eq!(
+        treasury.transfer_ownership(accounts().bob),
+        Err(Error::NotOwner)
+    );
+    assert_eq!(treasury.owner(), accounts().alice);
+}
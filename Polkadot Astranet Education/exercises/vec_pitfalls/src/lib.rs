@@ -0,0 +1,92 @@
+// exercises/vec_pitfalls/src/lib.rs
+//
+// A guest book that outgrows its Vec
+//
+// A `Vec` field is packed like any other: the whole guest book is loaded on every
+// call and written back on every signature, so each one costs more than the last,
+// until the encoded book no longer fits the 16 KiB buffer a value is read into and
+// the contract can no longer be called at all. `StorageVec` keeps its length in one
+// cell and each element in a cell of its own, so a call touches only what it uses.
+//
+// - Change the type of `entries` to `StorageVec<Entry>`, keeping its name.
+// - `sign`: appends the caller's message, or returns `Error::MessageTooLong` for one
+//   over `MAX_MESSAGE_LEN` bytes.
+// - `len` and `entry`: the number of entries, and the one at an index if there is one.
+// - `page`: up to `count` entries from index `start`, never more than `MAX_PAGE`, and
+//   none past the end. It must not panic, whatever the arguments.
+//
+// Fill in the `TODO`s below, then run `grader run vec_pitfalls` to check your work.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod vec_pitfalls {
+    use ink::prelude::{string::String, vec::Vec};
+
+    /// The longest message, in bytes.
+    pub const MAX_MESSAGE_LEN: usize = 140;
+    /// The most entries `page` returns.
+    pub const MAX_PAGE: u32 = 20;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Entry {
+        pub author: AccountId,
+        pub message: String,
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        MessageTooLong,
+    }
+
+    #[ink(storage)]
+    pub struct VecPitfalls {
+        // TODO: store each entry in a cell of its own.
+        entries: Vec<Entry>,
+    }
+
+    impl VecPitfalls {
+        /// Creates an empty guest book.
+        #[ink(constructor)]
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Self {
+                entries: Default::default(),
+            }
+        }
+
+        /// Signs the guest book with `message`.
+        #[ink(message)]
+        pub fn sign(&mut self, message: String) -> Result<(), Error> {
+            // TODO: check the message, then append it.
+            let _ = message;
+            todo!("implement `sign`")
+        }
+
+        /// The number of entries.
+        #[ink(message)]
+        pub fn len(&self) -> u32 {
+            // TODO
+            todo!("implement `len`")
+        }
+
+        /// The entry at `index`.
+        #[ink(message)]
+        pub fn entry(&self, index: u32) -> Option<Entry> {
+            // TODO
+            let _ = index;
+            todo!("implement `entry`")
+        }
+
+        /// Up to `count` entries from index `start`, at most `MAX_PAGE`.
+        #[ink(message)]
+        pub fn page(&self, start: u32, count: u32) -> Vec<Entry> {
+            // TODO
+            let _ = (start, count);
+            todo!("implement `page`")
+        }
+    }
+}
@@ -0,0 +1,116 @@
+// exercises/vec_pitfalls/tests/grading.rs
+//
+// The grading tests of the exercise. Learners are asked not to read them: they check
+// the behaviour `src/lib.rs` describes, whichever way it is implemented.
+
+use ink::{
+    env::{
+        test::{default_accounts, set_caller},
+        DefaultEnvironment,
+    },
+    metadata::layout::Layout,
+    storage::traits::StorageLayout,
+};
+use vec_pitfalls::vec_pitfalls::{Error, VecPitfalls, MAX_MESSAGE_LEN, MAX_PAGE};
+
+/// A guest book signed `count` times, with messages "0", "1" and so on.
+fn signed(count: u32) -> VecPitfalls {
+    let mut book = VecPitfalls::new();
+    for index in 0..count {
+        book.sign(index.to_string()).unwrap();
+    }
+    book
+}
+
+fn messages(book: &VecPitfalls, start: u32, count: u32) -> Vec<String> {
+    book.page(start, count)
+        .into_iter()
+        .map(|entry| entry.message)
+        .collect()
+}
+
+#[test]
+fn entries_have_cells_of_their_own() {
+    let Layout::Struct(layout) = <VecPitfalls as StorageLayout>::layout(&0) else {
+        panic!("the storage of the contract is not a struct");
+    };
+    let entries = layout
+        .fields()
+        .iter()
+        .find(|field| *field.name() == "entries")
+        .expect("the contract has no storage field `entries`");
+    assert!(
+        matches!(entries.layout(), Layout::Root(_)),
+        "`entries` is still loaded whole on every call"
+    );
+}
+
+#[ink::test]
+fn signatures_are_kept_in_order() {
+    let mut book = VecPitfalls::new();
+    assert_eq!(book.len(), 0);
+    assert_eq!(book.entry(0), None);
+    assert!(book.page(0, 5).is_empty());
+
+    let accounts = default_accounts::<DefaultEnvironment>();
+    book.sign("hello".into()).unwrap();
+    set_caller::<DefaultEnvironment>(accounts.bob);
+    book.sign("hi from bob".into()).unwrap();
+
+    assert_eq!(book.len(), 2);
+    let first = book.entry(0).unwrap();
+    assert_eq!((first.author, first.message.as_str()), (accounts.alice, "hello"));
+    let second = book.entry(1).unwrap();
+    assert_eq!(
+        (second.author, second.message.as_str()),
+        (accounts.bob, "hi from bob")
+    );
+    assert_eq!(book.entry(2), None);
+}
+
+#[ink::test]
+fn messages_are_bounded() {
+    let mut book = VecPitfalls::new();
+    assert_eq!(
+        book.sign("x".repeat(MAX_MESSAGE_LEN + 1)),
+        Err(Error::MessageTooLong)
+    );
+    assert_eq!(book.len(), 0);
+    book.sign("x".repeat(MAX_MESSAGE_LEN)).unwrap();
+    assert_eq!(book.len(), 1);
+}
+
+#[ink::test]
+fn pages_are_slices_of_the_book() {
+    let book = signed(5);
+    assert_eq!(messages(&book, 0, 2), ["0", "1"]);
+    assert_eq!(messages(&book, 3, 2), ["3", "4"]);
+    assert_eq!(messages(&book, 3, 10), ["3", "4"]);
+    assert_eq!(messages(&book, 0, 0), Vec::<String>::new());
+}
+
+#[ink::test]
+fn pages_are_bounded() {
+    let book = signed(MAX_PAGE * 2 + 5);
+    assert_eq!(book.page(0, MAX_PAGE + 5).len(), MAX_PAGE as usize);
+    assert_eq!(book.page(10, u32::MAX).len(), MAX_PAGE as usize);
+}
+
+#[ink::test]
+fn pages_past_the_end_are_empty() {
+    let book = signed(3);
+    assert!(book.page(3, 1).is_empty());
+    assert!(book.page(100, 5).is_empty());
+    assert!(book.page(u32::MAX, u32::MAX).is_empty());
+}
+
+#[ink::test]
+fn the_book_grows_past_a_storage_cell() {
+    // About 35 KiB of entries: more than a `Vec` field could ever load.
+    let mut book = VecPitfalls::new();
+    for _ in 0..250 {
+        book.sign("x".repeat(MAX_MESSAGE_LEN)).unwrap();
+    }
+    assert_eq!(book.len(), 250);
+    assert_eq!(book.entry(249).unwrap().message.len(), MAX_MESSAGE_LEN);
+}
@@ -0,0 +1,173 @@
+// pallets/pallet-caller/src/lib.rs
+//
+// A FRAME pallet that calls into a deployed ink! flipper contract.
+// Where pallet-flipper re-implements the contract's logic in the runtime, this pallet
+// keeps the logic in the contract and drives it from the runtime through
+// `pallet_contracts::Pallet::bare_call`, the same entry point the contracts RPC uses.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use frame_support::{pallet_prelude::DispatchError, weights::Weight};
+use sp_std::vec::Vec;
+
+/// Selector of the flipper's `flip` message.
+pub const FLIP_SELECTOR: [u8; 4] = [0x63, 0x3a, 0xa5, 0x51];
+
+/// Selector of the flipper's `get` message.
+pub const GET_SELECTOR: [u8; 4] = [0x2f, 0x86, 0x5b, 0xd9];
+
+/// Calls a deployed contract and returns its raw output.
+///
+/// The pallet talks to contracts through this trait rather than to `pallet-contracts`
+/// directly, so the mock runtime can stand in a fake contract without uploading Wasm.
+pub trait ContractCaller<AccountId> {
+    /// Calls `dest` as `origin` with the SCALE-encoded `data`, spending at most
+    /// `gas_limit`. Returns an error if the call traps or the contract reverts.
+    fn call(
+        origin: AccountId,
+        dest: AccountId,
+        gas_limit: Weight,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, DispatchError>;
+}
+
+impl<T: pallet_contracts::Config> ContractCaller<T::AccountId> for pallet_contracts::Pallet<T> {
+    fn call(
+        origin: T::AccountId,
+        dest: T::AccountId,
+        gas_limit: Weight,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, DispatchError> {
+        let outcome = pallet_contracts::Pallet::<T>::bare_call(
+            origin,
+            dest,
+            Default::default(),
+            gas_limit,
+            None,
+            data,
+            pallet_contracts::DebugInfo::Skip,
+            pallet_contracts::CollectEvents::Skip,
+            pallet_contracts::Determinism::Enforced,
+        );
+        let value = outcome.result?;
+        if value.did_revert() {
+            return Err(DispatchError::Other("contract reverted"));
+        }
+        Ok(value.data)
+    }
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use codec::Decode;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// How calls reach the contract; `pallet_contracts::Pallet<Runtime>` in a real runtime.
+        type Contracts: ContractCaller<Self::AccountId>;
+
+        /// Gas limit passed to every contract call.
+        #[pallet::constant]
+        type CallGasLimit: Get<Weight>;
+    }
+
+    /// The flipper contract this pallet drives.
+    #[pallet::storage]
+    #[pallet::getter(fn target)]
+    pub type Target<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+    /// The contract value as last read by `sync_value`.
+    #[pallet::storage]
+    #[pallet::getter(fn observed_value)]
+    pub type ObservedValue<T> = StorageValue<_, bool, OptionQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// The target contract was changed.
+        TargetSet { contract: T::AccountId },
+        /// `who` flipped the target contract through the runtime.
+        ContractFlipped { who: T::AccountId },
+        /// The target contract's value was read into runtime storage.
+        ValueSynced { value: bool },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// No target contract has been configured.
+        NoTarget,
+        /// The contract call trapped or reverted.
+        ContractCallFailed,
+        /// The contract returned bytes that are not a `get` result.
+        UnexpectedOutput,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Sets the flipper contract this pallet calls into.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_target(origin: OriginFor<T>, contract: T::AccountId) -> DispatchResult {
+            ensure_root(origin)?;
+            Target::<T>::put(contract.clone());
+            Self::deposit_event(Event::TargetSet { contract });
+            Ok(())
+        }
+
+        /// Calls the contract's `flip` message with the signer as the contract's caller.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::CallGasLimit::get().saturating_add(T::DbWeight::get().reads(1)))]
+        pub fn flip(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::call_target(who.clone(), FLIP_SELECTOR.to_vec())?;
+            Self::deposit_event(Event::ContractFlipped { who });
+            Ok(())
+        }
+
+        /// Calls the contract's `get` message and stores the answer in `ObservedValue`.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::CallGasLimit::get().saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+        pub fn sync_value(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let output = Self::call_target(who, GET_SELECTOR.to_vec())?;
+            let value = Self::decode_message_result(&output)?;
+            ObservedValue::<T>::put(value);
+            Self::deposit_event(Event::ValueSynced { value });
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        fn call_target(origin: T::AccountId, data: Vec<u8>) -> Result<Vec<u8>, DispatchError> {
+            let target = Target::<T>::get().ok_or(Error::<T>::NoTarget)?;
+            T::Contracts::call(origin, target, T::CallGasLimit::get(), data)
+                .map_err(|_| Error::<T>::ContractCallFailed.into())
+        }
+
+        /// Decodes a message return value. ink! wraps every message result in
+        /// `Result<T, LangError>`, so `get` answers with `Ok(bool)`.
+        fn decode_message_result(mut output: &[u8]) -> Result<bool, DispatchError> {
+            match core::result::Result::<bool, u8>::decode(&mut output) {
+                Ok(Ok(value)) if output.is_empty() => Ok(value),
+                _ => Err(Error::<T>::UnexpectedOutput.into()),
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+// pallets/pallet-caller/src/mock.rs
+//
+// A minimal runtime with a fake flipper standing in for `pallet-contracts`,
+// used by the unit tests in `tests.rs`.
+
+use crate::{self as pallet_caller, ContractCaller, FLIP_SELECTOR, GET_SELECTOR};
+use codec::Encode;
+use frame_support::{derive_impl, pallet_prelude::DispatchError, parameter_types, weights::Weight};
+use sp_runtime::BuildStorage;
+use std::cell::RefCell;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Caller: pallet_caller,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+}
+
+/// Address the fake flipper is "deployed" at.
+pub const FLIPPER: u64 = 100;
+
+thread_local! {
+    static FLIPPER_VALUE: RefCell<bool> = const { RefCell::new(false) };
+    static CALLERS: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Behaves like the flipper contract at `FLIPPER`; any other address has no code.
+pub struct MockContracts;
+
+impl MockContracts {
+    /// Returns the fake contract's value.
+    pub fn value() -> bool {
+        FLIPPER_VALUE.with(|v| *v.borrow())
+    }
+
+    /// Returns every origin that called the fake contract, in order.
+    pub fn callers() -> Vec<u64> {
+        CALLERS.with(|c| c.borrow().clone())
+    }
+}
+
+impl ContractCaller<u64> for MockContracts {
+    fn call(
+        origin: u64,
+        dest: u64,
+        _gas_limit: Weight,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, DispatchError> {
+        if dest != FLIPPER {
+            return Err(DispatchError::Other("no code at address"));
+        }
+        CALLERS.with(|c| c.borrow_mut().push(origin));
+        match data.as_slice() {
+            s if s == FLIP_SELECTOR => {
+                FLIPPER_VALUE.with(|v| {
+                    let flipped = !*v.borrow();
+                    *v.borrow_mut() = flipped;
+                });
+                Ok(Ok::<(), u8>(()).encode())
+            }
+            s if s == GET_SELECTOR => Ok(Ok::<bool, u8>(Self::value()).encode()),
+            _ => Err(DispatchError::Other("unknown selector")),
+        }
+    }
+}
+
+parameter_types! {
+    pub const CallGasLimit: Weight = Weight::from_parts(1_000_000_000, 64 * 1024);
+}
+
+impl pallet_caller::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Contracts = MockContracts;
+    type CallGasLimit = CallGasLimit;
+}
+
+/// Builds test externalities with a reset fake flipper.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    FLIPPER_VALUE.with(|v| *v.borrow_mut() = false);
+    CALLERS.with(|c| c.borrow_mut().clear());
+
+    let storage = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
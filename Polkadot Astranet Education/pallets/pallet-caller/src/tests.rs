@@ -0,0 +1,61 @@
+// pallets/pallet-caller/src/tests.rs
+
+use crate::{mock::*, Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+#[test]
+fn set_target_requires_root() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Caller::set_target(RuntimeOrigin::signed(1), FLIPPER),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Caller::set_target(RuntimeOrigin::root(), FLIPPER));
+        assert_eq!(Caller::target(), Some(FLIPPER));
+        System::assert_last_event(Event::TargetSet { contract: FLIPPER }.into());
+    });
+}
+
+#[test]
+fn flip_without_target_fails() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(Caller::flip(RuntimeOrigin::signed(1)), Error::<Test>::NoTarget);
+    });
+}
+
+#[test]
+fn flip_calls_contract_as_signer() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Caller::set_target(RuntimeOrigin::root(), FLIPPER));
+        assert_ok!(Caller::flip(RuntimeOrigin::signed(7)));
+        assert!(MockContracts::value());
+        assert_eq!(MockContracts::callers(), vec![7]);
+        System::assert_last_event(Event::ContractFlipped { who: 7 }.into());
+    });
+}
+
+#[test]
+fn sync_value_reads_contract_state() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Caller::set_target(RuntimeOrigin::root(), FLIPPER));
+        assert_ok!(Caller::sync_value(RuntimeOrigin::signed(1)));
+        assert_eq!(Caller::observed_value(), Some(false));
+
+        assert_ok!(Caller::flip(RuntimeOrigin::signed(1)));
+        assert_ok!(Caller::sync_value(RuntimeOrigin::signed(1)));
+        assert_eq!(Caller::observed_value(), Some(true));
+        System::assert_last_event(Event::ValueSynced { value: true }.into());
+    });
+}
+
+#[test]
+fn failing_contract_call_is_reported() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Caller::set_target(RuntimeOrigin::root(), 42));
+        assert_noop!(
+            Caller::flip(RuntimeOrigin::signed(1)),
+            Error::<Test>::ContractCallFailed
+        );
+    });
+}
@@ -0,0 +1,82 @@
+// pallets/pallet-flipper/src/lib.rs
+//
+// The flipper example rewritten as a FRAME runtime pallet.
+// Compare with examples/demo-contracts/flipper.rs: the logic is identical, but here
+// the boolean lives in runtime storage shared by the whole chain instead of in one
+// contract instance, and calls are dispatched as extrinsics rather than messages.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Configuration trait of this pallet.
+    ///
+    /// A contract gets its environment (caller, events) from `pallet-contracts`;
+    /// a pallet declares what it needs from the runtime here instead.
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+    }
+
+    /// The single boolean value stored by the pallet.
+    ///
+    /// This plays the role of the contract's `#[ink(storage)]` struct.
+    #[pallet::storage]
+    #[pallet::getter(fn value)]
+    pub type Value<T> = StorageValue<_, bool, ValueQuery>;
+
+    /// Genesis configuration, the pallet's counterpart of the contract constructor.
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Value stored when the chain starts.
+        pub init_value: bool,
+        #[serde(skip)]
+        pub _marker: PhantomData<T>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            Value::<T>::put(self.init_value);
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// The value was flipped by `who`.
+        Flipped { who: T::AccountId, new_value: bool },
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Flips the stored value from `true` to `false` and vice versa.
+        ///
+        /// Unlike a contract message, every extrinsic must declare its weight up front.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+        pub fn flip(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let new_value = !Value::<T>::get();
+            Value::<T>::put(new_value);
+            Self::deposit_event(Event::Flipped { who, new_value });
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// pallets/pallet-flipper/src/mock.rs
+//
+// A minimal runtime containing only `frame_system` and the flipper pallet,
+// used by the unit tests in `tests.rs`.
+
+use crate as pallet_flipper;
+use frame_support::derive_impl;
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Flipper: pallet_flipper,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+}
+
+impl pallet_flipper::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+}
+
+/// Builds test externalities with the flipper initialised to `init_value`.
+pub fn new_test_ext(init_value: bool) -> sp_io::TestExternalities {
+    let storage = RuntimeGenesisConfig {
+        system: Default::default(),
+        flipper: pallet_flipper::GenesisConfig {
+            init_value,
+            ..Default::default()
+        },
+    }
+    .build_storage()
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(storage);
+    // Events are not recorded in the genesis block.
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
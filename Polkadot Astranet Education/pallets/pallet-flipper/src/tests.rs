@@ -0,0 +1,48 @@
+// pallets/pallet-flipper/src/tests.rs
+//
+// The same scenarios as the flipper contract's unit tests, run against a mock runtime.
+
+use crate::{mock::*, Event};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+/// We test if the default genesis does its job.
+#[test]
+fn default_works() {
+    new_test_ext(false).execute_with(|| {
+        assert!(!Flipper::value());
+    });
+}
+
+/// We test a simple use case of our pallet.
+#[test]
+fn it_works() {
+    new_test_ext(false).execute_with(|| {
+        assert_ok!(Flipper::flip(RuntimeOrigin::signed(1)));
+        assert!(Flipper::value());
+        assert_ok!(Flipper::flip(RuntimeOrigin::signed(1)));
+        assert!(!Flipper::value());
+    });
+}
+
+#[test]
+fn genesis_value_is_respected() {
+    new_test_ext(true).execute_with(|| {
+        assert!(Flipper::value());
+    });
+}
+
+#[test]
+fn flip_emits_event() {
+    new_test_ext(false).execute_with(|| {
+        assert_ok!(Flipper::flip(RuntimeOrigin::signed(7)));
+        System::assert_last_event(Event::Flipped { who: 7, new_value: true }.into());
+    });
+}
+
+#[test]
+fn flip_requires_signed_origin() {
+    new_test_ext(false).execute_with(|| {
+        assert_noop!(Flipper::flip(RuntimeOrigin::none()), DispatchError::BadOrigin);
+    });
+}
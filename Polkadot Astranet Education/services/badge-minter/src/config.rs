@@ -0,0 +1,163 @@
+// services/badge-minter/src/config.rs
+//
+// Which badge each milestone earns, read from a TOML file:
+//
+//     # The progress contract to watch and the badge contract to award from.
+//     progress = "5F..."
+//     badge = "5G..."
+//
+//     # Completing module 2 of course 0 earns badge 1002.
+//     [[milestone]]
+//     course = 0
+//     module = 2
+//     badge = 1002
+//
+//     # Completing all of course 0 earns badge 1000.
+//     [[milestone]]
+//     course = 0
+//     badge = 1000
+//
+// Each badge is an event of the badge contract, created beforehand by the account
+// the minter signs as, so that it is the event's organizer and may award it.
+
+use repl::value::parse_account;
+use serde::Deserialize;
+use std::{fmt, fs, path::Path};
+
+/// The id of a course of the progress contract.
+pub type CourseId = u32;
+
+/// The code of a badge contract event, which is also the id of its badge.
+pub type EventCode = u32;
+
+/// A completion that earns a badge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Milestone {
+    pub course: CourseId,
+    /// The module to complete, or `None` for the whole course.
+    #[serde(default)]
+    pub module: Option<u8>,
+    pub badge: EventCode,
+}
+
+/// The contracts and milestones the minter works with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub progress: [u8; 32],
+    pub badge: [u8; 32],
+    pub milestones: Vec<Milestone>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    progress: String,
+    badge: String,
+    #[serde(default, rename = "milestone")]
+    milestones: Vec<Milestone>,
+}
+
+impl Config {
+    /// Reads a configuration in the format above.
+    pub fn parse(toml: &str) -> Result<Self, String> {
+        let file: ConfigFile = toml::from_str(toml).map_err(|err| err.message().to_owned())?;
+        let config = Config {
+            progress: parse_account(&file.progress).map_err(|err| format!("progress: {}", err))?,
+            badge: parse_account(&file.badge).map_err(|err| format!("badge: {}", err))?,
+            milestones: file.milestones,
+        };
+        for (i, milestone) in config.milestones.iter().enumerate() {
+            let twice = config.milestones[..i]
+                .iter()
+                .any(|other| (other.course, other.module) == (milestone.course, milestone.module));
+            if twice {
+                return Err(format!("{} is listed twice", milestone));
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let toml =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        Self::parse(&toml).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// The badge earned by completing `module` of `course`, or the whole course if
+    /// `module` is `None`.
+    pub fn badge_for(&self, course: CourseId, module: Option<u8>) -> Option<EventCode> {
+        self.milestones
+            .iter()
+            .find(|milestone| milestone.course == course && milestone.module == module)
+            .map(|milestone| milestone.badge)
+    }
+}
+
+impl fmt::Display for Milestone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.module {
+            Some(module) => write!(f, "module {} of course {}", module, self.course),
+            None => write!(f, "course {}", self.course),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESSES: &str = r#"
+        progress = "0x0101010101010101010101010101010101010101010101010101010101010101"
+        badge = "0x0202020202020202020202020202020202020202020202020202020202020202"
+    "#;
+
+    #[test]
+    fn milestones_pick_their_badge() {
+        let config = Config::parse(&format!(
+            "{}{}",
+            ADDRESSES,
+            r#"
+            [[milestone]]
+            course = 0
+            module = 2
+            badge = 1002
+
+            [[milestone]]
+            course = 0
+            badge = 1000
+            "#
+        ))
+        .unwrap();
+        assert_eq!(config.progress, [1; 32]);
+        assert_eq!(config.badge, [2; 32]);
+        assert_eq!(config.badge_for(0, Some(2)), Some(1002));
+        assert_eq!(config.badge_for(0, None), Some(1000));
+        assert_eq!(config.badge_for(0, Some(1)), None);
+        assert_eq!(config.badge_for(1, None), None);
+    }
+
+    #[test]
+    fn a_milestone_earns_one_badge() {
+        let err = Config::parse(&format!(
+            "{}{}",
+            ADDRESSES,
+            r#"
+            [[milestone]]
+            course = 3
+            badge = 1
+            [[milestone]]
+            course = 3
+            badge = 2
+            "#
+        ))
+        .unwrap_err();
+        assert_eq!(err, "course 3 is listed twice");
+    }
+
+    #[test]
+    fn addresses_must_parse() {
+        let err = Config::parse("progress = \"nobody\"\nbadge = \"0x02\"").unwrap_err();
+        assert!(err.starts_with("progress: "), "{}", err);
+    }
+}
@@ -0,0 +1,126 @@
+// services/badge-minter/src/ledger.rs
+//
+// What the minter has already processed, kept in a JSON file so a restart picks up
+// where it stopped:
+//
+//     { "next_block": 1042, "processed": [{ "block": 1041, "index": 3 }] }
+//
+// Blocks are processed whole and in order, so everything before `next_block` is done
+// and only the events of the block in progress need listing. An event is listed once
+// its badge is awarded, or found to be held already, and the file is rewritten after
+// every event. A crash between an award and the rewrite leaves the award unlisted;
+// on restart the badge contract refuses it as `AlreadyClaimed`, which the minter
+// takes as done, so no badge is minted twice.
+
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, fs, path::Path};
+
+/// File name of the ledger, by default in the working directory.
+pub const LEDGER_FILE: &str = "badge-minter.json";
+
+/// An event of the chain, by block number and its index among the block's events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct EventId {
+    pub block: u32,
+    pub index: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ledger {
+    /// The first block not processed in full.
+    pub next_block: u32,
+    /// The events of `next_block` already processed.
+    #[serde(default)]
+    pub processed: BTreeSet<EventId>,
+}
+
+impl Ledger {
+    /// A ledger starting at block `next_block`.
+    pub fn starting_at(next_block: u32) -> Self {
+        Ledger {
+            next_block,
+            processed: BTreeSet::new(),
+        }
+    }
+
+    /// Reads the ledger at `path`, or `None` if there is none yet.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// Writes the ledger to a temporary file and renames it over `path`, so a crash
+    /// leaves either the old ledger or the new one.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|err| err.to_string())?;
+        let temp = path.with_extension("json.tmp");
+        fs::write(&temp, json + "\n").map_err(|err| format!("{}: {}", temp.display(), err))?;
+        fs::rename(&temp, path).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn is_processed(&self, event: EventId) -> bool {
+        event.block < self.next_block || self.processed.contains(&event)
+    }
+
+    pub fn mark_processed(&mut self, event: EventId) {
+        if !self.is_processed(event) {
+            self.processed.insert(event);
+        }
+    }
+
+    /// Records that every event of `block` is processed.
+    pub fn finish_block(&mut self, block: u32) {
+        self.next_block = self.next_block.max(block + 1);
+        let next_block = self.next_block;
+        self.processed.retain(|event| event.block >= next_block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(block: u32, index: u32) -> EventId {
+        EventId { block, index }
+    }
+
+    #[test]
+    fn finished_blocks_need_no_event_list() {
+        let mut ledger = Ledger::starting_at(10);
+        assert!(ledger.is_processed(event(9, 0)));
+        assert!(!ledger.is_processed(event(10, 0)));
+
+        ledger.mark_processed(event(10, 2));
+        assert!(ledger.is_processed(event(10, 2)));
+        assert!(!ledger.is_processed(event(10, 3)));
+
+        ledger.finish_block(10);
+        assert_eq!(ledger, Ledger::starting_at(11));
+        assert!(ledger.is_processed(event(10, 3)));
+
+        // Finishing an old block again does not move the ledger back.
+        ledger.finish_block(4);
+        assert_eq!(ledger.next_block, 11);
+        ledger.mark_processed(event(7, 0));
+        assert!(ledger.processed.is_empty());
+    }
+
+    #[test]
+    fn the_ledger_round_trips_through_its_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(LEDGER_FILE);
+        assert_eq!(Ledger::load(&path).unwrap(), None);
+
+        let mut ledger = Ledger::starting_at(1041);
+        ledger.mark_processed(event(1041, 3));
+        ledger.save(&path).unwrap();
+        assert_eq!(Ledger::load(&path).unwrap(), Some(ledger));
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+}
@@ -0,0 +1,228 @@
+// services/badge-minter/src/lib.rs
+//
+// The badge minter turns milestones recorded in the progress contract
+// (`examples/demo-contracts/progress.rs`) into soulbound badges of the badge contract
+// (`examples/demo-contracts/badge.rs`). It follows the chain's finalized blocks, picks
+// out the progress contract's `ModuleCompleted` and `CourseCompleted` events, and for
+// each milestone the configuration names a badge for (see `config`), calls the badge
+// contract's `award`, signed as the badge event's organizer.
+//
+// Only finalized blocks are read, so an award never follows a milestone that a reorg
+// takes back. Each award is dry-run first, and a learner already holding the badge is
+// skipped rather than charged for a failing transaction. Processed events are listed
+// in a ledger file (see `ledger`), so restarting the minter neither misses a block
+// nor awards a badge twice.
+//
+// This module decides what to award from the events of a block; `minter` reads the
+// blocks and submits the awards.
+
+pub mod config;
+pub mod ledger;
+pub mod minter;
+
+pub use config::{Config, CourseId, EventCode, Milestone};
+pub use ledger::{EventId, Ledger};
+
+use astranet_errors::badge::Error as BadgeError;
+use astranet_events::progress::{CourseCompleted, Event, ModuleCompleted};
+use deployer::CallDryRun;
+use scale::{Decode, Encode};
+
+/// A `Contracts::ContractEmitted` event of a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Emitted {
+    pub id: EventId,
+    pub contract: [u8; 32],
+    pub data: Vec<u8>,
+}
+
+/// A badge earned by a learner, and the event it was earned by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Award {
+    pub event: EventId,
+    pub milestone: Milestone,
+    pub learner: [u8; 32],
+}
+
+/// The badges earned by the progress contract's events among `emitted`, in order.
+/// Fails if one of its events does not decode, which means the configured address is
+/// not a progress contract or its events changed.
+pub fn awards(config: &Config, emitted: &[Emitted]) -> Result<Vec<Award>, String> {
+    let mut awards = Vec::new();
+    for emitted in emitted {
+        if emitted.contract != config.progress {
+            continue;
+        }
+        let event: Event = astranet_events::decode(&emitted.data).map_err(|err| {
+            format!(
+                "cannot decode event {} of block {} as a progress event: {}",
+                emitted.id.index, emitted.id.block, err
+            )
+        })?;
+        let (course, module, learner) = match event {
+            Event::ModuleCompleted(ModuleCompleted {
+                course_id,
+                learner,
+                module,
+            }) => (course_id, Some(module), learner),
+            Event::CourseCompleted(CourseCompleted { course_id, learner }) => {
+                (course_id, None, learner)
+            }
+            _ => continue,
+        };
+        if let Some(badge) = config.badge_for(course, module) {
+            awards.push(Award {
+                event: emitted.id,
+                milestone: Milestone {
+                    course,
+                    module,
+                    badge,
+                },
+                learner: *learner.as_ref(),
+            });
+        }
+    }
+    Ok(awards)
+}
+
+/// Call data of the badge contract's `award(code, attendee)`.
+pub fn award_call_data(code: EventCode, learner: [u8; 32]) -> Vec<u8> {
+    let mut data = selector::compute("award").to_vec();
+    (code, learner).encode_to(&mut data);
+    data
+}
+
+/// Reads what a dry run of `award` says: `Ok` if the badge would be minted, or the
+/// badge contract's error, which it reverts with.
+pub fn award_outcome(dry_run: &CallDryRun) -> Result<Result<(), BadgeError>, String> {
+    let returned = match &dry_run.result {
+        Ok(returned) => returned,
+        Err(err) => return Err(format!("`award` failed with {:?}", err)),
+    };
+    Result::<(), BadgeError>::decode(&mut returned.data.as_slice())
+        .map_err(|err| format!("cannot decode what `award` returned: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use astranet_events::{progress::LessonCompleted, AccountId};
+    use deployer::{ExecReturnValue, StorageDeposit, Weight};
+
+    const PROGRESS: [u8; 32] = [1; 32];
+    const LEARNER: [u8; 32] = [0xd4; 32];
+
+    fn config() -> Config {
+        Config {
+            progress: PROGRESS,
+            badge: [2; 32],
+            milestones: vec![
+                Milestone {
+                    course: 0,
+                    module: Some(1),
+                    badge: 1001,
+                },
+                Milestone {
+                    course: 0,
+                    module: None,
+                    badge: 1000,
+                },
+            ],
+        }
+    }
+
+    fn emitted(index: u32, contract: [u8; 32], event: Event) -> Emitted {
+        Emitted {
+            id: EventId { block: 7, index },
+            contract,
+            data: event.encode(),
+        }
+    }
+
+    fn module_completed(course_id: CourseId, module: u8) -> Event {
+        Event::ModuleCompleted(ModuleCompleted {
+            course_id,
+            learner: AccountId::from(LEARNER),
+            module,
+        })
+    }
+
+    #[test]
+    fn configured_milestones_earn_badges() {
+        let emitted = [
+            emitted(
+                0,
+                PROGRESS,
+                Event::LessonCompleted(LessonCompleted {
+                    course_id: 0,
+                    learner: AccountId::from(LEARNER),
+                    module: 1,
+                    lesson: 3,
+                }),
+            ),
+            emitted(1, PROGRESS, module_completed(0, 1)),
+            emitted(2, PROGRESS, module_completed(0, 2)),
+            emitted(3, PROGRESS, module_completed(5, 1)),
+            emitted(
+                4,
+                PROGRESS,
+                Event::CourseCompleted(CourseCompleted {
+                    course_id: 0,
+                    learner: AccountId::from(LEARNER),
+                }),
+            ),
+        ];
+        let awards = awards(&config(), &emitted).unwrap();
+        let earned: Vec<_> = awards
+            .iter()
+            .map(|award| (award.event.index, award.milestone.badge, award.learner))
+            .collect();
+        assert_eq!(earned, [(1, 1001, LEARNER), (4, 1000, LEARNER)]);
+    }
+
+    #[test]
+    fn other_contracts_are_ignored() {
+        let mut other = emitted(0, [9; 32], module_completed(0, 1));
+        assert_eq!(awards(&config(), &[other.clone()]).unwrap(), []);
+
+        // The progress contract's events must decode.
+        other.contract = PROGRESS;
+        other.data.push(0);
+        assert!(awards(&config(), &[other])
+            .unwrap_err()
+            .starts_with("cannot decode event 0 of block 7"));
+    }
+
+    #[test]
+    fn award_call_data_starts_with_the_selector() {
+        let data = award_call_data(1001, LEARNER);
+        assert_eq!(data[..4], selector::compute("award"));
+        assert_eq!(data[4..8], 1001u32.to_le_bytes());
+        assert_eq!(data[8..], LEARNER);
+    }
+
+    #[test]
+    fn award_outcomes_decode_reverted_errors() {
+        let dry_run = |flags, data| CallDryRun {
+            gas_consumed: Weight::default(),
+            gas_required: Weight::default(),
+            storage_deposit: StorageDeposit::Charge(0),
+            debug_message: String::new(),
+            result: Ok(ExecReturnValue { flags, data }),
+            events: None,
+        };
+        assert_eq!(
+            award_outcome(&dry_run(0, Ok::<(), BadgeError>(()).encode())).unwrap(),
+            Ok(())
+        );
+        assert_eq!(
+            award_outcome(&dry_run(
+                1,
+                Err::<(), _>(BadgeError::AlreadyClaimed).encode()
+            ))
+            .unwrap(),
+            Err(BadgeError::AlreadyClaimed)
+        );
+        assert!(award_outcome(&dry_run(1, Vec::new())).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+// services/badge-minter/src/main.rs
+//
+// Usage:
+//   badge-minter --config badge-minter.toml --key organizer
+//   badge-minter --config badge-minter.toml --suri //Alice --from-block 1
+//   badge-minter --config badge-minter.toml --network shibuya --ledger /var/lib/badges.json
+//
+// Awards the badges named in `--config` (see `config` in the library) as learners
+// complete milestones, signing as the badge events' organizer: `--key` names a stored
+// key, `--suri` is a secret URI, and without either the network profile's signer is
+// used, as for the deployer.
+//
+// The first run starts at the latest finalized block, or at `--from-block`; later runs
+// resume from the ledger. The minter catches up on the blocks it missed, then follows
+// new finalized blocks until the connection fails, when it exits with 1; run it under
+// a supervisor that restarts it.
+
+use badge_minter::{
+    ledger::LEDGER_FILE,
+    minter::{Minter, Outcome},
+    Award, Config,
+};
+use clap::Parser;
+use deployer::{Network, Networks, Signer};
+use repl::keystore::{KeyPair, Keystore, Scheme};
+use std::{fs, path::PathBuf, process::ExitCode};
+
+#[derive(Parser)]
+#[command(about = "Award badges for the milestones learners complete in the progress contract")]
+struct Cli {
+    /// The contracts and the badge of each milestone.
+    #[arg(long)]
+    config: PathBuf,
+    /// File listing the processed events.
+    #[arg(long, default_value = LEDGER_FILE)]
+    ledger: PathBuf,
+    /// Block to start at when there is no ledger yet.
+    #[arg(long)]
+    from_block: Option<u32>,
+    /// Network profile name, or the WebSocket URL of a node.
+    #[arg(long, default_value = "local")]
+    network: String,
+    /// File of network profiles to add to the built-in ones.
+    #[arg(long)]
+    networks: Option<PathBuf>,
+    /// WebSocket URL of the node, instead of the profile's.
+    #[arg(long)]
+    url: Option<String>,
+    /// Secret URI of the organizer's account, instead of the profile's signer.
+    #[arg(long, conflicts_with = "key")]
+    suri: Option<String>,
+    /// Name of the organizer's stored key, instead of the profile's signer.
+    #[arg(long)]
+    key: Option<String>,
+    /// Keystore directory, `~/.astranet/keys` by default.
+    #[arg(long)]
+    keystore: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
+    let config = Config::load(&cli.config)?;
+    let network = network(&cli)?;
+    let signer = signer(&cli, &network)?;
+    let mut minter = Minter::connect(
+        &network.url,
+        signer,
+        config,
+        cli.ledger.clone(),
+        cli.from_block,
+    )
+    .await?;
+    println!(
+        "connected to {} ({}), starting at block {}",
+        cli.network,
+        network.url,
+        minter.ledger().next_block
+    );
+
+    let show = |award: &Award, outcome: Outcome| {
+        let verb = match outcome {
+            Outcome::Awarded => "awarded",
+            Outcome::AlreadyHeld => "already holds",
+        };
+        println!(
+            "block {}: {} {} badge {} for {}",
+            award.event.block,
+            network.address(award.learner),
+            verb,
+            award.milestone.badge,
+            award.milestone
+        );
+    };
+    for (award, outcome) in minter.catch_up().await? {
+        show(&award, outcome);
+    }
+    println!("caught up to block {}", minter.ledger().next_block - 1);
+    minter.follow(show).await
+}
+
+/// Resolves `--key`, `--suri` and the profile's signer into the key to sign with.
+fn signer(cli: &Cli, network: &Network) -> Result<KeyPair, String> {
+    let suri = match (&cli.key, &cli.suri, &network.signer) {
+        (Some(name), _, _) | (None, None, Signer::Key(name)) => {
+            let keystore = match &cli.keystore {
+                Some(dir) => Keystore::new(dir),
+                None => Keystore::new(Keystore::default_dir()?),
+            };
+            return keystore.unlock(name);
+        }
+        (None, Some(suri), _) => suri.clone(),
+        (None, None, _) => network.suri()?,
+    };
+    KeyPair::from_suri(Scheme::Sr25519, &suri).map_err(|err| format!("signer: {}", err))
+}
+
+/// Resolves `--network`, `--networks` and `--url` into the network to use.
+fn network(cli: &Cli) -> Result<Network, String> {
+    let mut networks = Networks::builtin();
+    if let Some(path) = &cli.networks {
+        let toml =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        networks
+            .extend(Networks::parse(&toml).map_err(|err| format!("{}: {}", path.display(), err))?);
+    }
+    let mut network = networks.get(&cli.network)?;
+    if let Some(url) = &cli.url {
+        network.url = url.clone();
+    }
+    Ok(network)
+}
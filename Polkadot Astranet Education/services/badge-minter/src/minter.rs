@@ -0,0 +1,216 @@
+// services/badge-minter/src/minter.rs
+//
+// Reading finalized blocks and submitting awards. A `Minter` owns the ledger and
+// rewrites its file after every award and every block, so it can be stopped at any
+// point and started again.
+
+use crate::{award_call_data, award_outcome, awards, Award, Config, Emitted, EventId, Ledger};
+use astranet_errors::badge::Error as BadgeError;
+use deployer::{
+    dry_run,
+    submit::{call_tx, submit},
+    CallRequest,
+};
+use repl::keystore::KeyPair;
+use scale::Decode;
+use std::path::PathBuf;
+use subxt::{
+    backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+    OnlineClient, SubstrateConfig,
+};
+
+type Client = OnlineClient<SubstrateConfig>;
+
+/// What became of an award.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The badge was minted.
+    Awarded,
+    /// The learner already held the badge.
+    AlreadyHeld,
+}
+
+pub struct Minter {
+    rpc: RpcClient,
+    client: Client,
+    signer: KeyPair,
+    config: Config,
+    ledger: Ledger,
+    ledger_path: PathBuf,
+}
+
+impl Minter {
+    /// Connects to the node at `url`. Without a ledger, the minter starts at the
+    /// latest finalized block, or at `from_block` if given.
+    pub async fn connect(
+        url: &str,
+        signer: KeyPair,
+        config: Config,
+        ledger_path: PathBuf,
+        from_block: Option<u32>,
+    ) -> Result<Self, String> {
+        let rpc = RpcClient::from_url(url)
+            .await
+            .map_err(|err| format!("cannot connect to {}: {}", url, err))?;
+        let client = Client::from_rpc_client(rpc.clone())
+            .await
+            .map_err(|err| err.to_string())?;
+        let mut minter = Minter {
+            rpc,
+            client,
+            signer,
+            config,
+            ledger: Ledger::default(),
+            ledger_path,
+        };
+        minter.ledger = match Ledger::load(&minter.ledger_path)? {
+            Some(ledger) => ledger,
+            None => match from_block {
+                Some(block) => Ledger::starting_at(block),
+                None => Ledger::starting_at(minter.finalized_block().await?),
+            },
+        };
+        Ok(minter)
+    }
+
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    /// The number of the latest finalized block.
+    pub async fn finalized_block(&self) -> Result<u32, String> {
+        let methods = self.methods();
+        let hash = methods
+            .chain_get_finalized_head()
+            .await
+            .map_err(|err| format!("chain_getFinalizedHead failed: {}", err))?;
+        let header = methods
+            .chain_get_header(Some(hash))
+            .await
+            .map_err(|err| format!("chain_getHeader failed: {}", err))?
+            .ok_or("the node does not know its finalized head")?;
+        Ok(header.number)
+    }
+
+    /// Processes every block from the ledger's next block up to the latest finalized
+    /// one, and returns the awards made.
+    pub async fn catch_up(&mut self) -> Result<Vec<(Award, Outcome)>, String> {
+        let finalized = self.finalized_block().await?;
+        let mut done = Vec::new();
+        while self.ledger.next_block <= finalized {
+            done.extend(self.process_block(self.ledger.next_block).await?);
+        }
+        Ok(done)
+    }
+
+    /// Makes the awards earned in block `number` that are not in the ledger yet.
+    pub async fn process_block(&mut self, number: u32) -> Result<Vec<(Award, Outcome)>, String> {
+        let emitted = self.emitted(number).await?;
+        let mut done = Vec::new();
+        for award in awards(&self.config, &emitted)? {
+            if self.ledger.is_processed(award.event) {
+                continue;
+            }
+            let outcome = self.award(&award).await?;
+            self.ledger.mark_processed(award.event);
+            self.ledger.save(&self.ledger_path)?;
+            done.push((award, outcome));
+        }
+        self.ledger.finish_block(number);
+        self.ledger.save(&self.ledger_path)?;
+        Ok(done)
+    }
+
+    /// Waits for new finalized blocks and processes them as they come, until the
+    /// connection fails.
+    pub async fn follow(
+        &mut self,
+        mut on_award: impl FnMut(&Award, Outcome),
+    ) -> Result<(), String> {
+        let mut blocks = self
+            .client
+            .blocks()
+            .subscribe_finalized()
+            .await
+            .map_err(|err| format!("cannot follow finalized blocks: {}", err))?;
+        while let Some(block) = blocks.next().await {
+            let block = block.map_err(|err| format!("lost finalized blocks: {}", err))?;
+            // The subscription can skip blocks; process everything up to this one.
+            while self.ledger.next_block <= block.number() {
+                for (award, outcome) in self.process_block(self.ledger.next_block).await? {
+                    on_award(&award, outcome);
+                }
+            }
+        }
+        Err("the node ended the finalized block subscription".to_owned())
+    }
+
+    /// The `Contracts::ContractEmitted` events of block `number`.
+    async fn emitted(&self, number: u32) -> Result<Vec<Emitted>, String> {
+        let hash = self
+            .methods()
+            .chain_get_block_hash(Some(number.into()))
+            .await
+            .map_err(|err| format!("chain_getBlockHash failed: {}", err))?
+            .ok_or_else(|| format!("the node has no block {}", number))?;
+        let events = self
+            .client
+            .blocks()
+            .at(hash)
+            .await
+            .map_err(|err| format!("block {}: {}", number, err))?
+            .events()
+            .await
+            .map_err(|err| format!("events of block {}: {}", number, err))?;
+        let mut emitted = Vec::new();
+        for event in events.iter() {
+            let event = event.map_err(|err| format!("events of block {}: {}", number, err))?;
+            if event.pallet_name() != "Contracts" || event.variant_name() != "ContractEmitted" {
+                continue;
+            }
+            // `ContractEmitted { contract: AccountId32, data: Vec<u8> }`
+            let (contract, data) = <([u8; 32], Vec<u8>)>::decode(&mut event.field_bytes())
+                .map_err(|err| format!("ContractEmitted in block {}: {}", number, err))?;
+            emitted.push(Emitted {
+                id: EventId {
+                    block: number,
+                    index: event.index(),
+                },
+                contract,
+                data,
+            });
+        }
+        Ok(emitted)
+    }
+
+    /// Dry-runs the award and submits it if the badge contract accepts it. Any
+    /// refusal but `AlreadyClaimed` is an error: the configuration or the badge
+    /// events need fixing before the minter can go on.
+    async fn award(&self, award: &Award) -> Result<Outcome, String> {
+        let request = CallRequest {
+            origin: self.signer.account_id(),
+            dest: self.config.badge,
+            value: 0,
+            gas_limit: None,
+            storage_deposit_limit: None,
+            input_data: award_call_data(award.milestone.badge, award.learner),
+        };
+        let dry_run = dry_run::call(&self.rpc, &request).await?;
+        match award_outcome(&dry_run)? {
+            Ok(()) => {}
+            Err(BadgeError::AlreadyClaimed) => return Ok(Outcome::AlreadyHeld),
+            Err(err) => {
+                return Err(format!(
+                    "the badge contract refuses badge {} for {}: {}",
+                    award.milestone.badge, award.milestone, err
+                ))
+            }
+        }
+        submit(self.rpc.clone(), &self.signer, &call_tx(&request, &dry_run)).await?;
+        Ok(Outcome::Awarded)
+    }
+
+    fn methods(&self) -> LegacyRpcMethods<SubstrateConfig> {
+        LegacyRpcMethods::new(self.rpc.clone())
+    }
+}
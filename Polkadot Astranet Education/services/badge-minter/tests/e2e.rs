@@ -0,0 +1,241 @@
+// services/badge-minter/tests/e2e.rs
+//
+// Runs the minter against a contracts node. These tests need a node and the bundles
+// of the progress and badge contracts, so they are ignored by default:
+//
+//     devnet &
+//     bundle
+//     cargo test -- --ignored
+//
+// The node is at `ws://127.0.0.1:9944` unless `BADGE_MINTER_NODE` says otherwise, and
+// the bundles in `dist/` at the repository root unless `BADGE_MINTER_DIST` does.
+// Every test deploys fresh contracts as //Alice, so the node can be reused.
+
+use badge_minter::{minter::Minter, minter::Outcome, Config, Milestone};
+use deployer::{
+    bundle::{self, Bundle},
+    dry_run,
+    submit::{call_tx, deposit_limit, submit, weight},
+    CallRequest, Code, InstantiateRequest,
+};
+use repl::{
+    keystore::{KeyPair, Scheme},
+    value::parse_account,
+};
+use scale::{Decode, Encode};
+use std::{
+    env, fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use subxt::{backend::rpc::RpcClient, dynamic::Value};
+
+const MODULE_BADGE: u32 = 1001;
+const COURSE_BADGE: u32 = 1000;
+
+fn node_url() -> String {
+    env::var("BADGE_MINTER_NODE").unwrap_or_else(|_| "ws://127.0.0.1:9944".to_owned())
+}
+
+fn alice() -> KeyPair {
+    KeyPair::from_suri(Scheme::Sr25519, "//Alice").unwrap()
+}
+
+/// Uploads and instantiates the `name` bundle with its `new` constructor.
+async fn deploy(rpc: &RpcClient, name: &str) -> [u8; 32] {
+    let dist = env::var("BADGE_MINTER_DIST")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../dist"));
+    let path = dist.join(format!("{}.contract", name));
+    let json = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("{}: {}; build it with `bundle`", path.display(), err));
+    let bundle = Bundle::parse(&json).unwrap();
+    let salt = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+        .encode();
+    let request = InstantiateRequest {
+        origin: alice().account_id(),
+        value: 0,
+        gas_limit: None,
+        storage_deposit_limit: None,
+        code: Code::Upload(bundle.code().unwrap().to_vec()),
+        data: bundle::call_data(bundle.constructor("new").unwrap(), &[]).unwrap(),
+        salt,
+    };
+    let dry_run = dry_run::instantiate(rpc, &request).await.unwrap();
+    let address = dry_run.check().unwrap().account_id;
+    let tx = subxt::dynamic::tx(
+        "Contracts",
+        "instantiate_with_code",
+        vec![
+            Value::u128(0),
+            weight(dry_run.gas_required),
+            deposit_limit(dry_run.storage_deposit.charge()),
+            Value::from_bytes(bundle.code().unwrap()),
+            Value::from_bytes(&request.data),
+            Value::from_bytes(&request.salt),
+        ],
+    );
+    submit(rpc.clone(), &alice(), &tx).await.unwrap();
+    address
+}
+
+/// Calls `message` of `contract` as //Alice and returns what its dry run returned.
+async fn call<R: Decode>(
+    rpc: &RpcClient,
+    contract: [u8; 32],
+    message: &str,
+    args: impl Encode,
+) -> R {
+    let mut input_data = selector::compute(message).to_vec();
+    args.encode_to(&mut input_data);
+    let request = CallRequest {
+        origin: alice().account_id(),
+        dest: contract,
+        value: 0,
+        gas_limit: None,
+        storage_deposit_limit: None,
+        input_data,
+    };
+    let dry_run = dry_run::call(rpc, &request).await.unwrap();
+    let returned = R::decode(&mut dry_run.check().unwrap().data.as_slice()).unwrap();
+    submit(rpc.clone(), &alice(), &call_tx(&request, &dry_run))
+        .await
+        .unwrap();
+    returned
+}
+
+/// Reads `balance_of(owner, code)` of the badge contract.
+async fn badges(rpc: &RpcClient, badge: [u8; 32], owner: [u8; 32], code: u32) -> u128 {
+    let mut input_data = selector::compute("balance_of").to_vec();
+    (owner, code).encode_to(&mut input_data);
+    let request = CallRequest {
+        origin: alice().account_id(),
+        dest: badge,
+        value: 0,
+        gas_limit: None,
+        storage_deposit_limit: None,
+        input_data,
+    };
+    let dry_run = dry_run::call(rpc, &request).await.unwrap();
+    u128::decode(&mut dry_run.check().unwrap().data.as_slice()).unwrap()
+}
+
+/// Catches up until `count` awards are made, waiting for blocks to be finalized.
+async fn awards_made(minter: &mut Minter, count: usize) -> Vec<Outcome> {
+    let mut outcomes = Vec::new();
+    for _ in 0..30 {
+        outcomes.extend(
+            minter
+                .catch_up()
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|(_, outcome)| outcome),
+        );
+        if outcomes.len() >= count {
+            return outcomes;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    panic!("only {} of {} awards were made", outcomes.len(), count);
+}
+
+/// Deploys both contracts with a one-lesson course and its two badges, and returns
+/// the minter's configuration.
+async fn classroom(rpc: &RpcClient) -> Config {
+    let progress = deploy(rpc, "progress").await;
+    let badge = deploy(rpc, "badge").await;
+    let course: u32 = call(rpc, progress, "create_course", ()).await;
+    let module: Result<u8, astranet_errors::progress::Error> =
+        call(rpc, progress, "add_module", (course, 1u8, 0u32)).await;
+    assert_eq!(module, Ok(0));
+    for code in [MODULE_BADGE, COURSE_BADGE] {
+        let created: Result<(), astranet_errors::badge::Error> =
+            call(rpc, badge, "create_event", (code, [0u8; 32])).await;
+        assert_eq!(created, Ok(()));
+    }
+    Config {
+        progress,
+        badge,
+        milestones: vec![
+            Milestone {
+                course,
+                module: Some(0),
+                badge: MODULE_BADGE,
+            },
+            Milestone {
+                course,
+                module: None,
+                badge: COURSE_BADGE,
+            },
+        ],
+    }
+}
+
+async fn complete_course(rpc: &RpcClient, config: &Config, learner: [u8; 32]) {
+    let course = config.milestones[0].course;
+    let completed: Result<(), astranet_errors::progress::Error> = call(
+        rpc,
+        config.progress,
+        "complete_lesson",
+        (course, learner, 0u8, 0u8),
+    )
+    .await;
+    assert_eq!(completed, Ok(()));
+}
+
+#[tokio::test]
+#[ignore = "needs a contracts node and the bundles in dist/"]
+async fn completing_a_course_awards_its_badges_once() {
+    let rpc = RpcClient::from_url(node_url()).await.unwrap();
+    let config = classroom(&rpc).await;
+    let bob = parse_account("bob").unwrap();
+    let temp = tempfile::tempdir().unwrap();
+    let ledger = temp.path().join("ledger.json");
+
+    let mut minter = Minter::connect(&node_url(), alice(), config.clone(), ledger, None)
+        .await
+        .unwrap();
+    complete_course(&rpc, &config, bob).await;
+    assert_eq!(
+        awards_made(&mut minter, 2).await,
+        [Outcome::Awarded, Outcome::Awarded]
+    );
+    assert_eq!(badges(&rpc, config.badge, bob, MODULE_BADGE).await, 1);
+    assert_eq!(badges(&rpc, config.badge, bob, COURSE_BADGE).await, 1);
+
+    // Caught up, the minter finds nothing left to do.
+    assert!(minter.catch_up().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+#[ignore = "needs a contracts node and the bundles in dist/"]
+async fn a_lost_ledger_awards_nothing_twice() {
+    let rpc = RpcClient::from_url(node_url()).await.unwrap();
+    let config = classroom(&rpc).await;
+    let charlie = parse_account("charlie").unwrap();
+    let temp = tempfile::tempdir().unwrap();
+
+    let first = temp.path().join("first.json");
+    let mut minter = Minter::connect(&node_url(), alice(), config.clone(), first, None)
+        .await
+        .unwrap();
+    let start = minter.ledger().next_block;
+    complete_course(&rpc, &config, charlie).await;
+    awards_made(&mut minter, 2).await;
+
+    // A minter without the ledger reads the same blocks again, and the badge
+    // contract's refusals keep it from minting the badges a second time.
+    let second = temp.path().join("second.json");
+    let mut again = Minter::connect(&node_url(), alice(), config.clone(), second, Some(start))
+        .await
+        .unwrap();
+    assert_eq!(
+        awards_made(&mut again, 2).await,
+        [Outcome::AlreadyHeld, Outcome::AlreadyHeld]
+    );
+    assert_eq!(badges(&rpc, config.badge, charlie, MODULE_BADGE).await, 1);
+}
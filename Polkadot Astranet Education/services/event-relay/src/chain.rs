@@ -0,0 +1,141 @@
+// services/event-relay/src/chain.rs
+//
+// Following the node. The relay reads finalized blocks only, so the website never
+// shows an event that a reorg later takes back.
+//
+// Classroom nodes come and go: a laptop sleeps, `devnet` is restarted. When the
+// connection fails, `follow` waits (see `Backoff`) and connects again, then relays the
+// blocks finalized while it was away before new ones, so clients see every event once
+// and in order. A restarted dev node starts a new chain, with a new genesis hash; the
+// relay then starts over from the new chain's latest finalized block.
+
+use crate::{Backoff, Contract, Decoder, Relayed};
+use scale::Decode;
+use subxt::{
+    backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+    utils::H256,
+    OnlineClient, SubstrateConfig,
+};
+
+type Client = OnlineClient<SubstrateConfig>;
+
+/// Relays the events of `contracts` from the node at `url` to `send`, reconnecting
+/// whenever the connection fails. Returns once `send` refuses an event.
+pub async fn follow(
+    url: &str,
+    decoder: &Decoder,
+    contracts: &[Contract],
+    mut backoff: Backoff,
+    mut send: impl FnMut(Relayed) -> bool,
+) {
+    let mut follower = Follower {
+        decoder,
+        contracts,
+        genesis: None,
+        next_block: None,
+    };
+    loop {
+        match follower.run(url, &mut backoff, &mut send).await {
+            Ok(()) => return,
+            Err(err) => {
+                let delay = backoff.next_delay();
+                eprintln!("{}; reconnecting in {}s", err, delay.as_secs());
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+}
+
+struct Follower<'a> {
+    decoder: &'a Decoder,
+    contracts: &'a [Contract],
+    /// The genesis hash of the chain being followed.
+    genesis: Option<H256>,
+    /// The first block of that chain not relayed yet.
+    next_block: Option<u32>,
+}
+
+impl Follower<'_> {
+    /// Relays blocks until the connection fails, or `send` refuses an event.
+    async fn run(
+        &mut self,
+        url: &str,
+        backoff: &mut Backoff,
+        send: &mut impl FnMut(Relayed) -> bool,
+    ) -> Result<(), String> {
+        let rpc = RpcClient::from_url(url)
+            .await
+            .map_err(|err| format!("cannot connect to {}: {}", url, err))?;
+        let client = Client::from_rpc_client(rpc.clone())
+            .await
+            .map_err(|err| format!("{}: {}", url, err))?;
+        let mut blocks = client
+            .blocks()
+            .subscribe_finalized()
+            .await
+            .map_err(|err| format!("cannot follow finalized blocks: {}", err))?;
+        if self.genesis != Some(client.genesis_hash()) {
+            self.genesis = Some(client.genesis_hash());
+            self.next_block = None;
+        }
+        println!("following finalized blocks of {}", url);
+        backoff.reset();
+
+        while let Some(block) = blocks.next().await {
+            let block = block.map_err(|err| format!("lost finalized blocks: {}", err))?;
+            let finalized = block.number();
+            let next_block = self.next_block.unwrap_or(finalized);
+            for number in next_block..=finalized {
+                for relayed in self.relay_block(&client, &rpc, number).await? {
+                    if !send(relayed) {
+                        return Ok(());
+                    }
+                }
+                self.next_block = Some(number + 1);
+            }
+        }
+        Err("the node ended the finalized block subscription".to_owned())
+    }
+
+    /// The events of the watched contracts in block `number`.
+    async fn relay_block(
+        &self,
+        client: &Client,
+        rpc: &RpcClient,
+        number: u32,
+    ) -> Result<Vec<Relayed>, String> {
+        let hash = LegacyRpcMethods::<SubstrateConfig>::new(rpc.clone())
+            .chain_get_block_hash(Some(number.into()))
+            .await
+            .map_err(|err| format!("chain_getBlockHash failed: {}", err))?
+            .ok_or_else(|| format!("the node has no block {}", number))?;
+        let events = client
+            .blocks()
+            .at(hash)
+            .await
+            .map_err(|err| format!("block {}: {}", number, err))?
+            .events()
+            .await
+            .map_err(|err| format!("events of block {}: {}", number, err))?;
+        let mut relayed = Vec::new();
+        for event in events.iter() {
+            let event = event.map_err(|err| format!("events of block {}: {}", number, err))?;
+            if event.pallet_name() != "Contracts" || event.variant_name() != "ContractEmitted" {
+                continue;
+            }
+            // `ContractEmitted { contract: AccountId32, data: Vec<u8> }`
+            let (address, data) = <([u8; 32], Vec<u8>)>::decode(&mut event.field_bytes())
+                .map_err(|err| format!("ContractEmitted in block {}: {}", number, err))?;
+            if let Some(contract) = self.contracts.iter().find(|c| c.address == address) {
+                relayed.push(Relayed::new(
+                    self.decoder,
+                    contract,
+                    number,
+                    event.index(),
+                    &data,
+                ));
+            }
+        }
+        Ok(relayed)
+    }
+}
@@ -0,0 +1,146 @@
+// services/event-relay/src/config.rs
+//
+// The contracts whose events are relayed, read from a TOML file:
+//
+//     [[contract]]
+//     name = "erc20"
+//     address = "5F..."
+//
+//     # A second token, whose events are decoded as erc20's.
+//     [[contract]]
+//     name = "course-token"
+//     events = "erc20"
+//     address = "5G..."
+//
+// `name` labels the contract's events for the website and picks the WebSocket path
+// that carries only them; `events` is the module of `crates/events` that mirrors its
+// events, `name` unless given.
+
+use crate::decode::Decoder;
+use repl::value::parse_account;
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+/// A contract whose events are relayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contract {
+    pub name: String,
+    pub events: String,
+    pub address: [u8; 32],
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ContractEntry {
+    name: String,
+    events: Option<String>,
+    address: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default, rename = "contract")]
+    contracts: Vec<ContractEntry>,
+}
+
+/// Reads the contracts of a configuration in the format above, checking that
+/// `decoder` knows their events.
+pub fn parse(toml: &str, decoder: &Decoder) -> Result<Vec<Contract>, String> {
+    let file: ConfigFile = toml::from_str(toml).map_err(|err| err.message().to_owned())?;
+    let mut contracts: Vec<Contract> = Vec::new();
+    for entry in file.contracts {
+        let events = entry.events.unwrap_or_else(|| entry.name.clone());
+        if !decoder.knows(&events) {
+            return Err(format!(
+                "`{}`: crates/events has no module `{}`",
+                entry.name, events
+            ));
+        }
+        let address =
+            parse_account(&entry.address).map_err(|err| format!("`{}`: {}", entry.name, err))?;
+        if contracts.iter().any(|contract| contract.name == entry.name) {
+            return Err(format!("`{}` is listed twice", entry.name));
+        }
+        if contracts.iter().any(|contract| contract.address == address) {
+            return Err(format!(
+                "`{}` has the address of another contract",
+                entry.name
+            ));
+        }
+        contracts.push(Contract {
+            name: entry.name,
+            events,
+            address,
+        });
+    }
+    if contracts.is_empty() {
+        return Err("no contracts to relay events of".to_owned());
+    }
+    Ok(contracts)
+}
+
+pub fn load(path: &Path, decoder: &Decoder) -> Result<Vec<Contract>, String> {
+    let toml = fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    parse(&toml, decoder).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "0x0101010101010101010101010101010101010101010101010101010101010101";
+    const OTHER: &str = "0x0202020202020202020202020202020202020202020202020202020202020202";
+
+    #[test]
+    fn events_default_to_the_name() {
+        let contracts = parse(
+            &format!(
+                "[[contract]]\nname = \"erc20\"\naddress = \"{}\"\n\
+                 [[contract]]\nname = \"course-token\"\nevents = \"erc20\"\naddress = \"{}\"\n",
+                TOKEN, OTHER
+            ),
+            &Decoder::new(42),
+        )
+        .unwrap();
+        assert_eq!(
+            contracts,
+            [
+                Contract {
+                    name: "erc20".to_owned(),
+                    events: "erc20".to_owned(),
+                    address: [1; 32],
+                },
+                Contract {
+                    name: "course-token".to_owned(),
+                    events: "erc20".to_owned(),
+                    address: [2; 32],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn contracts_are_checked() {
+        let decoder = Decoder::new(42);
+        let entry = |name: &str, address: &str| {
+            format!(
+                "[[contract]]\nname = \"{}\"\naddress = \"{}\"\n",
+                name, address
+            )
+        };
+        assert_eq!(
+            parse(&entry("erc1155", TOKEN), &decoder).unwrap_err(),
+            "`erc1155`: crates/events has no module `erc1155`"
+        );
+        assert_eq!(
+            parse(&(entry("erc20", TOKEN) + &entry("erc20", OTHER)), &decoder).unwrap_err(),
+            "`erc20` is listed twice"
+        );
+        assert_eq!(
+            parse(&(entry("erc20", TOKEN) + &entry("erc721", TOKEN)), &decoder).unwrap_err(),
+            "`erc721` has the address of another contract"
+        );
+        assert!(parse("", &decoder).is_err());
+    }
+}
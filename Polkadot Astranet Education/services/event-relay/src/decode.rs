@@ -0,0 +1,307 @@
+// services/event-relay/src/decode.rs
+//
+// Turning a contract's `ContractEmitted` payload into JSON for the website, for every
+// contract mirrored in `crates/events`.
+//
+// Each mirror's `Event` enum derives `scale_info::TypeInfo`, which describes its
+// encoding: the variants in order, each with its fields and their types. `Decoder`
+// registers every contract's `Event` in one type registry and walks the description
+// while reading the payload, so adding a contract to the events crate only takes a
+// line in `decoders!` below, and the JSON follows the mirror when fields change.
+//
+// The JSON is shaped for JavaScript rather than mirroring SCALE exactly:
+//
+// - structs are objects, and a struct or tuple with a single unnamed field is that
+//   field, so `Transfer(Transfer { .. })` is just the inner object;
+// - `Option` is the value or `null`; other enums are the variant's name when it has
+//   no fields, and `{ "Variant": fields }` when it does;
+// - accounts are SS58 addresses in the network's format, and byte arrays and vectors
+//   (hashes, signatures, call data) are `0x`-prefixed hex;
+// - integers of 64 bits or more are decimal strings, since a JavaScript number cannot
+//   hold every `u64` or `Balance` exactly, and smaller ones are numbers.
+
+use deployer::network::ss58_encode;
+use repl::value::to_hex;
+use scale::{Compact, Decode};
+use scale_info::{
+    form::PortableForm, MetaType, PortableRegistry, Registry, Type, TypeDef, TypeDefPrimitive,
+};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// Registers the `Event` enum of each listed module of `astranet_events`.
+macro_rules! decoders {
+    ($($contract:ident),* $(,)?) => {
+        fn event_types() -> Vec<(&'static str, MetaType)> {
+            vec![$(
+                (
+                    stringify!($contract),
+                    MetaType::new::<astranet_events::$contract::Event>(),
+                ),
+            )*]
+        }
+    };
+}
+
+decoders! {
+    arbitration,
+    badge,
+    cdp,
+    config_store,
+    crowdloan,
+    dividend_token,
+    donation,
+    enrollment,
+    erc20,
+    erc721,
+    events_showcase,
+    factory,
+    faucet,
+    flash_borrower,
+    flashloan_pool,
+    forwarder,
+    forwarder_recipient,
+    insurance,
+    leaderboard,
+    limited_token,
+    marketplace,
+    migrate_v2,
+    multisig,
+    nomination,
+    orderbook,
+    payroll,
+    peer_review,
+    prediction,
+    progress,
+    quadratic_funding,
+    rebase_token,
+    recovery_wallet,
+    referral,
+    revenue_share,
+    runtime_call,
+    scholarship,
+    spending_manager,
+    staking,
+    timed_quiz,
+    token_locker,
+    treasury,
+    vesting,
+    vesting_factory,
+    votes_token,
+    xcm_transfer,
+}
+
+/// A decoded event: the variant's name and its fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoded {
+    pub event: String,
+    pub fields: Value,
+}
+
+/// Decodes the events of every contract in the events crate.
+pub struct Decoder {
+    registry: PortableRegistry,
+    /// The type id of each contract's `Event` enum, by module name.
+    events: BTreeMap<&'static str, u32>,
+    ss58_prefix: u16,
+}
+
+impl Decoder {
+    /// A decoder writing accounts as addresses with `ss58_prefix`.
+    pub fn new(ss58_prefix: u16) -> Self {
+        let mut registry = Registry::new();
+        let events = event_types()
+            .into_iter()
+            .map(|(contract, meta)| (contract, registry.register_type(&meta).id))
+            .collect();
+        Decoder {
+            registry: registry.into(),
+            events,
+            ss58_prefix,
+        }
+    }
+
+    /// Formats `account` as an SS58 address, as accounts in events are.
+    pub fn address(&self, account: [u8; 32]) -> String {
+        ss58_encode(self.ss58_prefix, account)
+    }
+
+    /// Whether `contract` names a module of the events crate.
+    pub fn knows(&self, contract: &str) -> bool {
+        self.events.contains_key(contract)
+    }
+
+    /// The contracts whose events can be decoded, by module name.
+    pub fn contracts(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.events.keys().copied()
+    }
+
+    /// Decodes `data`, emitted by a contract whose events are mirrored in module
+    /// `contract` of the events crate. Like `astranet_events::decode`, fails if bytes
+    /// are left over.
+    pub fn decode(&self, contract: &str, data: &[u8]) -> Result<Decoded, String> {
+        let id = *self
+            .events
+            .get(contract)
+            .ok_or_else(|| format!("no events are known for `{}`", contract))?;
+        let input = &mut &data[..];
+        let ty = self.resolve(id)?;
+        let TypeDef::Variant(def) = &ty.type_def else {
+            return Err(format!("the events of `{}` are not an enum", contract));
+        };
+        let index = u8::decode(input).map_err(|err| err.to_string())?;
+        let variant = def
+            .variants
+            .iter()
+            .find(|variant| variant.index == index)
+            .ok_or_else(|| format!("`{}` has no event {}", contract, index))?;
+        let field_types: Vec<_> = variant.fields.iter().map(|field| field.ty.id).collect();
+        let fields = self.fields(&variant.fields, &field_types, input)?;
+        if !input.is_empty() {
+            return Err(format!(
+                "{} byte(s) left over after `{}`",
+                input.len(),
+                variant.name
+            ));
+        }
+        Ok(Decoded {
+            event: variant.name.clone(),
+            fields,
+        })
+    }
+
+    fn resolve(&self, id: u32) -> Result<&Type<PortableForm>, String> {
+        self.registry
+            .resolve(id)
+            .ok_or_else(|| format!("unknown type {}", id))
+    }
+
+    /// Reads a value of type `id`.
+    fn value(&self, id: u32, input: &mut &[u8]) -> Result<Value, String> {
+        let ty = self.resolve(id)?;
+        let name = ty.path.segments.last().map(String::as_str);
+        match &ty.type_def {
+            TypeDef::Composite(_) if name == Some("AccountId") => {
+                let account = <[u8; 32]>::decode(input).map_err(|err| err.to_string())?;
+                Ok(json!(self.address(account)))
+            }
+            TypeDef::Composite(def) => {
+                let types: Vec<_> = def.fields.iter().map(|field| field.ty.id).collect();
+                self.fields(&def.fields, &types, input)
+            }
+            TypeDef::Variant(def) => {
+                let index = u8::decode(input).map_err(|err| err.to_string())?;
+                let variant = def
+                    .variants
+                    .iter()
+                    .find(|variant| variant.index == index)
+                    .ok_or_else(|| format!("`{}` has no variant {}", ty.path, index))?;
+                let types: Vec<_> = variant.fields.iter().map(|field| field.ty.id).collect();
+                if name == Some("Option") {
+                    return match types.first() {
+                        Some(&inner) => self.value(inner, input),
+                        None => Ok(Value::Null),
+                    };
+                }
+                if types.is_empty() {
+                    return Ok(json!(variant.name));
+                }
+                let fields = self.fields(&variant.fields, &types, input)?;
+                Ok(json!({ variant.name.clone(): fields }))
+            }
+            TypeDef::Sequence(def) => {
+                let len = Compact::<u32>::decode(input)
+                    .map_err(|err| err.to_string())?
+                    .0;
+                self.elements(def.type_param.id, len, input)
+            }
+            TypeDef::Array(def) => self.elements(def.type_param.id, def.len, input),
+            TypeDef::Tuple(def) => {
+                let types: Vec<_> = def.fields.iter().map(|field| field.id).collect();
+                self.fields(&[], &types, input)
+            }
+            TypeDef::Primitive(primitive) => primitive_value(primitive, input),
+            TypeDef::Compact(def) => {
+                let ty = self.resolve(def.type_param.id)?;
+                let value = Compact::<u128>::decode(input)
+                    .map_err(|err| err.to_string())?
+                    .0;
+                Ok(match &ty.type_def {
+                    TypeDef::Primitive(TypeDefPrimitive::U8 | TypeDefPrimitive::U16)
+                    | TypeDef::Primitive(TypeDefPrimitive::U32) => json!(value as u32),
+                    _ => json!(value.to_string()),
+                })
+            }
+            TypeDef::BitSequence(_) => Err("bit sequences are not supported".to_owned()),
+        }
+    }
+
+    /// Reads the fields of a struct, tuple or variant: an object if they are named,
+    /// the value itself if there is one unnamed field, and an array otherwise.
+    fn fields(
+        &self,
+        fields: &[scale_info::Field<PortableForm>],
+        types: &[u32],
+        input: &mut &[u8],
+    ) -> Result<Value, String> {
+        let named = !fields.is_empty() && fields.iter().all(|field| field.name.is_some());
+        let mut values = Vec::new();
+        for &id in types {
+            values.push(self.value(id, input)?);
+        }
+        if named {
+            let mut object = Map::new();
+            for (field, value) in fields.iter().zip(values) {
+                object.insert(field.name.clone().unwrap_or_default(), value);
+            }
+            return Ok(Value::Object(object));
+        }
+        Ok(match values.len() {
+            0 => Value::Null,
+            1 => values.remove(0),
+            _ => Value::Array(values),
+        })
+    }
+
+    /// Reads `len` elements of type `id`: hex if they are bytes, an array otherwise.
+    fn elements(&self, id: u32, len: u32, input: &mut &[u8]) -> Result<Value, String> {
+        if let TypeDef::Primitive(TypeDefPrimitive::U8) = self.resolve(id)?.type_def {
+            if input.len() < len as usize {
+                return Err("not enough bytes".to_owned());
+            }
+            let (bytes, rest) = input.split_at(len as usize);
+            *input = rest;
+            return Ok(json!(to_hex(bytes)));
+        }
+        (0..len)
+            .map(|_| self.value(id, input))
+            .collect::<Result<_, _>>()
+            .map(Value::Array)
+    }
+}
+
+fn primitive_value(primitive: &TypeDefPrimitive, input: &mut &[u8]) -> Result<Value, String> {
+    fn read<T: Decode>(input: &mut &[u8]) -> Result<T, String> {
+        T::decode(input).map_err(|err| err.to_string())
+    }
+    Ok(match primitive {
+        TypeDefPrimitive::Bool => json!(read::<bool>(input)?),
+        TypeDefPrimitive::Char => json!(char::from_u32(read::<u32>(input)?)
+            .ok_or("invalid char")?
+            .to_string()),
+        TypeDefPrimitive::Str => json!(read::<String>(input)?),
+        TypeDefPrimitive::U8 => json!(read::<u8>(input)?),
+        TypeDefPrimitive::U16 => json!(read::<u16>(input)?),
+        TypeDefPrimitive::U32 => json!(read::<u32>(input)?),
+        TypeDefPrimitive::U64 => json!(read::<u64>(input)?.to_string()),
+        TypeDefPrimitive::U128 => json!(read::<u128>(input)?.to_string()),
+        TypeDefPrimitive::I8 => json!(read::<i8>(input)?),
+        TypeDefPrimitive::I16 => json!(read::<i16>(input)?),
+        TypeDefPrimitive::I32 => json!(read::<i32>(input)?),
+        TypeDefPrimitive::I64 => json!(read::<i64>(input)?.to_string()),
+        TypeDefPrimitive::I128 => json!(read::<i128>(input)?.to_string()),
+        TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => {
+            json!(to_hex(&read::<[u8; 32]>(input)?))
+        }
+    })
+}
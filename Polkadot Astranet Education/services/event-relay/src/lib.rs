@@ -0,0 +1,90 @@
+// services/event-relay/src/lib.rs
+//
+// The event relay lets the course website show what the example contracts do as it
+// happens, without the website decoding SCALE itself. It follows the node's
+// finalized blocks (see `chain`), decodes the events of the configured contracts
+// with the mirrors in `crates/events` (see `decode`), and sends each as a JSON
+// message to every WebSocket client connected to it (see `server`):
+//
+//     {
+//       "block": 1042,
+//       "index": 3,
+//       "contract": "erc20",
+//       "address": "5F...",
+//       "event": "Transfer",
+//       "fields": { "from": null, "to": "5G...", "value": "1000" }
+//     }
+//
+// An event that does not decode is still sent, with `error` and the raw `data` in
+// place of `event` and `fields`, so a stale mirror shows up on the page rather than
+// as missing events.
+
+pub mod backoff;
+pub mod chain;
+pub mod config;
+pub mod decode;
+pub mod server;
+
+pub use backoff::Backoff;
+pub use config::Contract;
+pub use decode::{Decoded, Decoder};
+
+use repl::value::to_hex;
+use serde::Serialize;
+use serde_json::Value;
+
+/// An event sent to the website.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Relayed {
+    pub block: u32,
+    /// The index of the event among the block's events.
+    pub index: u32,
+    /// The configured name of the contract that emitted it.
+    pub contract: String,
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+impl Relayed {
+    /// Decodes `data`, emitted by `contract` as event `index` of `block`.
+    pub fn new(
+        decoder: &Decoder,
+        contract: &Contract,
+        block: u32,
+        index: u32,
+        data: &[u8],
+    ) -> Self {
+        let mut relayed = Relayed {
+            block,
+            index,
+            contract: contract.name.clone(),
+            address: decoder.address(contract.address),
+            event: None,
+            fields: None,
+            error: None,
+            data: None,
+        };
+        match decoder.decode(&contract.events, data) {
+            Ok(Decoded { event, fields }) => {
+                relayed.event = Some(event);
+                relayed.fields = Some(fields);
+            }
+            Err(err) => {
+                relayed.error = Some(err);
+                relayed.data = Some(to_hex(data));
+            }
+        }
+        relayed
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("events serialize")
+    }
+}
@@ -0,0 +1,86 @@
+// services/event-relay/src/main.rs
+//
+// Usage:
+//   event-relay --config relay.toml
+//   event-relay --config relay.toml --port 9955 --network shibuya
+//
+// Relays the events of the contracts in `--config` (see `config` in the library) from
+// the node to WebSocket clients on `127.0.0.1:--port`. `--network` picks the node and
+// the address format of accounts from the deployer's network profiles; `--url`
+// overrides the node. Runs until stopped, reconnecting to the node when it goes away.
+
+use clap::Parser;
+use deployer::Networks;
+use event_relay::{chain, config, server, Backoff, Decoder};
+use std::{fs, net::Ipv4Addr, path::PathBuf, process::ExitCode};
+use tokio::{net::TcpListener, sync::broadcast};
+
+#[derive(Parser)]
+#[command(about = "Relay the example contracts' events to the website over a local WebSocket")]
+struct Cli {
+    /// The contracts whose events to relay.
+    #[arg(long)]
+    config: PathBuf,
+    /// Port of the WebSocket server, on 127.0.0.1.
+    #[arg(long, default_value_t = 9955)]
+    port: u16,
+    /// Network profile name, or the WebSocket URL of a node.
+    #[arg(long, default_value = "local")]
+    network: String,
+    /// File of network profiles to add to the built-in ones.
+    #[arg(long)]
+    networks: Option<PathBuf>,
+    /// WebSocket URL of the node, instead of the profile's.
+    #[arg(long)]
+    url: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
+    let mut networks = Networks::builtin();
+    if let Some(path) = &cli.networks {
+        let toml =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        networks
+            .extend(Networks::parse(&toml).map_err(|err| format!("{}: {}", path.display(), err))?);
+    }
+    let network = networks.get(&cli.network)?;
+    let url = cli.url.unwrap_or(network.url);
+    let decoder = Decoder::new(network.ss58_prefix);
+    let contracts = config::load(&cli.config, &decoder)?;
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, cli.port))
+        .await
+        .map_err(|err| format!("cannot listen on port {}: {}", cli.port, err))?;
+    println!("relaying to ws://127.0.0.1:{}/", cli.port);
+    for contract in &contracts {
+        println!(
+            "  {} ({}) at ws://127.0.0.1:{}/{}",
+            contract.name,
+            decoder.address(contract.address),
+            cli.port,
+            contract.name
+        );
+    }
+
+    let (events, _) = broadcast::channel(server::BACKLOG);
+    let relay = chain::follow(&url, &decoder, &contracts, Backoff::default(), |event| {
+        // Without clients the event is dropped, which is fine.
+        let _ = events.send(event);
+        true
+    });
+    tokio::select! {
+        () = relay => Ok(()),
+        err = server::serve(listener, events.clone()) => Err(err),
+    }
+}
@@ -0,0 +1,101 @@
+// services/event-relay/src/server.rs
+//
+// The WebSocket side. Clients connect to `ws://127.0.0.1:<port>/` for the events of
+// every contract, or to `/<name>` for those of the contract configured as `name`, and
+// receive each event as a text message. Clients only listen: anything they send is
+// read and dropped.
+//
+// Events reach the connections through a broadcast channel. A client too slow to keep
+// up misses the events it fell behind on rather than holding the others back, and is
+// sent `{"lagged": n}` with the number it missed, so the page can say so.
+
+use crate::Relayed;
+use soketto::handshake::{server::Response, Server};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::broadcast::{self, error::RecvError},
+};
+use tokio_util::compat::TokioAsyncReadCompatExt;
+
+/// The number of events a client may fall behind by before it misses some.
+pub const BACKLOG: usize = 1024;
+
+/// Accepts WebSocket clients on `listener` and sends them the events from `events`,
+/// until the listener fails.
+pub async fn serve(listener: TcpListener, events: broadcast::Sender<Relayed>) -> String {
+    loop {
+        let (socket, peer) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(err) => return format!("cannot accept clients: {}", err),
+        };
+        let events = events.subscribe();
+        tokio::spawn(async move {
+            if let Err(err) = client(socket, events).await {
+                eprintln!("client {}: {}", peer, err);
+            }
+        });
+    }
+}
+
+/// The contract a client asked for with the path of its request, if any.
+pub fn contract_filter(path: &str) -> Option<String> {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let name = path.trim_matches('/');
+    (!name.is_empty()).then(|| name.to_owned())
+}
+
+async fn client(socket: TcpStream, mut events: broadcast::Receiver<Relayed>) -> Result<(), String> {
+    let mut server = Server::new(socket.compat());
+    let request = server
+        .receive_request()
+        .await
+        .map_err(|err| format!("handshake failed: {}", err))?;
+    let filter = contract_filter(request.path());
+    let key = request.key();
+    server
+        .send_response(&Response::Accept {
+            key,
+            protocol: None,
+        })
+        .await
+        .map_err(|err| format!("handshake failed: {}", err))?;
+    let (mut sender, mut receiver) = server.into_builder().finish();
+
+    // Reading answers the client's pings and notices when it goes away.
+    let mut closed = tokio::spawn(async move {
+        let mut message = Vec::new();
+        while receiver.receive_data(&mut message).await.is_ok() {
+            message.clear();
+        }
+    });
+    loop {
+        let json = tokio::select! {
+            _ = &mut closed => return Ok(()),
+            event = events.recv() => match event {
+                Ok(event) if filter.as_ref().is_some_and(|name| *name != event.contract) => continue,
+                Ok(event) => event.to_json(),
+                Err(RecvError::Lagged(missed)) => format!("{{\"lagged\":{}}}", missed),
+                Err(RecvError::Closed) => return Ok(()),
+            },
+        };
+        sender
+            .send_text_owned(json)
+            .await
+            .map_err(|err| err.to_string())?;
+        sender.flush().await.map_err(|err| err.to_string())?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_pick_a_contract() {
+        assert_eq!(contract_filter("/"), None);
+        assert_eq!(contract_filter(""), None);
+        assert_eq!(contract_filter("/?since=0"), None);
+        assert_eq!(contract_filter("/erc20"), Some("erc20".to_owned()));
+        assert_eq!(contract_filter("/erc20/?x=1"), Some("erc20".to_owned()));
+    }
+}
@@ -0,0 +1,177 @@
+// services/event-relay/tests/decode.rs
+//
+// Decodes events encoded with the mirrors in `crates/events`, as the contracts emit
+// them, and checks the JSON the website receives.
+
+use astranet_events::{config_store, erc20, timed_quiz, AccountId, Hash};
+use event_relay::{Contract, Decoded, Decoder, Relayed};
+use repl::value::parse_account;
+use scale::Encode;
+use serde_json::json;
+
+const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+
+fn account(name: &str) -> AccountId {
+    AccountId::from(parse_account(name).unwrap())
+}
+
+fn decode(contract: &str, event: impl Encode) -> Decoded {
+    Decoder::new(42).decode(contract, &event.encode()).unwrap()
+}
+
+#[test]
+fn every_mirror_is_registered() {
+    let lib = include_str!("../../../crates/events/src/lib.rs");
+    let modules: Vec<&str> = lib
+        .lines()
+        .filter_map(|line| line.strip_prefix("pub mod "))
+        .map(|line| line.trim_end_matches(';'))
+        .collect();
+    let decoder = Decoder::new(42);
+    assert_eq!(decoder.contracts().collect::<Vec<_>>(), modules);
+}
+
+#[test]
+fn structs_become_objects_and_balances_strings() {
+    let decoded = decode(
+        "erc20",
+        erc20::Event::Transfer(erc20::Transfer {
+            from: None,
+            to: Some(account("alice")),
+            value: 1_000_000_000_000_000_000_000,
+        }),
+    );
+    assert_eq!(decoded.event, "Transfer");
+    assert_eq!(
+        decoded.fields,
+        json!({ "from": null, "to": ALICE, "value": "1000000000000000000000" })
+    );
+
+    let decoded = decode(
+        "erc20",
+        erc20::Event::Approval(erc20::Approval {
+            owner: account("alice"),
+            spender: account("bob"),
+            value: 5,
+        }),
+    );
+    assert_eq!(
+        decoded.fields,
+        json!({ "owner": ALICE, "spender": BOB, "value": "5" })
+    );
+}
+
+#[test]
+fn bytes_and_hashes_become_hex() {
+    let decoded = decode(
+        "timed_quiz",
+        timed_quiz::Event::QuizCreated(timed_quiz::QuizCreated {
+            quiz_id: 3,
+            author: account("bob"),
+            key_hash: Hash::from([0xab; 32]),
+            questions: 4,
+            deadline: 1_760_000_000_000,
+        }),
+    );
+    assert_eq!(
+        decoded.fields,
+        json!({
+            "quiz_id": 3,
+            "author": BOB,
+            "key_hash": format!("0x{}", "ab".repeat(32)),
+            "questions": 4,
+            "deadline": "1760000000000",
+        })
+    );
+
+    let decoded = decode(
+        "timed_quiz",
+        timed_quiz::Event::AnswersSubmitted(timed_quiz::AnswersSubmitted {
+            quiz_id: 3,
+            learner: account("alice"),
+            answers: vec![1, 0, 2],
+        }),
+    );
+    assert_eq!(decoded.fields["answers"], json!("0x010002"));
+}
+
+#[test]
+fn enums_become_names_or_tagged_objects() {
+    let value_set = |value| {
+        decode(
+            "config_store",
+            config_store::Event::ValueSet(config_store::ValueSet {
+                key: b"fee".to_vec(),
+                version: 2,
+                value,
+                set_by: account("alice"),
+            }),
+        )
+        .fields
+    };
+    assert_eq!(
+        value_set(config_store::Value::Bool(true)),
+        json!({ "key": "0x666565", "version": 2, "value": { "Bool": true }, "set_by": ALICE })
+    );
+    assert_eq!(
+        value_set(config_store::Value::Account(account("bob")))["value"],
+        json!({ "Account": BOB })
+    );
+}
+
+#[test]
+fn malformed_payloads_are_reported() {
+    let decoder = Decoder::new(42);
+    let mut data = erc20::Event::Approval(erc20::Approval {
+        owner: account("alice"),
+        spender: account("bob"),
+        value: 5,
+    })
+    .encode();
+    data.push(0);
+    assert_eq!(
+        decoder.decode("erc20", &data).unwrap_err(),
+        "1 byte(s) left over after `Approval`"
+    );
+    assert_eq!(
+        decoder.decode("erc20", &[0xff]).unwrap_err(),
+        "`erc20` has no event 255"
+    );
+    assert!(decoder.decode("erc20", &data[..10]).is_err());
+    assert!(decoder.decode("erc1155", &data).is_err());
+}
+
+#[test]
+fn relayed_events_carry_their_origin() {
+    let decoder = Decoder::new(42);
+    let contract = Contract {
+        name: "course-token".to_owned(),
+        events: "erc20".to_owned(),
+        address: parse_account("alice").unwrap(),
+    };
+    let data = erc20::Event::Transfer(erc20::Transfer {
+        from: Some(account("alice")),
+        to: None,
+        value: 7,
+    })
+    .encode();
+    let relayed = Relayed::new(&decoder, &contract, 12, 3, &data);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&relayed.to_json()).unwrap(),
+        json!({
+            "block": 12,
+            "index": 3,
+            "contract": "course-token",
+            "address": ALICE,
+            "event": "Transfer",
+            "fields": { "from": ALICE, "to": null, "value": "7" },
+        })
+    );
+
+    // An event that does not decode is relayed raw.
+    let relayed = Relayed::new(&decoder, &contract, 12, 4, &[0xff, 0x01]);
+    assert_eq!(relayed.event, None);
+    assert_eq!(relayed.error.as_deref(), Some("`erc20` has no event 255"));
+    assert_eq!(relayed.data.as_deref(), Some("0xff01"));
+}
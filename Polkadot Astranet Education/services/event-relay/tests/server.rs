@@ -0,0 +1,106 @@
+// services/event-relay/tests/server.rs
+//
+// Connects WebSocket clients to the relay's server and checks which events each
+// receives.
+
+use event_relay::{server, Relayed};
+use soketto::handshake::{Client, ServerResponse};
+use std::time::Duration;
+use tokio::{net::TcpListener, net::TcpStream, sync::broadcast};
+use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+
+type Receiver = soketto::connection::Receiver<Compat<TcpStream>>;
+
+fn event(contract: &str, index: u32) -> Relayed {
+    Relayed {
+        block: 1,
+        index,
+        contract: contract.to_owned(),
+        address: "5F...".to_owned(),
+        event: Some("Transfer".to_owned()),
+        fields: Some(serde_json::json!({})),
+        error: None,
+        data: None,
+    }
+}
+
+async fn start() -> (u16, broadcast::Sender<Relayed>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (events, _) = broadcast::channel(16);
+    tokio::spawn(server::serve(listener, events.clone()));
+    (port, events)
+}
+
+async fn connect(port: u16, path: &str) -> Receiver {
+    let socket = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    let host = format!("127.0.0.1:{}", port);
+    let mut client = Client::new(socket.compat(), &host, path);
+    match client.handshake().await.unwrap() {
+        ServerResponse::Accepted { .. } => {}
+        _ => panic!("the relay refused the connection"),
+    }
+    client.into_builder().finish().1
+}
+
+async fn receive(receiver: &mut Receiver) -> serde_json::Value {
+    let mut message = Vec::new();
+    tokio::time::timeout(Duration::from_secs(5), receiver.receive_data(&mut message))
+        .await
+        .expect("no event arrived")
+        .unwrap();
+    serde_json::from_slice(&message).unwrap()
+}
+
+/// Waits until `count` clients are subscribed.
+async fn wait_for_clients(events: &broadcast::Sender<Relayed>, count: usize) {
+    while events.receiver_count() < count {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[tokio::test]
+async fn clients_receive_all_events_or_one_contracts() {
+    let (port, events) = start().await;
+    let mut everything = connect(port, "/").await;
+    let mut tokens = connect(port, "/erc20").await;
+    wait_for_clients(&events, 2).await;
+
+    events.send(event("multisig", 0)).unwrap();
+    events.send(event("erc20", 1)).unwrap();
+
+    let first = receive(&mut everything).await;
+    assert_eq!(
+        (&first["contract"], &first["index"]),
+        (&"multisig".into(), &0.into())
+    );
+    let second = receive(&mut everything).await;
+    assert_eq!(second["contract"], "erc20");
+    let only = receive(&mut tokens).await;
+    assert_eq!(
+        (&only["contract"], &only["index"]),
+        (&"erc20".into(), &1.into())
+    );
+}
+
+#[tokio::test]
+async fn slow_clients_are_told_what_they_missed() {
+    let (port, events) = start().await;
+    let mut slow = connect(port, "/").await;
+    wait_for_clients(&events, 1).await;
+
+    // Sent faster than the client reads: the channel keeps the last 16.
+    for index in 0..20 {
+        events.send(event("erc20", index)).unwrap();
+    }
+    let mut lagged = None;
+    let mut last = 0;
+    while last != 19 {
+        let message = receive(&mut slow).await;
+        match message.get("lagged") {
+            Some(missed) => lagged = missed.as_u64(),
+            None => last = message["index"].as_u64().unwrap(),
+        }
+    }
+    assert!(lagged.is_some_and(|missed| missed > 0));
+}
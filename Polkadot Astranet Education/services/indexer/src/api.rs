@@ -0,0 +1,274 @@
+// services/indexer/src/api.rs
+//
+// The REST API. Every endpoint answers `GET` with JSON:
+//
+//     GET /status                               the next block to index, the latest
+//                                               finalized one and the contracts
+//     GET /holders?contract=course-token        ERC-20 balances, largest first
+//     GET /history?contract=course-token&account=5F...
+//                                               an account's ERC-20 transfers and balances
+//     GET /balance_at?contract=course-token&account=5F...&block=1042
+//                                               an account's ERC-20 balance after a block
+//     GET /mints?contract=certificates          ERC-721 mints, oldest first
+//     GET /proposals?contract=treasury          treasury spend proposals, by id
+//
+// `contract` is a name from the configuration, and may be left out when only one
+// contract of the endpoint's kind is indexed. `account` is an address, a hex account
+// id or a development account name such as `alice`. Accounts in responses are
+// addresses of the network, and token amounts are decimal strings, as they do not
+// fit in a JavaScript number. Errors are `{ "error": "..." }` with a 4xx or 5xx
+// status.
+//
+// `/balance_at` adds up the transfers indexed up to the block, so it answers for
+// indexed blocks only, and matches the contract's `balance_of` at that block when
+// the indexer started before the token was deployed. Its `finalized` field says
+// whether the block was final; when the indexer reads unfinalized blocks too, the
+// balance at a block that is not may still change with a reorg.
+
+use crate::{Config, Kind, Store};
+use deployer::network::ss58_encode;
+use http_body_util::Full;
+use hyper::{body::Bytes, server::conn::http1, service::service_fn, Method, Request, StatusCode};
+use hyper_util::rt::TokioIo;
+use repl::value::parse_account;
+use serde_json::{json, Value};
+use std::{collections::HashMap, convert::Infallible, sync::Arc};
+use tokio::net::TcpListener;
+
+pub struct Api {
+    config: Config,
+    store: Arc<Store>,
+    ss58_prefix: u16,
+}
+
+type Response = (StatusCode, Value);
+
+impl Api {
+    pub fn new(config: Config, store: Arc<Store>, ss58_prefix: u16) -> Self {
+        Api {
+            config,
+            store,
+            ss58_prefix,
+        }
+    }
+
+    /// Answers a `GET` of `path` with the query string `query`.
+    pub fn get(&self, path: &str, query: &str) -> Response {
+        let params: HashMap<String, String> = form_urlencoded::parse(query.as_bytes())
+            .into_owned()
+            .collect();
+        let answer = match path {
+            "/status" => self.status(),
+            "/holders" => self.holders(&params),
+            "/history" => self.history(&params),
+            "/balance_at" => self.balance_at(&params),
+            "/mints" => self.mints(&params),
+            "/proposals" => self.proposals(&params),
+            _ => return error(StatusCode::NOT_FOUND, format!("no endpoint {}", path)),
+        };
+        answer.unwrap_or_else(|err| err)
+    }
+
+    fn status(&self) -> Result<Response, Response> {
+        let contracts: Vec<Value> = self
+            .config
+            .contracts
+            .iter()
+            .map(|contract| {
+                json!({
+                    "name": contract.name,
+                    "kind": contract.kind,
+                    "address": self.address(contract.address),
+                })
+            })
+            .collect();
+        Ok(ok(json!({
+            "next_block": self.store.next_block().map_err(internal)?,
+            "finalized_block": self.store.finalized_block().map_err(internal)?,
+            "contracts": contracts,
+        })))
+    }
+
+    fn holders(&self, params: &HashMap<String, String>) -> Result<Response, Response> {
+        let contract = self.contract(Kind::Erc20, params)?;
+        let holders = self.store.holders(contract).map_err(internal)?;
+        Ok(ok(holders
+            .into_iter()
+            .map(|(account, balance)| {
+                json!({ "account": self.address(account), "balance": balance.to_string() })
+            })
+            .collect()))
+    }
+
+    fn history(&self, params: &HashMap<String, String>) -> Result<Response, Response> {
+        let contract = self.contract(Kind::Erc20, params)?;
+        let account = account(params)?;
+        let history = self.store.history(contract, account).map_err(internal)?;
+        Ok(ok(history
+            .into_iter()
+            .map(|change| {
+                let transfer = change.transfer;
+                json!({
+                    "block": transfer.event.block,
+                    "index": transfer.event.index,
+                    "from": transfer.from.map(|from| self.address(from)),
+                    "to": transfer.to.map(|to| self.address(to)),
+                    "value": transfer.value.to_string(),
+                    "balance": change.balance.to_string(),
+                })
+            })
+            .collect()))
+    }
+
+    fn balance_at(&self, params: &HashMap<String, String>) -> Result<Response, Response> {
+        let contract = self.contract(Kind::Erc20, params)?;
+        let account = account(params)?;
+        let block: u32 = params
+            .get("block")
+            .ok_or_else(|| error(StatusCode::BAD_REQUEST, "missing `block`"))?
+            .parse()
+            .map_err(|err| error(StatusCode::BAD_REQUEST, format!("`block`: {}", err)))?;
+        let indexed = (
+            self.store.first_block().map_err(internal)?,
+            self.store.next_block().map_err(internal)?,
+        );
+        match indexed {
+            (Some(first), Some(next)) if (first..next).contains(&block) => {}
+            (Some(first), _) if block < first => {
+                return Err(error(
+                    StatusCode::NOT_FOUND,
+                    format!(
+                        "block {} is before the first indexed block, {}",
+                        block, first
+                    ),
+                ))
+            }
+            _ => {
+                return Err(error(
+                    StatusCode::NOT_FOUND,
+                    format!("block {} is not indexed yet", block),
+                ))
+            }
+        }
+        let balance = self
+            .store
+            .balance_at(contract, account, block)
+            .map_err(internal)?;
+        let finalized = self.store.finalized_block().map_err(internal)?;
+        Ok(ok(json!({
+            "block": block,
+            "account": self.address(account),
+            "balance": balance.to_string(),
+            "finalized": finalized.is_some_and(|finalized| block <= finalized),
+        })))
+    }
+
+    fn mints(&self, params: &HashMap<String, String>) -> Result<Response, Response> {
+        let contract = self.contract(Kind::Erc721, params)?;
+        let mints = self.store.mints(contract).map_err(internal)?;
+        Ok(ok(mints
+            .into_iter()
+            .map(|mint| {
+                json!({
+                    "block": mint.event.block,
+                    "index": mint.event.index,
+                    "token": mint.token,
+                    "owner": self.address(mint.owner),
+                })
+            })
+            .collect()))
+    }
+
+    fn proposals(&self, params: &HashMap<String, String>) -> Result<Response, Response> {
+        let contract = self.contract(Kind::Treasury, params)?;
+        let proposals = self.store.proposals(contract).map_err(internal)?;
+        Ok(ok(proposals
+            .into_iter()
+            .map(|proposal| {
+                json!({
+                    "proposal": proposal.proposal,
+                    "block": proposal.event.block,
+                    "proposer": self.address(proposal.proposer),
+                    "asset": match proposal.asset {
+                        Some(token) => self.address(token),
+                        None => "native".to_owned(),
+                    },
+                    "beneficiary": self.address(proposal.beneficiary),
+                    "amount": proposal.amount.to_string(),
+                    "status": if proposal.executed.is_some() { "executed" } else { "proposed" },
+                    "executed_block": proposal.executed.map(|event| event.block),
+                })
+            })
+            .collect()))
+    }
+
+    /// The address of the contract of kind `kind` named by the `contract` parameter.
+    fn contract(&self, kind: Kind, params: &HashMap<String, String>) -> Result<[u8; 32], Response> {
+        self.config
+            .pick(kind, params.get("contract").map(String::as_str))
+            .map(|contract| contract.address)
+            .map_err(|err| error(StatusCode::NOT_FOUND, err))
+    }
+
+    fn address(&self, account: [u8; 32]) -> String {
+        ss58_encode(self.ss58_prefix, account)
+    }
+}
+
+/// Serves `api` to the connections `listener` accepts, until accepting fails.
+/// Returns the reason.
+pub async fn serve(listener: TcpListener, api: Arc<Api>) -> String {
+    loop {
+        let (stream, _) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(err) => return format!("cannot accept connections: {}", err),
+        };
+        let api = api.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request: Request<hyper::body::Incoming>| {
+                let api = api.clone();
+                async move { Ok::<_, Infallible>(respond(&api, &request)) }
+            });
+            // A client hanging up early is its own business.
+            let _ = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        });
+    }
+}
+
+fn respond<B>(api: &Api, request: &Request<B>) -> hyper::Response<Full<Bytes>> {
+    let (status, body) = if request.method() == Method::GET {
+        api.get(request.uri().path(), request.uri().query().unwrap_or(""))
+    } else {
+        error(StatusCode::METHOD_NOT_ALLOWED, "only GET is supported")
+    };
+    hyper::Response::builder()
+        .status(status)
+        .header("content-type", "application/json")
+        // The course website runs on another origin.
+        .header("access-control-allow-origin", "*")
+        .body(Full::new(Bytes::from(body.to_string())))
+        .expect("a valid response")
+}
+
+/// The account named by the `account` parameter.
+fn account(params: &HashMap<String, String>) -> Result<[u8; 32], Response> {
+    let account = params
+        .get("account")
+        .ok_or_else(|| error(StatusCode::BAD_REQUEST, "missing `account`"))?;
+    parse_account(account)
+        .map_err(|err| error(StatusCode::BAD_REQUEST, format!("`account`: {}", err)))
+}
+
+fn ok(body: Value) -> Response {
+    (StatusCode::OK, body)
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, json!({ "error": message.into() }))
+}
+
+fn internal(err: String) -> Response {
+    error(StatusCode::INTERNAL_SERVER_ERROR, err)
+}
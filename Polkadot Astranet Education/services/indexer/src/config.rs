@@ -0,0 +1,219 @@
+// services/indexer/src/config.rs
+//
+// The contracts to index, read from a TOML file:
+//
+//     [[contract]]
+//     name = "course-token"
+//     kind = "erc20"
+//     address = "5F..."
+//
+//     [[contract]]
+//     name = "certificates"
+//     kind = "erc721"
+//     address = "5G..."
+//
+//     [[contract]]
+//     name = "treasury"
+//     kind = "treasury"
+//     address = "5H..."
+//
+// `kind` says which example the contract was deployed from, and so which of its
+// events are stored; `name` is how the REST API refers to it.
+
+use repl::value::parse_account;
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs, path::Path};
+
+/// The examples whose events the indexer understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    /// `examples/demo-contracts/erc20.rs`: its transfers.
+    Erc20,
+    /// `examples/demo-contracts/erc721.rs`: its mints.
+    Erc721,
+    /// `examples/demo-contracts/treasury.rs`: its spend proposals.
+    Treasury,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Kind::Erc20 => "erc20",
+            Kind::Erc721 => "erc721",
+            Kind::Treasury => "treasury",
+        })
+    }
+}
+
+/// A contract whose events are indexed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contract {
+    pub name: String,
+    pub kind: Kind,
+    pub address: [u8; 32],
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ContractEntry {
+    name: String,
+    kind: Kind,
+    address: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default, rename = "contract")]
+    contracts: Vec<ContractEntry>,
+}
+
+/// The contracts the indexer works with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub contracts: Vec<Contract>,
+}
+
+impl Config {
+    /// Reads a configuration in the format above.
+    pub fn parse(toml: &str) -> Result<Self, String> {
+        let file: ConfigFile = toml::from_str(toml).map_err(|err| err.message().to_owned())?;
+        let mut contracts: Vec<Contract> = Vec::new();
+        for entry in file.contracts {
+            let address = parse_account(&entry.address)
+                .map_err(|err| format!("`{}`: {}", entry.name, err))?;
+            if contracts.iter().any(|contract| contract.name == entry.name) {
+                return Err(format!("`{}` is listed twice", entry.name));
+            }
+            if contracts.iter().any(|contract| contract.address == address) {
+                return Err(format!(
+                    "`{}` has the address of another contract",
+                    entry.name
+                ));
+            }
+            contracts.push(Contract {
+                name: entry.name,
+                kind: entry.kind,
+                address,
+            });
+        }
+        if contracts.is_empty() {
+            return Err("no contracts to index".to_owned());
+        }
+        Ok(Config { contracts })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let toml =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        Self::parse(&toml).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// The contract at `address`, if it is indexed.
+    pub fn at(&self, address: [u8; 32]) -> Option<&Contract> {
+        self.contracts
+            .iter()
+            .find(|contract| contract.address == address)
+    }
+
+    /// The contract of kind `kind` called `name`, or the only contract of that kind
+    /// if `name` is `None`.
+    pub fn pick(&self, kind: Kind, name: Option<&str>) -> Result<&Contract, String> {
+        let mut of_kind = self
+            .contracts
+            .iter()
+            .filter(|contract| contract.kind == kind);
+        match name {
+            Some(name) => of_kind
+                .find(|contract| contract.name == name)
+                .ok_or_else(|| format!("no {} contract is called `{}`", kind, name)),
+            None => match (of_kind.next(), of_kind.next()) {
+                (Some(contract), None) => Ok(contract),
+                (None, _) => Err(format!("no {} contract is indexed", kind)),
+                (Some(_), Some(_)) => Err(format!(
+                    "several {} contracts are indexed; name one with `contract=`",
+                    kind
+                )),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACTS: &str = r#"
+        [[contract]]
+        name = "course-token"
+        kind = "erc20"
+        address = "0x0101010101010101010101010101010101010101010101010101010101010101"
+
+        [[contract]]
+        name = "reward-token"
+        kind = "erc20"
+        address = "0x0202020202020202020202020202020202020202020202020202020202020202"
+
+        [[contract]]
+        name = "treasury"
+        kind = "treasury"
+        address = "0x0303030303030303030303030303030303030303030303030303030303030303"
+    "#;
+
+    #[test]
+    fn contracts_are_picked_by_kind_and_name() {
+        let config = Config::parse(CONTRACTS).unwrap();
+        assert_eq!(config.at([3; 32]).unwrap().name, "treasury");
+        assert_eq!(config.at([4; 32]), None);
+
+        assert_eq!(config.pick(Kind::Treasury, None).unwrap().address, [3; 32]);
+        assert_eq!(
+            config
+                .pick(Kind::Erc20, Some("reward-token"))
+                .unwrap()
+                .address,
+            [2; 32]
+        );
+        assert!(config
+            .pick(Kind::Erc20, None)
+            .unwrap_err()
+            .contains("several erc20 contracts"));
+        assert_eq!(
+            config.pick(Kind::Erc20, Some("treasury")).unwrap_err(),
+            "no erc20 contract is called `treasury`"
+        );
+        assert_eq!(
+            config.pick(Kind::Erc721, None).unwrap_err(),
+            "no erc721 contract is indexed"
+        );
+    }
+
+    #[test]
+    fn bad_configurations_are_refused() {
+        let twice = format!(
+            "{}{}",
+            CONTRACTS,
+            r#"
+            [[contract]]
+            name = "treasury"
+            kind = "treasury"
+            address = "0x0404040404040404040404040404040404040404040404040404040404040404"
+            "#
+        );
+        assert_eq!(
+            Config::parse(&twice).unwrap_err(),
+            "`treasury` is listed twice"
+        );
+        let unknown = r#"
+            [[contract]]
+            name = "dao"
+            kind = "dao"
+            address = "0x0404040404040404040404040404040404040404040404040404040404040404"
+        "#;
+        assert!(Config::parse(unknown)
+            .unwrap_err()
+            .contains("unknown variant `dao`"));
+        assert_eq!(Config::parse("").unwrap_err(), "no contracts to index");
+    }
+}
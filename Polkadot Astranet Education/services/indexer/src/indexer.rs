@@ -0,0 +1,250 @@
+// services/indexer/src/indexer.rs
+//
+// Reading blocks into the store, one block at a time and in order.
+//
+// By default only finalized blocks are read, and nothing stored is ever taken back.
+// In `Mode::Best` the indexer also reads the blocks after the finalized one, up to
+// the node's best block, so the API is a few seconds fresher. Those blocks can be
+// dropped by a reorg: before reading on, the indexer compares the hashes it stored
+// for them with the node's current chain and rolls back from the first that differs.
+
+use crate::{
+    records,
+    store::{Block, Store},
+    Config, Emitted, EventId, Record,
+};
+use scale::Decode;
+use std::sync::Arc;
+use subxt::{
+    backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+    utils::H256,
+    OnlineClient, SubstrateConfig,
+};
+
+type Client = OnlineClient<SubstrateConfig>;
+
+/// Which blocks the indexer reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Finalized blocks only.
+    Finalized,
+    /// Every block up to the best one, rolling back those a reorg drops.
+    Best,
+}
+
+/// What following the chain did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress<'a> {
+    /// A block was indexed, with these records.
+    Indexed(u32, &'a [Record]),
+    /// A reorg dropped the blocks from this one on, which were rolled back.
+    RolledBack(u32),
+}
+
+pub struct Indexer {
+    rpc: RpcClient,
+    client: Client,
+    config: Config,
+    store: Arc<Store>,
+    mode: Mode,
+    next_block: u32,
+}
+
+impl Indexer {
+    /// Connects to the node at `url`. With an empty store, the indexer starts at
+    /// `from_block`, or at the latest finalized block if it is not given.
+    pub async fn connect(
+        url: &str,
+        config: Config,
+        store: Arc<Store>,
+        mode: Mode,
+        from_block: Option<u32>,
+    ) -> Result<Self, String> {
+        let rpc = RpcClient::from_url(url)
+            .await
+            .map_err(|err| format!("cannot connect to {}: {}", url, err))?;
+        let client = Client::from_rpc_client(rpc.clone())
+            .await
+            .map_err(|err| err.to_string())?;
+        let mut indexer = Indexer {
+            rpc,
+            client,
+            config,
+            store,
+            mode,
+            next_block: 0,
+        };
+        indexer.next_block = match (indexer.store.next_block()?, from_block) {
+            (Some(block), _) | (None, Some(block)) => block,
+            (None, None) => indexer.finalized_block().await?,
+        };
+        Ok(indexer)
+    }
+
+    /// The first block not indexed yet.
+    pub fn next_block(&self) -> u32 {
+        self.next_block
+    }
+
+    /// The number of the latest finalized block.
+    pub async fn finalized_block(&self) -> Result<u32, String> {
+        let methods = self.methods();
+        let hash = methods
+            .chain_get_finalized_head()
+            .await
+            .map_err(|err| format!("chain_getFinalizedHead failed: {}", err))?;
+        self.number(Some(hash)).await
+    }
+
+    /// Indexes every block up to the latest one the mode reads, and returns the
+    /// number of records stored.
+    pub async fn catch_up(&mut self) -> Result<usize, String> {
+        let last = match self.mode {
+            Mode::Finalized => self.finalized_block().await?,
+            Mode::Best => {
+                self.drop_orphans().await?;
+                self.number(None).await?
+            }
+        };
+        let mut stored = 0;
+        while self.next_block <= last {
+            stored += self.index_block(self.next_block).await?.len();
+        }
+        if self.mode == Mode::Best {
+            self.store.finalize(self.finalized_block().await?)?;
+        }
+        Ok(stored)
+    }
+
+    /// Stores the records of block `number`, which must be the next block, and
+    /// returns them.
+    pub async fn index_block(&mut self, number: u32) -> Result<Vec<Record>, String> {
+        let hash = self.block_hash(number).await?;
+        let records = records(&self.config, &self.emitted(number, hash).await?)?;
+        let block = Block {
+            number,
+            hash: hash.0,
+            finalized: self.mode == Mode::Finalized,
+        };
+        self.store.add_block(block, &records)?;
+        self.next_block = number + 1;
+        Ok(records)
+    }
+
+    /// Rolls back the blocks that are no longer on the node's chain, and returns the
+    /// first of them, if any. Only unfinalized blocks are compared, as finalized
+    /// ones cannot change.
+    pub async fn drop_orphans(&mut self) -> Result<Option<u32>, String> {
+        let mut orphaned = None;
+        let mut number = self.next_block;
+        while number > 0 {
+            number -= 1;
+            let block = match self.store.block(number)? {
+                Some(block) if !block.finalized => block,
+                _ => break,
+            };
+            // After a reorg to a shorter fork, the node has no block at the height.
+            if self.canonical_hash(number).await? == Some(H256(block.hash)) {
+                break;
+            }
+            orphaned = Some(number);
+        }
+        if let Some(number) = orphaned {
+            self.store.roll_back(number)?;
+            self.next_block = number;
+        }
+        Ok(orphaned)
+    }
+
+    /// Waits for new blocks and indexes them as they come, until the connection
+    /// fails.
+    pub async fn follow(&mut self, mut on_progress: impl FnMut(Progress)) -> Result<(), String> {
+        let blocks = self.client.blocks();
+        let mut blocks = match self.mode {
+            Mode::Finalized => blocks.subscribe_finalized().await,
+            Mode::Best => blocks.subscribe_best().await,
+        }
+        .map_err(|err| format!("cannot follow blocks: {}", err))?;
+        while let Some(block) = blocks.next().await {
+            let block = block.map_err(|err| format!("lost the block subscription: {}", err))?;
+            if self.mode == Mode::Best {
+                if let Some(number) = self.drop_orphans().await? {
+                    on_progress(Progress::RolledBack(number));
+                }
+            }
+            // The subscription can skip blocks; index everything up to this one.
+            while self.next_block <= block.number() {
+                let number = self.next_block;
+                let records = self.index_block(number).await?;
+                on_progress(Progress::Indexed(number, &records));
+            }
+            if self.mode == Mode::Best {
+                self.store.finalize(self.finalized_block().await?)?;
+            }
+        }
+        Err("the node ended the block subscription".to_owned())
+    }
+
+    /// The `Contracts::ContractEmitted` events of block `number`, whose hash is
+    /// `hash`.
+    async fn emitted(&self, number: u32, hash: H256) -> Result<Vec<Emitted>, String> {
+        let events = self
+            .client
+            .blocks()
+            .at(hash)
+            .await
+            .map_err(|err| format!("block {}: {}", number, err))?
+            .events()
+            .await
+            .map_err(|err| format!("events of block {}: {}", number, err))?;
+        let mut emitted = Vec::new();
+        for event in events.iter() {
+            let event = event.map_err(|err| format!("events of block {}: {}", number, err))?;
+            if event.pallet_name() != "Contracts" || event.variant_name() != "ContractEmitted" {
+                continue;
+            }
+            // `ContractEmitted { contract: AccountId32, data: Vec<u8> }`
+            let (contract, data) = <([u8; 32], Vec<u8>)>::decode(&mut event.field_bytes())
+                .map_err(|err| format!("ContractEmitted in block {}: {}", number, err))?;
+            emitted.push(Emitted {
+                id: EventId {
+                    block: number,
+                    index: event.index(),
+                },
+                contract,
+                data,
+            });
+        }
+        Ok(emitted)
+    }
+
+    /// The hash of block `number` on the node's current chain.
+    async fn block_hash(&self, number: u32) -> Result<H256, String> {
+        self.canonical_hash(number)
+            .await?
+            .ok_or_else(|| format!("the node has no block {}", number))
+    }
+
+    /// The hash of block `number` on the node's current chain, if it has one.
+    async fn canonical_hash(&self, number: u32) -> Result<Option<H256>, String> {
+        self.methods()
+            .chain_get_block_hash(Some(number.into()))
+            .await
+            .map_err(|err| format!("chain_getBlockHash failed: {}", err))
+    }
+
+    /// The number of block `hash`, or of the best block if `hash` is `None`.
+    async fn number(&self, hash: Option<H256>) -> Result<u32, String> {
+        let header = self
+            .methods()
+            .chain_get_header(hash)
+            .await
+            .map_err(|err| format!("chain_getHeader failed: {}", err))?
+            .ok_or("the node does not know the block")?;
+        Ok(header.number)
+    }
+
+    fn methods(&self) -> LegacyRpcMethods<SubstrateConfig> {
+        LegacyRpcMethods::new(self.rpc.clone())
+    }
+}
@@ -0,0 +1,338 @@
+// services/indexer/src/lib.rs
+//
+// The indexer is the reference for the course's lessons on indexing: it follows the
+// chain's finalized blocks, keeps the events of a few example contracts in a SQLite
+// database, and answers questions the contracts themselves cannot, such as who holds
+// a token, how an account's balance changed or what it was at a given block, over a
+// small REST API.
+//
+// Three kinds of contract are indexed (see `config`):
+//
+// - ERC-20 tokens (`examples/demo-contracts/erc20.rs`): every `Transfer`, including
+//   mints and burns, from which balances and their history are computed;
+// - ERC-721 collections (`examples/demo-contracts/erc721.rs`): every mint, that is
+//   every `Transfer` from no one;
+// - treasuries (`examples/demo-contracts/treasury.rs`): every `SpendProposed`, the
+//   proposals a DAO votes on, and whether `SpendExecuted` has paid them out.
+//
+// By default only finalized blocks are read, so nothing stored is ever taken back by
+// a reorg; the indexer can also read up to the best block and roll back what a reorg
+// drops (see `indexer`). A block's records and the number of the next block to read
+// are written in one transaction, so a restarted indexer resumes exactly where it
+// stopped.
+//
+// This module turns a block's events into records; `store` keeps them, `indexer`
+// reads the blocks and `api` serves the results.
+
+pub mod api;
+pub mod config;
+pub mod indexer;
+pub mod sqlite;
+pub mod store;
+
+pub use config::{Config, Contract, Kind};
+pub use store::Store;
+
+use astranet_events::{erc20, erc721, treasury, AccountId};
+use serde::Serialize;
+
+/// An event of the chain, by block number and its index among the block's events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct EventId {
+    pub block: u32,
+    pub index: u32,
+}
+
+/// A `Contracts::ContractEmitted` event of a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Emitted {
+    pub id: EventId,
+    pub contract: [u8; 32],
+    pub data: Vec<u8>,
+}
+
+/// Tokens moved by an ERC-20 contract; `from` is `None` on mint, `to` on burn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer {
+    pub contract: [u8; 32],
+    pub event: EventId,
+    pub from: Option<[u8; 32]>,
+    pub to: Option<[u8; 32]>,
+    pub value: u128,
+}
+
+/// A token created by an ERC-721 contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mint {
+    pub contract: [u8; 32],
+    pub event: EventId,
+    pub token: u32,
+    pub owner: [u8; 32],
+}
+
+/// A spend proposed to a treasury.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proposal {
+    pub contract: [u8; 32],
+    pub event: EventId,
+    pub proposal: u32,
+    pub proposer: [u8; 32],
+    /// The ERC-20 contract the spend is paid from, or `None` for native tokens.
+    pub asset: Option<[u8; 32]>,
+    pub beneficiary: [u8; 32],
+    pub amount: u128,
+    /// The event that paid the spend out, if it was.
+    pub executed: Option<EventId>,
+}
+
+/// What the indexer stores of an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Record {
+    Transfer(Transfer),
+    Mint(Mint),
+    Proposal(Proposal),
+    /// Proposal `proposal` of the treasury `contract` was paid out.
+    Executed {
+        contract: [u8; 32],
+        event: EventId,
+        proposal: u32,
+    },
+}
+
+/// The records of the indexed contracts' events among `emitted`, in order. Fails if
+/// one of their events does not decode, which means a configured address is not a
+/// contract of its kind or the contract's events changed.
+pub fn records(config: &Config, emitted: &[Emitted]) -> Result<Vec<Record>, String> {
+    let mut records = Vec::new();
+    for emitted in emitted {
+        let contract = match config.at(emitted.contract) {
+            Some(contract) => contract,
+            None => continue,
+        };
+        let (id, address) = (emitted.id, emitted.contract);
+        let undecodable = |err| {
+            format!(
+                "cannot decode event {} of block {} as an event of {} `{}`: {}",
+                id.index, id.block, contract.kind, contract.name, err
+            )
+        };
+        let record = match contract.kind {
+            Kind::Erc20 => match astranet_events::decode(&emitted.data).map_err(undecodable)? {
+                erc20::Event::Transfer(erc20::Transfer { from, to, value }) => {
+                    Record::Transfer(Transfer {
+                        contract: address,
+                        event: id,
+                        from: from.map(bytes),
+                        to: to.map(bytes),
+                        value,
+                    })
+                }
+                _ => continue,
+            },
+            Kind::Erc721 => match astranet_events::decode(&emitted.data).map_err(undecodable)? {
+                erc721::Event::Transfer(erc721::Transfer {
+                    from: None,
+                    to: Some(owner),
+                    id: token,
+                }) => Record::Mint(Mint {
+                    contract: address,
+                    event: id,
+                    token,
+                    owner: bytes(owner),
+                }),
+                _ => continue,
+            },
+            Kind::Treasury => match astranet_events::decode(&emitted.data).map_err(undecodable)? {
+                treasury::Event::SpendProposed(treasury::SpendProposed {
+                    proposal_id,
+                    proposer,
+                    asset,
+                    beneficiary,
+                    amount,
+                }) => Record::Proposal(Proposal {
+                    contract: address,
+                    event: id,
+                    proposal: proposal_id,
+                    proposer: bytes(proposer),
+                    asset: match asset {
+                        treasury::Asset::Native => None,
+                        treasury::Asset::Erc20(token) => Some(bytes(token)),
+                    },
+                    beneficiary: bytes(beneficiary),
+                    amount,
+                    executed: None,
+                }),
+                treasury::Event::SpendExecuted(treasury::SpendExecuted { proposal_id, .. }) => {
+                    Record::Executed {
+                        contract: address,
+                        event: id,
+                        proposal: proposal_id,
+                    }
+                }
+                _ => continue,
+            },
+        };
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn bytes(account: AccountId) -> [u8; 32] {
+    *account.as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::Encode;
+
+    fn config() -> Config {
+        Config::parse(
+            r#"
+            [[contract]]
+            name = "token"
+            kind = "erc20"
+            address = "0x0101010101010101010101010101010101010101010101010101010101010101"
+
+            [[contract]]
+            name = "items"
+            kind = "erc721"
+            address = "0x0202020202020202020202020202020202020202020202020202020202020202"
+
+            [[contract]]
+            name = "treasury"
+            kind = "treasury"
+            address = "0x0303030303030303030303030303030303030303030303030303030303030303"
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn emitted(index: u32, contract: u8, event: impl Encode) -> Emitted {
+        Emitted {
+            id: EventId { block: 9, index },
+            contract: [contract; 32],
+            data: event.encode(),
+        }
+    }
+
+    fn account(byte: u8) -> AccountId {
+        AccountId::from([byte; 32])
+    }
+
+    #[test]
+    fn indexed_events_become_records() {
+        let emitted = [
+            emitted(
+                0,
+                1,
+                erc20::Event::Transfer(erc20::Transfer {
+                    from: None,
+                    to: Some(account(0xa)),
+                    value: 100,
+                }),
+            ),
+            // Approvals are not indexed.
+            emitted(
+                1,
+                1,
+                erc20::Event::Approval(erc20::Approval {
+                    owner: account(0xa),
+                    spender: account(0xb),
+                    value: 5,
+                }),
+            ),
+            emitted(
+                2,
+                2,
+                erc721::Event::Transfer(erc721::Transfer {
+                    from: None,
+                    to: Some(account(0xb)),
+                    id: 7,
+                }),
+            ),
+            // Nor are NFT transfers other than mints.
+            emitted(
+                3,
+                2,
+                erc721::Event::Transfer(erc721::Transfer {
+                    from: Some(account(0xb)),
+                    to: Some(account(0xa)),
+                    id: 7,
+                }),
+            ),
+            emitted(
+                4,
+                3,
+                treasury::Event::SpendProposed(treasury::SpendProposed {
+                    proposal_id: 4,
+                    proposer: account(0xa),
+                    asset: treasury::Asset::Erc20(account(1)),
+                    beneficiary: account(0xb),
+                    amount: 50,
+                }),
+            ),
+            emitted(
+                5,
+                3,
+                treasury::Event::SpendExecuted(treasury::SpendExecuted {
+                    proposal_id: 4,
+                    beneficiary: account(0xb),
+                    amount: 50,
+                }),
+            ),
+            // Events of other contracts are skipped without decoding.
+            Emitted {
+                id: EventId { block: 9, index: 6 },
+                contract: [9; 32],
+                data: vec![0xff],
+            },
+        ];
+        let at = |index| EventId { block: 9, index };
+        assert_eq!(
+            records(&config(), &emitted).unwrap(),
+            [
+                Record::Transfer(Transfer {
+                    contract: [1; 32],
+                    event: at(0),
+                    from: None,
+                    to: Some([0xa; 32]),
+                    value: 100,
+                }),
+                Record::Mint(Mint {
+                    contract: [2; 32],
+                    event: at(2),
+                    token: 7,
+                    owner: [0xb; 32],
+                }),
+                Record::Proposal(Proposal {
+                    contract: [3; 32],
+                    event: at(4),
+                    proposal: 4,
+                    proposer: [0xa; 32],
+                    asset: Some([1; 32]),
+                    beneficiary: [0xb; 32],
+                    amount: 50,
+                    executed: None,
+                }),
+                Record::Executed {
+                    contract: [3; 32],
+                    event: at(5),
+                    proposal: 4,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn undecodable_events_are_errors() {
+        let emitted = [Emitted {
+            id: EventId { block: 9, index: 2 },
+            contract: [3; 32],
+            data: vec![0xff],
+        }];
+        assert!(records(&config(), &emitted)
+            .unwrap_err()
+            .starts_with("cannot decode event 2 of block 9 as an event of treasury `treasury`"));
+    }
+}
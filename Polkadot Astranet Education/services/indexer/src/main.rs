@@ -0,0 +1,126 @@
+// services/indexer/src/main.rs
+//
+// Usage:
+//   indexer --config indexer.toml
+//   indexer --config indexer.toml --from-block 1 --port 9956
+//   indexer --config indexer.toml --network shibuya --db /var/lib/indexer.sqlite
+//   indexer --config indexer.toml --best
+//
+// Indexes the contracts in `--config` (see `config` in the library) into the SQLite
+// database `--db`, and serves the REST API (see `api`) on `127.0.0.1:--port`.
+// `--network` picks the node and the address format of accounts from the deployer's
+// network profiles; `--url` overrides the node.
+//
+// The first run starts at the latest finalized block, or at `--from-block`; later runs
+// resume from the database. The indexer catches up on the blocks it missed, then
+// follows new finalized blocks until the connection fails, when it exits with 1; run
+// it under a supervisor that restarts it. With `--best` it follows the best block
+// instead, rolling back the blocks a reorg drops (see `indexer` in the library).
+
+use clap::Parser;
+use deployer::Networks;
+use indexer::{
+    api::{self, Api},
+    indexer::{Indexer, Mode, Progress},
+    store::DATABASE_FILE,
+    Config, Store,
+};
+use std::{fs, net::Ipv4Addr, path::PathBuf, process::ExitCode, sync::Arc};
+use tokio::net::TcpListener;
+
+#[derive(Parser)]
+#[command(about = "Index the example contracts' events into SQLite and serve them over REST")]
+struct Cli {
+    /// The contracts to index.
+    #[arg(long)]
+    config: PathBuf,
+    /// SQLite database to keep the index in.
+    #[arg(long, default_value = DATABASE_FILE)]
+    db: PathBuf,
+    /// Block to start at when the database is empty.
+    #[arg(long)]
+    from_block: Option<u32>,
+    /// Also index blocks that are not finalized yet.
+    #[arg(long)]
+    best: bool,
+    /// Port of the REST API, on 127.0.0.1.
+    #[arg(long, default_value_t = 9956)]
+    port: u16,
+    /// Network profile name, or the WebSocket URL of a node.
+    #[arg(long, default_value = "local")]
+    network: String,
+    /// File of network profiles to add to the built-in ones.
+    #[arg(long)]
+    networks: Option<PathBuf>,
+    /// WebSocket URL of the node, instead of the profile's.
+    #[arg(long)]
+    url: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
+    let mut networks = Networks::builtin();
+    if let Some(path) = &cli.networks {
+        let toml =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        networks
+            .extend(Networks::parse(&toml).map_err(|err| format!("{}: {}", path.display(), err))?);
+    }
+    let network = networks.get(&cli.network)?;
+    let url = cli.url.unwrap_or(network.url);
+    let config = Config::load(&cli.config)?;
+    let store = Arc::new(Store::open(&cli.db)?);
+
+    let mode = if cli.best {
+        Mode::Best
+    } else {
+        Mode::Finalized
+    };
+    let mut indexer =
+        Indexer::connect(&url, config.clone(), store.clone(), mode, cli.from_block).await?;
+    println!(
+        "connected to {} ({}), starting at block {}",
+        cli.network,
+        url,
+        indexer.next_block()
+    );
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, cli.port))
+        .await
+        .map_err(|err| format!("cannot listen on port {}: {}", cli.port, err))?;
+    println!("serving the API at http://127.0.0.1:{}/status", cli.port);
+    let api = Arc::new(Api::new(config, store, network.ss58_prefix));
+
+    let index = async {
+        let stored = indexer.catch_up().await?;
+        println!(
+            "caught up to block {}, {} records stored",
+            indexer.next_block() - 1,
+            stored
+        );
+        indexer
+            .follow(|progress| match progress {
+                Progress::Indexed(block, records) if !records.is_empty() => {
+                    println!("block {}: {} records stored", block, records.len());
+                }
+                Progress::Indexed(..) => {}
+                Progress::RolledBack(block) => {
+                    println!("reorg: rolled back to block {}", block);
+                }
+            })
+            .await
+    };
+    tokio::select! {
+        result = index => result,
+        err = api::serve(listener, api) => Err(err),
+    }
+}
@@ -0,0 +1,442 @@
+// services/indexer/src/sqlite.rs
+//
+// A minimal binding to the system's SQLite library (`libsqlite3`, which every Linux
+// distribution and macOS ship), covering what the store needs: opening a database,
+// running statements with bound parameters and reading their rows. Each call
+// prepares, runs and finalizes its statement, so no statement outlives the call and
+// the unsafe code stays in this file.
+
+use std::{
+    ffi::{c_char, c_int, c_void, CStr, CString},
+    path::Path,
+    ptr,
+};
+
+#[repr(C)]
+struct RawDb {
+    _private: [u8; 0],
+}
+
+#[repr(C)]
+struct RawStmt {
+    _private: [u8; 0],
+}
+
+const SQLITE_OK: c_int = 0;
+const SQLITE_ROW: c_int = 100;
+const SQLITE_DONE: c_int = 101;
+
+const SQLITE_INTEGER: c_int = 1;
+const SQLITE_FLOAT: c_int = 2;
+const SQLITE_TEXT: c_int = 3;
+const SQLITE_BLOB: c_int = 4;
+
+const SQLITE_OPEN_READWRITE: c_int = 0x0000_0002;
+const SQLITE_OPEN_CREATE: c_int = 0x0000_0004;
+const SQLITE_OPEN_FULLMUTEX: c_int = 0x0001_0000;
+
+/// Tells SQLite to copy a bound text or blob before the call returns.
+const SQLITE_TRANSIENT: isize = -1;
+
+#[link(name = "sqlite3")]
+extern "C" {
+    fn sqlite3_open_v2(
+        filename: *const c_char,
+        db: *mut *mut RawDb,
+        flags: c_int,
+        vfs: *const c_char,
+    ) -> c_int;
+    fn sqlite3_close(db: *mut RawDb) -> c_int;
+    fn sqlite3_errmsg(db: *mut RawDb) -> *const c_char;
+    fn sqlite3_changes(db: *mut RawDb) -> c_int;
+    fn sqlite3_prepare_v2(
+        db: *mut RawDb,
+        sql: *const c_char,
+        len: c_int,
+        stmt: *mut *mut RawStmt,
+        tail: *mut *const c_char,
+    ) -> c_int;
+    fn sqlite3_finalize(stmt: *mut RawStmt) -> c_int;
+    fn sqlite3_step(stmt: *mut RawStmt) -> c_int;
+    fn sqlite3_bind_null(stmt: *mut RawStmt, index: c_int) -> c_int;
+    fn sqlite3_bind_int64(stmt: *mut RawStmt, index: c_int, value: i64) -> c_int;
+    fn sqlite3_bind_text(
+        stmt: *mut RawStmt,
+        index: c_int,
+        value: *const c_char,
+        len: c_int,
+        destructor: isize,
+    ) -> c_int;
+    fn sqlite3_bind_blob(
+        stmt: *mut RawStmt,
+        index: c_int,
+        value: *const c_void,
+        len: c_int,
+        destructor: isize,
+    ) -> c_int;
+    fn sqlite3_column_count(stmt: *mut RawStmt) -> c_int;
+    fn sqlite3_column_type(stmt: *mut RawStmt, column: c_int) -> c_int;
+    fn sqlite3_column_int64(stmt: *mut RawStmt, column: c_int) -> i64;
+    fn sqlite3_column_text(stmt: *mut RawStmt, column: c_int) -> *const u8;
+    fn sqlite3_column_blob(stmt: *mut RawStmt, column: c_int) -> *const c_void;
+    fn sqlite3_column_bytes(stmt: *mut RawStmt, column: c_int) -> c_int;
+}
+
+/// A value bound to a statement or read from a row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Null,
+    Integer(i64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl Value {
+    pub fn as_i64(&self) -> Result<i64, String> {
+        match self {
+            Value::Integer(value) => Ok(*value),
+            other => Err(format!("expected an integer, found {:?}", other)),
+        }
+    }
+
+    pub fn as_text(&self) -> Result<&str, String> {
+        match self {
+            Value::Text(value) => Ok(value),
+            other => Err(format!("expected text, found {:?}", other)),
+        }
+    }
+
+    /// The value as a 32-byte account, or `None` if it is null.
+    pub fn as_account(&self) -> Result<Option<[u8; 32]>, String> {
+        match self {
+            Value::Null => Ok(None),
+            Value::Blob(bytes) => bytes
+                .as_slice()
+                .try_into()
+                .map(Some)
+                .map_err(|_| format!("expected 32 bytes, found {}", bytes.len())),
+            other => Err(format!("expected an account, found {:?}", other)),
+        }
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Integer(value)
+    }
+}
+
+impl From<u32> for Value {
+    fn from(value: u32) -> Self {
+        Value::Integer(value.into())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Text(value.to_owned())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl From<[u8; 32]> for Value {
+    fn from(value: [u8; 32]) -> Self {
+        Value::Blob(value.to_vec())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+/// An open database.
+pub struct Connection {
+    db: *mut RawDb,
+}
+
+// The connection is opened in SQLite's serialized mode, where one connection may be
+// used from any thread.
+unsafe impl Send for Connection {}
+
+impl Connection {
+    /// Opens the database at `path`, creating it if it does not exist.
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let name = path
+            .to_str()
+            .ok_or_else(|| format!("{}: not a UTF-8 path", path.display()))?;
+        Self::open_named(name).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// Opens a database that lives in memory until it is dropped.
+    pub fn open_in_memory() -> Result<Self, String> {
+        Self::open_named(":memory:")
+    }
+
+    fn open_named(name: &str) -> Result<Self, String> {
+        let name = CString::new(name).map_err(|err| err.to_string())?;
+        let mut db = ptr::null_mut();
+        let flags = SQLITE_OPEN_READWRITE | SQLITE_OPEN_CREATE | SQLITE_OPEN_FULLMUTEX;
+        let code = unsafe { sqlite3_open_v2(name.as_ptr(), &mut db, flags, ptr::null()) };
+        // SQLite allocates a handle even when opening fails, to carry the error.
+        let connection = Connection { db };
+        if code != SQLITE_OK {
+            return Err(connection.error());
+        }
+        Ok(connection)
+    }
+
+    /// Runs `sql`, which may be several statements separated by `;`, without
+    /// parameters.
+    pub fn execute_batch(&self, sql: &str) -> Result<(), String> {
+        let mut rest = sql.trim();
+        while !rest.is_empty() {
+            let (stmt, tail) = self.prepare(rest)?;
+            if !stmt.0.is_null() {
+                stmt.run(&[], |_| Ok(()))?;
+            }
+            rest = tail.trim();
+        }
+        Ok(())
+    }
+
+    /// Runs the statement `sql` with `params` bound to its `?` placeholders, and
+    /// returns the number of rows it changed.
+    pub fn execute(&self, sql: &str, params: &[Value]) -> Result<usize, String> {
+        let (stmt, _) = self.prepare(sql)?;
+        stmt.run(params, |_| Ok(()))?;
+        Ok(unsafe { sqlite3_changes(self.db) } as usize)
+    }
+
+    /// Runs the query `sql` with `params` bound to its `?` placeholders, and maps its
+    /// rows with `row`.
+    pub fn query<T>(
+        &self,
+        sql: &str,
+        params: &[Value],
+        mut row: impl FnMut(&[Value]) -> Result<T, String>,
+    ) -> Result<Vec<T>, String> {
+        let (stmt, _) = self.prepare(sql)?;
+        let mut rows = Vec::new();
+        stmt.run(params, |values| {
+            rows.push(row(values)?);
+            Ok(())
+        })?;
+        Ok(rows)
+    }
+
+    /// Runs `body` in a transaction, committed if it succeeds and rolled back if it
+    /// fails.
+    pub fn transaction<T>(
+        &self,
+        body: impl FnOnce(&Self) -> Result<T, String>,
+    ) -> Result<T, String> {
+        self.execute_batch("BEGIN")?;
+        match body(self) {
+            Ok(value) => {
+                self.execute_batch("COMMIT")?;
+                Ok(value)
+            }
+            Err(err) => {
+                // The error that made the transaction fail matters more than one
+                // from rolling back.
+                let _ = self.execute_batch("ROLLBACK");
+                Err(err)
+            }
+        }
+    }
+
+    /// Prepares the first statement of `sql`, returning it and the rest of `sql`.
+    fn prepare<'a>(&self, sql: &'a str) -> Result<(Statement<'_>, &'a str), String> {
+        let len = c_int::try_from(sql.len()).map_err(|_| "statement too long".to_owned())?;
+        let mut stmt = ptr::null_mut();
+        let mut tail = ptr::null();
+        let code =
+            unsafe { sqlite3_prepare_v2(self.db, sql.as_ptr().cast(), len, &mut stmt, &mut tail) };
+        if code != SQLITE_OK {
+            return Err(self.error());
+        }
+        let used = tail as usize - sql.as_ptr() as usize;
+        Ok((
+            Statement(stmt, self),
+            // SQLite stops after the `;` ending the statement, an ASCII byte.
+            &sql[used..],
+        ))
+    }
+
+    fn error(&self) -> String {
+        if self.db.is_null() {
+            return "out of memory".to_owned();
+        }
+        unsafe { CStr::from_ptr(sqlite3_errmsg(self.db)) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        unsafe {
+            sqlite3_close(self.db);
+        }
+    }
+}
+
+/// A prepared statement, finalized when dropped. The pointer is null if the SQL
+/// held only whitespace or comments.
+struct Statement<'c>(*mut RawStmt, &'c Connection);
+
+impl Statement<'_> {
+    fn run(
+        &self,
+        params: &[Value],
+        mut row: impl FnMut(&[Value]) -> Result<(), String>,
+    ) -> Result<(), String> {
+        for (i, param) in params.iter().enumerate() {
+            self.bind(i as c_int + 1, param)?;
+        }
+        loop {
+            match unsafe { sqlite3_step(self.0) } {
+                SQLITE_ROW => row(&self.columns()?)?,
+                SQLITE_DONE => return Ok(()),
+                _ => return Err(self.1.error()),
+            }
+        }
+    }
+
+    fn bind(&self, index: c_int, param: &Value) -> Result<(), String> {
+        let code = unsafe {
+            match param {
+                Value::Null => sqlite3_bind_null(self.0, index),
+                Value::Integer(value) => sqlite3_bind_int64(self.0, index, *value),
+                Value::Text(text) => sqlite3_bind_text(
+                    self.0,
+                    index,
+                    text.as_ptr().cast(),
+                    length(text.len())?,
+                    SQLITE_TRANSIENT,
+                ),
+                Value::Blob(bytes) => sqlite3_bind_blob(
+                    self.0,
+                    index,
+                    bytes.as_ptr().cast(),
+                    length(bytes.len())?,
+                    SQLITE_TRANSIENT,
+                ),
+            }
+        };
+        if code != SQLITE_OK {
+            return Err(self.1.error());
+        }
+        Ok(())
+    }
+
+    fn columns(&self) -> Result<Vec<Value>, String> {
+        let count = unsafe { sqlite3_column_count(self.0) };
+        (0..count)
+            .map(|column| unsafe {
+                match sqlite3_column_type(self.0, column) {
+                    SQLITE_INTEGER => Ok(Value::Integer(sqlite3_column_int64(self.0, column))),
+                    SQLITE_TEXT => {
+                        let text = sqlite3_column_text(self.0, column);
+                        let len = sqlite3_column_bytes(self.0, column) as usize;
+                        Ok(Value::Text(
+                            String::from_utf8_lossy(std::slice::from_raw_parts(text, len))
+                                .into_owned(),
+                        ))
+                    }
+                    SQLITE_BLOB => {
+                        let blob = sqlite3_column_blob(self.0, column);
+                        let len = sqlite3_column_bytes(self.0, column) as usize;
+                        Ok(Value::Blob(if len == 0 {
+                            Vec::new()
+                        } else {
+                            std::slice::from_raw_parts(blob.cast::<u8>(), len).to_vec()
+                        }))
+                    }
+                    SQLITE_FLOAT => Err("the store keeps no floating-point values".to_owned()),
+                    _ => Ok(Value::Null),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Drop for Statement<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            sqlite3_finalize(self.0);
+        }
+    }
+}
+
+fn length(len: usize) -> Result<c_int, String> {
+    c_int::try_from(len).map_err(|_| "value too long to bind".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_round_trip() {
+        let db = Connection::open_in_memory().unwrap();
+        db.execute_batch(
+            "CREATE TABLE t (n INTEGER, s TEXT, b BLOB);
+             -- a comment after the last statement",
+        )
+        .unwrap();
+        let changed = db
+            .execute(
+                "INSERT INTO t VALUES (?, ?, ?), (?, ?, ?)",
+                &[
+                    7i64.into(),
+                    "seven".into(),
+                    [7; 32].into(),
+                    Value::Null,
+                    "".into(),
+                    Value::Blob(Vec::new()),
+                ],
+            )
+            .unwrap();
+        assert_eq!(changed, 2);
+        let rows = db
+            .query("SELECT n, s, b FROM t ORDER BY n", &[], |row| {
+                Ok(row.to_vec())
+            })
+            .unwrap();
+        assert_eq!(
+            rows,
+            [
+                vec![Value::Null, "".into(), Value::Blob(Vec::new())],
+                vec![7i64.into(), "seven".into(), [7; 32].into()],
+            ]
+        );
+        assert_eq!(rows[1][2].as_account().unwrap(), Some([7; 32]));
+    }
+
+    #[test]
+    fn failed_transactions_roll_back() {
+        let db = Connection::open_in_memory().unwrap();
+        db.execute_batch("CREATE TABLE t (n INTEGER PRIMARY KEY)")
+            .unwrap();
+        let err = db
+            .transaction(|db| {
+                db.execute("INSERT INTO t VALUES (1)", &[])?;
+                db.execute("INSERT INTO t VALUES (1)", &[])
+            })
+            .unwrap_err();
+        assert!(err.contains("UNIQUE"), "{}", err);
+        let count = db
+            .query("SELECT COUNT(*) FROM t", &[], |row| row[0].as_i64())
+            .unwrap();
+        assert_eq!(count, [0]);
+        assert!(db.execute("SELECT nonsense FROM t", &[]).is_err());
+    }
+}
@@ -0,0 +1,635 @@
+// services/indexer/src/store.rs
+//
+// The SQLite database the indexer writes and the API reads. It has one table per
+// kind of record, keyed by the event that produced it, so storing a block twice
+// stores nothing new, a table of the blocks read, with their hashes and whether they
+// were finalized, and a one-row table holding the next block to index.
+//
+// A block that was not finalized can be rolled back: its records and those of every
+// later block are deleted, and indexing resumes from it. Finalized blocks are never
+// rolled back.
+//
+// SQLite integers are 64-bit, so token amounts are stored as decimal text and
+// balances are added up here rather than with `SUM`. Accounts and contracts are
+// stored as their 32 bytes.
+
+use crate::{
+    sqlite::{Connection, Value},
+    EventId, Mint, Proposal, Record, Transfer,
+};
+use std::{collections::BTreeMap, path::Path, sync::Mutex};
+
+/// File name of the database, by default in the working directory.
+pub const DATABASE_FILE: &str = "indexer.sqlite";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS cursor (
+        id INTEGER PRIMARY KEY CHECK (id = 0),
+        next_block INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS blocks (
+        number INTEGER PRIMARY KEY,
+        hash BLOB NOT NULL,
+        finalized INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS transfers (
+        contract BLOB NOT NULL,
+        block INTEGER NOT NULL,
+        event_index INTEGER NOT NULL,
+        sender BLOB,
+        recipient BLOB,
+        value TEXT NOT NULL,
+        PRIMARY KEY (block, event_index)
+    );
+    CREATE INDEX IF NOT EXISTS transfers_by_contract
+        ON transfers (contract, block, event_index);
+    CREATE TABLE IF NOT EXISTS mints (
+        contract BLOB NOT NULL,
+        block INTEGER NOT NULL,
+        event_index INTEGER NOT NULL,
+        token INTEGER NOT NULL,
+        owner BLOB NOT NULL,
+        PRIMARY KEY (block, event_index)
+    );
+    CREATE TABLE IF NOT EXISTS proposals (
+        contract BLOB NOT NULL,
+        proposal INTEGER NOT NULL,
+        block INTEGER NOT NULL,
+        event_index INTEGER NOT NULL,
+        proposer BLOB NOT NULL,
+        asset BLOB,
+        beneficiary BLOB NOT NULL,
+        amount TEXT NOT NULL,
+        executed_block INTEGER,
+        executed_index INTEGER,
+        PRIMARY KEY (contract, proposal)
+    );
+";
+
+/// A block the indexer has read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block {
+    pub number: u32,
+    pub hash: [u8; 32],
+    /// Whether the block was finalized when it was last seen.
+    pub finalized: bool,
+}
+
+/// A change in an account's balance, and the balance after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceChange {
+    pub transfer: Transfer,
+    pub balance: u128,
+}
+
+pub struct Store {
+    db: Mutex<Connection>,
+}
+
+impl Store {
+    /// Opens the database at `path`, creating it and its tables if needed.
+    pub fn open(path: &Path) -> Result<Self, String> {
+        Self::with(Connection::open(path)?)
+    }
+
+    /// A store that lives in memory, for tests.
+    pub fn in_memory() -> Result<Self, String> {
+        Self::with(Connection::open_in_memory()?)
+    }
+
+    fn with(db: Connection) -> Result<Self, String> {
+        db.execute_batch(SCHEMA)?;
+        Ok(Store { db: Mutex::new(db) })
+    }
+
+    /// The first block not indexed yet, or `None` if nothing was indexed.
+    pub fn next_block(&self) -> Result<Option<u32>, String> {
+        let rows = self
+            .db()
+            .query("SELECT next_block FROM cursor", &[], block)?;
+        Ok(rows.into_iter().next())
+    }
+
+    /// The first block indexed, or `None` if nothing was indexed.
+    pub fn first_block(&self) -> Result<Option<u32>, String> {
+        let rows = self.db().query(
+            "SELECT number FROM blocks ORDER BY number LIMIT 1",
+            &[],
+            block,
+        )?;
+        Ok(rows.into_iter().next())
+    }
+
+    /// The latest finalized block indexed, or `None` if there is none.
+    pub fn finalized_block(&self) -> Result<Option<u32>, String> {
+        let rows = self.db().query(
+            "SELECT number FROM blocks WHERE finalized = 1 ORDER BY number DESC LIMIT 1",
+            &[],
+            block,
+        )?;
+        Ok(rows.into_iter().next())
+    }
+
+    /// Block `number`, if it was indexed.
+    pub fn block(&self, number: u32) -> Result<Option<Block>, String> {
+        let rows = self.db().query(
+            "SELECT number, hash, finalized FROM blocks WHERE number = ?",
+            &[number.into()],
+            |row| {
+                Ok(Block {
+                    number: block(row)?,
+                    hash: account(&row[1])?,
+                    finalized: row[2].as_i64()? != 0,
+                })
+            },
+        )?;
+        Ok(rows.into_iter().next())
+    }
+
+    /// Stores `block` and its records and moves on to the next block, all or nothing.
+    pub fn add_block(&self, block: Block, records: &[Record]) -> Result<(), String> {
+        self.db().transaction(|db| {
+            for record in records {
+                add_record(db, record)?;
+            }
+            db.execute(
+                "INSERT OR REPLACE INTO blocks (number, hash, finalized) VALUES (?, ?, ?)",
+                &[
+                    block.number.into(),
+                    block.hash.into(),
+                    i64::from(block.finalized).into(),
+                ],
+            )?;
+            set_next_block(db, block.number + 1)
+        })
+    }
+
+    /// Marks every indexed block up to `number` finalized.
+    pub fn finalize(&self, number: u32) -> Result<(), String> {
+        self.db()
+            .execute(
+                "UPDATE blocks SET finalized = 1 WHERE number <= ? AND finalized = 0",
+                &[number.into()],
+            )
+            .map(drop)
+    }
+
+    /// Forgets block `number`, every later block and their records, so that indexing
+    /// resumes from `number`. Fails if one of the blocks was finalized.
+    pub fn roll_back(&self, number: u32) -> Result<(), String> {
+        self.db().transaction(|db| {
+            let finalized = db.query(
+                "SELECT number FROM blocks WHERE number >= ? AND finalized = 1 LIMIT 1",
+                &[number.into()],
+                block,
+            )?;
+            if let Some(finalized) = finalized.first() {
+                return Err(format!(
+                    "cannot roll back to block {}: block {} is finalized",
+                    number, finalized
+                ));
+            }
+            for table in ["transfers", "mints", "proposals", "blocks"] {
+                let column = if table == "blocks" { "number" } else { "block" };
+                db.execute(
+                    &format!("DELETE FROM {} WHERE {} >= ?", table, column),
+                    &[number.into()],
+                )?;
+            }
+            db.execute(
+                "UPDATE proposals SET executed_block = NULL, executed_index = NULL
+                 WHERE executed_block >= ?",
+                &[number.into()],
+            )?;
+            set_next_block(db, number)
+        })
+    }
+
+    /// The transfers of the ERC-20 `contract`, oldest first.
+    pub fn transfers(&self, contract: [u8; 32]) -> Result<Vec<Transfer>, String> {
+        self.db().query(
+            "SELECT contract, block, event_index, sender, recipient, value FROM transfers
+             WHERE contract = ? ORDER BY block, event_index",
+            &[contract.into()],
+            transfer,
+        )
+    }
+
+    /// The accounts holding tokens of `contract` and their balances, largest first.
+    pub fn holders(&self, contract: [u8; 32]) -> Result<Vec<([u8; 32], u128)>, String> {
+        let mut balances = BTreeMap::new();
+        for transfer in self.transfers(contract)? {
+            if let Some(from) = transfer.from {
+                let balance: &mut u128 = balances.entry(from).or_default();
+                *balance = balance.saturating_sub(transfer.value);
+            }
+            if let Some(to) = transfer.to {
+                let balance: &mut u128 = balances.entry(to).or_default();
+                *balance = balance.saturating_add(transfer.value);
+            }
+        }
+        let mut holders: Vec<_> = balances
+            .into_iter()
+            .filter(|(_, balance)| *balance > 0)
+            .collect();
+        holders.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        Ok(holders)
+    }
+
+    /// The transfers of `contract` to or from `account`, oldest first, each with the
+    /// account's balance after it.
+    pub fn history(
+        &self,
+        contract: [u8; 32],
+        account: [u8; 32],
+    ) -> Result<Vec<BalanceChange>, String> {
+        let transfers = self.db().query(
+            "SELECT contract, block, event_index, sender, recipient, value FROM transfers
+             WHERE contract = ? AND (sender = ? OR recipient = ?)
+             ORDER BY block, event_index",
+            &[contract.into(), account.into(), account.into()],
+            transfer,
+        )?;
+        let mut balance = 0u128;
+        Ok(transfers
+            .into_iter()
+            .map(|transfer| {
+                balance = apply(balance, account, &transfer);
+                BalanceChange { transfer, balance }
+            })
+            .collect())
+    }
+
+    /// The balance of `account` in `contract` at the end of block `number`, summed
+    /// from the transfers indexed up to it. It is the contract's balance only if
+    /// indexing started before the contract was deployed.
+    pub fn balance_at(
+        &self,
+        contract: [u8; 32],
+        account: [u8; 32],
+        number: u32,
+    ) -> Result<u128, String> {
+        let transfers = self.db().query(
+            "SELECT contract, block, event_index, sender, recipient, value FROM transfers
+             WHERE contract = ? AND (sender = ? OR recipient = ?) AND block <= ?
+             ORDER BY block, event_index",
+            &[
+                contract.into(),
+                account.into(),
+                account.into(),
+                number.into(),
+            ],
+            transfer,
+        )?;
+        Ok(transfers
+            .iter()
+            .fold(0, |balance, transfer| apply(balance, account, transfer)))
+    }
+
+    /// The mints of the ERC-721 `contract`, oldest first.
+    pub fn mints(&self, contract: [u8; 32]) -> Result<Vec<Mint>, String> {
+        self.db().query(
+            "SELECT contract, block, event_index, token, owner FROM mints
+             WHERE contract = ? ORDER BY block, event_index",
+            &[contract.into()],
+            |row| {
+                Ok(Mint {
+                    contract: account(&row[0])?,
+                    event: event(&row[1], &row[2])?,
+                    token: u32::try_from(row[3].as_i64()?).map_err(|err| err.to_string())?,
+                    owner: account(&row[4])?,
+                })
+            },
+        )
+    }
+
+    /// The spends proposed to the treasury `contract`, by proposal id.
+    pub fn proposals(&self, contract: [u8; 32]) -> Result<Vec<Proposal>, String> {
+        self.db().query(
+            "SELECT contract, proposal, block, event_index, proposer, asset, beneficiary,
+                    amount, executed_block, executed_index
+             FROM proposals WHERE contract = ? ORDER BY proposal",
+            &[contract.into()],
+            |row| {
+                Ok(Proposal {
+                    contract: account(&row[0])?,
+                    proposal: u32::try_from(row[1].as_i64()?).map_err(|err| err.to_string())?,
+                    event: event(&row[2], &row[3])?,
+                    proposer: account(&row[4])?,
+                    asset: row[5].as_account()?,
+                    beneficiary: account(&row[6])?,
+                    amount: amount(&row[7])?,
+                    executed: match row[8] {
+                        Value::Null => None,
+                        _ => Some(event(&row[8], &row[9])?),
+                    },
+                })
+            },
+        )
+    }
+
+    fn db(&self) -> std::sync::MutexGuard<'_, Connection> {
+        // A panic while holding the lock cannot leave a transaction half-applied,
+        // as SQLite rolls it back, so the connection is still usable.
+        self.db
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The balance of `account` after `transfer`, given the balance before it.
+fn apply(balance: u128, account: [u8; 32], transfer: &Transfer) -> u128 {
+    let mut balance = balance;
+    if transfer.from == Some(account) {
+        balance = balance.saturating_sub(transfer.value);
+    }
+    if transfer.to == Some(account) {
+        balance = balance.saturating_add(transfer.value);
+    }
+    balance
+}
+
+fn set_next_block(db: &Connection, number: u32) -> Result<(), String> {
+    db.execute(
+        "INSERT INTO cursor (id, next_block) VALUES (0, ?)
+         ON CONFLICT (id) DO UPDATE SET next_block = excluded.next_block",
+        &[number.into()],
+    )
+    .map(drop)
+}
+
+fn add_record(db: &Connection, record: &Record) -> Result<(), String> {
+    match record {
+        Record::Transfer(transfer) => db.execute(
+            "INSERT OR IGNORE INTO transfers
+             (contract, block, event_index, sender, recipient, value)
+             VALUES (?, ?, ?, ?, ?, ?)",
+            &[
+                transfer.contract.into(),
+                transfer.event.block.into(),
+                transfer.event.index.into(),
+                transfer.from.into(),
+                transfer.to.into(),
+                transfer.value.to_string().into(),
+            ],
+        ),
+        Record::Mint(mint) => db.execute(
+            "INSERT OR IGNORE INTO mints (contract, block, event_index, token, owner)
+             VALUES (?, ?, ?, ?, ?)",
+            &[
+                mint.contract.into(),
+                mint.event.block.into(),
+                mint.event.index.into(),
+                mint.token.into(),
+                mint.owner.into(),
+            ],
+        ),
+        Record::Proposal(proposal) => db.execute(
+            "INSERT OR IGNORE INTO proposals
+             (contract, proposal, block, event_index, proposer, asset, beneficiary, amount)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            &[
+                proposal.contract.into(),
+                proposal.proposal.into(),
+                proposal.event.block.into(),
+                proposal.event.index.into(),
+                proposal.proposer.into(),
+                proposal.asset.into(),
+                proposal.beneficiary.into(),
+                proposal.amount.to_string().into(),
+            ],
+        ),
+        // A proposal made before the indexer's first block is not stored, so there
+        // may be nothing to update.
+        Record::Executed {
+            contract,
+            event,
+            proposal,
+        } => db.execute(
+            "UPDATE proposals SET executed_block = ?, executed_index = ?
+             WHERE contract = ? AND proposal = ?",
+            &[
+                event.block.into(),
+                event.index.into(),
+                (*contract).into(),
+                (*proposal).into(),
+            ],
+        ),
+    }
+    .map(drop)
+}
+
+fn transfer(row: &[Value]) -> Result<Transfer, String> {
+    Ok(Transfer {
+        contract: account(&row[0])?,
+        event: event(&row[1], &row[2])?,
+        from: row[3].as_account()?,
+        to: row[4].as_account()?,
+        value: amount(&row[5])?,
+    })
+}
+
+fn block(row: &[Value]) -> Result<u32, String> {
+    u32::try_from(row[0].as_i64()?).map_err(|err| err.to_string())
+}
+
+fn event(block: &Value, index: &Value) -> Result<EventId, String> {
+    let number = |value: &Value| u32::try_from(value.as_i64()?).map_err(|err| err.to_string());
+    Ok(EventId {
+        block: number(block)?,
+        index: number(index)?,
+    })
+}
+
+fn account(value: &Value) -> Result<[u8; 32], String> {
+    value
+        .as_account()?
+        .ok_or_else(|| "expected an account, found NULL".to_owned())
+}
+
+fn amount(value: &Value) -> Result<u128, String> {
+    value
+        .as_text()?
+        .parse()
+        .map_err(|err| format!("bad amount: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: [u8; 32] = [1; 32];
+    const ALICE: [u8; 32] = [0xa; 32];
+    const BOB: [u8; 32] = [0xb; 32];
+
+    fn transfer(
+        block: u32,
+        index: u32,
+        from: Option<[u8; 32]>,
+        to: Option<[u8; 32]>,
+        value: u128,
+    ) -> Record {
+        Record::Transfer(Transfer {
+            contract: TOKEN,
+            event: EventId { block, index },
+            from,
+            to,
+            value,
+        })
+    }
+
+    fn finalized(number: u32) -> Block {
+        Block {
+            number,
+            hash: [number as u8; 32],
+            finalized: true,
+        }
+    }
+
+    /// Block `number` of the fork `fork`, not finalized.
+    fn unfinalized(number: u32, fork: u8) -> Block {
+        Block {
+            number,
+            hash: [fork; 32],
+            finalized: false,
+        }
+    }
+
+    #[test]
+    fn balances_follow_the_transfers() {
+        let store = Store::in_memory().unwrap();
+        assert_eq!(store.next_block().unwrap(), None);
+        store
+            .add_block(
+                finalized(4),
+                &[
+                    transfer(4, 0, None, Some(ALICE), u128::MAX - 1),
+                    transfer(4, 2, Some(ALICE), Some(BOB), 10),
+                ],
+            )
+            .unwrap();
+        // Storing a block again changes nothing.
+        store
+            .add_block(finalized(4), &[transfer(4, 2, Some(ALICE), Some(BOB), 10)])
+            .unwrap();
+        store
+            .add_block(finalized(5), &[transfer(5, 1, Some(BOB), None, 4)])
+            .unwrap();
+        assert_eq!(store.next_block().unwrap(), Some(6));
+
+        assert_eq!(
+            store.holders(TOKEN).unwrap(),
+            [(ALICE, u128::MAX - 11), (BOB, 6)]
+        );
+        assert_eq!(store.holders([2; 32]).unwrap(), []);
+        let balances: Vec<_> = store
+            .history(TOKEN, BOB)
+            .unwrap()
+            .into_iter()
+            .map(|change| (change.transfer.event.block, change.balance))
+            .collect();
+        assert_eq!(balances, [(4, 10), (5, 6)]);
+
+        assert_eq!(store.balance_at(TOKEN, BOB, 3).unwrap(), 0);
+        assert_eq!(store.balance_at(TOKEN, BOB, 4).unwrap(), 10);
+        assert_eq!(store.balance_at(TOKEN, BOB, 5).unwrap(), 6);
+        assert_eq!(store.balance_at(TOKEN, ALICE, 9).unwrap(), u128::MAX - 11);
+        assert_eq!(store.first_block().unwrap(), Some(4));
+    }
+
+    #[test]
+    fn unfinalized_blocks_roll_back() {
+        let store = Store::in_memory().unwrap();
+        store
+            .add_block(finalized(1), &[transfer(1, 0, None, Some(ALICE), 100)])
+            .unwrap();
+        store
+            .add_block(
+                unfinalized(2, 0xf1),
+                &[
+                    transfer(2, 0, Some(ALICE), Some(BOB), 30),
+                    Record::Proposal(Proposal {
+                        contract: [3; 32],
+                        event: EventId { block: 2, index: 1 },
+                        proposal: 0,
+                        proposer: ALICE,
+                        asset: None,
+                        beneficiary: BOB,
+                        amount: 50,
+                        executed: None,
+                    }),
+                ],
+            )
+            .unwrap();
+        store
+            .add_block(unfinalized(3, 0xf1), &[transfer(3, 0, Some(BOB), None, 5)])
+            .unwrap();
+        assert_eq!(store.finalized_block().unwrap(), Some(1));
+        assert_eq!(store.block(2).unwrap(), Some(unfinalized(2, 0xf1)));
+
+        // Block 3 of another fork became the best block: 2 stays, 3 goes.
+        store.roll_back(3).unwrap();
+        assert_eq!(store.next_block().unwrap(), Some(3));
+        assert_eq!(store.block(3).unwrap(), None);
+        assert_eq!(store.balance_at(TOKEN, BOB, 3).unwrap(), 30);
+        store
+            .add_block(unfinalized(3, 0xf2), &[transfer(3, 0, Some(BOB), None, 7)])
+            .unwrap();
+        assert_eq!(store.balance_at(TOKEN, BOB, 3).unwrap(), 23);
+
+        // Finalizing block 2 keeps it from being rolled back.
+        store.finalize(2).unwrap();
+        assert_eq!(store.finalized_block().unwrap(), Some(2));
+        assert_eq!(
+            store.roll_back(2).unwrap_err(),
+            "cannot roll back to block 2: block 2 is finalized"
+        );
+        assert_eq!(store.proposals([3; 32]).unwrap().len(), 1);
+        store.roll_back(3).unwrap();
+        assert_eq!(store.holders(TOKEN).unwrap(), [(ALICE, 70), (BOB, 30)]);
+    }
+
+    #[test]
+    fn proposals_are_marked_executed() {
+        let store = Store::in_memory().unwrap();
+        let proposal = Proposal {
+            contract: [3; 32],
+            event: EventId { block: 2, index: 1 },
+            proposal: 0,
+            proposer: ALICE,
+            asset: None,
+            beneficiary: BOB,
+            amount: 50,
+            executed: None,
+        };
+        store
+            .add_block(finalized(2), &[Record::Proposal(proposal.clone())])
+            .unwrap();
+        let executed = EventId { block: 3, index: 0 };
+        store
+            .add_block(
+                finalized(3),
+                &[
+                    Record::Executed {
+                        contract: [3; 32],
+                        event: executed,
+                        proposal: 0,
+                    },
+                    // Not indexed, so nothing to mark.
+                    Record::Executed {
+                        contract: [3; 32],
+                        event: EventId { block: 3, index: 1 },
+                        proposal: 9,
+                    },
+                ],
+            )
+            .unwrap();
+        assert_eq!(
+            store.proposals([3; 32]).unwrap(),
+            [Proposal {
+                executed: Some(executed),
+                ..proposal
+            }]
+        );
+    }
+}
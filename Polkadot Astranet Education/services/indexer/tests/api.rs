@@ -0,0 +1,318 @@
+// services/indexer/tests/api.rs
+//
+// Fills a store with records, as the indexer would, and checks the API's answers.
+
+use hyper::StatusCode;
+use indexer::{api::Api, store::Block, Config, EventId, Mint, Proposal, Record, Store, Transfer};
+use repl::value::parse_account;
+use serde_json::json;
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    sync::Arc,
+};
+use tokio::net::TcpListener;
+
+const TOKEN: [u8; 32] = [1; 32];
+const ITEMS: [u8; 32] = [2; 32];
+const TREASURY: [u8; 32] = [3; 32];
+
+const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+
+const CONFIG: &str = r#"
+    [[contract]]
+    name = "course-token"
+    kind = "erc20"
+    address = "0x0101010101010101010101010101010101010101010101010101010101010101"
+
+    [[contract]]
+    name = "certificates"
+    kind = "erc721"
+    address = "0x0202020202020202020202020202020202020202020202020202020202020202"
+
+    [[contract]]
+    name = "treasury"
+    kind = "treasury"
+    address = "0x0303030303030303030303030303030303030303030303030303030303030303"
+"#;
+
+fn account(name: &str) -> [u8; 32] {
+    parse_account(name).unwrap()
+}
+
+fn at(block: u32, index: u32) -> EventId {
+    EventId { block, index }
+}
+
+fn finalized(number: u32) -> Block {
+    Block {
+        number,
+        hash: [number as u8; 32],
+        finalized: true,
+    }
+}
+
+fn transfer(event: EventId, from: Option<&str>, to: Option<&str>, value: u128) -> Record {
+    Record::Transfer(Transfer {
+        contract: TOKEN,
+        event,
+        from: from.map(account),
+        to: to.map(account),
+        value,
+    })
+}
+
+/// An API over `store`.
+fn api_over(store: Store) -> Api {
+    Api::new(Config::parse(CONFIG).unwrap(), Arc::new(store), 42)
+}
+
+/// An API over `store`, once it holds a few blocks of each contract's events.
+fn api(store: Store) -> Api {
+    fill(&store);
+    api_over(store)
+}
+
+/// Stores blocks 1 to 3, with events of each contract.
+fn fill(store: &Store) {
+    store
+        .add_block(
+            finalized(1),
+            &[
+                transfer(at(1, 0), None, Some("alice"), 1_000_000_000_000_000_000_000),
+                Record::Mint(Mint {
+                    contract: ITEMS,
+                    event: at(1, 1),
+                    token: 7,
+                    owner: account("bob"),
+                }),
+            ],
+        )
+        .unwrap();
+    store
+        .add_block(
+            finalized(2),
+            &[
+                transfer(at(2, 0), Some("alice"), Some("bob"), 300),
+                Record::Proposal(Proposal {
+                    contract: TREASURY,
+                    event: at(2, 1),
+                    proposal: 0,
+                    proposer: account("alice"),
+                    asset: None,
+                    beneficiary: account("bob"),
+                    amount: 50,
+                    executed: None,
+                }),
+                Record::Proposal(Proposal {
+                    contract: TREASURY,
+                    event: at(2, 2),
+                    proposal: 1,
+                    proposer: account("bob"),
+                    asset: Some(TOKEN),
+                    beneficiary: account("bob"),
+                    amount: 20,
+                    executed: None,
+                }),
+            ],
+        )
+        .unwrap();
+    store
+        .add_block(
+            finalized(3),
+            &[
+                transfer(at(3, 0), Some("bob"), None, 100),
+                Record::Executed {
+                    contract: TREASURY,
+                    event: at(3, 1),
+                    proposal: 0,
+                },
+            ],
+        )
+        .unwrap();
+}
+
+#[test]
+fn status_lists_the_contracts() {
+    let (status, body) = api(Store::in_memory().unwrap()).get("/status", "");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["next_block"], 4);
+    assert_eq!(body["finalized_block"], 3);
+    assert_eq!(body["contracts"][1]["name"], "certificates");
+    assert_eq!(body["contracts"][1]["kind"], "erc721");
+}
+
+#[test]
+fn holders_and_history_follow_the_transfers() {
+    let api = api(Store::in_memory().unwrap());
+    let (status, body) = api.get("/holders", "");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        json!([
+            { "account": ALICE, "balance": "999999999999999999700" },
+            { "account": BOB, "balance": "200" },
+        ])
+    );
+
+    let (_, body) = api.get("/history", &format!("account={}", BOB));
+    assert_eq!(
+        body,
+        json!([
+            { "block": 2, "index": 0, "from": ALICE, "to": BOB, "value": "300", "balance": "300" },
+            { "block": 3, "index": 0, "from": BOB, "to": null, "value": "100", "balance": "200" },
+        ])
+    );
+    // Accounts may be given as development names too.
+    assert_eq!(
+        api.get("/history", "contract=course-token&account=bob").1,
+        body
+    );
+    assert_eq!(api.get("/history", "account=charlie").1, json!([]));
+}
+
+#[test]
+fn balances_are_reconstructed_at_any_indexed_block() {
+    let store = Store::in_memory().unwrap();
+    fill(&store);
+    // Block 4 is not finalized yet.
+    store
+        .add_block(
+            Block {
+                number: 4,
+                hash: [0xf4; 32],
+                finalized: false,
+            },
+            &[transfer(at(4, 0), Some("bob"), Some("alice"), 50)],
+        )
+        .unwrap();
+    let api = api_over(store);
+    let balance_at = |block: u32| {
+        let (status, body) = api.get("/balance_at", &format!("account=bob&block={}", block));
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        (body["balance"].clone(), body["finalized"].clone())
+    };
+    assert_eq!(balance_at(1), (json!("0"), json!(true)));
+    assert_eq!(balance_at(2), (json!("300"), json!(true)));
+    assert_eq!(balance_at(3), (json!("200"), json!(true)));
+    assert_eq!(balance_at(4), (json!("150"), json!(false)));
+
+    // Every balance in `/history` is the balance at its block.
+    let (_, history) = api.get("/history", "account=alice");
+    for change in history.as_array().unwrap() {
+        let block = change["block"].as_u64().unwrap();
+        let (_, body) = api.get("/balance_at", &format!("account=alice&block={}", block));
+        assert_eq!(body["balance"], change["balance"]);
+        assert_eq!(body["account"], ALICE);
+    }
+
+    assert_eq!(
+        api.get("/balance_at", "account=bob&block=5"),
+        (
+            StatusCode::NOT_FOUND,
+            json!({ "error": "block 5 is not indexed yet" })
+        )
+    );
+    assert_eq!(
+        api.get("/balance_at", "account=bob&block=0").1,
+        json!({ "error": "block 0 is before the first indexed block, 1" })
+    );
+    assert_eq!(
+        api.get("/balance_at", "account=bob").1,
+        json!({ "error": "missing `block`" })
+    );
+    assert_eq!(
+        api.get("/balance_at", "account=bob&block=latest").0,
+        StatusCode::BAD_REQUEST
+    );
+}
+
+#[test]
+fn mints_and_proposals_are_listed() {
+    let api = api(Store::in_memory().unwrap());
+    assert_eq!(
+        api.get("/mints", "").1,
+        json!([{ "block": 1, "index": 1, "token": 7, "owner": BOB }])
+    );
+    let (_, proposals) = api.get("/proposals", "contract=treasury");
+    assert_eq!(
+        proposals[0],
+        json!({
+            "proposal": 0,
+            "block": 2,
+            "proposer": ALICE,
+            "asset": "native",
+            "beneficiary": BOB,
+            "amount": "50",
+            "status": "executed",
+            "executed_block": 3,
+        })
+    );
+    assert_eq!(proposals[1]["status"], "proposed");
+    assert_eq!(proposals[1]["executed_block"], json!(null));
+}
+
+#[test]
+fn bad_requests_are_explained() {
+    let api = api(Store::in_memory().unwrap());
+    assert_eq!(
+        api.get("/history", ""),
+        (
+            StatusCode::BAD_REQUEST,
+            json!({ "error": "missing `account`" })
+        )
+    );
+    assert_eq!(
+        api.get("/history", "account=nobody").0,
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        api.get("/mints", "contract=treasury"),
+        (
+            StatusCode::NOT_FOUND,
+            json!({ "error": "no erc721 contract is called `treasury`" })
+        )
+    );
+    assert_eq!(api.get("/balances", "").0, StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn the_database_outlives_the_indexer() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("indexer.sqlite");
+    let before = api(Store::open(&path).unwrap()).get("/holders", "");
+    let store = Store::open(&path).unwrap();
+    assert_eq!(store.next_block().unwrap(), Some(4));
+    let after = api_over(store).get("/holders", "");
+    assert_eq!(before, after);
+}
+
+#[tokio::test]
+async fn the_api_is_served_over_http() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(indexer::api::serve(
+        listener,
+        Arc::new(api(Store::in_memory().unwrap())),
+    ));
+
+    let response = tokio::task::spawn_blocking(move || {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream
+            .write_all(b"GET /mints?contract=certificates HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    })
+    .await
+    .unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(head.starts_with("HTTP/1.1 200 OK"), "{}", head);
+    assert!(head.contains("content-type: application/json"), "{}", head);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(body).unwrap()[0]["token"],
+        7
+    );
+}
@@ -0,0 +1,226 @@
+// services/indexer/tests/reconcile.rs
+//
+// Checks the balances the indexer reconstructs against the token contract itself:
+// for every block since the token was deployed, `balance_at` must equal what
+// `balance_of` returned in that block. These tests need a contracts node and the
+// ERC-20 bundle, so they are ignored by default:
+//
+//     devnet &
+//     bundle
+//     cargo test -- --ignored
+//
+// The node is at `ws://127.0.0.1:9944` unless `INDEXER_NODE` says otherwise, and the
+// bundles in `dist/` at the repository root unless `INDEXER_DIST` does. Every test
+// deploys a fresh token as //Alice, so the node can be reused.
+
+use astranet_errors::erc20::Error;
+use deployer::{
+    bundle::Bundle,
+    dry_run,
+    submit::{call_tx, deposit_limit, submit, weight},
+    CallRequest, Code, InstantiateRequest,
+};
+use indexer::{
+    api::Api,
+    indexer::{Indexer, Mode},
+    Config, Store,
+};
+use repl::{
+    keystore::{KeyPair, Scheme},
+    value::parse_account,
+};
+use scale::{Decode, Encode};
+use std::{
+    env, fs,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use subxt::{
+    backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+    dynamic::Value,
+    utils::H256,
+    SubstrateConfig,
+};
+
+const SUPPLY: u128 = 1_000_000;
+
+fn node_url() -> String {
+    env::var("INDEXER_NODE").unwrap_or_else(|_| "ws://127.0.0.1:9944".to_owned())
+}
+
+fn signer(suri: &str) -> KeyPair {
+    KeyPair::from_suri(Scheme::Sr25519, suri).unwrap()
+}
+
+/// Uploads and instantiates the ERC-20 bundle as //Alice, who gets the whole supply.
+async fn deploy_token(rpc: &RpcClient) -> [u8; 32] {
+    let dist = env::var("INDEXER_DIST")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../dist"));
+    let path = dist.join("erc20.contract");
+    let json = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("{}: {}; build it with `bundle`", path.display(), err));
+    let bundle = Bundle::parse(&json).unwrap();
+    let mut data = bundle.constructor("new").unwrap().selector.to_vec();
+    (SUPPLY, "Course".to_owned(), "CRS".to_owned(), 12u8).encode_to(&mut data);
+    let salt = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+        .encode();
+    let request = InstantiateRequest {
+        origin: signer("//Alice").account_id(),
+        value: 0,
+        gas_limit: None,
+        storage_deposit_limit: None,
+        code: Code::Upload(bundle.code().unwrap().to_vec()),
+        data,
+        salt,
+    };
+    let dry_run = dry_run::instantiate(rpc, &request).await.unwrap();
+    let address = dry_run.check().unwrap().account_id;
+    let tx = subxt::dynamic::tx(
+        "Contracts",
+        "instantiate_with_code",
+        vec![
+            Value::u128(0),
+            weight(dry_run.gas_required),
+            deposit_limit(dry_run.storage_deposit.charge()),
+            Value::from_bytes(bundle.code().unwrap()),
+            Value::from_bytes(&request.data),
+            Value::from_bytes(&request.salt),
+        ],
+    );
+    submit(rpc.clone(), &signer("//Alice"), &tx).await.unwrap();
+    address
+}
+
+/// Sends `value` tokens from `from` to the account `to`.
+async fn transfer(rpc: &RpcClient, token: [u8; 32], from: &str, to: &str, value: u128) {
+    let from = signer(from);
+    let mut input_data = selector::compute("transfer").to_vec();
+    (parse_account(to).unwrap(), value).encode_to(&mut input_data);
+    let request = CallRequest {
+        origin: from.account_id(),
+        dest: token,
+        value: 0,
+        gas_limit: None,
+        storage_deposit_limit: None,
+        input_data,
+    };
+    let dry_run = dry_run::call(rpc, &request).await.unwrap();
+    let returned = <Result<(), Error>>::decode(&mut dry_run.check().unwrap().data.as_slice());
+    assert_eq!(returned, Ok(Ok(())));
+    submit(rpc.clone(), &from, &call_tx(&request, &dry_run))
+        .await
+        .unwrap();
+}
+
+/// `balance_of(owner)` of `token` as it was in block `at`.
+async fn balance_of(rpc: &RpcClient, token: [u8; 32], owner: [u8; 32], at: [u8; 32]) -> u128 {
+    let mut input_data = selector::compute("balance_of").to_vec();
+    owner.encode_to(&mut input_data);
+    let request = CallRequest {
+        origin: owner,
+        dest: token,
+        value: 0,
+        gas_limit: None,
+        storage_deposit_limit: None,
+        input_data,
+    };
+    let dry_run = dry_run::call_at(rpc, &request, H256(at)).await.unwrap();
+    u128::decode(&mut dry_run.check().unwrap().data.as_slice()).unwrap()
+}
+
+/// Deploys a token, moves it around, indexes the blocks in `mode` and compares every
+/// reconstructed balance with the contract's.
+async fn reconcile(mode: Mode) {
+    let rpc = RpcClient::from_url(node_url()).await.unwrap();
+    let store = Arc::new(Store::in_memory().unwrap());
+
+    // Indexing starts at the best block before the deployment.
+    let start = LegacyRpcMethods::<SubstrateConfig>::new(rpc.clone())
+        .chain_get_header(None)
+        .await
+        .unwrap()
+        .unwrap()
+        .number;
+    let token = deploy_token(&rpc).await;
+    let config = Config::parse(&format!(
+        r#"
+        [[contract]]
+        name = "course-token"
+        kind = "erc20"
+        address = "0x{}"
+        "#,
+        token
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>()
+    ))
+    .unwrap();
+    let mut indexer = Indexer::connect(
+        &node_url(),
+        config.clone(),
+        store.clone(),
+        mode,
+        Some(start),
+    )
+    .await
+    .unwrap();
+
+    transfer(&rpc, token, "//Alice", "bob", 300).await;
+    transfer(&rpc, token, "//Alice", "charlie", 200).await;
+    transfer(&rpc, token, "//Bob", "charlie", 50).await;
+    transfer(&rpc, token, "//Charlie", "alice", 25).await;
+
+    // Index until the last transfer is in: Charlie has sent Alice her 25.
+    let alice = parse_account("alice").unwrap();
+    let mut indexed = false;
+    for _ in 0..30 {
+        indexer.catch_up().await.unwrap();
+        let last = store.history(token, alice).unwrap();
+        if last.last().map(|change| change.balance) == Some(SUPPLY - 475) {
+            indexed = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    assert!(indexed, "the transfers were not indexed in time");
+
+    let api = Api::new(config, store.clone(), 42);
+    let deployed = store.history(token, alice).unwrap()[0].transfer.event.block;
+    let next = store.next_block().unwrap().unwrap();
+    let mut checked = 0;
+    for number in deployed..next {
+        let block = store.block(number).unwrap().unwrap();
+        for name in ["alice", "bob", "charlie"] {
+            let account = parse_account(name).unwrap();
+            let on_chain = balance_of(&rpc, token, account, block.hash).await;
+            assert_eq!(
+                store.balance_at(token, account, number).unwrap(),
+                on_chain,
+                "{}'s balance at block {}",
+                name,
+                number
+            );
+            let (_, body) = api.get("/balance_at", &format!("account={}&block={}", name, number));
+            assert_eq!(body["balance"], on_chain.to_string());
+            checked += 1;
+        }
+    }
+    assert!(checked >= 15, "only {} balances checked", checked);
+}
+
+#[tokio::test]
+#[ignore = "needs a contracts node and the bundles in dist/"]
+async fn finalized_balances_match_the_contract() {
+    reconcile(Mode::Finalized).await;
+}
+
+#[tokio::test]
+#[ignore = "needs a contracts node and the bundles in dist/"]
+async fn best_block_balances_match_the_contract() {
+    reconcile(Mode::Best).await;
+}
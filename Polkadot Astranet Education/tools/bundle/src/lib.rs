@@ -0,0 +1,250 @@
+// tools/bundle/src/lib.rs
+//
+// `cargo contract build` produces a `.contract` bundle per contract: a JSON file with
+// the Wasm code, its hash, and the metadata. The website needs a summary of every
+// bundle (which contracts exist, which version, which code hash to expect on chain,
+// which selectors to call), so this crate reduces a directory of bundles to a single
+// `manifest.json`:
+//
+//     {
+//       "contracts": [
+//         {
+//           "name": "flipper",
+//           "version": "0.1.0",
+//           "code_hash": "0x…",
+//           "selectors": [{ "kind": "constructor", "label": "new", "selector": "0x9bae9d5e" }]
+//         }
+//       ]
+//     }
+//
+// Contracts are sorted by name and the file ends with a newline, so regenerating an
+// unchanged `dist/` produces a byte-identical manifest and `--check` can compare text.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{fmt, fs, io, path::Path};
+
+/// File name of the manifest inside the dist directory.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Extension `cargo contract` gives to contract bundles.
+pub const BUNDLE_EXTENSION: &str = "contract";
+
+/// A constructor or message of a bundled contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectorInfo {
+    pub kind: String,
+    pub label: String,
+    pub selector: String,
+}
+
+/// The summary of one contract bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractInfo {
+    pub name: String,
+    pub version: String,
+    pub code_hash: String,
+    pub selectors: Vec<SelectorInfo>,
+}
+
+/// The summary of every bundle in a dist directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub contracts: Vec<ContractInfo>,
+}
+
+/// Errors returned while collecting bundles.
+#[derive(Debug)]
+pub enum BundleError {
+    /// A file or directory could not be read or written.
+    Io(String, io::Error),
+    /// A bundle is not valid JSON or lacks a required field.
+    InvalidBundle(String, String),
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::Io(path, err) => write!(f, "{}: {}", path, err),
+            BundleError::InvalidBundle(path, what) => {
+                write!(f, "{}: invalid bundle: {}", path, what)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+/// Reads the summary of a single `.contract` bundle.
+pub fn read_bundle(json: &str) -> Result<ContractInfo, String> {
+    let root: Value = serde_json::from_str(json).map_err(|err| err.to_string())?;
+    let field = |pointer: &str| {
+        root.pointer(pointer)
+            .and_then(Value::as_str)
+            .map(str::to_owned)
+            .ok_or_else(|| format!("missing `{}`", pointer))
+    };
+
+    let selectors = selector::entries_from_metadata(json)
+        .map_err(|err| err.to_string())?
+        .into_iter()
+        .map(|entry| SelectorInfo {
+            kind: entry.kind.to_string(),
+            selector: selector::to_hex(&entry.selector),
+            label: entry.label,
+        })
+        .collect();
+
+    Ok(ContractInfo {
+        name: field("/contract/name")?,
+        version: field("/contract/version")?,
+        code_hash: field("/source/hash")?,
+        selectors,
+    })
+}
+
+/// Builds the manifest from every `.contract` file directly inside `dir`.
+pub fn collect(dir: &Path) -> Result<Manifest, BundleError> {
+    let io_err = |path: &Path| {
+        let path = path.display().to_string();
+        move |err| BundleError::Io(path, err)
+    };
+
+    let mut contracts = Vec::new();
+    for entry in fs::read_dir(dir).map_err(io_err(dir))? {
+        let path = entry.map_err(io_err(dir))?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(BUNDLE_EXTENSION) {
+            continue;
+        }
+        let json = fs::read_to_string(&path).map_err(io_err(&path))?;
+        let info = read_bundle(&json)
+            .map_err(|what| BundleError::InvalidBundle(path.display().to_string(), what))?;
+        contracts.push(info);
+    }
+    contracts.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(Manifest { contracts })
+}
+
+/// Renders the manifest exactly as it is written to disk.
+pub fn render(manifest: &Manifest) -> String {
+    let mut json = serde_json::to_string_pretty(manifest).expect("manifest always serializes");
+    json.push('\n');
+    json
+}
+
+/// Compares a freshly collected manifest with the one on disk and returns the names
+/// of the contracts that were added, removed, or changed. An empty list means the
+/// manifest is up to date.
+pub fn stale_contracts(on_disk: &str, fresh: &Manifest) -> Vec<String> {
+    let on_disk: Manifest = match serde_json::from_str(on_disk) {
+        Ok(manifest) => manifest,
+        Err(_) => return vec![MANIFEST_FILE.to_owned()],
+    };
+
+    let mut stale: Vec<String> = fresh
+        .contracts
+        .iter()
+        .filter(|info| !on_disk.contracts.contains(info))
+        .map(|info| info.name.clone())
+        .collect();
+    for old in &on_disk.contracts {
+        if !fresh.contracts.iter().any(|info| info.name == old.name) {
+            stale.push(old.name.clone());
+        }
+    }
+    stale.sort();
+    stale.dedup();
+    stale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLIPPER: &str = r#"{
+        "source": { "hash": "0x11", "language": "ink! 3.4.0" },
+        "contract": { "name": "flipper", "version": "0.1.0" },
+        "spec": {
+            "constructors": [{ "label": "new", "selector": "0x9bae9d5e" }],
+            "messages": [{ "label": "flip", "selector": "0x633aa551" }]
+        }
+    }"#;
+
+    fn info(name: &str, code_hash: &str) -> ContractInfo {
+        ContractInfo {
+            name: name.to_owned(),
+            version: "0.1.0".to_owned(),
+            code_hash: code_hash.to_owned(),
+            selectors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn reads_bundle_summary() {
+        let info = read_bundle(FLIPPER).unwrap();
+        assert_eq!(info.name, "flipper");
+        assert_eq!(info.version, "0.1.0");
+        assert_eq!(info.code_hash, "0x11");
+        assert_eq!(
+            info.selectors,
+            vec![
+                SelectorInfo {
+                    kind: "constructor".to_owned(),
+                    label: "new".to_owned(),
+                    selector: "0x9bae9d5e".to_owned(),
+                },
+                SelectorInfo {
+                    kind: "message".to_owned(),
+                    label: "flip".to_owned(),
+                    selector: "0x633aa551".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_bundle_without_hash() {
+        let json = FLIPPER.replace(r#""hash": "0x11", "#, "");
+        assert_eq!(read_bundle(&json), Err("missing `/source/hash`".to_owned()));
+    }
+
+    #[test]
+    fn render_round_trips() {
+        let manifest = Manifest {
+            contracts: vec![info("erc20", "0x01"), info("flipper", "0x02")],
+        };
+        let json = render(&manifest);
+        assert!(json.ends_with("}\n"));
+        assert!(stale_contracts(&json, &manifest).is_empty());
+    }
+
+    #[test]
+    fn reports_changed_added_and_removed_contracts() {
+        let on_disk = render(&Manifest {
+            contracts: vec![
+                info("erc20", "0x01"),
+                info("flipper", "0x02"),
+                info("old", "0x03"),
+            ],
+        });
+        let fresh = Manifest {
+            contracts: vec![
+                info("erc20", "0x01"),
+                info("flipper", "0xff"),
+                info("new", "0x04"),
+            ],
+        };
+        assert_eq!(
+            stale_contracts(&on_disk, &fresh),
+            vec!["flipper", "new", "old"]
+        );
+    }
+
+    #[test]
+    fn unreadable_manifest_is_stale() {
+        assert_eq!(
+            stale_contracts("not json", &Manifest::default()),
+            vec![MANIFEST_FILE]
+        );
+    }
+}
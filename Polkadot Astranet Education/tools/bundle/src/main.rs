@@ -0,0 +1,127 @@
+// tools/bundle/src/main.rs
+//
+// Usage:
+//   bundle                              build every contract crate and refresh dist/
+//   bundle --no-build                   refresh dist/ from bundles built earlier
+//   bundle --check --dist path/to/dist  fail if dist/manifest.json is out of date
+//
+// A contract crate is any directory under `--contracts` holding a `Cargo.toml`.
+
+use bundle::{collect, render, stale_contracts, BUNDLE_EXTENSION, MANIFEST_FILE};
+use clap::Parser;
+use contract_crates::contract_crates;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+};
+
+#[derive(Parser)]
+#[command(about = "Build the example contracts and collect their bundles into a manifest")]
+struct Cli {
+    /// Directory whose subdirectories are contract crates.
+    #[arg(long, default_value = "examples/demo-contracts")]
+    contracts: PathBuf,
+    /// Directory receiving the `.contract` files and `manifest.json`.
+    #[arg(long, default_value = "dist")]
+    dist: PathBuf,
+    /// Collect bundles already built instead of running `cargo contract build`.
+    #[arg(long)]
+    no_build: bool,
+    /// Only check that the manifest matches the bundles in `--dist`; write nothing.
+    #[arg(long)]
+    check: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = if cli.check {
+        check(&cli.dist)
+    } else {
+        bundle(&cli)
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn check(dist: &Path) -> Result<(), String> {
+    let manifest = collect(dist).map_err(|err| err.to_string())?;
+    let path = dist.join(MANIFEST_FILE);
+    let on_disk =
+        fs::read_to_string(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+
+    let stale = stale_contracts(&on_disk, &manifest);
+    if !stale.is_empty() {
+        return Err(format!(
+            "{} is out of date for: {} (run `bundle --no-build` to refresh it)",
+            path.display(),
+            stale.join(", ")
+        ));
+    }
+    if on_disk != render(&manifest) {
+        return Err(format!("{} is not formatted by this tool", path.display()));
+    }
+    println!(
+        "{} is up to date ({} contracts)",
+        path.display(),
+        manifest.contracts.len()
+    );
+    Ok(())
+}
+
+fn bundle(cli: &Cli) -> Result<(), String> {
+    fs::create_dir_all(&cli.dist).map_err(|err| format!("{}: {}", cli.dist.display(), err))?;
+
+    for krate in contract_crates(&cli.contracts)? {
+        if !cli.no_build {
+            build(&krate)?;
+        }
+        for bundle in built_bundles(&krate)? {
+            let target = cli
+                .dist
+                .join(bundle.file_name().expect("bundle paths name a file"));
+            fs::copy(&bundle, &target).map_err(|err| format!("{}: {}", bundle.display(), err))?;
+        }
+    }
+
+    let manifest = collect(&cli.dist).map_err(|err| err.to_string())?;
+    let path = cli.dist.join(MANIFEST_FILE);
+    fs::write(&path, render(&manifest)).map_err(|err| format!("{}: {}", path.display(), err))?;
+    println!(
+        "wrote {} ({} contracts)",
+        path.display(),
+        manifest.contracts.len()
+    );
+    Ok(())
+}
+
+fn build(krate: &Path) -> Result<(), String> {
+    println!("building {}", krate.display());
+    let status = Command::new("cargo")
+        .args(["contract", "build", "--release", "--manifest-path"])
+        .arg(krate.join("Cargo.toml"))
+        .status()
+        .map_err(|err| format!("cannot run cargo contract: {}", err))?;
+    if !status.success() {
+        return Err(format!(
+            "cargo contract build failed for {}",
+            krate.display()
+        ));
+    }
+    Ok(())
+}
+
+fn built_bundles(krate: &Path) -> Result<Vec<PathBuf>, String> {
+    let ink_dir = krate.join("target").join("ink");
+    let entries =
+        fs::read_dir(&ink_dir).map_err(|err| format!("{}: {}", ink_dir.display(), err))?;
+    Ok(entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some(BUNDLE_EXTENSION))
+        .collect())
+}
@@ -0,0 +1,49 @@
+// tools/bundle/tests/check.rs
+//
+// Runs `bundle --check` against the fixture dist directory. The fixture bundle is a
+// trimmed-down `.contract` file (no Wasm) with the flipper's real selectors.
+
+use std::{fs, path::PathBuf, process::Command};
+
+fn fixture_dist() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/dist")
+}
+
+fn run_check(dist: &PathBuf) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_bundle"))
+        .arg("--check")
+        .arg("--dist")
+        .arg(dist)
+        .output()
+        .expect("bundle binary runs")
+}
+
+#[test]
+fn fixture_manifest_is_up_to_date() {
+    let output = run_check(&fixture_dist());
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn check_fails_when_a_bundle_changes() {
+    let dist = std::env::temp_dir().join(format!("bundle-check-{}", std::process::id()));
+    fs::create_dir_all(&dist).unwrap();
+    for file in ["flipper.contract", "manifest.json"] {
+        fs::copy(fixture_dist().join(file), dist.join(file)).unwrap();
+    }
+    let bundle = fs::read_to_string(dist.join("flipper.contract")).unwrap();
+    fs::write(
+        dist.join("flipper.contract"),
+        bundle.replace("\"version\": \"0.1.0\"", "\"version\": \"0.2.0\""),
+    )
+    .unwrap();
+
+    let output = run_check(&dist);
+    fs::remove_dir_all(&dist).unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("out of date for: flipper"));
+}
@@ -0,0 +1,61 @@
+// tools/contract-crates/src/lib.rs
+//
+// `bundle`, `coverage` and `size-report` each work on every contract crate under a
+// `--contracts` directory: any subdirectory holding a `Cargo.toml`. They share the
+// lookup from here, so the three agree on what a contract crate is.
+//
+// Finding none is an error rather than an empty list. The contracts under
+// `examples/demo-contracts` are single files, not crates, and a tool that quietly
+// processed nothing there would report success on an empty run.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Returns the contract crates under `dir`, sorted, or an error if there are none.
+pub fn contract_crates(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(dir).map_err(|err| format!("{}: {}", dir.display(), err))?;
+    let mut crates: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join("Cargo.toml").is_file())
+        .collect();
+    if crates.is_empty() {
+        return Err(format!(
+            "{}: no contract crates (subdirectories holding a Cargo.toml)",
+            dir.display()
+        ));
+    }
+    crates.sort();
+    Ok(crates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crates_are_subdirectories_with_a_manifest() {
+        let temp = tempfile::tempdir().unwrap();
+        for name in ["token", "flipper", "notes"] {
+            fs::create_dir(temp.path().join(name)).unwrap();
+        }
+        fs::write(temp.path().join("token/Cargo.toml"), "").unwrap();
+        fs::write(temp.path().join("flipper/Cargo.toml"), "").unwrap();
+        fs::write(temp.path().join("erc20.rs"), "").unwrap();
+
+        assert_eq!(
+            contract_crates(temp.path()).unwrap(),
+            [temp.path().join("flipper"), temp.path().join("token")]
+        );
+    }
+
+    #[test]
+    fn finding_no_crate_is_an_error() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(temp.path().join("erc20.rs"), "").unwrap();
+        let err = contract_crates(temp.path()).unwrap_err();
+        assert!(err.ends_with(": no contract crates (subdirectories holding a Cargo.toml)"));
+        assert!(contract_crates(&temp.path().join("missing")).is_err());
+    }
+}
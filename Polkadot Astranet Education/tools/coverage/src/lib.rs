@@ -0,0 +1,420 @@
+// tools/coverage/src/lib.rs
+//
+// Measuring the coverage of ink! unit tests takes a few settings that are easy to miss:
+//
+// - The tests run off chain, against ink!'s test environment, so the crate must be
+//   built for the host with its `std` feature, which contract crates enable by
+//   default. Building with `--no-default-features`, as for the chain, compiles no
+//   tests at all.
+// - Only the library's tests are run (`--lib`). End-to-end tests behind the
+//   `e2e-tests` feature need a running node and `cargo contract`, and under coverage
+//   they would measure the node, not the contract.
+// - Each contract is a crate of its own, with its own target directory, so there is no
+//   workspace for `cargo llvm-cov` to cover in one run. Each crate is run on its own,
+//   writing an LCOV report, and the reports are merged here.
+//
+// The merge sums the hits of each line and function over the reports, so code shared
+// by several crates, such as a path dependency on `crates/utils`, is covered by the
+// tests of all of them. Branch records are dropped; neither tool reports them reliably
+// for code expanded from the `#[ink::contract]` macro.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+};
+
+/// File name of the merged report inside the output directory.
+pub const MERGED_FILE: &str = "lcov.info";
+
+/// The coverage tool to run the tests under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    /// `cargo llvm-cov`, using the compiler's source-based coverage.
+    LlvmCov,
+    /// `cargo tarpaulin`, for platforms without llvm-tools.
+    Tarpaulin,
+}
+
+impl FromStr for Tool {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "llvm-cov" => Ok(Tool::LlvmCov),
+            "tarpaulin" => Ok(Tool::Tarpaulin),
+            other => Err(format!(
+                "unknown tool `{}`; expected llvm-cov or tarpaulin",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Tool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Tool::LlvmCov => "llvm-cov",
+            Tool::Tarpaulin => "tarpaulin",
+        })
+    }
+}
+
+impl Tool {
+    /// The command to install the tool with.
+    pub fn install_hint(self) -> &'static str {
+        match self {
+            Tool::LlvmCov => {
+                "cargo install cargo-llvm-cov --locked && rustup component add llvm-tools-preview"
+            }
+            Tool::Tarpaulin => "cargo install cargo-tarpaulin --locked",
+        }
+    }
+
+    /// The command running the unit tests of the crate at `manifest_path` under
+    /// coverage, writing an LCOV report into `out_dir`. Returns the path the report
+    /// will be at.
+    pub fn command(self, manifest_path: &Path, out_dir: &Path, name: &str) -> (Command, PathBuf) {
+        let mut command = Command::new("cargo");
+        match self {
+            Tool::LlvmCov => {
+                let report = out_dir.join(format!("{}.lcov", name));
+                command
+                    .args(["llvm-cov", "--lib", "--lcov", "--output-path"])
+                    .arg(&report)
+                    .args(["--ignore-filename-regex", r"(\.cargo/registry|/rustc/)"])
+                    .arg("--manifest-path")
+                    .arg(manifest_path);
+                (command, report)
+            }
+            Tool::Tarpaulin => {
+                // Tarpaulin names its report itself, so each crate gets a directory.
+                let dir = out_dir.join(name);
+                command
+                    .args(["tarpaulin", "--lib", "--skip-clean", "--out", "Lcov"])
+                    .arg("--output-dir")
+                    .arg(&dir)
+                    .arg("--manifest-path")
+                    .arg(manifest_path);
+                (command, dir.join(MERGED_FILE))
+            }
+        }
+    }
+}
+
+/// The hits of one source file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileCoverage {
+    /// Hits by line number.
+    pub lines: BTreeMap<u32, u64>,
+    /// Line and hits by function name.
+    pub functions: BTreeMap<String, (u32, u64)>,
+}
+
+impl FileCoverage {
+    /// Lines found and lines hit.
+    pub fn line_counts(&self) -> (usize, usize) {
+        let hit = self.lines.values().filter(|hits| **hits > 0).count();
+        (self.lines.len(), hit)
+    }
+}
+
+/// An LCOV report, by source file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub files: BTreeMap<String, FileCoverage>,
+}
+
+impl Report {
+    /// Reads an LCOV tracefile.
+    pub fn parse(lcov: &str) -> Result<Self, String> {
+        let mut report = Report::default();
+        let mut current: Option<(String, FileCoverage)> = None;
+        for (number, line) in lcov.lines().enumerate() {
+            let line = line.trim();
+            let err = |what: &str| format!("line {}: {}: `{}`", number + 1, what, line);
+            if line.is_empty() {
+                continue;
+            }
+            if line == "end_of_record" {
+                let (path, file) = current.take().ok_or_else(|| err("record without SF"))?;
+                report.add(path, file);
+                continue;
+            }
+            let (key, value) = line.split_once(':').unwrap_or((line, ""));
+            if key == "SF" {
+                if current.is_some() {
+                    return Err(err("SF before end_of_record"));
+                }
+                current = Some((value.to_owned(), FileCoverage::default()));
+                continue;
+            }
+            let Some((_, file)) = current.as_mut() else {
+                // Test names and other records outside a file.
+                continue;
+            };
+            match key {
+                "DA" => {
+                    let mut fields = value.split(',');
+                    let line_number = parse_field(fields.next()).ok_or_else(|| err("bad DA"))?;
+                    let hits: u64 = parse_field(fields.next()).ok_or_else(|| err("bad DA"))?;
+                    *file.lines.entry(line_number).or_default() += hits;
+                }
+                "FN" => {
+                    let (line_number, name) = value.split_once(',').ok_or_else(|| err("bad FN"))?;
+                    let line_number =
+                        parse_field(Some(line_number)).ok_or_else(|| err("bad FN"))?;
+                    file.functions.entry(name.to_owned()).or_default().0 = line_number;
+                }
+                "FNDA" => {
+                    let (hits, name) = value.split_once(',').ok_or_else(|| err("bad FNDA"))?;
+                    let hits: u64 = parse_field(Some(hits)).ok_or_else(|| err("bad FNDA"))?;
+                    file.functions.entry(name.to_owned()).or_default().1 += hits;
+                }
+                // Totals are recomputed, and branches are dropped.
+                _ => {}
+            }
+        }
+        if current.is_some() {
+            return Err("the last record has no end_of_record".to_owned());
+        }
+        Ok(report)
+    }
+
+    fn add(&mut self, path: String, file: FileCoverage) {
+        let merged = self.files.entry(path).or_default();
+        for (line, hits) in file.lines {
+            *merged.lines.entry(line).or_default() += hits;
+        }
+        for (name, (line, hits)) in file.functions {
+            let function = merged.functions.entry(name).or_default();
+            function.0 = line;
+            function.1 += hits;
+        }
+    }
+
+    /// Adds the hits of `other` to these.
+    pub fn merge(&mut self, other: Report) {
+        for (path, file) in other.files {
+            self.add(path, file);
+        }
+    }
+
+    /// Lines found and lines hit over all files.
+    pub fn line_counts(&self) -> (usize, usize) {
+        self.files
+            .values()
+            .map(FileCoverage::line_counts)
+            .fold((0, 0), |(found, hit), (f, h)| (found + f, hit + h))
+    }
+
+    /// Writes the report as an LCOV tracefile.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for (path, file) in &self.files {
+            out.push_str(&format!("SF:{}\n", path));
+            for (name, (line, _)) in &file.functions {
+                out.push_str(&format!("FN:{},{}\n", line, name));
+            }
+            for (name, (_, hits)) in &file.functions {
+                out.push_str(&format!("FNDA:{},{}\n", hits, name));
+            }
+            let functions_hit = file.functions.values().filter(|(_, hits)| *hits > 0);
+            out.push_str(&format!("FNF:{}\n", file.functions.len()));
+            out.push_str(&format!("FNH:{}\n", functions_hit.count()));
+            for (line, hits) in &file.lines {
+                out.push_str(&format!("DA:{},{}\n", line, hits));
+            }
+            let (found, hit) = file.line_counts();
+            out.push_str(&format!("LF:{}\nLH:{}\nend_of_record\n", found, hit));
+        }
+        out
+    }
+
+    /// The summary to print: line coverage per file, with paths relative to `root`
+    /// where they are inside it, and in total.
+    pub fn summary(&self, root: &Path) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (path, file) in &self.files {
+            let shown = Path::new(path)
+                .strip_prefix(root)
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|_| PathBuf::from(path));
+            let (found, hit) = file.line_counts();
+            lines.push(format!(
+                "  {:>6.1}%  {:>5}/{:<5}  {}",
+                percent(hit, found),
+                hit,
+                found,
+                shown.display()
+            ));
+        }
+        let (found, hit) = self.line_counts();
+        lines.push(format!(
+            "total: {:.1}% of lines ({} of {}) in {} file(s)",
+            percent(hit, found),
+            hit,
+            found,
+            self.files.len()
+        ));
+        lines
+    }
+}
+
+fn parse_field<T: FromStr>(field: Option<&str>) -> Option<T> {
+    field?.trim().parse().ok()
+}
+
+/// `hit` as a percentage of `found`, 0 when there is nothing to cover, so an empty
+/// report never passes `--fail-under`.
+pub fn percent(hit: usize, found: usize) -> f64 {
+    if found == 0 {
+        0.0
+    } else {
+        hit as f64 * 100.0 / found as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// What `cargo llvm-cov --lcov` writes for flipper, trimmed.
+    const FLIPPER: &str = "\
+SF:/work/examples/demo-contracts/flipper/lib.rs
+FN:20,_RNvMs_Cs4flipper7Flipper3new
+FN:26,_RNvMs_Cs4flipper7Flipper4flip
+FN:31,_RNvMs_Cs4flipper7Flipper3get
+FNDA:2,_RNvMs_Cs4flipper7Flipper3new
+FNDA:1,_RNvMs_Cs4flipper7Flipper4flip
+FNDA:0,_RNvMs_Cs4flipper7Flipper3get
+FNF:3
+FNH:2
+BRDA:27,0,0,1
+DA:20,2
+DA:21,2
+DA:26,1
+DA:27,1
+DA:31,0
+DA:32,0
+LF:6
+LH:4
+end_of_record
+SF:/work/crates/utils/src/lib.rs
+DA:3,1
+DA:4,0
+LF:2
+LH:1
+end_of_record
+";
+
+    /// Another crate's report, sharing `crates/utils`.
+    const ERC20: &str = "\
+TN:
+SF:/work/crates/utils/src/lib.rs
+DA:3,5
+DA:4,2
+end_of_record
+SF:/work/examples/demo-contracts/erc20/lib.rs
+DA:10,0
+end_of_record
+";
+
+    #[test]
+    fn reports_parse() {
+        let report = Report::parse(FLIPPER).unwrap();
+        assert_eq!(report.files.len(), 2);
+        let flipper = &report.files["/work/examples/demo-contracts/flipper/lib.rs"];
+        assert_eq!(flipper.line_counts(), (6, 4));
+        assert_eq!(flipper.lines[&20], 2);
+        assert_eq!(flipper.functions["_RNvMs_Cs4flipper7Flipper3get"], (31, 0));
+    }
+
+    #[test]
+    fn merging_sums_hits_per_line() {
+        let mut report = Report::parse(FLIPPER).unwrap();
+        report.merge(Report::parse(ERC20).unwrap());
+        assert_eq!(report.files.len(), 3);
+        let utils = &report.files["/work/crates/utils/src/lib.rs"];
+        assert_eq!(utils.lines[&3], 6);
+        assert_eq!(utils.lines[&4], 2);
+        assert_eq!(utils.line_counts(), (2, 2));
+        assert_eq!(report.line_counts(), (9, 6));
+    }
+
+    #[test]
+    fn rendered_reports_parse_back() {
+        let mut report = Report::parse(FLIPPER).unwrap();
+        report.merge(Report::parse(ERC20).unwrap());
+        let rendered = report.render();
+        assert_eq!(Report::parse(&rendered).unwrap(), report);
+        assert!(!rendered.contains("BRDA"));
+        assert!(rendered.contains("FNF:3\nFNH:2\n"));
+        assert!(rendered.ends_with("LF:6\nLH:4\nend_of_record\n"));
+    }
+
+    #[test]
+    fn malformed_reports_are_rejected() {
+        assert!(Report::parse("SF:a.rs\nDA:x,1\nend_of_record\n").is_err());
+        assert!(Report::parse("SF:a.rs\nDA:1,1\n").is_err());
+        assert!(Report::parse("SF:a.rs\nSF:b.rs\nend_of_record\n").is_err());
+        assert!(Report::parse("end_of_record\n").is_err());
+        assert_eq!(Report::parse("").unwrap(), Report::default());
+    }
+
+    #[test]
+    fn summaries_show_paths_under_the_root() {
+        let report = Report::parse(FLIPPER).unwrap();
+        let lines = report.summary(Path::new("/work"));
+        assert_eq!(lines[0], "    50.0%      1/2      crates/utils/src/lib.rs");
+        assert!(lines[1].ends_with("  examples/demo-contracts/flipper/lib.rs"));
+        assert_eq!(lines[2], "total: 62.5% of lines (5 of 8) in 2 file(s)");
+        assert!(
+            report.summary(Path::new("/elsewhere"))[0].ends_with("/work/crates/utils/src/lib.rs")
+        );
+    }
+
+    #[test]
+    fn empty_reports_cover_nothing() {
+        assert_eq!(percent(0, 0), 0.0);
+        assert_eq!(
+            Report::default().summary(Path::new("/work")),
+            ["total: 0.0% of lines (0 of 0) in 0 file(s)"]
+        );
+    }
+
+    #[test]
+    fn commands_run_only_unit_tests() {
+        let manifest = Path::new("examples/demo-contracts/flipper/Cargo.toml");
+        let out = Path::new("target/coverage");
+
+        let (command, report) = Tool::LlvmCov.command(manifest, out, "flipper");
+        let args: Vec<_> = command
+            .get_args()
+            .map(|arg| arg.to_str().unwrap())
+            .collect();
+        assert_eq!(&args[..3], ["llvm-cov", "--lib", "--lcov"]);
+        assert!(!args
+            .iter()
+            .any(|arg| arg.contains("e2e") || arg.contains("no-default")));
+        assert_eq!(report, Path::new("target/coverage/flipper.lcov"));
+
+        let (command, report) = Tool::Tarpaulin.command(manifest, out, "flipper");
+        let args: Vec<_> = command
+            .get_args()
+            .map(|arg| arg.to_str().unwrap())
+            .collect();
+        assert_eq!(&args[..2], ["tarpaulin", "--lib"]);
+        assert_eq!(report, Path::new("target/coverage/flipper/lcov.info"));
+    }
+
+    #[test]
+    fn tools_parse() {
+        assert_eq!("llvm-cov".parse(), Ok(Tool::LlvmCov));
+        assert_eq!("tarpaulin".parse(), Ok(Tool::Tarpaulin));
+        assert!("grcov".parse::<Tool>().is_err());
+        assert_eq!(Tool::Tarpaulin.to_string(), "tarpaulin");
+    }
+}
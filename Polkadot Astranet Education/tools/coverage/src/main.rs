@@ -0,0 +1,115 @@
+// tools/coverage/src/main.rs
+//
+// Usage:
+//   coverage                        run every contract crate's unit tests under llvm-cov
+//   coverage --tool tarpaulin       use cargo-tarpaulin instead
+//   coverage --no-run               merge the reports of an earlier run again
+//   coverage --fail-under 80        exit with 1 below 80% line coverage
+//
+// A contract crate is any directory under `--contracts` holding a `Cargo.toml`. Each
+// crate's report goes into `--out`, and the merged one into `--out`/lcov.info, which
+// editors' coverage gutters and `genhtml` read.
+
+use clap::Parser;
+use contract_crates::contract_crates;
+use coverage::{Report, Tool, MERGED_FILE};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+#[derive(Parser)]
+#[command(about = "Run the contract crates' unit tests under coverage and merge the reports")]
+struct Cli {
+    /// Directory whose subdirectories are contract crates.
+    #[arg(long, default_value = "examples/demo-contracts")]
+    contracts: PathBuf,
+    /// `llvm-cov` or `tarpaulin`.
+    #[arg(long, default_value = "llvm-cov")]
+    tool: Tool,
+    /// Directory receiving the reports.
+    #[arg(long, default_value = "target/coverage")]
+    out: PathBuf,
+    /// Merge the reports already in `--out` instead of running the tests.
+    #[arg(long)]
+    no_run: bool,
+    /// Fail if line coverage is below this percentage.
+    #[arg(long)]
+    fail_under: Option<f64>,
+}
+
+fn main() -> ExitCode {
+    match run(&Cli::parse()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Returns whether coverage is at least `--fail-under`.
+fn run(cli: &Cli) -> Result<bool, String> {
+    fs::create_dir_all(&cli.out).map_err(|err| format!("{}: {}", cli.out.display(), err))?;
+    // Reports name sources by absolute path, and the tools run from each crate.
+    let out = absolute(&cli.out)?;
+
+    let mut merged = Report::default();
+    let mut crates = 0;
+    for krate in contract_crates(&cli.contracts)? {
+        let name = krate
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_owned();
+        let (mut command, report_path) = cli.tool.command(&krate.join("Cargo.toml"), &out, &name);
+        if !cli.no_run {
+            println!("testing {} under {}", krate.display(), cli.tool);
+            let status = command.status().map_err(|err| {
+                format!(
+                    "cannot run cargo {}: {} (install it with `{}`)",
+                    cli.tool,
+                    err,
+                    cli.tool.install_hint()
+                )
+            })?;
+            if !status.success() {
+                return Err(format!(
+                    "the tests of {} failed under {}; is it installed (`{}`)?",
+                    krate.display(),
+                    cli.tool,
+                    cli.tool.install_hint()
+                ));
+            }
+        }
+        let lcov = fs::read_to_string(&report_path)
+            .map_err(|err| format!("{}: {}", report_path.display(), err))?;
+        merged.merge(
+            Report::parse(&lcov).map_err(|err| format!("{}: {}", report_path.display(), err))?,
+        );
+        crates += 1;
+    }
+
+    let path = out.join(MERGED_FILE);
+    fs::write(&path, merged.render()).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let root = env::current_dir().map_err(|err| err.to_string())?;
+    for line in merged.summary(&root) {
+        println!("{}", line);
+    }
+    println!("wrote {} ({} crate(s))", path.display(), crates);
+
+    let (found, hit) = merged.line_counts();
+    match cli.fail_under {
+        Some(minimum) if coverage::percent(hit, found) < minimum => {
+            println!("coverage is below --fail-under {}%", minimum);
+            Ok(false)
+        }
+        _ => Ok(true),
+    }
+}
+
+fn absolute(path: &Path) -> Result<PathBuf, String> {
+    fs::canonicalize(path).map_err(|err| format!("{}: {}", path.display(), err))
+}
@@ -0,0 +1,152 @@
+// tools/deployer/src/bundle.rs
+//
+// Reads a `.contract` bundle: its metadata, parsed by the repl crate, and its Wasm code
+// under `source.wasm`. Bare `metadata.json` files can be used to call deployed
+// contracts, but not to instantiate new ones, since they carry no code.
+
+use repl::{
+    metadata::{self, ContractMetadata, Message},
+    value::parse_hex,
+};
+use serde_json::Value;
+
+/// A contract's metadata and, if the file is a bundle, its code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bundle {
+    pub metadata: ContractMetadata,
+    pub wasm: Option<Vec<u8>>,
+}
+
+impl Bundle {
+    /// Reads a `.contract` bundle or `metadata.json` file.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let metadata = metadata::parse(json).map_err(|err| err.to_string())?;
+        let root: Value = serde_json::from_str(json).map_err(|err| err.to_string())?;
+        let wasm = match root.pointer("/source/wasm").and_then(Value::as_str) {
+            Some(wasm) => Some(parse_hex(wasm).map_err(|err| format!("source.wasm: {}", err))?),
+            None => None,
+        };
+        Ok(Bundle { metadata, wasm })
+    }
+
+    /// Returns the code to upload, or an error if the file has none.
+    pub fn code(&self) -> Result<&[u8], String> {
+        self.wasm
+            .as_deref()
+            .ok_or_else(|| "the file has no `source.wasm`; pass a `.contract` bundle".to_owned())
+    }
+
+    /// Finds a constructor by label or position.
+    pub fn constructor(&self, name_or_index: &str) -> Result<&Message, String> {
+        let constructors = &self.metadata.constructors;
+        let found = match name_or_index.parse::<usize>() {
+            Ok(index) => constructors.get(index),
+            Err(_) => constructors
+                .iter()
+                .find(|constructor| constructor.label == name_or_index),
+        };
+        found.ok_or_else(|| {
+            format!(
+                "no constructor `{}`; the contract has {}",
+                name_or_index,
+                signatures(constructors)
+            )
+        })
+    }
+
+    /// Finds a message by label or position.
+    pub fn message(&self, name_or_index: &str) -> Result<&Message, String> {
+        self.metadata.find(name_or_index).ok_or_else(|| {
+            format!(
+                "no message `{}`; the contract has {}",
+                name_or_index,
+                signatures(&self.metadata.messages)
+            )
+        })
+    }
+}
+
+/// Encodes typed arguments, as they are typed at the repl prompt, into call data for
+/// `message` (a message or a constructor).
+pub fn call_data(message: &Message, args: &[String]) -> Result<Vec<u8>, String> {
+    if args.len() != message.args.len() {
+        return Err(format!(
+            "`{}` takes {} argument(s), got {}",
+            message.signature(),
+            message.args.len(),
+            args.len()
+        ));
+    }
+    let mut encoded = Vec::new();
+    for (arg, input) in message.args.iter().zip(args) {
+        let bytes = repl::encode(arg.ty, input).map_err(|err| format!("{}: {}", arg.label, err))?;
+        encoded.push(bytes);
+    }
+    Ok(message.call_data(&encoded))
+}
+
+fn signatures(messages: &[Message]) -> String {
+    let signatures: Vec<String> = messages.iter().map(Message::signature).collect();
+    if signatures.is_empty() {
+        "none".to_owned()
+    } else {
+        signatures.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUNDLE: &str = r#"{
+        "source": { "hash": "0x00", "wasm": "0x0061736d01000000" },
+        "contract": { "name": "flipper", "version": "0.1.0" },
+        "V3": {
+            "spec": {
+                "constructors": [
+                    { "label": "new", "selector": "0x9bae9d5e",
+                      "args": [{ "label": "init_value", "type": { "displayName": ["bool"], "type": 0 } }] },
+                    { "label": "default", "selector": "0xed4b9d1b", "args": [] }
+                ],
+                "messages": [
+                    { "label": "flip", "selector": "0x633aa551", "mutates": true, "args": [] }
+                ]
+            },
+            "types": [{ "id": 0, "type": { "def": { "primitive": "bool" } } }]
+        }
+    }"#;
+
+    #[test]
+    fn reads_code_and_constructors() {
+        let bundle = Bundle::parse(BUNDLE).unwrap();
+        assert_eq!(bundle.code().unwrap(), b"\0asm\x01\0\0\0");
+        assert_eq!(bundle.constructor("1").unwrap().label, "default");
+
+        let new = bundle.constructor("new").unwrap();
+        assert_eq!(
+            call_data(new, &["true".to_owned()]).unwrap(),
+            [0x9b, 0xae, 0x9d, 0x5e, 1]
+        );
+        assert!(bundle.constructor("missing").is_err());
+    }
+
+    #[test]
+    fn arguments_are_counted_and_typed() {
+        let bundle = Bundle::parse(BUNDLE).unwrap();
+        let new = bundle.constructor("new").unwrap();
+        assert!(call_data(new, &[])
+            .unwrap_err()
+            .contains("takes 1 argument(s), got 0"));
+        assert!(call_data(new, &["maybe".to_owned()]).is_err());
+        let flip = bundle.message("flip").unwrap();
+        assert_eq!(call_data(flip, &[]).unwrap(), [0x63, 0x3a, 0xa5, 0x51]);
+    }
+
+    #[test]
+    fn bare_metadata_has_no_code() {
+        let metadata = BUNDLE.replace(r#""wasm": "0x0061736d01000000""#, r#""language": "ink!""#);
+        let bundle = Bundle::parse(&metadata).unwrap();
+        assert!(bundle.code().is_err());
+        assert!(bundle.message("flip").is_ok());
+    }
+}
@@ -0,0 +1,305 @@
+// tools/deployer/src/dry_run.rs
+//
+// Dry runs through the runtime API of the contracts pallet: `ContractsApi_instantiate`
+// and `ContractsApi_call` execute a transaction against the latest block and discard
+// its changes, reporting what it would have cost.
+//
+// Requests and results are SCALE-encoded with the types below, which mirror
+// `pallet_contracts::primitives`, rather than through the runtime's metadata. A dry run
+// is then one `state_call` request, which tests can answer without a node. The result
+// ends with the events the transaction emitted; these only decode with the runtime's
+// own event type, so they are counted here (the repl decodes a contract's events).
+//
+// A dry run cannot list the storage a transaction touches, but the storage deposit
+// shows how it changes: a charge means the transaction adds storage, a refund that it
+// frees some.
+
+use crate::network::ss58_encode;
+use repl::value::to_hex;
+use scale::{Compact, Decode, Encode, Input};
+use subxt::{
+    backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+    utils::H256,
+    SubstrateConfig,
+};
+
+/// The `REVERT` bit of the flags a contract returns with.
+pub const REVERT_FLAG: u32 = 1;
+
+/// Computation time and proof size, as `sp_weights::Weight` encodes them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Encode, Decode)]
+pub struct Weight {
+    #[codec(compact)]
+    pub ref_time: u64,
+    #[codec(compact)]
+    pub proof_size: u64,
+}
+
+/// What the caller pays for the storage a transaction adds, or gets back for the
+/// storage it frees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum StorageDeposit {
+    Refund(u128),
+    Charge(u128),
+}
+
+impl StorageDeposit {
+    /// Returns the deposit to allow when submitting, `None` if nothing is charged.
+    pub fn charge(&self) -> Option<u128> {
+        match self {
+            StorageDeposit::Charge(amount) if *amount > 0 => Some(*amount),
+            _ => None,
+        }
+    }
+}
+
+/// What a contract returned.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct ExecReturnValue {
+    pub flags: u32,
+    pub data: Vec<u8>,
+}
+
+impl ExecReturnValue {
+    /// Whether the contract reverted, undoing its changes.
+    pub fn reverted(&self) -> bool {
+        self.flags & REVERT_FLAG != 0
+    }
+}
+
+/// What a constructor returned, and the address of the new contract.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct InstantiateReturnValue {
+    pub result: ExecReturnValue,
+    pub account_id: [u8; 32],
+}
+
+/// Why the runtime refused a transaction, as `sp_runtime::DispatchError` encodes it.
+/// Nested error enums are kept as their index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum DispatchError {
+    Other,
+    CannotLookup,
+    BadOrigin,
+    /// An error of the pallet at `index`; `error[0]` is the error's index in it. Errors
+    /// of the contracts pallet, such as `ContractTrapped`, arrive this way.
+    Module {
+        index: u8,
+        error: [u8; 4],
+    },
+    ConsumerRemaining,
+    NoProviders,
+    TooManyConsumers,
+    Token(u8),
+    Arithmetic(u8),
+    Transactional(u8),
+    Exhausted,
+    Corruption,
+    Unavailable,
+    RootNotAllowed,
+}
+
+/// The arguments of `ContractsApi_call`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct CallRequest {
+    pub origin: [u8; 32],
+    pub dest: [u8; 32],
+    pub value: u128,
+    /// `None` lets the dry run use as much gas as a block allows.
+    pub gas_limit: Option<Weight>,
+    /// `None` lets the dry run charge any storage deposit.
+    pub storage_deposit_limit: Option<u128>,
+    pub input_data: Vec<u8>,
+}
+
+/// The code to instantiate: new code to upload, or the hash of code already on chain.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum Code {
+    Upload(Vec<u8>),
+    Existing([u8; 32]),
+}
+
+/// The arguments of `ContractsApi_instantiate`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct InstantiateRequest {
+    pub origin: [u8; 32],
+    pub value: u128,
+    pub gas_limit: Option<Weight>,
+    pub storage_deposit_limit: Option<u128>,
+    pub code: Code,
+    /// The constructor's selector and arguments.
+    pub data: Vec<u8>,
+    /// Makes the address differ from other instances of the same code by the same
+    /// origin.
+    pub salt: Vec<u8>,
+}
+
+/// The outcome of a dry run, generic over what a successful execution returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRun<R> {
+    pub gas_consumed: Weight,
+    /// The gas limit the transaction needs, which can exceed what it consumed.
+    pub gas_required: Weight,
+    pub storage_deposit: StorageDeposit,
+    /// Debug messages of the contract, empty unless it was built with debug output.
+    pub debug_message: String,
+    pub result: Result<R, DispatchError>,
+    /// The number of events emitted, or `None` if the node does not report them.
+    pub events: Option<u32>,
+}
+
+/// The outcome of a `call` dry run.
+pub type CallDryRun = DryRun<ExecReturnValue>;
+
+/// The outcome of an `instantiate` dry run.
+pub type InstantiateDryRun = DryRun<InstantiateReturnValue>;
+
+impl<R: Decode> Decode for DryRun<R> {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let gas_consumed = Weight::decode(input)?;
+        let gas_required = Weight::decode(input)?;
+        let storage_deposit = StorageDeposit::decode(input)?;
+        let debug_message = String::from_utf8_lossy(&Vec::<u8>::decode(input)?).into_owned();
+        let result = Result::<R, DispatchError>::decode(input)?;
+        // `Option<Vec<EventRecord>>`, which older nodes leave out: only the length of
+        // the vector is read.
+        let events = match input.remaining_len()? {
+            Some(0) => None,
+            _ => match u8::decode(input)? {
+                0 => None,
+                _ => Some(Compact::<u32>::decode(input)?.0),
+            },
+        };
+        Ok(DryRun {
+            gas_consumed,
+            gas_required,
+            storage_deposit,
+            debug_message,
+            result,
+            events,
+        })
+    }
+}
+
+/// What both kinds of dry run return on success.
+pub trait Returned {
+    fn exec(&self) -> &ExecReturnValue;
+
+    /// The address of the contract a constructor created.
+    fn address(&self) -> Option<[u8; 32]> {
+        None
+    }
+}
+
+impl Returned for ExecReturnValue {
+    fn exec(&self) -> &ExecReturnValue {
+        self
+    }
+}
+
+impl Returned for InstantiateReturnValue {
+    fn exec(&self) -> &ExecReturnValue {
+        &self.result
+    }
+
+    fn address(&self) -> Option<[u8; 32]> {
+        Some(self.account_id)
+    }
+}
+
+impl<R: Returned> DryRun<R> {
+    /// Returns what was returned, or why the transaction must not be submitted.
+    pub fn check(&self) -> Result<&R, String> {
+        match &self.result {
+            Ok(returned) if returned.exec().reverted() => Err(format!(
+                "dry run reverted, returning {}; not submitting",
+                to_hex(&returned.exec().data)
+            )),
+            Ok(returned) => Ok(returned),
+            Err(err) => Err(format!("dry run failed with {:?}; not submitting", err)),
+        }
+    }
+
+    /// Returns the lines describing the dry run, with addresses in the format of
+    /// `ss58_prefix`; `show` formats the returned bytes.
+    pub fn report(&self, ss58_prefix: u16, show: impl Fn(&[u8]) -> String) -> Vec<String> {
+        let mut lines = vec![match &self.result {
+            Ok(returned) => format!(
+                "result: {}, returned {}",
+                if returned.exec().reverted() {
+                    "REVERTED"
+                } else {
+                    "ok"
+                },
+                show(&returned.exec().data)
+            ),
+            Err(err) => format!("result: failed with {:?}", err),
+        }];
+        if let Some(address) = self.result.as_ref().ok().and_then(Returned::address) {
+            lines.push(format!("address: {}", ss58_encode(ss58_prefix, address)));
+        }
+        lines.push(format!(
+            "gas required: ref_time {}, proof_size {} (consumed: ref_time {}, proof_size {})",
+            self.gas_required.ref_time,
+            self.gas_required.proof_size,
+            self.gas_consumed.ref_time,
+            self.gas_consumed.proof_size
+        ));
+        lines.push(match self.storage_deposit {
+            StorageDeposit::Charge(0) | StorageDeposit::Refund(0) => {
+                "storage: unchanged, no deposit".to_owned()
+            }
+            StorageDeposit::Charge(amount) => format!("storage: grows, deposit charged {}", amount),
+            StorageDeposit::Refund(amount) => {
+                format!("storage: shrinks, deposit refunded {}", amount)
+            }
+        });
+        lines.push(match self.events {
+            Some(count) => format!("events emitted: {}", count),
+            None => "events emitted: not reported by the node".to_owned(),
+        });
+        for line in self.debug_message.lines() {
+            lines.push(format!("debug: {}", line));
+        }
+        lines
+    }
+}
+
+/// Dry-runs a call of a deployed contract.
+pub async fn call(rpc: &RpcClient, request: &CallRequest) -> Result<CallDryRun, String> {
+    state_call(rpc, "ContractsApi_call", request, None).await
+}
+
+/// Dry-runs a call against the state of block `at` rather than the latest block, to
+/// read what a contract held back then. The node must still have that state, which
+/// archive nodes and development nodes keep for every block.
+pub async fn call_at(
+    rpc: &RpcClient,
+    request: &CallRequest,
+    at: H256,
+) -> Result<CallDryRun, String> {
+    state_call(rpc, "ContractsApi_call", request, Some(at)).await
+}
+
+/// Dry-runs instantiating a contract.
+pub async fn instantiate(
+    rpc: &RpcClient,
+    request: &InstantiateRequest,
+) -> Result<InstantiateDryRun, String> {
+    state_call(rpc, "ContractsApi_instantiate", request, None).await
+}
+
+async fn state_call<R: Decode>(
+    rpc: &RpcClient,
+    function: &str,
+    request: &impl Encode,
+    at: Option<H256>,
+) -> Result<R, String> {
+    let methods = LegacyRpcMethods::<SubstrateConfig>::new(rpc.clone());
+    let bytes = methods
+        .state_call(function, Some(&request.encode()), at)
+        .await
+        .map_err(|err| format!("{} failed: {}", function, err))?;
+    R::decode(&mut bytes.as_slice())
+        .map_err(|err| format!("cannot decode the result of {}: {}", function, err))
+}
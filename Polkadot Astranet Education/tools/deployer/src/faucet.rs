@@ -0,0 +1,256 @@
+// tools/deployer/src/faucet.rs
+//
+// Funding a learner account from a faucet. A network's profile says where funds come
+// from: the faucet contract of `examples/demo-contracts/faucet.rs`, which pays
+// whoever calls its `drip` message, or on a local dev node a dev account that simply
+// transfers the amount.
+//
+// The faucet contract rate-limits each account and answers a drip that comes too
+// soon with `RateLimited { retry_at }`, the block from which the account may drip
+// again. A dry run shows this without spending fees, so `ready_to_drip` waits for that
+// block and dry-runs again, as long as the wait stays within the caller's limits.
+//
+// The faucet is an ink! 3 contract: its messages return their value without the
+// `Result<_, LangError>` wrapper of later versions, and a drip that fails reverts with
+// the error as its output.
+
+use crate::dry_run::{self, CallDryRun, CallRequest};
+use astranet_errors::faucet::Error as FaucetError;
+use scale::Decode;
+use serde::Deserialize;
+use std::time::Duration;
+use subxt::{
+    backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+    SubstrateConfig,
+};
+
+/// Where a network's faucet takes funds from.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "FaucetFields")]
+pub enum Faucet {
+    /// A deployed faucet contract, by address.
+    Contract { contract: String },
+    /// A dev account, by secret URI, that transfers `amount` itself.
+    DevAccount { dev_account: String, amount: u128 },
+}
+
+/// `Faucet` as written in a profile, where the fields given pick the variant. TOML
+/// integers are 64-bit, so amounts are too.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FaucetFields {
+    contract: Option<String>,
+    dev_account: Option<String>,
+    amount: Option<u64>,
+}
+
+impl TryFrom<FaucetFields> for Faucet {
+    type Error = &'static str;
+
+    fn try_from(fields: FaucetFields) -> Result<Self, Self::Error> {
+        match fields {
+            FaucetFields {
+                contract: Some(contract),
+                dev_account: None,
+                amount: None,
+            } => Ok(Faucet::Contract { contract }),
+            FaucetFields {
+                contract: None,
+                dev_account: Some(dev_account),
+                amount: Some(amount),
+            } => Ok(Faucet::DevAccount {
+                dev_account,
+                amount: amount.into(),
+            }),
+            _ => Err("a faucet is either `{ contract }` or `{ dev_account, amount }`"),
+        }
+    }
+}
+
+/// How long to keep trying a rate-limited drip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retry {
+    /// Dry runs to make in total, the first included.
+    pub attempts: u32,
+    /// The most blocks to wait for a cooldown; a longer one fails straight away.
+    pub max_wait: u32,
+    /// How often to check the latest block while waiting.
+    pub poll: Duration,
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Retry {
+            attempts: 3,
+            max_wait: 50,
+            poll: Duration::from_secs(6),
+        }
+    }
+}
+
+/// A cooldown being waited out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cooldown {
+    pub now: u32,
+    pub retry_at: u32,
+}
+
+/// Call data of the faucet's `drip` message.
+pub fn drip_call_data() -> Vec<u8> {
+    selector::compute("drip").to_vec()
+}
+
+/// Reads what a dry run of `drip` says about the drip: `Ok` if the faucet would pay
+/// out, or the faucet's error.
+pub fn drip_outcome(dry_run: &CallDryRun) -> Result<Result<(), FaucetError>, String> {
+    let returned = match &dry_run.result {
+        Ok(returned) => returned,
+        Err(err) => return Err(format!("the drip failed with {:?}", err)),
+    };
+    Result::<(), FaucetError>::decode(&mut returned.data.as_slice())
+        .map_err(|err| format!("cannot decode what the faucet returned: {}", err))
+}
+
+/// Dry-runs `drip` as `account`.
+pub async fn dry_run_drip(
+    rpc: &RpcClient,
+    faucet: [u8; 32],
+    account: [u8; 32],
+) -> Result<CallDryRun, String> {
+    dry_run::call(rpc, &drip_request(faucet, account)).await
+}
+
+/// Asks the faucet how much one drip pays.
+pub async fn drip_amount(
+    rpc: &RpcClient,
+    faucet: [u8; 32],
+    account: [u8; 32],
+) -> Result<u128, String> {
+    let mut request = drip_request(faucet, account);
+    request.input_data = selector::compute("drip_amount").to_vec();
+    let dry_run = dry_run::call(rpc, &request).await?;
+    let returned = dry_run.check()?;
+    u128::decode(&mut returned.data.as_slice())
+        .map_err(|err| format!("cannot decode the drip amount: {}", err))
+}
+
+fn drip_request(faucet: [u8; 32], account: [u8; 32]) -> CallRequest {
+    CallRequest {
+        origin: account,
+        dest: faucet,
+        value: 0,
+        gas_limit: None,
+        storage_deposit_limit: None,
+        input_data: drip_call_data(),
+    }
+}
+
+/// Dry-runs `drip` until the faucet would pay `account`, waiting out its cooldowns
+/// within `retry`, and returns the dry run to submit with. `on_wait` is told about
+/// each cooldown before it is waited out.
+pub async fn ready_to_drip(
+    rpc: &RpcClient,
+    faucet: [u8; 32],
+    account: [u8; 32],
+    retry: Retry,
+    mut on_wait: impl FnMut(Cooldown),
+) -> Result<CallDryRun, String> {
+    let mut attempt = 1;
+    loop {
+        let dry_run = dry_run_drip(rpc, faucet, account).await?;
+        let retry_at = match drip_outcome(&dry_run)? {
+            Ok(()) => return Ok(dry_run),
+            Err(FaucetError::RateLimited { retry_at }) => retry_at,
+            Err(err) => return Err(format!("the faucet refused: {}", err)),
+        };
+        let now = best_block(rpc).await?;
+        let cooldown = Cooldown { now, retry_at };
+        if attempt >= retry.attempts {
+            return Err(format!(
+                "still rate limited after {} attempt(s); the faucet pays this account \
+                 again from block {} (now {})",
+                attempt, retry_at, now
+            ));
+        }
+        if retry_at.saturating_sub(now) > retry.max_wait {
+            return Err(format!(
+                "rate limited until block {}, {} blocks from now (block {}), longer than \
+                 the {} blocks allowed by --max-wait",
+                retry_at,
+                retry_at - now,
+                now,
+                retry.max_wait
+            ));
+        }
+        on_wait(cooldown);
+        wait_for_block(rpc, retry_at, retry.poll).await?;
+        attempt += 1;
+    }
+}
+
+/// Returns the number of the latest block.
+pub async fn best_block(rpc: &RpcClient) -> Result<u32, String> {
+    let methods = LegacyRpcMethods::<SubstrateConfig>::new(rpc.clone());
+    let header = methods
+        .chain_get_header(None)
+        .await
+        .map_err(|err| format!("chain_getHeader failed: {}", err))?
+        .ok_or("the node returned no latest block")?;
+    Ok(header.number)
+}
+
+/// Polls every `poll` until the latest block is at least `block`, and returns it.
+pub async fn wait_for_block(rpc: &RpcClient, block: u32, poll: Duration) -> Result<u32, String> {
+    loop {
+        let now = best_block(rpc).await?;
+        if now >= block {
+            return Ok(now);
+        }
+        tokio::time::sleep(poll).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DispatchError, ExecReturnValue, StorageDeposit, Weight};
+    use scale::Encode;
+
+    fn returning(flags: u32, data: Vec<u8>) -> CallDryRun {
+        CallDryRun {
+            gas_consumed: Weight::default(),
+            gas_required: Weight::default(),
+            storage_deposit: StorageDeposit::Charge(0),
+            debug_message: String::new(),
+            result: Ok(ExecReturnValue { flags, data }),
+            events: None,
+        }
+    }
+
+    #[test]
+    fn drip_outcomes_decode_the_faucet_error() {
+        assert_eq!(drip_outcome(&returning(0, vec![0])), Ok(Ok(())));
+        let limited = Result::<(), _>::Err(FaucetError::RateLimited { retry_at: 105 });
+        assert_eq!(
+            drip_outcome(&returning(1, limited.encode())),
+            Ok(Err(FaucetError::RateLimited { retry_at: 105 }))
+        );
+        assert_eq!(
+            drip_outcome(&returning(1, vec![1, 1])),
+            Ok(Err(FaucetError::FaucetEmpty))
+        );
+        assert!(drip_outcome(&returning(0, Vec::new())).is_err());
+
+        let mut failed = returning(0, Vec::new());
+        failed.result = Err(DispatchError::Module {
+            index: 8,
+            error: [11, 0, 0, 0],
+        });
+        assert!(drip_outcome(&failed).unwrap_err().contains("Module"));
+    }
+
+    #[test]
+    fn drips_call_the_drip_selector() {
+        assert_eq!(drip_call_data(), selector::compute("drip"));
+    }
+}
@@ -0,0 +1,30 @@
+// tools/deployer/src/lib.rs
+//
+// Instantiating a contract or calling one of its messages is a transaction that costs
+// fees and can fail after it is included. The deployer dry-runs every transaction
+// first, prints what it would cost and change, and only submits it if the dry run
+// went through. With `--dry-run` it stops after the report, which makes it safe to
+// try calls out against a live network.
+//
+// The same commands work against a local node and public testnets: `--network`
+// picks a profile from `networks.toml` with the node's URL, where the signer's key
+// comes from, and the chain's address format.
+//
+// This crate holds network profiles, reading bundles, building call data from typed
+// arguments (with the repl crate), the dry runs themselves, the faucet client's
+// cooldown handling, and submitting a call once its dry run went through, so other
+// tools can make calls the same way. Deciding which key signs is left to the binaries.
+
+pub mod bundle;
+pub mod dry_run;
+pub mod faucet;
+pub mod network;
+pub mod submit;
+
+pub use bundle::Bundle;
+pub use dry_run::{
+    CallDryRun, CallRequest, Code, DispatchError, DryRun, ExecReturnValue, InstantiateDryRun,
+    InstantiateRequest, InstantiateReturnValue, StorageDeposit, Weight,
+};
+pub use faucet::{Faucet, Retry};
+pub use network::{Network, Networks, Signer};
@@ -0,0 +1,575 @@
+// tools/deployer/src/main.rs
+//
+// Usage:
+//   deployer instantiate dist/flipper.contract --constructor new --arg true
+//   deployer call dist/erc20.contract 5F... transfer --arg bob --arg 1_000
+//   deployer call dist/erc20.contract 5F... transfer --arg bob --arg 1_000 --dry-run
+//   SHIBUYA_SURI="<mnemonic>" deployer --network shibuya instantiate dist/flipper.contract
+//   deployer --networks my-networks.toml --network classroom call ...
+//   deployer keys generate learner
+//   deployer --network shibuya --key learner instantiate dist/flipper.contract
+//   deployer --key learner faucet
+//
+// Arguments are typed as at the repl prompt: numbers, booleans, strings, accounts by
+// dev name, SS58 or hex, and anything else as its SCALE encoding in hex. Every
+// transaction is dry-run first and only submitted, signed with `--suri`, if the dry
+// run neither failed nor reverted; `--dry-run` stops after the report.
+//
+// `--network` selects a profile from `networks.toml` (`local` by default) or takes a
+// node's URL; `--url` and `--suri` or `--key` override the profile's node and signer.
+//
+// `keys` manages the keystore the repl shares: `generate` makes a new sr25519 or ECDSA
+// key and shows its mnemonic once, `import` stores an existing mnemonic typed at a
+// hidden prompt, and `list` and `show` print accounts without unlocking anything.
+// Stored keys are encrypted with a passphrase, read from `ASTRANET_PASSPHRASE` or the
+// terminal.
+//
+// `faucet` funds the signer's account from the network's faucet: a dev account on a
+// local node, or the course's faucet contract, whose `drip` is dry-run first and,
+// while the account is in its cooldown, retried once the faucet allows it again.
+
+use clap::{Parser, Subcommand};
+use deployer::{
+    bundle::{self, Bundle},
+    dry_run::{self, Returned},
+    faucet,
+    submit::{call_tx, deposit_limit, submit, weight},
+    CallRequest, Code, DryRun, Faucet, InstantiateRequest, Network, Networks, Retry, Signer,
+};
+use repl::{
+    keystore::{self, KeyFile, KeyPair, Keystore, Scheme, PASSPHRASE_ENV},
+    value::{parse_account, parse_amount, parse_hex},
+    ContractMetadata, Message,
+};
+use scale::Decode;
+use std::{env, fs, path::PathBuf, process::ExitCode};
+use subxt::{backend::rpc::RpcClient, dynamic::Value};
+
+#[derive(Parser)]
+#[command(
+    about = "Instantiate contracts and call their messages, dry-running every transaction first"
+)]
+struct Cli {
+    /// Network profile name, or the WebSocket URL of a node.
+    #[arg(long, global = true, default_value = "local")]
+    network: String,
+    /// File of network profiles to add to the built-in ones.
+    #[arg(long, global = true)]
+    networks: Option<PathBuf>,
+    /// WebSocket URL of the node, instead of the profile's.
+    #[arg(long, global = true)]
+    url: Option<String>,
+    /// Secret URI of the account transactions are made from, instead of the profile's
+    /// signer.
+    #[arg(long, global = true, conflicts_with = "key")]
+    suri: Option<String>,
+    /// Name of a stored key to make transactions from, instead of the profile's signer.
+    #[arg(long, global = true)]
+    key: Option<String>,
+    /// Keystore directory, `~/.astranet/keys` by default.
+    #[arg(long, global = true)]
+    keystore: Option<PathBuf>,
+    /// Only dry-run the transaction and report what it would do.
+    #[arg(long, global = true)]
+    dry_run: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Upload a contract's code and instantiate it.
+    Instantiate {
+        /// Path to the contract's `.contract` bundle.
+        bundle: PathBuf,
+        /// Constructor label or index.
+        #[arg(long, default_value = "new")]
+        constructor: String,
+        /// Constructor argument, in order. Repeatable.
+        #[arg(long = "arg")]
+        args: Vec<String>,
+        /// Value to transfer to a payable constructor.
+        #[arg(long, default_value = "0", value_parser = parse_value)]
+        value: u128,
+        /// Salt in hex, to instantiate the same code again at a new address.
+        #[arg(long, default_value = "0x")]
+        salt: String,
+    },
+    /// Call a message of a deployed contract.
+    Call {
+        /// Path to the contract's `.contract` bundle or `metadata.json`.
+        bundle: PathBuf,
+        /// Address of the deployed contract.
+        contract: String,
+        /// Message label or index.
+        message: String,
+        /// Message argument, in order. Repeatable.
+        #[arg(long = "arg")]
+        args: Vec<String>,
+        /// Value to transfer to a payable message.
+        #[arg(long, default_value = "0", value_parser = parse_value)]
+        value: u128,
+    },
+    /// Fund the signer's account from the network's faucet.
+    Faucet {
+        /// Account to fund instead of the signer's; only a dev-account faucet pays
+        /// accounts other than the caller.
+        #[arg(long)]
+        to: Option<String>,
+        /// Address of a faucet contract, instead of the profile's faucet.
+        #[arg(long)]
+        contract: Option<String>,
+        /// Dry runs to make before giving up on a rate-limited drip.
+        #[arg(long, default_value = "3")]
+        attempts: u32,
+        /// The most blocks to wait for the faucet's cooldown.
+        #[arg(long, default_value = "50")]
+        max_wait: u32,
+    },
+    /// Manage the keys in the keystore.
+    Keys {
+        #[command(subcommand)]
+        command: KeysCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum KeysCommand {
+    /// Generate a new key and print its mnemonic once.
+    Generate {
+        /// Name to store the key as.
+        name: String,
+        /// Signature scheme, sr25519 or ecdsa.
+        #[arg(long, default_value = "sr25519")]
+        scheme: Scheme,
+        /// Number of words in the mnemonic, 12 or 24.
+        #[arg(long, default_value = "12")]
+        words: usize,
+    },
+    /// Store an existing mnemonic or secret URI, typed at a hidden prompt.
+    Import {
+        /// Name to store the key as.
+        name: String,
+        /// Signature scheme, sr25519 or ecdsa.
+        #[arg(long, default_value = "sr25519")]
+        scheme: Scheme,
+    },
+    /// List the stored keys.
+    List,
+    /// Print a stored key's account in the network's address format.
+    Show {
+        /// Name of the key.
+        name: String,
+    },
+}
+
+fn parse_value(input: &str) -> Result<u128, String> {
+    parse_amount(input).ok_or_else(|| format!("`{}` is not an amount", input))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
+    let network = network(&cli)?;
+    match &cli.command {
+        Command::Keys { command } => return keys(&keystore(&cli)?, &network, command),
+        Command::Faucet {
+            to,
+            contract,
+            attempts,
+            max_wait,
+        } => {
+            let retry = Retry {
+                attempts: *attempts,
+                max_wait: *max_wait,
+                ..Retry::default()
+            };
+            return fund(&cli, &network, to.as_deref(), contract.as_deref(), retry).await;
+        }
+        _ => {}
+    }
+    let signer = signer(&cli, &network)?;
+    let rpc = connect(&network).await?;
+    println!(
+        "connected to {} ({}) as {}",
+        cli.network,
+        network.url,
+        network.address(signer.account_id())
+    );
+    let prefix = network.ss58_prefix;
+
+    match cli.command {
+        Command::Instantiate {
+            bundle,
+            constructor,
+            args,
+            value,
+            salt,
+        } => {
+            let bundle = read_bundle(&bundle)?;
+            let constructor = bundle.constructor(&constructor)?;
+            let request = InstantiateRequest {
+                origin: signer.account_id(),
+                value,
+                gas_limit: None,
+                storage_deposit_limit: None,
+                code: Code::Upload(bundle.code()?.to_vec()),
+                data: bundle::call_data(constructor, &args)?,
+                salt: parse_hex(&salt).map_err(|err| format!("--salt: {}", err))?,
+            };
+            println!("dry run of {}:", constructor.signature());
+            let dry_run = dry_run::instantiate(&rpc, &request).await?;
+            print_report(&dry_run, prefix, show_hex);
+            let address = dry_run.check()?.account_id;
+            if cli.dry_run {
+                println!("dry run only; nothing was submitted");
+                return Ok(());
+            }
+
+            let tx = subxt::dynamic::tx(
+                "Contracts",
+                "instantiate_with_code",
+                vec![
+                    Value::u128(request.value),
+                    weight(dry_run.gas_required),
+                    deposit_limit(dry_run.storage_deposit.charge()),
+                    Value::from_bytes(bundle.code()?),
+                    Value::from_bytes(&request.data),
+                    Value::from_bytes(&request.salt),
+                ],
+            );
+            let events = submit(rpc, &signer, &tx).await?;
+            let instantiated = events
+                .iter()
+                .filter_map(Result::ok)
+                .find(|event| {
+                    event.pallet_name() == "Contracts" && event.variant_name() == "Instantiated"
+                })
+                // `Instantiated { deployer: AccountId32, contract: AccountId32 }`
+                .and_then(|event| <([u8; 32], [u8; 32])>::decode(&mut event.field_bytes()).ok())
+                .map(|(_, contract)| contract)
+                .unwrap_or(address);
+            println!("instantiated at {}", network.address(instantiated));
+        }
+        Command::Call {
+            bundle,
+            contract,
+            message,
+            args,
+            value,
+        } => {
+            let bundle = read_bundle(&bundle)?;
+            let message = bundle.message(&message)?;
+            let dest = parse_account(&contract)?;
+            let request = CallRequest {
+                origin: signer.account_id(),
+                dest,
+                value,
+                gas_limit: None,
+                storage_deposit_limit: None,
+                input_data: bundle::call_data(message, &args)?,
+            };
+            println!("dry run of {}:", message.signature());
+            let dry_run = dry_run::call(&rpc, &request).await?;
+            print_report(&dry_run, prefix, |data| {
+                show_return(&bundle.metadata, message, data)
+            });
+            dry_run.check()?;
+            if cli.dry_run {
+                println!("dry run only; nothing was submitted");
+                return Ok(());
+            }
+            if !message.mutates {
+                println!(
+                    "`{}` does not change state; nothing to submit",
+                    message.label
+                );
+                return Ok(());
+            }
+
+            let events = submit(rpc, &signer, &call_tx(&request, &dry_run)).await?;
+            let emitted = events
+                .iter()
+                .filter_map(Result::ok)
+                .filter(|event| {
+                    event.pallet_name() == "Contracts" && event.variant_name() == "ContractEmitted"
+                })
+                .count();
+            println!("called, {} contract event(s) emitted", emitted);
+        }
+        Command::Faucet { .. } | Command::Keys { .. } => unreachable!("handled above"),
+    }
+    Ok(())
+}
+
+/// Funds an account from the network's faucet, or the faucet contract at `contract`.
+async fn fund(
+    cli: &Cli,
+    network: &Network,
+    to: Option<&str>,
+    contract: Option<&str>,
+    retry: Retry,
+) -> Result<(), String> {
+    let source = match contract {
+        Some(contract) => Faucet::Contract {
+            contract: contract.to_owned(),
+        },
+        None => network.faucet.clone().ok_or_else(|| {
+            format!(
+                "the `{}` network has no faucet; pass --contract with the address of a \
+                 faucet contract",
+                cli.network
+            )
+        })?,
+    };
+
+    match source {
+        Faucet::DevAccount {
+            dev_account,
+            amount,
+        } => {
+            let account = match to {
+                Some(to) => parse_account(to)?,
+                None => account(cli, network)?,
+            };
+            let dev = KeyPair::from_suri(Scheme::Sr25519, &dev_account)
+                .map_err(|err| format!("dev account: {}", err))?;
+            let rpc = connect(network).await?;
+            println!(
+                "transferring {} from dev account {} to {}",
+                amount,
+                network.address(dev.account_id()),
+                network.address(account)
+            );
+            if cli.dry_run {
+                println!("dry run only; nothing was submitted");
+                return Ok(());
+            }
+            let tx = subxt::dynamic::tx(
+                "Balances",
+                "transfer_keep_alive",
+                vec![
+                    Value::unnamed_variant("Id", [Value::from_bytes(account)]),
+                    Value::u128(amount),
+                ],
+            );
+            submit(rpc, &dev, &tx).await?;
+            println!("funded {}", network.address(account));
+        }
+        Faucet::Contract { contract } => {
+            let faucet = parse_account(&contract)?;
+            let signer = signer(cli, network)?;
+            let account = signer.account_id();
+            if to
+                .map(parse_account)
+                .transpose()?
+                .is_some_and(|to| to != account)
+            {
+                return Err(
+                    "the faucet contract pays whoever calls `drip`; sign with the key \
+                     of the account to fund instead of passing --to"
+                        .to_owned(),
+                );
+            }
+            let rpc = connect(network).await?;
+            let amount = faucet::drip_amount(&rpc, faucet, account).await?;
+            println!(
+                "dry run of drip() on faucet {}, paying {}:",
+                network.address(faucet),
+                amount
+            );
+            let dry_run = faucet::ready_to_drip(&rpc, faucet, account, retry, |cooldown| {
+                println!(
+                    "  rate limited until block {} (now {}); waiting",
+                    cooldown.retry_at, cooldown.now
+                )
+            })
+            .await?;
+            print_report(&dry_run, network.ss58_prefix, show_hex);
+            if cli.dry_run {
+                println!("dry run only; nothing was submitted");
+                return Ok(());
+            }
+            let request = CallRequest {
+                origin: account,
+                dest: faucet,
+                value: 0,
+                gas_limit: None,
+                storage_deposit_limit: None,
+                input_data: faucet::drip_call_data(),
+            };
+            submit(rpc, &signer, &call_tx(&request, &dry_run)).await?;
+            println!("funded {} with {}", network.address(account), amount);
+        }
+    }
+    Ok(())
+}
+
+async fn connect(network: &Network) -> Result<RpcClient, String> {
+    RpcClient::from_url(&network.url)
+        .await
+        .map_err(|err| format!("cannot connect to {}: {}", network.url, err))
+}
+
+fn keys(keystore: &Keystore, network: &Network, command: &KeysCommand) -> Result<(), String> {
+    if let KeysCommand::Generate { name, .. } | KeysCommand::Import { name, .. } = command {
+        if keystore.contains(name)? {
+            return Err(format!("a key named `{}` already exists", name));
+        }
+    }
+    match command {
+        KeysCommand::Generate {
+            name,
+            scheme,
+            words,
+        } => {
+            let phrase = keystore::generate_phrase(*words)?;
+            let path = store(keystore, network, name, *scheme, &phrase)?;
+            println!("saved to {}", path.display());
+            println!("write down the mnemonic below; it is not shown again, and with");
+            println!("`deployer keys import` it restores the key if the file is lost:");
+            println!();
+            println!("  {}", phrase);
+        }
+        KeysCommand::Import { name, scheme } => {
+            let suri = keystore::read_hidden("mnemonic or secret URI: ")?;
+            let path = store(keystore, network, name, *scheme, &suri)?;
+            println!("saved to {}", path.display());
+        }
+        KeysCommand::List => {
+            let keys = keystore.list()?;
+            if keys.is_empty() {
+                println!("no keys in {}", keystore.dir.display());
+            }
+            for (name, file) in keys {
+                println!(
+                    "{:<16} {:<8} {}",
+                    name,
+                    file.scheme,
+                    network.address(file.account_id()?)
+                );
+            }
+        }
+        KeysCommand::Show { name } => {
+            let file = keystore.load(name)?;
+            println!("scheme: {}", file.scheme);
+            println!("address: {}", network.address(file.account_id()?));
+            println!("account id: {}", file.account);
+        }
+    }
+    Ok(())
+}
+
+/// Encrypts a key with a new passphrase and stores it as `name`.
+fn store(
+    keystore: &Keystore,
+    network: &Network,
+    name: &str,
+    scheme: Scheme,
+    suri: &str,
+) -> Result<PathBuf, String> {
+    let pair = KeyPair::from_suri(scheme, suri)?;
+    let passphrase = new_passphrase()?;
+    let path = keystore.save(name, &KeyFile::encrypt(scheme, suri, &passphrase)?)?;
+    println!(
+        "stored {} key `{}`: {}",
+        scheme,
+        name,
+        network.address(pair.account_id())
+    );
+    Ok(path)
+}
+
+/// Reads the passphrase for a new key from `ASTRANET_PASSPHRASE`, or asks for it twice.
+fn new_passphrase() -> Result<String, String> {
+    if let Ok(passphrase) = env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    let passphrase = keystore::read_hidden("new passphrase: ")?;
+    if passphrase.is_empty() {
+        return Err("the passphrase must not be empty".to_owned());
+    }
+    if *keystore::read_hidden("repeat passphrase: ")? != *passphrase {
+        return Err("the passphrases do not match".to_owned());
+    }
+    Ok(passphrase.to_string())
+}
+
+fn keystore(cli: &Cli) -> Result<Keystore, String> {
+    match &cli.keystore {
+        Some(dir) => Ok(Keystore::new(dir)),
+        None => Keystore::default_dir().map(Keystore::new),
+    }
+}
+
+/// Returns the signer's account, reading a stored key's without unlocking it.
+fn account(cli: &Cli, network: &Network) -> Result<[u8; 32], String> {
+    match (&cli.key, &cli.suri, &network.signer) {
+        (Some(name), _, _) | (None, None, Signer::Key(name)) => {
+            keystore(cli)?.load(name)?.account_id()
+        }
+        _ => signer(cli, network).map(|signer| signer.account_id()),
+    }
+}
+
+/// Resolves `--key`, `--suri` and the profile's signer into the key to sign with.
+fn signer(cli: &Cli, network: &Network) -> Result<KeyPair, String> {
+    let suri = match (&cli.key, &cli.suri, &network.signer) {
+        (Some(name), _, _) | (None, None, Signer::Key(name)) => return keystore(cli)?.unlock(name),
+        (None, Some(suri), _) => suri.clone(),
+        (None, None, _) => network.suri()?,
+    };
+    KeyPair::from_suri(Scheme::Sr25519, &suri).map_err(|err| format!("signer: {}", err))
+}
+
+/// Resolves `--network`, `--networks` and `--url` into the network to use.
+fn network(cli: &Cli) -> Result<Network, String> {
+    let mut networks = Networks::builtin();
+    if let Some(path) = &cli.networks {
+        let toml =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        networks
+            .extend(Networks::parse(&toml).map_err(|err| format!("{}: {}", path.display(), err))?);
+    }
+    let mut network = networks.get(&cli.network)?;
+    if let Some(url) = &cli.url {
+        network.url = url.clone();
+    }
+    Ok(network)
+}
+
+fn read_bundle(path: &PathBuf) -> Result<Bundle, String> {
+    let json = fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    Bundle::parse(&json).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+fn print_report<R: Returned>(dry_run: &DryRun<R>, prefix: u16, show: impl Fn(&[u8]) -> String) {
+    for line in dry_run.report(prefix, show) {
+        println!("  {}", line);
+    }
+}
+
+fn show_hex(data: &[u8]) -> String {
+    repl::value::to_hex(data)
+}
+
+/// Formats a message's return value with the contract's types.
+fn show_return(metadata: &ContractMetadata, message: &Message, data: &[u8]) -> String {
+    if metadata.wraps_result {
+        // `Result<T, LangError>`: a leading 0 is `Ok`.
+        return match data.split_first() {
+            Some((0, rest)) => metadata.format_return(message, rest),
+            _ => format!("a LangError, {}", show_hex(data)),
+        };
+    }
+    metadata.format_return(message, data)
+}
@@ -0,0 +1,258 @@
+// tools/deployer/src/network.rs
+//
+// Network profiles: where to connect, how to sign, and how addresses are written.
+//
+// The built-in profiles live in `networks.toml` next to the manifest and are compiled
+// in; a file passed with `--networks` adds to them or overrides them by name. Secrets
+// do not belong in these files. A profile names a key in the keystore (see
+// `repl::keystore`) or the environment variable holding the signer's secret URI, and
+// only the local profile spells out `//Alice`, whose key is public anyway.
+//
+// Each chain picks an SS58 prefix for its addresses, so the same account is `5Grw..`
+// on a local node and `ajYM..` on Shibuya. Addresses in any format are accepted as
+// input; output uses the network's own.
+
+use crate::faucet::Faucet;
+use blake2::{Blake2b512, Digest};
+use serde::Deserialize;
+use std::{collections::BTreeMap, env};
+
+/// The built-in profiles.
+pub const BUILTIN: &str = include_str!("../networks.toml");
+
+/// The SS58 prefix of generic Substrate chains, used when a profile sets none.
+pub const GENERIC_PREFIX: u16 = 42;
+
+/// Where the signer's secret URI comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Signer {
+    /// The secret URI itself, e.g. `//Alice`.
+    Suri(String),
+    /// The name of the environment variable holding the secret URI.
+    SuriEnv(String),
+    /// The name of a key in the keystore, unlocked with its passphrase.
+    Key(String),
+}
+
+/// A network the deployer can connect to.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Network {
+    /// WebSocket URL of a node.
+    pub url: String,
+    #[serde(default = "generic_prefix")]
+    pub ss58_prefix: u16,
+    pub signer: Signer,
+    /// Where `deployer faucet` takes funds from, if the network has a faucet.
+    #[serde(default)]
+    pub faucet: Option<Faucet>,
+}
+
+fn generic_prefix() -> u16 {
+    GENERIC_PREFIX
+}
+
+impl Network {
+    /// A network with no profile, reached at `url`, signed for by `//Alice`.
+    pub fn custom(url: &str) -> Self {
+        Network {
+            url: url.to_owned(),
+            ss58_prefix: GENERIC_PREFIX,
+            signer: Signer::Suri("//Alice".to_owned()),
+            faucet: None,
+        }
+    }
+
+    /// Returns the signer's secret URI, unless the signer is a stored key.
+    pub fn suri(&self) -> Result<String, String> {
+        match &self.signer {
+            Signer::Suri(suri) => Ok(suri.clone()),
+            Signer::SuriEnv(var) => env::var(var).map_err(|_| {
+                format!(
+                    "set {} to the secret URI of the account to sign with, or pass --suri",
+                    var
+                )
+            }),
+            Signer::Key(name) => Err(format!(
+                "the signer is the stored key `{}`, which has no secret URI to show",
+                name
+            )),
+        }
+    }
+
+    /// Formats an account as an address of this network.
+    pub fn address(&self, account: [u8; 32]) -> String {
+        ss58_encode(self.ss58_prefix, account)
+    }
+}
+
+/// Network profiles by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Networks(BTreeMap<String, Network>);
+
+impl Networks {
+    /// Reads profiles in the format of `networks.toml`.
+    pub fn parse(toml: &str) -> Result<Self, String> {
+        toml::from_str(toml)
+            .map(Networks)
+            .map_err(|err| err.message().to_owned())
+    }
+
+    /// Returns the built-in profiles.
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN).expect("built-in networks.toml is valid")
+    }
+
+    /// Adds the profiles of `other`, replacing those with the same name.
+    pub fn extend(&mut self, other: Networks) {
+        self.0.extend(other.0);
+    }
+
+    /// Looks up a profile by name, or makes one for a `ws://` or `wss://` URL.
+    pub fn get(&self, name_or_url: &str) -> Result<Network, String> {
+        if name_or_url.starts_with("ws://") || name_or_url.starts_with("wss://") {
+            return Ok(Network::custom(name_or_url));
+        }
+        self.0.get(name_or_url).cloned().ok_or_else(|| {
+            let names: Vec<&str> = self.0.keys().map(String::as_str).collect();
+            format!(
+                "no network `{}`; known networks are {}, or pass a ws:// URL",
+                name_or_url,
+                names.join(", ")
+            )
+        })
+    }
+}
+
+/// Encodes an account as an SS58 address with `prefix`.
+pub fn ss58_encode(prefix: u16, account: [u8; 32]) -> String {
+    // Prefixes up to 63 take one byte, larger ones (up to 16383) two.
+    let mut data = if prefix < 64 {
+        vec![prefix as u8]
+    } else {
+        vec![
+            ((prefix & 0b1111_1100) >> 2) as u8 | 0b0100_0000,
+            (prefix >> 8) as u8 | ((prefix & 0b11) << 6) as u8,
+        ]
+    };
+    data.extend_from_slice(&account);
+    let checksum = Blake2b512::new()
+        .chain_update(b"SS58PRE")
+        .chain_update(&data)
+        .finalize();
+    data.extend_from_slice(&checksum[..2]);
+    bs58::encode(data).into_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use repl::value::parse_account;
+
+    #[test]
+    fn builtin_networks_parse() {
+        let networks = Networks::builtin();
+        let local = networks.get("local").unwrap();
+        assert_eq!(local.url, "ws://127.0.0.1:9944");
+        assert_eq!(local.ss58_prefix, 42);
+        assert_eq!(local.suri().unwrap(), "//Alice");
+        assert!(matches!(local.faucet, Some(Faucet::DevAccount { .. })));
+
+        let shibuya = networks.get("shibuya").unwrap();
+        assert_eq!(shibuya.ss58_prefix, 5);
+        assert_eq!(shibuya.signer, Signer::SuriEnv("SHIBUYA_SURI".to_owned()));
+        assert!(networks.get("rococo-contracts").is_ok());
+    }
+
+    #[test]
+    fn files_add_and_override_networks() {
+        let mut networks = Networks::builtin();
+        networks.extend(
+            Networks::parse(
+                r#"
+                [local]
+                url = "ws://127.0.0.1:9955"
+                signer = { suri = "//Bob" }
+
+                [classroom]
+                url = "wss://classroom.example:443"
+                ss58_prefix = 0
+                signer = { suri_env = "DEPLOYER_TEST_CLASSROOM_SURI" }
+
+                [homework]
+                url = "wss://rpc.shibuya.astar.network"
+                ss58_prefix = 5
+                signer = { key = "learner" }
+                faucet = { contract = "5DZahar1q7Bd1h9yA8ngnxsgq76E7vS5Wj2KAVkjee9mhP9o" }
+                "#,
+            )
+            .unwrap(),
+        );
+        assert_eq!(networks.get("local").unwrap().url, "ws://127.0.0.1:9955");
+        assert!(networks.get("shibuya").is_ok());
+
+        let classroom = networks.get("classroom").unwrap();
+        assert!(classroom
+            .suri()
+            .unwrap_err()
+            .contains("DEPLOYER_TEST_CLASSROOM_SURI"));
+        env::set_var("DEPLOYER_TEST_CLASSROOM_SURI", "//Charlie");
+        assert_eq!(classroom.suri().unwrap(), "//Charlie");
+
+        let homework = networks.get("homework").unwrap();
+        assert_eq!(homework.signer, Signer::Key("learner".to_owned()));
+        assert!(homework.suri().is_err());
+        assert_eq!(
+            homework.faucet,
+            Some(Faucet::Contract {
+                contract: "5DZahar1q7Bd1h9yA8ngnxsgq76E7vS5Wj2KAVkjee9mhP9o".to_owned()
+            })
+        );
+        assert_eq!(classroom.faucet, None);
+    }
+
+    #[test]
+    fn urls_are_custom_networks() {
+        let networks = Networks::builtin();
+        let custom = networks.get("ws://10.0.0.7:9944").unwrap();
+        assert_eq!(custom, Network::custom("ws://10.0.0.7:9944"));
+        assert!(networks
+            .get("kusama")
+            .unwrap_err()
+            .contains("known networks are local, rococo-contracts, shibuya"));
+    }
+
+    #[test]
+    fn bad_profiles_are_rejected() {
+        assert!(Networks::parse("[x]\nurl = \"ws://a\"\n").is_err());
+        assert!(
+            Networks::parse("[x]\nurl = \"ws://a\"\nsigner = { suri = \"//A\" }\nport = 1\n")
+                .is_err()
+        );
+        assert!(Networks::parse("[x]\nurl = \"ws://a\"\nsigner = { seed = \"0x00\" }\n").is_err());
+        let signed = "[x]\nurl = \"ws://a\"\nsigner = { suri = \"//A\" }\n";
+        assert!(
+            Networks::parse(&format!("{}faucet = {{ dev_account = \"//A\" }}\n", signed)).is_err()
+        );
+        assert!(Networks::parse(&format!(
+            "{}faucet = {{ contract = \"5D\", amount = 1 }}\n",
+            signed
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn addresses_use_the_network_prefix() {
+        let alice = parse_account("alice").unwrap();
+        assert_eq!(
+            ss58_encode(42, alice),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+        let shibuya = Networks::builtin().get("shibuya").unwrap().address(alice);
+        assert_eq!(shibuya, "ajYMsCKsEAhEvHpeA4XqsfiA9v1CdzZPrCfS6pEfeGHW9j8");
+        // Addresses of any network are read back as the same account.
+        assert_eq!(parse_account(&shibuya).unwrap(), alice);
+        assert_eq!(parse_account(&ss58_encode(1284, alice)).unwrap(), alice);
+    }
+}
@@ -0,0 +1,74 @@
+// tools/deployer/src/submit.rs
+//
+// Submitting transactions once their dry run went through. A contract call is
+// submitted with the gas limit and storage deposit its dry run reported as needed,
+// rather than a guess, and the submission is watched until it is in a block.
+
+use crate::dry_run::{CallRequest, DryRun, Weight};
+use repl::keystore::KeyPair;
+use subxt::{
+    backend::rpc::RpcClient, blocks::ExtrinsicEvents, dynamic::Value, tx::Payload, OnlineClient,
+    SubstrateConfig,
+};
+
+type Client = OnlineClient<SubstrateConfig>;
+
+/// A `Contracts::call` of `request`, with the gas and deposit its dry run needed.
+pub fn call_tx<R>(request: &CallRequest, dry_run: &DryRun<R>) -> impl Payload {
+    subxt::dynamic::tx(
+        "Contracts",
+        "call",
+        vec![
+            Value::unnamed_variant("Id", [Value::from_bytes(request.dest)]),
+            Value::u128(request.value),
+            weight(dry_run.gas_required),
+            deposit_limit(dry_run.storage_deposit.charge()),
+            Value::from_bytes(&request.input_data),
+        ],
+    )
+}
+
+/// `weight` as the `Weight` argument of a dynamic transaction.
+pub fn weight(weight: Weight) -> Value {
+    Value::named_composite([
+        ("ref_time", Value::u128(weight.ref_time.into())),
+        ("proof_size", Value::u128(weight.proof_size.into())),
+    ])
+}
+
+/// `limit` as the `Option<Compact<Balance>>` deposit limit of a dynamic transaction.
+pub fn deposit_limit(limit: Option<u128>) -> Value {
+    match limit {
+        Some(limit) => Value::unnamed_variant("Some", [Value::u128(limit)]),
+        None => Value::unnamed_variant("None", []),
+    }
+}
+
+/// Signs and submits `tx`, waiting until it is in a block.
+pub async fn submit(
+    rpc: RpcClient,
+    signer: &KeyPair,
+    tx: &impl Payload,
+) -> Result<ExtrinsicEvents<SubstrateConfig>, String> {
+    let client = Client::from_rpc_client(rpc)
+        .await
+        .map_err(|err| err.to_string())?;
+    let mut progress = client
+        .tx()
+        .sign_and_submit_then_watch_default(tx, signer)
+        .await
+        .map_err(|err| format!("submission failed: {}", err))?;
+    while let Some(status) = progress.next().await {
+        let status = status.map_err(|err| err.to_string())?;
+        let in_block = match status.as_in_block().or_else(|| status.as_finalized()) {
+            Some(in_block) => in_block,
+            None => continue,
+        };
+        println!("included in block {:?}", in_block.block_hash());
+        return in_block
+            .wait_for_success()
+            .await
+            .map_err(|err| format!("transaction failed: {}", err));
+    }
+    Err("transaction was dropped before inclusion".to_owned())
+}
@@ -0,0 +1,154 @@
+// tools/deployer/tests/common/mod.rs
+//
+// A mocked contracts node shared by the integration tests. `MockNode` answers
+// `state_call` requests with SCALE-encoded results, as a contracts node would, and
+// `chain_getHeader` with the block numbers it was given; both are answered in order,
+// repeating the last answer, and every `state_call` is recorded.
+
+#![allow(dead_code)]
+
+use deployer::{DispatchError, StorageDeposit, Weight};
+use repl::value::{parse_hex, to_hex};
+use scale::{Compact, Encode};
+use serde_json::{json, value::RawValue, Value};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use subxt::{
+    backend::rpc::{RawRpcFuture, RawRpcSubscription, RpcClient, RpcClientT},
+    error::RpcError,
+};
+
+pub const ALICE: [u8; 32] = [0xd4; 32];
+pub const CONTRACT: [u8; 32] = [0x42; 32];
+pub const GAS_CONSUMED: Weight = Weight {
+    ref_time: 1_000_000_000,
+    proof_size: 16_000,
+};
+pub const GAS_REQUIRED: Weight = Weight {
+    ref_time: 1_200_000_000,
+    proof_size: 18_000,
+};
+
+/// The `(function, args)` of every `state_call` a `MockNode` received, and the
+/// `chain_getHeader` requests as `("chain_getHeader", [])`.
+pub type Requests = Arc<Mutex<Vec<(String, Vec<u8>)>>>;
+
+/// A node that answers `state_call` for the runtime API functions it was given, and
+/// `chain_getHeader` with the blocks it was given.
+#[derive(Default)]
+pub struct MockNode {
+    results: HashMap<&'static str, Vec<Vec<u8>>>,
+    blocks: Vec<u32>,
+    requests: Requests,
+}
+
+impl MockNode {
+    pub fn answering(function: &'static str, result: Vec<u8>) -> Self {
+        MockNode::default().then(function, result)
+    }
+
+    /// Answers the next `state_call` of `function` with `result`, after those given
+    /// before.
+    pub fn then(mut self, function: &'static str, result: Vec<u8>) -> Self {
+        self.results.entry(function).or_default().push(result);
+        self
+    }
+
+    /// Reports `blocks` as the latest block, one per `chain_getHeader` request.
+    pub fn at_blocks(mut self, blocks: &[u32]) -> Self {
+        self.blocks = blocks.to_vec();
+        self
+    }
+
+    /// Starts the node, returning a client and the requests it will receive.
+    pub fn start(self) -> (RpcClient, Requests) {
+        let requests = self.requests.clone();
+        (RpcClient::new(self), requests)
+    }
+}
+
+impl RpcClientT for MockNode {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        params: Option<Box<RawValue>>,
+    ) -> RawRpcFuture<'a, Box<RawValue>> {
+        Box::pin(async move {
+            let answer = match method {
+                "state_call" => {
+                    // `[function, "0x" args, block hash or null]`
+                    let params: Vec<Value> = serde_json::from_str(params.unwrap().get()).unwrap();
+                    let function = params[0].as_str().unwrap().to_owned();
+                    let args = parse_hex(params[1].as_str().unwrap()).unwrap();
+                    let mut requests = self.requests.lock().unwrap();
+                    let asked = requests.iter().filter(|(f, _)| *f == function).count();
+                    requests.push((function.clone(), args));
+
+                    let results = self
+                        .results
+                        .get(function.as_str())
+                        .ok_or_else(|| RpcError::request_rejected("Method not found"))?;
+                    json!(to_hex(&results[asked.min(results.len() - 1)]))
+                }
+                "chain_getHeader" => {
+                    let mut requests = self.requests.lock().unwrap();
+                    let asked = requests.iter().filter(|(f, _)| f == method).count();
+                    requests.push((method.to_owned(), Vec::new()));
+                    let number = *self
+                        .blocks
+                        .get(asked.min(self.blocks.len().saturating_sub(1)))
+                        .ok_or_else(|| RpcError::request_rejected("no blocks mocked"))?;
+                    json!({
+                        "parentHash": to_hex(&[0; 32]),
+                        "number": format!("0x{:x}", number),
+                        "stateRoot": to_hex(&[0; 32]),
+                        "extrinsicsRoot": to_hex(&[0; 32]),
+                        "digest": { "logs": [] },
+                    })
+                }
+                _ => {
+                    return Err(RpcError::request_rejected(format!(
+                        "{} is not mocked",
+                        method
+                    )))
+                }
+            };
+            Ok(RawValue::from_string(answer.to_string()).unwrap())
+        })
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        _sub: &'a str,
+        _params: Option<Box<RawValue>>,
+        _unsub: &'a str,
+    ) -> RawRpcFuture<'a, RawRpcSubscription> {
+        Box::pin(async { Err(RpcError::request_rejected("subscriptions are not mocked")) })
+    }
+}
+
+/// Encodes a dry-run result as the contracts pallet does, with `events` opaque event
+/// records if given.
+pub fn encode_result<R: Encode>(
+    storage_deposit: StorageDeposit,
+    debug_message: &str,
+    result: Result<R, DispatchError>,
+    events: Option<u32>,
+) -> Vec<u8> {
+    let mut bytes = (
+        GAS_CONSUMED,
+        GAS_REQUIRED,
+        storage_deposit,
+        debug_message.as_bytes().to_vec(),
+        result,
+    )
+        .encode();
+    if let Some(count) = events {
+        bytes.push(1);
+        bytes.extend(Compact(count).encode());
+        bytes.extend(vec![0xee; 40 * count as usize]);
+    }
+    bytes
+}
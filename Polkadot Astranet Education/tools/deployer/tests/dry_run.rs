@@ -0,0 +1,172 @@
+// tools/deployer/tests/dry_run.rs
+//
+// Runs dry runs against a mocked node, see `common`.
+
+mod common;
+
+use common::{encode_result, MockNode, ALICE, CONTRACT};
+use deployer::{
+    dry_run, CallDryRun, CallRequest, Code, DispatchError, ExecReturnValue, InstantiateRequest,
+    InstantiateReturnValue, StorageDeposit,
+};
+use repl::value::to_hex;
+use scale::Decode;
+
+fn call_request(input_data: Vec<u8>) -> CallRequest {
+    CallRequest {
+        origin: ALICE,
+        dest: CONTRACT,
+        value: 0,
+        gas_limit: None,
+        storage_deposit_limit: None,
+        input_data,
+    }
+}
+
+fn returned(flags: u32, data: Vec<u8>) -> Result<ExecReturnValue, DispatchError> {
+    Ok(ExecReturnValue { flags, data })
+}
+
+#[tokio::test]
+async fn call_dry_runs_report_gas_storage_and_events() {
+    let result = encode_result(
+        StorageDeposit::Charge(3_000),
+        "transfer value=5\n",
+        returned(0, vec![0]),
+        Some(2),
+    );
+    let (rpc, requests) = MockNode::answering("ContractsApi_call", result).start();
+
+    let request = call_request(vec![0x84, 0xa1, 0x5d, 0xa1, 5]);
+    let dry_run = dry_run::call(&rpc, &request).await.unwrap();
+    assert!(dry_run.check().is_ok());
+    assert_eq!(dry_run.storage_deposit.charge(), Some(3_000));
+    assert_eq!(
+        dry_run.report(42, to_hex),
+        [
+            "result: ok, returned 0x00",
+            "gas required: ref_time 1200000000, proof_size 18000 \
+             (consumed: ref_time 1000000000, proof_size 16000)",
+            "storage: grows, deposit charged 3000",
+            "events emitted: 2",
+            "debug: transfer value=5",
+        ]
+    );
+
+    let requests = requests.lock().unwrap();
+    let (function, args) = &requests[0];
+    assert_eq!(function, "ContractsApi_call");
+    assert_eq!(CallRequest::decode(&mut args.as_slice()).unwrap(), request);
+}
+
+#[tokio::test]
+async fn reverted_calls_are_refused() {
+    let result = encode_result(StorageDeposit::Charge(0), "", returned(1, vec![1, 0]), None);
+    let (rpc, _) = MockNode::answering("ContractsApi_call", result).start();
+
+    let dry_run = dry_run::call(&rpc, &call_request(vec![0; 4]))
+        .await
+        .unwrap();
+    let err = dry_run.check().unwrap_err();
+    assert!(err.contains("reverted, returning 0x0100"), "{}", err);
+    let report = dry_run.report(42, to_hex);
+    assert_eq!(report[0], "result: REVERTED, returned 0x0100");
+    assert_eq!(report[2], "storage: unchanged, no deposit");
+}
+
+#[tokio::test]
+async fn failed_calls_are_refused() {
+    let trapped = DispatchError::Module {
+        index: 8,
+        error: [11, 0, 0, 0],
+    };
+    let result =
+        encode_result::<ExecReturnValue>(StorageDeposit::Refund(0), "", Err(trapped), Some(0));
+    let (rpc, _) = MockNode::answering("ContractsApi_call", result).start();
+
+    let dry_run: CallDryRun = dry_run::call(&rpc, &call_request(vec![0; 4]))
+        .await
+        .unwrap();
+    assert_eq!(dry_run.result, Err(trapped));
+    assert!(dry_run.check().is_err());
+    assert_eq!(
+        dry_run.report(42, to_hex)[0],
+        "result: failed with Module { index: 8, error: [11, 0, 0, 0] }"
+    );
+}
+
+#[tokio::test]
+async fn instantiate_dry_runs_report_the_address() {
+    let result = encode_result(
+        StorageDeposit::Charge(1_500_000),
+        "",
+        Ok(InstantiateReturnValue {
+            result: ExecReturnValue {
+                flags: 0,
+                data: Vec::new(),
+            },
+            account_id: CONTRACT,
+        }),
+        Some(1),
+    );
+    let (rpc, requests) = MockNode::answering("ContractsApi_instantiate", result).start();
+
+    let request = InstantiateRequest {
+        origin: ALICE,
+        value: 0,
+        gas_limit: None,
+        storage_deposit_limit: None,
+        code: Code::Upload(b"\0asm\x01\0\0\0".to_vec()),
+        data: vec![0x9b, 0xae, 0x9d, 0x5e, 1],
+        salt: Vec::new(),
+    };
+    let dry_run = dry_run::instantiate(&rpc, &request).await.unwrap();
+    assert_eq!(dry_run.check().unwrap().account_id, CONTRACT);
+    let report = dry_run.report(42, to_hex);
+    assert_eq!(
+        report[1],
+        "address: 5DZahar1q7Bd1h9yA8ngnxsgq76E7vS5Wj2KAVkjee9mhP9o"
+    );
+    assert_eq!(report[3], "storage: grows, deposit charged 1500000");
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests[0].0, "ContractsApi_instantiate");
+    assert_eq!(
+        InstantiateRequest::decode(&mut requests[0].1.as_slice()).unwrap(),
+        request
+    );
+}
+
+#[tokio::test]
+async fn nodes_without_events_are_supported() {
+    let result = encode_result(
+        StorageDeposit::Refund(200),
+        "",
+        returned(0, Vec::new()),
+        None,
+    );
+    let (rpc, _) = MockNode::answering("ContractsApi_call", result).start();
+
+    let dry_run = dry_run::call(&rpc, &call_request(vec![0; 4]))
+        .await
+        .unwrap();
+    assert_eq!(dry_run.events, None);
+    let report = dry_run.report(42, to_hex);
+    assert_eq!(report[2], "storage: shrinks, deposit refunded 200");
+    assert_eq!(report[3], "events emitted: not reported by the node");
+}
+
+#[tokio::test]
+async fn rpc_errors_are_reported() {
+    let (rpc, _) = MockNode::default().start();
+    let err = dry_run::call(&rpc, &call_request(vec![0; 4]))
+        .await
+        .unwrap_err();
+    assert!(err.starts_with("ContractsApi_call failed"), "{}", err);
+
+    let (rpc, _) = MockNode::answering("ContractsApi_call", vec![0x01]).start();
+    let err = dry_run::call(&rpc, &call_request(vec![0; 4]))
+        .await
+        .unwrap_err();
+    assert!(err.starts_with("cannot decode the result"), "{}", err);
+}
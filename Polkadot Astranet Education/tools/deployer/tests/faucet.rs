@@ -0,0 +1,167 @@
+// tools/deployer/tests/faucet.rs
+//
+// Runs the faucet client against a mocked node that answers as a deployment of
+// `examples/demo-contracts/faucet.rs` would: `Faucet::new(10, 2, 100)` pays 10 per
+// drip, two drips in a row, then one more every 100 blocks. Its replies are encoded
+// with the faucet's error type as mirrored in the errors crate.
+
+mod common;
+
+use astranet_errors::faucet::Error;
+use common::{encode_result, MockNode, ALICE, CONTRACT};
+use deployer::{
+    faucet::{self, Cooldown},
+    CallRequest, ExecReturnValue, Retry, StorageDeposit,
+};
+use scale::{Decode, Encode};
+use std::time::Duration;
+
+const CALL: &str = "ContractsApi_call";
+const HEADER: &str = "chain_getHeader";
+
+/// Retries without sleeping between polls.
+fn retry(attempts: u32, max_wait: u32) -> Retry {
+    Retry {
+        attempts,
+        max_wait,
+        poll: Duration::ZERO,
+    }
+}
+
+/// What a dry run of `drip` returns: a failed drip reverts with the error.
+fn drip(result: Result<(), Error>) -> Vec<u8> {
+    let flags = if result.is_err() { 1 } else { 0 };
+    encode_result(
+        StorageDeposit::Charge(0),
+        "",
+        Ok(ExecReturnValue {
+            flags,
+            data: result.encode(),
+        }),
+        Some(if result.is_ok() { 1 } else { 0 }),
+    )
+}
+
+fn rate_limited(retry_at: u32) -> Vec<u8> {
+    drip(Err(Error::RateLimited { retry_at }))
+}
+
+#[tokio::test]
+async fn drips_are_dry_run_as_the_learner() {
+    let (rpc, requests) = MockNode::answering(CALL, drip(Ok(()))).start();
+    let mut waited = Vec::new();
+    let dry_run = faucet::ready_to_drip(&rpc, CONTRACT, ALICE, retry(3, 50), |cooldown| {
+        waited.push(cooldown)
+    })
+    .await
+    .unwrap();
+    assert!(dry_run.check().is_ok());
+    assert!(waited.is_empty());
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    let request = CallRequest::decode(&mut requests[0].1.as_slice()).unwrap();
+    assert_eq!(request.origin, ALICE);
+    assert_eq!(request.dest, CONTRACT);
+    assert_eq!(request.input_data, selector::compute("drip"));
+}
+
+#[tokio::test]
+async fn cooldowns_are_waited_out_and_retried() {
+    let node = MockNode::answering(CALL, rate_limited(12))
+        .then(CALL, drip(Ok(())))
+        .at_blocks(&[10, 10, 11, 12]);
+    let (rpc, requests) = node.start();
+    let mut waited = Vec::new();
+    let dry_run = faucet::ready_to_drip(&rpc, CONTRACT, ALICE, retry(3, 50), |cooldown| {
+        waited.push(cooldown)
+    })
+    .await
+    .unwrap();
+    assert!(dry_run.check().is_ok());
+    assert_eq!(
+        waited,
+        [Cooldown {
+            now: 10,
+            retry_at: 12
+        }]
+    );
+
+    // One dry run, the cooldown check, polls until block 12, and the second dry run.
+    let methods: Vec<String> = requests
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(method, _)| method.clone())
+        .collect();
+    assert_eq!(methods, [CALL, HEADER, HEADER, HEADER, HEADER, CALL]);
+}
+
+#[tokio::test]
+async fn long_cooldowns_fail_without_waiting() {
+    // Two drips at block 5 leave bob's bucket empty until block 105.
+    let node = MockNode::answering(CALL, rate_limited(105)).at_blocks(&[5]);
+    let (rpc, requests) = node.start();
+    let err = faucet::ready_to_drip(&rpc, CONTRACT, ALICE, retry(3, 50), |_| {
+        panic!("waited for a cooldown longer than --max-wait")
+    })
+    .await
+    .unwrap_err();
+    assert!(
+        err.contains("rate limited until block 105, 100 blocks"),
+        "{}",
+        err
+    );
+    assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn retries_stop_after_the_last_attempt() {
+    let node = MockNode::answering(CALL, rate_limited(11))
+        .then(CALL, rate_limited(12))
+        .at_blocks(&[10, 11, 11, 12]);
+    let (rpc, _) = node.start();
+    let mut waited = 0;
+    let err = faucet::ready_to_drip(&rpc, CONTRACT, ALICE, retry(2, 50), |_| waited += 1)
+        .await
+        .unwrap_err();
+    assert_eq!(waited, 1);
+    assert!(
+        err.contains("still rate limited after 2 attempt(s)"),
+        "{}",
+        err
+    );
+    assert!(err.contains("from block 12"), "{}", err);
+}
+
+#[tokio::test]
+async fn empty_faucets_are_not_retried() {
+    let (rpc, requests) = MockNode::answering(CALL, drip(Err(Error::FaucetEmpty))).start();
+    let err = faucet::ready_to_drip(&rpc, CONTRACT, ALICE, retry(3, 50), |_| {})
+        .await
+        .unwrap_err();
+    assert_eq!(err, "the faucet refused: the faucet cannot cover a drip");
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn drip_amounts_are_queried() {
+    let amount = encode_result(
+        StorageDeposit::Charge(0),
+        "",
+        Ok(ExecReturnValue {
+            flags: 0,
+            data: 10u128.encode(),
+        }),
+        None,
+    );
+    let (rpc, requests) = MockNode::answering(CALL, amount).start();
+    assert_eq!(
+        faucet::drip_amount(&rpc, CONTRACT, ALICE).await.unwrap(),
+        10
+    );
+
+    let requests = requests.lock().unwrap();
+    let request = CallRequest::decode(&mut requests[0].1.as_slice()).unwrap();
+    assert_eq!(request.input_data, selector::compute("drip_amount"));
+}
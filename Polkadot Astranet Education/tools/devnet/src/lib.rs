@@ -0,0 +1,298 @@
+// tools/devnet/src/lib.rs
+//
+// The pieces of the devnet sandbox that do not need a running node:
+//
+// - where to download `substrate-contracts-node` from,
+// - how to add funded accounts to its chain spec, so learners' own addresses start
+//   with a balance without anyone signing a transfer,
+// - how to ask the node whether its RPC server is up,
+// - which constructor of a bundle can be called without arguments.
+//
+// The binary strings these together; everything here is plain data in, data out.
+
+use serde_json::{json, Value};
+use std::fmt;
+
+/// Release of `substrate-contracts-node` downloaded when none is installed.
+pub const DEFAULT_NODE_VERSION: &str = "v0.41.0";
+
+/// Balance given to accounts funded without an explicit amount: 1_000_000 UNIT at
+/// the node's 12 decimals.
+pub const DEFAULT_FUNDING: u64 = 1_000_000_000_000_000_000;
+
+/// An account to fund at genesis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Funding {
+    /// SS58 address, as it appears in the chain spec.
+    pub address: String,
+    pub amount: u64,
+}
+
+/// Errors returned while preparing the devnet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DevnetError {
+    /// No release is published for this platform.
+    UnsupportedPlatform(String),
+    /// A `--fund` argument could not be parsed.
+    InvalidFunding(String),
+    /// The chain spec is not JSON or has no balances section.
+    InvalidChainSpec(String),
+}
+
+impl fmt::Display for DevnetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DevnetError::UnsupportedPlatform(os) => {
+                write!(
+                    f,
+                    "no substrate-contracts-node release for {}; pass --node",
+                    os
+                )
+            }
+            DevnetError::InvalidFunding(arg) => {
+                write!(
+                    f,
+                    "invalid --fund `{}`, expected ADDRESS or ADDRESS=AMOUNT",
+                    arg
+                )
+            }
+            DevnetError::InvalidChainSpec(what) => write!(f, "invalid chain spec: {}", what),
+        }
+    }
+}
+
+impl std::error::Error for DevnetError {}
+
+/// Returns the download URL of the release tarball for `os` (`std::env::consts::OS`).
+pub fn release_url(version: &str, os: &str) -> Result<String, DevnetError> {
+    let platform = match os {
+        "linux" => "linux",
+        "macos" => "mac-universal",
+        other => return Err(DevnetError::UnsupportedPlatform(other.to_owned())),
+    };
+    Ok(format!(
+        "https://github.com/paritytech/substrate-contracts-node/releases/download/{}/substrate-contracts-node-{}.tar.gz",
+        version, platform
+    ))
+}
+
+/// Parses a `--fund` argument of the form `ADDRESS` or `ADDRESS=AMOUNT`.
+pub fn parse_funding(arg: &str) -> Result<Funding, DevnetError> {
+    let invalid = || DevnetError::InvalidFunding(arg.to_owned());
+    let (address, amount) = match arg.split_once('=') {
+        Some((address, amount)) => (address, amount.parse().map_err(|_| invalid())?),
+        None => (arg, DEFAULT_FUNDING),
+    };
+    // SS58 addresses are base58 and therefore purely alphanumeric.
+    if address.is_empty() || !address.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(invalid());
+    }
+    Ok(Funding {
+        address: address.to_owned(),
+        amount,
+    })
+}
+
+/// Adds every account in `funding` to the genesis balances of a chain spec produced
+/// by `substrate-contracts-node build-spec`, replacing the amount of accounts that
+/// are already funded.
+///
+/// Depending on the node version the balances live under `genesis.runtime`,
+/// `genesis.runtimeGenesis.config` or `genesis.runtimeGenesis.patch`.
+pub fn fund_accounts(chain_spec: &str, funding: &[Funding]) -> Result<String, DevnetError> {
+    let mut spec: Value = serde_json::from_str(chain_spec)
+        .map_err(|err| DevnetError::InvalidChainSpec(err.to_string()))?;
+
+    let pointer = [
+        "/genesis/runtime/balances/balances",
+        "/genesis/runtimeGenesis/config/balances/balances",
+        "/genesis/runtimeGenesis/patch/balances/balances",
+    ]
+    .into_iter()
+    .find(|pointer| spec.pointer(pointer).is_some_and(Value::is_array))
+    .ok_or_else(|| DevnetError::InvalidChainSpec("no genesis balances".to_owned()))?;
+    let balances = spec
+        .pointer_mut(pointer)
+        .and_then(Value::as_array_mut)
+        .expect("pointer was checked above");
+
+    for account in funding {
+        balances.retain(|entry| entry[0] != account.address.as_str());
+        balances.push(json!([account.address, account.amount]));
+    }
+    serde_json::to_string_pretty(&spec)
+        .map_err(|err| DevnetError::InvalidChainSpec(err.to_string()))
+}
+
+/// Returns the raw HTTP request asking the node at `port` for `system_health`.
+pub fn health_request(port: u16) -> String {
+    let body = r#"{"jsonrpc":"2.0","id":1,"method":"system_health","params":[]}"#;
+    format!(
+        "POST / HTTP/1.1\r\nHost: 127.0.0.1:{}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        port,
+        body.len(),
+        body
+    )
+}
+
+/// Returns whether a raw HTTP response to `health_request` reports a node that is
+/// ready to accept transactions, i.e. answered and is not still syncing.
+pub fn is_healthy(response: &str) -> bool {
+    let body = match response.split_once("\r\n\r\n") {
+        Some((_, body)) => body,
+        None => return false,
+    };
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|value| value.pointer("/result/isSyncing").and_then(Value::as_bool))
+        == Some(false)
+}
+
+/// Returns the label of the first constructor in a `.contract` bundle that takes no
+/// arguments, which is the only kind the devnet can deploy unattended.
+pub fn zero_arg_constructor(bundle: &str) -> Option<String> {
+    let root: Value = serde_json::from_str(bundle).ok()?;
+    let spec = root.get("spec").or_else(|| {
+        root.as_object()?
+            .iter()
+            .filter(|(key, _)| key.starts_with('V'))
+            .find_map(|(_, versioned)| versioned.get("spec"))
+    })?;
+    spec.get("constructors")?
+        .as_array()?
+        .iter()
+        .find(|constructor| {
+            constructor
+                .get("args")
+                .and_then(Value::as_array)
+                .is_none_or(Vec::is_empty)
+        })?
+        .get("label")?
+        .as_str()
+        .map(str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+
+    fn chain_spec(section: &str) -> String {
+        let mut spec = json!({ "name": "Development", "genesis": {} });
+        let balances = json!({ "balances": { "balances": [[ALICE, 1000]] } });
+        spec["genesis"] = match section {
+            "runtime" => json!({ "runtime": balances }),
+            other => json!({ "runtimeGenesis": { other: balances } }),
+        };
+        spec.to_string()
+    }
+
+    #[test]
+    fn release_urls() {
+        assert!(release_url("v0.41.0", "linux")
+            .unwrap()
+            .ends_with("/v0.41.0/substrate-contracts-node-linux.tar.gz"));
+        assert!(release_url("v0.41.0", "macos")
+            .unwrap()
+            .contains("mac-universal"));
+        assert_eq!(
+            release_url("v0.41.0", "windows"),
+            Err(DevnetError::UnsupportedPlatform("windows".to_owned()))
+        );
+    }
+
+    #[test]
+    fn parses_funding_arguments() {
+        assert_eq!(
+            parse_funding(BOB),
+            Ok(Funding {
+                address: BOB.to_owned(),
+                amount: DEFAULT_FUNDING
+            })
+        );
+        assert_eq!(parse_funding(&format!("{}=42", BOB)).unwrap().amount, 42);
+        assert!(parse_funding(&format!("{}=lots", BOB)).is_err());
+        assert!(parse_funding("").is_err());
+        assert!(parse_funding("not an address").is_err());
+    }
+
+    #[test]
+    fn funds_accounts_in_every_spec_layout() {
+        let funding = [Funding {
+            address: BOB.to_owned(),
+            amount: 7,
+        }];
+        for section in ["runtime", "config", "patch"] {
+            let patched: Value =
+                serde_json::from_str(&fund_accounts(&chain_spec(section), &funding).unwrap())
+                    .unwrap();
+            let balances = patched["genesis"]
+                .as_object()
+                .unwrap()
+                .values()
+                .next()
+                .unwrap();
+            let balances = match section {
+                "runtime" => &balances["balances"]["balances"],
+                other => &balances[other]["balances"]["balances"],
+            };
+            assert_eq!(balances, &json!([[ALICE, 1000], [BOB, 7]]), "{}", section);
+        }
+    }
+
+    #[test]
+    fn funding_replaces_existing_amount() {
+        let funding = [Funding {
+            address: ALICE.to_owned(),
+            amount: 5,
+        }];
+        let patched: Value =
+            serde_json::from_str(&fund_accounts(&chain_spec("patch"), &funding).unwrap()).unwrap();
+        assert_eq!(
+            patched["genesis"]["runtimeGenesis"]["patch"]["balances"]["balances"],
+            json!([[ALICE, 5]])
+        );
+    }
+
+    #[test]
+    fn rejects_spec_without_balances() {
+        assert!(matches!(
+            fund_accounts(r#"{"genesis":{}}"#, &[]),
+            Err(DevnetError::InvalidChainSpec(_))
+        ));
+    }
+
+    #[test]
+    fn health_request_has_matching_length() {
+        let request = health_request(9944);
+        let (head, body) = request.split_once("\r\n\r\n").unwrap();
+        assert!(head.contains(&format!("Content-Length: {}", body.len())));
+        assert!(head.contains("Host: 127.0.0.1:9944"));
+    }
+
+    #[test]
+    fn reads_health_responses() {
+        let ready = "HTTP/1.1 200 OK\r\n\r\n{\"jsonrpc\":\"2.0\",\"result\":{\"isSyncing\":false,\"peers\":0,\"shouldHavePeers\":false},\"id\":1}";
+        let syncing = ready.replace("false,\"peers", "true,\"peers");
+        assert!(is_healthy(ready));
+        assert!(!is_healthy(&syncing));
+        assert!(!is_healthy("HTTP/1.1 503 Service Unavailable\r\n\r\n"));
+        assert!(!is_healthy(""));
+    }
+
+    #[test]
+    fn finds_zero_arg_constructor() {
+        let flipper = r#"{ "spec": { "constructors": [
+            { "label": "new", "args": [{ "label": "init_value" }] },
+            { "label": "default", "args": [] }
+        ] } }"#;
+        assert_eq!(zero_arg_constructor(flipper), Some("default".to_owned()));
+
+        let erc20 = r#"{ "V3": { "spec": { "constructors": [
+            { "label": "new", "args": [{ "label": "initial_supply" }] }
+        ] } } }"#;
+        assert_eq!(zero_arg_constructor(erc20), None);
+    }
+}
@@ -0,0 +1,242 @@
+// tools/devnet/src/main.rs
+//
+// Usage:
+//   devnet                                   download (if needed) and start the node
+//   devnet --fund 5F...=1000000000000        also fund an address at genesis
+//   devnet --deploy dist                     also deploy every bundle in dist/
+//   devnet --debug                           also log contract debug messages
+//
+// Needs `curl` and `tar` to download the node, and `cargo contract` to deploy.
+// The node runs in the foreground with a temporary database; Ctrl-C stops it.
+
+use clap::Parser;
+use devnet::{
+    fund_accounts, health_request, is_healthy, parse_funding, release_url, zero_arg_constructor,
+    Funding, DEFAULT_NODE_VERSION,
+};
+use std::{
+    fs,
+    io::{Read, Write},
+    net::TcpStream,
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitCode, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+const NODE_BINARY: &str = "substrate-contracts-node";
+
+#[derive(Parser)]
+#[command(about = "Start a local contracts node with funded accounts and deployed examples")]
+struct Cli {
+    /// Node binary to run instead of looking it up or downloading it.
+    #[arg(long)]
+    node: Option<PathBuf>,
+    /// Release to download when no node is installed.
+    #[arg(long, default_value = DEFAULT_NODE_VERSION)]
+    version: String,
+    /// Directory for the downloaded node and generated chain spec.
+    #[arg(long, default_value = ".devnet")]
+    cache: PathBuf,
+    /// RPC port of the node.
+    #[arg(long, default_value_t = 9944)]
+    port: u16,
+    /// Account to fund at genesis, as ADDRESS or ADDRESS=AMOUNT. Repeatable.
+    #[arg(long = "fund", value_parser = parse_funding)]
+    funding: Vec<Funding>,
+    /// Directory of `.contract` bundles (see tools/bundle) to deploy as //Alice.
+    #[arg(long)]
+    deploy: Option<PathBuf>,
+    /// Seconds to wait for the node's RPC server.
+    #[arg(long, default_value_t = 60)]
+    timeout: u64,
+    /// Log the debug messages of contract dry-runs, such as `astranet_utils::trace!`
+    /// lines from contracts built with `--features debug`.
+    #[arg(long)]
+    debug: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<(), String> {
+    fs::create_dir_all(&cli.cache).map_err(|err| format!("{}: {}", cli.cache.display(), err))?;
+    let node = match &cli.node {
+        Some(node) => node.clone(),
+        None => find_or_download(cli)?,
+    };
+
+    let spec = write_chain_spec(&node, &cli.cache, &cli.funding)?;
+    let mut command = Command::new(&node);
+    command
+        .args(["--dev", "--tmp", "--rpc-cors", "all", "--rpc-port"])
+        .arg(cli.port.to_string())
+        .arg("--chain")
+        .arg(&spec);
+    if cli.debug {
+        command.arg("-lruntime::contracts=debug");
+    }
+    let mut child = command
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|err| format!("cannot start {}: {}", node.display(), err))?;
+
+    if let Err(err) = wait_until_ready(&mut child, cli.port, Duration::from_secs(cli.timeout)) {
+        let _ = child.kill();
+        return Err(err);
+    }
+    println!("node ready at ws://127.0.0.1:{}", cli.port);
+    for account in &cli.funding {
+        println!("funded {} with {}", account.address, account.amount);
+    }
+
+    if let Some(dist) = &cli.deploy {
+        if let Err(err) = deploy_all(dist, cli.port) {
+            let _ = child.kill();
+            return Err(err);
+        }
+    }
+
+    println!("press Ctrl-C to stop the node");
+    child.wait().map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+/// Returns the node from `PATH`, the cache, or a fresh download, in that order.
+fn find_or_download(cli: &Cli) -> Result<PathBuf, String> {
+    if Command::new(NODE_BINARY)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+    {
+        return Ok(PathBuf::from(NODE_BINARY));
+    }
+
+    let dir = cli.cache.join(&cli.version);
+    let cached = dir.join(NODE_BINARY);
+    if cached.is_file() {
+        return Ok(cached);
+    }
+
+    let url = release_url(&cli.version, std::env::consts::OS).map_err(|err| err.to_string())?;
+    println!("downloading {}", url);
+    fs::create_dir_all(&dir).map_err(|err| format!("{}: {}", dir.display(), err))?;
+    let tarball = dir.join("node.tar.gz");
+    run_command(
+        Command::new("curl")
+            .args(["-fL", "-o"])
+            .arg(&tarball)
+            .arg(&url),
+    )?;
+    run_command(
+        Command::new("tar")
+            .arg("-xzf")
+            .arg(&tarball)
+            .arg("-C")
+            .arg(&dir),
+    )?;
+
+    // Releases have shipped the binary both at the top level and in a subdirectory.
+    [cached.clone(), dir.join("artifacts").join(NODE_BINARY)]
+        .into_iter()
+        .find(|path| path.is_file())
+        .ok_or_else(|| format!("{} not found in {}", NODE_BINARY, url))
+}
+
+fn write_chain_spec(node: &Path, cache: &Path, funding: &[Funding]) -> Result<PathBuf, String> {
+    let output = Command::new(node)
+        .args(["build-spec", "--dev"])
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|err| format!("cannot run {}: {}", node.display(), err))?;
+    if !output.status.success() {
+        return Err(format!("{} build-spec failed", node.display()));
+    }
+
+    let spec = String::from_utf8_lossy(&output.stdout);
+    let patched = fund_accounts(&spec, funding).map_err(|err| err.to_string())?;
+    let path = cache.join("chain-spec.json");
+    fs::write(&path, patched).map_err(|err| format!("{}: {}", path.display(), err))?;
+    Ok(path)
+}
+
+fn wait_until_ready(child: &mut Child, port: u16, timeout: Duration) -> Result<(), String> {
+    let started = Instant::now();
+    while started.elapsed() < timeout {
+        if let Ok(Some(status)) = child.try_wait() {
+            return Err(format!("node exited during startup ({})", status));
+        }
+        if query_health(port).is_ok_and(|response| is_healthy(&response)) {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(500));
+    }
+    Err(format!("node RPC not ready after {}s", timeout.as_secs()))
+}
+
+fn query_health(port: u16) -> std::io::Result<String> {
+    let mut stream = TcpStream::connect(("127.0.0.1", port))?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    stream.write_all(health_request(port).as_bytes())?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    Ok(response)
+}
+
+fn deploy_all(dist: &Path, port: u16) -> Result<(), String> {
+    let entries = fs::read_dir(dist).map_err(|err| format!("{}: {}", dist.display(), err))?;
+    let mut bundles: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("contract"))
+        .collect();
+    bundles.sort();
+
+    let url = format!("ws://127.0.0.1:{}", port);
+    for bundle in bundles {
+        let json =
+            fs::read_to_string(&bundle).map_err(|err| format!("{}: {}", bundle.display(), err))?;
+        let constructor = match zero_arg_constructor(&json) {
+            Some(constructor) => constructor,
+            None => {
+                println!(
+                    "skipping {}: every constructor takes arguments",
+                    bundle.display()
+                );
+                continue;
+            }
+        };
+        println!("deploying {} with `{}`", bundle.display(), constructor);
+        run_command(
+            Command::new("cargo")
+                .args(["contract", "instantiate", "--suri", "//Alice", "--execute"])
+                .args([
+                    "--skip-confirm",
+                    "--constructor",
+                    &constructor,
+                    "--url",
+                    &url,
+                ])
+                .arg(&bundle),
+        )?;
+    }
+    Ok(())
+}
+
+fn run_command(command: &mut Command) -> Result<(), String> {
+    let status = command
+        .status()
+        .map_err(|err| format!("cannot run {:?}: {}", command.get_program(), err))?;
+    if !status.success() {
+        return Err(format!("{:?} failed ({})", command.get_program(), status));
+    }
+    Ok(())
+}
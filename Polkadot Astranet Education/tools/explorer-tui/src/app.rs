@@ -0,0 +1,219 @@
+// tools/explorer-tui/src/app.rs
+//
+// What the explorer shows, kept as updates come in: the latest blocks, the latest
+// contract events, and the latest calls to each contract seen. Each list is bounded,
+// so an explorer left running through a whole class does not grow without limit.
+
+use crate::{BlockActivity, ContractCall, Emitted, Update};
+use std::collections::VecDeque;
+
+/// How many blocks are kept.
+pub const BLOCKS: usize = 100;
+/// How many contract events are kept.
+pub const EVENTS: usize = 200;
+/// How many calls are kept per contract.
+pub const CALLS: usize = 100;
+
+/// The explorer's state.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct App {
+    /// The latest blocks, oldest first.
+    pub blocks: VecDeque<BlockActivity>,
+    /// The latest contract events, oldest first.
+    pub events: VecDeque<Emitted>,
+    /// Each contract called or emitting events, in the order first seen, with its
+    /// latest calls, oldest first.
+    pub contracts: Vec<([u8; 32], VecDeque<ContractCall>)>,
+    /// The index in `contracts` of the contract whose calls are shown.
+    pub selected: usize,
+    pub chain: Option<String>,
+    pub finalized: Option<u32>,
+    /// Why the node is not connected, while it is not.
+    pub disconnected: Option<String>,
+    genesis: Option<[u8; 32]>,
+}
+
+impl App {
+    pub fn update(&mut self, update: Update) {
+        match update {
+            Update::Connected { chain, genesis } => {
+                if self.genesis.is_some_and(|known| known != genesis) {
+                    *self = App::default();
+                }
+                self.genesis = Some(genesis);
+                self.chain = Some(chain);
+                self.disconnected = None;
+            }
+            Update::Disconnected(reason) => self.disconnected = Some(reason),
+            Update::Finalized(number) => self.finalized = Some(number),
+            Update::Block(block) => self.add_block(block),
+        }
+    }
+
+    /// The number of the latest block.
+    pub fn best(&self) -> Option<u32> {
+        self.blocks.back().map(|block| block.number)
+    }
+
+    /// The contract whose calls are shown and its calls, if any contract was seen.
+    pub fn selected(&self) -> Option<&([u8; 32], VecDeque<ContractCall>)> {
+        self.contracts.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.contracts.is_empty() {
+            self.selected = (self.selected + 1) % self.contracts.len();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.contracts.is_empty() {
+            self.selected = (self.selected + self.contracts.len() - 1) % self.contracts.len();
+        }
+    }
+
+    /// Adds `block`. A block at or below the latest one comes from a reorg, and
+    /// replaces what was shown from its height on.
+    fn add_block(&mut self, block: BlockActivity) {
+        let number = block.number;
+        while self.best().is_some_and(|best| best >= number) {
+            self.blocks.pop_back();
+        }
+        while self
+            .events
+            .back()
+            .is_some_and(|event| event.block >= number)
+        {
+            self.events.pop_back();
+        }
+        for (_, calls) in &mut self.contracts {
+            while calls.back().is_some_and(|call| call.block >= number) {
+                calls.pop_back();
+            }
+        }
+
+        for event in &block.emitted {
+            self.contract(event.contract);
+            push(&mut self.events, event.clone(), EVENTS);
+        }
+        for call in &block.calls {
+            let calls = self.contract(call.contract);
+            push(calls, call.clone(), CALLS);
+        }
+        push(&mut self.blocks, block, BLOCKS);
+    }
+
+    /// The calls to `contract`, adding it to the contracts seen if it is new.
+    fn contract(&mut self, contract: [u8; 32]) -> &mut VecDeque<ContractCall> {
+        let index = match self
+            .contracts
+            .iter()
+            .position(|(seen, _)| *seen == contract)
+        {
+            Some(index) => index,
+            None => {
+                self.contracts.push((contract, VecDeque::new()));
+                self.contracts.len() - 1
+            }
+        };
+        &mut self.contracts[index].1
+    }
+}
+
+fn push<T>(list: &mut VecDeque<T>, item: T, limit: usize) {
+    if list.len() == limit {
+        list.pop_front();
+    }
+    list.push_back(item);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(number: u32, hash: u8, contract: u8) -> BlockActivity {
+        BlockActivity {
+            number,
+            hash: [hash; 32],
+            extrinsics: 2,
+            events: 4,
+            emitted: vec![Emitted {
+                block: number,
+                index: 1,
+                contract: [contract; 32],
+                data: vec![0],
+            }],
+            calls: vec![ContractCall {
+                block: number,
+                extrinsic: 1,
+                caller: Some([9; 32]),
+                contract: [contract; 32],
+                value: 0,
+                data: vec![1, 2, 3, 4],
+                success: true,
+            }],
+        }
+    }
+
+    #[test]
+    fn reorged_blocks_are_replaced() {
+        let mut app = App::default();
+        for number in 1..=3 {
+            app.update(Update::Block(block(number, 1, number as u8)));
+        }
+        assert_eq!(app.best(), Some(3));
+        assert_eq!(app.contracts.len(), 3);
+
+        // Block 2 is replaced by another, which drops block 3 with it.
+        app.update(Update::Block(block(2, 2, 1)));
+        let hashes: Vec<_> = app.blocks.iter().map(|block| block.hash[0]).collect();
+        assert_eq!(hashes, [1, 2]);
+        assert_eq!(app.events.len(), 2);
+        let calls: Vec<_> = app.contracts.iter().map(|(_, calls)| calls.len()).collect();
+        assert_eq!(calls, [2, 0, 0]);
+    }
+
+    #[test]
+    fn lists_are_bounded() {
+        let mut app = App::default();
+        for number in 0..(BLOCKS as u32 + 10) {
+            app.update(Update::Block(block(number, 1, 1)));
+        }
+        assert_eq!(app.blocks.len(), BLOCKS);
+        assert_eq!(app.blocks.front().unwrap().number, 10);
+        assert_eq!(app.contracts[0].1.len(), CALLS);
+    }
+
+    #[test]
+    fn selection_wraps_and_a_new_chain_starts_over() {
+        let mut app = App::default();
+        app.update(Update::Connected {
+            chain: "Development".to_owned(),
+            genesis: [1; 32],
+        });
+        app.select_next();
+        assert_eq!(app.selected, 0);
+        for number in 1..=3 {
+            app.update(Update::Block(block(number, 1, number as u8)));
+        }
+        app.select_previous();
+        assert_eq!(app.selected().unwrap().0, [3; 32]);
+        app.select_next();
+        assert_eq!(app.selected().unwrap().0, [1; 32]);
+
+        app.update(Update::Disconnected("gone".to_owned()));
+        app.update(Update::Connected {
+            chain: "Development".to_owned(),
+            genesis: [1; 32],
+        });
+        assert_eq!(app.blocks.len(), 3);
+        assert_eq!(app.disconnected, None);
+
+        app.update(Update::Connected {
+            chain: "Development".to_owned(),
+            genesis: [2; 32],
+        });
+        assert!(app.blocks.is_empty() && app.contracts.is_empty());
+        assert_eq!(app.chain.as_deref(), Some("Development"));
+    }
+}
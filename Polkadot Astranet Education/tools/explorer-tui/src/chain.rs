@@ -0,0 +1,358 @@
+// tools/explorer-tui/src/chain.rs
+//
+// Following the node's best blocks. On connecting, the explorer reads the last
+// `BACKFILL` blocks so the screen does not start empty, then each block the node
+// announces. A block whose parent is not the block shown before it means a reorg:
+// the explorer reads back until the chains meet and sends the new blocks, which
+// replace the old ones on screen.
+//
+// When the connection fails, `follow` waits (see `event_relay::Backoff`) and
+// connects again, so a laptop that slept or a restarted `devnet` is picked up
+// without restarting the explorer.
+
+use crate::{BlockActivity, ContractCall, Emitted, Update};
+use deployer::Weight;
+use event_relay::Backoff;
+use scale::{Compact, Decode};
+use std::collections::BTreeMap;
+use subxt::{
+    backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+    events::Phase,
+    utils::H256,
+    OnlineClient, SubstrateConfig,
+};
+
+type Client = OnlineClient<SubstrateConfig>;
+
+/// How many blocks are read on connecting, and how far back a reorg is followed.
+pub const BACKFILL: u32 = 20;
+
+/// Sends what happens on the node at `url` to `send`, reconnecting whenever the
+/// connection fails. Returns once `send` refuses an update.
+pub async fn follow(url: &str, mut backoff: Backoff, mut send: impl FnMut(Update) -> bool) {
+    let mut follower = Follower::default();
+    loop {
+        let err = match follower.run(url, &mut backoff, &mut send).await {
+            Ok(()) => return,
+            Err(err) => err,
+        };
+        let delay = backoff.next_delay();
+        let reason = format!("{}; reconnecting in {}s", err, delay.as_secs());
+        if !send(Update::Disconnected(reason)) {
+            return;
+        }
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[derive(Default)]
+struct Follower {
+    genesis: Option<H256>,
+    /// The hashes of the blocks sent, by number, the last `BACKFILL` of them.
+    sent: BTreeMap<u32, H256>,
+}
+
+/// The connection to a node.
+struct Node {
+    client: Client,
+    methods: LegacyRpcMethods<SubstrateConfig>,
+}
+
+impl Follower {
+    /// Sends updates until the connection fails, or `send` refuses one.
+    async fn run(
+        &mut self,
+        url: &str,
+        backoff: &mut Backoff,
+        send: &mut impl FnMut(Update) -> bool,
+    ) -> Result<(), String> {
+        let rpc = RpcClient::from_url(url)
+            .await
+            .map_err(|err| format!("cannot connect to {}: {}", url, err))?;
+        let node = Node {
+            client: Client::from_rpc_client(rpc.clone())
+                .await
+                .map_err(|err| format!("{}: {}", url, err))?,
+            methods: LegacyRpcMethods::new(rpc),
+        };
+        let chain = node
+            .methods
+            .system_chain()
+            .await
+            .map_err(|err| format!("system_chain failed: {}", err))?;
+        let genesis = node.client.genesis_hash();
+        if self.genesis != Some(genesis) {
+            self.genesis = Some(genesis);
+            self.sent.clear();
+        }
+        if !send(Update::Connected {
+            chain,
+            genesis: genesis.0,
+        }) {
+            return Ok(());
+        }
+        backoff.reset();
+
+        let mut blocks = node
+            .client
+            .blocks()
+            .subscribe_best()
+            .await
+            .map_err(|err| format!("cannot follow best blocks: {}", err))?;
+        let best = node.number(None).await?;
+        if !self
+            .send_from(&node, best.saturating_sub(BACKFILL - 1), best, send)
+            .await?
+        {
+            return Ok(());
+        }
+        while let Some(block) = blocks.next().await {
+            let block = block.map_err(|err| format!("lost best blocks: {}", err))?;
+            if !self.send_block(&node, block.number(), send).await? {
+                return Ok(());
+            }
+        }
+        Err("the node ended the best block subscription".to_owned())
+    }
+
+    /// Sends block `number`, now the best block, and the blocks before it that were
+    /// missed or replaced by a reorg.
+    async fn send_block(
+        &mut self,
+        node: &Node,
+        number: u32,
+        send: &mut impl FnMut(Update) -> bool,
+    ) -> Result<bool, String> {
+        // The subscription can skip blocks.
+        let mut from = match self.sent.last_key_value() {
+            Some((&last, _)) => (last + 1).min(number),
+            None => number,
+        };
+        while from > 0 && number - from < BACKFILL {
+            let Some(&hash) = self.sent.get(&(from - 1)) else {
+                break;
+            };
+            if node.hash(from - 1).await? == Some(hash) {
+                break;
+            }
+            from -= 1;
+        }
+        self.send_from(
+            node,
+            from.max(number.saturating_sub(BACKFILL - 1)),
+            number,
+            send,
+        )
+        .await
+    }
+
+    /// Sends blocks `from` to `to` of the node's current chain, and the latest
+    /// finalized block. Returns whether `send` took them all.
+    async fn send_from(
+        &mut self,
+        node: &Node,
+        from: u32,
+        to: u32,
+        send: &mut impl FnMut(Update) -> bool,
+    ) -> Result<bool, String> {
+        self.sent.retain(|&number, _| number < from);
+        for number in from..=to {
+            let Some(hash) = node.hash(number).await? else {
+                // A reorg to a shorter chain happened meanwhile; the next block fixes it.
+                break;
+            };
+            let activity = node.activity(number, hash).await?;
+            self.sent.insert(number, hash);
+            if !send(Update::Block(activity)) {
+                return Ok(false);
+            }
+        }
+        while self.sent.len() > BACKFILL as usize {
+            self.sent.pop_first();
+        }
+        let finalized = node
+            .methods
+            .chain_get_finalized_head()
+            .await
+            .map_err(|err| format!("chain_getFinalizedHead failed: {}", err))?;
+        Ok(send(Update::Finalized(node.number(Some(finalized)).await?)))
+    }
+}
+
+impl Node {
+    /// What block `number`, whose hash is `hash`, holds.
+    async fn activity(&self, number: u32, hash: H256) -> Result<BlockActivity, String> {
+        let block = self
+            .client
+            .blocks()
+            .at(hash)
+            .await
+            .map_err(|err| format!("block {}: {}", number, err))?;
+        let events = block
+            .events()
+            .await
+            .map_err(|err| format!("events of block {}: {}", number, err))?;
+        let mut activity = BlockActivity {
+            number,
+            hash: hash.0,
+            extrinsics: 0,
+            events: 0,
+            emitted: Vec::new(),
+            calls: Vec::new(),
+        };
+        let mut failed = Vec::new();
+        for event in events.iter() {
+            let event = event.map_err(|err| format!("events of block {}: {}", number, err))?;
+            activity.events += 1;
+            match (event.pallet_name(), event.variant_name()) {
+                ("System", "ExtrinsicFailed") => {
+                    if let Phase::ApplyExtrinsic(index) = event.phase() {
+                        failed.push(index);
+                    }
+                }
+                ("Contracts", "ContractEmitted") => {
+                    // `ContractEmitted { contract: AccountId32, data: Vec<u8> }`
+                    let (contract, data) = <([u8; 32], Vec<u8>)>::decode(&mut event.field_bytes())
+                        .map_err(|err| format!("ContractEmitted in block {}: {}", number, err))?;
+                    activity.emitted.push(Emitted {
+                        block: number,
+                        index: event.index(),
+                        contract,
+                        data,
+                    });
+                }
+                _ => {}
+            }
+        }
+        let extrinsics = block
+            .extrinsics()
+            .await
+            .map_err(|err| format!("extrinsics of block {}: {}", number, err))?;
+        for extrinsic in extrinsics.iter() {
+            let extrinsic =
+                extrinsic.map_err(|err| format!("extrinsics of block {}: {}", number, err))?;
+            activity.extrinsics += 1;
+            let is_call = extrinsic.pallet_name().ok() == Some("Contracts")
+                && extrinsic.variant_name().ok() == Some("call");
+            if !is_call {
+                continue;
+            }
+            let index = extrinsic.index();
+            let call = decode_call(
+                number,
+                index,
+                extrinsic.address_bytes(),
+                extrinsic.field_bytes(),
+                !failed.contains(&index),
+            )
+            .map_err(|err| format!("Contracts::call in block {}: {}", number, err))?;
+            activity.calls.push(call);
+        }
+        Ok(activity)
+    }
+
+    /// The hash of block `number` on the node's current chain, if it has one.
+    async fn hash(&self, number: u32) -> Result<Option<H256>, String> {
+        self.methods
+            .chain_get_block_hash(Some(number.into()))
+            .await
+            .map_err(|err| format!("chain_getBlockHash failed: {}", err))
+    }
+
+    /// The number of block `hash`, or of the best block if `hash` is `None`.
+    async fn number(&self, hash: Option<H256>) -> Result<u32, String> {
+        let header = self
+            .methods
+            .chain_get_header(hash)
+            .await
+            .map_err(|err| format!("chain_getHeader failed: {}", err))?
+            .ok_or("the node does not know the block")?;
+        Ok(header.number)
+    }
+}
+
+/// Reads a `Contracts::call` from the signer's address and the call's fields, which
+/// are `dest: MultiAddress`, `value: Compact<Balance>`, `gas_limit: Weight`,
+/// `storage_deposit_limit: Option<Compact<Balance>>` and `data: Vec<u8>`.
+pub fn decode_call(
+    block: u32,
+    extrinsic: u32,
+    address: Option<&[u8]>,
+    fields: &[u8],
+    success: bool,
+) -> Result<ContractCall, String> {
+    let input = &mut &fields[..];
+    let contract = account_id(input)?.ok_or("the contract is not given by account id")?;
+    let Compact(value) = Compact::<u128>::decode(input).map_err(|err| err.to_string())?;
+    Weight::decode(input).map_err(|err| err.to_string())?;
+    Option::<Compact<u128>>::decode(input).map_err(|err| err.to_string())?;
+    let data = Vec::<u8>::decode(input).map_err(|err| err.to_string())?;
+    let caller = match address {
+        Some(address) => account_id(&mut &address[..])?,
+        None => None,
+    };
+    Ok(ContractCall {
+        block,
+        extrinsic,
+        caller,
+        contract,
+        value,
+        data,
+        success,
+    })
+}
+
+/// Reads a `MultiAddress`, returning the account if it is given by id.
+fn account_id(input: &mut &[u8]) -> Result<Option<[u8; 32]>, String> {
+    match u8::decode(input).map_err(|err| err.to_string())? {
+        // `MultiAddress::Id`
+        0 => <[u8; 32]>::decode(input)
+            .map(Some)
+            .map_err(|err| err.to_string()),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::Encode;
+
+    #[test]
+    fn contract_calls_decode() {
+        let mut fields = vec![0];
+        fields.extend([7; 32]);
+        fields.extend(Compact(1_000u128).encode());
+        fields.extend(
+            Weight {
+                ref_time: 5_000_000,
+                proof_size: 60_000,
+            }
+            .encode(),
+        );
+        fields.extend(Some(Compact(20u128)).encode());
+        fields.extend(vec![0x84u8, 0xa1, 0x5d, 0xa1].encode());
+        let mut address = vec![0];
+        address.extend([1; 32]);
+
+        let call = decode_call(12, 2, Some(&address), &fields, true).unwrap();
+        assert_eq!(
+            call,
+            ContractCall {
+                block: 12,
+                extrinsic: 2,
+                caller: Some([1; 32]),
+                contract: [7; 32],
+                value: 1_000,
+                data: vec![0x84, 0xa1, 0x5d, 0xa1],
+                success: true,
+            }
+        );
+
+        fields[0] = 1;
+        assert_eq!(
+            decode_call(12, 2, Some(&address), &fields, true).unwrap_err(),
+            "the contract is not given by account id"
+        );
+    }
+}
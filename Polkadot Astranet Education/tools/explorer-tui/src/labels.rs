@@ -0,0 +1,217 @@
+// tools/explorer-tui/src/labels.rs
+//
+// Names for what the explorer shows, so learners read `alice called erc20.transfer`
+// rather than hex. Contracts are named by the `--contracts` file, in the event
+// relay's format (see `event_relay::config`), which also says how to decode their
+// events. Messages are named by their selector, looked up in the bundles of
+// `--dist`. Development accounts go by their names. Anything else is shown as a
+// shortened address or as hex.
+
+use deployer::Bundle;
+use event_relay::{config, Contract, Decoded, Decoder};
+use repl::value::{parse_account, to_hex};
+use serde_json::Value;
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// The development accounts, as `repl` names them.
+const DEV_ACCOUNTS: [&str; 6] = ["alice", "bob", "charlie", "dave", "eve", "ferdie"];
+
+pub struct Labels {
+    decoder: Decoder,
+    contracts: Vec<Contract>,
+    /// Message labels by selector.
+    messages: BTreeMap<[u8; 4], String>,
+    accounts: BTreeMap<[u8; 32], &'static str>,
+}
+
+impl Labels {
+    /// Labels writing addresses with `ss58_prefix`, for the contracts of the
+    /// `contracts` configuration (which may be empty) and the messages of `bundles`.
+    pub fn new(ss58_prefix: u16, contracts: &str, bundles: &[Bundle]) -> Result<Self, String> {
+        let decoder = Decoder::new(ss58_prefix);
+        let contracts = if contracts.trim().is_empty() {
+            Vec::new()
+        } else {
+            config::parse(contracts, &decoder)?
+        };
+        let mut messages = BTreeMap::new();
+        for bundle in bundles {
+            for message in &bundle.metadata.messages {
+                // Contracts sharing a message (`transfer`, say) give it the same label.
+                messages
+                    .entry(message.selector)
+                    .or_insert_with(|| message.label.clone());
+            }
+        }
+        let accounts = DEV_ACCOUNTS
+            .into_iter()
+            .map(|name| (parse_account(name).expect("dev accounts parse"), name))
+            .collect();
+        Ok(Labels {
+            decoder,
+            contracts,
+            messages,
+            accounts,
+        })
+    }
+
+    /// Reads the labels from the `--contracts` file, if any, and every `.contract`
+    /// bundle directly inside `dist`, if it exists.
+    pub fn load(ss58_prefix: u16, contracts: Option<&Path>, dist: &Path) -> Result<Self, String> {
+        let read = |path: &Path| {
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))
+        };
+        let toml = match contracts {
+            Some(path) => read(path)?,
+            None => String::new(),
+        };
+        let mut bundles = Vec::new();
+        if dist.is_dir() {
+            let mut paths: Vec<_> = fs::read_dir(dist)
+                .map_err(|err| format!("{}: {}", dist.display(), err))?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "contract"))
+                .collect();
+            paths.sort();
+            for path in paths {
+                let bundle = Bundle::parse(&read(&path)?)
+                    .map_err(|err| format!("{}: {}", path.display(), err))?;
+                bundles.push(bundle);
+            }
+        }
+        Self::new(ss58_prefix, &toml, &bundles).map_err(|err| match contracts {
+            Some(path) => format!("{}: {}", path.display(), err),
+            None => err,
+        })
+    }
+
+    /// The configured name of the contract at `address`, or its shortened address.
+    pub fn contract(&self, address: [u8; 32]) -> String {
+        match self.configured(address) {
+            Some(contract) => contract.name.clone(),
+            None => short(&self.decoder.address(address)),
+        }
+    }
+
+    /// The name of a development account, or the shortened address of `account`.
+    pub fn account(&self, account: [u8; 32]) -> String {
+        match self.accounts.get(&account) {
+            Some(name) => (*name).to_owned(),
+            None => self.contract(account),
+        }
+    }
+
+    /// The label of the message `data` calls, or its selector in hex.
+    pub fn message(&self, data: &[u8]) -> String {
+        let Some(selector) = data.get(..4) else {
+            return format!("{} ({} bytes)", to_hex(data), data.len());
+        };
+        let selector: [u8; 4] = selector.try_into().expect("4 bytes");
+        match self.messages.get(&selector) {
+            Some(label) => label.clone(),
+            None => to_hex(&selector),
+        }
+    }
+
+    /// `data`, emitted by the contract at `address`, decoded as `Event field=value
+    /// ...`, or its size if the contract's events are not known.
+    pub fn event(&self, address: [u8; 32], data: &[u8]) -> String {
+        let Some(contract) = self.configured(address) else {
+            return format!("{} bytes", data.len());
+        };
+        match self.decoder.decode(&contract.events, data) {
+            Ok(Decoded { event, fields }) => {
+                let fields = match fields {
+                    Value::Object(fields) => fields
+                        .into_iter()
+                        .map(|(name, value)| format!("{}={}", name, self.value(value)))
+                        .collect(),
+                    Value::Null => Vec::new(),
+                    value => vec![self.value(value)],
+                };
+                let mut text = event;
+                for field in fields {
+                    text.push(' ');
+                    text.push_str(&field);
+                }
+                text
+            }
+            Err(err) => format!("undecodable: {}", err),
+        }
+    }
+
+    /// A field of a decoded event, with accounts named.
+    fn value(&self, value: Value) -> String {
+        match value {
+            Value::String(text) => match parse_account(&text) {
+                Ok(account) if !text.starts_with("0x") => self.account(account),
+                _ => text,
+            },
+            Value::Null => "none".to_owned(),
+            value => value.to_string(),
+        }
+    }
+
+    fn configured(&self, address: [u8; 32]) -> Option<&Contract> {
+        self.contracts
+            .iter()
+            .find(|contract| contract.address == address)
+    }
+}
+
+/// The start and end of an address, enough to tell accounts apart on screen.
+fn short(address: &str) -> String {
+    let chars: Vec<char> = address.chars().collect();
+    if chars.len() <= 12 {
+        return address.to_owned();
+    }
+    let start: String = chars[..6].iter().collect();
+    let end: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}…{}", start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use astranet_events::erc20::{Event, Transfer};
+    use deployer::network::GENERIC_PREFIX;
+    use scale::Encode;
+
+    const CONTRACTS: &str = r#"
+        [[contract]]
+        name = "course-token"
+        events = "erc20"
+        address = "0x0101010101010101010101010101010101010101010101010101010101010101"
+    "#;
+
+    #[test]
+    fn contracts_accounts_and_events_are_named() {
+        let labels = Labels::new(GENERIC_PREFIX, CONTRACTS, &[]).unwrap();
+        let alice = parse_account("alice").unwrap();
+        assert_eq!(labels.contract([1; 32]), "course-token");
+        assert_eq!(labels.account(alice), "alice");
+        assert_eq!(labels.account([1; 32]), "course-token");
+        assert_eq!(labels.account([2; 32]), "5C7LYp…kqWt");
+
+        let transfer = Event::Transfer(Transfer {
+            from: None,
+            to: Some(alice.into()),
+            value: 1_000,
+        });
+        assert_eq!(
+            labels.event([1; 32], &transfer.encode()),
+            "Transfer from=none to=alice value=1000"
+        );
+        assert_eq!(labels.event([2; 32], &[0, 1, 2]), "3 bytes");
+        assert!(labels
+            .event([1; 32], &[9])
+            .starts_with("undecodable: `erc20` has no event 9"));
+    }
+
+    #[test]
+    fn messages_are_named_by_selector() {
+        let labels = Labels::new(GENERIC_PREFIX, "", &[]).unwrap();
+        assert_eq!(labels.message(&[0x84, 0xa1, 0x5d, 0xa1, 0]), "0x84a15da1");
+        assert_eq!(labels.message(&[1, 2]), "0x0102 (2 bytes)");
+    }
+}
@@ -0,0 +1,73 @@
+// tools/explorer-tui/src/lib.rs
+//
+// A block explorer for the terminal, so a classroom without internet access can still
+// watch the local devnet: the latest blocks, the events the contracts emit and, for
+// each contract, the calls made to it, updated as blocks are produced.
+//
+// The explorer follows the node's best blocks rather than its finalized ones, since
+// seeing a call land right away is the point; blocks the node later finalizes are
+// marked, and when a reorg replaces a block, what was shown for it is replaced too.
+//
+// `chain` reads the blocks, `app` keeps what is on screen, `labels` names contracts,
+// accounts, messages and events, and `ui` lays the screen out as lines of text, which
+// the binary draws with crossterm.
+
+pub mod app;
+pub mod chain;
+pub mod labels;
+pub mod ui;
+
+pub use app::App;
+pub use labels::Labels;
+
+/// A `Contracts::ContractEmitted` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Emitted {
+    pub block: u32,
+    /// The index of the event among the block's events.
+    pub index: u32,
+    pub contract: [u8; 32],
+    pub data: Vec<u8>,
+}
+
+/// A `Contracts::call` extrinsic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractCall {
+    pub block: u32,
+    /// The index of the extrinsic in the block.
+    pub extrinsic: u32,
+    /// The signer, if they signed with an account id.
+    pub caller: Option<[u8; 32]>,
+    pub contract: [u8; 32],
+    pub value: u128,
+    pub data: Vec<u8>,
+    /// Whether the extrinsic succeeded; a reverted message fails it.
+    pub success: bool,
+}
+
+/// What a block holds, as far as the explorer shows it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockActivity {
+    pub number: u32,
+    pub hash: [u8; 32],
+    pub extrinsics: usize,
+    pub events: usize,
+    pub emitted: Vec<Emitted>,
+    pub calls: Vec<ContractCall>,
+}
+
+/// News from the chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Update {
+    /// Connected to the node of chain `chain`. A new `genesis` means the node was
+    /// restarted with a fresh chain, and what was shown before is gone.
+    Connected {
+        chain: String,
+        genesis: [u8; 32],
+    },
+    /// Lost the node, for this reason; the explorer reconnects.
+    Disconnected(String),
+    Block(BlockActivity),
+    /// The latest finalized block.
+    Finalized(u32),
+}
@@ -0,0 +1,164 @@
+// tools/explorer-tui/src/main.rs
+//
+// Usage:
+//   explorer-tui
+//   explorer-tui --contracts relay.toml --dist dist
+//   explorer-tui --network local --url ws://192.168.1.20:9944
+//
+// Shows the local devnet's latest blocks, the events contracts emit and the calls made
+// to each contract, until `q` is pressed. `--contracts` names contracts and decodes
+// their events, in the event relay's format; message names come from the bundles in
+// `--dist`. `--network` picks the node and the address format from the deployer's
+// network profiles; `--url` overrides the node, e.g. the teacher's machine.
+//
+// The screen is drawn with crossterm directly; `ui` lays it out as plain lines.
+
+use clap::Parser;
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use deployer::Networks;
+use event_relay::Backoff;
+use explorer_tui::{chain, ui, App, Labels, Update};
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    process::ExitCode,
+    thread,
+};
+use tokio::sync::mpsc;
+
+#[derive(Parser)]
+#[command(about = "Watch blocks, contract events and contract calls on the local devnet")]
+struct Cli {
+    /// Contracts to name and decode the events of, in the event relay's format.
+    #[arg(long)]
+    contracts: Option<PathBuf>,
+    /// Directory of `.contract` bundles to name messages from.
+    #[arg(long, default_value = "dist")]
+    dist: PathBuf,
+    /// Network profile name, or the WebSocket URL of a node.
+    #[arg(long, default_value = "local")]
+    network: String,
+    /// File of network profiles to add to the built-in ones.
+    #[arg(long)]
+    networks: Option<PathBuf>,
+    /// WebSocket URL of the node, instead of the profile's.
+    #[arg(long)]
+    url: Option<String>,
+}
+
+/// Something the screen changes for.
+enum Input {
+    Chain(Update),
+    Terminal(Event),
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
+    let mut networks = Networks::builtin();
+    if let Some(path) = &cli.networks {
+        let toml =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        networks
+            .extend(Networks::parse(&toml).map_err(|err| format!("{}: {}", path.display(), err))?);
+    }
+    let network = networks.get(&cli.network)?;
+    let url = cli.url.unwrap_or(network.url);
+    let labels = Labels::load(network.ss58_prefix, cli.contracts.as_deref(), &cli.dist)?;
+
+    let (inputs, mut received) = mpsc::unbounded_channel();
+    let chain_inputs = inputs.clone();
+    tokio::spawn(async move {
+        chain::follow(&url, Backoff::default(), |update| {
+            chain_inputs.send(Input::Chain(update)).is_ok()
+        })
+        .await
+    });
+    // Reading the terminal blocks, so it gets a thread of its own, which ends with
+    // the process.
+    thread::spawn(move || {
+        while let Ok(event) = event::read() {
+            if inputs.send(Input::Terminal(event)).is_err() {
+                break;
+            }
+        }
+    });
+
+    let _screen = Screen::enter().map_err(|err| format!("cannot set up the terminal: {}", err))?;
+    let mut app = App::default();
+    draw(&app, &labels).map_err(|err| err.to_string())?;
+    while let Some(input) = received.recv().await {
+        match input {
+            Input::Chain(update) => app.update(update),
+            Input::Terminal(Event::Key(key)) if key.kind != KeyEventKind::Release => {
+                if quits(key) {
+                    return Ok(());
+                }
+                match key.code {
+                    KeyCode::Right | KeyCode::Tab => app.select_next(),
+                    KeyCode::Left | KeyCode::BackTab => app.select_previous(),
+                    _ => continue,
+                }
+            }
+            Input::Terminal(Event::Resize(..)) => {}
+            Input::Terminal(_) => continue,
+        }
+        draw(&app, &labels).map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+fn quits(key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => true,
+        KeyCode::Char('c') => key.modifiers.contains(KeyModifiers::CONTROL),
+        _ => false,
+    }
+}
+
+fn draw(app: &App, labels: &Labels) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let mut out = io::stdout();
+    for (row, line) in ui::render(app, labels, width.into(), height.into())
+        .iter()
+        .enumerate()
+    {
+        queue!(out, MoveTo(0, row as u16), Print(line))?;
+    }
+    out.flush()
+}
+
+/// The terminal in raw mode on the alternate screen, restored when dropped, so an
+/// error does not leave the shell unusable.
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
@@ -0,0 +1,285 @@
+// tools/explorer-tui/src/ui.rs
+//
+// Laying out the screen. `render` turns the state into lines of text exactly as wide
+// as the terminal, so the layout can be tested without one:
+//
+//     explorer-tui  Development  best #1042  finalized #1040
+//     ┌ Blocks ──────────────────────┐┌ Contract events ──────────────────────┐
+//     │#1042   0x3f2a…91c0 2 ext 6 ev││#1042 course-token Transfer from=alice…│
+//     │#1041 ✓ 0x77d1…0e5b 1 ext 2 ev││#1039 course-token Approval owner=bob …│
+//     └──────────────────────────────┘└───────────────────────────────────────┘
+//     ┌ Calls to course-token (1/2) ──────────────────────────────────────────┐
+//     │#1042-1 alice transfer  ok                                             │
+//     └───────────────────────────────────────────────────────────────────────┘
+//      q quit  ←/→ contract
+//
+// Newest entries come first. A ✓ marks finalized blocks; the others may still be
+// replaced by a reorg. Text that does not fit is cut with `…`.
+
+use crate::{App, Labels};
+use repl::value::to_hex;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// The smallest screen the layout fits in.
+pub const MIN_WIDTH: usize = 40;
+pub const MIN_HEIGHT: usize = 10;
+
+/// The screen for `app`, as `height` lines of `width` columns.
+pub fn render(app: &App, labels: &Labels, width: usize, height: usize) -> Vec<String> {
+    if width < MIN_WIDTH || height < MIN_HEIGHT {
+        let mut lines = vec![fit(
+            &format!(
+                "The terminal is too small; make it at least {}x{}.",
+                MIN_WIDTH, MIN_HEIGHT
+            ),
+            width,
+        )];
+        lines.resize(height, " ".repeat(width));
+        return lines;
+    }
+    let mut lines = vec![fit(&title(app), width)];
+
+    // The title and help lines aside, the events take the top half, the calls the rest.
+    let top = (height - 2) / 2;
+    let bottom = height - 2 - top;
+    let blocks_width = width * 2 / 5;
+    let blocks = boxed("Blocks", &block_lines(app), blocks_width, top);
+    let events = boxed(
+        "Contract events",
+        &event_lines(app, labels),
+        width - blocks_width,
+        top,
+    );
+    lines.extend(
+        blocks
+            .into_iter()
+            .zip(events)
+            .map(|(left, right)| left + &right),
+    );
+
+    let (calls_title, calls) = call_lines(app, labels);
+    lines.extend(boxed(&calls_title, &calls, width, bottom));
+
+    let help = match &app.disconnected {
+        Some(reason) => format!(" {}", reason),
+        None => " q quit  ←/→ contract".to_owned(),
+    };
+    lines.push(fit(&help, width));
+    lines
+}
+
+fn title(app: &App) -> String {
+    let mut title = "explorer-tui".to_owned();
+    match &app.chain {
+        Some(chain) => title += &format!("  {}", chain),
+        None => title += "  connecting…",
+    }
+    if let Some(best) = app.best() {
+        title += &format!("  best #{}", best);
+    }
+    if let Some(finalized) = app.finalized {
+        title += &format!("  finalized #{}", finalized);
+    }
+    title
+}
+
+fn block_lines(app: &App) -> Vec<String> {
+    app.blocks
+        .iter()
+        .rev()
+        .map(|block| {
+            let finalized = app
+                .finalized
+                .is_some_and(|finalized| block.number <= finalized);
+            let hash = to_hex(&block.hash);
+            format!(
+                "#{} {} {}…{} {} ext {} ev",
+                block.number,
+                if finalized { '✓' } else { ' ' },
+                &hash[..6],
+                &hash[hash.len() - 4..],
+                block.extrinsics,
+                block.events,
+            )
+        })
+        .collect()
+}
+
+fn event_lines(app: &App, labels: &Labels) -> Vec<String> {
+    app.events
+        .iter()
+        .rev()
+        .map(|event| {
+            format!(
+                "#{} {} {}",
+                event.block,
+                labels.contract(event.contract),
+                labels.event(event.contract, &event.data)
+            )
+        })
+        .collect()
+}
+
+/// The title of the calls box and its lines.
+fn call_lines(app: &App, labels: &Labels) -> (String, Vec<String>) {
+    let Some((contract, calls)) = app.selected() else {
+        return (
+            "Calls".to_owned(),
+            vec!["No contract calls yet.".to_owned()],
+        );
+    };
+    let title = format!(
+        "Calls to {} ({}/{})",
+        labels.contract(*contract),
+        app.selected + 1,
+        app.contracts.len()
+    );
+    if calls.is_empty() {
+        return (title, vec!["No calls yet, only events.".to_owned()]);
+    }
+    let lines = calls
+        .iter()
+        .rev()
+        .map(|call| {
+            let caller = match call.caller {
+                Some(caller) => labels.account(caller),
+                None => "?".to_owned(),
+            };
+            let mut line = format!(
+                "#{}-{} {} {}",
+                call.block,
+                call.extrinsic,
+                caller,
+                labels.message(&call.data)
+            );
+            if call.value > 0 {
+                line += &format!(" value={}", call.value);
+            }
+            line += if call.success { "  ok" } else { "  failed" };
+            line
+        })
+        .collect();
+    (title, lines)
+}
+
+/// `lines` in a box of `width` by `height` with `title` in its top border, as many
+/// of them as fit.
+fn boxed(title: &str, lines: &[String], width: usize, height: usize) -> Vec<String> {
+    let inner = width - 2;
+    let mut title = format!(" {} ", title);
+    if title.width() > inner {
+        title = fit(&title, inner);
+    }
+    let mut boxed = vec![format!("┌{}{}┐", title, "─".repeat(inner - title.width()))];
+    for row in 0..height - 2 {
+        let line = lines.get(row).map(String::as_str).unwrap_or("");
+        boxed.push(format!("│{}│", fit(line, inner)));
+    }
+    boxed.push(format!("└{}┘", "─".repeat(inner)));
+    boxed
+}
+
+/// `text` cut or padded to exactly `width` columns, ending with `…` if cut.
+fn fit(text: &str, width: usize) -> String {
+    let mut fitted = String::new();
+    let mut used = 0;
+    if text.width() <= width {
+        fitted.push_str(text);
+        used = text.width();
+    } else if width > 0 {
+        for c in text.chars() {
+            let c_width = c.width().unwrap_or(0);
+            if used + c_width > width - 1 {
+                break;
+            }
+            fitted.push(c);
+            used += c_width;
+        }
+        fitted.push('…');
+        used += 1;
+    }
+    fitted.push_str(&" ".repeat(width - used));
+    fitted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockActivity, ContractCall, Emitted, Update};
+    use deployer::network::GENERIC_PREFIX;
+
+    const CONTRACTS: &str = r#"
+        [[contract]]
+        name = "course-token"
+        events = "erc20"
+        address = "0x0101010101010101010101010101010101010101010101010101010101010101"
+    "#;
+
+    fn app() -> App {
+        let mut app = App::default();
+        app.update(Update::Connected {
+            chain: "Development".to_owned(),
+            genesis: [0; 32],
+        });
+        for number in 1..=2 {
+            app.update(Update::Block(BlockActivity {
+                number,
+                hash: [number as u8; 32],
+                extrinsics: 2,
+                events: 5,
+                emitted: vec![Emitted {
+                    block: number,
+                    index: 2,
+                    contract: [1; 32],
+                    data: vec![9],
+                }],
+                calls: vec![ContractCall {
+                    block: number,
+                    extrinsic: 1,
+                    caller: Some(repl::value::parse_account("bob").unwrap()),
+                    contract: [1; 32],
+                    value: 0,
+                    data: vec![0x84, 0xa1, 0x5d, 0xa1],
+                    success: number == 1,
+                }],
+            }));
+        }
+        app.update(Update::Finalized(1));
+        app
+    }
+
+    #[test]
+    fn the_screen_fills_the_terminal() {
+        let labels = Labels::new(GENERIC_PREFIX, CONTRACTS, &[]).unwrap();
+        let lines = render(&app(), &labels, 80, 14);
+        assert_eq!(lines.len(), 14);
+        assert!(lines.iter().all(|line| line.width() == 80));
+
+        assert!(lines[0].starts_with("explorer-tui  Development  best #2  finalized #1 "));
+        assert!(lines[1].starts_with("┌ Blocks ─"));
+        assert!(lines[2].starts_with("│#2   0x0202…0202 2 ext 5 ev "));
+        assert!(lines[3].starts_with("│#1 ✓ 0x0101…0101 2 ext 5 ev "));
+        assert!(lines[2].contains("│#2 course-token undecodable: "));
+        assert!(lines[7].starts_with("┌ Calls to course-token (1/1) ─"));
+        assert!(lines[8].starts_with("│#2-1 bob 0x84a15da1  failed "));
+        assert!(lines[9].starts_with("│#1-1 bob 0x84a15da1  ok "));
+        assert_eq!(lines[13].trim_end(), " q quit  ←/→ contract");
+    }
+
+    #[test]
+    fn text_that_does_not_fit_is_cut() {
+        assert_eq!(fit("finalized", 5), "fina…");
+        assert_eq!(fit("best", 6), "best  ");
+        assert_eq!(fit("区块链", 4), "区… ");
+
+        let labels = Labels::new(GENERIC_PREFIX, "", &[]).unwrap();
+        let mut app = app();
+        app.update(Update::Disconnected("cannot connect".to_owned()));
+        let lines = render(&app, &labels, 40, 10);
+        assert!(lines.iter().all(|line| line.width() == 40));
+        assert_eq!(lines[9].trim_end(), " cannot connect");
+        let small = render(&app, &labels, 30, 4);
+        assert_eq!(small.len(), 4);
+        assert!(small[0].ends_with('…'));
+    }
+}
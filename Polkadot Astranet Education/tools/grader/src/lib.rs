@@ -0,0 +1,64 @@
+// tools/grader/src/lib.rs
+//
+// The grader runs an exercise's hidden tests, `tests/grading.rs`, against the
+// learner's `src/lib.rs`, and keeps their progress (see `progress`). The exercise
+// crates are laid out by `new-exercise`, whose library reads their metadata, and
+// ordered into tracks by `tracks.toml` (see `tracks`). Each exercise's reference
+// solution is checked against its tests by `verify-solutions` (see `solutions`).
+//
+// Every exercise builds into one target directory, `target/` in the exercises
+// directory, so ink! and its dependencies are compiled once rather than per exercise.
+
+pub mod progress;
+pub mod solutions;
+pub mod tracks;
+pub mod watch;
+
+pub use new_exercise::{Difficulty, Exercise, Hints};
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// The names of the exercises in `exercises_dir`, sorted.
+pub fn exercises(exercises_dir: &Path) -> Result<Vec<String>, String> {
+    let entries = fs::read_dir(exercises_dir)
+        .map_err(|err| format!("{}: {}", exercises_dir.display(), err))?;
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join(new_exercise::METADATA_FILE).is_file())
+        .filter_map(|path| path.file_name()?.to_str().map(str::to_owned))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// The crate of exercise `name` in `exercises_dir`, checked to be one.
+pub fn exercise_dir(exercises_dir: &Path, name: &str) -> Result<PathBuf, String> {
+    let dir = exercises_dir.join(name);
+    if !dir.join(new_exercise::METADATA_FILE).is_file() || !dir.join("Cargo.toml").is_file() {
+        return Err(format!(
+            "no exercise `{}` in {}",
+            name,
+            exercises_dir.display()
+        ));
+    }
+    Ok(dir)
+}
+
+/// `cargo test` running the grading tests of the exercise crate in `dir`, built in the
+/// shared target directory of `exercises_dir` unless `CARGO_TARGET_DIR` says otherwise.
+pub fn test_command(exercises_dir: &Path, dir: &Path) -> Command {
+    let mut command = Command::new("cargo");
+    command
+        .arg("test")
+        .arg("--manifest-path")
+        .arg(dir.join("Cargo.toml"))
+        .args(["--test", "grading"]);
+    if env::var_os("CARGO_TARGET_DIR").is_none() {
+        command.env("CARGO_TARGET_DIR", exercises_dir.join("target"));
+    }
+    command
+}
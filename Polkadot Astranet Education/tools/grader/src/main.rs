@@ -0,0 +1,270 @@
+// tools/grader/src/main.rs
+//
+// Usage:
+//   grader run storage_mapping              run the exercise's grading tests
+//   grader run storage_mapping --hint 1     show its first hint instead
+//   grader watch storage_mapping            re-run them whenever the exercise changes
+//   grader status                           show the progress through every exercise
+//   grader verify-solutions [--no-e2e]      check the reference solutions pass
+//
+// Run from the repository root, or point `--dir` at the exercises. Hints unlock one
+// at a time, each after a failed run. Progress is kept in `progress.json` in the
+// exercises directory. Exits with 1 if the tests fail.
+
+use clap::{Parser, Subcommand};
+use grader::{
+    exercise_dir, exercises,
+    progress::{now, Progress, PROGRESS_FILE},
+    solutions, test_command,
+    tracks::Tracks,
+    watch::{feedback, snapshot, Style, TestRun},
+    Exercise, Hints,
+};
+use std::{path::PathBuf, process::ExitCode, thread, time::Duration};
+
+/// How often `watch` looks for changes.
+const POLL: Duration = Duration::from_millis(500);
+
+#[derive(Parser)]
+#[command(about = "Grade the exercises and reveal their hints")]
+struct Cli {
+    /// Directory of the exercise crates.
+    #[arg(long, default_value = "exercises", global = true)]
+    dir: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run an exercise's grading tests.
+    Run {
+        /// Name of the exercise.
+        exercise: String,
+        /// Show hint N of the exercise instead of running it.
+        #[arg(long, value_name = "N")]
+        hint: Option<usize>,
+    },
+    /// Re-run an exercise's grading tests whenever one of its files changes.
+    Watch {
+        /// Name of the exercise.
+        exercise: String,
+    },
+    /// Show the progress through every exercise.
+    Status,
+    /// Check that the reference solutions pass their exercises' grading tests.
+    VerifySolutions {
+        /// Exercises to check; all of them if none are given.
+        exercises: Vec<String>,
+        /// Skip exercises graded by end-to-end tests, which need a node.
+        #[arg(long)]
+        no_e2e: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match &cli.command {
+        Command::Run {
+            exercise,
+            hint: Some(number),
+        } => hint(&cli, exercise, *number),
+        Command::Run {
+            exercise,
+            hint: None,
+        } => run(&cli, exercise),
+        Command::Watch { exercise } => watch(&cli, exercise),
+        Command::Status => status(&cli),
+        Command::VerifySolutions { exercises, no_e2e } => {
+            verify_solutions(&cli, exercises, *no_e2e)
+        }
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Returns whether the grading tests passed.
+fn run(cli: &Cli, name: &str) -> Result<bool, String> {
+    let dir = exercise_dir(&cli.dir, name)?;
+    let exercise = Exercise::load(&dir)?;
+    let hints = Hints::load(&dir)?;
+    println!("{} ({})", exercise.title, exercise.difficulty);
+
+    let status = test_command(&cli.dir, &dir)
+        .status()
+        .map_err(|err| format!("cannot run cargo: {}", err))?;
+    let passed = status.success();
+
+    if passed {
+        println!("passed: {}", name);
+    } else {
+        println!("not yet: {}", name);
+    }
+    record(cli, name, passed, &hints)?;
+    if passed {
+        suggest_next(cli, name)?;
+    }
+    Ok(passed)
+}
+
+/// Re-runs the grading tests after every change, until interrupted.
+fn watch(cli: &Cli, name: &str) -> Result<bool, String> {
+    let dir = exercise_dir(&cli.dir, name)?;
+    let style = Style::detect();
+    let mut previous: Option<TestRun> = None;
+    loop {
+        let exercise = Exercise::load(&dir)?;
+        let hints = Hints::load(&dir)?;
+        if style.color {
+            // Clear the screen, so only the latest run shows.
+            print!("\x1b[2J\x1b[H");
+        }
+        println!(
+            "{}",
+            style.bold(&format!("{} ({})", exercise.title, exercise.difficulty))
+        );
+
+        let seen = snapshot(&dir);
+        let output = test_command(&cli.dir, &dir)
+            .args(["--color", if style.color { "always" } else { "never" }])
+            .args(["--", "--color", "never"])
+            .output()
+            .map_err(|err| format!("cannot run cargo: {}", err))?;
+        let run = TestRun::parse(&String::from_utf8_lossy(&output.stdout));
+        if !run.compiled {
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        } else if !run.failures.is_empty() {
+            println!("{}\n", run.failures);
+        }
+        for line in feedback(previous.as_ref(), &run, style) {
+            println!("{}", line);
+        }
+        record(cli, name, run.passed(), &hints)?;
+        if run.passed() {
+            println!(
+                "{}",
+                style.green(&format!("passed: {}; Ctrl-C to stop watching", name))
+            );
+            suggest_next(cli, name)?;
+        } else {
+            println!("watching {} for changes; Ctrl-C to stop", dir.display());
+        }
+        previous = Some(run);
+
+        while snapshot(&dir) == seen {
+            thread::sleep(POLL);
+        }
+        // Let an editor finish writing before building.
+        thread::sleep(POLL);
+    }
+}
+
+/// Records a run in the progress store, pointing at the next hint after a failure.
+fn record(cli: &Cli, name: &str, passed: bool, hints: &Hints) -> Result<(), String> {
+    let path = cli.dir.join(PROGRESS_FILE);
+    let mut progress = Progress::load(&path)?;
+    progress.record_run(name, passed, now());
+    progress.save(&path)?;
+    let revealed = progress.exercises[name].hints_revealed;
+    if !passed && revealed < hints.hints.len() {
+        println!(
+            "stuck? `grader run {} --hint {}` shows a hint",
+            name,
+            revealed + 1
+        );
+    }
+    Ok(())
+}
+
+fn hint(cli: &Cli, name: &str, number: usize) -> Result<bool, String> {
+    let dir = exercise_dir(&cli.dir, name)?;
+    let hints = Hints::load(&dir)?;
+    let path = cli.dir.join(PROGRESS_FILE);
+    let mut progress = Progress::load(&path)?;
+    progress.reveal_hint(name, number, hints.hints.len())?;
+    progress.save(&path)?;
+    println!(
+        "hint {} of {}: {}",
+        number,
+        hints.hints.len(),
+        hints.hints[number - 1]
+    );
+    Ok(true)
+}
+
+/// Points at the exercise after `name` in its track, if there is one.
+fn suggest_next(cli: &Cli, name: &str) -> Result<(), String> {
+    if let Some(next) = Tracks::load(&cli.dir)?.next(name) {
+        println!("next: `grader watch {}`", next);
+    }
+    Ok(())
+}
+
+fn status(cli: &Cli) -> Result<bool, String> {
+    let progress = Progress::load(&cli.dir.join(PROGRESS_FILE))?;
+    let exercises = exercises(&cli.dir)?;
+    let tracks = Tracks::load(&cli.dir)?;
+    tracks.check(&exercises)?;
+    let now = now();
+    for (index, (title, exercises)) in tracks.grouped(&exercises).iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        println!("{}", title);
+        for line in progress.table(exercises, now) {
+            println!("  {}", line);
+        }
+    }
+    Ok(true)
+}
+
+/// Returns whether every reference solution checked passed.
+fn verify_solutions(cli: &Cli, names: &[String], no_e2e: bool) -> Result<bool, String> {
+    let names = match names {
+        [] => exercises(&cli.dir)?,
+        names => names.to_vec(),
+    };
+    let (mut passed, mut failed, mut skipped) = (0, Vec::new(), 0);
+    for name in &names {
+        let dir = exercise_dir(&cli.dir, name)?;
+        if no_e2e && solutions::is_end_to_end(&dir)? {
+            println!("skipped {} (end-to-end)", name);
+            skipped += 1;
+            continue;
+        }
+        let copy = solutions::prepare(&cli.dir, name)?;
+        let output = solutions::test_command(&cli.dir, &copy)
+            .args(["--", "--color", "never"])
+            .output()
+            .map_err(|err| format!("cannot run cargo: {}", err))?;
+        let run = TestRun::parse(&String::from_utf8_lossy(&output.stdout));
+        if run.passed() {
+            println!("ok {} ({} grading test(s))", name, run.results.len());
+            passed += 1;
+            continue;
+        }
+        println!("FAILED {}", name);
+        if !run.compiled {
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        } else {
+            println!("{}\n", run.failures);
+        }
+        failed.push(name.as_str());
+    }
+
+    let mut summary = format!("{} of {} solution(s) pass", passed, names.len() - skipped);
+    if skipped > 0 {
+        summary += &format!(", {} skipped", skipped);
+    }
+    println!("{}", summary);
+    if !failed.is_empty() {
+        println!("failing: {}", failed.join(", "));
+    }
+    Ok(failed.is_empty())
+}
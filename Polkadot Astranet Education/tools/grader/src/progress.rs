@@ -0,0 +1,314 @@
+// tools/grader/src/progress.rs
+//
+// A learner's progress, kept in `progress.json` next to the exercises. It is local to
+// one checkout and never committed. It is plain JSON with times in Unix seconds, so
+// the web platform can read and sync it as it is:
+//
+//     {
+//       "exercises": {
+//         "storage_mapping": {
+//           "status": "passing",
+//           "attempts": 3,
+//           "first_attempt_at": 1760000000,
+//           "last_attempt_at": 1760000420,
+//           "passed_at": 1760000420,
+//           "hints_revealed": 1,
+//           "failures_since_hint": 0
+//         }
+//       }
+//     }
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// File name of the progress store, in the exercises directory.
+pub const PROGRESS_FILE: &str = "progress.json";
+
+/// Progress through every exercise tried so far, by exercise name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Progress {
+    #[serde(default)]
+    pub exercises: BTreeMap<String, ExerciseProgress>,
+}
+
+/// How the last run of an exercise went.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    #[default]
+    Failing,
+    Passing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Status::Failing => "failing",
+            Status::Passing => "passing",
+        })
+    }
+}
+
+/// Progress through one exercise.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExerciseProgress {
+    #[serde(default)]
+    pub status: Status,
+    /// Runs of the grading tests, passed or not.
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(default)]
+    pub first_attempt_at: u64,
+    #[serde(default)]
+    pub last_attempt_at: u64,
+    /// When the grading tests first passed.
+    #[serde(default)]
+    pub passed_at: Option<u64>,
+    /// How many hints have been revealed, from the first.
+    #[serde(default)]
+    pub hints_revealed: usize,
+    /// Failed runs since the last hint was revealed, or since the start.
+    #[serde(default)]
+    pub failures_since_hint: u32,
+}
+
+impl Progress {
+    /// Reads the store at `path`; a missing one is empty.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        serde_json::from_str(&json).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|err| err.to_string())?;
+        fs::write(path, json + "\n").map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// Records a run of the grading tests of `exercise` at `now`, in Unix seconds.
+    pub fn record_run(&mut self, exercise: &str, passed: bool, now: u64) {
+        let progress = self
+            .exercises
+            .entry(exercise.to_owned())
+            .or_insert_with(|| ExerciseProgress {
+                first_attempt_at: now,
+                ..ExerciseProgress::default()
+            });
+        progress.attempts += 1;
+        progress.last_attempt_at = now;
+        if passed {
+            progress.status = Status::Passing;
+            progress.passed_at.get_or_insert(now);
+        } else {
+            progress.status = Status::Failing;
+            progress.failures_since_hint += 1;
+        }
+    }
+
+    /// Reveals hint `number`, counted from 1, of `exercise`, which has `count` hints.
+    /// Hints already revealed can be seen again; the next one only after a failed run
+    /// since the one before, so they come one attempt at a time.
+    pub fn reveal_hint(
+        &mut self,
+        exercise: &str,
+        number: usize,
+        count: usize,
+    ) -> Result<(), String> {
+        if number == 0 || number > count {
+            return Err(match count {
+                0 => format!("`{}` has no hints", exercise),
+                _ => format!("`{}` has hints 1 to {}", exercise, count),
+            });
+        }
+        let locked = format!(
+            "hint {} unlocks after a failed run; try `grader run {}` first",
+            number, exercise
+        );
+        let Some(progress) = self.exercises.get_mut(exercise) else {
+            return Err(locked);
+        };
+        if number <= progress.hints_revealed {
+            return Ok(());
+        }
+        let next = progress.hints_revealed + 1;
+        if number > next {
+            return Err(format!("reveal hint {} first", next));
+        }
+        if progress.failures_since_hint == 0 {
+            return Err(locked);
+        }
+        progress.hints_revealed = number;
+        progress.failures_since_hint = 0;
+        Ok(())
+    }
+
+    /// A table of the progress through `exercises`, in that order, with how long ago
+    /// each was last run as of `now`, and a closing total.
+    pub fn table(&self, exercises: &[String], now: u64) -> Vec<String> {
+        let width = exercises
+            .iter()
+            .map(String::len)
+            .chain([8])
+            .max()
+            .unwrap_or_default();
+        let mut lines = vec![format!(
+            "{:<width$}  {:<11}  {:>8}  {:>5}  LAST RUN",
+            "EXERCISE", "STATUS", "ATTEMPTS", "HINTS"
+        )];
+        let mut passing = 0;
+        for name in exercises {
+            let line = match self.exercises.get(name) {
+                Some(progress) => {
+                    if progress.status == Status::Passing {
+                        passing += 1;
+                    }
+                    // Stores written before attempts were counted have none.
+                    let last_run = match progress.attempts {
+                        0 => "-".to_owned(),
+                        _ => ago(progress.last_attempt_at, now),
+                    };
+                    format!(
+                        "{:<width$}  {:<11}  {:>8}  {:>5}  {}",
+                        name, progress.status, progress.attempts, progress.hints_revealed, last_run
+                    )
+                }
+                None => format!(
+                    "{:<width$}  {:<11}  {:>8}  {:>5}  -",
+                    name, "not started", 0, 0
+                ),
+            };
+            lines.push(line);
+        }
+        lines.push(format!(
+            "{} of {} exercise(s) passing",
+            passing,
+            exercises.len()
+        ));
+        lines
+    }
+}
+
+/// The current time in Unix seconds.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// How long before `now` the time `then` was, roughly: `just now`, `5m ago`, `3h ago`,
+/// `2d ago`.
+pub fn ago(then: u64, now: u64) -> String {
+    match now.saturating_sub(then) {
+        seconds if seconds < 60 => "just now".to_owned(),
+        seconds if seconds < 60 * 60 => format!("{}m ago", seconds / 60),
+        seconds if seconds < 24 * 60 * 60 => format!("{}h ago", seconds / (60 * 60)),
+        seconds => format!("{}d ago", seconds / (24 * 60 * 60)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hints_unlock_one_failed_run_at_a_time() {
+        let mut progress = Progress::default();
+        assert!(progress
+            .reveal_hint("flipper", 1, 2)
+            .unwrap_err()
+            .contains("after a failed run"));
+
+        progress.record_run("flipper", false, 0);
+        assert_eq!(
+            progress.reveal_hint("flipper", 2, 2).unwrap_err(),
+            "reveal hint 1 first"
+        );
+        progress.reveal_hint("flipper", 1, 2).unwrap();
+        progress.reveal_hint("flipper", 1, 2).unwrap();
+        assert!(progress.reveal_hint("flipper", 2, 2).is_err());
+
+        progress.record_run("flipper", true, 0);
+        assert!(progress.reveal_hint("flipper", 2, 2).is_err());
+        progress.record_run("flipper", false, 0);
+        progress.reveal_hint("flipper", 2, 2).unwrap();
+        assert_eq!(progress.exercises["flipper"].hints_revealed, 2);
+    }
+
+    #[test]
+    fn hint_numbers_are_checked() {
+        let mut progress = Progress::default();
+        progress.record_run("flipper", false, 0);
+        assert_eq!(
+            progress.reveal_hint("flipper", 3, 2).unwrap_err(),
+            "`flipper` has hints 1 to 2"
+        );
+        assert!(progress.reveal_hint("flipper", 0, 2).is_err());
+        assert_eq!(
+            progress.reveal_hint("flipper", 1, 0).unwrap_err(),
+            "`flipper` has no hints"
+        );
+    }
+
+    #[test]
+    fn runs_are_counted_and_timed() {
+        let mut progress = Progress::default();
+        progress.record_run("flipper", false, 100);
+        progress.record_run("flipper", true, 200);
+        progress.record_run("flipper", false, 300);
+        progress.record_run("flipper", true, 400);
+        let flipper = &progress.exercises["flipper"];
+        assert_eq!(flipper.status, Status::Passing);
+        assert_eq!(flipper.attempts, 4);
+        assert_eq!(flipper.first_attempt_at, 100);
+        assert_eq!(flipper.last_attempt_at, 400);
+        assert_eq!(flipper.passed_at, Some(200));
+    }
+
+    #[test]
+    fn the_table_lists_every_exercise_in_order() {
+        let mut progress = Progress::default();
+        progress.record_run("flipper", true, 1_000);
+        progress.record_run("storage_mapping", false, 9_000);
+        let exercises = ["storage_mapping", "flipper", "lazy"].map(String::from);
+        assert_eq!(
+            progress.table(&exercises, 10_000),
+            [
+                "EXERCISE         STATUS       ATTEMPTS  HINTS  LAST RUN",
+                "storage_mapping  failing             1      0  16m ago",
+                "flipper          passing             1      0  2h ago",
+                "lazy             not started         0      0  -",
+                "1 of 3 exercise(s) passing",
+            ]
+        );
+    }
+
+    #[test]
+    fn ages_are_rounded_down() {
+        assert_eq!(ago(100, 159), "just now");
+        assert_eq!(ago(0, 3_599), "59m ago");
+        assert_eq!(ago(0, 2 * 86_400 + 5), "2d ago");
+        assert_eq!(ago(200, 100), "just now");
+    }
+
+    #[test]
+    fn progress_round_trips_through_its_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(PROGRESS_FILE);
+        assert_eq!(Progress::load(&path).unwrap(), Progress::default());
+
+        let mut progress = Progress::default();
+        progress.record_run("flipper", false, 0);
+        progress.reveal_hint("flipper", 1, 1).unwrap();
+        progress.record_run("counter", false, 0);
+        progress.save(&path).unwrap();
+        assert_eq!(Progress::load(&path).unwrap(), progress);
+    }
+}
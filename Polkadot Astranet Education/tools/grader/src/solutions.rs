@@ -0,0 +1,202 @@
+// tools/grader/src/solutions.rs
+//
+// What `grader verify-solutions` needs: every exercise has a reference solution in
+// `exercises/solutions/NAME.rs`, and a change to its grading tests that the solution
+// no longer passes means the exercise and its tests have drifted apart.
+//
+// A solution is tested in a copy of its exercise crate, under `target/solutions/`
+// in the exercises directory, with the solution as `src/lib.rs`; the crate learners
+// work in is never touched. The copies build into their own target directory: a copy
+// has the same package name and version as the crate it copies, so in the shared one
+// cargo would take either build for the other.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Where solutions are copied and built, in the exercises directory.
+const WORK_DIR: &str = "target/solutions";
+
+/// Whether the grading tests of the exercise crate in `dir` are end-to-end tests,
+/// which need a node to run.
+pub fn is_end_to_end(dir: &Path) -> Result<bool, String> {
+    let path = dir.join("tests/grading.rs");
+    let grading =
+        fs::read_to_string(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    Ok(grading.contains("#[ink_e2e::test]"))
+}
+
+/// Copies the exercise crate `name` to the work directory with its reference solution
+/// as `src/lib.rs`, returning the copy.
+pub fn prepare(exercises_dir: &Path, name: &str) -> Result<PathBuf, String> {
+    let solution = new_exercise::solution_path(exercises_dir, name);
+    if !solution.is_file() {
+        return Err(format!("no reference solution at {}", solution.display()));
+    }
+    let dir = exercises_dir.join(name);
+    let copy = exercises_dir.join(WORK_DIR).join("crates").join(name);
+    if copy.exists() {
+        fs::remove_dir_all(&copy).map_err(|err| format!("{}: {}", copy.display(), err))?;
+    }
+    copy_crate(&dir, &copy)?;
+
+    let manifest_path = dir.join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path)
+        .map_err(|err| format!("{}: {}", manifest_path.display(), err))?;
+    let base = dir
+        .canonicalize()
+        .map_err(|err| format!("{}: {}", dir.display(), err))?;
+    let manifest = absolute_paths(&manifest, &base)
+        .map_err(|err| format!("{}: {}", manifest_path.display(), err))?;
+    write(&copy.join("Cargo.toml"), &manifest)?;
+    fs::copy(&solution, copy.join("src/lib.rs"))
+        .map_err(|err| format!("{}: {}", solution.display(), err))?;
+    Ok(copy)
+}
+
+/// `cargo test` running the grading tests of the copy in `copy`, in the target
+/// directory of the copies.
+pub fn test_command(exercises_dir: &Path, copy: &Path) -> Command {
+    let mut command = Command::new("cargo");
+    command
+        .arg("test")
+        .arg("--manifest-path")
+        .arg(copy.join("Cargo.toml"))
+        .args(["--test", "grading"])
+        .env(
+            "CARGO_TARGET_DIR",
+            exercises_dir.join(WORK_DIR).join("target"),
+        );
+    command
+}
+
+/// Copies the crate in `from` to `to`, leaving out `target/` and `Cargo.lock`.
+fn copy_crate(from: &Path, to: &Path) -> Result<(), String> {
+    fs::create_dir_all(to).map_err(|err| format!("{}: {}", to.display(), err))?;
+    let entries = fs::read_dir(from).map_err(|err| format!("{}: {}", from.display(), err))?;
+    for entry in entries {
+        let entry = entry.map_err(|err| format!("{}: {}", from.display(), err))?;
+        let name = entry.file_name();
+        if name == "target" || name == "Cargo.lock" {
+            continue;
+        }
+        let path = entry.path();
+        if path.is_dir() {
+            copy_crate(&path, &to.join(&name))?;
+        } else {
+            fs::copy(&path, to.join(&name))
+                .map_err(|err| format!("{}: {}", path.display(), err))?;
+        }
+    }
+    Ok(())
+}
+
+/// Rewrites the relative `path` dependencies of `manifest`, a `Cargo.toml` in
+/// `base`, as absolute paths, so the manifest works from anywhere.
+fn absolute_paths(manifest: &str, base: &Path) -> Result<String, String> {
+    let mut manifest: toml::Table =
+        toml::from_str(manifest).map_err(|err| err.message().to_owned())?;
+    for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        let Some(toml::Value::Table(dependencies)) = manifest.get_mut(section) else {
+            continue;
+        };
+        for (_, dependency) in dependencies.iter_mut() {
+            let Some(toml::Value::String(path)) = dependency.get_mut("path") else {
+                continue;
+            };
+            if Path::new(path.as_str()).is_relative() {
+                *path = base.join(path.as_str()).display().to_string();
+            }
+        }
+    }
+    Ok(manifest.to_string())
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+[package]
+name = "counter_caller"
+version = "0.1.0"
+
+[dependencies]
+ink = { version = "5.1", default-features = false }
+counter = { path = "../support/counter", default-features = false }
+
+[dev-dependencies]
+astranet-scenario = { path = "../../crates/scenario" }
+pinned = { path = "/opt/pinned" }
+"#;
+
+    #[test]
+    fn path_dependencies_become_absolute() {
+        let manifest =
+            absolute_paths(MANIFEST, Path::new("/repo/exercises/counter_caller")).unwrap();
+        let manifest: toml::Table = toml::from_str(&manifest).unwrap();
+        let path = |section: &str, name: &str| {
+            manifest[section][name]["path"].as_str().unwrap().to_owned()
+        };
+        assert_eq!(
+            path("dependencies", "counter"),
+            "/repo/exercises/counter_caller/../support/counter"
+        );
+        assert_eq!(
+            path("dev-dependencies", "astranet-scenario"),
+            "/repo/exercises/counter_caller/../../crates/scenario"
+        );
+        assert_eq!(path("dev-dependencies", "pinned"), "/opt/pinned");
+        assert_eq!(
+            manifest["dependencies"]["ink"]["version"].as_str(),
+            Some("5.1")
+        );
+        assert_eq!(
+            manifest["dependencies"]["counter"]["default-features"].as_bool(),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn solutions_are_tested_in_a_copy() {
+        let temp = tempfile::tempdir().unwrap();
+        let exercises = temp.path();
+        let dir = exercises.join("flipper");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::create_dir_all(dir.join("tests")).unwrap();
+        fs::create_dir_all(dir.join("target/debug")).unwrap();
+        fs::write(dir.join("Cargo.toml"), MANIFEST).unwrap();
+        fs::write(dir.join("src/lib.rs"), "todo").unwrap();
+        fs::write(dir.join("tests/grading.rs"), "#[ink::test]").unwrap();
+        fs::write(dir.join("target/debug/out"), "").unwrap();
+        assert!(prepare(exercises, "flipper")
+            .unwrap_err()
+            .starts_with("no reference solution"));
+
+        fs::create_dir_all(exercises.join("solutions")).unwrap();
+        fs::write(exercises.join("solutions/flipper.rs"), "solved").unwrap();
+        let copy = prepare(exercises, "flipper").unwrap();
+        assert_eq!(
+            fs::read_to_string(copy.join("src/lib.rs")).unwrap(),
+            "solved"
+        );
+        assert_eq!(fs::read_to_string(dir.join("src/lib.rs")).unwrap(), "todo");
+        assert!(copy.join("tests/grading.rs").is_file());
+        assert!(!copy.join("target").exists());
+        assert!(fs::read_to_string(copy.join("Cargo.toml"))
+            .unwrap()
+            .contains(&*dir.canonicalize().unwrap().display().to_string()));
+        assert!(!is_end_to_end(&copy).unwrap());
+
+        // Preparing again starts from a fresh copy.
+        fs::write(copy.join("stale"), "").unwrap();
+        let copy = prepare(exercises, "flipper").unwrap();
+        assert!(!copy.join("stale").exists());
+    }
+}
@@ -0,0 +1,172 @@
+// tools/grader/src/tracks.rs
+//
+// The exercise tracks of `tracks.toml` in the exercises directory: named, ordered
+// sequences of exercises, taken one after another.
+
+use serde::Deserialize;
+use std::{collections::BTreeSet, fs, path::Path};
+
+/// File name of the track manifest, in the exercises directory.
+pub const TRACKS_FILE: &str = "tracks.toml";
+
+/// A sequence of exercises on one topic.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Track {
+    pub name: String,
+    pub title: String,
+    /// In the order to take them.
+    pub exercises: Vec<String>,
+}
+
+/// Every track, in the order to take them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Tracks {
+    #[serde(default, rename = "track")]
+    pub tracks: Vec<Track>,
+}
+
+impl Tracks {
+    /// Reads tracks in the format of `tracks.toml`.
+    pub fn parse(toml: &str) -> Result<Self, String> {
+        toml::from_str(toml).map_err(|err| err.message().to_owned())
+    }
+
+    /// Reads the manifest of `exercises_dir`; without one, there are no tracks.
+    pub fn load(exercises_dir: &Path) -> Result<Self, String> {
+        let path = exercises_dir.join(TRACKS_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let toml =
+            fs::read_to_string(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        Self::parse(&toml).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// Checks that the tracks name only `exercises`, each at most once, and that no two
+    /// tracks share a name.
+    pub fn check(&self, exercises: &[String]) -> Result<(), String> {
+        let mut names = BTreeSet::new();
+        let mut listed = BTreeSet::new();
+        for track in &self.tracks {
+            if !names.insert(&track.name) {
+                return Err(format!("track `{}` is listed twice", track.name));
+            }
+            for exercise in &track.exercises {
+                if !exercises.contains(exercise) {
+                    return Err(format!(
+                        "track `{}` lists `{}`, which is not an exercise",
+                        track.name, exercise
+                    ));
+                }
+                if !listed.insert(exercise) {
+                    return Err(format!("`{}` is in more than one track", exercise));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The exercise after `exercise` in its track.
+    pub fn next(&self, exercise: &str) -> Option<&str> {
+        self.tracks.iter().find_map(|track| {
+            let position = track.exercises.iter().position(|name| name == exercise)?;
+            track.exercises.get(position + 1).map(String::as_str)
+        })
+    }
+
+    /// `exercises` grouped by track title, in track order, followed by those in no
+    /// track.
+    pub fn grouped(&self, exercises: &[String]) -> Vec<(String, Vec<String>)> {
+        let mut groups: Vec<(String, Vec<String>)> = self
+            .tracks
+            .iter()
+            .map(|track| {
+                let listed = track
+                    .exercises
+                    .iter()
+                    .filter(|name| exercises.contains(name))
+                    .cloned()
+                    .collect();
+                (track.title.clone(), listed)
+            })
+            .collect();
+        let rest: Vec<String> = exercises
+            .iter()
+            .filter(|name| {
+                !self
+                    .tracks
+                    .iter()
+                    .any(|track| track.exercises.contains(name))
+            })
+            .cloned()
+            .collect();
+        if !rest.is_empty() {
+            groups.push(("Other exercises".to_owned(), rest));
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACKS: &str = r#"
+[[track]]
+name = "storage"
+title = "Storage"
+exercises = ["mapping", "lazy"]
+
+[[track]]
+name = "calls"
+title = "Calls"
+exercises = ["caller"]
+"#;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn exercises_are_grouped_in_track_order() {
+        let tracks = Tracks::parse(TRACKS).unwrap();
+        let groups = tracks.grouped(&names(&["caller", "extra", "lazy", "mapping"]));
+        assert_eq!(
+            groups,
+            [
+                ("Storage".to_owned(), names(&["mapping", "lazy"])),
+                ("Calls".to_owned(), names(&["caller"])),
+                ("Other exercises".to_owned(), names(&["extra"])),
+            ]
+        );
+    }
+
+    #[test]
+    fn the_next_exercise_is_in_the_same_track() {
+        let tracks = Tracks::parse(TRACKS).unwrap();
+        assert_eq!(tracks.next("mapping"), Some("lazy"));
+        assert_eq!(tracks.next("lazy"), None);
+        assert_eq!(tracks.next("unknown"), None);
+    }
+
+    #[test]
+    fn tracks_name_existing_exercises_once() {
+        let tracks = Tracks::parse(TRACKS).unwrap();
+        assert!(tracks.check(&names(&["mapping", "lazy", "caller"])).is_ok());
+        assert_eq!(
+            tracks.check(&names(&["mapping", "caller"])).unwrap_err(),
+            "track `storage` lists `lazy`, which is not an exercise"
+        );
+        let twice = Tracks::parse(&format!(
+            "{}\n[[track]]\nname = \"again\"\ntitle = \"Again\"\nexercises = [\"lazy\"]\n",
+            TRACKS
+        ))
+        .unwrap();
+        assert!(twice
+            .check(&names(&["mapping", "lazy", "caller"]))
+            .unwrap_err()
+            .contains("more than one track"));
+    }
+}
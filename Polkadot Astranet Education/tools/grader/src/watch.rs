@@ -0,0 +1,269 @@
+// tools/grader/src/watch.rs
+//
+// What `grader watch` needs between runs: when the exercise's files last changed, and
+// what the grading tests said, test by test, so each run can be reported against the
+// one before ("2 of 4 pass, 1 more than last time").
+//
+// There is no file-system notification here; the watcher polls modification times,
+// which is plenty for a handful of files edited by hand.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// The modification time of every file in an exercise crate, `target/` left out.
+pub type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Takes a snapshot of the files under `dir`.
+pub fn snapshot(dir: &Path) -> Snapshot {
+    let mut files = Snapshot::new();
+    collect(dir, &mut files);
+    files
+}
+
+fn collect(dir: &Path, files: &mut Snapshot) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if entry.file_name() != "target" {
+                collect(&path, files);
+            }
+        } else if let Ok(modified) = entry.metadata().and_then(|meta| meta.modified()) {
+            files.insert(path, modified);
+        }
+    }
+}
+
+/// The outcome of one run of the grading tests.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TestRun {
+    /// Whether the grading tests ran, which they do once the exercise compiles.
+    pub compiled: bool,
+    /// Whether each test passed, by name.
+    pub results: BTreeMap<String, bool>,
+    /// The `---- NAME stdout ----` sections libtest prints for the failures.
+    pub failures: String,
+}
+
+impl TestRun {
+    /// Reads the libtest output of a run, which must use `--color never`.
+    pub fn parse(stdout: &str) -> Self {
+        let mut run = TestRun::default();
+        let mut in_failures = false;
+        for line in stdout.lines() {
+            if line.starts_with("running ") {
+                run.compiled = true;
+            }
+            if let Some(rest) = line.strip_prefix("test ") {
+                if let Some((name, result)) = rest.rsplit_once(" ... ") {
+                    match result {
+                        "ok" => run.results.insert(name.to_owned(), true),
+                        "FAILED" => run.results.insert(name.to_owned(), false),
+                        _ => None,
+                    };
+                    continue;
+                }
+            }
+            if line == "failures:" && !in_failures {
+                in_failures = true;
+                continue;
+            }
+            if in_failures {
+                // The details end where libtest lists the failing tests' names.
+                if line == "failures:" {
+                    in_failures = false;
+                    continue;
+                }
+                run.failures.push_str(line);
+                run.failures.push('\n');
+            }
+        }
+        run.failures = run.failures.trim().to_owned();
+        run
+    }
+
+    pub fn passed(&self) -> bool {
+        self.compiled && !self.results.is_empty() && self.results.values().all(|&ok| ok)
+    }
+
+    fn passing(&self) -> usize {
+        self.results.values().filter(|&&ok| ok).count()
+    }
+}
+
+/// ANSI colours, on when writing to a terminal and `NO_COLOR` is unset.
+#[derive(Debug, Clone, Copy)]
+pub struct Style {
+    pub color: bool,
+}
+
+impl Style {
+    pub fn detect() -> Self {
+        Style {
+            color: io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+        }
+    }
+
+    pub fn green(self, text: &str) -> String {
+        self.paint("32", text)
+    }
+
+    pub fn red(self, text: &str) -> String {
+        self.paint("31", text)
+    }
+
+    pub fn yellow(self, text: &str) -> String {
+        self.paint("33", text)
+    }
+
+    pub fn bold(self, text: &str) -> String {
+        self.paint("1", text)
+    }
+
+    fn paint(self, code: &str, text: &str) -> String {
+        if self.color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_owned()
+        }
+    }
+}
+
+/// The report of `run`, test by test, marking what changed since `previous`.
+pub fn feedback(previous: Option<&TestRun>, run: &TestRun, style: Style) -> Vec<String> {
+    if !run.compiled {
+        return vec![style.red("✗ the exercise does not compile yet; see the errors above")];
+    }
+    let mut lines = Vec::new();
+    for (name, &ok) in &run.results {
+        let before = previous.and_then(|previous| previous.results.get(name));
+        let change = match (before, ok) {
+            (Some(false) | None, true) if previous.is_some() => style.yellow("  (new)"),
+            (Some(true), false) => style.yellow("  (broke)"),
+            _ => String::new(),
+        };
+        let mark = if ok {
+            style.green("✓")
+        } else {
+            style.red("✗")
+        };
+        lines.push(format!("{} {}{}", mark, name, change));
+    }
+
+    let total = run.results.len();
+    let passing = run.passing();
+    let mut summary = format!("{} of {} grading test(s) pass", passing, total);
+    if let Some(previous) = previous.filter(|previous| previous.compiled) {
+        let before = previous.passing();
+        if passing > before {
+            summary += &format!(", {} more than last time", passing - before);
+        } else if passing < before {
+            summary += &format!(", {} fewer than last time", before - passing);
+        }
+    }
+    lines.push(if run.passed() {
+        style.green(&summary)
+    } else {
+        style.bold(&summary)
+    });
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "
+running 3 tests
+test balances_start_empty ... ok
+test transfer_moves_balance ... FAILED
+test transfer_checks_balance ... ok
+
+failures:
+
+---- transfer_moves_balance stdout ----
+thread 'transfer_moves_balance' panicked at tests/grading.rs:20:5:
+assertion `left == right` failed
+
+failures:
+    transfer_moves_balance
+
+test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out
+";
+
+    const PLAIN: Style = Style { color: false };
+
+    #[test]
+    fn libtest_output_is_parsed() {
+        let run = TestRun::parse(OUTPUT);
+        assert_eq!(run.results.len(), 3);
+        assert!(!run.results["transfer_moves_balance"]);
+        assert!(run
+            .failures
+            .starts_with("---- transfer_moves_balance stdout ----"));
+        assert!(run.failures.ends_with("failed"));
+        assert!(!run.passed());
+    }
+
+    #[test]
+    fn feedback_marks_changes_since_the_last_run() {
+        let before = TestRun::parse(OUTPUT);
+        let mut after = before.clone();
+        after
+            .results
+            .insert("transfer_moves_balance".to_owned(), true);
+        after
+            .results
+            .insert("transfer_checks_balance".to_owned(), false);
+        assert_eq!(
+            feedback(Some(&before), &after, PLAIN),
+            [
+                "✓ balances_start_empty",
+                "✗ transfer_checks_balance  (broke)",
+                "✓ transfer_moves_balance  (new)",
+                "2 of 3 grading test(s) pass",
+            ]
+        );
+
+        after
+            .results
+            .insert("transfer_checks_balance".to_owned(), true);
+        assert_eq!(
+            feedback(Some(&before), &after, PLAIN).last().unwrap(),
+            "3 of 3 grading test(s) pass, 1 more than last time"
+        );
+        assert!(after.passed());
+    }
+
+    #[test]
+    fn the_first_run_and_build_failures_have_no_changes() {
+        let run = TestRun::parse(OUTPUT);
+        let lines = feedback(None, &run, PLAIN);
+        assert!(!lines.iter().any(|line| line.contains("(new)")));
+        assert!(feedback(Some(&run), &TestRun::default(), PLAIN)[0].contains("does not compile"));
+        assert_eq!(
+            feedback(Some(&TestRun::default()), &run, PLAIN)
+                .last()
+                .unwrap(),
+            "2 of 3 grading test(s) pass"
+        );
+    }
+
+    #[test]
+    fn snapshots_skip_the_target_directory() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join("src")).unwrap();
+        fs::create_dir_all(temp.path().join("target/debug")).unwrap();
+        fs::write(temp.path().join("src/lib.rs"), "").unwrap();
+        fs::write(temp.path().join("target/debug/out"), "").unwrap();
+        let files: Vec<_> = snapshot(temp.path()).into_keys().collect();
+        assert_eq!(files, [temp.path().join("src/lib.rs")]);
+    }
+}
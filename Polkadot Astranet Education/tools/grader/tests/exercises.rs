@@ -0,0 +1,78 @@
+// tools/grader/tests/exercises.rs
+//
+// Checks the repository's exercises against what the grader expects of them, so a
+// malformed exercise fails here rather than in front of a learner.
+
+use grader::{exercises, tracks::Tracks, Exercise, Hints};
+use std::{fs, path::Path};
+
+fn exercises_dir() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../../exercises"))
+}
+
+#[test]
+fn every_exercise_is_complete() {
+    let names = exercises(exercises_dir()).unwrap();
+    assert!(!names.is_empty());
+    for name in &names {
+        let dir = exercises_dir().join(name);
+        Exercise::load(&dir).unwrap();
+        let hints = Hints::load(&dir).unwrap();
+        assert!(!hints.hints.is_empty(), "{} has no hints", name);
+        assert!(
+            hints.hints.iter().all(|hint| !hint.contains("TODO")),
+            "{} has unwritten hints",
+            name
+        );
+        let grading = fs::read_to_string(dir.join("tests/grading.rs")).unwrap();
+        assert!(
+            grading.contains("#[ink::test]") || grading.contains("#[ink_e2e::test]"),
+            "{} has no grading tests",
+            name
+        );
+        let skeleton = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+        assert!(skeleton.contains("TODO"), "{} has nothing to do", name);
+    }
+}
+
+#[test]
+fn every_exercise_is_in_a_track() {
+    let names = exercises(exercises_dir()).unwrap();
+    let tracks = Tracks::load(exercises_dir()).unwrap();
+    tracks.check(&names).unwrap();
+    for name in &names {
+        assert!(
+            tracks
+                .tracks
+                .iter()
+                .any(|track| track.exercises.contains(name)),
+            "{} is in no track",
+            name
+        );
+    }
+}
+
+#[test]
+fn every_exercise_has_a_reference_solution() {
+    let names = exercises(exercises_dir()).unwrap();
+    for name in &names {
+        let solution = new_exercise::solution_path(exercises_dir(), name);
+        let solution = fs::read_to_string(&solution)
+            .unwrap_or_else(|err| panic!("{}: {}", solution.display(), err));
+        assert!(
+            solution.contains(&format!("pub mod {} {{", name)),
+            "the solution of {} is not its contract",
+            name
+        );
+    }
+    let solutions = fs::read_dir(exercises_dir().join(new_exercise::SOLUTIONS_DIR)).unwrap();
+    for entry in solutions {
+        let path = entry.unwrap().path();
+        let name = path.file_stem().unwrap().to_str().unwrap();
+        assert!(
+            names.iter().any(|exercise| exercise == name),
+            "{} solves no exercise",
+            path.display()
+        );
+    }
+}
@@ -0,0 +1,344 @@
+// tools/lint-contracts/src/facts.rs
+//
+// What the lints need to know about a contract's functions, read off the syntax tree:
+// where each one does arithmetic, writes storage, calls another contract, calls
+// another function of the contract, and whether it looks at its caller.
+//
+// Storage is whatever hangs off `self`: `self.total = ..`, `*self.owner = ..`, and the
+// mapping methods `insert`, `remove`, `set` and so on called on a field of `self`.
+// A call to another contract is a `build_call` fired or invoked, `invoke_contract`, or
+// a message sent through a field holding a contract reference (a type named `...Ref`).
+
+use proc_macro2::Span;
+use std::collections::BTreeSet;
+use syn::{
+    punctuated::Punctuated,
+    spanned::Spanned,
+    visit::{self, Visit},
+    BinOp, Expr, ExprBinary, ExprCall, ExprMethodCall, Fields, FnArg, ImplItem, ImplItemFn, Item,
+    ItemMod, Token, Type,
+};
+
+/// Methods that write to the storage value they are called on.
+const WRITING_METHODS: &[&str] = &[
+    "insert",
+    "remove",
+    "set",
+    "push",
+    "pop",
+    "clear",
+    "take",
+    "swap_remove",
+    "retain",
+    "extend",
+];
+
+/// Methods and functions that run another contract.
+const CALLING_METHODS: &[&str] = &["fire", "invoke", "try_invoke"];
+const CALLING_FUNCTIONS: &[&str] = &["invoke_contract", "invoke_contract_delegate"];
+
+/// One thing a function does, at a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// An arithmetic operator that can overflow, and its checked counterpart.
+    Arithmetic {
+        op: &'static str,
+        checked: &'static str,
+    },
+    /// A write to the contract's storage.
+    Write,
+    /// A call to another contract.
+    ExternalCall,
+    /// A call to a function of the contract, by name.
+    SelfCall(String),
+    /// A look at the caller.
+    Caller,
+}
+
+/// A function in an `impl` block of a contract.
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub name: String,
+    pub line: usize,
+    /// `#[ink(message)]`.
+    pub message: bool,
+    /// Takes `&mut self`.
+    pub mutable: bool,
+    /// What it does, in source order.
+    pub events: Vec<(usize, Event)>,
+}
+
+/// The functions of every `#[ink::contract]` module in `file`, leaving out
+/// `#[cfg(test)]` items.
+pub fn contract_functions(file: &syn::File) -> Vec<Function> {
+    let mut functions = Vec::new();
+    for item in &file.items {
+        if let Item::Mod(module) = item {
+            if is_contract(module) {
+                collect(module, &reference_fields(module), &mut functions);
+            }
+        }
+    }
+    functions
+}
+
+fn is_contract(module: &ItemMod) -> bool {
+    module.attrs.iter().any(|attr| {
+        let path = attr.path();
+        path.segments
+            .last()
+            .is_some_and(|last| last.ident == "contract")
+            && path
+                .segments
+                .first()
+                .is_some_and(|first| first.ident == "ink" || first.ident == "ink_lang")
+    })
+}
+
+fn is_cfg_test(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("cfg")
+            && attr
+                .meta
+                .require_list()
+                .is_ok_and(|list| list.tokens.to_string().contains("test"))
+    })
+}
+
+/// Whether `attrs` hold `#[ink(NAME, ..)]`.
+fn has_ink(attrs: &[syn::Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("ink")
+            && attr.meta.require_list().is_ok_and(|list| {
+                list.tokens
+                    .to_string()
+                    .split(',')
+                    .any(|part| part.trim() == name)
+            })
+    })
+}
+
+/// Fields of the storage struct holding references to other contracts.
+fn reference_fields(module: &ItemMod) -> BTreeSet<String> {
+    let mut fields = BTreeSet::new();
+    for item in module.content.iter().flat_map(|(_, items)| items) {
+        let Item::Struct(storage) = item else {
+            continue;
+        };
+        if !has_ink(&storage.attrs, "storage") {
+            continue;
+        }
+        if let Fields::Named(named) = &storage.fields {
+            for field in &named.named {
+                if names_reference(&field.ty) {
+                    fields.extend(field.ident.as_ref().map(ToString::to_string));
+                }
+            }
+        }
+    }
+    fields
+}
+
+fn names_reference(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.iter().any(|segment| {
+            segment.ident.to_string().ends_with("Ref")
+                || match &segment.arguments {
+                    syn::PathArguments::AngleBracketed(args) => {
+                        args.args.iter().any(|arg| match arg {
+                            syn::GenericArgument::Type(ty) => names_reference(ty),
+                            _ => false,
+                        })
+                    }
+                    _ => false,
+                }
+        }),
+        _ => false,
+    }
+}
+
+fn collect(module: &ItemMod, references: &BTreeSet<String>, functions: &mut Vec<Function>) {
+    for item in module.content.iter().flat_map(|(_, items)| items) {
+        match item {
+            Item::Impl(block) if !is_cfg_test(&block.attrs) => {
+                for item in &block.items {
+                    if let ImplItem::Fn(function) = item {
+                        if !is_cfg_test(&function.attrs) {
+                            functions.push(read_function(function, references));
+                        }
+                    }
+                }
+            }
+            Item::Mod(inner) if !is_cfg_test(&inner.attrs) => collect(inner, references, functions),
+            _ => {}
+        }
+    }
+}
+
+fn read_function(function: &ImplItemFn, references: &BTreeSet<String>) -> Function {
+    let mutable = matches!(
+        function.sig.inputs.first(),
+        Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() && receiver.mutability.is_some()
+    );
+    let mut events = Events {
+        references,
+        events: Vec::new(),
+    };
+    events.visit_block(&function.block);
+    Function {
+        name: function.sig.ident.to_string(),
+        line: line(function.sig.ident.span()),
+        message: has_ink(&function.attrs, "message"),
+        mutable,
+        events: events.events,
+    }
+}
+
+pub fn line(span: Span) -> usize {
+    span.start().line
+}
+
+struct Events<'a> {
+    references: &'a BTreeSet<String>,
+    events: Vec<(usize, Event)>,
+}
+
+impl Events<'_> {
+    fn push(&mut self, span: Span, event: Event) {
+        self.events.push((line(span), event));
+    }
+}
+
+impl<'ast> Visit<'ast> for Events<'_> {
+    fn visit_expr_binary(&mut self, expr: &'ast ExprBinary) {
+        let arithmetic = match expr.op {
+            BinOp::Add(_) | BinOp::AddAssign(_) => Some(("+", "checked_add")),
+            BinOp::Sub(_) | BinOp::SubAssign(_) => Some(("-", "checked_sub")),
+            BinOp::Mul(_) | BinOp::MulAssign(_) => Some(("*", "checked_mul")),
+            _ => None,
+        };
+        if let Some((op, checked)) = arithmetic {
+            if !(is_literal(&expr.left) && is_literal(&expr.right)) {
+                self.push(expr.op.span(), Event::Arithmetic { op, checked });
+            }
+        }
+        let assigns = matches!(
+            expr.op,
+            BinOp::AddAssign(_)
+                | BinOp::SubAssign(_)
+                | BinOp::MulAssign(_)
+                | BinOp::DivAssign(_)
+                | BinOp::RemAssign(_)
+                | BinOp::BitAndAssign(_)
+                | BinOp::BitOrAssign(_)
+                | BinOp::BitXorAssign(_)
+                | BinOp::ShlAssign(_)
+                | BinOp::ShrAssign(_)
+        );
+        visit::visit_expr_binary(self, expr);
+        // After the operands, which are evaluated first.
+        if assigns && on_self_field(&expr.left) {
+            self.push(expr.op.span(), Event::Write);
+        }
+    }
+
+    fn visit_expr_assign(&mut self, expr: &'ast syn::ExprAssign) {
+        visit::visit_expr_assign(self, expr);
+        if on_self_field(&expr.left) {
+            self.push(expr.eq_token.span(), Event::Write);
+        }
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        visit::visit_expr_method_call(self, call);
+        let method = call.method.to_string();
+        let span = call.method.span();
+        if method == "caller" {
+            self.push(span, Event::Caller);
+        } else if is_self(&call.receiver) {
+            self.push(span, Event::SelfCall(method));
+        } else if CALLING_METHODS.contains(&method.as_str())
+            || matches!(
+                self_field(&call.receiver),
+                Some(syn::Member::Named(field)) if self.references.contains(&field.to_string())
+            )
+        {
+            self.push(span, Event::ExternalCall);
+        } else if WRITING_METHODS.contains(&method.as_str()) && on_self_field(&call.receiver) {
+            self.push(span, Event::Write);
+        }
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        visit::visit_expr_call(self, call);
+        if let Expr::Path(path) = &*call.func {
+            let last = path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string());
+            match last.as_deref() {
+                Some(name) if CALLING_FUNCTIONS.contains(&name) => {
+                    self.push(call.func.span(), Event::ExternalCall)
+                }
+                // `Self::helper(..)`, an associated function of the contract.
+                Some(name)
+                    if path.path.segments.len() == 2 && path.path.segments[0].ident == "Self" =>
+                {
+                    self.push(call.func.span(), Event::SelfCall(name.to_owned()))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // `assert!`, `ensure!` and the like take expressions, which syn leaves as tokens.
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if let Ok(args) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+            for arg in &args {
+                self.visit_expr(arg);
+            }
+        }
+    }
+
+    // Nested items run elsewhere, or not at all.
+    fn visit_item(&mut self, _: &'ast Item) {}
+}
+
+fn is_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(_) => true,
+        Expr::Paren(inner) => is_literal(&inner.expr),
+        Expr::Binary(inner) => is_literal(&inner.left) && is_literal(&inner.right),
+        // Constants, by naming convention.
+        Expr::Path(path) => path.path.segments.last().is_some_and(|segment| {
+            let name = segment.ident.to_string();
+            name.len() > 1
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c == '_' || c.is_ascii_digit())
+        }),
+        _ => false,
+    }
+}
+
+fn is_self(expr: &Expr) -> bool {
+    matches!(expr, Expr::Path(path) if path.path.is_ident("self"))
+}
+
+/// The field of `self` that `expr` is, or is in: `self.a`, `self.a.b`, `*self.a`,
+/// `self.a[i]`.
+fn self_field(expr: &Expr) -> Option<&syn::Member> {
+    match expr {
+        Expr::Field(field) if is_self(&field.base) => Some(&field.member),
+        Expr::Field(field) => self_field(&field.base),
+        Expr::Unary(unary) => self_field(&unary.expr),
+        Expr::Index(index) => self_field(&index.expr),
+        Expr::Paren(inner) => self_field(&inner.expr),
+        _ => None,
+    }
+}
+
+fn on_self_field(expr: &Expr) -> bool {
+    self_field(expr).is_some()
+}
@@ -0,0 +1,365 @@
+// tools/lint-contracts/src/lib.rs
+//
+// A linter for the pitfalls the security lessons warn about, run over the example
+// contracts' source. It reads the syntax tree with syn and knows nothing of types, so
+// each lint is a heuristic: it points at code worth a second look, and a finding that
+// has been looked at can be silenced with a comment on its line or the line above:
+//
+//     // lint-contracts: allow(write-after-call)
+//
+// The lints:
+//
+// - `unchecked-arithmetic`: `+`, `-` and `*` (and their assigning forms) in contract
+//   code. Whether an overflow panics or wraps depends on the build profile; the
+//   `checked_*` methods make the contract decide what happens.
+// - `missing-access-control`: a message taking `&mut self` that writes storage
+//   without ever looking at its caller, directly or through a helper of the contract
+//   such as `ensure_owner`. Anyone can call it, which is sometimes the point, as for
+//   a faucet's `drip`, and sometimes a bug.
+// - `write-after-call`: a storage write after a call to another contract in the same
+//   function. The callee can call back in and see the old state, the re-entrancy the
+//   checks-effects-interactions pattern avoids by writing first.
+//
+// Helpers are followed: a call to a function of the contract counts as the writes,
+// calls to other contracts and looks at the caller it makes, however deep.
+
+mod facts;
+
+use facts::{contract_functions, Event, Function};
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+/// A lint, by the name findings and allow comments use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Lint {
+    UncheckedArithmetic,
+    MissingAccessControl,
+    WriteAfterCall,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 3] = [
+        Lint::UncheckedArithmetic,
+        Lint::MissingAccessControl,
+        Lint::WriteAfterCall,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Lint::UncheckedArithmetic => "unchecked-arithmetic",
+            Lint::MissingAccessControl => "missing-access-control",
+            Lint::WriteAfterCall => "write-after-call",
+        }
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for Lint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Lint::ALL
+            .into_iter()
+            .find(|lint| lint.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = Lint::ALL.iter().map(|lint| lint.name()).collect();
+                format!("unknown lint `{}`; expected one of {}", s, names.join(", "))
+            })
+    }
+}
+
+/// Something a lint found.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Finding {
+    pub line: usize,
+    pub lint: Lint,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.line, self.lint, self.message)
+    }
+}
+
+/// Runs `lints` over the contract source `source`, returning what they find by line.
+pub fn lint(source: &str, lints: &[Lint]) -> Result<Vec<Finding>, String> {
+    let file = syn::parse_file(source).map_err(|err| {
+        format!(
+            "{}:{}: {}",
+            err.span().start().line,
+            err.span().start().column + 1,
+            err
+        )
+    })?;
+    let functions = contract_functions(&file);
+    let summaries = summarize(&functions);
+
+    let mut findings = Vec::new();
+    for function in &functions {
+        if lints.contains(&Lint::UncheckedArithmetic) {
+            unchecked_arithmetic(function, &mut findings);
+        }
+        if lints.contains(&Lint::MissingAccessControl) {
+            missing_access_control(function, &summaries, &mut findings);
+        }
+        if lints.contains(&Lint::WriteAfterCall) {
+            write_after_call(function, &summaries, &mut findings);
+        }
+    }
+
+    let lines: Vec<&str> = source.lines().collect();
+    findings.retain(|finding| !allowed(&lines, finding));
+    findings.sort();
+    findings.dedup();
+    Ok(findings)
+}
+
+/// Whether the line of `finding`, or the one above, allows its lint.
+fn allowed(lines: &[&str], finding: &Finding) -> bool {
+    let allow = format!("lint-contracts: allow({})", finding.lint);
+    let index = finding.line.saturating_sub(1);
+    [index.checked_sub(1), Some(index)]
+        .into_iter()
+        .flatten()
+        .filter_map(|index| lines.get(index))
+        .any(|line| line.contains(&allow))
+}
+
+/// What a function does, helpers included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Summary {
+    writes: bool,
+    calls_out: bool,
+    checks_caller: bool,
+}
+
+/// Summarizes every function, following calls between them to a fixed point.
+fn summarize(functions: &[Function]) -> BTreeMap<&str, Summary> {
+    let mut summaries: BTreeMap<&str, Summary> = BTreeMap::new();
+    loop {
+        let mut changed = false;
+        for function in functions {
+            let mut summary = summaries
+                .get(function.name.as_str())
+                .copied()
+                .unwrap_or_default();
+            let before = summary;
+            for (_, event) in &function.events {
+                match event {
+                    Event::Write => summary.writes = true,
+                    Event::ExternalCall => summary.calls_out = true,
+                    Event::Caller => summary.checks_caller = true,
+                    Event::SelfCall(name) => {
+                        if let Some(callee) = summaries.get(name.as_str()) {
+                            summary.writes |= callee.writes;
+                            summary.calls_out |= callee.calls_out;
+                            summary.checks_caller |= callee.checks_caller;
+                        }
+                    }
+                    Event::Arithmetic { .. } => {}
+                }
+            }
+            if summary != before || !summaries.contains_key(function.name.as_str()) {
+                summaries.insert(&function.name, summary);
+                changed |= summary != before;
+            }
+        }
+        if !changed {
+            return summaries;
+        }
+    }
+}
+
+fn unchecked_arithmetic(function: &Function, findings: &mut Vec<Finding>) {
+    for (line, event) in &function.events {
+        if let Event::Arithmetic { op, checked } = event {
+            findings.push(Finding {
+                line: *line,
+                lint: Lint::UncheckedArithmetic,
+                message: format!(
+                    "unchecked `{}` in `{}` can overflow; use `{}` and handle `None`",
+                    op, function.name, checked
+                ),
+            });
+        }
+    }
+}
+
+fn missing_access_control(
+    function: &Function,
+    summaries: &BTreeMap<&str, Summary>,
+    findings: &mut Vec<Finding>,
+) {
+    let summary = summaries[function.name.as_str()];
+    if function.message && function.mutable && summary.writes && !summary.checks_caller {
+        findings.push(Finding {
+            line: function.line,
+            lint: Lint::MissingAccessControl,
+            message: format!(
+                "message `{}` writes storage but never checks its caller; anyone can call it",
+                function.name
+            ),
+        });
+    }
+}
+
+fn write_after_call(
+    function: &Function,
+    summaries: &BTreeMap<&str, Summary>,
+    findings: &mut Vec<Finding>,
+) {
+    let mut first_call = None;
+    for (line, event) in &function.events {
+        let (writes, calls_out) = match event {
+            Event::Write => (true, false),
+            Event::ExternalCall => (false, true),
+            Event::SelfCall(name) => summaries
+                .get(name.as_str())
+                .map_or((false, false), |callee| (callee.writes, callee.calls_out)),
+            _ => (false, false),
+        };
+        if let (true, Some(call_line)) = (writes, first_call) {
+            findings.push(Finding {
+                line: *line,
+                lint: Lint::WriteAfterCall,
+                message: format!(
+                    "storage written after the call to another contract on line {}; \
+                     write first, then call (checks-effects-interactions)",
+                    call_line
+                ),
+            });
+        }
+        if calls_out && first_call.is_none() {
+            first_call = Some(*line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(source: &str) -> Vec<(usize, &'static str)> {
+        lint(source, &Lint::ALL)
+            .unwrap()
+            .into_iter()
+            .map(|finding| (finding.line, finding.lint.name()))
+            .collect()
+    }
+
+    #[test]
+    fn only_contract_modules_are_linted() {
+        let source = "
+mod not_a_contract {
+    impl Thing {
+        fn add(&mut self, a: u32) { self.total = self.total + a; }
+    }
+}
+";
+        assert!(found(source).is_empty());
+    }
+
+    #[test]
+    fn helpers_are_followed() {
+        let source = "
+#[ink::contract]
+mod vault {
+    impl Vault {
+        #[ink(message)]
+        pub fn set_limit(&mut self, limit: u32) {
+            self.ensure_owner();
+            self.store(limit);
+        }
+
+        #[ink(message)]
+        pub fn reset(&mut self) {
+            self.store(0);
+        }
+
+        fn store(&mut self, limit: u32) {
+            self.limit = limit;
+        }
+
+        fn ensure_owner(&self) {
+            assert_eq!(Self::env().caller(), self.owner);
+        }
+    }
+}
+";
+        assert_eq!(found(source), [(12, "missing-access-control")]);
+    }
+
+    #[test]
+    fn literal_and_constant_arithmetic_is_not_flagged() {
+        let source = "
+#[ink::contract]
+mod fees {
+    impl Fees {
+        #[ink(message)]
+        pub fn max(&self) -> u128 {
+            let bps = 10_000 * 2;
+            MAX_FEE * BPS + bps
+        }
+    }
+}
+";
+        assert_eq!(found(source), [(8, "unchecked-arithmetic")]);
+    }
+
+    #[test]
+    fn allow_comments_silence_their_lint_only() {
+        let source = "
+#[ink::contract]
+mod counter {
+    impl Counter {
+        #[ink(message)]
+        pub fn bump(&mut self) {
+            let caller = self.env().caller();
+            // lint-contracts: allow(unchecked-arithmetic)
+            self.count += 1;
+            self.last = caller; self.total = self.total + 1;
+        }
+    }
+}
+";
+        assert_eq!(found(source), [(10, "unchecked-arithmetic")]);
+    }
+
+    #[test]
+    fn test_modules_are_skipped() {
+        let source = "
+#[ink::contract]
+mod counter {
+    #[cfg(test)]
+    mod tests {
+        impl Helper {
+            fn sum(&self, a: u32) -> u32 { self.a + a }
+        }
+    }
+}
+";
+        assert!(found(source).is_empty());
+    }
+
+    #[test]
+    fn lints_parse_by_name() {
+        for lint in Lint::ALL {
+            assert_eq!(lint.name().parse(), Ok(lint));
+        }
+        assert!("reentrancy"
+            .parse::<Lint>()
+            .unwrap_err()
+            .contains("write-after-call"));
+    }
+
+    #[test]
+    fn syntax_errors_name_their_line() {
+        let err = lint("\n\nfn broken( {", &Lint::ALL).unwrap_err();
+        assert!(err.starts_with("3:"), "{}", err);
+    }
+}
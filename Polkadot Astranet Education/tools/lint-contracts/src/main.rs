@@ -0,0 +1,81 @@
+// tools/lint-contracts/src/main.rs
+//
+// Usage:
+//   lint-contracts                                     lint examples/demo-contracts
+//   lint-contracts examples/demo-contracts/erc20.rs    lint some files or directories
+//   lint-contracts --lint write-after-call             run some lints only
+//
+// Prints `path:line: lint: message` for each finding and exits with 1 if there are any.
+
+use clap::Parser;
+use lint_contracts::{lint, Lint};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+#[derive(Parser)]
+#[command(about = "Flag common ink! pitfalls in contract source")]
+struct Cli {
+    /// Contract files, or directories of them.
+    #[arg(default_value = "examples/demo-contracts")]
+    paths: Vec<PathBuf>,
+    /// Run only these lints: unchecked-arithmetic, missing-access-control or
+    /// write-after-call.
+    #[arg(long)]
+    lint: Vec<Lint>,
+}
+
+fn main() -> ExitCode {
+    match run(&Cli::parse()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Returns whether nothing was found.
+fn run(cli: &Cli) -> Result<bool, String> {
+    let lints = if cli.lint.is_empty() {
+        Lint::ALL.to_vec()
+    } else {
+        cli.lint.clone()
+    };
+    let mut files = Vec::new();
+    for path in &cli.paths {
+        collect_files(path, &mut files)?;
+    }
+
+    let mut total = 0;
+    for file in &files {
+        let source =
+            fs::read_to_string(file).map_err(|err| format!("{}: {}", file.display(), err))?;
+        let findings =
+            lint(&source, &lints).map_err(|err| format!("{}:{}", file.display(), err))?;
+        for finding in &findings {
+            println!("{}:{}", file.display(), finding);
+        }
+        total += findings.len();
+    }
+    println!("{} finding(s) in {} file(s)", total, files.len());
+    Ok(total == 0)
+}
+
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    if !path.is_dir() {
+        files.push(path.to_owned());
+        return Ok(());
+    }
+    let entries = fs::read_dir(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let mut found: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("rs"))
+        .collect();
+    found.sort();
+    files.extend(found);
+    Ok(())
+}
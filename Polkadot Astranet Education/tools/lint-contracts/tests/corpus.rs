@@ -0,0 +1,63 @@
+// tools/lint-contracts/tests/corpus.rs
+//
+// Lints every snippet under `tests/corpus`. A snippet marks each line a lint should
+// flag with `// expect: LINT`; snippets under `good/` expect nothing, and snippets
+// under `bad/` expect something.
+
+use lint_contracts::{lint, Lint};
+use std::{fs, path::Path};
+
+fn expected(source: &str) -> Vec<(usize, String)> {
+    source
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let (_, lints) = line.split_once("// expect: ")?;
+            Some(
+                lints
+                    .split(',')
+                    .map(move |lint| (index + 1, lint.trim().to_owned())),
+            )
+        })
+        .flatten()
+        .collect()
+}
+
+fn check(dir: &str) -> usize {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/corpus")
+        .join(dir);
+    let mut snippets = 0;
+    for entry in fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        let source = fs::read_to_string(&path).unwrap();
+        let found: Vec<(usize, String)> = lint(&source, &Lint::ALL)
+            .unwrap_or_else(|err| panic!("{}: {}", path.display(), err))
+            .into_iter()
+            .map(|finding| (finding.line, finding.lint.to_string()))
+            .collect();
+        assert_eq!(found, expected(&source), "{}", path.display());
+        snippets += 1;
+    }
+    snippets
+}
+
+#[test]
+fn good_snippets_are_clean() {
+    assert!(check("good") > 0);
+}
+
+#[test]
+fn bad_snippets_are_flagged_where_marked() {
+    assert!(check("bad") > 0);
+}
+
+#[test]
+fn every_bad_snippet_expects_a_finding() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus/bad");
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let source = fs::read_to_string(&path).unwrap();
+        assert!(!expected(&source).is_empty(), "{}", path.display());
+    }
+}
@@ -0,0 +1,33 @@
+// A registry whose configuration anyone can change.
+
+#[ink::contract]
+mod registry {
+    impl Registry {
+        #[ink(constructor)]
+        pub fn new(fee: Balance) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                fee,
+            }
+        }
+
+        #[ink(message)]
+        pub fn set_fee(&mut self, fee: Balance) { // expect: missing-access-control
+            self.fee = fee;
+        }
+
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) { // expect: missing-access-control
+            self.set_owner(new_owner);
+        }
+
+        #[ink(message)]
+        pub fn fee(&self) -> Balance {
+            self.fee
+        }
+
+        fn set_owner(&mut self, owner: AccountId) {
+            self.owner = owner;
+        }
+    }
+}
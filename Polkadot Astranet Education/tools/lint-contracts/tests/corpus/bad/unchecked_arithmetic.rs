@@ -0,0 +1,38 @@
+// A token whose balances overflow silently in a build without overflow checks.
+
+#[ink::contract]
+mod token {
+    impl Token {
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            let from_balance = self.balance_of(from);
+            if from_balance < value {
+                return Err(Error::InsufficientBalance);
+            }
+            self.balances.insert(from, &(from_balance - value)); // expect: unchecked-arithmetic
+            let to_balance = self.balance_of(to);
+            self.balances.insert(to, &(to_balance + value)); // expect: unchecked-arithmetic
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn mint(&mut self, value: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            self.total_supply += value; // expect: unchecked-arithmetic
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn fee(&self, value: Balance) -> Balance {
+            value * self.fee_bps / BPS // expect: unchecked-arithmetic
+        }
+
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+    }
+}
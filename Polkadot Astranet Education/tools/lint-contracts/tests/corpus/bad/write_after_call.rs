@@ -0,0 +1,58 @@
+// A vault that pays out before it books the withdrawal, and a pool that settles
+// through another contract before updating its reserves.
+
+#[ink::contract]
+mod vault {
+    use ink_env::call::{build_call, ExecutionInput, Selector};
+
+    #[ink(storage)]
+    pub struct Vault {
+        deposits: Mapping<AccountId, Balance>,
+        reserves: Balance,
+        oracle: OracleRef,
+    }
+
+    impl Vault {
+        #[ink(message)]
+        pub fn withdraw(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            let amount = self.deposits.get(caller).unwrap_or_default();
+            build_call::<ink_env::DefaultEnvironment>()
+                .callee(caller)
+                .exec_input(ExecutionInput::new(Selector::new(ON_WITHDRAW)))
+                .returns::<()>()
+                .fire()
+                .map_err(|_| Error::CallFailed)?;
+            self.deposits.remove(caller); // expect: write-after-call
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn settle(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            self.pay(caller)?;
+            self.book(caller); // expect: write-after-call
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn refresh(&mut self) {
+            let caller = self.env().caller();
+            let price = self.oracle.price(caller);
+            self.reserves = price; // expect: write-after-call
+        }
+
+        fn pay(&self, to: AccountId) -> Result<()> {
+            build_call::<ink_env::DefaultEnvironment>()
+                .callee(to)
+                .exec_input(ExecutionInput::new(Selector::new(ON_SETTLE)))
+                .returns::<()>()
+                .fire()
+                .map_err(|_| Error::CallFailed)
+        }
+
+        fn book(&mut self, account: AccountId) {
+            self.deposits.insert(account, &0);
+        }
+    }
+}
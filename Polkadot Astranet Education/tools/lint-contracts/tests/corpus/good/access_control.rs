@@ -0,0 +1,44 @@
+// The registry of `bad/missing_access_control.rs`, guarded by its owner.
+
+#[ink::contract]
+mod registry {
+    impl Registry {
+        #[ink(constructor)]
+        pub fn new(fee: Balance) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                fee,
+            }
+        }
+
+        #[ink(message)]
+        pub fn set_fee(&mut self, fee: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            self.fee = fee;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.owner = new_owner;
+            Ok(())
+        }
+
+        /// Open to everyone on purpose: anyone may pay to be listed.
+        #[ink(message, payable)]
+        // lint-contracts: allow(missing-access-control)
+        pub fn list(&mut self, name: String) {
+            self.listed.insert(name, &true);
+        }
+
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// The token of `bad/unchecked_arithmetic.rs` with every operation checked.
+
+#[ink::contract]
+mod token {
+    impl Token {
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            let from_balance = self.balance_of(from);
+            let debited = from_balance
+                .checked_sub(value)
+                .ok_or(Error::InsufficientBalance)?;
+            let credited = self
+                .balance_of(to)
+                .checked_add(value)
+                .ok_or(Error::Overflow)?;
+            self.balances.insert(from, &debited);
+            self.balances.insert(to, &credited);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn mint(&mut self, value: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            self.total_supply = self
+                .total_supply
+                .checked_add(value)
+                .ok_or(Error::Overflow)?;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn max_fee(&self) -> Balance {
+            // Constants and literals cannot overflow at run time.
+            MAX_FEE_BPS * 10_000
+        }
+
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+    }
+}
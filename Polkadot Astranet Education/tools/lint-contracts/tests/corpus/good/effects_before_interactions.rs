@@ -0,0 +1,42 @@
+// The vault of `bad/write_after_call.rs`, booking the withdrawal before paying out.
+
+#[ink::contract]
+mod vault {
+    use ink_env::call::{build_call, ExecutionInput, Selector};
+
+    impl Vault {
+        #[ink(message)]
+        pub fn withdraw(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            let amount = self.deposits.get(caller).unwrap_or_default();
+            self.deposits.remove(caller);
+            build_call::<ink_env::DefaultEnvironment>()
+                .callee(caller)
+                .exec_input(ExecutionInput::new(Selector::new(ON_WITHDRAW)))
+                .returns::<()>()
+                .fire()
+                .map_err(|_| Error::CallFailed)?;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn settle(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            self.book(caller);
+            self.pay(caller)
+        }
+
+        fn pay(&self, to: AccountId) -> Result<()> {
+            build_call::<ink_env::DefaultEnvironment>()
+                .callee(to)
+                .exec_input(ExecutionInput::new(Selector::new(ON_SETTLE)))
+                .returns::<()>()
+                .fire()
+                .map_err(|_| Error::CallFailed)
+        }
+
+        fn book(&mut self, account: AccountId) {
+            self.deposits.insert(account, &0);
+        }
+    }
+}
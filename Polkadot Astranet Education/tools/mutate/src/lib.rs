@@ -0,0 +1,472 @@
+// tools/mutate/src/lib.rs
+//
+// Mutation testing asks how good a contract's tests are by breaking the contract on
+// purpose. Each mutant is a copy of the source with one small, plausible bug: `<`
+// where `<=` was meant, a `+` turned into `-`, a balance update left out. If the tests
+// fail, they killed the mutant; if they still pass, the mutant survived, and the bug
+// it stands for would go unnoticed. Survivors point at the tests to write next.
+//
+// Mutants are made line by line from a fixed set of operators, which is crude next to
+// a real mutation tool but easy to follow: every mutant is a one-line diff. Binary
+// operators are only matched with a space on each side, as rustfmt writes them, so
+// the `<` of a generic type such as `Mapping<AccountId, Balance>` is left alone.
+// Comments and `#[cfg(test)]` modules are never mutated.
+//
+// A mutant that does not compile, say `-=` on a type without it, is unviable: it says
+// nothing about the tests and is reported apart from the rest.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+use wait_timeout::ChildExt;
+
+/// The built-in manifest template of scratch crates.
+pub const SCRATCH_MANIFEST: &str = include_str!("../scratch.toml");
+
+/// The contract mutated when no other is given, relative to the repository root.
+pub const DEFAULT_SOURCE: &str = "examples/demo-contracts/erc20.rs";
+
+/// Replacements of binary operators, by operator name.
+const SWAPS: &[(&str, &str, &str)] = &[
+    ("relational", " < ", " <= "),
+    ("relational", " <= ", " < "),
+    ("relational", " > ", " >= "),
+    ("relational", " >= ", " > "),
+    ("equality", " == ", " != "),
+    ("equality", " != ", " == "),
+    ("arithmetic", " + ", " - "),
+    ("arithmetic", " - ", " + "),
+    ("arithmetic", " += ", " -= "),
+    ("arithmetic", " -= ", " += "),
+    ("arithmetic", ".checked_add(", ".checked_sub("),
+    ("arithmetic", ".checked_sub(", ".checked_add("),
+    ("arithmetic", ".saturating_add(", ".saturating_sub("),
+    ("arithmetic", ".saturating_sub(", ".saturating_add("),
+    ("logical", " && ", " || "),
+    ("logical", " || ", " && "),
+];
+
+/// Name of the operator leaving out a storage update.
+const DROP_UPDATE: &str = "drop-update";
+
+/// A copy of the source with one change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mutant {
+    /// Which kind of change: `relational`, `equality`, `arithmetic`, `logical` or
+    /// `drop-update`.
+    pub operator: &'static str,
+    /// 1-based line of the change.
+    pub line: usize,
+    /// The line before and after the change, without indentation.
+    pub original: String,
+    pub mutated: String,
+}
+
+impl fmt::Display for Mutant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {} ({}): `{}` -> `{}`",
+            self.line, self.operator, self.original, self.mutated
+        )
+    }
+}
+
+/// Lists the mutants of `source`, in line order.
+pub fn mutants(source: &str) -> Vec<Mutant> {
+    let mut mutants = Vec::new();
+    for (index, line) in mutable_lines(source) {
+        let code = code_of(line);
+        let trimmed = line.trim();
+        for (operator, from, to) in SWAPS {
+            for (at, _) in code.match_indices(from) {
+                let mut mutated = line.to_owned();
+                mutated.replace_range(at..at + from.len(), to);
+                mutants.push(Mutant {
+                    operator,
+                    line: index + 1,
+                    original: trimmed.to_owned(),
+                    mutated: mutated.trim().to_owned(),
+                });
+            }
+        }
+        if is_storage_update(code.trim()) {
+            mutants.push(Mutant {
+                operator: DROP_UPDATE,
+                line: index + 1,
+                original: trimmed.to_owned(),
+                mutated: String::new(),
+            });
+        }
+    }
+    mutants.sort_by_key(|mutant| mutant.line);
+    mutants
+}
+
+/// The lines that may be mutated, with their 0-based index: not comments, and not in
+/// a `#[cfg(test)]` item.
+fn mutable_lines(source: &str) -> Vec<(usize, &str)> {
+    let mut lines = Vec::new();
+    let mut depth = 0i32;
+    // The depth to get back to for the test module to have ended, once inside it.
+    let mut test_module: Option<i32> = None;
+    let mut cfg_test = false;
+    for (index, line) in source.lines().enumerate() {
+        let code = code_of(line);
+        let trimmed = code.trim();
+        let before = depth;
+        depth += code.matches('{').count() as i32 - code.matches('}').count() as i32;
+        if let Some(end) = test_module {
+            if depth <= end {
+                test_module = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("#[cfg(") && trimmed.contains("test") {
+            cfg_test = true;
+            continue;
+        }
+        if cfg_test && !trimmed.starts_with("#[") && !trimmed.is_empty() {
+            cfg_test = false;
+            if depth > before {
+                test_module = Some(before);
+            }
+            continue;
+        }
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        lines.push((index, line));
+    }
+    lines
+}
+
+/// `line` without a trailing `//` comment.
+fn code_of(line: &str) -> &str {
+    match line.find("//") {
+        Some(at) => &line[..at],
+        None => line,
+    }
+}
+
+/// Whether `statement` writes to the contract's storage on one line.
+fn is_storage_update(statement: &str) -> bool {
+    let on_self = statement.starts_with("self.") || statement.starts_with("*self.");
+    let writes = [".insert(", ".remove(", ".set(", " = ", " += ", " -= "]
+        .iter()
+        .any(|write| statement.contains(write));
+    on_self && writes && statement.ends_with(';')
+}
+
+/// Returns `source` with `mutant` applied.
+pub fn apply(source: &str, mutant: &Mutant) -> String {
+    let mut out = String::with_capacity(source.len());
+    for (index, line) in source.lines().enumerate() {
+        if index + 1 == mutant.line {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            out.push_str(indent);
+            if mutant.mutated.is_empty() {
+                out.push_str("// removed by mutate");
+            } else {
+                out.push_str(&mutant.mutated);
+            }
+        } else {
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// What the tests made of a mutant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The tests failed.
+    Killed,
+    /// The tests passed.
+    Survived,
+    /// The mutant did not compile.
+    Unviable,
+    /// The tests ran longer than allowed, which counts as killed.
+    TimedOut,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Outcome::Killed => "killed",
+            Outcome::Survived => "SURVIVED",
+            Outcome::Unviable => "unviable",
+            Outcome::TimedOut => "timed out",
+        })
+    }
+}
+
+/// A crate of its own that mutants of one contract are tested in.
+#[derive(Debug, Clone)]
+pub struct Scratch {
+    pub dir: PathBuf,
+}
+
+impl Scratch {
+    /// Sets up a scratch crate in `dir` from the manifest `template`, filling in
+    /// `{name}` and `{repo}`.
+    pub fn create(dir: &Path, template: &str, name: &str, repo: &Path) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let manifest = template
+            .replace("{name}", name)
+            .replace("{repo}", &repo.display().to_string());
+        fs::write(dir.join("Cargo.toml"), manifest)?;
+        Ok(Scratch {
+            dir: dir.to_owned(),
+        })
+    }
+
+    /// Makes `source` the crate's code.
+    pub fn write(&self, source: &str) -> io::Result<()> {
+        fs::write(self.dir.join("lib.rs"), source)
+    }
+
+    /// Builds the tests, then runs them with `timeout`.
+    pub fn test(&self, timeout: Duration) -> io::Result<Outcome> {
+        if !self.cargo(&["test", "--no-run"]).status()?.success() {
+            return Ok(Outcome::Unviable);
+        }
+        let mut child = self.cargo(&["test"]).spawn()?;
+        match child.wait_timeout(timeout)? {
+            Some(status) if status.success() => Ok(Outcome::Survived),
+            Some(_) => Ok(Outcome::Killed),
+            None => {
+                child.kill()?;
+                child.wait()?;
+                Ok(Outcome::TimedOut)
+            }
+        }
+    }
+
+    fn cargo(&self, args: &[&str]) -> Command {
+        let mut command = Command::new("cargo");
+        command
+            .args(args)
+            .arg("--quiet")
+            .arg("--manifest-path")
+            .arg(self.dir.join("Cargo.toml"))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        command
+    }
+}
+
+/// Runs the tests of the unmutated `source` in `scratch`, which must pass before
+/// any mutant is tried, and returns how long they took.
+pub fn baseline(scratch: &Scratch, source: &str) -> Result<Duration, String> {
+    let io_err = |err: io::Error| format!("{}: {}", scratch.dir.display(), err);
+    scratch.write(source).map_err(io_err)?;
+    let started = Instant::now();
+    match scratch
+        .test(Duration::from_secs(24 * 60 * 60))
+        .map_err(io_err)?
+    {
+        Outcome::Survived => Ok(started.elapsed()),
+        Outcome::Unviable => Err(format!(
+            "the unmutated contract does not compile; {}",
+            try_by_hand(&scratch.dir)
+        )),
+        _ => Err(format!(
+            "the tests of the unmutated contract fail; {}",
+            try_by_hand(&scratch.dir)
+        )),
+    }
+}
+
+fn try_by_hand(dir: &Path) -> String {
+    format!(
+        "see `cargo test --manifest-path {}`",
+        dir.join("Cargo.toml").display()
+    )
+}
+
+/// Tallies outcomes and lists the survivors.
+pub fn summary(results: &[(Mutant, Outcome)]) -> Vec<String> {
+    let count = |outcome| results.iter().filter(|(_, o)| *o == outcome).count();
+    let killed = count(Outcome::Killed) + count(Outcome::TimedOut);
+    let survived = count(Outcome::Survived);
+    let mut lines = vec![format!(
+        "{} mutant(s): {} killed, {} survived, {} unviable",
+        results.len(),
+        killed,
+        survived,
+        count(Outcome::Unviable)
+    )];
+    if let Some(score) = (killed * 100).checked_div(killed + survived) {
+        lines.push(format!(
+            "mutation score: {}% of viable mutants killed",
+            score
+        ));
+    }
+    if survived > 0 {
+        lines.push("survivors, bugs the tests would miss:".to_owned());
+        for (mutant, _) in results.iter().filter(|(_, o)| *o == Outcome::Survived) {
+            lines.push(format!("  {}", mutant));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = r#"
+#[ink::contract]
+mod token {
+    impl Token {
+        // Refuses a transfer over the balance, so value < balance is fine.
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            let from_balance = self.balance_of(from);
+            if from_balance < value {
+                return Err(Error::InsufficientBalance);
+            }
+            self.balances.insert(from, from_balance - value);
+            self.balances.insert(to, self.balance_of(to) + value);
+            Ok(())
+        }
+
+        pub fn balances(&self) -> Mapping<AccountId, Balance> {
+            self.balances.clone()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        #[ink::test]
+        fn transfer_works() {
+            assert!(1 < 2 && 3 - 1 == 2);
+        }
+    }
+}
+
+pub fn after_the_tests(a: u32) -> bool {
+    a >= 1
+}
+"#;
+
+    fn line_of(needle: &str) -> usize {
+        TOKEN
+            .lines()
+            .position(|line| line.contains(needle))
+            .unwrap()
+            + 1
+    }
+
+    #[test]
+    fn mutants_cover_the_built_in_operators() {
+        let mutants = mutants(TOKEN);
+        let found: Vec<(&str, usize, &str)> = mutants
+            .iter()
+            .map(|mutant| (mutant.operator, mutant.line, mutant.mutated.as_str()))
+            .collect();
+        let check = line_of("if from_balance");
+        let debit = line_of("from_balance - value");
+        let credit = line_of("+ value");
+        let after = line_of("a >= 1");
+        assert_eq!(
+            found,
+            [
+                ("relational", check, "if from_balance <= value {"),
+                (
+                    "arithmetic",
+                    debit,
+                    "self.balances.insert(from, from_balance + value);"
+                ),
+                ("drop-update", debit, ""),
+                (
+                    "arithmetic",
+                    credit,
+                    "self.balances.insert(to, self.balance_of(to) - value);"
+                ),
+                ("drop-update", credit, ""),
+                ("relational", after, "a > 1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn comments_generics_and_tests_are_left_alone() {
+        let lines: Vec<usize> = mutants(TOKEN).iter().map(|mutant| mutant.line).collect();
+        assert!(!lines.contains(&line_of("so value < balance")));
+        assert!(!lines.contains(&line_of("Mapping<AccountId, Balance>")));
+        assert!(!lines.contains(&line_of("1 < 2")));
+    }
+
+    #[test]
+    fn applying_changes_one_line() {
+        let mutant = &mutants(TOKEN)[0];
+        let mutated = apply(TOKEN, mutant);
+        assert!(mutated.contains("            if from_balance <= value {\n"));
+        let differing = TOKEN
+            .lines()
+            .zip(mutated.lines())
+            .filter(|(a, b)| a != b)
+            .count();
+        assert_eq!(differing, 1);
+
+        let dropped = mutants(TOKEN)
+            .into_iter()
+            .find(|mutant| mutant.operator == DROP_UPDATE)
+            .unwrap();
+        let mutated = apply(TOKEN, &dropped);
+        assert!(!mutated.contains("from_balance - value"));
+        assert!(mutated.contains("            // removed by mutate\n"));
+    }
+
+    #[test]
+    fn mutants_read_as_diffs() {
+        let mutant = &mutants(TOKEN)[0];
+        assert_eq!(
+            mutant.to_string(),
+            format!(
+                "line {} (relational): `if from_balance < value {{` -> `if from_balance <= value {{`",
+                mutant.line
+            )
+        );
+    }
+
+    #[test]
+    fn summaries_list_survivors() {
+        let mutants = mutants(TOKEN);
+        let results = vec![
+            (mutants[0].clone(), Outcome::Survived),
+            (mutants[1].clone(), Outcome::Killed),
+            (mutants[2].clone(), Outcome::TimedOut),
+            (mutants[3].clone(), Outcome::Unviable),
+        ];
+        let lines = summary(&results);
+        assert_eq!(lines[0], "4 mutant(s): 2 killed, 1 survived, 1 unviable");
+        assert_eq!(lines[1], "mutation score: 66% of viable mutants killed");
+        assert_eq!(lines[3], format!("  {}", mutants[0]));
+        assert_eq!(lines.len(), 4);
+
+        let lines = summary(&[(mutants[1].clone(), Outcome::Killed)]);
+        assert_eq!(lines[1], "mutation score: 100% of viable mutants killed");
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn scratch_manifests_are_filled_in() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("erc20");
+        let scratch =
+            Scratch::create(&dir, SCRATCH_MANIFEST, "erc20", Path::new("/work/repo")).unwrap();
+        let manifest = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"erc20\""));
+        assert!(manifest.contains("path = \"/work/repo/crates/utils\""));
+        assert!(!manifest.contains("{name}") && !manifest.contains("{repo}"));
+        scratch.write("pub fn f() {}\n").unwrap();
+        assert!(dir.join("lib.rs").is_file());
+    }
+}
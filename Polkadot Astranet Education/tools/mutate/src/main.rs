@@ -0,0 +1,117 @@
+// tools/mutate/src/main.rs
+//
+// Usage:
+//   mutate                                       mutate erc20.rs and run its tests
+//   mutate --list                                only list the mutants
+//   mutate --only relational --only drop-update  only some operators
+//   mutate --source examples/demo-contracts/flipper.rs --manifest my-scratch.toml
+//
+// The contract is copied into a scratch crate under `--scratch`; the original is never
+// touched. Its tests must pass before any mutant is tried. Exits with 1 if a mutant
+// survives.
+
+use clap::Parser;
+use mutate::{
+    apply, baseline, mutants, summary, Outcome, Scratch, DEFAULT_SOURCE, SCRATCH_MANIFEST,
+};
+use std::{fs, path::PathBuf, process::ExitCode, time::Duration};
+
+#[derive(Parser)]
+#[command(about = "Mutate a contract's source and report which mutants its tests miss")]
+struct Cli {
+    /// The contract to mutate.
+    #[arg(long, default_value = DEFAULT_SOURCE)]
+    source: PathBuf,
+    /// Manifest template of the scratch crate instead of the built-in one.
+    #[arg(long)]
+    manifest: Option<PathBuf>,
+    /// Directory of the scratch crate.
+    #[arg(long, default_value = "target/mutate")]
+    scratch: PathBuf,
+    /// Only apply these operators: relational, equality, arithmetic, logical or
+    /// drop-update.
+    #[arg(long)]
+    only: Vec<String>,
+    /// List the mutants without running any tests.
+    #[arg(long)]
+    list: bool,
+    /// Seconds a test run may take before the mutant counts as killed; by default
+    /// three times the unmutated run, and at least 30.
+    #[arg(long)]
+    timeout: Option<u64>,
+}
+
+fn main() -> ExitCode {
+    match run(&Cli::parse()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Returns whether every mutant was killed.
+fn run(cli: &Cli) -> Result<bool, String> {
+    let source = fs::read_to_string(&cli.source)
+        .map_err(|err| format!("{}: {}", cli.source.display(), err))?;
+    let mutants: Vec<_> = mutants(&source)
+        .into_iter()
+        .filter(|mutant| cli.only.is_empty() || cli.only.iter().any(|op| op == mutant.operator))
+        .collect();
+    if cli.list {
+        for mutant in &mutants {
+            println!("{}", mutant);
+        }
+        println!("{} mutant(s)", mutants.len());
+        return Ok(true);
+    }
+
+    let template = match &cli.manifest {
+        Some(path) => {
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?
+        }
+        None => SCRATCH_MANIFEST.to_owned(),
+    };
+    let name = cli
+        .source
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("contract");
+    let repo = fs::canonicalize(".").map_err(|err| err.to_string())?;
+    let scratch = Scratch::create(&cli.scratch.join(name), &template, name, &repo)
+        .map_err(|err| format!("{}: {}", cli.scratch.display(), err))?;
+    let io_err = |err: std::io::Error| format!("{}: {}", scratch.dir.display(), err);
+
+    println!("testing unmutated {}", cli.source.display());
+    let elapsed = baseline(&scratch, &source)?;
+    let timeout = match cli.timeout {
+        Some(seconds) => Duration::from_secs(seconds),
+        None => (elapsed * 3).max(Duration::from_secs(30)),
+    };
+
+    let mut results = Vec::new();
+    for (number, mutant) in mutants.iter().enumerate() {
+        scratch.write(&apply(&source, mutant)).map_err(io_err)?;
+        let outcome = scratch.test(timeout).map_err(io_err)?;
+        println!(
+            "[{}/{}] {:<9} {}",
+            number + 1,
+            mutants.len(),
+            outcome,
+            mutant
+        );
+        results.push((mutant.clone(), outcome));
+    }
+    // Leave the scratch crate as the contract was, to rerun its tests by hand.
+    scratch.write(&source).map_err(io_err)?;
+
+    println!();
+    for line in summary(&results) {
+        println!("{}", line);
+    }
+    Ok(results
+        .iter()
+        .all(|(_, outcome)| *outcome != Outcome::Survived))
+}
@@ -0,0 +1,22 @@
+// tools/mutate/tests/default_source.rs
+//
+// Runs the baseline step on the default `--source` with the built-in manifest, so a
+// bare `mutate` is known to get past it. This builds an ink! contract and its
+// dependencies, so it takes a while.
+
+use mutate::{baseline, Scratch, DEFAULT_SOURCE, SCRATCH_MANIFEST};
+use std::{fs, path::Path};
+
+#[test]
+fn default_source_passes_its_baseline() {
+    let repo = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../.."))
+        .canonicalize()
+        .unwrap();
+    let source = fs::read_to_string(repo.join(DEFAULT_SOURCE)).unwrap();
+    let temp = tempfile::tempdir().unwrap();
+    let scratch =
+        Scratch::create(&temp.path().join("erc20"), SCRATCH_MANIFEST, "erc20", &repo).unwrap();
+    if let Err(err) = baseline(&scratch, &source) {
+        panic!("{}", err);
+    }
+}
@@ -0,0 +1,83 @@
+// tools/mutate/tests/harness.rs
+//
+// Runs mutants through real `cargo test` runs, in a scratch crate without
+// dependencies, to check that outcomes are told apart.
+
+use mutate::{apply, mutants, Outcome, Scratch};
+use std::{path::Path, time::Duration};
+
+const MANIFEST: &str = r#"
+[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+
+[lib]
+path = "lib.rs"
+
+[workspace]
+"#;
+
+/// A withdrawal check whose tests may or may not probe the boundary.
+fn vault(tests: &str) -> String {
+    format!(
+        "pub fn can_withdraw(balance: u64, amount: u64) -> bool {{\n    \
+             amount <= balance\n\
+         }}\n\
+         \n\
+         #[cfg(test)]\n\
+         mod tests {{\n    \
+             use super::can_withdraw;\n\
+             \n    \
+             #[test]\n    \
+             fn withdrawals() {{\n{}    }}\n\
+         }}\n",
+        tests
+    )
+}
+
+fn outcome(source: &str) -> Outcome {
+    let temp = tempfile::tempdir().unwrap();
+    let scratch = Scratch::create(
+        &temp.path().join("vault"),
+        MANIFEST,
+        "vault",
+        Path::new("/"),
+    )
+    .unwrap();
+    let mutants = mutants(source);
+    assert_eq!(mutants.len(), 1);
+    assert_eq!(mutants[0].mutated, "amount < balance");
+    scratch.write(&apply(source, &mutants[0])).unwrap();
+    scratch.test(Duration::from_secs(300)).unwrap()
+}
+
+#[test]
+fn boundary_mutants_survive_tests_that_skip_the_boundary() {
+    let weak =
+        vault("        assert!(can_withdraw(10, 5));\n        assert!(!can_withdraw(10, 11));\n");
+    assert_eq!(outcome(&weak), Outcome::Survived);
+}
+
+#[test]
+fn boundary_tests_kill_boundary_mutants() {
+    let strong = vault("        assert!(can_withdraw(10, 10));\n");
+    assert_eq!(outcome(&strong), Outcome::Killed);
+}
+
+#[test]
+fn mutants_that_do_not_compile_are_unviable() {
+    let temp = tempfile::tempdir().unwrap();
+    let scratch = Scratch::create(
+        &temp.path().join("vault"),
+        MANIFEST,
+        "vault",
+        Path::new("/"),
+    )
+    .unwrap();
+    scratch.write("pub fn f() -> u32 { true }\n").unwrap();
+    assert_eq!(
+        scratch.test(Duration::from_secs(300)).unwrap(),
+        Outcome::Unviable
+    );
+}
@@ -0,0 +1,332 @@
+// tools/new-exercise/src/lib.rs
+//
+// Exercises are ink! 5 contract crates under `exercises/`, one per exercise, all laid
+// out the same way:
+//
+//     exercises/NAME/
+//     ├── Cargo.toml
+//     ├── exercise.toml      title, difficulty and summary
+//     ├── hints.toml         hints, revealed one at a time by the grader
+//     ├── src/lib.rs         the skeleton the learner completes, marked with TODOs
+//     └── tests/grading.rs   the tests the exercise is graded by
+//
+// The grading tests are integration tests, so they see the contract only through its
+// public API, as a caller would, and a learner can restructure `src/lib.rs` freely.
+// Each exercise's reference solution, a complete `src/lib.rs`, is kept apart from the
+// crate learners work in, in `exercises/solutions/NAME.rs`. This crate writes that
+// layout from the templates in `template/`, for a contributor to fill in.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// File name of an exercise's metadata.
+pub const METADATA_FILE: &str = "exercise.toml";
+
+/// File name of an exercise's hints.
+pub const HINTS_FILE: &str = "hints.toml";
+
+/// Directory of the reference solutions, in the exercises directory.
+pub const SOLUTIONS_DIR: &str = "solutions";
+
+/// The template of a reference solution.
+const SOLUTION_TEMPLATE: &str = include_str!("../template/solution.rs.tmpl");
+
+/// The generated files, by path inside the exercise crate.
+const TEMPLATES: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../template/Cargo.toml.tmpl")),
+    (
+        METADATA_FILE,
+        include_str!("../template/exercise.toml.tmpl"),
+    ),
+    (HINTS_FILE, include_str!("../template/hints.toml.tmpl")),
+    ("src/lib.rs", include_str!("../template/lib.rs.tmpl")),
+    (
+        "tests/grading.rs",
+        include_str!("../template/grading.rs.tmpl"),
+    ),
+];
+
+/// Words that cannot name a module.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "do", "dyn", "else",
+    "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self",
+    "static", "struct", "super", "trait", "true", "try", "type", "typeof", "unsafe", "unsized",
+    "use", "virtual", "where", "while", "yield",
+];
+
+/// How hard an exercise is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Advanced,
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "beginner" => Ok(Difficulty::Beginner),
+            "intermediate" => Ok(Difficulty::Intermediate),
+            "advanced" => Ok(Difficulty::Advanced),
+            other => Err(format!(
+                "unknown difficulty `{}`; expected beginner, intermediate or advanced",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Difficulty::Beginner => "beginner",
+            Difficulty::Intermediate => "intermediate",
+            Difficulty::Advanced => "advanced",
+        })
+    }
+}
+
+/// The metadata of an exercise, from its `exercise.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Exercise {
+    pub title: String,
+    pub difficulty: Difficulty,
+    pub summary: String,
+}
+
+impl Exercise {
+    /// Reads metadata in the format of `exercise.toml`.
+    pub fn parse(toml: &str) -> Result<Self, String> {
+        toml::from_str(toml).map_err(|err| err.message().to_owned())
+    }
+
+    /// Reads the metadata of the exercise crate in `dir`.
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let path = dir.join(METADATA_FILE);
+        let toml =
+            fs::read_to_string(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        Self::parse(&toml).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+}
+
+/// The hints of an exercise, from its `hints.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Hints {
+    /// From a nudge to nearly the answer; the grader reveals them in order.
+    #[serde(default)]
+    pub hints: Vec<String>,
+}
+
+impl Hints {
+    /// Reads hints in the format of `hints.toml`.
+    pub fn parse(toml: &str) -> Result<Self, String> {
+        toml::from_str(toml).map_err(|err| err.message().to_owned())
+    }
+
+    /// Reads the hints of the exercise crate in `dir`; an exercise without a
+    /// `hints.toml` has none.
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let path = dir.join(HINTS_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let toml =
+            fs::read_to_string(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        Self::parse(&toml).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+}
+
+/// Checks that `name` can name the exercise's crate and contract module: lowercase
+/// snake case, starting with a letter, and not a keyword.
+pub fn check_name(name: &str) -> Result<(), String> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && !name.ends_with('_')
+        && !name.contains("__");
+    if !valid {
+        return Err(format!(
+            "`{}` is not a valid exercise name; use lowercase snake case, such as `storage_mapping`",
+            name
+        ));
+    }
+    if KEYWORDS.contains(&name) {
+        return Err(format!("`{}` is a Rust keyword", name));
+    }
+    Ok(())
+}
+
+/// The name of the contract type of exercise `name`: `storage_mapping` gives
+/// `StorageMapping`.
+pub fn contract_name(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// The reference solution of exercise `name` in `exercises_dir`.
+pub fn solution_path(exercises_dir: &Path, name: &str) -> PathBuf {
+    exercises_dir
+        .join(SOLUTIONS_DIR)
+        .join(name)
+        .with_extension("rs")
+}
+
+/// Writes a new exercise crate `name` into `exercises_dir`, and its reference
+/// solution, returning the files written. Refuses to touch an existing directory or
+/// solution.
+pub fn generate(
+    exercises_dir: &Path,
+    name: &str,
+    title: &str,
+    difficulty: Difficulty,
+) -> Result<Vec<PathBuf>, String> {
+    check_name(name)?;
+    let dir = exercises_dir.join(name);
+    let solution = solution_path(exercises_dir, name);
+    for path in [&dir, &solution] {
+        if path.exists() {
+            return Err(format!("{} already exists", path.display()));
+        }
+    }
+    let title_toml = toml::Value::String(title.to_owned()).to_string();
+    let contract = contract_name(name);
+    let difficulty = difficulty.to_string();
+
+    let files = TEMPLATES
+        .iter()
+        .map(|(path, template)| (dir.join(path), template))
+        .chain([(solution, &SOLUTION_TEMPLATE)]);
+    let mut written = Vec::new();
+    for (path, template) in files {
+        let content = template
+            .replace("{{name}}", name)
+            .replace("{{Contract}}", &contract)
+            .replace("{{title}}", title)
+            .replace("{{title_toml}}", &title_toml)
+            .replace("{{difficulty}}", &difficulty);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| format!("{}: {}", parent.display(), err))?;
+        }
+        fs::write(&path, content).map_err(|err| format!("{}: {}", path.display(), err))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exercises_are_generated_from_the_templates() {
+        let temp = tempfile::tempdir().unwrap();
+        let written = generate(
+            temp.path(),
+            "storage_mapping",
+            "Balances in a \"Mapping\"",
+            Difficulty::Beginner,
+        )
+        .unwrap();
+        assert_eq!(written.len(), TEMPLATES.len() + 1);
+
+        let dir = temp.path().join("storage_mapping");
+        let exercise = Exercise::load(&dir).unwrap();
+        assert_eq!(exercise.title, "Balances in a \"Mapping\"");
+        assert_eq!(exercise.difficulty, Difficulty::Beginner);
+        assert_eq!(Hints::load(&dir).unwrap().hints.len(), 2);
+
+        let lib = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+        assert!(lib.starts_with("// exercises/storage_mapping/src/lib.rs\n"));
+        assert!(lib.contains("pub mod storage_mapping {"));
+        assert!(lib.contains("pub struct StorageMapping {"));
+        assert!(lib.contains("TODO"));
+        let grading = fs::read_to_string(dir.join("tests/grading.rs")).unwrap();
+        assert!(grading.contains("use storage_mapping::storage_mapping::StorageMapping;"));
+        let manifest = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"storage_mapping\""));
+        let solution =
+            fs::read_to_string(temp.path().join("solutions/storage_mapping.rs")).unwrap();
+        assert!(solution.contains("pub mod storage_mapping {"));
+
+        for path in written {
+            let content = fs::read_to_string(&path).unwrap();
+            assert!(!content.contains("{{"), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn existing_exercises_are_not_overwritten() {
+        let temp = tempfile::tempdir().unwrap();
+        generate(temp.path(), "flipper", "Flipper", Difficulty::Beginner).unwrap();
+        let err = generate(temp.path(), "flipper", "Flipper", Difficulty::Advanced).unwrap_err();
+        assert!(err.ends_with("flipper already exists"), "{}", err);
+
+        fs::remove_dir_all(temp.path().join("flipper")).unwrap();
+        let err = generate(temp.path(), "flipper", "Flipper", Difficulty::Advanced).unwrap_err();
+        assert!(err.ends_with("flipper.rs already exists"), "{}", err);
+        assert!(!temp.path().join("flipper").exists());
+    }
+
+    #[test]
+    fn names_must_be_snake_case_identifiers() {
+        assert!(check_name("storage_mapping").is_ok());
+        assert!(check_name("vec_pitfalls_2").is_ok());
+        for name in [
+            "",
+            "Storage",
+            "2fast",
+            "storage-mapping",
+            "a__b",
+            "trailing_",
+            "_x",
+        ] {
+            assert!(check_name(name).is_err(), "{}", name);
+        }
+        assert_eq!(check_name("move").unwrap_err(), "`move` is a Rust keyword");
+    }
+
+    #[test]
+    fn contract_names_are_camel_case() {
+        assert_eq!(contract_name("storage_mapping"), "StorageMapping");
+        assert_eq!(contract_name("vec_pitfalls_2"), "VecPitfalls2");
+        assert_eq!(contract_name("flipper"), "Flipper");
+    }
+
+    #[test]
+    fn metadata_is_checked() {
+        assert!(
+            Exercise::parse("title = \"t\"\ndifficulty = \"hard\"\nsummary = \"s\"\n").is_err()
+        );
+        assert!(Exercise::parse("title = \"t\"\ndifficulty = \"advanced\"\n").is_err());
+        assert!(Exercise::parse(
+            "title = \"t\"\ndifficulty = \"advanced\"\nsummary = \"s\"\nhints = []\n"
+        )
+        .is_err());
+        assert_eq!("intermediate".parse(), Ok(Difficulty::Intermediate));
+    }
+
+    #[test]
+    fn exercises_without_hints_have_none() {
+        let temp = tempfile::tempdir().unwrap();
+        assert_eq!(Hints::load(temp.path()).unwrap(), Hints::default());
+        assert!(Hints::parse("hint = \"singular\"\n").is_err());
+    }
+}
@@ -0,0 +1,48 @@
+// tools/new-exercise/src/main.rs
+//
+// Usage:
+//   new-exercise storage_mapping --title "Balances in a Mapping" --difficulty beginner
+//   new-exercise reentrancy --title "..." --difficulty advanced --dir path/to/exercises
+//
+// Writes the crate of a new exercise and its reference solution, with TODOs marking
+// what to fill in.
+
+use clap::Parser;
+use new_exercise::{generate, Difficulty};
+use std::{path::PathBuf, process::ExitCode};
+
+#[derive(Parser)]
+#[command(about = "Generate an exercise crate from the template")]
+struct Cli {
+    /// Name of the exercise, its crate and its contract module, in snake case.
+    name: String,
+    /// Title shown to learners.
+    #[arg(long)]
+    title: String,
+    /// beginner, intermediate or advanced.
+    #[arg(long)]
+    difficulty: Difficulty,
+    /// Directory of the exercise crates.
+    #[arg(long, default_value = "exercises")]
+    dir: PathBuf,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match generate(&cli.dir, &cli.name, &cli.title, cli.difficulty) {
+        Ok(written) => {
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+            println!(
+                "next: describe the task in src/lib.rs, write tests/grading.rs and the solution, \
+                 and fill in exercise.toml and hints.toml"
+            );
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,529 @@
+// tools/repl/src/keystore.rs
+//
+// Keys stored on disk, encrypted with a passphrase, so that learners sign with a key
+// name instead of pasting a seed phrase into their shell. The repl and the deployer
+// both read this keystore; the deployer's `keys` command fills it.
+//
+// A key file holds the key's secret URI (its mnemonic, with any derivation path)
+// encrypted with ChaCha20 under a key stretched from the passphrase with
+// PBKDF2-HMAC-SHA256, and an HMAC-SHA256 tag over everything else in the file. A wrong
+// passphrase or an edited file fails the tag check before anything is decrypted. The
+// account id is stored in the clear, so keys can be listed without unlocking them.
+
+use crate::value::{parse_hex, to_hex};
+use chacha20::{
+    cipher::{KeyIvInit, StreamCipher},
+    ChaCha20,
+};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::{
+    env, fmt, fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+};
+use subxt::{config::substrate::MultiSignature, tx::Signer, utils::AccountId32, SubstrateConfig};
+use subxt_signer::{bip39::Mnemonic, ecdsa, sr25519, SecretUri};
+use zeroize::Zeroizing;
+
+/// The environment variable naming the keystore directory.
+pub const KEYSTORE_ENV: &str = "ASTRANET_KEYSTORE";
+
+/// The environment variable the passphrase is read from before prompting for it.
+pub const PASSPHRASE_ENV: &str = "ASTRANET_PASSPHRASE";
+
+/// PBKDF2 rounds for new key files. Slow on purpose: each guess at a passphrase costs
+/// this many HMAC computations.
+pub const DEFAULT_ITERATIONS: u32 = 600_000;
+
+const FORMAT_VERSION: u32 = 1;
+
+/// The signature scheme of a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scheme {
+    /// Schnorr signatures on Ristretto, what Substrate accounts use by default.
+    Sr25519,
+    /// ECDSA on secp256k1, as Ethereum uses. The account id is the BLAKE2 hash of the
+    /// compressed public key.
+    Ecdsa,
+}
+
+impl FromStr for Scheme {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, String> {
+        match input {
+            "sr25519" => Ok(Scheme::Sr25519),
+            "ecdsa" => Ok(Scheme::Ecdsa),
+            other => Err(format!("unknown scheme `{}`; use sr25519 or ecdsa", other)),
+        }
+    }
+}
+
+impl fmt::Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Scheme::Sr25519 => "sr25519",
+            Scheme::Ecdsa => "ecdsa",
+        })
+    }
+}
+
+/// A key pair of either scheme, which signs transactions for subxt.
+#[derive(Clone)]
+pub enum KeyPair {
+    Sr25519(sr25519::Keypair),
+    Ecdsa(ecdsa::Keypair),
+}
+
+impl KeyPair {
+    /// Derives the key pair of `scheme` from a secret URI such as `//Alice` or a
+    /// mnemonic with an optional derivation path.
+    pub fn from_suri(scheme: Scheme, suri: &str) -> Result<Self, String> {
+        let uri = SecretUri::from_str(suri).map_err(|err| err.to_string())?;
+        match scheme {
+            Scheme::Sr25519 => sr25519::Keypair::from_uri(&uri)
+                .map(KeyPair::Sr25519)
+                .map_err(|err| err.to_string()),
+            Scheme::Ecdsa => ecdsa::Keypair::from_uri(&uri)
+                .map(KeyPair::Ecdsa)
+                .map_err(|err| err.to_string()),
+        }
+    }
+
+    pub fn scheme(&self) -> Scheme {
+        match self {
+            KeyPair::Sr25519(_) => Scheme::Sr25519,
+            KeyPair::Ecdsa(_) => Scheme::Ecdsa,
+        }
+    }
+
+    /// The account id transactions signed with this key are made from.
+    pub fn account_id(&self) -> [u8; 32] {
+        match self {
+            KeyPair::Sr25519(pair) => pair.public_key().0,
+            KeyPair::Ecdsa(pair) => pair.public_key().to_account_id().0,
+        }
+    }
+}
+
+impl Signer<SubstrateConfig> for KeyPair {
+    fn account_id(&self) -> AccountId32 {
+        AccountId32(KeyPair::account_id(self))
+    }
+
+    fn address(&self) -> <SubstrateConfig as subxt::Config>::Address {
+        AccountId32(KeyPair::account_id(self)).into()
+    }
+
+    fn sign(&self, payload: &[u8]) -> MultiSignature {
+        match self {
+            KeyPair::Sr25519(pair) => pair.sign(payload).into(),
+            KeyPair::Ecdsa(pair) => pair.sign(payload).into(),
+        }
+    }
+}
+
+/// Returns a new 12- or 24-word mnemonic from the operating system's randomness.
+pub fn generate_phrase(words: usize) -> Result<String, String> {
+    let mut entropy = Zeroizing::new(match words {
+        12 => vec![0; 16],
+        24 => vec![0; 32],
+        _ => return Err(format!("a mnemonic has 12 or 24 words, not {}", words)),
+    });
+    getrandom::getrandom(&mut entropy).map_err(|err| err.to_string())?;
+    Mnemonic::from_entropy(&entropy)
+        .map(|mnemonic| mnemonic.to_string())
+        .map_err(|err| err.to_string())
+}
+
+/// A key encrypted with a passphrase, as stored in the keystore.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeyFile {
+    pub version: u32,
+    pub scheme: Scheme,
+    /// The account id in hex.
+    pub account: String,
+    pub iterations: u32,
+    pub salt: String,
+    pub nonce: String,
+    /// The encrypted secret URI.
+    pub ciphertext: String,
+    /// HMAC-SHA256 over the fields above.
+    pub mac: String,
+}
+
+impl KeyFile {
+    /// Encrypts the secret URI of a `scheme` key with `passphrase`.
+    pub fn encrypt(scheme: Scheme, suri: &str, passphrase: &str) -> Result<Self, String> {
+        Self::encrypt_with(scheme, suri, passphrase, DEFAULT_ITERATIONS)
+    }
+
+    /// Encrypts as `encrypt` does, stretching the passphrase with `iterations` rounds.
+    pub fn encrypt_with(
+        scheme: Scheme,
+        suri: &str,
+        passphrase: &str,
+        iterations: u32,
+    ) -> Result<Self, String> {
+        let account = KeyPair::from_suri(scheme, suri)?.account_id();
+        let mut salt = [0; 16];
+        let mut nonce = [0; 12];
+        getrandom::getrandom(&mut salt).map_err(|err| err.to_string())?;
+        getrandom::getrandom(&mut nonce).map_err(|err| err.to_string())?;
+
+        let keys = stretch(passphrase, &salt, iterations);
+        let mut ciphertext = suri.as_bytes().to_vec();
+        ChaCha20::new(keys[..32].into(), &nonce.into()).apply_keystream(&mut ciphertext);
+        let mut file = KeyFile {
+            version: FORMAT_VERSION,
+            scheme,
+            account: to_hex(&account),
+            iterations,
+            salt: to_hex(&salt),
+            nonce: to_hex(&nonce),
+            ciphertext: to_hex(&ciphertext),
+            mac: String::new(),
+        };
+        file.mac = to_hex(&file.tag(&keys[32..]).finalize().into_bytes());
+        Ok(file)
+    }
+
+    /// Checks `passphrase` against the file and returns the secret URI.
+    pub fn decrypt(&self, passphrase: &str) -> Result<Zeroizing<String>, String> {
+        if self.version != FORMAT_VERSION {
+            return Err(format!("unsupported key file version {}", self.version));
+        }
+        let salt = parse_hex(&self.salt)?;
+        let nonce = <[u8; 12]>::try_from(parse_hex(&self.nonce)?)
+            .map_err(|_| "the nonce is not 12 bytes".to_owned())?;
+        let keys = stretch(passphrase, &salt, self.iterations);
+        self.tag(&keys[32..])
+            .verify_slice(&parse_hex(&self.mac)?)
+            .map_err(|_| "wrong passphrase, or the key file was changed".to_owned())?;
+
+        let mut plaintext = Zeroizing::new(parse_hex(&self.ciphertext)?);
+        ChaCha20::new(keys[..32].into(), &nonce.into()).apply_keystream(&mut plaintext);
+        String::from_utf8(plaintext.to_vec())
+            .map(Zeroizing::new)
+            .map_err(|_| "the decrypted key is not text".to_owned())
+    }
+
+    /// Decrypts the file and derives its key pair.
+    pub fn unlock(&self, passphrase: &str) -> Result<KeyPair, String> {
+        let pair = KeyPair::from_suri(self.scheme, &self.decrypt(passphrase)?)?;
+        if to_hex(&pair.account_id()) != self.account {
+            return Err("the key does not match the account it was stored for".to_owned());
+        }
+        Ok(pair)
+    }
+
+    /// The account id of the key.
+    pub fn account_id(&self) -> Result<[u8; 32], String> {
+        <[u8; 32]>::try_from(parse_hex(&self.account)?)
+            .map_err(|_| "the account id is not 32 bytes".to_owned())
+    }
+
+    /// Starts the MAC over every field but the MAC itself.
+    fn tag(&self, mac_key: &[u8]) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(mac_key).expect("HMAC takes any key size");
+        for field in [
+            &self.version.to_string(),
+            &self.scheme.to_string(),
+            &self.account,
+            &self.iterations.to_string(),
+            &self.salt,
+            &self.nonce,
+            &self.ciphertext,
+        ] {
+            mac.update(field.as_bytes());
+            mac.update(b"\n");
+        }
+        mac
+    }
+}
+
+/// Stretches a passphrase into a 32-byte encryption key followed by a 32-byte MAC key.
+fn stretch(passphrase: &str, salt: &[u8], iterations: u32) -> Zeroizing<[u8; 64]> {
+    let mut keys = Zeroizing::new([0; 64]);
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, &mut *keys);
+    keys
+}
+
+/// A directory of key files, one `NAME.json` per key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keystore {
+    pub dir: PathBuf,
+}
+
+impl Keystore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Keystore { dir: dir.into() }
+    }
+
+    /// The keystore named by `ASTRANET_KEYSTORE`, or `~/.astranet/keys`.
+    pub fn default_dir() -> Result<PathBuf, String> {
+        if let Ok(dir) = env::var(KEYSTORE_ENV) {
+            return Ok(PathBuf::from(dir));
+        }
+        env::var("HOME")
+            .map(|home| Path::new(&home).join(".astranet").join("keys"))
+            .map_err(|_| format!("set {} to the keystore directory", KEYSTORE_ENV))
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf, String> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(format!(
+                "`{}` is not a key name; use letters, digits, `-` and `_`",
+                name
+            ));
+        }
+        Ok(self.dir.join(format!("{}.json", name)))
+    }
+
+    /// Whether a key named `name` is stored.
+    pub fn contains(&self, name: &str) -> Result<bool, String> {
+        Ok(self.path(name)?.exists())
+    }
+
+    /// Stores `file` as `name`, refusing to replace an existing key.
+    ///
+    /// On Unix the file is created readable by its owner only, so the key is never
+    /// on disk with wider permissions, not even briefly.
+    pub fn save(&self, name: &str, file: &KeyFile) -> Result<PathBuf, String> {
+        let path = self.path(name)?;
+        fs::create_dir_all(&self.dir).map_err(|err| format!("{}: {}", self.dir.display(), err))?;
+        let json = serde_json::to_string_pretty(file).map_err(|err| err.to_string())?;
+        let mut options = fs::OpenOptions::new();
+        options.create_new(true).write(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut out = options.open(&path).map_err(|err| match err.kind() {
+            io::ErrorKind::AlreadyExists => format!("a key named `{}` already exists", name),
+            _ => format!("{}: {}", path.display(), err),
+        })?;
+        out.write_all((json + "\n").as_bytes())
+            .map_err(|err| format!("{}: {}", path.display(), err))?;
+        Ok(path)
+    }
+
+    pub fn load(&self, name: &str) -> Result<KeyFile, String> {
+        let path = self.path(name)?;
+        let json = fs::read_to_string(&path).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => format!(
+                "no key named `{}` in {}; create one with `deployer keys generate`",
+                name,
+                self.dir.display()
+            ),
+            _ => format!("{}: {}", path.display(), err),
+        })?;
+        serde_json::from_str(&json).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// Returns the stored keys by name, in order.
+    pub fn list(&self) -> Result<Vec<(String, KeyFile)>, String> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(format!("{}: {}", self.dir.display(), err)),
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                name.strip_suffix(".json").map(str::to_owned)
+            })
+            .collect();
+        names.sort();
+        names
+            .into_iter()
+            .map(|name| self.load(&name).map(|file| (name, file)))
+            .collect()
+    }
+
+    /// Loads the key `name` and unlocks it with the passphrase from
+    /// `ASTRANET_PASSPHRASE` or the terminal.
+    pub fn unlock(&self, name: &str) -> Result<KeyPair, String> {
+        let file = self.load(name)?;
+        let passphrase = read_passphrase(&format!("passphrase for `{}`: ", name))?;
+        file.unlock(&passphrase)
+            .map_err(|err| format!("key `{}`: {}", name, err))
+    }
+}
+
+/// Reads a passphrase from `ASTRANET_PASSPHRASE`, or prompts for it without echoing
+/// it to the terminal.
+pub fn read_passphrase(text: &str) -> Result<Zeroizing<String>, String> {
+    if let Ok(passphrase) = env::var(PASSPHRASE_ENV) {
+        return Ok(Zeroizing::new(passphrase));
+    }
+    read_hidden(text)
+}
+
+/// Prints `text` and reads one line from the terminal with echo turned off.
+pub fn read_hidden(text: &str) -> Result<Zeroizing<String>, String> {
+    print!("{}", text);
+    io::stdout().flush().map_err(|err| err.to_string())?;
+    // Not a terminal, or not a Unix one: the line is read with echo left on.
+    let hidden = stty("-echo");
+    let mut line = Zeroizing::new(String::new());
+    let read = io::stdin().lock().read_line(&mut line);
+    if hidden {
+        stty("echo");
+        println!();
+    }
+    read.map_err(|err| err.to_string())?;
+    Ok(Zeroizing::new(
+        line.trim_end_matches(['\r', '\n']).to_owned(),
+    ))
+}
+
+fn stty(setting: &str) -> bool {
+    Command::new("stty")
+        .arg(setting)
+        .stdin(std::process::Stdio::inherit())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str = "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+
+    fn encrypt(scheme: Scheme, suri: &str, passphrase: &str) -> KeyFile {
+        // Few rounds keep the tests fast; the format is the same.
+        KeyFile::encrypt_with(scheme, suri, passphrase, 1_000).unwrap()
+    }
+
+    #[test]
+    fn keys_round_trip_through_encryption() {
+        for scheme in [Scheme::Sr25519, Scheme::Ecdsa] {
+            let suri = format!("{}//astranet", PHRASE);
+            let file = encrypt(scheme, &suri, "correct horse");
+            assert!(!file.ciphertext.contains("bottom"));
+            assert_eq!(*file.decrypt("correct horse").unwrap(), suri);
+
+            let pair = file.unlock("correct horse").unwrap();
+            assert_eq!(pair.scheme(), scheme);
+            assert_eq!(
+                pair.account_id(),
+                KeyPair::from_suri(scheme, &suri).unwrap().account_id()
+            );
+            assert_eq!(file.account_id().unwrap(), pair.account_id());
+        }
+    }
+
+    #[test]
+    fn encryption_is_salted() {
+        let first = encrypt(Scheme::Sr25519, PHRASE, "pw");
+        let second = encrypt(Scheme::Sr25519, PHRASE, "pw");
+        assert_eq!(first.account, second.account);
+        assert_ne!(first.salt, second.salt);
+        assert_ne!(first.ciphertext, second.ciphertext);
+    }
+
+    #[test]
+    fn wrong_passphrases_and_edits_are_detected() {
+        let file = encrypt(Scheme::Sr25519, PHRASE, "right");
+        assert!(file
+            .decrypt("wrong")
+            .unwrap_err()
+            .contains("wrong passphrase"));
+
+        let mut edited = file.clone();
+        let mut ciphertext = parse_hex(&edited.ciphertext).unwrap();
+        ciphertext[0] ^= 1;
+        edited.ciphertext = to_hex(&ciphertext);
+        assert!(edited.decrypt("right").is_err());
+
+        let mut edited = file.clone();
+        edited.scheme = Scheme::Ecdsa;
+        assert!(edited.decrypt("right").is_err());
+
+        let mut edited = file;
+        edited.iterations = 1;
+        assert!(edited.decrypt("right").is_err());
+    }
+
+    #[test]
+    fn schemes_derive_different_accounts() {
+        let sr25519 = KeyPair::from_suri(Scheme::Sr25519, "//Alice").unwrap();
+        assert_eq!(
+            to_hex(&sr25519.account_id()),
+            "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+        );
+        // `5C7C2Z5sWbytvHpuLTvzKunnnRwQxft1jiqrLD5rhucQ5S9X`, Alice's ECDSA account.
+        let ecdsa = KeyPair::from_suri(Scheme::Ecdsa, "//Alice").unwrap();
+        assert_eq!(
+            to_hex(&ecdsa.account_id()),
+            "0x01e552298e47454041ea31273b4b630c64c104e4514aa3643490b8aaca9cf8ed"
+        );
+    }
+
+    #[test]
+    fn generated_phrases_are_valid_mnemonics() {
+        let phrase = generate_phrase(12).unwrap();
+        assert_eq!(phrase.split(' ').count(), 12);
+        assert_ne!(phrase, generate_phrase(12).unwrap());
+        assert_eq!(generate_phrase(24).unwrap().split(' ').count(), 24);
+        assert!(generate_phrase(13).is_err());
+        assert!(KeyPair::from_suri(Scheme::Ecdsa, &phrase).is_ok());
+    }
+
+    #[test]
+    fn keystores_save_list_and_load_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let keystore = Keystore::new(dir.path().join("keys"));
+        assert!(keystore.list().unwrap().is_empty());
+
+        let file = encrypt(Scheme::Sr25519, PHRASE, "pw");
+        let path = keystore.save("learner", &file).unwrap();
+        assert!(keystore.contains("learner").unwrap());
+        assert!(keystore.save("learner", &file).is_err());
+        keystore
+            .save("ethereum", &encrypt(Scheme::Ecdsa, PHRASE, "pw"))
+            .unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        assert_eq!(keystore.load("learner").unwrap(), file);
+        let names: Vec<String> = keystore
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, ["ethereum", "learner"]);
+        assert!(keystore
+            .load("missing")
+            .unwrap_err()
+            .contains("no key named"));
+        assert!(keystore.save("../escape", &file).is_err());
+    }
+
+    #[test]
+    fn schemes_parse() {
+        assert_eq!("ecdsa".parse::<Scheme>().unwrap(), Scheme::Ecdsa);
+        assert_eq!(Scheme::Sr25519.to_string(), "sr25519");
+        assert!("ed25519".parse::<Scheme>().is_err());
+    }
+}
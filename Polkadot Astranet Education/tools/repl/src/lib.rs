@@ -0,0 +1,24 @@
+// tools/repl/src/lib.rs
+//
+// Everything the REPL needs to know about a contract comes from its metadata: the
+// messages, their selectors, and the types of their arguments and return values. This
+// crate reads that metadata and converts between what a learner types at the prompt
+// and SCALE bytes, leaving the node connection to the binary.
+//
+// Only simple types can be typed in directly (booleans, integers, strings, byte
+// strings, account ids and other fixed-size byte arrays). Anything else, such as an
+// enum or a struct argument, is entered as its SCALE encoding in hex, which the
+// codec-playground crate shows how to build. Output is richer: return values and
+// events are printed with the metadata's full type registry, see `transcode`.
+
+pub mod keystore;
+pub mod metadata;
+pub mod report;
+pub mod transcode;
+pub mod value;
+
+pub use keystore::{KeyFile, KeyPair, Keystore, Scheme};
+pub use metadata::{parse, Arg, ContractMetadata, Event, EventArg, Message, MetadataError};
+pub use report::DryRun;
+pub use transcode::Emitted;
+pub use value::{decode, encode, ValueType};
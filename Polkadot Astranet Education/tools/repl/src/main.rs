@@ -0,0 +1,372 @@
+// tools/repl/src/main.rs
+//
+// Usage:
+//   repl --metadata target/ink/flipper.contract --contract 5F...
+//   repl --metadata erc20.contract --contract 5F... --url ws://127.0.0.1:9944 --suri //Bob
+//   repl --metadata erc20.contract --contract 5F... --url wss://... --key learner
+//
+// At the prompt, type a message number or label to call it, `list` to show the
+// messages again, or `quit`. Every call is dry-run first, printing the decoded return
+// value and events; messages that change state can then be submitted for real, signed
+// with `--suri`, or with `--key`, a key from the keystore that `deployer keys` manages.
+
+use clap::Parser;
+use repl::{
+    encode, parse,
+    value::{parse_account, parse_amount},
+    ContractMetadata, DryRun, Emitted, KeyPair, Keystore, Message, Scheme,
+};
+use scale::Decode;
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+use subxt::{
+    dynamic::{At, Value},
+    ext::scale_value::ValueDef,
+    OnlineClient, SubstrateConfig,
+};
+
+type Client = OnlineClient<SubstrateConfig>;
+
+/// The `REVERT` bit of the flags a contract returns with.
+const REVERT_FLAG: u128 = 1;
+
+#[derive(Parser)]
+#[command(about = "Call the messages of a deployed contract from an interactive prompt")]
+struct Cli {
+    /// Path to the contract's `.contract` bundle or `metadata.json`.
+    #[arg(long)]
+    metadata: PathBuf,
+    /// Address of the deployed contract.
+    #[arg(long)]
+    contract: String,
+    /// WebSocket URL of the node.
+    #[arg(long, default_value = "ws://127.0.0.1:9944")]
+    url: String,
+    /// Secret URI of the account calls are made from.
+    #[arg(long, default_value = "//Alice", conflicts_with = "key")]
+    suri: String,
+    /// Name of a stored key to make calls from, instead of `--suri`.
+    #[arg(long)]
+    key: Option<String>,
+    /// Keystore directory, `~/.astranet/keys` by default.
+    #[arg(long)]
+    keystore: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
+    let json = fs::read_to_string(&cli.metadata)
+        .map_err(|err| format!("{}: {}", cli.metadata.display(), err))?;
+    let metadata = parse(&json).map_err(|err| err.to_string())?;
+    let contract = parse_account(&cli.contract)?;
+    let (signer, signer_name) = match &cli.key {
+        Some(name) => {
+            let keystore = match &cli.keystore {
+                Some(dir) => Keystore::new(dir),
+                None => Keystore::new(Keystore::default_dir()?),
+            };
+            (keystore.unlock(name)?, format!("key `{}`", name))
+        }
+        None => (
+            KeyPair::from_suri(Scheme::Sr25519, &cli.suri)
+                .map_err(|err| format!("--suri: {}", err))?,
+            cli.suri.clone(),
+        ),
+    };
+
+    let client = Client::from_url(&cli.url)
+        .await
+        .map_err(|err| format!("cannot connect to {}: {}", cli.url, err))?;
+    println!(
+        "connected to {}, calling {} at {} as {}",
+        cli.url,
+        metadata.name.as_deref().unwrap_or("contract"),
+        cli.contract,
+        signer_name
+    );
+    list(&metadata);
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        let command = match prompt(&mut lines, "> ")? {
+            Some(command) => command,
+            None => return Ok(()),
+        };
+        match command.as_str() {
+            "" => continue,
+            "quit" | "exit" => return Ok(()),
+            "list" => list(&metadata),
+            name => match metadata.find(name) {
+                Some(message) => {
+                    if let Err(err) =
+                        call(&client, &signer, contract, &metadata, message, &mut lines).await
+                    {
+                        println!("error: {}", err);
+                    }
+                }
+                None => println!("no message `{}`; type `list` to see them", name),
+            },
+        }
+    }
+}
+
+fn list(metadata: &ContractMetadata) {
+    for (index, message) in metadata.messages.iter().enumerate() {
+        println!(
+            "  [{}] {}{}",
+            index,
+            message.signature(),
+            if message.mutates { "  (mutates)" } else { "" }
+        );
+    }
+}
+
+/// Prints `text` and reads one trimmed line, or `None` at end of input.
+fn prompt(
+    lines: &mut impl Iterator<Item = io::Result<String>>,
+    text: &str,
+) -> Result<Option<String>, String> {
+    print!("{}", text);
+    io::stdout().flush().map_err(|err| err.to_string())?;
+    match lines.next() {
+        Some(line) => Ok(Some(line.map_err(|err| err.to_string())?.trim().to_owned())),
+        None => Ok(None),
+    }
+}
+
+async fn call(
+    client: &Client,
+    signer: &KeyPair,
+    contract: [u8; 32],
+    metadata: &ContractMetadata,
+    message: &Message,
+    lines: &mut impl Iterator<Item = io::Result<String>>,
+) -> Result<(), String> {
+    let mut args = Vec::new();
+    for arg in &message.args {
+        let text = format!("  {}: {} ({}) = ", arg.label, arg.display, arg.ty.hint());
+        let input = prompt(lines, &text)?.ok_or("input closed")?;
+        args.push(encode(arg.ty, &input)?);
+    }
+    let value = if message.payable {
+        let input = prompt(lines, "  value to transfer [0] = ")?.ok_or("input closed")?;
+        match input.as_str() {
+            "" => 0,
+            amount => parse_amount(amount).ok_or("invalid value")?,
+        }
+    } else {
+        0
+    };
+    let data = message.call_data(&args);
+
+    let dry_run = dry_run(client, signer, contract, value, &data).await?;
+    for line in dry_run.report(metadata, message)? {
+        println!("  {}", line);
+    }
+
+    if !message.mutates || dry_run.reverted {
+        return Ok(());
+    }
+    let answer = prompt(lines, "  submit for real? [y/N] ")?.unwrap_or_default();
+    if answer.eq_ignore_ascii_case("y") {
+        execute(client, signer, contract, metadata, value, data, &dry_run).await?;
+    }
+    Ok(())
+}
+
+/// Runs the call through the `ContractsApi_call` runtime API, which executes it
+/// against the latest block without submitting a transaction.
+async fn dry_run(
+    client: &Client,
+    signer: &KeyPair,
+    contract: [u8; 32],
+    value: u128,
+    data: &[u8],
+) -> Result<DryRun, String> {
+    let payload = subxt::dynamic::runtime_api_call(
+        "ContractsApi",
+        "call",
+        vec![
+            Value::from_bytes(signer.account_id()),
+            Value::from_bytes(contract),
+            Value::u128(value),
+            Value::unnamed_variant("None", []),
+            Value::unnamed_variant("None", []),
+            Value::from_bytes(data),
+        ],
+    );
+    let result = client
+        .runtime_api()
+        .at_latest()
+        .await
+        .map_err(|err| err.to_string())?
+        .call(payload)
+        .await
+        .map_err(|err| format!("dry run failed: {}", err))?
+        .to_value()
+        .map_err(|err| err.to_string())?;
+
+    let outcome = result.at("result").ok_or("dry run returned no result")?;
+    let returned = match &outcome.value {
+        ValueDef::Variant(variant) if variant.name == "Ok" => {
+            outcome.at(0).ok_or("dry run returned no value")?
+        }
+        _ => return Err(format!("call failed: {}", outcome)),
+    };
+    let gas = result.at("gas_required").ok_or("dry run returned no gas")?;
+    // Only returned by nodes that collect events in dry runs, as development nodes do.
+    let events = result
+        .at("events")
+        .and_then(|events| events.at(0))
+        .map(|records| emitted_by(records, contract))
+        .unwrap_or_default();
+    Ok(DryRun {
+        data: bytes(returned.at("data").ok_or("dry run returned no data")?),
+        reverted: returned
+            .at("flags")
+            .and_then(first_number)
+            .is_some_and(|flags| flags & REVERT_FLAG != 0),
+        ref_time: gas.at("ref_time").and_then(first_number).unwrap_or(0),
+        proof_size: gas.at("proof_size").and_then(first_number).unwrap_or(0),
+        events,
+    })
+}
+
+/// Picks the `Contracts::ContractEmitted` events of `contract` out of event records.
+fn emitted_by<T>(records: &Value<T>, contract: [u8; 32]) -> Vec<Emitted> {
+    let mut emitted = Vec::new();
+    let mut index = 0;
+    while let Some(record) = records.at(index) {
+        index += 1;
+        // `RuntimeEvent::Contracts(Event::ContractEmitted { contract, data })`
+        let pallet = match record.at("event") {
+            Some(pallet) if variant_name(pallet) == Some("Contracts") => pallet,
+            _ => continue,
+        };
+        let event = match pallet.at(0) {
+            Some(event) if variant_name(event) == Some("ContractEmitted") => event,
+            _ => continue,
+        };
+        if event.at("contract").map(bytes).as_deref() != Some(&contract[..]) {
+            continue;
+        }
+        let mut topics = Vec::new();
+        let mut topic = 0;
+        while let Some(value) = record.at("topics").and_then(|topics| topics.at(topic)) {
+            topics.extend(<[u8; 32]>::try_from(bytes(value)));
+            topic += 1;
+        }
+        emitted.push(Emitted {
+            topics,
+            data: event.at("data").map(bytes).unwrap_or_default(),
+        });
+    }
+    emitted
+}
+
+/// Submits the call as a `Contracts::call` extrinsic with the gas the dry run needed.
+async fn execute(
+    client: &Client,
+    signer: &KeyPair,
+    contract: [u8; 32],
+    metadata: &ContractMetadata,
+    value: u128,
+    data: Vec<u8>,
+    dry_run: &DryRun,
+) -> Result<(), String> {
+    let tx = subxt::dynamic::tx(
+        "Contracts",
+        "call",
+        vec![
+            Value::unnamed_variant("Id", [Value::from_bytes(contract)]),
+            Value::u128(value),
+            Value::named_composite([
+                ("ref_time", Value::u128(dry_run.ref_time)),
+                ("proof_size", Value::u128(dry_run.proof_size)),
+            ]),
+            Value::unnamed_variant("None", []),
+            Value::from_bytes(data),
+        ],
+    );
+    let mut progress = client
+        .tx()
+        .sign_and_submit_then_watch_default(&tx, signer)
+        .await
+        .map_err(|err| format!("submission failed: {}", err))?;
+
+    while let Some(status) = progress.next().await {
+        let status = status.map_err(|err| err.to_string())?;
+        let in_block = match status.as_in_block().or_else(|| status.as_finalized()) {
+            Some(in_block) => in_block,
+            None => continue,
+        };
+        let events = in_block
+            .wait_for_success()
+            .await
+            .map_err(|err| format!("transaction failed: {}", err))?;
+        println!("  included in block {:?}", in_block.block_hash());
+        for event in events.iter().filter_map(Result::ok) {
+            if event.pallet_name() != "Contracts" || event.variant_name() != "ContractEmitted" {
+                continue;
+            }
+            // `ContractEmitted { contract: AccountId32, data: Vec<u8> }`
+            let (emitter, data) = match <([u8; 32], Vec<u8>)>::decode(&mut event.field_bytes()) {
+                Ok(fields) => fields,
+                Err(_) => continue,
+            };
+            if emitter == contract {
+                let topics = event.topics().iter().map(|topic| topic.0).collect();
+                let emitted = Emitted { topics, data };
+                println!("  event {}", metadata.format_event(&emitted));
+            }
+        }
+        return Ok(());
+    }
+    Err("transaction was dropped before inclusion".to_owned())
+}
+
+fn variant_name<T>(value: &Value<T>) -> Option<&str> {
+    match &value.value {
+        ValueDef::Variant(variant) => Some(&variant.name),
+        _ => None,
+    }
+}
+
+/// Collects a decoded `Vec<u8>` or `[u8; N]` back into bytes, looking through newtype
+/// wrappers such as `AccountId32` and `H256`.
+fn bytes<T>(value: &Value<T>) -> Vec<u8> {
+    match (value.at(0), value.at(1)) {
+        (Some(inner), None) if inner.as_u128().is_none() => return bytes(inner),
+        _ => {}
+    }
+    let mut out = Vec::new();
+    let mut index = 0;
+    while let Some(byte) = value.at(index).and_then(Value::as_u128) {
+        out.push(byte as u8);
+        index += 1;
+    }
+    out
+}
+
+/// Returns the first number inside `value`, looking through newtype wrappers such as
+/// `ReturnFlags { bits }` and `Compact`.
+fn first_number<T>(value: &Value<T>) -> Option<u128> {
+    value
+        .as_u128()
+        .or_else(|| value.at(0).and_then(first_number))
+}
@@ -0,0 +1,527 @@
+// tools/repl/src/metadata.rs
+//
+// Reads the messages of a contract from its `.contract` bundle or `metadata.json`.
+//
+// ink! 3 emits metadata nested under a version key (`"V3": { "spec": .., "types": .. }`)
+// and returns message values as they are. ink! 4 and later put `spec` and `types` at
+// the top level next to a `"version"` field, and wrap every return value in
+// `Result<T, LangError>`; `ContractMetadata::wraps_result` records which one applies.
+// ink! 5 also changed how events are told apart, see `ContractMetadata::signature_topics`.
+//
+// The type registry is kept whole for `transcode` to print any value; `ValueType` only
+// covers what can be typed in at the prompt.
+
+use crate::value::{parse_hex, ValueType};
+use scale_info::PortableRegistry;
+use selector::Selector;
+use serde_json::{json, Value};
+use std::fmt;
+
+/// An argument of a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arg {
+    pub label: String,
+    /// The type name as written in the contract, e.g. `AccountId` or `Balance`.
+    pub display: String,
+    pub ty: ValueType,
+}
+
+/// A message the REPL can call, or a constructor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub label: String,
+    pub selector: Selector,
+    /// Whether the message changes state, so calling it for real is worthwhile.
+    pub mutates: bool,
+    /// Whether the message accepts a transferred value.
+    pub payable: bool,
+    pub args: Vec<Arg>,
+    /// The type of the return value, or `None` if the message returns nothing.
+    pub returns: Option<ValueType>,
+    /// The id of the return type in `ContractMetadata::types`, inside the `Result` of
+    /// ink! 4 and later; `None` if the message returns nothing.
+    pub return_type: Option<u32>,
+}
+
+impl Message {
+    /// Formats the message as a signature, e.g. `transfer(to: AccountId, value: Balance)`.
+    pub fn signature(&self) -> String {
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| format!("{}: {}", arg.label, arg.display))
+            .collect();
+        format!("{}({})", self.label, args.join(", "))
+    }
+
+    /// Builds the call data for this message from encoded arguments.
+    pub fn call_data(&self, encoded_args: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = self.selector.to_vec();
+        for arg in encoded_args {
+            data.extend_from_slice(arg);
+        }
+        data
+    }
+}
+
+/// A field of an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventArg {
+    pub label: String,
+    /// Whether the field is also published as a topic.
+    pub indexed: bool,
+    /// The id of the field's type in `ContractMetadata::types`.
+    pub ty: u32,
+}
+
+/// An event the contract can emit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub label: String,
+    pub args: Vec<EventArg>,
+    /// The topic that identifies the event (ink! 5), or `None` for anonymous events
+    /// and older metadata.
+    pub signature_topic: Option<[u8; 32]>,
+}
+
+/// The parts of a contract's metadata the REPL uses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractMetadata {
+    /// The contract name, if the file is a bundle rather than bare metadata.
+    pub name: Option<String>,
+    /// The constructors, which take arguments like messages but never mutate.
+    pub constructors: Vec<Message>,
+    pub messages: Vec<Message>,
+    /// The events, in the order their index refers to before ink! 5.
+    pub events: Vec<Event>,
+    /// Whether return values are wrapped in `Result<T, LangError>` (ink! 4 and later).
+    pub wraps_result: bool,
+    /// Whether events are identified by their signature topic (ink! 5) rather than by
+    /// an index in the first byte of their data.
+    pub signature_topics: bool,
+    /// Every type the messages and events refer to; empty if the metadata's types
+    /// could not be read, in which case values are shown as hex.
+    pub types: PortableRegistry,
+}
+
+impl ContractMetadata {
+    /// Finds a message by label or by its position in `messages`.
+    pub fn find(&self, name_or_index: &str) -> Option<&Message> {
+        match name_or_index.parse::<usize>() {
+            Ok(index) => self.messages.get(index),
+            Err(_) => self
+                .messages
+                .iter()
+                .find(|message| message.label == name_or_index),
+        }
+    }
+}
+
+/// Errors returned while reading metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetadataError {
+    /// The input is not JSON.
+    InvalidJson(String),
+    /// No `spec` object was found, at the top level or under a version key.
+    MissingSpec,
+    /// A message or constructor lacks a label or has a malformed selector.
+    InvalidMessage(String),
+}
+
+impl fmt::Display for MetadataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetadataError::InvalidJson(err) => write!(f, "metadata is not valid JSON: {}", err),
+            MetadataError::MissingSpec => write!(f, "metadata has no contract spec"),
+            MetadataError::InvalidMessage(what) => write!(f, "invalid message: {}", what),
+        }
+    }
+}
+
+impl std::error::Error for MetadataError {}
+
+/// Reads the messages of a `.contract` or `metadata.json` file.
+pub fn parse(json: &str) -> Result<ContractMetadata, MetadataError> {
+    let root: Value =
+        serde_json::from_str(json).map_err(|err| MetadataError::InvalidJson(err.to_string()))?;
+    let wraps_result = root.get("version").is_some();
+    let version = match root.get("version") {
+        Some(Value::String(version)) => version.parse().unwrap_or(0),
+        Some(version) => version.as_u64().unwrap_or(0),
+        None => 0,
+    };
+    let versioned = if root.get("spec").is_some() {
+        &root
+    } else {
+        root.as_object()
+            .and_then(|object| {
+                object
+                    .iter()
+                    .find(|(key, value)| key.starts_with('V') && value.get("spec").is_some())
+            })
+            .map(|(_, versioned)| versioned)
+            .ok_or(MetadataError::MissingSpec)?
+    };
+    let spec = &versioned["spec"];
+    let types = Types(versioned.get("types").and_then(Value::as_array));
+
+    let mut constructors = Vec::new();
+    for item in spec
+        .get("constructors")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        constructors.push(parse_message(item, &types, wraps_result)?);
+    }
+    let mut messages = Vec::new();
+    for item in spec
+        .get("messages")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        messages.push(parse_message(item, &types, wraps_result)?);
+    }
+    let events = spec
+        .get("events")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(parse_event)
+        .collect();
+
+    Ok(ContractMetadata {
+        name: root
+            .pointer("/contract/name")
+            .and_then(Value::as_str)
+            .map(str::to_owned),
+        constructors,
+        messages,
+        events,
+        wraps_result,
+        signature_topics: version >= 5,
+        types: serde_json::from_value(json!({ "types": versioned.get("types") }))
+            .unwrap_or_else(|_| PortableRegistry { types: Vec::new() }),
+    })
+}
+
+fn parse_message(
+    item: &Value,
+    types: &Types,
+    wraps_result: bool,
+) -> Result<Message, MetadataError> {
+    let label = label_of(item)
+        .ok_or_else(|| MetadataError::InvalidMessage("message without label".to_owned()))?;
+    let selector = item
+        .get("selector")
+        .and_then(Value::as_str)
+        .and_then(selector::from_hex)
+        .ok_or_else(|| MetadataError::InvalidMessage(format!("`{}` selector", label)))?;
+
+    let args = item
+        .get("args")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|arg| Arg {
+            label: label_of(arg).unwrap_or_default(),
+            display: display_name(&arg["type"]),
+            ty: types.resolve(arg.pointer("/type/type")),
+        })
+        .collect();
+
+    let (returns, return_type) = match item.get("returnType") {
+        None | Some(Value::Null) => (None, None),
+        Some(return_type) if wraps_result => match types.ok_id(return_type.get("type")) {
+            Some(ok) => match types.resolve(Some(ok)) {
+                ValueType::Unit => (None, None),
+                ty => (Some(ty), type_id(Some(ok))),
+            },
+            None => (None, None),
+        },
+        Some(return_type) => (
+            Some(types.resolve(return_type.get("type"))),
+            type_id(return_type.get("type")),
+        ),
+    };
+
+    Ok(Message {
+        label,
+        selector,
+        mutates: item
+            .get("mutates")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        payable: item
+            .get("payable")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        args,
+        returns,
+        return_type,
+    })
+}
+
+fn parse_event(item: &Value) -> Event {
+    Event {
+        label: label_of(item).unwrap_or_default(),
+        args: item
+            .get("args")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|arg| EventArg {
+                label: label_of(arg).unwrap_or_default(),
+                indexed: arg.get("indexed").and_then(Value::as_bool).unwrap_or(false),
+                ty: type_id(arg.pointer("/type/type")).unwrap_or(u32::MAX),
+            })
+            .collect(),
+        signature_topic: item
+            .get("signature_topic")
+            .and_then(Value::as_str)
+            .and_then(|topic| parse_hex(topic).ok()?.try_into().ok()),
+    }
+}
+
+fn type_id(id: Option<&Value>) -> Option<u32> {
+    id?.as_u64()?.try_into().ok()
+}
+
+/// Returns an item's label; metadata V1 stored it as a list of path segments.
+fn label_of(item: &Value) -> Option<String> {
+    match item.get("label").or_else(|| item.get("name"))? {
+        Value::String(label) => Some(label.clone()),
+        Value::Array(parts) => Some(
+            parts
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join("::"),
+        ),
+        _ => None,
+    }
+}
+
+fn display_name(ty: &Value) -> String {
+    ty.get("displayName")
+        .and_then(Value::as_array)
+        .and_then(|path| path.last())
+        .and_then(Value::as_str)
+        .unwrap_or("?")
+        .to_owned()
+}
+
+/// The type registry of the metadata.
+struct Types<'a>(Option<&'a Vec<Value>>);
+
+impl Types<'_> {
+    fn get(&self, id: Option<&Value>) -> Option<&Value> {
+        let id = id?.as_u64()?;
+        self.0?
+            .iter()
+            .find(|entry| entry.get("id").and_then(Value::as_u64) == Some(id))
+            .map(|entry| &entry["type"])
+    }
+
+    /// Maps a type id to the kind of value the REPL can read and print.
+    fn resolve(&self, id: Option<&Value>) -> ValueType {
+        let ty = match self.get(id) {
+            Some(ty) => ty,
+            None => return ValueType::Raw,
+        };
+        let def = &ty["def"];
+        let last_path = ty
+            .get("path")
+            .and_then(Value::as_array)
+            .and_then(|path| path.last())
+            .and_then(Value::as_str);
+
+        if let Some(primitive) = def.get("primitive").and_then(Value::as_str) {
+            return ValueType::from_primitive(primitive);
+        }
+        if let Some(array) = def.get("array") {
+            let len = array.get("len").and_then(Value::as_u64).unwrap_or(0) as usize;
+            return match self.resolve(array.get("type")) {
+                ValueType::U8 if len == 32 && last_path == Some("AccountId") => {
+                    ValueType::AccountId
+                }
+                ValueType::U8 => ValueType::FixedBytes(len),
+                _ => ValueType::Raw,
+            };
+        }
+        if let Some(sequence) = def.get("sequence") {
+            return match self.resolve(sequence.get("type")) {
+                ValueType::U8 => ValueType::Bytes,
+                _ => ValueType::Raw,
+            };
+        }
+        // Newtypes such as `AccountId([u8; 32])` encode exactly like their field.
+        if let Some(fields) = def.pointer("/composite/fields").and_then(Value::as_array) {
+            if let [field] = fields.as_slice() {
+                return match self.resolve(field.get("type")) {
+                    ValueType::FixedBytes(32) if last_path == Some("AccountId") => {
+                        ValueType::AccountId
+                    }
+                    inner => inner,
+                };
+            }
+            if fields.is_empty() {
+                return ValueType::Unit;
+            }
+        }
+        if def
+            .pointer("/tuple")
+            .and_then(Value::as_array)
+            .is_some_and(Vec::is_empty)
+        {
+            return ValueType::Unit;
+        }
+        ValueType::Raw
+    }
+
+    /// Returns the type id of the `Ok` variant of a `Result` type.
+    fn ok_id(&self, id: Option<&Value>) -> Option<&Value> {
+        let variants = self.get(id)?.pointer("/def/variant/variants")?.as_array()?;
+        variants
+            .iter()
+            .find(|variant| variant.get("name").and_then(Value::as_str) == Some("Ok"))?
+            .pointer("/fields/0/type")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Trimmed-down ink! 3 metadata of the ERC-20 example.
+    const ERC20_V3: &str = r#"{
+        "contract": { "name": "erc20", "version": "0.1.0" },
+        "V3": {
+            "spec": {
+                "constructors": [
+                    {
+                        "label": "new", "selector": "0x9bae9d5e", "payable": false,
+                        "args": [{ "label": "total_supply", "type": { "displayName": ["Balance"], "type": 3 } }]
+                    }
+                ],
+                "messages": [
+                    {
+                        "label": "balance_of", "selector": "0x0f755a56",
+                        "mutates": false, "payable": false,
+                        "args": [{ "label": "owner", "type": { "displayName": ["AccountId"], "type": 0 } }],
+                        "returnType": { "displayName": ["Balance"], "type": 3 }
+                    },
+                    {
+                        "label": "transfer", "selector": "0x84a15da1",
+                        "mutates": true, "payable": false,
+                        "args": [
+                            { "label": "to", "type": { "displayName": ["AccountId"], "type": 0 } },
+                            { "label": "value", "type": { "displayName": ["Balance"], "type": 3 } }
+                        ],
+                        "returnType": { "displayName": ["Result"], "type": 4 }
+                    }
+                ]
+            },
+            "types": [
+                { "id": 0, "type": { "path": ["ink_env", "types", "AccountId"],
+                    "def": { "composite": { "fields": [{ "type": 1, "typeName": "[u8; 32]" }] } } } },
+                { "id": 1, "type": { "def": { "array": { "len": 32, "type": 2 } } } },
+                { "id": 2, "type": { "def": { "primitive": "u8" } } },
+                { "id": 3, "type": { "def": { "primitive": "u128" } } },
+                { "id": 4, "type": { "path": ["Result"], "def": { "variant": { "variants": [] } } } }
+            ]
+        }
+    }"#;
+
+    /// Trimmed-down ink! 4 metadata of the flipper example.
+    const FLIPPER_V4: &str = r#"{
+        "version": "4",
+        "spec": {
+            "messages": [
+                { "label": "flip", "selector": "0x633aa551", "mutates": true, "args": [],
+                  "returnType": { "displayName": ["ink", "MessageResult"], "type": 2 } },
+                { "label": "get", "selector": "0x2f865bd9", "mutates": false, "args": [],
+                  "returnType": { "displayName": ["ink", "MessageResult"], "type": 4 } }
+            ]
+        },
+        "types": [
+            { "id": 0, "type": { "def": { "primitive": "bool" } } },
+            { "id": 1, "type": { "def": { "tuple": [] } } },
+            { "id": 2, "type": { "path": ["Result"], "def": { "variant": { "variants": [
+                { "name": "Ok", "fields": [{ "type": 1 }], "index": 0 },
+                { "name": "Err", "fields": [{ "type": 3 }], "index": 1 } ] } } } },
+            { "id": 3, "type": { "path": ["ink_primitives", "LangError"], "def": { "variant": {} } } },
+            { "id": 4, "type": { "path": ["Result"], "def": { "variant": { "variants": [
+                { "name": "Ok", "fields": [{ "type": 0 }], "index": 0 },
+                { "name": "Err", "fields": [{ "type": 3 }], "index": 1 } ] } } } }
+        ]
+    }"#;
+
+    #[test]
+    fn reads_ink3_messages() {
+        let metadata = parse(ERC20_V3).unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("erc20"));
+        assert!(!metadata.wraps_result);
+
+        let transfer = metadata.find("transfer").unwrap();
+        assert_eq!(transfer.selector, [0x84, 0xa1, 0x5d, 0xa1]);
+        assert!(transfer.mutates);
+        assert_eq!(
+            transfer.signature(),
+            "transfer(to: AccountId, value: Balance)"
+        );
+        assert_eq!(transfer.args[0].ty, ValueType::AccountId);
+        assert_eq!(transfer.args[1].ty, ValueType::U128);
+        assert_eq!(transfer.returns, Some(ValueType::Raw));
+
+        assert_eq!(metadata.find("0").unwrap().label, "balance_of");
+        assert_eq!(metadata.find("0").unwrap().returns, Some(ValueType::U128));
+
+        let new = &metadata.constructors[0];
+        assert_eq!(new.signature(), "new(total_supply: Balance)");
+        assert_eq!(new.args[0].ty, ValueType::U128);
+        assert_eq!(new.returns, None);
+    }
+
+    #[test]
+    fn unwraps_ink4_return_types() {
+        let metadata = parse(FLIPPER_V4).unwrap();
+        assert!(metadata.wraps_result);
+        assert_eq!(metadata.find("flip").unwrap().returns, None);
+        assert_eq!(metadata.find("get").unwrap().returns, Some(ValueType::Bool));
+        assert_eq!(metadata.find("get").unwrap().return_type, Some(0));
+        assert_eq!(metadata.find("flip").unwrap().return_type, None);
+        assert!(!metadata.signature_topics);
+    }
+
+    #[test]
+    fn call_data_is_selector_then_args() {
+        let metadata = parse(ERC20_V3).unwrap();
+        let transfer = metadata.find("transfer").unwrap();
+        let data = transfer.call_data(&[vec![1; 32], 5u128.to_le_bytes().to_vec()]);
+        assert_eq!(&data[..4], &[0x84, 0xa1, 0x5d, 0xa1]);
+        assert_eq!(data.len(), 4 + 32 + 16);
+    }
+
+    #[test]
+    fn missing_types_fall_back_to_raw() {
+        let json = r#"{ "V1": { "spec": { "messages": [
+            { "name": ["Erc20", "transfer"], "selector": "0x00000000",
+              "args": [{ "name": "to", "type": { "displayName": ["AccountId"], "type": 1 } }] }
+        ] } } }"#;
+        let metadata = parse(json).unwrap();
+        assert_eq!(metadata.messages[0].label, "Erc20::transfer");
+        assert_eq!(metadata.messages[0].args[0].ty, ValueType::Raw);
+    }
+
+    #[test]
+    fn rejects_bad_metadata() {
+        assert!(matches!(parse("{"), Err(MetadataError::InvalidJson(_))));
+        assert_eq!(parse("{}"), Err(MetadataError::MissingSpec));
+        assert!(matches!(
+            parse(r#"{"spec":{"messages":[{"label":"x"}]}}"#),
+            Err(MetadataError::InvalidMessage(_))
+        ));
+    }
+}
@@ -0,0 +1,56 @@
+// tools/repl/src/report.rs
+//
+// What the REPL prints after dry-running a message: whether the call went through,
+// what it returned, the gas it needed, then one line per event the contract emitted.
+//
+// Kept apart from the node connection so the output can be checked against recorded
+// dry-run results, see `tests/dry_runs.rs`.
+
+use crate::{
+    metadata::{ContractMetadata, Message},
+    transcode::Emitted,
+    value::to_hex,
+};
+
+/// The outcome of a dry run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRun {
+    /// The bytes the contract returned.
+    pub data: Vec<u8>,
+    /// Whether the contract returned with the `REVERT` flag, undoing its changes.
+    pub reverted: bool,
+    pub ref_time: u128,
+    pub proof_size: u128,
+    /// The events the called contract emitted, in order.
+    pub events: Vec<Emitted>,
+}
+
+impl DryRun {
+    /// Returns the lines to print for this dry run of `message`, or an error if ink!
+    /// rejected the call before it reached the message.
+    pub fn report(
+        &self,
+        metadata: &ContractMetadata,
+        message: &Message,
+    ) -> Result<Vec<String>, String> {
+        let mut returned = self.data.as_slice();
+        if metadata.wraps_result {
+            // `Result<T, LangError>`: a leading 0 is `Ok`.
+            match returned.split_first() {
+                Some((0, rest)) => returned = rest,
+                _ => return Err(format!("contract rejected the call: {}", to_hex(returned))),
+            }
+        }
+        let mut lines = vec![format!(
+            "dry run {}: returned {} (gas required: ref_time {}, proof_size {})",
+            if self.reverted { "REVERTED" } else { "ok" },
+            metadata.format_return(message, returned),
+            self.ref_time,
+            self.proof_size
+        )];
+        for event in &self.events {
+            lines.push(format!("event {}", metadata.format_event(event)));
+        }
+        Ok(lines)
+    }
+}
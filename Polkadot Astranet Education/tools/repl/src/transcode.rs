@@ -0,0 +1,343 @@
+// tools/repl/src/transcode.rs
+//
+// Prints return values and events using the type registry in the contract's metadata,
+// so a call shows `Err(InsufficientBalance)` or `Transfer { from: None, .. }` rather
+// than SCALE bytes in hex.
+//
+// Values are rendered close to Rust syntax: structs and enum variants by name, `Vec`
+// and arrays in brackets, strings quoted, and accounts in SS58. Byte strings and
+// hashes stay hex, as that is how they are typed in at the prompt.
+//
+// Events are matched the way ink! emits them. Up to ink! 4 the first byte of the data
+// is the event's index in the metadata. ink! 5 drops that byte and publishes the
+// event's signature topic first instead; anonymous events have none, so an event
+// whose first topic matches nothing is taken to be the contract's one anonymous event,
+// if it has exactly one.
+
+use crate::{
+    metadata::{ContractMetadata, Event, Message},
+    value::{decode, to_hex},
+};
+use scale_info::{form::PortableForm, Type, TypeDef, TypeDefPrimitive};
+use scale_value::{Composite, Primitive, ValueDef};
+use subxt::utils::AccountId32;
+
+type Value = scale_value::Value<u32>;
+
+/// An event emitted by a contract, as the node reports it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Emitted {
+    pub topics: Vec<[u8; 32]>,
+    pub data: Vec<u8>,
+}
+
+impl ContractMetadata {
+    /// Decodes one value of type `ty` that fills `bytes` exactly, or returns `None`.
+    pub fn decode_value(&self, ty: u32, bytes: &[u8]) -> Option<String> {
+        let mut input = bytes;
+        match scale_value::scale::decode_as_type(&mut input, ty, &self.types) {
+            Ok(value) if input.is_empty() => {
+                let mut out = String::new();
+                self.write(&value, &mut out);
+                Some(out)
+            }
+            _ => None,
+        }
+    }
+
+    /// Formats what `message` returned, given the bytes inside any `Result<_, LangError>`.
+    pub fn format_return(&self, message: &Message, bytes: &[u8]) -> String {
+        let typed = message
+            .return_type
+            .and_then(|ty| self.decode_value(ty, bytes));
+        match (typed, message.returns) {
+            (Some(typed), _) => typed,
+            (None, Some(ty)) => decode(ty, bytes),
+            (None, None) => "nothing".to_owned(),
+        }
+    }
+
+    /// Formats an event emitted by this contract, e.g. `Transfer { from: None, to:
+    /// Some(5Grw..), value: 100 }`, falling back to hex for events it cannot match.
+    pub fn format_event(&self, event: &Emitted) -> String {
+        let (spec, mut data) = match self.find_event(event) {
+            Some(found) => found,
+            None => return format!("unknown event {}", to_hex(&event.data)),
+        };
+        let mut fields = Vec::new();
+        for arg in &spec.args {
+            match scale_value::scale::decode_as_type(&mut data, arg.ty, &self.types) {
+                Ok(value) => {
+                    let mut out = format!("{}: ", arg.label);
+                    self.write(&value, &mut out);
+                    fields.push(out);
+                }
+                Err(_) => break,
+            }
+        }
+        if fields.len() < spec.args.len() || !data.is_empty() {
+            return format!("{} (undecodable) {}", spec.label, to_hex(&event.data));
+        }
+        if fields.is_empty() {
+            spec.label.clone()
+        } else {
+            format!("{} {{ {} }}", spec.label, fields.join(", "))
+        }
+    }
+
+    /// Returns the event `event` is an instance of, and its encoded fields.
+    fn find_event<'a>(&self, event: &'a Emitted) -> Option<(&Event, &'a [u8])> {
+        if !self.signature_topics {
+            let (index, fields) = event.data.split_first()?;
+            return Some((self.events.get(usize::from(*index))?, fields));
+        }
+        let first = event.topics.first();
+        let signed = self
+            .events
+            .iter()
+            .find(|spec| spec.signature_topic.is_some() && spec.signature_topic.as_ref() == first);
+        let spec = match signed {
+            Some(spec) => spec,
+            None => {
+                let mut anonymous = self
+                    .events
+                    .iter()
+                    .filter(|spec| spec.signature_topic.is_none());
+                match (anonymous.next(), anonymous.next()) {
+                    (Some(spec), None) => spec,
+                    _ => return None,
+                }
+            }
+        };
+        Some((spec, &event.data))
+    }
+
+    fn write(&self, value: &Value, out: &mut String) {
+        let ty = self.types.resolve(value.context);
+        if let Some(bytes) = ty.and_then(|ty| self.byte_string(ty, value)) {
+            if ty.is_some_and(is_account) && bytes.len() == 32 {
+                let account: [u8; 32] = bytes.try_into().unwrap_or([0; 32]);
+                out.push_str(&AccountId32(account).to_string());
+            } else {
+                out.push_str(&to_hex(&bytes));
+            }
+            return;
+        }
+        match &value.value {
+            ValueDef::Composite(composite) => {
+                let name = ty.and_then(|ty| ty.path.segments.last());
+                match ty.map(|ty| &ty.type_def) {
+                    Some(TypeDef::Sequence(_)) | Some(TypeDef::Array(_)) => {
+                        out.push('[');
+                        self.write_list(composite, out);
+                        out.push(']');
+                    }
+                    Some(TypeDef::Tuple(_)) => {
+                        out.push('(');
+                        self.write_list(composite, out);
+                        out.push(')');
+                    }
+                    // Newtypes print as the value they wrap.
+                    _ if composite.len() == 1 && matches!(composite, Composite::Unnamed(_)) => {
+                        self.write_list(composite, out)
+                    }
+                    _ => self.write_fields(name.map(String::as_str), composite, out),
+                }
+            }
+            ValueDef::Variant(variant) => {
+                self.write_fields(Some(&variant.name), &variant.values, out)
+            }
+            ValueDef::Primitive(Primitive::String(text)) => out.push_str(&format!("{:?}", text)),
+            ValueDef::Primitive(Primitive::Char(c)) => out.push_str(&format!("{:?}", c)),
+            ValueDef::Primitive(Primitive::U256(bytes) | Primitive::I256(bytes)) => {
+                out.push_str(&to_hex(bytes))
+            }
+            _ => out.push_str(&value.to_string()),
+        }
+    }
+
+    /// Writes `Name`, `Name(a, b)` or `Name { a: 1 }`, without the name if it is `None`.
+    fn write_fields(&self, name: Option<&str>, fields: &Composite<u32>, out: &mut String) {
+        out.push_str(name.unwrap_or_default());
+        match fields {
+            Composite::Unnamed(values) if values.is_empty() => {
+                if name.is_none() {
+                    out.push_str("()");
+                }
+            }
+            Composite::Unnamed(_) => {
+                out.push('(');
+                self.write_list(fields, out);
+                out.push(')');
+            }
+            Composite::Named(values) => {
+                if name.is_some() {
+                    out.push(' ');
+                }
+                out.push_str("{ ");
+                for (i, (label, value)) in values.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    out.push_str(label);
+                    out.push_str(": ");
+                    self.write(value, out);
+                }
+                out.push_str(" }");
+            }
+        }
+    }
+
+    fn write_list(&self, values: &Composite<u32>, out: &mut String) {
+        for (i, value) in values.values().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            self.write(value, out);
+        }
+    }
+
+    /// Returns the bytes of `value` if its type is `[u8; N]` or `Vec<u8>`, directly or
+    /// wrapped in newtypes such as `AccountId` and `Hash`.
+    fn byte_string(&self, ty: &Type<PortableForm>, value: &Value) -> Option<Vec<u8>> {
+        let element = match &ty.type_def {
+            TypeDef::Array(array) => array.type_param.id,
+            TypeDef::Sequence(sequence) => sequence.type_param.id,
+            TypeDef::Composite(composite) if composite.fields.len() == 1 => {
+                let inner = value_fields(value)?.values().next()?;
+                return self.byte_string(self.types.resolve(inner.context)?, inner);
+            }
+            _ => return None,
+        };
+        let element = self.types.resolve(element)?;
+        if !matches!(element.type_def, TypeDef::Primitive(TypeDefPrimitive::U8)) {
+            return None;
+        }
+        value_fields(value)?
+            .values()
+            .map(|byte| byte.as_u128().and_then(|byte| u8::try_from(byte).ok()))
+            .collect()
+    }
+}
+
+fn value_fields(value: &Value) -> Option<&Composite<u32>> {
+    match &value.value {
+        ValueDef::Composite(composite) => Some(composite),
+        _ => None,
+    }
+}
+
+fn is_account(ty: &Type<PortableForm>) -> bool {
+    matches!(
+        ty.path.segments.last().map(String::as_str),
+        Some("AccountId" | "AccountId32")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::parse;
+    use scale::Encode;
+
+    const ALICE: [u8; 32] = [
+        0xd4, 0x35, 0x93, 0xc7, 0x15, 0xfd, 0xd3, 0x1c, 0x61, 0x14, 0x1a, 0xbd, 0x04, 0xa9, 0x9f,
+        0xd6, 0x82, 0x2c, 0x85, 0x58, 0x85, 0x4c, 0xcd, 0xe3, 0x9a, 0x56, 0x84, 0xe7, 0xa5, 0x6d,
+        0xa2, 0x7d,
+    ];
+
+    /// A registry with an account, an `Option` of it, a struct and a byte vector.
+    const METADATA: &str = r#"{
+        "V3": {
+            "spec": {
+                "messages": [
+                    { "label": "owner", "selector": "0x00000001", "args": [],
+                      "returnType": { "displayName": ["Option"], "type": 3 } },
+                    { "label": "info", "selector": "0x00000002", "args": [],
+                      "returnType": { "displayName": ["Info"], "type": 4 } }
+                ],
+                "events": [
+                    { "label": "Renamed", "args": [
+                        { "label": "by", "indexed": true, "type": { "displayName": ["AccountId"], "type": 0 } },
+                        { "label": "name", "indexed": false, "type": { "displayName": ["String"], "type": 6 } }
+                    ] },
+                    { "label": "Cleared", "args": [] }
+                ]
+            },
+            "types": [
+                { "id": 0, "type": { "path": ["ink_env", "types", "AccountId"],
+                    "def": { "composite": { "fields": [{ "type": 1 }] } } } },
+                { "id": 1, "type": { "def": { "array": { "len": 32, "type": 2 } } } },
+                { "id": 2, "type": { "def": { "primitive": "u8" } } },
+                { "id": 3, "type": { "path": ["Option"], "params": [{ "name": "T", "type": 0 }],
+                    "def": { "variant": { "variants": [
+                        { "name": "None", "index": 0 },
+                        { "name": "Some", "fields": [{ "type": 0 }], "index": 1 } ] } } } },
+                { "id": 4, "type": { "path": ["demo", "Info"], "def": { "composite": { "fields": [
+                    { "name": "tags", "type": 5 }, { "name": "name", "type": 6 },
+                    { "name": "pair", "type": 7 } ] } } } },
+                { "id": 5, "type": { "def": { "sequence": { "type": 2 } } } },
+                { "id": 6, "type": { "def": { "primitive": "str" } } },
+                { "id": 7, "type": { "def": { "tuple": [8, 8] } } },
+                { "id": 8, "type": { "def": { "primitive": "u32" } } }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn accounts_print_as_ss58() {
+        let metadata = parse(METADATA).unwrap();
+        let owner = metadata.find("owner").unwrap();
+        assert_eq!(
+            metadata.format_return(owner, &Some(ALICE).encode()),
+            "Some(5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY)"
+        );
+        assert_eq!(metadata.format_return(owner, &[0]), "None");
+    }
+
+    #[test]
+    fn structs_print_their_fields() {
+        let metadata = parse(METADATA).unwrap();
+        let info = metadata.find("info").unwrap();
+        let bytes = (vec![0xabu8, 0xcd], "ink", (1u32, 2u32)).encode();
+        assert_eq!(
+            metadata.format_return(info, &bytes),
+            r#"Info { tags: 0xabcd, name: "ink", pair: (1, 2) }"#
+        );
+    }
+
+    #[test]
+    fn undecodable_values_fall_back_to_hex() {
+        let metadata = parse(METADATA).unwrap();
+        let info = metadata.find("info").unwrap();
+        assert_eq!(metadata.format_return(info, &[0x04]), "0x04");
+        let owner = metadata.find("owner").unwrap();
+        assert_eq!(metadata.format_return(owner, &[0, 0]), "0x0000");
+    }
+
+    #[test]
+    fn events_are_found_by_index() {
+        let metadata = parse(METADATA).unwrap();
+        let mut data = vec![0];
+        data.extend((ALICE, "ada").encode());
+        let renamed = Emitted {
+            topics: Vec::new(),
+            data,
+        };
+        assert_eq!(
+            metadata.format_event(&renamed),
+            r#"Renamed { by: 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY, name: "ada" }"#
+        );
+
+        let cleared = Emitted {
+            topics: Vec::new(),
+            data: vec![1],
+        };
+        assert_eq!(metadata.format_event(&cleared), "Cleared");
+        let unknown = Emitted {
+            topics: Vec::new(),
+            data: vec![2],
+        };
+        assert_eq!(metadata.format_event(&unknown), "unknown event 0x02");
+    }
+}
@@ -0,0 +1,239 @@
+// tools/repl/src/value.rs
+//
+// Conversion between prompt input and SCALE bytes for the types the REPL understands.
+
+use scale::{Decode, Encode};
+use std::str::FromStr;
+use subxt::utils::AccountId32;
+
+/// The kinds of values the REPL can read from the prompt and print back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Str,
+    /// `Vec<u8>`, entered as hex.
+    Bytes,
+    /// `[u8; N]`, entered as hex.
+    FixedBytes(usize),
+    /// A 32-byte account id, entered as SS58, hex, or a dev account name.
+    AccountId,
+    /// `()`, which encodes to nothing.
+    Unit,
+    /// Any other type, entered and printed as its SCALE encoding in hex.
+    Raw,
+}
+
+impl ValueType {
+    /// Maps a scale-info primitive name such as `u32` to a value type.
+    pub fn from_primitive(name: &str) -> Self {
+        match name {
+            "bool" => ValueType::Bool,
+            "u8" => ValueType::U8,
+            "u16" => ValueType::U16,
+            "u32" => ValueType::U32,
+            "u64" => ValueType::U64,
+            "u128" => ValueType::U128,
+            "i8" => ValueType::I8,
+            "i16" => ValueType::I16,
+            "i32" => ValueType::I32,
+            "i64" => ValueType::I64,
+            "i128" => ValueType::I128,
+            "str" => ValueType::Str,
+            _ => ValueType::Raw,
+        }
+    }
+
+    /// Describes what to type at the prompt.
+    pub fn hint(&self) -> &'static str {
+        match self {
+            ValueType::Bool => "true or false",
+            ValueType::Str => "text",
+            ValueType::Bytes | ValueType::FixedBytes(_) => "0x-prefixed hex",
+            ValueType::AccountId => "SS58 address, 0x hex, or alice/bob/charlie/dave/eve/ferdie",
+            ValueType::Unit => "nothing",
+            ValueType::Raw => "SCALE encoding as 0x-prefixed hex",
+            _ => "number",
+        }
+    }
+}
+
+/// Encodes prompt input as a value of type `ty`.
+pub fn encode(ty: ValueType, input: &str) -> Result<Vec<u8>, String> {
+    let input = input.trim();
+    let number = |what: &str| format!("`{}` is not a valid {}", input, what);
+    Ok(match ty {
+        ValueType::Bool => bool::from_str(input).map_err(|_| number("bool"))?.encode(),
+        ValueType::U8 => input.parse::<u8>().map_err(|_| number("u8"))?.encode(),
+        ValueType::U16 => input.parse::<u16>().map_err(|_| number("u16"))?.encode(),
+        ValueType::U32 => input.parse::<u32>().map_err(|_| number("u32"))?.encode(),
+        ValueType::U64 => input.parse::<u64>().map_err(|_| number("u64"))?.encode(),
+        ValueType::U128 => parse_amount(input).ok_or_else(|| number("u128"))?.encode(),
+        ValueType::I8 => input.parse::<i8>().map_err(|_| number("i8"))?.encode(),
+        ValueType::I16 => input.parse::<i16>().map_err(|_| number("i16"))?.encode(),
+        ValueType::I32 => input.parse::<i32>().map_err(|_| number("i32"))?.encode(),
+        ValueType::I64 => input.parse::<i64>().map_err(|_| number("i64"))?.encode(),
+        ValueType::I128 => input.parse::<i128>().map_err(|_| number("i128"))?.encode(),
+        ValueType::Str => input.encode(),
+        ValueType::Bytes => parse_hex(input)?.encode(),
+        ValueType::FixedBytes(len) => {
+            let bytes = parse_hex(input)?;
+            if bytes.len() != len {
+                return Err(format!("expected {} bytes, got {}", len, bytes.len()));
+            }
+            bytes
+        }
+        ValueType::AccountId => parse_account(input)?.to_vec(),
+        ValueType::Unit => Vec::new(),
+        ValueType::Raw => parse_hex(input)?,
+    })
+}
+
+/// Decodes a value of type `ty` for display, falling back to hex if `bytes` does not
+/// hold exactly one such value.
+pub fn decode(ty: ValueType, bytes: &[u8]) -> String {
+    fn exact<T: Decode>(bytes: &[u8]) -> Option<T> {
+        scale::DecodeAll::decode_all(&mut &bytes[..]).ok()
+    }
+
+    let decoded = match ty {
+        ValueType::Bool => exact::<bool>(bytes).map(|v| v.to_string()),
+        ValueType::U8 => exact::<u8>(bytes).map(|v| v.to_string()),
+        ValueType::U16 => exact::<u16>(bytes).map(|v| v.to_string()),
+        ValueType::U32 => exact::<u32>(bytes).map(|v| v.to_string()),
+        ValueType::U64 => exact::<u64>(bytes).map(|v| v.to_string()),
+        ValueType::U128 => exact::<u128>(bytes).map(|v| v.to_string()),
+        ValueType::I8 => exact::<i8>(bytes).map(|v| v.to_string()),
+        ValueType::I16 => exact::<i16>(bytes).map(|v| v.to_string()),
+        ValueType::I32 => exact::<i32>(bytes).map(|v| v.to_string()),
+        ValueType::I64 => exact::<i64>(bytes).map(|v| v.to_string()),
+        ValueType::I128 => exact::<i128>(bytes).map(|v| v.to_string()),
+        ValueType::Str => exact::<String>(bytes).map(|v| format!("{:?}", v)),
+        ValueType::Bytes => exact::<Vec<u8>>(bytes).map(|v| to_hex(&v)),
+        ValueType::AccountId => exact::<[u8; 32]>(bytes).map(|v| AccountId32(v).to_string()),
+        ValueType::Unit if bytes.is_empty() => Some("()".to_owned()),
+        _ => None,
+    };
+    decoded.unwrap_or_else(|| to_hex(bytes))
+}
+
+/// Parses an account given as SS58, 32 bytes of hex, or a well-known dev name.
+pub fn parse_account(input: &str) -> Result<[u8; 32], String> {
+    use subxt_signer::sr25519::dev;
+
+    let dev = match input.to_ascii_lowercase().as_str() {
+        "alice" => Some(dev::alice()),
+        "bob" => Some(dev::bob()),
+        "charlie" => Some(dev::charlie()),
+        "dave" => Some(dev::dave()),
+        "eve" => Some(dev::eve()),
+        "ferdie" => Some(dev::ferdie()),
+        _ => None,
+    };
+    if let Some(keypair) = dev {
+        return Ok(keypair.public_key().0);
+    }
+    if input.starts_with("0x") {
+        let bytes = parse_hex(input)?;
+        return bytes
+            .try_into()
+            .map_err(|bytes: Vec<u8>| format!("expected 32 bytes, got {}", bytes.len()));
+    }
+    AccountId32::from_str(input)
+        .map(|account| account.0)
+        .map_err(|_| format!("`{}` is not an account", input))
+}
+
+/// Parses `0x`-prefixed hex.
+pub fn parse_hex(input: &str) -> Result<Vec<u8>, String> {
+    let digits = input
+        .strip_prefix("0x")
+        .ok_or_else(|| format!("`{}` must start with 0x", input))?;
+    if digits.len() % 2 != 0 {
+        return Err(format!("`{}` has an odd number of hex digits", input));
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16))
+        .collect::<Result<_, _>>()
+        .map_err(|_| format!("`{}` is not valid hex", input))
+}
+
+/// Formats bytes as `0x`-prefixed hex.
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut out = String::from("0x");
+    for byte in bytes {
+        out.push_str(&format!("{:02x}", byte));
+    }
+    out
+}
+
+/// Parses a balance, allowing `_` separators as in Rust literals.
+pub fn parse_amount(input: &str) -> Option<u128> {
+    input.replace('_', "").parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    #[test]
+    fn encodes_primitives() {
+        assert_eq!(encode(ValueType::Bool, "true"), Ok(vec![1]));
+        assert_eq!(encode(ValueType::U32, " 7 "), Ok(vec![7, 0, 0, 0]));
+        assert_eq!(encode(ValueType::I8, "-1"), Ok(vec![0xff]));
+        assert_eq!(encode(ValueType::U128, "1_000").unwrap(), 1000u128.encode());
+        assert!(encode(ValueType::U8, "256").is_err());
+        assert!(encode(ValueType::Bool, "yes").is_err());
+    }
+
+    #[test]
+    fn encodes_strings_and_bytes() {
+        assert_eq!(encode(ValueType::Str, "hi"), Ok(vec![2 << 2, b'h', b'i']));
+        assert_eq!(encode(ValueType::Bytes, "0x0102"), Ok(vec![2 << 2, 1, 2]));
+        assert_eq!(encode(ValueType::FixedBytes(2), "0x0102"), Ok(vec![1, 2]));
+        assert!(encode(ValueType::FixedBytes(3), "0x0102").is_err());
+        assert_eq!(encode(ValueType::Raw, "0x"), Ok(vec![]));
+        assert!(encode(ValueType::Raw, "0x1").is_err());
+        assert!(encode(ValueType::Raw, "12").is_err());
+    }
+
+    #[test]
+    fn accounts_accept_every_spelling() {
+        let from_ss58 = encode(ValueType::AccountId, ALICE).unwrap();
+        assert_eq!(encode(ValueType::AccountId, "alice").unwrap(), from_ss58);
+        assert_eq!(
+            encode(ValueType::AccountId, &to_hex(&from_ss58)).unwrap(),
+            from_ss58
+        );
+        assert!(encode(ValueType::AccountId, "mallory").is_err());
+        assert!(encode(ValueType::AccountId, "0x00").is_err());
+    }
+
+    #[test]
+    fn decodes_for_display() {
+        assert_eq!(decode(ValueType::Bool, &[1]), "true");
+        assert_eq!(decode(ValueType::U128, &42u128.encode()), "42");
+        assert_eq!(decode(ValueType::Str, &"hi".encode()), "\"hi\"");
+        assert_eq!(decode(ValueType::Unit, &[]), "()");
+        let alice = encode(ValueType::AccountId, "alice").unwrap();
+        assert_eq!(decode(ValueType::AccountId, &alice), ALICE);
+    }
+
+    #[test]
+    fn undecodable_values_print_as_hex() {
+        assert_eq!(decode(ValueType::Bool, &[1, 2]), "0x0102");
+        assert_eq!(decode(ValueType::Raw, &[0, 1]), "0x0001");
+    }
+}
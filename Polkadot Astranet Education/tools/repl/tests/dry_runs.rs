@@ -0,0 +1,100 @@
+// tools/repl/tests/dry_runs.rs
+//
+// Checks what the REPL prints for dry runs against golden output.
+//
+// Each file in `tests/fixtures/dry_runs` names a metadata file and a message, and holds
+// a dry-run result in the shape `ContractsApi_call` returns it: the flags, the returned
+// bytes, the gas required and the contract's events, with bytes in hex. The lines
+// printed for it are compared with `tests/golden/<fixture>.txt`. After changing the
+// output on purpose, rerun with `UPDATE_GOLDEN=1` to rewrite the golden files.
+
+use repl::{parse, value::parse_hex, DryRun, Emitted};
+use serde_json::Value;
+use std::{env, fs, path::Path};
+
+/// The `REVERT` bit of the flags a contract returns with.
+const REVERT_FLAG: u64 = 1;
+
+fn hex(value: &Value) -> Vec<u8> {
+    parse_hex(value.as_str().expect("hex string")).unwrap()
+}
+
+fn dry_run(result: &Value) -> DryRun {
+    DryRun {
+        data: hex(&result["data"]),
+        reverted: result["flags"].as_u64().unwrap() & REVERT_FLAG != 0,
+        ref_time: result["gas_required"]["ref_time"].as_u64().unwrap().into(),
+        proof_size: result["gas_required"]["proof_size"]
+            .as_u64()
+            .unwrap()
+            .into(),
+        events: result["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| Emitted {
+                topics: event["topics"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|topic| hex(topic).try_into().expect("32-byte topic"))
+                    .collect(),
+                data: hex(&event["data"]),
+            })
+            .collect(),
+    }
+}
+
+/// Renders one fixture the way the REPL prints it, without the indentation.
+fn render(fixtures: &Path, fixture: &Value) -> String {
+    let name = fixture["metadata"].as_str().unwrap();
+    let json = fs::read_to_string(fixtures.join(name)).unwrap();
+    let metadata = parse(&json).unwrap();
+    let message = metadata
+        .find(fixture["message"].as_str().unwrap())
+        .expect("message in metadata");
+    let lines = match dry_run(&fixture["result"]).report(&metadata, message) {
+        Ok(lines) => lines,
+        Err(err) => vec![format!("error: {}", err)],
+    };
+    format!("{}\n", lines.join("\n"))
+}
+
+#[test]
+fn dry_runs_match_golden_output() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+    let fixtures = root.join("fixtures");
+    let update = env::var_os("UPDATE_GOLDEN").is_some();
+
+    let mut paths: Vec<_> = fs::read_dir(fixtures.join("dry_runs"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no dry-run fixtures found");
+
+    let mut mismatches = Vec::new();
+    for path in paths {
+        let fixture: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let output = render(&fixtures, &fixture);
+        let stem = path.file_stem().unwrap().to_string_lossy();
+        let golden_path = root.join("golden").join(format!("{}.txt", stem));
+        if update {
+            fs::write(&golden_path, &output).unwrap();
+            continue;
+        }
+        let golden = fs::read_to_string(&golden_path)
+            .unwrap_or_else(|err| panic!("{}: {}", golden_path.display(), err));
+        if golden != output {
+            mismatches.push(format!(
+                "{}:\n--- golden\n{}--- printed\n{}",
+                stem, golden, output
+            ));
+        }
+    }
+    assert!(
+        mismatches.is_empty(),
+        "dry-run output differs from golden files (rerun with UPDATE_GOLDEN=1 if intended):\n{}",
+        mismatches.join("\n")
+    );
+}
@@ -0,0 +1,226 @@
+// tools/selector/src/lib.rs
+//
+// ink! identifies constructors and messages by a 4-byte selector: the first four
+// bytes of the BLAKE2b-256 hash of the item's label. For inherent methods the label
+// is the method name (`flip`); for trait implementations it is `Trait::method`.
+// Renaming a message therefore changes its selector and breaks every existing caller,
+// which is what the pinning tests in this crate are there to catch.
+
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use serde_json::Value;
+use std::fmt;
+
+/// A 4-byte ink! selector.
+pub type Selector = [u8; 4];
+
+/// Computes the selector ink! derives for `label`.
+pub fn compute(label: &str) -> Selector {
+    let hash = Blake2b::<U32>::digest(label.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Formats a selector the way contract metadata does, e.g. `0x633aa551`.
+pub fn to_hex(selector: &Selector) -> String {
+    let mut out = String::from("0x");
+    for byte in selector {
+        out.push_str(&format!("{:02x}", byte));
+    }
+    out
+}
+
+/// Parses a selector written as `0x` followed by eight hex digits.
+pub fn from_hex(text: &str) -> Option<Selector> {
+    let digits = text.strip_prefix("0x")?;
+    if digits.len() != 8 {
+        return None;
+    }
+    let mut selector = [0u8; 4];
+    for (i, byte) in selector.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&digits[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(selector)
+}
+
+/// Whether a metadata entry is a constructor or a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Constructor,
+    Message,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Constructor => write!(f, "constructor"),
+            Kind::Message => write!(f, "message"),
+        }
+    }
+}
+
+/// A constructor or message listed in contract metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub kind: Kind,
+    pub label: String,
+    pub selector: Selector,
+}
+
+impl Entry {
+    /// Returns whether the selector is the one derived from the label.
+    ///
+    /// A mismatch is not necessarily a bug: `#[ink(selector = ..)]` overrides the
+    /// derived value on purpose. It does mean callers cannot rely on the name alone.
+    pub fn is_derived(&self) -> bool {
+        compute(&self.label) == self.selector
+    }
+}
+
+/// Errors returned while reading contract metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetadataError {
+    /// The input is not JSON.
+    InvalidJson(String),
+    /// No `spec` object was found, at the top level or under a version key.
+    MissingSpec,
+    /// An entry lacks a label or has a malformed selector.
+    InvalidEntry(String),
+}
+
+impl fmt::Display for MetadataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetadataError::InvalidJson(err) => write!(f, "metadata is not valid JSON: {}", err),
+            MetadataError::MissingSpec => write!(f, "metadata has no contract spec"),
+            MetadataError::InvalidEntry(what) => write!(f, "invalid metadata entry: {}", what),
+        }
+    }
+}
+
+impl std::error::Error for MetadataError {}
+
+/// Lists every constructor and message in a `.contract` or `metadata.json` file.
+///
+/// Newer metadata keeps `spec` at the top level; older versions nest it under the
+/// version key (`"V3": { "spec": .. }`). Both are accepted.
+pub fn entries_from_metadata(json: &str) -> Result<Vec<Entry>, MetadataError> {
+    let root: Value =
+        serde_json::from_str(json).map_err(|err| MetadataError::InvalidJson(err.to_string()))?;
+    let spec = root
+        .get("spec")
+        .or_else(|| {
+            root.as_object()?
+                .iter()
+                .filter(|(key, _)| key.starts_with('V'))
+                .find_map(|(_, versioned)| versioned.get("spec"))
+        })
+        .ok_or(MetadataError::MissingSpec)?;
+
+    let mut entries = Vec::new();
+    for (key, kind) in [("constructors", Kind::Constructor), ("messages", Kind::Message)] {
+        for item in spec.get(key).and_then(Value::as_array).into_iter().flatten() {
+            entries.push(parse_entry(item, kind)?);
+        }
+    }
+    Ok(entries)
+}
+
+fn parse_entry(item: &Value, kind: Kind) -> Result<Entry, MetadataError> {
+    // Metadata V1 stored the label as a list of path segments.
+    let label = match item.get("label").or_else(|| item.get("name")) {
+        Some(Value::String(label)) => label.clone(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .map(|part| part.as_str().unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("::"),
+        _ => return Err(MetadataError::InvalidEntry(format!("{} without label", kind))),
+    };
+    let selector = item
+        .get("selector")
+        .and_then(Value::as_str)
+        .and_then(from_hex)
+        .ok_or_else(|| MetadataError::InvalidEntry(format!("{} `{}` selector", kind, label)))?;
+    Ok(Entry {
+        kind,
+        label,
+        selector,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_known_selectors() {
+        assert_eq!(compute("flip"), [0x63, 0x3a, 0xa5, 0x51]);
+        assert_eq!(compute("get"), [0x2f, 0x86, 0x5b, 0xd9]);
+        assert_eq!(compute("new"), [0x9b, 0xae, 0x9d, 0x5e]);
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        let selector = [0x00, 0x1f, 0xa0, 0xff];
+        assert_eq!(to_hex(&selector), "0x001fa0ff");
+        assert_eq!(from_hex("0x001fa0ff"), Some(selector));
+        assert_eq!(from_hex("001fa0ff"), None);
+        assert_eq!(from_hex("0x001fa0f"), None);
+        assert_eq!(from_hex("0x001fa0fg"), None);
+    }
+
+    #[test]
+    fn reads_current_metadata_layout() {
+        let json = r#"{
+            "spec": {
+                "constructors": [{ "label": "new", "selector": "0x9bae9d5e" }],
+                "messages": [
+                    { "label": "flip", "selector": "0x633aa551" },
+                    { "label": "get", "selector": "0xdeadbeef" }
+                ]
+            }
+        }"#;
+        let entries = entries_from_metadata(json).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].kind, Kind::Constructor);
+        assert!(entries[0].is_derived());
+        assert!(entries[1].is_derived());
+        assert!(!entries[2].is_derived());
+    }
+
+    #[test]
+    fn reads_versioned_metadata_layout() {
+        let json = r#"{
+            "V3": {
+                "spec": {
+                    "constructors": [],
+                    "messages": [{ "label": "flip", "selector": "0x633aa551" }]
+                }
+            }
+        }"#;
+        let entries = entries_from_metadata(json).unwrap();
+        assert_eq!(entries[0].label, "flip");
+    }
+
+    #[test]
+    fn reads_path_labels() {
+        let json = r#"{
+            "V1": {
+                "spec": {
+                    "messages": [{ "name": ["Erc20", "transfer"], "selector": "0x00000000" }]
+                }
+            }
+        }"#;
+        let entries = entries_from_metadata(json).unwrap();
+        assert_eq!(entries[0].label, "Erc20::transfer");
+    }
+
+    #[test]
+    fn rejects_bad_metadata() {
+        assert!(matches!(entries_from_metadata("{"), Err(MetadataError::InvalidJson(_))));
+        assert_eq!(entries_from_metadata("{}"), Err(MetadataError::MissingSpec));
+        assert!(matches!(
+            entries_from_metadata(r#"{"spec":{"messages":[{"label":"x"}]}}"#),
+            Err(MetadataError::InvalidEntry(_))
+        ));
+    }
+}
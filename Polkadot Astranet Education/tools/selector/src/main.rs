@@ -0,0 +1,87 @@
+// tools/selector/src/main.rs
+//
+// Usage:
+//   selector compute flip get Erc20::transfer
+//   selector verify target/ink/flipper.contract
+
+use clap::{Parser, Subcommand};
+use std::{fs, path::PathBuf, process::ExitCode};
+
+#[derive(Parser)]
+#[command(about = "Compute and verify ink! selectors")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the selector of each label.
+    Compute {
+        /// Message or constructor labels, e.g. `flip` or `Trait::method`.
+        #[arg(required = true)]
+        labels: Vec<String>,
+    },
+    /// Check that every selector in a metadata file matches its label.
+    Verify {
+        /// Path to a `.contract` bundle or `metadata.json`.
+        metadata: PathBuf,
+    },
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Compute { labels } => {
+            for label in labels {
+                println!("{}  {}", selector::to_hex(&selector::compute(&label)), label);
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Verify { metadata } => verify(&metadata),
+    }
+}
+
+fn verify(path: &PathBuf) -> ExitCode {
+    let json = match fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(err) => {
+            eprintln!("error: cannot read {}: {}", path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let entries = match selector::entries_from_metadata(&json) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut mismatches = 0;
+    for entry in &entries {
+        let status = if entry.is_derived() {
+            "ok"
+        } else {
+            mismatches += 1;
+            "MISMATCH"
+        };
+        println!(
+            "{:<8} {:<11} {}  {} (derived {})",
+            status,
+            entry.kind,
+            selector::to_hex(&entry.selector),
+            entry.label,
+            selector::to_hex(&selector::compute(&entry.label)),
+        );
+    }
+
+    if mismatches > 0 {
+        eprintln!(
+            "{} of {} selectors do not match their labels (custom selectors are reported too)",
+            mismatches,
+            entries.len()
+        );
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,382 @@
+// tools/size-report/src/lib.rs
+//
+// Every byte of a contract's Wasm is paid for: uploading it costs a storage deposit
+// proportional to its size, and chains cap how large code may be. This crate breaks a
+// contract's Wasm down by section and by function, so the "keeping contracts small"
+// lesson can point at what takes the space, and checks it against a size budget.
+//
+// Sizes count each section with its header, so the sections of a module add up to its
+// size less the 8-byte preamble. The budget applies to the uploaded size, which leaves
+// out custom sections: `cargo contract build --keep-debug-symbols` keeps the `name`
+// section that function names are read from, and stripping it would leave only
+// exports and indices to go by.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use wasmparser::{KnownCustom, Name, Parser, Payload};
+
+/// The built-in budgets.
+pub const BUDGETS: &str = include_str!("../budgets.toml");
+
+/// A section and the bytes it takes, header included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// `code`, `data`, ..., or `custom:NAME` for a custom section.
+    pub name: String,
+    pub size: usize,
+}
+
+/// A function defined in the module and the bytes of its body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Function {
+    /// Index in the function index space, which counts imported functions first.
+    pub index: u32,
+    /// Its name from the `name` section, else the name it is exported as, else
+    /// `func[INDEX]`.
+    pub name: String,
+    pub size: usize,
+}
+
+/// The size breakdown of a module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    pub total: usize,
+    /// The size without custom sections, which is what gets uploaded.
+    pub uploaded: usize,
+    /// In the order they appear.
+    pub sections: Vec<Section>,
+    /// Largest first.
+    pub functions: Vec<Function>,
+}
+
+/// Breaks `wasm` down by section and function.
+pub fn analyze(wasm: &[u8]) -> Result<Analysis, String> {
+    let mut sections = Vec::new();
+    let mut bodies = Vec::new();
+    let mut imported_functions = 0;
+    let mut names = BTreeMap::new();
+    let mut exports = BTreeMap::new();
+
+    for payload in Parser::new(0).parse_all(wasm) {
+        let payload = payload.map_err(|err| err.to_string())?;
+        match &payload {
+            Payload::ImportSection(imports) => {
+                for import in imports.clone() {
+                    let import = import.map_err(|err| err.to_string())?;
+                    if let wasmparser::TypeRef::Func(_) = import.ty {
+                        imported_functions += 1;
+                    }
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader.clone() {
+                    let export = export.map_err(|err| err.to_string())?;
+                    if export.kind == wasmparser::ExternalKind::Func {
+                        exports.insert(export.index, export.name.to_owned());
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) => bodies.push(body.range().len()),
+            Payload::CustomSection(custom) => {
+                if let KnownCustom::Name(reader) = custom.as_known() {
+                    // A malformed name section only costs the names.
+                    for name in reader.into_iter().flatten() {
+                        if let Name::Function(map) = name {
+                            for naming in map.into_iter().flatten() {
+                                names.insert(naming.index, naming.name.to_owned());
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        if let Some((id, range)) = payload.as_section() {
+            let name = match &payload {
+                Payload::CustomSection(custom) => format!("custom:{}", custom.name()),
+                _ => section_name(id).to_owned(),
+            };
+            sections.push(Section {
+                name,
+                size: 1 + leb128_len(range.len()) + range.len(),
+            });
+        }
+    }
+
+    let mut functions: Vec<Function> = bodies
+        .into_iter()
+        .enumerate()
+        .map(|(local, size)| {
+            let index = imported_functions + local as u32;
+            let name = names
+                .get(&index)
+                .or_else(|| exports.get(&index))
+                .cloned()
+                .unwrap_or_else(|| format!("func[{}]", index));
+            Function { index, name, size }
+        })
+        .collect();
+    functions.sort_by(|a, b| b.size.cmp(&a.size).then(a.index.cmp(&b.index)));
+
+    let custom: usize = sections
+        .iter()
+        .filter(|section| section.name.starts_with("custom:"))
+        .map(|section| section.size)
+        .sum();
+    Ok(Analysis {
+        total: wasm.len(),
+        uploaded: wasm.len() - custom,
+        sections,
+        functions,
+    })
+}
+
+fn section_name(id: u8) -> &'static str {
+    match id {
+        1 => "type",
+        2 => "import",
+        3 => "function",
+        4 => "table",
+        5 => "memory",
+        6 => "global",
+        7 => "export",
+        8 => "start",
+        9 => "element",
+        10 => "code",
+        11 => "data",
+        12 => "datacount",
+        13 => "tag",
+        _ => "unknown",
+    }
+}
+
+/// Bytes of the unsigned LEB128 encoding of `value`.
+fn leb128_len(mut value: usize) -> usize {
+    let mut len = 1;
+    while value >= 0x80 {
+        value >>= 7;
+        len += 1;
+    }
+    len
+}
+
+/// Size budgets by contract.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Budgets {
+    /// Budget of contracts without one of their own.
+    pub default: usize,
+    #[serde(default)]
+    pub contracts: BTreeMap<String, usize>,
+}
+
+impl Budgets {
+    /// Reads budgets in the format of `budgets.toml`.
+    pub fn parse(toml: &str) -> Result<Self, String> {
+        toml::from_str(toml).map_err(|err| err.message().to_owned())
+    }
+
+    /// Returns the built-in budgets.
+    pub fn builtin() -> Self {
+        Self::parse(BUDGETS).expect("built-in budgets.toml is valid")
+    }
+
+    /// Takes the default of `other` and adds its budgets, replacing those of the same
+    /// contracts.
+    pub fn extend(&mut self, other: Budgets) {
+        self.default = other.default;
+        self.contracts.extend(other.contracts);
+    }
+
+    /// The budget of the contract `name`.
+    pub fn of(&self, name: &str) -> usize {
+        self.contracts.get(name).copied().unwrap_or(self.default)
+    }
+}
+
+/// Reports on one contract: whether it is within `budget`, and the lines to print,
+/// listing the `top` largest functions.
+pub fn report(name: &str, analysis: &Analysis, budget: usize, top: usize) -> (bool, Vec<String>) {
+    let within = analysis.uploaded <= budget;
+    let verdict = if within {
+        format!("{}% of budget", analysis.uploaded * 100 / budget.max(1))
+    } else {
+        format!("OVER BUDGET by {} bytes", analysis.uploaded - budget)
+    };
+    let mut lines = vec![format!(
+        "{}: {} bytes uploaded, budget {} ({})",
+        name, analysis.uploaded, budget, verdict
+    )];
+    if analysis.total != analysis.uploaded {
+        lines.push(format!("  {} bytes with custom sections", analysis.total));
+    }
+
+    lines.push("  sections:".to_owned());
+    for section in &analysis.sections {
+        lines.push(format!(
+            "    {:>8}  {:>5.1}%  {}",
+            section.size,
+            percent(section.size, analysis.total),
+            section.name
+        ));
+    }
+
+    let code: usize = analysis
+        .functions
+        .iter()
+        .map(|function| function.size)
+        .sum();
+    let shown = analysis.functions.len().min(top);
+    if shown > 0 {
+        lines.push(format!(
+            "  largest functions ({} of {}, % of code):",
+            shown,
+            analysis.functions.len()
+        ));
+    }
+    for function in &analysis.functions[..shown] {
+        lines.push(format!(
+            "    {:>8}  {:>5.1}%  {}",
+            function.size,
+            percent(function.size, code),
+            function.name
+        ));
+    }
+    (within, lines)
+}
+
+fn percent(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / whole as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A contract-shaped module: an imported host function, `deploy` and `call`
+    /// exports, a helper, and some data.
+    const CONTRACT: &str = r#"
+        (module
+          (import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+          (memory (export "memory") 1)
+          (func $deploy (export "deploy"))
+          (func $call (export "call")
+            (call $helper (i32.const 1))
+            (call $helper (i32.const 2))
+            (call $helper (i32.const 3))
+            (call $seal_return (i32.const 0) (i32.const 0) (i32.const 4)))
+          (func $helper (param i32)
+            (drop (i32.add (local.get 0) (i32.const 41))))
+          (data (i32.const 0) "flipper state"))
+    "#;
+
+    fn wasm() -> Vec<u8> {
+        wat::parse_str(CONTRACT).unwrap()
+    }
+
+    #[test]
+    fn sections_add_up_to_the_module() {
+        let wasm = wasm();
+        let analysis = analyze(&wasm).unwrap();
+        let sections: usize = analysis.sections.iter().map(|section| section.size).sum();
+        // Less the magic number and version.
+        assert_eq!(sections + 8, wasm.len());
+        let names: Vec<&str> = analysis
+            .sections
+            .iter()
+            .map(|section| section.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "type",
+                "import",
+                "function",
+                "memory",
+                "export",
+                "code",
+                "data",
+                "custom:name"
+            ]
+        );
+        assert_eq!(analysis.total, wasm.len());
+        assert_eq!(analysis.uploaded, wasm.len() - analysis.sections[7].size);
+    }
+
+    #[test]
+    fn functions_are_named_and_sorted_by_size() {
+        let analysis = analyze(&wasm()).unwrap();
+        let names: Vec<&str> = analysis
+            .functions
+            .iter()
+            .map(|function| function.name.as_str())
+            .collect();
+        assert_eq!(names, ["call", "helper", "deploy"]);
+        // The imported function comes first in the index space and has no body.
+        assert_eq!(analysis.functions[0].index, 2);
+    }
+
+    #[test]
+    fn stripped_functions_fall_back_to_exports_and_indices() {
+        let stripped: Vec<u8> = {
+            let wasm = wasm();
+            let analysis = analyze(&wasm).unwrap();
+            wasm[..analysis.uploaded].to_vec()
+        };
+        let analysis = analyze(&stripped).unwrap();
+        assert_eq!(analysis.total, analysis.uploaded);
+        let names: Vec<&str> = analysis
+            .functions
+            .iter()
+            .map(|function| function.name.as_str())
+            .collect();
+        assert_eq!(names, ["call", "func[3]", "deploy"]);
+    }
+
+    #[test]
+    fn budgets_are_reported() {
+        let analysis = analyze(&wasm()).unwrap();
+        let (within, lines) = report("flipper", &analysis, 1_000, 2);
+        assert!(within);
+        assert!(lines[0].starts_with(&format!("flipper: {} bytes uploaded", analysis.uploaded)));
+        assert!(lines.iter().any(|line| line.ends_with("custom:name")));
+        assert!(lines.contains(&"  largest functions (2 of 3, % of code):".to_owned()));
+        assert!(lines.last().unwrap().ends_with("helper"));
+
+        let (within, lines) = report("flipper", &analysis, 10, 0);
+        assert!(!within);
+        assert!(lines[0].ends_with(&format!("OVER BUDGET by {} bytes)", analysis.uploaded - 10)));
+        assert!(!lines.iter().any(|line| line.contains("largest functions")));
+    }
+
+    #[test]
+    fn budgets_parse_and_override() {
+        let mut budgets = Budgets::builtin();
+        assert_eq!(budgets.of("flipper"), budgets.default);
+        budgets.extend(
+            Budgets::parse("default = 16_384\n[contracts]\nvesting_factory = 65_536\n").unwrap(),
+        );
+        assert_eq!(budgets.of("flipper"), 16_384);
+        assert_eq!(budgets.of("vesting_factory"), 65_536);
+        assert!(Budgets::parse("[contracts]\nerc20 = 1\n").is_err());
+        assert!(Budgets::parse("default = 1\nmax = 2\n").is_err());
+    }
+
+    #[test]
+    fn invalid_modules_are_rejected() {
+        assert!(analyze(b"\0asm\x01\0\0\0\x0a\x05").is_err());
+        assert!(analyze(b"not wasm").is_err());
+    }
+
+    #[test]
+    fn leb128_lengths() {
+        assert_eq!(leb128_len(0), 1);
+        assert_eq!(leb128_len(127), 1);
+        assert_eq!(leb128_len(128), 2);
+        assert_eq!(leb128_len(16_384), 3);
+    }
+}
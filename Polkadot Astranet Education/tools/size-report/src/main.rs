@@ -0,0 +1,170 @@
+// tools/size-report/src/main.rs
+//
+// Usage:
+//   size-report                             build every contract crate and report on it
+//   size-report --no-build                  report on the Wasm built earlier
+//   size-report dist/erc20.contract a.wasm  report on given .wasm or .contract files
+//   size-report --budget 16384 --top 20     a tighter default budget, more functions
+//
+// A contract crate is any directory under `--contracts` holding a `Cargo.toml`.
+// Contracts are built with `--keep-debug-symbols`, so functions can be reported by
+// name. Exits with 1 if any contract is over its budget.
+
+use clap::Parser;
+use contract_crates::contract_crates;
+use size_report::{analyze, report, Budgets};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+};
+
+#[derive(Parser)]
+#[command(about = "Report the Wasm size of the example contracts by section and function")]
+struct Cli {
+    /// `.wasm` or `.contract` files to report on instead of the contract crates.
+    files: Vec<PathBuf>,
+    /// Directory whose subdirectories are contract crates.
+    #[arg(long, default_value = "examples/demo-contracts")]
+    contracts: PathBuf,
+    /// Report on the Wasm already built instead of running `cargo contract build`.
+    #[arg(long)]
+    no_build: bool,
+    /// File of budgets adding to or overriding the built-in ones.
+    #[arg(long)]
+    budgets: Option<PathBuf>,
+    /// Default budget in bytes, for contracts without one of their own.
+    #[arg(long)]
+    budget: Option<usize>,
+    /// How many of the largest functions to list per contract.
+    #[arg(long, default_value_t = 10)]
+    top: usize,
+}
+
+fn main() -> ExitCode {
+    match run(&Cli::parse()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Returns whether every contract is within its budget.
+fn run(cli: &Cli) -> Result<bool, String> {
+    let mut budgets = Budgets::builtin();
+    if let Some(path) = &cli.budgets {
+        let toml =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        budgets
+            .extend(Budgets::parse(&toml).map_err(|err| format!("{}: {}", path.display(), err))?);
+    }
+    if let Some(budget) = cli.budget {
+        budgets.default = budget;
+    }
+
+    let files = if cli.files.is_empty() {
+        let mut files = Vec::new();
+        for krate in contract_crates(&cli.contracts)? {
+            if !cli.no_build {
+                build(&krate)?;
+            }
+            files.extend(built_wasm(&krate)?);
+        }
+        if files.is_empty() {
+            return Err(format!(
+                "no Wasm under the target/ink of the contract crates in {}",
+                cli.contracts.display()
+            ));
+        }
+        files
+    } else {
+        cli.files.clone()
+    };
+
+    let mut over = Vec::new();
+    for path in &files {
+        let wasm = read_wasm(path)?;
+        let analysis = analyze(&wasm).map_err(|err| format!("{}: {}", path.display(), err))?;
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        let (within, lines) = report(name, &analysis, budgets.of(name), cli.top);
+        for line in lines {
+            println!("{}", line);
+        }
+        println!();
+        if !within {
+            over.push(name.to_owned());
+        }
+    }
+
+    if over.is_empty() {
+        println!("{} contract(s) within budget", files.len());
+    } else {
+        println!("over budget: {}", over.join(", "));
+    }
+    Ok(over.is_empty())
+}
+
+/// Reads the Wasm of a `.wasm` file, or out of a `.contract` bundle.
+fn read_wasm(path: &Path) -> Result<Vec<u8>, String> {
+    let bytes = fs::read(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    if path.extension().and_then(|ext| ext.to_str()) != Some("contract") {
+        return Ok(bytes);
+    }
+    let bundle: serde_json::Value =
+        serde_json::from_slice(&bytes).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let hex = bundle["source"]["wasm"]
+        .as_str()
+        .ok_or_else(|| format!("{}: the bundle has no source.wasm", path.display()))?;
+    decode_hex(hex.trim_start_matches("0x"))
+        .ok_or_else(|| format!("{}: source.wasm is not hex", path.display()))
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn build(krate: &Path) -> Result<(), String> {
+    println!("building {}", krate.display());
+    let status = Command::new("cargo")
+        .args([
+            "contract",
+            "build",
+            "--release",
+            "--keep-debug-symbols",
+            "--manifest-path",
+        ])
+        .arg(krate.join("Cargo.toml"))
+        .status()
+        .map_err(|err| format!("cannot run cargo contract: {}", err))?;
+    if !status.success() {
+        return Err(format!(
+            "cargo contract build failed for {}",
+            krate.display()
+        ));
+    }
+    Ok(())
+}
+
+fn built_wasm(krate: &Path) -> Result<Vec<PathBuf>, String> {
+    let ink_dir = krate.join("target").join("ink");
+    let entries =
+        fs::read_dir(&ink_dir).map_err(|err| format!("{}: {}", ink_dir.display(), err))?;
+    let mut wasm: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("wasm"))
+        .collect();
+    wasm.sort();
+    Ok(wasm)
+}
@@ -0,0 +1,165 @@
+// tools/storage-dump/src/layout.rs
+//
+// Walks the `storage` section of contract metadata and lists where each field lives.
+
+use blake2::{
+    digest::consts::{U16, U32},
+    Blake2b, Digest,
+};
+use serde_json::Value;
+
+/// A field stored under a fixed 32-byte key (ink! 3).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    /// Dotted path of the field, e.g. `ownable.owner`.
+    pub path: String,
+    pub key: [u8; 32],
+    pub ty: u32,
+}
+
+/// A storage root (ink! 4 and later): the fields packed under `key`, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Root {
+    pub path: String,
+    pub key: Vec<u8>,
+    /// Path and type of every plain field stored in this root's value.
+    pub leaves: Vec<(String, u32)>,
+}
+
+/// Where the fields of a contract are stored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Layout {
+    pub cells: Vec<Cell>,
+    pub roots: Vec<Root>,
+    /// Paths of fields whose keys cannot be derived from the layout alone.
+    pub opaque: Vec<String>,
+}
+
+/// Reads the storage layout section of the metadata.
+pub fn parse(storage: &Value) -> Layout {
+    let mut layout = Layout::default();
+    walk(storage, "", None, &mut layout);
+    layout
+}
+
+/// Returns the child-trie key `pallet-contracts` stores a fixed key under.
+pub fn fixed_trie_key(key: &[u8; 32]) -> Vec<u8> {
+    Blake2b::<U32>::digest(key).to_vec()
+}
+
+/// Returns the child-trie key `pallet-contracts` stores a variable key under.
+pub fn variable_trie_key(key: &[u8]) -> Vec<u8> {
+    let mut out = Blake2b::<U16>::digest(key).to_vec();
+    out.extend_from_slice(key);
+    out
+}
+
+/// Returns the original key of a trie key produced by `variable_trie_key`.
+pub fn variable_key(trie_key: &[u8]) -> Option<&[u8]> {
+    if trie_key.len() < 16 {
+        return None;
+    }
+    let (hash, key) = trie_key.split_at(16);
+    (Blake2b::<U16>::digest(key).as_slice() == hash).then_some(key)
+}
+
+fn walk(node: &Value, path: &str, root: Option<usize>, layout: &mut Layout) {
+    let join = |name: &str| {
+        if path.is_empty() {
+            name.to_owned()
+        } else {
+            format!("{}.{}", path, name)
+        }
+    };
+
+    if let Some(cell) = node.get("cell") {
+        let key = cell
+            .get("key")
+            .and_then(Value::as_str)
+            .and_then(crate::from_hex)
+            .and_then(|key| <[u8; 32]>::try_from(key).ok());
+        match (key, type_id(cell)) {
+            (Some(key), Some(ty)) => layout.cells.push(Cell {
+                path: path.to_owned(),
+                key,
+                ty,
+            }),
+            _ => layout.opaque.push(path.to_owned()),
+        }
+    } else if let Some(leaf) = node.get("leaf") {
+        match (root, type_id(leaf)) {
+            (Some(root), Some(ty)) => layout.roots[root].leaves.push((path.to_owned(), ty)),
+            _ => layout.opaque.push(path.to_owned()),
+        }
+    } else if let Some(new_root) = node.get("root") {
+        let key = new_root
+            .get("root_key")
+            .and_then(Value::as_str)
+            .and_then(crate::from_hex);
+        match key {
+            Some(key) => {
+                layout.roots.push(Root {
+                    path: path.to_owned(),
+                    key,
+                    leaves: Vec::new(),
+                });
+                let index = layout.roots.len() - 1;
+                walk(&new_root["layout"], path, Some(index), layout);
+            }
+            None => layout.opaque.push(path.to_owned()),
+        }
+    } else if let Some(fields) = node.pointer("/struct/fields").and_then(Value::as_array) {
+        for field in fields {
+            let name = field.get("name").and_then(Value::as_str).unwrap_or("?");
+            walk(&field["layout"], &join(name), root, layout);
+        }
+    } else if node.is_object() {
+        // `hash`, `array` and `enum` layouts place values at computed keys.
+        layout.opaque.push(path.to_owned());
+    }
+}
+
+fn type_id(node: &Value) -> Option<u32> {
+    node.get("ty").and_then(Value::as_u64).map(|ty| ty as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variable_keys_round_trip() {
+        let trie_key = variable_trie_key(&[1, 2, 3, 4]);
+        assert_eq!(trie_key.len(), 16 + 4);
+        assert_eq!(variable_key(&trie_key), Some(&[1u8, 2, 3, 4][..]));
+        assert_eq!(variable_key(&fixed_trie_key(&[0; 32])), None);
+    }
+
+    #[test]
+    fn nested_structs_get_dotted_paths() {
+        let storage = serde_json::json!({ "struct": { "fields": [
+            { "name": "ownable", "layout": { "struct": { "fields": [
+                { "name": "owner", "layout": { "cell": { "key": format!("0x{}", "00".repeat(32)), "ty": 0 } } }
+            ] } } }
+        ] } });
+        let layout = parse(&storage);
+        assert_eq!(layout.cells[0].path, "ownable.owner");
+    }
+
+    #[test]
+    fn leaves_belong_to_their_nearest_root() {
+        let storage = serde_json::json!({ "root": { "root_key": "0x00000000", "layout": { "struct": { "fields": [
+            { "name": "a", "layout": { "leaf": { "key": "0x00000000", "ty": 0 } } },
+            { "name": "m", "layout": { "root": { "root_key": "0x01000000", "layout": { "leaf": { "key": "0x01000000", "ty": 1 } } } } },
+            { "name": "b", "layout": { "leaf": { "key": "0x00000000", "ty": 2 } } }
+        ] } } } });
+        let layout = parse(&storage);
+        assert_eq!(layout.roots.len(), 2);
+        assert_eq!(
+            layout.roots[0].leaves,
+            vec![("a".to_owned(), 0), ("b".to_owned(), 2)]
+        );
+        assert_eq!(layout.roots[1].path, "m");
+        assert_eq!(layout.roots[1].leaves, vec![("m".to_owned(), 1)]);
+    }
+}
@@ -0,0 +1,269 @@
+// tools/storage-dump/src/lib.rs
+//
+// A contract's storage lives in its own child trie, keyed by whatever the contract
+// passed to `seal_set_storage`, hashed by `pallet-contracts`:
+//
+// - ink! 3 uses fixed 32-byte keys, one per storage cell, and the trie key is
+//   `blake2_256(key)`. The hash cannot be reversed, so cells are found by hashing
+//   every key the metadata layout lists. Entries of `HashMap`s and other hashed
+//   layouts derive their keys from the map keys and cannot be named this way.
+// - ink! 4 and later use short variable keys, and the trie key is
+//   `blake2_128(key) ++ key`, so the original key is readable from the trie key. All
+//   plain fields under one storage root are packed into a single value, encoded in
+//   field order; `Mapping` entries live under their own root, with the SCALE-encoded
+//   map key appended to the root key.
+//
+// `dump` takes the metadata and the raw trie entries and produces JSON with every
+// value it could place, plus the entries it could not.
+
+pub mod layout;
+
+use layout::Root;
+use scale_info::PortableRegistry;
+use serde_json::{json, Map, Value};
+use std::fmt;
+
+/// A raw child-trie entry: hashed key and encoded value.
+pub type Entry = (Vec<u8>, Vec<u8>);
+
+/// Errors returned while decoding storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DumpError {
+    /// The metadata is not JSON or lacks the storage layout or type registry.
+    InvalidMetadata(String),
+}
+
+impl fmt::Display for DumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DumpError::InvalidMetadata(what) => write!(f, "invalid metadata: {}", what),
+        }
+    }
+}
+
+impl std::error::Error for DumpError {}
+
+/// Decodes raw child-trie `entries` using the storage layout and types in `metadata`.
+pub fn dump(metadata: &str, entries: &[Entry]) -> Result<Value, DumpError> {
+    let root: Value = serde_json::from_str(metadata)
+        .map_err(|err| DumpError::InvalidMetadata(err.to_string()))?;
+    let versioned = if root.get("storage").is_some() {
+        &root
+    } else {
+        root.as_object()
+            .and_then(|object| {
+                object
+                    .iter()
+                    .find(|(key, value)| key.starts_with('V') && value.get("storage").is_some())
+            })
+            .map(|(_, versioned)| versioned)
+            .ok_or_else(|| DumpError::InvalidMetadata("no storage layout".to_owned()))?
+    };
+    let registry: PortableRegistry = serde_json::from_value(
+        json!({ "types": versioned.get("types").cloned().unwrap_or_default() }),
+    )
+    .map_err(|err| DumpError::InvalidMetadata(format!("types: {}", err)))?;
+    let layout = layout::parse(&versioned["storage"]);
+
+    let mut used = vec![false; entries.len()];
+    let mut fields = Map::new();
+    let mut mappings = Map::new();
+
+    for cell in &layout.cells {
+        let trie_key = layout::fixed_trie_key(&cell.key);
+        if let Some(index) = entries.iter().position(|(key, _)| *key == trie_key) {
+            used[index] = true;
+            fields.insert(
+                cell.path.clone(),
+                decode(&registry, cell.ty, &entries[index].1),
+            );
+        }
+    }
+
+    for root in &layout.roots {
+        for (index, (key, value)) in entries.iter().enumerate() {
+            let raw = match layout::variable_key(key) {
+                Some(raw) if raw.starts_with(&root.key) => raw,
+                _ => continue,
+            };
+            if raw.len() == root.key.len() {
+                used[index] = true;
+                decode_packed(&registry, root, value, &mut fields);
+            } else if !root.path.is_empty() && !root.leaves.is_empty() {
+                // Only nested roots hold mappings; the contract's own root never does.
+                used[index] = true;
+                let ty = root.leaves[0].1;
+                let mapping = mappings
+                    .entry(root.path.clone())
+                    .or_insert_with(|| Value::Array(Vec::new()));
+                mapping
+                    .as_array_mut()
+                    .expect("mappings are arrays")
+                    .push(json!({
+                        "key": to_hex(&raw[root.key.len()..]),
+                        "value": decode(&registry, ty, value),
+                    }));
+            }
+        }
+    }
+
+    let unmatched: Vec<Value> = entries
+        .iter()
+        .zip(&used)
+        .filter(|(_, used)| !**used)
+        .map(|((key, value), _)| json!({ "key": to_hex(key), "value": to_hex(value) }))
+        .collect();
+
+    Ok(json!({
+        "fields": fields,
+        "mappings": mappings,
+        "opaque": layout.opaque,
+        "unmatched": unmatched,
+    }))
+}
+
+/// Decodes the plain fields of a root, which are stored back to back in one value.
+fn decode_packed(
+    registry: &PortableRegistry,
+    root: &Root,
+    value: &[u8],
+    fields: &mut Map<String, Value>,
+) {
+    let mut input = value;
+    for (path, ty) in &root.leaves {
+        match scale_value::scale::decode_as_type(&mut input, *ty, registry) {
+            Ok(decoded) => {
+                fields.insert(path.clone(), to_json(decoded));
+            }
+            Err(_) => {
+                fields.insert(path.clone(), json!({ "undecodable": to_hex(input) }));
+                return;
+            }
+        }
+    }
+}
+
+/// Decodes a whole value, falling back to hex if it does not decode exactly.
+fn decode(registry: &PortableRegistry, ty: u32, bytes: &[u8]) -> Value {
+    let mut input = bytes;
+    match scale_value::scale::decode_as_type(&mut input, ty, registry) {
+        Ok(decoded) if input.is_empty() => to_json(decoded),
+        _ => json!({ "undecodable": to_hex(bytes) }),
+    }
+}
+
+fn to_json(value: scale_value::Value<u32>) -> Value {
+    serde_json::to_value(value.remove_context()).unwrap_or(Value::Null)
+}
+
+/// Formats bytes as `0x`-prefixed hex.
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut out = String::from("0x");
+    for byte in bytes {
+        out.push_str(&format!("{:02x}", byte));
+    }
+    out
+}
+
+/// Parses `0x`-prefixed hex.
+pub fn from_hex(text: &str) -> Option<Vec<u8>> {
+    let digits = text.strip_prefix("0x")?;
+    if digits.len() % 2 != 0 {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use layout::{fixed_trie_key, variable_trie_key};
+
+    /// ink! 3 layout of a flipper-like contract with a `HashMap` field.
+    const V3: &str = r#"{ "V3": {
+        "storage": { "struct": { "fields": [
+            { "name": "value", "layout": { "cell": { "key": "0x0000000000000000000000000000000000000000000000000000000000000000", "ty": 0 } } },
+            { "name": "count", "layout": { "cell": { "key": "0x0100000000000000000000000000000000000000000000000000000000000000", "ty": 1 } } },
+            { "name": "balances", "layout": { "hash": { "offset": "0x0200000000000000000000000000000000000000000000000000000000000000", "layout": { "cell": { "key": "0x0200000000000000000000000000000000000000000000000000000000000000", "ty": 1 } } } } }
+        ] } },
+        "types": [
+            { "id": 0, "type": { "def": { "primitive": "bool" } } },
+            { "id": 1, "type": { "def": { "primitive": "u32" } } }
+        ]
+    } }"#;
+
+    /// ink! 4 layout with two packed fields and a `Mapping<[u8; 2], u32>`.
+    const V4: &str = r#"{
+        "version": "4",
+        "storage": { "root": { "root_key": "0x00000000", "layout": { "struct": { "name": "Counter", "fields": [
+            { "name": "value", "layout": { "leaf": { "key": "0x00000000", "ty": 0 } } },
+            { "name": "count", "layout": { "leaf": { "key": "0x00000000", "ty": 1 } } },
+            { "name": "scores", "layout": { "root": { "root_key": "0x0a0b0c0d", "layout": { "leaf": { "key": "0x0a0b0c0d", "ty": 1 } } } } }
+        ] } } } },
+        "types": [
+            { "id": 0, "type": { "def": { "primitive": "bool" } } },
+            { "id": 1, "type": { "def": { "primitive": "u32" } } }
+        ]
+    }"#;
+
+    fn cell_key(first: u8) -> [u8; 32] {
+        let mut key = [0u8; 32];
+        key[0] = first;
+        key
+    }
+
+    #[test]
+    fn decodes_ink3_cells() {
+        let entries = vec![
+            (fixed_trie_key(&cell_key(0)), vec![1]),
+            (fixed_trie_key(&cell_key(1)), 7u32.to_le_bytes().to_vec()),
+            (vec![0xaa; 32], vec![5, 0, 0, 0]),
+        ];
+        let dump = dump(V3, &entries).unwrap();
+        assert_eq!(dump["fields"]["value"], json!(true));
+        assert_eq!(dump["fields"]["count"], json!(7));
+        assert_eq!(dump["opaque"], json!(["balances"]));
+        assert_eq!(dump["unmatched"][0]["key"], json!(to_hex(&[0xaa; 32])));
+    }
+
+    #[test]
+    fn decodes_ink4_packed_root_and_mapping() {
+        let mut mapping_key = vec![0x0a, 0x0b, 0x0c, 0x0d];
+        mapping_key.extend_from_slice(&[0x01, 0x02]);
+        let entries = vec![
+            (variable_trie_key(&[0, 0, 0, 0]), vec![1, 3, 0, 0, 0]),
+            (variable_trie_key(&mapping_key), 9u32.to_le_bytes().to_vec()),
+        ];
+        let dump = dump(V4, &entries).unwrap();
+        assert_eq!(dump["fields"]["value"], json!(true));
+        assert_eq!(dump["fields"]["count"], json!(3));
+        assert_eq!(
+            dump["mappings"]["scores"],
+            json!([{ "key": "0x0102", "value": 9 }])
+        );
+        assert_eq!(dump["unmatched"], json!([]));
+    }
+
+    #[test]
+    fn undecodable_values_are_kept_as_hex() {
+        let entries = vec![(fixed_trie_key(&cell_key(1)), vec![1, 2])];
+        let dump = dump(V3, &entries).unwrap();
+        assert_eq!(dump["fields"]["count"], json!({ "undecodable": "0x0102" }));
+    }
+
+    #[test]
+    fn rejects_metadata_without_layout() {
+        assert!(dump(r#"{"V3":{"spec":{}}}"#, &[]).is_err());
+        assert!(dump("[", &[]).is_err());
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        assert_eq!(from_hex(&to_hex(&[0, 255])), Some(vec![0, 255]));
+        assert_eq!(from_hex("00ff"), None);
+        assert_eq!(from_hex("0x0"), None);
+    }
+}
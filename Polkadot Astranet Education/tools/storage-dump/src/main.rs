@@ -0,0 +1,167 @@
+// tools/storage-dump/src/main.rs
+//
+// Usage:
+//   storage-dump --metadata target/ink/erc20.contract --contract 5F...
+//   storage-dump --metadata erc20.contract --contract 5F... --at 0x1234... > state.json
+//
+// Reads every entry of the contract's child trie over RPC and prints it as JSON,
+// decoded with the storage layout in the metadata.
+
+use clap::Parser;
+use serde_json::json;
+use std::{fs, path::PathBuf, process::ExitCode, str::FromStr};
+use storage_dump::{dump, from_hex, to_hex, Entry};
+use subxt::{
+    backend::rpc::{rpc_params, RpcClient},
+    dynamic::{At, Value},
+    utils::{AccountId32, H256},
+    OnlineClient, SubstrateConfig,
+};
+
+/// Prefix of the storage key under which the runtime keeps default child tries.
+const CHILD_STORAGE_PREFIX: &[u8] = b":child_storage:default:";
+
+/// Number of keys fetched per RPC request.
+const PAGE_SIZE: u32 = 500;
+
+#[derive(Parser)]
+#[command(about = "Dump a deployed contract's storage as JSON decoded with its metadata")]
+struct Cli {
+    /// Path to the contract's `.contract` bundle or `metadata.json`.
+    #[arg(long)]
+    metadata: PathBuf,
+    /// SS58 address of the deployed contract.
+    #[arg(long)]
+    contract: String,
+    /// WebSocket URL of the node.
+    #[arg(long, default_value = "ws://127.0.0.1:9944")]
+    url: String,
+    /// Hash of the block to read; defaults to the latest block.
+    #[arg(long)]
+    at: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
+    let metadata = fs::read_to_string(&cli.metadata)
+        .map_err(|err| format!("{}: {}", cli.metadata.display(), err))?;
+    let contract = AccountId32::from_str(&cli.contract)
+        .map_err(|_| format!("`{}` is not an SS58 address", cli.contract))?;
+
+    let rpc = RpcClient::from_url(&cli.url)
+        .await
+        .map_err(|err| format!("cannot connect to {}: {}", cli.url, err))?;
+    let client = OnlineClient::<SubstrateConfig>::from_rpc_client(rpc.clone())
+        .await
+        .map_err(|err| err.to_string())?;
+    let block = match &cli.at {
+        Some(hash) => from_hex(hash)
+            .filter(|bytes| bytes.len() == 32)
+            .map(|bytes| H256::from_slice(&bytes))
+            .ok_or_else(|| format!("`{}` is not a block hash", hash))?,
+        None => client
+            .backend()
+            .latest_finalized_block_ref()
+            .await
+            .map_err(|err| err.to_string())?
+            .hash(),
+    };
+
+    let trie_id = trie_id(&client, &contract, block).await?;
+    let entries = child_entries(&rpc, &trie_id, block).await?;
+    let mut output = dump(&metadata, &entries).map_err(|err| err.to_string())?;
+    output["contract"] = json!(cli.contract);
+    output["block"] = json!(to_hex(block.as_bytes()));
+    output["trie_id"] = json!(to_hex(&trie_id));
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&output).expect("JSON always serializes")
+    );
+    Ok(())
+}
+
+/// Reads the id of the contract's child trie from `Contracts::ContractInfoOf`.
+async fn trie_id(
+    client: &OnlineClient<SubstrateConfig>,
+    contract: &AccountId32,
+    block: H256,
+) -> Result<Vec<u8>, String> {
+    let address = subxt::dynamic::storage(
+        "Contracts",
+        "ContractInfoOf",
+        vec![Value::from_bytes(contract.0)],
+    );
+    let info = client
+        .storage()
+        .at(block)
+        .fetch(&address)
+        .await
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("no contract at {}", contract))?
+        .to_value()
+        .map_err(|err| err.to_string())?;
+
+    let trie_id = info.at("trie_id").ok_or("contract info has no trie_id")?;
+    // `TrieId` is a `BoundedVec<u8>`, which decodes as a newtype around the bytes.
+    let bytes = trie_id
+        .at(0)
+        .filter(|inner| inner.as_u128().is_none())
+        .unwrap_or(trie_id);
+    let mut out = Vec::new();
+    while let Some(byte) = bytes.at(out.len()).and_then(Value::as_u128) {
+        out.push(byte as u8);
+    }
+    Ok(out)
+}
+
+/// Fetches every key and value of the child trie `trie_id`.
+async fn child_entries(rpc: &RpcClient, trie_id: &[u8], block: H256) -> Result<Vec<Entry>, String> {
+    let mut child_key = CHILD_STORAGE_PREFIX.to_vec();
+    child_key.extend_from_slice(trie_id);
+    let child_key = to_hex(&child_key);
+    let block = to_hex(block.as_bytes());
+
+    let mut keys: Vec<String> = Vec::new();
+    loop {
+        let page: Vec<String> = rpc
+            .request(
+                "childstate_getKeysPaged",
+                rpc_params![&child_key, "0x", PAGE_SIZE, keys.last(), &block],
+            )
+            .await
+            .map_err(|err| format!("childstate_getKeysPaged: {}", err))?;
+        let done = page.len() < PAGE_SIZE as usize;
+        keys.extend(page);
+        if done {
+            break;
+        }
+    }
+
+    let values: Vec<Option<String>> = rpc
+        .request(
+            "childstate_getStorageEntries",
+            rpc_params![&child_key, &keys, &block],
+        )
+        .await
+        .map_err(|err| format!("childstate_getStorageEntries: {}", err))?;
+
+    keys.iter()
+        .zip(values)
+        .filter_map(|(key, value)| Some((key, value?)))
+        .map(|(key, value)| match (from_hex(key), from_hex(&value)) {
+            (Some(key), Some(value)) => Ok((key, value)),
+            _ => Err(format!("node returned malformed entry {}", key)),
+        })
+        .collect()
+}
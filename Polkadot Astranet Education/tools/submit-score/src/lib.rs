@@ -0,0 +1,310 @@
+// tools/submit-score/src/lib.rs
+//
+// The grader keeps a learner's progress in `progress.json`, on their machine only.
+// `submit-score` puts it on chain, in two contracts of `examples/demo-contracts`:
+//
+// - the leaderboard, as a score: the number of exercises passing;
+// - the progress contract, as completed lessons of a course laid out like the tracks
+//   of `tracks.toml`: track `m` is module `m` and its `l`th exercise lesson `l`.
+//
+// Both contracts only take records from one account, the leaderboard's grader and the
+// course's instructor. Everything is dry-run first, so an account without that role
+// finds out before paying any fees, and records already on chain (a best score at
+// least as high, a lesson already completed) are left alone rather than sent again.
+//
+// This crate works out what to submit and reads the dry runs; connecting and signing
+// is left to the binary.
+
+use astranet_errors::{leaderboard, progress as course};
+use deployer::CallDryRun;
+use grader::{
+    progress::{Progress, Status},
+    tracks::Tracks,
+};
+use scale::{Decode, Encode};
+
+/// The id of a course of the progress contract.
+pub type CourseId = u32;
+
+/// The most modules in a course, and lessons in a module, of the progress contract.
+pub const MAX_ITEMS: usize = 32;
+
+/// The leaderboard score of `progress`: the number of exercises passing.
+pub fn score(progress: &Progress) -> u32 {
+    progress
+        .exercises
+        .values()
+        .filter(|exercise| exercise.status == Status::Passing)
+        .count() as u32
+}
+
+/// A passing exercise, as a lesson of the course.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lesson {
+    pub exercise: String,
+    pub module: u8,
+    pub lesson: u8,
+}
+
+/// The lessons passed in `progress`, in the order of `tracks`, so a module's lessons
+/// come after those of the modules before it. Exercises outside every track have no
+/// lesson.
+pub fn lessons(progress: &Progress, tracks: &Tracks) -> Result<Vec<Lesson>, String> {
+    if tracks.tracks.len() > MAX_ITEMS {
+        return Err(format!(
+            "a course has at most {} modules, but there are {} tracks",
+            MAX_ITEMS,
+            tracks.tracks.len()
+        ));
+    }
+    let mut lessons = Vec::new();
+    for (module, track) in tracks.tracks.iter().enumerate() {
+        if track.exercises.len() > MAX_ITEMS {
+            return Err(format!(
+                "a module has at most {} lessons, but track `{}` has {} exercises",
+                MAX_ITEMS,
+                track.name,
+                track.exercises.len()
+            ));
+        }
+        for (lesson, exercise) in track.exercises.iter().enumerate() {
+            let passing = progress
+                .exercises
+                .get(exercise)
+                .is_some_and(|progress| progress.status == Status::Passing);
+            if passing {
+                lessons.push(Lesson {
+                    exercise: exercise.clone(),
+                    module: module as u8,
+                    lesson: lesson as u8,
+                });
+            }
+        }
+    }
+    Ok(lessons)
+}
+
+/// Call data of the leaderboard's `best_score(learner)`.
+pub fn best_score_call_data(learner: [u8; 32]) -> Vec<u8> {
+    call_data("best_score", learner)
+}
+
+/// Call data of the leaderboard's `submit_score(learner, score)`.
+pub fn submit_score_call_data(learner: [u8; 32], score: u32) -> Vec<u8> {
+    call_data("submit_score", (learner, score))
+}
+
+/// Call data of the progress contract's `complete_lesson(course_id, learner, module,
+/// lesson)`.
+pub fn complete_lesson_call_data(
+    course_id: CourseId,
+    learner: [u8; 32],
+    lesson: &Lesson,
+) -> Vec<u8> {
+    call_data(
+        "complete_lesson",
+        (course_id, learner, lesson.module, lesson.lesson),
+    )
+}
+
+fn call_data(message: &str, args: impl Encode) -> Vec<u8> {
+    let mut data = selector::compute(message).to_vec();
+    args.encode_to(&mut data);
+    data
+}
+
+/// Reads the learner's best score from a dry run of `best_score`.
+pub fn best_score(dry_run: &CallDryRun) -> Result<Option<u32>, String> {
+    let returned = dry_run.check()?;
+    Option::<u32>::decode(&mut returned.data.as_slice())
+        .map_err(|err| format!("cannot decode the best score: {}", err))
+}
+
+/// Reads what a dry run of `submit_score` says: the learner's rank afterwards, if
+/// they are on the board, or the leaderboard's error.
+pub fn submit_score_outcome(
+    dry_run: &CallDryRun,
+) -> Result<Result<Option<u32>, leaderboard::Error>, String> {
+    outcome(dry_run, "submit_score")
+}
+
+/// Reads what a dry run of `complete_lesson` says: `Ok` if the lesson would be
+/// recorded, or the progress contract's error.
+pub fn complete_lesson_outcome(dry_run: &CallDryRun) -> Result<Result<(), course::Error>, String> {
+    outcome(dry_run, "complete_lesson")
+}
+
+/// Both contracts are ink! 3 contracts, whose messages return their `Result` as it is
+/// and revert with it when it is an error.
+fn outcome<T: Decode>(dry_run: &CallDryRun, message: &str) -> Result<T, String> {
+    let returned = match &dry_run.result {
+        Ok(returned) => returned,
+        Err(err) => return Err(format!("`{}` failed with {:?}", message, err)),
+    };
+    T::decode(&mut returned.data.as_slice())
+        .map_err(|err| format!("cannot decode what `{}` returned: {}", message, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deployer::{DispatchError, ExecReturnValue, StorageDeposit, Weight};
+    use grader::tracks::Track;
+
+    const LEARNER: [u8; 32] = [0xd4; 32];
+
+    fn progress(runs: &[(&str, bool)]) -> Progress {
+        let mut progress = Progress::default();
+        for (exercise, passed) in runs {
+            progress.record_run(exercise, *passed, 0);
+        }
+        progress
+    }
+
+    fn track(name: &str, exercises: &[&str]) -> Track {
+        Track {
+            name: name.to_owned(),
+            title: name.to_owned(),
+            exercises: exercises.iter().map(|&name| name.to_owned()).collect(),
+        }
+    }
+
+    fn dry_run(reverted: bool, data: Vec<u8>) -> CallDryRun {
+        CallDryRun {
+            gas_consumed: Weight::default(),
+            gas_required: Weight::default(),
+            storage_deposit: StorageDeposit::Charge(0),
+            debug_message: String::new(),
+            result: Ok(ExecReturnValue {
+                flags: reverted as u32,
+                data,
+            }),
+            events: None,
+        }
+    }
+
+    #[test]
+    fn the_score_counts_passing_exercises() {
+        assert_eq!(score(&Progress::default()), 0);
+        let progress = progress(&[
+            ("lazy_config", true),
+            ("batch_token", false),
+            ("mapping_ledger", false),
+            ("mapping_ledger", true),
+            ("safe_relay", true),
+            ("safe_relay", false),
+        ]);
+        assert_eq!(score(&progress), 2);
+    }
+
+    #[test]
+    fn passing_exercises_are_lessons_in_track_order() {
+        let tracks = Tracks {
+            tracks: vec![
+                track(
+                    "storage",
+                    &["mapping_ledger", "lazy_config", "vec_pitfalls"],
+                ),
+                track("calls", &["counter_caller", "safe_relay"]),
+            ],
+        };
+        let progress = progress(&[
+            ("safe_relay", true),
+            ("vec_pitfalls", true),
+            ("lazy_config", false),
+            ("mapping_ledger", true),
+            ("not_in_a_track", true),
+        ]);
+        let lessons = lessons(&progress, &tracks).unwrap();
+        let placed: Vec<_> = lessons
+            .iter()
+            .map(|lesson| (lesson.exercise.as_str(), lesson.module, lesson.lesson))
+            .collect();
+        assert_eq!(
+            placed,
+            [
+                ("mapping_ledger", 0, 0),
+                ("vec_pitfalls", 0, 2),
+                ("safe_relay", 1, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn tracks_must_fit_a_course() {
+        let long: Vec<String> = (0..=MAX_ITEMS).map(|i| format!("e{}", i)).collect();
+        let long: Vec<&str> = long.iter().map(String::as_str).collect();
+        let tracks = Tracks {
+            tracks: vec![track("long", &long)],
+        };
+        assert!(lessons(&Progress::default(), &tracks)
+            .unwrap_err()
+            .contains("track `long` has 33 exercises"));
+
+        let tracks = Tracks {
+            tracks: (0..=MAX_ITEMS)
+                .map(|i| track(&i.to_string(), &[]))
+                .collect(),
+        };
+        assert!(lessons(&Progress::default(), &tracks)
+            .unwrap_err()
+            .contains("there are 33 tracks"));
+    }
+
+    #[test]
+    fn call_data_starts_with_the_selector() {
+        let data = submit_score_call_data(LEARNER, 7);
+        assert_eq!(data[..4], selector::compute("submit_score"));
+        assert_eq!(data[4..36], LEARNER);
+        assert_eq!(data[36..], 7u32.to_le_bytes());
+
+        let lesson = Lesson {
+            exercise: "safe_relay".to_owned(),
+            module: 1,
+            lesson: 4,
+        };
+        let data = complete_lesson_call_data(3, LEARNER, &lesson);
+        assert_eq!(data[..4], selector::compute("complete_lesson"));
+        assert_eq!(data[4..8], 3u32.to_le_bytes());
+        assert_eq!(data[8..40], LEARNER);
+        assert_eq!(data[40..], [1, 4]);
+    }
+
+    #[test]
+    fn outcomes_decode_results_and_reverted_errors() {
+        let ok = dry_run(false, Ok::<_, leaderboard::Error>(Some(2u32)).encode());
+        assert_eq!(submit_score_outcome(&ok).unwrap(), Ok(Some(2)));
+        let refused = dry_run(
+            true,
+            Err::<Option<u32>, _>(leaderboard::Error::NotGrader).encode(),
+        );
+        assert_eq!(
+            submit_score_outcome(&refused).unwrap(),
+            Err(leaderboard::Error::NotGrader)
+        );
+        let done = dry_run(true, Err::<(), _>(course::Error::AlreadyCompleted).encode());
+        assert_eq!(
+            complete_lesson_outcome(&done).unwrap(),
+            Err(course::Error::AlreadyCompleted)
+        );
+
+        let mut failed = ok.clone();
+        failed.result = Err(DispatchError::Other);
+        assert!(submit_score_outcome(&failed)
+            .unwrap_err()
+            .starts_with("`submit_score` failed"));
+    }
+
+    #[test]
+    fn best_scores_need_a_clean_dry_run() {
+        assert_eq!(
+            best_score(&dry_run(false, Some(5u32).encode())).unwrap(),
+            Some(5)
+        );
+        assert_eq!(
+            best_score(&dry_run(false, None::<u32>.encode())).unwrap(),
+            None
+        );
+        assert!(best_score(&dry_run(true, Vec::new())).is_err());
+    }
+}
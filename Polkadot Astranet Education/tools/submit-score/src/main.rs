@@ -0,0 +1,276 @@
+// tools/submit-score/src/main.rs
+//
+// Usage:
+//   submit-score --key learner --leaderboard 5F...
+//   submit-score --key learner --progress 5G... --course 0
+//   submit-score --key learner --leaderboard 5F... --progress 5G... --course 0 --dry-run
+//   submit-score --network shibuya --suri "$GRADER_SURI" --learner 5H... --leaderboard 5F...
+//
+// Reads `progress.json` and `tracks.toml` from the exercises directory (`--dir`) and
+// submits the learner's score to the leaderboard at `--leaderboard`, and their passed
+// exercises as lessons of course `--course` of the progress contract at `--progress`.
+//
+// Calls are signed with the learner's key, given as for the deployer: `--key` names a
+// stored key, `--suri` is a secret URI, and without either the network profile's
+// signer is used. The learner is the signer's account unless `--learner` names
+// another, for a grader or instructor submitting on a learner's behalf. The contracts
+// only accept records from their grader or course instructor; a learner who deployed
+// their own, on a local node, is both.
+//
+// Every call is dry-run first and only submitted if the contract would accept it;
+// `--dry-run` stops after the dry runs.
+
+use clap::Parser;
+use deployer::{
+    dry_run,
+    submit::{call_tx, submit},
+    CallDryRun, CallRequest, Network, Networks, Signer,
+};
+use grader::{
+    progress::{Progress, PROGRESS_FILE},
+    tracks::Tracks,
+};
+use repl::{
+    keystore::{KeyPair, Keystore, Scheme},
+    value::parse_account,
+};
+use std::{fs, path::PathBuf, process::ExitCode};
+use submit_score::{CourseId, Lesson};
+use subxt::backend::rpc::RpcClient;
+
+#[derive(Parser)]
+#[command(about = "Submit a learner's graded progress to the leaderboard and progress contracts")]
+struct Cli {
+    /// Directory of the exercise crates, with `progress.json` and `tracks.toml`.
+    #[arg(long, default_value = "exercises")]
+    dir: PathBuf,
+    /// Network profile name, or the WebSocket URL of a node.
+    #[arg(long, default_value = "local")]
+    network: String,
+    /// File of network profiles to add to the built-in ones.
+    #[arg(long)]
+    networks: Option<PathBuf>,
+    /// WebSocket URL of the node, instead of the profile's.
+    #[arg(long)]
+    url: Option<String>,
+    /// Secret URI of the account to sign with, instead of the profile's signer.
+    #[arg(long, conflicts_with = "key")]
+    suri: Option<String>,
+    /// Name of a stored key to sign with, instead of the profile's signer.
+    #[arg(long)]
+    key: Option<String>,
+    /// Keystore directory, `~/.astranet/keys` by default.
+    #[arg(long)]
+    keystore: Option<PathBuf>,
+    /// Account the progress is recorded for, instead of the signer's.
+    #[arg(long)]
+    learner: Option<String>,
+    /// Address of the leaderboard contract to submit the score to.
+    #[arg(long, required_unless_present = "progress")]
+    leaderboard: Option<String>,
+    /// Address of the progress contract to record passed exercises in.
+    #[arg(long, requires = "course")]
+    progress: Option<String>,
+    /// Id of the course, in the progress contract, that follows the tracks.
+    #[arg(long, requires = "progress")]
+    course: Option<CourseId>,
+    /// Only dry-run the calls and report what they would do.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
+    let progress = Progress::load(&cli.dir.join(PROGRESS_FILE))?;
+    let tracks = Tracks::load(&cli.dir)?;
+    let network = network(&cli)?;
+    let signer = signer(&cli, &network)?;
+    let learner = match &cli.learner {
+        Some(learner) => parse_account(learner)?,
+        None => signer.account_id(),
+    };
+    let rpc = RpcClient::from_url(&network.url)
+        .await
+        .map_err(|err| format!("cannot connect to {}: {}", network.url, err))?;
+    println!(
+        "connected to {} ({}) as {}, submitting for {}",
+        cli.network,
+        network.url,
+        network.address(signer.account_id()),
+        network.address(learner)
+    );
+    let caller = Caller {
+        rpc,
+        signer,
+        dry_run: cli.dry_run,
+    };
+
+    if let Some(leaderboard) = &cli.leaderboard {
+        let leaderboard = parse_account(leaderboard)?;
+        submit_score(
+            &caller,
+            leaderboard,
+            learner,
+            submit_score::score(&progress),
+        )
+        .await?;
+    }
+    if let (Some(contract), Some(course)) = (&cli.progress, cli.course) {
+        let contract = parse_account(contract)?;
+        let lessons = submit_score::lessons(&progress, &tracks)?;
+        complete_lessons(&caller, contract, course, learner, &lessons).await?;
+    }
+    Ok(())
+}
+
+/// Submits `score` unless the learner's best is already as high.
+async fn submit_score(
+    caller: &Caller,
+    leaderboard: [u8; 32],
+    learner: [u8; 32],
+    score: u32,
+) -> Result<(), String> {
+    let (_, best) = caller
+        .dry_run(leaderboard, submit_score::best_score_call_data(learner))
+        .await?;
+    let best = submit_score::best_score(&best)?;
+    if best.is_some_and(|best| best >= score) {
+        println!(
+            "leaderboard: score {} is no higher than the best of {}; nothing to submit",
+            score,
+            best.unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    let (request, dry_run) = caller
+        .dry_run(
+            leaderboard,
+            submit_score::submit_score_call_data(learner, score),
+        )
+        .await?;
+    match submit_score::submit_score_outcome(&dry_run)? {
+        Ok(Some(rank)) => println!("leaderboard: score {} ranks #{}", score, rank),
+        Ok(None) => println!("leaderboard: score {} does not make the board", score),
+        Err(err) => {
+            return Err(format!(
+                "the leaderboard refuses score {}: {}; scores are submitted by its grader",
+                score, err
+            ))
+        }
+    }
+    caller.submit(&request, &dry_run).await
+}
+
+/// Records each of `lessons` the learner has not completed yet, in order.
+async fn complete_lessons(
+    caller: &Caller,
+    contract: [u8; 32],
+    course: CourseId,
+    learner: [u8; 32],
+    lessons: &[Lesson],
+) -> Result<(), String> {
+    if lessons.is_empty() {
+        println!("progress: no exercise of a track is passing; nothing to submit");
+    }
+    for lesson in lessons {
+        let (request, dry_run) = caller
+            .dry_run(
+                contract,
+                submit_score::complete_lesson_call_data(course, learner, lesson),
+            )
+            .await?;
+        let label = format!(
+            "progress: `{}` (module {}, lesson {})",
+            lesson.exercise, lesson.module, lesson.lesson
+        );
+        match submit_score::complete_lesson_outcome(&dry_run)? {
+            Ok(()) => println!("{}: recording", label),
+            Err(astranet_errors::progress::Error::AlreadyCompleted) => {
+                println!("{}: already recorded", label);
+                continue;
+            }
+            Err(err) => return Err(format!("{}: refused: {}", label, err)),
+        }
+        caller.submit(&request, &dry_run).await?;
+    }
+    Ok(())
+}
+
+/// Dry-runs and submits contract calls as the signer.
+struct Caller {
+    rpc: RpcClient,
+    signer: KeyPair,
+    dry_run: bool,
+}
+
+impl Caller {
+    async fn dry_run(
+        &self,
+        dest: [u8; 32],
+        input_data: Vec<u8>,
+    ) -> Result<(CallRequest, CallDryRun), String> {
+        let request = CallRequest {
+            origin: self.signer.account_id(),
+            dest,
+            value: 0,
+            gas_limit: None,
+            storage_deposit_limit: None,
+            input_data,
+        };
+        let dry_run = dry_run::call(&self.rpc, &request).await?;
+        Ok((request, dry_run))
+    }
+
+    /// Submits a call whose dry run the contract accepted, unless only dry-running.
+    async fn submit(&self, request: &CallRequest, dry_run: &CallDryRun) -> Result<(), String> {
+        if self.dry_run {
+            println!("  dry run only; nothing was submitted");
+            return Ok(());
+        }
+        submit(self.rpc.clone(), &self.signer, &call_tx(request, dry_run)).await?;
+        Ok(())
+    }
+}
+
+/// Resolves `--key`, `--suri` and the profile's signer into the key to sign with.
+fn signer(cli: &Cli, network: &Network) -> Result<KeyPair, String> {
+    let suri = match (&cli.key, &cli.suri, &network.signer) {
+        (Some(name), _, _) | (None, None, Signer::Key(name)) => {
+            let keystore = match &cli.keystore {
+                Some(dir) => Keystore::new(dir),
+                None => Keystore::new(Keystore::default_dir()?),
+            };
+            return keystore.unlock(name);
+        }
+        (None, Some(suri), _) => suri.clone(),
+        (None, None, _) => network.suri()?,
+    };
+    KeyPair::from_suri(Scheme::Sr25519, &suri).map_err(|err| format!("signer: {}", err))
+}
+
+/// Resolves `--network`, `--networks` and `--url` into the network to use.
+fn network(cli: &Cli) -> Result<Network, String> {
+    let mut networks = Networks::builtin();
+    if let Some(path) = &cli.networks {
+        let toml =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        networks
+            .extend(Networks::parse(&toml).map_err(|err| format!("{}: {}", path.display(), err))?);
+    }
+    let mut network = networks.get(&cli.network)?;
+    if let Some(url) = &cli.url {
+        network.url = url.clone();
+    }
+    Ok(network)
+}
@@ -0,0 +1,279 @@
+// tools/verify/src/lib.rs
+//
+// A deployed contract is only a code hash and some Wasm on chain; nothing there says
+// which source it came from. A build is verifiable when anyone can rebuild the
+// published source and get the same Wasm, byte for byte: then its BLAKE2-256 hash,
+// which the contracts pallet uses as the code hash, matches the one on chain, and
+// the source is known to be what runs.
+//
+// Byte-identical output needs an identical build: the same compiler, the same
+// cargo-contract, the same dependency versions (`--locked`), and no leftovers from
+// builds with other settings. `verify` rebuilds a contract crate in the configuration
+// pinned in `pinned.toml`, into a target directory of its own, and compares hashes.
+//
+// The on-chain hash is read straight from storage. `Contracts::ContractInfoOf` maps an
+// account to its `ContractInfo`, whose first fields are the trie id and the code hash;
+// the rest of the struct has changed between releases of the pallet and is not read.
+
+use scale::Decode;
+use serde::Deserialize;
+use sp_crypto_hashing::{blake2_256, twox_128, twox_64};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use subxt::{
+    backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+    SubstrateConfig,
+};
+
+/// The built-in pins.
+pub const PINNED: &str = include_str!("../pinned.toml");
+
+/// Target directory of verification builds, inside the contract crate.
+pub const TARGET_DIR: &str = "target/verify";
+
+/// The build configuration contracts are verified in.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pins {
+    /// Rust toolchain, as passed to `cargo +TOOLCHAIN`.
+    pub toolchain: String,
+    /// Version of cargo-contract.
+    pub cargo_contract: String,
+}
+
+impl Pins {
+    /// Reads pins in the format of `pinned.toml`.
+    pub fn parse(toml: &str) -> Result<Self, String> {
+        toml::from_str(toml).map_err(|err| err.message().to_owned())
+    }
+
+    /// Returns the built-in pins.
+    pub fn builtin() -> Self {
+        Self::parse(PINNED).expect("built-in pinned.toml is valid")
+    }
+
+    /// Checks the output of `cargo contract --version` against the pinned version.
+    pub fn check_cargo_contract(&self, version_output: &str) -> Result<(), String> {
+        // `cargo-contract-contract 5.0.1-unknown-x86_64-unknown-linux-gnu`
+        let found = version_output
+            .split_whitespace()
+            .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+            .map(|word| word.split('-').next().unwrap_or(word))
+            .ok_or_else(|| {
+                format!(
+                    "cannot read the version of cargo-contract from `{}`",
+                    version_output.trim()
+                )
+            })?;
+        if found != self.cargo_contract {
+            return Err(format!(
+                "verification needs cargo-contract {} but {} is installed; run \
+                 `cargo install cargo-contract --version {} --locked --force`",
+                self.cargo_contract, found, self.cargo_contract
+            ));
+        }
+        Ok(())
+    }
+
+    /// The command rebuilding the crate at `manifest_path` with these pins.
+    pub fn build_command(&self, manifest_path: &Path) -> Command {
+        let mut command = Command::new("cargo");
+        command
+            .arg(format!("+{}", self.toolchain))
+            .args([
+                "contract",
+                "build",
+                "--release",
+                "--locked",
+                "--manifest-path",
+            ])
+            .arg(manifest_path)
+            .env("CARGO_TARGET_DIR", target_dir(manifest_path));
+        command
+    }
+}
+
+/// Where the verification build of the crate at `manifest_path` goes.
+pub fn target_dir(manifest_path: &Path) -> PathBuf {
+    manifest_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(TARGET_DIR)
+}
+
+/// Finds the Wasm a verification build of the crate at `manifest_path` produced.
+pub fn built_wasm(manifest_path: &Path) -> Result<PathBuf, String> {
+    let ink_dir = target_dir(manifest_path).join("ink");
+    let entries =
+        fs::read_dir(&ink_dir).map_err(|err| format!("{}: {}", ink_dir.display(), err))?;
+    let mut wasm: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("wasm"))
+        .collect();
+    match wasm.len() {
+        1 => Ok(wasm.remove(0)),
+        0 => Err(format!("no .wasm file in {}", ink_dir.display())),
+        _ => Err(format!("more than one .wasm file in {}", ink_dir.display())),
+    }
+}
+
+/// The code hash the contracts pallet gives `wasm`.
+pub fn code_hash(wasm: &[u8]) -> [u8; 32] {
+    blake2_256(wasm)
+}
+
+/// The storage key of `Contracts::ContractInfoOf(account)`, a `Twox64Concat` map.
+pub fn contract_info_key(account: [u8; 32]) -> Vec<u8> {
+    let mut key = twox_128(b"Contracts").to_vec();
+    key.extend(twox_128(b"ContractInfoOf"));
+    key.extend(twox_64(&account));
+    key.extend(account);
+    key
+}
+
+/// Reads the code hash out of an encoded `ContractInfo`.
+pub fn decode_code_hash(contract_info: &[u8]) -> Result<[u8; 32], String> {
+    <(Vec<u8>, [u8; 32])>::decode(&mut &contract_info[..])
+        .map(|(_trie_id, code_hash)| code_hash)
+        .map_err(|err| format!("cannot decode the contract info: {}", err))
+}
+
+/// Returns the code hash of the contract at `account`, or `None` if there is none.
+pub async fn on_chain_code_hash(
+    rpc: &RpcClient,
+    account: [u8; 32],
+) -> Result<Option<[u8; 32]>, String> {
+    let methods = LegacyRpcMethods::<SubstrateConfig>::new(rpc.clone());
+    let info = methods
+        .state_get_storage(&contract_info_key(account), None)
+        .await
+        .map_err(|err| format!("state_getStorage failed: {}", err))?;
+    info.as_deref().map(decode_code_hash).transpose()
+}
+
+/// Whether the rebuilt code matches the expected hash, and the lines saying so.
+pub fn compare(built: [u8; 32], expected: [u8; 32], expected_from: &str) -> (bool, Vec<String>) {
+    let matches = built == expected;
+    let mut lines = vec![
+        format!("  rebuilt code hash:  {}", repl::value::to_hex(&built)),
+        format!(
+            "  expected code hash: {} ({})",
+            repl::value::to_hex(&expected),
+            expected_from
+        ),
+    ];
+    lines.push(if matches {
+        "MATCH: the deployed code was built from this source".to_owned()
+    } else {
+        "MISMATCH: the deployed code was built from other source, other dependencies \
+         or in another configuration"
+            .to_owned()
+    });
+    (matches, lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::Encode;
+
+    #[test]
+    fn builtin_pins_parse() {
+        let pins = Pins::builtin();
+        assert!(!pins.toolchain.is_empty());
+        assert!(pins
+            .cargo_contract
+            .starts_with(|c: char| c.is_ascii_digit()));
+        assert!(Pins::parse("toolchain = \"1.81.0\"\n").is_err());
+        assert!(
+            Pins::parse("toolchain = \"1.81.0\"\ncargo_contract = \"5.0.1\"\nwasm_opt = 3\n")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn cargo_contract_versions_are_checked() {
+        let pins = Pins::parse("toolchain = \"1.81.0\"\ncargo_contract = \"5.0.1\"\n").unwrap();
+        assert!(pins
+            .check_cargo_contract(
+                "cargo-contract-contract 5.0.1-unknown-x86_64-unknown-linux-gnu\n"
+            )
+            .is_ok());
+        let err = pins
+            .check_cargo_contract("cargo-contract-contract 4.1.1-unknown-x86_64-unknown-linux-gnu")
+            .unwrap_err();
+        assert!(
+            err.contains("needs cargo-contract 5.0.1 but 4.1.1"),
+            "{}",
+            err
+        );
+        assert!(pins.check_cargo_contract("error: no such command").is_err());
+    }
+
+    #[test]
+    fn builds_are_pinned_and_isolated() {
+        let pins = Pins::parse("toolchain = \"1.81.0\"\ncargo_contract = \"5.0.1\"\n").unwrap();
+        let manifest = Path::new("examples/flipper/Cargo.toml");
+        let command = pins.build_command(manifest);
+        let args: Vec<_> = command
+            .get_args()
+            .map(|arg| arg.to_str().unwrap())
+            .collect();
+        assert_eq!(
+            args,
+            [
+                "+1.81.0",
+                "contract",
+                "build",
+                "--release",
+                "--locked",
+                "--manifest-path",
+                "examples/flipper/Cargo.toml"
+            ]
+        );
+        let envs: Vec<_> = command.get_envs().collect();
+        assert_eq!(envs.len(), 1);
+        assert_eq!(
+            envs[0].1.map(Path::new),
+            Some(Path::new("examples/flipper/target/verify"))
+        );
+    }
+
+    #[test]
+    fn contract_info_keys_follow_the_storage_layout() {
+        let account = [0x42; 32];
+        let key = contract_info_key(account);
+        assert_eq!(key.len(), 16 + 16 + 8 + 32);
+        // The prefix every storage item of the contracts pallet shares.
+        assert_eq!(
+            repl::value::to_hex(&key[..16]),
+            "0x4342193e496fab7ec59d615ed0dc5530"
+        );
+        assert_eq!(key[40..], account);
+        assert_ne!(contract_info_key([0x43; 32])[32..40], key[32..40]);
+    }
+
+    #[test]
+    fn code_hashes_are_read_from_contract_info() {
+        // `ContractInfo { trie_id, code_hash, .. }` with later fields that differ
+        // between pallet versions.
+        let info = (vec![7u8; 32], [0xabu8; 32], 3u32, 1_000u128).encode();
+        assert_eq!(decode_code_hash(&info).unwrap(), [0xab; 32]);
+        assert!(decode_code_hash(&[0x80]).is_err());
+    }
+
+    #[test]
+    fn comparisons_report_match_or_mismatch() {
+        let hash = code_hash(b"\0asm\x01\0\0\0");
+        let (matches, lines) = compare(hash, hash, "on chain");
+        assert!(matches);
+        assert!(lines[2].starts_with("MATCH"));
+        let (matches, lines) = compare(hash, [0; 32], "on chain");
+        assert!(!matches);
+        assert!(lines[2].starts_with("MISMATCH"));
+        assert!(lines[1].ends_with("(on chain)"));
+    }
+}
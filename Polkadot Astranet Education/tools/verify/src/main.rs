@@ -0,0 +1,133 @@
+// tools/verify/src/main.rs
+//
+// Usage:
+//   verify --manifest-path examples/demo-contracts/flipper/Cargo.toml --contract 5F...
+//   verify --manifest-path ... --contract 5F... --url wss://rococo-contracts-rpc.polkadot.io
+//   verify --manifest-path ... --code-hash 0x...   compare with a published hash instead
+//   verify --manifest-path ... --no-build ...      reuse the last verification build
+//
+// Rebuilds the contract crate with the toolchain and cargo-contract version pinned in
+// `pinned.toml` (or `--pins`), hashes the Wasm, and compares the hash with the code
+// hash of the contract deployed at `--contract`. Exits with 1 on a mismatch.
+
+use clap::Parser;
+use repl::value::{parse_account, parse_hex};
+use std::{
+    fs,
+    path::PathBuf,
+    process::{Command, ExitCode},
+};
+use subxt::backend::rpc::RpcClient;
+use verify::{built_wasm, code_hash, compare, on_chain_code_hash, Pins};
+
+#[derive(Parser)]
+#[command(about = "Rebuild a contract in a pinned configuration and compare code hashes")]
+struct Cli {
+    /// `Cargo.toml` of the contract crate.
+    #[arg(long)]
+    manifest_path: PathBuf,
+    /// Address of the deployed contract.
+    #[arg(long, required_unless_present = "code_hash")]
+    contract: Option<String>,
+    /// Code hash to compare with, instead of a deployed contract's.
+    #[arg(long, conflicts_with = "contract")]
+    code_hash: Option<String>,
+    /// WebSocket URL of the node.
+    #[arg(long, default_value = "ws://127.0.0.1:9944")]
+    url: String,
+    /// File of pins to build with instead of the built-in ones.
+    #[arg(long)]
+    pins: Option<PathBuf>,
+    /// Hash the Wasm of the last verification build instead of building again.
+    #[arg(long)]
+    no_build: bool,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Returns whether the hashes match.
+async fn run(cli: Cli) -> Result<bool, String> {
+    let pins = match &cli.pins {
+        Some(path) => {
+            let toml =
+                fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+            Pins::parse(&toml).map_err(|err| format!("{}: {}", path.display(), err))?
+        }
+        None => Pins::builtin(),
+    };
+    if !cli.no_build {
+        build(&pins, &cli)?;
+    }
+    let wasm_path = built_wasm(&cli.manifest_path)?;
+    let wasm = fs::read(&wasm_path).map_err(|err| format!("{}: {}", wasm_path.display(), err))?;
+    println!("hashed {} ({} bytes)", wasm_path.display(), wasm.len());
+    let built = code_hash(&wasm);
+
+    let (expected, expected_from) = match (&cli.code_hash, &cli.contract) {
+        (Some(hash), _) => {
+            let hash = <[u8; 32]>::try_from(parse_hex(hash)?)
+                .map_err(|_| "--code-hash must be 32 bytes".to_owned())?;
+            (hash, "given".to_owned())
+        }
+        (None, Some(contract)) => {
+            let account = parse_account(contract)?;
+            let rpc = RpcClient::from_url(&cli.url)
+                .await
+                .map_err(|err| format!("cannot connect to {}: {}", cli.url, err))?;
+            let hash = on_chain_code_hash(&rpc, account)
+                .await?
+                .ok_or_else(|| format!("no contract at {} on {}", contract, cli.url))?;
+            (hash, format!("of {} on {}", contract, cli.url))
+        }
+        (None, None) => unreachable!("clap requires --contract or --code-hash"),
+    };
+
+    let (matches, lines) = compare(built, expected, &expected_from);
+    for line in lines {
+        println!("{}", line);
+    }
+    if !matches {
+        println!(
+            "  the rebuilt Wasm is {}; compare it with the deployed code to see what changed",
+            wasm_path.display()
+        );
+    }
+    Ok(matches)
+}
+
+fn build(pins: &Pins, cli: &Cli) -> Result<(), String> {
+    let output = Command::new("cargo")
+        .args(["contract", "--version"])
+        .output()
+        .map_err(|err| format!("cannot run cargo contract: {}", err))?;
+    pins.check_cargo_contract(&String::from_utf8_lossy(&output.stdout))?;
+
+    println!(
+        "building {} with Rust {} and cargo-contract {} (--locked)",
+        cli.manifest_path.display(),
+        pins.toolchain,
+        pins.cargo_contract
+    );
+    let status = pins
+        .build_command(&cli.manifest_path)
+        .status()
+        .map_err(|err| format!("cannot run cargo: {}", err))?;
+    if !status.success() {
+        return Err(format!(
+            "the build failed; is the toolchain installed (`rustup toolchain install {}`), \
+             and is Cargo.lock committed?",
+            pins.toolchain
+        ));
+    }
+    Ok(())
+}
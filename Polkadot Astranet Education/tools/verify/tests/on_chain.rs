@@ -0,0 +1,86 @@
+// tools/verify/tests/on_chain.rs
+//
+// Reads code hashes from a mocked node that answers `state_getStorage` for one
+// contract's `ContractInfoOf` entry, as a contracts node would.
+
+use repl::value::{parse_hex, to_hex};
+use scale::Encode;
+use serde_json::{json, value::RawValue, Value};
+use subxt::{
+    backend::rpc::{RawRpcFuture, RawRpcSubscription, RpcClient, RpcClientT},
+    error::RpcError,
+};
+use verify::{code_hash, compare, contract_info_key, on_chain_code_hash};
+
+const CONTRACT: [u8; 32] = [0x42; 32];
+
+/// A node storing the contract info of `CONTRACT`.
+struct MockNode {
+    contract_info: Vec<u8>,
+}
+
+impl RpcClientT for MockNode {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        params: Option<Box<RawValue>>,
+    ) -> RawRpcFuture<'a, Box<RawValue>> {
+        Box::pin(async move {
+            if method != "state_getStorage" {
+                return Err(RpcError::request_rejected(format!(
+                    "{} is not mocked",
+                    method
+                )));
+            }
+            // `["0x" key, block hash or null]`
+            let params: Vec<Value> = serde_json::from_str(params.unwrap().get()).unwrap();
+            let key = parse_hex(params[0].as_str().unwrap()).unwrap();
+            let value = if key == contract_info_key(CONTRACT) {
+                json!(to_hex(&self.contract_info))
+            } else {
+                Value::Null
+            };
+            Ok(RawValue::from_string(value.to_string()).unwrap())
+        })
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        _sub: &'a str,
+        _params: Option<Box<RawValue>>,
+        _unsub: &'a str,
+    ) -> RawRpcFuture<'a, RawRpcSubscription> {
+        Box::pin(async { Err(RpcError::request_rejected("subscriptions are not mocked")) })
+    }
+}
+
+fn node_with_code(wasm: &[u8]) -> RpcClient {
+    // `ContractInfo { trie_id, code_hash, storage_bytes, storage_items, .. }`
+    let contract_info = (vec![1u8; 32], code_hash(wasm), 120u32, 2u32, 5_000u128).encode();
+    RpcClient::new(MockNode { contract_info })
+}
+
+#[tokio::test]
+async fn matching_code_is_verified() {
+    let wasm = b"\0asm\x01\0\0\0flipper".to_vec();
+    let rpc = node_with_code(&wasm);
+    let on_chain = on_chain_code_hash(&rpc, CONTRACT).await.unwrap().unwrap();
+    let (matches, lines) = compare(code_hash(&wasm), on_chain, "on chain");
+    assert!(matches, "{:?}", lines);
+}
+
+#[tokio::test]
+async fn changed_code_is_a_mismatch() {
+    let rpc = node_with_code(b"\0asm\x01\0\0\0flipper");
+    let on_chain = on_chain_code_hash(&rpc, CONTRACT).await.unwrap().unwrap();
+    let rebuilt = code_hash(b"\0asm\x01\0\0\0flipper, edited");
+    let (matches, lines) = compare(rebuilt, on_chain, "on chain");
+    assert!(!matches);
+    assert!(lines[2].starts_with("MISMATCH"));
+}
+
+#[tokio::test]
+async fn accounts_without_contracts_have_no_code_hash() {
+    let rpc = node_with_code(b"\0asm\x01\0\0\0");
+    assert_eq!(on_chain_code_hash(&rpc, [0x43; 32]).await.unwrap(), None);
+}